@@ -32,6 +32,10 @@ pub enum SgrCode {
     Invert,
     Hidden,
     Strikethrough,
+    /// One of the 16 system colors (`0..16`), for terminals that don't support the ANSI
+    /// 256 color palette.
+    ForegroundAnsi16(u8),
+    BackgroundAnsi16(u8),
     ForegroundAnsi256(u8),
     BackgroundAnsi256(u8),
     ForegroundRGB(u8, u8, u8),
@@ -72,12 +76,23 @@ pub mod sgr_code_impl {
             SgrCode::Hidden            => format!("{CSI}8{SGR}"),
             SgrCode::Strikethrough     => format!("{CSI}9{SGR}"),
             SgrCode::Overline           => format!("{CSI}53{SGR}"),
+            SgrCode::ForegroundAnsi16(index) => format!("{CSI}{}{SGR}", ansi16_param(index, 30, 90)),
+            SgrCode::BackgroundAnsi16(index) => format!("{CSI}{}{SGR}", ansi16_param(index, 40, 100)),
             SgrCode::ForegroundAnsi256(index) => format!("{CSI}38;5;{index}{SGR}"),
             SgrCode::BackgroundAnsi256(index) => format!("{CSI}48;5;{index}{SGR}"),
             SgrCode::ForegroundRGB(r, g, b) => format!("{CSI}38;2;{r};{g};{b}{SGR}"),
             SgrCode::BackgroundRGB(r, g, b) => format!("{CSI}48;2;{r};{g};{b}{SGR}"),
         }
     }
+
+    /// The 8 normal colors start at `normal_base` and the 8 bright ones start at
+    /// `bright_base`.
+    fn ansi16_param(index: u8, normal_base: u8, bright_base: u8) -> u8 {
+        match index % 16 {
+            it @ 0..=7 => normal_base + it,
+            it => bright_base + it - 8,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +167,18 @@ mod tests {
         assert_eq!(sgr_code.to_string(), "\x1b[53m");
     }
 
+    #[test]
+    fn fg_color_ansi16() {
+        assert_eq!(SgrCode::ForegroundAnsi16(1).to_string(), "\x1b[31m");
+        assert_eq!(SgrCode::ForegroundAnsi16(9).to_string(), "\x1b[91m");
+    }
+
+    #[test]
+    fn bg_color_ansi16() {
+        assert_eq!(SgrCode::BackgroundAnsi16(7).to_string(), "\x1b[47m");
+        assert_eq!(SgrCode::BackgroundAnsi16(15).to_string(), "\x1b[107m");
+    }
+
     #[test]
     fn fg_color_ansi256() {
         let sgr_code = SgrCode::ForegroundAnsi256(150);
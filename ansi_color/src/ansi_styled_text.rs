@@ -107,6 +107,19 @@ mod style_impl {
                 )
             }
 
+            ColorSupport::Ansi16 => {
+                // ANSI 16 color mode.
+                let index = color.as_ansi16().index;
+                write!(
+                    f,
+                    "{}",
+                    match color_kind {
+                        ColorKind::Foreground => SgrCode::ForegroundAnsi16(index),
+                        ColorKind::Background => SgrCode::BackgroundAnsi16(index),
+                    }
+                )
+            }
+
             ColorSupport::Grayscale => {
                 // Grayscale mode.
                 let color = color.as_grayscale();
@@ -217,6 +230,27 @@ mod display_trait_impl {
             Ok(())
         }
 
+        #[serial]
+        #[test]
+        fn test_formatted_string_creation_ansi16() -> Result<(), String> {
+            global_color_support::set_override(ColorSupport::Ansi16);
+            let eg_1 = AnsiStyledText {
+                text: "Hello",
+                style: &[
+                    Style::Bold,
+                    Style::Foreground(Color::Rgb(250, 10, 10)),
+                    Style::Background(Color::Ansi256(231)),
+                ],
+            };
+
+            assert_eq!(
+                format!("{0}", eg_1),
+                "\x1b[1m\x1b[91m\x1b[107mHello\x1b[0m".to_string()
+            );
+
+            Ok(())
+        }
+
         #[serial]
         #[test]
         fn test_formatted_string_creation_truecolor() -> Result<(), String> {
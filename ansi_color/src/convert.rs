@@ -95,6 +95,18 @@ pub fn convert_rgb_into_ansi256(rgb_color: RgbColor) -> Ansi256Color {
     }
 }
 
+/// Returns the closest of the 16 system colors (indices `0..16` of the ANSI 256 color
+/// palette) to the `rgb_color`, for terminals that only support [crate::ColorSupport::Ansi16].
+pub fn convert_rgb_into_ansi16(rgb_color: RgbColor) -> Ansi256Color {
+    let index = (0..16_u8)
+        .min_by_key(|&index| {
+            let system_color = RgbColor::from(ANSI_COLOR_PALETTE[index as usize]);
+            calculate_relative_diff_between_colors(rgb_color, system_color)
+        })
+        .unwrap_or_default();
+    Ansi256Color { index }
+}
+
 mod cube_mapping {
     use crate::RgbColor;
 
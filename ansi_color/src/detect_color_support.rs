@@ -28,6 +28,7 @@ pub mod global_color_support {
     use super::*;
 
     static mut COLOR_SUPPORT_GLOBAL: AtomicI8 = AtomicI8::new(NOT_SET_VALUE);
+    static mut COLOR_SUPPORT_DETECTED: AtomicI8 = AtomicI8::new(NOT_SET_VALUE);
     const NOT_SET_VALUE: i8 = -1;
 
     /// This is the main function that is used to determine whether color is supported.
@@ -36,17 +37,22 @@ pub mod global_color_support {
     /// - If the value has been set using [set_override], then that value will be
     ///   returned.
     /// - Otherwise, the value will be determined calling
-    ///   [examine_env_vars_to_determine_color_support].
+    ///   [examine_env_vars_to_determine_color_support]. This is only done once, and the
+    ///   result is memoized, since this is called every time that a color is painted.
+    #[allow(static_mut_refs)]
     pub fn detect() -> ColorSupport {
-        match try_get_override() {
-            Ok(it) => match it {
-                ColorSupport::Ansi256 => ColorSupport::Ansi256,
-                ColorSupport::Truecolor => ColorSupport::Truecolor,
-                ColorSupport::Grayscale => ColorSupport::Grayscale,
-                ColorSupport::NoColor => ColorSupport::NoColor,
-            },
-            Err(_) => examine_env_vars_to_determine_color_support(Stream::Stdout),
+        if let Ok(it) = try_get_override() {
+            return it;
+        }
+
+        let it = unsafe { COLOR_SUPPORT_DETECTED.load(Ordering::Acquire) };
+        if let Ok(it) = ColorSupport::try_from(it) {
+            return it;
         }
+
+        let it = examine_env_vars_to_determine_color_support(Stream::Stdout);
+        unsafe { COLOR_SUPPORT_DETECTED.store(i8::from(it), Ordering::Release) };
+        it
     }
 
     /// Override the color support. Regardless of the value of the environment variables
@@ -81,43 +87,70 @@ pub mod global_color_support {
 }
 
 /// Determine whether color is supported heuristically. This is based on the environment
-/// variables.
+/// variables. Please use [global_color_support::detect] instead of calling this
+/// directly, since it memoizes the result.
 pub fn examine_env_vars_to_determine_color_support(stream: Stream) -> ColorSupport {
-    if env_no_color()
-        || as_str(&env::var("TERM")) == Ok("dumb")
-        || !(is_a_tty(stream) || env::var("IGNORE_IS_TERMINAL").is_ok_and(|v| v != "0"))
-    {
+    determine_color_support_from_env(
+        is_a_tty(stream) || env::var("IGNORE_IS_TERMINAL").is_ok_and(|v| v != "0"),
+        is_ci::uncached(),
+        env::consts::OS,
+        |key| env::var(key).ok(),
+    )
+}
+
+/// Determine the color support of the terminal from the environment variables returned
+/// by `get_env`, so that the heuristics can be tested w/out changing the environment of
+/// the process. The checks are done from the most to the least specific:
+///
+/// 1. [ColorSupport::NoColor] if `NO_COLOR` is set, `TERM` is `dumb`, or the output
+///    isn't a terminal.
+/// 2. [ColorSupport::Truecolor] if `COLORTERM` is `truecolor` or `24bit`, the terminal
+///    (in `TERM_PROGRAM`) is known to support it, or the terminfo name in `TERM` has a
+///    `direct` (or `truecolor`) suffix, eg: `xterm-direct`.
+/// 3. [ColorSupport::Ansi256] if the terminfo name in `TERM` has a `256color` suffix, eg:
+///    `xterm-256color`, or `COLORTERM` is set to something else.
+/// 4. [ColorSupport::Ansi16] if `TERM` is a terminal that supports color (eg: `xterm`,
+///    `linux`, `screen`), `CLICOLOR` is set, or this is running in CI.
+///
+/// Windows 10+ terminals support truecolor, so that is used on Windows (unless it is
+/// [ColorSupport::NoColor]).
+pub fn determine_color_support_from_env(
+    is_a_tty: bool,
+    is_ci: bool,
+    os: &str,
+    get_env: impl Fn(&str) -> Option<String>,
+) -> ColorSupport {
+    let term = get_env("TERM").unwrap_or_default();
+    let colorterm = get_env("COLORTERM");
+    let term_program = get_env("TERM_PROGRAM").unwrap_or_default();
+
+    let is_no_color = get_env("NO_COLOR").is_some_and(|v| v != "0");
+    if is_no_color || term == "dumb" || !is_a_tty {
         return ColorSupport::NoColor;
     }
 
-    if env::consts::OS == "macos" {
-        if as_str(&env::var("TERM_PROGRAM")) == Ok("Apple_Terminal")
-            && env::var("TERM").map(|term| check_256_color(&term)) == Ok(true)
-        {
-            return ColorSupport::Ansi256;
-        }
+    if os == "windows" {
+        return ColorSupport::Truecolor;
+    }
 
-        if as_str(&env::var("TERM_PROGRAM")) == Ok("iTerm.app")
-            || as_str(&env::var("COLORTERM")) == Ok("truecolor")
-        {
-            return ColorSupport::Truecolor;
-        }
+    // Apple Terminal doesn't support truecolor, even if `COLORTERM` says it does.
+    if term_program == "Apple_Terminal" && check_256_color(&term) {
+        return ColorSupport::Ansi256;
     }
 
-    if env::consts::OS == "linux" && as_str(&env::var("COLORTERM")) == Ok("truecolor") {
+    if matches!(colorterm.as_deref(), Some("truecolor") | Some("24bit"))
+        || matches!(term_program.as_str(), "iTerm.app" | "WezTerm" | "vscode")
+        || check_truecolor(&term)
+    {
         return ColorSupport::Truecolor;
     }
 
-    if env::consts::OS == "windows" {
-        return ColorSupport::Truecolor;
+    if check_256_color(&term) || colorterm.is_some() {
+        return ColorSupport::Ansi256;
     }
 
-    if env::var("COLORTERM").is_ok()
-        || env::var("TERM").map(|term| check_ansi_color(&term)) == Ok(true)
-        || env::var("CLICOLOR").is_ok_and(|v| v != "0")
-        || is_ci::uncached()
-    {
-        return ColorSupport::Truecolor;
+    if check_ansi_color(&term) || get_env("CLICOLOR").is_some_and(|v| v != "0") || is_ci {
+        return ColorSupport::Ansi16;
     }
 
     ColorSupport::NoColor
@@ -135,6 +168,9 @@ pub enum Stream {
 pub enum ColorSupport {
     Truecolor,
     Ansi256,
+    /// Only the 16 basic colors (8 normal & 8 bright) are supported, eg: in the Linux
+    /// console.
+    Ansi16,
     Grayscale,
     NoColor,
 }
@@ -151,6 +187,7 @@ mod convert_between_color_and_i8 {
                 2 => Ok(super::ColorSupport::Truecolor),
                 3 => Ok(super::ColorSupport::NoColor),
                 4 => Ok(super::ColorSupport::Grayscale),
+                5 => Ok(super::ColorSupport::Ansi16),
                 _ => Err(()),
             }
         }
@@ -164,6 +201,7 @@ mod convert_between_color_and_i8 {
                 super::ColorSupport::Truecolor => 2,
                 super::ColorSupport::NoColor   => 3,
                 super::ColorSupport::Grayscale => 4,
+                super::ColorSupport::Ansi16    => 5,
             }
         }
     }
@@ -184,6 +222,10 @@ mod helpers {
         term.ends_with("256") || term.ends_with("256color")
     }
 
+    pub fn check_truecolor(term: &str) -> bool {
+        term.ends_with("direct") || term.ends_with("truecolor") || term.ends_with("24bit")
+    }
+
     pub fn check_ansi_color(term: &str) -> bool {
         term.starts_with("screen")
             || term.starts_with("xterm")
@@ -197,7 +239,7 @@ mod helpers {
     }
 
     pub fn env_no_color() -> bool {
-        match as_str(&env::var("NO_COLOR")) {
+        match env::var("NO_COLOR").as_deref() {
             Ok("0") | Err(_) => false,
             Ok(_) => true,
         }
@@ -205,13 +247,6 @@ mod helpers {
 }
 pub use helpers::*;
 
-fn as_str<E>(option: &Result<String, E>) -> Result<&str, &E> {
-    match option {
-        Ok(inner) => Ok(inner),
-        Err(e) => Err(e),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use serial_test::serial;
@@ -264,4 +299,57 @@ mod tests {
         global_color_support::clear_override();
         assert_eq!(global_color_support::try_get_override(), Err(()));
     }
+
+    #[test]
+    #[serial]
+    fn cycle_6() {
+        global_color_support::set_override(ColorSupport::Ansi16);
+        assert_eq!(global_color_support::detect(), ColorSupport::Ansi16);
+        global_color_support::clear_override();
+    }
+
+    fn detect_from(env_vars: &[(&str, &str)]) -> ColorSupport {
+        determine_color_support_from_env(true, false, "linux", |key| {
+            env_vars
+                .iter()
+                .find(|(it, _)| *it == key)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_determine_color_support_from_env() {
+        assert_eq!(
+            detect_from(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+            ColorSupport::Truecolor
+        );
+        assert_eq!(
+            detect_from(&[("TERM", "xterm-direct")]),
+            ColorSupport::Truecolor
+        );
+        assert_eq!(
+            detect_from(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]),
+            ColorSupport::Truecolor
+        );
+        assert_eq!(
+            detect_from(&[("TERM", "xterm-256color")]),
+            ColorSupport::Ansi256
+        );
+        assert_eq!(detect_from(&[("TERM", "linux")]), ColorSupport::Ansi16);
+        assert_eq!(detect_from(&[("TERM", "xterm")]), ColorSupport::Ansi16);
+        assert_eq!(detect_from(&[("TERM", "dumb")]), ColorSupport::NoColor);
+        assert_eq!(
+            detect_from(&[("TERM", "xterm-256color"), ("NO_COLOR", "1")]),
+            ColorSupport::NoColor
+        );
+        assert_eq!(detect_from(&[]), ColorSupport::NoColor);
+
+        // Not a terminal.
+        assert_eq!(
+            determine_color_support_from_env(false, false, "linux", |_| Some(
+                "truecolor".to_string()
+            )),
+            ColorSupport::NoColor
+        );
+    }
 }
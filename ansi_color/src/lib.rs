@@ -96,6 +96,9 @@
 //! Rust crate to generate formatted ANSI 256 (8-bit) and truecolor (24-bit) color output to stdout. On
 //! macOS, the default Terminal.app does not support truecolor, so ANSI 256 colors are used instead.
 //!
+//! Terminals that only support the 16 system colors (eg: the Linux console) are detected too,
+//! and colors are downgraded to the closest system color in them.
+//!
 //! This crate performs its own detection of terminal color capability heuristically. And does not
 //! use other crates to perform this function.
 //!
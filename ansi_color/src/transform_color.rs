@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use crate::{Ansi256Color, RgbColor};
+use crate::{convert_rgb_into_ansi16, Ansi256Color, RgbColor};
 
 pub trait TransformColor {
     /// Returns a [RgbColor] representation of the `self` color.
@@ -28,6 +28,10 @@ pub trait TransformColor {
     /// Returns the index of a color in 256-color ANSI palette approximating the `self`
     /// color as grayscale.
     fn as_grayscale(&self) -> Ansi256Color;
+
+    /// Returns the index of a color in the 16 system colors of the ANSI palette
+    /// approximating the `self` color.
+    fn as_ansi16(&self) -> Ansi256Color { convert_rgb_into_ansi16(self.as_rgb()) }
 }
 
 #[cfg(test)]
@@ -78,6 +82,15 @@ mod tests {
         assert_eq!(rgb_color.as_grayscale(), expected_gray);
     }
 
+    #[test_case(Color::Rgb(0, 0, 0), 0)]
+    #[test_case(Color::Rgb(250, 10, 10), 9)]
+    #[test_case(Color::Rgb(0, 200, 200), 6)]
+    #[test_case(Color::Ansi256(231), 15)]
+    fn test_color_as_ansi16(color: crate::Color, index: u8) {
+        let expected_ansi = Ansi256Color { index };
+        assert_eq!(color.as_ansi16(), expected_ansi);
+    }
+
     #[test_case(RgbColor{red: 0, green: 128, blue: 255}, 245)]
     #[test_case(RgbColor{red: 128, green: 128, blue: 128}, 244)]
     fn test_rgb_color_as_grayscale(rgb_color: RgbColor, index: u8) {
@@ -60,13 +60,16 @@ use r3bl_tui::{box_end,
                ComponentRegistry,
                ComponentRegistryMap,
                DialogBuffer,
+               DialogButton,
                DialogChoice,
                DialogComponent,
+               DialogConfirmChoice,
                DialogEngineConfigOptions,
                DialogEngineMode,
                EditMode,
                EditorComponent,
                EditorEngineConfig,
+               EditorFileWatcher,
                EventPropagation,
                FlexBox,
                FlexBoxId,
@@ -100,6 +103,9 @@ use crate::edi::{file_utils, State};
 pub enum AppSignal {
     AskForFilenameToSaveFile,
     SaveFile,
+    /// Sent by the [EditorFileWatcher] when the file that is open was changed by another
+    /// program.
+    FileChangedOnDisk,
     #[default]
     Noop,
 }
@@ -119,6 +125,7 @@ pub enum Id {
     // Components.
     ComponentEditor = 1,
     ComponentSimpleDialogAskForFilenameToSaveFile = 2,
+    ComponentDialogFileChangedOnDisk = 3,
 
     // Styles.
     StyleEditorDefault = 10,
@@ -141,7 +148,10 @@ mod id_impl {
 }
 
 /// The main app struct.
-pub struct AppMain;
+pub struct AppMain {
+    /// Watches the file that is open in the editor component (once it has a file path).
+    file_watcher: EditorFileWatcher,
+}
 
 mod app_main_constructor {
     use super::*;
@@ -151,14 +161,18 @@ mod app_main_constructor {
             call_if_true!(DEBUG_TUI_MOD, {
                 tracing::debug!("🪙 construct edi::AppMain");
             });
-            Self
+            Self {
+                file_watcher: Default::default(),
+            }
         }
     }
 
     impl AppMain {
         /// Note that this needs to be initialized before it can be used.
         pub fn new_boxed() -> BoxedSafeApp<State, AppSignal> {
-            let it = Self;
+            let it = Self {
+                file_watcher: Default::default(),
+            };
             Box::new(it)
         }
     }
@@ -167,6 +181,31 @@ mod app_main_constructor {
 mod app_main_impl_app_trait {
     use super::*;
 
+    /// The title of the terminal window (or tab), w/ the name of the file that is open.
+    pub fn get_title(maybe_file_path: Option<&str>) -> String {
+        let file_name = maybe_file_path
+            .map(|it| {
+                std::path::Path::new(it)
+                    .file_name()
+                    .map(|it| it.to_string_lossy().to_string())
+                    .unwrap_or_else(|| it.to_string())
+            })
+            .unwrap_or_else(|| "untitled".to_string());
+        format!("edi - {file_name}")
+    }
+
+    /// Called by the [EditorFileWatcher] (from its task) when the file that is open was
+    /// changed on disk.
+    fn on_file_changed_on_disk(
+        _id: FlexBoxId,
+        main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AppSignal>>,
+    ) {
+        send_signal!(
+            main_thread_channel_sender,
+            TerminalWindowMainThreadSignal::ApplyAction(AppSignal::FileChangedOnDisk)
+        );
+    }
+
     impl App for AppMain {
         type S = State;
         type AS = AppSignal;
@@ -257,14 +296,10 @@ mod app_main_impl_app_trait {
                         .get_mut(&FlexBoxId::from(Id::ComponentEditor));
 
                     if let Some(editor_buffer) = maybe_editor_buffer {
-                        let maybe_file_path =
-                            editor_buffer.editor_content.maybe_file_path.clone();
-                        let content: String = editor_buffer.get_as_string_with_newlines();
-
-                        match maybe_file_path {
+                        match editor_buffer.editor_content.maybe_file_path {
                             // Found file path in the editor buffer.
-                            Some(file_path) => {
-                                file_utils::save_content_to_file(file_path, content);
+                            Some(_) => {
+                                file_utils::save_editor_buffer(editor_buffer);
                             }
                             // Could not find file path in the editor buffer. This is a
                             // new buffer. Need to ask user via dialog box.
@@ -308,6 +343,44 @@ mod app_main_impl_app_trait {
 
                     return Ok(EventPropagation::ConsumedRender);
                 }
+                AppSignal::FileChangedOnDisk => {
+                    let GlobalData { state, .. } = global_data;
+
+                    let Some(editor_buffer) = state
+                        .editor_buffers
+                        .get_mut(&FlexBoxId::from(Id::ComponentEditor))
+                    else {
+                        return Ok(EventPropagation::Consumed);
+                    };
+
+                    // The buffer may have saved the file after the watcher noticed the
+                    // change.
+                    if !editor_buffer.has_file_changed_on_disk() {
+                        return Ok(EventPropagation::Consumed);
+                    }
+
+                    // There are no changes to lose, so just reload the file.
+                    if !editor_buffer.is_dirty() {
+                        if let Err(err) = editor_buffer.reload() {
+                            tracing::error!("📣 Error reloading file: {err:?}");
+                        }
+                        return Ok(EventPropagation::ConsumedRender);
+                    }
+
+                    // Reset the dialog component prior to activating / showing it.
+                    ComponentRegistry::reset_component(
+                        component_registry_map,
+                        FlexBoxId::from(Id::ComponentDialogFileChangedOnDisk),
+                    );
+
+                    if let Err(err) =
+                        modal_dialog_file_changed_on_disk::show(has_focus, state)
+                    {
+                        tracing::error!("📣 Error activating confirm modal: {err:?}");
+                    };
+
+                    return Ok(EventPropagation::ConsumedRender);
+                }
                 AppSignal::Noop => {}
             }
 
@@ -323,6 +396,38 @@ mod app_main_impl_app_trait {
             throws_with_return!({
                 let window_size = global_data.window_size;
 
+                // Show the name of the file that is open in the terminal's title.
+                let title = get_title(
+                    global_data
+                        .state
+                        .editor_buffers
+                        .get(&FlexBoxId::from(Id::ComponentEditor))
+                        .and_then(|it| it.editor_content.maybe_file_path.as_deref()),
+                );
+                global_data.set_title(title);
+
+                // Watch the file that is open for changes made by other programs. A new
+                // buffer doesn't have a file path until it is saved.
+                if !self.file_watcher.is_started() {
+                    if let Some(editor_buffer) = global_data
+                        .state
+                        .editor_buffers
+                        .get(&FlexBoxId::from(Id::ComponentEditor))
+                    {
+                        if let Some(file_path) =
+                            &editor_buffer.editor_content.maybe_file_path
+                        {
+                            self.file_watcher.start(
+                                FlexBoxId::from(Id::ComponentEditor),
+                                file_path,
+                                editor_buffer.get_shared_file_stamp(),
+                                global_data.main_thread_channel_sender.clone(),
+                                on_file_changed_on_disk,
+                            );
+                        }
+                    }
+                }
+
                 // Create a surface and then run the SurfaceRenderer (ContainerSurfaceRender) on it.
                 let mut surface = {
                     let mut it = surface!(stylesheet: stylesheet::create_stylesheet()?);
@@ -427,6 +532,7 @@ mod modal_dialog_ask_for_filename_to_save_file {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let boxed_dialog_component = {
@@ -498,6 +604,11 @@ mod modal_dialog_ask_for_filename_to_save_file {
                             "".to_string(),
                         );
                     }
+                    // This dialog isn't a form, a confirm dialog, or a date (or color) picker.
+                    DialogChoice::Form(_)
+                    | DialogChoice::Confirm(_)
+                    | DialogChoice::Date(_)
+                    | DialogChoice::Color(_) => {}
                 }
             }
 
@@ -524,6 +635,123 @@ mod modal_dialog_ask_for_filename_to_save_file {
     }
 }
 
+mod modal_dialog_file_changed_on_disk {
+    use super::*;
+
+    /// Ask the user whether to reload the file (that was changed by another program) &
+    /// lose the changes in the buffer.
+    pub fn show(has_focus: &mut HasFocus, state: &mut State) -> CommonResult<()> {
+        throws!({
+            let id = FlexBoxId::from(Id::ComponentDialogFileChangedOnDisk);
+
+            let file_path = state
+                .editor_buffers
+                .get(&FlexBoxId::from(Id::ComponentEditor))
+                .and_then(|it| it.editor_content.maybe_file_path.clone())
+                .unwrap_or_default();
+
+            // Setting the has_focus to Id::ComponentDialogFileChangedOnDisk will cause
+            // the dialog to appear on the next render.
+            has_focus.try_set_modal_id(id)?;
+
+            state.dialog_buffers.insert(
+                id,
+                DialogBuffer::new_confirm(
+                    format!(
+                        "{file_path} was changed on disk. Reload it & lose your changes?"
+                    ),
+                    vec![DialogButton::yes(), DialogButton::no()],
+                ),
+            );
+
+            call_if_true!(DEBUG_TUI_MOD, {
+                tracing::debug!("📣 activate modal confirm: {:?}", has_focus);
+            });
+        });
+    }
+
+    /// Insert confirm dialog component into registry if it's not already there.
+    pub fn insert_component_into_registry(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+    ) {
+        let result_stylesheet = stylesheet::create_stylesheet();
+
+        let dialog_options = DialogEngineConfigOptions {
+            mode: DialogEngineMode::ModalConfirm,
+            maybe_style_border: get_tui_style! { @from_result: result_stylesheet , Id::StyleDialogBorder.into() },
+            maybe_style_title: get_tui_style! { @from_result: result_stylesheet , Id::StyleDialogTitle.into() },
+            maybe_style_editor: get_tui_style! { @from_result: result_stylesheet , Id::StyleDialogEditor.into() },
+            maybe_style_results_panel: get_tui_style! { @from_result: result_stylesheet , Id::StyleDialogResultsPanel.into() },
+            ..Default::default()
+        };
+
+        let editor_options = EditorEngineConfig {
+            multiline_mode: LineMode::SingleLine,
+            syntax_highlight: SyntaxHighlightMode::Disable,
+            edit_mode: EditMode::ReadOnly,
+            ..Default::default()
+        };
+
+        let boxed_dialog_component = {
+            let it = DialogComponent::new_boxed(
+                FlexBoxId::from(Id::ComponentDialogFileChangedOnDisk),
+                dialog_options,
+                editor_options,
+                on_dialog_press_handler,
+                on_dialog_editor_change_handler,
+            );
+
+            fn on_dialog_press_handler(
+                dialog_choice: DialogChoice,
+                state: &mut State,
+                _main_thread_channel_sender: &mut Sender<
+                    TerminalWindowMainThreadSignal<AppSignal>,
+                >,
+            ) {
+                match dialog_choice {
+                    DialogChoice::Confirm(DialogConfirmChoice::Yes) => {
+                        let maybe_editor_buffer = state
+                            .get_mut_editor_buffer(FlexBoxId::from(Id::ComponentEditor));
+                        if let Some(editor_buffer) = maybe_editor_buffer {
+                            if let Err(err) = editor_buffer.reload() {
+                                tracing::error!("📣 Error reloading file: {err:?}");
+                            }
+                        }
+                    }
+                    // Keep the changes in the buffer.
+                    DialogChoice::Confirm(_) => {}
+                    // This dialog is only a confirm dialog.
+                    DialogChoice::Yes(_)
+                    | DialogChoice::No
+                    | DialogChoice::Form(_)
+                    | DialogChoice::Date(_)
+                    | DialogChoice::Color(_) => {}
+                }
+            }
+
+            fn on_dialog_editor_change_handler(
+                _state: &mut State,
+                _main_thread_channel_sender: &mut Sender<
+                    TerminalWindowMainThreadSignal<AppSignal>,
+                >,
+            ) {
+            }
+
+            it
+        };
+
+        ComponentRegistry::put(
+            component_registry_map,
+            FlexBoxId::from(Id::ComponentDialogFileChangedOnDisk),
+            boxed_dialog_component,
+        );
+
+        call_if_true!(DEBUG_TUI_MOD, {
+            tracing::debug!("🪙 construct DialogComponent (confirm) [ on_dialog_press ]",);
+        });
+    }
+}
+
 mod perform_layout {
     use super::*;
 
@@ -573,6 +801,20 @@ mod perform_layout {
                       has_focus:          has_focus
                     };
                 }
+
+                // Or the confirm modal dialog, when the file was changed on disk.
+                if has_focus
+                    .is_modal_id(FlexBoxId::from(Id::ComponentDialogFileChangedOnDisk))
+                {
+                    render_component_in_given_box! {
+                      in:                 surface,
+                      box:                FlexBox::default(), /* This is not used as the modal breaks out of its box. */
+                      component_id:       FlexBoxId::from(Id::ComponentDialogFileChangedOnDisk),
+                      from:               component_registry_map,
+                      global_data:        global_data,
+                      has_focus:          has_focus
+                    };
+                }
             });
         }
     }
@@ -589,6 +831,9 @@ mod populate_component_registry {
         modal_dialog_ask_for_filename_to_save_file::insert_component_into_registry(
             component_registry_map,
         );
+        modal_dialog_file_changed_on_disk::insert_component_into_registry(
+            component_registry_map,
+        );
 
         // Switch focus to the editor component if focus is not set.
         let id = FlexBoxId::from(Id::ComponentEditor);
@@ -613,7 +858,7 @@ mod populate_component_registry {
             ) {
                 send_signal!(
                     main_thread_channel_sender,
-                    TerminalWindowMainThreadSignal::Render(Some(my_id))
+                    TerminalWindowMainThreadSignal::RequestRender(Some(my_id))
                 );
             }
 
@@ -182,13 +182,22 @@ pub mod constructor {
     fn create_hash_map_of_editor_buffers(
         maybe_file_path: &Option<String>,
     ) -> HashMap<FlexBoxId, EditorBuffer> {
-        let editor_buffer = {
-            let mut editor_buffer = EditorBuffer::new_empty(
-                &Some(file_utils::get_file_extension(maybe_file_path)),
-                maybe_file_path,
-            );
-            editor_buffer.set_lines(file_utils::get_content(maybe_file_path));
-            editor_buffer
+        // Loading the file (instead of just its content) keeps its format (line endings,
+        // etc) & its stamp, which is used to detect changes made to it by other programs.
+        let maybe_loaded_editor_buffer = maybe_file_path
+            .as_ref()
+            .and_then(|it| EditorBuffer::load_from_path(it).ok());
+        let editor_buffer = match maybe_loaded_editor_buffer {
+            Some(editor_buffer) => editor_buffer,
+            // The file doesn't exist yet (or it can't be read).
+            None => {
+                let mut editor_buffer = EditorBuffer::new_empty(
+                    &Some(file_utils::get_file_extension(maybe_file_path)),
+                    maybe_file_path,
+                );
+                editor_buffer.set_lines(file_utils::get_content(maybe_file_path));
+                editor_buffer
+            }
         };
 
         {
@@ -243,31 +252,33 @@ pub mod file_utils {
         vec![]
     }
 
-    pub fn save_content_to_file(file_path: String, content: String) {
-        tokio::spawn(async move {
-            report_analytics::start_task_to_generate_event(
-                "".to_string(),
-                AnalyticsAction::EdiFileSave,
-            );
+    /// Save the buffer to its file path. This is done by the buffer itself, so that the
+    /// file's format is kept, and the buffer's file stamp is updated (which is how the
+    /// [r3bl_tui::EditorFileWatcher] knows that the change wasn't made by another
+    /// program).
+    pub fn save_editor_buffer(editor_buffer: &mut EditorBuffer) {
+        report_analytics::start_task_to_generate_event(
+            "".to_string(),
+            AnalyticsAction::EdiFileSave,
+        );
 
-            let result_file_write = std::fs::write(file_path.clone(), content);
-            match result_file_write {
-                Ok(_) => {
-                    call_if_true!(DEBUG_TUI_MOD, {
-                        tracing::debug!(
-                            "\n💾💾💾❌ Successfully saved file: {}",
-                            format!("{file_path:?}").green()
-                        );
-                    });
-                }
-                Err(error) => {
-                    tracing::error!(
-                        "\n💾💾💾✅ Failed to save file: {}",
-                        format!("{error:?}").red()
+        let file_path = editor_buffer.editor_content.maybe_file_path.clone();
+        match editor_buffer.save() {
+            Ok(_) => {
+                call_if_true!(DEBUG_TUI_MOD, {
+                    tracing::debug!(
+                        "\n💾💾💾✅ Successfully saved file: {}",
+                        format!("{file_path:?}").green()
                     );
-                }
+                });
+            }
+            Err(error) => {
+                tracing::error!(
+                    "\n💾💾💾❌ Failed to save file: {}",
+                    format!("{error:?}").red()
+                );
             }
-        });
+        }
     }
 }
 
@@ -17,6 +17,8 @@
 
 use std::io::{self, Write};
 
+use r3bl_ansi_color::{AnsiStyledText, Color, Style};
+
 use crate::ok;
 
 pub type Text = Vec<u8>;
@@ -43,6 +45,12 @@ pub type Text = Vec<u8>;
 /// - `manage_shared_writer_output::flush_internal()`.
 ///
 /// If you want to output data without a newline, you can call [`SharedWriter::flush()`].
+///
+/// # Writers for background tasks
+///
+/// When many tasks print above the prompt, give each of them its own writer w/
+/// [`SharedWriter::clone_with_prefix()`], so that each of its lines starts w/ the name of
+/// the task, and its lines are never interleaved mid-line w/ the ones from other tasks.
 pub struct SharedWriter {
     /// Holds the data to be written to the terminal.
     pub buffer: Text,
@@ -56,8 +64,24 @@ pub struct SharedWriter {
     /// struct will report errors when [`std::io::Write::write()`] fails, due to the
     /// receiver end of the channel being closed.
     pub silent_error: bool,
+
+    /// Each line starts w/ this, see [`SharedWriter::clone_with_prefix()`]. When this is
+    /// set, only complete lines are sent (and [`SharedWriter::flush()`] completes the
+    /// partial one), so that they aren't interleaved w/ the lines from other writers.
+    pub maybe_line_prefix: Option<String>,
 }
 
+/// The colors of the prefixes of [`SharedWriter::clone_with_prefix()`], one of which is
+/// picked for each task name, so that the lines from different tasks can be told apart.
+const SHARED_WRITER_PREFIX_COLORS: [Color; 6] = [
+    Color::Rgb(50, 200, 50),
+    Color::Rgb(100, 100, 200),
+    Color::Rgb(200, 150, 50),
+    Color::Rgb(200, 50, 200),
+    Color::Rgb(50, 200, 200),
+    Color::Rgb(200, 100, 100),
+];
+
 /// Signals that can be sent to the `line` channel, which is monitored by the task.
 #[derive(Debug, Clone)]
 pub enum LineStateControlSignal {
@@ -77,7 +101,37 @@ impl SharedWriter {
             buffer: Default::default(),
             line_state_control_channel_sender: line_sender,
             silent_error: false,
+            maybe_line_prefix: None,
+        }
+    }
+
+    /// Returns a clone (w/ its own buffer) for a background task, which starts each line
+    /// w/ the `task_name` in a color (that is picked by the name), eg: `[db] connected`.
+    pub fn clone_with_prefix(&self, task_name: &str) -> Self {
+        let color_index = task_name.bytes().fold(0_usize, |acc, it| {
+            acc.wrapping_mul(31).wrapping_add(it as usize)
+        }) % SHARED_WRITER_PREFIX_COLORS.len();
+        let prefix = AnsiStyledText {
+            text: &format!("[{task_name}]"),
+            style: &[Style::Foreground(SHARED_WRITER_PREFIX_COLORS[color_index])],
+        };
+        Self {
+            maybe_line_prefix: Some(format!("{prefix} ")),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the `text` w/ the [Self::maybe_line_prefix] at the start of each line.
+    fn add_line_prefix(&self, text: &[u8]) -> Text {
+        let Some(ref prefix) = self.maybe_line_prefix else {
+            return text.to_vec();
+        };
+        let mut it = Text::with_capacity(text.len() + prefix.len());
+        for line in text.split_inclusive(|it| *it == b'\n') {
+            it.extend_from_slice(prefix.as_bytes());
+            it.extend_from_slice(line);
         }
+        it
     }
 }
 
@@ -94,25 +148,35 @@ impl Clone for SharedWriter {
                 .line_state_control_channel_sender
                 .clone(),
             silent_error: true,
+            maybe_line_prefix: self.maybe_line_prefix.clone(),
         }
     }
 }
 
 impl Write for SharedWriter {
     fn write(&mut self, payload: &[u8]) -> io::Result<usize> {
-        let self_buffer = &mut self.buffer;
+        // Append the payload to self.buffer.
+        self.buffer.extend_from_slice(payload);
 
-        // Append the payload to self_buffer.
-        self_buffer.extend_from_slice(payload);
+        // If self.buffer ends with a newline, send it to the line_sender. W/ a prefix,
+        // the complete lines in it are sent, and the partial one is kept.
+        let maybe_line_end = match self.maybe_line_prefix {
+            None => self.buffer.ends_with(b"\n").then_some(self.buffer.len()),
+            Some(_) => self
+                .buffer
+                .iter()
+                .rposition(|it| *it == b'\n')
+                .map(|it| it + 1),
+        };
 
-        // If self_buffer ends with a newline, send it to the line_sender.
-        if self_buffer.ends_with(b"\n") {
-            match self
-                .line_state_control_channel_sender
-                .try_send(LineStateControlSignal::Line(self_buffer.clone()))
-            {
+        if let Some(line_end) = maybe_line_end {
+            match self.line_state_control_channel_sender.try_send(
+                LineStateControlSignal::Line(
+                    self.add_line_prefix(&self.buffer[..line_end]),
+                ),
+            ) {
                 Ok(_) => {
-                    self_buffer.clear();
+                    self.buffer.drain(..line_end);
                 }
                 Err(_) => {
                     if !self.silent_error {
@@ -129,10 +193,20 @@ impl Write for SharedWriter {
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        match self
-            .line_state_control_channel_sender
-            .try_send(LineStateControlSignal::Line(self.buffer.clone()))
-        {
+        // W/ a prefix, the partial line is ended, so that the next line from another
+        // writer doesn't continue it.
+        if self.maybe_line_prefix.is_some() {
+            if self.buffer.is_empty() {
+                return ok!();
+            }
+            if !self.buffer.ends_with(b"\n") {
+                self.buffer.push(b'\n');
+            }
+        }
+
+        match self.line_state_control_channel_sender.try_send(
+            LineStateControlSignal::Line(self.add_line_prefix(&self.buffer)),
+        ) {
             Ok(_) => {
                 self.buffer.clear();
             }
@@ -217,4 +291,33 @@ mod tests {
         // Will produce error.
         assert!(shared_writer.write_all(b"error\n").is_err());
     }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_clone_with_prefix() {
+        let (line_sender, mut line_receiver) = tokio::sync::mpsc::channel(1_000);
+        let shared_writer = SharedWriter::new(line_sender);
+
+        let mut task_writer = shared_writer.clone_with_prefix("db");
+        let prefix = task_writer.maybe_line_prefix.clone().unwrap();
+        assert!(prefix.contains("[db]"));
+        assert_eq!(task_writer.clone().maybe_line_prefix, Some(prefix.clone()));
+
+        // Only the complete lines are sent, each w/ the prefix.
+        task_writer.write_all(b"one\ntwo\nthr").unwrap();
+        assert_eq!(task_writer.buffer, b"thr");
+        let Some(LineStateControlSignal::Line(bytes)) = line_receiver.recv().await else {
+            panic!("Expected LineStateControlSignal::Line, got something else");
+        };
+        assert_eq!(bytes, format!("{prefix}one\n{prefix}two\n").into_bytes());
+
+        // Flush ends the partial line.
+        task_writer.write_all(b"ee").unwrap();
+        task_writer.flush().unwrap();
+        assert!(task_writer.buffer.is_empty());
+        let Some(LineStateControlSignal::Line(bytes)) = line_receiver.recv().await else {
+            panic!("Expected LineStateControlSignal::Line, got something else");
+        };
+        assert_eq!(bytes, format!("{prefix}three\n").into_bytes());
+    }
 }
@@ -34,6 +34,30 @@ mod tests {
         assert_eq2!(u_s.display_width, ch!(15));
     }
 
+    #[test]
+    fn test_unicode_string_with_tab_width() {
+        // Each tab goes up to the next tab stop.
+        let u_s = UnicodeString::new_with_tab_width("a\tbc\td", 4);
+        assert_eq2!(u_s[1].unicode_width, ch!(3));
+        assert_eq2!(u_s[4].display_col_offset, ch!(6));
+        assert_eq2!(u_s[4].unicode_width, ch!(2));
+        assert_eq2!(u_s[5].display_col_offset, ch!(8));
+        assert_eq2!(u_s.display_width, ch!(9));
+
+        // Re-layout w/ a different tab width.
+        let mut u_s = u_s;
+        u_s.set_tab_width(2);
+        assert_eq2!(u_s[1].unicode_width, ch!(1));
+        assert_eq2!(u_s[4].unicode_width, ch!(2));
+        assert_eq2!(u_s.display_width, ch!(7));
+
+        // Strings w/out tabs are not affected.
+        assert_eq2!(
+            UnicodeString::new_with_tab_width("abc", 4),
+            UnicodeString::from("abc")
+        );
+    }
+
     #[allow(clippy::zero_prefixed_literal)]
     #[test]
     fn test_grapheme_cluster_segment() {
@@ -40,7 +40,24 @@ mod unicode_string_impl {
 
     impl UnicodeString {
         /// Constructor function that creates a [UnicodeString] from a string slice.
-        pub fn new(this: &str) -> UnicodeString {
+        pub fn new(this: &str) -> UnicodeString { Self::new_impl(this, None) }
+
+        /// Just like [UnicodeString::new], except that each `\t` is as wide as it takes
+        /// to get to the next tab stop (which are `tab_width` columns apart), instead of
+        /// having no width at all.
+        pub fn new_with_tab_width(this: &str, tab_width: usize) -> UnicodeString {
+            Self::new_impl(this, Some(tab_width.max(1)))
+        }
+
+        /// Lay out the `\t` in this string again w/ the given `tab_width` (see
+        /// [UnicodeString::new_with_tab_width]). This does nothing if there are no tabs.
+        pub fn set_tab_width(&mut self, tab_width: usize) {
+            if self.string.contains('\t') {
+                *self = Self::new_with_tab_width(&self.string, tab_width);
+            }
+        }
+
+        fn new_impl(this: &str, maybe_tab_width: Option<usize>) -> UnicodeString {
             let mut total_byte_offset = 0;
             let mut total_grapheme_cluster_count = 0;
             let mut my_unicode_string_segments = vec![];
@@ -49,8 +66,13 @@ mod unicode_string_impl {
             for (grapheme_cluster_index, (byte_offset, grapheme_cluster_str)) in
                 this.grapheme_indices(true).enumerate()
             {
-                let unicode_width =
-                    ch!(UnicodeString::str_display_width(grapheme_cluster_str));
+                let unicode_width = match (grapheme_cluster_str, maybe_tab_width) {
+                    ("\t", Some(tab_width)) => {
+                        let col = ch!(@to_usize my_unicode_width_offset_accumulator);
+                        ch!(tab_width - col % tab_width)
+                    }
+                    _ => ch!(UnicodeString::str_display_width(grapheme_cluster_str)),
+                };
                 my_unicode_string_segments.push(GraphemeClusterSegment {
                     string: grapheme_cluster_str.into(),
                     byte_offset,
@@ -70,12 +70,18 @@ pub struct TuiStyle {
     pub italic: bool,
     pub dim: bool,
     pub underline: bool,
+    /// The kind of line that is painted when [TuiStyle::underline] is set. Terminals that
+    /// don't support the extended kinds paint a [TuiUnderlineStyle::Single] line instead.
+    pub underline_style: TuiUnderlineStyle,
     pub reverse: bool,
     pub hidden: bool,
     pub strikethrough: bool,
     pub computed: bool,
     pub color_fg: Option<TuiColor>,
     pub color_bg: Option<TuiColor>,
+    /// The color of the underline (SGR 58), which is the same as [TuiStyle::color_fg]
+    /// when it is [None].
+    pub color_underline: Option<TuiColor>,
     /// The semantics of this are the same as CSS. The padding is space that is taken up
     /// inside a `FlexBox`. This does not affect the size or position of a `FlexBox`, it
     /// only applies to the contents inside of that `FlexBox`.
@@ -86,6 +92,43 @@ pub struct TuiStyle {
     pub lolcat: bool,
 }
 
+/// The kinds of lines that can be used for [TuiStyle::underline] (SGR 4:1 to 4:5), eg:
+/// [TuiUnderlineStyle::Curly] for spell check squiggles.
+#[derive(
+    Copy,
+    Default,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Hash,
+    size_of::SizeOf,
+)]
+pub enum TuiUnderlineStyle {
+    #[default]
+    Single,
+    Double,
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+impl TuiUnderlineStyle {
+    /// The parameters of the SGR escape sequence that turns on this kind of underline,
+    /// eg: `4:3` for [TuiUnderlineStyle::Curly].
+    pub fn get_sgr_params(&self) -> &'static str {
+        match self {
+            TuiUnderlineStyle::Single => "4",
+            TuiUnderlineStyle::Double => "4:2",
+            TuiUnderlineStyle::Curly => "4:3",
+            TuiUnderlineStyle::Dotted => "4:4",
+            TuiUnderlineStyle::Dashed => "4:5",
+        }
+    }
+}
+
 mod addition {
     use super::*;
 
@@ -124,6 +167,10 @@ mod addition {
             }
             if other.underline {
                 new_style.underline = other.underline;
+                new_style.underline_style = other.underline_style;
+            }
+            if other.color_underline.is_some() {
+                new_style.color_underline = other.color_underline;
             }
             if other.padding.is_some() {
                 new_style.padding = other.padding;
@@ -201,7 +248,10 @@ mod style_helpers {
             }
 
             if self.underline {
-                msg_vec.push("und".to_string())
+                match self.underline_style {
+                    TuiUnderlineStyle::Single => msg_vec.push("und".to_string()),
+                    it => msg_vec.push(format!("und:{it:?}")),
+                }
             }
 
             if self.reverse {
@@ -257,7 +307,14 @@ mod style_helpers {
             }
 
             if self.underline {
-                msg_vec.push("underline".to_string())
+                match self.underline_style {
+                    TuiUnderlineStyle::Single => msg_vec.push("underline".to_string()),
+                    it => msg_vec.push(format!("underline({it:?})")),
+                }
+            }
+
+            if let Some(color_underline) = self.color_underline {
+                msg_vec.push(format!("underline color: {color_underline:?}"))
             }
 
             if self.reverse {
@@ -347,6 +404,30 @@ mod test_style {
         assert!(!style.strikethrough);
         assert!(!style.reverse);
     }
+
+    #[test]
+    fn test_add_styles_w_underline_style() {
+        let squiggle = TuiStyle {
+            underline: true,
+            underline_style: TuiUnderlineStyle::Curly,
+            color_underline: color!(@red).into(),
+            ..TuiStyle::default()
+        };
+        let style = TuiStyle {
+            color_fg: color!(@green).into(),
+            ..TuiStyle::default()
+        } + squiggle;
+
+        assert!(style.underline);
+        assert_eq2!(style.underline_style, TuiUnderlineStyle::Curly);
+        assert_eq2!(style.color_underline, color!(@red).into());
+        assert_eq2!(style.color_fg, color!(@green).into());
+        assert_eq2!(TuiUnderlineStyle::Curly.get_sgr_params(), "4:3");
+
+        // The kind of underline is only taken from a style that sets the underline.
+        let style = squiggle + TuiStyle::default();
+        assert_eq2!(style.underline_style, TuiUnderlineStyle::Curly);
+    }
 }
 
 pub mod convert_to_ansi_color_styles {
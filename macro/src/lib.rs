@@ -142,7 +142,8 @@ use proc_macro::TokenStream;
 ///
 /// - All the attributes are:
 ///   - `bold`, `dim`, `underline`, `reverse`, `hidden`, `strikethrough`
-/// - `color_fg` and `color_bg` can take any of the following:
+///   - `double_underline`, `curly_underline`, `dotted_underline`, `dashed_underline`
+/// - `color_fg`, `color_bg` and `color_underline` can take any of the following:
 ///   - Color enum value.
 ///   - Rgb value.
 ///   - Variable holding either of the above.
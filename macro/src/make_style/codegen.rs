@@ -27,12 +27,34 @@ pub(crate) fn code_gen(
         padding,
         color_fg,
         color_bg,
+        color_underline,
         lolcat,
     }: StyleMetadata,
 ) -> proc_macro::TokenStream {
     let has_attrib_bold = attrib_vec.contains(&Attrib::Bold);
     let has_attrib_dim = attrib_vec.contains(&Attrib::Dim);
-    let has_attrib_underline = attrib_vec.contains(&Attrib::Underline);
+    // The extended kinds of underline also turn on the underline.
+    let underline_style = if attrib_vec.contains(&Attrib::DoubleUnderline) {
+        quote! { r3bl_core::TuiUnderlineStyle::Double }
+    } else if attrib_vec.contains(&Attrib::CurlyUnderline) {
+        quote! { r3bl_core::TuiUnderlineStyle::Curly }
+    } else if attrib_vec.contains(&Attrib::DottedUnderline) {
+        quote! { r3bl_core::TuiUnderlineStyle::Dotted }
+    } else if attrib_vec.contains(&Attrib::DashedUnderline) {
+        quote! { r3bl_core::TuiUnderlineStyle::Dashed }
+    } else {
+        quote! { r3bl_core::TuiUnderlineStyle::Single }
+    };
+    let has_attrib_underline = attrib_vec.iter().any(|it| {
+        matches!(
+            it,
+            Attrib::Underline
+                | Attrib::DoubleUnderline
+                | Attrib::CurlyUnderline
+                | Attrib::DottedUnderline
+                | Attrib::DashedUnderline
+        )
+    });
     let has_attrib_reverse = attrib_vec.contains(&Attrib::Reverse);
     let has_attrib_hidden = attrib_vec.contains(&Attrib::Hidden);
     let has_attrib_strikethrough = attrib_vec.contains(&Attrib::Strikethrough);
@@ -66,6 +88,15 @@ pub(crate) fn code_gen(
         None => quote! {},
     };
 
+    let maybe_color_underline_expr = match color_underline {
+        Some(color_expr) => {
+            quote! {
+              color_underline: Some(#color_expr.into()),
+            }
+        }
+        None => quote! {},
+    };
+
     let maybe_lolcat_expr = match lolcat {
         Some(lolcat_bool) => {
             quote! {
@@ -82,12 +113,14 @@ pub(crate) fn code_gen(
         italic: #has_attrib_italic,
         dim: #has_attrib_dim,
         underline: #has_attrib_underline,
+        underline_style: #underline_style,
         reverse: #has_attrib_reverse,
         hidden: #has_attrib_hidden,
         strikethrough: #has_attrib_strikethrough,
         #maybe_padding_expr
         #maybe_color_fg_expr
         #maybe_color_bg_expr
+        #maybe_color_underline_expr
         #maybe_lolcat_expr
         .. Default::default()
       }
@@ -28,11 +28,12 @@
 //!     padding: 10         /* Optional. */
 //!     color_fg: black     /* Optional. */
 //!     color_bg: white     /* Optional. */
+//!     color_underline: black /* Optional. */
 //!     lolcat: true        /* Optional. */
 //! };
 //! ```
 //!
-//! `color_fg` and `color_bg` can take any [r3bl_core::TuiColor]:
+//! `color_fg`, `color_bg` and `color_underline` can take any [r3bl_core::TuiColor]:
 //! 1. Color enum value.
 //! 2. Rgb value.
 //! 3. Variable holding either of the above.
@@ -64,6 +65,7 @@ impl Parse for StyleMetadata {
             padding: None,
             color_fg: None,
             color_bg: None,
+            color_underline: None,
             lolcat: None,
         };
 
@@ -73,6 +75,7 @@ impl Parse for StyleMetadata {
         parse_optional_padding(&input, &mut metadata)?;
         parse_optional_color_fg(&input, &mut metadata)?;
         parse_optional_color_bg(&input, &mut metadata)?;
+        parse_optional_color_underline(&input, &mut metadata)?;
         parse_optional_lolcat(&input, &mut metadata)?;
 
         Ok(metadata)
@@ -93,6 +96,7 @@ pub(crate) mod custom_keywords {
     syn::custom_keyword!(padding);
     syn::custom_keyword!(color_fg);
     syn::custom_keyword!(color_bg);
+    syn::custom_keyword!(color_underline);
     syn::custom_keyword!(lolcat);
 }
 
@@ -162,6 +166,18 @@ fn parse_optional_attrib(
                         "italic" => metadata.attrib_vec.push(Attrib::Italic),
                         "dim" => metadata.attrib_vec.push(Attrib::Dim),
                         "underline" => metadata.attrib_vec.push(Attrib::Underline),
+                        "double_underline" => {
+                            metadata.attrib_vec.push(Attrib::DoubleUnderline)
+                        }
+                        "curly_underline" => {
+                            metadata.attrib_vec.push(Attrib::CurlyUnderline)
+                        }
+                        "dotted_underline" => {
+                            metadata.attrib_vec.push(Attrib::DottedUnderline)
+                        }
+                        "dashed_underline" => {
+                            metadata.attrib_vec.push(Attrib::DashedUnderline)
+                        }
                         "reverse" => metadata.attrib_vec.push(Attrib::Reverse),
                         "hidden" => metadata.attrib_vec.push(Attrib::Hidden),
                         "strikethrough" => {
@@ -247,3 +263,24 @@ fn parse_optional_color_bg(
         }
     });
 }
+
+// Parse color_underline (optional).
+fn parse_optional_color_underline(
+    input: &ParseStream,
+    metadata: &mut StyleMetadata,
+) -> SynResult<()> {
+    throws!({
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(custom_keywords::color_underline) {
+            input.parse::<custom_keywords::color_underline>()?;
+            input.parse::<Token![:]>()?;
+            let color_expr = input.parse::<Expr>()?;
+            metadata.color_underline = Some(color_expr);
+            call_if_true!(
+                DEBUG_MAKE_STYLE_MOD,
+                println!("🚀 color_underline: {:#?}", metadata.color_underline)
+            );
+        }
+    });
+}
@@ -22,37 +22,80 @@ use std::{io::{stderr, Write},
 use r3bl_core::StdMutex;
 use r3bl_terminal_async::{Spinner,
                           SpinnerColor,
+                          SpinnerManager,
                           SpinnerStyle,
                           SpinnerTemplate,
                           TerminalAsync,
                           ARTIFICIAL_UI_DELAY,
                           DELAY_MS,
                           DELAY_UNIT};
+use strum::IntoEnumIterator;
 use tokio::{time::Instant, try_join};
 
 #[tokio::main]
 #[allow(clippy::needless_return)]
 pub async fn main() -> miette::Result<()> {
-    println!("-------------> Example with concurrent output: Braille <-------------");
-    example_with_concurrent_output(SpinnerStyle {
-        template: SpinnerTemplate::Braille,
-        color: SpinnerColor::default_color_wheel(),
-    })
-    .await?;
+    // Show a gallery of all the spinners.
+    for template in SpinnerTemplate::iter() {
+        println!(
+            "-------------> Example with concurrent output: {template} <-------------"
+        );
+        example_with_concurrent_output(SpinnerStyle {
+            template,
+            color: SpinnerColor::default_color_wheel(),
+        })
+        .await?;
+    }
 
-    println!("-------------> Example with concurrent output: Block <-------------");
-    example_with_concurrent_output(SpinnerStyle {
-        template: SpinnerTemplate::Block,
-        color: SpinnerColor::default_color_wheel(),
-    })
-    .await?;
+    println!("-------------> Example with a spinner manager <-------------");
+    example_with_spinner_manager().await?;
 
-    println!("-------------> Example with concurrent output: Dots <-------------");
-    example_with_concurrent_output(SpinnerStyle {
-        template: SpinnerTemplate::Dots,
-        color: SpinnerColor::default_color_wheel(),
-    })
-    .await?;
+    Ok(())
+}
+
+/// Each task owns a line of the [SpinnerManager], and they are all shown at once.
+async fn example_with_spinner_manager() -> miette::Result<()> {
+    let terminal_async = TerminalAsync::try_new("$ ").await?;
+    let terminal_async = terminal_async.expect("terminal is not fully interactive");
+
+    let spinner_manager = SpinnerManager::try_new(
+        DELAY_UNIT,
+        Arc::new(StdMutex::new(stderr())),
+        terminal_async.clone_shared_writer(),
+    )
+    .expect("terminal is not fully interactive");
+
+    let mut handles = vec![];
+    for (index, template) in SpinnerTemplate::iter().enumerate() {
+        let spinner_handle = spinner_manager
+            .add(
+                format!("Downloading file {index}"),
+                SpinnerStyle {
+                    template,
+                    color: SpinnerColor::default_color_wheel(),
+                },
+            )
+            .await;
+        handles.push(tokio::spawn(async move {
+            // Each task takes a different amount of time.
+            let total = 10 * (index as u64 + 1);
+            for done in 0..=total {
+                if spinner_handle.is_shutdown() {
+                    spinner_handle.finish(&format!("Cancelled file {index}"));
+                    return;
+                }
+                spinner_handle.set_progress(done, total);
+                tokio::time::sleep(DELAY_UNIT).await;
+            }
+            spinner_handle.finish(&format!("Downloaded file {index}"));
+        }));
+    }
+
+    for handle in handles {
+        let _ = handle.await;
+    }
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
 
     Ok(())
 }
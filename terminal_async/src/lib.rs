@@ -260,6 +260,11 @@
 //! and <kbd>Ctrl+D</kbd> are allowed to make it through, the rest of the keypresses are
 //! ignored.
 //!
+//! The output can also be held (w/out pausing the input) while the user is typing, so
+//! that chatty tasks don't shred the line that is being edited, see
+//! [Readline::set_output_while_typing_policy]. It is printed once the line is submitted (or
+//! once the user goes idle).
+//!
 //! See [Readline] module docs for more implementation details on this.
 //!
 //! ## Input Editing Behavior
@@ -269,12 +274,19 @@
 //!
 //! - Works on all platforms supported by `crossterm`.
 //! - Full Unicode Support (Including Grapheme Clusters).
-//! - Multiline Editing.
+//! - Multiline Editing. Enter adds a new line (w/ a continuation prompt) instead of
+//!   submitting the input until it is complete, see [`InputValidator`].
 //! - In-memory History.
 //! - Left, Right: Move cursor left/right.
-//! - Up, Down: Scroll through input history.
+//! - Up, Down: Move to the previous/next line of a multiline input, or scroll through
+//!   input history.
+//! - Right, End (at the end of the line): Accept the hint, see [`HintProvider`].
+//! - Ctrl-R: Search the input history (press it again for older matches, and Esc to
+//!   go back to the line that was there before the search).
 //! - Ctrl-W: Erase the input from the cursor to the previous whitespace.
 //! - Ctrl-U: Erase the input before the cursor.
+//!     - The erased input is added to a kill ring, so that it can be yanked back (see
+//!       below).
 //! - Ctrl-L: Clear the screen.
 //! - Ctrl-Left / Ctrl-Right: Move to previous/next whitespace.
 //! - Home: Jump to the start of the line.
@@ -283,6 +295,17 @@
 //!     - When the "emacs" feature (on by default) is enabled, Ctrl-E has the same effect.
 //! - Ctrl-C, Ctrl-D: Send an `Eof` event.
 //! - Ctrl-C: Send an `Interrupt` event.
+//! - Emacs keymap (the default, see [`Keymap`]):
+//!     - Ctrl-B / Ctrl-F, Alt-B / Alt-F: Move to the previous/next char, word.
+//!     - Ctrl-K, Alt-D, Alt-Backspace: Erase the input to the end of the line, the next
+//!       word, the previous word.
+//!     - Ctrl-Y: Yank (insert) the last erased input. Alt-Y right after it: Replace it
+//!       w/ the one that was erased before it.
+//!     - Ctrl-T: Transpose the chars around the cursor.
+//!     - Ctrl-_: Undo.
+//! - Vi keymap (see [`Readline::set_keymap()`]): Esc switches from insert mode to normal
+//!   mode, which has motions (`h`, `l`, `w`, `b`, `0`, `$`), operators (`d`, `c`, `y`),
+//!   `x`, `p`, `u` (undo), and more, see [`KeymapState::get_command()`].
 //! - Extensible design based on `crossterm`'s `event-stream` feature.
 //!
 //! # Examples
@@ -336,11 +359,34 @@
 //!
 //! - After receiving a line from the user, if you wish to add it to the history (so that
 //!   the user can retrieve it while editing a later line), call
-//!   [`Readline::add_history_entry()`].
+//!   [`Readline::add_history_entry()`]. To remember the history across runs, call
+//!   [`Readline::set_history_file()`], which loads it from a file (and saves it there
+//!   each time that an entry is added).
+//!
+//! - To read a password (or a token), call [`Readline::read_secret()`] instead, which
+//!   masks the input (or doesn't show it at all), doesn't add it to the history, and
+//!   overwrites it w/ zeros once the returned [`SecretString`] is dropped.
 //!
 //! - Lines written to the associated [`r3bl_core::SharedWriter`] while
 //!   `readline()` is in progress will be output to the screen above the input line.
 //!
+//! - Give each background task its own writer w/
+//!   [`r3bl_core::SharedWriter::clone_with_prefix()`] (or
+//!   [`TerminalAsync::clone_shared_writer_with_prefix()`]), which starts each of its
+//!   lines w/ the name of the task (in color), and only sends complete lines, so that
+//!   the output of many tasks is never interleaved mid-line.
+//!
+//! - To show long output (eg: help text, or logs), call [`Readline::page_output()`] (or
+//!   [`TerminalAsync::page_output()`]), which shows it in a scrollable, searchable pager
+//!   (like `less`) that takes over the screen until the user presses <kbd>q</kbd>, and
+//!   then restores the prompt.
+//!
+//! - To show the progress of a background task, call [`Readline::add_progress_bar()`] (or
+//!   [`TerminalAsync::add_progress_bar()`]), which renders a progress bar (w/ its rate &
+//!   ETA) above the prompt, and returns a [`ProgressBarHandle`] that the task uses to
+//!   update it. The output from the [`r3bl_core::SharedWriter`]s is printed above the
+//!   progress bars, and the user can keep typing while they are shown.
+//!
 //! - When done, call [`crate::manage_shared_writer_output::flush_internal()`] to ensure
 //!   that all lines written to the [`r3bl_core::SharedWriter`] are output.
 //!
@@ -360,6 +406,13 @@
 //! Spinners can also be checked for completion or cancellation by long running tasks, to
 //! ensure that they exit as a response to user cancellation. Take a look at the
 //! `examples/terminal_async.rs` file to get an understanding of how to use this API.
+//!//!
+//! There are a few [`SpinnerTemplate`]s to choose from (dots, braille, blocks, and bars),
+//! which can be colored w/ a [`SpinnerColor`]. To show several spinners at once (eg: one
+//! for each of the tasks that are running concurrently), use a [`SpinnerManager`] instead.
+//! It shows each line above the prompt, w/ an optional progress bar, and each line is
+//! owned by a different task (w/ a [`SpinnerHandle`]). `cargo run --example spinner` shows
+//! all of them.
 //!
 //! The third change is that [`TerminalAsync::try_new()`] now accepts prompts that can
 //! have ANSI escape sequences in them. Here's an example of this.
@@ -465,7 +518,7 @@ pub use readline_impl::*;
 pub use spinner_impl::*;
 
 // External crates.
-use std::{collections::VecDeque, sync::Arc};
+use std::{collections::VecDeque, sync::Arc, time::Duration};
 
 // r3bl-open-core crates.
 use r3bl_core::{StdMutex, SendRawTerminal, SafeRawTerminal};
@@ -473,8 +526,11 @@ use r3bl_core::{StdMutex, SendRawTerminal, SafeRawTerminal};
 // Type aliases.
 pub type SafeLineState = Arc<StdMutex<LineState>>;
 pub type SafeHistory = Arc<StdMutex<History>>;
+pub type SafeHintProvider = Arc<dyn HintProvider>;
+pub type SafeInputValidator = Arc<dyn InputValidator>;
 
 pub type SafeBool = Arc<StdMutex<bool>>;
+pub type SafeSpinnerManagerState = Arc<StdMutex<SpinnerManagerState>>;
 
 pub type PauseBuffer = VecDeque<r3bl_core::Text>;
 pub type SafePauseBuffer = Arc<StdMutex<PauseBuffer>>;
@@ -482,3 +538,8 @@ pub type SafePauseBuffer = Arc<StdMutex<PauseBuffer>>;
 // Constants.
 pub const CHANNEL_CAPACITY: usize = 1_000;
 pub const HISTORY_SIZE_MAX: usize = 1_000;
+pub const CONTINUATION_PROMPT_DEFAULT: &str = "... ";
+pub const SECRET_INPUT_CAPACITY: usize = 1_024;
+pub const KILL_RING_SIZE_MAX: usize = 10;
+pub const UNDO_STACK_SIZE_MAX: usize = 100;
+pub const PROGRESS_BAR_REDRAW_INTERVAL: Duration = Duration::from_millis(50);
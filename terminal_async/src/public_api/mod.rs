@@ -17,8 +17,10 @@
 
 // Attach sources.
 pub mod spinner;
+pub mod spinner_manager;
 pub mod terminal_async;
 
 // Re-export.
 pub use spinner::*;
+pub use spinner_manager::*;
 pub use terminal_async::*;
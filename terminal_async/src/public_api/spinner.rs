@@ -198,7 +198,7 @@ impl Spinner {
     }
 }
 
-fn get_terminal_display_width() -> usize {
+pub(crate) fn get_terminal_display_width() -> usize {
     match terminal::size() {
         Ok((columns, _rows)) => columns as usize,
         Err(_) => 0,
@@ -0,0 +1,401 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{sync::Arc, time::Duration};
+
+use r3bl_ansi_color::{is_fully_uninteractive_terminal,
+                      is_stdout_piped,
+                      StdoutIsPipedResult,
+                      TTYResult};
+use r3bl_core::{LineStateControlSignal, SharedWriter};
+use tokio::time::interval;
+
+use super::spinner::get_terminal_display_width;
+use crate::{spinner_render,
+            SafeBool,
+            SafeRawTerminal,
+            SafeSpinnerManagerState,
+            SpinnerStyle,
+            StdMutex,
+            PROGRESS_BAR_WIDTH};
+
+/// One of the lines that are shown by the [SpinnerManager]. It is updated w/ its
+/// [SpinnerHandle].
+#[derive(Debug, Clone)]
+pub struct SpinnerLine {
+    pub id: usize,
+    pub message: String,
+    pub style: SpinnerStyle,
+    /// Is [Some] for a progress line, ie: `(done, total)`, which is shown as a progress
+    /// bar after the message.
+    pub maybe_progress: Option<(u64, u64)>,
+    /// Printed once (above the active lines) when the line is finished. The line is just
+    /// removed if this is [None].
+    pub maybe_final_message: Option<String>,
+    pub is_finished: bool,
+    count: usize,
+}
+
+impl SpinnerLine {
+    pub fn new(id: usize, message: String, style: SpinnerStyle) -> Self {
+        Self {
+            id,
+            message,
+            style,
+            maybe_progress: None,
+            maybe_final_message: None,
+            is_finished: false,
+            count: 0,
+        }
+    }
+
+    /// Render the next tick of the spinner (and the progress bar).
+    pub fn render_tick(&mut self, display_width: usize) -> String {
+        let message = match self.maybe_progress {
+            Some((done, total)) => format!(
+                "{} {}",
+                self.message,
+                spinner_render::render_progress_bar(done, total, PROGRESS_BAR_WIDTH)
+            ),
+            None => self.message.clone(),
+        };
+        let output = spinner_render::render_tick(
+            &mut self.style,
+            &message,
+            self.count,
+            display_width,
+        );
+        // Increment count to affect the output in the next tick.
+        self.count += 1;
+        output
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SpinnerManagerState {
+    /// In the order that they were added.
+    pub lines: Vec<SpinnerLine>,
+    next_id: usize,
+}
+
+/// Shows several spinners (or progress lines) at once, above the prompt, each of which
+/// is owned by a different task (w/ a [SpinnerHandle]). Like the [crate::Spinner], the
+/// terminal is paused while any of them is active, and it is resumed once they are all
+/// finished.
+///
+/// Once a line is finished, its final message is printed above the active lines (and
+/// stays there). <kbd>Ctrl+C</kbd> and <kbd>Ctrl+D</kbd> shut down all the lines (that
+/// are active), which the tasks can check w/ [SpinnerHandle::is_shutdown].
+pub struct SpinnerManager {
+    pub tick_delay: Duration,
+    pub safe_output_terminal: SafeRawTerminal,
+    pub shared_writer: SharedWriter,
+    pub shutdown_sender: tokio::sync::broadcast::Sender<()>,
+    safe_state: SafeSpinnerManagerState,
+    safe_is_shutdown: SafeBool,
+    /// Is `true` while the task that renders the lines is running. This is held while
+    /// the terminal is paused & resumed, so that the signals for two tasks are never
+    /// interleaved.
+    safe_is_task_running: Arc<tokio::sync::Mutex<bool>>,
+}
+
+impl SpinnerManager {
+    /// Create a new instance of [SpinnerManager]. Nothing is shown until a line is added
+    /// w/ [Self::add].
+    ///
+    /// # Returns
+    /// 1. If the terminal is not fully interactive then it will return [None]. This is
+    ///    the same as [crate::Spinner::try_start].
+    /// 2. Otherwise, it will return a [SpinnerManager] instance.
+    pub fn try_new(
+        tick_delay: Duration,
+        safe_output_terminal: SafeRawTerminal,
+        shared_writer: SharedWriter,
+    ) -> Option<SpinnerManager> {
+        if let StdoutIsPipedResult::StdoutIsPiped = is_stdout_piped() {
+            return None;
+        }
+        if let TTYResult::IsNotInteractive = is_fully_uninteractive_terminal() {
+            return None;
+        }
+
+        // Shutdown broadcast channel.
+        let (shutdown_sender, _) = tokio::sync::broadcast::channel::<()>(1);
+
+        Some(SpinnerManager {
+            tick_delay,
+            safe_output_terminal,
+            shared_writer,
+            shutdown_sender,
+            safe_state: Arc::new(StdMutex::new(SpinnerManagerState::default())),
+            safe_is_shutdown: Arc::new(StdMutex::new(false)),
+            safe_is_task_running: Arc::new(tokio::sync::Mutex::new(false)),
+        })
+    }
+
+    /// Add a line w/ the given `message` & `style` below the other ones, and return the
+    /// [SpinnerHandle] that is used to update it, and to finish it. Pausing the terminal
+    /// (if this is the only active line).
+    pub async fn add(&self, message: String, style: SpinnerStyle) -> SpinnerHandle {
+        let mut is_task_running = self.safe_is_task_running.lock().await;
+
+        let id = {
+            let mut state = self.safe_state.lock().unwrap();
+            let id = state.next_id;
+            state.next_id += 1;
+            state.lines.push(SpinnerLine::new(id, message, style));
+            id
+        };
+
+        if !*is_task_running {
+            *is_task_running = true;
+            *self.safe_is_shutdown.lock().unwrap() = false;
+            self.start_task().await;
+        }
+
+        SpinnerHandle {
+            id,
+            safe_state: self.safe_state.clone(),
+            safe_is_shutdown: self.safe_is_shutdown.clone(),
+        }
+    }
+
+    async fn start_task(&self) {
+        // Tell readline that spinner is active & register the spinner shutdown sender.
+        _ = self
+            .shared_writer
+            .line_state_control_channel_sender
+            .send(LineStateControlSignal::SpinnerActive(
+                self.shutdown_sender.clone(),
+            ))
+            .await;
+
+        // Pause the terminal.
+        let _ = self
+            .shared_writer
+            .line_state_control_channel_sender
+            .send(LineStateControlSignal::Pause)
+            .await;
+
+        let tick_delay = self.tick_delay;
+        let safe_output_terminal = self.safe_output_terminal.clone();
+        let shared_writer = self.shared_writer.clone();
+        let safe_state = self.safe_state.clone();
+        let self_safe_is_shutdown = self.safe_is_shutdown.clone();
+        let safe_is_task_running = self.safe_is_task_running.clone();
+
+        // Keep the channel open (even if this manager is dropped), so that recv doesn't
+        // return right away.
+        let shutdown_sender = self.shutdown_sender.clone();
+        let mut shutdown_receiver = shutdown_sender.subscribe();
+
+        tokio::spawn(async move {
+            let _shutdown_sender = shutdown_sender;
+            let mut interval = interval(tick_delay);
+
+            loop {
+                tokio::select! {
+                    // Poll interval.
+                    // This branch is cancel safe because tick is cancel safe.
+                    _ = interval.tick() => {
+                        if print_tick(&safe_state, &safe_output_terminal) {
+                            continue;
+                        }
+
+                        // All the lines are finished. Check again once the lock is held,
+                        // since a line might have been added in the meantime.
+                        let mut is_task_running = safe_is_task_running.lock().await;
+                        if print_tick(&safe_state, &safe_output_terminal) {
+                            continue;
+                        }
+
+                        // Tell readline that spinner is inactive.
+                        _ = shared_writer
+                            .line_state_control_channel_sender
+                            .send(LineStateControlSignal::SpinnerInactive)
+                            .await;
+
+                        // Resume the terminal.
+                        let _ = shared_writer
+                            .line_state_control_channel_sender
+                            .send(LineStateControlSignal::Resume)
+                            .await;
+
+                        *is_task_running = false;
+                        break;
+                    },
+
+                    // Poll shutdown channel.
+                    // This branch is cancel safe because recv is cancel safe.
+                    _ = shutdown_receiver.recv() => {
+                        // The tasks that own the lines will know that they have been
+                        // shutdown by user interaction, and finish them.
+                        *self_safe_is_shutdown.lock().unwrap() = true;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Print the final messages of the lines that are finished (and remove them), and the
+/// next tick of the active lines. Returns whether there are any active lines left.
+fn print_tick(
+    safe_state: &SafeSpinnerManagerState,
+    safe_output_terminal: &SafeRawTerminal,
+) -> bool {
+    let display_width = get_terminal_display_width();
+    let mut finished_lines = vec![];
+    let mut active_lines = vec![];
+
+    safe_state.lock().unwrap().lines.retain_mut(|line| {
+        if !line.is_finished {
+            active_lines.push(line.render_tick(display_width));
+            return true;
+        }
+        if let Some(final_message) = line.maybe_final_message.as_ref() {
+            finished_lines.push(spinner_render::render_final_tick(
+                &line.style,
+                final_message,
+                display_width,
+            ));
+        }
+        false
+    });
+
+    let _ = spinner_render::print_multi_tick(
+        &finished_lines,
+        &active_lines,
+        &mut (*safe_output_terminal.lock().unwrap()),
+    );
+
+    !active_lines.is_empty()
+}
+
+/// Owned by the task that a line of the [SpinnerManager] is for. The line is removed
+/// when this is dropped (w/out printing a final message, unlike [Self::finish]).
+pub struct SpinnerHandle {
+    pub id: usize,
+    safe_state: SafeSpinnerManagerState,
+    safe_is_shutdown: SafeBool,
+}
+
+impl SpinnerHandle {
+    pub fn set_message(&self, message: String) {
+        self.update(|line| line.message = message);
+    }
+
+    /// Show a progress bar after the message, eg: `[██████░░░░░░]  50%`.
+    pub fn set_progress(&self, done: u64, total: u64) {
+        self.update(|line| line.maybe_progress = Some((done, total)));
+    }
+
+    /// This is meant for the task that owns this line to check if it should shutdown,
+    /// due to the user pressing `Ctrl-C` or `Ctrl-D`.
+    pub fn is_shutdown(&self) -> bool { *self.safe_is_shutdown.lock().unwrap() }
+
+    /// Replace the line w/ the `final_message`, in the next tick.
+    pub fn finish(self, final_message: &str) {
+        self.update(|line| {
+            line.maybe_final_message = Some(final_message.to_string());
+            line.is_finished = true;
+        });
+    }
+
+    fn update(&self, f: impl FnOnce(&mut SpinnerLine)) {
+        let mut state = self.safe_state.lock().unwrap();
+        if let Some(line) = state.lines.iter_mut().find(|it| it.id == self.id) {
+            f(line);
+        }
+    }
+}
+
+impl Drop for SpinnerHandle {
+    fn drop(&mut self) { self.update(|line| line.is_finished = true); }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_test_fixtures::StdoutMock;
+
+    use super::*;
+    use crate::{SpinnerColor, SpinnerTemplate};
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_spinner_manager() {
+        let stdout_mock = StdoutMock::default();
+
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+
+        let (line_sender, mut line_receiver) = tokio::sync::mpsc::channel(1_000);
+        let shared_writer = SharedWriter::new(line_sender);
+
+        let quantum = Duration::from_millis(100);
+
+        let spinner_manager =
+            SpinnerManager::try_new(quantum, safe_output_terminal, shared_writer);
+
+        // This is for CI/CD.
+        if let TTYResult::IsNotInteractive = is_fully_uninteractive_terminal() {
+            return;
+        }
+
+        let spinner_manager = spinner_manager.unwrap();
+        let style = SpinnerStyle {
+            template: SpinnerTemplate::Bars,
+            color: SpinnerColor::None,
+        };
+        let download = spinner_manager
+            .add("download".to_string(), style.clone())
+            .await;
+        let build = spinner_manager.add("build".to_string(), style).await;
+
+        download.set_progress(1, 2);
+        tokio::time::sleep(quantum * 3).await;
+
+        download.finish("downloaded");
+        tokio::time::sleep(quantum * 2).await;
+        assert_eq!(spinner_manager.safe_state.lock().unwrap().lines.len(), 1);
+
+        drop(build);
+        tokio::time::sleep(quantum * 2).await;
+        assert!(spinner_manager.safe_state.lock().unwrap().lines.is_empty());
+        assert!(!*spinner_manager.safe_is_task_running.lock().await);
+
+        let output_buffer_data = stdout_mock.get_copy_of_buffer_as_string_strip_ansi();
+        // println!("{:?}", output_buffer_data);
+        assert!(output_buffer_data.contains(" download [██████████░░░░░░░░░░]  50%\n"));
+        assert!(output_buffer_data.contains(" build\n"));
+        assert!(output_buffer_data.contains("downloaded\n"));
+
+        // The terminal is paused once, and resumed once.
+        let mut line_control_signal_sink = vec![];
+        while let Ok(signal) = line_receiver.try_recv() {
+            line_control_signal_sink.push(signal);
+        }
+        assert_eq!(line_control_signal_sink.len(), 4);
+        assert!(matches!(
+            line_control_signal_sink[0],
+            LineStateControlSignal::SpinnerActive(_)
+        ));
+        assert!(matches!(
+            line_control_signal_sink[3],
+            LineStateControlSignal::Resume
+        ));
+    }
+}
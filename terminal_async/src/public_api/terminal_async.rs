@@ -30,7 +30,7 @@ use r3bl_ansi_color::{is_fully_uninteractive_terminal,
                       TTYResult};
 use r3bl_core::{InputDevice, LineStateControlSignal, OutputDevice, SharedWriter};
 
-use crate::{Readline, ReadlineEvent};
+use crate::{ProgressBarHandle, Readline, ReadlineEvent};
 
 pub struct TerminalAsync {
     pub readline: Readline,
@@ -105,11 +105,35 @@ impl TerminalAsync {
 
     pub fn clone_shared_writer(&self) -> SharedWriter { self.shared_writer.clone() }
 
+    /// Returns a [SharedWriter] for a background task, which starts each of its lines w/
+    /// the `task_name` (in color), and doesn't let them be interleaved mid-line w/ the
+    /// output of other tasks. More info in [SharedWriter::clone_with_prefix].
+    pub fn clone_shared_writer_with_prefix(&self, task_name: &str) -> SharedWriter {
+        self.shared_writer.clone_with_prefix(task_name)
+    }
+
     /// Replacement for [std::io::Stdin::read_line()] (this is async and non blocking).
     pub async fn get_readline_event(&mut self) -> miette::Result<ReadlineEvent> {
         self.readline.readline().fuse().await.into_diagnostic()
     }
 
+    /// Show the `text` in a scrollable, searchable pager (like `less`), until the user
+    /// presses <kbd>q</kbd>. More info in [Readline::page_output].
+    pub async fn page_output(&mut self, text: &str) -> miette::Result<()> {
+        self.readline.page_output(text).await.into_diagnostic()
+    }
+
+    /// Add a progress bar above the prompt. More info in [Readline::add_progress_bar].
+    pub fn add_progress_bar(
+        &self,
+        message: &str,
+        total: u64,
+    ) -> miette::Result<ProgressBarHandle> {
+        self.readline
+            .add_progress_bar(message, total)
+            .into_diagnostic()
+    }
+
     /// Don't change the `content`. Print it as is. This works concurrently and is async
     /// and non blocking. And it is compatible w/ the
     /// [get_readline_event](TerminalAsync::get_readline_event) method.
@@ -0,0 +1,77 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use crate::SafeHistory;
+
+/// Provides the hint (aka ghost text, or autosuggestion) that is shown (dimmed) after the
+/// cursor while the user is typing, like in `fish` or `zsh`. Pressing <kbd>Right</kbd>
+/// or <kbd>End</kbd> (at the end of the line) adds the hint to the line. Set it w/
+/// [crate::Readline::set_hint_provider].
+///
+/// The hint is only shown when the cursor is at the end of the line, and this is called
+/// each time that the line is rendered, so it should be fast.
+pub trait HintProvider: Send + Sync {
+    /// Returns the text to show after the `line` (not including the `line` itself), or
+    /// [None] if there is nothing to suggest.
+    fn get_hint(&self, line: &str) -> Option<String>;
+}
+
+/// Suggests the rest of the newest history entry that starts w/ the line, see
+/// [crate::Readline::enable_history_hints].
+pub struct HistoryHintProvider {
+    pub safe_history: SafeHistory,
+}
+
+impl HintProvider for HistoryHintProvider {
+    fn get_hint(&self, line: &str) -> Option<String> {
+        if line.is_empty() {
+            return None;
+        }
+        let history = self.safe_history.lock().ok()?;
+        history
+            .entries
+            .iter()
+            .find_map(|entry| entry.strip_prefix(line).filter(|rest| !rest.is_empty()))
+            .map(|rest| rest.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{History, StdMutex};
+
+    #[test]
+    fn test_history_hint_provider() {
+        let (mut history, _) = History::new();
+        for entry in ["cargo build", "cargo test", "cargo"] {
+            history.update(Some(entry.into()));
+        }
+        let hint_provider = HistoryHintProvider {
+            safe_history: Arc::new(StdMutex::new(history)),
+        };
+
+        // The newest entry that is longer than the line is suggested.
+        assert_eq!(hint_provider.get_hint("car"), Some("go".to_string()));
+        assert_eq!(hint_provider.get_hint("cargo"), Some(" test".to_string()));
+        assert_eq!(hint_provider.get_hint("cargo b"), Some("uild".to_string()));
+        assert_eq!(hint_provider.get_hint("ls"), None);
+        assert_eq!(hint_provider.get_hint(""), None);
+    }
+}
@@ -15,16 +15,42 @@
  *   limitations under the License.
  */
 
-use std::collections::VecDeque;
+use std::{collections::VecDeque,
+          fs::{self, OpenOptions},
+          io::{self, Write},
+          path::{Path, PathBuf}};
 
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
 use crate::HISTORY_SIZE_MAX;
 
+/// What happens when a line that is already in the [History] is added to it again.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryDedupPolicy {
+    /// All the lines are kept.
+    KeepAll,
+    /// The line isn't added if it is the same as the newest entry (like `ignoredups` in
+    /// bash).
+    #[default]
+    IgnoreConsecutive,
+    /// The older copies of the line are removed, so each line is only in the history once
+    /// (like `erasedups` in bash).
+    EraseOlder,
+}
+
 pub struct History {
     pub entries: VecDeque<String>,
     pub max_size: usize,
     pub sender: UnboundedSender<String>,
+    pub dedup_policy: HistoryDedupPolicy,
+    /// The file that the entries are loaded from, and saved to (each time that one is
+    /// added), see [History::load_from_file].
+    maybe_file_path: Option<PathBuf>,
+    /// How many lines are in the file. Each entry that is added is appended to it, so it
+    /// can have more lines than [History::max_size] (and the duplicates that the
+    /// [HistoryDedupPolicy] removed) until it is compacted, see
+    /// [History::append_to_file].
+    file_line_count: usize,
     current_position: Option<usize>,
 }
 
@@ -36,6 +62,9 @@ impl History {
                 entries: Default::default(),
                 max_size: HISTORY_SIZE_MAX,
                 sender,
+                dedup_policy: Default::default(),
+                maybe_file_path: None,
+                file_line_count: 0,
                 current_position: Default::default(),
             },
             receiver,
@@ -48,22 +77,144 @@ impl History {
     pub fn update(&mut self, maybe_line: Option<String>) {
         // Receive a new line.
         if let Some(line) = maybe_line {
-            // Don't add entry if last entry was same, or line was empty.
-            if self.entries.front() == Some(&line) || line.is_empty() {
+            if !self.add_entry(line) {
                 return;
             }
-            // Add entry to front of history.
-            self.entries.push_front(line);
+            if let Err(error) = self.append_to_file() {
+                tracing::error!("history -> append to file. Error: {error}");
+            }
+        }
+    }
+
+    /// Returns false if the `line` wasn't added, because of the [HistoryDedupPolicy] (or
+    /// because it is empty).
+    fn add_entry(&mut self, line: String) -> bool {
+        // Don't add entry if line was empty.
+        if line.is_empty() {
+            return false;
+        }
+        match self.dedup_policy {
+            HistoryDedupPolicy::KeepAll => {}
+            HistoryDedupPolicy::IgnoreConsecutive => {
+                if self.entries.front() == Some(&line) {
+                    return false;
+                }
+            }
+            HistoryDedupPolicy::EraseOlder => self.entries.retain(|it| *it != line),
+        }
+
+        // Add entry to front of history.
+        self.entries.push_front(line);
+
+        // Reset offset to newest entry.
+        self.current_position = None;
+
+        // Remove the oldest entries, if there are too many.
+        self.entries.truncate(self.max_size);
+        true
+    }
+
+    /// Set the maximum number of entries, and remove the oldest ones (from the file too)
+    /// if there are more than that.
+    pub fn set_max_size(&mut self, max_size: usize) -> io::Result<()> {
+        self.max_size = max_size;
+        if self.entries.len() <= max_size {
+            return Ok(());
+        }
+        self.entries.truncate(max_size);
+        self.current_position = None;
+        self.save_to_file()
+    }
 
-            // Reset offset to newest entry.
-            self.current_position = None;
+    pub fn get_file_path(&self) -> Option<&Path> { self.maybe_file_path.as_deref() }
 
-            // Check if already have enough entries.
-            if self.entries.len() > self.max_size {
-                // Remove oldest entry
-                self.entries.pop_back();
+    /// Load the entries from the file at `path` (one per line, from the oldest to the
+    /// newest), which are added to the existing ones w/ the [HistoryDedupPolicy] &
+    /// [History::max_size]. From now on, the entries are saved to this file each time
+    /// that one is added, so that they are remembered across runs. There is nothing to
+    /// load if the file doesn't exist yet.
+    pub fn load_from_file(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut file_line_count = 0;
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                for line in content.lines() {
+                    self.add_entry(unescape_entry(line));
+                    file_line_count += 1;
+                }
             }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error),
+        }
+        self.maybe_file_path = Some(path.to_path_buf());
+        self.file_line_count = file_line_count;
+        Ok(())
+    }
+
+    /// Append the newest entry to the file that the entries were loaded from, if any.
+    /// Once the file has twice as many lines as [History::max_size], it is compacted
+    /// instead (see [History::save_to_file]), so that it isn't rewritten each time that
+    /// an entry is added to a full history. The extra lines are dropped when it is
+    /// loaded.
+    fn append_to_file(&mut self) -> io::Result<()> {
+        let (Some(path), Some(entry)) = (&self.maybe_file_path, self.entries.front())
+        else {
+            return Ok(());
+        };
+        if self.file_line_count >= self.max_size.saturating_mul(2) {
+            return self.save_to_file();
         }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(format!("{}\n", escape_entry(entry)).as_bytes())?;
+        self.file_line_count += 1;
+        Ok(())
+    }
+
+    /// Save the entries to the file that they were loaded from, if any, replacing what
+    /// is in it. The entries that have more than one line are escaped, so that each
+    /// entry is a single line in the file.
+    ///
+    /// They are written to a temporary file (next to it), which is then renamed to it, so
+    /// that the file isn't left half written if this fails (or if another process reads
+    /// it at the same time).
+    pub fn save_to_file(&mut self) -> io::Result<()> {
+        let Some(ref path) = self.maybe_file_path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut content = String::new();
+        for entry in self.entries.iter().rev() {
+            content.push_str(&escape_entry(entry));
+            content.push('\n');
+        }
+        let temp_path = {
+            let mut it = path.clone().into_os_string();
+            it.push(".tmp");
+            PathBuf::from(it)
+        };
+        fs::write(&temp_path, content)?;
+        if let Err(error) = fs::rename(&temp_path, path) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(error);
+        }
+        self.file_line_count = self.entries.len();
+        Ok(())
+    }
+
+    /// Returns the newest entry (and its index) that contains the `query`, starting at
+    /// `start_index`, see [crate::ReverseSearch].
+    pub fn find_match(&self, query: &str, start_index: usize) -> Option<(usize, &str)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .skip(start_index)
+            .find(|(_, entry)| entry.contains(query))
+            .map(|(index, entry)| (index, entry.as_str()))
     }
 
     // Find next history that matches a given string from an index.
@@ -96,6 +247,29 @@ impl History {
     }
 }
 
+/// Escape the backslashes & new lines in the `entry`, eg: `a\b` followed by a new line
+/// is saved as `a\\b\n`.
+fn escape_entry(entry: &str) -> String {
+    entry.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape_entry(line: &str) -> String {
+    let mut it = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(character) = chars.next() {
+        if character != '\\' {
+            it.push(character);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => it.push('\n'),
+            Some(next_character) => it.push(next_character),
+            None => it.push('\\'),
+        }
+    }
+    it
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,4 +329,102 @@ mod tests {
         assert_eq!(history.search_previous(), Some(""));
         assert_eq!(history.search_previous(), None);
     }
+
+    #[test]
+    fn test_dedup_policy() {
+        let entries = |policy: HistoryDedupPolicy| -> Vec<String> {
+            let (mut history, _) = History::new();
+            history.dedup_policy = policy;
+            for line in ["a", "b", "b", "a"] {
+                history.update(Some(line.into()));
+            }
+            history.entries.into_iter().collect()
+        };
+
+        assert_eq!(
+            entries(HistoryDedupPolicy::KeepAll),
+            vec!["a", "b", "b", "a"]
+        );
+        assert_eq!(
+            entries(HistoryDedupPolicy::IgnoreConsecutive),
+            vec!["a", "b", "a"]
+        );
+        assert_eq!(entries(HistoryDedupPolicy::EraseOlder), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_load_and_save_file() {
+        // Each test run gets its own folder.
+        let temp_dir = std::env::temp_dir().join(format!(
+            "r3bl_terminal_async_history_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&temp_dir);
+        let path = temp_dir.join("history.txt");
+
+        // The file doesn't exist yet, and it is created when an entry is added.
+        let (mut history, _) = History::new();
+        history.load_from_file(&path).unwrap();
+        assert!(history.entries.is_empty());
+        for line in ["one", "two\nlines", "back\\slash"] {
+            history.update(Some(line.into()));
+        }
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "one\ntwo\\nlines\nback\\\\slash\n"
+        );
+
+        // The entries are remembered in the next run, up to the max size.
+        let (mut history, _) = History::new();
+        history.max_size = 2;
+        history.load_from_file(&path).unwrap();
+        assert_eq!(history.entries, vec!["back\\slash", "two\nlines"]);
+        assert_eq!(history.get_file_path(), Some(path.as_path()));
+
+        history.set_max_size(1).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "back\\\\slash\n");
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_is_appended_to_until_it_is_compacted() {
+        // Each test run gets its own folder.
+        let temp_dir = std::env::temp_dir().join(format!(
+            "r3bl_terminal_async_history_compact_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&temp_dir);
+        let path = temp_dir.join("history.txt");
+
+        let (mut history, _) = History::new();
+        history.max_size = 2;
+        history.dedup_policy = HistoryDedupPolicy::EraseOlder;
+        history.load_from_file(&path).unwrap();
+
+        // The older copy of "a" & the oldest entries are only removed from the file once
+        // it is compacted.
+        for line in ["a", "b", "a", "c"] {
+            history.update(Some(line.into()));
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a\nb\na\nc\n");
+
+        // The file has twice as many lines as the max size, so it is compacted.
+        history.update(Some("d".into()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "c\nd\n");
+        assert!(!temp_dir.join("history.txt.tmp").exists());
+
+        // Then the entries are appended to the compacted file.
+        history.update(Some("e".into()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "c\nd\ne\n");
+
+        // The duplicates in a file that wasn't compacted are removed when it is loaded.
+        fs::write(&path, "a\nb\na\n").unwrap();
+        let (mut history, _) = History::new();
+        history.dedup_policy = HistoryDedupPolicy::EraseOlder;
+        history.load_from_file(&path).unwrap();
+        assert_eq!(history.entries, vec!["a", "b"]);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
 }
@@ -0,0 +1,79 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+/// Decides whether the input is complete when the user presses <kbd>Enter</kbd>. If it
+/// isn't, a new line is added to the input instead of submitting it, so that it can span
+/// more than one line (each of which starts w/ the continuation prompt). Set it w/
+/// [crate::Readline::set_input_validator].
+pub trait InputValidator: Send + Sync {
+    fn is_complete(&self, input: &str) -> bool;
+}
+
+/// The input is complete once all of its brackets (`()`, `[]`, `{}`) are closed, and all
+/// of its quotes (`"`, `'`) too. A backslash at the end of the input continues it on the
+/// next line (like in a shell).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BracketInputValidator;
+
+impl InputValidator for BracketInputValidator {
+    fn is_complete(&self, input: &str) -> bool {
+        let mut open_brackets = vec![];
+        let mut maybe_open_quote: Option<char> = None;
+        let mut is_escaped = false;
+
+        for character in input.chars() {
+            if is_escaped {
+                is_escaped = false;
+                continue;
+            }
+            match (maybe_open_quote, character) {
+                (_, '\\') => is_escaped = true,
+                (Some(open_quote), _) if character == open_quote => {
+                    maybe_open_quote = None
+                }
+                (Some(_), _) => {}
+                (None, '"' | '\'') => maybe_open_quote = Some(character),
+                (None, '(' | '[' | '{') => open_brackets.push(character),
+                // An unmatched closing bracket can't be fixed by adding more lines, so
+                // the input is submitted (for the app to report the error).
+                (None, ')' | ']' | '}') => {
+                    open_brackets.pop();
+                }
+                _ => {}
+            }
+        }
+
+        !is_escaped && maybe_open_quote.is_none() && open_brackets.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bracket_input_validator() {
+        let input_validator = BracketInputValidator;
+
+        for input in ["", "ls -la", "f(a, [b])", "echo \"(\"", "a)", "'it\\'s'"] {
+            assert!(input_validator.is_complete(input), "{input}");
+        }
+        for input in ["f(a, [b]", "{\n  x", "echo \"a", "ls \\", "'it\\'s"] {
+            assert!(!input_validator.is_complete(input), "{input}");
+        }
+    }
+}
@@ -0,0 +1,443 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::VecDeque, ops::Range};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::{KILL_RING_SIZE_MAX, UNDO_STACK_SIZE_MAX};
+
+/// The key bindings that are used to edit the line, see [crate::Readline::set_keymap].
+/// The bindings that are listed in the crate docs (eg: <kbd>Ctrl+W</kbd>, or
+/// <kbd>Up</kbd>) work in both of them (in vi, only in [ViMode::Insert] for the ones that
+/// are chars).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Keymap {
+    /// Adds <kbd>Ctrl+B</kbd> / <kbd>Ctrl+F</kbd> & <kbd>Alt+B</kbd> / <kbd>Alt+F</kbd>
+    /// (move by char / word), <kbd>Ctrl+K</kbd> / <kbd>Alt+D</kbd> /
+    /// <kbd>Alt+Backspace</kbd> (kill), <kbd>Ctrl+Y</kbd> / <kbd>Alt+Y</kbd> (yank, and
+    /// cycle through the [KillRing]), <kbd>Ctrl+T</kbd> (transpose chars), and
+    /// <kbd>Ctrl+_</kbd> (undo).
+    #[default]
+    Emacs,
+    /// Starts in [ViMode::Insert], and <kbd>Esc</kbd> switches to [ViMode::Normal], see
+    /// [KeymapState::get_command] for the commands that it has.
+    Vi,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ViMode {
+    /// The keys are typed into the line.
+    #[default]
+    Insert,
+    /// The keys are commands, eg: `dw` kills the word after the cursor.
+    Normal,
+}
+
+/// The part of the line that a kill (or a copy) covers, from the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    CharBefore,
+    CharAfter,
+    WordBefore,
+    WordAfter,
+    ToStart,
+    ToEnd,
+    Line,
+}
+
+/// What the [crate::LineState] does w/ a key (or any other) event, which depends on the
+/// [Keymap], see [KeymapState::get_command].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditCommand {
+    /// Handle the event w/ the bindings that all the keymaps share. Some commands are
+    /// turned into one of these, eg: `h` in vi is handled as <kbd>Left</kbd>.
+    Default(Event),
+    /// Remove the text from the line, and add it to the [KillRing].
+    Kill(Motion),
+    /// Add the text to the [KillRing], w/out removing it from the line.
+    Copy(Motion),
+    /// Insert the newest text in the [KillRing] before (or after) the cursor.
+    Yank {
+        is_after_cursor: bool,
+    },
+    /// Replace the text that was just yanked w/ the next older one in the [KillRing].
+    YankPop,
+    /// Swap the char before the cursor w/ the one after it (or w/ the one before it, at
+    /// the end of the line), and move the cursor forward.
+    TransposeChars,
+    Undo,
+    /// The event is ignored, eg: a key that isn't a command in [ViMode::Normal].
+    Noop,
+}
+
+impl EditCommand {
+    /// Whether this types a char, so that the chars that are typed one after another are
+    /// undone together.
+    pub fn is_typing(&self) -> bool {
+        matches!(
+            self,
+            EditCommand::Default(Event::Key(KeyEvent {
+                code: KeyCode::Char(_),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                ..
+            }))
+        )
+    }
+}
+
+/// The texts that were killed (removed from the line), so that they can be yanked back,
+/// newest first. There are at most [crate::KILL_RING_SIZE_MAX] of them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KillRing {
+    entries: VecDeque<String>,
+}
+
+impl KillRing {
+    pub fn add(&mut self, text: String) {
+        if text.is_empty() {
+            return;
+        }
+        self.entries.push_front(text);
+        self.entries.truncate(KILL_RING_SIZE_MAX);
+    }
+
+    /// Returns the text at the `index`, where `0` is the newest one.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+}
+
+/// The line (and the cursor in it) before it was changed, so that the change can be
+/// undone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoEntry {
+    pub line: String,
+    pub line_cursor_grapheme: usize,
+}
+
+/// The text that was just yanked, so that [EditCommand::YankPop] can replace it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastYank {
+    /// The bytes (in the line) that the text is in.
+    pub range: Range<usize>,
+    /// The index (in the [KillRing]) of the text.
+    pub kill_ring_index: usize,
+}
+
+/// The state of the [Keymap] (and the edits that it does) in the [crate::LineState],
+/// which turns each event into an [EditCommand].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct KeymapState {
+    pub keymap: Keymap,
+    pub vi_mode: ViMode,
+    /// The vi operator (`d`, `c`, or `y`) that is waiting for a motion, eg: the `d` in
+    /// `dw`.
+    maybe_vi_operator: Option<char>,
+    pub kill_ring: KillRing,
+    pub maybe_last_yank: Option<LastYank>,
+    /// Oldest first. There are at most [crate::UNDO_STACK_SIZE_MAX] of them.
+    undo_stack: Vec<UndoEntry>,
+    /// Whether the last change typed a char, see [EditCommand::is_typing].
+    is_typing: bool,
+}
+
+impl KeymapState {
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+        self.reset();
+    }
+
+    /// Called when the line is submitted (or cancelled), so that the next one starts
+    /// fresh (in [ViMode::Insert], w/ nothing to undo). The [KillRing] is kept.
+    pub fn reset(&mut self) {
+        self.vi_mode = ViMode::Insert;
+        self.maybe_vi_operator = None;
+        self.maybe_last_yank = None;
+        self.undo_stack.clear();
+        self.is_typing = false;
+    }
+
+    /// Save the `undo_entry` (the line before the `command`) if the `command` changed the
+    /// `line`. The chars that are typed one after another are undone together.
+    pub fn add_undo_entry(
+        &mut self,
+        undo_entry: UndoEntry,
+        line: &str,
+        command: &EditCommand,
+    ) {
+        let is_typing = command.is_typing();
+        let is_same_change = is_typing && self.is_typing;
+        self.is_typing = is_typing;
+        if undo_entry.line == line || is_same_change {
+            return;
+        }
+        self.undo_stack.push(undo_entry);
+        if self.undo_stack.len() > UNDO_STACK_SIZE_MAX {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    pub fn pop_undo_entry(&mut self) -> Option<UndoEntry> {
+        self.is_typing = false;
+        self.undo_stack.pop()
+    }
+
+    /// Turn the `event` into an [EditCommand], w/ the bindings of the [Keymap].
+    ///
+    /// In vi's [ViMode::Normal]:
+    /// - `h`, `l`, `0`, `$`, `w`, `b`: Move left, right, to the start, to the end, to the
+    ///   next word, to the previous word.
+    /// - `k`, `j`: Scroll through the history.
+    /// - `i`, `a`, `I`, `A`: Switch to [ViMode::Insert] (before the cursor, after it, at
+    ///   the start, at the end).
+    /// - `x`, `X`, `D`: Kill the char under the cursor, the one before it, to the end.
+    /// - `d`, `c`, `y` followed by a motion (`h`, `l`, `w`, `b`, `0`, `$`, or the same
+    ///   key for the whole line): Kill, kill & switch to [ViMode::Insert], copy. `C` &
+    ///   `S` are the same as `c$` & `cc`.
+    /// - `p`, `P`: Yank after, before the cursor.
+    /// - `u`: Undo.
+    /// - All the other chars are ignored, and the other keys (eg: <kbd>Enter</kbd>, or
+    ///   <kbd>Ctrl+C</kbd>) work as usual.
+    pub fn get_command(&mut self, event: Event) -> EditCommand {
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        else {
+            return EditCommand::Default(event);
+        };
+
+        match self.keymap {
+            Keymap::Emacs => {
+                get_emacs_command(code, modifiers).unwrap_or(EditCommand::Default(event))
+            }
+            Keymap::Vi => match self.vi_mode {
+                ViMode::Insert => {
+                    if code == KeyCode::Esc {
+                        // Like in vi, the cursor moves onto the last char that was typed.
+                        self.vi_mode = ViMode::Normal;
+                        return default_command(KeyCode::Left, KeyModifiers::NONE);
+                    }
+                    EditCommand::Default(event)
+                }
+                ViMode::Normal => self.get_vi_normal_command(event, code, modifiers),
+            },
+        }
+    }
+
+    fn get_vi_normal_command(
+        &mut self,
+        event: Event,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> EditCommand {
+        let character = match (modifiers, code) {
+            (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(character)) => {
+                character
+            }
+            (_, KeyCode::Backspace) => {
+                return default_command(KeyCode::Left, KeyModifiers::NONE);
+            }
+            (_, KeyCode::Esc) => {
+                self.maybe_vi_operator = None;
+                return EditCommand::Noop;
+            }
+            _ => return EditCommand::Default(event),
+        };
+
+        if let Some(operator) = self.maybe_vi_operator.take() {
+            return self.get_vi_operator_command(operator, character);
+        }
+
+        match character {
+            'h' => default_command(KeyCode::Left, KeyModifiers::NONE),
+            'l' => default_command(KeyCode::Right, KeyModifiers::NONE),
+            '0' | '^' => default_command(KeyCode::Home, KeyModifiers::NONE),
+            '$' => default_command(KeyCode::End, KeyModifiers::NONE),
+            'w' => default_command(KeyCode::Right, KeyModifiers::CONTROL),
+            'b' => default_command(KeyCode::Left, KeyModifiers::CONTROL),
+            'k' => default_command(KeyCode::Up, KeyModifiers::NONE),
+            'j' => default_command(KeyCode::Down, KeyModifiers::NONE),
+            'i' => self.switch_to_insert(EditCommand::Noop),
+            'a' => {
+                self.switch_to_insert(default_command(KeyCode::Right, KeyModifiers::NONE))
+            }
+            'I' => {
+                self.switch_to_insert(default_command(KeyCode::Home, KeyModifiers::NONE))
+            }
+            'A' => {
+                self.switch_to_insert(default_command(KeyCode::End, KeyModifiers::NONE))
+            }
+            'x' => EditCommand::Kill(Motion::CharAfter),
+            'X' => EditCommand::Kill(Motion::CharBefore),
+            'D' => EditCommand::Kill(Motion::ToEnd),
+            'C' => self.switch_to_insert(EditCommand::Kill(Motion::ToEnd)),
+            'S' => self.switch_to_insert(EditCommand::Kill(Motion::Line)),
+            'd' | 'c' | 'y' => {
+                self.maybe_vi_operator = Some(character);
+                EditCommand::Noop
+            }
+            'p' => EditCommand::Yank {
+                is_after_cursor: true,
+            },
+            'P' => EditCommand::Yank {
+                is_after_cursor: false,
+            },
+            'u' => EditCommand::Undo,
+            _ => EditCommand::Noop,
+        }
+    }
+
+    fn get_vi_operator_command(
+        &mut self,
+        operator: char,
+        character: char,
+    ) -> EditCommand {
+        let motion = match character {
+            _ if character == operator => Motion::Line,
+            'h' => Motion::CharBefore,
+            'l' => Motion::CharAfter,
+            'w' => Motion::WordAfter,
+            'b' => Motion::WordBefore,
+            '0' | '^' => Motion::ToStart,
+            '$' => Motion::ToEnd,
+            _ => return EditCommand::Noop,
+        };
+        match operator {
+            'y' => EditCommand::Copy(motion),
+            'c' => self.switch_to_insert(EditCommand::Kill(motion)),
+            _ => EditCommand::Kill(motion),
+        }
+    }
+
+    fn switch_to_insert(&mut self, command: EditCommand) -> EditCommand {
+        self.vi_mode = ViMode::Insert;
+        command
+    }
+}
+
+fn get_emacs_command(code: KeyCode, modifiers: KeyModifiers) -> Option<EditCommand> {
+    let command = match (modifiers, code) {
+        (KeyModifiers::CONTROL, KeyCode::Char('b')) => {
+            default_command(KeyCode::Left, KeyModifiers::NONE)
+        }
+        (KeyModifiers::CONTROL, KeyCode::Char('f')) => {
+            default_command(KeyCode::Right, KeyModifiers::NONE)
+        }
+        (KeyModifiers::ALT, KeyCode::Char('b')) => {
+            default_command(KeyCode::Left, KeyModifiers::CONTROL)
+        }
+        (KeyModifiers::ALT, KeyCode::Char('f')) => {
+            default_command(KeyCode::Right, KeyModifiers::CONTROL)
+        }
+        (KeyModifiers::CONTROL, KeyCode::Char('k')) => EditCommand::Kill(Motion::ToEnd),
+        (KeyModifiers::ALT, KeyCode::Char('d')) => EditCommand::Kill(Motion::WordAfter),
+        (KeyModifiers::ALT, KeyCode::Backspace) => EditCommand::Kill(Motion::WordBefore),
+        (KeyModifiers::CONTROL, KeyCode::Char('y')) => EditCommand::Yank {
+            is_after_cursor: false,
+        },
+        (KeyModifiers::ALT, KeyCode::Char('y')) => EditCommand::YankPop,
+        (KeyModifiers::CONTROL, KeyCode::Char('t')) => EditCommand::TransposeChars,
+        // Terminals send Ctrl+_ (and Ctrl+/) as Ctrl+7, unless the kitty keyboard
+        // protocol is used.
+        (KeyModifiers::CONTROL, KeyCode::Char('_' | '/' | '7')) => EditCommand::Undo,
+        _ => return None,
+    };
+    Some(command)
+}
+
+fn default_command(code: KeyCode, modifiers: KeyModifiers) -> EditCommand {
+    EditCommand::Default(Event::Key(KeyEvent::new(code, modifiers)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> Event { Event::Key(KeyEvent::new(code, KeyModifiers::NONE)) }
+
+    #[test]
+    fn test_get_command_vi() {
+        let mut keymap_state = KeymapState::default();
+        keymap_state.set_keymap(Keymap::Vi);
+
+        // Keys are typed in insert mode.
+        assert_eq!(
+            keymap_state.get_command(key(KeyCode::Char('d'))),
+            EditCommand::Default(key(KeyCode::Char('d')))
+        );
+
+        // Esc switches to normal mode, where keys are commands.
+        keymap_state.get_command(key(KeyCode::Esc));
+        assert_eq!(keymap_state.vi_mode, ViMode::Normal);
+        assert_eq!(
+            keymap_state.get_command(key(KeyCode::Char('h'))),
+            EditCommand::Default(key(KeyCode::Left))
+        );
+        assert_eq!(
+            keymap_state.get_command(key(KeyCode::Char('d'))),
+            EditCommand::Noop
+        );
+        assert_eq!(
+            keymap_state.get_command(key(KeyCode::Char('w'))),
+            EditCommand::Kill(Motion::WordAfter)
+        );
+        assert_eq!(
+            keymap_state.get_command(key(KeyCode::Char('y'))),
+            EditCommand::Noop
+        );
+        assert_eq!(
+            keymap_state.get_command(key(KeyCode::Char('y'))),
+            EditCommand::Copy(Motion::Line)
+        );
+        assert_eq!(
+            keymap_state.get_command(key(KeyCode::Enter)),
+            EditCommand::Default(key(KeyCode::Enter))
+        );
+
+        // `c` switches back to insert mode.
+        keymap_state.get_command(key(KeyCode::Char('c')));
+        assert_eq!(
+            keymap_state.get_command(key(KeyCode::Char('$'))),
+            EditCommand::Kill(Motion::ToEnd)
+        );
+        assert_eq!(keymap_state.vi_mode, ViMode::Insert);
+    }
+
+    #[test]
+    fn test_kill_ring() {
+        let mut kill_ring = KillRing::default();
+        kill_ring.add("".into());
+        assert!(kill_ring.is_empty());
+
+        for index in 0..KILL_RING_SIZE_MAX + 1 {
+            kill_ring.add(index.to_string());
+        }
+        assert_eq!(kill_ring.len(), KILL_RING_SIZE_MAX);
+        assert_eq!(
+            kill_ring.get(0),
+            Some(KILL_RING_SIZE_MAX.to_string().as_str())
+        );
+    }
+}
@@ -15,17 +15,35 @@
  *   limitations under the License.
  */
 
-use std::io::{self, Write};
+use std::{io::{self, Write},
+          ops::Range,
+          time::{Duration, Instant}};
 
 use crossterm::{cursor,
                 event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+                style::{Attribute, SetAttribute},
                 terminal::{Clear,
                            ClearType::{All, FromCursorDown}},
                 QueueableCommand};
 use r3bl_core::{ok, MemoizedLenMap, StringLength};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{ReadlineError, ReadlineEvent, SafeHistory};
+use crate::{zeroize_string,
+            EditCommand,
+            KeymapState,
+            LastYank,
+            Motion,
+            ProgressBar,
+            ReadlineError,
+            ReadlineEvent,
+            SafeHintProvider,
+            SafeHistory,
+            SafeInputValidator,
+            SecretInputMode,
+            UndoEntry,
+            CONTINUATION_PROMPT_DEFAULT,
+            PROGRESS_BAR_REDRAW_INTERVAL,
+            SECRET_INPUT_CAPACITY};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum LineStateLiveness {
@@ -37,6 +55,51 @@ impl LineStateLiveness {
     pub fn is_paused(&self) -> bool { matches!(self, LineStateLiveness::Paused) }
 }
 
+/// What happens to the output from the [r3bl_core::SharedWriter]s while the user is
+/// typing, see [crate::Readline::set_output_while_typing_policy]. The output that is
+/// held is kept in the [crate::PauseBuffer] (like when the terminal is paused), so that
+/// the line isn't shredded by chatty tasks, and is printed all at once later.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputWhileTypingPolicy {
+    /// The output is printed (above the line) right away.
+    #[default]
+    Print,
+    /// The output is held while the line isn't empty, and printed once it is submitted
+    /// (or cleared).
+    HoldWhileEditing,
+    /// The output is held until no key is pressed for the given duration, or the line is
+    /// submitted.
+    HoldUntilIdle(Duration),
+}
+
+/// The state of a reverse incremental search through the history (<kbd>Ctrl+R</kbd>),
+/// while it is active. The prompt is replaced w/ the query, and the line w/ the newest
+/// entry that contains it:
+/// - Typing refines the query, and <kbd>Ctrl+R</kbd> again cycles to the next older
+///   match.
+/// - <kbd>Esc</kbd> (or <kbd>Ctrl+G</kbd>) aborts the search, and brings back the line
+///   that was there before it started.
+/// - Any other key (eg: <kbd>Enter</kbd>, or <kbd>Left</kbd>) keeps the match in the line,
+///   and is then handled as usual.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReverseSearch {
+    pub query: String,
+    /// The index (in [crate::History::entries]) of the entry in the line.
+    pub maybe_match_index: Option<usize>,
+    /// True if nothing (else) matches the query. The last match stays in the line.
+    pub is_failing: bool,
+    original_prompt: String,
+    original_line: String,
+    original_line_cursor_grapheme: usize,
+}
+
+impl ReverseSearch {
+    pub fn get_prompt(&self) -> String {
+        let failed = if self.is_failing { "failed " } else { "" };
+        format!("({failed}reverse-i-search)`{}': ", self.query)
+    }
+}
+
 /// This struct actually handles the line editing, and rendering. This works hand in hand
 /// with the [crate::Readline] to make sure that the line is rendered correctly, with
 /// pause and resume support.
@@ -55,6 +118,10 @@ pub struct LineState {
 
     pub prompt: String,
 
+    /// The prompt of each line (after the first one) of a multi-line input, see
+    /// [crate::InputValidator].
+    pub continuation_prompt: String,
+
     /// After pressing enter, should we print the line just submitted?
     pub should_print_line_on_enter: bool,
 
@@ -72,6 +139,44 @@ pub struct LineState {
 
     /// Use to memoize the length of strings.
     pub memoized_len_map: MemoizedLenMap,
+
+    /// Is [Some] while the history is being searched w/ <kbd>Ctrl+R</kbd>.
+    pub maybe_reverse_search: Option<ReverseSearch>,
+
+    /// Provides the hint that is shown (dimmed) after the cursor, see
+    /// [crate::HintProvider].
+    pub maybe_hint_provider: Option<SafeHintProvider>,
+
+    /// Decides whether <kbd>Enter</kbd> submits the line, or adds a new line to it, see
+    /// [crate::InputValidator]. The line is always submitted if this is [None].
+    pub maybe_input_validator: Option<SafeInputValidator>,
+
+    /// Whether a secret is being typed, see [crate::Readline::read_secret]. While it is,
+    /// the line is masked (or not shown at all), and the history, the hints, & the input
+    /// validator aren't used.
+    pub maybe_secret_input_mode: Option<SecretInputMode>,
+
+    /// The [crate::Keymap] that is used to edit the line, w/ its kill ring & undo stack.
+    pub keymap_state: KeymapState,
+
+    /// Whether the output is held while the user is typing, see
+    /// [Self::is_holding_output].
+    pub output_while_typing_policy: OutputWhileTypingPolicy,
+
+    /// When the last key was pressed while the line was edited. It is [None] once the line
+    /// is submitted.
+    pub maybe_last_key_press: Option<Instant>,
+
+    /// The progress bars that are rendered above the prompt, see
+    /// [crate::Readline::add_progress_bar].
+    pub progress_bars: Vec<ProgressBar>,
+    next_progress_bar_id: usize,
+    /// The number of progress bars that were rendered last time, which are cleared w/
+    /// the line.
+    rendered_progress_bar_count: u16,
+    /// When the progress bars were rendered last time, so that they aren't rendered more
+    /// often than [PROGRESS_BAR_REDRAW_INTERVAL] while they are updated.
+    maybe_progress_bars_rendered_at: Option<Instant>,
 }
 
 macro_rules! early_return_if_paused {
@@ -95,6 +200,7 @@ impl LineState {
             StringLength::StripAnsi.calculate(prompt.as_str(), &mut memoized_len_map);
         Self {
             prompt,
+            continuation_prompt: CONTINUATION_PROMPT_DEFAULT.to_string(),
             last_line_completed: true,
             term_size,
             current_column,
@@ -106,6 +212,17 @@ impl LineState {
             last_line_length: 0,
             is_paused: LineStateLiveness::NotPaused,
             memoized_len_map,
+            maybe_reverse_search: None,
+            maybe_hint_provider: None,
+            maybe_input_validator: None,
+            maybe_secret_input_mode: None,
+            keymap_state: Default::default(),
+            output_while_typing_policy: Default::default(),
+            maybe_last_key_press: None,
+            progress_bars: vec![],
+            next_progress_bar_id: 0,
+            rendered_progress_bar_count: 0,
+            maybe_progress_bars_rendered_at: None,
         }
     }
 
@@ -124,6 +241,14 @@ impl LineState {
         is_paused: LineStateLiveness,
         term: &mut dyn Write,
     ) -> io::Result<()> {
+        // Clear the progress bars, so that whatever is shown while paused (eg: a spinner)
+        // starts where they were. They are rendered again once resumed.
+        if is_paused.is_paused() && self.rendered_progress_bar_count > 0 {
+            self.clear(term)?;
+            self.rendered_progress_bar_count = 0;
+            term.flush()?;
+        }
+
         // Set the current value.
         self.is_paused = is_paused;
 
@@ -174,14 +299,126 @@ impl LineState {
         let (pos, str) = self.current_grapheme().unwrap_or((0, ""));
         let pos = pos + str.len();
 
+        self.current_column = self.calc_offset(pos);
+
+        ok!()
+    }
+
+    /// Returns the number of cells from the start of the prompt to the byte at `pos` in
+    /// the line (w/ the rows that the line wraps to). A new line in the line skips to the
+    /// start of the next row, which has the continuation prompt.
+    fn calc_offset(&mut self, pos: usize) -> u16 {
+        let width = self.term_size.0.max(1);
         let prompt_len =
             StringLength::StripAnsi.calculate(&self.prompt, &mut self.memoized_len_map);
+        let continuation_prompt_len = StringLength::StripAnsi
+            .calculate(&self.continuation_prompt, &mut self.memoized_len_map);
 
-        let line_len = StringLength::Unicode
-            .calculate(&self.line[0..pos], &mut self.memoized_len_map);
+        match self.maybe_secret_input_mode {
+            Some(SecretInputMode::NoEcho) => return prompt_len,
+            Some(SecretInputMode::Mask(_)) => {
+                return prompt_len + self.line[0..pos].graphemes(true).count() as u16;
+            }
+            None => {}
+        }
 
-        self.current_column = prompt_len + line_len;
+        let mut offset = prompt_len;
+        let mut row_start = 0;
+        for (index, segment) in self.line[0..pos].split('\n').enumerate() {
+            if index > 0 {
+                // A row that is filled exactly has already wrapped to the next one.
+                offset = if offset > row_start && offset % width == 0 {
+                    offset
+                } else {
+                    (offset / width + 1) * width
+                };
+                row_start = offset;
+                offset += continuation_prompt_len;
+            }
+            offset +=
+                StringLength::Unicode.calculate(segment, &mut self.memoized_len_map);
+        }
+        offset
+    }
 
+    /// Returns the row (in the line, whose rows are separated by new lines) & the column
+    /// (in graphemes) of the cursor.
+    fn get_cursor_row_and_col(&self) -> (usize, usize) {
+        let mut row = 0;
+        let mut col = 0;
+        for grapheme in self.line.graphemes(true).take(self.line_cursor_grapheme) {
+            if grapheme == "\n" {
+                row += 1;
+                col = 0;
+            } else {
+                col += 1;
+            }
+        }
+        (row, col)
+    }
+
+    /// Move the cursor to the `col` (or the end) of the `row` in the line. Returns false
+    /// (and the cursor doesn't move) if there is no such row.
+    fn move_cursor_to_row(
+        &mut self,
+        row: usize,
+        col: usize,
+        term: &mut dyn Write,
+    ) -> io::Result<bool> {
+        let row_lens: Vec<usize> = self
+            .line
+            .split('\n')
+            .map(|it| it.graphemes(true).count())
+            .collect();
+        let Some(row_len) = row_lens.get(row) else {
+            return Ok(false);
+        };
+        // Each of the rows before this one ends w/ a new line.
+        let row_start: usize = row_lens[..row].iter().map(|it| it + 1).sum();
+
+        self.reset_cursor(term)?;
+        self.line_cursor_grapheme = row_start + col.min(*row_len);
+        self.move_cursor(0)?;
+        self.set_cursor(term)?;
+        term.flush()?;
+        Ok(true)
+    }
+
+    /// Returns the line as it is shown, w/ the continuation prompt at the start of each
+    /// row after the first one, where each row starts w/ the `new_line`. A secret is
+    /// masked (or not shown at all).
+    fn get_displayed_line(&self, new_line: &str) -> String {
+        match self.maybe_secret_input_mode {
+            Some(SecretInputMode::NoEcho) => String::new(),
+            Some(SecretInputMode::Mask(mask)) => std::iter::repeat(mask)
+                .take(self.line.graphemes(true).count())
+                .collect(),
+            None => self
+                .line
+                .replace('\n', &format!("{new_line}{}", self.continuation_prompt)),
+        }
+    }
+
+    /// Start (or stop, if `maybe_secret_input_mode` is [None]) typing a secret. The line
+    /// is overwritten w/ zeros (see [crate::zeroize_string]) either way, so that what is
+    /// left of the secret (eg: after <kbd>Ctrl+C</kbd>) doesn't stay in memory.
+    pub fn set_secret_input_mode(
+        &mut self,
+        maybe_secret_input_mode: Option<SecretInputMode>,
+        term: &mut dyn Write,
+    ) -> io::Result<()> {
+        self.clear(term)?;
+        zeroize_string(&mut self.line);
+        self.cluster_buffer.clear();
+        self.line_cursor_grapheme = 0;
+        self.maybe_secret_input_mode = maybe_secret_input_mode;
+        if maybe_secret_input_mode.is_some() {
+            // So that the line isn't moved to a bigger allocation (leaving a copy of the
+            // secret behind) while it is typed.
+            self.line.reserve(SECRET_INPUT_CAPACITY);
+        }
+        self.move_cursor(0)?;
+        self.render_and_flush(term)?;
         ok!()
     }
 
@@ -203,6 +440,195 @@ impl LineState {
             .last()
     }
 
+    /// Whether the output from the [r3bl_core::SharedWriter]s is held (instead of printed),
+    /// since the user is typing, see [OutputWhileTypingPolicy].
+    pub fn is_holding_output(&self) -> bool {
+        match self.output_while_typing_policy {
+            OutputWhileTypingPolicy::Print => false,
+            OutputWhileTypingPolicy::HoldWhileEditing => !self.line.is_empty(),
+            OutputWhileTypingPolicy::HoldUntilIdle(idle_duration) => self
+                .maybe_last_key_press
+                .is_some_and(|it| it.elapsed() < idle_duration),
+        }
+    }
+
+    /// Returns when the user goes idle (and the output that is held can be printed), w/
+    /// [OutputWhileTypingPolicy::HoldUntilIdle].
+    pub fn get_idle_deadline(&self) -> Option<Instant> {
+        match self.output_while_typing_policy {
+            OutputWhileTypingPolicy::HoldUntilIdle(idle_duration) => {
+                self.maybe_last_key_press.map(|it| it + idle_duration)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the byte (in the line) that the cursor is at.
+    fn get_cursor_pos(&self) -> usize {
+        self.current_grapheme()
+            .map_or(0, |(pos, str)| pos + str.len())
+    }
+
+    /// Move the cursor to the byte at `pos` in the line.
+    fn move_cursor_to_pos(&mut self, pos: usize) -> io::Result<()> {
+        self.line_cursor_grapheme = self.line[0..pos].graphemes(true).count();
+        self.move_cursor(0)
+    }
+
+    /// Returns the byte (in the line) that the word before the cursor starts at.
+    fn get_word_start_before_cursor(&self) -> usize {
+        let count = self.line.graphemes(true).count();
+        let skip_count = count - self.line_cursor_grapheme;
+        self.line
+            .grapheme_indices(true)
+            .rev()
+            .skip(skip_count)
+            .skip_while(|(_, str)| *str == " ")
+            .find_map(|(pos, str)| if str == " " { Some(pos + 1) } else { None })
+            .unwrap_or(0)
+    }
+
+    /// Returns the byte (in the line) right after the word after the cursor.
+    fn get_word_end_after_cursor(&self) -> usize {
+        self.line
+            .grapheme_indices(true)
+            .skip(self.line_cursor_grapheme)
+            .skip_while(|(_, str)| *str == " ")
+            .find(|(_, str)| *str == " ")
+            .map_or(self.line.len(), |(pos, _)| pos)
+    }
+
+    /// Returns the bytes (in the line) that the `motion` covers from the cursor.
+    fn get_motion_range(&self, motion: Motion) -> Range<usize> {
+        let cursor_pos = self.get_cursor_pos();
+        match motion {
+            Motion::CharBefore => {
+                self.current_grapheme().map_or(cursor_pos, |(pos, _)| pos)..cursor_pos
+            }
+            Motion::CharAfter => {
+                cursor_pos
+                    ..self
+                        .next_grapheme()
+                        .map_or(cursor_pos, |(pos, str)| pos + str.len())
+            }
+            Motion::WordBefore => self.get_word_start_before_cursor()..cursor_pos,
+            Motion::WordAfter => cursor_pos..self.get_word_end_after_cursor(),
+            Motion::ToStart => 0..cursor_pos,
+            Motion::ToEnd => cursor_pos..self.line.len(),
+            Motion::Line => 0..self.line.len(),
+        }
+    }
+
+    /// Remove the `range` (of bytes) from the line, and add it to the [crate::KillRing],
+    /// so that it can be yanked back. The cursor moves to the start of the range.
+    fn kill(&mut self, range: Range<usize>, term: &mut dyn Write) -> io::Result<()> {
+        if range.is_empty() {
+            return ok!();
+        }
+
+        self.clear(term)?;
+        let mut killed: String = self.line.drain(range.clone()).collect();
+        if self.maybe_secret_input_mode.is_none() {
+            self.keymap_state.kill_ring.add(killed);
+        } else {
+            zeroize_string(&mut killed);
+        }
+        self.move_cursor_to_pos(range.start)?;
+        self.render_and_flush(term)?;
+
+        ok!()
+    }
+
+    /// Insert the newest text in the [crate::KillRing] before (or after) the cursor, and
+    /// move the cursor after it.
+    fn yank(&mut self, is_after_cursor: bool, term: &mut dyn Write) -> io::Result<()> {
+        let Some(text) = self.keymap_state.kill_ring.get(0).map(str::to_string) else {
+            return ok!();
+        };
+
+        self.clear(term)?;
+        if is_after_cursor {
+            self.move_cursor(1)?;
+        }
+        let pos = self.get_cursor_pos();
+        self.line.insert_str(pos, &text);
+        self.move_cursor_to_pos(pos + text.len())?;
+        self.keymap_state.maybe_last_yank = Some(LastYank {
+            range: pos..pos + text.len(),
+            kill_ring_index: 0,
+        });
+        self.render_and_flush(term)?;
+
+        ok!()
+    }
+
+    /// Replace the text that was just yanked w/ the next older one in the
+    /// [crate::KillRing] (the newest one comes after the oldest one).
+    fn yank_pop(&mut self, term: &mut dyn Write) -> io::Result<()> {
+        let Some(last_yank) = self.keymap_state.maybe_last_yank.take() else {
+            return ok!();
+        };
+        let kill_ring_index =
+            (last_yank.kill_ring_index + 1) % self.keymap_state.kill_ring.len();
+        let Some(text) = self
+            .keymap_state
+            .kill_ring
+            .get(kill_ring_index)
+            .map(str::to_string)
+        else {
+            return ok!();
+        };
+
+        self.clear(term)?;
+        self.line.replace_range(last_yank.range.clone(), &text);
+        let range = last_yank.range.start..last_yank.range.start + text.len();
+        self.move_cursor_to_pos(range.end)?;
+        self.keymap_state.maybe_last_yank = Some(LastYank {
+            range,
+            kill_ring_index,
+        });
+        self.render_and_flush(term)?;
+
+        ok!()
+    }
+
+    /// Swap the char before the cursor w/ the one after it (or w/ the one before it, at
+    /// the end of the line), and move the cursor after them.
+    fn transpose_chars(&mut self, term: &mut dyn Write) -> io::Result<()> {
+        let graphemes: Vec<(usize, &str)> = self.line.grapheme_indices(true).collect();
+        if graphemes.len() < 2 || self.line_cursor_grapheme == 0 {
+            return ok!();
+        }
+        let index = self.line_cursor_grapheme.min(graphemes.len() - 1);
+        let (start, before) = graphemes[index - 1];
+        let (after_pos, after) = graphemes[index];
+        let range = start..after_pos + after.len();
+        let swapped = format!("{after}{before}");
+
+        self.clear(term)?;
+        self.line.replace_range(range, &swapped);
+        self.line_cursor_grapheme = index + 1;
+        self.move_cursor(0)?;
+        self.render_and_flush(term)?;
+
+        ok!()
+    }
+
+    /// Bring back the line (and the cursor in it) from before the last change.
+    fn undo(&mut self, term: &mut dyn Write) -> io::Result<()> {
+        let Some(undo_entry) = self.keymap_state.pop_undo_entry() else {
+            return ok!();
+        };
+
+        self.clear(term)?;
+        self.line = undo_entry.line;
+        self.line_cursor_grapheme = undo_entry.line_cursor_grapheme;
+        self.move_cursor(0)?;
+        self.render_and_flush(term)?;
+
+        ok!()
+    }
+
     fn reset_cursor(&self, term: &mut dyn Write) -> io::Result<()> {
         self.move_to_beginning(term, self.current_column)
     }
@@ -216,25 +642,81 @@ impl LineState {
         early_return_if_paused!(self @Unit);
 
         self.move_to_beginning(term, self.current_column)?;
+        // The progress bars are above the prompt.
+        if self.rendered_progress_bar_count > 0 {
+            term.queue(cursor::MoveUp(self.rendered_progress_bar_count))?;
+        }
         term.queue(Clear(FromCursorDown))?;
 
         ok!()
     }
 
-    /// Render line (prompt + line) and flush.
+    /// Returns the hint to show after the line, if the cursor is at the end of it (and
+    /// the history isn't being searched). Only the first line of the hint is shown.
+    fn get_hint(&self) -> Option<String> {
+        let hint_provider = self.maybe_hint_provider.as_ref()?;
+        if self.maybe_reverse_search.is_some()
+            || self.maybe_secret_input_mode.is_some()
+            || self.line_cursor_grapheme != self.line.graphemes(true).count()
+        {
+            return None;
+        }
+        let hint = hint_provider.get_hint(&self.line)?;
+        let hint: String = hint
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect();
+        (!hint.is_empty()).then_some(hint)
+    }
+
+    /// Add the hint (if any) to the end of the line. Returns false if there is no hint.
+    fn accept_hint(&mut self, term: &mut dyn Write) -> io::Result<bool> {
+        let Some(hint) = self.get_hint() else {
+            return Ok(false);
+        };
+        self.clear(term)?;
+        self.line.push_str(&hint);
+        self.move_cursor(100000)?;
+        self.render_and_flush(term)?;
+        Ok(true)
+    }
+
+    /// Render line (prompt + line + hint) and flush.
     pub fn render_and_flush(&mut self, term: &mut dyn Write) -> io::Result<()> {
         early_return_if_paused!(self @Unit);
 
-        let output = format!("{}{}", self.prompt, self.line);
+        // In raw mode, a new line doesn't move the cursor to the start of the next row.
+        for progress_bar in &self.progress_bars {
+            write!(term, "{}\r\n", progress_bar.render(self.term_size.0))?;
+        }
+        self.rendered_progress_bar_count = self.progress_bars.len() as u16;
+        if !self.progress_bars.is_empty() {
+            self.maybe_progress_bars_rendered_at = Some(Instant::now());
+        }
+
+        let output = format!("{}{}", self.prompt, self.get_displayed_line("\r\n"));
         write!(term, "{}", output)?;
 
-        let prompt_len =
-            StringLength::StripAnsi.calculate(&self.prompt, &mut self.memoized_len_map);
+        let maybe_hint = self.get_hint();
+        if let Some(ref hint) = maybe_hint {
+            term.queue(SetAttribute(Attribute::Dim))?;
+            write!(term, "{}", hint)?;
+            term.queue(SetAttribute(Attribute::NormalIntensity))?;
+        }
 
-        let line_len =
-            StringLength::Unicode.calculate(&self.line, &mut self.memoized_len_map);
+        let line_len = self.calc_offset(self.line.len());
 
-        let total_line_len = prompt_len + line_len;
+        let hint_len = match maybe_hint {
+            Some(ref hint) => {
+                StringLength::Unicode.calculate(hint, &mut self.memoized_len_map)
+            }
+            None => 0,
+        };
+
+        let total_line_len = line_len + hint_len;
 
         self.move_to_beginning(term, total_line_len)?;
         self.move_from_beginning(term, self.current_column)?;
@@ -296,6 +778,75 @@ impl LineState {
         ok!()
     }
 
+    /// Add a progress bar below the other ones (above the prompt), and render it. Returns
+    /// its id.
+    pub fn add_progress_bar(
+        &mut self,
+        message: &str,
+        total: u64,
+        term: &mut dyn Write,
+    ) -> io::Result<usize> {
+        let id = self.next_progress_bar_id;
+        self.next_progress_bar_id += 1;
+
+        self.clear(term)?;
+        self.progress_bars
+            .push(ProgressBar::new(id, message.to_string(), total));
+        self.render_and_flush(term)?;
+
+        Ok(id)
+    }
+
+    /// Update the progress bar w/ the given `id`. It is rendered right away if it is
+    /// complete, otherwise not more often than [PROGRESS_BAR_REDRAW_INTERVAL], so that
+    /// the line doesn't flicker when the progress bars are updated often.
+    pub fn update_progress_bar(
+        &mut self,
+        id: usize,
+        f: impl FnOnce(&mut ProgressBar),
+        term: &mut dyn Write,
+    ) -> io::Result<()> {
+        let Some(progress_bar) = self.progress_bars.iter_mut().find(|it| it.id == id)
+        else {
+            return ok!();
+        };
+        f(progress_bar);
+
+        let is_complete = progress_bar.is_complete();
+        let is_due = match self.maybe_progress_bars_rendered_at {
+            Some(rendered_at) => rendered_at.elapsed() >= PROGRESS_BAR_REDRAW_INTERVAL,
+            None => true,
+        };
+        if is_complete || is_due {
+            self.clear_and_render_and_flush(term)?;
+        }
+
+        ok!()
+    }
+
+    /// Remove the progress bar w/ the given `id`, and print the `maybe_final_message`
+    /// (if any) above the prompt.
+    pub fn remove_progress_bar(
+        &mut self,
+        id: usize,
+        maybe_final_message: Option<&str>,
+        term: &mut dyn Write,
+    ) -> Result<(), ReadlineError> {
+        if !self.progress_bars.iter().any(|it| it.id == id) {
+            return ok!();
+        }
+        self.progress_bars.retain(|it| it.id != id);
+
+        match maybe_final_message {
+            Some(final_message) => {
+                self.print_and_flush(&format!("{final_message}\n"), term)?
+            }
+            None => self.clear_and_render_and_flush(term)?,
+        }
+
+        ok!()
+    }
+
     pub fn print_and_flush(
         &mut self,
         string: &str,
@@ -313,6 +864,12 @@ impl LineState {
         prompt: &str,
         term: &mut dyn Write,
     ) -> Result<(), ReadlineError> {
+        // The new prompt is shown once the search is done.
+        if let Some(ref mut reverse_search) = self.maybe_reverse_search {
+            reverse_search.original_prompt = prompt.to_string();
+            return ok!();
+        }
+
         self.clear(term)?;
         self.prompt.clear();
         self.prompt.push_str(prompt);
@@ -344,12 +901,236 @@ impl LineState {
         ok!()
     }
 
+    /// Clear the line, apply the `update` (to the search), and render the line w/ the
+    /// prompt of the search.
+    fn update_reverse_search(
+        &mut self,
+        term: &mut dyn Write,
+        update: impl FnOnce(&mut Self),
+    ) -> io::Result<()> {
+        self.clear(term)?;
+        update(self);
+        if let Some(ref reverse_search) = self.maybe_reverse_search {
+            self.prompt = reverse_search.get_prompt();
+        }
+        self.move_cursor(100000)?;
+        self.render_and_flush(term)
+    }
+
+    /// Put the newest entry that contains the query (starting at `start_index`) in the
+    /// line. There is nothing to search for if the query is empty.
+    fn search_history(&mut self, start_index: usize, safe_history: &SafeHistory) {
+        let Some(ref mut reverse_search) = self.maybe_reverse_search else {
+            return;
+        };
+        if reverse_search.query.is_empty() {
+            reverse_search.is_failing = false;
+            return;
+        }
+        let history = safe_history.lock().unwrap();
+        match history.find_match(&reverse_search.query, start_index) {
+            Some((index, entry)) => {
+                reverse_search.maybe_match_index = Some(index);
+                reverse_search.is_failing = false;
+                self.line = entry.to_string();
+            }
+            None => reverse_search.is_failing = true,
+        }
+    }
+
+    /// Restore the prompt, and keep the match in the line (or bring back the line from
+    /// before the search if it is `aborted`).
+    fn finish_reverse_search(
+        &mut self,
+        is_aborted: bool,
+        term: &mut dyn Write,
+    ) -> io::Result<()> {
+        let Some(reverse_search) = self.maybe_reverse_search.take() else {
+            return ok!();
+        };
+        self.clear(term)?;
+        self.prompt = reverse_search.original_prompt;
+        if is_aborted {
+            self.line = reverse_search.original_line;
+            self.line_cursor_grapheme = reverse_search.original_line_cursor_grapheme;
+            self.move_cursor(0)?;
+        } else {
+            self.move_cursor(100000)?;
+        }
+        self.render_and_flush(term)
+    }
+
+    /// Returns true if the `event` was consumed by the search (while it is active). The
+    /// events that end the search (other than <kbd>Esc</kbd>) aren't consumed, so that
+    /// they are handled as usual after it.
+    fn apply_event_to_reverse_search(
+        &mut self,
+        event: &Event,
+        term: &mut dyn Write,
+        safe_history: &SafeHistory,
+    ) -> io::Result<bool> {
+        let Some(ref reverse_search) = self.maybe_reverse_search else {
+            return Ok(false);
+        };
+        let maybe_match_index = reverse_search.maybe_match_index;
+
+        match event {
+            // Cycle to the next older match.
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                let start_index = maybe_match_index.map_or(0, |it| it + 1);
+                self.update_reverse_search(term, |it| {
+                    it.search_history(start_index, safe_history)
+                })?;
+            }
+            // Abort.
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('g'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            })
+            | Event::Key(KeyEvent {
+                code: KeyCode::Esc,
+                kind: KeyEventKind::Press,
+                ..
+            }) => self.finish_reverse_search(true, term)?,
+            // Remove a char from the query, and search again from the newest entry.
+            Event::Key(KeyEvent {
+                code: KeyCode::Backspace,
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                self.update_reverse_search(term, |it| {
+                    if let Some(ref mut reverse_search) = it.maybe_reverse_search {
+                        reverse_search.query.pop();
+                        reverse_search.maybe_match_index = None;
+                    }
+                    it.search_history(0, safe_history);
+                })?;
+            }
+            // Add to the query, and keep the current match if it still matches.
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(character),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                self.update_reverse_search(term, |it| {
+                    if let Some(ref mut reverse_search) = it.maybe_reverse_search {
+                        reverse_search.query.push(*character);
+                    }
+                    it.search_history(maybe_match_index.unwrap_or(0), safe_history);
+                })?;
+            }
+            Event::Key(KeyEvent {
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                self.finish_reverse_search(false, term)?;
+                return Ok(false);
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
     pub fn apply_event_and_render(
         &mut self,
         event: Event,
         term: &mut dyn Write,
         safe_history: SafeHistory,
     ) -> Result<Option<ReadlineEvent>, ReadlineError> {
+        if self.maybe_reverse_search.is_some() && !self.is_paused.is_paused() {
+            if self.apply_event_to_reverse_search(&event, term, &safe_history)? {
+                return Ok(None);
+            }
+        }
+
+        if let Event::Key(_) = event {
+            self.maybe_last_key_press = Some(Instant::now());
+        }
+
+        // The keymap turns the event into a command, see [crate::Keymap].
+        let command = if self.is_paused.is_paused() {
+            EditCommand::Default(event)
+        } else {
+            self.keymap_state.get_command(event)
+        };
+        if command != EditCommand::YankPop {
+            self.keymap_state.maybe_last_yank = None;
+        }
+
+        // The line isn't copied while a secret is typed.
+        let maybe_undo_entry =
+            self.maybe_secret_input_mode.is_none().then(|| UndoEntry {
+                line: self.line.clone(),
+                line_cursor_grapheme: self.line_cursor_grapheme,
+            });
+        let is_undo = command == EditCommand::Undo;
+
+        let result = self.apply_command_and_render(command.clone(), term, safe_history);
+
+        match result {
+            Ok(None) | Ok(Some(ReadlineEvent::Resized)) => {
+                if let Some(undo_entry) = maybe_undo_entry {
+                    if !is_undo {
+                        self.keymap_state
+                            .add_undo_entry(undo_entry, &self.line, &command);
+                    }
+                }
+            }
+            // The line was submitted (or cancelled).
+            Ok(Some(_)) => {
+                self.keymap_state.reset();
+                self.maybe_last_key_press = None;
+            }
+            Err(_) => {}
+        }
+
+        result
+    }
+
+    fn apply_command_and_render(
+        &mut self,
+        command: EditCommand,
+        term: &mut dyn Write,
+        safe_history: SafeHistory,
+    ) -> Result<Option<ReadlineEvent>, ReadlineError> {
+        let event = match command {
+            EditCommand::Default(event) => event,
+            EditCommand::Noop => return Ok(None),
+            command => {
+                early_return_if_paused!(self @None);
+
+                match command {
+                    EditCommand::Kill(motion) => {
+                        self.kill(self.get_motion_range(motion), term)?
+                    }
+                    EditCommand::Copy(motion) => {
+                        if self.maybe_secret_input_mode.is_none() {
+                            let text =
+                                self.line[self.get_motion_range(motion)].to_string();
+                            self.keymap_state.kill_ring.add(text);
+                        }
+                    }
+                    EditCommand::Yank { is_after_cursor } => {
+                        self.yank(is_after_cursor, term)?
+                    }
+                    EditCommand::YankPop => self.yank_pop(term)?,
+                    EditCommand::TransposeChars => self.transpose_chars(term)?,
+                    EditCommand::Undo => self.undo(term)?,
+                    EditCommand::Default(_) | EditCommand::Noop => {}
+                }
+                return Ok(None);
+            }
+        };
+
         match event {
             // Control Keys
             Event::Key(KeyEvent {
@@ -368,13 +1149,33 @@ impl LineState {
                     if self.should_print_line_on_control_c && !self.is_paused.is_paused()
                     {
                         self.print_and_flush(
-                            &format!("{}{}", self.prompt, self.line),
+                            &format!("{}{}", self.prompt, self.get_displayed_line("\n")),
                             term,
                         )?;
                     }
                     self.exit(term)?;
                     return Ok(Some(ReadlineEvent::Interrupted));
                 }
+                // Search the history
+                KeyCode::Char('r') => {
+                    early_return_if_paused!(self @None);
+
+                    if self.maybe_secret_input_mode.is_some() {
+                        return Ok(None);
+                    }
+
+                    let reverse_search = ReverseSearch {
+                        query: String::new(),
+                        maybe_match_index: None,
+                        is_failing: false,
+                        original_prompt: self.prompt.clone(),
+                        original_line: self.line.clone(),
+                        original_line_cursor_grapheme: self.line_cursor_grapheme,
+                    };
+                    self.update_reverse_search(term, |it| {
+                        it.maybe_reverse_search = Some(reverse_search)
+                    })?;
+                }
                 // Clear all
                 KeyCode::Char('l') => {
                     early_return_if_paused!(self @None);
@@ -386,43 +1187,13 @@ impl LineState {
                 KeyCode::Char('u') => {
                     early_return_if_paused!(self @None);
 
-                    if let Some((pos, str)) = self.current_grapheme() {
-                        let pos = pos + str.len();
-                        self.line.drain(0..pos);
-                        self.move_cursor(-100000)?;
-                        self.clear_and_render_and_flush(term)?;
-                    }
+                    self.kill(self.get_motion_range(Motion::ToStart), term)?;
                 }
                 // Clear last word
                 KeyCode::Char('w') => {
                     early_return_if_paused!(self @None);
 
-                    let count = self.line.graphemes(true).count();
-                    let skip_count = count - self.line_cursor_grapheme;
-                    let start = self
-                        .line
-                        .grapheme_indices(true)
-                        .rev()
-                        .skip(skip_count)
-                        .skip_while(|(_, str)| *str == " ")
-                        .find_map(
-                            |(pos, str)| if str == " " { Some(pos + 1) } else { None },
-                        )
-                        .unwrap_or(0);
-                    let end = self
-                        .line
-                        .grapheme_indices(true)
-                        .nth(self.line_cursor_grapheme)
-                        .map(|(end, _)| end);
-                    let change = start as isize - self.line_cursor_grapheme as isize;
-                    self.move_cursor(change)?;
-                    if let Some(end) = end {
-                        self.line.drain(start..end);
-                    } else {
-                        self.line.drain(start..);
-                    }
-
-                    self.clear_and_render_and_flush(term)?;
+                    self.kill(self.get_motion_range(Motion::WordBefore), term)?;
                 }
                 // Move to beginning
                 #[cfg(feature = "emacs")]
@@ -451,21 +1222,7 @@ impl LineState {
                     early_return_if_paused!(self @None);
 
                     self.reset_cursor(term)?;
-                    let count = self.line.graphemes(true).count();
-                    let skip_count = count - self.line_cursor_grapheme;
-                    if let Some((pos, _)) = self
-                        .line
-                        .grapheme_indices(true)
-                        .rev()
-                        .skip(skip_count)
-                        .skip_while(|(_, str)| *str == " ")
-                        .find(|(_, str)| *str == " ")
-                    {
-                        let change = pos as isize - self.line_cursor_grapheme as isize;
-                        self.move_cursor(change + 1)?;
-                    } else {
-                        self.move_cursor(-100000)?
-                    }
+                    self.move_cursor_to_pos(self.get_word_start_before_cursor())?;
                     self.set_cursor(term)?;
 
                     term.flush()?;
@@ -475,18 +1232,7 @@ impl LineState {
                     early_return_if_paused!(self @None);
 
                     self.reset_cursor(term)?;
-                    if let Some((pos, _)) = self
-                        .line
-                        .grapheme_indices(true)
-                        .skip(self.line_cursor_grapheme)
-                        .skip_while(|(_, c)| *c == " ")
-                        .find(|(_, c)| *c == " ")
-                    {
-                        let change = pos as isize - self.line_cursor_grapheme as isize;
-                        self.move_cursor(change)?;
-                    } else {
-                        self.move_cursor(10000)?;
-                    };
+                    self.move_cursor_to_pos(self.get_word_end_after_cursor())?;
                     self.set_cursor(term)?;
 
                     term.flush()?;
@@ -507,11 +1253,36 @@ impl LineState {
 
                 match code {
                     KeyCode::Enter => {
+                        // Add a new line (instead of submitting the line) until the input
+                        // is complete, see [crate::InputValidator].
+                        let is_complete = match self.maybe_input_validator {
+                            Some(ref input_validator)
+                                if self.maybe_secret_input_mode.is_none() =>
+                            {
+                                input_validator.is_complete(&self.line)
+                            }
+                            _ => true,
+                        };
+                        if !is_complete {
+                            self.clear(term)?;
+                            self.cluster_buffer.clear();
+                            let (g_pos, g_str) =
+                                self.current_grapheme().unwrap_or((0, ""));
+                            self.line.insert(g_pos + g_str.len(), '\n');
+                            self.move_cursor(1)?;
+                            self.render_and_flush(term)?;
+                            return Ok(None);
+                        }
+
                         // Print line so you can see what commands you've typed.
                         if self.should_print_line_on_enter && !self.is_paused.is_paused()
                         {
                             self.print_and_flush(
-                                &format!("{}{}\n", self.prompt, self.line),
+                                &format!(
+                                    "{}{}\n",
+                                    self.prompt,
+                                    self.get_displayed_line("\n")
+                                ),
                                 term,
                             )?;
                         }
@@ -550,6 +1321,9 @@ impl LineState {
                         term.flush()?;
                     }
                     KeyCode::Right => {
+                        if self.accept_hint(term)? {
+                            return Ok(None);
+                        }
                         self.reset_cursor(term)?;
                         self.move_cursor(1)?;
                         self.set_cursor(term)?;
@@ -562,27 +1336,62 @@ impl LineState {
                         term.flush()?;
                     }
                     KeyCode::End => {
-                        self.reset_cursor(term)?;
+                        if self.accept_hint(term)? {
+                            return Ok(None);
+                        }
+                        // The hint is shown once the cursor is at the end of the line.
+                        self.clear(term)?;
                         self.move_cursor(100000)?;
-                        self.set_cursor(term)?;
-                        term.flush()?;
+                        self.render_and_flush(term)?;
                     }
                     KeyCode::Up => {
-                        // search for next history item, replace line if found.
-                        if let Some(line) = safe_history.lock().unwrap().search_next() {
-                            self.line.clear();
-                            self.line += line;
+                        // The history isn't used while a secret is typed.
+                        if self.maybe_secret_input_mode.is_some() {
+                            return Ok(None);
+                        }
+
+                        // Move to the previous row of a multi-line input, before going
+                        // through the history.
+                        let (row, col) = self.get_cursor_row_and_col();
+                        if row > 0 && self.move_cursor_to_row(row - 1, col, term)? {
+                            return Ok(None);
+                        }
+
+                        // search for next history item, replace line if found. The
+                        // history isn't locked while the line is rendered, since the
+                        // hint provider might need it.
+                        let maybe_line = safe_history
+                            .lock()
+                            .unwrap()
+                            .search_next()
+                            .map(str::to_string);
+                        if let Some(line) = maybe_line {
+                            self.line = line;
                             self.clear(term)?;
                             self.move_cursor(100000)?;
                             self.render_and_flush(term)?;
                         }
                     }
                     KeyCode::Down => {
+                        if self.maybe_secret_input_mode.is_some() {
+                            return Ok(None);
+                        }
+
+                        // Move to the next row of a multi-line input, before going
+                        // through the history.
+                        let (row, col) = self.get_cursor_row_and_col();
+                        if self.move_cursor_to_row(row + 1, col, term)? {
+                            return Ok(None);
+                        }
+
                         // search for next history item, replace line if found.
-                        if let Some(line) = safe_history.lock().unwrap().search_previous()
-                        {
-                            self.line.clear();
-                            self.line += line;
+                        let maybe_line = safe_history
+                            .lock()
+                            .unwrap()
+                            .search_previous()
+                            .map(str::to_string);
+                        if let Some(line) = maybe_line {
+                            self.line = line;
                             self.clear(term)?;
                             self.move_cursor(100000)?;
                             self.render_and_flush(term)?;
@@ -617,6 +1426,33 @@ impl LineState {
                     _ => {}
                 }
             }
+            // Pasted text (w/ bracketed paste) is inserted in one go, instead of one key
+            // event per char. The line breaks in it are turned into spaces, so that the
+            // pasted text isn't submitted until Enter is pressed.
+            Event::Paste(text) => {
+                early_return_if_paused!(self @None);
+
+                let text: String = text
+                    .replace("\r\n", "\n")
+                    .chars()
+                    .filter_map(|c| match c {
+                        '\n' | '\r' | '\t' => Some(' '),
+                        c if c.is_control() => None,
+                        c => Some(c),
+                    })
+                    .collect();
+                if text.is_empty() {
+                    return Ok(None);
+                }
+
+                self.clear(term)?;
+                self.cluster_buffer.clear();
+                let (g_pos, g_str) = self.current_grapheme().unwrap_or((0, ""));
+                let pos = g_pos + g_str.len();
+                self.line.insert_str(pos, &text);
+                self.move_cursor(text.graphemes(true).count() as isize)?;
+                self.render_and_flush(term)?;
+            }
             Event::Resize(x, y) => {
                 early_return_if_paused!(self @None);
 
@@ -639,7 +1475,12 @@ mod tests {
     use r3bl_test_fixtures::StdoutMock;
 
     use super::*;
-    use crate::{History, StdMutex};
+    use crate::{BracketInputValidator,
+                History,
+                HistoryHintProvider,
+                Keymap,
+                StdMutex,
+                ViMode};
 
     #[tokio::test]
     #[allow(clippy::needless_return)]
@@ -666,6 +1507,34 @@ mod tests {
         assert_eq!(line.line, "a");
     }
 
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_paste() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+        line.line = "ad".into();
+        line.line_cursor_grapheme = 1;
+
+        let stdout_mock = StdoutMock::default();
+
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+
+        let (history, _) = History::new();
+        let safe_history = Arc::new(StdMutex::new(history));
+
+        let event = Event::Paste("b\r\nc".into());
+
+        let it = line.apply_event_and_render(
+            event,
+            &mut *safe_output_terminal.lock().unwrap(),
+            safe_history,
+        );
+
+        assert!(matches!(it, Ok(None)));
+
+        assert_eq!(line.line, "ab cd");
+        assert_eq!(line.line_cursor_grapheme, 4);
+    }
+
     #[tokio::test]
     #[allow(clippy::needless_return)]
     async fn test_move_cursor() {
@@ -715,4 +1584,361 @@ mod tests {
 
         assert_eq!(line.line, "");
     }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_reverse_search() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+        line.line = "bar".into();
+
+        let stdout_mock = StdoutMock::default();
+
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+
+        let (mut history, _) = History::new();
+        for entry in ["cargo build", "ls", "cargo test"] {
+            history.update(Some(entry.into()));
+        }
+        let safe_history = Arc::new(StdMutex::new(history));
+
+        let apply = |line: &mut LineState, code: KeyCode, modifiers: KeyModifiers| {
+            line.apply_event_and_render(
+                Event::Key(KeyEvent::new(code, modifiers)),
+                &mut *safe_output_terminal.lock().unwrap(),
+                safe_history.clone(),
+            )
+            .unwrap()
+        };
+
+        // The newest match is shown as the query is typed, and Ctrl+R cycles to the
+        // older ones.
+        apply(&mut line, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        for character in "car".chars() {
+            apply(&mut line, KeyCode::Char(character), KeyModifiers::NONE);
+        }
+        assert_eq!(line.line, "cargo test");
+        apply(&mut line, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "cargo build");
+        apply(&mut line, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "cargo build");
+        assert_eq!(line.prompt, "(failed reverse-i-search)`car': ");
+
+        // Esc brings back the original line & prompt.
+        apply(&mut line, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(line.line, "bar");
+        assert_eq!(line.prompt, "foo");
+        assert!(line.maybe_reverse_search.is_none());
+
+        // Enter submits the match.
+        apply(&mut line, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        apply(&mut line, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(
+            apply(&mut line, KeyCode::Enter, KeyModifiers::NONE),
+            Some(ReadlineEvent::Line("ls".into()))
+        );
+        assert_eq!(line.prompt, "foo");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_accept_hint() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+
+        let stdout_mock = StdoutMock::default();
+
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+
+        let (mut history, _) = History::new();
+        history.update(Some("cargo test".into()));
+        let safe_history = Arc::new(StdMutex::new(history));
+        line.maybe_hint_provider = Some(Arc::new(HistoryHintProvider {
+            safe_history: safe_history.clone(),
+        }));
+
+        let apply = |line: &mut LineState, code: KeyCode| {
+            line.apply_event_and_render(
+                Event::Key(KeyEvent::new(code, KeyModifiers::NONE)),
+                &mut *safe_output_terminal.lock().unwrap(),
+                safe_history.clone(),
+            )
+            .unwrap()
+        };
+
+        // The hint is painted after the line, but isn't part of it until it is accepted.
+        apply(&mut line, KeyCode::Char('c'));
+        assert_eq!(line.line, "c");
+        assert!(stdout_mock
+            .get_copy_of_buffer_as_string()
+            .contains("argo test"));
+
+        // Right moves the cursor when it isn't at the end of the line.
+        apply(&mut line, KeyCode::Left);
+        apply(&mut line, KeyCode::Right);
+        assert_eq!(line.line, "c");
+
+        apply(&mut line, KeyCode::Right);
+        assert_eq!(line.line, "cargo test");
+        assert_eq!(line.line_cursor_grapheme, 10);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_multi_line_input() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+        line.maybe_input_validator = Some(Arc::new(BracketInputValidator));
+
+        let stdout_mock = StdoutMock::default();
+
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+
+        let (history, _) = History::new();
+        let safe_history = Arc::new(StdMutex::new(history));
+
+        let apply = |line: &mut LineState, code: KeyCode| {
+            line.apply_event_and_render(
+                Event::Key(KeyEvent::new(code, KeyModifiers::NONE)),
+                &mut *safe_output_terminal.lock().unwrap(),
+                safe_history.clone(),
+            )
+            .unwrap()
+        };
+
+        // Enter adds a new line while the input is incomplete, and the next row starts
+        // w/ the continuation prompt.
+        apply(&mut line, KeyCode::Char('f'));
+        apply(&mut line, KeyCode::Char('('));
+        assert_eq!(apply(&mut line, KeyCode::Enter), None);
+        apply(&mut line, KeyCode::Char('x'));
+        assert_eq!(line.line, "f(\nx");
+        assert_eq!(line.current_column, 100 + 4 + 1);
+
+        // Up & Down move between the rows.
+        apply(&mut line, KeyCode::Up);
+        assert_eq!(line.line_cursor_grapheme, 1);
+        apply(&mut line, KeyCode::Down);
+        assert_eq!(line.line_cursor_grapheme, 4);
+
+        apply(&mut line, KeyCode::Char(')'));
+        assert_eq!(
+            apply(&mut line, KeyCode::Enter),
+            Some(ReadlineEvent::Line("f(\nx)".into()))
+        );
+    }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_secret_input_mode() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+
+        let stdout_mock = StdoutMock::default();
+
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+
+        let (mut history, _) = History::new();
+        history.update(Some("hunter2".into()));
+        let safe_history = Arc::new(StdMutex::new(history));
+
+        let apply = |line: &mut LineState, code: KeyCode| {
+            line.apply_event_and_render(
+                Event::Key(KeyEvent::new(code, KeyModifiers::NONE)),
+                &mut *safe_output_terminal.lock().unwrap(),
+                safe_history.clone(),
+            )
+            .unwrap()
+        };
+
+        line.set_secret_input_mode(
+            Some(SecretInputMode::Mask('*')),
+            &mut *safe_output_terminal.lock().unwrap(),
+        )
+        .unwrap();
+
+        // The line is masked.
+        apply(&mut line, KeyCode::Char('a'));
+        apply(&mut line, KeyCode::Char('b'));
+        assert_eq!(line.current_column, 3 + 2);
+        let output = stdout_mock.get_copy_of_buffer_as_string_strip_ansi();
+        assert!(output.contains("foo**"));
+        assert!(!output.contains("ab"));
+
+        // The history isn't used.
+        apply(&mut line, KeyCode::Up);
+        assert_eq!(line.line, "ab");
+
+        assert_eq!(
+            apply(&mut line, KeyCode::Enter),
+            Some(ReadlineEvent::Line("ab".into()))
+        );
+        assert!(!stdout_mock
+            .get_copy_of_buffer_as_string_strip_ansi()
+            .contains("hunter2"));
+
+        line.set_secret_input_mode(None, &mut *safe_output_terminal.lock().unwrap())
+            .unwrap();
+        assert_eq!(line.maybe_secret_input_mode, None);
+    }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_emacs_keymap() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+
+        let stdout_mock = StdoutMock::default();
+
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+
+        let (history, _) = History::new();
+        let safe_history = Arc::new(StdMutex::new(history));
+
+        let apply = |line: &mut LineState, code: KeyCode, modifiers: KeyModifiers| {
+            line.apply_event_and_render(
+                Event::Key(KeyEvent::new(code, modifiers)),
+                &mut *safe_output_terminal.lock().unwrap(),
+                safe_history.clone(),
+            )
+            .unwrap()
+        };
+
+        for character in "foo bar".chars() {
+            apply(&mut line, KeyCode::Char(character), KeyModifiers::NONE);
+        }
+
+        // Kill & yank.
+        apply(&mut line, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "foo ");
+        apply(&mut line, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "foo bar");
+        apply(&mut line, KeyCode::Home, KeyModifiers::NONE);
+        apply(&mut line, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "");
+
+        // Yank, and then cycle to the older kill.
+        apply(&mut line, KeyCode::Char('y'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "foo bar");
+        apply(&mut line, KeyCode::Char('y'), KeyModifiers::ALT);
+        assert_eq!(line.line, "bar");
+
+        // Transpose the last 2 chars.
+        apply(&mut line, KeyCode::Char('t'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "bra");
+
+        // Undo.
+        apply(&mut line, KeyCode::Char('_'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "bar");
+        apply(&mut line, KeyCode::Char('_'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "foo bar");
+        apply(&mut line, KeyCode::Char('_'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "");
+
+        // Undo the kills & yanks before that.
+        for _ in 0..3 {
+            apply(&mut line, KeyCode::Char('_'), KeyModifiers::CONTROL);
+        }
+        assert_eq!(line.line, "foo bar");
+
+        // The chars that were typed one after another are undone together.
+        apply(&mut line, KeyCode::Char('_'), KeyModifiers::CONTROL);
+        assert_eq!(line.line, "");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_vi_keymap() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+        line.keymap_state.set_keymap(Keymap::Vi);
+
+        let stdout_mock = StdoutMock::default();
+
+        let safe_output_terminal = Arc::new(StdMutex::new(stdout_mock.clone()));
+
+        let (history, _) = History::new();
+        let safe_history = Arc::new(StdMutex::new(history));
+
+        let apply = |line: &mut LineState, code: KeyCode| {
+            line.apply_event_and_render(
+                Event::Key(KeyEvent::new(code, KeyModifiers::NONE)),
+                &mut *safe_output_terminal.lock().unwrap(),
+                safe_history.clone(),
+            )
+            .unwrap()
+        };
+
+        for character in "foo bar".chars() {
+            apply(&mut line, KeyCode::Char(character));
+        }
+        apply(&mut line, KeyCode::Esc);
+        assert_eq!(line.keymap_state.vi_mode, ViMode::Normal);
+        assert_eq!(line.line_cursor_grapheme, 6);
+
+        // Kill the first word, and put it back at the end.
+        for character in "0dw$p".chars() {
+            apply(&mut line, KeyCode::Char(character));
+        }
+        assert_eq!(line.line, " barfoo");
+
+        // Undo both changes, and append to the line.
+        for character in "uuA!".chars() {
+            apply(&mut line, KeyCode::Char(character));
+        }
+        assert_eq!(line.line, "foo bar!");
+        assert_eq!(line.keymap_state.vi_mode, ViMode::Insert);
+    }
+
+    #[test]
+    fn test_is_holding_output() {
+        let mut line = LineState::new("foo".into(), (100, 100));
+        line.line = "ab".into();
+        assert!(!line.is_holding_output());
+
+        line.output_while_typing_policy = OutputWhileTypingPolicy::HoldWhileEditing;
+        assert!(line.is_holding_output());
+        line.line.clear();
+        assert!(!line.is_holding_output());
+
+        let idle_duration = Duration::from_secs(60);
+        line.output_while_typing_policy =
+            OutputWhileTypingPolicy::HoldUntilIdle(idle_duration);
+        assert!(!line.is_holding_output());
+        assert_eq!(line.get_idle_deadline(), None);
+        let last_key_press = Instant::now();
+        line.maybe_last_key_press = Some(last_key_press);
+        assert!(line.is_holding_output());
+        assert_eq!(
+            line.get_idle_deadline(),
+            Some(last_key_press + idle_duration)
+        );
+    }
+
+    #[test]
+    fn test_progress_bars() {
+        let mut line = LineState::new("> ".into(), (100, 100));
+        line.line = "ab".into();
+        line.move_cursor(2).unwrap();
+
+        let mut stdout_mock = StdoutMock::default();
+
+        // The progress bar is rendered above the prompt.
+        let id = line
+            .add_progress_bar("download", 40, &mut stdout_mock)
+            .unwrap();
+        let output_buffer_data = stdout_mock.get_copy_of_buffer_as_string_strip_ansi();
+        assert!(output_buffer_data
+            .contains("download [░░░░░░░░░░░░░░░░░░░░]   0% 0/40 0.0/s ETA -"));
+        assert!(output_buffer_data.ends_with("> ab"));
+
+        // It is rendered right away once it is complete.
+        line.update_progress_bar(id, |it| it.position = 40, &mut stdout_mock)
+            .unwrap();
+        assert!(stdout_mock
+            .get_copy_of_buffer_as_string_strip_ansi()
+            .contains("download [████████████████████] 100% 40/40"));
+
+        // The final message is printed above the prompt, instead of the progress bar.
+        line.remove_progress_bar(id, Some("downloaded"), &mut stdout_mock)
+            .unwrap();
+        assert!(line.progress_bars.is_empty());
+        assert!(stdout_mock
+            .get_copy_of_buffer_as_string_strip_ansi()
+            .ends_with("downloaded\n> ab"));
+    }
 }
@@ -16,11 +16,23 @@
  */
 
 // Attach.
+pub mod hint;
 pub mod history;
+pub mod input_validator;
+pub mod keymap;
 pub mod line_state;
+pub mod pager;
+pub mod progress_bar;
 pub mod readline;
+pub mod secret;
 
 // Re-export.
+pub use hint::*;
 pub use history::*;
+pub use input_validator::*;
+pub use keymap::*;
 pub use line_state::*;
+pub use pager::*;
+pub use progress_bar::*;
 pub use readline::*;
+pub use secret::*;
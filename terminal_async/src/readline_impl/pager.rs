@@ -0,0 +1,374 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::io::{self, Write};
+
+use crossterm::{cursor,
+                event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+                style::{Attribute, Print, SetAttribute},
+                terminal::{Clear, ClearType},
+                QueueableCommand};
+use r3bl_core::{ch, ok, UnicodeString};
+
+/// What to do after an event is applied to the [Pager].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PagerControlFlow {
+    Continue,
+    Quit,
+}
+
+/// A scrollable, searchable view of some text that takes over the screen, like `less`,
+/// see [crate::Readline::page_output]. Lines that are wider than the terminal are cut
+/// off (they aren't wrapped), and the last row of the terminal is used for the status
+/// line.
+///
+/// | Key                                                          | Action                 |
+/// | ------------------------------------------------------------ | ---------------------- |
+/// | <kbd>q</kbd>, <kbd>Esc</kbd>, <kbd>Ctrl+C</kbd>              | Quit                   |
+/// | <kbd>j</kbd>, <kbd>Down</kbd>, <kbd>Enter</kbd>              | Scroll down a line     |
+/// | <kbd>k</kbd>, <kbd>Up</kbd>                                  | Scroll up a line       |
+/// | <kbd>Space</kbd>, <kbd>f</kbd>, <kbd>PageDown</kbd>          | Scroll down a page     |
+/// | <kbd>b</kbd>, <kbd>PageUp</kbd>                              | Scroll up a page       |
+/// | <kbd>d</kbd>, <kbd>u</kbd>                                   | Scroll down / up half a page |
+/// | <kbd>g</kbd>, <kbd>Home</kbd>, <kbd>G</kbd>, <kbd>End</kbd>  | Go to the top / bottom |
+/// | <kbd>/</kbd>                                                 | Search (forward)       |
+/// | <kbd>n</kbd>, <kbd>N</kbd>                                   | Go to the next / previous match |
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pager {
+    pub lines: Vec<String>,
+    /// Index of the line that is shown in the first row.
+    pub top_row_index: usize,
+    /// Same as [crate::LineState::term_size], ie: `(columns, rows)`.
+    pub term_size: (u16, u16),
+    /// Is [Some] while the user is typing a search query (after pressing <kbd>/</kbd>).
+    pub maybe_search_input: Option<String>,
+    /// The last search query, which is highlighted, and used by <kbd>n</kbd> &
+    /// <kbd>N</kbd>.
+    pub maybe_search_query: Option<String>,
+    /// Index of the line w/ the last match.
+    pub maybe_match_row_index: Option<usize>,
+    /// Shown in the status line (until the next key is pressed), eg: when a search has
+    /// no matches.
+    pub maybe_message: Option<String>,
+}
+
+impl Pager {
+    pub fn new(text: &str, term_size: (u16, u16)) -> Self {
+        Self {
+            lines: text
+                .lines()
+                .map(|line| line.replace('\t', "    "))
+                .collect(),
+            top_row_index: 0,
+            term_size,
+            maybe_search_input: None,
+            maybe_search_query: None,
+            maybe_match_row_index: None,
+            maybe_message: None,
+        }
+    }
+
+    /// The number of lines that are shown (the last row is the status line).
+    pub fn get_page_height(&self) -> usize {
+        (self.term_size.1 as usize).saturating_sub(1).max(1)
+    }
+
+    fn get_max_top_row_index(&self) -> usize {
+        self.lines.len().saturating_sub(self.get_page_height())
+    }
+
+    fn scroll_down(&mut self, row_count: usize) {
+        self.top_row_index = self
+            .top_row_index
+            .saturating_add(row_count)
+            .min(self.get_max_top_row_index());
+    }
+
+    fn scroll_up(&mut self, row_count: usize) {
+        self.top_row_index = self.top_row_index.saturating_sub(row_count);
+    }
+
+    /// Scroll to the next (or previous) line that contains the search query, starting
+    /// after (or before) the last match.
+    fn search(&mut self, is_forward: bool) {
+        let Some(query) = self.maybe_search_query.as_deref() else {
+            return;
+        };
+        let start_row_index = self.maybe_match_row_index.unwrap_or(self.top_row_index);
+        let maybe_row_index = if is_forward {
+            let skip = if self.maybe_match_row_index.is_some() {
+                start_row_index + 1
+            } else {
+                start_row_index
+            };
+            (skip..self.lines.len()).find(|&it| self.lines[it].contains(query))
+        } else {
+            (0..start_row_index)
+                .rev()
+                .find(|&it| self.lines[it].contains(query))
+        };
+        match maybe_row_index {
+            Some(row_index) => {
+                self.maybe_match_row_index = Some(row_index);
+                self.top_row_index = row_index.min(self.get_max_top_row_index());
+            }
+            None => self.maybe_message = Some(format!("Pattern not found: {query}")),
+        }
+    }
+
+    pub fn apply_event(&mut self, event: Event) -> PagerControlFlow {
+        let key_event = match event {
+            Event::Resize(col_count, row_count) => {
+                self.term_size = (col_count, row_count);
+                self.top_row_index = self.top_row_index.min(self.get_max_top_row_index());
+                return PagerControlFlow::Continue;
+            }
+            Event::Key(
+                key_event @ KeyEvent {
+                    kind: KeyEventKind::Press,
+                    ..
+                },
+            ) => key_event,
+            _ => return PagerControlFlow::Continue,
+        };
+
+        // Type the search query.
+        if let Some(search_input) = self.maybe_search_input.as_mut() {
+            match key_event.code {
+                KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    self.maybe_search_input = None;
+                }
+                KeyCode::Char(c) => search_input.push(c),
+                KeyCode::Backspace => {
+                    if search_input.pop().is_none() {
+                        self.maybe_search_input = None;
+                    }
+                }
+                KeyCode::Enter => {
+                    let query = self.maybe_search_input.take().unwrap_or_default();
+                    if !query.is_empty() {
+                        self.maybe_search_query = Some(query);
+                        self.maybe_match_row_index = None;
+                        self.search(true);
+                    }
+                }
+                KeyCode::Esc => self.maybe_search_input = None,
+                _ => {}
+            }
+            return PagerControlFlow::Continue;
+        }
+
+        self.maybe_message = None;
+        let page_height = self.get_page_height();
+        match (key_event.modifiers, key_event.code) {
+            (KeyModifiers::CONTROL, KeyCode::Char('c'))
+            | (_, KeyCode::Char('q' | 'Q') | KeyCode::Esc) => {
+                return PagerControlFlow::Quit;
+            }
+            (_, KeyCode::Char('j' | 'e') | KeyCode::Down | KeyCode::Enter) => {
+                self.scroll_down(1)
+            }
+            (_, KeyCode::Char('k' | 'y') | KeyCode::Up) => self.scroll_up(1),
+            (_, KeyCode::Char(' ' | 'f') | KeyCode::PageDown) => {
+                self.scroll_down(page_height)
+            }
+            (_, KeyCode::Char('b') | KeyCode::PageUp) => self.scroll_up(page_height),
+            (_, KeyCode::Char('d')) => self.scroll_down(page_height / 2),
+            (_, KeyCode::Char('u')) => self.scroll_up(page_height / 2),
+            (_, KeyCode::Char('g' | '<') | KeyCode::Home) => self.top_row_index = 0,
+            (_, KeyCode::Char('G' | '>') | KeyCode::End) => {
+                self.top_row_index = self.get_max_top_row_index()
+            }
+            (_, KeyCode::Char('/')) => self.maybe_search_input = Some(String::new()),
+            (_, KeyCode::Char('n')) => self.search(true),
+            (_, KeyCode::Char('N')) => self.search(false),
+            _ => {}
+        }
+        PagerControlFlow::Continue
+    }
+
+    /// Eg: `lines 1-23/100 (23%)`, or `/foo` while a search query is typed.
+    pub fn get_status_line(&self) -> String {
+        if let Some(search_input) = self.maybe_search_input.as_ref() {
+            return format!("/{search_input}");
+        }
+        if let Some(message) = self.maybe_message.as_ref() {
+            return message.clone();
+        }
+        let line_count = self.lines.len();
+        let end_row_index = (self.top_row_index + self.get_page_height()).min(line_count);
+        let percent = if line_count == 0 {
+            100
+        } else {
+            end_row_index * 100 / line_count
+        };
+        format!(
+            "lines {}-{}/{} ({}%){} q: quit, /: search",
+            (self.top_row_index + 1).min(line_count),
+            end_row_index,
+            line_count,
+            percent,
+            if end_row_index == line_count {
+                " (END)"
+            } else {
+                ""
+            }
+        )
+    }
+
+    /// Paint the page (w/ the matches of the search query highlighted) & the status line.
+    pub fn render_and_flush(&self, term: &mut dyn Write) -> io::Result<()> {
+        let col_count = ch!(self.term_size.0);
+        let page_height = self.get_page_height();
+
+        term.queue(cursor::MoveTo(0, 0))?;
+        term.queue(Clear(ClearType::All))?;
+
+        for (row_index, line) in self
+            .lines
+            .iter()
+            .skip(self.top_row_index)
+            .take(page_height)
+            .enumerate()
+        {
+            term.queue(cursor::MoveTo(0, row_index as u16))?;
+            let line = UnicodeString::from(line.as_str());
+            let text = line.truncate_end_to_fit_width(col_count);
+            match self.maybe_search_query.as_deref() {
+                Some(query) if text.contains(query) => {
+                    for (index, part) in text.split(query).enumerate() {
+                        if index > 0 {
+                            term.queue(SetAttribute(Attribute::Reverse))?;
+                            term.queue(Print(query))?;
+                            term.queue(SetAttribute(Attribute::NoReverse))?;
+                        }
+                        term.queue(Print(part))?;
+                    }
+                }
+                _ => {
+                    term.queue(Print(text))?;
+                }
+            }
+        }
+
+        let status_line = UnicodeString::from(self.get_status_line());
+        term.queue(cursor::MoveTo(0, page_height as u16))?;
+        term.queue(SetAttribute(Attribute::Reverse))?;
+        term.queue(Print(status_line.truncate_end_to_fit_width(col_count)))?;
+        term.queue(SetAttribute(Attribute::Reset))?;
+
+        term.flush()?;
+
+        ok!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> Event { Event::Key(KeyEvent::new(code, KeyModifiers::NONE)) }
+
+    fn get_text() -> String {
+        (1..=20)
+            .map(|it| format!("line {it}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_scroll() {
+        // 5 lines are shown, w/ the status line below them.
+        let mut pager = Pager::new(&get_text(), (40, 6));
+        assert_eq!(pager.get_page_height(), 5);
+        assert_eq!(
+            pager.get_status_line(),
+            "lines 1-5/20 (25%) q: quit, /: search"
+        );
+
+        pager.apply_event(key(KeyCode::Char('j')));
+        assert_eq!(pager.top_row_index, 1);
+        pager.apply_event(key(KeyCode::Char(' ')));
+        assert_eq!(pager.top_row_index, 6);
+        pager.apply_event(key(KeyCode::Char('b')));
+        assert_eq!(pager.top_row_index, 1);
+        pager.apply_event(key(KeyCode::Char('k')));
+        pager.apply_event(key(KeyCode::Char('k')));
+        assert_eq!(pager.top_row_index, 0);
+
+        // The last page is full.
+        pager.apply_event(key(KeyCode::Char('G')));
+        assert_eq!(pager.top_row_index, 15);
+        pager.apply_event(key(KeyCode::PageDown));
+        assert_eq!(pager.top_row_index, 15);
+        assert_eq!(
+            pager.get_status_line(),
+            "lines 16-20/20 (100%) (END) q: quit, /: search"
+        );
+
+        assert_eq!(
+            pager.apply_event(key(KeyCode::Char('q'))),
+            PagerControlFlow::Quit
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let mut pager = Pager::new(&get_text(), (40, 6));
+
+        // Type the query.
+        for c in "/line 1".chars() {
+            pager.apply_event(key(KeyCode::Char(c)));
+        }
+        assert_eq!(pager.get_status_line(), "/line 1");
+        pager.apply_event(key(KeyCode::Enter));
+        assert_eq!(pager.maybe_match_row_index, Some(0));
+        assert_eq!(pager.top_row_index, 0);
+
+        // Go to the next & previous matches.
+        pager.apply_event(key(KeyCode::Char('n')));
+        assert_eq!(pager.maybe_match_row_index, Some(9));
+        assert_eq!(pager.top_row_index, 9);
+        pager.apply_event(key(KeyCode::Char('n')));
+        pager.apply_event(key(KeyCode::Char('n')));
+        assert_eq!(pager.maybe_match_row_index, Some(11));
+        assert_eq!(pager.top_row_index, 11);
+        pager.apply_event(key(KeyCode::Char('N')));
+        assert_eq!(pager.maybe_match_row_index, Some(10));
+
+        // No match.
+        for c in "/foo".chars() {
+            pager.apply_event(key(KeyCode::Char(c)));
+        }
+        pager.apply_event(key(KeyCode::Enter));
+        assert_eq!(pager.get_status_line(), "Pattern not found: foo");
+        assert_eq!(pager.top_row_index, 10);
+        pager.apply_event(key(KeyCode::Char('j')));
+        assert_eq!(
+            pager.get_status_line(),
+            "lines 12-16/20 (80%) q: quit, /: search"
+        );
+
+        // Esc cancels the search (instead of quitting).
+        pager.apply_event(key(KeyCode::Char('/')));
+        assert_eq!(
+            pager.apply_event(key(KeyCode::Esc)),
+            PagerControlFlow::Continue
+        );
+        assert_eq!(pager.maybe_search_input, None);
+    }
+}
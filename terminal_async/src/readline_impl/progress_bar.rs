@@ -0,0 +1,202 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::{Duration, Instant};
+
+use r3bl_core::{ch, output_device_as_mut, OutputDevice, UnicodeString};
+
+use crate::{spinner_render, SafeLineState, SafePauseBuffer, PROGRESS_BAR_WIDTH};
+
+/// A determinate progress bar that is rendered above the prompt (below the output from
+/// the [r3bl_core::SharedWriter]s), w/ its rate & ETA, eg:
+/// `download [██████████░░░░░░░░░░]  50% 20/40 2.0/s ETA 10s`. It is added w/
+/// [crate::Readline::add_progress_bar], and updated w/ its [ProgressBarHandle].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressBar {
+    pub id: usize,
+    pub message: String,
+    pub position: u64,
+    pub total: u64,
+    pub started_at: Instant,
+}
+
+impl ProgressBar {
+    pub fn new(id: usize, message: String, total: u64) -> Self {
+        Self {
+            id,
+            message,
+            position: 0,
+            total,
+            started_at: Instant::now(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool { self.position >= self.total }
+
+    /// Returns the number of items per second, since the progress bar was added.
+    pub fn get_rate(&self) -> f64 {
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.position as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns how long it will take to complete, at the current rate. Returns [None]
+    /// if nothing has been done yet.
+    pub fn get_eta(&self) -> Option<Duration> {
+        let rate = self.get_rate();
+        if rate <= 0.0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.position) as f64;
+        Some(Duration::from_secs_f64(remaining / rate))
+    }
+
+    /// Render the progress bar, so that it fits in a single row of the terminal (that is
+    /// `display_width` wide).
+    pub fn render(&self, display_width: u16) -> String {
+        let eta = match self.get_eta() {
+            Some(eta) => format_duration(eta),
+            None => "-".to_string(),
+        };
+        let output = format!(
+            "{} {} {}/{} {:.1}/s ETA {}",
+            self.message,
+            spinner_render::render_progress_bar(
+                self.position,
+                self.total,
+                PROGRESS_BAR_WIDTH
+            ),
+            self.position.min(self.total),
+            self.total,
+            self.get_rate(),
+            eta
+        );
+        // The last column is skipped, so that the row doesn't wrap.
+        UnicodeString::from(output)
+            .truncate_end_to_fit_width(ch!(display_width.saturating_sub(1)))
+            .to_string()
+    }
+}
+
+/// Eg: `5s`, `1m 05s`, or `2h 03m`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3_599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3_600, secs % 3_600 / 60),
+    }
+}
+
+/// Owned by the (background) task that a [ProgressBar] is for, to update it. The
+/// progress bar is removed when this is dropped (w/out printing a final message, unlike
+/// [Self::finish]).
+pub struct ProgressBarHandle {
+    pub id: usize,
+    pub safe_line_state: SafeLineState,
+    pub output_device: OutputDevice,
+    pub safe_is_paused_buffer: SafePauseBuffer,
+}
+
+impl ProgressBarHandle {
+    pub fn set_position(&self, position: u64) {
+        self.update(|progress_bar| progress_bar.position = position);
+    }
+
+    pub fn inc(&self, delta: u64) {
+        self.update(|progress_bar| {
+            progress_bar.position = progress_bar.position.saturating_add(delta)
+        });
+    }
+
+    pub fn set_message(&self, message: &str) {
+        self.update(|progress_bar| progress_bar.message = message.to_string());
+    }
+
+    /// Remove the progress bar, and print the `final_message` in its place (above the
+    /// prompt, like the output from the [r3bl_core::SharedWriter]s). If the output is
+    /// paused (or held, see [crate::OutputWhileTypingPolicy]), then it is printed once it
+    /// is resumed.
+    pub fn finish(self, final_message: &str) {
+        let term = output_device_as_mut!(self.output_device);
+        let mut line_state = self.safe_line_state.lock().unwrap();
+        let maybe_final_message =
+            if line_state.is_paused.is_paused() || line_state.is_holding_output() {
+                self.safe_is_paused_buffer
+                    .lock()
+                    .unwrap()
+                    .push_back(format!("{final_message}\n").into_bytes());
+                None
+            } else {
+                Some(final_message)
+            };
+        if let Err(error) =
+            line_state.remove_progress_bar(self.id, maybe_final_message, term)
+        {
+            tracing::error!("progress bar -> finish. Error: {error}");
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut ProgressBar)) {
+        let term = output_device_as_mut!(self.output_device);
+        let mut line_state = self.safe_line_state.lock().unwrap();
+        if let Err(error) = line_state.update_progress_bar(self.id, f, term) {
+            tracing::error!("progress bar -> update. Error: {error}");
+        }
+    }
+}
+
+impl Drop for ProgressBarHandle {
+    fn drop(&mut self) {
+        let term = output_device_as_mut!(self.output_device);
+        let mut line_state = self.safe_line_state.lock().unwrap();
+        if let Err(error) = line_state.remove_progress_bar(self.id, None, term) {
+            tracing::error!("progress bar -> drop. Error: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut progress_bar = ProgressBar::new(0, "download".into(), 40);
+        assert_eq!(progress_bar.get_eta(), None);
+
+        progress_bar.position = 20;
+        progress_bar.started_at = Instant::now() - Duration::from_secs(10);
+        assert_eq!(
+            progress_bar.render(100),
+            "download [██████████░░░░░░░░░░]  50% 20/40 2.0/s ETA 10s"
+        );
+
+        // It is cut off so that it doesn't wrap.
+        assert_eq!(progress_bar.render(11), "download [");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(5)), "5s");
+        assert_eq!(format_duration(Duration::from_secs(65)), "1m 05s");
+        assert_eq!(format_duration(Duration::from_secs(7_380)), "2h 03m");
+    }
+}
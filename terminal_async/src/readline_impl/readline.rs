@@ -16,9 +16,17 @@
  */
 
 use std::{io::{self, Write},
-          sync::Arc};
-
-use crossterm::{terminal::{self, disable_raw_mode, Clear},
+          path::Path,
+          sync::Arc,
+          time::Instant};
+
+use crossterm::{cursor,
+                event::{DisableBracketedPaste, EnableBracketedPaste, Event},
+                terminal::{self,
+                           disable_raw_mode,
+                           Clear,
+                           EnterAlternateScreen,
+                           LeaveAlternateScreen},
                 QueueableCommand};
 use r3bl_core::{output_device_as_mut,
                 InputDevice,
@@ -30,12 +38,23 @@ use thiserror::Error;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
 use crate::{History,
+            HistoryDedupPolicy,
+            HistoryHintProvider,
+            Keymap,
             LineState,
             LineStateLiveness,
+            OutputWhileTypingPolicy,
+            Pager,
+            PagerControlFlow,
             PauseBuffer,
+            ProgressBarHandle,
+            SafeHintProvider,
             SafeHistory,
+            SafeInputValidator,
             SafeLineState,
             SafePauseBuffer,
+            SecretInputMode,
+            SecretString,
             StdMutex,
             CHANNEL_CAPACITY};
 
@@ -320,8 +339,10 @@ pub mod manage_shared_writer_output {
             LineStateControlSignal::Line(buf) => {
                 // Early return if paused. Push the line to pause_buffer, don't render
                 // anything, and return!
+                // The line is held in the same way while the user is typing, see
+                // [OutputWhileTypingPolicy].
                 let mut line_state = self_safe_line_state.lock().unwrap();
-                if line_state.is_paused.is_paused() {
+                if line_state.is_paused.is_paused() || line_state.is_holding_output() {
                     let pause_buffer = &mut *self_safe_is_paused_buffer.lock().unwrap();
                     pause_buffer.push_back(buf);
                     return ControlFlowLimited::Continue;
@@ -428,10 +449,22 @@ pub mod manage_shared_writer_output {
     }
 }
 
+/// Sleep until the `maybe_deadline`, or forever if it is [None].
+async fn sleep_until(maybe_deadline: Option<Instant>) {
+    match maybe_deadline {
+        Some(deadline) => {
+            tokio::time::sleep_until(tokio::time::Instant::from_std(deadline)).await
+        }
+        None => std::future::pending().await,
+    }
+}
+
 impl Drop for Readline {
     fn drop(&mut self) {
         let term = output_device_as_mut!(self.output_device);
         _ = self.safe_line_state.lock().unwrap().exit(term);
+        _ = term.queue(DisableBracketedPaste);
+        _ = term.flush();
         _ = disable_raw_mode();
     }
 }
@@ -441,6 +474,12 @@ impl Readline {
     /// behavior of this instance, you can use the following methods:
     /// - [Self::should_print_line_on]
     /// - [Self::set_max_history]
+    /// - [Self::set_history_file]
+    /// - [Self::set_history_dedup_policy]
+    /// - [Self::set_hint_provider]
+    /// - [Self::set_input_validator]
+    /// - [Self::set_keymap]
+    /// - [Self::set_output_while_typing_policy]
     pub fn new(
         prompt: String,
         output_device: OutputDevice,
@@ -499,6 +538,8 @@ impl Readline {
             .unwrap()
             .render_and_flush(term)?;
         term.queue(terminal::EnableLineWrap)?;
+        // So that pasted text arrives as a single event, see [LineState::apply_event_and_render].
+        term.queue(EnableBracketedPaste)?;
         term.flush()?;
 
         // Create the shared writer.
@@ -530,11 +571,83 @@ impl Readline {
         Ok(())
     }
 
-    /// Set maximum history length. The default length is [crate::HISTORY_SIZE_MAX].
+    /// Set maximum history length. The default length is [crate::HISTORY_SIZE_MAX]. The
+    /// oldest entries are removed (from the history file too, if there is one).
     pub fn set_max_history(&mut self, max_size: usize) {
         let mut history = self.safe_history.lock().unwrap();
-        history.max_size = max_size;
-        history.entries.truncate(max_size);
+        if let Err(error) = history.set_max_size(max_size) {
+            tracing::error!("readline -> set max history. Error: {error}");
+        }
+    }
+
+    /// Load the history from the file at `path` (if it exists), and save it there each
+    /// time that an entry is added w/ [Self::add_history_entry], so that it is
+    /// remembered across runs. Call this right after [Self::new], and after
+    /// [Self::set_max_history] & [Self::set_history_dedup_policy] so that they apply to
+    /// the entries that are loaded. More info in [History::load_from_file].
+    pub fn set_history_file(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ReadlineError> {
+        self.safe_history.lock().unwrap().load_from_file(path)?;
+        Ok(())
+    }
+
+    /// Set what happens when a line that is already in the history is added to it again.
+    /// The default is [HistoryDedupPolicy::IgnoreConsecutive].
+    pub fn set_history_dedup_policy(&mut self, dedup_policy: HistoryDedupPolicy) {
+        self.safe_history.lock().unwrap().dedup_policy = dedup_policy;
+    }
+
+    /// Show a hint (dimmed) after the cursor, which is accepted w/ <kbd>Right</kbd> or
+    /// <kbd>End</kbd>, see [crate::HintProvider]. Pass [None] to stop showing hints.
+    pub fn set_hint_provider(&mut self, maybe_hint_provider: Option<SafeHintProvider>) {
+        self.safe_line_state.lock().unwrap().maybe_hint_provider = maybe_hint_provider;
+    }
+
+    /// Suggest the rest of the newest history entry that starts w/ the line, like the
+    /// autosuggestions in `fish`, see [HistoryHintProvider].
+    pub fn enable_history_hints(&mut self) {
+        self.set_hint_provider(Some(Arc::new(HistoryHintProvider {
+            safe_history: self.safe_history.clone(),
+        })));
+    }
+
+    /// Add a new line to the input when <kbd>Enter</kbd> is pressed, instead of
+    /// submitting it, until it is complete, see [crate::InputValidator]. Pass [None] to
+    /// always submit the line.
+    pub fn set_input_validator(
+        &mut self,
+        maybe_input_validator: Option<SafeInputValidator>,
+    ) {
+        self.safe_line_state.lock().unwrap().maybe_input_validator =
+            maybe_input_validator;
+    }
+
+    /// Set the prompt of each line (after the first one) of a multi-line input. The
+    /// default is [crate::CONTINUATION_PROMPT_DEFAULT].
+    pub fn set_continuation_prompt(&mut self, continuation_prompt: &str) {
+        self.safe_line_state.lock().unwrap().continuation_prompt =
+            continuation_prompt.to_string();
+    }
+
+    /// Set what happens to the output from the [`SharedWriter`]s while the user is
+    /// typing. The default is [OutputWhileTypingPolicy::Print].
+    pub fn set_output_while_typing_policy(&mut self, policy: OutputWhileTypingPolicy) {
+        self.safe_line_state
+            .lock()
+            .unwrap()
+            .output_while_typing_policy = policy;
+    }
+
+    /// Set the key bindings that are used to edit the line. The default is
+    /// [Keymap::Emacs].
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.safe_line_state
+            .lock()
+            .unwrap()
+            .keymap_state
+            .set_keymap(keymap);
     }
 
     /// Set whether the input line should remain on the screen after events.
@@ -564,6 +677,9 @@ impl Readline {
     /// either an [ReadlineEvent] or an [ReadlineError].
     pub async fn readline(&mut self) -> miette::Result<ReadlineEvent, ReadlineError> {
         loop {
+            let maybe_idle_deadline =
+                self.safe_line_state.lock().unwrap().get_idle_deadline();
+
             tokio::select! {
                 // Poll for events.
                 // This branch is cancel safe because no state is declared inside the
@@ -572,14 +688,18 @@ impl Readline {
                 // - So if this future is dropped, then the item in the
                 //   pinned_input_stream isn't used and the state isn't modified.
                 result_crossterm_event = self.input_device.next() => {
-                    match readline_internal::apply_event_to_line_state_and_render(
-                        result_crossterm_event,
-                        self.safe_line_state.clone(),
-                        output_device_as_mut!(self.output_device),
-                        self.safe_history.clone(),
-                        self.safe_spinner_is_active.clone(),
-                    ) {
+                    let control_flow =
+                        readline_internal::apply_event_to_line_state_and_render(
+                            result_crossterm_event,
+                            self.safe_line_state.clone(),
+                            output_device_as_mut!(self.output_device),
+                            self.safe_history.clone(),
+                            self.safe_spinner_is_active.clone(),
+                        );
+                    match control_flow {
                         ControlFlowExtended::ReturnOk(ok_value) => {
+                            // Print the output that was held while the line was edited.
+                            self.flush_held_output()?;
                             return Ok(ok_value);
                         },
                         ControlFlowExtended::ReturnError(err_value) => {
@@ -594,8 +714,156 @@ impl Readline {
                 maybe_line = self.history_receiver.recv() => {
                     self.safe_history.lock().unwrap().update(maybe_line);
                 }
+
+                // Print the output that was held while the user was typing, once they go
+                // idle, see [OutputWhileTypingPolicy::HoldUntilIdle].
+                // This branch is cancel safe because sleep is cancel safe.
+                _ = sleep_until(maybe_idle_deadline) => {
+                    self.safe_line_state.lock().unwrap().maybe_last_key_press = None;
+                    self.flush_held_output()?;
+                }
             }
+
+            // Print the output that was held while the line was edited (eg: if it was
+            // cleared).
+            self.flush_held_output()?;
+        }
+    }
+
+    /// Print the output that was held while the user was typing (see
+    /// [OutputWhileTypingPolicy]), if it isn't held anymore.
+    fn flush_held_output(&self) -> Result<(), ReadlineError> {
+        let term = output_device_as_mut!(self.output_device);
+        let line_state = self.safe_line_state.lock().unwrap();
+        if line_state.is_paused.is_paused()
+            || line_state.is_holding_output()
+            || self.safe_is_paused_buffer.lock().unwrap().is_empty()
+        {
+            return Ok(());
+        }
+        let is_paused = line_state.is_paused;
+        manage_shared_writer_output::flush_internal(
+            self.safe_is_paused_buffer.clone(),
+            is_paused,
+            line_state,
+            term,
+        )
+    }
+
+    /// Read a secret (eg: a password, or a token) that is masked (or not shown at all)
+    /// while it is typed, see [SecretInputMode]. It isn't added to the history (and the
+    /// history, the hints & the input validator aren't used while it is typed), and its
+    /// bytes are overwritten w/ zeros when the returned [SecretString] is dropped.
+    ///
+    /// Returns [None] if the user pressed <kbd>Ctrl+D</kbd> or <kbd>Ctrl+C</kbd>. The
+    /// prompt isn't changed, so call [Self::update_prompt] before this to show a different
+    /// one (eg: `"password: "`), and after it to go back to the old one.
+    pub async fn read_secret(
+        &mut self,
+        secret_input_mode: SecretInputMode,
+    ) -> miette::Result<Option<SecretString>, ReadlineError> {
+        {
+            let term = output_device_as_mut!(self.output_device);
+            self.safe_line_state
+                .lock()
+                .unwrap()
+                .set_secret_input_mode(Some(secret_input_mode), term)?;
+        }
+
+        let result = loop {
+            match self.readline().await {
+                Ok(ReadlineEvent::Line(line)) => break Ok(Some(SecretString::new(line))),
+                Ok(ReadlineEvent::Eof | ReadlineEvent::Interrupted) => break Ok(None),
+                Ok(ReadlineEvent::Resized) => continue,
+                Err(error) => break Err(error),
+            }
+        };
+
+        // This also zeroizes what is left of the secret (eg: after Ctrl+C).
+        let term = output_device_as_mut!(self.output_device);
+        self.safe_line_state
+            .lock()
+            .unwrap()
+            .set_secret_input_mode(None, term)?;
+
+        result
+    }
+
+    /// Show the `text` in a scrollable, searchable [Pager] (like `less`) that takes over
+    /// the screen, until the user presses <kbd>q</kbd>. Then the screen is restored, w/
+    /// the prompt & the line that was being edited. This is useful to show long help
+    /// text, or logs, w/out scrolling them past the top of the terminal.
+    ///
+    /// The output from the [`SharedWriter`]s is held while the pager is shown (like when
+    /// the terminal is paused), and is printed once it is closed.
+    pub async fn page_output(&mut self, text: &str) -> Result<(), ReadlineError> {
+        // Hold the output, and switch to the alternate screen.
+        let (mut pager, was_paused) = {
+            let term = output_device_as_mut!(self.output_device);
+            let mut line_state = self.safe_line_state.lock().unwrap();
+            let was_paused = line_state.is_paused;
+            line_state.set_paused(LineStateLiveness::Paused, term)?;
+            term.queue(EnterAlternateScreen)?;
+            term.queue(cursor::Hide)?;
+            (Pager::new(text, line_state.term_size), was_paused)
+        };
+
+        let result = loop {
+            if let Err(error) =
+                pager.render_and_flush(output_device_as_mut!(self.output_device))
+            {
+                break Err(error.into());
+            }
+            // The pager is closed if there are no more events (eg: stdin is closed).
+            let Ok(event) = self.input_device.next().await else {
+                break Ok(());
+            };
+            // So that the line is rendered correctly once the pager is closed.
+            if let Event::Resize(col_count, row_count) = event {
+                self.safe_line_state.lock().unwrap().term_size = (col_count, row_count);
+            }
+            if let PagerControlFlow::Quit = pager.apply_event(event) {
+                break Ok(());
+            }
+        };
+
+        // Switch back to the main screen, render the line, and print the held output.
+        {
+            let term = output_device_as_mut!(self.output_device);
+            term.queue(cursor::Show)?;
+            term.queue(LeaveAlternateScreen)?;
+            self.safe_line_state
+                .lock()
+                .unwrap()
+                .set_paused(was_paused, term)?;
+            term.flush()?;
         }
+        self.flush_held_output()?;
+
+        result
+    }
+
+    /// Add a determinate [crate::ProgressBar] (w/ its rate & ETA) above the prompt, below
+    /// the other ones. It is updated from a background task w/ the returned
+    /// [ProgressBarHandle], while the user types, and while other tasks print output w/
+    /// the [`SharedWriter`]s (which is printed above the progress bars).
+    pub fn add_progress_bar(
+        &self,
+        message: &str,
+        total: u64,
+    ) -> Result<ProgressBarHandle, ReadlineError> {
+        let term = output_device_as_mut!(self.output_device);
+        let id = self
+            .safe_line_state
+            .lock()
+            .unwrap()
+            .add_progress_bar(message, total, term)?;
+        Ok(ProgressBarHandle {
+            id,
+            safe_line_state: self.safe_line_state.clone(),
+            output_device: self.output_device.clone(),
+            safe_is_paused_buffer: self.safe_is_paused_buffer.clone(),
+        })
     }
 
     /// Add a line to the input history.
@@ -882,6 +1150,96 @@ mod test_readline {
             LineStateLiveness::NotPaused
         );
     }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_hold_output_while_editing() {
+        let prompt_str = "> ";
+
+        // This is for CI/CD.
+        if let TTYResult::IsNotInteractive = is_fully_uninteractive_terminal() {
+            return;
+        }
+
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+        let input_device = InputDevice::new_mock(get_input_vec());
+        let (mut readline, shared_writer) = Readline::new(
+            prompt_str.into(),
+            output_device.clone(),
+            /* move */ input_device,
+        )
+        .unwrap();
+        readline
+            .set_output_while_typing_policy(OutputWhileTypingPolicy::HoldWhileEditing);
+        readline.safe_line_state.lock().unwrap().line = "ab".into();
+
+        // The output is held while the line is edited.
+        shared_writer
+            .line_state_control_channel_sender
+            .send(LineStateControlSignal::Line("abc\n".into()))
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+
+        assert_eq!(readline.safe_is_paused_buffer.lock().unwrap().len(), 1);
+        readline.flush_held_output().unwrap();
+        assert_eq!(readline.safe_is_paused_buffer.lock().unwrap().len(), 1);
+
+        // And printed once the line is cleared.
+        readline.safe_line_state.lock().unwrap().line.clear();
+        readline.flush_held_output().unwrap();
+        assert!(readline.safe_is_paused_buffer.lock().unwrap().is_empty());
+        assert!(stdout_mock
+            .get_copy_of_buffer_as_string_strip_ansi()
+            .contains("abc"));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::needless_return)]
+    async fn test_page_output() {
+        let prompt_str = "> ";
+
+        // This is for CI/CD.
+        if let TTYResult::IsNotInteractive = is_fully_uninteractive_terminal() {
+            return;
+        }
+
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+        let input_device =
+            InputDevice::new_mock(vec![Ok(Event::Key(crossterm::event::KeyEvent::new(
+                crossterm::event::KeyCode::Char('q'),
+                crossterm::event::KeyModifiers::NONE,
+            )))]);
+        let (mut readline, shared_writer) = Readline::new(
+            prompt_str.into(),
+            output_device.clone(),
+            /* move */ input_device,
+        )
+        .unwrap();
+
+        // The output is held while the pager is shown.
+        readline.safe_line_state.lock().unwrap().is_paused = LineStateLiveness::Paused;
+        shared_writer
+            .line_state_control_channel_sender
+            .send(LineStateControlSignal::Line("abc\n".into()))
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        readline.safe_line_state.lock().unwrap().is_paused = LineStateLiveness::NotPaused;
+
+        // The pager is closed w/ 'q', and the held output is printed.
+        readline.page_output("line 1\nline 2").await.unwrap();
+        assert_eq!(
+            readline.safe_line_state.lock().unwrap().is_paused,
+            LineStateLiveness::NotPaused
+        );
+        assert!(readline.safe_is_paused_buffer.lock().unwrap().is_empty());
+
+        let output_buffer_data = stdout_mock.get_copy_of_buffer_as_string_strip_ansi();
+        assert!(output_buffer_data.contains("line 2"));
+        assert!(output_buffer_data.contains("(END)"));
+        assert!(output_buffer_data.contains("abc"));
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,79 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::{Debug, Formatter};
+
+/// How the line is shown while a secret (eg: a password, or a token) is typed, see
+/// [crate::Readline::read_secret].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SecretInputMode {
+    /// Nothing is shown, and the cursor doesn't move (like `sudo`).
+    #[default]
+    NoEcho,
+    /// Each of the characters that are typed is shown as the given one, eg: `*`.
+    Mask(char),
+}
+
+/// A secret that was typed by the user, see [crate::Readline::read_secret]. Its bytes are
+/// overwritten w/ zeros when it is dropped, and it isn't printed by [Debug].
+#[derive(Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(secret: String) -> Self { Self(secret) }
+
+    pub fn expose_secret(&self) -> &str { &self.0 }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) { zeroize_string(&mut self.0); }
+}
+
+impl Debug for SecretString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretString(***)")
+    }
+}
+
+/// Overwrite all the bytes of the `string` (including its spare capacity) w/ zeros, and
+/// leave it empty. This is best effort, since copies that were left behind when the
+/// string grew (and was moved to a bigger allocation) aren't overwritten.
+pub fn zeroize_string(string: &mut String) {
+    let mut bytes = std::mem::take(string).into_bytes();
+    let capacity = bytes.capacity();
+    bytes.clear();
+    bytes.resize(capacity, 0);
+    // Don't let the compiler skip the writes above, since the bytes aren't read again.
+    std::hint::black_box(&bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zeroize_string() {
+        let mut string = String::with_capacity(16);
+        string.push_str("hunter2");
+        zeroize_string(&mut string);
+        assert!(string.is_empty());
+
+        let secret = SecretString::new("hunter2".to_string());
+        assert_eq!(secret.expose_secret(), "hunter2");
+        assert_eq!(format!("{secret:?}"), "SecretString(***)");
+    }
+}
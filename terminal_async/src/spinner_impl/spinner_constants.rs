@@ -28,3 +28,11 @@ pub const BRAILLE_DOTS: [&str; 34] = [
 ];
 
 pub const BLOCK_DOTS: [&str; 8] = ["█", "▓", "▒", "░", "░", "▒", "▓", "█"];
+
+pub const BARS: [&str; 14] = [
+    "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█", "▇", "▆", "▅", "▄", "▃", "▂",
+];
+
+/// The number of cells in the progress bar of a [crate::SpinnerLine], not including the
+/// brackets & the percentage.
+pub const PROGRESS_BAR_WIDTH: usize = 20;
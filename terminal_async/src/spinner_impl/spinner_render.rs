@@ -29,6 +29,7 @@ use crate::{spinner_render::style::style,
             SpinnerColor,
             SpinnerStyle,
             SpinnerTemplate,
+            BARS,
             BLOCK_DOTS,
             BRAILLE_DOTS};
 
@@ -74,9 +75,36 @@ pub fn render_tick(
             let clipped_message = apply_color(&clipped_message, &mut style.color);
             format!("{output_symbol} {clipped_message}")
         }
+        SpinnerTemplate::Bars => {
+            // Translate count into the index of the BARS array.
+            let index_to_use = count % BARS.len();
+            let output_symbol = BARS[index_to_use];
+            let output_symbol = apply_color(output_symbol, &mut style.color);
+            let clipped_message = clip_string_to_width_with_ellipsis(
+                message.to_string(),
+                ch!(display_width) - ch!(2),
+            );
+            let clipped_message = apply_color(&clipped_message, &mut style.color);
+            format!("{output_symbol} {clipped_message}")
+        }
     }
 }
 
+/// Returns a progress bar that is `bar_width` cells wide, eg: `[██████░░░░░░]  50%`.
+pub fn render_progress_bar(done: u64, total: u64, bar_width: usize) -> String {
+    let percent = if total == 0 {
+        100
+    } else {
+        done.min(total) * 100 / total
+    };
+    let filled_width = bar_width * percent as usize / 100;
+    format!(
+        "[{}{}] {percent:>3}%",
+        "█".repeat(filled_width),
+        "░".repeat(bar_width - filled_width)
+    )
+}
+
 pub fn print_tick(
     style: &SpinnerStyle,
     output: &str,
@@ -109,7 +137,7 @@ pub fn print_tick(
                 .into_diagnostic()?;
         }
 
-        SpinnerTemplate::Block => {
+        SpinnerTemplate::Block | SpinnerTemplate::Bars => {
             // Print the output. And make sure to terminate w/ a newline, so that the
             // output is printed.
             writer
@@ -140,6 +168,7 @@ pub fn render_final_tick(
         SpinnerTemplate::Dots => clipped_final_message.to_string(),
         SpinnerTemplate::Braille => clipped_final_message.to_string(),
         SpinnerTemplate::Block => clipped_final_message.to_string(),
+        SpinnerTemplate::Bars => clipped_final_message.to_string(),
     }
 }
 
@@ -149,15 +178,16 @@ pub fn print_final_tick(
     writer: &mut SendRawTerminal,
 ) -> miette::Result<()> {
     match style.template {
-        SpinnerTemplate::Dots | SpinnerTemplate::Braille | SpinnerTemplate::Block => {
-            writer
-                .queue(MoveToColumn(0))
-                .into_diagnostic()?
-                .queue(Print(Clear(ClearType::CurrentLine)))
-                .into_diagnostic()?
-                .queue(Print(format!("{}\n", output)))
-                .into_diagnostic()?
-        }
+        SpinnerTemplate::Dots
+        | SpinnerTemplate::Braille
+        | SpinnerTemplate::Block
+        | SpinnerTemplate::Bars => writer
+            .queue(MoveToColumn(0))
+            .into_diagnostic()?
+            .queue(Print(Clear(ClearType::CurrentLine)))
+            .into_diagnostic()?
+            .queue(Print(format!("{}\n", output)))
+            .into_diagnostic()?,
     };
 
     writer.flush().into_diagnostic()?;
@@ -165,14 +195,54 @@ pub fn print_final_tick(
     Ok(())
 }
 
+/// Paint the `finished_lines` (which stay on the screen), and the `active_lines` below
+/// them (which are painted over in the next tick), see [crate::SpinnerManager].
+pub fn print_multi_tick(
+    finished_lines: &[String],
+    active_lines: &[String],
+    writer: &mut SendRawTerminal,
+) -> miette::Result<()> {
+    writer
+        .queue(MoveToColumn(0))
+        .into_diagnostic()?
+        .queue(Clear(ClearType::FromCursorDown))
+        .into_diagnostic()?;
+
+    // Make sure to terminate each line w/ a newline, so that the output is printed.
+    for line in finished_lines.iter().chain(active_lines) {
+        writer
+            .queue(MoveToColumn(0))
+            .into_diagnostic()?
+            .queue(Print(format!("{}\n", line)))
+            .into_diagnostic()?;
+    }
+
+    if !active_lines.is_empty() {
+        writer
+            .queue(MoveUp(active_lines.len() as u16))
+            .into_diagnostic()?;
+    }
+
+    writer.flush().into_diagnostic()?;
+
+    Ok(())
+}
+
 fn apply_color(output: &str, color: &mut SpinnerColor) -> String {
     let mut return_it = output.to_string();
-    if let SpinnerColor::ColorWheel(ref mut color_wheel) = color {
-        let maybe_next_color = color_wheel.next_color();
-        if let Some(next_color) = maybe_next_color {
-            let color = convert_from_tui_color_to_crossterm_color(next_color);
-            let styled_content = style(output).with(color);
-            return_it = styled_content.to_string()
+    match color {
+        SpinnerColor::None => {}
+        SpinnerColor::Solid(tui_color) => {
+            let color = convert_from_tui_color_to_crossterm_color(*tui_color);
+            return_it = style(output).with(color).to_string()
+        }
+        SpinnerColor::ColorWheel(ref mut color_wheel) => {
+            let maybe_next_color = color_wheel.next_color();
+            if let Some(next_color) = maybe_next_color {
+                let color = convert_from_tui_color_to_crossterm_color(next_color);
+                let styled_content = style(output).with(color);
+                return_it = styled_content.to_string()
+            }
         }
     }
     return_it
@@ -15,18 +15,24 @@
  *   limitations under the License.
  */
 
-use r3bl_core::{ColorWheel, ColorWheelConfig, ColorWheelSpeed};
+use r3bl_core::{ColorWheel, ColorWheelConfig, ColorWheelSpeed, TuiColor};
+use strum_macros::{Display, EnumIter};
 
-#[derive(Debug, Clone, Copy)]
+/// Use [strum::IntoEnumIterator::iter] to get all of them, eg: to show a gallery of the
+/// spinners (like in `cargo run --example spinner`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter)]
 pub enum SpinnerTemplate {
     Dots,
     Braille,
     Block,
+    Bars,
 }
 
 #[derive(Debug, Clone)]
 pub enum SpinnerColor {
     None,
+    /// The same color for each tick.
+    Solid(TuiColor),
     ColorWheel(ColorWheel),
 }
 
@@ -44,11 +44,13 @@ use r3bl_tui::{box_end,
                surface,
                App,
                BoxedSafeApp,
+               Breakpoints,
                ComponentRegistry,
                ComponentRegistryMap,
                Continuation,
                EventPropagation,
                FlexBoxId,
+               FunctionKey,
                GlobalData,
                HasFocus,
                InputEvent,
@@ -91,6 +93,8 @@ mod id_impl {
 
 #[derive(Default)]
 pub struct AppMain {
+    /// Toggled w/ F12, see [Surface::is_debug_overlay_enabled].
+    is_debug_overlay_enabled: bool,
     _phantom: std::marker::PhantomData<(State, AppSignal)>,
 }
 
@@ -127,9 +131,28 @@ mod app_main_impl_app_trait {
             component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
             has_focus: &mut HasFocus,
         ) -> CommonResult<EventPropagation> {
+            // Show or hide the outlines of the boxes.
+            if let InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::FunctionKey(FunctionKey::F12),
+            }) = input_event
+            {
+                self.is_debug_overlay_enabled = !self.is_debug_overlay_enabled;
+                return Ok(EventPropagation::ConsumedRender);
+            }
+
+            // Route mouse events to the column under the pointer.
+            if let InputEvent::Mouse(_) = input_event {
+                return ComponentRegistry::route_mouse_event_to_component_at_pos(
+                    global_data,
+                    input_event,
+                    component_registry_map,
+                    has_focus,
+                );
+            }
+
             // Try to handle left and right arrow key input events & return if handled.
             if let Continuation::Return =
-                handle_focus::handle_focus_switch(input_event, has_focus)
+                handle_focus::handle_focus_switch(&input_event, has_focus)
             {
                 return Ok(EventPropagation::ConsumedRender);
             }
@@ -190,6 +213,11 @@ mod app_main_impl_app_trait {
                 let mut surface = {
                     let mut it = surface!(stylesheet: stylesheet::create_stylesheet()?);
 
+                    // Reuse the boxes that were laid out in the last render.
+                    it.maybe_layout_cache =
+                        Some(std::mem::take(&mut global_data.layout_cache));
+                    it.is_debug_overlay_enabled = self.is_debug_overlay_enabled;
+
                     it.surface_start(SurfaceProps {
                         pos: position!(col_index: 0, row_index: 0),
                         size: size!(
@@ -207,6 +235,11 @@ mod app_main_impl_app_trait {
 
                     it.surface_end()?;
 
+                    if let Some(layout_cache) = it.maybe_layout_cache.take() {
+                        global_data.layout_cache = layout_cache;
+                    }
+                    global_data.box_registry = it.get_box_registry();
+
                     it
                 };
 
@@ -223,6 +256,21 @@ mod app_main_impl_app_trait {
 mod perform_layout {
     use super::*;
 
+    /// The cols are stacked (in rows) when the terminal is too narrow for them to be side
+    /// by side.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum ColumnsLayout {
+        Stacked,
+        SideBySide,
+    }
+
+    fn breakpoints() -> Breakpoints<ColumnsLayout> {
+        Breakpoints::new(ColumnsLayout::Stacked).add(
+            size!(col_count: 80, row_count: 0),
+            ColumnsLayout::SideBySide,
+        )
+    }
+
     pub struct ContainerSurfaceRenderer<'a> {
         pub _app: &'a mut AppMain,
     }
@@ -237,12 +285,24 @@ mod perform_layout {
         ) -> CommonResult<()> {
             // Layout and render the container.
             throws!({
+                let (container_dir, column_size_percent) =
+                    match surface.get_active_layout(&breakpoints()) {
+                        ColumnsLayout::SideBySide => (
+                            LayoutDirection::Horizontal,
+                            requested_size_percent!(width: 50, height: 100),
+                        ),
+                        ColumnsLayout::Stacked => (
+                            LayoutDirection::Vertical,
+                            requested_size_percent!(width: 100, height: 50),
+                        ),
+                    };
+
                 // Container - start.
                 let id_container = FlexBoxId::from(Id::Container);
                 box_start!(
                     in: surface,
                     id: id_container,
-                    dir: LayoutDirection::Horizontal,
+                    dir: container_dir,
                     requested_size_percent: requested_size_percent!(width: 100, height: 100),
                     styles:                 [*id_container],
                 );
@@ -254,7 +314,7 @@ mod perform_layout {
                       in:                     surface,
                       id:                     id_column_1,
                       dir:                    LayoutDirection::Vertical,
-                      requested_size_percent: requested_size_percent!(width: 50, height: 100),
+                      requested_size_percent: column_size_percent,
                       styles:                 [*id_column_1],
                     );
                     render_component_in_current_box!(
@@ -274,7 +334,7 @@ mod perform_layout {
                       in:                     surface,
                       id:                     id_column_2,
                       dir:                    LayoutDirection::Vertical,
-                      requested_size_percent: requested_size_percent!(width: 50, height: 100),
+                      requested_size_percent: column_size_percent,
                       styles:                 [*id_column_2],
                     );
                     render_component_in_current_box!(
@@ -298,7 +358,7 @@ mod handle_focus {
     use super::*;
 
     pub fn handle_focus_switch(
-        input_event: InputEvent,
+        input_event: &InputEvent,
         has_focus: &mut HasFocus,
     ) -> Continuation<String> {
         let mut event_consumed = false;
@@ -429,7 +489,9 @@ mod status_bar {
             tui_styled_text! { @style: tui_style!(attrib: [dim]),       @text: " … " },
             tui_styled_text! { @style: tui_style!(attrib: [underline]), @text: " ↓ / - : dec " },
             tui_styled_text! { @style: tui_style!(attrib: [dim]),       @text: " … " },
-            tui_styled_text! { @style: tui_style!(attrib: [underline]), @text: " ← / → : focus " }
+            tui_styled_text! { @style: tui_style!(attrib: [underline]), @text: " ← / → : focus " },
+            tui_styled_text! { @style: tui_style!(attrib: [dim]),       @text: " … " },
+            tui_styled_text! { @style: tui_style!(attrib: [underline]), @text: " F12 : outlines " }
         };
 
         let display_width = styled_texts.display_width();
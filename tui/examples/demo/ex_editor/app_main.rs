@@ -41,7 +41,7 @@ use r3bl_tui::{box_end,
                box_props,
                box_start,
                render_component_in_current_box,
-               render_component_in_given_box,
+               render_modal_stack,
                render_ops,
                render_tui_styled_texts_into,
                surface,
@@ -59,7 +59,6 @@ use r3bl_tui::{box_end,
                EditorComponent,
                EditorEngineConfig,
                EventPropagation,
-               FlexBox,
                FlexBoxId,
                GlobalData,
                HasEditorBuffers,
@@ -97,6 +96,7 @@ pub enum Id {
     DialogStyleNameTitle = 6,
     DialogStyleNameEditor = 7,
     DialogStyleNameResultsPanel = 8,
+    GoToLineDialog = 9,
 }
 
 mod id_impl {
@@ -165,7 +165,7 @@ mod app_main_impl_app_trait {
             // Check to see if the modal dialog should be activated.
             if let modal_dialogs::ModalActivateResult::Yes =
                 modal_dialogs::should_activate(
-                    input_event,
+                    &input_event,
                     component_registry_map,
                     has_focus,
                     state,
@@ -309,7 +309,7 @@ mod modal_dialogs {
     }
 
     pub fn should_activate(
-        input_event: InputEvent,
+        input_event: &InputEvent,
         component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
         has_focus: &mut HasFocus,
         state: &mut State,
@@ -370,6 +370,31 @@ mod modal_dialogs {
             };
         };
 
+        // "Ctrl + g" => activate Go to line.
+        if input_event.matches_keypress(KeyPress::WithModifiers {
+            key: Key::Character('g'),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        }) {
+            // Reset the dialog component prior to activating / showing it.
+            ComponentRegistry::reset_component(
+                component_registry_map,
+                FlexBoxId::from(Id::GoToLineDialog),
+            );
+            return match activate_go_to_line_modal(has_focus, state) {
+                Ok(_) => ModalActivateResult::Yes,
+                Err(err) => {
+                    if let Some(CommonError {
+                        error_type: _,
+                        error_message: msg,
+                    }) = err.downcast_ref::<CommonError>()
+                    {
+                        tracing::error!("📣 Error activating go to line modal: {msg:?}");
+                    }
+                    ModalActivateResult::No
+                }
+            };
+        };
+
         ModalActivateResult::No
     }
 
@@ -479,6 +504,28 @@ mod modal_dialogs {
 
         Ok(())
     }
+
+    fn activate_go_to_line_modal(
+        has_focus: &mut HasFocus,
+        state: &mut State,
+    ) -> CommonResult<()> {
+        throws!({
+            // Setting the has_focus to Id::GoToLineDialog will cause the dialog to
+            // appear on the next render.
+            has_focus.try_set_modal_id(FlexBoxId::from(Id::GoToLineDialog))?;
+
+            dialog_component_initialize_focused(
+                state,
+                FlexBoxId::from(Id::GoToLineDialog),
+                "Go to line".to_owned(),
+                "".to_owned(),
+            );
+
+            call_if_true!(DEBUG_TUI_MOD, {
+                tracing::debug!("📣 activate modal go to line: {:?}", has_focus);
+            });
+        });
+    }
 }
 
 mod perform_layout {
@@ -516,31 +563,15 @@ mod perform_layout {
                     box_end!(in: surface);
                 }
 
-                // Then, render simple modal dialog (if it is active, on top of the editor
-                // component).
-                if has_focus.is_modal_id(FlexBoxId::from(Id::SimpleDialog)) {
-                    render_component_in_given_box! {
-                      in:                 surface,
-                      box:                FlexBox::default(), /* This is not used as the modal breaks out of its box. */
-                      component_id:       FlexBoxId::from(Id::SimpleDialog),
-                      from:               component_registry_map,
-                      global_data:        global_data,
-                      has_focus:          has_focus
-                    };
-                }
-
-                // Or, render autocomplete modal dialog (if it is active, on top of the editor
-                // component).
-                if has_focus.is_modal_id(FlexBoxId::from(Id::AutocompleteDialog)) {
-                    render_component_in_given_box! {
-                      in:                 surface,
-                      box:                FlexBox::default(), /* This is not used as the modal breaks out of its box. */
-                      component_id:       FlexBoxId::from(Id::AutocompleteDialog),
-                      from:               component_registry_map,
-                      global_data:        global_data,
-                      has_focus:          has_focus
-                    };
-                }
+                // Then, render the modal dialogs that are active (if any), on top of the
+                // editor component. A dialog that is opened from another one is painted
+                // on top of it.
+                render_modal_stack! {
+                  in:                 surface,
+                  from:               component_registry_map,
+                  global_data:        global_data,
+                  has_focus:          has_focus
+                };
             });
         }
     }
@@ -556,6 +587,7 @@ mod populate_component_registry {
         insert_editor_component(component_registry_map);
         insert_dialog_component_simple(component_registry_map);
         insert_dialog_component_autocomplete(component_registry_map);
+        insert_dialog_component_go_to_line(component_registry_map);
 
         // Switch focus to the editor component if focus is not set.
         let id = FlexBoxId::from(Id::Editor);
@@ -580,7 +612,7 @@ mod populate_component_registry {
             ) {
                 send_signal!(
                     main_thread_channel_sender,
-                    TerminalWindowMainThreadSignal::Render(Some(my_id))
+                    TerminalWindowMainThreadSignal::RequestRender(Some(my_id))
                 );
             }
 
@@ -614,6 +646,7 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let boxed_dialog_component = {
@@ -649,6 +682,11 @@ mod populate_component_registry {
                             "".to_string(),
                         );
                     }
+                    // This dialog isn't a form, a confirm dialog, or a date (or color) picker.
+                    DialogChoice::Form(_)
+                    | DialogChoice::Confirm(_)
+                    | DialogChoice::Date(_)
+                    | DialogChoice::Color(_) => {}
                 }
             }
 
@@ -697,6 +735,7 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            ..Default::default()
         };
 
         let boxed_dialog_component = {
@@ -732,6 +771,11 @@ mod populate_component_registry {
                             "".to_string(),
                         );
                     }
+                    // This dialog isn't a form, a confirm dialog, or a date (or color) picker.
+                    DialogChoice::Form(_)
+                    | DialogChoice::Confirm(_)
+                    | DialogChoice::Date(_)
+                    | DialogChoice::Color(_) => {}
                 }
             }
 
@@ -762,6 +806,82 @@ mod populate_component_registry {
             );
         });
     }
+
+    /// Insert go to line dialog component into registry if it's not already there.
+    fn insert_dialog_component_go_to_line(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+    ) {
+        let result_stylesheet = stylesheet::create_stylesheet();
+
+        let dialog_options = DialogEngineConfigOptions {
+            mode: DialogEngineMode::ModalSimple,
+            maybe_style_border: get_tui_style! { @from_result: result_stylesheet , Id::DialogStyleNameBorder.into() },
+            maybe_style_title: get_tui_style! { @from_result: result_stylesheet , Id::DialogStyleNameTitle.into() },
+            maybe_style_editor: get_tui_style! { @from_result: result_stylesheet , Id::DialogStyleNameEditor.into() },
+            maybe_style_results_panel: get_tui_style! { @from_result: result_stylesheet , Id::DialogStyleNameResultsPanel.into() },
+            ..Default::default()
+        };
+
+        let editor_options = EditorEngineConfig {
+            multiline_mode: LineMode::SingleLine,
+            syntax_highlight: SyntaxHighlightMode::Disable,
+            edit_mode: EditMode::ReadWrite,
+            ..Default::default()
+        };
+
+        let boxed_dialog_component = {
+            let it = DialogComponent::new_boxed(
+                FlexBoxId::from(Id::GoToLineDialog),
+                dialog_options,
+                editor_options,
+                on_dialog_press_handler,
+                on_dialog_editor_change_handler,
+            );
+
+            /// The editor engine isn't available here, so the editor is asked to go to
+            /// the line the next time it renders.
+            fn on_dialog_press_handler(
+                dialog_choice: DialogChoice,
+                state: &mut State,
+                _main_thread_channel_sender: &mut Sender<
+                    TerminalWindowMainThreadSignal<AppSignal>,
+                >,
+            ) {
+                if let DialogChoice::Yes(text) = dialog_choice {
+                    let maybe_line_number = text.trim().parse::<usize>().ok();
+                    let maybe_editor_buffer =
+                        state.get_mut_editor_buffer(FlexBoxId::from(Id::Editor));
+                    if let (Some(line_number), Some(editor_buffer)) =
+                        (maybe_line_number, maybe_editor_buffer)
+                    {
+                        editor_buffer.request_go_to_line(line_number);
+                    }
+                }
+            }
+
+            fn on_dialog_editor_change_handler(
+                _state: &mut State,
+                _main_thread_channel_sender: &mut Sender<
+                    TerminalWindowMainThreadSignal<AppSignal>,
+                >,
+            ) {
+            }
+
+            it
+        };
+
+        ComponentRegistry::put(
+            component_registry_map,
+            FlexBoxId::from(Id::GoToLineDialog),
+            boxed_dialog_component,
+        );
+
+        call_if_true!(DEBUG_TUI_MOD, {
+            tracing::debug!(
+                "🪙 construct DialogComponent (go to line) [ on_dialog_press ]"
+            );
+        });
+    }
 }
 
 mod stylesheet {
@@ -822,6 +942,9 @@ mod status_bar {
             tui_styled_text! { @style: tui_style!(attrib: [dim, underline]) , @text: "Ctrl + k"},
             tui_styled_text! { @style: tui_style!(attrib: [bold]) ,           @text: " : Autocomplete 🤖"},
             tui_styled_text! { @style: tui_style!(attrib: [dim]) ,            @text: " … "},
+            tui_styled_text! { @style: tui_style!(attrib: [dim, underline]) , @text: "Ctrl + g"},
+            tui_styled_text! { @style: tui_style!(attrib: [bold]) ,           @text: " : Go to line 🎯"},
+            tui_styled_text! { @style: tui_style!(attrib: [dim]) ,            @text: " … "},
             tui_styled_text! { @style: tui_style!(attrib: [underline]) ,      @text: "Type content 🌊"},
         };
 
@@ -447,7 +447,7 @@ mod populate_component_registry {
             ) {
                 send_signal!(
                     main_thread_channel_sender,
-                    TerminalWindowMainThreadSignal::Render(Some(my_id))
+                    TerminalWindowMainThreadSignal::RequestRender(Some(my_id))
                 );
             }
 
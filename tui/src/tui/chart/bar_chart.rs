@@ -0,0 +1,405 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, position, Position, Size, TuiStyle, UnicodeString};
+
+use super::{format_chart_value, get_max_value, scale_value, CHART_VERTICAL_BLOCK_CHARS};
+use crate::{paint_spans, render_ops, RenderOps};
+
+/// The chars at the end of a horizontal bar, from empty to almost full, in eighths of a
+/// cell.
+pub const CHART_HORIZONTAL_BLOCK_CHARS: [&str; 8] =
+    ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+pub const CHART_FULL_BLOCK_CHAR: &str = "█";
+
+/// The default width of the bars of a vertical [BarChart].
+pub const DEFAULT_BAR_CHART_BAR_COL_COUNT: usize = 3;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum BarChartDirection {
+    /// The bars grow to the right, one per row, w/ their labels to the left.
+    #[default]
+    Horizontal,
+    /// The bars grow upwards, one after the other, w/ their labels below them.
+    Vertical,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bar {
+    pub label: String,
+    pub value: f64,
+    /// This overrides the [BarChart::maybe_bar_style], eg: to highlight a bar.
+    pub maybe_style: Option<TuiStyle>,
+}
+
+impl Bar {
+    pub fn new(label: impl Into<String>, value: f64) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            maybe_style: None,
+        }
+    }
+
+    pub fn with_style(mut self, style: TuiStyle) -> Self {
+        self.maybe_style = Some(style);
+        self
+    }
+}
+
+/// A chart that compares values w/ bars, w/ an axis that starts at 0 & ends at the max
+/// value, eg: (horizontal)
+///
+/// ```text
+///    cpu│█████████▌           50
+/// memory│███████████████████ 100
+///       └───────────────────
+///        0               100
+/// ```
+///
+/// The bars that don't fit are dropped from the end. The axes are only painted if there
+/// are at least 3 rows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BarChart {
+    pub bars: Vec<Bar>,
+    pub direction: BarChartDirection,
+    /// The value of a full bar. The largest value is used if this is [None].
+    pub maybe_max: Option<f64>,
+    /// The width of each bar of a vertical chart.
+    pub bar_col_count: usize,
+    /// The value of each bar is painted after it (or above it).
+    pub is_value_shown: bool,
+    pub maybe_bar_style: Option<TuiStyle>,
+    pub maybe_axis_style: Option<TuiStyle>,
+}
+
+impl Default for BarChart {
+    fn default() -> Self {
+        Self {
+            bars: vec![],
+            direction: BarChartDirection::default(),
+            maybe_max: None,
+            bar_col_count: DEFAULT_BAR_CHART_BAR_COL_COUNT,
+            is_value_shown: true,
+            maybe_bar_style: None,
+            maybe_axis_style: Some(TuiStyle {
+                dim: true,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// The text of a row of a chart, split into spans w/ their styles.
+pub type ChartSpans = Vec<(String, Option<TuiStyle>)>;
+
+impl BarChart {
+    pub fn new(direction: BarChartDirection, bars: Vec<Bar>) -> Self {
+        Self {
+            bars,
+            direction,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.maybe_max = Some(max);
+        self
+    }
+
+    pub fn with_bar_col_count(mut self, bar_col_count: usize) -> Self {
+        self.bar_col_count = bar_col_count.max(1);
+        self
+    }
+
+    pub fn with_value_shown(mut self, is_value_shown: bool) -> Self {
+        self.is_value_shown = is_value_shown;
+        self
+    }
+
+    fn get_max(&self) -> f64 {
+        self.maybe_max.unwrap_or_else(|| {
+            get_max_value(&self.bars.iter().map(|it| it.value).collect::<Vec<_>>())
+        })
+    }
+
+    /// Returns the rows of the chart (w/ their styles), which is `size` big. Each row is
+    /// padded to the width of the chart.
+    pub fn get_rows(&self, size: Size) -> Vec<ChartSpans> {
+        let col_count = ch!(@to_usize size.col_count);
+        let row_count = ch!(@to_usize size.row_count);
+        let rows = match self.direction {
+            BarChartDirection::Horizontal => {
+                self.get_horizontal_rows(col_count, row_count)
+            }
+            BarChartDirection::Vertical => self.get_vertical_rows(col_count, row_count),
+        };
+        rows.into_iter()
+            .map(|spans| clip_spans(spans, col_count))
+            .collect()
+    }
+
+    /// Returns the text of the rows of the chart (w/out their styles), see
+    /// [BarChart::get_rows].
+    pub fn get_lines(&self, size: Size) -> Vec<String> {
+        self.get_rows(size)
+            .into_iter()
+            .map(|spans| spans.into_iter().map(|(text, _)| text).collect())
+            .collect()
+    }
+
+    /// Returns the [RenderOps] to paint the chart in the box at `origin_pos`.
+    pub fn render(&self, origin_pos: Position, size: Size) -> RenderOps {
+        let mut render_ops = render_ops!();
+        for (row_index, spans) in self.get_rows(size).into_iter().enumerate() {
+            paint_spans(
+                &mut render_ops,
+                origin_pos + position!(col_index: 0, row_index: row_index),
+                spans,
+            );
+        }
+        render_ops
+    }
+
+    fn get_horizontal_rows(&self, col_count: usize, row_count: usize) -> Vec<ChartSpans> {
+        let max = self.get_max();
+        let has_axes = row_count >= 3;
+        let bar_row_count = match has_axes {
+            true => row_count - 2,
+            false => row_count,
+        };
+        let bars = &self.bars[..self.bars.len().min(bar_row_count)];
+
+        let label_width = bars
+            .iter()
+            .map(|it| UnicodeString::str_display_width(&it.label))
+            .max()
+            .unwrap_or_default()
+            .min(col_count / 3);
+        let value_width = match self.is_value_shown {
+            true => {
+                bars.iter()
+                    .map(|it| format_chart_value(it.value).len())
+                    .max()
+                    .unwrap_or_default()
+                    + 1
+            }
+            false => 0,
+        };
+        let bar_area_width = col_count.saturating_sub(label_width + 1 + value_width);
+
+        let mut rows = vec![];
+        for bar in bars {
+            let eighths = scale_value(bar.value, max, bar_area_width * 8);
+            let bar_text = format!(
+                "{}{}",
+                CHART_FULL_BLOCK_CHAR.repeat(eighths / 8),
+                CHART_HORIZONTAL_BLOCK_CHARS[eighths % 8]
+            );
+            let bar_text_width = eighths / 8 + usize::from(eighths % 8 > 0);
+            let mut spans = vec![
+                (right_align(&bar.label, label_width), None),
+                ("│".to_string(), self.maybe_axis_style),
+                (bar_text, bar.maybe_style.or(self.maybe_bar_style)),
+                (" ".repeat(bar_area_width - bar_text_width), None),
+            ];
+            if self.is_value_shown {
+                spans.push((
+                    format!("{:>value_width$}", format_chart_value(bar.value)),
+                    None,
+                ));
+            }
+            rows.push(spans);
+        }
+
+        if has_axes {
+            rows.push(vec![
+                (" ".repeat(label_width), None),
+                (
+                    format!("└{}", "─".repeat(bar_area_width)),
+                    self.maybe_axis_style,
+                ),
+            ]);
+            let max_label = format_chart_value(max);
+            let scale = match bar_area_width >= max_label.len() + 2 {
+                true => format!(
+                    "0{}{max_label}",
+                    " ".repeat(bar_area_width - max_label.len() - 1)
+                ),
+                false => "0".to_string(),
+            };
+            rows.push(vec![
+                (" ".repeat(label_width + 1), None),
+                (scale, self.maybe_axis_style),
+            ]);
+        }
+        rows
+    }
+
+    fn get_vertical_rows(&self, col_count: usize, row_count: usize) -> Vec<ChartSpans> {
+        let max = self.get_max();
+        if row_count < 3 {
+            return vec![];
+        }
+        let bar_row_count = row_count - 2;
+        let max_label = format_chart_value(max);
+        let axis_width = max_label.len() + 1;
+
+        // Each bar has 1 col of space before it.
+        let bar_count = (col_count.saturating_sub(axis_width) / (self.bar_col_count + 1))
+            .min(self.bars.len());
+        let bars = &self.bars[..bar_count];
+        let bar_eighths: Vec<usize> = bars
+            .iter()
+            .map(|it| scale_value(it.value, max, bar_row_count * 8))
+            .collect();
+
+        let mut rows = vec![];
+        for row_index in 0..bar_row_count {
+            let row_below_count = bar_row_count - 1 - row_index;
+            let mut spans = vec![match row_index {
+                0 => (format!("{max_label}┤"), self.maybe_axis_style),
+                _ => (
+                    format!("{}│", " ".repeat(max_label.len())),
+                    self.maybe_axis_style,
+                ),
+            }];
+            for (bar, eighths) in bars.iter().zip(&bar_eighths) {
+                spans.push((" ".to_string(), None));
+
+                // The value is painted in the row above the top of the bar.
+                let top_row_below_count = eighths.div_ceil(8);
+                if self.is_value_shown && top_row_below_count == row_below_count {
+                    let value =
+                        UnicodeString::from(format_chart_value(bar.value).as_str());
+                    spans.push((
+                        UnicodeString::from(
+                            value.truncate_end_to_fit_width(ch!(self.bar_col_count)),
+                        )
+                        .pad_end_with_spaces_to_fit_width(" ", ch!(self.bar_col_count)),
+                        None,
+                    ));
+                    continue;
+                }
+
+                let cell_eighths = eighths.saturating_sub(row_below_count * 8).min(8);
+                spans.push((
+                    CHART_VERTICAL_BLOCK_CHARS[cell_eighths].repeat(self.bar_col_count),
+                    bar.maybe_style.or(self.maybe_bar_style),
+                ));
+            }
+            rows.push(spans);
+        }
+
+        rows.push(vec![(
+            format!(
+                "{}└{}",
+                right_align("0", max_label.len()),
+                "─".repeat(col_count.saturating_sub(axis_width))
+            ),
+            self.maybe_axis_style,
+        )]);
+
+        let mut labels = " ".repeat(axis_width);
+        for bar in bars {
+            let label = UnicodeString::from(bar.label.as_str());
+            let label = UnicodeString::from(
+                label.truncate_end_to_fit_width(ch!(self.bar_col_count)),
+            );
+            labels.push(' ');
+            labels.push_str(
+                &label.pad_end_with_spaces_to_fit_width(" ", ch!(self.bar_col_count)),
+            );
+        }
+        rows.push(vec![(labels, None)]);
+        rows
+    }
+}
+
+fn right_align(text: &str, width: usize) -> String {
+    let text = UnicodeString::from(text);
+    let text = text.truncate_end_to_fit_width(ch!(width));
+    format!(
+        "{}{text}",
+        " ".repeat(width - UnicodeString::str_display_width(text))
+    )
+}
+
+/// Clip the spans to the `col_count`, and pad them to it.
+fn clip_spans(spans: ChartSpans, col_count: usize) -> ChartSpans {
+    let mut available_width = col_count;
+    let mut it = vec![];
+    for (text, maybe_style) in spans {
+        let text = UnicodeString::from(text.as_str());
+        let text = text
+            .truncate_end_to_fit_width(ch!(available_width))
+            .to_string();
+        available_width -= UnicodeString::str_display_width(&text);
+        it.push((text, maybe_style));
+    }
+    it.push((" ".repeat(available_width), None));
+    it.retain(|(text, _)| !text.is_empty());
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+
+    #[test]
+    fn test_horizontal_bar_chart() {
+        let bar_chart = BarChart::new(
+            BarChartDirection::Horizontal,
+            vec![Bar::new("cpu", 50.0), Bar::new("memory", 100.0)],
+        );
+        assert_eq2!(
+            bar_chart.get_lines(size!(col_count: 30, row_count: 4)),
+            vec![
+                "   cpu│█████████▌           50".to_string(),
+                "memory│███████████████████ 100".to_string(),
+                "      └───────────────────    ".to_string(),
+                "       0               100    ".to_string(),
+            ]
+        );
+
+        // There is no room for the axes, or for the second bar.
+        assert_eq2!(
+            bar_chart.get_lines(size!(col_count: 30, row_count: 1)),
+            vec!["cpu│███████████▌            50".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_vertical_bar_chart() {
+        let bar_chart = BarChart::new(
+            BarChartDirection::Vertical,
+            vec![Bar::new("a", 2.0), Bar::new("b", 4.0), Bar::new("c", 1.0)],
+        )
+        .with_bar_col_count(2);
+        assert_eq2!(
+            bar_chart.get_lines(size!(col_count: 10, row_count: 4)),
+            vec![
+                "4┤ 2  ██  ".to_string(),
+                " │ ██ ██  ".to_string(),
+                "0└────────".to_string(),
+                "   a  b   ".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use r3bl_core::{throws_with_return, CommonResult, Position, Size};
+
+use crate::{render_pipeline,
+            BarChart,
+            BoxedSafeComponent,
+            Component,
+            EventPropagation,
+            FlexBox,
+            FlexBoxId,
+            GlobalData,
+            HasFocus,
+            InputEvent,
+            RenderOps,
+            RenderPipeline,
+            Sparkline,
+            SurfaceBounds,
+            ZOrder};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Chart {
+    Sparkline(Sparkline),
+    BarChart(BarChart),
+}
+
+impl Default for Chart {
+    fn default() -> Self { Chart::Sparkline(Sparkline::default()) }
+}
+
+impl Chart {
+    /// Returns the [RenderOps] to paint the chart in the box at `origin_pos`.
+    pub fn render(&self, origin_pos: Position, size: Size) -> RenderOps {
+        match self {
+            Chart::Sparkline(sparkline) => sparkline.render(origin_pos, size),
+            Chart::BarChart(bar_chart) => bar_chart.render(origin_pos, size),
+        }
+    }
+}
+
+/// This is called before the chart is rendered, to update its data from the app's
+/// state, eg: w/ [Sparkline::push].
+pub type OnChartUpdateFn<S> = fn(&S, &mut Chart);
+
+/// This is a re-usable component that paints a [Chart] so that it fills its box, eg: in a
+/// dashboard. It doesn't handle any input events.
+///
+/// Like [crate::StatusBarComponent], the chart is owned by the component, and the
+/// `on_chart_update_handler` is called each time that the component is rendered, to
+/// update it from the app's state.
+#[derive(Debug, Default)]
+pub struct ChartComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub data: ChartComponentData<S, AS>,
+}
+
+#[derive(Debug, Default)]
+pub struct ChartComponentData<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub chart: Chart,
+    pub on_chart_update_handler: Option<OnChartUpdateFn<S>>,
+    _phantom: PhantomData<AS>,
+}
+
+impl<S, AS> Component<S, AS> for ChartComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    fn reset(&mut self) {}
+
+    fn get_id(&self) -> FlexBoxId { self.data.id }
+
+    fn render(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        current_box: FlexBox,
+        _surface_bounds: SurfaceBounds, /* Ignore this. */
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<RenderPipeline> {
+        throws_with_return!({
+            let ChartComponentData {
+                chart,
+                on_chart_update_handler,
+                ..
+            } = &mut self.data;
+
+            if let Some(on_chart_update_handler) = on_chart_update_handler {
+                on_chart_update_handler(&global_data.state, chart);
+            }
+
+            let mut render_pipeline = render_pipeline!();
+            render_pipeline.push(
+                ZOrder::Normal,
+                chart.render(
+                    current_box.style_adjusted_origin_pos,
+                    current_box.style_adjusted_bounds_size,
+                ),
+            );
+            render_pipeline
+        });
+    }
+
+    fn handle_event(
+        &mut self,
+        _global_data: &mut GlobalData<S, AS>,
+        _input_event: InputEvent,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        Ok(EventPropagation::Propagate)
+    }
+}
+
+pub mod constructor {
+    use super::*;
+
+    impl<S, AS> ChartComponent<S, AS>
+    where
+        S: Debug + Default + Clone + Sync + Send + 'static,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        pub fn new(
+            id: FlexBoxId,
+            chart: Chart,
+            on_chart_update: OnChartUpdateFn<S>,
+        ) -> Self {
+            Self {
+                data: ChartComponentData {
+                    id,
+                    chart,
+                    on_chart_update_handler: Some(on_chart_update),
+                    _phantom: PhantomData,
+                },
+            }
+        }
+
+        pub fn new_boxed(
+            id: FlexBoxId,
+            chart: Chart,
+            on_chart_update: OnChartUpdateFn<S>,
+        ) -> BoxedSafeComponent<S, AS> {
+            Box::new(ChartComponent::new(id, chart, on_chart_update))
+        }
+    }
+}
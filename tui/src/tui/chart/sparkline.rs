@@ -0,0 +1,249 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, position, Position, Size, TuiStyle};
+
+use crate::{render_ops, RenderOp, RenderOps};
+
+/// The chars of a column in a sparkline (or in a vertical bar), from empty to full, in
+/// eighths of a cell.
+pub const CHART_VERTICAL_BLOCK_CHARS: [&str; 9] =
+    [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// The bits of the dots of a braille char (which start at `U+2800`), from its top row to
+/// its bottom row, for its left & right cols.
+const BRAILLE_DOT_BITS: [[u32; 2]; 4] =
+    [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+const BRAILLE_BLANK_CHAR: u32 = 0x2800;
+
+/// A sparkline only keeps this many of the latest values, see [Sparkline::push].
+pub const SPARKLINE_MAX_DATA_LEN: usize = 1_024;
+
+/// The glyphs that a chart is painted w/.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ChartGlyphs {
+    /// 1 value per col, w/ 8 levels per row, eg: `▁▂▃▅▇`.
+    #[default]
+    Block,
+    /// 2 values per col, w/ 4 levels per row, eg: `⣀⣠⣴⣾`. This fits twice as many values
+    /// in the same width, but not all fonts have these chars.
+    Braille,
+}
+
+/// A small chart of the latest values of a series, eg: the CPU usage over the last
+/// minute, which is painted w/out any axes, so that it can fit in a single row. The
+/// latest value is in the last col, and the values that don't fit are dropped from the
+/// start.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Sparkline {
+    pub data: Vec<f64>,
+    /// The value of a full col. The largest value is used if this is [None].
+    pub maybe_max: Option<f64>,
+    pub glyphs: ChartGlyphs,
+    pub maybe_style: Option<TuiStyle>,
+}
+
+impl Sparkline {
+    pub fn new(data: Vec<f64>) -> Self {
+        Self {
+            data,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.maybe_max = Some(max);
+        self
+    }
+
+    pub fn with_glyphs(mut self, glyphs: ChartGlyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    pub fn with_style(mut self, style: TuiStyle) -> Self {
+        self.maybe_style = Some(style);
+        self
+    }
+
+    /// Add the latest value, eg: on each tick of a monitoring task. The oldest values are
+    /// dropped once there are more than [SPARKLINE_MAX_DATA_LEN].
+    pub fn push(&mut self, value: f64) {
+        self.data.push(value);
+        if self.data.len() > SPARKLINE_MAX_DATA_LEN {
+            let excess = self.data.len() - SPARKLINE_MAX_DATA_LEN;
+            self.data.drain(..excess);
+        }
+    }
+
+    /// Returns the rows of the sparkline, which is `size` big.
+    pub fn get_lines(&self, size: Size) -> Vec<String> {
+        let col_count = ch!(@to_usize size.col_count);
+        let row_count = ch!(@to_usize size.row_count);
+        let values_per_col = match self.glyphs {
+            ChartGlyphs::Block => 1,
+            ChartGlyphs::Braille => 2,
+        };
+        let levels_per_row = match self.glyphs {
+            ChartGlyphs::Block => 8,
+            ChartGlyphs::Braille => 4,
+        };
+
+        // The values are right aligned, so the latest one is in the last col.
+        let value_count = col_count * values_per_col;
+        let visible_data = &self.data[self.data.len().saturating_sub(value_count)..];
+        let max = self
+            .maybe_max
+            .unwrap_or_else(|| get_max_value(visible_data));
+        let mut levels = vec![0; value_count - visible_data.len()];
+        levels.extend(
+            visible_data
+                .iter()
+                .map(|it| scale_value(*it, max, row_count * levels_per_row)),
+        );
+
+        (0..row_count)
+            .map(|row_index| {
+                // The number of levels below this row.
+                let base_level = (row_count - 1 - row_index) * levels_per_row;
+                match self.glyphs {
+                    ChartGlyphs::Block => levels
+                        .iter()
+                        .map(|level| {
+                            let cell_level = level.saturating_sub(base_level).min(8);
+                            CHART_VERTICAL_BLOCK_CHARS[cell_level]
+                        })
+                        .collect(),
+                    ChartGlyphs::Braille => levels
+                        .chunks(2)
+                        .map(|pair| get_braille_char(pair, base_level))
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the [RenderOps] to paint the sparkline in the box at `origin_pos`.
+    pub fn render(&self, origin_pos: Position, size: Size) -> RenderOps {
+        let mut render_ops = render_ops!();
+        for (row_index, line) in self.get_lines(size).into_iter().enumerate() {
+            render_ops.push(RenderOp::MoveCursorPositionAbs(
+                origin_pos + position!(col_index: 0, row_index: row_index),
+            ));
+            render_ops.push(RenderOp::ApplyColors(self.maybe_style));
+            render_ops.push(RenderOp::PaintTextWithAttributes(line, self.maybe_style));
+            render_ops.push(RenderOp::ResetColor);
+        }
+        render_ops
+    }
+}
+
+/// The dots of each of the 2 cols of the char are filled from the bottom up to their
+/// level (the `base_level` is the number of levels below the row of the char).
+fn get_braille_char(pair: &[usize], base_level: usize) -> char {
+    let mut bits = 0;
+    for (col_index, level) in pair.iter().enumerate() {
+        for (dot_row_index, dot_bits) in BRAILLE_DOT_BITS.iter().enumerate() {
+            let dot_level = base_level + (3 - dot_row_index);
+            if dot_level < *level {
+                bits |= dot_bits[col_index];
+            }
+        }
+    }
+    char::from_u32(BRAILLE_BLANK_CHAR + bits).unwrap_or(' ')
+}
+
+/// Returns the largest of the `values` (or 0 if there aren't any).
+pub(crate) fn get_max_value(values: &[f64]) -> f64 {
+    values.iter().copied().fold(0.0, f64::max)
+}
+
+/// Returns the number of `level_count` levels that the `value` fills (rounded), where
+/// the `max` fills all of them. Negative values are empty.
+pub(crate) fn scale_value(value: f64, max: f64, level_count: usize) -> usize {
+    if max <= 0.0 || value <= 0.0 {
+        return 0;
+    }
+    let level = (value.min(max) / max * level_count as f64).round();
+    level as usize
+}
+
+/// Returns the value w/out its fraction if it doesn't have one, eg: `42`, or w/ 1
+/// decimal place if it does, eg: `4.2`.
+pub(crate) fn format_chart_value(value: f64) -> String {
+    match value.fract() == 0.0 {
+        true => format!("{value:.0}"),
+        false => format!("{value:.1}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+
+    #[test]
+    fn test_block_sparkline() {
+        let sparkline = Sparkline::new((0..=8).map(f64::from).collect());
+        assert_eq2!(
+            sparkline.get_lines(size!(col_count: 9, row_count: 1)),
+            vec![" ▁▂▃▄▅▆▇█".to_string()]
+        );
+
+        // The values are spread over the rows, and right aligned.
+        let sparkline = Sparkline::new(vec![0.0, 8.0, 16.0]);
+        assert_eq2!(
+            sparkline.get_lines(size!(col_count: 4, row_count: 2)),
+            vec!["   █".to_string(), "  ██".to_string()]
+        );
+
+        // The oldest values are dropped when they don't fit, and the max is clamped.
+        let sparkline = Sparkline::new(vec![8.0, 2.0, 4.0, 100.0]).with_max(8.0);
+        assert_eq2!(
+            sparkline.get_lines(size!(col_count: 3, row_count: 1)),
+            vec!["▂▄█".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_braille_sparkline() {
+        let sparkline = Sparkline::new(vec![0.0, 4.0]).with_glyphs(ChartGlyphs::Braille);
+        assert_eq2!(
+            sparkline.get_lines(size!(col_count: 1, row_count: 1)),
+            vec!["⢸".to_string()]
+        );
+
+        let sparkline = Sparkline::new(vec![2.0, 1.0])
+            .with_max(4.0)
+            .with_glyphs(ChartGlyphs::Braille);
+        assert_eq2!(
+            sparkline.get_lines(size!(col_count: 2, row_count: 1)),
+            vec!["⠀⣄".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_push_drops_oldest_values() {
+        let mut sparkline = Sparkline::default();
+        for value in 0..SPARKLINE_MAX_DATA_LEN + 2 {
+            sparkline.push(value as f64);
+        }
+        assert_eq2!(sparkline.data.len(), SPARKLINE_MAX_DATA_LEN);
+        assert_eq2!(sparkline.data[0], 2.0);
+    }
+}
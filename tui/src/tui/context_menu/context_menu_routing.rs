@@ -0,0 +1,270 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_core::{throws_with_return, CommonResult, Position};
+
+use crate::{Button,
+            ComponentRegistry,
+            ComponentRegistryMap,
+            EventPropagation,
+            FlexBoxId,
+            FunctionKey,
+            GlobalData,
+            HasFocus,
+            InputEvent,
+            Key,
+            KeyPress,
+            KeyState,
+            ModifierKeysMask,
+            MouseInput,
+            MouseInputKind};
+
+/// Returns true for <kbd>Shift+F10</kbd>, which opens the context menu of the focused
+/// component (like the <kbd>Menu</kbd> key, which terminals don't report).
+pub fn is_context_menu_key(input_event: &InputEvent) -> bool {
+    matches!(
+        input_event,
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::FunctionKey(FunctionKey::F10),
+            mask: ModifierKeysMask {
+                shift_key_state: KeyState::Pressed,
+                ctrl_key_state: KeyState::NotPressed,
+                alt_key_state: KeyState::NotPressed,
+            },
+        })
+    )
+}
+
+impl<S, AS> ComponentRegistry<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    /// Open [GlobalData::context_menu] w/ the items of a component (see
+    /// [crate::Component::context_menu_items]):
+    /// - A right click opens the menu of the top most component under the pointer (using
+    ///   the boxes in [GlobalData::box_registry]), at the pointer.
+    /// - <kbd>Shift+F10</kbd> opens the menu of the focused component, at its anchor (see
+    ///   [crate::Component::get_context_menu_anchor]).
+    ///
+    /// While a modal is active, only its menu can be opened. The main event loop calls
+    /// this w/ the input events that the app didn't consume. The `input_event` is
+    /// propagated if it isn't one of these, or if the component has no items.
+    pub fn try_open_context_menu(
+        global_data: &mut GlobalData<S, AS>,
+        input_event: &InputEvent,
+        component_registry_map: &mut ComponentRegistryMap<S, AS>,
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        throws_with_return!({
+            let maybe_id_and_anchor_pos: Option<(FlexBoxId, Position)> = match input_event
+            {
+                InputEvent::Mouse(MouseInput {
+                    pos,
+                    kind: MouseInputKind::MouseDown(Button::Right),
+                    ..
+                }) => {
+                    let maybe_id = match has_focus.is_modal_set() {
+                        true => has_focus.get_id(),
+                        false => global_data
+                            .box_registry
+                            .get_boxes_at(*pos)
+                            .map(|it| it.id)
+                            .find(|id| component_registry_map.contains_key(id)),
+                    };
+                    maybe_id.map(|id| (id, *pos))
+                }
+                _ if is_context_menu_key(input_event) => has_focus.get_id().map(|id| {
+                    let box_origin_pos = global_data
+                        .box_registry
+                        .get_box(id)
+                        .map(|it| it.style_adjusted_origin_pos)
+                        .unwrap_or_default();
+                    let anchor_pos = ComponentRegistry::try_to_get_component_by_id(
+                        component_registry_map,
+                        id,
+                    )
+                    .and_then(|it| it.get_context_menu_anchor(global_data))
+                    .unwrap_or_default();
+                    (id, box_origin_pos + anchor_pos)
+                }),
+                _ => None,
+            };
+
+            let Some((id, anchor_pos)) = maybe_id_and_anchor_pos else {
+                return Ok(EventPropagation::Propagate);
+            };
+            let items =
+                ComponentRegistry::try_to_get_component_by_id(component_registry_map, id)
+                    .map(|it| it.context_menu_items(global_data))
+                    .unwrap_or_default();
+
+            match global_data.context_menu.open(anchor_pos, items) {
+                true => EventPropagation::ConsumedRender,
+                false => EventPropagation::Propagate,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, position, size, throws};
+
+    use super::*;
+    use crate::{test_fixtures::mock_real_objects_for_editor,
+                Component,
+                FlexBox,
+                FlexBoxRegistry,
+                MenuItem,
+                RenderPipeline,
+                SurfaceBounds};
+
+    #[derive(Debug)]
+    struct TestComponent {
+        id: FlexBoxId,
+        item_count: usize,
+    }
+
+    impl Component<(), ()> for TestComponent {
+        fn reset(&mut self) {}
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        fn render(
+            &mut self,
+            _global_data: &mut GlobalData<(), ()>,
+            _current_box: FlexBox,
+            _surface_bounds: SurfaceBounds,
+            _has_focus: &mut HasFocus,
+        ) -> CommonResult<RenderPipeline> {
+            Ok(RenderPipeline::default())
+        }
+
+        fn handle_event(
+            &mut self,
+            _global_data: &mut GlobalData<(), ()>,
+            _input_event: InputEvent,
+            _has_focus: &mut HasFocus,
+        ) -> CommonResult<EventPropagation> {
+            Ok(EventPropagation::Propagate)
+        }
+
+        fn context_menu_items(
+            &self,
+            _global_data: &GlobalData<(), ()>,
+        ) -> Vec<MenuItem<()>> {
+            (0..self.item_count)
+                .map(|it| MenuItem::action(format!("Item {it}"), ()))
+                .collect()
+        }
+
+        fn get_context_menu_anchor(
+            &self,
+            _global_data: &GlobalData<(), ()>,
+        ) -> Option<Position> {
+            Some(position!(col_index: 2, row_index: 1))
+        }
+    }
+
+    fn right_click(col_index: u16, row_index: u16) -> InputEvent {
+        InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind: MouseInputKind::MouseDown(Button::Right),
+            maybe_modifier_keys: None,
+        })
+    }
+
+    #[test]
+    fn test_try_open_context_menu() -> CommonResult<()> {
+        throws!({
+            let map = &mut ComponentRegistryMap::default();
+            for (id, item_count) in [(1, 2), (2, 0)] {
+                let component = TestComponent {
+                    id: FlexBoxId::from(id),
+                    item_count,
+                };
+                ComponentRegistry::put(map, FlexBoxId::from(id), Box::new(component));
+            }
+            let (mut global_data, _) =
+                mock_real_objects_for_editor::make_global_data::<(), ()>(None);
+            global_data.box_registry = FlexBoxRegistry::new(
+                [(1, 0), (2, 10)]
+                    .map(|(id, col_index)| FlexBox {
+                        id: FlexBoxId::from(id),
+                        origin_pos: position!(col_index: col_index, row_index: 0),
+                        style_adjusted_origin_pos: position!(col_index: col_index, row_index: 0),
+                        bounds_size: size!(col_count: 10, row_count: 10),
+                        ..Default::default()
+                    })
+                    .to_vec(),
+            );
+            let has_focus = &mut HasFocus::default();
+            has_focus.set_id(FlexBoxId::from(1));
+
+            // The component w/out items doesn't have a context menu.
+            assert_eq2!(
+                ComponentRegistry::try_open_context_menu(
+                    &mut global_data,
+                    &right_click(15, 5),
+                    map,
+                    has_focus
+                )?,
+                EventPropagation::Propagate
+            );
+            assert!(!global_data.context_menu.is_open());
+
+            // Right click opens the menu at the pointer.
+            assert_eq2!(
+                ComponentRegistry::try_open_context_menu(
+                    &mut global_data,
+                    &right_click(5, 5),
+                    map,
+                    has_focus
+                )?,
+                EventPropagation::ConsumedRender
+            );
+            assert_eq2!(global_data.context_menu.items.len(), 2);
+            assert_eq2!(
+                global_data.context_menu.get_anchor_pos(),
+                position!(col_index: 5, row_index: 5)
+            );
+
+            // Shift+F10 opens the menu of the focused component at its anchor.
+            global_data.context_menu.close();
+            let shift_f10 = InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::FunctionKey(FunctionKey::F10),
+                mask: ModifierKeysMask::new().with_shift(),
+            });
+            assert_eq2!(
+                ComponentRegistry::try_open_context_menu(
+                    &mut global_data,
+                    &shift_f10,
+                    map,
+                    has_focus
+                )?,
+                EventPropagation::ConsumedRender
+            );
+            assert_eq2!(
+                global_data.context_menu.get_anchor_pos(),
+                position!(col_index: 2, row_index: 1)
+            );
+        });
+    }
+}
@@ -0,0 +1,491 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, position, size, Position, Size, TuiStyle};
+
+use crate::{calc_dropdown_inner_width,
+            find_selectable_item,
+            is_inside,
+            render_dropdown,
+            render_ops,
+            render_pipeline,
+            Button,
+            InputEvent,
+            Key,
+            KeyPress,
+            MenuItem,
+            MenuItemKind,
+            MouseInput,
+            MouseInputKind,
+            RenderPipeline,
+            SpecialKey,
+            ZOrder};
+
+/// What happened when an [InputEvent] was applied to a [ContextMenu] w/
+/// [ContextMenu::apply_event].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContextMenuApplyResponse<AS> {
+    /// An item was chosen, and the menu was closed.
+    Chosen(AS),
+    /// The highlighted item changed, or a submenu was opened or closed.
+    Updated,
+    Closed,
+    /// The event was handled, but nothing changed. All events are consumed while the
+    /// menu is open.
+    Consumed,
+    /// The menu isn't open, so the event should be passed on.
+    Noop,
+}
+
+/// A popup menu (w/ the same [MenuItem]s as a [crate::MenuBar], including submenus,
+/// separators & disabled items), which is opened at a position in the window, eg: where
+/// the mouse was right clicked, or at the caret.
+///
+/// The main event loop owns one of these (see [crate::GlobalData::context_menu]), which
+/// is opened w/ the items of a component (see [crate::Component::context_menu_items]),
+/// and the item that is chosen is dispatched as an action. See
+/// [crate::ComponentRegistry::try_open_context_menu].
+///
+/// Key bindings:
+/// - <kbd>Up</kbd> & <kbd>Down</kbd> move the highlight, skipping separators & disabled
+///   items.
+/// - <kbd>Right</kbd> opens the highlighted submenu, and <kbd>Left</kbd> closes the
+///   innermost submenu.
+/// - <kbd>Enter</kbd> chooses the highlighted item.
+/// - <kbd>Esc</kbd> closes the innermost submenu, or the menu.
+///
+/// Clicking on an item chooses it, and clicking anywhere else closes the menu.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextMenu<AS> {
+    pub items: Vec<MenuItem<AS>>,
+    pub maybe_style: Option<TuiStyle>,
+    /// The top left corner of the menu (it is moved up or left to fit in the window).
+    anchor_pos: Position,
+    /// Empty when the menu is closed. Otherwise, the index of the highlighted item in
+    /// each of the open dropdowns.
+    open_path: Vec<usize>,
+    /// The bounds of each open dropdown from the last call to [ContextMenu::render],
+    /// which are needed to handle mouse clicks.
+    last_dropdowns: Vec<(Position, Size)>,
+}
+
+impl<AS> Default for ContextMenu<AS> {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            maybe_style: None,
+            anchor_pos: Position::default(),
+            open_path: vec![],
+            last_dropdowns: vec![],
+        }
+    }
+}
+
+impl<AS: Clone> ContextMenu<AS> {
+    /// Open the menu w/ the `items` at the `anchor_pos`, and highlight its first item.
+    /// Returns false (and the menu stays closed) if there are no items.
+    pub fn open(&mut self, anchor_pos: Position, items: Vec<MenuItem<AS>>) -> bool {
+        if items.is_empty() {
+            return false;
+        }
+        self.open_path = vec![find_selectable_item(&items, 0, 1).unwrap_or_default()];
+        self.items = items;
+        self.anchor_pos = anchor_pos;
+        self.last_dropdowns.clear();
+        true
+    }
+
+    pub fn is_open(&self) -> bool { !self.open_path.is_empty() }
+
+    /// See [Self::open_path].
+    pub fn get_open_path(&self) -> &[usize] { &self.open_path }
+
+    pub fn get_anchor_pos(&self) -> Position { self.anchor_pos }
+
+    pub fn close(&mut self) {
+        self.open_path.clear();
+        self.last_dropdowns.clear();
+    }
+
+    /// Returns the items of the open dropdown at `depth` (0 is the menu, 1 is its open
+    /// submenu, and so on).
+    fn get_items_at(&self, depth: usize) -> Option<&[MenuItem<AS>]> {
+        let mut items = self.items.as_slice();
+        for index in self.open_path.iter().take(depth) {
+            match &items.get(*index)?.kind {
+                MenuItemKind::Submenu(children) => items = children,
+                _ => return None,
+            }
+        }
+        Some(items)
+    }
+
+    /// Returns the highlighted item in the innermost open dropdown.
+    pub fn get_highlighted_item(&self) -> Option<&MenuItem<AS>> {
+        let depth = self.open_path.len().checked_sub(1)?;
+        self.get_items_at(depth)?.get(*self.open_path.last()?)
+    }
+
+    fn move_highlight(&mut self, step: isize) {
+        let Some(depth) = self.open_path.len().checked_sub(1) else {
+            return;
+        };
+        let Some(&item_index) = self.open_path.last() else {
+            return;
+        };
+        let maybe_item_index = self.get_items_at(depth).and_then(|items| {
+            find_selectable_item(items, item_index as isize + step, step)
+        });
+        if let (Some(new_item_index), Some(last)) =
+            (maybe_item_index, self.open_path.last_mut())
+        {
+            *last = new_item_index;
+        }
+    }
+
+    fn open_submenu(&mut self) -> bool {
+        let maybe_item_index = match self.get_highlighted_item() {
+            Some(MenuItem {
+                kind: MenuItemKind::Submenu(children),
+                is_enabled: true,
+                ..
+            }) => find_selectable_item(children, 0, 1),
+            _ => None,
+        };
+        match maybe_item_index {
+            Some(item_index) => {
+                self.open_path.push(item_index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn close_submenu(&mut self) -> bool {
+        if self.open_path.len() <= 1 {
+            return false;
+        }
+        self.open_path.pop();
+        true
+    }
+
+    /// Choose the highlighted item, which dispatches its action, or opens its submenu.
+    fn choose_highlighted_item(&mut self) -> ContextMenuApplyResponse<AS> {
+        let maybe_action = match self.get_highlighted_item() {
+            Some(MenuItem {
+                kind: MenuItemKind::Action(action),
+                is_enabled: true,
+                ..
+            }) => Some(action.clone()),
+            _ => None,
+        };
+        if let Some(action) = maybe_action {
+            self.close();
+            return ContextMenuApplyResponse::Chosen(action);
+        }
+        match self.open_submenu() {
+            true => ContextMenuApplyResponse::Updated,
+            false => ContextMenuApplyResponse::Consumed,
+        }
+    }
+
+    pub fn apply_event(
+        &mut self,
+        input_event: InputEvent,
+    ) -> ContextMenuApplyResponse<AS> {
+        if !self.is_open() {
+            return ContextMenuApplyResponse::Noop;
+        }
+
+        let key_press = match input_event {
+            InputEvent::Keyboard(key_press) => key_press,
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDown(button),
+                ..
+            }) => return self.apply_click(pos, button),
+            // Other events are consumed while the menu is open, so that they don't reach
+            // the components under it.
+            _ => return ContextMenuApplyResponse::Consumed,
+        };
+
+        match key_press {
+            KeyPress::Plain {
+                key: Key::SpecialKey(special_key),
+            } => match special_key {
+                SpecialKey::Esc => {
+                    if self.close_submenu() {
+                        return ContextMenuApplyResponse::Updated;
+                    }
+                    self.close();
+                    ContextMenuApplyResponse::Closed
+                }
+                SpecialKey::Left => match self.close_submenu() {
+                    true => ContextMenuApplyResponse::Updated,
+                    false => ContextMenuApplyResponse::Consumed,
+                },
+                SpecialKey::Right => match self.open_submenu() {
+                    true => ContextMenuApplyResponse::Updated,
+                    false => ContextMenuApplyResponse::Consumed,
+                },
+                SpecialKey::Up => {
+                    self.move_highlight(-1);
+                    ContextMenuApplyResponse::Updated
+                }
+                SpecialKey::Down => {
+                    self.move_highlight(1);
+                    ContextMenuApplyResponse::Updated
+                }
+                SpecialKey::Enter => self.choose_highlighted_item(),
+                _ => ContextMenuApplyResponse::Consumed,
+            },
+            _ => ContextMenuApplyResponse::Consumed,
+        }
+    }
+
+    fn apply_click(
+        &mut self,
+        pos: Position,
+        button: Button,
+    ) -> ContextMenuApplyResponse<AS> {
+        // The submenus are on top of their parents, so they are checked first.
+        for (depth, (origin_pos, bounds_size)) in
+            self.last_dropdowns.clone().into_iter().enumerate().rev()
+        {
+            if !is_inside(pos, origin_pos, bounds_size) {
+                continue;
+            }
+            if button != Button::Left {
+                return ContextMenuApplyResponse::Consumed;
+            }
+            // Skip the top border.
+            let Some(item_index) =
+                ch!(@to_usize pos.row_index - origin_pos.row_index).checked_sub(1)
+            else {
+                return ContextMenuApplyResponse::Consumed;
+            };
+            let is_selectable = self
+                .get_items_at(depth)
+                .and_then(|items| items.get(item_index))
+                .is_some_and(|it| it.is_selectable());
+            if !is_selectable {
+                return ContextMenuApplyResponse::Consumed;
+            }
+            self.open_path.truncate(depth);
+            self.open_path.push(item_index);
+            return self.choose_highlighted_item();
+        }
+
+        self.close();
+        ContextMenuApplyResponse::Closed
+    }
+
+    /// Returns the bounds of each open dropdown (w/ its border), from the menu to the
+    /// innermost submenu.
+    fn calc_dropdowns(&self, window_size: Size) -> Vec<(Position, Size)> {
+        let mut dropdowns: Vec<(Position, Size)> = vec![];
+        for depth in 0..self.open_path.len() {
+            let Some(items) = self.get_items_at(depth) else {
+                break;
+            };
+            let bounds_size = size!(
+                col_count: calc_dropdown_inner_width(items) + 2,
+                row_count: items.len() + 2
+            );
+            // A submenu is next to the item that opened it.
+            let origin_pos = match dropdowns.last() {
+                None => self.anchor_pos,
+                Some((parent_origin_pos, parent_bounds_size)) => {
+                    *parent_origin_pos
+                        + position!(
+                            col_index: parent_bounds_size.col_count,
+                            row_index: self.open_path[depth - 1] + 1
+                        )
+                }
+            };
+            // Keep the dropdown inside the window.
+            let origin_pos = position!(
+                col_index: origin_pos
+                    .col_index
+                    .min(window_size.col_count - bounds_size.col_count),
+                row_index: origin_pos
+                    .row_index
+                    .min(window_size.row_count - bounds_size.row_count)
+            );
+            dropdowns.push((origin_pos, bounds_size));
+        }
+        dropdowns
+    }
+
+    /// Returns the [RenderPipeline] to paint the open dropdowns on top of everything else
+    /// (in [ZOrder::Glass]). Nothing is painted if the menu is closed.
+    pub fn render(&mut self, window_size: Size) -> RenderPipeline {
+        let mut render_pipeline = render_pipeline!();
+        if !self.is_open() {
+            return render_pipeline;
+        }
+
+        let dropdowns = self.calc_dropdowns(window_size);
+        let mut render_ops = render_ops!();
+        for (depth, bounds) in dropdowns.iter().enumerate() {
+            let Some(items) = self.get_items_at(depth) else {
+                break;
+            };
+            render_dropdown(
+                &mut render_ops,
+                *bounds,
+                items,
+                self.open_path[depth],
+                self.maybe_style,
+            );
+        }
+        render_pipeline.push(ZOrder::Glass, render_ops);
+
+        self.last_dropdowns = dropdowns;
+        render_pipeline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum TestAction {
+        Cut,
+        Copy,
+        Upper,
+        Lower,
+    }
+
+    fn key(special_key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        })
+    }
+
+    fn click(col_index: u16, row_index: u16) -> InputEvent {
+        InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: None,
+        })
+    }
+
+    fn make_items() -> Vec<MenuItem<TestAction>> {
+        vec![
+            MenuItem::action("Cut", TestAction::Cut).disabled(),
+            MenuItem::action("Copy", TestAction::Copy),
+            MenuItem::separator(),
+            MenuItem::submenu(
+                "Case",
+                vec![
+                    MenuItem::action("Upper", TestAction::Upper),
+                    MenuItem::action("Lower", TestAction::Lower),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_keyboard_navigation() {
+        let mut context_menu = ContextMenu::default();
+        assert_eq2!(
+            context_menu.apply_event(key(SpecialKey::Down)),
+            ContextMenuApplyResponse::Noop
+        );
+        assert!(!context_menu.open(position!(col_index: 0, row_index: 0), vec![]));
+        assert!(!context_menu.is_open());
+
+        // The disabled item is skipped.
+        assert!(context_menu.open(position!(col_index: 0, row_index: 0), make_items()));
+        assert_eq2!(context_menu.get_open_path(), &[1]);
+        context_menu.apply_event(key(SpecialKey::Down));
+        assert_eq2!(context_menu.get_open_path(), &[3]);
+
+        // Open the submenu, close it, and open it again.
+        context_menu.apply_event(key(SpecialKey::Right));
+        assert_eq2!(context_menu.get_open_path(), &[3, 0]);
+        context_menu.apply_event(key(SpecialKey::Esc));
+        assert_eq2!(context_menu.get_open_path(), &[3]);
+        context_menu.apply_event(key(SpecialKey::Enter));
+        context_menu.apply_event(key(SpecialKey::Down));
+        assert_eq2!(
+            context_menu.apply_event(key(SpecialKey::Enter)),
+            ContextMenuApplyResponse::Chosen(TestAction::Lower)
+        );
+        assert!(!context_menu.is_open());
+
+        context_menu.open(position!(col_index: 0, row_index: 0), make_items());
+        assert_eq2!(
+            context_menu.apply_event(key(SpecialKey::Esc)),
+            ContextMenuApplyResponse::Closed
+        );
+    }
+
+    #[test]
+    fn test_render_and_mouse() {
+        let window_size = size!(col_count: 40, row_count: 10);
+        let mut context_menu = ContextMenu::default();
+
+        // The menu is moved up to fit in the window.
+        context_menu.open(position!(col_index: 5, row_index: 8), make_items());
+        context_menu.apply_event(key(SpecialKey::Down));
+        context_menu.apply_event(key(SpecialKey::Right));
+        assert!(context_menu
+            .render(window_size)
+            .get(&ZOrder::Glass)
+            .is_some());
+        assert_eq2!(
+            context_menu.last_dropdowns,
+            vec![
+                (
+                    position!(col_index: 5, row_index: 4),
+                    size!(col_count: 11, row_count: 6)
+                ),
+                (
+                    position!(col_index: 16, row_index: 6),
+                    size!(col_count: 9, row_count: 4)
+                ),
+            ]
+        );
+
+        // Clicking on the disabled item does nothing, and clicking on an item chooses it.
+        assert_eq2!(
+            context_menu.apply_event(click(7, 5)),
+            ContextMenuApplyResponse::Consumed
+        );
+        assert_eq2!(
+            context_menu.apply_event(click(18, 7)),
+            ContextMenuApplyResponse::Chosen(TestAction::Upper)
+        );
+
+        // Clicking outside of the menu closes it.
+        context_menu.open(position!(col_index: 5, row_index: 0), make_items());
+        context_menu.render(window_size);
+        assert_eq2!(
+            context_menu.apply_event(click(30, 9)),
+            ContextMenuApplyResponse::Closed
+        );
+        assert!(context_menu
+            .render(window_size)
+            .get(&ZOrder::Glass)
+            .is_none());
+    }
+}
@@ -20,18 +20,45 @@ use std::fmt::{Debug, Formatter, Result};
 use r3bl_core::{ch, ChUnit};
 use serde::{Deserialize, Serialize};
 
-use crate::{format_option, EditorBuffer, DEFAULT_SYN_HI_FILE_EXT};
+use crate::{format_option,
+            DialogButton,
+            DialogColorPicker,
+            DialogDatePicker,
+            DialogFilePicker,
+            DialogFormField,
+            EditorBuffer,
+            Suggestion,
+            DEFAULT_SYN_HI_FILE_EXT};
 
 /// Please do not construct this struct directly and use [new_empty](DialogBuffer::new_empty)
 /// instead.
 ///
 /// Stores the data for a modal dialog. It contains the text content in an [EditorBuffer] and a
 /// title that is displayed.
+///
+/// In [crate::DialogEngineMode::ModalForm], the `form_fields` are shown (and edited)
+/// instead of the `editor_buffer`, see [DialogBuffer::new_form]. And in
+/// [crate::DialogEngineMode::ModalFilePicker], the `editor_buffer` filters the entries of
+/// the `maybe_file_picker`, see [DialogBuffer::new_file_picker]. And in
+/// [crate::DialogEngineMode::ModalConfirm], the `confirm_buttons` are shown below the
+/// title, see [DialogBuffer::new_confirm]. And in
+/// [crate::DialogEngineMode::ModalDatePicker], the month grid of the `maybe_date_picker`
+/// is shown below the title, see [DialogBuffer::new_date_picker]. And in
+/// [crate::DialogEngineMode::ModalColorPicker], the colors of the `maybe_color_picker`
+/// are shown below the title, see [DialogBuffer::new_color_picker].
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct DialogBuffer {
     pub editor_buffer: EditorBuffer,
     pub title: String,
     pub maybe_results: Option<Vec<String>>,
+    /// The suggestions that the `maybe_results` came from (if they came from a
+    /// [crate::SuggestionProvider]), which have the chars that matched the query.
+    pub maybe_suggestions: Option<Vec<Suggestion>>,
+    pub form_fields: Vec<DialogFormField>,
+    pub maybe_file_picker: Option<DialogFilePicker>,
+    pub confirm_buttons: Vec<DialogButton>,
+    pub maybe_date_picker: Option<DialogDatePicker>,
+    pub maybe_color_picker: Option<DialogColorPicker>,
 }
 
 impl DialogBuffer {
@@ -42,6 +69,13 @@ impl DialogBuffer {
             ch!(0)
         }
     }
+
+    /// Show the `suggestions` in the results panel, w/ the chars that matched the query
+    /// highlighted.
+    pub fn set_suggestions(&mut self, suggestions: Vec<Suggestion>) {
+        self.maybe_results = Some(suggestions.iter().map(|it| it.text.clone()).collect());
+        self.maybe_suggestions = Some(suggestions);
+    }
 }
 
 impl DialogBuffer {
@@ -53,6 +87,90 @@ impl DialogBuffer {
             ),
             title: Default::default(),
             maybe_results: None,
+            maybe_suggestions: None,
+            form_fields: vec![],
+            maybe_file_picker: None,
+            confirm_buttons: vec![],
+            maybe_date_picker: None,
+            maybe_color_picker: None,
+        }
+    }
+
+    pub fn new_form(title: impl Into<String>, form_fields: Vec<DialogFormField>) -> Self {
+        DialogBuffer {
+            title: title.into(),
+            form_fields,
+            ..Self::new_empty()
+        }
+    }
+
+    /// The `title` is the question that is asked, eg: "Save changes?". Use
+    /// [DialogButton::yes_no_cancel] for the usual buttons.
+    pub fn new_confirm(
+        title: impl Into<String>,
+        confirm_buttons: Vec<DialogButton>,
+    ) -> Self {
+        DialogBuffer {
+            title: title.into(),
+            confirm_buttons,
+            ..Self::new_empty()
+        }
+    }
+
+    /// The `title` is what the date is for, eg: "Due date".
+    pub fn new_date_picker(
+        title: impl Into<String>,
+        date_picker: DialogDatePicker,
+    ) -> Self {
+        DialogBuffer {
+            title: title.into(),
+            maybe_date_picker: Some(date_picker),
+            ..Self::new_empty()
+        }
+    }
+
+    /// The `title` is what the color is for, eg: "Border color".
+    pub fn new_color_picker(
+        title: impl Into<String>,
+        color_picker: DialogColorPicker,
+    ) -> Self {
+        DialogBuffer {
+            title: title.into(),
+            maybe_color_picker: Some(color_picker),
+            ..Self::new_empty()
+        }
+    }
+
+    pub fn new_file_picker(
+        title: impl Into<String>,
+        file_picker: DialogFilePicker,
+    ) -> Self {
+        let mut it = DialogBuffer {
+            title: title.into(),
+            maybe_file_picker: Some(file_picker),
+            ..Self::new_empty()
+        };
+        it.update_file_picker_results();
+        it
+    }
+
+    /// Show the entries of the `maybe_file_picker` that match the text in the editor (or
+    /// all of them, while a new folder is being named) in the results panel.
+    pub fn update_file_picker_results(&mut self) {
+        if let Some(file_picker) = &self.maybe_file_picker {
+            let filter = if file_picker.is_creating_folder {
+                String::new()
+            } else {
+                self.editor_buffer
+                    .get_as_string_with_comma_instead_of_newlines()
+            };
+            self.maybe_results = Some(
+                file_picker
+                    .get_visible_entries(&filter)
+                    .iter()
+                    .map(|it| it.get_display_name())
+                    .collect(),
+            );
         }
     }
 }
@@ -64,10 +182,20 @@ impl Debug for DialogBuffer {
           "\nDialogBuffer [      \n\
           ├ title: {}            \n\
           ├ maybe_results: {:?}  \n\
+          ├ form_fields: {:?}    \n\
+          ├ maybe_file_picker: {:?} \n\
+          ├ confirm_buttons: {:?} \n\
+          ├ maybe_date_picker: {:?} \n\
+          ├ maybe_color_picker: {:?} \n\
           └ editor_buffer: {}  \n\
           ]",
           self.title,
           maybe_results,
+          self.form_fields,
+          self.maybe_file_picker,
+          self.confirm_buttons,
+          self.maybe_date_picker,
+          self.maybe_color_picker,
           self.editor_buffer.get_as_string_with_comma_instead_of_newlines()
         }
     }
@@ -0,0 +1,335 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ANSIBasicColor, AnsiValue, RgbValue, TuiColor};
+use serde::{Deserialize, Serialize};
+
+/// The 16 ANSI colors that are shown in [DialogColorPickerTab::Basic], in the usual order
+/// (the dark ones in the 1st row, and the light ones in the 2nd), w/ their names.
+pub const DIALOG_COLOR_PICKER_BASIC_COLORS: [(ANSIBasicColor, &str); 16] = [
+    (ANSIBasicColor::Black, "Black"),
+    (ANSIBasicColor::DarkRed, "Dark red"),
+    (ANSIBasicColor::DarkGreen, "Dark green"),
+    (ANSIBasicColor::DarkYellow, "Dark yellow"),
+    (ANSIBasicColor::DarkBlue, "Dark blue"),
+    (ANSIBasicColor::DarkMagenta, "Dark magenta"),
+    (ANSIBasicColor::DarkCyan, "Dark cyan"),
+    (ANSIBasicColor::Grey, "Grey"),
+    (ANSIBasicColor::DarkGrey, "Dark grey"),
+    (ANSIBasicColor::Red, "Red"),
+    (ANSIBasicColor::Green, "Green"),
+    (ANSIBasicColor::Yellow, "Yellow"),
+    (ANSIBasicColor::Blue, "Blue"),
+    (ANSIBasicColor::Magenta, "Magenta"),
+    (ANSIBasicColor::Cyan, "Cyan"),
+    (ANSIBasicColor::White, "White"),
+];
+
+/// The number of colors in each row of the grid of [DialogColorPickerTab::Basic].
+pub const DIALOG_COLOR_PICKER_BASIC_ROW_LEN: usize = 8;
+
+/// The number of colors in each row of the grid of [DialogColorPickerTab::Ansi256].
+pub const DIALOG_COLOR_PICKER_ANSI256_ROW_LEN: usize = 16;
+
+/// The max number of chars that can be typed in [DialogColorPickerTab::Rgb].
+pub const DIALOG_COLOR_PICKER_RGB_INPUT_MAX_LEN: usize = 16;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogColorPickerTab {
+    /// The 16 ANSI colors, see [DIALOG_COLOR_PICKER_BASIC_COLORS].
+    #[default]
+    Basic,
+    /// The 256 ANSI colors, in a grid.
+    Ansi256,
+    /// A color that is typed in, eg: `#ff8000` or `255, 128, 0`.
+    Rgb,
+}
+
+impl DialogColorPickerTab {
+    pub const ALL: [DialogColorPickerTab; 3] = [Self::Basic, Self::Ansi256, Self::Rgb];
+
+    pub fn get_title(&self) -> &'static str {
+        match self {
+            Self::Basic => "16 colors",
+            Self::Ansi256 => "256 colors",
+            Self::Rgb => "RGB",
+        }
+    }
+}
+
+/// The state of a modal color picker, see [crate::DialogEngineMode::ModalColorPicker].
+/// It is saved in [crate::DialogBuffer::maybe_color_picker].
+///
+/// The colors are shown in tabs (see [DialogColorPickerTab]), and the keys are:
+/// - <kbd>Tab</kbd> & <kbd>Shift+Tab</kbd> show the next & previous tab.
+/// - The arrow keys select a color in the grid of the 16 (or 256) colors.
+/// - In the RGB tab, the color is typed in (as hex or as `R, G, B`), and
+///   <kbd>Backspace</kbd> deletes the last char.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogColorPicker {
+    pub tab: DialogColorPickerTab,
+    /// The index in [DIALOG_COLOR_PICKER_BASIC_COLORS].
+    pub selected_basic_index: usize,
+    pub selected_ansi256_index: u8,
+    pub rgb_input: String,
+    /// Why the `rgb_input` isn't a color (after <kbd>Enter</kbd> was pressed).
+    pub maybe_error: Option<String>,
+}
+
+impl DialogColorPicker {
+    pub fn new() -> Self { Self::default() }
+
+    /// Show the tab that has the `color`, w/ it selected, eg: the current color of the
+    /// setting that is being changed.
+    pub fn with_color(mut self, color: TuiColor) -> Self {
+        match color {
+            TuiColor::Basic(basic_color) => {
+                self.tab = DialogColorPickerTab::Basic;
+                self.selected_basic_index = DIALOG_COLOR_PICKER_BASIC_COLORS
+                    .iter()
+                    .position(|(it, _)| *it == basic_color)
+                    .unwrap_or_default();
+            }
+            TuiColor::Ansi(ansi_value) => {
+                self.tab = DialogColorPickerTab::Ansi256;
+                self.selected_ansi256_index = ansi_value.color;
+            }
+            TuiColor::Rgb(rgb_value) => {
+                self.tab = DialogColorPickerTab::Rgb;
+                self.rgb_input = get_hex_text(rgb_value);
+            }
+            TuiColor::Reset => {}
+        }
+        self
+    }
+
+    /// Returns the color that is selected in the current tab, or [None] if the
+    /// `rgb_input` isn't a color (in [DialogColorPickerTab::Rgb]).
+    pub fn get_selected_color(&self) -> Option<TuiColor> {
+        match self.tab {
+            DialogColorPickerTab::Basic => DIALOG_COLOR_PICKER_BASIC_COLORS
+                .get(self.selected_basic_index)
+                .map(|(it, _)| TuiColor::Basic(*it)),
+            DialogColorPickerTab::Ansi256 => {
+                Some(TuiColor::Ansi(AnsiValue::new(self.selected_ansi256_index)))
+            }
+            DialogColorPickerTab::Rgb => {
+                parse_rgb_input(&self.rgb_input).map(TuiColor::Rgb)
+            }
+        }
+    }
+
+    /// Show the next tab (or the previous one, if `delta` is negative), wrapping around.
+    pub fn select_next_tab(&mut self, delta: isize) {
+        let tab_count = DialogColorPickerTab::ALL.len() as isize;
+        let tab_index = DialogColorPickerTab::ALL
+            .iter()
+            .position(|it| *it == self.tab)
+            .unwrap_or_default() as isize;
+        self.tab =
+            DialogColorPickerTab::ALL[(tab_index + delta).rem_euclid(tab_count) as usize];
+        self.maybe_error = None;
+    }
+
+    /// Move the selection in the grid of the current tab by `col_delta` & `row_delta`
+    /// (stopping at the edges). Returns whether the selected color changed.
+    pub fn move_selection(&mut self, col_delta: isize, row_delta: isize) -> bool {
+        let (selected_index, color_count, row_len) = match self.tab {
+            DialogColorPickerTab::Basic => (
+                self.selected_basic_index,
+                DIALOG_COLOR_PICKER_BASIC_COLORS.len(),
+                DIALOG_COLOR_PICKER_BASIC_ROW_LEN,
+            ),
+            DialogColorPickerTab::Ansi256 => (
+                self.selected_ansi256_index as usize,
+                256,
+                DIALOG_COLOR_PICKER_ANSI256_ROW_LEN,
+            ),
+            DialogColorPickerTab::Rgb => return false,
+        };
+
+        let row_count = color_count / row_len;
+        let col_index = (selected_index % row_len) as isize + col_delta;
+        let row_index = (selected_index / row_len) as isize + row_delta;
+        let col_index = col_index.clamp(0, row_len as isize - 1) as usize;
+        let row_index = row_index.clamp(0, row_count as isize - 1) as usize;
+        let new_index = row_index * row_len + col_index;
+        if new_index == selected_index {
+            return false;
+        }
+
+        match self.tab {
+            DialogColorPickerTab::Basic => self.selected_basic_index = new_index,
+            _ => self.selected_ansi256_index = new_index as u8,
+        }
+        true
+    }
+
+    /// Type the `character` in the `rgb_input` (in [DialogColorPickerTab::Rgb]). Only
+    /// the chars that can be in a color are accepted. Returns whether it was typed.
+    pub fn insert_char(&mut self, character: char) -> bool {
+        let is_accepted = self.tab == DialogColorPickerTab::Rgb
+            && (character.is_ascii_hexdigit() || matches!(character, '#' | ',' | ' '))
+            && self.rgb_input.len() < DIALOG_COLOR_PICKER_RGB_INPUT_MAX_LEN;
+        if is_accepted {
+            self.rgb_input.push(character);
+            self.maybe_error = None;
+        }
+        is_accepted
+    }
+
+    /// Delete the last char of the `rgb_input` (in [DialogColorPickerTab::Rgb]). Returns
+    /// whether a char was deleted.
+    pub fn delete_char(&mut self) -> bool {
+        let is_deleted =
+            self.tab == DialogColorPickerTab::Rgb && self.rgb_input.pop().is_some();
+        if is_deleted {
+            self.maybe_error = None;
+        }
+        is_deleted
+    }
+}
+
+/// Returns the `rgb_value` as hex, eg: `#ff8000`.
+pub fn get_hex_text(rgb_value: RgbValue) -> String {
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        rgb_value.red, rgb_value.green, rgb_value.blue
+    )
+}
+
+/// Parse a color that is typed in as hex (w/ or w/out the `#`), eg: `#ff8000`, or as its
+/// red, green & blue values, eg: `255, 128, 0`.
+pub fn parse_rgb_input(input: &str) -> Option<RgbValue> {
+    let input = input.trim();
+
+    if input.contains(',') {
+        let values = input
+            .split(',')
+            .map(|it| it.trim().parse::<u8>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        return match values[..] {
+            [red, green, blue] => Some(RgbValue::from_u8(red, green, blue)),
+            _ => None,
+        };
+    }
+
+    let hex = input.strip_prefix('#').unwrap_or(input);
+    if hex.len() != 6 || !hex.chars().all(|it| it.is_ascii_hexdigit()) {
+        return None;
+    }
+    RgbValue::try_from_hex_color(&format!("#{hex}")).ok()
+}
+
+/// Returns the name of the `color` that is shown below the colors, eg: `Dark red`,
+/// `ANSI 208`, or `#ff8000`.
+pub fn get_color_description(color: TuiColor) -> String {
+    match color {
+        TuiColor::Basic(basic_color) => DIALOG_COLOR_PICKER_BASIC_COLORS
+            .iter()
+            .find(|(it, _)| *it == basic_color)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_default(),
+        TuiColor::Ansi(ansi_value) => format!("ANSI {}", ansi_value.color),
+        TuiColor::Rgb(rgb_value) => get_hex_text(rgb_value),
+        TuiColor::Reset => "Default".to_string(),
+    }
+}
+
+/// Returns black for light colors, and white for dark ones, so that text that is painted
+/// on the `color` can be read.
+pub fn get_contrast_color(color: TuiColor) -> TuiColor {
+    let rgb_value = match color {
+        TuiColor::Ansi(ansi_value) => RgbValue::from(ansi_value),
+        _ => RgbValue::try_from_tui_color(color).unwrap_or_default(),
+    };
+    let luminance = 0.299 * rgb_value.red as f32
+        + 0.587 * rgb_value.green as f32
+        + 0.114 * rgb_value.blue as f32;
+    match luminance > 128.0 {
+        true => TuiColor::Basic(ANSIBasicColor::Black),
+        false => TuiColor::Basic(ANSIBasicColor::White),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_select_colors_in_tabs() {
+        let mut color_picker = DialogColorPicker::new();
+        assert!(color_picker.move_selection(1, 1));
+        assert_eq2!(
+            color_picker.get_selected_color(),
+            Some(TuiColor::Basic(ANSIBasicColor::Red))
+        );
+        // Stops at the edges.
+        assert!(!color_picker.move_selection(0, 1));
+
+        color_picker.select_next_tab(1);
+        assert_eq2!(color_picker.tab, DialogColorPickerTab::Ansi256);
+        assert!(color_picker.move_selection(2, 13));
+        assert_eq2!(
+            color_picker.get_selected_color(),
+            Some(TuiColor::Ansi(AnsiValue::new(210)))
+        );
+
+        // Wraps around.
+        color_picker.select_next_tab(2);
+        assert_eq2!(color_picker.tab, DialogColorPickerTab::Basic);
+        color_picker.select_next_tab(-1);
+        assert_eq2!(color_picker.tab, DialogColorPickerTab::Rgb);
+        assert_eq2!(color_picker.get_selected_color(), None);
+
+        // The current color is selected.
+        let color_picker =
+            DialogColorPicker::new().with_color(TuiColor::Ansi(AnsiValue::new(57)));
+        assert_eq2!(color_picker.tab, DialogColorPickerTab::Ansi256);
+        assert_eq2!(color_picker.selected_ansi256_index, 57);
+    }
+
+    #[test]
+    fn test_rgb_input() {
+        let mut color_picker = DialogColorPicker::new();
+        color_picker.tab = DialogColorPickerTab::Rgb;
+        for character in "#FF80x00".chars() {
+            color_picker.insert_char(character);
+        }
+        assert_eq2!(color_picker.rgb_input, "#FF8000".to_string());
+        assert_eq2!(
+            color_picker.get_selected_color(),
+            Some(TuiColor::Rgb(RgbValue::from_u8(255, 128, 0)))
+        );
+
+        assert!(color_picker.delete_char());
+        assert_eq2!(color_picker.get_selected_color(), None);
+
+        assert_eq2!(
+            parse_rgb_input(" 255, 128,0 "),
+            Some(RgbValue::from_u8(255, 128, 0))
+        );
+        assert_eq2!(parse_rgb_input("255, 128"), None);
+        assert_eq2!(parse_rgb_input("256, 0, 0"), None);
+        assert_eq2!(
+            get_color_description(TuiColor::Rgb(RgbValue::from_u8(255, 128, 0))),
+            "#ff8000".to_string()
+        );
+    }
+}
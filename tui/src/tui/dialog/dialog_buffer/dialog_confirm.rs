@@ -0,0 +1,96 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use serde::{Deserialize, Serialize};
+
+/// The button that was pressed in a confirm dialog, see
+/// [crate::DialogEngineMode::ModalConfirm]. It is passed to the [crate::OnDialogPressFn]
+/// in [crate::DialogChoice::Confirm].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogConfirmChoice {
+    Yes,
+    No,
+    /// <kbd>Esc</kbd> was pressed, or the cancel button.
+    Cancel,
+    /// A custom button, w/ its label.
+    Button(String),
+}
+
+/// A button in a confirm dialog. The buttons are saved in
+/// [crate::DialogBuffer::confirm_buttons].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogButton {
+    pub label: String,
+    /// The key that presses this button (ignoring case), eg: `y` for Yes.
+    pub maybe_shortcut: Option<char>,
+    pub choice: DialogConfirmChoice,
+}
+
+impl DialogButton {
+    /// A custom button, which is passed as [DialogConfirmChoice::Button] w/ its `label`
+    /// when it is pressed.
+    pub fn new(label: impl Into<String>, maybe_shortcut: Option<char>) -> Self {
+        let label = label.into();
+        Self {
+            choice: DialogConfirmChoice::Button(label.clone()),
+            label,
+            maybe_shortcut,
+        }
+    }
+
+    pub fn yes() -> Self {
+        Self {
+            label: "Yes".to_string(),
+            maybe_shortcut: Some('y'),
+            choice: DialogConfirmChoice::Yes,
+        }
+    }
+
+    pub fn no() -> Self {
+        Self {
+            label: "No".to_string(),
+            maybe_shortcut: Some('n'),
+            choice: DialogConfirmChoice::No,
+        }
+    }
+
+    pub fn cancel() -> Self {
+        Self {
+            label: "Cancel".to_string(),
+            maybe_shortcut: None,
+            choice: DialogConfirmChoice::Cancel,
+        }
+    }
+
+    pub fn yes_no_cancel() -> Vec<Self> { vec![Self::yes(), Self::no(), Self::cancel()] }
+
+    /// The text that is painted for the button, eg: `[ Yes (y) ]`.
+    pub fn get_display_text(&self) -> String {
+        match self.maybe_shortcut {
+            Some(shortcut) => format!("[ {} ({shortcut}) ]", self.label),
+            None => format!("[ {} ]", self.label),
+        }
+    }
+
+    /// Whether the `key` is the shortcut of this button (ignoring case).
+    pub fn is_shortcut(&self, key: char) -> bool {
+        match self.maybe_shortcut {
+            Some(shortcut) => shortcut.to_lowercase().eq(key.to_lowercase()),
+            None => false,
+        }
+    }
+}
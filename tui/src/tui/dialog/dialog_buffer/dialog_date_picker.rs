@@ -0,0 +1,273 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// The number of cols that each day takes in the month grid of a [DialogDatePicker] (2
+/// for the day, and 1 for the space before the next one).
+pub const DIALOG_DATE_PICKER_DAY_COL_COUNT: usize = 3;
+
+/// The width of the month grid of a [DialogDatePicker] (w/out the space after the last
+/// day of the week).
+pub const DIALOG_DATE_PICKER_GRID_COL_COUNT: usize =
+    DIALOG_DATE_PICKER_DAY_COL_COUNT * 7 - 1;
+
+/// A week in the month grid of a [DialogDatePicker], w/ [None] for the days that are in
+/// the previous (or next) month.
+pub type DialogDatePickerWeek = [Option<NaiveDate>; 7];
+
+/// The state of a modal date picker, see [crate::DialogEngineMode::ModalDatePicker]. It is
+/// saved in [crate::DialogBuffer::maybe_date_picker].
+///
+/// The month of the `selected_date` is shown as a grid (one row per week), and the keys
+/// are:
+/// - <kbd>Left</kbd> & <kbd>Right</kbd> select the previous & next day.
+/// - <kbd>Up</kbd> & <kbd>Down</kbd> select the same day in the previous & next week.
+/// - <kbd>PageUp</kbd> & <kbd>PageDown</kbd> select the same day in the previous & next
+///   month (or the last day of that month, if it is shorter).
+/// - <kbd>Home</kbd> & <kbd>End</kbd> select the first & last day of the month.
+///
+/// The dates before `maybe_min_date` (or after `maybe_max_date`) can't be selected.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogDatePicker {
+    pub selected_date: NaiveDate,
+    pub maybe_min_date: Option<NaiveDate>,
+    pub maybe_max_date: Option<NaiveDate>,
+    /// The day that the weeks in the month grid start on.
+    pub first_weekday: Weekday,
+}
+
+impl DialogDatePicker {
+    pub fn new(selected_date: NaiveDate) -> Self {
+        Self {
+            selected_date,
+            maybe_min_date: None,
+            maybe_max_date: None,
+            first_weekday: Weekday::Mon,
+        }
+    }
+
+    /// The `selected_date` is moved to `min_date` if it is before it.
+    pub fn with_min_date(mut self, min_date: NaiveDate) -> Self {
+        self.maybe_min_date = Some(min_date);
+        self.selected_date = self.clamp(self.selected_date);
+        self
+    }
+
+    /// The `selected_date` is moved to `max_date` if it is after it.
+    pub fn with_max_date(mut self, max_date: NaiveDate) -> Self {
+        self.maybe_max_date = Some(max_date);
+        self.selected_date = self.clamp(self.selected_date);
+        self
+    }
+
+    pub fn with_first_weekday(mut self, first_weekday: Weekday) -> Self {
+        self.first_weekday = first_weekday;
+        self
+    }
+
+    /// Whether the `date` is between the min & max dates (if any).
+    pub fn is_selectable(&self, date: NaiveDate) -> bool {
+        !self.maybe_min_date.is_some_and(|it| date < it)
+            && !self.maybe_max_date.is_some_and(|it| date > it)
+    }
+
+    fn clamp(&self, date: NaiveDate) -> NaiveDate {
+        let date = match self.maybe_min_date {
+            Some(min_date) if date < min_date => min_date,
+            _ => date,
+        };
+        match self.maybe_max_date {
+            Some(max_date) if date > max_date => max_date,
+            _ => date,
+        }
+    }
+
+    /// Select the `date` (or the closest one that can be selected). Returns whether the
+    /// selected date changed.
+    pub fn select(&mut self, date: NaiveDate) -> bool {
+        let date = self.clamp(date);
+        if date == self.selected_date {
+            return false;
+        }
+        self.selected_date = date;
+        true
+    }
+
+    /// Select the date that is `days` after (or before, if it is negative) the selected
+    /// one. Returns whether the selected date changed.
+    pub fn move_by_days(&mut self, days: i64) -> bool {
+        match self.selected_date.checked_add_signed(Duration::days(days)) {
+            Some(date) => self.select(date),
+            None => false,
+        }
+    }
+
+    /// Select the same day `months` after (or before, if it is negative) the selected
+    /// one, or the last day of that month if it is shorter. Returns whether the selected
+    /// date changed.
+    pub fn move_by_months(&mut self, months: i32) -> bool {
+        let maybe_date = match months >= 0 {
+            true => self
+                .selected_date
+                .checked_add_months(Months::new(months.unsigned_abs())),
+            false => self
+                .selected_date
+                .checked_sub_months(Months::new(months.unsigned_abs())),
+        };
+        match maybe_date {
+            Some(date) => self.select(date),
+            None => false,
+        }
+    }
+
+    /// Select the first day of the month of the selected date.
+    pub fn move_to_start_of_month(&mut self) -> bool {
+        self.select(self.get_first_day_of_month())
+    }
+
+    /// Select the last day of the month of the selected date.
+    pub fn move_to_end_of_month(&mut self) -> bool {
+        match self.get_days_of_month().last() {
+            Some(date) => self.select(*date),
+            None => false,
+        }
+    }
+
+    /// The title that is painted above the month grid, eg: `October 2026`.
+    pub fn get_month_title(&self) -> String {
+        self.selected_date.format("%B %Y").to_string()
+    }
+
+    /// The names of the days of the week that are painted above the month grid, in the
+    /// order that they are painted in, eg: `["Mo", "Tu", .., "Su"]`.
+    pub fn get_weekday_titles(&self) -> Vec<String> {
+        let mut weekday = self.first_weekday;
+        let mut it = vec![];
+        for _ in 0..7 {
+            it.push(weekday.to_string().chars().take(2).collect());
+            weekday = weekday.succ();
+        }
+        it
+    }
+
+    /// Returns the weeks of the month of the selected date (4 to 6 of them).
+    pub fn get_weeks(&self) -> Vec<DialogDatePickerWeek> {
+        let mut it = vec![];
+        let mut week: DialogDatePickerWeek = [None; 7];
+        for date in self.get_days_of_month() {
+            let weekday_index = (date.weekday().num_days_from_monday() + 7
+                - self.first_weekday.num_days_from_monday())
+                % 7;
+            week[weekday_index as usize] = Some(date);
+            if weekday_index == 6 {
+                it.push(week);
+                week = [None; 7];
+            }
+        }
+        if week.iter().any(Option::is_some) {
+            it.push(week);
+        }
+        it
+    }
+
+    fn get_first_day_of_month(&self) -> NaiveDate {
+        self.selected_date.with_day(1).unwrap_or(self.selected_date)
+    }
+
+    fn get_days_of_month(&self) -> Vec<NaiveDate> {
+        let first_day = self.get_first_day_of_month();
+        first_day
+            .iter_days()
+            .take_while(|it| it.month() == first_day.month())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_month_grid() {
+        // October 2026 starts on a Thursday, and has 31 days.
+        let date_picker = DialogDatePicker::new(date(2026, 10, 16));
+        assert_eq2!(date_picker.get_month_title(), "October 2026".to_string());
+        assert_eq2!(
+            date_picker.get_weekday_titles(),
+            vec!["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
+        );
+
+        let weeks = date_picker.get_weeks();
+        assert_eq2!(weeks.len(), 5);
+        assert_eq2!(
+            weeks[0],
+            [
+                None,
+                None,
+                None,
+                Some(date(2026, 10, 1)),
+                Some(date(2026, 10, 2)),
+                Some(date(2026, 10, 3)),
+                Some(date(2026, 10, 4)),
+            ]
+        );
+        assert_eq2!(weeks[4][5], Some(date(2026, 10, 31)));
+        assert_eq2!(weeks[4][6], None);
+
+        // Weeks that start on Sunday.
+        let date_picker = date_picker.with_first_weekday(Weekday::Sun);
+        assert_eq2!(date_picker.get_weekday_titles()[0], "Su".to_string());
+        assert_eq2!(date_picker.get_weeks()[0][4], Some(date(2026, 10, 1)));
+    }
+
+    #[test]
+    fn test_move_w_min_and_max_dates() {
+        let mut date_picker = DialogDatePicker::new(date(2026, 1, 31))
+            .with_min_date(date(2026, 1, 10))
+            .with_max_date(date(2026, 3, 15));
+
+        // The last day of February.
+        assert!(date_picker.move_by_months(1));
+        assert_eq2!(date_picker.selected_date, date(2026, 2, 28));
+
+        // Clamped to the max date.
+        assert!(date_picker.move_by_months(1));
+        assert_eq2!(date_picker.selected_date, date(2026, 3, 15));
+        assert!(!date_picker.move_by_days(7));
+        assert!(!date_picker.move_to_end_of_month());
+
+        // Clamped to the min date.
+        assert!(date_picker.move_by_months(-2));
+        assert_eq2!(date_picker.selected_date, date(2026, 1, 15));
+        assert!(date_picker.move_to_start_of_month());
+        assert_eq2!(date_picker.selected_date, date(2026, 1, 10));
+        assert!(!date_picker.is_selectable(date(2026, 1, 9)));
+
+        // The selected date is moved into the range.
+        let date_picker =
+            DialogDatePicker::new(date(2026, 5, 1)).with_max_date(date(2026, 4, 30));
+        assert_eq2!(date_picker.selected_date, date(2026, 4, 30));
+    }
+}
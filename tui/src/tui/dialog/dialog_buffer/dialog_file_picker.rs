@@ -0,0 +1,295 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fs,
+          path::{Path, PathBuf}};
+
+use serde::{Deserialize, Serialize};
+
+/// What a [DialogFilePicker] is used to pick, which changes what <kbd>Enter</kbd> does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogFilePickerKind {
+    /// <kbd>Enter</kbd> on a file picks it (eg: for Open).
+    #[default]
+    OpenFile,
+    /// <kbd>Enter</kbd> picks the file w/ the name that was typed in the current folder
+    /// (or the selected file, if nothing was typed), eg: for Save As.
+    SaveFile,
+    /// Only folders are listed, and <kbd>Enter</kbd> on `./` picks the current folder.
+    PickFolder,
+}
+
+/// An item in the listing of the current folder of a [DialogFilePicker].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogFilePickerEntry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+impl DialogFilePickerEntry {
+    pub const PARENT_DIR: &'static str = "..";
+    pub const CURRENT_DIR: &'static str = ".";
+
+    /// The text that is shown in the results panel, folders end w/ a `/`.
+    pub fn get_display_name(&self) -> String {
+        if self.is_dir {
+            format!("{}/", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// The state of a modal file (or folder) picker, see
+/// [crate::DialogEngineMode::ModalFilePicker]. It is saved in
+/// [crate::DialogBuffer::maybe_file_picker].
+///
+/// The entries of the `current_dir` are shown in the results panel of the dialog (folders
+/// first), and the text in the editor filters them. The keys are:
+/// - <kbd>Up</kbd> & <kbd>Down</kbd> select an entry.
+/// - <kbd>Enter</kbd> on a folder (or `../`) opens it, see [DialogFilePickerKind] for
+///   what it does otherwise.
+/// - <kbd>Ctrl+t</kbd> shows (or hides) the hidden files.
+/// - <kbd>Ctrl+n</kbd> creates a new folder, w/ the name that is typed next.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogFilePicker {
+    pub kind: DialogFilePickerKind,
+    pub current_dir: PathBuf,
+    pub show_hidden: bool,
+    /// Whether the text in the editor is the name of a new folder (instead of a filter).
+    pub is_creating_folder: bool,
+    /// The entries of the `current_dir`, w/out the ones that are hidden.
+    entries: Vec<DialogFilePickerEntry>,
+    /// The error from the last time the `current_dir` was read (or a folder was created).
+    pub maybe_error: Option<String>,
+}
+
+impl DialogFilePicker {
+    pub fn new(kind: DialogFilePickerKind, current_dir: impl Into<PathBuf>) -> Self {
+        let mut it = Self {
+            kind,
+            current_dir: current_dir.into(),
+            show_hidden: false,
+            is_creating_folder: false,
+            entries: vec![],
+            maybe_error: None,
+        };
+        it.read_current_dir();
+        it
+    }
+
+    /// Read the entries of the `current_dir` again (eg: after it changed on disk). If it
+    /// can't be read then there are no entries, and the error is saved in `maybe_error`.
+    pub fn read_current_dir(&mut self) {
+        self.entries.clear();
+        self.maybe_error = None;
+
+        let read_dir = match fs::read_dir(&self.current_dir) {
+            Ok(it) => it,
+            Err(error) => {
+                self.maybe_error = Some(format!(
+                    "Can't read {}: {error}",
+                    self.current_dir.display()
+                ));
+                return;
+            }
+        };
+
+        for dir_entry in read_dir.flatten() {
+            let name = dir_entry.file_name().to_string_lossy().to_string();
+            if !self.show_hidden && name.starts_with('.') {
+                continue;
+            }
+            // Follow symlinks, so that a link to a folder can be opened.
+            let is_dir = dir_entry.path().is_dir();
+            if self.kind == DialogFilePickerKind::PickFolder && !is_dir {
+                continue;
+            }
+            self.entries.push(DialogFilePickerEntry { name, is_dir });
+        }
+
+        // Folders first, then by name (ignoring case).
+        self.entries.sort_by(|lhs, rhs| {
+            rhs.is_dir
+                .cmp(&lhs.is_dir)
+                .then_with(|| lhs.name.to_lowercase().cmp(&rhs.name.to_lowercase()))
+        });
+    }
+
+    /// Returns the entries that are shown for the `filter` (the ones whose name contains
+    /// it, ignoring case), after `../` (if the `current_dir` has a parent) and `./` (when
+    /// picking a folder).
+    pub fn get_visible_entries(&self, filter: &str) -> Vec<DialogFilePickerEntry> {
+        let mut it = vec![];
+
+        if self.kind == DialogFilePickerKind::PickFolder {
+            it.push(DialogFilePickerEntry {
+                name: DialogFilePickerEntry::CURRENT_DIR.to_string(),
+                is_dir: true,
+            });
+        }
+
+        if self.current_dir.parent().is_some() {
+            it.push(DialogFilePickerEntry {
+                name: DialogFilePickerEntry::PARENT_DIR.to_string(),
+                is_dir: true,
+            });
+        }
+
+        let filter = filter.trim().to_lowercase();
+        it.extend(
+            self.entries
+                .iter()
+                .filter(|entry| entry.name.to_lowercase().contains(&filter))
+                .cloned(),
+        );
+
+        it
+    }
+
+    /// Open the folder w/ the `name` in the `current_dir` (or its parent for `..`).
+    pub fn open_dir(&mut self, name: &str) {
+        let new_dir = match name {
+            DialogFilePickerEntry::PARENT_DIR => match self.current_dir.parent() {
+                Some(it) => it.to_path_buf(),
+                None => return,
+            },
+            _ => self.current_dir.join(name),
+        };
+        self.current_dir = new_dir;
+        self.read_current_dir();
+    }
+
+    pub fn toggle_show_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        self.read_current_dir();
+    }
+
+    /// Create a folder w/ the `name` in the `current_dir`, and read it again (so that
+    /// the new folder is listed). If it can't be created then [None] is returned, and
+    /// the error is saved in `maybe_error`.
+    pub fn create_dir(&mut self, name: &str) -> Option<PathBuf> {
+        let name = name.trim();
+        if name.is_empty() || name.contains(std::path::MAIN_SEPARATOR) {
+            self.maybe_error = Some(format!("Invalid folder name: {name:?}"));
+            return None;
+        }
+
+        let path = self.current_dir.join(name);
+        if let Err(error) = fs::create_dir(&path) {
+            self.maybe_error = Some(format!("Can't create {}: {error}", path.display()));
+            return None;
+        }
+
+        self.read_current_dir();
+        Some(path)
+    }
+
+    pub fn get_path(&self, name: &str) -> PathBuf {
+        match name {
+            DialogFilePickerEntry::CURRENT_DIR => self.current_dir.clone(),
+            _ => self.current_dir.join(name),
+        }
+    }
+
+    pub fn get_current_dir(&self) -> &Path { &self.current_dir }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    /// Each test gets its own folder, since tests run in parallel.
+    fn make_temp_dir(test_name: &str) -> PathBuf {
+        let it = std::env::temp_dir().join(format!(
+            "r3bl_tui_dialog_file_picker_{test_name}_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&it);
+        fs::create_dir_all(it.join("src")).unwrap();
+        fs::write(it.join("Cargo.toml"), "").unwrap();
+        fs::write(it.join("README.md"), "").unwrap();
+        fs::write(it.join(".gitignore"), "").unwrap();
+        it
+    }
+
+    fn get_display_names(file_picker: &DialogFilePicker, filter: &str) -> Vec<String> {
+        file_picker
+            .get_visible_entries(filter)
+            .iter()
+            .map(|it| it.get_display_name())
+            .collect()
+    }
+
+    #[test]
+    fn test_list_and_filter() {
+        let temp_dir = make_temp_dir("list_and_filter");
+        let mut file_picker =
+            DialogFilePicker::new(DialogFilePickerKind::OpenFile, &temp_dir);
+
+        assert_eq2!(
+            get_display_names(&file_picker, ""),
+            vec!["../", "src/", "Cargo.toml", "README.md"]
+        );
+        assert_eq2!(
+            get_display_names(&file_picker, "read"),
+            vec!["../", "README.md"]
+        );
+
+        file_picker.toggle_show_hidden();
+        assert_eq2!(
+            get_display_names(&file_picker, ""),
+            vec!["../", "src/", ".gitignore", "Cargo.toml", "README.md"]
+        );
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_open_and_create_dir() {
+        let temp_dir = make_temp_dir("open_and_create_dir");
+        let mut file_picker =
+            DialogFilePicker::new(DialogFilePickerKind::PickFolder, &temp_dir);
+        assert_eq2!(
+            get_display_names(&file_picker, ""),
+            vec!["./", "../", "src/"]
+        );
+
+        file_picker.open_dir("src");
+        assert_eq2!(
+            file_picker.get_current_dir(),
+            temp_dir.join("src").as_path()
+        );
+        assert_eq2!(file_picker.get_path("."), temp_dir.join("src"));
+
+        let new_dir = file_picker.create_dir("new").unwrap();
+        assert!(new_dir.is_dir());
+        assert_eq2!(
+            get_display_names(&file_picker, ""),
+            vec!["./", "../", "new/"]
+        );
+        assert!(file_picker.create_dir("new").is_none());
+        assert!(file_picker.maybe_error.is_some());
+
+        file_picker.open_dir("..");
+        assert_eq2!(file_picker.get_current_dir(), temp_dir.as_path());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
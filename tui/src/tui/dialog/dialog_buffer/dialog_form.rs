@@ -0,0 +1,160 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::{EditorBuffer, DEFAULT_SYN_HI_FILE_EXT};
+
+/// A labeled field in a form dialog, see [crate::DialogEngineMode::ModalForm]. The fields
+/// are saved in [crate::DialogBuffer::form_fields].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DialogFormField {
+    pub label: String,
+    pub kind: DialogFormFieldKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DialogFormFieldKind {
+    /// A single line of text, that is edited w/ the editor engine.
+    Text(EditorBuffer),
+    /// Toggled w/ <kbd>Space</kbd>.
+    Checkbox(bool),
+    /// One of the `options`, which is changed w/ <kbd>Left</kbd> & <kbd>Right</kbd> (or
+    /// <kbd>Space</kbd>).
+    Select {
+        options: Vec<String>,
+        selected_index: usize,
+    },
+}
+
+impl DialogFormField {
+    pub fn new_text(label: impl Into<String>, text: &str) -> Self {
+        let mut editor_buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        if !text.is_empty() {
+            editor_buffer.set_lines(vec![text.to_string()]);
+        }
+        Self {
+            label: label.into(),
+            kind: DialogFormFieldKind::Text(editor_buffer),
+        }
+    }
+
+    pub fn new_checkbox(label: impl Into<String>, is_checked: bool) -> Self {
+        Self {
+            label: label.into(),
+            kind: DialogFormFieldKind::Checkbox(is_checked),
+        }
+    }
+
+    pub fn new_select(
+        label: impl Into<String>,
+        options: Vec<String>,
+        selected_index: usize,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            kind: DialogFormFieldKind::Select {
+                options,
+                selected_index,
+            },
+        }
+    }
+
+    pub fn get_value(&self) -> DialogFormValue {
+        match &self.kind {
+            DialogFormFieldKind::Text(editor_buffer) => DialogFormValue::Text(
+                editor_buffer.get_as_string_with_comma_instead_of_newlines(),
+            ),
+            DialogFormFieldKind::Checkbox(is_checked) => {
+                DialogFormValue::Checkbox(*is_checked)
+            }
+            DialogFormFieldKind::Select {
+                options,
+                selected_index,
+            } => DialogFormValue::Select(options.get(*selected_index).cloned()),
+        }
+    }
+}
+
+/// The value of a [DialogFormField], when the form is submitted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogFormValue {
+    Text(String),
+    Checkbox(bool),
+    /// [None] if the select doesn't have any options.
+    Select(Option<String>),
+}
+
+/// The values of all the fields of a form dialog (in order, w/ their labels), which are
+/// passed to the [crate::OnDialogPressFn] in [crate::DialogChoice::Form] when the form is
+/// submitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogFormValues {
+    pub values: Vec<(String, DialogFormValue)>,
+}
+
+impl DialogFormValues {
+    pub fn from_fields(form_fields: &[DialogFormField]) -> Self {
+        Self {
+            values: form_fields
+                .iter()
+                .map(|it| (it.label.clone(), it.get_value()))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, label: &str) -> Option<&DialogFormValue> {
+        self.values
+            .iter()
+            .find(|(it, _)| it == label)
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_text(&self, label: &str) -> Option<&str> {
+        match self.get(label)? {
+            DialogFormValue::Text(it) => Some(it),
+            _ => None,
+        }
+    }
+
+    pub fn get_checkbox(&self, label: &str) -> Option<bool> {
+        match self.get(label)? {
+            DialogFormValue::Checkbox(it) => Some(*it),
+            _ => None,
+        }
+    }
+
+    pub fn get_select(&self, label: &str) -> Option<&str> {
+        match self.get(label)? {
+            DialogFormValue::Select(it) => it.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Returned by a [DialogFormValidatorFn] when the form can't be submitted. The error is
+/// shown in the dialog, and the field w/ the `label` gets the focus.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogFormError {
+    pub label: String,
+    pub message: String,
+}
+
+/// Called w/ the values of the form when <kbd>Enter</kbd> is pressed. The form is only
+/// submitted if this returns [Ok]. See [crate::DialogComponent::new_form_boxed].
+pub type DialogFormValidatorFn = fn(&DialogFormValues) -> Result<(), DialogFormError>;
@@ -17,6 +17,16 @@
 
 // Attach.
 pub mod dialog_buffer_struct;
+pub mod dialog_color_picker;
+pub mod dialog_confirm;
+pub mod dialog_date_picker;
+pub mod dialog_file_picker;
+pub mod dialog_form;
 
 // Re-export.
 pub use dialog_buffer_struct::*;
+pub use dialog_color_picker::*;
+pub use dialog_confirm::*;
+pub use dialog_date_picker::*;
+pub use dialog_file_picker::*;
+pub use dialog_form::*;
@@ -26,6 +26,8 @@ use crate::{Component,
             DialogEngineApplyResponse,
             DialogEngineArgs,
             DialogEngineConfigOptions,
+            DialogEngineMode,
+            DialogFormValidatorFn,
             EditorEngineConfig,
             EventPropagation,
             FlexBox,
@@ -37,7 +39,9 @@ use crate::{Component,
             OnDialogEditorChangeFn,
             OnDialogPressFn,
             RenderPipeline,
+            SuggestionRequester,
             SurfaceBounds,
+            SystemClipboard,
             DEBUG_TUI_MOD};
 
 /// This is a shim which allows the reusable [DialogEngine] to be used in the context of
@@ -71,7 +75,7 @@ where
 impl<S, AS> Component<S, AS> for DialogComponent<S, AS>
 where
     S: Debug + Default + Clone + Sync + Send + HasDialogBuffers,
-    AS: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send + 'static,
 {
     fn reset(&mut self) { self.data.dialog_engine.reset(); }
 
@@ -145,6 +149,7 @@ where
         let GlobalData {
             state,
             main_thread_channel_sender,
+            pending_clipboard_contents,
             ..
         } = global_data;
 
@@ -166,10 +171,15 @@ where
                     id,
                     dialog_engine,
                     input_event,
+                    &mut SystemClipboard::new(pending_clipboard_contents.clone()),
                 )? {
                     // Handler user's choice.
                     DialogEngineApplyResponse::DialogChoice(dialog_choice) => {
-                        has_focus.reset_modal_id();
+                        // Restore focus to the modal below this one (if any), or to the
+                        // non modal component.
+                        if has_focus.is_modal_id(id) {
+                            has_focus.reset_modal_id();
+                        }
 
                         call_if_true!(DEBUG_TUI_MOD, {
                             tracing::debug!(
@@ -193,6 +203,22 @@ where
 
                     // Handler user input that has updated the dialog_buffer.editor_buffer.
                     DialogEngineApplyResponse::UpdateEditorBuffer => {
+                        // Ask for the suggestions for the new text (if there's a
+                        // provider).
+                        if let Some(suggestion_requester) =
+                            &mut dialog_engine.maybe_suggestion_requester
+                        {
+                            if let Some(dialog_buffer) = state.get_mut_dialog_buffer(id) {
+                                suggestion_requester.request(
+                                    dialog_buffer
+                                        .editor_buffer
+                                        .get_as_string_with_comma_instead_of_newlines(),
+                                    id,
+                                    main_thread_channel_sender.clone(),
+                                );
+                            }
+                        }
+
                         // Run the handler (if any) w/ `new_editor_buffer`.
                         if let Some(it) = &on_dialog_editor_change_handler {
                             it(state, &mut main_thread_channel_sender.clone());
@@ -208,6 +234,26 @@ where
                         Ok(EventPropagation::ConsumedRender)
                     }
 
+                    // Handle user input that has moved the focus in (or changed) the form.
+                    DialogEngineApplyResponse::UpdateForm => {
+                        Ok(EventPropagation::ConsumedRender)
+                    }
+
+                    // Handle user input that has moved the focus between the buttons.
+                    DialogEngineApplyResponse::FocusButton => {
+                        Ok(EventPropagation::ConsumedRender)
+                    }
+
+                    // Handle user input that has selected another date.
+                    DialogEngineApplyResponse::SelectDate => {
+                        Ok(EventPropagation::ConsumedRender)
+                    }
+
+                    // Handle user input that has selected (or typed) another color.
+                    DialogEngineApplyResponse::SelectColor => {
+                        Ok(EventPropagation::ConsumedRender)
+                    }
+
                     // All else.
                     _ => Ok(EventPropagation::Propagate),
                 }
@@ -267,4 +313,168 @@ where
         );
         Box::new(it)
     }
+
+    /// Create a dialog in [DialogEngineMode::ModalForm] (regardless of the mode in
+    /// `dialog_options`), that shows the [crate::DialogBuffer::form_fields]. When the
+    /// form is submitted, the on_dialog_press_handler is called w/
+    /// [crate::DialogChoice::Form], but only if the `maybe_form_validator` (if any)
+    /// returns [Ok] for its values.
+    pub fn new_form_boxed(
+        id: FlexBoxId,
+        dialog_options: DialogEngineConfigOptions,
+        editor_options: EditorEngineConfig,
+        on_dialog_press_handler: OnDialogPressFn<S, AS>,
+        on_dialog_editor_change_handler: OnDialogEditorChangeFn<S, AS>,
+        maybe_form_validator: Option<DialogFormValidatorFn>,
+    ) -> Box<Self> {
+        let mut it = DialogComponent::new(
+            id,
+            DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalForm,
+                ..dialog_options
+            },
+            editor_options,
+            on_dialog_press_handler,
+            on_dialog_editor_change_handler,
+        );
+        it.data.dialog_engine.maybe_form_validator = maybe_form_validator;
+        Box::new(it)
+    }
+
+    /// Create a dialog in [DialogEngineMode::ModalAutocomplete] (regardless of the mode in
+    /// `dialog_options`), whose results panel shows the suggestions from the
+    /// [crate::SuggestionProvider] of the `suggestion_requester`, which is asked for them
+    /// each time the text in the editor changes.
+    pub fn new_autocomplete_boxed(
+        id: FlexBoxId,
+        dialog_options: DialogEngineConfigOptions,
+        editor_options: EditorEngineConfig,
+        on_dialog_press_handler: OnDialogPressFn<S, AS>,
+        on_dialog_editor_change_handler: OnDialogEditorChangeFn<S, AS>,
+        suggestion_requester: SuggestionRequester,
+    ) -> Box<Self> {
+        let mut it = DialogComponent::new(
+            id,
+            DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalAutocomplete,
+                ..dialog_options
+            },
+            editor_options,
+            on_dialog_press_handler,
+            on_dialog_editor_change_handler,
+        );
+        it.data.dialog_engine.maybe_suggestion_requester = Some(suggestion_requester);
+        Box::new(it)
+    }
+
+    /// Create a dialog in [DialogEngineMode::ModalFilePicker] (regardless of the mode in
+    /// `dialog_options`), that shows the [crate::DialogBuffer::maybe_file_picker] (create
+    /// the dialog buffer w/ [crate::DialogBuffer::new_file_picker]). When a file (or
+    /// folder) is picked, the on_dialog_press_handler is called w/ its path in
+    /// [crate::DialogChoice::Yes].
+    pub fn new_file_picker_boxed(
+        id: FlexBoxId,
+        dialog_options: DialogEngineConfigOptions,
+        editor_options: EditorEngineConfig,
+        on_dialog_press_handler: OnDialogPressFn<S, AS>,
+        on_dialog_editor_change_handler: OnDialogEditorChangeFn<S, AS>,
+    ) -> Box<Self> {
+        DialogComponent::new_boxed(
+            id,
+            DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalFilePicker,
+                ..dialog_options
+            },
+            editor_options,
+            on_dialog_press_handler,
+            on_dialog_editor_change_handler,
+        )
+    }
+
+    /// Create a dialog in [DialogEngineMode::ModalConfirm] (regardless of the mode in
+    /// `dialog_options`), that asks the [crate::DialogBuffer::title] w/ the
+    /// [crate::DialogBuffer::confirm_buttons] (create the dialog buffer w/
+    /// [crate::DialogBuffer::new_confirm]). When a button is pressed, the
+    /// on_dialog_press_handler is called w/ [crate::DialogChoice::Confirm].
+    pub fn new_confirm_boxed(
+        id: FlexBoxId,
+        dialog_options: DialogEngineConfigOptions,
+        on_dialog_press_handler: OnDialogPressFn<S, AS>,
+    ) -> Box<Self> {
+        let dialog_engine = DialogEngine::new(
+            DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalConfirm,
+                ..dialog_options
+            },
+            // There's no editor in a confirm dialog.
+            EditorEngineConfig::default(),
+        );
+        Box::new(Self {
+            data: DialogComponentData {
+                id,
+                dialog_engine,
+                on_dialog_press_handler: Some(on_dialog_press_handler),
+                on_dialog_editor_change_handler: None,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Create a dialog in [DialogEngineMode::ModalDatePicker] (regardless of the mode in
+    /// `dialog_options`), that shows the month grid of the
+    /// [crate::DialogBuffer::maybe_date_picker] (create the dialog buffer w/
+    /// [crate::DialogBuffer::new_date_picker]). When a date is picked, the
+    /// on_dialog_press_handler is called w/ [crate::DialogChoice::Date].
+    pub fn new_date_picker_boxed(
+        id: FlexBoxId,
+        dialog_options: DialogEngineConfigOptions,
+        on_dialog_press_handler: OnDialogPressFn<S, AS>,
+    ) -> Box<Self> {
+        let dialog_engine = DialogEngine::new(
+            DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalDatePicker,
+                ..dialog_options
+            },
+            // There's no editor in a date picker dialog.
+            EditorEngineConfig::default(),
+        );
+        Box::new(Self {
+            data: DialogComponentData {
+                id,
+                dialog_engine,
+                on_dialog_press_handler: Some(on_dialog_press_handler),
+                on_dialog_editor_change_handler: None,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Create a dialog in [DialogEngineMode::ModalColorPicker] (regardless of the mode in
+    /// `dialog_options`), that shows the colors of the
+    /// [crate::DialogBuffer::maybe_color_picker] (create the dialog buffer w/
+    /// [crate::DialogBuffer::new_color_picker]). When a color is picked, the
+    /// on_dialog_press_handler is called w/ [crate::DialogChoice::Color].
+    pub fn new_color_picker_boxed(
+        id: FlexBoxId,
+        dialog_options: DialogEngineConfigOptions,
+        on_dialog_press_handler: OnDialogPressFn<S, AS>,
+    ) -> Box<Self> {
+        let dialog_engine = DialogEngine::new(
+            DialogEngineConfigOptions {
+                mode: DialogEngineMode::ModalColorPicker,
+                ..dialog_options
+            },
+            // The RGB input is edited by the color picker itself.
+            EditorEngineConfig::default(),
+        );
+        Box::new(Self {
+            data: DialogComponentData {
+                id,
+                dialog_engine,
+                on_dialog_press_handler: Some(on_dialog_press_handler),
+                on_dialog_editor_change_handler: None,
+                ..Default::default()
+            },
+        })
+    }
 }
@@ -38,7 +38,7 @@ mod dialog_event_impl {
         /// - Enter and Esc are also matched against to return [DialogEvent::EnterPressed] and
         ///   [DialogEvent::EscPressed]
         /// - Otherwise, [Err] is returned.
-        pub fn from(input_event: InputEvent) -> Self {
+        pub fn from(input_event: &InputEvent) -> Self {
             if let InputEvent::Keyboard(keypress) = input_event {
                 match keypress {
                     // Compare to `Enter`.
@@ -74,14 +74,14 @@ mod test_dialog_event {
     #[test]
     fn dialog_event_handles_enter() {
         let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
-        let dialog_event = DialogEvent::from(input_event);
+        let dialog_event = DialogEvent::from(&input_event);
         assert_eq2!(dialog_event, DialogEvent::EnterPressed);
     }
 
     #[test]
     fn dialog_event_handles_esc() {
         let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
-        let dialog_event = DialogEvent::from(input_event);
+        let dialog_event = DialogEvent::from(&input_event);
         assert_eq2!(dialog_event, DialogEvent::EscPressed);
     }
 }
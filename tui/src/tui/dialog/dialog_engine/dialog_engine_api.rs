@@ -17,11 +17,15 @@
 
 use std::fmt::Debug;
 
+use chrono::Datelike;
 use r3bl_core::{ch,
                 percent,
                 position,
                 size,
                 throws_with_return,
+                ANSIBasicColor,
+                AnsiValue,
+                ChUnit,
                 ColorWheel,
                 CommonError,
                 CommonErrorType,
@@ -30,46 +34,75 @@ use r3bl_core::{ch,
                 Position,
                 Size,
                 TextColorizationPolicy,
+                TuiColor,
                 TuiStyle,
                 UnicodeString,
                 SPACER};
 
-use crate::{render_ops,
+use crate::{get_color_description,
+            get_contrast_color,
+            keypress,
+            render_ops,
             render_pipeline,
             render_tui_styled_texts_into,
             BorderGlyphCharacter,
+            ClipboardService,
             DialogBuffer,
             DialogChoice,
+            DialogColorPickerTab,
+            DialogConfirmChoice,
             DialogEngine,
             DialogEngineArgs,
             DialogEngineConfigOptions,
             DialogEngineMode,
             DialogEvent,
+            DialogFilePicker,
+            DialogFilePickerEntry,
+            DialogFilePickerKind,
+            DialogFormField,
+            DialogFormFieldKind,
+            DialogFormValues,
             EditorEngineApi,
             EditorEngineApplyEventResult,
             EventPropagation,
             FlexBox,
             FlexBoxId,
+            FloatingBoxAnchor,
             GlobalData,
             HasDialogBuffers,
             InputEvent,
             Key,
             KeyPress,
             MinSize,
+            ModifierKeysMask,
             PartialFlexBox,
             RenderOp,
             RenderOps,
             RenderPipeline,
             SpecialKey,
+            Suggestion,
             SurfaceBounds,
-            SystemClipboard,
-            ZOrder};
+            ZOrder,
+            DIALOG_COLOR_PICKER_ANSI256_ROW_LEN,
+            DIALOG_COLOR_PICKER_BASIC_COLORS,
+            DIALOG_COLOR_PICKER_BASIC_ROW_LEN,
+            DIALOG_DATE_PICKER_DAY_COL_COUNT,
+            DIALOG_DATE_PICKER_GRID_COL_COUNT};
 
 #[derive(Debug)]
 pub enum DialogEngineApplyResponse {
     UpdateEditorBuffer,
     DialogChoice(DialogChoice),
     SelectScrollResultsPanel,
+    /// The focus moved to another field of the form, or a checkbox or select field was
+    /// changed, or the form wasn't submitted because it isn't valid.
+    UpdateForm,
+    /// The focus moved to another button of the confirm dialog.
+    FocusButton,
+    /// Another date was selected in the date picker.
+    SelectDate,
+    /// Another color (or tab) was selected in the color picker, or its RGB input changed.
+    SelectColor,
     Noop,
 }
 
@@ -95,14 +128,33 @@ impl DialogEngineApi {
         // Unpack global data.
         let GlobalData { state, .. } = global_data;
 
+        // Show the suggestions that arrived since the last render (if any).
+        if let Some(suggestions) = dialog_engine
+            .maybe_suggestion_requester
+            .as_ref()
+            .and_then(|it| it.take_suggestions())
+        {
+            if let Some(dialog_buffer) = state.get_mut_dialog_buffer(self_id) {
+                dialog_buffer.set_suggestions(suggestions);
+                dialog_engine.reset();
+            }
+        }
+
         let mode = dialog_engine.dialog_options.mode;
+        let form_field_count = state
+            .get_mut_dialog_buffer(self_id)
+            .map(|it| it.form_fields.len())
+            .unwrap_or_default();
         let overlay_flex_box: PartialFlexBox = {
             let window_size = global_data.window_size;
             match &dialog_engine.maybe_flex_box {
                 // No need to calculate new flex box if:
-                // 1) there's an existing one & 2) the window size hasn't changed.
+                // 1) there's an existing one & 2) the window size hasn't changed &
+                // 3) it isn't a form (whose fields can change between renders).
                 Some((saved_size, saved_mode, saved_box))
-                    if *saved_size == window_size && saved_mode == &mode =>
+                    if *saved_size == window_size
+                        && saved_mode == &mode
+                        && mode != DialogEngineMode::ModalForm =>
                 {
                     *saved_box
                 }
@@ -113,6 +165,7 @@ impl DialogEngineApi {
                         dialog_engine.dialog_options,
                         window_size,
                         dialog_engine.maybe_surface_bounds,
+                        form_field_count,
                     )?;
 
                     dialog_engine
@@ -135,27 +188,30 @@ impl DialogEngineApi {
                 internal_impl::render_border(&origin_pos, &bounds_size, dialog_engine),
             );
 
-            // Paint title.
-            let title = if let Some(dialog_buffer) = state.get_mut_dialog_buffer(self_id)
-            {
-                &dialog_buffer.title
-            } else {
-                "N/A"
+            // Paint title (w/ the current folder of the file picker, if any).
+            let title = match state.get_mut_dialog_buffer(self_id) {
+                Some(DialogBuffer {
+                    title,
+                    maybe_file_picker: Some(file_picker),
+                    ..
+                }) => format!("{title}: {}", file_picker.current_dir.display()),
+                Some(dialog_buffer) => dialog_buffer.title.clone(),
+                None => "N/A".to_string(),
             };
             it.push(
                 ZOrder::Glass,
                 internal_impl::render_title(
                     &origin_pos,
                     &bounds_size,
-                    title,
+                    &title,
                     dialog_engine,
                 ),
             );
 
-            // Call render_results_panel() if mode is autocomplete.
+            // Call render_results_panel() if mode is autocomplete (or file picker).
             if matches!(
                 dialog_engine.dialog_options.mode,
-                DialogEngineMode::ModalAutocomplete
+                DialogEngineMode::ModalAutocomplete | DialogEngineMode::ModalFilePicker
             ) {
                 let results_panel_ops = internal_impl::render_results_panel(
                     &origin_pos,
@@ -169,16 +225,29 @@ impl DialogEngineApi {
                 }
             }
 
-            it += internal_impl::render_editor(
-                &origin_pos,
-                &bounds_size,
-                DialogEngineArgs {
-                    self_id,
-                    global_data,
-                    dialog_engine,
-                    has_focus,
-                },
-            )?;
+            let args = DialogEngineArgs {
+                self_id,
+                global_data,
+                dialog_engine,
+                has_focus,
+            };
+            it += match mode {
+                DialogEngineMode::ModalForm => {
+                    internal_impl::render_form(&origin_pos, &bounds_size, args)?
+                }
+                DialogEngineMode::ModalConfirm => internal_impl::render_confirm_buttons(
+                    &origin_pos,
+                    &bounds_size,
+                    args,
+                )?,
+                DialogEngineMode::ModalDatePicker => {
+                    internal_impl::render_date_picker(&origin_pos, &bounds_size, args)?
+                }
+                DialogEngineMode::ModalColorPicker => {
+                    internal_impl::render_color_picker(&origin_pos, &bounds_size, args)?
+                }
+                _ => internal_impl::render_editor(&origin_pos, &bounds_size, args)?,
+            };
 
             it
         };
@@ -191,20 +260,49 @@ impl DialogEngineApi {
     /// - [DialogEngineApplyResponse::DialogChoice] => <kbd>Enter</kbd> or <kbd>Esc</kbd> was
     ///   pressed.
     /// - [DialogEngineApplyResponse::UpdateEditorBuffer] => the editor buffer was updated.
+    /// - [DialogEngineApplyResponse::UpdateForm] => the focus moved between the fields of
+    ///   the form, or a field that isn't text was changed.
+    /// - [DialogEngineApplyResponse::FocusButton] => the focus moved between the buttons
+    ///   of the confirm dialog.
+    /// - [DialogEngineApplyResponse::SelectDate] => another date was selected in the
+    ///   date picker.
+    /// - [DialogEngineApplyResponse::SelectColor] => another color was selected (or
+    ///   typed) in the color picker.
     /// - [DialogEngineApplyResponse::Noop] => otherwise.
     pub fn apply_event<S, AS>(
         mut_state: &mut S,
         self_id: FlexBoxId,
         dialog_engine: &mut DialogEngine,
         input_event: InputEvent,
+        clipboard_service_provider: &mut impl ClipboardService,
     ) -> CommonResult<DialogEngineApplyResponse>
     where
         S: Debug + Default + Clone + Sync + Send + HasDialogBuffers,
         AS: Debug + Default + Clone + Sync + Send,
     {
+        // Is the form being submitted w/ values that aren't valid?
+        if let EventPropagation::ConsumedRender =
+            internal_impl::try_handle_form_validation(
+                &input_event,
+                mut_state.get_mut_dialog_buffer(self_id),
+                dialog_engine,
+            )
+        {
+            return Ok(DialogEngineApplyResponse::UpdateForm);
+        }
+
+        // Was a key for the file picker pressed (eg: to open a folder)?
+        if let Some(response) = internal_impl::try_handle_file_picker(
+            &input_event,
+            mut_state.get_mut_dialog_buffer(self_id),
+            dialog_engine,
+        ) {
+            return Ok(response);
+        }
+
         // Was a dialog choice made?
         if let Some(choice) = internal_impl::try_handle_dialog_choice(
-            input_event,
+            &input_event,
             mut_state.get_mut_dialog_buffer(self_id),
             dialog_engine,
         ) {
@@ -212,9 +310,46 @@ impl DialogEngineApi {
             return Ok(DialogEngineApplyResponse::DialogChoice(choice));
         }
 
+        // Was a button of the confirm dialog pressed (or focused)?
+        if let Some(response) = internal_impl::try_handle_confirm_buttons(
+            &input_event,
+            mut_state.get_mut_dialog_buffer(self_id),
+            dialog_engine,
+        ) {
+            return Ok(response);
+        }
+
+        // Was another date selected in the date picker?
+        if let Some(response) = internal_impl::try_handle_date_picker(
+            &input_event,
+            mut_state.get_mut_dialog_buffer(self_id),
+            dialog_engine,
+        ) {
+            return Ok(response);
+        }
+
+        // Was another color selected (or typed) in the color picker?
+        if let Some(response) = internal_impl::try_handle_color_picker(
+            &input_event,
+            mut_state.get_mut_dialog_buffer(self_id),
+            dialog_engine,
+        ) {
+            return Ok(response);
+        }
+
+        // Was the focus moved between the fields of the form, or a field changed?
+        if let EventPropagation::ConsumedRender = internal_impl::try_handle_form_field(
+            &input_event,
+            mut_state.get_mut_dialog_buffer(self_id),
+            dialog_engine,
+        ) {
+            dialog_engine.maybe_form_error = None;
+            return Ok(DialogEngineApplyResponse::UpdateForm);
+        }
+
         // Was up / down pressed to select autocomplete results & vert scroll the results panel?
         if let EventPropagation::ConsumedRender = internal_impl::try_handle_up_down(
-            input_event,
+            &input_event,
             mut_state.get_mut_dialog_buffer(self_id),
             dialog_engine,
         ) {
@@ -232,16 +367,37 @@ impl DialogEngineApi {
             }
         };
 
+        // In a form, the event goes to the text field that has focus (if any).
+        let editor_buffer = match dialog_engine.dialog_options.mode {
+            DialogEngineMode::ModalForm => {
+                let focused_index = ch!(@to_usize dialog_engine.selected_row_index);
+                match dialog_buffer.form_fields.get_mut(focused_index) {
+                    Some(DialogFormField {
+                        kind: DialogFormFieldKind::Text(it),
+                        ..
+                    }) => it,
+                    _ => return Ok(DialogEngineApplyResponse::Noop),
+                }
+            }
+            _ => &mut dialog_buffer.editor_buffer,
+        };
+
         let result = EditorEngineApi::apply_event(
-            &mut dialog_buffer.editor_buffer,
+            editor_buffer,
             &mut dialog_engine.editor_engine,
             input_event,
-            &mut SystemClipboard,
+            clipboard_service_provider,
         )?;
 
         match result {
             // If the editor engine applied the event, return the new editor buffer.
             EditorEngineApplyEventResult::Applied => {
+                dialog_engine.maybe_form_error = None;
+                // The filter of the file picker changed.
+                if dialog_buffer.maybe_file_picker.is_some() {
+                    dialog_buffer.update_file_picker_results();
+                    dialog_engine.reset();
+                }
                 Ok(DialogEngineApplyResponse::UpdateEditorBuffer)
             }
             _ =>
@@ -259,8 +415,15 @@ pub enum DisplayConstants {
     DialogComponentBorderWidthPercent = 90,
     /// border-top, title, input, border-bottom.
     SimpleModalRowCount = 4,
+    /// The min width of the column that the values of the fields of a form are painted
+    /// in (the labels are clipped to make room for it).
+    FormValueMinColCount = 10,
     EmptyLine = 1,
     DefaultResultsPanelRowCount = 5,
+    /// border-top, title, month, days of the week, 6 weeks, border-bottom.
+    DatePickerModalRowCount = 11,
+    /// border-top, title, tabs, 8 rows of colors, preview, border-bottom.
+    ColorPickerModalRowCount = 13,
 }
 
 mod internal_impl {
@@ -273,6 +436,7 @@ mod internal_impl {
     ///   boundaries of the layout engine and really just paints itself on top of everything. It can
     ///   reach any corner of the screen.
     ///   - In autocomplete mode it sizes itself differently than in normal mode.
+    ///   - In form mode it has a row for each of the `form_field_count` fields.
     /// - However, it is still constrained by the bounds of the [Surface] itself and does not take
     ///   into account the full window size (in case these are different). This only applies if a
     ///   [Surface] is passed in as an argument.
@@ -290,6 +454,7 @@ mod internal_impl {
         dialog_options: DialogEngineConfigOptions,
         window_size: Size,
         maybe_surface_bounds: Option<SurfaceBounds>,
+        form_field_count: usize,
     ) -> CommonResult<PartialFlexBox> {
         let surface_size = if let Some(surface_bounds) = maybe_surface_bounds {
             surface_bounds.box_size
@@ -317,8 +482,8 @@ mod internal_impl {
             );
         }
 
-        let (origin_pos, bounds_size) = match dialog_options.mode {
-            DialogEngineMode::ModalSimple => {
+        let bounds_size = match dialog_options.mode {
+            DialogEngineMode::ModalSimple | DialogEngineMode::ModalConfirm => {
                 let simple_dialog_size = {
                     // Calc dialog bounds size based on window size.
                     let col_count = {
@@ -333,20 +498,9 @@ mod internal_impl {
                     size
                 };
 
-                let origin_pos = {
-                    // Calc origin position based on window size & dialog size.
-                    let origin_col =
-                        surface_size.col_count / 2 - simple_dialog_size.col_count / 2;
-                    let origin_row =
-                        surface_size.row_count / 2 - simple_dialog_size.row_count / 2;
-                    let mut it = position!(col_index: origin_col, row_index: origin_row);
-                    it += surface_origin_pos;
-                    it
-                };
-
-                (origin_pos, simple_dialog_size)
+                simple_dialog_size
             }
-            DialogEngineMode::ModalAutocomplete => {
+            DialogEngineMode::ModalAutocomplete | DialogEngineMode::ModalFilePicker => {
                 let autocomplete_dialog_size = {
                     // Calc dialog bounds size based on window size.
                     let row_count = ch!(DisplayConstants::SimpleModalRowCount as u16)
@@ -363,21 +517,54 @@ mod internal_impl {
                     size
                 };
 
-                let origin_pos = {
-                    // Calc origin position based on window size & dialog size.
-                    let origin_col = surface_size.col_count / 2
-                        - autocomplete_dialog_size.col_count / 2;
-                    let origin_row = surface_size.row_count / 2
-                        - autocomplete_dialog_size.row_count / 2;
-                    let mut it = position!(col_index: origin_col, row_index: origin_row);
-                    it += surface_origin_pos;
-                    it
+                autocomplete_dialog_size
+            }
+            DialogEngineMode::ModalForm => {
+                // border-top, title, one row per field, error (or hint), border-bottom.
+                // It is clipped to the surface if there are too many fields.
+                let row_count = std::cmp::min(
+                    ch!(DisplayConstants::SimpleModalRowCount as u16)
+                        + ch!(form_field_count),
+                    surface_size.row_count,
+                );
+                let col_count = {
+                    let percent = percent!(
+                        DisplayConstants::DialogComponentBorderWidthPercent as u16
+                    )?;
+                    percent.calc_percentage(surface_size.col_count)
                 };
-
-                (origin_pos, autocomplete_dialog_size)
+                size!(col_count: col_count, row_count: row_count)
+            }
+            DialogEngineMode::ModalDatePicker | DialogEngineMode::ModalColorPicker => {
+                // It is clipped to the surface if it is too short.
+                let row_count = match dialog_options.mode {
+                    DialogEngineMode::ModalColorPicker => {
+                        DisplayConstants::ColorPickerModalRowCount
+                    }
+                    _ => DisplayConstants::DatePickerModalRowCount,
+                };
+                let row_count =
+                    std::cmp::min(ch!(row_count as u16), surface_size.row_count);
+                let col_count = {
+                    let percent = percent!(
+                        DisplayConstants::DialogComponentBorderWidthPercent as u16
+                    )?;
+                    percent.calc_percentage(surface_size.col_count)
+                };
+                size!(col_count: col_count, row_count: row_count)
             }
         };
 
+        // The dialog is a floating box that is centered in the surface.
+        let origin_pos = FloatingBoxAnchor::CenteredInSurface.calc_origin_pos(
+            bounds_size,
+            SurfaceBounds {
+                origin_pos: surface_origin_pos,
+                box_size: surface_size,
+            },
+            None,
+        );
+
         throws_with_return!({
             PartialFlexBox {
                 id: dialog_id,
@@ -450,7 +637,22 @@ mod internal_impl {
                 == ""
         {
             let mut ops = render_ops!();
-            let msg = "Press <Esc> to close, or <Enter> to accept".to_string();
+            let msg = match &dialog_buffer.maybe_file_picker {
+                Some(DialogFilePicker {
+                    maybe_error: Some(error),
+                    ..
+                }) => error.clone(),
+                Some(DialogFilePicker {
+                    is_creating_folder: true,
+                    ..
+                }) => "Type the name of the new folder, <Enter> to create it, or <Esc> \
+                       to cancel"
+                    .to_string(),
+                Some(_) => "Type to filter, <Ctrl+t> to show hidden files, <Ctrl+n> for \
+                            a new folder"
+                    .to_string(),
+                None => "Press <Esc> to close, or <Enter> to accept".to_string(),
+            };
 
             ops.push(RenderOp::ResetColor);
             ops.push(RenderOp::MoveCursorPositionAbs(
@@ -475,173 +677,873 @@ mod internal_impl {
         Ok(pipeline)
     }
 
-    pub fn render_results_panel<S>(
+    /// Paint a row for each of the [DialogBuffer::form_fields], w/ its label on the left
+    /// & its value on the right. The text field that has focus is painted by the editor
+    /// engine (so that it shows the caret), and the other fields are painted as text. The
+    /// row after the fields shows the error from the validator, or a hint.
+    pub fn render_form<S, AS>(
         origin_pos: &Position,
         bounds_size: &Size,
-        dialog_engine: &DialogEngine,
-        self_id: FlexBoxId,
-        state: &mut S,
-    ) -> CommonResult<RenderOps>
+        args: DialogEngineArgs<'_, S, AS>,
+    ) -> CommonResult<RenderPipeline>
     where
-        S: Default + Clone + Debug + Sync + Send + HasDialogBuffers,
+        S: Debug + Default + Clone + Sync + Send + HasDialogBuffers,
+        AS: Debug + Default + Clone + Sync + Send,
     {
-        let mut it = render_ops!();
-
-        if let Some(dialog_buffer) = state.get_mut_dialog_buffer(self_id) {
-            if let Some(results) = dialog_buffer.maybe_results.as_ref() {
-                if !results.is_empty() {
-                    paint_results(
-                        &mut it,
-                        origin_pos,
-                        bounds_size,
-                        results,
-                        dialog_engine,
-                    );
-                };
-            }
-        };
-
-        return Ok(it);
-
-        pub fn paint_results(
-            ops: &mut RenderOps,
-            origin_pos: &Position,
-            bounds_size: &Size,
-            results: &[String],
-            dialog_engine: &DialogEngine,
-        ) {
-            let col_start_index = ch!(1);
-            let row_start_index =
-                ch!(DisplayConstants::SimpleModalRowCount as u16) - ch!(1);
-
-            let mut rel_insertion_pos =
-                position!(col_index: col_start_index, row_index: row_start_index);
+        let DialogEngineArgs {
+            self_id,
+            global_data,
+            dialog_engine,
+            has_focus,
+        } = args;
 
-            let scroll_offset_row_index = dialog_engine.scroll_offset_row_index;
-            let selected_row_index = dialog_engine.selected_row_index;
+        let window_size = global_data.window_size;
+        let GlobalData { state, .. } = global_data;
 
-            // Print results panel.
-            for (row_index, item) in results.iter().enumerate() {
-                let row_index = ch!(row_index);
+        let style = dialog_engine
+            .dialog_options
+            .maybe_style_editor
+            .unwrap_or_default();
 
-                // Skip rows that are above the scroll offset.
-                if row_index < scroll_offset_row_index {
-                    continue;
+        let dialog_buffer = {
+            let it = state.get_mut_dialog_buffer(self_id);
+            match it {
+                Some(it) => it,
+                None => {
+                    return CommonError::new_error_result(
+                        CommonErrorType::NotFound,
+                        &format!(
+                            "Dialog buffer does not exist for component id:{}",
+                            self_id
+                        ),
+                    )
                 }
+            }
+        };
 
-                rel_insertion_pos.add_row(1);
-
-                let text = UnicodeString::from(item.as_str());
-                let max_display_col_count = bounds_size.col_count - 2;
-                let clipped_text = if text.display_width > max_display_col_count {
-                    let snip_len = ch!(2); /* `..` */
-                    let postfix_len = ch!(5); /* last 5 characters */
+        let inner_col_count = bounds_size.col_count - 2;
+        let label_col_count = {
+            let max_label_width = dialog_buffer
+                .form_fields
+                .iter()
+                .map(|it| UnicodeString::from(it.label.as_str()).display_width)
+                .max()
+                .unwrap_or(ch!(0));
+            // Leave room for `: ` after the label, and for the value.
+            std::cmp::min(
+                max_label_width + ch!(2),
+                inner_col_count
+                    - std::cmp::min(
+                        inner_col_count,
+                        ch!(DisplayConstants::FormValueMinColCount as u16),
+                    ),
+            )
+        };
+        let value_col_count = inner_col_count - label_col_count;
 
-                    let lhs_start_index = ch!(0);
-                    let lhs_end_index = max_display_col_count - postfix_len - snip_len;
-                    let lhs = text.clip_to_width(lhs_start_index, lhs_end_index);
+        // The last field is painted above the error (or hint) row & the bottom border.
+        let max_row_index = origin_pos.row_index + bounds_size.row_count - 2;
+        let focused_index = ch!(@to_usize dialog_engine.selected_row_index);
 
-                    let rhs_start_index = text.display_width - postfix_len;
-                    let rhs_end_index = text.display_width;
-                    let rhs = text.clip_to_width(rhs_start_index, rhs_end_index);
+        let mut pipeline = render_pipeline!();
+        let mut ops = render_ops!();
 
-                    format!("{lhs}..{rhs}")
-                } else {
-                    text.string
-                };
+        for (index, field) in dialog_buffer.form_fields.iter_mut().enumerate() {
+            let row_index = origin_pos.row_index + 2 + ch!(index);
+            if row_index >= max_row_index {
+                break;
+            }
 
-                let max_display_row_count =
-                    /* Viewport height: */ dialog_engine.dialog_options.result_panel_display_row_count +
-                    /* Scroll offset: */ scroll_offset_row_index;
-                if row_index >= max_display_row_count {
-                    break;
-                }
+            let is_focused = index == focused_index;
 
-                ops.push(RenderOp::ResetColor);
-                ops.push(RenderOp::MoveCursorPositionRelTo(
-                    *origin_pos,
-                    rel_insertion_pos,
-                ));
+            // Paint the label.
+            let label = UnicodeString::from(format!("{}: ", field.label));
+            ops.push(RenderOp::ResetColor);
+            ops.push(RenderOp::MoveCursorPositionAbs(position!(
+                col_index: origin_pos.col_index + 1,
+                row_index: row_index
+            )));
+            ops.push(RenderOp::PaintTextWithAttributes(
+                label
+                    .truncate_to_fit_size(size!(col_count: label_col_count, row_count: 1))
+                    .to_string(),
+                Some(TuiStyle {
+                    bold: is_focused,
+                    ..style
+                }),
+            ));
 
-                // Set style to underline if selected row & paint.
-                match selected_row_index.eq(&row_index) {
-                    // This is the selected row.
-                    true => {
-                        let my_selected_style = match dialog_engine
-                            .dialog_options
-                            .maybe_style_results_panel
-                        {
-                            // Update existing style.
-                            Some(style) => TuiStyle {
-                                underline: true,
-                                ..style
-                            },
-                            // No existing style, so create a new style w/ only underline.
-                            _ => TuiStyle {
-                                underline: true,
-                                ..Default::default()
-                            },
-                        }
-                        .into();
-                        // Paint the text for the row.
-                        ops.push(RenderOp::ApplyColors(my_selected_style));
-                        ops.push(RenderOp::PaintTextWithAttributes(
-                            clipped_text,
-                            my_selected_style,
-                        ));
-                    }
-                    // Regular row, not selected.
-                    false => {
-                        // Paint the text for the row.
-                        ops.push(RenderOp::ApplyColors(
-                            dialog_engine.dialog_options.maybe_style_results_panel,
-                        ));
-                        ops.push(RenderOp::PaintTextWithAttributes(
-                            clipped_text,
-                            dialog_engine.dialog_options.maybe_style_results_panel,
-                        ));
+            // Paint the value.
+            let value_pos = position!(
+                col_index: origin_pos.col_index + 1 + label_col_count,
+                row_index: row_index
+            );
+            match &mut field.kind {
+                DialogFormFieldKind::Text(editor_buffer) if is_focused => {
+                    let flex_box: FlexBox = PartialFlexBox {
+                        id: self_id,
+                        style_adjusted_origin_pos: value_pos,
+                        style_adjusted_bounds_size: size!(col_count: value_col_count, row_count: 1),
+                        maybe_computed_style: dialog_engine.dialog_options.maybe_style_editor,
                     }
+                    .into();
+                    let mut editor_pipeline = EditorEngineApi::render_engine(
+                        &mut dialog_engine.editor_engine,
+                        editor_buffer,
+                        flex_box,
+                        has_focus,
+                        window_size,
+                    )?;
+                    editor_pipeline.hoist(ZOrder::Normal, ZOrder::Glass);
+                    pipeline += editor_pipeline;
+                }
+                kind => {
+                    let text = match kind {
+                        DialogFormFieldKind::Text(editor_buffer) => {
+                            editor_buffer.get_as_string_with_comma_instead_of_newlines()
+                        }
+                        DialogFormFieldKind::Checkbox(is_checked) => {
+                            (if *is_checked { "[x]" } else { "[ ]" }).to_string()
+                        }
+                        DialogFormFieldKind::Select {
+                            options,
+                            selected_index,
+                        } => format!(
+                            "< {} >",
+                            options
+                                .get(*selected_index)
+                                .map(String::as_str)
+                                .unwrap_or_default()
+                        ),
+                    };
+                    let text = UnicodeString::from(text);
+                    ops.push(RenderOp::ResetColor);
+                    ops.push(RenderOp::MoveCursorPositionAbs(value_pos));
+                    ops.push(RenderOp::PaintTextWithAttributes(
+                        text.truncate_to_fit_size(
+                            size!(col_count: value_col_count, row_count: 1),
+                        )
+                        .to_string(),
+                        Some(TuiStyle {
+                            underline: is_focused,
+                            ..style
+                        }),
+                    ));
                 }
             }
         }
-    }
-
-    pub fn render_title(
-        origin_pos: &Position,
-        bounds_size: &Size,
-        title: &str,
-        dialog_engine: &mut DialogEngine,
-    ) -> RenderOps {
-        let mut ops = render_ops!();
-
-        let row_pos = position!(col_index: origin_pos.col_index + 1, row_index: origin_pos.row_index + 1);
-
-        let title_us = UnicodeString::from(title);
-        let text_content = title_us.truncate_to_fit_size(size! {
-          col_count: bounds_size.col_count - 2, row_count: bounds_size.row_count
-        });
 
+        // Paint the error (or hint).
+        let (msg, msg_style) = match &dialog_engine.maybe_form_error {
+            Some(error) => (
+                error.clone(),
+                TuiStyle {
+                    color_fg: Some(TuiColor::Basic(ANSIBasicColor::Red)),
+                    ..style
+                },
+            ),
+            None => (
+                "Press <Tab> to move, <Esc> to close, or <Enter> to submit".to_string(),
+                TuiStyle { dim: true, ..style },
+            ),
+        };
+        let msg = UnicodeString::from(msg);
         ops.push(RenderOp::ResetColor);
-        ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
-        ops.push(RenderOp::ApplyColors(
-            dialog_engine.dialog_options.maybe_style_title,
+        ops.push(RenderOp::MoveCursorPositionAbs(position!(
+            col_index: origin_pos.col_index + 1,
+            row_index: max_row_index
+        )));
+        ops.push(RenderOp::PaintTextWithAttributes(
+            msg.truncate_to_fit_size(size!(col_count: inner_col_count, row_count: 1))
+                .to_string(),
+            Some(msg_style),
         ));
 
-        // Apply lolcat override (if enabled) to the fg_color of text_content.
-        lolcat_from_style(
-            &mut ops,
-            &mut dialog_engine.color_wheel,
-            &dialog_engine.dialog_options.maybe_style_title,
-            text_content,
-        );
+        pipeline.push(ZOrder::Glass, ops);
 
-        ops
+        Ok(pipeline)
     }
 
-    /// Only Colorizes text in-place if [Style]'s `lolcat` field is true. Otherwise leaves `text`
-    /// alone.
-    fn lolcat_from_style(
-        ops: &mut RenderOps,
+    /// Paint the [DialogBuffer::confirm_buttons] in the row below the title (centered),
+    /// w/ the one that has focus in reverse video.
+    pub fn render_confirm_buttons<S, AS>(
+        origin_pos: &Position,
+        bounds_size: &Size,
+        args: DialogEngineArgs<'_, S, AS>,
+    ) -> CommonResult<RenderPipeline>
+    where
+        S: Debug + Default + Clone + Sync + Send + HasDialogBuffers,
+        AS: Debug + Default + Clone + Sync + Send,
+    {
+        let DialogEngineArgs {
+            self_id,
+            global_data,
+            dialog_engine,
+            ..
+        } = args;
+
+        let GlobalData { state, .. } = global_data;
+
+        let dialog_buffer = {
+            let it = state.get_mut_dialog_buffer(self_id);
+            match it {
+                Some(it) => it,
+                None => {
+                    return CommonError::new_error_result(
+                        CommonErrorType::NotFound,
+                        &format!(
+                            "Dialog buffer does not exist for component id:{}",
+                            self_id
+                        ),
+                    )
+                }
+            }
+        };
+
+        let style = dialog_engine
+            .dialog_options
+            .maybe_style_editor
+            .unwrap_or_default();
+        let focused_index = ch!(@to_usize dialog_engine.selected_row_index);
+        let button_texts: Vec<UnicodeString> = dialog_buffer
+            .confirm_buttons
+            .iter()
+            .map(|it| UnicodeString::from(it.get_display_text()))
+            .collect();
+
+        // Center the buttons (w/ a space between them) in the row.
+        let inner_col_count = bounds_size.col_count - 2;
+        let total_col_count = button_texts
+            .iter()
+            .fold(ch!(0), |acc, it| acc + it.display_width)
+            + ch!(button_texts.len())
+            - ch!(1);
+        let mut col_index = origin_pos.col_index
+            + 1
+            + (inner_col_count - std::cmp::min(inner_col_count, total_col_count)) / 2;
+        let max_col_index = origin_pos.col_index + 1 + inner_col_count;
+        let row_index = origin_pos.row_index + 2;
+
+        let mut ops = render_ops!();
+        for (index, button_text) in button_texts.iter().enumerate() {
+            if col_index >= max_col_index {
+                break;
+            }
+            ops.push(RenderOp::ResetColor);
+            ops.push(RenderOp::MoveCursorPositionAbs(position!(
+                col_index: col_index,
+                row_index: row_index
+            )));
+            ops.push(RenderOp::PaintTextWithAttributes(
+                button_text
+                    .truncate_to_fit_size(size!(
+                        col_count: max_col_index - col_index,
+                        row_count: 1
+                    ))
+                    .to_string(),
+                Some(TuiStyle {
+                    reverse: index == focused_index,
+                    ..style
+                }),
+            ));
+            col_index += button_text.display_width + ch!(1);
+        }
+
+        let mut pipeline = render_pipeline!();
+        pipeline.push(ZOrder::Glass, ops);
+        Ok(pipeline)
+    }
+
+    /// Paint the month (w/ arrows to show that <kbd>PageUp</kbd> & <kbd>PageDown</kbd>
+    /// change it), the days of the week, and the month grid of the
+    /// [DialogBuffer::maybe_date_picker] below the title (centered). The selected date is
+    /// painted in reverse video, and the dates that can't be selected are dimmed.
+    pub fn render_date_picker<S, AS>(
+        origin_pos: &Position,
+        bounds_size: &Size,
+        args: DialogEngineArgs<'_, S, AS>,
+    ) -> CommonResult<RenderPipeline>
+    where
+        S: Debug + Default + Clone + Sync + Send + HasDialogBuffers,
+        AS: Debug + Default + Clone + Sync + Send,
+    {
+        let DialogEngineArgs {
+            self_id,
+            global_data,
+            dialog_engine,
+            ..
+        } = args;
+
+        let GlobalData { state, .. } = global_data;
+
+        let Some(date_picker) = state
+            .get_mut_dialog_buffer(self_id)
+            .and_then(|it| it.maybe_date_picker.as_ref())
+        else {
+            return CommonError::new_error_result(
+                CommonErrorType::NotFound,
+                &format!("Date picker does not exist for component id:{}", self_id),
+            );
+        };
+
+        let style = dialog_engine
+            .dialog_options
+            .maybe_style_editor
+            .unwrap_or_default();
+        let dim_style = TuiStyle { dim: true, ..style };
+
+        // Center the month grid in the dialog, and don't paint over the bottom border.
+        let inner_col_count = bounds_size.col_count - 2;
+        let grid_col_count = ch!(DIALOG_DATE_PICKER_GRID_COL_COUNT);
+        let grid_col_index = origin_pos.col_index
+            + 1
+            + (inner_col_count - std::cmp::min(inner_col_count, grid_col_count)) / 2;
+        let max_row_index = origin_pos.row_index + bounds_size.row_count - 1;
+        let mut row_index = origin_pos.row_index + 2;
+
+        let mut ops = render_ops!();
+        let paint = |ops: &mut RenderOps,
+                     col_index: ChUnit,
+                     row_index: ChUnit,
+                     text: String,
+                     style: TuiStyle| {
+            ops.push(RenderOp::ResetColor);
+            ops.push(RenderOp::MoveCursorPositionAbs(position!(
+                col_index: col_index,
+                row_index: row_index
+            )));
+            ops.push(RenderOp::PaintTextWithAttributes(text, Some(style)));
+        };
+
+        // The month, eg: `◀ October 2026 ▶`.
+        if row_index < max_row_index {
+            let month_title =
+                UnicodeString::from(format!("◀ {} ▶", date_picker.get_month_title()));
+            let col_index = origin_pos.col_index
+                + 1
+                + (inner_col_count
+                    - std::cmp::min(inner_col_count, month_title.display_width))
+                    / 2;
+            paint(
+                &mut ops,
+                col_index,
+                row_index,
+                month_title
+                    .truncate_to_fit_size(size!(
+                        col_count: inner_col_count,
+                        row_count: 1
+                    ))
+                    .to_string(),
+                TuiStyle {
+                    bold: true,
+                    ..style
+                },
+            );
+            row_index += 1;
+        }
+
+        // The days of the week, eg: `Mo Tu We Th Fr Sa Su`.
+        if row_index < max_row_index {
+            paint(
+                &mut ops,
+                grid_col_index,
+                row_index,
+                date_picker.get_weekday_titles().join(" "),
+                dim_style,
+            );
+            row_index += 1;
+        }
+
+        // The month grid.
+        for week in date_picker.get_weeks() {
+            if row_index >= max_row_index {
+                break;
+            }
+            for (weekday_index, maybe_date) in week.iter().enumerate() {
+                let Some(date) = maybe_date else {
+                    continue;
+                };
+                let day_style = if *date == date_picker.selected_date {
+                    TuiStyle {
+                        reverse: true,
+                        ..style
+                    }
+                } else if date_picker.is_selectable(*date) {
+                    style
+                } else {
+                    dim_style
+                };
+                paint(
+                    &mut ops,
+                    grid_col_index
+                        + ch!(weekday_index * DIALOG_DATE_PICKER_DAY_COL_COUNT),
+                    row_index,
+                    format!("{:>2}", date.day()),
+                    day_style,
+                );
+            }
+            row_index += 1;
+        }
+
+        let mut pipeline = render_pipeline!();
+        pipeline.push(ZOrder::Glass, ops);
+        Ok(pipeline)
+    }
+
+    /// Paint the tabs of the [DialogBuffer::maybe_color_picker] below the title, the
+    /// colors of the current tab (or the RGB input) below them, and a preview of the
+    /// selected color in the last row. In the grid of the 256 colors, the rows are
+    /// scrolled so that the selected color is visible.
+    pub fn render_color_picker<S, AS>(
+        origin_pos: &Position,
+        bounds_size: &Size,
+        args: DialogEngineArgs<'_, S, AS>,
+    ) -> CommonResult<RenderPipeline>
+    where
+        S: Debug + Default + Clone + Sync + Send + HasDialogBuffers,
+        AS: Debug + Default + Clone + Sync + Send,
+    {
+        let DialogEngineArgs {
+            self_id,
+            global_data,
+            dialog_engine,
+            ..
+        } = args;
+
+        let GlobalData { state, .. } = global_data;
+
+        let Some(color_picker) = state
+            .get_mut_dialog_buffer(self_id)
+            .and_then(|it| it.maybe_color_picker.as_ref())
+        else {
+            return CommonError::new_error_result(
+                CommonErrorType::NotFound,
+                &format!("Color picker does not exist for component id:{}", self_id),
+            );
+        };
+
+        let style = dialog_engine
+            .dialog_options
+            .maybe_style_editor
+            .unwrap_or_default();
+        let dim_style = TuiStyle { dim: true, ..style };
+        let get_swatch_style = |color: TuiColor| TuiStyle {
+            color_fg: Some(get_contrast_color(color)),
+            color_bg: Some(color),
+            ..Default::default()
+        };
+
+        let inner_col_count = bounds_size.col_count - 2;
+        let col_index = origin_pos.col_index + 1;
+        let tabs_row_index = origin_pos.row_index + 2;
+        // The last row (above the bottom border) has the preview.
+        let preview_row_index = origin_pos.row_index + bounds_size.row_count - 2;
+        if preview_row_index <= tabs_row_index {
+            return Ok(render_pipeline!());
+        }
+        let content_row_count = ch!(@to_usize preview_row_index - tabs_row_index - 1);
+
+        let mut ops = render_ops!();
+        let paint = |ops: &mut RenderOps,
+                     col_index: ChUnit,
+                     row_index: ChUnit,
+                     text: String,
+                     style: TuiStyle| {
+            ops.push(RenderOp::ResetColor);
+            ops.push(RenderOp::MoveCursorPositionAbs(position!(
+                col_index: col_index,
+                row_index: row_index
+            )));
+            ops.push(RenderOp::PaintTextWithAttributes(
+                UnicodeString::from(text)
+                    .truncate_to_fit_size(size!(
+                        col_count: inner_col_count,
+                        row_count: 1
+                    ))
+                    .to_string(),
+                Some(style),
+            ));
+        };
+
+        // The tabs, w/ the current one in reverse video.
+        let mut tab_col_index = col_index;
+        for tab in DialogColorPickerTab::ALL {
+            let text = format!(" {} ", tab.get_title());
+            let text_width = ch!(UnicodeString::str_display_width(&text));
+            if tab_col_index + text_width > col_index + inner_col_count {
+                break;
+            }
+            paint(
+                &mut ops,
+                tab_col_index,
+                tabs_row_index,
+                text,
+                TuiStyle {
+                    reverse: tab == color_picker.tab,
+                    ..style
+                },
+            );
+            tab_col_index += text_width + ch!(1);
+        }
+
+        // The colors of the current tab (each one is a 2 col swatch, w/ a space after
+        // it), or the RGB input.
+        let content_row_index = tabs_row_index + 1;
+        let (colors, row_len, selected_index): (Vec<TuiColor>, usize, usize) =
+            match color_picker.tab {
+                DialogColorPickerTab::Basic => (
+                    DIALOG_COLOR_PICKER_BASIC_COLORS
+                        .iter()
+                        .map(|(it, _)| TuiColor::Basic(*it))
+                        .collect(),
+                    DIALOG_COLOR_PICKER_BASIC_ROW_LEN,
+                    color_picker.selected_basic_index,
+                ),
+                DialogColorPickerTab::Ansi256 => (
+                    (0..=255)
+                        .map(|it| TuiColor::Ansi(AnsiValue::new(it)))
+                        .collect(),
+                    DIALOG_COLOR_PICKER_ANSI256_ROW_LEN,
+                    color_picker.selected_ansi256_index as usize,
+                ),
+                DialogColorPickerTab::Rgb => (vec![], 1, 0),
+            };
+
+        if color_picker.tab == DialogColorPickerTab::Rgb {
+            let label = "Hex or R, G, B: ";
+            paint(
+                &mut ops,
+                col_index,
+                content_row_index,
+                label.to_string(),
+                dim_style,
+            );
+            let input_col_index =
+                col_index + ch!(UnicodeString::str_display_width(label));
+            paint(
+                &mut ops,
+                input_col_index,
+                content_row_index,
+                color_picker.rgb_input.clone(),
+                style,
+            );
+            // The caret.
+            paint(
+                &mut ops,
+                input_col_index + ch!(color_picker.rgb_input.len()),
+                content_row_index,
+                SPACER.to_string(),
+                TuiStyle {
+                    reverse: true,
+                    ..style
+                },
+            );
+        } else {
+            let selected_row_index = selected_index / row_len;
+            let first_row_index =
+                selected_row_index.saturating_sub(content_row_count.saturating_sub(1));
+            for (row_offset, row) in colors
+                .chunks(row_len)
+                .skip(first_row_index)
+                .take(content_row_count)
+                .enumerate()
+            {
+                for (color_index, color) in row.iter().enumerate() {
+                    let index = (first_row_index + row_offset) * row_len + color_index;
+                    let swatch_col_index = col_index + ch!(color_index * 3);
+                    if swatch_col_index + ch!(2) > col_index + inner_col_count {
+                        break;
+                    }
+                    paint(
+                        &mut ops,
+                        swatch_col_index,
+                        content_row_index + ch!(row_offset),
+                        match index == selected_index {
+                            true => "<>".to_string(),
+                            false => SPACER.repeat(2),
+                        },
+                        get_swatch_style(*color),
+                    );
+                }
+            }
+        }
+
+        // The preview of the selected color (or why it isn't a color).
+        match (color_picker.get_selected_color(), &color_picker.maybe_error) {
+            (_, Some(error)) => paint(
+                &mut ops,
+                col_index,
+                preview_row_index,
+                error.clone(),
+                TuiStyle {
+                    color_fg: Some(TuiColor::Basic(ANSIBasicColor::Red)),
+                    ..style
+                },
+            ),
+            (Some(color), None) => {
+                paint(
+                    &mut ops,
+                    col_index,
+                    preview_row_index,
+                    SPACER.repeat(6),
+                    get_swatch_style(color),
+                );
+                paint(
+                    &mut ops,
+                    col_index + ch!(7),
+                    preview_row_index,
+                    get_color_description(color),
+                    style,
+                );
+            }
+            (None, None) => paint(
+                &mut ops,
+                col_index,
+                preview_row_index,
+                "Type a color, eg: #ff8000".to_string(),
+                dim_style,
+            ),
+        }
+
+        let mut pipeline = render_pipeline!();
+        pipeline.push(ZOrder::Glass, ops);
+        Ok(pipeline)
+    }
+
+    pub fn render_results_panel<S>(
+        origin_pos: &Position,
+        bounds_size: &Size,
+        dialog_engine: &DialogEngine,
+        self_id: FlexBoxId,
+        state: &mut S,
+    ) -> CommonResult<RenderOps>
+    where
+        S: Default + Clone + Debug + Sync + Send + HasDialogBuffers,
+    {
+        let mut it = render_ops!();
+
+        if let Some(dialog_buffer) = state.get_mut_dialog_buffer(self_id) {
+            if let Some(results) = dialog_buffer.maybe_results.as_ref() {
+                if !results.is_empty() {
+                    paint_results(
+                        &mut it,
+                        origin_pos,
+                        bounds_size,
+                        results,
+                        dialog_buffer.maybe_suggestions.as_deref(),
+                        dialog_engine,
+                    );
+                };
+            }
+        };
+
+        return Ok(it);
+
+        pub fn paint_results(
+            ops: &mut RenderOps,
+            origin_pos: &Position,
+            bounds_size: &Size,
+            results: &[String],
+            maybe_suggestions: Option<&[Suggestion]>,
+            dialog_engine: &DialogEngine,
+        ) {
+            let col_start_index = ch!(1);
+            let row_start_index =
+                ch!(DisplayConstants::SimpleModalRowCount as u16) - ch!(1);
+
+            let mut rel_insertion_pos =
+                position!(col_index: col_start_index, row_index: row_start_index);
+
+            let scroll_offset_row_index = dialog_engine.scroll_offset_row_index;
+            let selected_row_index = dialog_engine.selected_row_index;
+
+            // Print results panel.
+            for (row_index, item) in results.iter().enumerate() {
+                let row_index = ch!(row_index);
+
+                // Skip rows that are above the scroll offset.
+                if row_index < scroll_offset_row_index {
+                    continue;
+                }
+
+                rel_insertion_pos.add_row(1);
+
+                let text = UnicodeString::from(item.as_str());
+                let max_display_col_count = bounds_size.col_count - 2;
+
+                // Highlight the chars that matched the query (unless the text is
+                // clipped, which moves them).
+                let match_indices: &[usize] = match maybe_suggestions
+                    .and_then(|it| it.get(ch!(@to_usize row_index)))
+                {
+                    Some(suggestion)
+                        if suggestion.text == *item
+                            && text.display_width <= max_display_col_count =>
+                    {
+                        &suggestion.match_indices
+                    }
+                    _ => &[],
+                };
+                let clipped_text = if text.display_width > max_display_col_count {
+                    let snip_len = ch!(2); /* `..` */
+                    let postfix_len = ch!(5); /* last 5 characters */
+
+                    let lhs_start_index = ch!(0);
+                    let lhs_end_index = max_display_col_count - postfix_len - snip_len;
+                    let lhs = text.clip_to_width(lhs_start_index, lhs_end_index);
+
+                    let rhs_start_index = text.display_width - postfix_len;
+                    let rhs_end_index = text.display_width;
+                    let rhs = text.clip_to_width(rhs_start_index, rhs_end_index);
+
+                    format!("{lhs}..{rhs}")
+                } else {
+                    text.string
+                };
+
+                let max_display_row_count =
+                    /* Viewport height: */ dialog_engine.dialog_options.result_panel_display_row_count +
+                    /* Scroll offset: */ scroll_offset_row_index;
+                if row_index >= max_display_row_count {
+                    break;
+                }
+
+                ops.push(RenderOp::ResetColor);
+                ops.push(RenderOp::MoveCursorPositionRelTo(
+                    *origin_pos,
+                    rel_insertion_pos,
+                ));
+
+                // Set style to underline if selected row & paint.
+                match selected_row_index.eq(&row_index) {
+                    // This is the selected row.
+                    true => {
+                        let my_selected_style = match dialog_engine
+                            .dialog_options
+                            .maybe_style_results_panel
+                        {
+                            // Update existing style.
+                            Some(style) => TuiStyle {
+                                underline: true,
+                                ..style
+                            },
+                            // No existing style, so create a new style w/ only underline.
+                            _ => TuiStyle {
+                                underline: true,
+                                ..Default::default()
+                            },
+                        }
+                        .into();
+                        // Paint the text for the row.
+                        ops.push(RenderOp::ApplyColors(my_selected_style));
+                        paint_highlighted_text(
+                            ops,
+                            clipped_text,
+                            match_indices,
+                            my_selected_style,
+                        );
+                    }
+                    // Regular row, not selected.
+                    false => {
+                        // Paint the text for the row.
+                        ops.push(RenderOp::ApplyColors(
+                            dialog_engine.dialog_options.maybe_style_results_panel,
+                        ));
+                        paint_highlighted_text(
+                            ops,
+                            clipped_text,
+                            match_indices,
+                            dialog_engine.dialog_options.maybe_style_results_panel,
+                        );
+                    }
+                }
+            }
+        }
+
+        /// Paint the `text` w/ the chars at the `match_indices` in bold.
+        fn paint_highlighted_text(
+            ops: &mut RenderOps,
+            text: String,
+            match_indices: &[usize],
+            maybe_style: Option<TuiStyle>,
+        ) {
+            if match_indices.is_empty() {
+                ops.push(RenderOp::PaintTextWithAttributes(text, maybe_style));
+                return;
+            }
+
+            let highlight_style = Some(TuiStyle {
+                bold: true,
+                ..maybe_style.unwrap_or_default()
+            });
+
+            // Paint each run of chars that are (or aren't) highlighted.
+            let mut run = String::new();
+            let mut is_run_highlighted = false;
+            for (index, character) in text.chars().enumerate() {
+                let is_highlighted = match_indices.contains(&index);
+                if is_highlighted != is_run_highlighted && !run.is_empty() {
+                    ops.push(RenderOp::PaintTextWithAttributes(
+                        std::mem::take(&mut run),
+                        if is_run_highlighted {
+                            highlight_style
+                        } else {
+                            maybe_style
+                        },
+                    ));
+                }
+                is_run_highlighted = is_highlighted;
+                run.push(character);
+            }
+            if !run.is_empty() {
+                ops.push(RenderOp::PaintTextWithAttributes(
+                    run,
+                    if is_run_highlighted {
+                        highlight_style
+                    } else {
+                        maybe_style
+                    },
+                ));
+            }
+        }
+    }
+
+    pub fn render_title(
+        origin_pos: &Position,
+        bounds_size: &Size,
+        title: &str,
+        dialog_engine: &mut DialogEngine,
+    ) -> RenderOps {
+        let mut ops = render_ops!();
+
+        let row_pos = position!(col_index: origin_pos.col_index + 1, row_index: origin_pos.row_index + 1);
+
+        let title_us = UnicodeString::from(title);
+        let text_content = title_us.truncate_to_fit_size(size! {
+          col_count: bounds_size.col_count - 2, row_count: bounds_size.row_count
+        });
+
+        ops.push(RenderOp::ResetColor);
+        ops.push(RenderOp::MoveCursorPositionAbs(row_pos));
+        ops.push(RenderOp::ApplyColors(
+            dialog_engine.dialog_options.maybe_style_title,
+        ));
+
+        // Apply lolcat override (if enabled) to the fg_color of text_content.
+        lolcat_from_style(
+            &mut ops,
+            &mut dialog_engine.color_wheel,
+            &dialog_engine.dialog_options.maybe_style_title,
+            text_content,
+        );
+
+        ops
+    }
+
+    /// Only Colorizes text in-place if [Style]'s `lolcat` field is true. Otherwise leaves `text`
+    /// alone.
+    fn lolcat_from_style(
+        ops: &mut RenderOps,
         color_wheel: &mut ColorWheel,
         maybe_style: &Option<TuiStyle>,
         text: &str,
@@ -744,8 +1646,13 @@ mod internal_impl {
 
             // Paint separator for results panel if in autocomplete mode.
             match dialog_engine.dialog_options.mode {
-                DialogEngineMode::ModalSimple => {}
-                DialogEngineMode::ModalAutocomplete => {
+                DialogEngineMode::ModalSimple
+                | DialogEngineMode::ModalForm
+                | DialogEngineMode::ModalConfirm
+                | DialogEngineMode::ModalDatePicker
+                | DialogEngineMode::ModalColorPicker => {}
+                DialogEngineMode::ModalAutocomplete
+                | DialogEngineMode::ModalFilePicker => {
                     let inner_line = BorderGlyphCharacter::Horizontal
                         .as_ref()
                         .repeat(ch!(@to_usize bounds_size.col_count - 2))
@@ -779,55 +1686,476 @@ mod internal_impl {
                     );
                 }
             }
-        }
+        }
+
+        ops
+    }
+
+    pub fn try_handle_dialog_choice(
+        input_event: &InputEvent,
+        maybe_dialog_buffer: Option<&mut DialogBuffer>,
+        dialog_engine: &mut DialogEngine,
+    ) -> Option<DialogChoice> {
+        // It is safe to unwrap the dialog buffer here (since it will have Some value).
+        let dialog_buffer = { maybe_dialog_buffer? };
+
+        match DialogEvent::from(input_event) {
+            // Handle Enter.
+            DialogEvent::EnterPressed => match dialog_engine.dialog_options.mode {
+                DialogEngineMode::ModalSimple => {
+                    let text = dialog_buffer
+                        .editor_buffer
+                        .get_as_string_with_comma_instead_of_newlines();
+                    return Some(DialogChoice::Yes(text));
+                }
+
+                DialogEngineMode::ModalAutocomplete => {
+                    let selected_index = ch!(@to_usize dialog_engine.selected_row_index);
+                    if let Some(results) = &dialog_buffer.maybe_results {
+                        if let Some(selected_result) = results.get(selected_index) {
+                            return Some(DialogChoice::Yes(selected_result.clone()));
+                        }
+                    }
+                    return Some(DialogChoice::No);
+                }
+
+                DialogEngineMode::ModalForm => {
+                    return Some(DialogChoice::Form(DialogFormValues::from_fields(
+                        &dialog_buffer.form_fields,
+                    )));
+                }
+
+                // This is handled by try_handle_file_picker().
+                DialogEngineMode::ModalFilePicker => {}
+
+                DialogEngineMode::ModalConfirm => {
+                    let selected_index = ch!(@to_usize dialog_engine.selected_row_index);
+                    return Some(DialogChoice::Confirm(
+                        match dialog_buffer.confirm_buttons.get(selected_index) {
+                            Some(button) => button.choice.clone(),
+                            None => DialogConfirmChoice::Cancel,
+                        },
+                    ));
+                }
+
+                DialogEngineMode::ModalDatePicker => {
+                    return match &dialog_buffer.maybe_date_picker {
+                        Some(date_picker) => {
+                            Some(DialogChoice::Date(date_picker.selected_date))
+                        }
+                        None => Some(DialogChoice::No),
+                    };
+                }
+
+                // If the RGB input isn't a color, this is handled by
+                // try_handle_color_picker() (to show the error).
+                DialogEngineMode::ModalColorPicker => {
+                    match &dialog_buffer.maybe_color_picker {
+                        Some(color_picker) => {
+                            if let Some(color) = color_picker.get_selected_color() {
+                                return Some(DialogChoice::Color(color));
+                            }
+                        }
+                        None => return Some(DialogChoice::No),
+                    }
+                }
+            },
+
+            // Handle Esc.
+            DialogEvent::EscPressed => match dialog_engine.dialog_options.mode {
+                DialogEngineMode::ModalConfirm => {
+                    return Some(DialogChoice::Confirm(DialogConfirmChoice::Cancel));
+                }
+                _ => return Some(DialogChoice::No),
+            },
+            _ => {}
+        }
+        None
+    }
+
+    pub fn try_handle_up_down(
+        input_event: &InputEvent,
+        maybe_dialog_buffer: Option<&mut DialogBuffer>,
+        dialog_engine: &mut DialogEngine,
+    ) -> EventPropagation {
+        // It is safe to unwrap the dialog buffer here (since it will have Some value).
+        let dialog_buffer = {
+            if let Some(it) = maybe_dialog_buffer {
+                it
+            } else {
+                return EventPropagation::Propagate;
+            }
+        };
+
+        // Handle up arrow?
+        if input_event.matches(&[InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(SpecialKey::Up),
+        })]) {
+            if dialog_engine.selected_row_index > ch!(0) {
+                dialog_engine.selected_row_index -= 1;
+            }
+
+            if dialog_engine.selected_row_index < dialog_engine.scroll_offset_row_index {
+                dialog_engine.scroll_offset_row_index -= 1;
+            }
+
+            return EventPropagation::ConsumedRender;
+        }
+
+        // Handle down arrow?
+        if input_event.matches(&[InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(SpecialKey::Down),
+        })]) {
+            let max_abs_row_index = dialog_buffer.get_results_count() - ch!(1);
+
+            let results_panel_viewport_height_row_count =
+                dialog_engine.dialog_options.result_panel_display_row_count;
+
+            if dialog_engine.selected_row_index < max_abs_row_index {
+                dialog_engine.selected_row_index += 1;
+            }
+
+            if dialog_engine.selected_row_index
+                >= dialog_engine.scroll_offset_row_index
+                    + results_panel_viewport_height_row_count
+            {
+                dialog_engine.scroll_offset_row_index += 1;
+            }
+
+            return EventPropagation::ConsumedRender;
+        }
+
+        EventPropagation::Propagate
+    }
+
+    /// In a confirm dialog:
+    /// - <kbd>Left</kbd> / <kbd>Shift+Tab</kbd> & <kbd>Right</kbd> / <kbd>Tab</kbd> move the
+    ///   focus to the previous & next button (wrapping around).
+    /// - The shortcut of a button presses it.
+    ///
+    /// All the other keys (except for <kbd>Enter</kbd> & <kbd>Esc</kbd>, which are handled
+    /// by [try_handle_dialog_choice]) are ignored, since there's no editor.
+    pub fn try_handle_confirm_buttons(
+        input_event: &InputEvent,
+        maybe_dialog_buffer: Option<&mut DialogBuffer>,
+        dialog_engine: &mut DialogEngine,
+    ) -> Option<DialogEngineApplyResponse> {
+        if dialog_engine.dialog_options.mode != DialogEngineMode::ModalConfirm {
+            return None;
+        }
+
+        let dialog_buffer = maybe_dialog_buffer?;
+        let button_count = dialog_buffer.confirm_buttons.len();
+        if button_count == 0 {
+            return Some(DialogEngineApplyResponse::Noop);
+        }
+
+        let key = match input_event {
+            InputEvent::Keyboard(KeyPress::Plain { key }) => *key,
+            // Some terminals report Shift+Tab as BackTab w/ the shift modifier.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: key @ Key::SpecialKey(SpecialKey::BackTab),
+                ..
+            }) => *key,
+            _ => return Some(DialogEngineApplyResponse::Noop),
+        };
+
+        let focused_index = ch!(@to_usize dialog_engine.selected_row_index);
+        match key {
+            Key::SpecialKey(SpecialKey::Right | SpecialKey::Tab) => {
+                dialog_engine.selected_row_index =
+                    ch!((focused_index + 1) % button_count);
+                Some(DialogEngineApplyResponse::FocusButton)
+            }
+            Key::SpecialKey(SpecialKey::Left | SpecialKey::BackTab) => {
+                dialog_engine.selected_row_index =
+                    ch!((focused_index + button_count - 1) % button_count);
+                Some(DialogEngineApplyResponse::FocusButton)
+            }
+            Key::Character(character) => {
+                match dialog_buffer
+                    .confirm_buttons
+                    .iter()
+                    .find(|it| it.is_shortcut(character))
+                {
+                    Some(button) => {
+                        let choice = button.choice.clone();
+                        dialog_engine.reset();
+                        Some(DialogEngineApplyResponse::DialogChoice(
+                            DialogChoice::Confirm(choice),
+                        ))
+                    }
+                    None => Some(DialogEngineApplyResponse::Noop),
+                }
+            }
+            _ => Some(DialogEngineApplyResponse::Noop),
+        }
+    }
+
+    /// In a date picker, move the selected date of the [DialogBuffer::maybe_date_picker]
+    /// (see [crate::DialogDatePicker] for the keys).
+    ///
+    /// All the other keys (except for <kbd>Enter</kbd> & <kbd>Esc</kbd>, which are handled
+    /// by [try_handle_dialog_choice]) are ignored, since there's no editor.
+    pub fn try_handle_date_picker(
+        input_event: &InputEvent,
+        maybe_dialog_buffer: Option<&mut DialogBuffer>,
+        dialog_engine: &mut DialogEngine,
+    ) -> Option<DialogEngineApplyResponse> {
+        if dialog_engine.dialog_options.mode != DialogEngineMode::ModalDatePicker {
+            return None;
+        }
+
+        let Some(date_picker) = maybe_dialog_buffer?.maybe_date_picker.as_mut() else {
+            return Some(DialogEngineApplyResponse::Noop);
+        };
+
+        let InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        }) = input_event
+        else {
+            return Some(DialogEngineApplyResponse::Noop);
+        };
+
+        let is_changed = match special_key {
+            SpecialKey::Left => date_picker.move_by_days(-1),
+            SpecialKey::Right => date_picker.move_by_days(1),
+            SpecialKey::Up => date_picker.move_by_days(-7),
+            SpecialKey::Down => date_picker.move_by_days(7),
+            SpecialKey::PageUp => date_picker.move_by_months(-1),
+            SpecialKey::PageDown => date_picker.move_by_months(1),
+            SpecialKey::Home => date_picker.move_to_start_of_month(),
+            SpecialKey::End => date_picker.move_to_end_of_month(),
+            _ => false,
+        };
+
+        match is_changed {
+            true => Some(DialogEngineApplyResponse::SelectDate),
+            false => Some(DialogEngineApplyResponse::Noop),
+        }
+    }
+
+    /// In a color picker, change the tab or the selected color of the
+    /// [DialogBuffer::maybe_color_picker], or edit its RGB input (see
+    /// [crate::DialogColorPicker] for the keys). <kbd>Enter</kbd> is only handled here
+    /// when the RGB input isn't a color, to show why.
+    ///
+    /// All the other keys (except for <kbd>Esc</kbd>, which is handled by
+    /// [try_handle_dialog_choice]) are ignored, since there's no editor.
+    pub fn try_handle_color_picker(
+        input_event: &InputEvent,
+        maybe_dialog_buffer: Option<&mut DialogBuffer>,
+        dialog_engine: &mut DialogEngine,
+    ) -> Option<DialogEngineApplyResponse> {
+        if dialog_engine.dialog_options.mode != DialogEngineMode::ModalColorPicker {
+            return None;
+        }
+
+        let Some(color_picker) = maybe_dialog_buffer?.maybe_color_picker.as_mut() else {
+            return Some(DialogEngineApplyResponse::Noop);
+        };
+
+        let key = match input_event {
+            InputEvent::Keyboard(KeyPress::Plain { key }) => *key,
+            // Some terminals report Shift+Tab as BackTab w/ the shift modifier.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: key @ Key::SpecialKey(SpecialKey::BackTab),
+                ..
+            }) => *key,
+            _ => return Some(DialogEngineApplyResponse::Noop),
+        };
+
+        let is_changed = match key {
+            Key::SpecialKey(SpecialKey::Tab) => {
+                color_picker.select_next_tab(1);
+                true
+            }
+            Key::SpecialKey(SpecialKey::BackTab) => {
+                color_picker.select_next_tab(-1);
+                true
+            }
+            Key::SpecialKey(SpecialKey::Left) => color_picker.move_selection(-1, 0),
+            Key::SpecialKey(SpecialKey::Right) => color_picker.move_selection(1, 0),
+            Key::SpecialKey(SpecialKey::Up) => color_picker.move_selection(0, -1),
+            Key::SpecialKey(SpecialKey::Down) => color_picker.move_selection(0, 1),
+            Key::SpecialKey(SpecialKey::Backspace) => color_picker.delete_char(),
+            Key::SpecialKey(SpecialKey::Enter) => {
+                color_picker.maybe_error =
+                    Some("Not a color, eg: #ff8000 or 255, 128, 0".to_string());
+                true
+            }
+            Key::Character(character) => color_picker.insert_char(character),
+            _ => false,
+        };
 
-        ops
+        match is_changed {
+            true => Some(DialogEngineApplyResponse::SelectColor),
+            false => Some(DialogEngineApplyResponse::Noop),
+        }
     }
 
-    pub fn try_handle_dialog_choice(
-        input_event: InputEvent,
+    /// Handle the keys of the [DialogBuffer::maybe_file_picker] (see [DialogFilePicker]),
+    /// and return [None] for the ones that it doesn't handle.
+    pub fn try_handle_file_picker(
+        input_event: &InputEvent,
         maybe_dialog_buffer: Option<&mut DialogBuffer>,
         dialog_engine: &mut DialogEngine,
-    ) -> Option<DialogChoice> {
-        // It is safe to unwrap the dialog buffer here (since it will have Some value).
-        let dialog_buffer = { maybe_dialog_buffer? };
+    ) -> Option<DialogEngineApplyResponse> {
+        if dialog_engine.dialog_options.mode != DialogEngineMode::ModalFilePicker {
+            return None;
+        }
+
+        let dialog_buffer = maybe_dialog_buffer?;
+        let text = dialog_buffer
+            .editor_buffer
+            .get_as_string_with_comma_instead_of_newlines();
+        let file_picker = dialog_buffer.maybe_file_picker.as_mut()?;
+        let ctrl = ModifierKeysMask::new().with_ctrl();
+
+        // Show (or hide) the hidden files. This isn't Ctrl+h, since most terminals send
+        // that as Backspace.
+        if input_event.matches_keypress(keypress!(@char ctrl, 't')) {
+            file_picker.toggle_show_hidden();
+            update_file_picker(dialog_buffer, dialog_engine, false);
+            return Some(DialogEngineApplyResponse::SelectScrollResultsPanel);
+        }
+
+        // Start naming a new folder.
+        if input_event.matches_keypress(keypress!(@char ctrl, 'n')) {
+            file_picker.is_creating_folder = true;
+            update_file_picker(dialog_buffer, dialog_engine, true);
+            return Some(DialogEngineApplyResponse::SelectScrollResultsPanel);
+        }
 
         match DialogEvent::from(input_event) {
-            // Handle Enter.
-            DialogEvent::EnterPressed => match dialog_engine.dialog_options.mode {
-                DialogEngineMode::ModalSimple => {
-                    let text = dialog_buffer
-                        .editor_buffer
-                        .get_as_string_with_comma_instead_of_newlines();
-                    return Some(DialogChoice::Yes(text));
+            // Stop naming a new folder (instead of closing the dialog).
+            DialogEvent::EscPressed if file_picker.is_creating_folder => {
+                file_picker.is_creating_folder = false;
+                update_file_picker(dialog_buffer, dialog_engine, true);
+                Some(DialogEngineApplyResponse::SelectScrollResultsPanel)
+            }
+
+            // Create the new folder.
+            DialogEvent::EnterPressed if file_picker.is_creating_folder => {
+                if file_picker.create_dir(&text).is_some() {
+                    file_picker.is_creating_folder = false;
+                    update_file_picker(dialog_buffer, dialog_engine, true);
                 }
+                Some(DialogEngineApplyResponse::SelectScrollResultsPanel)
+            }
 
-                DialogEngineMode::ModalAutocomplete => {
-                    let selected_index = ch!(@to_usize dialog_engine.selected_row_index);
-                    if let Some(results) = &dialog_buffer.maybe_results {
-                        if let Some(selected_result) = results.get(selected_index) {
-                            return Some(DialogChoice::Yes(selected_result.clone()));
-                        }
+            DialogEvent::EnterPressed => {
+                let maybe_selected_entry = file_picker
+                    .get_visible_entries(&text)
+                    .get(ch!(@to_usize dialog_engine.selected_row_index))
+                    .cloned();
+
+                let maybe_picked_path = match maybe_selected_entry {
+                    // Pick the current folder.
+                    Some(entry)
+                        if file_picker.kind == DialogFilePickerKind::PickFolder
+                            && entry.name == DialogFilePickerEntry::CURRENT_DIR =>
+                    {
+                        Some(file_picker.get_path(&entry.name))
                     }
-                    return Some(DialogChoice::No);
+                    // Open the folder.
+                    Some(entry) if entry.is_dir => {
+                        file_picker.open_dir(&entry.name);
+                        update_file_picker(dialog_buffer, dialog_engine, true);
+                        return Some(DialogEngineApplyResponse::SelectScrollResultsPanel);
+                    }
+                    // Save w/ the name that was typed.
+                    _ if file_picker.kind == DialogFilePickerKind::SaveFile
+                        && !text.trim().is_empty() =>
+                    {
+                        Some(file_picker.get_path(text.trim()))
+                    }
+                    // Pick the file.
+                    Some(entry) => Some(file_picker.get_path(&entry.name)),
+                    None => None,
+                };
+
+                match maybe_picked_path {
+                    Some(path) => {
+                        dialog_engine.reset();
+                        Some(DialogEngineApplyResponse::DialogChoice(DialogChoice::Yes(
+                            path.display().to_string(),
+                        )))
+                    }
+                    None => Some(DialogEngineApplyResponse::Noop),
                 }
-            },
+            }
 
-            // Handle Esc.
-            DialogEvent::EscPressed => {
-                return Some(DialogChoice::No);
+            _ => None,
+        }
+    }
+
+    /// Show the entries of the file picker (after it changed), w/ the first one selected.
+    /// The text in the editor is cleared if `clear_text` is true.
+    fn update_file_picker(
+        dialog_buffer: &mut DialogBuffer,
+        dialog_engine: &mut DialogEngine,
+        clear_text: bool,
+    ) {
+        if clear_text {
+            dialog_buffer.editor_buffer.set_lines(vec![String::new()]);
+        }
+        dialog_buffer.update_file_picker_results();
+        dialog_engine.reset();
+    }
+
+    /// When <kbd>Enter</kbd> is pressed in a form, run the
+    /// [DialogEngine::maybe_form_validator]. If the values aren't valid, then the error is
+    /// saved (to be painted), the field that it is about gets the focus, and the event is
+    /// consumed so that the form isn't submitted.
+    pub fn try_handle_form_validation(
+        input_event: &InputEvent,
+        maybe_dialog_buffer: Option<&mut DialogBuffer>,
+        dialog_engine: &mut DialogEngine,
+    ) -> EventPropagation {
+        if dialog_engine.dialog_options.mode != DialogEngineMode::ModalForm
+            || !matches!(DialogEvent::from(input_event), DialogEvent::EnterPressed)
+        {
+            return EventPropagation::Propagate;
+        }
+
+        let (Some(dialog_buffer), Some(validator)) =
+            (maybe_dialog_buffer, dialog_engine.maybe_form_validator)
+        else {
+            return EventPropagation::Propagate;
+        };
+
+        match validator(&DialogFormValues::from_fields(&dialog_buffer.form_fields)) {
+            Ok(_) => EventPropagation::Propagate,
+            Err(error) => {
+                if let Some(index) = dialog_buffer
+                    .form_fields
+                    .iter()
+                    .position(|it| it.label == error.label)
+                {
+                    dialog_engine.selected_row_index = ch!(index);
+                }
+                dialog_engine.maybe_form_error = Some(error.message);
+                EventPropagation::ConsumedRender
             }
-            _ => {}
         }
-        None
     }
 
-    pub fn try_handle_up_down(
-        input_event: InputEvent,
+    /// In a form:
+    /// - <kbd>Tab</kbd> / <kbd>Down</kbd> & <kbd>Shift+Tab</kbd> / <kbd>Up</kbd> move the
+    ///   focus to the next & previous field (wrapping around).
+    /// - <kbd>Space</kbd> toggles the checkbox that has focus.
+    /// - <kbd>Space</kbd> / <kbd>Right</kbd> & <kbd>Left</kbd> select the next & previous
+    ///   option of the select that has focus.
+    pub fn try_handle_form_field(
+        input_event: &InputEvent,
         maybe_dialog_buffer: Option<&mut DialogBuffer>,
         dialog_engine: &mut DialogEngine,
     ) -> EventPropagation {
-        // It is safe to unwrap the dialog buffer here (since it will have Some value).
+        if dialog_engine.dialog_options.mode != DialogEngineMode::ModalForm {
+            return EventPropagation::Propagate;
+        }
+
         let dialog_buffer = {
             if let Some(it) = maybe_dialog_buffer {
                 it
@@ -836,45 +2164,68 @@ mod internal_impl {
             }
         };
 
-        // Handle up arrow?
-        if input_event.matches(&[InputEvent::Keyboard(KeyPress::Plain {
-            key: Key::SpecialKey(SpecialKey::Up),
-        })]) {
-            if dialog_engine.selected_row_index > ch!(0) {
-                dialog_engine.selected_row_index -= 1;
-            }
+        let field_count = dialog_buffer.form_fields.len();
+        if field_count == 0 {
+            return EventPropagation::Propagate;
+        }
 
-            if dialog_engine.selected_row_index < dialog_engine.scroll_offset_row_index {
-                dialog_engine.scroll_offset_row_index -= 1;
-            }
+        let key = match input_event {
+            InputEvent::Keyboard(KeyPress::Plain { key }) => *key,
+            // Some terminals report Shift+Tab as BackTab w/ the shift modifier.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: key @ Key::SpecialKey(SpecialKey::BackTab),
+                ..
+            }) => *key,
+            _ => return EventPropagation::Propagate,
+        };
 
+        // Move the focus?
+        let focused_index = ch!(@to_usize dialog_engine.selected_row_index);
+        let maybe_new_focused_index = match key {
+            Key::SpecialKey(SpecialKey::Tab | SpecialKey::Down) => {
+                Some((focused_index + 1) % field_count)
+            }
+            Key::SpecialKey(SpecialKey::BackTab | SpecialKey::Up) => {
+                Some((focused_index + field_count - 1) % field_count)
+            }
+            _ => None,
+        };
+        if let Some(new_focused_index) = maybe_new_focused_index {
+            dialog_engine.selected_row_index = ch!(new_focused_index);
             return EventPropagation::ConsumedRender;
         }
 
-        // Handle down arrow?
-        if input_event.matches(&[InputEvent::Keyboard(KeyPress::Plain {
-            key: Key::SpecialKey(SpecialKey::Down),
-        })]) {
-            let max_abs_row_index = dialog_buffer.get_results_count() - ch!(1);
-
-            let results_panel_viewport_height_row_count =
-                dialog_engine.dialog_options.result_panel_display_row_count;
-
-            if dialog_engine.selected_row_index < max_abs_row_index {
-                dialog_engine.selected_row_index += 1;
+        // Change the field that has focus?
+        let field = match dialog_buffer.form_fields.get_mut(focused_index) {
+            Some(it) => it,
+            None => return EventPropagation::Propagate,
+        };
+        match (&mut field.kind, key) {
+            (DialogFormFieldKind::Checkbox(is_checked), Key::Character(' ')) => {
+                *is_checked = !*is_checked;
             }
-
-            if dialog_engine.selected_row_index
-                >= dialog_engine.scroll_offset_row_index
-                    + results_panel_viewport_height_row_count
-            {
-                dialog_engine.scroll_offset_row_index += 1;
+            (
+                DialogFormFieldKind::Select {
+                    options,
+                    selected_index,
+                },
+                Key::Character(' ') | Key::SpecialKey(SpecialKey::Right),
+            ) if !options.is_empty() => {
+                *selected_index = (*selected_index + 1) % options.len();
             }
-
-            return EventPropagation::ConsumedRender;
+            (
+                DialogFormFieldKind::Select {
+                    options,
+                    selected_index,
+                },
+                Key::SpecialKey(SpecialKey::Left),
+            ) if !options.is_empty() => {
+                *selected_index = (*selected_index + options.len() - 1) % options.len();
+            }
+            _ => return EventPropagation::Propagate,
         }
 
-        EventPropagation::Propagate
+        EventPropagation::ConsumedRender
     }
 }
 
@@ -961,6 +2312,7 @@ mod test_dialog_api_make_flex_box_for_dialog {
             },
             window_size,
             Some(SurfaceBounds::from(&surface)),
+            0,
         ));
 
         // Assert that a general `CommonError` is returned.
@@ -1005,6 +2357,7 @@ mod test_dialog_api_make_flex_box_for_dialog {
             },
             window_size,
             Some(SurfaceBounds::from(&surface)),
+            0,
         ));
 
         // Assert that a general `CommonError` is returned.
@@ -1045,6 +2398,7 @@ mod test_dialog_api_make_flex_box_for_dialog {
             },
             window_size,
             Some(SurfaceBounds::from(&surface)),
+            0,
         ));
 
         assert_eq2!(result_flex_box.is_ok(), true);
@@ -1082,6 +2436,7 @@ mod test_dialog_api_make_flex_box_for_dialog {
             },
             window_size,
             Some(SurfaceBounds::from(&surface)),
+            0,
         ));
 
         assert_eq2!(result_flex_box.is_ok(), true);
@@ -1104,7 +2459,13 @@ mod test_dialog_engine_api_apply_event {
     use r3bl_core::assert_eq2;
 
     use super::*;
-    use crate::{keypress, test_dialog::mock_real_objects_for_dialog};
+    use crate::{keypress,
+                system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_dialog::mock_real_objects_for_dialog,
+                DialogButton,
+                DialogColorPicker,
+                DialogDatePicker,
+                DialogFormError};
 
     #[test]
     fn apply_event_esc() {
@@ -1117,6 +2478,7 @@ mod test_dialog_engine_api_apply_event {
             self_id,
             dialog_engine,
             input_event,
+            &mut TestClipboard::default(),
         )
         .unwrap());
         assert!(matches!(
@@ -1134,7 +2496,13 @@ mod test_dialog_engine_api_apply_event {
         let response = dbg!(DialogEngineApi::apply_event::<
             mock_real_objects_for_dialog::State,
             (),
-        >(state, self_id, dialog_engine, input_event)
+        >(
+            state,
+            self_id,
+            dialog_engine,
+            input_event,
+            &mut TestClipboard::default(),
+        )
         .unwrap());
         if let DialogEngineApplyResponse::DialogChoice(DialogChoice::Yes(value)) =
             &response
@@ -1156,7 +2524,13 @@ mod test_dialog_engine_api_apply_event {
         let response = dbg!(DialogEngineApi::apply_event::<
             mock_real_objects_for_dialog::State,
             (),
-        >(state, self_id, dialog_engine, input_event)
+        >(
+            state,
+            self_id,
+            dialog_engine,
+            input_event,
+            &mut TestClipboard::default(),
+        )
         .unwrap());
         if let DialogEngineApplyResponse::UpdateEditorBuffer = &response {
             let editor_content = state
@@ -1167,4 +2541,407 @@ mod test_dialog_engine_api_apply_event {
             assert_eq2!(editor_content, "a");
         }
     }
+
+    fn make_form_state_and_engine() -> (mock_real_objects_for_dialog::State, DialogEngine)
+    {
+        let mut state = mock_real_objects_for_dialog::create_state();
+        state.dialog_buffers.insert(
+            FlexBoxId::from(0),
+            DialogBuffer::new_form(
+                "Settings",
+                vec![
+                    DialogFormField::new_text("Name", ""),
+                    DialogFormField::new_checkbox("Verbose", false),
+                    DialogFormField::new_select(
+                        "Theme",
+                        vec!["Light".to_string(), "Dark".to_string()],
+                        0,
+                    ),
+                ],
+            ),
+        );
+        let mut dialog_engine = mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.mode = DialogEngineMode::ModalForm;
+        (state, dialog_engine)
+    }
+
+    fn apply(
+        state: &mut mock_real_objects_for_dialog::State,
+        dialog_engine: &mut DialogEngine,
+        input_event: InputEvent,
+    ) -> DialogEngineApplyResponse {
+        DialogEngineApi::apply_event::<_, ()>(
+            state,
+            FlexBoxId::from(0),
+            dialog_engine,
+            input_event,
+            &mut TestClipboard::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn apply_event_form_tab_moves_focus() {
+        let (state, dialog_engine) = &mut make_form_state_and_engine();
+        let tab = InputEvent::Keyboard(keypress!(@special SpecialKey::Tab));
+        let back_tab = InputEvent::Keyboard(keypress!(@special SpecialKey::BackTab));
+
+        assert!(matches!(
+            apply(state, dialog_engine, tab.clone()),
+            DialogEngineApplyResponse::UpdateForm
+        ));
+        assert_eq2!(dialog_engine.selected_row_index, ch!(1));
+
+        // Wraps around.
+        apply(state, dialog_engine, tab.clone());
+        apply(state, dialog_engine, tab);
+        assert_eq2!(dialog_engine.selected_row_index, ch!(0));
+        apply(state, dialog_engine, back_tab);
+        assert_eq2!(dialog_engine.selected_row_index, ch!(2));
+    }
+
+    #[test]
+    fn apply_event_form_change_fields() {
+        let (state, dialog_engine) = &mut make_form_state_and_engine();
+
+        // Type into the text field.
+        assert!(matches!(
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@char 'a'))
+            ),
+            DialogEngineApplyResponse::UpdateEditorBuffer
+        ));
+
+        // Toggle the checkbox.
+        dialog_engine.selected_row_index = ch!(1);
+        apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@char ' ')),
+        );
+
+        // Select the next option.
+        dialog_engine.selected_row_index = ch!(2);
+        apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Right)),
+        );
+
+        let response = apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)),
+        );
+        let DialogEngineApplyResponse::DialogChoice(DialogChoice::Form(values)) =
+            response
+        else {
+            panic!("Expected the form to be submitted");
+        };
+        assert_eq2!(values.get_text("Name"), Some("a"));
+        assert_eq2!(values.get_checkbox("Verbose"), Some(true));
+        assert_eq2!(values.get_select("Theme"), Some("Dark"));
+    }
+
+    #[test]
+    fn apply_event_form_validation() {
+        let (state, dialog_engine) = &mut make_form_state_and_engine();
+        fn validate(values: &DialogFormValues) -> Result<(), DialogFormError> {
+            match values.get_text("Name") {
+                Some("") => Err(DialogFormError {
+                    label: "Name".to_string(),
+                    message: "Name is required".to_string(),
+                }),
+                _ => Ok(()),
+            }
+        }
+        dialog_engine.maybe_form_validator = Some(validate);
+        dialog_engine.selected_row_index = ch!(2);
+        let enter = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
+
+        // The form isn't submitted, and the invalid field gets the focus.
+        assert!(matches!(
+            apply(state, dialog_engine, enter.clone()),
+            DialogEngineApplyResponse::UpdateForm
+        ));
+        assert_eq2!(dialog_engine.selected_row_index, ch!(0));
+        assert_eq2!(
+            dialog_engine.maybe_form_error,
+            Some("Name is required".to_string())
+        );
+
+        // The error is cleared once the field is changed.
+        apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@char 'a')),
+        );
+        assert_eq2!(dialog_engine.maybe_form_error, None);
+        assert!(matches!(
+            apply(state, dialog_engine, enter),
+            DialogEngineApplyResponse::DialogChoice(DialogChoice::Form(_))
+        ));
+    }
+
+    #[test]
+    fn apply_event_file_picker() {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "r3bl_tui_dialog_engine_api_file_picker_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(temp_dir.join("src")).unwrap();
+        std::fs::write(temp_dir.join("src").join("lib.rs"), "").unwrap();
+        std::fs::write(temp_dir.join("src").join("main.rs"), "").unwrap();
+
+        let mut state = mock_real_objects_for_dialog::create_state();
+        state.dialog_buffers.insert(
+            FlexBoxId::from(0),
+            DialogBuffer::new_file_picker(
+                "Open",
+                DialogFilePicker::new(DialogFilePickerKind::OpenFile, &temp_dir),
+            ),
+        );
+        let state = &mut state;
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.mode = DialogEngineMode::ModalFilePicker;
+        let get_results = |state: &mut mock_real_objects_for_dialog::State| {
+            state
+                .get_mut_dialog_buffer(FlexBoxId::from(0))
+                .unwrap()
+                .maybe_results
+                .clone()
+                .unwrap()
+        };
+        let enter = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
+        assert_eq2!(get_results(state), vec!["../", "src/"]);
+
+        // Open the folder.
+        apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Down)),
+        );
+        apply(state, dialog_engine, enter.clone());
+        assert_eq2!(get_results(state), vec!["../", "lib.rs", "main.rs"]);
+
+        // Filter the files.
+        apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@char 'm')),
+        );
+        apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@char 'a')),
+        );
+        assert_eq2!(get_results(state), vec!["../", "main.rs"]);
+
+        // Pick the file.
+        apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Down)),
+        );
+        let response = apply(state, dialog_engine, enter);
+        let DialogEngineApplyResponse::DialogChoice(DialogChoice::Yes(path)) = response
+        else {
+            panic!("Expected a file to be picked");
+        };
+        assert_eq2!(
+            path,
+            temp_dir.join("src").join("main.rs").display().to_string()
+        );
+
+        std::fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn apply_event_confirm() {
+        let mut state = mock_real_objects_for_dialog::create_state();
+        state.dialog_buffers.insert(FlexBoxId::from(0), {
+            let mut buttons = DialogButton::yes_no_cancel();
+            buttons.push(DialogButton::new("Diff", Some('d')));
+            DialogBuffer::new_confirm("Save changes?", buttons)
+        });
+        let state = &mut state;
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.mode = DialogEngineMode::ModalConfirm;
+
+        // Move the focus (wrapping around) & press the button that has focus.
+        let left = InputEvent::Keyboard(keypress!(@special SpecialKey::Left));
+        assert!(matches!(
+            apply(state, dialog_engine, left),
+            DialogEngineApplyResponse::FocusButton
+        ));
+        assert_eq2!(dialog_engine.selected_row_index, ch!(3));
+        let response = apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)),
+        );
+        assert!(matches!(
+            response,
+            DialogEngineApplyResponse::DialogChoice(DialogChoice::Confirm(
+                DialogConfirmChoice::Button(ref label)
+            )) if label == "Diff"
+        ));
+
+        // Shortcuts (ignoring case), and Esc.
+        assert!(matches!(
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@char 'N'))
+            ),
+            DialogEngineApplyResponse::DialogChoice(DialogChoice::Confirm(
+                DialogConfirmChoice::No
+            ))
+        ));
+        assert!(matches!(
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@special SpecialKey::Esc))
+            ),
+            DialogEngineApplyResponse::DialogChoice(DialogChoice::Confirm(
+                DialogConfirmChoice::Cancel
+            ))
+        ));
+
+        // Other keys are ignored.
+        assert!(matches!(
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@char 'x'))
+            ),
+            DialogEngineApplyResponse::Noop
+        ));
+    }
+
+    #[test]
+    fn apply_event_date_picker() {
+        let date = |month: u32, day: u32| {
+            chrono::NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+        };
+        let mut state = mock_real_objects_for_dialog::create_state();
+        state.dialog_buffers.insert(
+            FlexBoxId::from(0),
+            DialogBuffer::new_date_picker(
+                "Due date",
+                DialogDatePicker::new(date(10, 16)).with_max_date(date(11, 20)),
+            ),
+        );
+        let state = &mut state;
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.mode = DialogEngineMode::ModalDatePicker;
+
+        // Move by a week, then by a month (clamped to the max date).
+        assert!(matches!(
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@special SpecialKey::Down))
+            ),
+            DialogEngineApplyResponse::SelectDate
+        ));
+        assert!(matches!(
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@special SpecialKey::PageDown))
+            ),
+            DialogEngineApplyResponse::SelectDate
+        ));
+        assert!(matches!(
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@special SpecialKey::Right))
+            ),
+            DialogEngineApplyResponse::Noop
+        ));
+
+        // Other keys are ignored.
+        assert!(matches!(
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@char 'x'))
+            ),
+            DialogEngineApplyResponse::Noop
+        ));
+
+        let response = apply(
+            state,
+            dialog_engine,
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)),
+        );
+        let DialogEngineApplyResponse::DialogChoice(DialogChoice::Date(picked_date)) =
+            response
+        else {
+            panic!("Expected a date to be picked");
+        };
+        assert_eq2!(picked_date, date(11, 20));
+    }
+
+    #[test]
+    fn apply_event_color_picker() {
+        let mut state = mock_real_objects_for_dialog::create_state();
+        state.dialog_buffers.insert(
+            FlexBoxId::from(0),
+            DialogBuffer::new_color_picker("Border color", DialogColorPicker::new()),
+        );
+        let state = &mut state;
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.mode = DialogEngineMode::ModalColorPicker;
+        let enter = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
+
+        // Show the RGB tab, and type a color that isn't valid.
+        assert!(matches!(
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@special SpecialKey::BackTab))
+            ),
+            DialogEngineApplyResponse::SelectColor
+        ));
+        for character in "#ff80".chars() {
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@char character)),
+            );
+        }
+        assert!(matches!(
+            apply(state, dialog_engine, enter.clone()),
+            DialogEngineApplyResponse::SelectColor
+        ));
+        assert!(state
+            .get_mut_dialog_buffer(FlexBoxId::from(0))
+            .and_then(|it| it.maybe_color_picker.as_ref())
+            .is_some_and(|it| it.maybe_error.is_some()));
+
+        // Finish typing it, and pick it.
+        for character in "00".chars() {
+            apply(
+                state,
+                dialog_engine,
+                InputEvent::Keyboard(keypress!(@char character)),
+            );
+        }
+        let DialogEngineApplyResponse::DialogChoice(DialogChoice::Color(color)) =
+            apply(state, dialog_engine, enter)
+        else {
+            panic!("Expected a color to be picked");
+        };
+        assert_eq2!(
+            color,
+            TuiColor::Rgb(r3bl_core::RgbValue::from_u8(255, 128, 0))
+        );
+    }
 }
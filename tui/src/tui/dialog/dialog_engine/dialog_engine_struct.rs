@@ -29,10 +29,12 @@ use r3bl_core::{ch,
 use serde::{Deserialize, Serialize};
 
 use crate::{lookup_size,
+            DialogFormValidatorFn,
             DisplayConstants,
             EditorEngine,
             EditorEngineConfig,
             PartialFlexBox,
+            SuggestionRequester,
             SurfaceBounds};
 
 /// Please do not construct this struct directly, and use [new](DialogEngine::new)
@@ -69,8 +71,22 @@ pub struct DialogEngine {
         /* flex box calculated by render_engine(): */ PartialFlexBox,
     )>,
     pub maybe_surface_bounds: Option<SurfaceBounds>,
+    /// The selected row in the results panel (in [DialogEngineMode::ModalAutocomplete]),
+    /// or the field that has focus (in [DialogEngineMode::ModalForm]), or the button
+    /// that has focus (in [DialogEngineMode::ModalConfirm]).
     pub selected_row_index: ChUnit,
     pub scroll_offset_row_index: ChUnit,
+    /// Checks the values of the form before it is submitted (in
+    /// [DialogEngineMode::ModalForm]).
+    #[serde(skip)]
+    pub maybe_form_validator: Option<DialogFormValidatorFn>,
+    /// The error from [Self::maybe_form_validator] that is shown in the form, until a
+    /// field is changed.
+    pub maybe_form_error: Option<String>,
+    /// Asks for the suggestions that are shown in the results panel (in
+    /// [DialogEngineMode::ModalAutocomplete]) when the text in the editor changes.
+    #[serde(skip)]
+    pub maybe_suggestion_requester: Option<SuggestionRequester>,
 }
 
 impl DialogEngine {
@@ -117,6 +133,7 @@ impl DialogEngine {
     pub fn reset(&mut self) {
         self.selected_row_index = ch!(0);
         self.scroll_offset_row_index = ch!(0);
+        self.maybe_form_error = None;
     }
 }
 
@@ -154,4 +171,28 @@ mod dialog_engine_config_options_impl {
 pub enum DialogEngineMode {
     ModalSimple,
     ModalAutocomplete,
+    /// A form w/ the [crate::DialogBuffer::form_fields], one per row. <kbd>Tab</kbd>
+    /// (or <kbd>Down</kbd>) & <kbd>Shift+Tab</kbd> (or <kbd>Up</kbd>) move the focus
+    /// between the fields, and <kbd>Enter</kbd> submits it (see
+    /// [crate::DialogChoice::Form]).
+    ModalForm,
+    /// A file (or folder) picker, w/ the [crate::DialogBuffer::maybe_file_picker]. It
+    /// looks like [DialogEngineMode::ModalAutocomplete], w/ the entries of the current
+    /// folder in the results panel, and the editor to filter them. When a file is
+    /// picked, its path is passed in [crate::DialogChoice::Yes].
+    ModalFilePicker,
+    /// A question (the [crate::DialogBuffer::title]) w/ the
+    /// [crate::DialogBuffer::confirm_buttons] below it. <kbd>Left</kbd> &
+    /// <kbd>Right</kbd> (or <kbd>Tab</kbd>) move the focus between the buttons, and
+    /// <kbd>Enter</kbd> (or the shortcut of a button) presses it (see
+    /// [crate::DialogChoice::Confirm]).
+    ModalConfirm,
+    /// A month grid (w/ the [crate::DialogBuffer::maybe_date_picker]) below the
+    /// [crate::DialogBuffer::title], see [crate::DialogDatePicker] for the keys.
+    /// <kbd>Enter</kbd> picks the selected date (see [crate::DialogChoice::Date]).
+    ModalDatePicker,
+    /// The colors of the [crate::DialogBuffer::maybe_color_picker] (in tabs) below the
+    /// [crate::DialogBuffer::title], see [crate::DialogColorPicker] for the keys.
+    /// <kbd>Enter</kbd> picks the selected color (see [crate::DialogChoice::Color]).
+    ModalColorPicker,
 }
@@ -0,0 +1,294 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::{Debug, Formatter},
+          sync::{atomic::{AtomicU64, Ordering},
+                 Arc,
+                 Mutex},
+          time::Duration};
+
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use tokio::{sync::mpsc::Sender, task::AbortHandle};
+
+use crate::{FlexBoxId, TerminalWindowMainThreadSignal};
+
+/// How long to wait after the last keystroke before a query is sent to the
+/// [SuggestionProvider], so that it isn't asked for suggestions on each keystroke while
+/// the user is typing.
+pub const DEFAULT_SUGGESTION_DEBOUNCE_DELAY: Duration = Duration::from_millis(150);
+
+/// A suggestion that is shown in the results panel of an autocomplete dialog.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub text: String,
+    /// The indices of the chars in `text` that matched the query, which are highlighted
+    /// in the results panel.
+    pub match_indices: Vec<usize>,
+    /// Higher is better, see [fuzzy_match].
+    pub score: i64,
+}
+
+/// Provides the suggestions for the text that is typed in an autocomplete dialog (eg:
+/// from a file system, a database, or a web service). It is registered w/ a
+/// [SuggestionRequester], see [crate::DialogComponent::new_autocomplete_boxed].
+///
+/// The suggestions are shown in the order that they are returned in, so use
+/// [rank_suggestions] to rank the candidates (if they aren't ranked already).
+pub trait SuggestionProvider: Send + Sync {
+    fn get_suggestions(&self, query: String) -> BoxFuture<'static, Vec<Suggestion>>;
+}
+
+/// A [SuggestionProvider] for a fixed list of candidates, which are ranked w/
+/// [rank_suggestions].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StaticSuggestionProvider {
+    pub candidates: Vec<String>,
+}
+
+impl StaticSuggestionProvider {
+    pub fn new(candidates: Vec<String>) -> Self { Self { candidates } }
+}
+
+impl SuggestionProvider for StaticSuggestionProvider {
+    fn get_suggestions(&self, query: String) -> BoxFuture<'static, Vec<Suggestion>> {
+        let suggestions = rank_suggestions(&query, &self.candidates);
+        Box::pin(async move { suggestions })
+    }
+}
+
+/// Returns [None] if the chars of the `query` (ignoring case & whitespace) don't appear
+/// in the `candidate` in the same order. Otherwise the [Suggestion] has the indices of
+/// the chars that matched, and a score which is higher when:
+/// - the matched chars are next to each other,
+/// - they are at the start of words (eg: `fb` matches `foo_bar` better than `fab`),
+/// - the first one is closer to the start of the `candidate`.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<Suggestion> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|it| !it.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+
+    let mut match_indices = vec![];
+    let mut score = 0;
+    for (index, character) in candidate_chars.iter().enumerate() {
+        let Some(query_char) = query.get(match_indices.len()) else {
+            break;
+        };
+        if character.to_lowercase().next() != Some(*query_char) {
+            continue;
+        }
+
+        score += 1;
+        let maybe_prev_char = index.checked_sub(1).map(|it| candidate_chars[it]);
+        // Next to the previous match.
+        if index > 0 && match_indices.last() == Some(&(index - 1)) {
+            score += 5;
+        }
+        // At the start of a word (or of a camelCase hump).
+        let is_word_start = match maybe_prev_char {
+            None => true,
+            Some(prev_char) => {
+                !prev_char.is_alphanumeric()
+                    || (prev_char.is_lowercase() && character.is_uppercase())
+            }
+        };
+        if is_word_start {
+            score += 3;
+        }
+        match_indices.push(index);
+    }
+
+    if match_indices.len() < query.len() {
+        return None;
+    }
+
+    if let Some(first_index) = match_indices.first() {
+        score -= *first_index as i64;
+    }
+
+    Some(Suggestion {
+        text: candidate.to_string(),
+        match_indices,
+        score,
+    })
+}
+
+/// Returns the `candidates` that [fuzzy_match] the `query`, from the best match to the
+/// worst (and the shorter ones first when they match equally well).
+pub fn rank_suggestions(query: &str, candidates: &[String]) -> Vec<Suggestion> {
+    let mut it: Vec<Suggestion> = candidates
+        .iter()
+        .filter_map(|candidate| fuzzy_match(query, candidate))
+        .collect();
+    it.sort_by(|lhs, rhs| {
+        rhs.score
+            .cmp(&lhs.score)
+            .then_with(|| lhs.text.len().cmp(&rhs.text.len()))
+            .then_with(|| lhs.text.cmp(&rhs.text))
+    });
+    it
+}
+
+/// Asks a [SuggestionProvider] for suggestions (in a tokio task) when the text in an
+/// autocomplete dialog changes, see [SuggestionRequester::request].
+/// - The query is only sent after the text hasn't changed for the `debounce_delay`.
+/// - The task for the previous query is aborted when a new one is made, and the
+///   suggestions for a query that is stale (when it finishes) are dropped.
+///
+/// The suggestions are saved until they are taken by
+/// [crate::DialogEngineApi::render_engine] (w/ [SuggestionRequester::take_suggestions]),
+/// which is requested w/ [TerminalWindowMainThreadSignal::RequestRender].
+#[derive(Clone)]
+pub struct SuggestionRequester {
+    provider: Arc<dyn SuggestionProvider>,
+    pub debounce_delay: Duration,
+    generation: Arc<AtomicU64>,
+    pending_suggestions: Arc<Mutex<Option<Vec<Suggestion>>>>,
+    maybe_abort_handle: Option<AbortHandle>,
+}
+
+impl Debug for SuggestionRequester {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SuggestionRequester")
+            .field("debounce_delay", &self.debounce_delay)
+            .field("generation", &self.generation.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+impl SuggestionRequester {
+    pub fn new(provider: Arc<dyn SuggestionProvider>) -> Self {
+        Self {
+            provider,
+            debounce_delay: DEFAULT_SUGGESTION_DEBOUNCE_DELAY,
+            generation: Arc::new(AtomicU64::new(0)),
+            pending_suggestions: Arc::new(Mutex::new(None)),
+            maybe_abort_handle: None,
+        }
+    }
+
+    pub fn with_debounce_delay(mut self, debounce_delay: Duration) -> Self {
+        self.debounce_delay = debounce_delay;
+        self
+    }
+
+    /// Ask for the suggestions for the `query` (after the `debounce_delay`). Once they
+    /// are ready, the dialog w/ the `id` is rendered (which shows them).
+    pub fn request<AS>(
+        &mut self,
+        query: String,
+        id: FlexBoxId,
+        main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
+    ) where
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        // The previous query is stale, so don't wait for it.
+        if let Some(it) = self.maybe_abort_handle.take() {
+            it.abort();
+        }
+
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let latest_generation = self.generation.clone();
+        let pending_suggestions = self.pending_suggestions.clone();
+        let provider = self.provider.clone();
+        let debounce_delay = self.debounce_delay;
+
+        let join_handle = tokio::spawn(async move {
+            tokio::time::sleep(debounce_delay).await;
+            let suggestions = provider.get_suggestions(query).await;
+
+            // A newer query was made while this one was running.
+            if latest_generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+
+            if let Ok(mut it) = pending_suggestions.lock() {
+                *it = Some(suggestions);
+            }
+            let _ = main_thread_channel_sender
+                .send(TerminalWindowMainThreadSignal::RequestRender(Some(id)))
+                .await;
+        });
+        self.maybe_abort_handle = Some(join_handle.abort_handle());
+    }
+
+    /// Returns the suggestions for the latest query, if they arrived since this was last
+    /// called.
+    pub fn take_suggestions(&self) -> Option<Vec<Suggestion>> {
+        self.pending_suggestions.lock().ok()?.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match() {
+        let it = fuzzy_match("fb", "foo_bar").unwrap();
+        assert_eq2!(it.match_indices, vec![0, 4]);
+        assert_eq2!(
+            fuzzy_match("FOO", "foo_bar").unwrap().match_indices,
+            vec![0, 1, 2]
+        );
+        assert_eq2!(fuzzy_match("bf", "foo_bar"), None);
+        assert_eq2!(fuzzy_match("", "foo").unwrap().match_indices, vec![]);
+    }
+
+    #[test]
+    fn test_rank_suggestions() {
+        let candidates = ["abc", "xfxb", "fab", "foo_bar"].map(String::from).to_vec();
+        let texts: Vec<String> = rank_suggestions("fb", &candidates)
+            .into_iter()
+            .map(|it| it.text)
+            .collect();
+        assert_eq2!(texts, vec!["foo_bar", "fab", "xfxb"]);
+    }
+
+    #[tokio::test]
+    async fn test_stale_queries_are_dropped() {
+        let (sender, mut receiver) =
+            tokio::sync::mpsc::channel::<TerminalWindowMainThreadSignal<()>>(10);
+        let mut requester =
+            SuggestionRequester::new(Arc::new(StaticSuggestionProvider::new(vec![
+                "foo".to_string(),
+                "bar".to_string(),
+            ])))
+            .with_debounce_delay(Duration::from_millis(10));
+
+        requester.request("f".to_string(), FlexBoxId::from(0), sender.clone());
+        requester.request("b".to_string(), FlexBoxId::from(0), sender);
+
+        assert!(matches!(
+            receiver.recv().await,
+            Some(TerminalWindowMainThreadSignal::RequestRender(_))
+        ));
+        let texts: Vec<String> = requester
+            .take_suggestions()
+            .unwrap()
+            .into_iter()
+            .map(|it| it.text)
+            .collect();
+        assert_eq2!(texts, vec!["bar"]);
+        assert_eq2!(requester.take_suggestions(), None);
+    }
+}
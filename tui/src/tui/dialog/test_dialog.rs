@@ -46,6 +46,19 @@ pub mod mock_real_objects_for_dialog {
             maybe_saved_offscreen_buffer,
             main_thread_channel_sender,
             output_device,
+            layout_cache: Default::default(),
+            box_registry: Default::default(),
+            last_paint_stats: Default::default(),
+            render_scheduler: Default::default(),
+            resize_debouncer: Default::default(),
+            maybe_title: None,
+            maybe_paint_task: None,
+            toasts: Default::default(),
+            shortcuts: Default::default(),
+            timers: Default::default(),
+            context_menu: Default::default(),
+            maybe_splash_screen: None,
+            pending_clipboard_contents: Default::default(),
         };
 
         (global_data, stdout_mock)
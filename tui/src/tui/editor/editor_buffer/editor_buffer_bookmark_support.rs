@@ -0,0 +1,216 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, ChUnit};
+
+use super::{editor_buffer_diff_support::{find_line_hunks, LineHunk},
+            EditorBuffer};
+use crate::CaretDirection;
+
+/// This is painted in the line number gutter for bookmarks that don't have a name (the
+/// named ones display their name).
+pub const BOOKMARK_INDICATOR: char = '●';
+
+/// A bookmarked line (scroll adjusted, ie: in the buffer and not the viewport).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub row_index: ChUnit,
+    /// Named bookmarks can be jumped to directly (see
+    /// [crate::EditorEvent::GoToBookmark]). There is at most one bookmark w/ each name.
+    pub maybe_name: Option<char>,
+}
+
+/// The bookmarked lines of the buffer. Bookmarks are attached to lines and not to row
+/// indices, so they move w/ their lines when lines are added or removed above them, and
+/// they are removed along w/ their lines.
+///
+/// This is done by keeping a copy of the lines from when the bookmarks were last synced,
+/// which is matched against the current lines (like [super::diff_lines] does) the next
+/// time that the buffer is rendered, or that a bookmark is toggled or navigated to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EditorBookmarks {
+    /// The [EditorBuffer::get_content_version] that `bookmarks` were synced w/.
+    maybe_synced_content_version: Option<u64>,
+    /// This is only kept while there are bookmarks.
+    synced_lines: Vec<String>,
+    bookmarks: Vec<Bookmark>,
+}
+
+impl EditorBookmarks {
+    /// Sorted by row, w/ at most one bookmark per row.
+    pub fn get_bookmarks(&self) -> &[Bookmark] { &self.bookmarks }
+
+    pub fn get_bookmark_at(&self, row_index: ChUnit) -> Option<Bookmark> {
+        let index = self
+            .bookmarks
+            .binary_search_by_key(&row_index, |it| it.row_index)
+            .ok()?;
+        Some(self.bookmarks[index])
+    }
+}
+
+impl EditorBuffer {
+    /// Move the bookmarks to where their lines are now, if the content changed since
+    /// they were last synced.
+    pub(crate) fn sync_bookmarks(&mut self) {
+        let content_version = self.get_content_version();
+        let bookmarks = self.get_bookmarks();
+        if bookmarks.maybe_synced_content_version == Some(content_version) {
+            return;
+        }
+
+        let (synced_lines, moved_bookmarks) = match bookmarks.bookmarks.is_empty() {
+            true => (vec![], vec![]),
+            false => {
+                let lines = self.copy_lines();
+                let row_map = map_rows(&bookmarks.synced_lines, &lines);
+                let moved_bookmarks = bookmarks
+                    .bookmarks
+                    .iter()
+                    .filter_map(|it| {
+                        let row_index = (*row_map.get(ch!(@to_usize it.row_index))?)?;
+                        Some(Bookmark {
+                            row_index: ch!(row_index),
+                            ..*it
+                        })
+                    })
+                    .collect();
+                (lines, moved_bookmarks)
+            }
+        };
+
+        *self.get_bookmarks_mut() = EditorBookmarks {
+            maybe_synced_content_version: Some(content_version),
+            synced_lines,
+            bookmarks: moved_bookmarks,
+        };
+    }
+
+    /// Add a bookmark to the line at `row_index`, or remove the one that is already on
+    /// it. A named bookmark replaces the bookmark on the line if it has a different name,
+    /// and it is moved from any other line that it was on.
+    pub fn toggle_bookmark(&mut self, row_index: ChUnit, maybe_name: Option<char>) {
+        if row_index >= self.len() {
+            return;
+        }
+        self.sync_bookmarks();
+
+        if self.get_bookmarks().bookmarks.is_empty() {
+            let lines = self.copy_lines();
+            self.get_bookmarks_mut().synced_lines = lines;
+        }
+
+        let bookmarks = &mut self.get_bookmarks_mut().bookmarks;
+        let maybe_existing = bookmarks.iter().find(|it| it.row_index == row_index);
+        let is_toggle_off = match (maybe_existing, maybe_name) {
+            (Some(_), None) => true,
+            (Some(existing), Some(_)) => existing.maybe_name == maybe_name,
+            (None, _) => false,
+        };
+
+        bookmarks.retain(|it| {
+            it.row_index != row_index
+                && (maybe_name.is_none() || it.maybe_name != maybe_name)
+        });
+        if !is_toggle_off {
+            let index = bookmarks.partition_point(|it| it.row_index < row_index);
+            bookmarks.insert(
+                index,
+                Bookmark {
+                    row_index,
+                    maybe_name,
+                },
+            );
+        }
+    }
+
+    pub fn clear_bookmarks(&mut self) { *self.get_bookmarks_mut() = Default::default(); }
+
+    /// Returns the row of the next bookmark below (or above, depending on the
+    /// `direction`) the `row_index`. This wraps around at the end (or the start) of the
+    /// buffer.
+    pub fn find_bookmark(
+        &mut self,
+        row_index: ChUnit,
+        direction: CaretDirection,
+    ) -> Option<ChUnit> {
+        self.sync_bookmarks();
+        let mut row_indices =
+            self.get_bookmarks().bookmarks.iter().map(|it| it.row_index);
+        match direction {
+            CaretDirection::Up | CaretDirection::Left => row_indices
+                .clone()
+                .filter(|it| *it < row_index)
+                .last()
+                .or_else(|| row_indices.last()),
+            CaretDirection::Down | CaretDirection::Right => row_indices
+                .clone()
+                .find(|it| *it > row_index)
+                .or_else(|| row_indices.next()),
+        }
+    }
+
+    /// Returns the row of the bookmark w/ the given `name`, if there is one.
+    pub fn find_named_bookmark(&mut self, name: char) -> Option<ChUnit> {
+        self.sync_bookmarks();
+        self.get_bookmarks()
+            .bookmarks
+            .iter()
+            .find(|it| it.maybe_name == Some(name))
+            .map(|it| it.row_index)
+    }
+
+    fn copy_lines(&self) -> Vec<String> {
+        self.get_lines()
+            .iter()
+            .map(|it| it.string.clone())
+            .collect()
+    }
+}
+
+/// Returns the index in `new_lines` of each of the `old_lines`, or [None] if it was
+/// removed. Lines that were modified keep their place, ie: when a block of lines is
+/// replaced, its first lines are mapped to the first lines of the new block.
+fn map_rows(old_lines: &[String], new_lines: &[String]) -> Vec<Option<usize>> {
+    let mut row_map = Vec::with_capacity(old_lines.len());
+    let mut new_index = 0;
+
+    for LineHunk {
+        old_start_index,
+        removed_count,
+        new_start_index,
+        added_count,
+    } in find_line_hunks(old_lines, new_lines)
+    {
+        // The lines before the hunk are the same.
+        while row_map.len() < old_start_index {
+            row_map.push(Some(new_index));
+            new_index += 1;
+        }
+        for offset in 0..removed_count {
+            row_map.push((offset < added_count).then_some(new_start_index + offset));
+        }
+        new_index = new_start_index + added_count;
+    }
+
+    while row_map.len() < old_lines.len() {
+        row_map.push(Some(new_index));
+        new_index += 1;
+    }
+
+    row_map
+}
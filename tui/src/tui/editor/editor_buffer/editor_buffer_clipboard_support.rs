@@ -77,39 +77,15 @@ pub fn paste_from_clipboard(
     let result = clipboard_service_provider.try_to_get_content_from_clipboard();
     match result {
         Ok(clipboard_text) => {
-            // If the clipboard text does not contain a new line, then insert the text.
-            if !clipboard_text.contains('\n') {
-                EditorEngineInternalApi::insert_str_at_caret(
-                    EditorArgsMut {
-                        editor_engine: args.editor_engine,
-                        editor_buffer: args.editor_buffer,
-                    },
-                    clipboard_text.as_str(),
-                );
-            }
-            // If the clipboard text contains a new line, then insert the text line by line.
-            else {
-                let lines = clipboard_text.split('\n');
-                let line_count = lines.clone().count();
-                for (line_index, line) in lines.enumerate() {
-                    EditorEngineInternalApi::insert_str_at_caret(
-                        EditorArgsMut {
-                            editor_engine: args.editor_engine,
-                            editor_buffer: args.editor_buffer,
-                        },
-                        line,
-                    );
-                    // This is not the last line, so insert a new line.
-                    if line_index < line_count - 1 {
-                        EditorEngineInternalApi::insert_new_line_at_caret(
-                            EditorArgsMut {
-                                editor_engine: args.editor_engine,
-                                editor_buffer: args.editor_buffer,
-                            },
-                        );
-                    }
-                }
-            }
+            // All the lines are inserted in one go, which is much faster than inserting
+            // them (and the new lines) one at a time when a lot of text is pasted.
+            EditorEngineInternalApi::insert_text_at_caret(
+                EditorArgsMut {
+                    editor_engine: args.editor_engine,
+                    editor_buffer: args.editor_buffer,
+                },
+                clipboard_text.as_str(),
+            );
 
             call_if_true!(DEBUG_TUI_COPY_PASTE, {
                 tracing::debug!(
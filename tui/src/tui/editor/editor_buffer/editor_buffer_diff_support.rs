@@ -0,0 +1,269 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::cmp;
+
+use r3bl_core::{ch, ChUnit};
+
+use super::EditorBuffer;
+use crate::CaretDirection;
+
+/// If the part of the buffer that changed (once the lines that are the same at the start
+/// & end are skipped) is bigger than this many lines (baseline x current), then it is
+/// marked as modified as a whole, instead of running the (quadratic) diff on it.
+pub const MAX_DIFF_CELL_COUNT: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineChangeKind {
+    Added,
+    Modified,
+    /// One or more lines were removed just above this row (or below it, if it is the
+    /// last row).
+    Removed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineChange {
+    pub row_index: ChUnit,
+    pub kind: LineChangeKind,
+}
+
+/// Tracks the changes that were made to the buffer since its baseline, which is the
+/// content when it was last loaded or saved (see [EditorBuffer::reset_change_baseline]).
+///
+/// The changes are cached until the content changes (they are re-synced when the buffer
+/// is rendered).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EditorDiff {
+    maybe_baseline: Option<Vec<String>>,
+    is_baseline_reset_pending: bool,
+    /// The [EditorBuffer::get_content_version] that `changes` were found in.
+    maybe_synced_content_version: Option<u64>,
+    changes: Vec<LineChange>,
+}
+
+impl EditorDiff {
+    pub fn has_baseline(&self) -> bool { self.maybe_baseline.is_some() }
+
+    /// Sorted by row.
+    pub fn get_changes(&self) -> &[LineChange] { &self.changes }
+
+    pub fn get_change_at(&self, row_index: ChUnit) -> Option<LineChange> {
+        let index = self
+            .changes
+            .binary_search_by_key(&row_index, |it| it.row_index)
+            .ok()?;
+        Some(self.changes[index])
+    }
+
+    /// The rows where a block of changed lines starts.
+    fn get_hunk_start_rows(&self) -> impl Iterator<Item = ChUnit> + '_ {
+        self.changes.iter().enumerate().filter_map(|(index, it)| {
+            let is_after_change = index > 0
+                && self.changes[index - 1].row_index + ch!(1) == it.row_index
+                && self.changes[index - 1].kind != LineChangeKind::Removed
+                && it.kind != LineChangeKind::Removed;
+            (!is_after_change).then_some(it.row_index)
+        })
+    }
+}
+
+impl EditorBuffer {
+    /// Make the content of the buffer the baseline that changes are tracked against.
+    /// This is called when the buffer is loaded from (or saved to) a file, and apps can
+    /// call it for buffers that aren't backed by a file.
+    ///
+    /// The baseline is taken the next time that the buffer is rendered, which is after
+    /// any tabs in the content have been expanded.
+    pub fn reset_change_baseline(&mut self) {
+        let diff = self.get_diff_mut();
+        diff.is_baseline_reset_pending = true;
+        diff.maybe_synced_content_version = None;
+    }
+
+    /// Find the changes again if the content (or the baseline) changed since they were
+    /// last found.
+    pub(crate) fn sync_diff(&mut self) {
+        if self.get_diff().is_baseline_reset_pending {
+            let baseline = self
+                .get_lines()
+                .iter()
+                .map(|it| it.string.clone())
+                .collect();
+            let diff = self.get_diff_mut();
+            diff.maybe_baseline = Some(baseline);
+            diff.is_baseline_reset_pending = false;
+        }
+
+        let content_version = self.get_content_version();
+        let diff = self.get_diff();
+        if diff.maybe_synced_content_version == Some(content_version) {
+            return;
+        }
+        let Some(baseline) = diff.maybe_baseline.as_ref() else {
+            return;
+        };
+
+        let lines: Vec<&str> = self
+            .get_lines()
+            .iter()
+            .map(|it| it.string.as_str())
+            .collect();
+        let changes = diff_lines(baseline, &lines);
+        let diff = self.get_diff_mut();
+        diff.changes = changes;
+        diff.maybe_synced_content_version = Some(content_version);
+    }
+
+    /// Returns the row where the next block of changes starts, below (or above,
+    /// depending on the `direction`) the `row_index`.
+    pub fn find_change(
+        &mut self,
+        row_index: ChUnit,
+        direction: CaretDirection,
+    ) -> Option<ChUnit> {
+        self.sync_diff();
+        let mut hunk_start_rows = self.get_diff().get_hunk_start_rows();
+        match direction {
+            CaretDirection::Up | CaretDirection::Left => {
+                hunk_start_rows.filter(|it| *it < row_index).last()
+            }
+            CaretDirection::Down | CaretDirection::Right => {
+                hunk_start_rows.find(|it| *it > row_index)
+            }
+        }
+    }
+}
+
+/// Find the lines that were added, modified, or removed in `lines` when compared to
+/// `baseline`. This is a line based diff (using the longest common subsequence), where
+/// lines that replace removed lines are marked as modified.
+pub fn diff_lines(
+    baseline: &[impl AsRef<str>],
+    lines: &[impl AsRef<str>],
+) -> Vec<LineChange> {
+    let mut changes = vec![];
+    for LineHunk {
+        removed_count,
+        new_start_index,
+        added_count,
+        ..
+    } in find_line_hunks(baseline, lines)
+    {
+        for offset in 0..added_count {
+            changes.push(LineChange {
+                row_index: ch!(new_start_index + offset),
+                kind: match offset < removed_count {
+                    true => LineChangeKind::Modified,
+                    false => LineChangeKind::Added,
+                },
+            });
+        }
+        if added_count == 0 {
+            let row_index = cmp::min(new_start_index, lines.len().saturating_sub(1));
+            changes.push(LineChange {
+                row_index: ch!(row_index),
+                kind: LineChangeKind::Removed,
+            });
+        }
+    }
+    changes
+}
+
+/// A block of lines in the baseline that was replaced by a block of lines in the new
+/// content. One of the blocks can be empty (when lines were only added or removed), but
+/// not both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct LineHunk {
+    pub old_start_index: usize,
+    pub removed_count: usize,
+    pub new_start_index: usize,
+    pub added_count: usize,
+}
+
+/// Find the blocks of lines that are different in `lines` & `baseline`, sorted by row.
+pub(crate) fn find_line_hunks(
+    baseline: &[impl AsRef<str>],
+    lines: &[impl AsRef<str>],
+) -> Vec<LineHunk> {
+    let is_same =
+        |(lhs, rhs): &(&_, &_)| AsRef::<str>::as_ref(*lhs) == AsRef::<str>::as_ref(*rhs);
+    let prefix_count = baseline.iter().zip(lines).take_while(is_same).count();
+    let suffix_count = baseline[prefix_count..]
+        .iter()
+        .rev()
+        .zip(lines[prefix_count..].iter().rev())
+        .take_while(is_same)
+        .count();
+    let old = &baseline[prefix_count..baseline.len() - suffix_count];
+    let new = &lines[prefix_count..lines.len() - suffix_count];
+
+    let mut hunks = vec![];
+    let mut push_hunk = |old_start_index: usize,
+                         new_start_index: usize,
+                         removed_count: usize,
+                         added_count: usize| {
+        if removed_count > 0 || added_count > 0 {
+            hunks.push(LineHunk {
+                old_start_index: prefix_count + old_start_index,
+                removed_count,
+                new_start_index: prefix_count + new_start_index,
+                added_count,
+            });
+        }
+    };
+
+    if old.len() * new.len() > MAX_DIFF_CELL_COUNT {
+        push_hunk(0, 0, old.len(), new.len());
+        return hunks;
+    }
+
+    // The length of the longest common subsequence of `old[i..]` & `new[j..]`.
+    let (old_count, new_count) = (old.len(), new.len());
+    let mut lcs = vec![vec![0_u32; new_count + 1]; old_count + 1];
+    for i in (0..old_count).rev() {
+        for j in (0..new_count).rev() {
+            lcs[i][j] = if old[i].as_ref() == new[j].as_ref() {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                cmp::max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let (mut old_start_index, mut new_start_index) = (0, 0);
+    let (mut removed_count, mut added_count) = (0, 0);
+    while i < old_count || j < new_count {
+        if i < old_count && j < new_count && old[i].as_ref() == new[j].as_ref() {
+            push_hunk(old_start_index, new_start_index, removed_count, added_count);
+            i += 1;
+            j += 1;
+            (old_start_index, new_start_index) = (i, j);
+            (removed_count, added_count) = (0, 0);
+        } else if j < new_count && (i == old_count || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            added_count += 1;
+            j += 1;
+        } else {
+            removed_count += 1;
+            i += 1;
+        }
+    }
+    push_hunk(old_start_index, new_start_index, removed_count, added_count);
+
+    hunks
+}
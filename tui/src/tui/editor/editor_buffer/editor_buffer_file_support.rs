@@ -0,0 +1,496 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{ffi::OsStr,
+          fs,
+          io::Write,
+          path::{Path, PathBuf},
+          sync::{Arc, Mutex as StdMutex},
+          time::SystemTime};
+
+use crossterm::style::Stylize;
+use r3bl_core::{call_if_true, ch, position, CommonError, CommonErrorType, CommonResult};
+use serde::{Deserialize, Serialize};
+
+use super::EditorBuffer;
+use crate::{DEBUG_TUI_MOD, DEFAULT_SYN_HI_FILE_EXT};
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// The encoding of the file that was loaded into an [EditorBuffer]. It is detected by
+/// [EditorBuffer::load_from_path] and the same encoding is used when the buffer is saved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileEncoding {
+    #[default]
+    Utf8,
+    /// UTF-8 w/ a byte order mark at the start of the file.
+    Utf8WithBom,
+}
+
+/// The newline that the lines of the file that was loaded into an [EditorBuffer] end w/.
+/// It is detected (from the first line) by [EditorBuffer::load_from_path] and the same
+/// newline is used for all the lines when the buffer is saved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    /// Returns [LineEnding::CrLf] if the first line in `content` ends w/ it.
+    pub fn detect(content: &str) -> Self {
+        match content.find('\n') {
+            Some(index) if content[..index].ends_with('\r') => LineEnding::CrLf,
+            _ => LineEnding::Lf,
+        }
+    }
+}
+
+/// Details about the format of the file that was loaded into an [EditorBuffer], which
+/// aren't part of its lines, but have to be preserved when it is saved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileFormat {
+    pub encoding: FileEncoding,
+    pub line_ending: LineEnding,
+    /// Whether the last line of the file ends w/ a newline.
+    pub ends_with_newline: bool,
+}
+
+/// Used to detect whether a file was changed on disk (by another program).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileStamp {
+    pub maybe_modified: Option<SystemTime>,
+    pub len: u64,
+}
+
+/// The [FileStamp] of the file that is open in an [EditorBuffer], which the buffer
+/// updates each time that it loads or saves the file. It is shared w/ the
+/// [crate::EditorFileWatcher] that watches the file (see
+/// [EditorBuffer::get_shared_file_stamp]), so that the watcher doesn't mistake the
+/// buffer's own changes for changes made by other programs.
+pub type SharedFileStamp = Arc<StdMutex<Option<FileStamp>>>;
+
+impl FileStamp {
+    pub fn from_metadata(metadata: &fs::Metadata) -> Self {
+        Self {
+            maybe_modified: metadata.modified().ok(),
+            len: metadata.len(),
+        }
+    }
+
+    /// Returns [None] if the file doesn't exist (or can't be accessed).
+    pub fn read(path: impl AsRef<Path>) -> Option<Self> {
+        fs::metadata(path).ok().map(|it| Self::from_metadata(&it))
+    }
+
+    pub async fn read_async(path: impl AsRef<Path>) -> Option<Self> {
+        tokio::fs::metadata(path)
+            .await
+            .ok()
+            .map(|it| Self::from_metadata(&it))
+    }
+}
+
+impl EditorBuffer {
+    /// Create a new buffer w/ the content of the file at `path`. The file extension is
+    /// used for syntax highlighting (`md` is used if there is none).
+    ///
+    /// Only UTF-8 (w/ or w/out a byte order mark) is supported, anything else results in
+    /// an error.
+    pub fn load_from_path(path: impl AsRef<Path>) -> CommonResult<Self> {
+        let path = path.as_ref();
+
+        let maybe_file_extension = Some(
+            path.extension()
+                .and_then(OsStr::to_str)
+                .filter(|it| !it.is_empty())
+                .unwrap_or(DEFAULT_SYN_HI_FILE_EXT)
+                .to_owned(),
+        );
+        let maybe_file_path = Some(path.to_string_lossy().to_string());
+
+        let mut buffer = EditorBuffer::new_empty(&maybe_file_extension, &maybe_file_path);
+        buffer.load_file_content(path)?;
+        Ok(buffer)
+    }
+
+    /// Replace the content of the buffer w/ the content of its file (eg: after the file
+    /// was changed on disk by another program). Any changes that haven't been saved are
+    /// lost, and the undo history is cleared.
+    ///
+    /// The caret stays where it is, if that position still exists in the new content.
+    /// Otherwise it is moved to the start of the buffer.
+    pub fn reload(&mut self) -> CommonResult<()> {
+        let Some(file_path) = self.editor_content.maybe_file_path.clone() else {
+            return CommonError::new_error_result(
+                CommonErrorType::InvalidState,
+                "Editor buffer does not have a file path to reload from",
+            );
+        };
+
+        let caret = self.editor_content.caret_display_position;
+        let scroll_offset = self.editor_content.scroll_offset;
+
+        self.load_file_content(Path::new(&file_path))?;
+        self.clear_selection();
+        self.clear_secondary_carets();
+
+        let caret_adj = position!(
+            col_index: caret.col_index + scroll_offset.col_index,
+            row_index: caret.row_index + scroll_offset.row_index
+        );
+        let is_caret_valid =
+            match self.get_lines().get(ch!(@to_usize caret_adj.row_index)) {
+                Some(line) => {
+                    caret_adj.col_index <= line.display_width
+                        && line
+                            .is_display_col_index_in_middle_of_grapheme_cluster(
+                                caret_adj.col_index,
+                            )
+                            .is_none()
+                }
+                None => false,
+            };
+        if is_caret_valid {
+            self.editor_content.caret_display_position = caret;
+            self.editor_content.scroll_offset = scroll_offset;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the buffer's file was changed (or deleted) since it was last
+    /// loaded or saved by this buffer.
+    pub fn has_file_changed_on_disk(&self) -> bool {
+        match self.editor_content.maybe_file_path {
+            Some(ref file_path) => {
+                FileStamp::read(file_path) != self.get_maybe_file_stamp()
+            }
+            None => false,
+        }
+    }
+
+    fn load_file_content(&mut self, path: &Path) -> CommonResult<()> {
+        let bytes = match fs::read(path) {
+            Ok(it) => it,
+            Err(error) => {
+                return CommonError::new_error_result(
+                    CommonErrorType::IOError,
+                    &format!("Failed to read file: {path:?}, error: {error}"),
+                )
+            }
+        };
+
+        let (encoding, bytes) = match bytes.strip_prefix(UTF8_BOM) {
+            Some(rest) => (FileEncoding::Utf8WithBom, rest),
+            None => (FileEncoding::Utf8, bytes.as_slice()),
+        };
+
+        let Ok(content) = std::str::from_utf8(bytes) else {
+            return CommonError::new_error_result(
+                CommonErrorType::ParsingError,
+                &format!("File is not UTF-8 encoded: {path:?}"),
+            );
+        };
+
+        // This splits on both `\n` & `\r\n`.
+        self.set_lines(content.lines().map(String::from).collect());
+        self.set_file_format(FileFormat {
+            encoding,
+            line_ending: LineEnding::detect(content),
+            ends_with_newline: content.ends_with('\n'),
+        });
+        self.set_maybe_file_stamp(FileStamp::read(path));
+        self.set_dirty(false);
+        self.reset_change_baseline();
+
+        call_if_true!(DEBUG_TUI_MOD, {
+            tracing::debug!(
+                "\n💾💾💾✅ Successfully read file: {}",
+                format!("{path:?}").green()
+            );
+        });
+
+        Ok(())
+    }
+
+    /// Save the content of the buffer to the file that it was loaded from (or last
+    /// saved to). Returns an error if the buffer doesn't have a file path.
+    pub fn save(&mut self) -> CommonResult<()> {
+        let Some(file_path) = self.editor_content.maybe_file_path.clone() else {
+            return CommonError::new_error_result(
+                CommonErrorType::InvalidState,
+                "Editor buffer does not have a file path to save to",
+            );
+        };
+        self.save_to_path(file_path)
+    }
+
+    /// Save the content of the buffer to `path` in its [FileFormat], and make `path` the
+    /// file path of the buffer. This clears the dirty flag.
+    ///
+    /// The write is atomic: the content is written to a temporary file in the same
+    /// folder, which is then renamed to `path`. So if something goes wrong, the file at
+    /// `path` is left untouched.
+    pub fn save_to_path(&mut self, path: impl AsRef<Path>) -> CommonResult<()> {
+        let path = path.as_ref();
+
+        let mut content = vec![];
+        let file_format = self.get_file_format();
+        if let FileEncoding::Utf8WithBom = file_format.encoding {
+            content.extend_from_slice(UTF8_BOM);
+        }
+        let line_ending = file_format.line_ending.as_str();
+        let lines = self
+            .get_lines()
+            .iter()
+            .map(|it| it.string.as_str())
+            .collect::<Vec<_>>();
+        content.extend_from_slice(lines.join(line_ending).as_bytes());
+        if file_format.ends_with_newline {
+            content.extend_from_slice(line_ending.as_bytes());
+        }
+
+        if let Err(error) = write_atomically(path, &content) {
+            return CommonError::new_error_result(
+                CommonErrorType::IOError,
+                &format!("Failed to save file: {path:?}, error: {error}"),
+            );
+        }
+
+        self.editor_content.maybe_file_path = Some(path.to_string_lossy().to_string());
+        self.set_maybe_file_stamp(FileStamp::read(path));
+        self.set_dirty(false);
+        self.reset_change_baseline();
+
+        call_if_true!(DEBUG_TUI_MOD, {
+            tracing::debug!(
+                "\n💾💾💾✅ Successfully saved file: {}",
+                format!("{path:?}").green()
+            );
+        });
+
+        Ok(())
+    }
+}
+
+fn write_atomically(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    // If `path` is a symlink, then the file that it points to is replaced, instead of the
+    // symlink itself. The file might not exist yet, in which case `path` is used as is.
+    let path = &fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let temp_path = get_temp_path(path);
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+
+        // Keep the permissions of the file that is being replaced.
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+
+        fs::rename(&temp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result
+}
+
+/// The temporary file is in the same folder as `path`, so that it can be renamed to
+/// `path` (renaming across file systems is not atomic).
+fn get_temp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|it| it.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    /// Each test gets its own folder, since tests run in parallel.
+    fn make_temp_dir(test_name: &str) -> PathBuf {
+        let it = std::env::temp_dir().join(format!(
+            "r3bl_tui_editor_buffer_file_support_{test_name}_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&it);
+        fs::create_dir_all(&it).unwrap();
+        it
+    }
+
+    #[test]
+    fn test_load_and_save() {
+        let temp_dir = make_temp_dir("load_and_save");
+        let path = temp_dir.join("test.rs");
+        fs::write(&path, "fn main() {\n}\n").unwrap();
+
+        let mut buffer = EditorBuffer::load_from_path(&path).unwrap();
+        assert_eq2!(buffer.get_as_string_with_newlines(), "fn main() {\n}");
+        assert_eq2!(buffer.get_maybe_file_extension(), Some("rs"));
+        assert_eq2!(buffer.get_file_format().encoding, FileEncoding::Utf8);
+        assert!(!buffer.is_dirty());
+
+        buffer.set_lines(vec!["// hi".into()]);
+        buffer.set_dirty(true);
+        assert!(buffer.is_dirty());
+        buffer.save().unwrap();
+        assert!(!buffer.is_dirty());
+        assert_eq2!(fs::read_to_string(&path).unwrap(), "// hi\n");
+
+        // No temporary files are left behind.
+        assert_eq2!(fs::read_dir(&temp_dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_change_on_disk_and_reload() {
+        let temp_dir = make_temp_dir("change_on_disk_and_reload");
+        let path = temp_dir.join("test.md");
+        fs::write(&path, "abc\ndef").unwrap();
+
+        let mut buffer = EditorBuffer::load_from_path(&path).unwrap();
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 2, row_index: 1);
+        assert!(!buffer.has_file_changed_on_disk());
+
+        // Saving from the buffer is not a change on disk.
+        buffer.save().unwrap();
+        assert!(!buffer.has_file_changed_on_disk());
+
+        fs::write(&path, "abc\ndefghi\nxyz").unwrap();
+        assert!(buffer.has_file_changed_on_disk());
+
+        buffer.reload().unwrap();
+        assert!(!buffer.has_file_changed_on_disk());
+        assert_eq2!(buffer.get_as_string_with_newlines(), "abc\ndefghi\nxyz");
+        assert_eq2!(
+            buffer.editor_content.caret_display_position,
+            position!(col_index: 2, row_index: 1)
+        );
+
+        // The caret is reset if its position doesn't exist anymore.
+        fs::write(&path, "a").unwrap();
+        buffer.reload().unwrap();
+        assert_eq2!(
+            buffer.editor_content.caret_display_position,
+            position!(col_index: 0, row_index: 0)
+        );
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_bom_is_preserved() {
+        let temp_dir = make_temp_dir("bom_is_preserved");
+        let path = temp_dir.join("test");
+        fs::write(&path, [UTF8_BOM, b"abc"].concat()).unwrap();
+
+        let mut buffer = EditorBuffer::load_from_path(&path).unwrap();
+        assert_eq2!(buffer.get_as_string_with_newlines(), "abc");
+        assert_eq2!(
+            buffer.get_maybe_file_extension(),
+            Some(DEFAULT_SYN_HI_FILE_EXT)
+        );
+        assert_eq2!(buffer.get_file_format().encoding, FileEncoding::Utf8WithBom);
+
+        let new_path = temp_dir.join("new.md");
+        buffer.save_to_path(&new_path).unwrap();
+        assert_eq2!(fs::read(&new_path).unwrap(), [UTF8_BOM, b"abc"].concat());
+        assert_eq2!(
+            buffer.get_maybe_file_path(),
+            Some(new_path.to_string_lossy().as_ref())
+        );
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_crlf_is_preserved() {
+        let temp_dir = make_temp_dir("crlf_is_preserved");
+        let path = temp_dir.join("test.md");
+        fs::write(&path, "abc\r\ndef\r\n").unwrap();
+
+        let mut buffer = EditorBuffer::load_from_path(&path).unwrap();
+        assert_eq2!(buffer.get_as_string_with_newlines(), "abc\ndef");
+        assert_eq2!(buffer.get_file_format().line_ending, LineEnding::CrLf);
+        assert!(buffer.get_file_format().ends_with_newline);
+
+        buffer.set_lines(vec!["abc".into(), "def".into(), "ghi".into()]);
+        buffer.save().unwrap();
+        assert_eq2!(fs::read_to_string(&path).unwrap(), "abc\r\ndef\r\nghi\r\n");
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_through_symlink() {
+        let temp_dir = make_temp_dir("save_through_symlink");
+        let target_path = temp_dir.join("target.md");
+        let link_path = temp_dir.join("link.md");
+        fs::write(&target_path, "abc").unwrap();
+        std::os::unix::fs::symlink(&target_path, &link_path).unwrap();
+
+        let mut buffer = EditorBuffer::load_from_path(&link_path).unwrap();
+        buffer.set_lines(vec!["xyz".into()]);
+        buffer.save().unwrap();
+
+        // The symlink is still there, and the file that it points to was changed.
+        assert!(fs::symlink_metadata(&link_path)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq2!(fs::read_to_string(&target_path).unwrap(), "xyz");
+        assert_eq2!(
+            buffer.get_maybe_file_path(),
+            Some(link_path.to_string_lossy().as_ref())
+        );
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let temp_dir = make_temp_dir("load_errors");
+
+        assert!(EditorBuffer::load_from_path(temp_dir.join("missing.md")).is_err());
+
+        let path = temp_dir.join("latin1.txt");
+        fs::write(&path, [0x63, 0x61, 0x66, 0xE9]).unwrap();
+        assert!(EditorBuffer::load_from_path(&path).is_err());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
@@ -0,0 +1,405 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{cmp::{self, Reverse},
+          iter::once,
+          mem::take};
+
+use r3bl_core::{ch,
+                position,
+                CaretMovementDirection,
+                ChUnit,
+                Position,
+                SelectionRange,
+                UnicodeString};
+use serde::{Deserialize, Serialize};
+
+use super::EditorBuffer;
+use crate::{CaretDirection,
+            CaretKind,
+            EditorEngine,
+            EditorEngineInternalApi,
+            SelectMode};
+
+/// A caret in addition to the primary one, which is the one that is stored in
+/// [crate::EditorContent::caret_display_position].
+///
+/// 1. Secondary carets are added w/ <kbd>Ctrl + D</kbd> (select the next occurrence of
+///    the selected text) or <kbd>Alt + Click</kbd>.
+/// 2. Editing events (typing, backspace, delete, etc) are applied at all the carets.
+///    Any other event (eg: moving the caret, or inserting a new line) collapses them
+///    back to the primary caret.
+#[derive(
+    Clone, Copy, PartialEq, Serialize, Deserialize, Debug, Default, size_of::SizeOf,
+)]
+pub struct SecondaryCaret {
+    /// Scroll adjusted position of the caret (in the buffer, not the viewport).
+    pub position: Position,
+    /// The selection that goes w/ this caret. It is always on the same row as the caret.
+    pub maybe_selection: Option<SelectionRange>,
+}
+
+/// Select the next occurrence of the selected text, and add a [SecondaryCaret] at its
+/// end. If nothing is selected, then the word at the caret is selected instead (w/out
+/// adding a caret).
+///
+/// Only single line selections are supported. The search starts after the caret that
+/// was added last, and wraps around to the top of the buffer.
+pub fn add_caret_at_next_occurrence(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+) -> Option<()> {
+    let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+
+    if !buffer.has_selection() {
+        let line = buffer.get_lines().get(ch!(@to_usize caret_adj.row_index))?;
+        let word_range = find_word_range_at(line, caret_adj.col_index)?;
+        select_range_at_caret(buffer, engine, caret_adj.row_index, word_range);
+        return None;
+    }
+
+    // Only a single line selection on the caret's row can be searched for.
+    let selection_map = buffer.get_selection_map();
+    if selection_map.map.len() != 1 {
+        return None;
+    }
+    let primary_range = *selection_map.get(caret_adj.row_index)?;
+    let needle = buffer
+        .get_lines()
+        .get(ch!(@to_usize caret_adj.row_index))?
+        .clip_to_range(primary_range)
+        .to_string();
+    if needle.is_empty() {
+        return None;
+    }
+
+    let last_caret = buffer
+        .editor_content
+        .secondary_carets
+        .last()
+        .map(|it| it.position)
+        .unwrap_or(caret_adj);
+
+    let (row_index, range) =
+        find_next_occurrence(buffer.get_lines(), &needle, last_caret)?;
+    let new_caret = {
+        let col_index = range.end_display_col_index;
+        position!(col_index: col_index, row_index: row_index)
+    };
+
+    // Every occurrence already has a caret.
+    if is_caret_at(buffer, new_caret) {
+        return None;
+    }
+
+    buffer.editor_content.secondary_carets.push(SecondaryCaret {
+        position: new_caret,
+        maybe_selection: Some(range),
+    });
+
+    None
+}
+
+/// Add a [SecondaryCaret] at the given scroll adjusted position (eg: in response to
+/// <kbd>Alt + Click</kbd>). If there is already a caret there then nothing happens.
+pub fn add_caret_at_position(buffer: &mut EditorBuffer, position: Position) {
+    if is_caret_at(buffer, position) {
+        return;
+    }
+    buffer.editor_content.secondary_carets.push(SecondaryCaret {
+        position,
+        maybe_selection: None,
+    });
+}
+
+/// Extend the rectangular (block) selection by moving the caret one step in the given
+/// direction (eg: in response to <kbd>Alt + Shift + Arrow</kbd>). The first step sets
+/// [crate::EditorContent::maybe_block_selection_anchor] to where the caret was.
+///
+/// The block is made out of a selection & a [SecondaryCaret] on every row between the
+/// anchor & the caret, so editing events are applied to all the rows (see
+/// [apply_at_all_carets]), which is how column-wise insert & delete work. The selection
+/// on every row is also added to the [crate::SelectionMap], so that the block can be
+/// copied (or cut). Rows that are too short to reach the block are skipped.
+pub fn extend_block_selection(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    direction: CaretDirection,
+) {
+    let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+    let anchor = *buffer
+        .editor_content
+        .maybe_block_selection_anchor
+        .get_or_insert(caret_adj);
+
+    match direction {
+        CaretDirection::Up => {
+            EditorEngineInternalApi::up(buffer, engine, SelectMode::Disabled)
+        }
+        CaretDirection::Down => {
+            EditorEngineInternalApi::down(buffer, engine, SelectMode::Disabled)
+        }
+        CaretDirection::Left => {
+            EditorEngineInternalApi::left(buffer, engine, SelectMode::Disabled)
+        }
+        CaretDirection::Right => {
+            EditorEngineInternalApi::right(buffer, engine, SelectMode::Disabled)
+        }
+    };
+
+    let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+    let start_col_index = cmp::min(anchor.col_index, caret_adj.col_index);
+    let end_col_index = cmp::max(anchor.col_index, caret_adj.col_index);
+    let is_block_empty = start_col_index == end_col_index;
+    let movement_direction = if caret_adj.col_index < anchor.col_index {
+        CaretMovementDirection::Left
+    } else {
+        CaretMovementDirection::Right
+    };
+
+    buffer.clear_selection();
+    buffer.clear_secondary_carets();
+
+    let start_row_index = ch!(@to_usize cmp::min(anchor.row_index, caret_adj.row_index));
+    let end_row_index = ch!(@to_usize cmp::max(anchor.row_index, caret_adj.row_index));
+    for row_index in start_row_index..=end_row_index {
+        let row_index = ch!(row_index);
+        let line_width = buffer.get_line_display_width(row_index);
+        if line_width < start_col_index {
+            continue;
+        }
+
+        let range =
+            SelectionRange::new(start_col_index, cmp::min(end_col_index, line_width));
+        let is_range_empty = range.start_display_col_index == range.end_display_col_index;
+        if row_index == caret_adj.row_index {
+            if !is_range_empty {
+                let (_, _, _, selection_map) = buffer.get_mut();
+                selection_map.insert(row_index, range, movement_direction);
+            }
+            continue;
+        }
+
+        // A row that ends where a block w/ some width starts has nothing to select.
+        let maybe_selection = match (is_range_empty, is_block_empty) {
+            (false, _) => Some(range),
+            (true, true) => None,
+            (true, false) => continue,
+        };
+        if let Some(range) = maybe_selection {
+            let (_, _, _, selection_map) = buffer.get_mut();
+            selection_map.insert(row_index, range, movement_direction);
+        }
+        let col_index = match movement_direction {
+            CaretMovementDirection::Left => range.start_display_col_index,
+            _ => range.end_display_col_index,
+        };
+        buffer.editor_content.secondary_carets.push(SecondaryCaret {
+            position: position!(col_index: col_index, row_index: row_index),
+            maybe_selection,
+        });
+    }
+}
+
+/// Run `apply` once for every caret (the primary one and all the secondary ones), w/ that
+/// caret temporarily being the primary one.
+///
+/// 1. The carets are processed from the bottom of the buffer to the top (and right to
+///    left in a row), so that an edit doesn't move the carets that are still waiting to
+///    be processed.
+/// 2. An edit might change the width of its row, so the carets on the same row that
+///    were already processed are shifted by the same amount.
+/// 3. `apply` must not add or remove lines, since the rows of the other carets aren't
+///    adjusted.
+///
+/// Once done, the selections are cleared, and carets that ended up in the same place
+/// are merged.
+pub fn apply_at_all_carets(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    mut apply: impl FnMut(&mut EditorBuffer, &mut EditorEngine),
+) {
+    let primary_caret = {
+        let position = buffer.get_caret(CaretKind::ScrollAdjusted);
+        SecondaryCaret {
+            position,
+            maybe_selection: buffer.get_selection_map().get(position.row_index).copied(),
+        }
+    };
+
+    // The primary caret is always at index 0.
+    let mut carets: Vec<SecondaryCaret> = vec![primary_caret];
+    carets.extend(take(&mut buffer.editor_content.secondary_carets));
+
+    let mut processing_order: Vec<usize> = (0..carets.len()).collect();
+    processing_order.sort_by_key(|&index| {
+        let it = carets[index].position;
+        Reverse((it.row_index, it.col_index))
+    });
+
+    for (count, &index) in processing_order.iter().enumerate() {
+        let SecondaryCaret {
+            position,
+            maybe_selection,
+        } = carets[index];
+        let row_index = position.row_index;
+
+        EditorEngineInternalApi::move_caret_to_buffer_position(buffer, engine, position);
+        if let Some(range) = maybe_selection {
+            select_range_at_caret(buffer, engine, row_index, range);
+        }
+
+        let width_before = buffer.get_line_display_width(row_index);
+        apply(buffer, engine);
+        buffer.clear_selection();
+        let width_after = buffer.get_line_display_width(row_index);
+
+        // The carets that were processed already are to the right of this one.
+        for &processed_index in &processing_order[..count] {
+            let it = &mut carets[processed_index].position;
+            if it.row_index == row_index {
+                it.col_index = it.col_index + width_after - width_before;
+            }
+        }
+
+        carets[index] = SecondaryCaret {
+            position: buffer.get_caret(CaretKind::ScrollAdjusted),
+            maybe_selection: None,
+        };
+    }
+
+    let primary_position = carets[0].position;
+    EditorEngineInternalApi::move_caret_to_buffer_position(
+        buffer,
+        engine,
+        primary_position,
+    );
+
+    for caret in carets.into_iter().skip(1) {
+        add_caret_at_position(buffer, caret.position);
+    }
+}
+
+/// Move the (primary) caret to the end of the `range` in `row_index`, and select it.
+pub(crate) fn select_range_at_caret(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    row_index: ChUnit,
+    range: SelectionRange,
+) {
+    let caret_adj = {
+        let col_index = range.end_display_col_index;
+        position!(col_index: col_index, row_index: row_index)
+    };
+    EditorEngineInternalApi::move_caret_to_buffer_position(buffer, engine, caret_adj);
+    let (_, _, _, selection_map) = buffer.get_mut();
+    selection_map.insert(row_index, range, CaretMovementDirection::Right);
+}
+
+fn is_caret_at(buffer: &EditorBuffer, position: Position) -> bool {
+    buffer.get_caret(CaretKind::ScrollAdjusted) == position
+        || buffer
+            .editor_content
+            .secondary_carets
+            .iter()
+            .any(|it| it.position == position)
+}
+
+fn is_word_segment(segment: &str) -> bool {
+    segment.chars().all(|it| it.is_alphanumeric() || it == '_')
+}
+
+/// Returns the range of the word that the display col index is in (or just after).
+fn find_word_range_at(line: &UnicodeString, col_index: ChUnit) -> Option<SelectionRange> {
+    let segments = &line.vec_segment;
+
+    // Prefer the segment at the caret, and fall back to the one to its left (for when
+    // the caret is at the end of a word).
+    let index = segments
+        .iter()
+        .position(|it| it.display_col_offset == col_index)
+        .filter(|&it| is_word_segment(&segments[it].string))
+        .or_else(|| {
+            segments
+                .iter()
+                .position(|it| it.display_col_offset + it.unicode_width == col_index)
+                .filter(|&it| is_word_segment(&segments[it].string))
+        })?;
+
+    let start_index = segments[..index]
+        .iter()
+        .rposition(|it| !is_word_segment(&it.string))
+        .map(|it| it + 1)
+        .unwrap_or(0);
+    let end_index = segments[index..]
+        .iter()
+        .position(|it| !is_word_segment(&it.string))
+        .map(|it| it + index)
+        .unwrap_or(segments.len());
+
+    let start = segments[start_index].display_col_offset;
+    let end = segments[end_index - 1].display_col_offset
+        + segments[end_index - 1].unicode_width;
+    Some(SelectionRange::new(start, end))
+}
+
+/// Find the first occurrence of `needle` that starts after `after` (a scroll adjusted
+/// position), wrapping around to the top of the buffer.
+fn find_next_occurrence(
+    lines: &[UnicodeString],
+    needle: &str,
+    after: Position,
+) -> Option<(ChUnit, SelectionRange)> {
+    let needle_width = ch!(UnicodeString::str_display_width(needle));
+    let after_row_index = ch!(@to_usize after.row_index);
+
+    // Rows after the caret, then the ones before it (wrap around), and finally the
+    // caret's own row (since it can have occurrences both to the left & right of it).
+    let row_indices = (after_row_index..lines.len())
+        .chain(0..after_row_index)
+        .chain(once(after_row_index));
+
+    for (count, row_index) in row_indices.enumerate() {
+        let Some(line) = lines.get(row_index) else {
+            continue;
+        };
+        let is_first_row = count == 0;
+        let is_last_row = count == lines.len();
+        let maybe_start = line
+            .vec_segment
+            .iter()
+            .filter(|it| {
+                if is_first_row {
+                    it.display_col_offset >= after.col_index
+                } else if is_last_row {
+                    it.display_col_offset < after.col_index
+                } else {
+                    true
+                }
+            })
+            .find(|it| line.string[it.byte_offset..].starts_with(needle))
+            .map(|it| it.display_col_offset);
+        if let Some(start) = maybe_start {
+            return Some((
+                ch!(row_index),
+                SelectionRange::new(start, start + needle_width),
+            ));
+        }
+    }
+
+    None
+}
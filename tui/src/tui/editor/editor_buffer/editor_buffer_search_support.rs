@@ -0,0 +1,108 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, ChUnit, SelectionRange, UnicodeString};
+
+use super::EditorBuffer;
+
+/// An occurrence of the search query in the buffer (scroll adjusted, ie: in the buffer
+/// and not the viewport).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchMatch {
+    pub row_index: ChUnit,
+    pub range: SelectionRange,
+}
+
+/// The search that stays active while the buffer is scrolled & edited, so that all the
+/// matches in the viewport stay highlighted, and the ones that are scrolled out of view
+/// are marked in the line number gutter.
+///
+/// The matches are found in the whole buffer, and are cached until the query or the
+/// content changes (they are re-synced when the buffer is rendered).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EditorSearch {
+    maybe_query: Option<String>,
+    /// The [EditorBuffer::get_content_version] that `matches` were found in.
+    maybe_synced_content_version: Option<u64>,
+    matches: Vec<SearchMatch>,
+}
+
+impl EditorSearch {
+    pub fn get_query(&self) -> Option<&str> { self.maybe_query.as_deref() }
+
+    /// Sorted by row, and then by col.
+    pub fn get_matches(&self) -> &[SearchMatch] { &self.matches }
+}
+
+impl EditorBuffer {
+    /// Start a search for `query` (or stop it if it is [None] or empty). The search stays
+    /// active until this is called again.
+    pub fn set_search_query(&mut self, maybe_query: Option<String>) {
+        let maybe_query = maybe_query.filter(|it| !it.is_empty());
+        if self.get_search().maybe_query == maybe_query {
+            return;
+        }
+        *self.get_search_mut() = EditorSearch {
+            maybe_query,
+            ..Default::default()
+        };
+    }
+
+    /// Find the matches again if the content changed since they were last found. This
+    /// is called when the buffer is rendered.
+    pub(crate) fn sync_search_matches(&mut self) {
+        let content_version = self.get_content_version();
+        let search = self.get_search();
+        if search.maybe_synced_content_version == Some(content_version) {
+            return;
+        }
+        let Some(query) = search.maybe_query.as_deref() else {
+            return;
+        };
+
+        let matches = find_search_matches(self.get_lines(), query);
+        let search = self.get_search_mut();
+        search.matches = matches;
+        search.maybe_synced_content_version = Some(content_version);
+    }
+}
+
+/// Find all the (non overlapping) occurrences of `query` in `lines`. An occurrence has
+/// to start at the start of a grapheme cluster.
+pub fn find_search_matches(lines: &[UnicodeString], query: &str) -> Vec<SearchMatch> {
+    let query_width = ch!(UnicodeString::str_display_width(query));
+    let mut matches = vec![];
+
+    for (row_index, line) in lines.iter().enumerate() {
+        let mut next_col_index = ch!(0);
+        for segment in line.vec_segment.iter() {
+            if segment.display_col_offset < next_col_index
+                || !line.string[segment.byte_offset..].starts_with(query)
+            {
+                continue;
+            }
+            let start = segment.display_col_offset;
+            next_col_index = start + query_width;
+            matches.push(SearchMatch {
+                row_index: ch!(row_index),
+                range: SelectionRange::new(start, next_col_index),
+            });
+        }
+    }
+
+    matches
+}
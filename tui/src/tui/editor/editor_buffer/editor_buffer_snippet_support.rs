@@ -0,0 +1,465 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashMap, iter::Peekable, mem, str::Chars};
+
+use r3bl_core::{ch, position, ChUnit, SelectionRange, UnicodeString};
+
+use super::{editor_buffer_multi_caret_support::select_range_at_caret,
+            EditorBuffer,
+            SecondaryCaret};
+use crate::{validate_editor_buffer_change,
+            CaretKind,
+            EditorEngine,
+            EditorEngineInternalApi,
+            EditorEvent};
+
+/// A tab stop in a [Snippet]. Its position is relative to the start of the snippet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnippetTabStop {
+    /// `$0` is the final tab stop.
+    pub number: usize,
+    pub row_index: ChUnit,
+    /// The display cols of the placeholder (this is empty if there isn't one).
+    pub range: SelectionRange,
+}
+
+/// A snippet that has been parsed into its lines (w/ the placeholders filled in) and its
+/// tab stops. The syntax is:
+/// 1. `${1:placeholder}` is a tab stop w/ a placeholder, which can't span lines.
+/// 2. `${1}` or `$1` is a tab stop w/out a placeholder. If the same number has a
+///    placeholder somewhere else in the snippet, then it is used here too (mirrored).
+/// 3. `$0` is where the caret ends up once all the tab stops are visited. If there isn't
+///    one, then the caret ends up at the end of the snippet.
+/// 4. `\$`, `\}` and `\\` are escapes for the literal chars.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snippet {
+    pub lines: Vec<String>,
+    pub tab_stops: Vec<SnippetTabStop>,
+}
+
+enum SnippetPiece {
+    Text(String),
+    TabStop {
+        number: usize,
+        maybe_placeholder: Option<String>,
+    },
+}
+
+impl Snippet {
+    pub fn parse(snippet: &str) -> Self {
+        let pieces = parse_pieces(snippet);
+
+        let placeholders: HashMap<usize, String> = pieces
+            .iter()
+            .rev()
+            .filter_map(|it| match it {
+                SnippetPiece::TabStop {
+                    number,
+                    maybe_placeholder: Some(placeholder),
+                } => Some((*number, placeholder.clone())),
+                _ => None,
+            })
+            .collect();
+
+        let mut lines = vec![String::new()];
+        let mut tab_stops = vec![];
+        for piece in pieces {
+            match piece {
+                SnippetPiece::Text(text) => {
+                    let mut split = text.split('\n');
+                    lines
+                        .last_mut()
+                        .unwrap()
+                        .push_str(split.next().unwrap_or_default());
+                    lines.extend(split.map(String::from));
+                }
+                SnippetPiece::TabStop {
+                    number,
+                    maybe_placeholder,
+                } => {
+                    let placeholder = maybe_placeholder
+                        .or_else(|| placeholders.get(&number).cloned())
+                        .unwrap_or_default()
+                        .replace('\n', " ");
+                    let line = lines.last_mut().unwrap();
+                    let start_col_index = ch!(UnicodeString::str_display_width(line));
+                    line.push_str(&placeholder);
+                    let end_col_index = ch!(UnicodeString::str_display_width(line));
+                    tab_stops.push(SnippetTabStop {
+                        number,
+                        row_index: ch!(lines.len() - 1),
+                        range: SelectionRange::new(start_col_index, end_col_index),
+                    });
+                }
+            }
+        }
+
+        Self { lines, tab_stops }
+    }
+}
+
+fn parse_pieces(snippet: &str) -> Vec<SnippetPiece> {
+    let mut pieces = vec![];
+    let mut text = String::new();
+    let mut chars = snippet.chars().peekable();
+
+    while let Some(character) = chars.next() {
+        match character {
+            '\\' if matches!(chars.peek(), Some('$' | '}' | '\\')) => {
+                text.extend(chars.next());
+            }
+            '$' => match parse_tab_stop(&mut chars) {
+                Some(tab_stop) => {
+                    pieces.push(SnippetPiece::Text(mem::take(&mut text)));
+                    pieces.push(tab_stop);
+                }
+                None => text.push(character),
+            },
+            _ => text.push(character),
+        }
+    }
+    pieces.push(SnippetPiece::Text(text));
+
+    pieces
+}
+
+/// Parse the tab stop after a `$`. Nothing is consumed from `chars` if there isn't one.
+fn parse_tab_stop(chars: &mut Peekable<Chars<'_>>) -> Option<SnippetPiece> {
+    let mut it = chars.clone();
+
+    let is_braced = it.next_if_eq(&'{').is_some();
+    let mut digits = String::new();
+    while let Some(digit) = it.next_if(char::is_ascii_digit) {
+        digits.push(digit);
+    }
+    let number = digits.parse().ok()?;
+
+    let maybe_placeholder = match is_braced {
+        false => None,
+        true => match it.next()? {
+            '}' => None,
+            ':' => {
+                let mut placeholder = String::new();
+                loop {
+                    match it.next()? {
+                        '\\' if matches!(it.peek(), Some('$' | '}' | '\\')) => {
+                            placeholder.extend(it.next());
+                        }
+                        '}' => break,
+                        character => placeholder.push(character),
+                    }
+                }
+                Some(placeholder)
+            }
+            _ => return None,
+        },
+    };
+
+    *chars = it;
+    Some(SnippetPiece::TabStop {
+        number,
+        maybe_placeholder,
+    })
+}
+
+/// The snippet that is being filled in, which is stored in the [EditorBuffer] until its
+/// final tab stop is reached (or another event ends it, see [try_handle_editor_event]).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SnippetSession {
+    /// The tab stops in the order that they are visited, each w/ all its (mirrored)
+    /// ranges in the buffer. The last one is the final tab stop.
+    tab_stops: Vec<Vec<(ChUnit, SelectionRange)>>,
+    current_index: usize,
+    /// The display width of the rows of the current tab stop, and the number of lines in
+    /// the buffer, when the current tab stop was selected. These are used to find out how
+    /// much the current tab stop has grown (or shrunk) by typing in it.
+    row_widths: Vec<(ChUnit, ChUnit)>,
+    line_count: ChUnit,
+}
+
+impl SnippetSession {
+    /// Adjust the ranges of the tab stops to the edits that were made to the current
+    /// tab stop. Returns [None] if that can't be done, since lines were added or removed.
+    fn sync_with_buffer(&mut self, buffer: &EditorBuffer) -> Option<()> {
+        if buffer.len() != self.line_count {
+            return None;
+        }
+
+        for &(row_index, width_before) in &self.row_widths {
+            let mut current_ranges: Vec<SelectionRange> = self.tab_stops
+                [self.current_index]
+                .iter()
+                .filter(|(it, _)| *it == row_index)
+                .map(|(_, range)| *range)
+                .collect();
+            current_ranges.sort_by_key(|it| it.start_display_col_index);
+
+            // Every (mirrored) range of the current tab stop in the row was edited the
+            // same way, so they all changed by the same amount.
+            let width_after = buffer.get_line_display_width(row_index);
+            let delta = (ch!(@to_usize width_after) as isize
+                - ch!(@to_usize width_before) as isize)
+                / current_ranges.len() as isize;
+            let shift = |col_index: ChUnit, count: usize| {
+                let it = ch!(@to_usize col_index) as isize + delta * count as isize;
+                ch!(it.max(0) as usize)
+            };
+
+            for (tab_stop_index, tab_stop) in self.tab_stops.iter_mut().enumerate() {
+                for (_, range) in tab_stop.iter_mut().filter(|(it, _)| *it == row_index) {
+                    let (start_count, end_count) = if tab_stop_index == self.current_index
+                    {
+                        let count = current_ranges
+                            .iter()
+                            .filter(|it| {
+                                it.start_display_col_index < range.start_display_col_index
+                            })
+                            .count();
+                        (count, count + 1)
+                    } else {
+                        let count = current_ranges
+                            .iter()
+                            .filter(|it| {
+                                it.end_display_col_index <= range.start_display_col_index
+                            })
+                            .count();
+                        (count, count)
+                    };
+                    *range = SelectionRange::new(
+                        shift(range.start_display_col_index, start_count),
+                        shift(range.end_display_col_index, end_count),
+                    );
+                }
+            }
+        }
+
+        Some(())
+    }
+}
+
+impl EditorBuffer {
+    pub fn is_snippet_active(&self) -> bool { self.get_maybe_snippet_session().is_some() }
+}
+
+/// Insert the `snippet` (see [Snippet] for its syntax) at the caret, and select its
+/// first tab stop. The lines of the snippet after the 1st one are indented like the
+/// caret's line.
+///
+/// Each tab stop is selected w/ the multi caret machinery: the primary caret selects its
+/// 1st range, and a [SecondaryCaret] selects each of the (mirrored) others, so typing
+/// replaces the placeholders.
+pub fn insert_snippet(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    snippet: &str,
+) {
+    let Snippet {
+        lines: snippet_lines,
+        tab_stops,
+    } = Snippet::parse(snippet);
+
+    let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+    let row_index = ch!(@to_usize caret_adj.row_index);
+    let line = buffer
+        .get_lines()
+        .get(row_index)
+        .cloned()
+        .unwrap_or_else(|| UnicodeString::from(""));
+    let indent: String = line.string.chars().take_while(|it| *it == ' ').collect();
+    let indent_width = ch!(UnicodeString::str_display_width(&indent));
+
+    let mut new_lines: Vec<String> = snippet_lines
+        .iter()
+        .enumerate()
+        .map(|(index, it)| match index {
+            0 => format!("{}{it}", line.clip_to_width(ch!(0), caret_adj.col_index)),
+            _ => format!("{indent}{it}"),
+        })
+        .collect();
+    let last_line_width = ch!(UnicodeString::str_display_width(
+        new_lines.last().map(String::as_str).unwrap_or_default()
+    ));
+    if let Some(last_line) = new_lines.last_mut() {
+        let after_caret_width = line.display_width - caret_adj.col_index;
+        last_line.push_str(line.clip_to_width(caret_adj.col_index, after_caret_width));
+    }
+
+    validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+        while lines.len() <= row_index {
+            lines.push(UnicodeString::from(""));
+        }
+        for (offset, new_line) in new_lines.into_iter().enumerate() {
+            match offset {
+                0 => lines[row_index] = new_line.into(),
+                _ => lines.insert(row_index + offset, new_line.into()),
+            }
+        }
+    });
+
+    // Convert the tab stops to buffer positions.
+    let to_buffer_range = |tab_stop: &SnippetTabStop| {
+        let col_index = match tab_stop.row_index == ch!(0) {
+            true => caret_adj.col_index,
+            false => indent_width,
+        };
+        (
+            caret_adj.row_index + tab_stop.row_index,
+            SelectionRange::new(
+                col_index + tab_stop.range.start_display_col_index,
+                col_index + tab_stop.range.end_display_col_index,
+            ),
+        )
+    };
+    let mut numbers: Vec<usize> = tab_stops
+        .iter()
+        .map(|it| it.number)
+        .filter(|it| *it != 0)
+        .collect();
+    numbers.sort();
+    numbers.dedup();
+    let mut session_tab_stops: Vec<Vec<(ChUnit, SelectionRange)>> = numbers
+        .iter()
+        .map(|number| {
+            tab_stops
+                .iter()
+                .filter(|it| it.number == *number)
+                .map(to_buffer_range)
+                .collect()
+        })
+        .collect();
+
+    // The final tab stop is `$0`, or the end of the snippet.
+    let final_tab_stop = tab_stops
+        .iter()
+        .find(|it| it.number == 0)
+        .map(to_buffer_range)
+        .unwrap_or_else(|| {
+            let row_index = caret_adj.row_index + ch!(snippet_lines.len() - 1);
+            (
+                row_index,
+                SelectionRange::new(last_line_width, last_line_width),
+            )
+        });
+    session_tab_stops.push(vec![final_tab_stop]);
+
+    select_tab_stop(
+        buffer,
+        engine,
+        SnippetSession {
+            tab_stops: session_tab_stops,
+            ..Default::default()
+        },
+    );
+}
+
+/// While a snippet is being filled in, <kbd>Tab</kbd> moves to the next tab stop and
+/// <kbd>Shift + Tab</kbd> moves to the previous one. Returns `true` if the
+/// `editor_event` was handled.
+///
+/// Editing the current tab stop (typing, backspace, delete, etc) keeps the snippet
+/// going, and any other event (eg: moving the caret) ends it.
+pub fn try_handle_editor_event(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    editor_event: &EditorEvent,
+) -> bool {
+    if !buffer.is_snippet_active() {
+        return false;
+    }
+
+    match editor_event {
+        EditorEvent::InsertTab | EditorEvent::Outdent => {
+            let Some(mut session) = buffer.get_maybe_snippet_session_mut().take() else {
+                return false;
+            };
+            if session.sync_with_buffer(buffer).is_none() {
+                return false;
+            }
+            session.current_index = match editor_event {
+                EditorEvent::InsertTab => session.current_index + 1,
+                _ => session.current_index.saturating_sub(1),
+            };
+            select_tab_stop(buffer, engine, session);
+            true
+        }
+        EditorEvent::InsertChar(_)
+        | EditorEvent::Backspace
+        | EditorEvent::BackspaceWord
+        | EditorEvent::Delete
+        | EditorEvent::DeleteWord
+        | EditorEvent::Copy
+        | EditorEvent::Save
+        | EditorEvent::Resize(_) => false,
+        EditorEvent::InsertString(chunk) if !chunk.contains('\n') => false,
+        EditorEvent::InsertText(text) if !text.contains('\n') => false,
+        _ => {
+            *buffer.get_maybe_snippet_session_mut() = None;
+            false
+        }
+    }
+}
+
+/// Select all the ranges of the current tab stop of the `session`, and store it in the
+/// `buffer`, unless it is the final tab stop, which ends the snippet.
+fn select_tab_stop(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    mut session: SnippetSession,
+) {
+    let ranges = session.tab_stops[session.current_index].clone();
+
+    buffer.clear_selection();
+    buffer.clear_secondary_carets();
+    for (index, (row_index, range)) in ranges.iter().enumerate() {
+        let is_empty = range.start_display_col_index == range.end_display_col_index;
+        match index {
+            0 if is_empty => {
+                let caret_adj = {
+                    let col_index = range.start_display_col_index;
+                    position!(col_index: col_index, row_index: *row_index)
+                };
+                EditorEngineInternalApi::move_caret_to_buffer_position(
+                    buffer, engine, caret_adj,
+                );
+            }
+            0 => select_range_at_caret(buffer, engine, *row_index, *range),
+            _ => {
+                let col_index = range.end_display_col_index;
+                buffer.editor_content.secondary_carets.push(SecondaryCaret {
+                    position: position!(col_index: col_index, row_index: *row_index),
+                    maybe_selection: (!is_empty).then_some(*range),
+                });
+            }
+        }
+    }
+
+    if session.current_index + 1 == session.tab_stops.len() {
+        *buffer.get_maybe_snippet_session_mut() = None;
+        return;
+    }
+
+    let mut row_indices: Vec<ChUnit> = ranges.iter().map(|(it, _)| *it).collect();
+    row_indices.sort();
+    row_indices.dedup();
+    session.row_widths = row_indices
+        .into_iter()
+        .map(|it| (it, buffer.get_line_display_width(it)))
+        .collect();
+    session.line_count = buffer.len();
+    *buffer.get_maybe_snippet_session_mut() = Some(session);
+}
@@ -0,0 +1,222 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashSet,
+          fmt::{Debug, Formatter},
+          path::{Path, PathBuf}};
+
+use r3bl_core::{CommonError, CommonErrorType, CommonResult};
+use zspell::Dictionary;
+
+use super::SpellChecker;
+
+/// The folders where Hunspell dictionaries are usually installed, see
+/// [DictionarySpellChecker::load_system_dictionary].
+pub const SYSTEM_DICTIONARY_FOLDERS: [&str; 4] = [
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/Library/Spelling",
+];
+
+/// The most suggestions that [DictionarySpellChecker::suggestions] returns.
+pub const MAX_SPELLING_SUGGESTIONS: usize = 8;
+
+/// A [SpellChecker] that is backed by a Hunspell dictionary (an `.aff` & a `.dic` file),
+/// using the `zspell` crate. This is only available w/ the `spellcheck` feature.
+///
+/// Suggestions are the words (that the dictionary knows) that are one edit away from
+/// the misspelled word, ie: one letter is deleted, inserted, replaced, or two adjacent
+/// letters are swapped.
+pub struct DictionarySpellChecker {
+    dictionary: Dictionary,
+}
+
+impl Debug for DictionarySpellChecker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DictionarySpellChecker")
+            .finish_non_exhaustive()
+    }
+}
+
+impl DictionarySpellChecker {
+    /// Create a spell checker from the content of a Hunspell `.aff` (affix) & `.dic`
+    /// (word list) file.
+    pub fn new(aff_content: &str, dic_content: &str) -> CommonResult<Self> {
+        match zspell::builder()
+            .config_str(aff_content)
+            .dict_str(dic_content)
+            .build()
+        {
+            Ok(dictionary) => Ok(Self { dictionary }),
+            Err(error) => CommonError::new_error_result(
+                CommonErrorType::ParsingError,
+                &format!("Failed to build spelling dictionary, error: {error}"),
+            ),
+        }
+    }
+
+    /// Create a spell checker from the Hunspell files at `aff_path` & `dic_path`.
+    pub fn load_from_paths(
+        aff_path: impl AsRef<Path>,
+        dic_path: impl AsRef<Path>,
+    ) -> CommonResult<Self> {
+        let aff_content = read_to_string(aff_path.as_ref())?;
+        let dic_content = read_to_string(dic_path.as_ref())?;
+        Self::new(&aff_content, &dic_content)
+    }
+
+    /// Create a spell checker from the Hunspell dictionary for the `language` (eg:
+    /// `en_US`) that is installed on the system, in one of the
+    /// [SYSTEM_DICTIONARY_FOLDERS].
+    pub fn load_system_dictionary(language: &str) -> CommonResult<Self> {
+        let maybe_paths = SYSTEM_DICTIONARY_FOLDERS.iter().find_map(|folder| {
+            let aff_path = PathBuf::from(folder).join(format!("{language}.aff"));
+            let dic_path = PathBuf::from(folder).join(format!("{language}.dic"));
+            (aff_path.is_file() && dic_path.is_file()).then_some((aff_path, dic_path))
+        });
+
+        match maybe_paths {
+            Some((aff_path, dic_path)) => Self::load_from_paths(aff_path, dic_path),
+            None => CommonError::new_error_result(
+                CommonErrorType::NotFound,
+                &format!("No spelling dictionary is installed for: {language}"),
+            ),
+        }
+    }
+}
+
+impl SpellChecker for DictionarySpellChecker {
+    fn is_correct(&self, word: &str) -> bool { self.dictionary.check_word(word) }
+
+    fn suggestions(&self, word: &str) -> Vec<String> {
+        let mut suggestions = vec![];
+        let mut seen = HashSet::new();
+        for candidate in find_edits(word) {
+            if suggestions.len() == MAX_SPELLING_SUGGESTIONS {
+                break;
+            }
+            if candidate != word
+                && seen.insert(candidate.clone())
+                && self.dictionary.check_word(&candidate)
+            {
+                suggestions.push(candidate);
+            }
+        }
+        suggestions
+    }
+}
+
+fn read_to_string(path: &Path) -> CommonResult<String> {
+    match std::fs::read_to_string(path) {
+        Ok(it) => Ok(it),
+        Err(error) => CommonError::new_error_result(
+            CommonErrorType::IOError,
+            &format!("Failed to read file: {path:?}, error: {error}"),
+        ),
+    }
+}
+
+/// The words that are one edit away from `word`, the likely ones first: swapped
+/// letters, then deleted, replaced & inserted letters. The letters that are inserted (or
+/// used as replacements) are the lowercase ASCII letters & the letters in `word`.
+fn find_edits(word: &str) -> Vec<String> {
+    let chars = word.chars().collect::<Vec<_>>();
+    let mut letters = ('a'..='z').collect::<Vec<_>>();
+    for character in chars.iter().flat_map(|it| it.to_lowercase()) {
+        if !letters.contains(&character) {
+            letters.push(character);
+        }
+    }
+
+    let to_string = |it: &[char]| it.iter().collect::<String>();
+    let mut edits = vec![];
+
+    for index in 1..chars.len() {
+        let mut it = chars.clone();
+        it.swap(index - 1, index);
+        edits.push(to_string(&it));
+    }
+    for index in 0..chars.len() {
+        let mut it = chars.clone();
+        it.remove(index);
+        edits.push(to_string(&it));
+    }
+    for index in 0..chars.len() {
+        for letter in &letters {
+            let mut it = chars.clone();
+            it[index] = *letter;
+            edits.push(to_string(&it));
+        }
+    }
+    for index in 0..=chars.len() {
+        for letter in &letters {
+            let mut it = chars.clone();
+            it.insert(index, *letter);
+            edits.push(to_string(&it));
+        }
+    }
+
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    const AFF: &str = "SET UTF-8\n";
+    const DIC: &str = "5\nhello\nworld\nword\nwould\nspell\n";
+
+    #[test]
+    fn test_is_correct() {
+        let spell_checker = DictionarySpellChecker::new(AFF, DIC).unwrap();
+        assert!(spell_checker.is_correct("hello"));
+        assert!(spell_checker.is_correct("world"));
+        assert!(!spell_checker.is_correct("wrold"));
+        assert!(!spell_checker.is_correct("helo"));
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let spell_checker = DictionarySpellChecker::new(AFF, DIC).unwrap();
+
+        // Swapped letters come first.
+        assert_eq2!(
+            spell_checker.suggestions("wrold"),
+            vec!["world".to_string()]
+        );
+        // Inserted letter.
+        assert_eq2!(spell_checker.suggestions("helo"), vec!["hello".to_string()]);
+        // Deleted & replaced letters.
+        assert_eq2!(
+            spell_checker.suggestions("worlds"),
+            vec!["world".to_string()]
+        );
+        assert_eq2!(spell_checker.suggestions("wurd"), vec!["word".to_string()]);
+        // Nothing is one edit away.
+        assert!(spell_checker.suggestions("xyzzy").is_empty());
+    }
+
+    #[test]
+    fn test_load_system_dictionary_not_found() {
+        assert!(
+            DictionarySpellChecker::load_system_dictionary("xx_NOT_A_LANGUAGE").is_err()
+        );
+    }
+}
@@ -0,0 +1,230 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, iter, sync::Arc};
+
+use r3bl_core::{ch, ChUnit, Position, SelectionRange, UnicodeString};
+
+use super::EditorBuffer;
+
+/// Apps implement this to add spell checking to the editor, by setting it in
+/// [crate::EditorEngine::maybe_spell_checker]. W/ the `spellcheck` feature, this crate
+/// provides `DictionarySpellChecker`, which is backed by a Hunspell dictionary. Apps can
+/// also implement it w/ their own dictionary crate.
+///
+/// Only the prose in markdown buffers is checked, code fences, inline code & links are
+/// skipped. The misspelled words are underlined, and
+/// [crate::EditorEvent::ShowSpellingSuggestions] displays the
+/// [SpellChecker::suggestions] for the word at the caret in the completion popup.
+pub trait SpellChecker: Debug + Send + Sync {
+    fn is_correct(&self, word: &str) -> bool;
+
+    /// The replacements for the misspelled `word`, the best one first.
+    fn suggestions(&self, word: &str) -> Vec<String>;
+}
+
+pub type SharedSpellChecker = Arc<dyn SpellChecker>;
+
+/// A misspelled word in the buffer (scroll adjusted, ie: in the buffer and not the
+/// viewport).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Misspelling {
+    pub row_index: ChUnit,
+    pub range: SelectionRange,
+    pub word: String,
+}
+
+/// The misspelled words in the buffer, which are cached until the content changes (they
+/// are re-synced when the buffer is rendered).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EditorSpellCheck {
+    /// The [EditorBuffer::get_content_version] that `misspellings` were found in.
+    maybe_synced_content_version: Option<u64>,
+    misspellings: Vec<Misspelling>,
+}
+
+impl EditorSpellCheck {
+    /// Sorted by row, and then by col.
+    pub fn get_misspellings(&self) -> &[Misspelling] { &self.misspellings }
+
+    /// The misspelled word that contains the `position`, or that ends right before it
+    /// (so that it is found when the caret is just after the word).
+    pub fn get_misspelling_at(&self, position: Position) -> Option<&Misspelling> {
+        let first_index = self
+            .misspellings
+            .partition_point(|it| it.row_index < position.row_index);
+        self.misspellings[first_index..]
+            .iter()
+            .take_while(|it| it.row_index == position.row_index)
+            .find(|it| {
+                it.range.start_display_col_index <= position.col_index
+                    && position.col_index <= it.range.end_display_col_index
+            })
+    }
+}
+
+impl EditorBuffer {
+    /// Find the misspelled words again if the content changed since they were last
+    /// found. This is called when the buffer is rendered.
+    pub(crate) fn sync_spell_check(
+        &mut self,
+        maybe_spell_checker: Option<&SharedSpellChecker>,
+    ) {
+        let Some(spell_checker) = maybe_spell_checker else {
+            if !self.get_spell_check().misspellings.is_empty() {
+                *self.get_spell_check_mut() = Default::default();
+            }
+            return;
+        };
+
+        let content_version = self.get_content_version();
+        if self.get_spell_check().maybe_synced_content_version == Some(content_version) {
+            return;
+        }
+
+        let misspellings = match self.is_file_extension_default() {
+            true => find_misspellings(self.get_lines(), spell_checker.as_ref()),
+            false => vec![],
+        };
+        *self.get_spell_check_mut() = EditorSpellCheck {
+            maybe_synced_content_version: Some(content_version),
+            misspellings,
+        };
+    }
+}
+
+/// Find the words in the markdown `lines` that the `spell_checker` doesn't know. Code
+/// fences, inline code & links are skipped, and so are words that look like
+/// identifiers or acronyms (eg: `snake_case`, `camelCase`, `HTTP`), and single letters.
+pub fn find_misspellings(
+    lines: &[UnicodeString],
+    spell_checker: &dyn SpellChecker,
+) -> Vec<Misspelling> {
+    let mut misspellings = vec![];
+    let mut is_in_code_fence = false;
+
+    for (row_index, line) in lines.iter().enumerate() {
+        let trimmed_line = line.string.trim_start();
+        if trimmed_line.starts_with("```") || trimmed_line.starts_with("~~~") {
+            is_in_code_fence = !is_in_code_fence;
+            continue;
+        }
+        if is_in_code_fence {
+            continue;
+        }
+
+        for (byte_offset, word) in find_prose_words(&line.string) {
+            if spell_checker.is_correct(word) {
+                continue;
+            }
+            let start_col_index = ch!(UnicodeString::str_display_width(
+                &line.string[..byte_offset]
+            ));
+            let end_col_index =
+                start_col_index + ch!(UnicodeString::str_display_width(word));
+            misspellings.push(Misspelling {
+                row_index: ch!(row_index),
+                range: SelectionRange::new(start_col_index, end_col_index),
+                word: word.to_string(),
+            });
+        }
+    }
+
+    misspellings
+}
+
+/// The words (w/ their byte offsets) in the `line` that should be spell checked.
+fn find_prose_words(line: &str) -> Vec<(usize, &str)> {
+    let mut words = vec![];
+    let mut is_in_inline_code = false;
+    let mut maybe_token_start = None;
+
+    // The trailing space ends the last token.
+    for (byte_index, character) in
+        line.char_indices().chain(iter::once((line.len(), ' ')))
+    {
+        match (character.is_whitespace(), maybe_token_start) {
+            (true, Some(token_start)) => {
+                find_words_in_token(
+                    &line[token_start..byte_index],
+                    token_start,
+                    &mut is_in_inline_code,
+                    &mut words,
+                );
+                maybe_token_start = None;
+            }
+            (false, None) => maybe_token_start = Some(byte_index),
+            _ => {}
+        }
+    }
+
+    words
+}
+
+/// Split the whitespace delimited `token` into words, which are runs of letters (w/
+/// apostrophes in them, eg: `don't`).
+fn find_words_in_token<'a>(
+    token: &'a str,
+    token_byte_offset: usize,
+    is_in_inline_code: &mut bool,
+    words: &mut Vec<(usize, &'a str)>,
+) {
+    let is_link =
+        token.contains("://") || token.contains('@') || token.starts_with("www.");
+    let is_identifier_char = |it: char| it.is_ascii_digit() || it == '_';
+
+    let mut maybe_word_start = None;
+    let mut previous_character = None;
+
+    // The trailing space ends the last word.
+    for (byte_index, character) in
+        token.char_indices().chain(iter::once((token.len(), ' ')))
+    {
+        let is_word_char = character.is_alphabetic()
+            || (character == '\'' && maybe_word_start.is_some());
+        if is_word_char {
+            if maybe_word_start.is_none() {
+                maybe_word_start = Some((byte_index, previous_character));
+            }
+            previous_character = Some(character);
+            continue;
+        }
+
+        if let Some((word_start, maybe_character_before)) = maybe_word_start.take() {
+            let word = token[word_start..byte_index].trim_end_matches('\'');
+            let touches_identifier = is_identifier_char(character)
+                || maybe_character_before.is_some_and(is_identifier_char);
+            if !*is_in_inline_code
+                && !is_link
+                && !touches_identifier
+                && should_check_word(word)
+            {
+                words.push((token_byte_offset + word_start, word));
+            }
+        }
+
+        if character == '`' {
+            *is_in_inline_code = !*is_in_inline_code;
+        }
+        previous_character = Some(character);
+    }
+}
+
+/// Single letters, acronyms & `camelCase` words are not checked.
+fn should_check_word(word: &str) -> bool {
+    word.chars().nth(1).is_some() && !word.chars().skip(1).any(char::is_uppercase)
+}
@@ -30,13 +30,26 @@ use r3bl_core::{call_if_true,
 use serde::{Deserialize, Serialize};
 use size_of::SizeOf as _;
 
-use super::SelectionMap;
-use crate::{EditorEngine,
+use super::{EditorBookmarks,
+            EditorDiff,
+            EditorSearch,
+            EditorSpellCheck,
+            FileFormat,
+            FileStamp,
+            SecondaryCaret,
+            SelectionMap,
+            SharedFileStamp,
+            SnippetSession,
+            SyntectHighlightCache};
+use crate::{timed_if_enabled,
+            EditorEngine,
             EditorEngineApi,
             HasFocus,
             RenderArgs,
             RenderOps,
             ScrollOffset,
+            SyntaxHighlightMode,
+            TelemetryKind,
             DEBUG_TUI_COPY_PASTE,
             DEBUG_TUI_MOD,
             DEFAULT_SYN_HI_FILE_EXT};
@@ -182,11 +195,100 @@ use crate::{EditorEngine,
 /// in the map represents a row of text in the buffer.
 /// - The row index is the key.
 /// - The value is the [r3bl_core::SelectionRange].
-#[derive(Clone, PartialEq, Serialize, Deserialize, Default)]
+///
+/// ## `secondary_carets`
+///
+/// The carets in addition to `caret_display_position`, see [SecondaryCaret]. This is
+/// empty unless multiple carets have been added (eg: w/ <kbd>Ctrl + D</kbd>).
+///
+/// ## `maybe_block_selection_anchor`
+///
+/// The corner of the rectangular (block) selection that stays put, while the caret is
+/// the opposite corner. This is [None] unless a block selection is being made (w/
+/// <kbd>Alt + Shift + Arrow</kbd>), see [super::extend_block_selection].
+///
+/// ## `content_version`
+///
+/// This is bumped every time the content might have been mutated. It is the key for
+/// the [SyntectHighlightCache], which only re-highlights lines starting from the first
+/// one that was edited. Neither of them are compared by [PartialEq], since they don't
+/// change what is in the buffer.
+///
+/// ## `file_format` & `is_dirty`
+///
+/// The [FileFormat] of the file that the content was loaded from (see
+/// [load_from_path](EditorBuffer::load_from_path)), which is used to save it back. And
+/// whether the content has been changed since it was loaded or saved.
+///
+/// ## `maybe_file_stamp`
+///
+/// The [FileStamp] of the file when it was last loaded or saved, which is used to detect
+/// changes made to the file by other programs. It is shared w/ the
+/// [crate::EditorFileWatcher] (if any) that watches the file, see [SharedFileStamp].
+///
+/// ## `maybe_pending_go_to_line`
+///
+/// The line number that the app asked to go to (see
+/// [request_go_to_line](EditorBuffer::request_go_to_line)). It is applied the next time
+/// the buffer is rendered, since the viewport size is needed to center the line.
+///
+/// ## `search`
+///
+/// The [EditorSearch] that stays active (see
+/// [set_search_query](EditorBuffer::set_search_query)), and its matches.
+///
+/// ## `diff`
+///
+/// The [EditorDiff] that tracks the changes made since the buffer was last loaded or
+/// saved (see [reset_change_baseline](EditorBuffer::reset_change_baseline)).
+///
+/// ## `spell_check`
+///
+/// The [EditorSpellCheck] that holds the misspelled words, when the editor has a
+/// [crate::SpellChecker].
+///
+/// ## `bookmarks`
+///
+/// The [EditorBookmarks] of the lines that were bookmarked (see
+/// [toggle_bookmark](EditorBuffer::toggle_bookmark)).
+///
+/// ## `maybe_snippet_session`
+///
+/// The [SnippetSession] of the snippet that is being filled in (see
+/// [crate::EditorEvent::InsertSnippet]), until its final tab stop is reached.
+///
+/// ## `maybe_laid_out_tab_width`
+///
+/// The tab width that the `\t` in the lines were laid out w/ (by the engine), or [None]
+/// if the lines were replaced w/out the engine (eg: by
+/// [set_lines](EditorBuffer::set_lines) or an undo), so they have to be laid out again.
+#[derive(Clone, Serialize, Deserialize, Default)]
 pub struct EditorBuffer {
     pub editor_content: EditorContent,
     pub history: EditorBufferHistory,
     pub render_cache: HashMap<String, RenderOps>,
+    #[serde(skip)]
+    pub syntect_highlight_cache: SyntectHighlightCache,
+    #[serde(skip)]
+    content_version: u64,
+    file_format: FileFormat,
+    is_dirty: bool,
+    #[serde(skip)]
+    maybe_file_stamp: SharedFileStamp,
+    #[serde(skip)]
+    maybe_pending_go_to_line: Option<usize>,
+    #[serde(skip)]
+    search: EditorSearch,
+    #[serde(skip)]
+    diff: EditorDiff,
+    #[serde(skip)]
+    spell_check: EditorSpellCheck,
+    #[serde(skip)]
+    bookmarks: EditorBookmarks,
+    #[serde(skip)]
+    maybe_snippet_session: Option<SnippetSession>,
+    #[serde(skip)]
+    maybe_laid_out_tab_width: Option<usize>,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, Default, size_of::SizeOf)]
@@ -197,6 +299,8 @@ pub struct EditorContent {
     pub maybe_file_extension: Option<String>,
     pub maybe_file_path: Option<String>,
     pub selection_map: SelectionMap,
+    pub secondary_carets: Vec<SecondaryCaret>,
+    pub maybe_block_selection_anchor: Option<Position>,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, size_of::SizeOf)]
@@ -258,6 +362,8 @@ pub mod history {
         if let Some(content) = editor_buffer.history.previous_content() {
             editor_buffer.editor_content = content;
             editor_buffer.editor_content.caret_display_position = retain_caret_position;
+            // It might have been laid out w/ another tab width.
+            editor_buffer.maybe_laid_out_tab_width = None;
         }
 
         call_if_true!(DEBUG_TUI_COPY_PASTE, {
@@ -271,6 +377,8 @@ pub mod history {
 
         if let Some(content) = editor_buffer.history.next_content() {
             editor_buffer.editor_content = content;
+            // It might have been laid out w/ another tab width.
+            editor_buffer.maybe_laid_out_tab_width = None;
         }
 
         call_if_true!(DEBUG_TUI_COPY_PASTE, {
@@ -566,7 +674,10 @@ mod constructor {
 pub mod cache {
     use super::*;
 
-    pub fn clear(editor_buffer: &mut EditorBuffer) { editor_buffer.render_cache.clear(); }
+    pub fn clear(editor_buffer: &mut EditorBuffer) {
+        editor_buffer.render_cache.clear();
+        editor_buffer.bump_content_version();
+    }
 
     /// Cache key is combination of scroll_offset and window_size.
     fn generate_key(editor_buffer: &EditorBuffer, window_size: Size) -> String {
@@ -580,6 +691,7 @@ pub mod cache {
     /// - Scroll Offset changes
     /// - Window size changes
     /// - Content of the editor changes
+    /// - Theme changes (see [EditorEngine::set_syntax_theme])
     pub fn render_content(
         editor_buffer: &mut EditorBuffer,
         editor_engine: &mut EditorEngine,
@@ -587,6 +699,15 @@ pub mod cache {
         has_focus: &mut HasFocus,
         render_ops: &mut RenderOps,
     ) {
+        // The theme changed, so nothing that was rendered (or highlighted) w/ the old
+        // one can be re-used.
+        if editor_buffer
+            .syntect_highlight_cache
+            .sync_theme_version(editor_engine.theme_version)
+        {
+            editor_buffer.render_cache.clear();
+        }
+
         let key = generate_key(editor_buffer, window_size);
         if let Some(cached_output) = editor_buffer.render_cache.get(&key) {
             // Cache hit
@@ -598,6 +719,26 @@ pub mod cache {
         // - Content has been modified.
         // - Scroll Offset or Window size has been modified.
         editor_buffer.render_cache.clear();
+
+        // Only the syntect render path uses the highlight provider.
+        if matches!(
+            editor_engine.config_options.syntax_highlight,
+            SyntaxHighlightMode::Enable
+        ) && !editor_buffer.is_file_extension_default()
+        {
+            let last_visible_row_index = ch!(@to_usize
+                editor_buffer.get_scroll_offset().row_index
+                + editor_engine.current_box.style_adjusted_bounds_size.row_count
+            );
+            timed_if_enabled(editor_engine, TelemetryKind::Highlight, || {
+                editor_engine.get_highlight_provider().sync(
+                    editor_buffer,
+                    editor_engine,
+                    last_visible_row_index,
+                )
+            });
+        }
+
         let render_args = RenderArgs {
             editor_engine,
             editor_buffer,
@@ -639,6 +780,82 @@ pub mod access_and_mutate {
             }
         }
 
+        pub fn get_maybe_file_path(&self) -> Option<&str> {
+            self.editor_content.maybe_file_path.as_deref()
+        }
+
+        pub fn get_file_format(&self) -> FileFormat { self.file_format }
+
+        pub(crate) fn set_file_format(&mut self, file_format: FileFormat) {
+            self.file_format = file_format;
+        }
+
+        /// Returns `true` if the content has been changed since it was loaded or saved.
+        pub fn is_dirty(&self) -> bool { self.is_dirty }
+
+        pub(crate) fn set_dirty(&mut self, is_dirty: bool) { self.is_dirty = is_dirty; }
+
+        /// The [FileStamp] of the file when it was last loaded or saved by this buffer.
+        pub fn get_maybe_file_stamp(&self) -> Option<FileStamp> {
+            self.maybe_file_stamp.lock().ok().and_then(|it| *it)
+        }
+
+        /// Pass this to [crate::EditorFileWatcher::start], so that the watcher knows
+        /// about the changes that this buffer makes to its file.
+        pub fn get_shared_file_stamp(&self) -> SharedFileStamp {
+            self.maybe_file_stamp.clone()
+        }
+
+        pub(crate) fn set_maybe_file_stamp(
+            &mut self,
+            maybe_file_stamp: Option<FileStamp>,
+        ) {
+            if let Ok(mut it) = self.maybe_file_stamp.lock() {
+                *it = maybe_file_stamp;
+            }
+        }
+
+        /// Ask the editor to apply [crate::EditorEvent::GoToLine] the next time that this
+        /// buffer is rendered. This is meant to be used by apps that don't have access to
+        /// the [crate::EditorEngine] (eg: in the handler of a "go to line" dialog).
+        pub fn request_go_to_line(&mut self, line_number: usize) {
+            self.maybe_pending_go_to_line = Some(line_number);
+        }
+
+        pub(crate) fn take_pending_go_to_line(&mut self) -> Option<usize> {
+            self.maybe_pending_go_to_line.take()
+        }
+
+        pub fn get_search(&self) -> &EditorSearch { &self.search }
+
+        pub(crate) fn get_search_mut(&mut self) -> &mut EditorSearch { &mut self.search }
+
+        pub fn get_diff(&self) -> &EditorDiff { &self.diff }
+
+        pub(crate) fn get_diff_mut(&mut self) -> &mut EditorDiff { &mut self.diff }
+
+        pub fn get_spell_check(&self) -> &EditorSpellCheck { &self.spell_check }
+
+        pub(crate) fn get_spell_check_mut(&mut self) -> &mut EditorSpellCheck {
+            &mut self.spell_check
+        }
+
+        pub fn get_bookmarks(&self) -> &EditorBookmarks { &self.bookmarks }
+
+        pub(crate) fn get_bookmarks_mut(&mut self) -> &mut EditorBookmarks {
+            &mut self.bookmarks
+        }
+
+        pub fn get_maybe_snippet_session(&self) -> Option<&SnippetSession> {
+            self.maybe_snippet_session.as_ref()
+        }
+
+        pub(crate) fn get_maybe_snippet_session_mut(
+            &mut self,
+        ) -> &mut Option<SnippetSession> {
+            &mut self.maybe_snippet_session
+        }
+
         pub fn is_empty(&self) -> bool { self.editor_content.lines.is_empty() }
 
         pub fn len(&self) -> ChUnit { ch!(self.editor_content.lines.len()) }
@@ -685,6 +902,39 @@ pub mod access_and_mutate {
 
             // Reset undo/redo history.
             history::clear(self);
+
+            // The tabs in the new lines haven't been laid out yet.
+            self.maybe_laid_out_tab_width = None;
+        }
+
+        /// Lay out the `\t` in each line w/ the given `tab_width`, so that each one is as
+        /// wide as it takes to get to the next tab stop (see
+        /// [UnicodeString::new_with_tab_width]). The content doesn't change, so the
+        /// content version isn't bumped.
+        ///
+        /// This is called for each event & render, so the lines are only laid out again
+        /// when the `tab_width` changes (or the lines were replaced w/out the engine). The
+        /// lines that are inserted or edited by the engine are already laid out w/ it.
+        pub(crate) fn sync_tab_width(&mut self, tab_width: usize) {
+            if self.maybe_laid_out_tab_width == Some(tab_width) {
+                return;
+            }
+            self.maybe_laid_out_tab_width = Some(tab_width);
+
+            let mut is_changed = false;
+            for line in self.editor_content.lines.iter_mut() {
+                if !line.string.contains('\t') {
+                    continue;
+                }
+                let new_line = UnicodeString::new_with_tab_width(&line.string, tab_width);
+                if *line != new_line {
+                    *line = new_line;
+                    is_changed = true;
+                }
+            }
+            if is_changed {
+                self.render_cache.clear();
+            }
         }
 
         /// Returns the current caret position in two variants:
@@ -723,6 +973,12 @@ pub mod access_and_mutate {
             self.editor_content.scroll_offset
         }
 
+        pub fn get_content_version(&self) -> u64 { self.content_version }
+
+        pub(crate) fn bump_content_version(&mut self) {
+            self.content_version = self.content_version.wrapping_add(1);
+        }
+
         /// Returns:
         /// 1. /* lines */ &mut `Vec<UnicodeString>`,
         /// 2. /* caret */ &mut Position,
@@ -740,6 +996,7 @@ pub mod access_and_mutate {
             /* scroll_offset */ &mut ScrollOffset,
             /* selection_map */ &mut SelectionMap,
         ) {
+            self.bump_content_version();
             (
                 &mut self.editor_content.lines,
                 &mut self.editor_content.caret_display_position,
@@ -757,6 +1014,44 @@ pub mod access_and_mutate {
         pub fn get_selection_map(&self) -> &SelectionMap {
             &self.editor_content.selection_map
         }
+
+        pub fn has_secondary_carets(&self) -> bool {
+            !self.editor_content.secondary_carets.is_empty()
+        }
+
+        pub fn get_secondary_carets(&self) -> &[SecondaryCaret] {
+            &self.editor_content.secondary_carets
+        }
+
+        pub fn clear_secondary_carets(&mut self) {
+            self.editor_content.secondary_carets.clear();
+        }
+    }
+}
+
+pub mod partial_eq_impl {
+    use super::*;
+
+    /// The `syntect_highlight_cache` & `content_version` are skipped, since the version
+    /// is bumped by each [EditorBuffer::get_mut] (even if nothing is changed), so two
+    /// buffers w/ the same content would otherwise not be equal. The
+    /// `maybe_laid_out_tab_width` is skipped, since it only caches how the lines were
+    /// laid out.
+    impl PartialEq for EditorBuffer {
+        fn eq(&self, other: &Self) -> bool {
+            self.editor_content == other.editor_content
+                && self.history == other.history
+                && self.render_cache == other.render_cache
+                && self.file_format == other.file_format
+                && self.is_dirty == other.is_dirty
+                && self.get_maybe_file_stamp() == other.get_maybe_file_stamp()
+                && self.maybe_pending_go_to_line == other.maybe_pending_go_to_line
+                && self.search == other.search
+                && self.diff == other.diff
+                && self.spell_check == other.spell_check
+                && self.bookmarks == other.bookmarks
+                && self.maybe_snippet_session == other.maybe_snippet_session
+        }
     }
 }
 
@@ -784,6 +1079,8 @@ pub mod debug_format_helpers {
                 "\n\tEditorContent [                                 \n \
                 \t├ lines: {0}, size: {1} b                          \n \
                 \t├ selection_map: {4}                               \n \
+                \t├ secondary_carets: {7:?}                          \n \
+                \t├ block_selection_anchor: {8:?}                    \n \
                 \t└ ext: {2:?}, path:{6:?}, caret: {3:?}, scroll_offset: {5:?}   \n \
                 \t]",
                 /* 0 */ self.lines.len(),
@@ -793,6 +1090,8 @@ pub mod debug_format_helpers {
                 /* 4 */ self.selection_map.to_formatted_string(),
                 /* 5 */ self.scroll_offset,
                 /* 6 */ self.maybe_file_path,
+                /* 7 */ self.secondary_carets,
+                /* 8 */ self.maybe_block_selection_anchor,
             }
         }
     }
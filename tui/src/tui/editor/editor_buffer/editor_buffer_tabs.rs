@@ -0,0 +1,155 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::EditorBuffer;
+use crate::DEFAULT_SYN_HI_FILE_EXT;
+
+/// The max number of closed buffers that are kept around, so that they can be reopened.
+pub const MAX_CLOSED_EDITOR_BUFFER_COUNT: usize = 16;
+
+/// The buffers that are open in an editor component, one of which is active (displayed
+/// in the editor). These are displayed as tabs by the [crate::EditorTabBarComponent].
+///
+/// The editor component gets its buffer from the app's state via
+/// [crate::HasEditorBuffers], so to display the active buffer, implement
+/// [crate::HasEditorBuffers::get_mut_editor_buffer] w/ [EditorBufferTabs::get_active_mut].
+///
+/// There's always at least one buffer. Closing the last one replaces it w/ an empty
+/// buffer.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EditorBufferTabs {
+    buffers: Vec<EditorBuffer>,
+    active_index: usize,
+    /// The buffers that were closed, the most recently closed one is last.
+    closed_buffers: Vec<EditorBuffer>,
+}
+
+impl Default for EditorBufferTabs {
+    fn default() -> Self { Self::new(make_empty_buffer()) }
+}
+
+fn make_empty_buffer() -> EditorBuffer {
+    EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_string()), &None)
+}
+
+impl EditorBufferTabs {
+    pub fn new(buffer: EditorBuffer) -> Self {
+        Self {
+            buffers: vec![buffer],
+            active_index: 0,
+            closed_buffers: vec![],
+        }
+    }
+
+    pub fn get_buffers(&self) -> &[EditorBuffer] { &self.buffers }
+
+    pub fn len(&self) -> usize { self.buffers.len() }
+
+    /// This is always `false`, since there's always at least one buffer.
+    pub fn is_empty(&self) -> bool { self.buffers.is_empty() }
+
+    pub fn get_active_index(&self) -> usize { self.active_index }
+
+    pub fn get_active(&self) -> &EditorBuffer { &self.buffers[self.active_index] }
+
+    pub fn get_active_mut(&mut self) -> &mut EditorBuffer {
+        &mut self.buffers[self.active_index]
+    }
+
+    pub fn get_closed_count(&self) -> usize { self.closed_buffers.len() }
+
+    /// Add the `buffer` after the active one, and make it active. If a buffer w/ the
+    /// same file path is already open, then that one is made active instead.
+    pub fn open(&mut self, buffer: EditorBuffer) {
+        if let Some(file_path) = buffer.get_maybe_file_path() {
+            let maybe_index = self
+                .buffers
+                .iter()
+                .position(|it| it.get_maybe_file_path() == Some(file_path));
+            if let Some(index) = maybe_index {
+                self.active_index = index;
+                return;
+            }
+        }
+
+        self.active_index += 1;
+        self.buffers.insert(self.active_index, buffer);
+    }
+
+    /// Returns `false` if there's no buffer at the `index`.
+    pub fn activate(&mut self, index: usize) -> bool {
+        if index >= self.buffers.len() {
+            return false;
+        }
+        self.active_index = index;
+        true
+    }
+
+    /// Make the buffer after the active one active, wrapping around to the first one.
+    pub fn activate_next(&mut self) {
+        self.active_index = (self.active_index + 1) % self.buffers.len();
+    }
+
+    /// Make the buffer before the active one active, wrapping around to the last one.
+    pub fn activate_previous(&mut self) {
+        self.active_index = match self.active_index {
+            0 => self.buffers.len() - 1,
+            it => it - 1,
+        };
+    }
+
+    /// Close the active buffer, and make the one after it active (or the one before it
+    /// if it was the last one). The closed buffer can be brought back w/
+    /// [Self::reopen_closed]. Note that the buffer isn't saved, so apps should ask the
+    /// user what to do about unsaved changes (see [EditorBuffer::is_dirty]) first.
+    pub fn close_active(&mut self) {
+        let buffer = self.buffers.remove(self.active_index);
+
+        if self.closed_buffers.len() == MAX_CLOSED_EDITOR_BUFFER_COUNT {
+            self.closed_buffers.remove(0);
+        }
+        self.closed_buffers.push(buffer);
+
+        if self.buffers.is_empty() {
+            self.buffers.push(make_empty_buffer());
+        }
+        self.active_index = self.active_index.min(self.buffers.len() - 1);
+    }
+
+    /// Reopen the most recently closed buffer. Returns `false` if there isn't one.
+    pub fn reopen_closed(&mut self) -> bool {
+        let Some(buffer) = self.closed_buffers.pop() else {
+            return false;
+        };
+        self.open(buffer);
+        true
+    }
+}
+
+/// The title of the tab for the `buffer`, which is the name of its file, or `untitled`
+/// if it doesn't have one.
+pub fn get_editor_buffer_title(buffer: &EditorBuffer) -> String {
+    buffer
+        .get_maybe_file_path()
+        .and_then(|it| Path::new(it).file_name())
+        .map(|it| it.to_string_lossy().to_string())
+        .unwrap_or_else(|| "untitled".to_string())
+}
@@ -16,15 +16,39 @@
  */
 
 // Attach.
+pub mod editor_buffer_bookmark_support;
 pub mod editor_buffer_clipboard_support;
+pub mod editor_buffer_diff_support;
+pub mod editor_buffer_file_support;
+pub mod editor_buffer_multi_caret_support;
+pub mod editor_buffer_search_support;
 pub mod editor_buffer_selection_support;
+pub mod editor_buffer_snippet_support;
+#[cfg(feature = "spellcheck")]
+pub mod editor_buffer_spell_check_dictionary;
+pub mod editor_buffer_spell_check_support;
 pub mod editor_buffer_struct;
+pub mod editor_buffer_tabs;
+pub mod osc52_clipboard_service_provider;
 pub mod selection_map;
+pub mod syntect_highlight_cache;
 pub mod system_clipboard_service_provider;
 
 // Re-export.
+pub use editor_buffer_bookmark_support::*;
 pub use editor_buffer_clipboard_support::*;
+pub use editor_buffer_diff_support::*;
+pub use editor_buffer_file_support::*;
+pub use editor_buffer_multi_caret_support::*;
+pub use editor_buffer_search_support::*;
 pub use editor_buffer_selection_support::*;
+pub use editor_buffer_snippet_support::*;
+#[cfg(feature = "spellcheck")]
+pub use editor_buffer_spell_check_dictionary::*;
+pub use editor_buffer_spell_check_support::*;
 pub use editor_buffer_struct::*;
+pub use editor_buffer_tabs::*;
+pub use osc52_clipboard_service_provider::*;
 pub use selection_map::*;
+pub use syntect_highlight_cache::*;
 pub use system_clipboard_service_provider::*;
@@ -0,0 +1,173 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crossterm::style::Stylize;
+use r3bl_core::{call_if_true, throws};
+
+use super::{ClipboardResult, ClipboardService};
+use crate::DEBUG_TUI_COPY_PASTE;
+
+/// The OSC 52 escape sequence that sets the clipboard (`c`) to the base64 encoded text
+/// that comes after it, see
+/// <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h3-Operating-System-Commands>.
+const OSC52_START: &str = "\x1b]52;c;";
+const OSC52_END: &str = "\x07";
+
+/// tmux swallows the escape sequences that it doesn't know about, unless they are wrapped
+/// in a DCS passthrough (w/ each `ESC` in them doubled), see
+/// <https://github.com/tmux/tmux/wiki/FAQ#what-is-the-passthrough-escape-sequence-and-how-do-i-use-it>.
+const TMUX_PASSTHROUGH_START: &str = "\x1bPtmux;";
+const TMUX_PASSTHROUGH_END: &str = "\x1b\\";
+
+static IS_INSIDE_TMUX: OnceLock<bool> = OnceLock::new();
+
+/// The text that was last copied w/ [Osc52Clipboard]. Most terminals don't allow the
+/// clipboard to be read w/ OSC 52, so this is used to paste it back in the app.
+static LAST_COPIED_CONTENT: StdMutex<Option<String>> = StdMutex::new(None);
+
+/// The text that was copied w/ [Osc52Clipboard] & hasn't been painted yet. Each one is
+/// painted w/ the next frame as a [crate::RenderOp::SetClipboard], see
+/// [crate::GlobalData::pending_clipboard_contents].
+pub type SharedPendingClipboardContents = Arc<StdMutex<Vec<String>>>;
+
+/// Whether the app is running in tmux. This is detected (once) from the `TMUX`
+/// environment variable.
+pub fn is_inside_tmux() -> bool {
+    *IS_INSIDE_TMUX.get_or_init(|| detect_tmux(|key| std::env::var(key).ok()))
+}
+
+pub fn detect_tmux(get_env: impl Fn(&str) -> Option<String>) -> bool {
+    get_env("TMUX").is_some_and(|it| !it.is_empty())
+}
+
+/// Returns the escape sequence that tells the terminal to put `content` into the system
+/// clipboard. It is wrapped in a tmux passthrough when `is_inside_tmux` is true.
+pub fn get_osc52_escape_sequence(content: &str, is_inside_tmux: bool) -> String {
+    let osc52 = format!("{OSC52_START}{}{OSC52_END}", STANDARD.encode(content));
+    if is_inside_tmux {
+        format!(
+            "{TMUX_PASSTHROUGH_START}{}{TMUX_PASSTHROUGH_END}",
+            osc52.replace('\x1b', "\x1b\x1b")
+        )
+    } else {
+        osc52
+    }
+}
+
+/// Copies text w/ the OSC 52 escape sequence, so that the terminal (and not the machine
+/// that the app is running on) puts it into the clipboard. This works over SSH & in tmux,
+/// where there is no local clipboard provider. [super::SystemClipboard] falls back to
+/// this when the native clipboard is unavailable.
+///
+/// The output device isn't written to while handling an input event, since a frame might
+/// be painted to it at the same time (see [crate::PaintTask]). Instead, the text is
+/// queued & its escape sequence is painted w/ the next frame. The terminal doesn't
+/// respond to it, so there is no way to know if the terminal supports it (or if it has
+/// been turned off).
+pub struct Osc52Clipboard {
+    pub pending_clipboard_contents: SharedPendingClipboardContents,
+}
+
+impl Osc52Clipboard {
+    pub fn new(pending_clipboard_contents: SharedPendingClipboardContents) -> Self {
+        Self {
+            pending_clipboard_contents,
+        }
+    }
+}
+
+impl ClipboardService for Osc52Clipboard {
+    fn try_to_put_content_into_clipboard(
+        &mut self,
+        content: String,
+    ) -> ClipboardResult<()> {
+        throws!({
+            self.pending_clipboard_contents
+                .lock()
+                .map_err(|_| "The pending clipboard contents are poisoned")?
+                .push(content.clone());
+
+            call_if_true!(DEBUG_TUI_COPY_PASTE, {
+                tracing::debug!(
+                    "\n📋📋📋 Selected Text was copied to clipboard w/ OSC 52: \n{}",
+                    content.to_string().black().on_green(),
+                );
+            });
+
+            if let Ok(mut it) = LAST_COPIED_CONTENT.lock() {
+                *it = Some(content);
+            }
+        })
+    }
+
+    fn try_to_get_content_from_clipboard(&mut self) -> ClipboardResult<String> {
+        let maybe_content = LAST_COPIED_CONTENT.lock().ok().and_then(|it| it.clone());
+        match maybe_content {
+            Some(content) => Ok(content),
+            None => Err("Nothing has been copied w/ OSC 52".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_get_osc52_escape_sequence() {
+        assert_eq2!(
+            get_osc52_escape_sequence("hello", false),
+            "\x1b]52;c;aGVsbG8=\x07"
+        );
+        assert_eq2!(
+            get_osc52_escape_sequence("hello", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGVsbG8=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_content_is_queued_to_be_painted() {
+        let pending_clipboard_contents = SharedPendingClipboardContents::default();
+        let mut clipboard = Osc52Clipboard::new(pending_clipboard_contents.clone());
+
+        clipboard
+            .try_to_put_content_into_clipboard("hello".to_string())
+            .unwrap();
+
+        assert_eq2!(
+            *pending_clipboard_contents.lock().unwrap(),
+            vec!["hello".to_string()]
+        );
+        assert_eq2!(
+            clipboard.try_to_get_content_from_clipboard().unwrap(),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn test_detect_tmux() {
+        assert!(detect_tmux(|key| {
+            (key == "TMUX").then(|| "/tmp/tmux-1000/default,1234,0".to_string())
+        }));
+        assert!(!detect_tmux(|key| (key == "TMUX").then(String::new)));
+        assert!(!detect_tmux(|_| None));
+    }
+}
@@ -155,6 +155,10 @@ impl SelectionMap {
         self.map.get(&row_index)
     }
 
+    pub fn get_mut(&mut self, row_index: RowIndex) -> Option<&mut SelectionRange> {
+        self.map.get_mut(&row_index)
+    }
+
     /// Compares the given direction (`current_direction`) with the
     /// [maybe_previous_direction](Self::maybe_previous_direction).
     /// - If there is no existing previous direction, it returns
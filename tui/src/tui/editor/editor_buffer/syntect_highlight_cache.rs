@@ -0,0 +1,266 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::UnicodeString;
+use syntect::{highlighting::{HighlightIterator, HighlightState, Highlighter, Theme},
+              parsing::{ParseState, ScopeStack, SyntaxSet}};
+
+use crate::{try_get_syntax_ref, SyntectStyleStrSpanLine};
+
+type SyntectStyle = syntect::highlighting::Style;
+
+/// Incremental syntax highlighting cache for the syntect render path of the editor
+/// component.
+///
+/// Syntect is a stateful highlighter: the parse & highlight state at the end of one line
+/// is the input for the next line. So instead of highlighting each visible line from
+/// scratch on every render, this cache holds the highlighted spans for each line along w/
+/// the state at the end of that line.
+///
+/// 1. The cache is keyed by [crate::EditorBuffer::get_content_version]. When the version
+///    hasn't changed since the last [SyntectHighlightCache::sync] then there is nothing to
+///    do.
+/// 2. Otherwise, the first line whose text is different from what was cached is found.
+///    All the lines before it are kept, and all the lines from it onwards are dropped,
+///    since their state might have changed.
+/// 3. Then only the lines up to the last visible line are (re)highlighted. Lines below
+///    the viewport are highlighted lazily when they are scrolled into view.
+#[derive(Clone, PartialEq, Default)]
+pub struct SyntectHighlightCache {
+    maybe_synced_version: Option<u64>,
+    maybe_file_extension: Option<String>,
+    /// The [crate::EditorEngine::theme_version] that the lines were highlighted w/.
+    maybe_theme_version: Option<u64>,
+    lines: Vec<HighlightedLine>,
+}
+
+#[derive(Clone, PartialEq)]
+struct HighlightedLine {
+    /// The text of the line when it was highlighted.
+    text: String,
+    spans: Vec<(SyntectStyle, String)>,
+    /// Parser state at the end of this line (where the next line starts).
+    parse_state: ParseState,
+    /// Highlighter state at the end of this line (where the next line starts).
+    highlight_state: HighlightState,
+}
+
+impl SyntectHighlightCache {
+    /// The theme version is kept, since the theme hasn't changed.
+    pub fn clear(&mut self) {
+        *self = Self {
+            maybe_theme_version: self.maybe_theme_version,
+            ..Default::default()
+        };
+    }
+
+    /// Drop everything that is cached if the `theme_version` (see
+    /// [crate::EditorEngine::theme_version]) changed since the last call. Returns `true`
+    /// if it did.
+    pub fn sync_theme_version(&mut self, theme_version: u64) -> bool {
+        if self.maybe_theme_version == Some(theme_version) {
+            return false;
+        }
+        self.clear();
+        self.maybe_theme_version = Some(theme_version);
+        true
+    }
+
+    /// Number of lines that are currently highlighted & cached.
+    pub fn len(&self) -> usize { self.lines.len() }
+
+    pub fn is_empty(&self) -> bool { self.lines.is_empty() }
+
+    /// Bring the cache up to date w/ the `lines` of the buffer, and make sure that all
+    /// the lines from the top of the buffer to `last_row_index` (inclusive) are
+    /// highlighted.
+    pub fn sync(
+        &mut self,
+        content_version: u64,
+        lines: &[UnicodeString],
+        maybe_file_extension: Option<&str>,
+        syntax_set: &SyntaxSet,
+        theme: &Theme,
+        last_row_index: usize,
+    ) {
+        // The syntax changed, so nothing that is cached can be re-used.
+        if self.maybe_file_extension.as_deref() != maybe_file_extension {
+            self.clear();
+            self.maybe_file_extension = maybe_file_extension.map(String::from);
+        }
+
+        // Drop everything from the first edited line onwards.
+        if self.maybe_synced_version != Some(content_version) {
+            let first_edited_row_index = self
+                .lines
+                .iter()
+                .zip(lines.iter())
+                .position(|(cached, line)| cached.text != line.string)
+                .unwrap_or(self.lines.len().min(lines.len()));
+            self.lines.truncate(first_edited_row_index);
+            self.maybe_synced_version = Some(content_version);
+        }
+
+        let Some(file_extension) = maybe_file_extension else {
+            return;
+        };
+        let Some(syntax_ref) = try_get_syntax_ref(syntax_set, file_extension) else {
+            return;
+        };
+        let highlighter = Highlighter::new(theme);
+
+        let end_row_index = last_row_index.saturating_add(1).min(lines.len());
+        for row_index in self.lines.len()..end_row_index {
+            let (mut parse_state, mut highlight_state) = match self.lines.last() {
+                Some(prev) => (prev.parse_state.clone(), prev.highlight_state.clone()),
+                None => (
+                    ParseState::new(syntax_ref),
+                    HighlightState::new(&highlighter, ScopeStack::new()),
+                ),
+            };
+
+            let text = &lines[row_index].string;
+
+            // The syntax set is loaded w/ newlines, so each line must end in one.
+            let text_with_newline = format!("{text}\n");
+            let Ok(ops) = parse_state.parse_line(&text_with_newline, syntax_set) else {
+                // Stop here, the render path falls back to no syntax highlighting.
+                return;
+            };
+            let spans = HighlightIterator::new(
+                &mut highlight_state,
+                &ops[..],
+                &text_with_newline,
+                &highlighter,
+            )
+            .filter_map(|(style, span_text)| {
+                let span_text = span_text.trim_end_matches('\n');
+                (!span_text.is_empty()).then(|| (style, span_text.to_string()))
+            })
+            .collect();
+
+            self.lines.push(HighlightedLine {
+                text: text.clone(),
+                spans,
+                parse_state,
+                highlight_state,
+            });
+        }
+    }
+
+    /// Returns the highlighted spans for the given line, if it is in the cache & the
+    /// cached text matches `line`.
+    pub fn get(
+        &self,
+        row_index: usize,
+        line: &str,
+    ) -> Option<SyntectStyleStrSpanLine<'_>> {
+        let it = self.lines.get(row_index)?;
+        if it.text != line {
+            return None;
+        }
+        Some(
+            it.spans
+                .iter()
+                .map(|(style, text)| (*style, text.as_str()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::try_load_r3bl_theme;
+
+    fn make_lines(lines: &[&str]) -> Vec<UnicodeString> {
+        lines.iter().map(|it| UnicodeString::from(*it)).collect()
+    }
+
+    #[test]
+    fn test_only_visible_lines_are_highlighted() {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let theme = try_load_r3bl_theme().unwrap();
+        let lines = make_lines(&["fn main() {", "    let a = 1;", "}", "", "// end"]);
+
+        let mut cache = SyntectHighlightCache::default();
+        cache.sync(0, &lines, Some("rs"), &syntax_set, &theme, 1);
+        assert_eq2!(cache.len(), 2);
+        assert!(cache.get(0, "fn main() {").is_some());
+        assert!(cache.get(2, "}").is_none());
+
+        // Scrolling down highlights the rest, and the last row index is clamped.
+        cache.sync(0, &lines, Some("rs"), &syntax_set, &theme, 100);
+        assert_eq2!(cache.len(), 5);
+    }
+
+    #[test]
+    fn test_edit_invalidates_from_first_edited_line() {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let theme = try_load_r3bl_theme().unwrap();
+        let lines = make_lines(&["let a = 1;", "let b = 2;", "let c = 3;"]);
+
+        let mut cache = SyntectHighlightCache::default();
+        cache.sync(0, &lines, Some("rs"), &syntax_set, &theme, 2);
+        assert_eq2!(cache.len(), 3);
+
+        // Same version, so nothing is re-checked.
+        let edited = make_lines(&["let a = 1;", "let b = 22;", "let c = 3;"]);
+        cache.sync(0, &edited, Some("rs"), &syntax_set, &theme, 0);
+        assert_eq2!(cache.len(), 3);
+        assert!(cache.get(1, "let b = 22;").is_none());
+
+        // New version, the lines after the edit are dropped, and only the ones up to the
+        // last row index are re-highlighted.
+        cache.sync(1, &edited, Some("rs"), &syntax_set, &theme, 0);
+        assert_eq2!(cache.len(), 1);
+        cache.sync(1, &edited, Some("rs"), &syntax_set, &theme, 2);
+        assert_eq2!(cache.len(), 3);
+        assert!(cache.get(1, "let b = 22;").is_some());
+    }
+
+    #[test]
+    fn test_state_is_carried_across_lines() {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let theme = try_load_r3bl_theme().unwrap();
+        let lines = make_lines(&["/* start", "fn inside_comment() {}", "*/"]);
+
+        let mut cache = SyntectHighlightCache::default();
+        cache.sync(0, &lines, Some("rs"), &syntax_set, &theme, 2);
+
+        // The whole middle line is inside the block comment, so it has a single style.
+        let spans = cache.get(1, "fn inside_comment() {}").unwrap();
+        let first_style = spans[0].0;
+        assert!(spans.iter().all(|(style, _)| *style == first_style));
+    }
+
+    #[test]
+    fn test_file_extension_change_clears_cache() {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let theme = try_load_r3bl_theme().unwrap();
+        let lines = make_lines(&["a", "b"]);
+
+        let mut cache = SyntectHighlightCache::default();
+        cache.sync(0, &lines, Some("rs"), &syntax_set, &theme, 1);
+        assert_eq2!(cache.len(), 2);
+
+        cache.sync(0, &lines, Some("no_such_ext"), &syntax_set, &theme, 1);
+        assert!(cache.is_empty());
+    }
+}
@@ -19,10 +19,39 @@ use copypasta_ext::{copypasta::ClipboardProvider, x11_fork::ClipboardContext};
 use crossterm::style::Stylize;
 use r3bl_core::{call_if_true, throws};
 
-use super::{ClipboardResult, ClipboardService};
+use super::{ClipboardResult,
+            ClipboardService,
+            Osc52Clipboard,
+            SharedPendingClipboardContents};
 use crate::DEBUG_TUI_COPY_PASTE;
 
-pub struct SystemClipboard;
+/// Uses the native clipboard of the machine that the app is running on. When it is
+/// unavailable (eg: over SSH, or w/out an X11 or Wayland display) this falls back to
+/// [Osc52Clipboard], so that the terminal puts the text into its clipboard instead.
+pub struct SystemClipboard {
+    /// The text that is copied w/ [Osc52Clipboard] is queued in this.
+    pub pending_clipboard_contents: SharedPendingClipboardContents,
+}
+
+impl SystemClipboard {
+    pub fn new(pending_clipboard_contents: SharedPendingClipboardContents) -> Self {
+        Self {
+            pending_clipboard_contents,
+        }
+    }
+
+    fn try_to_put_content_into_native_clipboard(content: String) -> ClipboardResult<()> {
+        let mut ctx = ClipboardContext::new()?;
+        ctx.set_contents(content)?;
+        Ok(())
+    }
+
+    fn try_to_get_content_from_native_clipboard() -> ClipboardResult<String> {
+        let mut ctx = ClipboardContext::new()?;
+        let content = ctx.get_contents()?;
+        Ok(content)
+    }
+}
 
 impl ClipboardService for SystemClipboard {
     fn try_to_put_content_into_clipboard(
@@ -30,8 +59,18 @@ impl ClipboardService for SystemClipboard {
         content: String,
     ) -> ClipboardResult<()> {
         throws!({
-            let mut ctx = ClipboardContext::new()?;
-            ctx.set_contents(content.clone())?;
+            if let Err(error) =
+                Self::try_to_put_content_into_native_clipboard(content.clone())
+            {
+                call_if_true!(DEBUG_TUI_COPY_PASTE, {
+                    tracing::debug!(
+                        "\n📋📋📋 Native clipboard is unavailable, using OSC 52: {}",
+                        error
+                    );
+                });
+                return Osc52Clipboard::new(self.pending_clipboard_contents.clone())
+                    .try_to_put_content_into_clipboard(content);
+            }
 
             call_if_true!(DEBUG_TUI_COPY_PASTE, {
                 tracing::debug!(
@@ -43,10 +82,10 @@ impl ClipboardService for SystemClipboard {
     }
 
     fn try_to_get_content_from_clipboard(&mut self) -> ClipboardResult<String> {
-        let mut ctx = ClipboardContext::new()?;
-        let content = ctx.get_contents()?;
-
-        Ok(content)
+        Self::try_to_get_content_from_native_clipboard().or_else(|_| {
+            Osc52Clipboard::new(self.pending_clipboard_contents.clone())
+                .try_to_get_content_from_clipboard()
+        })
     }
 }
 
@@ -0,0 +1,117 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_core::{throws, CommonResult};
+use tokio::sync::mpsc::{channel, Sender};
+
+use crate::{FlexBoxId, TerminalWindowMainThreadSignal, DEFAULT_CARET_BLINK_INTERVAL};
+
+/// Renders the app every [DEFAULT_CARET_BLINK_INTERVAL], so that the caret of an editor
+/// component w/ [crate::CaretBlinkMode::Enable] set actually blinks. Whether the caret is
+/// painted is worked out when the editor is rendered (see
+/// [crate::EditorEngine::is_caret_visible]), so all this has to do is ask for renders.
+///
+/// Apps typically start this when an editor component gets focus, and stop it when the
+/// editor loses focus. Just like [crate::Animator], the blinker can be stopped & started
+/// again.
+#[derive(Debug, Default)]
+pub struct EditorCaretBlinker {
+    /// This is the channel that is used to kill the blinker task. [None] means that the
+    /// blinker task is not running.
+    pub blinker_kill_channel: Option<Sender<()>>,
+}
+
+impl EditorCaretBlinker {
+    /// Starts the blinker task for the editor component w/ the given `id`, if one isn't
+    /// already running.
+    pub fn start<AS>(
+        &mut self,
+        id: FlexBoxId,
+        channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
+    ) where
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        if self.is_started() {
+            return;
+        }
+
+        let (kill_sender, mut kill_receiver) = channel::<()>(1);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(DEFAULT_CARET_BLINK_INTERVAL);
+
+            loop {
+                tokio::select! {
+                    _ = kill_receiver.recv() => break,
+                    _ = interval.tick() => {
+                        let signal = TerminalWindowMainThreadSignal::Render(Some(id));
+                        // The app has exited.
+                        if channel_sender.send(signal).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        self.blinker_kill_channel = Some(kill_sender);
+    }
+
+    pub fn is_started(&self) -> bool { self.blinker_kill_channel.is_some() }
+
+    pub fn stop(&mut self) -> CommonResult<()> {
+        throws!({
+            if let Some(kill_channel) = self.blinker_kill_channel.take() {
+                tokio::spawn(async move {
+                    let _ = kill_channel.send(()).await;
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::timeout;
+
+    use super::*;
+
+    #[derive(Debug, Default, Clone)]
+    struct TestAction;
+
+    #[tokio::test]
+    async fn test_blink_requests_render() {
+        let (sender, mut receiver) =
+            channel::<TerminalWindowMainThreadSignal<TestAction>>(8);
+        let mut blinker = EditorCaretBlinker::default();
+        blinker.start(FlexBoxId::from(1), sender);
+        assert!(blinker.is_started());
+
+        for _ in 0..2 {
+            let signal = timeout(DEFAULT_CARET_BLINK_INTERVAL * 4, receiver.recv()).await;
+            assert!(matches!(
+                signal,
+                Ok(Some(TerminalWindowMainThreadSignal::Render(Some(id)))) if id == FlexBoxId::from(1)
+            ));
+        }
+
+        blinker.stop().unwrap();
+        assert!(!blinker.is_started());
+    }
+}
@@ -100,6 +100,8 @@ pub mod editor_component_impl_component_trait {
 
         fn get_id(&self) -> FlexBoxId { self.data.id }
 
+        fn is_focusable(&self) -> bool { true }
+
         /// This shim simply calls
         /// [EditorEngineApi::render_engine](EditorEngineApi::render_engine) w/ all the
         /// necessary arguments:
@@ -151,7 +153,11 @@ pub mod editor_component_impl_component_trait {
             _: &mut HasFocus,
         ) -> CommonResult<EventPropagation> {
             throws_with_return!({
-                let GlobalData { state, .. } = global_data;
+                let GlobalData {
+                    state,
+                    pending_clipboard_contents,
+                    ..
+                } = global_data;
 
                 let EditorComponentData {
                     editor_engine,
@@ -174,7 +180,7 @@ pub mod editor_component_impl_component_trait {
                     mut_editor_buffer,
                     editor_engine,
                     input_event,
-                    &mut SystemClipboard,
+                    &mut SystemClipboard::new(pending_clipboard_contents.clone()),
                 )?;
 
                 match result {
@@ -15,24 +15,33 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug, iter::once};
 
 use crossterm::style::Stylize;
-use r3bl_core::{call_if_true, Size};
+use r3bl_core::{call_if_true, ch, Position, Size};
 use serde::{Deserialize, Serialize};
 
-use crate::{editor_buffer::EditorBuffer,
+use crate::{completion_popup,
+            editor_buffer::EditorBuffer,
             editor_buffer_clipboard_support::ClipboardService,
+            editor_buffer_multi_caret_support,
+            editor_buffer_snippet_support,
             history,
+            AutoClosePairsMode,
+            Button,
+            CaretKind,
             DeleteSelectionWith,
             EditorArgsMut,
             EditorEngine,
             EditorEngineInternalApi,
+            FunctionKey,
             InputEvent,
             Key,
             KeyPress,
             KeyState,
             ModifierKeysMask,
+            MouseInput,
+            MouseInputKind,
             SelectMode,
             SpecialKey,
             DEBUG_TUI_COPY_PASTE};
@@ -41,13 +50,49 @@ use crate::{editor_buffer::EditorBuffer,
 ///
 /// By providing a conversion from [InputEvent] to [EditorEvent] it becomes easier to write event
 /// handlers that consume [InputEvent] and then execute [EditorEvent] on an [EditorBuffer].
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EditorEvent {
     InsertChar(char),
     InsertString(String),
+    /// Insert text that can span many lines (eg: text that the app got from a bracketed
+    /// paste) in one go. Unlike [EditorEvent::InsertString] followed by
+    /// [EditorEvent::InsertNewLine] for each line, this isn't auto indented, it is a
+    /// single undo step, and the buffer is only re-parsed once.
+    InsertText(String),
+    /// Insert a snippet w/ tab stops (eg: `for ${1:item} in ${2:items} {$0}`), see
+    /// [crate::Snippet] for the syntax.
+    InsertSnippet(String),
     InsertNewLine,
+    InsertTab,
+    Outdent,
     Delete,
     Backspace,
+    /// Delete from the caret to the end of the word to its right.
+    DeleteWord,
+    /// Delete from the caret to the start of the word to its left.
+    BackspaceWord,
+    /// Swap the line at the caret w/ the line above it.
+    MoveLineUp,
+    /// Swap the line at the caret w/ the line below it.
+    MoveLineDown,
+    /// Insert a copy of the line at the caret below it.
+    DuplicateLine,
+    /// Join the line at the caret w/ the line below it.
+    JoinLines,
+    /// Remove the line at the caret.
+    DeleteLine,
+    /// Remove the trailing whitespace from every line. This doesn't have a default key
+    /// binding, and it is applied when the buffer is saved if
+    /// [crate::TrimTrailingWhitespaceMode::OnSave] is set.
+    TrimTrailingWhitespace,
+    /// Comment out the selected lines (or the line at the caret), or uncomment them if
+    /// they are all commented out, using the comment syntax of the buffer's language
+    /// (see [crate::LanguageMetadata]). <kbd>Ctrl+/</kbd>.
+    ToggleLineComment,
+    /// Move the caret to the start of the word to its left.
+    WordLeft,
+    /// Move the caret to the end of the word to its right.
+    WordRight,
     Home,
     End,
     PageDown,
@@ -60,12 +105,85 @@ pub enum EditorEvent {
     Cut,
     Undo,
     Redo,
+    Mouse(MouseAction),
+    /// Select the next occurrence of the selected text (or the word at the caret) &
+    /// add a caret there.
+    AddCaretAtNextOccurrence,
+    /// Save the buffer to its file path. Apps that want to handle saving themselves
+    /// (eg: to ask for a file name) can intercept <kbd>Ctrl + S</kbd> before the event
+    /// is routed to the editor component.
+    Save,
+    /// Move the caret to the start of the given line number (starting at 1, as shown in
+    /// the line number gutter), and scroll it to the middle of the viewport. The editor
+    /// doesn't have a key binding for this, since it needs to ask the user for the line
+    /// number. Apps can show a dialog for this (eg: on <kbd>Ctrl + G</kbd>) and then call
+    /// [EditorBuffer::request_go_to_line].
+    GoToLine(usize),
+    /// Move the caret to the start of the buffer.
+    GoToTop,
+    /// Move the caret to the end of the buffer.
+    GoToBottom,
+    /// Move the caret to the start of the next block of lines that changed since the
+    /// buffer was last loaded or saved (see [EditorBuffer::reset_change_baseline]).
+    GoToNextChange,
+    /// Move the caret to the start of the previous block of changed lines.
+    GoToPreviousChange,
+    /// Display the suggestions for the misspelled word at the caret in the completion
+    /// popup, when the editor has a [crate::SpellChecker].
+    ShowSpellingSuggestions,
+    /// Start recording the events that are applied, see [crate::EditorMacro].
+    /// <kbd>Alt+R</kbd>.
+    StartMacroRecording,
+    /// Stop recording, and keep the recorded events as the macro to replay.
+    /// <kbd>Alt+Q</kbd>.
+    StopMacroRecording,
+    /// Apply the events of the last recorded macro the given number of times.
+    /// <kbd>Alt+P</kbd> replays it once.
+    ReplayMacro(usize),
+    /// Add a bookmark to the line w/ the caret, or remove the one that is on it (see
+    /// [crate::EditorBookmarks]). <kbd>Ctrl+F2</kbd> toggles a bookmark w/out a name.
+    ToggleBookmark(Option<char>),
+    /// Move the caret to the next bookmarked line, wrapping around at the end of the
+    /// buffer. <kbd>F2</kbd>.
+    GoToNextBookmark,
+    /// Move the caret to the previous bookmarked line. <kbd>Shift+F2</kbd>.
+    GoToPreviousBookmark,
+    /// Move the caret to the line w/ the bookmark of the given name. This doesn't have a
+    /// key binding, apps can add them w/ a [crate::KeyBindingTable].
+    GoToBookmark(char),
+    /// Switch between inserting the characters that are typed, and typing over the ones
+    /// at the caret (see [EditorEngine::is_overwrite_mode]). <kbd>Insert</kbd>.
+    ToggleOverwriteMode,
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// The positions in these actions are in screen coordinates (straight from the
+/// [InputEvent::Mouse]). They are converted into buffer positions when the event is
+/// applied, since that requires the [EditorEngine]'s box origin and the
+/// [EditorBuffer]'s scroll offset.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MouseAction {
+    /// Move the caret to the position & clear the selection.
+    PlaceCaret(Position),
+    /// Move the caret to the position & extend the selection to it.
+    SelectTo(Position),
+    /// Add a secondary caret at the position.
+    AddCaret(Position),
+    ScrollUp,
+    ScrollDown,
+}
+
+enum MultiCaretBehavior {
+    ApplyAtAllCarets,
+    KeepSecondaryCarets,
+    ClearSecondaryCarets,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SelectionAction {
     OneCharLeft,
     OneCharRight,
+    OneWordLeft,
+    OneWordRight,
     OneLineUp,
     OneLineDown,
     PageUp,
@@ -74,9 +192,12 @@ pub enum SelectionAction {
     End,
     All,
     Esc,
+    /// Extend the rectangular (block) selection in the given direction. Editing events
+    /// are applied to every row of the block.
+    Block(CaretDirection),
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CaretDirection {
     Up,
     Down,
@@ -212,6 +333,55 @@ impl TryFrom<InputEvent> for EditorEvent {
                 key: Key::SpecialKey(SpecialKey::Esc),
             }) => Ok(EditorEvent::Select(SelectionAction::Esc)),
 
+            // Block selection events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Up),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::Block(
+                CaretDirection::Up,
+            ))),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Down),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::Block(
+                CaretDirection::Down,
+            ))),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Left),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::Block(
+                CaretDirection::Left,
+            ))),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::Block(
+                CaretDirection::Right,
+            ))),
+
             //  Clipboard events.
             InputEvent::Keyboard(KeyPress::WithModifiers {
                 key: Key::Character('c'),
@@ -243,7 +413,264 @@ impl TryFrom<InputEvent> for EditorEvent {
                     },
             }) => Ok(EditorEvent::Paste),
 
+            // File events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('s'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::Save),
+
+            // Multiple carets.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('d'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::AddCaretAtNextOccurrence),
+
+            // Line events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Up),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::NotPressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::MoveLineUp),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Down),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::NotPressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::MoveLineDown),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('d' | 'D'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::DuplicateLine),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('j'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::JoinLines),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('k' | 'K'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::DeleteLine),
+
+            // Most terminals send Ctrl+/ as Ctrl+7.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('/' | '7'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::ToggleLineComment),
+
+            // Word events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Left),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::WordLeft),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::WordRight),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Left),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::OneWordLeft)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::Select(SelectionAction::OneWordRight)),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Backspace),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::BackspaceWord),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Delete),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::DeleteWord),
+
+            // Go to events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Home),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::GoToTop),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::End),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::GoToBottom),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::PageDown),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::NotPressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::GoToNextChange),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::PageUp),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::NotPressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::GoToPreviousChange),
+
+            // Spell check events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('s'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::NotPressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::ShowSpellingSuggestions),
+
+            // Macro events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('r'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::NotPressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::StartMacroRecording),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('q'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::NotPressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::StopMacroRecording),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('p'),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::NotPressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::Pressed,
+                    },
+            }) => Ok(EditorEvent::ReplayMacro(1)),
+
+            // Bookmark events.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::FunctionKey(FunctionKey::F2),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::ToggleBookmark(None)),
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::FunctionKey(FunctionKey::F2),
+            }) => Ok(EditorEvent::GoToNextBookmark),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::FunctionKey(FunctionKey::F2),
+                mask:
+                    ModifierKeysMask {
+                        ctrl_key_state: KeyState::NotPressed,
+                        shift_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Ok(EditorEvent::GoToPreviousBookmark),
+
             // Other events.
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Insert),
+            }) => Ok(EditorEvent::ToggleOverwriteMode),
+
             InputEvent::Keyboard(KeyPress::Plain {
                 key: Key::SpecialKey(SpecialKey::PageDown),
             }) => Ok(EditorEvent::PageDown),
@@ -262,6 +689,8 @@ impl TryFrom<InputEvent> for EditorEvent {
 
             InputEvent::Resize(size) => Ok(EditorEvent::Resize(size)),
 
+            InputEvent::Paste(text) => Ok(EditorEvent::InsertText(text)),
+
             InputEvent::Keyboard(KeyPress::Plain {
                 key: Key::Character(character),
             }) => Ok(Self::InsertChar(character)),
@@ -270,6 +699,25 @@ impl TryFrom<InputEvent> for EditorEvent {
                 key: Key::SpecialKey(SpecialKey::Enter),
             }) => Ok(Self::InsertNewLine),
 
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Tab),
+            }) => Ok(Self::InsertTab),
+
+            InputEvent::Keyboard(
+                KeyPress::Plain {
+                    key: Key::SpecialKey(SpecialKey::BackTab),
+                }
+                | KeyPress::WithModifiers {
+                    key: Key::SpecialKey(SpecialKey::BackTab),
+                    mask:
+                        ModifierKeysMask {
+                            shift_key_state: KeyState::Pressed,
+                            ctrl_key_state: KeyState::NotPressed,
+                            alt_key_state: KeyState::NotPressed,
+                        },
+                },
+            ) => Ok(Self::Outdent),
+
             InputEvent::Keyboard(KeyPress::Plain {
                 key: Key::SpecialKey(SpecialKey::Delete),
             }) => Ok(Self::Delete),
@@ -294,12 +742,87 @@ impl TryFrom<InputEvent> for EditorEvent {
                 key: Key::SpecialKey(SpecialKey::Right),
             }) => Ok(Self::MoveCaret(CaretDirection::Right)),
 
+            // Mouse events.
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDown(Button::Left),
+                maybe_modifier_keys: None,
+            }) => Ok(Self::Mouse(MouseAction::PlaceCaret(pos))),
+
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDown(Button::Left),
+                maybe_modifier_keys:
+                    Some(ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    }),
+            }) => Ok(Self::Mouse(MouseAction::SelectTo(pos))),
+
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDown(Button::Left),
+                maybe_modifier_keys:
+                    Some(ModifierKeysMask {
+                        alt_key_state: KeyState::Pressed,
+                        shift_key_state: KeyState::NotPressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                    }),
+            }) => Ok(Self::Mouse(MouseAction::AddCaret(pos))),
+
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDrag(Button::Left),
+                ..
+            }) => Ok(Self::Mouse(MouseAction::SelectTo(pos))),
+
+            InputEvent::Mouse(MouseInput {
+                kind: MouseInputKind::ScrollUp,
+                ..
+            }) => Ok(Self::Mouse(MouseAction::ScrollUp)),
+
+            InputEvent::Mouse(MouseInput {
+                kind: MouseInputKind::ScrollDown,
+                ..
+            }) => Ok(Self::Mouse(MouseAction::ScrollDown)),
+
             _ => Err(format!("Invalid input event: {input_event:?}")),
         }
     }
 }
 
 impl EditorEvent {
+    /// Returns `true` if this event can change the content of the [EditorBuffer]. These
+    /// events are rejected when the editor is in [crate::EditMode::ReadOnly].
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            EditorEvent::InsertChar(_)
+                | EditorEvent::InsertString(_)
+                | EditorEvent::InsertText(_)
+                | EditorEvent::InsertSnippet(_)
+                | EditorEvent::InsertNewLine
+                | EditorEvent::InsertTab
+                | EditorEvent::Outdent
+                | EditorEvent::Delete
+                | EditorEvent::Backspace
+                | EditorEvent::DeleteWord
+                | EditorEvent::BackspaceWord
+                | EditorEvent::MoveLineUp
+                | EditorEvent::MoveLineDown
+                | EditorEvent::DuplicateLine
+                | EditorEvent::JoinLines
+                | EditorEvent::DeleteLine
+                | EditorEvent::TrimTrailingWhitespace
+                | EditorEvent::ToggleLineComment
+                | EditorEvent::Paste
+                | EditorEvent::Cut
+                | EditorEvent::Undo
+                | EditorEvent::Redo
+        )
+    }
+
     fn delete_text_if_selected(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
@@ -316,11 +839,155 @@ impl EditorEvent {
         );
     }
 
+    /// Edits that don't add or remove lines are applied at all the carets, events that
+    /// only deal w/ the carets (or don't touch the buffer) keep them, and everything
+    /// else collapses them back to the primary caret.
+    fn get_multi_caret_behavior(
+        &self,
+        editor_buffer: &EditorBuffer,
+    ) -> MultiCaretBehavior {
+        // Backspace (or delete) at a caret w/out a selection might join lines.
+        let carets_without_selection = once((
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+            editor_buffer.has_selection(),
+        ))
+        .chain(
+            editor_buffer
+                .get_secondary_carets()
+                .iter()
+                .map(|it| (it.position, it.maybe_selection.is_some())),
+        )
+        .filter(|(_, has_selection)| !has_selection)
+        .map(|(position, _)| position);
+        let is_any_caret_at_start_of_line = || {
+            carets_without_selection
+                .clone()
+                .any(|it| it.col_index == ch!(0))
+        };
+        let is_any_caret_at_end_of_line = || {
+            carets_without_selection.clone().any(|it| {
+                it.col_index == editor_buffer.get_line_display_width(it.row_index)
+            })
+        };
+
+        match self {
+            EditorEvent::InsertChar(_) => MultiCaretBehavior::ApplyAtAllCarets,
+            EditorEvent::InsertString(chunk) if !chunk.contains('\n') => {
+                MultiCaretBehavior::ApplyAtAllCarets
+            }
+            EditorEvent::InsertText(text) if !text.contains('\n') => {
+                MultiCaretBehavior::ApplyAtAllCarets
+            }
+            EditorEvent::InsertTab if !editor_buffer.has_selection() => {
+                MultiCaretBehavior::ApplyAtAllCarets
+            }
+            // Backspace at the start of a line, or delete at the end of one, joins lines,
+            // which would move the carets below it.
+            EditorEvent::Backspace | EditorEvent::BackspaceWord
+                if !is_any_caret_at_start_of_line() =>
+            {
+                MultiCaretBehavior::ApplyAtAllCarets
+            }
+            EditorEvent::Delete | EditorEvent::DeleteWord
+                if !is_any_caret_at_end_of_line() =>
+            {
+                MultiCaretBehavior::ApplyAtAllCarets
+            }
+            EditorEvent::AddCaretAtNextOccurrence
+            | EditorEvent::Mouse(
+                MouseAction::AddCaret(_)
+                | MouseAction::ScrollUp
+                | MouseAction::ScrollDown,
+            )
+            | EditorEvent::Copy
+            | EditorEvent::Save
+            | EditorEvent::ToggleBookmark(_)
+            | EditorEvent::ToggleOverwriteMode
+            | EditorEvent::Resize(_)
+            | EditorEvent::Undo
+            | EditorEvent::Redo => MultiCaretBehavior::KeepSecondaryCarets,
+            _ => MultiCaretBehavior::ClearSecondaryCarets,
+        }
+    }
+
     pub fn apply_editor_event(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
         editor_event: EditorEvent,
         clipboard_service_provider: &mut impl ClipboardService,
+    ) {
+        // Macro events don't change the buffer themselves (and they apply to all the
+        // carets). Replaying is done by [crate::EditorEngineApi::apply_editor_event], so
+        // that each of the recorded events is checked against the [crate::EditMode] &
+        // saved in history.
+        match editor_event {
+            EditorEvent::StartMacroRecording => {
+                editor_engine.editor_macro.start_recording();
+                return;
+            }
+            EditorEvent::StopMacroRecording => {
+                editor_engine.editor_macro.stop_recording();
+                return;
+            }
+            EditorEvent::ReplayMacro(_) => return,
+            _ => {}
+        }
+
+        // While a snippet is being filled in, Tab & Shift+Tab move between its tab stops.
+        if editor_buffer_snippet_support::try_handle_editor_event(
+            editor_buffer,
+            editor_engine,
+            &editor_event,
+        ) {
+            return;
+        }
+
+        if editor_event.is_mutating() {
+            editor_buffer.set_dirty(true);
+        }
+
+        // Any other event ends the block selection, so the next one starts a new block.
+        if !matches!(editor_event, EditorEvent::Select(SelectionAction::Block(_))) {
+            editor_buffer.editor_content.maybe_block_selection_anchor = None;
+        }
+
+        if editor_buffer.has_secondary_carets() {
+            match editor_event.get_multi_caret_behavior(editor_buffer) {
+                MultiCaretBehavior::ApplyAtAllCarets => {
+                    editor_buffer_multi_caret_support::apply_at_all_carets(
+                        editor_buffer,
+                        editor_engine,
+                        |editor_buffer, editor_engine| {
+                            Self::apply_editor_event_at_caret(
+                                editor_engine,
+                                editor_buffer,
+                                editor_event.clone(),
+                                clipboard_service_provider,
+                            );
+                        },
+                    );
+                    return;
+                }
+                MultiCaretBehavior::ClearSecondaryCarets => {
+                    editor_buffer.clear_secondary_carets();
+                }
+                MultiCaretBehavior::KeepSecondaryCarets => {}
+            }
+        }
+
+        Self::apply_editor_event_at_caret(
+            editor_engine,
+            editor_buffer,
+            editor_event,
+            clipboard_service_provider,
+        );
+    }
+
+    fn apply_editor_event_at_caret(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+        editor_event: EditorEvent,
+        clipboard_service_provider: &mut impl ClipboardService,
     ) {
         match editor_event {
             EditorEvent::Undo => {
@@ -332,14 +999,43 @@ impl EditorEvent {
             }
 
             EditorEvent::InsertChar(character) => {
+                let has_selection = editor_buffer.has_selection();
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
-                EditorEngineInternalApi::insert_str_at_caret(
-                    EditorArgsMut {
+                // Type over the character at the caret (but not over the end of the
+                // line, which would join it w/ the next one).
+                if editor_engine.is_overwrite_mode
+                    && !has_selection
+                    && EditorEngineInternalApi::string_at_caret(
                         editor_buffer,
                         editor_engine,
-                    },
-                    &String::from(character),
-                )
+                    )
+                    .is_some()
+                {
+                    EditorEngineInternalApi::delete_at_caret(
+                        editor_buffer,
+                        editor_engine,
+                    );
+                }
+                match editor_engine.config_options.auto_close_pairs {
+                    AutoClosePairsMode::Enable => {
+                        EditorEngineInternalApi::insert_char_with_auto_close_pairs(
+                            EditorArgsMut {
+                                editor_buffer,
+                                editor_engine,
+                            },
+                            character,
+                        )
+                    }
+                    AutoClosePairsMode::Disable => {
+                        EditorEngineInternalApi::insert_str_at_caret(
+                            EditorArgsMut {
+                                editor_buffer,
+                                editor_engine,
+                            },
+                            &String::from(character),
+                        )
+                    }
+                }
             }
 
             EditorEvent::InsertNewLine => {
@@ -350,6 +1046,25 @@ impl EditorEvent {
                 });
             }
 
+            EditorEvent::InsertTab => {
+                if editor_buffer.has_selection() {
+                    // Indent the selected lines as a block.
+                    EditorEngineInternalApi::indent_selected_lines(
+                        editor_buffer,
+                        editor_engine,
+                    );
+                } else {
+                    EditorEngineInternalApi::insert_tab_at_caret(EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    });
+                }
+            }
+
+            EditorEvent::Outdent => {
+                EditorEngineInternalApi::outdent_lines(editor_buffer, editor_engine);
+            }
+
             EditorEvent::Delete => {
                 if editor_buffer.get_selection_map().is_empty() {
                     // There is no selection and we want to delete a single character.
@@ -384,6 +1099,96 @@ impl EditorEvent {
                 }
             }
 
+            EditorEvent::DeleteWord => {
+                if editor_buffer.get_selection_map().is_empty() {
+                    EditorEngineInternalApi::delete_word_at_caret(
+                        editor_buffer,
+                        editor_engine,
+                    );
+                } else {
+                    // The text is selected and we want to delete the entire selected text.
+                    EditorEngineInternalApi::delete_selected(
+                        editor_buffer,
+                        editor_engine,
+                        DeleteSelectionWith::Delete,
+                    );
+                }
+            }
+
+            EditorEvent::BackspaceWord => {
+                if editor_buffer.get_selection_map().is_empty() {
+                    EditorEngineInternalApi::backspace_word_at_caret(
+                        editor_buffer,
+                        editor_engine,
+                    );
+                } else {
+                    // The text is selected and we want to delete the entire selected text.
+                    EditorEngineInternalApi::delete_selected(
+                        editor_buffer,
+                        editor_engine,
+                        DeleteSelectionWith::Backspace,
+                    );
+                }
+            }
+
+            EditorEvent::MoveLineUp => {
+                EditorEngineInternalApi::move_line(
+                    editor_buffer,
+                    editor_engine,
+                    CaretDirection::Up,
+                );
+            }
+
+            EditorEvent::MoveLineDown => {
+                EditorEngineInternalApi::move_line(
+                    editor_buffer,
+                    editor_engine,
+                    CaretDirection::Down,
+                );
+            }
+
+            EditorEvent::DuplicateLine => {
+                EditorEngineInternalApi::duplicate_line(editor_buffer, editor_engine);
+            }
+
+            EditorEvent::JoinLines => {
+                EditorEngineInternalApi::join_lines(editor_buffer, editor_engine);
+            }
+
+            EditorEvent::DeleteLine => {
+                EditorEngineInternalApi::delete_line(editor_buffer, editor_engine);
+            }
+
+            EditorEvent::TrimTrailingWhitespace => {
+                EditorEngineInternalApi::trim_trailing_whitespace(
+                    editor_buffer,
+                    editor_engine,
+                );
+            }
+
+            EditorEvent::ToggleLineComment => {
+                EditorEngineInternalApi::toggle_line_comment(
+                    editor_buffer,
+                    editor_engine,
+                );
+            }
+
+            EditorEvent::WordLeft => {
+                EditorEngineInternalApi::word_left(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Disabled,
+                );
+            }
+
+            EditorEvent::WordRight => {
+                EditorEngineInternalApi::word_right(
+                    editor_buffer,
+                    editor_engine,
+                    SelectMode::Disabled,
+                );
+            }
+
             EditorEvent::MoveCaret(direction) => {
                 match direction {
                     CaretDirection::Left => EditorEngineInternalApi::left(
@@ -420,6 +1225,26 @@ impl EditorEvent {
                 )
             }
 
+            EditorEvent::InsertText(text) => {
+                Self::delete_text_if_selected(editor_engine, editor_buffer);
+                EditorEngineInternalApi::insert_text_at_caret(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &text,
+                );
+            }
+
+            EditorEvent::InsertSnippet(snippet) => {
+                Self::delete_text_if_selected(editor_engine, editor_buffer);
+                editor_buffer_snippet_support::insert_snippet(
+                    editor_buffer,
+                    editor_engine,
+                    &snippet,
+                );
+            }
+
             EditorEvent::Resize(_) => {
                 // Check to see whether scroll is valid.
                 EditorEngineInternalApi::validate_scroll(EditorArgsMut {
@@ -444,6 +1269,84 @@ impl EditorEvent {
                 );
             }
 
+            EditorEvent::GoToLine(line_number) => {
+                EditorEngineInternalApi::go_to_row(
+                    editor_buffer,
+                    editor_engine,
+                    ch!(line_number) - ch!(1),
+                );
+            }
+
+            EditorEvent::GoToTop => {
+                EditorEngineInternalApi::go_to_top(editor_buffer, editor_engine);
+            }
+
+            EditorEvent::GoToBottom => {
+                EditorEngineInternalApi::go_to_bottom(editor_buffer, editor_engine);
+            }
+
+            EditorEvent::GoToNextChange | EditorEvent::GoToPreviousChange => {
+                let direction = match editor_event {
+                    EditorEvent::GoToNextChange => CaretDirection::Down,
+                    _ => CaretDirection::Up,
+                };
+                let row_index =
+                    editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+                if let Some(row_index) = editor_buffer.find_change(row_index, direction) {
+                    EditorEngineInternalApi::go_to_row(
+                        editor_buffer,
+                        editor_engine,
+                        row_index,
+                    );
+                }
+            }
+
+            EditorEvent::ToggleBookmark(maybe_name) => {
+                let row_index =
+                    editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+                editor_buffer.toggle_bookmark(row_index, maybe_name);
+            }
+
+            EditorEvent::GoToNextBookmark | EditorEvent::GoToPreviousBookmark => {
+                let direction = match editor_event {
+                    EditorEvent::GoToNextBookmark => CaretDirection::Down,
+                    _ => CaretDirection::Up,
+                };
+                let row_index =
+                    editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+                if let Some(row_index) = editor_buffer.find_bookmark(row_index, direction)
+                {
+                    EditorEngineInternalApi::go_to_row(
+                        editor_buffer,
+                        editor_engine,
+                        row_index,
+                    );
+                }
+            }
+
+            EditorEvent::GoToBookmark(name) => {
+                if let Some(row_index) = editor_buffer.find_named_bookmark(name) {
+                    EditorEngineInternalApi::go_to_row(
+                        editor_buffer,
+                        editor_engine,
+                        row_index,
+                    );
+                }
+            }
+
+            // These are handled in `apply_editor_event()`.
+            EditorEvent::StartMacroRecording
+            | EditorEvent::StopMacroRecording
+            | EditorEvent::ReplayMacro(_) => {}
+
+            EditorEvent::ToggleOverwriteMode => {
+                editor_engine.is_overwrite_mode = !editor_engine.is_overwrite_mode;
+            }
+
+            EditorEvent::ShowSpellingSuggestions => {
+                completion_popup::show_spelling_suggestions(editor_buffer, editor_engine);
+            }
+
             EditorEvent::PageDown => {
                 EditorEngineInternalApi::page_down(
                     editor_buffer,
@@ -475,6 +1378,20 @@ impl EditorEvent {
                         SelectMode::Enabled,
                     );
                 }
+                SelectionAction::OneWordRight => {
+                    EditorEngineInternalApi::word_right(
+                        editor_buffer,
+                        editor_engine,
+                        SelectMode::Enabled,
+                    );
+                }
+                SelectionAction::OneWordLeft => {
+                    EditorEngineInternalApi::word_left(
+                        editor_buffer,
+                        editor_engine,
+                        SelectMode::Enabled,
+                    );
+                }
                 SelectionAction::OneLineDown => {
                     EditorEngineInternalApi::down(
                         editor_buffer,
@@ -526,6 +1443,13 @@ impl EditorEvent {
                 SelectionAction::Esc => {
                     EditorEngineInternalApi::clear_selection(editor_buffer);
                 }
+                SelectionAction::Block(direction) => {
+                    editor_buffer_multi_caret_support::extend_block_selection(
+                        editor_buffer,
+                        editor_engine,
+                        direction,
+                    );
+                }
             },
 
             EditorEvent::Cut => {
@@ -543,6 +1467,69 @@ impl EditorEvent {
                 );
             }
 
+            EditorEvent::Mouse(mouse_action) => match mouse_action {
+                MouseAction::PlaceCaret(screen_position) => {
+                    EditorEngineInternalApi::move_caret_to_screen_position(
+                        editor_buffer,
+                        editor_engine,
+                        screen_position,
+                        SelectMode::Disabled,
+                    );
+                }
+                MouseAction::SelectTo(screen_position) => {
+                    EditorEngineInternalApi::move_caret_to_screen_position(
+                        editor_buffer,
+                        editor_engine,
+                        screen_position,
+                        SelectMode::Enabled,
+                    );
+                }
+                MouseAction::AddCaret(screen_position) => {
+                    if let Some(caret_adj) =
+                        EditorEngineInternalApi::screen_position_to_buffer_position(
+                            editor_buffer,
+                            editor_engine,
+                            screen_position,
+                        )
+                    {
+                        editor_buffer_multi_caret_support::add_caret_at_position(
+                            editor_buffer,
+                            caret_adj,
+                        );
+                    }
+                }
+                MouseAction::ScrollUp => {
+                    EditorEngineInternalApi::scroll_viewport_by_one_row(
+                        editor_buffer,
+                        editor_engine,
+                        CaretDirection::Up,
+                    );
+                }
+                MouseAction::ScrollDown => {
+                    EditorEngineInternalApi::scroll_viewport_by_one_row(
+                        editor_buffer,
+                        editor_engine,
+                        CaretDirection::Down,
+                    );
+                }
+            },
+
+            EditorEvent::Save => {
+                if let Err(error) = editor_buffer.save() {
+                    tracing::error!(
+                        "\n💾💾💾❌ Failed to save file: {}",
+                        format!("{error:?}").red()
+                    );
+                }
+            }
+
+            EditorEvent::AddCaretAtNextOccurrence => {
+                editor_buffer_multi_caret_support::add_caret_at_next_occurrence(
+                    editor_buffer,
+                    editor_engine,
+                );
+            }
+
             EditorEvent::Paste => {
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
                 EditorEngineInternalApi::paste_clipboard_content_into_editor(
@@ -0,0 +1,181 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, path::PathBuf, time::Duration};
+
+use r3bl_core::{throws, CommonResult};
+use tokio::sync::mpsc::{channel, Sender};
+
+use crate::{FileStamp, FlexBoxId, SharedFileStamp, TerminalWindowMainThreadSignal};
+
+pub const DEFAULT_FILE_WATCHER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub type OnFileChangedOnDiskFn<AS> =
+    fn(FlexBoxId, Sender<TerminalWindowMainThreadSignal<AS>>);
+
+/// Watches the file that is open in an editor component for changes made by other
+/// programs, so that the app can ask the user whether to reload the file or keep the
+/// content of the buffer.
+///
+/// 1. The file is polled (every [DEFAULT_FILE_WATCHER_POLL_INTERVAL]) and when its
+///    [FileStamp] is different from the one that the buffer last loaded or saved (see
+///    [crate::EditorBuffer::get_shared_file_stamp]), the `on_file_changed` callback is
+///    called w/ the main thread channel. This is where the app sends a signal to itself
+///    (eg: [TerminalWindowMainThreadSignal::ApplyAction]) to show a "file changed on
+///    disk" dialog.
+/// 2. Since the buffer updates the shared stamp when it saves the file, its own saves
+///    aren't signalled. And a change is only signalled once, until the file changes
+///    again. The watcher could still race w/ a save in progress, so in the app's signal
+///    handler, use [crate::EditorBuffer::has_file_changed_on_disk] to double check.
+/// 3. If the user decides to reload, call [crate::EditorBuffer::reload].
+///
+/// Polling is used (instead of OS file notifications) since a `stat` every
+/// [DEFAULT_FILE_WATCHER_POLL_INTERVAL] is cheap, it works on network file systems, and
+/// it isn't thrown off when other programs save the file atomically (by renaming a new
+/// file over it).
+///
+/// Just like [crate::Animator], the watcher can be stopped & started again (eg: when a
+/// different file is opened).
+#[derive(Debug, Default)]
+pub struct EditorFileWatcher {
+    /// This is the channel that is used to kill the watcher task. [None] means that the
+    /// watcher task is not running.
+    pub watcher_kill_channel: Option<Sender<()>>,
+}
+
+impl EditorFileWatcher {
+    /// Starts the watcher task for the file at `file_path` (that is open in the editor
+    /// component w/ the given `id`), if one isn't already running.
+    pub fn start<AS>(
+        &mut self,
+        id: FlexBoxId,
+        file_path: impl Into<PathBuf>,
+        shared_file_stamp: SharedFileStamp,
+        channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
+        on_file_changed: OnFileChangedOnDiskFn<AS>,
+    ) where
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        if self.is_started() {
+            return;
+        }
+
+        let file_path = file_path.into();
+        let (kill_sender, mut kill_receiver) = channel::<()>(1);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(DEFAULT_FILE_WATCHER_POLL_INTERVAL);
+            // The stamp that was last signalled, so each change is only signalled once.
+            let mut maybe_last_signalled_stamp: Option<Option<FileStamp>> = None;
+
+            loop {
+                tokio::select! {
+                    _ = kill_receiver.recv() => break,
+                    _ = interval.tick() => {
+                        // The app has exited.
+                        if channel_sender.is_closed() {
+                            break;
+                        }
+
+                        let maybe_stamp = FileStamp::read_async(&file_path).await;
+                        let maybe_buffer_stamp =
+                            shared_file_stamp.lock().ok().and_then(|it| *it);
+                        if maybe_stamp == maybe_buffer_stamp {
+                            maybe_last_signalled_stamp = None;
+                        } else if maybe_last_signalled_stamp != Some(maybe_stamp) {
+                            maybe_last_signalled_stamp = Some(maybe_stamp);
+                            on_file_changed(id, channel_sender.clone());
+                        }
+                    }
+                }
+            }
+        });
+
+        self.watcher_kill_channel = Some(kill_sender);
+    }
+
+    pub fn is_started(&self) -> bool { self.watcher_kill_channel.is_some() }
+
+    pub fn stop(&mut self) -> CommonResult<()> {
+        throws!({
+            if let Some(kill_channel) = self.watcher_kill_channel.take() {
+                tokio::spawn(async move {
+                    let _ = kill_channel.send(()).await;
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::timeout;
+
+    use super::*;
+    use crate::EditorBuffer;
+
+    #[derive(Debug, Default, Clone)]
+    struct TestAction;
+
+    #[tokio::test]
+    async fn test_change_on_disk_is_signalled() {
+        let file_path = std::env::temp_dir().join(format!(
+            "r3bl_tui_editor_file_watcher_{}.md",
+            std::process::id()
+        ));
+        std::fs::write(&file_path, "abc").unwrap();
+        let mut buffer = EditorBuffer::load_from_path(&file_path).unwrap();
+
+        let (sender, mut receiver) =
+            channel::<TerminalWindowMainThreadSignal<TestAction>>(8);
+        let mut watcher = EditorFileWatcher::default();
+        watcher.start(
+            FlexBoxId::from(1),
+            &file_path,
+            buffer.get_shared_file_stamp(),
+            sender,
+            |_id, sender| {
+                tokio::spawn(async move {
+                    let _ = sender
+                        .send(TerminalWindowMainThreadSignal::ApplyAction(TestAction))
+                        .await;
+                });
+            },
+        );
+        assert!(watcher.is_started());
+
+        // Saving the buffer isn't a change made by another program.
+        buffer.set_lines(vec!["abcd".to_string()]);
+        buffer.save().unwrap();
+        let signal =
+            timeout(DEFAULT_FILE_WATCHER_POLL_INTERVAL * 3, receiver.recv()).await;
+        assert!(signal.is_err());
+
+        std::fs::write(&file_path, "abcdef").unwrap();
+
+        let signal =
+            timeout(DEFAULT_FILE_WATCHER_POLL_INTERVAL * 4, receiver.recv()).await;
+        assert!(matches!(
+            signal,
+            Ok(Some(TerminalWindowMainThreadSignal::ApplyAction(_)))
+        ));
+
+        watcher.stop().unwrap();
+        assert!(!watcher.is_started());
+        std::fs::remove_file(&file_path).unwrap();
+    }
+}
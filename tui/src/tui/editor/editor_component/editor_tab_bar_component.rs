@@ -0,0 +1,332 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_core::{ch, position, throws_with_return, CommonResult, UnicodeString};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::Sender;
+
+use crate::{get_editor_buffer_title,
+            get_editor_tab_active_style,
+            get_editor_tab_style,
+            render_ops,
+            render_pipeline,
+            BoxedSafeComponent,
+            Component,
+            EditorBufferTabs,
+            EventPropagation,
+            FlexBox,
+            FlexBoxId,
+            GlobalData,
+            HasEditorBufferTabs,
+            HasFocus,
+            InputEvent,
+            Key,
+            KeyPress,
+            ModifierKeysMask,
+            RenderOp,
+            RenderOps,
+            RenderPipeline,
+            ShortcutGroup,
+            SpecialKey,
+            SurfaceBounds,
+            TerminalWindowMainThreadSignal,
+            ZOrder};
+
+/// This is painted after the title of a buffer that has unsaved changes.
+pub const EDITOR_TAB_DIRTY_INDICATOR: &str = "●";
+
+/// Events that switch between, close & reopen the buffers in [EditorBufferTabs].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditorTabEvent {
+    /// <kbd>Ctrl+Tab</kbd>.
+    NextTab,
+    /// <kbd>Ctrl+Shift+Tab</kbd>.
+    PreviousTab,
+    /// <kbd>Ctrl+W</kbd>.
+    CloseTab,
+    /// <kbd>Ctrl+Shift+T</kbd>.
+    ReopenClosedTab,
+    /// Make the buffer at the given index active. This doesn't have a key binding.
+    ActivateTab(usize),
+}
+
+impl TryFrom<InputEvent> for EditorTabEvent {
+    type Error = String;
+
+    fn try_from(input_event: InputEvent) -> Result<Self, Self::Error> {
+        let ctrl = ModifierKeysMask::new().with_ctrl();
+        let ctrl_shift = ModifierKeysMask::new().with_ctrl().with_shift();
+
+        match input_event {
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Tab),
+                mask,
+            }) if mask == ctrl => Ok(Self::NextTab),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Tab | SpecialKey::BackTab),
+                mask,
+            }) if mask == ctrl_shift => Ok(Self::PreviousTab),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('w'),
+                mask,
+            }) if mask == ctrl => Ok(Self::CloseTab),
+
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('t' | 'T'),
+                mask,
+            }) if mask == ctrl_shift => Ok(Self::ReopenClosedTab),
+
+            _ => Err(format!("Invalid input event: {input_event:?}")),
+        }
+    }
+}
+
+impl EditorTabEvent {
+    /// Returns `false` if the event didn't change anything (eg: there's no closed buffer
+    /// to reopen).
+    pub fn apply(self, tabs: &mut EditorBufferTabs) -> bool {
+        match self {
+            EditorTabEvent::NextTab => tabs.activate_next(),
+            EditorTabEvent::PreviousTab => tabs.activate_previous(),
+            EditorTabEvent::CloseTab => tabs.close_active(),
+            EditorTabEvent::ReopenClosedTab => return tabs.reopen_closed(),
+            EditorTabEvent::ActivateTab(index) => return tabs.activate(index),
+        }
+        true
+    }
+}
+
+/// Displays the [EditorBufferTabs] of an editor component as a strip of tabs (one row
+/// high), w/ the active one highlighted and a [EDITOR_TAB_DIRTY_INDICATOR] on the ones
+/// w/ unsaved changes.
+///
+/// The editor component has focus while the user is typing, so the app has to route
+/// input events to this component before the focused one, using
+/// [crate::ComponentRegistry::route_event_to_component]. Events that are handled by the
+/// tab bar (see [EditorTabEvent]) are consumed, and the `on_tab_change_handler` is
+/// called so that the app can react (eg: update its title).
+#[derive(Debug, Default)]
+pub struct EditorTabBarComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub data: EditorTabBarComponentData<S, AS>,
+}
+
+#[derive(Debug, Default)]
+pub struct EditorTabBarComponentData<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    /// The id of the editor component whose buffers are displayed.
+    pub editor_id: FlexBoxId,
+    pub on_tab_change_handler: Option<OnEditorTabChangeFn<AS>>,
+    _phantom: std::marker::PhantomData<S>,
+}
+
+pub type OnEditorTabChangeFn<A> =
+    fn(FlexBoxId, Sender<TerminalWindowMainThreadSignal<A>>);
+
+impl<S, AS> Component<S, AS> for EditorTabBarComponent<S, AS>
+where
+    S: HasEditorBufferTabs + Default + Clone + Debug + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    fn reset(&mut self) {}
+
+    fn get_id(&self) -> FlexBoxId { self.data.id }
+
+    fn get_shortcuts(&self) -> Option<ShortcutGroup> {
+        let ctrl = ModifierKeysMask::new().with_ctrl();
+        let ctrl_shift = ModifierKeysMask::new().with_ctrl().with_shift();
+        let key_press =
+            |key: Key, mask: ModifierKeysMask| KeyPress::WithModifiers { key, mask };
+        Some(
+            ShortcutGroup::new("Editor tabs")
+                .add(
+                    key_press(Key::SpecialKey(SpecialKey::Tab), ctrl),
+                    "Next tab",
+                )
+                .add(
+                    key_press(Key::SpecialKey(SpecialKey::Tab), ctrl_shift),
+                    "Previous tab",
+                )
+                .add(key_press(Key::Character('w'), ctrl), "Close tab")
+                .add(
+                    key_press(Key::Character('t'), ctrl_shift),
+                    "Reopen closed tab",
+                ),
+        )
+    }
+
+    fn render(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        current_box: FlexBox,
+        _surface_bounds: SurfaceBounds, /* Ignore this. */
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<RenderPipeline> {
+        throws_with_return!({
+            let mut render_pipeline = render_pipeline!();
+            if let Some(tabs) = global_data
+                .state
+                .get_editor_buffer_tabs(self.data.editor_id)
+            {
+                render_pipeline.push(ZOrder::Normal, render_tab_bar(tabs, &current_box));
+            }
+            render_pipeline
+        });
+    }
+
+    fn handle_event(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        input_event: InputEvent,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        throws_with_return!({
+            let EditorTabBarComponentData {
+                editor_id,
+                on_tab_change_handler,
+                ..
+            } = &self.data;
+
+            let Ok(tab_event) = EditorTabEvent::try_from(input_event) else {
+                return Ok(EventPropagation::Propagate);
+            };
+            let Some(tabs) = global_data.state.get_mut_editor_buffer_tabs(*editor_id)
+            else {
+                return Ok(EventPropagation::Propagate);
+            };
+
+            if tab_event.apply(tabs) {
+                if let Some(on_tab_change_handler) = on_tab_change_handler {
+                    on_tab_change_handler(
+                        *editor_id,
+                        global_data.main_thread_channel_sender.clone(),
+                    );
+                }
+            }
+            EventPropagation::ConsumedRender
+        });
+    }
+}
+
+/// Paint the tabs in the first row of the `current_box`. If they don't all fit, then the
+/// tabs on the left are dropped, so that the active one is visible.
+fn render_tab_bar(tabs: &EditorBufferTabs, current_box: &FlexBox) -> RenderOps {
+    let labels: Vec<String> = tabs
+        .get_buffers()
+        .iter()
+        .map(|buffer| {
+            let title = get_editor_buffer_title(buffer);
+            match buffer.is_dirty() {
+                true => format!(" {title} {EDITOR_TAB_DIRTY_INDICATOR} "),
+                false => format!(" {title} "),
+            }
+        })
+        .collect();
+    let label_widths: Vec<usize> = labels
+        .iter()
+        .map(|it| UnicodeString::str_display_width(it))
+        .collect();
+
+    // Drop tabs from the left until the active one fits.
+    let max_width = ch!(@to_usize current_box.style_adjusted_bounds_size.col_count);
+    let active_index = tabs.get_active_index();
+    let mut first_index = 0;
+    while first_index < active_index
+        && label_widths[first_index..=active_index]
+            .iter()
+            .sum::<usize>()
+            > max_width
+    {
+        first_index += 1;
+    }
+
+    let mut render_ops = render_ops!();
+    let mut col_index = 0;
+    for (index, label) in labels.iter().enumerate().skip(first_index) {
+        let remaining_width = max_width.saturating_sub(col_index);
+        if remaining_width == 0 {
+            break;
+        }
+
+        let style = match index == active_index {
+            true => get_editor_tab_active_style(),
+            false => get_editor_tab_style(),
+        };
+        let label = UnicodeString::from(label.as_str());
+        let clipped_label = label.clip_to_width(ch!(0), ch!(remaining_width));
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            current_box.style_adjusted_origin_pos,
+            position!(col_index: col_index, row_index: 0),
+        ));
+        render_ops.push(RenderOp::ApplyColors(Some(style)));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            clipped_label.to_string(),
+            Some(style),
+        ));
+        render_ops.push(RenderOp::ResetColor);
+
+        col_index += label_widths[index];
+    }
+    render_ops
+}
+
+pub mod constructor {
+    use super::*;
+
+    impl<S, AS> EditorTabBarComponent<S, AS>
+    where
+        S: Debug + Default + Clone + Sync + Send + HasEditorBufferTabs + 'static,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        /// The `on_tab_change` handler is called when the active buffer changes, or a
+        /// buffer is closed or reopened.
+        pub fn new(
+            id: FlexBoxId,
+            editor_id: FlexBoxId,
+            on_tab_change: OnEditorTabChangeFn<AS>,
+        ) -> Self {
+            Self {
+                data: EditorTabBarComponentData {
+                    id,
+                    editor_id,
+                    on_tab_change_handler: Some(on_tab_change),
+                    ..Default::default()
+                },
+            }
+        }
+
+        pub fn new_boxed(
+            id: FlexBoxId,
+            editor_id: FlexBoxId,
+            on_tab_change: OnEditorTabChangeFn<AS>,
+        ) -> BoxedSafeComponent<S, AS> {
+            Box::new(EditorTabBarComponent::new(id, editor_id, on_tab_change))
+        }
+    }
+}
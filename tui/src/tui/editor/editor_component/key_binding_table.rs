@@ -0,0 +1,206 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fs, path::Path};
+
+use r3bl_core::{CommonError, CommonErrorType, CommonResult};
+use serde::{Deserialize, Serialize};
+
+use crate::{EditorEvent, InputEvent, KeyPress};
+
+/// Overrides for the default key bindings of the editor, which are in
+/// `TryFrom<InputEvent> for EditorEvent`. It is stored in
+/// [EditorEngineConfig::key_binding_table](crate::EditorEngineConfig::key_binding_table),
+/// and is empty by default.
+///
+/// A [KeyBinding] can:
+/// 1. Rebind a key to another [EditorEvent], eg: <kbd>Ctrl + W</kbd> to
+///    [EditorEvent::Save].
+/// 2. Add a key that doesn't have a binding, eg: <kbd>Ctrl + T</kbd> to insert a snippet
+///    w/ [EditorEvent::InsertString].
+/// 3. Unbind a key, so that it isn't handled by the editor and is passed on to the app,
+///    eg: <kbd>Ctrl + S</kbd>, so that the app can do its own thing when saving.
+///
+/// The table can be built in code:
+///
+/// ```rust
+/// use r3bl_tui::*;
+///
+/// let table = KeyBindingTable::default()
+///     .bind(
+///         KeyPress::WithModifiers {
+///             key: Key::Character('w'),
+///             mask: ModifierKeysMask::new().with_ctrl(),
+///         },
+///         EditorEvent::Save,
+///     )
+///     .unbind(KeyPress::WithModifiers {
+///         key: Key::Character('s'),
+///         mask: ModifierKeysMask::new().with_ctrl(),
+///     });
+/// ```
+///
+/// Or loaded from a JSON config file w/ [KeyBindingTable::try_load_from_file], which has
+/// the same shape as the serialized [KeyBindingTable], eg:
+///
+/// ```json
+/// {
+///   "bindings": [
+///     {
+///       "key_press": {
+///         "WithModifiers": {
+///           "key": { "Character": "w" },
+///           "mask": {
+///             "shift_key_state": "NotPressed",
+///             "ctrl_key_state": "Pressed",
+///             "alt_key_state": "NotPressed"
+///           }
+///         }
+///       },
+///       "maybe_editor_event": "Save"
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindingTable {
+    pub bindings: Vec<KeyBinding>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    pub key_press: KeyPress,
+    /// [None] unbinds the key.
+    pub maybe_editor_event: Option<EditorEvent>,
+}
+
+impl KeyBindingTable {
+    /// Bind the `key_press` to the `editor_event`, replacing any previous binding for it.
+    pub fn bind(self, key_press: KeyPress, editor_event: EditorEvent) -> Self {
+        self.set(key_press, Some(editor_event))
+    }
+
+    /// Make the editor ignore the `key_press`, so that it is passed on to the app.
+    pub fn unbind(self, key_press: KeyPress) -> Self { self.set(key_press, None) }
+
+    fn set(
+        mut self,
+        key_press: KeyPress,
+        maybe_editor_event: Option<EditorEvent>,
+    ) -> Self {
+        self.bindings.retain(|it| it.key_press != key_press);
+        self.bindings.push(KeyBinding {
+            key_press,
+            maybe_editor_event,
+        });
+        self
+    }
+
+    /// Convert the `input_event` into an [EditorEvent] using the bindings in this table,
+    /// and falling back to the default key bindings for keys that aren't in it.
+    pub fn try_convert(&self, input_event: InputEvent) -> Result<EditorEvent, String> {
+        let InputEvent::Keyboard(key_press) = input_event else {
+            return EditorEvent::try_from(input_event);
+        };
+
+        match self.bindings.iter().find(|it| it.key_press == key_press) {
+            Some(KeyBinding {
+                maybe_editor_event: Some(editor_event),
+                ..
+            }) => Ok(editor_event.clone()),
+            Some(KeyBinding {
+                maybe_editor_event: None,
+                ..
+            }) => Err(format!("Key is unbound: {key_press:?}")),
+            None => EditorEvent::try_from(input_event),
+        }
+    }
+
+    pub fn try_from_json(json: &str) -> CommonResult<Self> {
+        match serde_json::from_str(json) {
+            Ok(it) => Ok(it),
+            Err(error) => CommonError::new_error_result(
+                CommonErrorType::ParsingError,
+                &format!("Failed to parse key binding table, error: {error}"),
+            ),
+        }
+    }
+
+    pub fn try_load_from_file(path: impl AsRef<Path>) -> CommonResult<Self> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(json) => Self::try_from_json(&json),
+            Err(error) => CommonError::new_error_result(
+                CommonErrorType::IOError,
+                &format!("Failed to read key binding table: {path:?}, error: {error}"),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{Key, ModifierKeysMask};
+
+    fn ctrl(character: char) -> KeyPress {
+        KeyPress::WithModifiers {
+            key: Key::Character(character),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        }
+    }
+
+    #[test]
+    fn test_rebind_and_unbind() {
+        let table = KeyBindingTable::default()
+            .bind(ctrl('w'), EditorEvent::Save)
+            .unbind(ctrl('s'));
+
+        assert_eq2!(
+            table.try_convert(InputEvent::Keyboard(ctrl('w'))),
+            Ok(EditorEvent::Save)
+        );
+        assert!(table.try_convert(InputEvent::Keyboard(ctrl('s'))).is_err());
+
+        // Keys that aren't in the table use the default key bindings.
+        assert_eq2!(
+            table.try_convert(InputEvent::Keyboard(ctrl('z'))),
+            Ok(EditorEvent::Undo)
+        );
+
+        // A later binding replaces an earlier one for the same key.
+        let table = table.bind(ctrl('s'), EditorEvent::Redo);
+        assert_eq2!(table.bindings.len(), 2);
+        assert_eq2!(
+            table.try_convert(InputEvent::Keyboard(ctrl('s'))),
+            Ok(EditorEvent::Redo)
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let table = KeyBindingTable::default()
+            .bind(ctrl('t'), EditorEvent::InsertString("TODO: ".into()))
+            .unbind(ctrl('s'));
+        let json = serde_json::to_string_pretty(&table).unwrap();
+        assert_eq2!(KeyBindingTable::try_from_json(&json).unwrap(), table);
+
+        assert!(KeyBindingTable::try_from_json("{ not json").is_err());
+    }
+}
@@ -16,9 +16,17 @@
  */
 
 // Attach.
+pub mod editor_caret_blinker;
 pub mod editor_component_struct;
 pub mod editor_event;
+pub mod editor_file_watcher;
+pub mod editor_tab_bar_component;
+pub mod key_binding_table;
 
 // Re-export.
+pub use editor_caret_blinker::*;
 pub use editor_component_struct::*;
 pub use editor_event::*;
+pub use editor_file_watcher::*;
+pub use editor_tab_bar_component::*;
+pub use key_binding_table::*;
@@ -0,0 +1,338 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{cmp, fmt::Debug, mem, sync::Arc};
+
+use r3bl_core::{ch, position, Position, SelectionRange, Size, UnicodeString};
+use serde::{Deserialize, Serialize};
+
+use crate::{get_completion_popup_selected_style,
+            get_completion_popup_style,
+            history,
+            render_ops,
+            validate_editor_buffer_change,
+            CaretKind,
+            EditorArgsMut,
+            EditorBuffer,
+            EditorEngine,
+            EditorEngineApplyEventResult,
+            EditorEngineInternalApi,
+            EditorEvent,
+            InputEvent,
+            Key,
+            KeyPress,
+            RenderOp,
+            RenderOps,
+            SpecialKey};
+
+/// The max number of items that are displayed in the popup at once. The rest can be
+/// scrolled to w/ <kbd>Up</kbd> & <kbd>Down</kbd>.
+pub const COMPLETION_POPUP_MAX_ROW_COUNT: usize = 8;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletionItem {
+    /// What is displayed in the popup.
+    pub label: String,
+    /// What replaces the [Self::replace_range] when the item is accepted. This has to be
+    /// a single line.
+    pub insert_text: String,
+    /// The display cols in the caret's row that are replaced, eg: the partial word to
+    /// the left of the caret.
+    pub replace_range: SelectionRange,
+}
+
+/// Apps implement this to add completion (eg: paths, emoji, or snippets) to the editor,
+/// by setting it in [EditorEngine::maybe_completion_provider].
+///
+/// After a character is typed, [CompletionProvider::completions] is called, and if it
+/// returns any items, a popup w/ them is displayed at the caret. While the popup is
+/// open:
+/// 1. <kbd>Up</kbd> & <kbd>Down</kbd> select an item.
+/// 2. <kbd>Tab</kbd> or <kbd>Enter</kbd> accept the selected item.
+/// 3. <kbd>Esc</kbd> closes the popup.
+/// 4. Typing (or deleting) characters updates the items, and any other key closes the
+///    popup & is handled as usual.
+pub trait CompletionProvider: Debug + Send + Sync {
+    /// `caret` is scroll adjusted (ie: it is in the buffer and not the viewport).
+    fn completions(
+        &self,
+        editor_buffer: &EditorBuffer,
+        caret: Position,
+    ) -> Vec<CompletionItem>;
+}
+
+pub type SharedCompletionProvider = Arc<dyn CompletionProvider>;
+
+/// This is stored in [EditorEngine::completion_popup], and the popup is open when it has
+/// items.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletionPopup {
+    pub items: Vec<CompletionItem>,
+    pub selected_index: usize,
+}
+
+impl CompletionPopup {
+    pub fn is_open(&self) -> bool { !self.items.is_empty() }
+
+    pub fn close(&mut self) { *self = Default::default(); }
+
+    /// The index of the 1st item that is displayed, so that the selected item is always
+    /// visible.
+    pub fn get_scroll_offset(&self) -> usize {
+        (self.selected_index + 1).saturating_sub(COMPLETION_POPUP_MAX_ROW_COUNT)
+    }
+
+    fn select_next(&mut self) {
+        self.selected_index = (self.selected_index + 1) % self.items.len();
+    }
+
+    fn select_previous(&mut self) {
+        self.selected_index = match self.selected_index {
+            0 => self.items.len() - 1,
+            it => it - 1,
+        };
+    }
+}
+
+/// Handle the keys that control the popup, if it is open. Returns [None] if the
+/// `input_event` should be handled as usual.
+pub fn try_handle_input_event(
+    editor_buffer: &mut EditorBuffer,
+    editor_engine: &mut EditorEngine,
+    input_event: &InputEvent,
+) -> Option<EditorEngineApplyEventResult> {
+    if !editor_engine.completion_popup.is_open() {
+        return None;
+    }
+
+    let InputEvent::Keyboard(KeyPress::Plain {
+        key: Key::SpecialKey(special_key),
+    }) = *input_event
+    else {
+        return None;
+    };
+
+    match special_key {
+        SpecialKey::Down => editor_engine.completion_popup.select_next(),
+        SpecialKey::Up => editor_engine.completion_popup.select_previous(),
+        SpecialKey::Esc => editor_engine.completion_popup.close(),
+        SpecialKey::Tab | SpecialKey::Enter => {
+            accept_selected_item(editor_buffer, editor_engine)
+        }
+        _ => return None,
+    }
+
+    Some(EditorEngineApplyEventResult::Applied)
+}
+
+/// Update the popup after the `editor_event` has been applied. Typing (or deleting)
+/// characters asks the [CompletionProvider] for new items, and everything else closes
+/// the popup (except [EditorEvent::ShowSpellingSuggestions], which opens it).
+pub fn update_after_editor_event(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &mut EditorEngine,
+    editor_event: &EditorEvent,
+) {
+    if let EditorEvent::ShowSpellingSuggestions = editor_event {
+        return;
+    }
+
+    let is_open = editor_engine.completion_popup.is_open();
+    let should_request_completions = match editor_event {
+        EditorEvent::InsertChar(_) => true,
+        EditorEvent::Backspace | EditorEvent::BackspaceWord => is_open,
+        _ => false,
+    };
+
+    let maybe_provider = editor_engine.maybe_completion_provider.clone();
+    match maybe_provider {
+        Some(provider) if should_request_completions => {
+            let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+            editor_engine.completion_popup = CompletionPopup {
+                items: provider.completions(editor_buffer, caret),
+                selected_index: 0,
+            };
+        }
+        _ => editor_engine.completion_popup.close(),
+    }
+}
+
+/// Open the popup w/ the [crate::SpellChecker::suggestions] for the misspelled word at
+/// the caret. Accepting one of them replaces the word. Nothing happens if there's no
+/// spell checker, or the word at the caret isn't misspelled.
+pub fn show_spelling_suggestions(
+    editor_buffer: &mut EditorBuffer,
+    editor_engine: &mut EditorEngine,
+) {
+    // The buffer might have changed since it was last rendered.
+    editor_buffer.sync_spell_check(editor_engine.maybe_spell_checker.as_ref());
+    let Some(spell_checker) = editor_engine.maybe_spell_checker.as_ref() else {
+        return;
+    };
+
+    let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+    let Some(misspelling) = editor_buffer.get_spell_check().get_misspelling_at(caret)
+    else {
+        return;
+    };
+
+    let items = spell_checker
+        .suggestions(&misspelling.word)
+        .into_iter()
+        .map(|suggestion| CompletionItem {
+            label: suggestion.clone(),
+            insert_text: suggestion,
+            replace_range: misspelling.range,
+        })
+        .collect();
+    editor_engine.completion_popup = CompletionPopup {
+        items,
+        selected_index: 0,
+    };
+}
+
+/// Replace the [CompletionItem::replace_range] of the selected item w/ its
+/// [CompletionItem::insert_text], and close the popup.
+fn accept_selected_item(
+    editor_buffer: &mut EditorBuffer,
+    editor_engine: &mut EditorEngine,
+) {
+    let CompletionPopup {
+        mut items,
+        selected_index,
+    } = mem::take(&mut editor_engine.completion_popup);
+    if selected_index >= items.len() {
+        return;
+    }
+    let CompletionItem {
+        insert_text,
+        replace_range,
+        ..
+    } = items.swap_remove(selected_index);
+
+    let row_index = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+    let Some(line) = editor_buffer.get_lines().get(ch!(@to_usize row_index)) else {
+        return;
+    };
+
+    // Remove the text that is replaced.
+    let start_col_index =
+        cmp::min(replace_range.start_display_col_index, line.display_width);
+    let end_col_index = cmp::min(replace_range.end_display_col_index, line.display_width);
+    let new_line = format!(
+        "{}{}",
+        line.clip_to_width(ch!(0), start_col_index),
+        line.clip_to_width(end_col_index, line.display_width - end_col_index)
+    );
+    validate_editor_buffer_change::apply_change(
+        editor_buffer,
+        editor_engine,
+        |lines, _, _| {
+            lines[ch!(@to_usize row_index)] = new_line.into();
+        },
+    );
+
+    let caret_adj = position!(col_index: start_col_index, row_index: row_index);
+    EditorEngineInternalApi::move_caret_to_buffer_position(
+        editor_buffer,
+        editor_engine,
+        caret_adj,
+    );
+    EditorEngineInternalApi::insert_str_at_caret(
+        EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        },
+        &insert_text,
+    );
+
+    editor_buffer.set_dirty(true);
+    history::push(editor_buffer);
+}
+
+/// Paint the popup below the caret (or above it, if there isn't enough room below). The
+/// popup can extend past the editor's box, so these ops are meant to be painted on top
+/// of everything else.
+pub fn render_completion_popup(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    window_size: Size,
+) -> Option<RenderOps> {
+    let popup = &editor_engine.completion_popup;
+    if !popup.is_open() {
+        return None;
+    }
+
+    let scroll_offset = popup.get_scroll_offset();
+    let visible_items = popup
+        .items
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(COMPLETION_POPUP_MAX_ROW_COUNT);
+
+    // 1 col of padding on each side of the label.
+    let max_label_width = visible_items
+        .clone()
+        .map(|(_, it)| UnicodeString::str_display_width(&it.label))
+        .max()
+        .unwrap_or(0);
+    let popup_width = cmp::min(ch!(max_label_width + 2), window_size.col_count);
+    let popup_height = ch!(visible_items.clone().count());
+    let label_width = popup_width - ch!(2);
+
+    // Place the popup at the caret.
+    let caret_pos = editor_engine.current_box.style_adjusted_origin_pos
+        + editor_buffer.get_caret(CaretKind::Raw);
+    let row_index = if caret_pos.row_index + ch!(1) + popup_height > window_size.row_count
+        && caret_pos.row_index >= popup_height
+    {
+        caret_pos.row_index - popup_height
+    } else {
+        caret_pos.row_index + ch!(1)
+    };
+    let col_index = cmp::min(caret_pos.col_index, window_size.col_count - popup_width);
+
+    let mut render_ops = render_ops!();
+    for (offset, (item_index, item)) in visible_items.enumerate() {
+        let style = if item_index == popup.selected_index {
+            get_completion_popup_selected_style()
+        } else {
+            get_completion_popup_style()
+        };
+
+        let label = UnicodeString::from(item.label.as_str());
+        let clipped_label = label.clip_to_width(ch!(0), label_width);
+        let padding_width =
+            label_width - ch!(UnicodeString::str_display_width(clipped_label));
+        let text = format!(
+            " {clipped_label}{} ",
+            " ".repeat(ch!(@to_usize padding_width))
+        );
+
+        let pos = {
+            let row_index = row_index + ch!(offset);
+            position!(col_index: col_index, row_index: row_index)
+        };
+        render_ops.push(RenderOp::MoveCursorPositionAbs(pos));
+        render_ops.push(RenderOp::ApplyColors(Some(style)));
+        render_ops.push(RenderOp::PaintTextWithAttributes(text, Some(style)));
+        render_ops.push(RenderOp::ResetColor);
+    }
+
+    Some(render_ops)
+}
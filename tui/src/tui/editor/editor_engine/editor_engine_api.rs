@@ -15,56 +15,91 @@
  *   limitations under the License.
  */
 
+use std::{cmp, time::Instant};
+
 use crossterm::style::Stylize;
 use r3bl_core::{call_if_true,
                 ch,
                 position,
+                size,
                 throws,
                 throws_with_return,
+                timed,
                 ANSIBasicColor,
                 ChUnit,
                 CommonResult,
+                Position,
                 PrettyPrintDebug,
                 ScrollOffsetColLocationInRange,
                 SelectionRange,
                 Size,
                 TuiColor,
-                TuiStyledTexts,
+                TuiStyle,
                 UnicodeString,
                 UnicodeStringSegmentSliceResult};
 use r3bl_macro::tui_style;
-use syntect::easy::HighlightLines;
 
 use crate::{cache,
-            convert_syntect_to_styled_text,
+            completion_popup,
             editor_buffer_clipboard_support::ClipboardService,
+            get_added_line_marker_style,
+            get_bookmark_style,
+            get_line_number_gutter_style,
+            get_matching_bracket_style,
+            get_misspelled_word_style,
+            get_modified_line_marker_style,
+            get_removed_line_marker_style,
+            get_search_match_style,
+            get_secondary_caret_style,
             get_selection_style,
+            get_whitespace_marker_style,
             history,
+            parse_fragment_starts_with_left_image_err_on_new_line,
             render_ops,
             render_pipeline,
-            render_tui_styled_texts_into,
-            try_get_syntax_ref,
+            render_style_us_span_line_into,
+            timed_if_enabled,
             try_parse_and_highlight,
+            Bookmark,
+            BracketMatchingMode,
             CaretKind,
+            CaretStyle,
+            ChangeMarkersMode,
             EditMode,
+            EditorArgsMut,
             EditorBuffer,
             EditorEngine,
             EditorEngineInternalApi,
             EditorEvent,
             FlexBox,
             HasFocus,
+            ImageBounds,
+            InlineImageMode,
             InputEvent,
-            Key,
-            KeyPress,
+            KeymapMode,
+            LineChange,
+            LineChangeKind,
+            LineNumberGutterMode,
             List,
+            Misspelling,
+            PartialFlexBox,
             RenderArgs,
             RenderOp,
             RenderOps,
             RenderPipeline,
-            SpecialKey,
+            SearchMatch,
             StyleUSSpan,
+            StyleUSSpanLine,
             SyntaxHighlightMode,
+            TabMode,
+            TelemetryKind,
+            TelemetryMode,
+            TrimTrailingWhitespaceMode,
+            VimKeymap,
+            WhitespaceRenderMode,
             ZOrder,
+            BOOKMARK_INDICATOR,
+            CARET_BAR_CHAR,
             DEBUG_TUI_COPY_PASTE,
             DEBUG_TUI_MOD,
             DEBUG_TUI_SYN_HI,
@@ -82,82 +117,195 @@ impl EditorEngineApi {
         input_event: InputEvent,
         clipboard_service_provider: &mut impl ClipboardService,
     ) -> CommonResult<EditorEngineApplyEventResult> {
-        let editor_config = &editor_engine.config_options;
+        let TelemetryMode::Enable = editor_engine.config_options.telemetry else {
+            return EditorEngineApi::try_apply_event(
+                editor_buffer,
+                editor_engine,
+                input_event,
+                clipboard_service_provider,
+            );
+        };
+        let (result, duration) = timed!({
+            EditorEngineApi::try_apply_event(
+                editor_buffer,
+                editor_engine,
+                input_event,
+                clipboard_service_provider,
+            )
+        });
+        editor_engine
+            .telemetry
+            .record(TelemetryKind::ApplyEvent, duration);
+        result
+    }
 
-        if let EditMode::ReadOnly = editor_config.edit_mode {
-            if !input_event.matches_any_of_these_keypresses(&[
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Up),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Down),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Left),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Right),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Home),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::End),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::PageUp),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::PageDown),
-                },
-            ]) {
-                return Ok(EditorEngineApplyEventResult::NotApplied);
-            }
+    fn try_apply_event(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        input_event: InputEvent,
+        clipboard_service_provider: &mut impl ClipboardService,
+    ) -> CommonResult<EditorEngineApplyEventResult> {
+        // Keep the caret visible while the user is typing (or moving it).
+        editor_engine.reset_caret_blink();
+
+        // The lines might have been set w/out the engine (eg: by loading a file), so the
+        // tabs in them have to be laid out before any of the caret math can work.
+        editor_buffer.sync_tab_width(editor_engine.get_tab_width(editor_buffer));
+
+        // The completion popup (if it is open) gets the first look at the event, since
+        // it uses keys that mean something else to the editor (eg: Up, Down, Tab).
+        if let Some(result) = completion_popup::try_handle_input_event(
+            editor_buffer,
+            editor_engine,
+            &input_event,
+        ) {
+            return Ok(result);
         }
 
-        if let Ok(editor_event) = EditorEvent::try_from(input_event) {
-            if editor_buffer.history.is_empty() {
-                history::push(editor_buffer);
+        // The modal layer gets the first look at the event, and hands back the ones that
+        // the default key bindings should handle (eg: typing in insert mode).
+        if let KeymapMode::Vim = editor_engine.config_options.keymap_mode {
+            if let Some(result) = VimKeymap::apply_event(
+                editor_buffer,
+                editor_engine,
+                &input_event,
+                clipboard_service_provider,
+            )? {
+                editor_engine.completion_popup.close();
+                return Ok(result);
             }
+        }
 
-            EditorEvent::apply_editor_event(
-                editor_engine,
+        if let Ok(editor_event) = editor_engine
+            .config_options
+            .key_binding_table
+            .try_convert(input_event)
+        {
+            EditorEngineApi::apply_editor_event(
                 editor_buffer,
-                editor_event.clone(),
+                editor_engine,
+                editor_event,
                 clipboard_service_provider,
-            );
+            )
+        } else {
+            Ok(EditorEngineApplyEventResult::NotApplied)
+        }
+    }
 
-            match editor_event {
-                EditorEvent::InsertChar(_) => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::InsertString(_) => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::InsertNewLine => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::Delete => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::Backspace => {
-                    history::push(editor_buffer);
-                }
-                EditorEvent::Copy => {
-                    history::push(editor_buffer);
+    /// Execute the `editor_event` the same way as [Self::apply_event] does after the
+    /// [InputEvent] has been converted, ie: it is checked against the
+    /// [EditMode], saved in history, and recorded in the [crate::EditorMacro].
+    pub fn apply_editor_event(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        editor_event: EditorEvent,
+        clipboard_service_provider: &mut impl ClipboardService,
+    ) -> CommonResult<EditorEngineApplyEventResult> {
+        // Each of the recorded events is applied (and saved in history) as if it had
+        // just been typed.
+        if let EditorEvent::ReplayMacro(count) = editor_event {
+            let recorded_events =
+                editor_engine.editor_macro.get_recorded_events().to_vec();
+            for _ in 0..count {
+                for recorded_event in &recorded_events {
+                    EditorEngineApi::apply_editor_event(
+                        editor_buffer,
+                        editor_engine,
+                        recorded_event.clone(),
+                        clipboard_service_provider,
+                    )?;
                 }
-                EditorEvent::Paste => {
+            }
+            return Ok(EditorEngineApplyEventResult::Applied);
+        }
+
+        // Navigation, selection & copy are allowed, but the content can't change, and
+        // neither can the file that it was loaded from.
+        if matches!(editor_engine.config_options.edit_mode, EditMode::ReadOnly)
+            && (editor_event.is_mutating() || matches!(editor_event, EditorEvent::Save))
+        {
+            return Ok(EditorEngineApplyEventResult::NotApplied);
+        }
+
+        if !matches!(
+            editor_event,
+            EditorEvent::StartMacroRecording | EditorEvent::StopMacroRecording
+        ) {
+            editor_engine.editor_macro.record(&editor_event);
+        }
+
+        // Saving needs a file path, if there isn't one then let the app handle it
+        // (eg: by asking the user for one).
+        if let EditorEvent::Save = editor_event {
+            if editor_buffer.get_maybe_file_path().is_none() {
+                return Ok(EditorEngineApplyEventResult::NotApplied);
+            }
+            if let TrimTrailingWhitespaceMode::OnSave =
+                editor_engine.config_options.trim_trailing_whitespace
+            {
+                if editor_buffer.history.is_empty() {
                     history::push(editor_buffer);
                 }
-                EditorEvent::Cut => {
+                let content_version = editor_buffer.get_content_version();
+                EditorEvent::apply_editor_event(
+                    editor_engine,
+                    editor_buffer,
+                    EditorEvent::TrimTrailingWhitespace,
+                    clipboard_service_provider,
+                );
+                if editor_buffer.get_content_version() != content_version {
                     history::push(editor_buffer);
                 }
-                _ => {}
             }
-            Ok(EditorEngineApplyEventResult::Applied)
-        } else {
-            Ok(EditorEngineApplyEventResult::NotApplied)
+            editor_buffer.save()?;
+            return Ok(EditorEngineApplyEventResult::Applied);
         }
+
+        // Tab & Shift+Tab move between the tab stops of a snippet that is being
+        // filled in. This doesn't change the content, so it isn't saved in history.
+        if editor_buffer.is_snippet_active()
+            && matches!(editor_event, EditorEvent::InsertTab | EditorEvent::Outdent)
+        {
+            EditorEvent::apply_editor_event(
+                editor_engine,
+                editor_buffer,
+                editor_event,
+                clipboard_service_provider,
+            );
+            return Ok(EditorEngineApplyEventResult::Applied);
+        }
+
+        // Let the app handle the tab key.
+        if let (TabMode::PassThrough, EditorEvent::InsertTab | EditorEvent::Outdent) =
+            (editor_engine.config_options.tab_mode, &editor_event)
+        {
+            return Ok(EditorEngineApplyEventResult::NotApplied);
+        }
+
+        if editor_buffer.history.is_empty() {
+            history::push(editor_buffer);
+        }
+
+        EditorEvent::apply_editor_event(
+            editor_engine,
+            editor_buffer,
+            editor_event.clone(),
+            clipboard_service_provider,
+        );
+
+        completion_popup::update_after_editor_event(
+            editor_buffer,
+            editor_engine,
+            &editor_event,
+        );
+
+        // Undo & redo move through the history, so they aren't saved in it.
+        if editor_event.is_mutating()
+            && !matches!(editor_event, EditorEvent::Undo | EditorEvent::Redo)
+        {
+            history::push(editor_buffer);
+        }
+        Ok(EditorEngineApplyEventResult::Applied)
     }
 
     pub fn render_engine(
@@ -166,9 +314,76 @@ impl EditorEngineApi {
         current_box: FlexBox,
         has_focus: &mut HasFocus,
         window_size: Size,
+    ) -> CommonResult<RenderPipeline> {
+        let TelemetryMode::Enable = editor_engine.config_options.telemetry else {
+            return EditorEngineApi::try_render_engine(
+                editor_engine,
+                editor_buffer,
+                current_box,
+                has_focus,
+                window_size,
+            );
+        };
+        let (result, duration) = timed!({
+            EditorEngineApi::try_render_engine(
+                editor_engine,
+                editor_buffer,
+                current_box,
+                has_focus,
+                window_size,
+            )
+        });
+        editor_engine.telemetry.end_frame(duration);
+        result
+    }
+
+    fn try_render_engine(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+        current_box: FlexBox,
+        has_focus: &mut HasFocus,
+        window_size: Size,
     ) -> CommonResult<RenderPipeline> {
         throws_with_return!({
-            editor_engine.current_box = current_box.into();
+            // Make room for the line number gutter (if enabled) by shrinking the box that
+            // the content is painted into. This keeps all the caret & scroll math the
+            // same, since it is relative to the box's origin and viewport size.
+            let gutter_width =
+                editor_engine.line_number_gutter_width(editor_buffer.get_lines().len());
+            let content_box =
+                line_number_gutter::exclude_gutter_from_box(current_box, gutter_width);
+            let content_box_size_changed =
+                editor_engine.current_box.style_adjusted_bounds_size
+                    != content_box.style_adjusted_bounds_size;
+            editor_engine.current_box = content_box;
+
+            // The lines might have been set w/out the engine (eg: by loading a file), so
+            // the tabs in them have to be laid out before any of the caret & scroll math
+            // can work.
+            editor_buffer.sync_tab_width(editor_engine.get_tab_width(editor_buffer));
+
+            // The viewport might have just shrunk (or grown) to make room for the gutter.
+            if gutter_width > ch!(0) && content_box_size_changed {
+                EditorEngineInternalApi::validate_scroll(EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                });
+            }
+
+            // The app asked to go to a line (eg: from a "go to line" dialog), which can
+            // only be done now that the viewport size is known.
+            if let Some(line_number) = editor_buffer.take_pending_go_to_line() {
+                EditorEngineInternalApi::go_to_row(
+                    editor_buffer,
+                    editor_engine,
+                    ch!(line_number) - ch!(1),
+                );
+            }
+
+            editor_buffer.sync_search_matches();
+            editor_buffer.sync_diff();
+            editor_buffer.sync_spell_check(editor_engine.maybe_spell_checker.as_ref());
+            editor_buffer.sync_bookmarks();
 
             if editor_buffer.is_empty() {
                 EditorEngineApi::render_empty_state(RenderArgs {
@@ -187,6 +402,30 @@ impl EditorEngineApi {
                     &mut render_ops,
                 );
 
+                EditorEngineApi::render_whitespace(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_misspellings(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_search_matches(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
                 EditorEngineApi::render_selection(
                     RenderArgs {
                         editor_buffer,
@@ -195,6 +434,22 @@ impl EditorEngineApi {
                     },
                     &mut render_ops,
                 );
+                EditorEngineApi::render_matching_bracket(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
+                EditorEngineApi::render_secondary_carets(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
                 EditorEngineApi::render_caret(
                     RenderArgs {
                         editor_buffer,
@@ -203,9 +458,43 @@ impl EditorEngineApi {
                     },
                     &mut render_ops,
                 );
+                EditorEngineApi::render_inline_images(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
+
+                // The gutter is not part of the cached content, since it depends on the
+                // caret's row (when relative line numbers are used).
+                if gutter_width > ch!(0) {
+                    EditorEngineApi::render_line_number_gutter(
+                        RenderArgs {
+                            editor_buffer,
+                            editor_engine,
+                            has_focus,
+                        },
+                        current_box.style_adjusted_origin_pos,
+                        gutter_width,
+                        &mut render_ops,
+                    );
+                }
 
                 let mut render_pipeline = render_pipeline!();
                 render_pipeline.push(ZOrder::Normal, render_ops);
+
+                // The popup can extend past the editor's box, so it is painted on top of
+                // everything else.
+                if let Some(popup_render_ops) = completion_popup::render_completion_popup(
+                    editor_buffer,
+                    editor_engine,
+                    window_size,
+                ) {
+                    render_pipeline.push(ZOrder::Glass, popup_render_ops);
+                }
+
                 render_pipeline
             }
         })
@@ -287,6 +576,280 @@ impl EditorEngineApi {
         };
     }
 
+    /// Paint a (dimmed) glyph in place of each invisible character in the viewport, see
+    /// [WhitespaceRenderMode]. This has to be done before the search matches & the
+    /// selection are painted, so that they are painted on top.
+    fn render_whitespace(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        if let WhitespaceRenderMode::Disable =
+            editor_engine.config_options.whitespace_render
+        {
+            return;
+        }
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+        let style = get_whitespace_marker_style();
+
+        let visible_lines = editor_buffer
+            .get_lines()
+            .iter()
+            .enumerate()
+            .skip(ch!(@to_usize scroll_offset.row_index))
+            .take(ch!(@to_usize viewport_height));
+
+        for (row_index, line) in visible_lines {
+            // The byte offset at which the trailing whitespace starts.
+            let trailing_whitespace_start = line.string.trim_end().len();
+
+            for segment in &line.vec_segment {
+                let is_trailing = segment.byte_offset >= trailing_whitespace_start;
+                let glyph = match segment.string.as_str() {
+                    " " if is_trailing => "·",
+                    "\u{a0}" => "␣",
+                    "\t" => "→",
+                    _ => continue,
+                };
+
+                let col_index = segment.display_col_offset;
+                if col_index < scroll_offset.col_index
+                    || col_index >= scroll_offset.col_index + viewport_width
+                {
+                    continue;
+                }
+
+                let raw_pos = {
+                    let col_index = col_index - scroll_offset.col_index;
+                    let row_index = ch!(row_index) - scroll_offset.row_index;
+                    position!(col_index: col_index, row_index: row_index)
+                };
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    editor_engine.current_box.style_adjusted_origin_pos,
+                    raw_pos,
+                ));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    glyph.to_string(),
+                    Some(style),
+                ));
+                render_ops.push(RenderOp::ResetColor);
+            }
+        }
+    }
+
+    /// Paint the images of the lines that only hold an image (see [InlineImageMode]), that
+    /// are in the viewport. Each image is painted over its line & the blank lines that
+    /// follow it.
+    fn render_inline_images(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        if editor_engine.config_options.inline_images == InlineImageMode::Disable
+            || !editor_buffer.is_file_extension_default()
+        {
+            return;
+        }
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+        let lines = editor_buffer.get_lines();
+        let start_row_index = ch!(@to_usize scroll_offset.row_index);
+        let end_row_index = cmp::min(
+            lines.len(),
+            start_row_index + ch!(@to_usize viewport_height),
+        );
+
+        for row_index in start_row_index..end_row_index {
+            let Ok((rem, hyperlink_data)) =
+                parse_fragment_starts_with_left_image_err_on_new_line(
+                    lines[row_index].string.trim(),
+                )
+            else {
+                continue;
+            };
+            if !rem.is_empty()
+                || hyperlink_data.url.starts_with("http://")
+                || hyperlink_data.url.starts_with("https://")
+            {
+                continue;
+            }
+
+            // The path is relative to the file of the buffer (if there is one).
+            let path = match editor_buffer
+                .get_maybe_file_path()
+                .and_then(|it| std::path::Path::new(it).parent())
+            {
+                Some(parent) => parent.join(hyperlink_data.url),
+                None => std::path::PathBuf::from(hyperlink_data.url),
+            };
+            let Ok(data) = std::fs::read(path) else {
+                continue;
+            };
+
+            let blank_line_count = lines[row_index + 1..end_row_index]
+                .iter()
+                .take_while(|it| it.string.trim().is_empty())
+                .count();
+
+            render_ops.push(RenderOp::PaintImage {
+                data,
+                bounds: ImageBounds {
+                    origin_pos: editor_engine.current_box.style_adjusted_origin_pos
+                        + position!(
+                            col_index: 0,
+                            row_index: ch!(row_index) - scroll_offset.row_index
+                        ),
+                    bounds_size: size!(
+                        col_count: viewport_width,
+                        row_count: ch!(1 + blank_line_count)
+                    ),
+                },
+            });
+        }
+    }
+
+    /// Paint the words that the [crate::SpellChecker] flagged as misspelled, that are in
+    /// the viewport. This has to be done before the search matches & the selection are
+    /// painted, so that they are painted on top.
+    fn render_misspellings(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+
+        // The misspellings are sorted by row.
+        let misspellings = editor_buffer.get_spell_check().get_misspellings();
+        let first_index =
+            misspellings.partition_point(|it| it.row_index < scroll_offset.row_index);
+        let last_index = misspellings.partition_point(|it| {
+            it.row_index < scroll_offset.row_index + viewport_height
+        });
+
+        for Misspelling {
+            row_index, range, ..
+        } in &misspellings[first_index..last_index]
+        {
+            // Clip the word to the cols that are in the viewport.
+            let start_col_index =
+                cmp::max(range.start_display_col_index, scroll_offset.col_index);
+            let end_col_index = cmp::min(
+                range.end_display_col_index,
+                scroll_offset.col_index + viewport_width,
+            );
+            if start_col_index >= end_col_index {
+                continue;
+            }
+
+            let Some(line) = editor_buffer.get_lines().get(ch!(@to_usize *row_index))
+            else {
+                continue;
+            };
+            let text =
+                line.clip_to_range(SelectionRange::new(start_col_index, end_col_index));
+            if text.is_empty() {
+                continue;
+            }
+
+            let raw_pos = {
+                let col_index = start_col_index - scroll_offset.col_index;
+                let row_index = *row_index - scroll_offset.row_index;
+                position!(col_index: col_index, row_index: row_index)
+            };
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                raw_pos,
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                EditorEngineInternalApi::expand_tabs_from_col(
+                    text,
+                    start_col_index,
+                    editor_engine.get_tab_width(editor_buffer),
+                ),
+                Some(get_misspelled_word_style()),
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    /// Paint the matches of the active search (see [EditorBuffer::set_search_query])
+    /// that are in the viewport. This has to be done before the selection is painted, so
+    /// that the selection is painted on top.
+    fn render_search_matches(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+
+        // The matches are sorted by row.
+        let matches = editor_buffer.get_search().get_matches();
+        let first_index =
+            matches.partition_point(|it| it.row_index < scroll_offset.row_index);
+        let last_index = matches.partition_point(|it| {
+            it.row_index < scroll_offset.row_index + viewport_height
+        });
+
+        for SearchMatch { row_index, range } in &matches[first_index..last_index] {
+            // Clip the match to the cols that are in the viewport.
+            let start_col_index =
+                cmp::max(range.start_display_col_index, scroll_offset.col_index);
+            let end_col_index = cmp::min(
+                range.end_display_col_index,
+                scroll_offset.col_index + viewport_width,
+            );
+            if start_col_index >= end_col_index {
+                continue;
+            }
+
+            let Some(line) = editor_buffer.get_lines().get(ch!(@to_usize *row_index))
+            else {
+                continue;
+            };
+            let text =
+                line.clip_to_range(SelectionRange::new(start_col_index, end_col_index));
+            if text.is_empty() {
+                continue;
+            }
+
+            let raw_pos = {
+                let col_index = start_col_index - scroll_offset.col_index;
+                let row_index = *row_index - scroll_offset.row_index;
+                position!(col_index: col_index, row_index: row_index)
+            };
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                raw_pos,
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                EditorEngineInternalApi::expand_tabs_from_col(
+                    text,
+                    start_col_index,
+                    editor_engine.get_tab_width(editor_buffer),
+                ),
+                Some(get_search_match_style()),
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
     // BOOKM: Render selection
     fn render_selection(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
         let RenderArgs {
@@ -364,8 +927,16 @@ impl EditorEngineApi {
 
                 render_ops.push(RenderOp::ApplyColors(Some(get_selection_style())));
 
+                let selection_start_col_index = cmp::max(
+                    range_of_display_col_indices.start_display_col_index,
+                    scroll_offset.col_index,
+                );
                 render_ops.push(RenderOp::PaintTextWithAttributes(
-                    selection.to_string(),
+                    EditorEngineInternalApi::expand_tabs_from_col(
+                        selection,
+                        selection_start_col_index,
+                        editor_engine.get_tab_width(editor_buffer),
+                    ),
                     None,
                 ));
 
@@ -374,37 +945,360 @@ impl EditorEngineApi {
         }
     }
 
-    fn render_caret(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+    /// Paint the bracket at the caret and the one that matches it. This has to be done
+    /// before the caret is painted, so that the caret is painted on top.
+    fn render_matching_bracket(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
         let RenderArgs {
             editor_buffer,
             editor_engine,
             has_focus,
         } = render_args;
 
-        if has_focus.does_id_have_focus(editor_engine.current_box.id) {
-            let str_at_caret: String = if let Some(UnicodeStringSegmentSliceResult {
-                unicode_string_seg: str_seg,
-                ..
-            }) =
-                EditorEngineInternalApi::string_at_caret(editor_buffer, editor_engine)
+        if let BracketMatchingMode::Disable =
+            editor_engine.config_options.bracket_matching
+        {
+            return;
+        }
+
+        if !has_focus.does_id_have_focus(editor_engine.current_box.id) {
+            return;
+        }
+
+        let Some((bracket_pos, matching_bracket_pos)) =
+            EditorEngineInternalApi::find_visible_matching_bracket(
+                editor_buffer,
+                editor_engine,
+            )
+        else {
+            return;
+        };
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+
+        for pos in [bracket_pos, matching_bracket_pos] {
+            // Skip the bracket if it is scrolled out of view horizontally.
+            if pos.col_index < scroll_offset.col_index
+                || pos.col_index - scroll_offset.col_index >= viewport_width
             {
-                str_seg.string
-            } else {
-                DEFAULT_CURSOR_CHAR.into()
+                continue;
+            }
+
+            let Some(line) = editor_buffer.get_lines().get(ch!(@to_usize pos.row_index))
+            else {
+                continue;
+            };
+            let Some(UnicodeStringSegmentSliceResult {
+                unicode_string_seg: bracket,
+                ..
+            }) = line.get_string_at_display_col_index(pos.col_index)
+            else {
+                continue;
             };
 
+            let raw_pos = position!(
+                col_index: pos.col_index - scroll_offset.col_index,
+                row_index: pos.row_index - scroll_offset.row_index
+            );
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
                 editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
+                raw_pos,
             ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                bracket.string,
+                Some(get_matching_bracket_style()),
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    /// Paint the selection & the caret for each [crate::SecondaryCaret] that is in the
+    /// viewport. They are painted w/ a different style than the primary caret.
+    fn render_secondary_carets(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            has_focus,
+        } = render_args;
+
+        if !has_focus.does_id_have_focus(editor_engine.current_box.id) {
+            return;
+        }
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+        let origin_pos = editor_engine.current_box.style_adjusted_origin_pos;
+
+        for secondary_caret in editor_buffer.get_secondary_carets() {
+            let pos = secondary_caret.position;
+
+            // Skip the caret if it is scrolled out of view.
+            if pos.row_index < scroll_offset.row_index
+                || pos.row_index - scroll_offset.row_index >= viewport_height
+                || pos.col_index < scroll_offset.col_index
+                || pos.col_index - scroll_offset.col_index >= viewport_width
+            {
+                continue;
+            }
+
+            let Some(line) = editor_buffer.get_lines().get(ch!(@to_usize pos.row_index))
+            else {
+                continue;
+            };
+            let raw_row_index = pos.row_index - scroll_offset.row_index;
+
+            if let Some(range) = secondary_caret.maybe_selection {
+                let start_col_index =
+                    cmp::max(range.start_display_col_index, scroll_offset.col_index);
+                let selection = line.clip_to_range(SelectionRange {
+                    start_display_col_index: start_col_index,
+                    ..range
+                });
+                if !selection.is_empty() {
+                    let raw_pos = position!(
+                        col_index: start_col_index - scroll_offset.col_index,
+                        row_index: raw_row_index
+                    );
+                    render_ops
+                        .push(RenderOp::MoveCursorPositionRelTo(origin_pos, raw_pos));
+                    render_ops.push(RenderOp::PaintTextWithAttributes(
+                        EditorEngineInternalApi::expand_tabs_from_col(
+                            selection,
+                            start_col_index,
+                            editor_engine.get_tab_width(editor_buffer),
+                        ),
+                        Some(get_selection_style()),
+                    ));
+                    render_ops.push(RenderOp::ResetColor);
+                }
+            }
+
+            let str_at_caret: String =
+                match line.get_string_at_display_col_index(pos.col_index) {
+                    // Only the first col of a `\t` is painted.
+                    Some(UnicodeStringSegmentSliceResult {
+                        unicode_string_seg: str_seg,
+                        ..
+                    }) if str_seg.string == "\t" => " ".into(),
+                    Some(UnicodeStringSegmentSliceResult {
+                        unicode_string_seg: str_seg,
+                        ..
+                    }) => str_seg.string,
+                    None => DEFAULT_CURSOR_CHAR.into(),
+                };
+            let raw_pos = position!(
+                col_index: pos.col_index - scroll_offset.col_index,
+                row_index: raw_row_index
+            );
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(origin_pos, raw_pos));
             render_ops.push(RenderOp::PaintTextWithAttributes(
                 str_at_caret,
-                tui_style! { attrib: [reverse] }.into(),
+                Some(get_secondary_caret_style()),
             ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    fn render_caret(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            has_focus,
+        } = render_args;
+
+        if !has_focus.does_id_have_focus(editor_engine.current_box.id) {
+            return;
+        }
+
+        // The terminal paints (& blinks) its own cursor.
+        if let CaretStyle::TerminalCursor = editor_engine.config_options.caret_style {
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
                 editor_engine.current_box.style_adjusted_origin_pos,
                 editor_buffer.get_caret(CaretKind::Raw),
             ));
+            render_ops.push(RenderOp::ShowCursor(editor_engine.get_cursor_shape()));
+            return;
+        }
+
+        if !editor_engine.is_caret_visible(Instant::now()) {
+            return;
+        }
+
+        let maybe_str_at_caret =
+            EditorEngineInternalApi::string_at_caret(editor_buffer, editor_engine).map(
+                |UnicodeStringSegmentSliceResult {
+                     unicode_string_seg: str_seg,
+                     ..
+                 }| match str_seg.string.as_str() {
+                    // Only the first col of a `\t` is painted.
+                    "\t" => " ".into(),
+                    _ => str_seg.string,
+                },
+            );
+
+        let (str_at_caret, style) =
+            match (editor_engine.config_options.caret_style, maybe_str_at_caret) {
+                (CaretStyle::Block, maybe_str_at_caret) => (
+                    maybe_str_at_caret.unwrap_or_else(|| DEFAULT_CURSOR_CHAR.into()),
+                    tui_style! { attrib: [reverse] },
+                ),
+                (CaretStyle::Bar, Some(str_at_caret))
+                    if !str_at_caret.trim().is_empty() =>
+                {
+                    (str_at_caret, tui_style! { attrib: [underline] })
+                }
+                (CaretStyle::Bar, _) => (CARET_BAR_CHAR.into(), TuiStyle::default()),
+                // The terminal cursor is shown above, so this is unreachable for it.
+                (
+                    CaretStyle::Underline | CaretStyle::TerminalCursor,
+                    maybe_str_at_caret,
+                ) => (
+                    maybe_str_at_caret.unwrap_or_else(|| " ".into()),
+                    tui_style! { attrib: [underline] },
+                ),
+            };
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            editor_buffer.get_caret(CaretKind::Raw),
+        ));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            str_at_caret,
+            style.into(),
+        ));
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            editor_engine.current_box.style_adjusted_origin_pos,
+            editor_buffer.get_caret(CaretKind::Raw),
+        ));
+        render_ops.push(RenderOp::ResetColor);
+    }
+
+    /// Paint the line numbers for all the visible lines in the gutter, which is located
+    /// at `gutter_origin_pos` (the origin of the box before it was shrunk to make room
+    /// for the gutter).
+    fn render_line_number_gutter(
+        render_args: RenderArgs<'_>,
+        gutter_origin_pos: Position,
+        gutter_width: ChUnit,
+        render_ops: &mut RenderOps,
+    ) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        let gutter_mode = editor_engine.config_options.line_number_gutter;
+        let style = editor_engine
+            .config_options
+            .maybe_line_number_gutter_style
+            .unwrap_or_else(get_line_number_gutter_style);
+        let max_display_row_count = editor_engine.viewport_height();
+        let scroll_offset_row_index = editor_buffer.get_scroll_offset().row_index;
+        let caret_row_index =
+            editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        let line_count = editor_buffer.len();
+        // Leave 1 col of padding between the line number and the content.
+        let line_number_width = ch!(@to_usize gutter_width, @dec);
+
+        for raw_row_index in 0..=ch!(@to_usize max_display_row_count) {
+            let row_index = scroll_offset_row_index + ch!(raw_row_index);
+            if row_index >= line_count {
+                break;
+            }
+
+            let line_number = line_number_gutter::line_number_to_display(
+                gutter_mode,
+                row_index,
+                caret_row_index,
+            );
+
+            // Bookmarked lines display the bookmark in place of their line number.
+            let (text, text_style) =
+                match editor_buffer.get_bookmarks().get_bookmark_at(row_index) {
+                    Some(Bookmark { maybe_name, .. }) => {
+                        let indicator = maybe_name.unwrap_or(BOOKMARK_INDICATOR);
+                        (
+                            format!("{indicator:>line_number_width$} "),
+                            get_bookmark_style(),
+                        )
+                    }
+                    None => (format!("{line_number:>line_number_width$} "), style),
+                };
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                gutter_origin_pos,
+                position! { col_index: 0, row_index: raw_row_index },
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(text_style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(text, Some(text_style)));
+            render_ops.push(RenderOp::ResetColor);
+
+            // Mark the line if it changed, in the padding col.
+            let maybe_line_change = match editor_engine.config_options.change_markers {
+                ChangeMarkersMode::Enable => {
+                    editor_buffer.get_diff().get_change_at(row_index)
+                }
+                ChangeMarkersMode::Disable => None,
+            };
+            if let Some(LineChange { kind, .. }) = maybe_line_change {
+                let (marker, marker_style) = match kind {
+                    LineChangeKind::Added => ("▎", get_added_line_marker_style()),
+                    LineChangeKind::Modified => ("▎", get_modified_line_marker_style()),
+                    LineChangeKind::Removed => ("▔", get_removed_line_marker_style()),
+                };
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    gutter_origin_pos,
+                    position! { col_index: line_number_width, row_index: raw_row_index },
+                ));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    marker.to_string(),
+                    Some(marker_style),
+                ));
+                render_ops.push(RenderOp::ResetColor);
+            }
+        }
+
+        // Mark the search matches that are scrolled out of view in the padding col. Like
+        // a scrollbar, the row of the marker is proportional to the row of the match in
+        // the buffer, and the marker points to where the match is.
+        let visible_row_count = cmp::min(
+            ch!(@to_usize max_display_row_count),
+            ch!(@to_usize line_count - scroll_offset_row_index),
+        );
+        if visible_row_count == 0 {
+            return;
+        }
+        let mut maybe_last_marker = None;
+        for SearchMatch { row_index, .. } in editor_buffer.get_search().get_matches() {
+            let row_index = *row_index;
+            let marker = if row_index < scroll_offset_row_index {
+                '▲'
+            } else if row_index >= scroll_offset_row_index + ch!(visible_row_count) {
+                '▼'
+            } else {
+                continue;
+            };
+            let raw_row_index =
+                ch!(@to_usize row_index) * visible_row_count / ch!(@to_usize line_count);
+            if maybe_last_marker == Some((raw_row_index, marker)) {
+                continue;
+            }
+            maybe_last_marker = Some((raw_row_index, marker));
+
+            let marker_pos = {
+                let col_index = ch!(line_number_width);
+                position!(col_index: col_index, row_index: ch!(raw_row_index))
+            };
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                gutter_origin_pos,
+                marker_pos,
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                marker.to_string(),
+                Some(get_search_match_style()),
+            ));
             render_ops.push(RenderOp::ResetColor);
         }
     }
@@ -467,6 +1361,44 @@ pub enum EditorEngineApplyEventResult {
     NotApplied,
 }
 
+pub mod line_number_gutter {
+    use super::*;
+
+    /// Shrink the given [FlexBox] from the left by `gutter_width` cols. The returned box
+    /// is where the content of the editor is painted, and the cols that are taken away
+    /// from it are where the gutter is painted.
+    pub fn exclude_gutter_from_box(
+        current_box: FlexBox,
+        gutter_width: ChUnit,
+    ) -> PartialFlexBox {
+        let mut it: PartialFlexBox = current_box.into();
+        it.style_adjusted_origin_pos.col_index += gutter_width;
+        it.style_adjusted_bounds_size.col_count -= gutter_width;
+        it
+    }
+
+    /// Line numbers are 1 based. In [LineNumberGutterMode::Relative] mode, only the line w/
+    /// the caret displays its own line number, and all the others display their distance
+    /// from it.
+    pub fn line_number_to_display(
+        gutter_mode: LineNumberGutterMode,
+        row_index: ChUnit,
+        caret_row_index: ChUnit,
+    ) -> usize {
+        match gutter_mode {
+            LineNumberGutterMode::Relative if row_index != caret_row_index => {
+                let distance = if row_index > caret_row_index {
+                    row_index - caret_row_index
+                } else {
+                    caret_row_index - row_index
+                };
+                ch!(@to_usize distance)
+            }
+            _ => ch!(@to_usize row_index, @inc),
+        }
+    }
+}
+
 mod syn_hi_r3bl_path {
     use super::*;
 
@@ -508,11 +1440,13 @@ mod syn_hi_r3bl_path {
         max_display_col_count: ChUnit,
     ) -> CommonResult<()> {
         throws!({
-            let lines = try_parse_and_highlight(
-                editor_buffer.get_lines(),
-                &editor_engine.current_box.get_computed_style(),
-                Some((&editor_engine.syntax_set, &editor_engine.theme)),
-            )?;
+            let lines = timed_if_enabled(editor_engine, TelemetryKind::MdParse, || {
+                try_parse_and_highlight(
+                    editor_buffer.get_lines(),
+                    &editor_engine.current_box.get_computed_style(),
+                    Some((&editor_engine.syntax_set, &editor_engine.theme)),
+                )
+            })?;
 
             call_if_true!(DEBUG_TUI_SYN_HI, {
                 tracing::debug!(
@@ -559,9 +1493,9 @@ mod syn_hi_r3bl_path {
             position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
         ));
         let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let styled_texts: TuiStyledTexts =
-            line.clip(scroll_offset_col, max_display_col_count);
-        render_tui_styled_texts_into(&styled_texts, render_ops);
+        let line = expand_tabs_in_spans(line, editor_engine.get_tab_width(editor_buffer));
+        let clipped_line = line.clip_spans(scroll_offset_col, max_display_col_count);
+        render_style_us_span_line_into(&clipped_line, render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
 }
@@ -613,15 +1547,23 @@ mod syn_hi_syntect_path {
             position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
         ));
 
-        let it =
-            try_get_syntect_highlighted_line(editor_engine, editor_buffer, &line.string);
+        let scroll_adj_row_index =
+            ch!(@to_usize editor_buffer.get_scroll_offset().row_index) + row_index;
+        let it = timed_if_enabled(editor_engine, TelemetryKind::Highlight, || {
+            editor_engine.get_highlight_provider().highlight_line(
+                editor_buffer,
+                editor_engine,
+                scroll_adj_row_index,
+            )
+        });
 
         match it {
-            // If enabled, and we have a SyntaxReference then try and highlight the line.
-            Some(syntect_highlighted_line) => {
-                render_line_with_syntect(
-                    syntect_highlighted_line,
+            // If enabled, and the line could be highlighted, then render it w/ the styles.
+            Some(highlighted_line) => {
+                render_highlighted_line(
+                    highlighted_line,
                     editor_buffer,
+                    editor_engine,
                     max_display_col_count,
                     render_ops,
                 );
@@ -639,41 +1581,23 @@ mod syn_hi_syntect_path {
         }
     }
 
-    fn render_line_with_syntect(
-        syntect_highlighted_line: Vec<(syntect::highlighting::Style, &str)>,
+    fn render_highlighted_line(
+        highlighted_line: StyleUSSpanLine,
         editor_buffer: &&EditorBuffer,
+        editor_engine: &&mut EditorEngine,
         max_display_col_count: ChUnit,
         render_ops: &mut RenderOps,
     ) {
         let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
-        let list: List<StyleUSSpan> =
-            convert_syntect_to_styled_text::convert_highlighted_line_from_syntect_to_tui(
-                syntect_highlighted_line,
-            );
-        let styled_texts: TuiStyledTexts =
-            list.clip(scroll_offset_col, max_display_col_count);
-        render_tui_styled_texts_into(&styled_texts, render_ops);
+        let highlighted_line = expand_tabs_in_spans(
+            &highlighted_line,
+            editor_engine.get_tab_width(editor_buffer),
+        );
+        let clipped_line =
+            highlighted_line.clip_spans(scroll_offset_col, max_display_col_count);
+        render_style_us_span_line_into(&clipped_line, render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
-
-    /// Try and load syntax highlighting for the current line. It might seem lossy to
-    /// create a new [HighlightLines] for each line, but if this struct is re-used then it
-    /// will not be able to highlight the lines correctly in the editor component. This
-    /// struct is mutated when it is used to highlight a line, so it must be re-created
-    /// for each line.
-    fn try_get_syntect_highlighted_line<'a>(
-        editor_engine: &'a &mut EditorEngine,
-        editor_buffer: &&EditorBuffer,
-        line: &'a str,
-    ) -> Option<Vec<(syntect::highlighting::Style, &'a str)>> {
-        let file_ext = editor_buffer.get_maybe_file_extension()?;
-        let syntax_ref = try_get_syntax_ref(&editor_engine.syntax_set, file_ext)?;
-        let theme = &editor_engine.theme;
-        let mut highlighter = HighlightLines::new(syntax_ref, theme);
-        highlighter
-            .highlight_line(line, &editor_engine.syntax_set)
-            .ok()
-    }
 }
 
 mod no_syn_hi_path {
@@ -742,6 +1666,18 @@ mod no_syn_hi_path {
     ) {
         let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
 
+        // Paint the tabs (if any) as spaces, which take up the same cols.
+        let expanded_line;
+        let line = if line.string.contains('\t') {
+            expanded_line = UnicodeString::from(EditorEngineInternalApi::expand_tabs(
+                &line.string,
+                editor_engine.get_tab_width(editor_buffer),
+            ));
+            &expanded_line
+        } else {
+            line
+        };
+
         // Clip the content [scroll_offset.col .. max cols].
         let truncated_line =
             line.clip_to_width(scroll_offset_col_index, max_display_col_count);
@@ -759,6 +1695,27 @@ mod no_syn_hi_path {
     }
 }
 
+/// Replace each `\t` in the spans of the `line` w/ spaces up to the next tab stop, so
+/// that the spans take up the same cols as the line in the buffer they were made from.
+fn expand_tabs_in_spans(line: &StyleUSSpanLine, tab_width: usize) -> StyleUSSpanLine {
+    let mut acc = StyleUSSpanLine::with_capacity(line.len());
+    let mut col_index = ch!(0);
+    for span in line.iter() {
+        let text = UnicodeString::from(EditorEngineInternalApi::expand_tabs_from_col(
+            &span.text.string,
+            col_index,
+            tab_width,
+        ));
+        col_index += text.display_width;
+        acc.push(StyleUSSpan {
+            style: span.style,
+            text,
+            maybe_hyperlink: span.maybe_hyperlink.clone(),
+        });
+    }
+    acc
+}
+
 #[cfg(test)]
 mod test_cache {
     use std::collections::HashMap;
@@ -766,7 +1723,7 @@ mod test_cache {
     use r3bl_core::assert_eq2;
 
     use super::*;
-    use crate::ScrollOffset;
+    use crate::{test_fixtures::mock_real_objects_for_editor, ScrollOffset};
 
     #[test]
     fn test_render_content() {
@@ -869,6 +1826,40 @@ mod test_cache {
         test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
     }
 
+    #[test]
+    fn test_render_content_syncs_syntect_highlight_cache() {
+        let render_ops = &mut render_ops!();
+        let editor_buffer = &mut EditorBuffer::new_empty(&Some("rs".to_string()), &None);
+        editor_buffer.set_lines((0..30).map(|it| format!("let a{it} = {it};")).collect());
+        let editor_engine = &mut mock_real_objects_for_editor::make_editor_engine();
+        let window_size = editor_engine.current_box.style_adjusted_bounds_size;
+        let has_focus = &mut HasFocus::default();
+
+        // Only the lines in the viewport are highlighted.
+        cache::render_content(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            has_focus,
+            render_ops,
+        );
+        assert_eq2!(editor_buffer.syntect_highlight_cache.len(), 11);
+
+        // Editing a line drops the cached lines from that line onwards.
+        let version = editor_buffer.get_content_version();
+        editor_buffer.get_mut().0[5] = "let edited = 0;".into();
+        assert!(editor_buffer.get_content_version() > version);
+        editor_buffer.syntect_highlight_cache.sync(
+            editor_buffer.get_content_version(),
+            editor_buffer.get_lines(),
+            editor_buffer.get_maybe_file_extension(),
+            &editor_engine.syntax_set,
+            &editor_engine.theme,
+            0,
+        );
+        assert_eq2!(editor_buffer.syntect_highlight_cache.len(), 5);
+    }
+
     fn test_cache_miss(
         editor_buffer: &mut EditorBuffer,
         window_size: Size,
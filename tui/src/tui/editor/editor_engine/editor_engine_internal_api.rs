@@ -15,18 +15,23 @@
  *   limitations under the License.
  */
 
-use std::{cmp::Ordering, collections::HashMap, mem::replace};
+use std::{cmp::{self, Ordering},
+          collections::HashMap,
+          mem::replace};
 
 use r3bl_core::{ch,
                 position,
                 ChUnit,
                 Position,
+                SelectionRange,
                 UnicodeString,
                 UnicodeStringSegmentSliceResult};
 use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{editor_buffer_clipboard_support,
             editor_buffer_clipboard_support::ClipboardService,
+            get_language_metadata,
             CaretDirection,
             CaretKind,
             EditorArgs,
@@ -34,8 +39,10 @@ use crate::{editor_buffer_clipboard_support,
             EditorBuffer,
             EditorBufferApi,
             EditorEngine,
+            LanguageMetadata,
             LineMode,
-            ScrollOffset};
+            ScrollOffset,
+            TabMode};
 
 /// Functions that implement the editor engine.
 pub struct EditorEngineInternalApi;
@@ -105,6 +112,84 @@ impl EditorEngineInternalApi {
         caret_mut::to_end_of_line(buffer, engine, select_mode)
     }
 
+    pub fn word_left(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        caret_mut::to_prev_word_start(buffer, engine, select_mode)
+    }
+
+    pub fn word_right(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        caret_mut::to_next_word_end(buffer, engine, select_mode)
+    }
+
+    pub fn move_caret_to_screen_position(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        screen_position: Position,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        caret_mut::to_screen_position(buffer, engine, screen_position, select_mode)
+    }
+
+    /// Move the caret to the given scroll adjusted position & clear the selection.
+    pub fn move_caret_to_buffer_position(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        caret_adj: Position,
+    ) -> Option<()> {
+        caret_mut::to_buffer_position(buffer, engine, caret_adj)
+    }
+
+    /// Move the caret to the start of the given row (clamped to the last row in the
+    /// buffer) & clear the selection. The viewport is scrolled so that the row is in the
+    /// middle of it.
+    pub fn go_to_row(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        row_index: ChUnit,
+    ) -> Option<()> {
+        caret_mut::to_row_centered(buffer, engine, row_index)
+    }
+
+    /// Move the caret to the start of the buffer & clear the selection.
+    pub fn go_to_top(buffer: &mut EditorBuffer, engine: &mut EditorEngine) -> Option<()> {
+        caret_mut::to_row_centered(buffer, engine, ch!(0))
+    }
+
+    /// Move the caret to the end of the buffer & clear the selection.
+    pub fn go_to_bottom(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        let max_row_index = ch!(buffer.get_lines().len(), @dec);
+        caret_mut::to_row_centered(buffer, engine, max_row_index)?;
+        caret_mut::to_end_of_line(buffer, engine, SelectMode::Disabled)
+    }
+
+    /// Converts the screen position (eg: from a mouse event) into a scroll adjusted
+    /// position in the buffer. Returns [None] if it is outside the editor's box.
+    pub fn screen_position_to_buffer_position(
+        buffer: &EditorBuffer,
+        engine: &EditorEngine,
+        screen_position: Position,
+    ) -> Option<Position> {
+        caret_get::screen_position_to_buffer_position(buffer, engine, screen_position)
+    }
+
+    pub fn scroll_viewport_by_one_row(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        direction: CaretDirection,
+    ) -> Option<()> {
+        caret_mut::scroll_viewport_by_one_row(buffer, engine, direction)
+    }
+
     pub fn select_all(buffer: &mut EditorBuffer, select_mode: SelectMode) -> Option<()> {
         caret_mut::select_all(buffer, select_mode)
     }
@@ -139,10 +224,59 @@ impl EditorEngineInternalApi {
         content_mut::insert_str_at_caret(args, chunk)
     }
 
+    pub fn insert_text_at_caret(args: EditorArgsMut<'_>, text: &str) {
+        content_mut::insert_text_at_caret(args, text);
+    }
+
+    pub fn insert_char_with_auto_close_pairs(args: EditorArgsMut<'_>, character: char) {
+        content_mut::insert_char_with_auto_close_pairs(args, character)
+    }
+
+    /// Returns the scroll adjusted positions of the bracket at (or just to the left of)
+    /// the caret, and the bracket that matches it, if it is in the viewport.
+    pub fn find_visible_matching_bracket(
+        buffer: &EditorBuffer,
+        engine: &EditorEngine,
+    ) -> Option<(Position, Position)> {
+        content_get::find_visible_matching_bracket(buffer, engine)
+    }
+
     pub fn insert_new_line_at_caret(args: EditorArgsMut<'_>) {
         content_mut::insert_new_line_at_caret(args);
     }
 
+    pub fn insert_tab_at_caret(args: EditorArgsMut<'_>) {
+        content_mut::insert_tab_at_caret(args);
+    }
+
+    pub fn indent_selected_lines(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::indent_selected_lines(buffer, engine)
+    }
+
+    pub fn outdent_lines(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::outdent_lines(buffer, engine)
+    }
+
+    pub fn expand_tabs(text: &str, tab_width: usize) -> String {
+        content_get::expand_tabs(text, ch!(0), tab_width)
+    }
+
+    /// Just like [EditorEngineInternalApi::expand_tabs], for `text` that starts at the
+    /// display col `start_col_index` of its line.
+    pub fn expand_tabs_from_col(
+        text: &str,
+        start_col_index: ChUnit,
+        tab_width: usize,
+    ) -> String {
+        content_get::expand_tabs(text, start_col_index, tab_width)
+    }
+
     pub fn delete_at_caret(
         buffer: &mut EditorBuffer,
         engine: &mut EditorEngine,
@@ -165,6 +299,63 @@ impl EditorEngineInternalApi {
         content_mut::backspace_at_caret(buffer, engine)
     }
 
+    pub fn backspace_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::backspace_word_at_caret(buffer, engine)
+    }
+
+    pub fn delete_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::delete_word_at_caret(buffer, engine)
+    }
+
+    pub fn move_line(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        direction: CaretDirection,
+    ) -> Option<()> {
+        content_mut::move_line_at_caret(buffer, engine, direction)
+    }
+
+    pub fn duplicate_line(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::duplicate_line_at_caret(buffer, engine)
+    }
+
+    pub fn join_lines(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::join_line_at_caret_with_next(buffer, engine)
+    }
+
+    pub fn delete_line(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::delete_line_at_caret(buffer, engine)
+    }
+
+    pub fn trim_trailing_whitespace(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::trim_trailing_whitespace_in_content(buffer, engine)
+    }
+
+    pub fn toggle_line_comment(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::toggle_line_comment(buffer, engine)
+    }
+
     pub fn copy_editor_selection_to_clipboard(
         buffer: &EditorBuffer,
         clipboard: &mut impl ClipboardService,
@@ -265,6 +456,47 @@ mod caret_get {
         }
     }
 
+    /// Convert a position in screen coordinates (eg: from a mouse event) into a (scroll
+    /// adjusted) position in the buffer. This takes the origin of the
+    /// [EditorEngine::current_box] and the scroll offset into account. The resulting
+    /// position is clipped to the content of the buffer.
+    ///
+    /// Returns [None] if the position is outside the bounds of the box.
+    pub fn screen_position_to_buffer_position(
+        buffer: &EditorBuffer,
+        engine: &EditorEngine,
+        screen_position: Position,
+    ) -> Option<Position> {
+        empty_check_early_return!(buffer, @None);
+
+        let (origin_pos, bounds_size) =
+            engine.current_box.get_style_adjusted_position_and_size();
+
+        let is_inside_box = screen_position.col_index >= origin_pos.col_index
+            && screen_position.row_index >= origin_pos.row_index
+            && screen_position.col_index < origin_pos.col_index + bounds_size.col_count
+            && screen_position.row_index < origin_pos.row_index + bounds_size.row_count;
+        if !is_inside_box {
+            return None;
+        }
+
+        let scroll_offset = buffer.get_scroll_offset();
+
+        let max_row_index = ch!(buffer.get_lines().len(), @dec);
+        let row_index = cmp::min(
+            screen_position.row_index - origin_pos.row_index + scroll_offset.row_index,
+            max_row_index,
+        );
+
+        let line_display_width = buffer.get_line_display_width(row_index);
+        let col_index = cmp::min(
+            screen_position.col_index - origin_pos.col_index + scroll_offset.col_index,
+            line_display_width,
+        );
+
+        Some(position!(col_index: col_index, row_index: row_index))
+    }
+
     /// ```text
     /// R ┌──────────┐
     /// 0 ▸          │
@@ -659,6 +891,265 @@ mod caret_mut {
         None
     }
 
+    /// Move the caret to the start of the word to its left (see
+    /// [content_get::find_prev_word_start]). If there is no word to its left, then it moves
+    /// to the start of the line, and if it is already there, to the end of the line
+    /// above.
+    ///
+    /// This calls [left] repeatedly, so that the selection (if `select_mode` is
+    /// enabled) is handled the same way.
+    pub fn to_prev_word_start(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret_adj.row_index))?;
+
+        if caret_adj.col_index == ch!(0) {
+            return left(editor_buffer, editor_engine, select_mode);
+        }
+
+        match content_get::find_prev_word_start(line, caret_adj.col_index) {
+            Some(word_start) => {
+                let grapheme_count = line
+                    .vec_segment
+                    .iter()
+                    .filter(|it| {
+                        it.display_col_offset >= word_start
+                            && it.display_col_offset < caret_adj.col_index
+                    })
+                    .count();
+                for _ in 0..grapheme_count {
+                    left(editor_buffer, editor_engine, select_mode);
+                }
+                None
+            }
+            None => to_start_of_line(editor_buffer, editor_engine, select_mode),
+        }
+    }
+
+    /// Move the caret to the end of the word to its right (see
+    /// [content_get::find_next_word_end]). If there is no word to its right, then it moves
+    /// to the end of the line, and if it is already there, to the start of the line
+    /// below.
+    ///
+    /// This calls [right] repeatedly, so that the selection (if `select_mode` is
+    /// enabled) is handled the same way.
+    pub fn to_next_word_end(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret_adj.row_index))?;
+
+        if caret_adj.col_index >= line.display_width {
+            return right(editor_buffer, editor_engine, select_mode);
+        }
+
+        match content_get::find_next_word_end(line, caret_adj.col_index) {
+            Some(word_end) => {
+                let grapheme_count = line
+                    .vec_segment
+                    .iter()
+                    .filter(|it| {
+                        it.display_col_offset >= caret_adj.col_index
+                            && it.display_col_offset < word_end
+                    })
+                    .count();
+                for _ in 0..grapheme_count {
+                    right(editor_buffer, editor_engine, select_mode);
+                }
+                None
+            }
+            None => to_end_of_line(editor_buffer, editor_engine, select_mode),
+        }
+    }
+
+    /// Move the caret to the start of the row at `desired_row_index` (clamped to the
+    /// last row in the buffer) & clear the selection. Unlike [to_buffer_position], the
+    /// viewport is scrolled so that the row is in the middle of it (or as close to the
+    /// middle as possible, for rows near the top of the buffer).
+    ///
+    /// Returns [Some] if the caret was moved, so that the caller can chain more
+    /// movements after it.
+    pub fn to_row_centered(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        desired_row_index: ChUnit,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+        multiline_disabled_check_early_return!(editor_engine, @None);
+
+        let max_row_index = ch!(editor_buffer.get_lines().len(), @dec);
+        let row_index = cmp::min(desired_row_index, max_row_index);
+        let half_viewport_height =
+            ch!(ch!(@to_usize editor_engine.viewport_height()) / 2);
+
+        editor_buffer.clear_selection();
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, caret, scroll_offset| {
+                scroll_offset.row_index = row_index - half_viewport_height;
+                caret.row_index = row_index - scroll_offset.row_index;
+                scroll_editor_buffer::reset_caret_col(caret, scroll_offset);
+            },
+        );
+
+        Some(())
+    }
+
+    /// Depending on [SelectMode], this acts as a:
+    /// - Click, which moves the caret to the given position & clears the selection.
+    /// - Drag (or <kbd>Shift + Click</kbd>), which moves the caret to the given
+    ///   position & extends the selection to it.
+    ///
+    /// The `screen_position` is converted into a buffer position using
+    /// [caret_get::screen_position_to_buffer_position].
+    pub fn to_screen_position(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        screen_position: Position,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let desired_caret_adj = caret_get::screen_position_to_buffer_position(
+            editor_buffer,
+            editor_engine,
+            screen_position,
+        )?;
+
+        // This is only set if select_mode is enabled.
+        let maybe_previous_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, caret, scroll_offset| {
+                scroll_editor_buffer::set_caret_position(
+                    caret,
+                    scroll_offset,
+                    desired_caret_adj,
+                );
+            },
+        );
+
+        // This is only set if select_mode is enabled.
+        let maybe_current_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        // This is only runs if select_mode is enabled.
+        let is_same_row = matches!(
+            (
+                maybe_previous_caret_display_position,
+                maybe_current_caret_display_position
+            ),
+            (Some(previous), Some(current)) if previous.row_index == current.row_index
+        );
+        if is_same_row {
+            select_mode.handle_selection_single_line_caret_movement(
+                editor_buffer,
+                maybe_previous_caret_display_position,
+                maybe_current_caret_display_position,
+            );
+        } else {
+            select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+                editor_buffer,
+                maybe_previous_caret_display_position,
+                maybe_current_caret_display_position,
+            );
+        }
+
+        None
+    }
+
+    /// Move the caret to the given scroll adjusted position, clear the selection, and
+    /// scroll the viewport (if needed) so that the caret is visible.
+    pub fn to_buffer_position(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        desired_caret_adj: Position,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        editor_buffer.clear_selection();
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, caret, scroll_offset| {
+                scroll_editor_buffer::set_caret_position(
+                    caret,
+                    scroll_offset,
+                    desired_caret_adj,
+                );
+            },
+        );
+
+        scroll_editor_buffer::validate_scroll(EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        });
+
+        None
+    }
+
+    /// Scroll the viewport by one row (eg: in response to the mouse wheel). The caret
+    /// stays on the same line of the buffer, unless that line would scroll out of the
+    /// viewport, in which case the caret moves along w/ the viewport.
+    pub fn scroll_viewport_by_one_row(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        direction: CaretDirection,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+        multiline_disabled_check_early_return!(editor_engine, @None);
+
+        let viewport_height = editor_engine.viewport_height();
+        let max_row_index = ch!(editor_buffer.get_lines().len(), @dec);
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, caret, scroll_offset| match direction {
+                CaretDirection::Down => {
+                    // Don't scroll past the last line.
+                    if scroll_offset.row_index < max_row_index {
+                        scroll_offset.row_index += 1;
+                        if caret.row_index > ch!(0) {
+                            caret.row_index -= 1;
+                        }
+                    }
+                }
+                CaretDirection::Up => {
+                    if scroll_offset.row_index > ch!(0) {
+                        scroll_offset.row_index -= 1;
+                        // Don't move the caret below the last row of the viewport.
+                        if caret.row_index < ch!(viewport_height, @dec) {
+                            caret.row_index += 1;
+                        }
+                    }
+                }
+                _ => {}
+            },
+        );
+
+        None
+    }
+
     pub fn select_all(
         editor_buffer: &mut EditorBuffer,
         select_mode: SelectMode,
@@ -955,6 +1446,78 @@ mod caret_mut {
 mod content_get {
     use super::*;
 
+    /// Replace each `\t` in `text` w/ spaces up to the next tab stop, given that `text`
+    /// starts at the display col `start_col_index` of its line. This is how a line that
+    /// holds tabs is painted, so it is as wide as the line's [UnicodeString] when it is
+    /// created w/ [UnicodeString::new_with_tab_width].
+    pub fn expand_tabs(text: &str, start_col_index: ChUnit, tab_width: usize) -> String {
+        if !text.contains('\t') {
+            return text.to_string();
+        }
+
+        let tab_width = tab_width.max(1);
+        let mut acc = String::with_capacity(text.len());
+        let mut col: usize = ch!(@to_usize start_col_index);
+        for character in text.chars() {
+            if character == '\t' {
+                let spaces = tab_width - col % tab_width;
+                acc.push_str(&" ".repeat(spaces));
+                col += spaces;
+            } else {
+                acc.push(character);
+                col += UnicodeString::char_display_width(character);
+            }
+        }
+        acc
+    }
+
+    /// Returns the display col ranges of the words in the `line`, using Unicode word
+    /// segmentation ([UAX #29](https://unicode.org/reports/tr29/#Word_Boundaries)),
+    /// rather than just splitting on whitespace:
+    /// - Runs of whitespace aren't words.
+    /// - Letters & digits are grouped into words (eg: `can't` and `3.14` are one word).
+    /// - Every other segment (punctuation, an emoji, a CJK character) is a word on its
+    ///   own.
+    pub fn find_word_ranges(line: &UnicodeString) -> Vec<SelectionRange> {
+        line.string
+            .split_word_bound_indices()
+            .filter(|(_, segment)| !segment.chars().all(char::is_whitespace))
+            .map(|(byte_offset, segment)| {
+                // Word boundaries are always grapheme cluster boundaries.
+                let start = line
+                    .vec_segment
+                    .iter()
+                    .find(|it| it.byte_offset == byte_offset)
+                    .map(|it| it.display_col_offset)
+                    .unwrap_or(line.display_width);
+                let end = start + ch!(UnicodeString::str_display_width(segment));
+                SelectionRange::new(start, end)
+            })
+            .collect()
+    }
+
+    /// Returns the start of the last word (see [find_word_ranges]) that starts before
+    /// `col_index`.
+    pub fn find_prev_word_start(
+        line: &UnicodeString,
+        col_index: ChUnit,
+    ) -> Option<ChUnit> {
+        find_word_ranges(line)
+            .iter()
+            .rev()
+            .map(|it| it.start_display_col_index)
+            .find(|&it| it < col_index)
+    }
+
+    /// Returns the end of the first word (see [find_word_ranges]) that ends after
+    /// `col_index`.
+    pub fn find_next_word_end(line: &UnicodeString, col_index: ChUnit) -> Option<ChUnit> {
+        find_word_ranges(line)
+            .iter()
+            .map(|it| it.end_display_col_index)
+            .find(|&it| it > col_index)
+    }
+
     pub fn line_display_width_at_caret(
         buffer: &EditorBuffer,
         engine: &EditorEngine,
@@ -1079,6 +1642,116 @@ mod content_get {
         }
     }
 
+    pub const BRACKET_PAIRS: [(&str, &str); 3] = [("(", ")"), ("[", "]"), ("{", "}")];
+
+    pub fn find_visible_matching_bracket(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+    ) -> Option<(Position, Position)> {
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+
+        // Prefer the bracket at the caret, then the one just to the left of it.
+        let (bracket_col_index, open, close, is_open) = [
+            string_at_caret(editor_buffer, editor_engine),
+            string_to_left_of_caret(editor_buffer, editor_engine),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|it| {
+            let text = it.unicode_string_seg.string.as_str();
+            BRACKET_PAIRS.iter().find_map(|(open, close)| {
+                if text == *open || text == *close {
+                    Some((
+                        it.display_col_at_which_seg_starts,
+                        *open,
+                        *close,
+                        text == *open,
+                    ))
+                } else {
+                    None
+                }
+            })
+        })?;
+
+        // Only the rows in the viewport are searched.
+        let lines = editor_buffer.get_lines();
+        let first_row_index = ch!(@to_usize editor_buffer.get_scroll_offset().row_index);
+        let last_row_index = cmp::min(
+            first_row_index + ch!(@to_usize editor_engine.viewport_height()),
+            lines.len().saturating_sub(1),
+        );
+        let caret_row_index = ch!(@to_usize caret_adj.row_index);
+        let bracket_pos =
+            position!(col_index: bracket_col_index, row_index: caret_row_index);
+
+        let mut depth = 0;
+        let mut check = |segment_text: &str| -> bool {
+            let (same, other) = if is_open {
+                (open, close)
+            } else {
+                (close, open)
+            };
+            if segment_text == same {
+                depth += 1;
+            } else if segment_text == other {
+                if depth == 0 {
+                    return true;
+                }
+                depth -= 1;
+            }
+            false
+        };
+
+        if is_open {
+            let rows = lines
+                .iter()
+                .enumerate()
+                .take(last_row_index + 1)
+                .skip(caret_row_index);
+            for (row_index, line) in rows {
+                for segment in line.iter() {
+                    if row_index == caret_row_index
+                        && segment.display_col_offset <= bracket_col_index
+                    {
+                        continue;
+                    }
+                    if check(&segment.string) {
+                        let matching_bracket_pos = position!(
+                            col_index: segment.display_col_offset,
+                            row_index: row_index
+                        );
+                        return Some((bracket_pos, matching_bracket_pos));
+                    }
+                }
+            }
+        } else {
+            let rows = lines
+                .iter()
+                .enumerate()
+                .take(caret_row_index + 1)
+                .skip(first_row_index)
+                .rev();
+            for (row_index, line) in rows {
+                for segment in line.iter().rev() {
+                    if row_index == caret_row_index
+                        && segment.display_col_offset >= bracket_col_index
+                    {
+                        continue;
+                    }
+                    if check(&segment.string) {
+                        let matching_bracket_pos = position!(
+                            col_index: segment.display_col_offset,
+                            row_index: row_index
+                        );
+                        return Some((bracket_pos, matching_bracket_pos));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
     pub fn string_at_end_of_line_at_caret(
         editor_buffer: &EditorBuffer,
         editor_engine: &EditorEngine,
@@ -1099,6 +1772,81 @@ mod content_get {
 mod content_mut {
     use super::*;
 
+    /// Insert the `text` (which can have any number of lines, w/ `\n`, `\r\n` or `\r`
+    /// line endings) at the caret, and move the caret to the end of it. The lines are
+    /// spliced into the buffer in a single change, instead of inserting each line & new
+    /// line separately, so that large pastes are fast. Unlike
+    /// [EditorEvent::InsertNewLine](crate::EditorEvent::InsertNewLine) the new lines
+    /// aren't indented.
+    pub fn insert_text_at_caret(args: EditorArgsMut<'_>, text: &str) -> Option<()> {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        // All the line endings are turned into `\n`, so that a lone `\r` (eg: from an old
+        // Mac file) isn't inserted into a line.
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+
+        if !text.contains('\n') {
+            insert_str_at_caret(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                &text,
+            );
+            return None;
+        }
+
+        let tab_width = editor_engine.get_tab_width(editor_buffer);
+
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_index = ch!(@to_usize caret_adj.row_index);
+        fill_in_missing_lines_up_to_row(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            row_index,
+        );
+
+        // Split the line at the caret, the 1st line of the text is appended to the part
+        // before it, and the part after it is appended to the last line of the text.
+        let line = editor_buffer.get_lines().get(row_index)?;
+        let byte_index = line.clip_to_width(ch!(0), caret_adj.col_index).len();
+        let (line_start, line_end) = line.string.split_at(byte_index);
+
+        let mut new_lines: Vec<String> = text.split('\n').map(String::from).collect();
+        new_lines[0].insert_str(0, line_start);
+        let last_row_index = row_index + new_lines.len() - 1;
+        let last_line = new_lines.last_mut()?;
+        // The caret goes right before `line_end`, and each `\t` before it is as wide as it
+        // takes to get to the next tab stop.
+        let col_index =
+            UnicodeString::new_with_tab_width(last_line, tab_width).display_width;
+        let new_caret_adj = position!(
+            col_index: col_index,
+            row_index: ch!(last_row_index)
+        );
+        last_line.push_str(line_end);
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                lines.splice(
+                    row_index..=row_index,
+                    new_lines
+                        .iter()
+                        .map(|it| UnicodeString::new_with_tab_width(it, tab_width)),
+                );
+            },
+        );
+
+        caret_mut::to_buffer_position(editor_buffer, editor_engine, new_caret_adj)
+    }
+
     pub fn insert_str_at_caret(args: EditorArgsMut<'_>, chunk: &str) {
         let EditorArgsMut {
             editor_buffer,
@@ -1138,6 +1886,215 @@ mod content_mut {
         }
     }
 
+    const AUTO_CLOSE_PAIRS: [(char, char); 5] =
+        [('(', ')'), ('[', ']'), ('{', '}'), ('"', '"'), ('\'', '\'')];
+
+    /// Insert `character` at the caret, and:
+    /// 1. If it is the closing char of a pair, and the same char is already at the
+    ///    caret, then just move the caret over it (type over).
+    /// 2. If it is the opening char of a pair, then also insert the closing char after
+    ///    the caret. Quotes are not auto closed when they are typed right after a word
+    ///    (eg: `don't`).
+    pub fn insert_char_with_auto_close_pairs(args: EditorArgsMut<'_>, character: char) {
+        let EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        } = args;
+
+        let is_char_at_caret = content_get::string_at_caret(editor_buffer, editor_engine)
+            .is_some_and(|it| it.unicode_string_seg.string == character.to_string());
+        let is_closing_char = AUTO_CLOSE_PAIRS
+            .iter()
+            .any(|(_, close)| *close == character);
+        if is_closing_char && is_char_at_caret {
+            caret_mut::right(editor_buffer, editor_engine, SelectMode::Disabled);
+            return;
+        }
+
+        let maybe_pair = AUTO_CLOSE_PAIRS.iter().find(|(open, _)| *open == character);
+        let is_after_word =
+            content_get::string_to_left_of_caret(editor_buffer, editor_engine)
+                .is_some_and(|it| {
+                    it.unicode_string_seg
+                        .string
+                        .chars()
+                        .all(char::is_alphanumeric)
+                });
+
+        match maybe_pair {
+            Some((open, close)) if !(open == close && is_after_word) => {
+                insert_str_at_caret(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &format!("{open}{close}"),
+                );
+                caret_mut::left(editor_buffer, editor_engine, SelectMode::Disabled);
+            }
+            _ => {
+                insert_str_at_caret(
+                    EditorArgsMut {
+                        editor_buffer,
+                        editor_engine,
+                    },
+                    &String::from(character),
+                );
+            }
+        }
+    }
+
+    /// Insert a `\t`, or spaces from the caret up to the next tab stop, depending on the
+    /// [TabMode].
+    pub fn insert_tab_at_caret(args: EditorArgsMut<'_>) {
+        if let TabMode::InsertTab = args.editor_engine.config_options.tab_mode {
+            insert_str_at_caret(args, "\t");
+            return;
+        }
+
+        let tab_width = args.editor_engine.get_tab_width(args.editor_buffer);
+        let caret_adj = args.editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let col: usize = ch!(@to_usize caret_adj.col_index);
+        let spaces = " ".repeat(tab_width - col % tab_width);
+        insert_str_at_caret(args, &spaces);
+    }
+
+    /// Indent each line that is (at least partially) selected by one tab stop. The
+    /// selection & the caret move along w/ the text.
+    pub fn indent_selected_lines(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        let tab_width = editor_engine.get_tab_width(editor_buffer);
+        let row_indices = selected_row_indices(editor_buffer);
+        if row_indices.is_empty() {
+            return None;
+        }
+
+        let indent = match editor_engine.config_options.tab_mode {
+            TabMode::InsertTab => "\t".to_string(),
+            TabMode::InsertSpaces | TabMode::PassThrough => " ".repeat(tab_width),
+        };
+        let caret_adj_row = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        let viewport_width = editor_engine.viewport_width();
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, caret, scroll_offset| {
+                for row_index in &row_indices {
+                    let line = &mut lines[ch!(@to_usize *row_index)];
+                    *line = UnicodeString::from(format!("{indent}{}", line.string));
+                }
+
+                if row_indices.contains(&caret_adj_row) {
+                    let line_display_width =
+                        lines[ch!(@to_usize caret_adj_row)].display_width;
+                    scroll_editor_buffer::inc_caret_col(
+                        caret,
+                        scroll_offset,
+                        ch!(tab_width),
+                        line_display_width,
+                        viewport_width,
+                    );
+                }
+            },
+        );
+
+        let (.., selection_map) = editor_buffer.get_mut();
+        for row_index in &row_indices {
+            if let Some(range) = selection_map.get_mut(*row_index) {
+                *range = SelectionRange::new(
+                    range.start_display_col_index + ch!(tab_width),
+                    range.end_display_col_index + ch!(tab_width),
+                );
+            }
+        }
+
+        None
+    }
+
+    /// Remove a leading `\t` or up to one tab stop worth of leading spaces from each line
+    /// that is (at least partially) selected, or from the line at the caret if there's no
+    /// selection. The selection & the caret move along w/ the text.
+    pub fn outdent_lines(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        let tab_width = editor_engine.get_tab_width(editor_buffer);
+        let caret_adj_row = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        let row_indices = if editor_buffer.has_selection() {
+            selected_row_indices(editor_buffer)
+        } else {
+            vec![caret_adj_row]
+        };
+
+        // How many bytes are removed from the start of each line, and how many cols wide
+        // they are.
+        let removed_amounts: Vec<(ChUnit, usize, ChUnit)> = row_indices
+            .iter()
+            .filter_map(|row_index| {
+                let line = editor_buffer.get_lines().get(ch!(@to_usize *row_index))?;
+                if line.string.starts_with('\t') {
+                    return Some((*row_index, 1, line.vec_segment[0].unicode_width));
+                }
+                let leading_spaces =
+                    line.string.len() - line.string.trim_start_matches(' ').len();
+                let amount = leading_spaces.min(tab_width);
+                (amount > 0).then_some((*row_index, amount, ch!(amount)))
+            })
+            .collect();
+        if removed_amounts.is_empty() {
+            return None;
+        }
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, caret, scroll_offset| {
+                for (row_index, byte_count, amount) in &removed_amounts {
+                    let line = &mut lines[ch!(@to_usize *row_index)];
+                    *line = UnicodeString::from(&line.string[*byte_count..]);
+
+                    if *row_index == caret_adj_row {
+                        let caret_adj_col = caret.col_index + scroll_offset.col_index;
+                        scroll_editor_buffer::dec_caret_col(
+                            caret,
+                            scroll_offset,
+                            cmp::min(*amount, caret_adj_col),
+                        );
+                    }
+                }
+            },
+        );
+
+        let (.., selection_map) = editor_buffer.get_mut();
+        for (row_index, _, amount) in &removed_amounts {
+            if let Some(range) = selection_map.get_mut(*row_index) {
+                *range = SelectionRange::new(
+                    range.start_display_col_index - *amount,
+                    range.end_display_col_index - *amount,
+                );
+            }
+        }
+
+        None
+    }
+
+    /// Rows that have a non empty selection range, in order.
+    fn selected_row_indices(editor_buffer: &EditorBuffer) -> Vec<ChUnit> {
+        let selection_map = editor_buffer.get_selection_map();
+        selection_map
+            .get_ordered_indices()
+            .into_iter()
+            .filter(|row_index| {
+                selection_map.get(*row_index).is_some_and(|range| {
+                    range.start_display_col_index != range.end_display_col_index
+                })
+            })
+            .collect()
+    }
+
     pub fn insert_new_line_at_caret(args: EditorArgsMut<'_>) {
         let EditorArgsMut {
             editor_buffer,
@@ -1157,6 +2114,35 @@ mod content_mut {
             return;
         }
 
+        // Auto indent: the new line starts w/ the same indent (and for markdown, the same
+        // list or blockquote marker) as the current line.
+        let caret_adj = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line_prefix = match editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret_adj.row_index))
+        {
+            Some(line) => auto_indent::LinePrefix::parse(
+                &line.string,
+                editor_buffer.is_file_extension_default(),
+            ),
+            None => auto_indent::LinePrefix::default(),
+        };
+        let is_caret_after_prefix =
+            ch!(@to_usize caret_adj.col_index) >= line_prefix.display_width;
+
+        // Pressing Enter on an empty list item (or blockquote) ends the list, by removing
+        // the marker, instead of inserting a new line.
+        if line_prefix.is_empty_item && is_caret_after_prefix {
+            inner::remove_marker_from_current_line(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                &line_prefix.indent,
+            );
+            return;
+        }
+
         match caret_get::find_col(EditorArgs {
             editor_buffer,
             editor_engine,
@@ -1181,9 +2167,52 @@ mod content_mut {
             }
         }
 
+        // The caret is now at the start of the new line.
+        if is_caret_after_prefix && !line_prefix.next_line_prefix.is_empty() {
+            insert_str_at_caret(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                &line_prefix.next_line_prefix,
+            );
+        }
+
         mod inner {
             use super::*;
 
+            // Replace the current line w/ just its indent.
+            pub fn remove_marker_from_current_line(
+                args: EditorArgsMut<'_>,
+                indent: &str,
+            ) {
+                let EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                } = args;
+
+                let viewport_width = editor_engine.viewport_width();
+                let indent_display_width = ch!(UnicodeString::str_display_width(indent));
+
+                validate_editor_buffer_change::apply_change(
+                    editor_buffer,
+                    editor_engine,
+                    |lines, caret, scroll_offset| {
+                        let row_index =
+                            EditorBuffer::calc_scroll_adj_caret_row(caret, scroll_offset);
+                        let _ =
+                            replace(&mut lines[row_index], UnicodeString::from(indent));
+                        scroll_editor_buffer::set_caret_col(
+                            caret,
+                            scroll_offset,
+                            viewport_width,
+                            indent_display_width,
+                            indent_display_width,
+                        );
+                    },
+                );
+            }
+
             // Handle inserting a new line at the end of the current line.
             pub fn insert_new_line_at_end_of_current_line(args: EditorArgsMut<'_>) {
                 let EditorArgsMut {
@@ -1357,6 +2386,370 @@ mod content_mut {
         }
     }
 
+    /// Delete the text between the caret and the start of the word to its left (see
+    /// [content_get::find_prev_word_start]), or the start of the line if there is no
+    /// word. At the start of a line, this joins it w/ the line above, just like
+    /// [backspace_at_caret].
+    pub fn backspace_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        if caret_adj.col_index == ch!(0) {
+            return backspace_at_caret(buffer, engine);
+        }
+
+        let line = buffer.get_lines().get(ch!(@to_usize caret_adj.row_index))?;
+        let word_start = content_get::find_prev_word_start(line, caret_adj.col_index)
+            .unwrap_or(ch!(0));
+        let new_line = format!(
+            "{}{}",
+            line.clip_to_width(ch!(0), word_start),
+            line.clip_to_width(caret_adj.col_index, line.display_width)
+        );
+
+        buffer.clear_selection();
+        validate_editor_buffer_change::apply_change(
+            buffer,
+            engine,
+            |lines, caret, scroll_offset| {
+                lines[ch!(@to_usize caret_adj.row_index)] = new_line.into();
+                let new_caret_adj =
+                    position!(col_index: word_start, row_index: caret_adj.row_index);
+                scroll_editor_buffer::set_caret_position(
+                    caret,
+                    scroll_offset,
+                    new_caret_adj,
+                );
+            },
+        );
+
+        None
+    }
+
+    /// Delete the text between the caret and the end of the word to its right (see
+    /// [content_get::find_next_word_end]), or the end of the line if there is no word.
+    /// At the end of a line, this joins it w/ the line below, just like
+    /// [delete_at_caret].
+    pub fn delete_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line = buffer.get_lines().get(ch!(@to_usize caret_adj.row_index))?;
+        if caret_adj.col_index >= line.display_width {
+            return delete_at_caret(buffer, engine);
+        }
+
+        let word_end = content_get::find_next_word_end(line, caret_adj.col_index)
+            .unwrap_or(line.display_width);
+        let new_line = format!(
+            "{}{}",
+            line.clip_to_width(ch!(0), caret_adj.col_index),
+            line.clip_to_width(word_end, line.display_width)
+        );
+
+        buffer.clear_selection();
+        validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+            lines[ch!(@to_usize caret_adj.row_index)] = new_line.into();
+        });
+
+        None
+    }
+
+    /// Swap the line at the caret w/ the one above (or below) it. The caret moves along
+    /// w/ the line.
+    pub fn move_line_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        direction: CaretDirection,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+        multiline_disabled_check_early_return!(engine, @None);
+
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_index = ch!(@to_usize caret_adj.row_index);
+        let other_row_index = match direction {
+            CaretDirection::Up => row_index.checked_sub(1)?,
+            CaretDirection::Down => {
+                Some(row_index + 1).filter(|&it| it < buffer.get_lines().len())?
+            }
+            CaretDirection::Left | CaretDirection::Right => return None,
+        };
+
+        validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+            lines.swap(row_index, other_row_index);
+        });
+
+        let new_caret_adj = position!(
+            col_index: caret_adj.col_index,
+            row_index: ch!(other_row_index)
+        );
+        caret_mut::to_buffer_position(buffer, engine, new_caret_adj)
+    }
+
+    /// Insert a copy of the line at the caret below it, and move the caret to the copy.
+    pub fn duplicate_line_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+        multiline_disabled_check_early_return!(engine, @None);
+
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_index = ch!(@to_usize caret_adj.row_index);
+        let line = buffer.get_lines().get(row_index)?.string.clone();
+
+        validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+            lines.insert(row_index + 1, line.into());
+        });
+
+        let new_caret_adj = position!(
+            col_index: caret_adj.col_index,
+            row_index: caret_adj.row_index + 1
+        );
+        caret_mut::to_buffer_position(buffer, engine, new_caret_adj)
+    }
+
+    /// Join the line at the caret w/ the line below it. The whitespace between them is
+    /// replaced w/ a single space, and the caret is moved to where they were joined.
+    pub fn join_line_at_caret_with_next(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+        multiline_disabled_check_early_return!(engine, @None);
+
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_index = ch!(@to_usize caret_adj.row_index);
+        let this_line = buffer.get_lines().get(row_index)?.string.trim_end();
+        let next_line = buffer.get_lines().get(row_index + 1)?.string.trim_start();
+
+        let separator = if this_line.is_empty() || next_line.is_empty() {
+            ""
+        } else {
+            " "
+        };
+        let joined_line = format!("{this_line}{separator}{next_line}");
+        let join_col_index = ch!(UnicodeString::str_display_width(this_line));
+
+        validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+            lines[row_index] = joined_line.into();
+            lines.remove(row_index + 1);
+        });
+
+        let new_caret_adj =
+            position!(col_index: join_col_index, row_index: caret_adj.row_index);
+        caret_mut::to_buffer_position(buffer, engine, new_caret_adj)
+    }
+
+    /// Remove the line at the caret. If it is the only line, then it is emptied instead.
+    pub fn delete_line_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_index = ch!(@to_usize caret_adj.row_index);
+
+        validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+            if lines.len() == 1 {
+                lines[0] = String::new().into();
+            } else {
+                lines.remove(row_index);
+            }
+        });
+
+        // The caret stays on the same row (the line below moved up into it), unless the
+        // last line was removed.
+        let max_row_index = ch!(buffer.get_lines().len(), @dec);
+        let new_caret_adj = position!(
+            col_index: caret_adj.col_index,
+            row_index: cmp::min(caret_adj.row_index, max_row_index)
+        );
+        caret_mut::to_buffer_position(buffer, engine, new_caret_adj)
+    }
+
+    /// Remove the trailing whitespace from every line. The caret is moved to the end of
+    /// its line, if it was in the whitespace that was removed.
+    pub fn trim_trailing_whitespace_in_content(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let has_trailing_whitespace = buffer
+            .get_lines()
+            .iter()
+            .any(|line| line.string.trim_end().len() != line.string.len());
+        if !has_trailing_whitespace {
+            return None;
+        }
+
+        validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+            for line in lines.iter_mut() {
+                let trimmed_line = line.string.trim_end();
+                if trimmed_line.len() != line.string.len() {
+                    *line = trimmed_line.to_string().into();
+                }
+            }
+        });
+
+        // The selection might be in the whitespace that was removed.
+        buffer.clear_selection();
+
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line_display_width = buffer.get_line_display_width(caret_adj.row_index);
+        let new_caret_adj = position!(
+            col_index: cmp::min(caret_adj.col_index, line_display_width),
+            row_index: caret_adj.row_index
+        );
+        caret_mut::to_buffer_position(buffer, engine, new_caret_adj)
+    }
+
+    /// Comment out each line that is (at least partially) selected, or the line at the
+    /// caret if there's no selection, w/ the comment syntax of the buffer's language
+    /// (see [get_language_metadata]). If all of them are already commented out, then
+    /// they are uncommented instead. Blank lines are left alone.
+    ///
+    /// The comment is inserted at the indentation of the least indented line, so that
+    /// the lines stay aligned. The selection & the caret move along w/ the text.
+    pub fn toggle_line_comment(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let &LanguageMetadata {
+            comment_start,
+            comment_end,
+            ..
+        } = buffer
+            .get_maybe_file_extension()
+            .and_then(get_language_metadata)?;
+
+        let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_indices = match buffer.has_selection() {
+            true => selected_row_indices(buffer),
+            false => vec![caret_adj.row_index],
+        };
+        let non_blank_lines: Vec<(ChUnit, &str)> = row_indices
+            .iter()
+            .filter_map(|row_index| {
+                let line = buffer.get_lines().get(ch!(@to_usize *row_index))?;
+                (!line.string.trim().is_empty())
+                    .then_some((*row_index, line.string.as_str()))
+            })
+            .collect();
+        if non_blank_lines.is_empty() {
+            return None;
+        }
+
+        let is_commented_out = |line: &str| {
+            let line = line.trim();
+            line.starts_with(comment_start.trim_end())
+                && line.ends_with(comment_end.trim_start())
+        };
+        let should_uncomment = non_blank_lines.iter().all(|(_, it)| is_commented_out(it));
+
+        // The (ASCII) spaces before the text of a line.
+        let get_indent = |line: &str| line.len() - line.trim_start_matches(' ').len();
+        let min_indent = non_blank_lines
+            .iter()
+            .map(|(_, it)| get_indent(it))
+            .min()
+            .unwrap_or(0);
+
+        // The new content of each line, and the col at which the comment start was
+        // inserted (or removed) along w/ its width.
+        let edits: Vec<(ChUnit, String, ChUnit, ChUnit)> = non_blank_lines
+            .iter()
+            .map(|(row_index, line)| {
+                if should_uncomment {
+                    let indent = get_indent(line);
+                    let text = line[indent..].trim_end();
+                    let text_after_start = text
+                        .strip_prefix(comment_start)
+                        .or_else(|| text.strip_prefix(comment_start.trim_end()))
+                        .unwrap_or(text);
+                    let text_inside = text_after_start
+                        .strip_suffix(comment_end)
+                        .or_else(|| {
+                            text_after_start.strip_suffix(comment_end.trim_start())
+                        })
+                        .unwrap_or(text_after_start);
+                    let removed_width = UnicodeString::str_display_width(
+                        &text[..text.len() - text_after_start.len()],
+                    );
+                    (
+                        *row_index,
+                        format!("{}{text_inside}", &line[..indent]),
+                        ch!(indent),
+                        ch!(removed_width),
+                    )
+                } else {
+                    (
+                        *row_index,
+                        format!(
+                            "{}{comment_start}{}{comment_end}",
+                            &line[..min_indent],
+                            &line[min_indent..]
+                        ),
+                        ch!(min_indent),
+                        ch!(UnicodeString::str_display_width(comment_start)),
+                    )
+                }
+            })
+            .collect();
+
+        validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+            for (row_index, new_line, ..) in &edits {
+                lines[ch!(@to_usize *row_index)] = new_line.as_str().into();
+            }
+        });
+
+        // Move a col that is after the comment start along w/ the text.
+        let shift_col = |col_index: ChUnit, edit_col_index: ChUnit, amount: ChUnit| match (
+            col_index <= edit_col_index,
+            should_uncomment,
+        ) {
+            (true, _) => col_index,
+            (false, true) => cmp::max(edit_col_index, col_index - amount),
+            (false, false) => col_index + amount,
+        };
+
+        let (.., selection_map) = buffer.get_mut();
+        for (row_index, _, edit_col_index, amount) in &edits {
+            if let Some(range) = selection_map.get_mut(*row_index) {
+                *range = SelectionRange::new(
+                    shift_col(range.start_display_col_index, *edit_col_index, *amount),
+                    shift_col(range.end_display_col_index, *edit_col_index, *amount),
+                );
+            }
+        }
+
+        let maybe_caret_edit = edits
+            .iter()
+            .find(|(row_index, ..)| *row_index == caret_adj.row_index);
+        let Some((_, _, edit_col_index, amount)) = maybe_caret_edit else {
+            return None;
+        };
+        let line_display_width = buffer.get_line_display_width(caret_adj.row_index);
+        let new_caret_adj = position!(
+            col_index: cmp::min(
+                shift_col(caret_adj.col_index, *edit_col_index, *amount),
+                line_display_width
+            ),
+            row_index: caret_adj.row_index
+        );
+        caret_mut::to_buffer_position(buffer, engine, new_caret_adj)
+    }
+
     pub fn backspace_at_caret(
         buffer: &mut EditorBuffer,
         engine: &mut EditorEngine,
@@ -1568,9 +2961,25 @@ mod content_mut {
 
         let row_index = ch!(@to_usize caret_adj.row_index);
         let line = editor_buffer.get_lines().get(row_index)?;
+        let tab_width = editor_engine.get_tab_width(editor_buffer);
 
-        let (new_line, char_display_width) =
+        let (mut new_line, _) =
             line.insert_char_at_display_col(ch!(caret_adj.col_index), chunk)?;
+        new_line.set_tab_width(tab_width);
+
+        // The width of the chunk depends on where it is inserted, if it holds a `\t`. So
+        // the caret goes to the display col of the segment right after the chunk.
+        let char_display_width = {
+            let logical_index = line
+                .logical_index_at_display_col_index(caret_adj.col_index)
+                .unwrap_or(line.len());
+            let inserted_segment_count = new_line.len() - line.len();
+            let new_caret_adj_col = new_line
+                .get(logical_index + inserted_segment_count)
+                .map(|it| it.display_col_offset)
+                .unwrap_or(new_line.display_width);
+            new_caret_adj_col - caret_adj.col_index
+        };
 
         let viewport_width = editor_engine.viewport_width();
 
@@ -1632,6 +3041,7 @@ mod content_mut {
         let _ = editor_buffer.get_lines().get(caret_adj_row)?;
 
         let viewport_width = editor_engine.viewport_width();
+        let tab_width = editor_engine.get_tab_width(editor_buffer);
 
         validate_editor_buffer_change::apply_change(
             editor_buffer,
@@ -1640,11 +3050,11 @@ mod content_mut {
                 // Actually add the character to the correct line.
                 let _ = replace(
                     &mut lines[ch!(@to_usize caret_adj_row)],
-                    UnicodeString::from(chunk),
+                    UnicodeString::new_with_tab_width(chunk, tab_width),
                 );
 
                 let line_content_display_width = lines[caret_adj_row].display_width;
-                let col_amt = ch!(UnicodeString::str_display_width(chunk));
+                let col_amt = line_content_display_width;
 
                 // Update caret position.
                 scroll_editor_buffer::inc_caret_col(
@@ -1661,6 +3071,131 @@ mod content_mut {
     }
 }
 
+mod auto_indent {
+    /// The leading part of a line that is carried over to the new line when the user
+    /// presses Enter.
+    #[derive(Debug, Default, PartialEq)]
+    pub struct LinePrefix {
+        /// The leading spaces of the line.
+        pub indent: String,
+        /// Display width of the indent & marker of the line.
+        pub display_width: usize,
+        /// What the new line should start with.
+        pub next_line_prefix: String,
+        /// The line is nothing but a markdown list or blockquote marker (eg: `- `).
+        pub is_empty_item: bool,
+    }
+
+    impl LinePrefix {
+        pub fn parse(line: &str, is_markdown: bool) -> Self {
+            let rest = line.trim_start_matches(' ');
+            let indent = &line[..line.len() - rest.len()];
+
+            let (marker_len, next_marker) = if is_markdown {
+                parse_md_marker(rest)
+            } else {
+                (0, String::new())
+            };
+
+            Self {
+                indent: indent.to_string(),
+                display_width: indent.len() + marker_len,
+                next_line_prefix: format!("{indent}{next_marker}"),
+                is_empty_item: marker_len > 0 && rest[marker_len..].trim().is_empty(),
+            }
+        }
+    }
+
+    /// Returns the byte length of the blockquote and / or list marker at the start of
+    /// `text`, and the marker that the next line should start with. Blockquotes are
+    /// carried over as is, checkboxes are unchecked, and ordered lists are incremented.
+    fn parse_md_marker(text: &str) -> (usize, String) {
+        let mut marker_len = 0;
+        let mut next_marker = String::new();
+
+        // Blockquotes, which can be nested.
+        while let Some(after) = text[marker_len..].strip_prefix('>') {
+            marker_len += 1;
+            if after.starts_with(' ') {
+                marker_len += 1;
+            }
+            next_marker.push_str("> ");
+        }
+
+        let rest = &text[marker_len..];
+
+        // Unordered list, w/ an optional checkbox.
+        for bullet in ["- ", "* ", "+ "] {
+            if let Some(after) = rest.strip_prefix(bullet) {
+                marker_len += bullet.len();
+                next_marker.push_str(bullet);
+                if ["[ ] ", "[x] ", "[X] "]
+                    .iter()
+                    .any(|checkbox| after.starts_with(checkbox))
+                {
+                    marker_len += "[ ] ".len();
+                    next_marker.push_str("[ ] ");
+                }
+                return (marker_len, next_marker);
+            }
+        }
+
+        // Ordered list.
+        let digit_count = rest.chars().take_while(char::is_ascii_digit).count();
+        if digit_count > 0 {
+            for delimiter in [". ", ") "] {
+                if !rest[digit_count..].starts_with(delimiter) {
+                    continue;
+                }
+                if let Ok(number) = rest[..digit_count].parse::<usize>() {
+                    marker_len += digit_count + delimiter.len();
+                    next_marker.push_str(&format!("{}{delimiter}", number + 1));
+                    return (marker_len, next_marker);
+                }
+            }
+        }
+
+        (marker_len, next_marker)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use r3bl_core::assert_eq2;
+
+        use super::*;
+
+        #[test]
+        fn test_parse_line_prefix() {
+            let it = LinePrefix::parse("    let a = 1;", false);
+            assert_eq2!(it.display_width, 4);
+            assert_eq2!(it.next_line_prefix, "    ");
+            assert!(!it.is_empty_item);
+
+            // Markdown markers are ignored if this isn't markdown.
+            let it = LinePrefix::parse("- item", false);
+            assert_eq2!(it.next_line_prefix, "");
+
+            let it = LinePrefix::parse("  - item", true);
+            assert_eq2!(it.display_width, 4);
+            assert_eq2!(it.next_line_prefix, "  - ");
+
+            let it = LinePrefix::parse("- [x] done", true);
+            assert_eq2!(it.next_line_prefix, "- [ ] ");
+
+            let it = LinePrefix::parse("9. item", true);
+            assert_eq2!(it.next_line_prefix, "10. ");
+
+            let it = LinePrefix::parse("> > - quote", true);
+            assert_eq2!(it.display_width, 6);
+            assert_eq2!(it.next_line_prefix, "> > - ");
+
+            let it = LinePrefix::parse("  * ", true);
+            assert!(it.is_empty_item);
+            assert_eq2!(it.indent, "  ");
+        }
+    }
+}
+
 /// This is marked as `pub` because `apply_change` is needed by `cargo doc`.
 pub mod validate_editor_buffer_change {
     use super::*;
@@ -1682,11 +3217,18 @@ pub mod validate_editor_buffer_change {
             /* EditorEngine::scroll_offset */ &mut ScrollOffset,
         ),
     ) -> Option<()> {
+        let tab_width = editor_engine.get_tab_width(editor_buffer);
         let (lines, caret, scroll_offset, _) = editor_buffer.get_mut();
 
         // Run the mutator first.
         mutator(lines, caret, scroll_offset);
 
+        // Any line that the mutator created (or changed) that holds a `\t` has to be
+        // laid out again, since it is as wide as it takes to get to the next tab stop.
+        for line in lines.iter_mut() {
+            line.set_tab_width(tab_width);
+        }
+
         // Check caret validity.
         adjust_caret_col_if_not_in_middle_of_grapheme_cluster(EditorArgsMut {
             editor_engine,
@@ -1956,6 +3498,27 @@ mod scroll_editor_buffer {
         }
     }
 
+    /// Move the caret to the given scroll adjusted position. The scroll offset is left
+    /// alone, unless the position is above or to the left of the viewport, in which case
+    /// the viewport is scrolled so that it starts at the position.
+    ///
+    /// This is meant to be called inside [validate::apply_change].
+    pub fn set_caret_position(
+        caret: &mut Position,
+        scroll_offset: &mut ScrollOffset,
+        desired_caret_adj: Position,
+    ) {
+        if desired_caret_adj.row_index < scroll_offset.row_index {
+            scroll_offset.row_index = desired_caret_adj.row_index;
+        }
+        caret.row_index = desired_caret_adj.row_index - scroll_offset.row_index;
+
+        if desired_caret_adj.col_index < scroll_offset.col_index {
+            scroll_offset.col_index = desired_caret_adj.col_index;
+        }
+        caret.col_index = desired_caret_adj.col_index - scroll_offset.col_index;
+    }
+
     /// This is meant to be called inside [validate::apply_change].
     pub fn reset_caret_col(caret: &mut Position, scroll_offset: &mut ScrollOffset) {
         scroll_offset.col_index = ch!(0);
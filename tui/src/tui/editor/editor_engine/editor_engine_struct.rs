@@ -15,13 +15,36 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug,
+          time::{Duration, Instant}};
 
-use r3bl_core::ChUnit;
+use r3bl_core::{ch, ChUnit, TuiStyle};
 use serde::{Deserialize, Serialize};
 use syntect::{highlighting::Theme, parsing::SyntaxSet};
 
-use crate::{load_default_theme, try_load_r3bl_theme, PartialFlexBox};
+use crate::{get_language_metadata,
+            load_theme_or_default,
+            try_load_theme,
+            CompletionPopup,
+            CursorShape,
+            EditorBuffer,
+            EditorMacro,
+            EditorTelemetry,
+            HighlightProvider,
+            KeyBindingTable,
+            PartialFlexBox,
+            SharedCompletionProvider,
+            SharedHighlightProvider,
+            SharedSpellChecker,
+            SyntaxThemeSource,
+            SyntectHighlightProvider,
+            VimMode,
+            VimState,
+            DEFAULT_TAB_WIDTH};
+
+/// How long the caret is visible (and then hidden) for, when [CaretBlinkMode::Enable] is
+/// set.
+pub const DEFAULT_CARET_BLINK_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Do not create this struct directly. Please use [new()](EditorEngine::new) instead.
 ///
@@ -43,8 +66,41 @@ pub struct EditorEngine {
     pub config_options: EditorEngineConfig,
     /// Syntax highlighting support. This is a very heavy object to create, re-use it.
     pub syntax_set: SyntaxSet,
-    /// Syntax highlighting support. This is a very heavy object to create, re-use it.
+    /// Syntax highlighting support. This is a very heavy object to create, re-use it. Use
+    /// [Self::set_syntax_theme] to change it, so that the buffers are re-highlighted.
     pub theme: Theme,
+    /// This is bumped each time the [Self::theme] changes, so that the buffers know
+    /// that their cached highlighting is stale.
+    #[serde(skip)]
+    pub theme_version: u64,
+    /// Set this to highlight the buffers that aren't markdown w/ something other than
+    /// syntect, see [HighlightProvider].
+    #[serde(skip)]
+    pub maybe_highlight_provider: Option<SharedHighlightProvider>,
+    /// Used when [EditorEngineConfig::keymap_mode] is [KeymapMode::Vim]. Apps can read
+    /// the [VimState::mode] to display it (eg: in a status bar).
+    pub vim_state: VimState,
+    /// When this is set, the characters that are typed replace the ones at the caret
+    /// (instead of being inserted before them). It is toggled w/
+    /// [crate::EditorEvent::ToggleOverwriteMode].
+    pub is_overwrite_mode: bool,
+    /// The macro that is being (or was last) recorded, see [EditorMacro].
+    pub editor_macro: EditorMacro,
+    /// Set this to add completion to the editor, see [crate::CompletionProvider].
+    #[serde(skip)]
+    pub maybe_completion_provider: Option<SharedCompletionProvider>,
+    /// The popup that displays the items from [Self::maybe_completion_provider].
+    pub completion_popup: CompletionPopup,
+    /// Set this to add spell checking to the editor, see [crate::SpellChecker].
+    #[serde(skip)]
+    pub maybe_spell_checker: Option<SharedSpellChecker>,
+    /// When the current blink cycle of the caret started, see [CaretBlinkMode].
+    #[serde(skip)]
+    pub maybe_caret_blink_start: Option<Instant>,
+    /// The timings of the last few frames, when [EditorEngineConfig::telemetry] is
+    /// enabled.
+    #[serde(skip)]
+    pub telemetry: EditorTelemetry,
 }
 
 impl Default for EditorEngine {
@@ -54,15 +110,94 @@ impl Default for EditorEngine {
 impl EditorEngine {
     /// Syntax highlighting support - [SyntaxSet] and [Theme] are a very expensive objects to
     /// create, so re-use them.
+    ///
+    /// If the [EditorEngineConfig::syntax_theme] can't be loaded, then the bundled r3bl
+    /// theme is used instead.
     pub fn new(config_options: EditorEngineConfig) -> Self {
+        let theme = load_theme_or_default(&config_options.syntax_theme);
         Self {
             current_box: Default::default(),
             config_options,
             syntax_set: SyntaxSet::load_defaults_newlines(),
-            theme: try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme()),
+            theme,
+            theme_version: 0,
+            maybe_highlight_provider: None,
+            vim_state: Default::default(),
+            is_overwrite_mode: false,
+            editor_macro: Default::default(),
+            maybe_completion_provider: None,
+            completion_popup: Default::default(),
+            maybe_spell_checker: None,
+            maybe_caret_blink_start: None,
+            telemetry: Default::default(),
+        }
+    }
+
+    /// The [Self::maybe_highlight_provider] if it is set, or the
+    /// [SyntectHighlightProvider].
+    pub fn get_highlight_provider(&self) -> &dyn HighlightProvider {
+        match self.maybe_highlight_provider {
+            Some(ref it) => it.as_ref(),
+            None => &SyntectHighlightProvider,
         }
     }
 
+    /// Switch to the theme from the `source` at runtime. If it can't be loaded, then the
+    /// current theme is kept and the error is returned.
+    pub fn set_syntax_theme(&mut self, source: SyntaxThemeSource) -> std::io::Result<()> {
+        self.theme = try_load_theme(&source)?;
+        self.theme_version += 1;
+        self.config_options.syntax_theme = source;
+        Ok(())
+    }
+
+    /// Restart the blink cycle of the caret, so that it is visible. This is called when
+    /// an event is applied, so that the caret doesn't blink while the user is typing.
+    pub fn reset_caret_blink(&mut self) {
+        self.maybe_caret_blink_start = Some(Instant::now());
+    }
+
+    /// Returns `false` if the caret is in the "off" half of its blink cycle at `now`. It
+    /// is always visible if [EditorEngineConfig::caret_blink] is disabled.
+    pub fn is_caret_visible(&self, now: Instant) -> bool {
+        let CaretBlinkMode::Enable = self.config_options.caret_blink else {
+            return true;
+        };
+        let Some(blink_start) = self.maybe_caret_blink_start else {
+            return true;
+        };
+        let elapsed = now.saturating_duration_since(blink_start);
+        (elapsed.as_millis() / DEFAULT_CARET_BLINK_INTERVAL.as_millis()) % 2 == 0
+    }
+
+    /// The shape of the terminal's cursor when [CaretStyle::TerminalCursor] is set, which
+    /// shows the mode that the editor is in: a block in [VimMode::Normal] &
+    /// [VimMode::Visual], an underline in overwrite mode (see
+    /// [Self::is_overwrite_mode]), and a bar otherwise. The terminal blinks it if
+    /// [EditorEngineConfig::caret_blink] is enabled.
+    pub fn get_cursor_shape(&self) -> CursorShape {
+        let is_vim_normal_mode = matches!(
+            (self.config_options.keymap_mode, self.vim_state.mode),
+            (KeymapMode::Vim, VimMode::Normal | VimMode::Visual)
+        );
+        let shape = if is_vim_normal_mode {
+            CursorShape::SteadyBlock
+        } else if self.is_overwrite_mode {
+            CursorShape::SteadyUnderline
+        } else {
+            CursorShape::SteadyBar
+        };
+        shape.with_blink(matches!(
+            self.config_options.caret_blink,
+            CaretBlinkMode::Enable
+        ))
+    }
+
+    /// The viewport is the area of the [Self::current_box] that is used to display the
+    /// content of the buffer. If the line number gutter is enabled, then the
+    /// [Self::current_box] has already been adjusted (by
+    /// [EditorEngineApi::render_engine](crate::EditorEngineApi::render_engine)) to
+    /// exclude the gutter, so caret and scroll math doesn't have to account for it.
     pub fn viewport_width(&self) -> ChUnit {
         self.current_box.style_adjusted_bounds_size.col_count
     }
@@ -70,6 +205,36 @@ impl EditorEngine {
     pub fn viewport_height(&self) -> ChUnit {
         self.current_box.style_adjusted_bounds_size.row_count
     }
+
+    /// The number of columns between tab stops in the `editor_buffer`, which depends on
+    /// its language if [IndentByLanguageMode::Enable] is set. This is never 0.
+    pub fn get_tab_width(&self, editor_buffer: &EditorBuffer) -> usize {
+        let maybe_indent_width = match self.config_options.indent_by_language {
+            IndentByLanguageMode::Enable => editor_buffer
+                .get_maybe_file_extension()
+                .and_then(get_language_metadata)
+                .and_then(|it| it.maybe_indent_width),
+            IndentByLanguageMode::Disable => None,
+        };
+        maybe_indent_width
+            .unwrap_or(self.config_options.tab_width)
+            .max(1)
+    }
+
+    /// Returns the number of cols that the line number gutter takes up, given the number
+    /// of lines in the buffer. This is `0` if the gutter is disabled. Otherwise it is
+    /// the number of digits in the largest line number plus one col of padding on the
+    /// right.
+    pub fn line_number_gutter_width(&self, line_count: usize) -> ChUnit {
+        match self.config_options.line_number_gutter {
+            LineNumberGutterMode::Disable => ch!(0),
+            LineNumberGutterMode::Absolute | LineNumberGutterMode::Relative => {
+                let max_line_number = line_count.max(1);
+                let digit_count = max_line_number.to_string().len();
+                ch!(digit_count + 1)
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -77,6 +242,41 @@ pub struct EditorEngineConfig {
     pub multiline_mode: LineMode,
     pub syntax_highlight: SyntaxHighlightMode,
     pub edit_mode: EditMode,
+    pub line_number_gutter: LineNumberGutterMode,
+    /// Style that is applied to the line number gutter. If [None] then
+    /// [get_line_number_gutter_style](crate::get_line_number_gutter_style) is used.
+    pub maybe_line_number_gutter_style: Option<TuiStyle>,
+    /// Number of columns between tab stops.
+    pub tab_width: usize,
+    /// Use the indent width of the buffer's language (see [crate::LanguageMetadata])
+    /// instead of [Self::tab_width], if it has one.
+    pub indent_by_language: IndentByLanguageMode,
+    pub tab_mode: TabMode,
+    /// Highlight the bracket that matches the one at the caret.
+    pub bracket_matching: BracketMatchingMode,
+    /// Insert the closing `)]}"'` when the opening one is typed, and type over it.
+    pub auto_close_pairs: AutoClosePairsMode,
+    pub keymap_mode: KeymapMode,
+    /// Mark the lines that changed since the buffer was last loaded or saved, in the line
+    /// number gutter (so the gutter has to be enabled too).
+    pub change_markers: ChangeMarkersMode,
+    /// Paint glyphs in place of trailing spaces & other invisible characters.
+    pub whitespace_render: WhitespaceRenderMode,
+    /// Paint the images of the `![alt](path)` lines in markdown buffers.
+    pub inline_images: InlineImageMode,
+    pub caret_style: CaretStyle,
+    pub caret_blink: CaretBlinkMode,
+    /// Remove the trailing whitespace from every line when [crate::EditorEvent::Save]
+    /// is applied.
+    pub trim_trailing_whitespace: TrimTrailingWhitespaceMode,
+    /// The theme that is used for syntax highlighting, which is loaded by
+    /// [EditorEngine::new]. To change it later use [EditorEngine::set_syntax_theme].
+    pub syntax_theme: SyntaxThemeSource,
+    /// Overrides for the default key bindings.
+    pub key_binding_table: KeyBindingTable,
+    /// Record how long the editor takes to apply events & render, see
+    /// [EditorTelemetry].
+    pub telemetry: TelemetryMode,
 }
 
 mod editor_engine_config_options_impl {
@@ -88,6 +288,23 @@ mod editor_engine_config_options_impl {
                 multiline_mode: LineMode::MultiLine,
                 syntax_highlight: SyntaxHighlightMode::Enable,
                 edit_mode: EditMode::ReadWrite,
+                line_number_gutter: LineNumberGutterMode::Disable,
+                maybe_line_number_gutter_style: None,
+                tab_width: DEFAULT_TAB_WIDTH,
+                indent_by_language: IndentByLanguageMode::Enable,
+                tab_mode: TabMode::InsertSpaces,
+                bracket_matching: BracketMatchingMode::Enable,
+                auto_close_pairs: AutoClosePairsMode::Disable,
+                keymap_mode: KeymapMode::Default,
+                change_markers: ChangeMarkersMode::Disable,
+                whitespace_render: WhitespaceRenderMode::Disable,
+                inline_images: InlineImageMode::Disable,
+                caret_style: CaretStyle::Block,
+                caret_blink: CaretBlinkMode::Disable,
+                trim_trailing_whitespace: TrimTrailingWhitespaceMode::Disable,
+                syntax_theme: SyntaxThemeSource::R3bl,
+                key_binding_table: Default::default(),
+                telemetry: TelemetryMode::Disable,
             }
         }
     }
@@ -95,6 +312,10 @@ mod editor_engine_config_options_impl {
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EditMode {
+    /// The content is rendered and can be navigated, selected & copied, but any
+    /// [crate::EditorEvent] that would change it (see
+    /// [is_mutating](crate::EditorEvent::is_mutating)) is not applied. This is useful
+    /// for log viewers & markdown preview panes.
     ReadOnly,
     ReadWrite,
 }
@@ -105,8 +326,142 @@ pub enum LineMode {
     MultiLine,
 }
 
+/// Controls what happens when the user presses <kbd>Tab</kbd> or <kbd>Shift+Tab</kbd>.
+///
+/// The content of the editor can hold `\t` characters (eg: by loading a file, pasting, or
+/// w/ [TabMode::InsertTab]), which are kept as is. Each one is as wide as it takes to get
+/// to the next tab stop, see [EditorEngineConfig::tab_width], and it is only expanded into
+/// spaces when it is painted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TabMode {
+    /// Insert spaces up to the next tab stop. If there's a selection, then
+    /// <kbd>Tab</kbd> indents and <kbd>Shift+Tab</kbd> outdents the selected lines.
+    InsertSpaces,
+    /// Just like [TabMode::InsertSpaces], except that a `\t` is inserted (and lines are
+    /// indented w/ one), instead of spaces.
+    InsertTab,
+    /// Don't handle the key, so that the app can use it (eg: to move focus).
+    PassThrough,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndentByLanguageMode {
+    Disable,
+    Enable,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BracketMatchingMode {
+    Disable,
+    Enable,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoClosePairsMode {
+    Disable,
+    Enable,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeMarkersMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether the [EditorTelemetry] is recorded. This is disabled by default, since
+/// it reads the clock a few times for each event & frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TelemetryMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether invisible characters are made visible, by painting a (dimmed) glyph
+/// in their place:
+/// 1. Trailing spaces are painted as `·`.
+/// 2. Non-breaking spaces (anywhere in the line) are painted as `␣`.
+/// 3. Tabs are painted as `→`, at the start of the space that they take up (see
+///    [TabMode]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WhitespaceRenderMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether the images in a markdown buffer are painted in the editor, w/
+/// [crate::RenderOp::PaintImage]. When enabled, a line that only holds an image, eg:
+/// `![logo](docs/logo.png)`, is painted over w/ the image, which also takes up the blank
+/// lines that follow it. The path is relative to the file of the buffer. Remote images
+/// (`http://` & `https://`) are not fetched.
+///
+/// Nothing is painted if the terminal doesn't support any of the image protocols, see
+/// [crate::ImageProtocol]. Kitty & sixel terminals also need the `images` feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InlineImageMode {
+    Disable,
+    Enable,
+}
+
+/// Controls whether the trailing whitespace is removed from every line when the buffer
+/// is saved. It can also be removed at any time w/
+/// [crate::EditorEvent::TrimTrailingWhitespace].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrimTrailingWhitespaceMode {
+    Disable,
+    OnSave,
+}
+
+/// How the caret is painted. The terminal's own cursor is hidden while the app is running
+/// (everything is painted into an offscreen buffer first), so the caret is painted as
+/// part of the content, unless [CaretStyle::TerminalCursor] is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaretStyle {
+    /// The character under the caret is painted in reverse.
+    Block,
+    /// A thin bar (`▏`) is painted at the end of the line or on whitespace. Since a cell
+    /// can only hold one character, the character under the caret is underlined
+    /// everywhere else.
+    Bar,
+    /// The character under the caret is underlined.
+    Underline,
+    /// The terminal's own cursor is shown at the caret, w/ a shape that depends on the
+    /// mode that the editor is in, see [EditorEngine::get_cursor_shape].
+    TerminalCursor,
+}
+
+/// Controls whether the caret blinks. The caret is hidden for every other
+/// [DEFAULT_CARET_BLINK_INTERVAL] (and it is visible right after any event is applied).
+/// Since the editor is only painted when the app is rendered, use a
+/// [crate::EditorCaretBlinker] to render the app on each blink.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaretBlinkMode {
+    Disable,
+    Enable,
+}
+
+/// Controls which key bindings are used to turn [crate::InputEvent]s into
+/// [crate::EditorEvent]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeymapMode {
+    Default,
+    /// Modal (vim like) key bindings, see [crate::VimKeymap].
+    Vim,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SyntaxHighlightMode {
     Disable,
     Enable,
 }
+
+/// Controls whether a line number gutter is painted to the left of the content of the
+/// editor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineNumberGutterMode {
+    Disable,
+    /// Each line displays its own (1 based) line number.
+    Absolute,
+    /// The line w/ the caret displays its own (1 based) line number, and every other
+    /// line displays its distance from the caret's line.
+    Relative,
+}
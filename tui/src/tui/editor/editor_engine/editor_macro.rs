@@ -0,0 +1,62 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::EditorEvent;
+
+/// Records the [EditorEvent]s that are applied between
+/// [EditorEvent::StartMacroRecording] and [EditorEvent::StopMacroRecording], so that
+/// they can be applied again w/ [EditorEvent::ReplayMacro]. This lives in the
+/// [crate::EditorEngine], so every editor component has its own.
+///
+/// Only the events that go through
+/// [EditorEngineApi::apply_event](crate::EditorEngineApi::apply_event) (or
+/// [EditorEngineApi::apply_editor_event](crate::EditorEngineApi::apply_editor_event))
+/// are recorded, and the macro events themselves are not.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EditorMacro {
+    maybe_recording: Option<Vec<EditorEvent>>,
+    recorded_events: Vec<EditorEvent>,
+}
+
+impl EditorMacro {
+    /// Apps can use this to display that a macro is being recorded (eg: in a status
+    /// bar).
+    pub fn is_recording(&self) -> bool { self.maybe_recording.is_some() }
+
+    /// The events of the last macro that was recorded.
+    pub fn get_recorded_events(&self) -> &[EditorEvent] { &self.recorded_events }
+
+    /// Any recording that is in progress is discarded.
+    pub fn start_recording(&mut self) { self.maybe_recording = Some(vec![]); }
+
+    /// The events that were recorded replace the last macro. Nothing happens if a macro
+    /// isn't being recorded.
+    pub fn stop_recording(&mut self) {
+        if let Some(events) = self.maybe_recording.take() {
+            self.recorded_events = events;
+        }
+    }
+
+    /// Add the `editor_event` to the macro that is being recorded, if there is one.
+    pub fn record(&mut self, editor_event: &EditorEvent) {
+        if let Some(events) = self.maybe_recording.as_mut() {
+            events.push(editor_event.clone());
+        }
+    }
+}
@@ -0,0 +1,169 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::VecDeque,
+          fmt::{Display, Formatter, Result},
+          sync::atomic::{AtomicU64, Ordering},
+          time::Duration};
+
+use r3bl_core::timed;
+
+use crate::{EditorEngine, TelemetryMode};
+
+/// How many frames the [EditorTelemetry] keeps by default.
+pub const DEFAULT_TELEMETRY_FRAME_COUNT: usize = 120;
+
+/// The time that the editor spent on one frame, ie: in the calls to
+/// [EditorEngineApi::apply_event](crate::EditorEngineApi::apply_event) since the previous
+/// frame, and in the call to
+/// [EditorEngineApi::render_engine](crate::EditorEngineApi::render_engine) that painted
+/// it.
+///
+/// The parse & highlight times are part of the `render_engine` time, and they are 0 when
+/// the content was painted from the render cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EditorFrameTimings {
+    pub apply_event: Duration,
+    /// Parsing (and highlighting) markdown buffers.
+    pub md_parse: Duration,
+    /// Highlighting the other buffers, see [crate::HighlightProvider].
+    pub highlight: Duration,
+    pub render_engine: Duration,
+}
+
+impl Display for EditorFrameTimings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "apply_event: {} μs, md_parse: {} μs, highlight: {} μs, render_engine: {} μs",
+            self.apply_event.as_micros(),
+            self.md_parse.as_micros(),
+            self.highlight.as_micros(),
+            self.render_engine.as_micros(),
+        )
+    }
+}
+
+/// What the time in [EditorTelemetry::record] was spent on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TelemetryKind {
+    ApplyEvent,
+    MdParse,
+    Highlight,
+}
+
+/// The [EditorFrameTimings] of the last few frames, which are only recorded when
+/// [TelemetryMode::Enable] is set. Apps can display these in a debug overlay, to find out
+/// where the time goes when typing feels slow.
+///
+/// The times for the frame that hasn't been rendered yet are atomics, since some of them
+/// are recorded while the [EditorEngine] is borrowed immutably.
+#[derive(Debug)]
+pub struct EditorTelemetry {
+    pending_apply_event_nanos: AtomicU64,
+    pending_md_parse_nanos: AtomicU64,
+    pending_highlight_nanos: AtomicU64,
+    frames: VecDeque<EditorFrameTimings>,
+    max_frame_count: usize,
+}
+
+impl Default for EditorTelemetry {
+    fn default() -> Self { Self::new(DEFAULT_TELEMETRY_FRAME_COUNT) }
+}
+
+impl Clone for EditorTelemetry {
+    fn clone(&self) -> Self {
+        let load = |it: &AtomicU64| AtomicU64::new(it.load(Ordering::Relaxed));
+        Self {
+            pending_apply_event_nanos: load(&self.pending_apply_event_nanos),
+            pending_md_parse_nanos: load(&self.pending_md_parse_nanos),
+            pending_highlight_nanos: load(&self.pending_highlight_nanos),
+            frames: self.frames.clone(),
+            max_frame_count: self.max_frame_count,
+        }
+    }
+}
+
+impl EditorTelemetry {
+    /// Keep the timings of the last `max_frame_count` frames (at least 1).
+    pub fn new(max_frame_count: usize) -> Self {
+        let max_frame_count = max_frame_count.max(1);
+        Self {
+            pending_apply_event_nanos: AtomicU64::new(0),
+            pending_md_parse_nanos: AtomicU64::new(0),
+            pending_highlight_nanos: AtomicU64::new(0),
+            frames: VecDeque::with_capacity(max_frame_count),
+            max_frame_count,
+        }
+    }
+
+    /// The oldest frame first.
+    pub fn get_frames(&self) -> &VecDeque<EditorFrameTimings> { &self.frames }
+
+    pub fn get_last_frame(&self) -> Option<&EditorFrameTimings> { self.frames.back() }
+
+    /// The slowest frame (by its total time) that is still kept.
+    pub fn get_slowest_frame(&self) -> Option<&EditorFrameTimings> {
+        self.frames
+            .iter()
+            .max_by_key(|it| it.apply_event + it.render_engine)
+    }
+
+    pub fn clear(&mut self) { *self = Self::new(self.max_frame_count); }
+
+    /// Add the `duration` to the frame that hasn't been rendered yet.
+    pub fn record(&self, kind: TelemetryKind, duration: Duration) {
+        let pending_nanos = match kind {
+            TelemetryKind::ApplyEvent => &self.pending_apply_event_nanos,
+            TelemetryKind::MdParse => &self.pending_md_parse_nanos,
+            TelemetryKind::Highlight => &self.pending_highlight_nanos,
+        };
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        pending_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    /// The frame was rendered (which took `render_engine`), so add it to the frames, and
+    /// drop the oldest one if there are too many.
+    pub fn end_frame(&mut self, render_engine: Duration) {
+        let take = |it: &AtomicU64| Duration::from_nanos(it.swap(0, Ordering::Relaxed));
+        let frame = EditorFrameTimings {
+            apply_event: take(&self.pending_apply_event_nanos),
+            md_parse: take(&self.pending_md_parse_nanos),
+            highlight: take(&self.pending_highlight_nanos),
+            render_engine,
+        };
+        if self.frames.len() == self.max_frame_count {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+}
+
+/// Run `f`, and record the time that it took as `kind`, if [TelemetryMode::Enable] is set
+/// in the `editor_engine`.
+pub(crate) fn timed_if_enabled<T>(
+    editor_engine: &EditorEngine,
+    kind: TelemetryKind,
+    f: impl FnOnce() -> T,
+) -> T {
+    let TelemetryMode::Enable = editor_engine.config_options.telemetry else {
+        return f();
+    };
+    let (it, duration) = timed!({ f() });
+    editor_engine.telemetry.record(kind, duration);
+    it
+}
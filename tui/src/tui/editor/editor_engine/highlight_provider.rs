@@ -0,0 +1,116 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, sync::Arc};
+
+use syntect::easy::HighlightLines;
+
+use crate::{convert_syntect_to_styled_text,
+            try_get_syntax_ref,
+            EditorBuffer,
+            EditorEngine,
+            StyleUSSpanLine};
+
+/// The source of syntax highlighting for the buffers that aren't markdown (markdown is
+/// highlighted by the md parser). The editor uses [SyntectHighlightProvider] unless a
+/// different one is set in [EditorEngine::maybe_highlight_provider].
+///
+/// W/ the `tree-sitter` feature, this crate provides `TreeSitterHighlightProvider`,
+/// which re-parses only the edited parts of the buffer. Apps can also implement this w/
+/// a different highlighter.
+pub trait HighlightProvider: Debug + Send + Sync {
+    /// This is called before the visible lines of the `editor_buffer` are rendered, and
+    /// only when they have to be (eg: the content or the scroll offset changed). This is
+    /// where the highlighting of the lines up to `last_row_index` (inclusive) can be
+    /// brought up to date.
+    fn sync(
+        &self,
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &EditorEngine,
+        last_row_index: usize,
+    );
+
+    /// The highlighted spans of the line at `row_index` (in the buffer, not the
+    /// viewport), or [None] to render it w/out syntax highlighting.
+    fn highlight_line(
+        &self,
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        row_index: usize,
+    ) -> Option<StyleUSSpanLine>;
+}
+
+pub type SharedHighlightProvider = Arc<dyn HighlightProvider>;
+
+/// Highlights w/ the [EditorEngine::syntax_set] & [EditorEngine::theme], and caches the
+/// results in the [crate::SyntectHighlightCache] of each buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SyntectHighlightProvider;
+
+impl HighlightProvider for SyntectHighlightProvider {
+    fn sync(
+        &self,
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &EditorEngine,
+        last_row_index: usize,
+    ) {
+        let content_version = editor_buffer.get_content_version();
+        let EditorBuffer {
+            editor_content,
+            syntect_highlight_cache,
+            ..
+        } = editor_buffer;
+        syntect_highlight_cache.sync(
+            content_version,
+            &editor_content.lines,
+            editor_content.maybe_file_extension.as_deref(),
+            &editor_engine.syntax_set,
+            &editor_engine.theme,
+            last_row_index,
+        );
+    }
+
+    fn highlight_line(
+        &self,
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        row_index: usize,
+    ) -> Option<StyleUSSpanLine> {
+        let line = &editor_buffer.get_lines().get(row_index)?.string;
+        let syntect_highlighted_line =
+            match editor_buffer.syntect_highlight_cache.get(row_index, line) {
+                Some(cached) => cached,
+                // The line isn't in the cache (which is synced by [Self::sync]), so it is
+                // highlighted in isolation, w/out any state from the lines above it.
+                None => {
+                    let file_ext = editor_buffer.get_maybe_file_extension()?;
+                    let syntax_ref =
+                        try_get_syntax_ref(&editor_engine.syntax_set, file_ext)?;
+                    let mut highlighter =
+                        HighlightLines::new(syntax_ref, &editor_engine.theme);
+                    highlighter
+                        .highlight_line(line, &editor_engine.syntax_set)
+                        .ok()?
+                }
+            };
+        Some(
+            convert_syntect_to_styled_text::convert_highlighted_line_from_syntect_to_tui(
+                syntect_highlighted_line,
+            ),
+        )
+    }
+}
@@ -0,0 +1,151 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+/// What the editor needs to know about the language of a buffer, which is looked up by
+/// the buffer's file extension in [LANGUAGE_METADATA_TABLE].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LanguageMetadata {
+    pub name: &'static str,
+    pub file_extensions: &'static [&'static str],
+    /// What [crate::EditorEvent::ToggleLineComment] inserts before the text of a line,
+    /// eg: `// `.
+    pub comment_start: &'static str,
+    /// What is appended to a line that is commented out, for languages that only have
+    /// block comments (eg: ` -->` for markdown). This is empty for the rest.
+    pub comment_end: &'static str,
+    /// Overrides [crate::EditorEngineConfig::tab_width] for buffers in this language,
+    /// when [crate::IndentByLanguageMode::Enable] is set.
+    pub maybe_indent_width: Option<usize>,
+}
+
+pub const LANGUAGE_METADATA_TABLE: &[LanguageMetadata] = &[
+    LanguageMetadata {
+        name: "rust",
+        file_extensions: &["rs"],
+        comment_start: "// ",
+        comment_end: "",
+        maybe_indent_width: Some(4),
+    },
+    LanguageMetadata {
+        name: "markdown",
+        file_extensions: &["md", "markdown"],
+        comment_start: "<!-- ",
+        comment_end: " -->",
+        maybe_indent_width: None,
+    },
+    LanguageMetadata {
+        name: "toml",
+        file_extensions: &["toml"],
+        comment_start: "# ",
+        comment_end: "",
+        maybe_indent_width: None,
+    },
+    LanguageMetadata {
+        name: "shell",
+        file_extensions: &["sh", "bash", "zsh", "fish"],
+        comment_start: "# ",
+        comment_end: "",
+        maybe_indent_width: None,
+    },
+    LanguageMetadata {
+        name: "python",
+        file_extensions: &["py"],
+        comment_start: "# ",
+        comment_end: "",
+        maybe_indent_width: Some(4),
+    },
+    LanguageMetadata {
+        name: "yaml",
+        file_extensions: &["yaml", "yml"],
+        comment_start: "# ",
+        comment_end: "",
+        maybe_indent_width: Some(2),
+    },
+    LanguageMetadata {
+        name: "javascript",
+        file_extensions: &["js", "jsx", "mjs", "cjs"],
+        comment_start: "// ",
+        comment_end: "",
+        maybe_indent_width: Some(2),
+    },
+    LanguageMetadata {
+        name: "typescript",
+        file_extensions: &["ts", "tsx"],
+        comment_start: "// ",
+        comment_end: "",
+        maybe_indent_width: Some(2),
+    },
+    LanguageMetadata {
+        name: "c",
+        file_extensions: &["c", "h", "cc", "cpp", "hpp"],
+        comment_start: "// ",
+        comment_end: "",
+        maybe_indent_width: None,
+    },
+    LanguageMetadata {
+        name: "go",
+        file_extensions: &["go"],
+        comment_start: "// ",
+        comment_end: "",
+        maybe_indent_width: None,
+    },
+    LanguageMetadata {
+        name: "java",
+        file_extensions: &["java", "kt"],
+        comment_start: "// ",
+        comment_end: "",
+        maybe_indent_width: Some(4),
+    },
+    LanguageMetadata {
+        name: "html",
+        file_extensions: &["html", "htm", "xml", "svg"],
+        comment_start: "<!-- ",
+        comment_end: " -->",
+        maybe_indent_width: Some(2),
+    },
+    LanguageMetadata {
+        name: "css",
+        file_extensions: &["css", "scss"],
+        comment_start: "/* ",
+        comment_end: " */",
+        maybe_indent_width: Some(2),
+    },
+    LanguageMetadata {
+        name: "sql",
+        file_extensions: &["sql"],
+        comment_start: "-- ",
+        comment_end: "",
+        maybe_indent_width: None,
+    },
+    LanguageMetadata {
+        name: "lua",
+        file_extensions: &["lua"],
+        comment_start: "-- ",
+        comment_end: "",
+        maybe_indent_width: None,
+    },
+];
+
+/// Returns [None] if the language of the `file_extension` isn't in the
+/// [LANGUAGE_METADATA_TABLE].
+pub fn get_language_metadata(file_extension: &str) -> Option<&'static LanguageMetadata> {
+    LANGUAGE_METADATA_TABLE.iter().find(|it| {
+        it.file_extensions
+            .iter()
+            .any(|ext| ext.eq_ignore_ascii_case(file_extension))
+    })
+}
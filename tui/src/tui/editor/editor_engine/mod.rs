@@ -16,11 +16,27 @@
  */
 
 // Attach.
+pub mod completion_popup;
 pub mod editor_engine_api;
 pub mod editor_engine_internal_api;
 pub mod editor_engine_struct;
+pub mod editor_macro;
+pub mod editor_telemetry;
+pub mod highlight_provider;
+pub mod language_metadata;
+#[cfg(feature = "tree-sitter")]
+pub mod tree_sitter_highlight_provider;
+pub mod vim_keymap;
 
 // Re-export.
+pub use completion_popup::*;
 pub use editor_engine_api::*;
 pub use editor_engine_internal_api::*;
 pub use editor_engine_struct::*;
+pub use editor_macro::*;
+pub use editor_telemetry::*;
+pub use highlight_provider::*;
+pub use language_metadata::*;
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter_highlight_provider::*;
+pub use vim_keymap::*;
@@ -0,0 +1,529 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::{Debug, Formatter},
+          iter::once,
+          ops::Range,
+          sync::Mutex as StdMutex};
+
+use r3bl_core::{CommonError, CommonErrorType, CommonResult};
+use syntect::highlighting::{Highlighter, Scope, Style as SyntectStyle, Theme};
+use tree_sitter::{InputEdit, Language, Parser, Point, Query, QueryCursor, Tree};
+
+use crate::{convert_syntect_to_styled_text,
+            EditorBuffer,
+            EditorEngine,
+            HighlightProvider,
+            StyleUSSpanLine,
+            SyntectHighlightProvider,
+            SyntectStyleStrSpanLine};
+
+/// A tree-sitter grammar, w/ its highlights query (this is usually the
+/// `queries/highlights.scm` file in the grammar's repo), that is used for the buffers
+/// that have one of the `file_extensions`.
+pub struct TreeSitterGrammar {
+    pub language: Language,
+    pub highlights_query: String,
+    pub file_extensions: Vec<String>,
+}
+
+impl TreeSitterGrammar {
+    pub fn rust() -> Self {
+        Self {
+            language: tree_sitter_rust::language(),
+            highlights_query: tree_sitter_rust::HIGHLIGHT_QUERY.to_string(),
+            file_extensions: vec!["rs".to_string()],
+        }
+    }
+}
+
+/// A [HighlightProvider] that parses the buffer w/ a tree-sitter grammar. This is only
+/// available w/ the `tree-sitter` feature.
+///
+/// 1. The tree of the last buffer that was parsed is kept, so when the buffer is edited,
+///    only the part of it that changed is re-parsed.
+/// 2. The captures of the grammar's highlights query (eg: `keyword`, `string`,
+///    `function.method`) are mapped to the syntect scopes that themes use, so the
+///    buffer is painted w/ the [EditorEngine::theme].
+/// 3. Buffers that don't have a grammar (for their file extension) are highlighted by
+///    the [SyntectHighlightProvider].
+///
+/// The `tree-sitter-highlight` crate isn't used, since its highlighter parses the whole
+/// buffer each time, and can't re-use the tree from the last parse.
+pub struct TreeSitterHighlightProvider {
+    grammars: Vec<CompiledGrammar>,
+    inner: StdMutex<Inner>,
+}
+
+struct CompiledGrammar {
+    language: Language,
+    query: Query,
+    file_extensions: Vec<String>,
+}
+
+struct Inner {
+    parser: Parser,
+    maybe_parse_state: Option<ParseState>,
+}
+
+/// The tree of the last buffer that was parsed, and the highlights that were found in
+/// it (for the lines up to `highlighted_row_count`).
+struct ParseState {
+    grammar_index: usize,
+    source: String,
+    line_start_byte_indices: Vec<usize>,
+    tree: Tree,
+    theme_version: u64,
+    highlighted_row_count: usize,
+    /// Sorted by start, and outer nodes come before the nodes that are inside them.
+    highlights: Vec<(Range<usize>, SyntectStyle)>,
+}
+
+impl Debug for TreeSitterHighlightProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TreeSitterHighlightProvider")
+            .field(
+                "file_extensions",
+                &self
+                    .grammars
+                    .iter()
+                    .map(|it| &it.file_extensions)
+                    .collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl TreeSitterHighlightProvider {
+    /// Returns an error if the highlights query of one of the `grammars` can't be
+    /// compiled.
+    pub fn try_new(grammars: Vec<TreeSitterGrammar>) -> CommonResult<Self> {
+        let mut compiled_grammars = vec![];
+        for grammar in grammars {
+            let query = match Query::new(&grammar.language, &grammar.highlights_query) {
+                Ok(it) => it,
+                Err(error) => {
+                    let message = format!(
+                        "Failed to compile highlights query for: {:?}, error: {error}",
+                        grammar.file_extensions
+                    );
+                    return CommonError::new_error_result(
+                        CommonErrorType::ParsingError,
+                        &message,
+                    );
+                }
+            };
+            compiled_grammars.push(CompiledGrammar {
+                language: grammar.language,
+                query,
+                file_extensions: grammar.file_extensions,
+            });
+        }
+
+        Ok(Self {
+            grammars: compiled_grammars,
+            inner: StdMutex::new(Inner {
+                parser: Parser::new(),
+                maybe_parse_state: None,
+            }),
+        })
+    }
+
+    /// The grammars that are built into this crate, see [TreeSitterGrammar::rust].
+    pub fn try_new_with_default_grammars() -> CommonResult<Self> {
+        Self::try_new(vec![TreeSitterGrammar::rust()])
+    }
+
+    fn find_grammar_index(&self, editor_buffer: &EditorBuffer) -> Option<usize> {
+        let file_extension = editor_buffer.get_maybe_file_extension()?;
+        self.grammars.iter().position(|it| {
+            it.file_extensions
+                .iter()
+                .any(|it| it.eq_ignore_ascii_case(file_extension))
+        })
+    }
+}
+
+impl HighlightProvider for TreeSitterHighlightProvider {
+    fn sync(
+        &self,
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &EditorEngine,
+        last_row_index: usize,
+    ) {
+        let Some(grammar_index) = self.find_grammar_index(editor_buffer) else {
+            SyntectHighlightProvider.sync(editor_buffer, editor_engine, last_row_index);
+            return;
+        };
+        let grammar = &self.grammars[grammar_index];
+
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        let Inner {
+            parser,
+            maybe_parse_state,
+        } = &mut *inner;
+
+        let source = editor_buffer
+            .get_lines()
+            .iter()
+            .map(|it| it.string.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if parser.set_language(&grammar.language).is_err() {
+            return;
+        }
+
+        let maybe_last_parse_state = maybe_parse_state
+            .take()
+            .filter(|it| it.grammar_index == grammar_index);
+        let tree = match maybe_last_parse_state {
+            // Nothing changed, but more lines (or a different theme) may be needed.
+            Some(last_parse_state) if last_parse_state.source == source => {
+                if last_parse_state.theme_version == editor_engine.theme_version
+                    && last_row_index < last_parse_state.highlighted_row_count
+                {
+                    *maybe_parse_state = Some(last_parse_state);
+                    return;
+                }
+                last_parse_state.tree
+            }
+            // Tell the last tree what changed, so only that part is re-parsed.
+            Some(mut last_parse_state) => {
+                last_parse_state
+                    .tree
+                    .edit(&find_input_edit(&last_parse_state.source, &source));
+                match parser.parse(&source, Some(&last_parse_state.tree)) {
+                    Some(it) => it,
+                    None => return,
+                }
+            }
+            None => match parser.parse(&source, None) {
+                Some(it) => it,
+                None => return,
+            },
+        };
+
+        let line_start_byte_indices = once(0)
+            .chain(source.match_indices('\n').map(|(it, _)| it + 1))
+            .collect::<Vec<_>>();
+        let highlighted_row_count = last_row_index + 1;
+        let end_byte_index = line_start_byte_indices
+            .get(highlighted_row_count)
+            .copied()
+            .unwrap_or(source.len());
+        let highlights = find_highlights(
+            &grammar.query,
+            &tree,
+            &source,
+            0..end_byte_index,
+            &editor_engine.theme,
+        );
+
+        *maybe_parse_state = Some(ParseState {
+            grammar_index,
+            source,
+            line_start_byte_indices,
+            tree,
+            theme_version: editor_engine.theme_version,
+            highlighted_row_count,
+            highlights,
+        });
+    }
+
+    fn highlight_line(
+        &self,
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        row_index: usize,
+    ) -> Option<StyleUSSpanLine> {
+        if self.find_grammar_index(editor_buffer).is_none() {
+            return SyntectHighlightProvider.highlight_line(
+                editor_buffer,
+                editor_engine,
+                row_index,
+            );
+        }
+
+        let line = editor_buffer.get_lines().get(row_index)?.string.as_str();
+        let inner = self.inner.lock().ok()?;
+        let parse_state = inner.maybe_parse_state.as_ref()?;
+        if row_index >= parse_state.highlighted_row_count {
+            return None;
+        }
+        let line_start = *parse_state.line_start_byte_indices.get(row_index)?;
+        let line_end = line_start + line.len();
+        // The last parse was of a different buffer (or an older version of this one).
+        if parse_state.source.get(line_start..line_end) != Some(line) {
+            return None;
+        }
+
+        // Paint the style of each highlight over the bytes of the line, so the nodes
+        // that are inside other nodes win.
+        let mut styles =
+            vec![Highlighter::new(&editor_engine.theme).get_default(); line.len()];
+        let end_index = parse_state
+            .highlights
+            .partition_point(|(range, _)| range.start < line_end);
+        for (range, style) in &parse_state.highlights[..end_index] {
+            if range.end <= line_start {
+                continue;
+            }
+            let start = range.start.max(line_start) - line_start;
+            let end = range.end.min(line_end) - line_start;
+            styles[start..end].fill(*style);
+        }
+
+        // Each run of characters that have the same style is a span.
+        let mut spans: SyntectStyleStrSpanLine<'_> = vec![];
+        let mut span_start = 0;
+        for (byte_index, _) in line.char_indices().skip(1) {
+            if styles[byte_index] != styles[span_start] {
+                spans.push((styles[span_start], &line[span_start..byte_index]));
+                span_start = byte_index;
+            }
+        }
+        if !line.is_empty() {
+            spans.push((styles[span_start], &line[span_start..]));
+        }
+
+        Some(
+            convert_syntect_to_styled_text::convert_highlighted_line_from_syntect_to_tui(
+                spans,
+            ),
+        )
+    }
+}
+
+/// The highlights (w/ the styles from the `theme`) of the captures of the `query` in
+/// the `byte_range` of the `source`.
+fn find_highlights(
+    query: &Query,
+    tree: &Tree,
+    source: &str,
+    byte_range: Range<usize>,
+    theme: &Theme,
+) -> Vec<(Range<usize>, SyntectStyle)> {
+    let highlighter = Highlighter::new(theme);
+    let styles = query
+        .capture_names()
+        .iter()
+        .map(|it| {
+            let scope = Scope::new(get_scope_for_capture_name(it)?).ok()?;
+            Some(highlighter.style_for_stack(&[scope]))
+        })
+        .collect::<Vec<_>>();
+
+    let mut cursor = QueryCursor::new();
+    cursor.set_byte_range(byte_range);
+    let mut highlights = vec![];
+    for (query_match, capture_index) in
+        cursor.captures(query, tree.root_node(), source.as_bytes())
+    {
+        let capture = query_match.captures[capture_index];
+        if let Some(style) = styles[capture.index as usize] {
+            highlights.push((capture.node.byte_range(), style));
+        }
+    }
+
+    // Outer nodes come first, so the nodes inside them are painted over them. And when
+    // a node is captured more than once, the first pattern in the query wins.
+    highlights.sort_by(|(lhs, _), (rhs, _)| {
+        lhs.start.cmp(&rhs.start).then(rhs.end.cmp(&lhs.end))
+    });
+    highlights.dedup_by(|(next, _), (previous, _)| next == previous);
+    highlights
+}
+
+/// The syntect scope (that themes have styles for) of the tree-sitter capture, eg:
+/// `function.method` is painted like `entity.name.function`.
+fn get_scope_for_capture_name(capture_name: &str) -> Option<&'static str> {
+    let scope = match capture_name {
+        "constant.builtin" => "constant.language",
+        "function.macro" => "support.function",
+        "type.builtin" => "storage.type",
+        "variable.builtin" => "variable.language",
+        "variable.parameter" => "variable.parameter",
+        _ => match capture_name.split('.').next()? {
+            "attribute" => "entity.other.attribute-name",
+            "comment" => "comment",
+            "constant" => "constant",
+            "constructor" => "entity.name.type",
+            "escape" => "constant.character.escape",
+            "function" => "entity.name.function",
+            "keyword" => "keyword",
+            "label" => "entity.name.label",
+            "number" => "constant.numeric",
+            "operator" => "keyword.operator",
+            "property" => "variable.other.member",
+            "punctuation" => "punctuation",
+            "string" => "string",
+            "type" => "entity.name.type",
+            _ => return None,
+        },
+    };
+    Some(scope)
+}
+
+/// The edit that turns the `old_source` into the `new_source`, which is the part
+/// between their common prefix & suffix.
+fn find_input_edit(old_source: &str, new_source: &str) -> InputEdit {
+    let (old_bytes, new_bytes) = (old_source.as_bytes(), new_source.as_bytes());
+    let start_byte = old_bytes
+        .iter()
+        .zip(new_bytes)
+        .take_while(|(lhs, rhs)| lhs == rhs)
+        .count();
+    let max_suffix_len = old_bytes.len().min(new_bytes.len()) - start_byte;
+    let suffix_len = old_bytes
+        .iter()
+        .rev()
+        .zip(new_bytes.iter().rev())
+        .take(max_suffix_len)
+        .take_while(|(lhs, rhs)| lhs == rhs)
+        .count();
+    let old_end_byte = old_bytes.len() - suffix_len;
+    let new_end_byte = new_bytes.len() - suffix_len;
+
+    InputEdit {
+        start_byte,
+        old_end_byte,
+        new_end_byte,
+        start_position: find_point(old_bytes, start_byte),
+        old_end_position: find_point(old_bytes, old_end_byte),
+        new_end_position: find_point(new_bytes, new_end_byte),
+    }
+}
+
+/// The row & col (in bytes) of the `byte_index` in the `source`.
+fn find_point(source: &[u8], byte_index: usize) -> Point {
+    let before = &source[..byte_index];
+    let row = before.iter().filter(|it| **it == b'\n').count();
+    let line_start = before
+        .iter()
+        .rposition(|it| *it == b'\n')
+        .map_or(0, |it| it + 1);
+    Point {
+        row,
+        column: byte_index - line_start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, TuiColor};
+
+    use super::*;
+    use crate::test_fixtures::mock_real_objects_for_editor;
+
+    fn make_rust_buffer(lines: &[&str]) -> EditorBuffer {
+        let mut buffer = mock_real_objects_for_editor::make_editor_buffer(lines);
+        buffer.editor_content.maybe_file_extension = Some("rs".to_string());
+        buffer
+    }
+
+    /// The text of each span in the line, w/ its foreground color.
+    fn get_spans(line: &StyleUSSpanLine) -> Vec<(String, Option<TuiColor>)> {
+        line.iter()
+            .map(|it| (it.text.string.clone(), it.style.color_fg))
+            .collect()
+    }
+
+    fn get_scope_color(engine: &EditorEngine, scope: &str) -> Option<TuiColor> {
+        let style = Highlighter::new(&engine.theme)
+            .style_for_stack(&[Scope::new(scope).unwrap()]);
+        convert_syntect_to_styled_text::convert_style_from_syntect_to_tui(style).color_fg
+    }
+
+    #[test]
+    fn test_highlight_rust() {
+        let provider =
+            TreeSitterHighlightProvider::try_new_with_default_grammars().unwrap();
+        let engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_rust_buffer(&["fn main() {", "    // hi", "}"]);
+
+        provider.sync(&mut buffer, &engine, 2);
+
+        let line = provider.highlight_line(&buffer, &engine, 0).unwrap();
+        let spans = get_spans(&line);
+        assert_eq2!(
+            spans.iter().map(|it| it.0.as_str()).collect::<String>(),
+            "fn main() {"
+        );
+        assert_eq2!(spans[0].0, "fn");
+        assert_eq2!(spans[0].1, get_scope_color(&engine, "keyword"));
+
+        let line = provider.highlight_line(&buffer, &engine, 1).unwrap();
+        let spans = get_spans(&line);
+        assert_eq2!(spans.last().unwrap().0, "// hi");
+        assert_eq2!(spans.last().unwrap().1, get_scope_color(&engine, "comment"));
+    }
+
+    #[test]
+    fn test_edits_are_re_parsed() {
+        let provider =
+            TreeSitterHighlightProvider::try_new_with_default_grammars().unwrap();
+        let engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_rust_buffer(&["fn main() {", "}"]);
+        provider.sync(&mut buffer, &engine, 1);
+
+        buffer.set_lines(vec![
+            "fn main() {".to_string(),
+            "    let it = \"abc\";".to_string(),
+            "}".to_string(),
+        ]);
+        provider.sync(&mut buffer, &engine, 2);
+
+        let line = provider.highlight_line(&buffer, &engine, 1).unwrap();
+        let spans = get_spans(&line);
+        assert!(spans.contains(&("let".to_string(), get_scope_color(&engine, "keyword"))));
+        assert!(
+            spans.contains(&("\"abc\"".to_string(), get_scope_color(&engine, "string")))
+        );
+    }
+
+    #[test]
+    fn test_other_buffer_is_not_highlighted_w_stale_tree() {
+        let provider =
+            TreeSitterHighlightProvider::try_new_with_default_grammars().unwrap();
+        let engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_rust_buffer(&["fn main() {}"]);
+        provider.sync(&mut buffer, &engine, 0);
+
+        // This buffer hasn't been synced yet.
+        let other_buffer = make_rust_buffer(&["struct Foo;"]);
+        assert!(provider.highlight_line(&other_buffer, &engine, 0).is_none());
+    }
+
+    #[test]
+    fn test_find_input_edit() {
+        let edit = find_input_edit("ab\ncd\nef", "ab\ncXd\nef");
+        assert_eq2!(edit.start_byte, 4);
+        assert_eq2!(edit.old_end_byte, 4);
+        assert_eq2!(edit.new_end_byte, 5);
+        assert_eq2!(edit.start_position, Point { row: 1, column: 1 });
+        assert_eq2!(edit.new_end_position, Point { row: 1, column: 2 });
+
+        // The common suffix doesn't overlap the common prefix.
+        let edit = find_input_edit("aa", "aaa");
+        assert_eq2!(edit.start_byte, 2);
+        assert_eq2!(edit.old_end_byte, 2);
+        assert_eq2!(edit.new_end_byte, 3);
+    }
+}
@@ -0,0 +1,1009 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Modal (vim like) key bindings for the editor. This is enabled by setting
+//! [EditorEngineConfig::keymap_mode](crate::EditorEngineConfig::keymap_mode) to
+//! [KeymapMode::Vim](crate::KeymapMode::Vim).
+//!
+//! The modal layer sits in front of the conversion of [InputEvent] into [EditorEvent].
+//! In [VimMode::Insert] every key (except <kbd>Esc</kbd>) is handled by the default
+//! key bindings. In [VimMode::Normal] & [VimMode::Visual] the keys are parsed into a
+//! [VimCommand] (w/ an optional count & register), which is then executed as a sequence
+//! of [EditorEvent]s, and is a single undo step.
+//!
+//! The following is supported:
+//! - Modes: `i`, `a`, `I`, `A`, `o`, `O` enter insert mode, `v` enters visual mode, and
+//!   <kbd>Esc</kbd> goes back to normal mode.
+//! - Motions: `h`, `j`, `k`, `l`, `w` & `e` (end of the word), `b`, `0` & `^`, `$`,
+//!   `gg`, `G`.
+//! - Operators: `d`, `c`, `y` followed by a motion, or doubled to act on whole lines (eg:
+//!   `dd`). Also `x`, `X`, `D`, `C`, `J`, `p`, `P`, `u`, <kbd>Ctrl + r</kbd>.
+//! - Counts: eg: `3j`, `2dw`, `5x`, `10G`.
+//! - Registers: eg: `"ayy`, `"ap`. The unnamed register holds the text of the last yank
+//!   or delete. The `+` & `*` registers are the system clipboard. Text that is yanked
+//!   from whole lines ends w/ a new line, and is pasted as whole lines.
+
+use std::{collections::HashMap, mem::take, ops::RangeInclusive};
+
+use r3bl_core::{call_if_true, ch, position, CommonResult};
+use serde::{Deserialize, Serialize};
+
+use crate::{editor_buffer_clipboard_support::{ClipboardResult, ClipboardService},
+            history,
+            validate_editor_buffer_change,
+            CaretDirection,
+            CaretKind,
+            DeleteSelectionWith,
+            EditMode,
+            EditorBuffer,
+            EditorEngine,
+            EditorEngineApplyEventResult,
+            EditorEngineInternalApi,
+            EditorEvent,
+            InputEvent,
+            Key,
+            KeyPress,
+            KeyState,
+            ModifierKeysMask,
+            SelectionAction,
+            SpecialKey,
+            DEBUG_TUI_COPY_PASTE};
+
+/// The name of the register that is used when no register is given.
+pub const VIM_UNNAMED_REGISTER: char = '"';
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VimMode {
+    #[default]
+    Normal,
+    Insert,
+    Visual,
+}
+
+/// Holds the mode, the keys that have been typed so far for a command that isn't
+/// complete yet (eg: `2d` in `2dw`), and the registers. This lives in the
+/// [EditorEngine], so every editor component has its own.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VimState {
+    pub mode: VimMode,
+    /// The text in each register, see [VIM_UNNAMED_REGISTER].
+    pub registers: HashMap<char, String>,
+    maybe_count: Option<usize>,
+    maybe_operator: Option<VimOperator>,
+    maybe_register_name: Option<char>,
+    pending_key: VimPendingKey,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+enum VimPendingKey {
+    #[default]
+    None,
+    /// `g` was typed, waiting for the second `g` of `gg`.
+    G,
+    /// `"` was typed, waiting for the name of the register.
+    RegisterName,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VimOperator {
+    Delete,
+    Change,
+    Yank,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VimMotion {
+    CharLeft,
+    CharRight,
+    LineUp,
+    LineDown,
+    WordLeft,
+    WordRight,
+    LineStart,
+    LineEnd,
+    /// `gg`, or the given line number.
+    FirstLine,
+    /// `G`, or the given line number.
+    LastLine,
+    /// The motion of a doubled operator (eg: `dd`).
+    CurrentLine,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VimInsertPosition {
+    /// `i`
+    AtCaret,
+    /// `a`
+    AfterCaret,
+    /// `I`
+    LineStart,
+    /// `A`
+    LineEnd,
+    /// `o`
+    NewLineBelow,
+    /// `O`
+    NewLineAbove,
+}
+
+/// A complete command, parsed from the keys typed in [VimMode::Normal] or
+/// [VimMode::Visual].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VimCommand {
+    /// The keys typed so far don't make up a complete command yet.
+    Pending,
+    /// The keys typed don't make up a command, and are dropped.
+    Unknown,
+    Move {
+        motion: VimMotion,
+        maybe_count: Option<usize>,
+    },
+    Operate {
+        operator: VimOperator,
+        motion: VimMotion,
+        maybe_count: Option<usize>,
+        maybe_register_name: Option<char>,
+    },
+    Paste {
+        is_before_caret: bool,
+        count: usize,
+        maybe_register_name: Option<char>,
+    },
+    EnterInsertMode(VimInsertPosition),
+    EnterVisualMode,
+    OperateOnSelection {
+        operator: VimOperator,
+        maybe_register_name: Option<char>,
+    },
+    JoinLines(usize),
+    Undo(usize),
+    Redo(usize),
+    /// <kbd>Esc</kbd>
+    EnterNormalMode,
+}
+
+impl VimCommand {
+    /// Whether the command changes the content of the buffer. These commands are not
+    /// applied in [EditMode::ReadOnly], and are a single undo step.
+    pub fn is_mutating(&self) -> bool {
+        match self {
+            VimCommand::Operate { operator, .. }
+            | VimCommand::OperateOnSelection { operator, .. } => {
+                !matches!(operator, VimOperator::Yank)
+            }
+            VimCommand::EnterInsertMode(insert_position) => matches!(
+                insert_position,
+                VimInsertPosition::NewLineBelow | VimInsertPosition::NewLineAbove
+            ),
+            VimCommand::Paste { .. }
+            | VimCommand::JoinLines(_)
+            | VimCommand::Undo(_)
+            | VimCommand::Redo(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The keys that the modal layer cares about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VimKey {
+    Char(char),
+    Esc,
+    CtrlR,
+}
+
+impl VimKey {
+    fn from_input_event(input_event: &InputEvent) -> Option<Self> {
+        match *input_event {
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character(character),
+            }) => Some(VimKey::Char(character)),
+            // Shift + char might not arrive as an upper case char.
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character(character),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::Pressed,
+                        ctrl_key_state: KeyState::NotPressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Some(VimKey::Char(character.to_ascii_uppercase())),
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('r'),
+                mask:
+                    ModifierKeysMask {
+                        shift_key_state: KeyState::NotPressed,
+                        ctrl_key_state: KeyState::Pressed,
+                        alt_key_state: KeyState::NotPressed,
+                    },
+            }) => Some(VimKey::CtrlR),
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Esc),
+            }) => Some(VimKey::Esc),
+            _ => None,
+        }
+    }
+}
+
+mod vim_state_impl {
+    use super::*;
+
+    impl VimState {
+        /// Parse the next key typed in [VimMode::Normal] or [VimMode::Visual]. Once the
+        /// keys make up a complete command (or can't), the keys typed so far are
+        /// dropped.
+        fn parse_key(&mut self, key: VimKey) -> VimCommand {
+            let command = self.parse_key_inner(key);
+            if command != VimCommand::Pending {
+                self.maybe_count = None;
+                self.maybe_operator = None;
+                self.maybe_register_name = None;
+                self.pending_key = VimPendingKey::None;
+            }
+            command
+        }
+
+        fn parse_key_inner(&mut self, key: VimKey) -> VimCommand {
+            let character = match key {
+                VimKey::Esc => return VimCommand::EnterNormalMode,
+                VimKey::CtrlR => return VimCommand::Redo(self.get_count()),
+                VimKey::Char(character) => character,
+            };
+
+            match take(&mut self.pending_key) {
+                VimPendingKey::RegisterName => {
+                    self.maybe_register_name = Some(character);
+                    return VimCommand::Pending;
+                }
+                VimPendingKey::G if character == 'g' => {
+                    return self.parse_motion(VimMotion::FirstLine);
+                }
+                VimPendingKey::G => return VimCommand::Unknown,
+                VimPendingKey::None => {}
+            }
+
+            // `0` is a motion, unless it is part of a count.
+            if let Some(digit) = character.to_digit(10) {
+                if digit != 0 || self.maybe_count.is_some() {
+                    let count = self.maybe_count.unwrap_or(0);
+                    self.maybe_count = Some(count * 10 + digit as usize);
+                    return VimCommand::Pending;
+                }
+            }
+
+            let maybe_motion = match character {
+                'h' => Some(VimMotion::CharLeft),
+                'l' => Some(VimMotion::CharRight),
+                'k' => Some(VimMotion::LineUp),
+                'j' => Some(VimMotion::LineDown),
+                'b' => Some(VimMotion::WordLeft),
+                'w' | 'e' => Some(VimMotion::WordRight),
+                '0' | '^' => Some(VimMotion::LineStart),
+                '$' => Some(VimMotion::LineEnd),
+                'G' => Some(VimMotion::LastLine),
+                _ => None,
+            };
+            if let Some(motion) = maybe_motion {
+                return self.parse_motion(motion);
+            }
+
+            match character {
+                '"' => {
+                    self.pending_key = VimPendingKey::RegisterName;
+                    VimCommand::Pending
+                }
+                'g' => {
+                    self.pending_key = VimPendingKey::G;
+                    VimCommand::Pending
+                }
+                'd' | 'c' | 'y' => {
+                    let operator = match character {
+                        'd' => VimOperator::Delete,
+                        'c' => VimOperator::Change,
+                        _ => VimOperator::Yank,
+                    };
+                    match (self.mode, self.maybe_operator) {
+                        (VimMode::Visual, _) => VimCommand::OperateOnSelection {
+                            operator,
+                            maybe_register_name: self.maybe_register_name,
+                        },
+                        (_, None) => {
+                            self.maybe_operator = Some(operator);
+                            VimCommand::Pending
+                        }
+                        (_, Some(pending_operator)) if pending_operator == operator => {
+                            self.parse_motion(VimMotion::CurrentLine)
+                        }
+                        (_, Some(_)) => VimCommand::Unknown,
+                    }
+                }
+                'x' if self.mode == VimMode::Visual => VimCommand::OperateOnSelection {
+                    operator: VimOperator::Delete,
+                    maybe_register_name: self.maybe_register_name,
+                },
+                'v' if self.mode == VimMode::Visual => VimCommand::EnterNormalMode,
+                _ if self.mode == VimMode::Visual || self.maybe_operator.is_some() => {
+                    VimCommand::Unknown
+                }
+                'x' => self
+                    .parse_operator_shortcut(VimOperator::Delete, VimMotion::CharRight),
+                'X' => {
+                    self.parse_operator_shortcut(VimOperator::Delete, VimMotion::CharLeft)
+                }
+                'D' => {
+                    self.parse_operator_shortcut(VimOperator::Delete, VimMotion::LineEnd)
+                }
+                'C' => {
+                    self.parse_operator_shortcut(VimOperator::Change, VimMotion::LineEnd)
+                }
+                'p' | 'P' => VimCommand::Paste {
+                    is_before_caret: character == 'P',
+                    count: self.get_count(),
+                    maybe_register_name: self.maybe_register_name,
+                },
+                'i' => VimCommand::EnterInsertMode(VimInsertPosition::AtCaret),
+                'a' => VimCommand::EnterInsertMode(VimInsertPosition::AfterCaret),
+                'I' => VimCommand::EnterInsertMode(VimInsertPosition::LineStart),
+                'A' => VimCommand::EnterInsertMode(VimInsertPosition::LineEnd),
+                'o' => VimCommand::EnterInsertMode(VimInsertPosition::NewLineBelow),
+                'O' => VimCommand::EnterInsertMode(VimInsertPosition::NewLineAbove),
+                'v' => VimCommand::EnterVisualMode,
+                'J' => VimCommand::JoinLines(self.get_count()),
+                'u' => VimCommand::Undo(self.get_count()),
+                _ => VimCommand::Unknown,
+            }
+        }
+
+        fn parse_motion(&mut self, motion: VimMotion) -> VimCommand {
+            match self.maybe_operator {
+                Some(operator) => VimCommand::Operate {
+                    operator,
+                    motion,
+                    maybe_count: self.maybe_count,
+                    maybe_register_name: self.maybe_register_name,
+                },
+                None => VimCommand::Move {
+                    motion,
+                    maybe_count: self.maybe_count,
+                },
+            }
+        }
+
+        /// Eg: `x` is the same as `dl`.
+        fn parse_operator_shortcut(
+            &mut self,
+            operator: VimOperator,
+            motion: VimMotion,
+        ) -> VimCommand {
+            self.maybe_operator = Some(operator);
+            self.parse_motion(motion)
+        }
+
+        fn get_count(&self) -> usize { self.maybe_count.unwrap_or(1) }
+
+        fn set_register(
+            &mut self,
+            maybe_register_name: Option<char>,
+            text: String,
+            clipboard_service_provider: &mut impl ClipboardService,
+        ) {
+            match maybe_register_name {
+                Some('+' | '*') => {
+                    let result = clipboard_service_provider
+                        .try_to_put_content_into_clipboard(text.clone());
+                    if let Err(error) = result {
+                        call_if_true!(DEBUG_TUI_COPY_PASTE, {
+                            tracing::debug!(
+                                "\n📋📋📋 Failed to copy register to clipboard: {error}"
+                            );
+                        });
+                    }
+                }
+                // An upper case register name appends to the register.
+                Some(name) if name.is_ascii_uppercase() => {
+                    self.registers
+                        .entry(name.to_ascii_lowercase())
+                        .or_default()
+                        .push_str(&text);
+                }
+                Some(name) if name.is_ascii_lowercase() => {
+                    self.registers.insert(name, text.clone());
+                }
+                _ => {}
+            }
+            self.registers.insert(VIM_UNNAMED_REGISTER, text);
+        }
+
+        fn get_register(
+            &self,
+            maybe_register_name: Option<char>,
+            clipboard_service_provider: &mut impl ClipboardService,
+        ) -> Option<String> {
+            match maybe_register_name {
+                Some('+' | '*') => clipboard_service_provider
+                    .try_to_get_content_from_clipboard()
+                    .ok(),
+                Some(name) => self.registers.get(&name.to_ascii_lowercase()).cloned(),
+                None => self.registers.get(&VIM_UNNAMED_REGISTER).cloned(),
+            }
+        }
+    }
+}
+
+/// A [ClipboardService] that is used to move text between the editor & a register, when
+/// [EditorEvent::Copy], [EditorEvent::Cut] & [EditorEvent::Paste] are applied.
+#[derive(Default)]
+struct RegisterClipboard {
+    content: String,
+}
+
+impl ClipboardService for RegisterClipboard {
+    fn try_to_put_content_into_clipboard(
+        &mut self,
+        content: String,
+    ) -> ClipboardResult<()> {
+        self.content = content;
+        Ok(())
+    }
+
+    fn try_to_get_content_from_clipboard(&mut self) -> ClipboardResult<String> {
+        Ok(self.content.clone())
+    }
+}
+
+pub struct VimKeymap;
+
+impl VimKeymap {
+    /// Apply the [InputEvent] to the buffer using the vim key bindings. Returns [None]
+    /// if the event should be handled by the default key bindings instead (eg: any key
+    /// in [VimMode::Insert] other than <kbd>Esc</kbd>).
+    pub fn apply_event(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        input_event: &InputEvent,
+        clipboard_service_provider: &mut impl ClipboardService,
+    ) -> CommonResult<Option<EditorEngineApplyEventResult>> {
+        let maybe_vim_key = VimKey::from_input_event(input_event);
+
+        if editor_engine.vim_state.mode == VimMode::Insert {
+            if maybe_vim_key != Some(VimKey::Esc) {
+                return Ok(None);
+            }
+            editor_engine.vim_state.mode = VimMode::Normal;
+            // Like vim, the caret moves onto the last char that was typed.
+            move_caret_by_chars(editor_buffer, editor_engine, CaretDirection::Left, 1);
+            return Ok(Some(EditorEngineApplyEventResult::Applied));
+        }
+
+        let Some(vim_key) = maybe_vim_key else {
+            // Keys that vim doesn't use (eg: arrow keys, Ctrl + S, mouse events) are
+            // handled by the default key bindings, as long as they don't change the
+            // content.
+            let key_binding_table = &editor_engine.config_options.key_binding_table;
+            return match key_binding_table.try_convert(input_event.clone()) {
+                Ok(editor_event) if !editor_event.is_mutating() => Ok(None),
+                _ => Ok(Some(EditorEngineApplyEventResult::NotApplied)),
+            };
+        };
+
+        let command = editor_engine.vim_state.parse_key(vim_key);
+        match command {
+            VimCommand::Pending => {
+                return Ok(Some(EditorEngineApplyEventResult::Applied));
+            }
+            VimCommand::Unknown => {
+                return Ok(Some(EditorEngineApplyEventResult::NotApplied));
+            }
+            _ => {}
+        }
+
+        if command.is_mutating() {
+            if matches!(editor_engine.config_options.edit_mode, EditMode::ReadOnly) {
+                return Ok(Some(EditorEngineApplyEventResult::NotApplied));
+            }
+            if editor_buffer.history.is_empty() {
+                history::push(editor_buffer);
+            }
+        }
+
+        // The state is taken out of the engine so that both can be mutated.
+        let mut vim_state = take(&mut editor_engine.vim_state);
+        execute_command(
+            &mut vim_state,
+            editor_buffer,
+            editor_engine,
+            command,
+            clipboard_service_provider,
+        );
+        editor_engine.vim_state = vim_state;
+
+        if command.is_mutating()
+            && !matches!(command, VimCommand::Undo(_) | VimCommand::Redo(_))
+        {
+            editor_buffer.set_dirty(true);
+            history::push(editor_buffer);
+        }
+
+        Ok(Some(EditorEngineApplyEventResult::Applied))
+    }
+}
+
+fn execute_command(
+    vim_state: &mut VimState,
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    command: VimCommand,
+    clipboard_service_provider: &mut impl ClipboardService,
+) {
+    match command {
+        VimCommand::Pending | VimCommand::Unknown => {}
+
+        VimCommand::EnterNormalMode => {
+            vim_state.mode = VimMode::Normal;
+            EditorEngineInternalApi::clear_selection(buffer);
+        }
+
+        VimCommand::Move {
+            motion,
+            maybe_count,
+        } => match vim_state.mode {
+            VimMode::Visual => {
+                if let Some(selection_action) = get_selection_action(motion) {
+                    let editor_event = EditorEvent::Select(selection_action);
+                    apply_n_times(buffer, engine, editor_event, maybe_count.unwrap_or(1));
+                }
+            }
+            _ => move_caret(buffer, engine, motion, maybe_count),
+        },
+
+        VimCommand::Operate {
+            operator,
+            motion,
+            maybe_count,
+            maybe_register_name,
+        } => {
+            if let Some(row_indices) = get_row_indices(buffer, motion, maybe_count) {
+                operate_on_lines(
+                    vim_state,
+                    buffer,
+                    engine,
+                    operator,
+                    row_indices,
+                    maybe_register_name,
+                    clipboard_service_provider,
+                );
+            } else if let Some(selection_action) = get_selection_action(motion) {
+                let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+                let count = match motion {
+                    VimMotion::CharLeft | VimMotion::CharRight => {
+                        get_char_count(buffer, motion, maybe_count.unwrap_or(1))
+                    }
+                    _ => maybe_count.unwrap_or(1),
+                };
+                apply_n_times(
+                    buffer,
+                    engine,
+                    EditorEvent::Select(selection_action),
+                    count,
+                );
+                operate_on_selection(
+                    vim_state,
+                    buffer,
+                    engine,
+                    operator,
+                    maybe_register_name,
+                    clipboard_service_provider,
+                );
+                // A yank leaves the caret at the start of the text.
+                let new_caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+                if operator == VimOperator::Yank
+                    && (caret_adj.row_index, caret_adj.col_index)
+                        < (new_caret_adj.row_index, new_caret_adj.col_index)
+                {
+                    EditorEngineInternalApi::move_caret_to_buffer_position(
+                        buffer, engine, caret_adj,
+                    );
+                }
+            }
+        }
+
+        VimCommand::OperateOnSelection {
+            operator,
+            maybe_register_name,
+        } => {
+            operate_on_selection(
+                vim_state,
+                buffer,
+                engine,
+                operator,
+                maybe_register_name,
+                clipboard_service_provider,
+            );
+        }
+
+        VimCommand::Paste {
+            is_before_caret,
+            count,
+            maybe_register_name,
+        } => {
+            let Some(text) =
+                vim_state.get_register(maybe_register_name, clipboard_service_provider)
+            else {
+                return;
+            };
+            match text.strip_suffix('\n') {
+                // Whole lines are pasted below (or above) the line w/ the caret.
+                Some(lines_text) => {
+                    let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+                    let row_index = ch!(@to_usize caret_adj.row_index)
+                        + if is_before_caret { 0 } else { 1 };
+                    let lines = (0..count)
+                        .flat_map(|_| lines_text.split('\n').map(String::from))
+                        .collect();
+                    insert_lines(buffer, engine, row_index, lines);
+                }
+                None => {
+                    if !is_before_caret {
+                        move_caret_by_chars(buffer, engine, CaretDirection::Right, 1);
+                    }
+                    let mut register_clipboard = RegisterClipboard { content: text };
+                    for _ in 0..count {
+                        EditorEvent::apply_editor_event(
+                            engine,
+                            buffer,
+                            EditorEvent::Paste,
+                            &mut register_clipboard,
+                        );
+                    }
+                }
+            }
+        }
+
+        VimCommand::EnterInsertMode(insert_position) => {
+            vim_state.mode = VimMode::Insert;
+            let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+            let row_index = ch!(@to_usize caret_adj.row_index);
+            match insert_position {
+                VimInsertPosition::AtCaret => {}
+                VimInsertPosition::AfterCaret => {
+                    move_caret_by_chars(buffer, engine, CaretDirection::Right, 1);
+                }
+                VimInsertPosition::LineStart => apply(buffer, engine, EditorEvent::Home),
+                VimInsertPosition::LineEnd => apply(buffer, engine, EditorEvent::End),
+                VimInsertPosition::NewLineBelow => {
+                    insert_lines(buffer, engine, row_index + 1, vec![String::new()]);
+                }
+                VimInsertPosition::NewLineAbove => {
+                    insert_lines(buffer, engine, row_index, vec![String::new()]);
+                }
+            }
+        }
+
+        VimCommand::EnterVisualMode => {
+            vim_state.mode = VimMode::Visual;
+            // Like vim, the char at the caret is selected.
+            if get_char_count(buffer, VimMotion::CharRight, 1) > 0 {
+                apply(
+                    buffer,
+                    engine,
+                    EditorEvent::Select(SelectionAction::OneCharRight),
+                );
+            }
+        }
+
+        // Like vim, `3J` joins 3 lines (ie: 2 joins).
+        VimCommand::JoinLines(count) => {
+            let join_count = count.saturating_sub(1).max(1);
+            apply_n_times(buffer, engine, EditorEvent::JoinLines, join_count);
+        }
+
+        VimCommand::Undo(count) => {
+            apply_n_times(buffer, engine, EditorEvent::Undo, count)
+        }
+
+        VimCommand::Redo(count) => {
+            apply_n_times(buffer, engine, EditorEvent::Redo, count)
+        }
+    }
+}
+
+/// Apply an event that doesn't use the clipboard.
+fn apply(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    editor_event: EditorEvent,
+) {
+    EditorEvent::apply_editor_event(
+        engine,
+        buffer,
+        editor_event,
+        &mut RegisterClipboard::default(),
+    );
+}
+
+fn apply_n_times(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    editor_event: EditorEvent,
+    count: usize,
+) {
+    for _ in 0..count {
+        apply(buffer, engine, editor_event.clone());
+    }
+}
+
+fn move_caret(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    motion: VimMotion,
+    maybe_count: Option<usize>,
+) {
+    let count = maybe_count.unwrap_or(1);
+    match motion {
+        VimMotion::CharLeft => {
+            move_caret_by_chars(buffer, engine, CaretDirection::Left, count)
+        }
+        VimMotion::CharRight => {
+            move_caret_by_chars(buffer, engine, CaretDirection::Right, count)
+        }
+        VimMotion::LineUp => apply_n_times(
+            buffer,
+            engine,
+            EditorEvent::MoveCaret(CaretDirection::Up),
+            count,
+        ),
+        VimMotion::LineDown => {
+            let editor_event = EditorEvent::MoveCaret(CaretDirection::Down);
+            apply_n_times(buffer, engine, editor_event, count)
+        }
+        VimMotion::WordLeft => {
+            apply_n_times(buffer, engine, EditorEvent::WordLeft, count)
+        }
+        VimMotion::WordRight => {
+            apply_n_times(buffer, engine, EditorEvent::WordRight, count)
+        }
+        VimMotion::FirstLine | VimMotion::LastLine if maybe_count.is_some() => {
+            apply(buffer, engine, EditorEvent::GoToLine(count))
+        }
+        VimMotion::FirstLine => apply(buffer, engine, EditorEvent::GoToTop),
+        VimMotion::LastLine => apply(buffer, engine, EditorEvent::GoToBottom),
+        VimMotion::LineStart => apply(buffer, engine, EditorEvent::Home),
+        VimMotion::LineEnd => apply(buffer, engine, EditorEvent::End),
+        VimMotion::CurrentLine => {}
+    }
+}
+
+/// Unlike [EditorEvent::MoveCaret], the caret doesn't wrap to the next (or previous)
+/// line.
+fn move_caret_by_chars(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    direction: CaretDirection,
+    count: usize,
+) {
+    let motion = match direction {
+        CaretDirection::Left => VimMotion::CharLeft,
+        _ => VimMotion::CharRight,
+    };
+    for _ in 0..get_char_count(buffer, motion, count) {
+        apply(buffer, engine, EditorEvent::MoveCaret(direction.clone()));
+    }
+}
+
+/// Returns how many of the `count` chars (grapheme clusters) to the left or right of
+/// the caret are in the line w/ the caret.
+fn get_char_count(buffer: &EditorBuffer, motion: VimMotion, count: usize) -> usize {
+    let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+    let Some(line) = buffer.get_lines().get(ch!(@to_usize caret_adj.row_index)) else {
+        return 0;
+    };
+    let segments = line.vec_segment.iter();
+    let available_count = match motion {
+        VimMotion::CharLeft => segments
+            .filter(|it| it.display_col_offset < caret_adj.col_index)
+            .count(),
+        _ => segments
+            .filter(|it| it.display_col_offset >= caret_adj.col_index)
+            .count(),
+    };
+    count.min(available_count)
+}
+
+fn get_selection_action(motion: VimMotion) -> Option<SelectionAction> {
+    match motion {
+        VimMotion::CharLeft => Some(SelectionAction::OneCharLeft),
+        VimMotion::CharRight => Some(SelectionAction::OneCharRight),
+        VimMotion::LineUp => Some(SelectionAction::OneLineUp),
+        VimMotion::LineDown => Some(SelectionAction::OneLineDown),
+        VimMotion::WordLeft => Some(SelectionAction::OneWordLeft),
+        VimMotion::WordRight => Some(SelectionAction::OneWordRight),
+        VimMotion::LineStart => Some(SelectionAction::Home),
+        VimMotion::LineEnd => Some(SelectionAction::End),
+        VimMotion::FirstLine | VimMotion::LastLine | VimMotion::CurrentLine => None,
+    }
+}
+
+/// Returns the rows that an operator acts on for motions that move between lines (eg:
+/// `dj`, `dd`, `yG`). Returns [None] for the other motions.
+fn get_row_indices(
+    buffer: &EditorBuffer,
+    motion: VimMotion,
+    maybe_count: Option<usize>,
+) -> Option<RangeInclusive<usize>> {
+    if buffer.get_lines().is_empty() {
+        return None;
+    }
+
+    let row_index = ch!(@to_usize buffer.get_caret(CaretKind::ScrollAdjusted).row_index);
+    let max_row_index = buffer.get_lines().len().saturating_sub(1);
+    let count = maybe_count.unwrap_or(1);
+    let other_row_index = match motion {
+        VimMotion::CurrentLine => row_index + count - 1,
+        VimMotion::LineDown => row_index + count,
+        VimMotion::LineUp => row_index.saturating_sub(count),
+        VimMotion::FirstLine => maybe_count.map(|it| it.saturating_sub(1)).unwrap_or(0),
+        VimMotion::LastLine => maybe_count
+            .map(|it| it.saturating_sub(1))
+            .unwrap_or(max_row_index),
+        _ => return None,
+    }
+    .min(max_row_index);
+    Some(row_index.min(other_row_index)..=row_index.max(other_row_index))
+}
+
+fn operate_on_lines(
+    vim_state: &mut VimState,
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    operator: VimOperator,
+    row_indices: RangeInclusive<usize>,
+    maybe_register_name: Option<char>,
+    clipboard_service_provider: &mut impl ClipboardService,
+) {
+    let text = buffer.get_lines()[row_indices.clone()]
+        .iter()
+        .map(|it| format!("{}\n", it.string))
+        .collect::<String>();
+    vim_state.set_register(maybe_register_name, text, clipboard_service_provider);
+
+    let start_row_index = *row_indices.start();
+    let caret_adj = buffer.get_caret(CaretKind::ScrollAdjusted);
+    match operator {
+        VimOperator::Yank => {
+            if ch!(@to_usize caret_adj.row_index) == start_row_index {
+                return;
+            }
+        }
+        VimOperator::Delete => {
+            validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+                lines.drain(row_indices);
+                if lines.is_empty() {
+                    lines.push(String::new().into());
+                }
+            });
+        }
+        VimOperator::Change => {
+            validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+                lines.drain(start_row_index + 1..=*row_indices.end());
+                lines[start_row_index] = String::new().into();
+            });
+        }
+    }
+
+    let max_row_index = buffer.get_lines().len().saturating_sub(1);
+    let new_caret_adj = {
+        let row_index = ch!(start_row_index.min(max_row_index));
+        position!(col_index: 0, row_index: row_index)
+    };
+    EditorEngineInternalApi::move_caret_to_buffer_position(buffer, engine, new_caret_adj);
+
+    if operator == VimOperator::Change {
+        vim_state.mode = VimMode::Insert;
+    }
+}
+
+fn operate_on_selection(
+    vim_state: &mut VimState,
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    operator: VimOperator,
+    maybe_register_name: Option<char>,
+    clipboard_service_provider: &mut impl ClipboardService,
+) {
+    let mut register_clipboard = RegisterClipboard::default();
+    EditorEngineInternalApi::copy_editor_selection_to_clipboard(
+        buffer,
+        &mut register_clipboard,
+    );
+    if operator != VimOperator::Yank {
+        delete_selection(buffer, engine);
+    }
+    EditorEngineInternalApi::clear_selection(buffer);
+
+    if !register_clipboard.content.is_empty() {
+        vim_state.set_register(
+            maybe_register_name,
+            register_clipboard.content,
+            clipboard_service_provider,
+        );
+    }
+
+    vim_state.mode = match operator {
+        VimOperator::Change => VimMode::Insert,
+        _ => VimMode::Normal,
+    };
+}
+
+/// [EditorEvent::Cut] removes a line if all of it is selected, but in vim deleting all
+/// the chars in a line (eg: `x` on a line w/ one char, or `D` at its start) leaves an
+/// empty line. So a selection in a single line is removed here instead.
+fn delete_selection(buffer: &mut EditorBuffer, engine: &mut EditorEngine) {
+    let selection_map = buffer.get_selection_map();
+    let maybe_single_row = match selection_map.map.len() {
+        1 => selection_map
+            .map
+            .iter()
+            .next()
+            .map(|(row_index, range)| (*row_index, *range)),
+        _ => None,
+    };
+
+    let Some((row_index, range)) = maybe_single_row else {
+        EditorEngineInternalApi::delete_selected(
+            buffer,
+            engine,
+            DeleteSelectionWith::AnyKey,
+        );
+        return;
+    };
+
+    let new_line = {
+        let line = &buffer.get_lines()[ch!(@to_usize row_index)];
+        let end_width = line.display_width - range.end_display_col_index;
+        format!(
+            "{}{}",
+            line.clip_to_width(ch!(0), range.start_display_col_index),
+            line.clip_to_width(range.end_display_col_index, end_width)
+        )
+    };
+    validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+        lines[ch!(@to_usize row_index)] = new_line.into();
+    });
+
+    let caret_adj = {
+        let col_index = range.start_display_col_index;
+        position!(col_index: col_index, row_index: row_index)
+    };
+    EditorEngineInternalApi::move_caret_to_buffer_position(buffer, engine, caret_adj);
+}
+
+/// Insert the `lines` before the line at `row_index`, and move the caret to the start
+/// of the first one.
+fn insert_lines(
+    buffer: &mut EditorBuffer,
+    engine: &mut EditorEngine,
+    row_index: usize,
+    lines_to_insert: Vec<String>,
+) {
+    let row_index = row_index.min(buffer.get_lines().len());
+    validate_editor_buffer_change::apply_change(buffer, engine, |lines, _, _| {
+        for (offset, line) in lines_to_insert.into_iter().enumerate() {
+            lines.insert(row_index + offset, line.into());
+        }
+    });
+    let caret_adj = position!(col_index: 0, row_index: ch!(row_index));
+    EditorEngineInternalApi::move_caret_to_buffer_position(buffer, engine, caret_adj);
+}
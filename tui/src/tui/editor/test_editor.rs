@@ -1644,6 +1644,21 @@ mod test_editor_ops {
             assert_eq2!(result.unwrap().unicode_string_seg.string, "░");
         }
     }
+
+    #[test]
+    fn editor_buffer_eq_ignores_content_version() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["abc".into(), "def".into()]);
+        let copy = buffer.clone();
+
+        // Mutable access bumps the content version, w/out changing the content.
+        let _ = buffer.get_mut();
+        assert_eq2!(buffer, copy);
+
+        buffer.set_lines(vec!["abc".into()]);
+        assert!(buffer != copy);
+    }
 }
 
 #[cfg(test)]
@@ -2098,3 +2113,4300 @@ mod clipboard_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod test_line_number_gutter {
+    use r3bl_core::{assert_eq2, ch, position, size};
+
+    use crate::{line_number_gutter,
+                test_fixtures::mock_real_objects_for_editor,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEngineConfig,
+                FlexBox,
+                HasFocus,
+                LineNumberGutterMode,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn make_engine_with_gutter(mode: LineNumberGutterMode) -> EditorEngine {
+        mock_real_objects_for_editor::make_editor_engine_with_config(EditorEngineConfig {
+            line_number_gutter: mode,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_gutter_width() {
+        let engine = make_engine_with_gutter(LineNumberGutterMode::Disable);
+        assert_eq2!(engine.line_number_gutter_width(5), ch!(0));
+
+        let engine = make_engine_with_gutter(LineNumberGutterMode::Absolute);
+        assert_eq2!(engine.line_number_gutter_width(0), ch!(2));
+        assert_eq2!(engine.line_number_gutter_width(9), ch!(2));
+        assert_eq2!(engine.line_number_gutter_width(10), ch!(3));
+        assert_eq2!(engine.line_number_gutter_width(100), ch!(4));
+
+        let engine = make_engine_with_gutter(LineNumberGutterMode::Relative);
+        assert_eq2!(engine.line_number_gutter_width(42), ch!(3));
+    }
+
+    #[test]
+    fn test_line_number_to_display() {
+        let absolute = LineNumberGutterMode::Absolute;
+        assert_eq2!(
+            line_number_gutter::line_number_to_display(absolute, ch!(0), ch!(3)),
+            1
+        );
+        assert_eq2!(
+            line_number_gutter::line_number_to_display(absolute, ch!(5), ch!(3)),
+            6
+        );
+
+        let relative = LineNumberGutterMode::Relative;
+        assert_eq2!(
+            line_number_gutter::line_number_to_display(relative, ch!(3), ch!(3)),
+            4
+        );
+        assert_eq2!(
+            line_number_gutter::line_number_to_display(relative, ch!(1), ch!(3)),
+            2
+        );
+        assert_eq2!(
+            line_number_gutter::line_number_to_display(relative, ch!(5), ch!(3)),
+            2
+        );
+    }
+
+    #[test]
+    fn test_gutter_shrinks_content_viewport() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((1..=12).map(|it| it.to_string()).collect());
+
+        let mut engine = make_engine_with_gutter(LineNumberGutterMode::Absolute);
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 2, row_index: 1),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 5),
+            ..Default::default()
+        };
+
+        EditorEngineApi::render_engine(
+            &mut engine,
+            &mut buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 40, row_count: 10),
+        )
+        .unwrap();
+
+        // 12 lines -> 2 digits + 1 col of padding.
+        assert_eq2!(engine.viewport_width(), ch!(17));
+        assert_eq2!(engine.viewport_height(), ch!(5));
+        assert_eq2!(
+            engine.current_box.style_adjusted_origin_pos,
+            position!(col_index: 5, row_index: 1)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_mouse_events {
+    use r3bl_core::{assert_eq2, ch, position, SelectionRange};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              make_editor_buffer},
+                Button,
+                CaretKind,
+                EditorBuffer,
+                EditorEvent,
+                InputEvent,
+                ModifierKeysMask,
+                MouseAction,
+                MouseInput,
+                MouseInputKind,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn make_buffer() -> EditorBuffer { make_editor_buffer(&["abc", "abcdef", "ab"]) }
+
+    #[test]
+    fn test_convert_mouse_input_to_editor_event() {
+        let pos = position!(col_index: 3, row_index: 4);
+
+        let click = InputEvent::Mouse(MouseInput {
+            pos,
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: None,
+        });
+        assert!(matches!(
+            EditorEvent::try_from(click),
+            Ok(EditorEvent::Mouse(MouseAction::PlaceCaret(it))) if it == pos
+        ));
+
+        let shift_click = InputEvent::Mouse(MouseInput {
+            pos,
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: Some(ModifierKeysMask::new().with_shift()),
+        });
+        assert!(matches!(
+            EditorEvent::try_from(shift_click),
+            Ok(EditorEvent::Mouse(MouseAction::SelectTo(it))) if it == pos
+        ));
+
+        let drag = InputEvent::Mouse(MouseInput {
+            pos,
+            kind: MouseInputKind::MouseDrag(Button::Left),
+            maybe_modifier_keys: None,
+        });
+        assert!(matches!(
+            EditorEvent::try_from(drag),
+            Ok(EditorEvent::Mouse(MouseAction::SelectTo(it))) if it == pos
+        ));
+
+        let wheel = InputEvent::Mouse(MouseInput {
+            pos,
+            kind: MouseInputKind::ScrollDown,
+            maybe_modifier_keys: None,
+        });
+        assert!(matches!(
+            EditorEvent::try_from(wheel),
+            Ok(EditorEvent::Mouse(MouseAction::ScrollDown))
+        ));
+
+        let right_click = InputEvent::Mouse(MouseInput {
+            pos,
+            kind: MouseInputKind::MouseDown(Button::Right),
+            maybe_modifier_keys: None,
+        });
+        assert!(EditorEvent::try_from(right_click).is_err());
+    }
+
+    #[test]
+    fn test_click_places_caret() {
+        let mut buffer = make_buffer();
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Mouse(MouseAction::PlaceCaret(
+                position!(col_index: 4, row_index: 1),
+            ))],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+
+        // Clicking past the end of a line puts the caret at the end of that line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Mouse(MouseAction::PlaceCaret(
+                position!(col_index: 8, row_index: 2),
+            ))],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 2)
+        );
+
+        // Clicking below the last line puts the caret on the last line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Mouse(MouseAction::PlaceCaret(
+                position!(col_index: 1, row_index: 7),
+            ))],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn test_click_outside_box_is_ignored() {
+        let mut buffer = make_buffer();
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Mouse(MouseAction::PlaceCaret(
+                position!(col_index: 20, row_index: 1),
+            ))],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_drag_selects() {
+        let mut buffer = make_buffer();
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Mouse(MouseAction::PlaceCaret(
+                    position!(col_index: 1, row_index: 0),
+                )),
+                EditorEvent::Mouse(MouseAction::SelectTo(
+                    position!(col_index: 2, row_index: 0),
+                )),
+                EditorEvent::Mouse(MouseAction::SelectTo(
+                    position!(col_index: 3, row_index: 0),
+                )),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(1), ch!(3)))
+        );
+
+        // A click clears the selection.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Mouse(MouseAction::PlaceCaret(
+                position!(col_index: 0, row_index: 1),
+            ))],
+            &mut TestClipboard::default(),
+        );
+        assert!(!buffer.has_selection());
+    }
+
+    #[test]
+    fn test_wheel_scrolls_viewport() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((0..30).map(|it| it.to_string()).collect());
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Mouse(MouseAction::ScrollDown); 3],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(3));
+        // The caret was at the top of the viewport, so it moves along w/ it.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 3)
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Mouse(MouseAction::ScrollUp); 5],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(0));
+        // The caret stays on the same line of the buffer.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 3)
+        );
+    }
+
+    #[test]
+    fn test_wheel_scroll_up_keeps_caret_in_viewport() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((0..30).map(|it| it.to_string()).collect());
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Put the caret on the last row of the viewport (which is 10 rows tall).
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Mouse(MouseAction::ScrollDown),
+                EditorEvent::Mouse(MouseAction::ScrollDown),
+                EditorEvent::Mouse(MouseAction::ScrollDown),
+                EditorEvent::Mouse(MouseAction::PlaceCaret(
+                    position!(col_index: 0, row_index: 9),
+                )),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_caret(CaretKind::Raw).row_index, ch!(9));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 12)
+        );
+
+        // The caret can't move below the viewport, so it moves up a line in the buffer.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Mouse(MouseAction::ScrollUp)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(2));
+        assert_eq2!(buffer.get_caret(CaretKind::Raw).row_index, ch!(9));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 11)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_tabs {
+    use r3bl_core::{assert_eq2, ch, position, size};
+
+    use crate::{render_ops,
+                system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                CaretKind,
+                EditorBuffer,
+                EditorEngineApi,
+                EditorEngineApplyEventResult,
+                EditorEngineInternalApi,
+                EditorEvent,
+                FlexBox,
+                HasFocus,
+                InputEvent,
+                Key,
+                KeyPress,
+                RenderOp,
+                SpecialKey,
+                TabMode,
+                ZOrder,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    #[test]
+    fn test_expand_tabs() {
+        assert_eq2!(EditorEngineInternalApi::expand_tabs("\t", 4), "    ");
+        assert_eq2!(EditorEngineInternalApi::expand_tabs("a\tb", 4), "a   b");
+        assert_eq2!(
+            EditorEngineInternalApi::expand_tabs("abcd\te", 4),
+            "abcd    e"
+        );
+        assert_eq2!(EditorEngineInternalApi::expand_tabs("a\t\tb", 2), "a   b");
+        assert_eq2!(EditorEngineInternalApi::expand_tabs("😃\tb", 4), "😃  b");
+        assert_eq2!(
+            EditorEngineInternalApi::expand_tabs_from_col("\tb", ch!(1), 4),
+            "   b"
+        );
+    }
+
+    #[test]
+    fn test_tab_inserts_spaces_up_to_next_tab_stop() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("ab".into()),
+                EditorEvent::InsertTab,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "ab  ");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertTab],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "ab      ");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 8, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_tabs_in_content_are_kept() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["\tx".into(), "ab\tc".into()]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.tab_width = 2;
+
+        let result = EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::End),
+            }),
+            &mut TestClipboard::default(),
+        );
+        assert!(matches!(result, Ok(EditorEngineApplyEventResult::Applied)));
+
+        // The tabs are still there, and each one is as wide as it takes to get to the
+        // next tab stop.
+        assert_eq2!(buffer.get_lines()[0].string, "\tx");
+        assert_eq2!(buffer.get_lines()[1].string, "ab\tc");
+        assert_eq2!(buffer.get_lines()[0].display_width, ch!(3));
+        assert_eq2!(buffer.get_lines()[1].display_width, ch!(5));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_tabs_are_only_laid_out_again_when_tab_width_changes() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["a\tb".into()]);
+
+        buffer.sync_tab_width(2);
+        assert_eq2!(buffer.get_lines()[0].display_width, ch!(3));
+
+        // Nothing is laid out again (so the render cache is kept) w/ the same tab width.
+        buffer.render_cache.insert("key".into(), render_ops!());
+        buffer.sync_tab_width(2);
+        assert!(buffer.render_cache.contains_key("key"));
+
+        buffer.sync_tab_width(4);
+        assert_eq2!(buffer.get_lines()[0].display_width, ch!(5));
+        assert!(buffer.render_cache.is_empty());
+
+        // The lines that are set w/out the engine are laid out again.
+        buffer.set_lines(vec!["\tb".into()]);
+        buffer.sync_tab_width(4);
+        assert_eq2!(buffer.get_lines()[0].display_width, ch!(5));
+    }
+
+    #[test]
+    fn test_render_does_not_change_tabs() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["a\tb".into()]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let content_version = buffer.get_content_version();
+
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 10),
+            ..Default::default()
+        };
+        let render_ops = EditorEngineApi::render_engine(
+            &mut engine,
+            &mut buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 20, row_count: 10),
+        )
+        .unwrap()
+        .get_all_render_op_in(ZOrder::Normal)
+        .unwrap();
+
+        // The tab is painted as spaces, and the content is left alone.
+        assert!(render_ops.iter().all(|it| match it {
+            RenderOp::PaintTextWithAttributes(text, _) => !text.contains('\t'),
+            _ => true,
+        }));
+        assert_eq2!(buffer.get_lines()[0].string, "a\tb");
+        assert_eq2!(buffer.get_content_version(), content_version);
+    }
+
+    #[test]
+    fn test_tab_inserts_tab_character() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.tab_mode = TabMode::InsertTab;
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("ab".into()),
+                EditorEvent::InsertTab,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "ab\t");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        // Typing before the tab makes it narrower, and the caret stays after the text.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Home, EditorEvent::InsertString("x".into())],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "xab\t");
+        assert_eq2!(buffer.get_lines()[0].display_width, ch!(4));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+
+        // The whole tab is deleted at once.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::Backspace],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "xab");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_tab_pass_through() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.tab_mode = TabMode::PassThrough;
+
+        let result = EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Tab),
+            }),
+            &mut TestClipboard::default(),
+        );
+        assert!(matches!(
+            result,
+            Ok(EditorEngineApplyEventResult::NotApplied)
+        ));
+        assert_eq2!(buffer.get_lines()[0].string, "");
+    }
+}
+
+#[cfg(test)]
+mod test_auto_indent {
+    use r3bl_core::{assert_eq2, ch, position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                CaretDirection,
+                CaretKind,
+                EditorBuffer,
+                EditorEvent,
+                SelectionAction,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    #[test]
+    fn test_enter_copies_indent() {
+        let mut buffer = EditorBuffer::new_empty(&Some("rs".to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("    - a".into()),
+                EditorEvent::InsertNewLine,
+            ],
+            &mut TestClipboard::default(),
+        );
+        // List markers are only carried over in markdown.
+        assert_eq2!(buffer.get_lines()[1].string, "    ");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_enter_continues_markdown_list() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("1. a".into()),
+                EditorEvent::InsertNewLine,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[1].string, "2. ");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 1)
+        );
+
+        // Enter on an empty list item ends the list.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertNewLine],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.len(), ch!(2));
+        assert_eq2!(buffer.get_lines()[1].string, "");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_enter_in_middle_of_line() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("> ab".into()),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::InsertNewLine,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "> a");
+        assert_eq2!(buffer.get_lines()[1].string, "> b");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_tab_and_shift_tab_indent_selected_lines() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["a".into(), "b".into()]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionAction::All),
+                EditorEvent::InsertTab,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "    a");
+        assert_eq2!(buffer.get_lines()[1].string, "    b");
+        // The selection moves along w/ the text.
+        assert_eq2!(
+            buffer
+                .get_selection_map()
+                .get(ch!(1))
+                .map(|it| it.start_display_col_index),
+            Some(ch!(4))
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Outdent, EditorEvent::Outdent],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "a");
+        assert_eq2!(buffer.get_lines()[1].string, "b");
+    }
+
+    #[test]
+    fn test_shift_tab_outdents_line_at_caret() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("      x".into()),
+                EditorEvent::Outdent,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "  x");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_brackets {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                AutoClosePairsMode,
+                CaretKind,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineInternalApi,
+                EditorEvent,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn make_engine_with_auto_close_pairs() -> EditorEngine {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.auto_close_pairs = AutoClosePairsMode::Enable;
+        engine
+    }
+
+    #[test]
+    fn test_find_matching_bracket() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["fn a() {".into(), "  (x)".into(), "}".into()]);
+        let engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Caret on the opening bracket.
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 7, row_index: 0);
+        assert_eq2!(
+            EditorEngineInternalApi::find_visible_matching_bracket(&buffer, &engine),
+            Some((
+                position!(col_index: 7, row_index: 0),
+                position!(col_index: 0, row_index: 2)
+            ))
+        );
+
+        // Caret just after the closing bracket.
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 5, row_index: 1);
+        assert_eq2!(
+            EditorEngineInternalApi::find_visible_matching_bracket(&buffer, &engine),
+            Some((
+                position!(col_index: 4, row_index: 1),
+                position!(col_index: 2, row_index: 1)
+            ))
+        );
+
+        // No bracket near the caret.
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 1, row_index: 0);
+        assert_eq2!(
+            EditorEngineInternalApi::find_visible_matching_bracket(&buffer, &engine),
+            None
+        );
+    }
+
+    #[test]
+    fn test_auto_close_and_type_over() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = make_engine_with_auto_close_pairs();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertChar('('),
+                EditorEvent::InsertChar('['),
+                EditorEvent::InsertChar('a'),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "([a])");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar(']'), EditorEvent::InsertChar(')')],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "([a])");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 5, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_overwrite_mode() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.set_lines(vec!["abc".into()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::ToggleOverwriteMode,
+                EditorEvent::InsertChar('x'),
+                EditorEvent::InsertChar('y'),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert!(engine.is_overwrite_mode);
+        assert_eq2!(buffer.get_lines()[0].string, "xyc");
+
+        // The end of the line isn't typed over.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('z'), EditorEvent::InsertChar('!')],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "xyz!");
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::ToggleOverwriteMode,
+                EditorEvent::Home,
+                EditorEvent::InsertChar('-'),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "-xyz!");
+    }
+
+    #[test]
+    fn test_quote_after_word_is_not_auto_closed() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = make_engine_with_auto_close_pairs();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertString("don".into()),
+                EditorEvent::InsertChar('\''),
+                EditorEvent::InsertString("t ".into()),
+                EditorEvent::InsertChar('"'),
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "don't \"\"");
+    }
+
+    #[test]
+    fn test_auto_close_pairs_disabled_by_default() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('(')],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "(");
+    }
+}
+
+#[cfg(test)]
+mod test_multiple_carets {
+    use r3bl_core::{assert_eq2, ch, position, SelectionRange};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              make_editor_buffer},
+                Button,
+                CaretDirection,
+                CaretKind,
+                EditorBuffer,
+                EditorEvent,
+                InputEvent,
+                ModifierKeysMask,
+                MouseAction,
+                MouseInput,
+                MouseInputKind,
+                SecondaryCaret};
+
+    fn get_secondary_caret_positions(buffer: &EditorBuffer) -> Vec<(usize, usize)> {
+        buffer
+            .get_secondary_carets()
+            .iter()
+            .map(|it| (it.position.col_index.into(), it.position.row_index.into()))
+            .collect()
+    }
+
+    #[test]
+    fn test_alt_click_converts_to_add_caret() {
+        let pos = position!(col_index: 3, row_index: 4);
+        let alt_click = InputEvent::Mouse(MouseInput {
+            pos,
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: Some(ModifierKeysMask::new().with_alt()),
+        });
+        assert!(matches!(
+            EditorEvent::try_from(alt_click),
+            Ok(EditorEvent::Mouse(MouseAction::AddCaret(it))) if it == pos
+        ));
+    }
+
+    #[test]
+    fn test_select_next_occurrence() {
+        let mut buffer = make_editor_buffer(&["foo bar", "foo baz foo"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 1, row_index: 0);
+
+        // The first one selects the word at the caret.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::AddCaretAtNextOccurrence],
+            &mut TestClipboard::default(),
+        );
+        assert!(!buffer.has_secondary_carets());
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)).copied(),
+            Some(SelectionRange::new(ch!(0), ch!(3)))
+        );
+
+        // The rest add a caret at each occurrence, until they run out.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::AddCaretAtNextOccurrence,
+                EditorEvent::AddCaretAtNextOccurrence,
+                EditorEvent::AddCaretAtNextOccurrence,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_secondary_carets(),
+            &[
+                SecondaryCaret {
+                    position: position!(col_index: 3, row_index: 1),
+                    maybe_selection: Some(SelectionRange::new(ch!(0), ch!(3))),
+                },
+                SecondaryCaret {
+                    position: position!(col_index: 11, row_index: 1),
+                    maybe_selection: Some(SelectionRange::new(ch!(8), ch!(11))),
+                },
+            ]
+        );
+
+        // Typing replaces all the selections.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertChar('x')],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "x bar");
+        assert_eq2!(buffer.get_lines()[1].string, "x baz x");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+        assert_eq2!(get_secondary_caret_positions(&buffer), vec![(1, 1), (7, 1)]);
+        assert!(!buffer.has_selection());
+    }
+
+    #[test]
+    fn test_edits_are_applied_at_all_carets() {
+        let mut buffer = make_editor_buffer(&["abc", "abc"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 3, row_index: 0);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Mouse(MouseAction::AddCaret(
+                    position!(col_index: 1, row_index: 1),
+                )),
+                EditorEvent::Mouse(MouseAction::AddCaret(
+                    position!(col_index: 3, row_index: 1),
+                )),
+                EditorEvent::InsertString("12".into()),
+                EditorEvent::Backspace,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "abc1");
+        assert_eq2!(buffer.get_lines()[1].string, "a1bc1");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+        assert_eq2!(get_secondary_caret_positions(&buffer), vec![(2, 1), (5, 1)]);
+
+        // Moving the caret collapses back to the primary caret.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::MoveCaret(CaretDirection::Left)],
+            &mut TestClipboard::default(),
+        );
+        assert!(!buffer.has_secondary_carets());
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_backspace_at_start_of_line_collapses_carets() {
+        let mut buffer = make_editor_buffer(&["abc", "abc"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 0, row_index: 1);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Mouse(MouseAction::AddCaret(
+                    position!(col_index: 2, row_index: 0),
+                )),
+                EditorEvent::Backspace,
+            ],
+            &mut TestClipboard::default(),
+        );
+        assert!(!buffer.has_secondary_carets());
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(buffer.get_lines()[0].string, "abcabc");
+    }
+}
+
+#[cfg(test)]
+mod test_read_only {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self, apply_key_press},
+                CaretKind,
+                EditMode,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApplyEventResult,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                SpecialKey,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn make_read_only_engine() -> EditorEngine {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+        engine
+    }
+
+    #[test]
+    fn test_mutating_events_are_rejected() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["abc".into()]);
+        let mut engine = make_read_only_engine();
+        let mut clipboard = TestClipboard {
+            content: "xyz".into(),
+        };
+
+        for key_press in [
+            KeyPress::Plain {
+                key: Key::Character('a'),
+            },
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Enter),
+            },
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Backspace),
+            },
+            KeyPress::WithModifiers {
+                key: Key::Character('v'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            },
+        ] {
+            let result =
+                apply_key_press(&mut buffer, &mut engine, key_press, &mut clipboard);
+            assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+        }
+
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(buffer.get_lines()[0].string, "abc");
+    }
+
+    #[test]
+    fn test_navigation_selection_and_copy_are_allowed() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["abc".into()]);
+        let mut engine = make_read_only_engine();
+        let mut clipboard = TestClipboard::default();
+
+        for key_press in [
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Right),
+            },
+            KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask: ModifierKeysMask::new().with_shift(),
+            },
+            KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Right),
+                mask: ModifierKeysMask::new().with_shift(),
+            },
+            KeyPress::WithModifiers {
+                key: Key::Character('c'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            },
+        ] {
+            let result =
+                apply_key_press(&mut buffer, &mut engine, key_press, &mut clipboard);
+            assert!(matches!(result, EditorEngineApplyEventResult::Applied));
+        }
+
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+        assert_eq2!(clipboard.content, "bc");
+        assert_eq2!(buffer.get_lines()[0].string, "abc");
+    }
+
+    #[test]
+    fn test_save_is_rejected() {
+        let path = std::env::temp_dir().join(format!(
+            "r3bl_tui_test_editor_read_only_save_{}.md",
+            std::process::id()
+        ));
+        let mut buffer = EditorBuffer::new_empty(
+            &Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()),
+            &Some(path.to_string_lossy().to_string()),
+        );
+        buffer.set_lines(vec!["abc".into()]);
+        let mut engine = make_read_only_engine();
+
+        let result = apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::Character('s'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            },
+            &mut TestClipboard::default(),
+        );
+        assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+        assert!(!path.exists());
+    }
+}
+
+#[cfg(test)]
+mod test_save {
+    use r3bl_core::assert_eq2;
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                EditorBuffer,
+                EditorEngineApi,
+                EditorEngineApplyEventResult,
+                EditorEvent,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn make_ctrl_s() -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character('s'),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        })
+    }
+
+    #[test]
+    fn test_ctrl_s_converts_to_save() {
+        assert!(matches!(
+            EditorEvent::try_from(make_ctrl_s()),
+            Ok(EditorEvent::Save)
+        ));
+    }
+
+    #[test]
+    fn test_save_without_file_path_is_not_applied() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        let result = EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            make_ctrl_s(),
+            &mut TestClipboard::default(),
+        );
+        assert!(matches!(
+            result,
+            Ok(EditorEngineApplyEventResult::NotApplied)
+        ));
+    }
+
+    #[test]
+    fn test_edits_mark_buffer_dirty_and_save_clears_it() {
+        let path = std::env::temp_dir().join(format!(
+            "r3bl_tui_test_editor_save_{}.md",
+            std::process::id()
+        ));
+        let mut buffer = EditorBuffer::new_empty(
+            &Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()),
+            &Some(path.to_string_lossy().to_string()),
+        );
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        assert!(!buffer.is_dirty());
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Home],
+            &mut TestClipboard::default(),
+        );
+        assert!(!buffer.is_dirty());
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertString("abc".into())],
+            &mut TestClipboard::default(),
+        );
+        assert!(buffer.is_dirty());
+
+        let result = EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            make_ctrl_s(),
+            &mut TestClipboard::default(),
+        );
+        assert!(matches!(result, Ok(EditorEngineApplyEventResult::Applied)));
+        assert!(!buffer.is_dirty());
+        assert_eq2!(std::fs::read_to_string(&path).unwrap(), "abc");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod test_go_to {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              make_editor_buffer},
+                CaretKind,
+                EditorBuffer,
+                EditorEngineApi,
+                EditorEvent,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                ScrollOffset,
+                SpecialKey};
+
+    fn make_buffer() -> EditorBuffer {
+        let lines = (0..30).map(|it| format!("line {it}")).collect::<Vec<_>>();
+        make_editor_buffer(&lines)
+    }
+
+    #[test]
+    fn test_go_to_line_centers_the_line() {
+        let mut buffer = make_buffer();
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // The viewport is 10 rows high.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::GoToLine(20)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 19)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 5)
+        );
+
+        // Lines near the top of the buffer can't be centered.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::GoToLine(3)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 2)
+        );
+        assert_eq2!(buffer.get_scroll_offset(), ScrollOffset::default());
+
+        // Line numbers past the end of the buffer go to the last line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::GoToLine(100)],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 29)
+        );
+    }
+
+    #[test]
+    fn test_go_to_top_and_bottom() {
+        let mut buffer = make_buffer();
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut clipboard = TestClipboard::default();
+
+        // Ctrl + End.
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::End),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            }),
+            &mut clipboard,
+        )
+        .unwrap();
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 29)
+        );
+
+        // Ctrl + Home.
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Home),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            }),
+            &mut clipboard,
+        )
+        .unwrap();
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+        assert_eq2!(buffer.get_scroll_offset(), ScrollOffset::default());
+    }
+}
+
+#[cfg(test)]
+mod test_word_movement {
+    use r3bl_core::{assert_eq2, ch, position, SelectionRange};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              apply_editor_event,
+                                                              make_editor_buffer},
+                CaretDirection,
+                CaretKind,
+                EditorBuffer,
+                EditorEngineApi,
+                EditorEvent,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                SelectionAction,
+                SpecialKey};
+
+    fn caret_col(buffer: &EditorBuffer) -> usize {
+        ch!(@to_usize buffer.get_caret(CaretKind::ScrollAdjusted).col_index)
+    }
+
+    #[test]
+    fn test_word_right_and_left_use_word_segmentation() {
+        let mut buffer = make_editor_buffer(&["hello, world! 🙂 你好 foo_bar"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Punctuation, the emoji, and each CJK character are words of their own.
+        let mut stops = vec![];
+        for _ in 0..8 {
+            apply_editor_event(&mut buffer, &mut engine, EditorEvent::WordRight);
+            stops.push(caret_col(&buffer));
+        }
+        assert_eq2!(stops, vec![5, 6, 12, 13, 16, 19, 21, 29]);
+
+        let mut stops = vec![];
+        for _ in 0..8 {
+            apply_editor_event(&mut buffer, &mut engine, EditorEvent::WordLeft);
+            stops.push(caret_col(&buffer));
+        }
+        assert_eq2!(stops, vec![22, 19, 17, 14, 12, 7, 5, 0]);
+    }
+
+    #[test]
+    fn test_word_movement_wraps_to_adjacent_lines() {
+        let mut buffer = make_editor_buffer(&["abc", "def"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut clipboard = TestClipboard::default();
+
+        // Ctrl + Right.
+        let ctrl_right = InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::SpecialKey(SpecialKey::Right),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        });
+        for _ in 0..2 {
+            EditorEngineApi::apply_event(
+                &mut buffer,
+                &mut engine,
+                ctrl_right.clone(),
+                &mut clipboard,
+            )
+            .unwrap();
+        }
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::WordLeft);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_select_word() {
+        let mut buffer = make_editor_buffer(&["foo bar"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        apply_editor_event(
+            &mut buffer,
+            &mut engine,
+            EditorEvent::Select(SelectionAction::OneWordRight),
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(3)))
+        );
+    }
+
+    #[test]
+    fn test_backspace_word() {
+        let mut buffer = make_editor_buffer(&["abc", "foo bar, baz"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        apply_editor_event(
+            &mut buffer,
+            &mut engine,
+            EditorEvent::MoveCaret(CaretDirection::Down),
+        );
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::End);
+
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::BackspaceWord);
+        assert_eq2!(buffer.get_lines()[1].string, "foo bar, ");
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::BackspaceWord);
+        assert_eq2!(buffer.get_lines()[1].string, "foo bar");
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::BackspaceWord);
+        assert_eq2!(buffer.get_lines()[1].string, "foo ");
+        assert_eq2!(caret_col(&buffer), 4);
+
+        // The line is emptied, not removed.
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::BackspaceWord);
+        assert_eq2!(buffer.get_lines()[1].string, "");
+        assert_eq2!(buffer.get_lines().len(), 2);
+
+        // At the start of the line, it is joined w/ the line above.
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::BackspaceWord);
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_delete_word() {
+        let mut buffer = make_editor_buffer(&["foo  bar", "baz"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut clipboard = TestClipboard::default();
+
+        // Ctrl + Delete.
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Delete),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            }),
+            &mut clipboard,
+        )
+        .unwrap();
+        assert_eq2!(buffer.get_lines()[0].string, "  bar");
+        assert_eq2!(caret_col(&buffer), 0);
+
+        // Whitespace before the word is deleted along w/ it.
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::DeleteWord);
+        assert_eq2!(buffer.get_lines()[0].string, "");
+
+        // At the end of the line, it is joined w/ the line below.
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::DeleteWord);
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(buffer.get_lines()[0].string, "baz");
+    }
+}
+
+#[cfg(test)]
+mod test_line_operations {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              apply_editor_event,
+                                                              apply_key_press,
+                                                              get_lines_as_strings,
+                                                              make_editor_buffer},
+                CaretDirection,
+                CaretKind,
+                EditorEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                SpecialKey};
+
+    #[test]
+    fn test_move_line_is_a_single_undo_step() {
+        let mut buffer = make_editor_buffer(&["a", "b", "c"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Alt + Down.
+        apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Down),
+                mask: ModifierKeysMask::new().with_alt(),
+            },
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["b", "a", "c"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        // Alt + Up.
+        apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::Up),
+                mask: ModifierKeysMask::new().with_alt(),
+            },
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a", "b", "c"]);
+
+        // Can't move past the top of the buffer.
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::MoveLineUp);
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a", "b", "c"]);
+
+        // Ctrl + Z.
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::MoveLineDown);
+        apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::Character('z'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            },
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_duplicate_line() {
+        let mut buffer = make_editor_buffer(&["abc", "def"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        apply_editor_event(
+            &mut buffer,
+            &mut engine,
+            EditorEvent::MoveCaret(CaretDirection::Right),
+        );
+
+        // Ctrl + Shift + D.
+        apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::Character('D'),
+                mask: ModifierKeysMask::new().with_ctrl().with_shift(),
+            },
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["abc", "abc", "def"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_join_lines() {
+        let mut buffer = make_editor_buffer(&["abc  ", "   def", "", "ghi"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Ctrl + J.
+        apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::Character('j'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            },
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["abc def", "", "ghi"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+
+        // No space is added when joining w/ an empty line.
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::JoinLines);
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["abc def", "ghi"]);
+
+        // Nothing to join the last line with.
+        apply_editor_event(
+            &mut buffer,
+            &mut engine,
+            EditorEvent::MoveCaret(CaretDirection::Down),
+        );
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::JoinLines);
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["abc def", "ghi"]);
+    }
+
+    #[test]
+    fn test_delete_line() {
+        let mut buffer = make_editor_buffer(&["abc", "d", "efg"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::End);
+
+        // Ctrl + Shift + K.
+        apply_key_press(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::Character('K'),
+                mask: ModifierKeysMask::new().with_ctrl().with_shift(),
+            },
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["d", "efg"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+
+        apply_editor_event(
+            &mut buffer,
+            &mut engine,
+            EditorEvent::MoveCaret(CaretDirection::Down),
+        );
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::DeleteLine);
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["d"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+
+        // The last line is emptied, not removed.
+        apply_editor_event(&mut buffer, &mut engine, EditorEvent::DeleteLine);
+        assert_eq2!(get_lines_as_strings(&buffer), vec![""]);
+    }
+}
+
+#[cfg(test)]
+mod test_block_selection {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              apply_editor_events_w_clipboard,
+                                                              apply_key_press,
+                                                              get_lines_as_strings,
+                                                              make_editor_buffer},
+                CaretDirection,
+                CaretKind,
+                EditorEvent,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                SelectionAction,
+                SpecialKey};
+
+    #[test]
+    fn test_alt_shift_arrow_converts_to_block_selection() {
+        let key_press = KeyPress::WithModifiers {
+            key: Key::SpecialKey(SpecialKey::Down),
+            mask: ModifierKeysMask::new().with_alt().with_shift(),
+        };
+        assert!(matches!(
+            EditorEvent::try_from(InputEvent::Keyboard(key_press)),
+            Ok(EditorEvent::Select(SelectionAction::Block(
+                CaretDirection::Down
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_column_insert() {
+        let mut buffer = make_editor_buffer(&["abc", "def", "ghi"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut clipboard = TestClipboard::default();
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 1, row_index: 0);
+
+        let key_press = KeyPress::WithModifiers {
+            key: Key::SpecialKey(SpecialKey::Down),
+            mask: ModifierKeysMask::new().with_alt().with_shift(),
+        };
+        for _ in 0..2 {
+            apply_key_press(&mut buffer, &mut engine, key_press, &mut clipboard);
+        }
+        assert_eq2!(buffer.get_secondary_carets().len(), 2);
+
+        apply_editor_events_w_clipboard(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::InsertChar('-')],
+            &mut clipboard,
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a-bc", "d-ef", "g-hi"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn test_copy_and_delete_block() {
+        let mut buffer = make_editor_buffer(&["abcd", "e", "ijkl"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut clipboard = TestClipboard::default();
+        buffer.editor_content.caret_display_position =
+            position!(col_index: 1, row_index: 0);
+
+        apply_editor_events_w_clipboard(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::Select(SelectionAction::Block(CaretDirection::Down)),
+                EditorEvent::Select(SelectionAction::Block(CaretDirection::Down)),
+                EditorEvent::Select(SelectionAction::Block(CaretDirection::Right)),
+                EditorEvent::Select(SelectionAction::Block(CaretDirection::Right)),
+            ],
+            &mut clipboard,
+        );
+        assert_eq2!(
+            buffer.editor_content.maybe_block_selection_anchor,
+            Some(position!(col_index: 1, row_index: 0))
+        );
+
+        // The short row in the middle doesn't reach the block, so it is skipped.
+        apply_editor_events_w_clipboard(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::Copy],
+            &mut clipboard,
+        );
+        assert_eq2!(clipboard.content, "bc\njk");
+
+        apply_editor_events_w_clipboard(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::Delete],
+            &mut clipboard,
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["ad", "e", "il"]);
+        assert_eq2!(buffer.editor_content.maybe_block_selection_anchor, None);
+    }
+}
+
+#[cfg(test)]
+mod test_vim_keymap {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              get_lines_as_strings,
+                                                              make_editor_buffer},
+                CaretKind,
+                EditMode,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEngineApplyEventResult,
+                InputEvent,
+                Key,
+                KeyPress,
+                KeymapMode,
+                SpecialKey,
+                VimMode,
+                VIM_UNNAMED_REGISTER};
+
+    fn make_vim_engine() -> EditorEngine {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.keymap_mode = KeymapMode::Vim;
+        engine
+    }
+
+    fn press(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        key: Key,
+    ) -> EditorEngineApplyEventResult {
+        EditorEngineApi::apply_event(
+            buffer,
+            engine,
+            InputEvent::Keyboard(KeyPress::Plain { key }),
+            &mut TestClipboard::default(),
+        )
+        .unwrap()
+    }
+
+    fn type_keys(buffer: &mut EditorBuffer, engine: &mut EditorEngine, keys: &str) {
+        for character in keys.chars() {
+            press(buffer, engine, Key::Character(character));
+        }
+    }
+
+    #[test]
+    fn test_insert_mode_and_esc() {
+        let mut buffer = make_editor_buffer(&[""]);
+        let mut engine = make_vim_engine();
+
+        // In normal mode, typing doesn't insert text.
+        type_keys(&mut buffer, &mut engine, "q");
+        assert_eq2!(get_lines_as_strings(&buffer), vec![""]);
+
+        type_keys(&mut buffer, &mut engine, "ihello");
+        assert_eq2!(engine.vim_state.mode, VimMode::Insert);
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["hello"]);
+
+        // Esc moves the caret onto the last char that was typed.
+        press(&mut buffer, &mut engine, Key::SpecialKey(SpecialKey::Esc));
+        assert_eq2!(engine.vim_state.mode, VimMode::Normal);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 0)
+        );
+
+        type_keys(&mut buffer, &mut engine, "o");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["hello", ""]);
+        assert_eq2!(engine.vim_state.mode, VimMode::Insert);
+    }
+
+    #[test]
+    fn test_motions_with_count() {
+        let mut buffer = make_editor_buffer(&["abc def", "x", "y", "z"]);
+        let mut engine = make_vim_engine();
+
+        type_keys(&mut buffer, &mut engine, "2j");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
+
+        type_keys(&mut buffer, &mut engine, "gg$");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+
+        // The caret doesn't wrap to the next line.
+        type_keys(&mut buffer, &mut engine, "0l10l");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 7, row_index: 0)
+        );
+
+        type_keys(&mut buffer, &mut engine, "3G");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn test_delete_lines_paste_and_undo() {
+        let mut buffer = make_editor_buffer(&["a", "b", "c", "d"]);
+        let mut engine = make_vim_engine();
+
+        type_keys(&mut buffer, &mut engine, "2dd");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["c", "d"]);
+        assert_eq2!(
+            engine
+                .vim_state
+                .registers
+                .get(&VIM_UNNAMED_REGISTER)
+                .cloned(),
+            Some("a\nb\n".to_string())
+        );
+
+        // Whole lines are pasted below the line w/ the caret.
+        type_keys(&mut buffer, &mut engine, "p");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["c", "a", "b", "d"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 1)
+        );
+
+        // Each command is a single undo step.
+        type_keys(&mut buffer, &mut engine, "u");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["c", "d"]);
+        type_keys(&mut buffer, &mut engine, "u");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_delete_chars() {
+        let mut buffer = make_editor_buffer(&["abcd", "e"]);
+        let mut engine = make_vim_engine();
+
+        type_keys(&mut buffer, &mut engine, "3x");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["d", "e"]);
+
+        // Deleting all the chars in a line leaves an empty line.
+        type_keys(&mut buffer, &mut engine, "x");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["", "e"]);
+        assert_eq2!(
+            engine
+                .vim_state
+                .registers
+                .get(&VIM_UNNAMED_REGISTER)
+                .cloned(),
+            Some("d".to_string())
+        );
+    }
+
+    #[test]
+    fn test_named_register() {
+        let mut buffer = make_editor_buffer(&["hello world"]);
+        let mut engine = make_vim_engine();
+
+        // Yanking leaves the caret where it was.
+        type_keys(&mut buffer, &mut engine, "\"ayw");
+        assert_eq2!(
+            engine.vim_state.registers.get(&'a').cloned(),
+            Some("hello".to_string())
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        type_keys(&mut buffer, &mut engine, "x$\"aP");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["ello worldhello"]);
+    }
+
+    #[test]
+    fn test_change_word() {
+        let mut buffer = make_editor_buffer(&["foo bar"]);
+        let mut engine = make_vim_engine();
+
+        type_keys(&mut buffer, &mut engine, "cw");
+        assert_eq2!(get_lines_as_strings(&buffer), vec![" bar"]);
+        assert_eq2!(engine.vim_state.mode, VimMode::Insert);
+
+        type_keys(&mut buffer, &mut engine, "baz");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["baz bar"]);
+    }
+
+    #[test]
+    fn test_visual_mode() {
+        let mut buffer = make_editor_buffer(&["hello world"]);
+        let mut engine = make_vim_engine();
+
+        type_keys(&mut buffer, &mut engine, "vll");
+        assert_eq2!(engine.vim_state.mode, VimMode::Visual);
+
+        type_keys(&mut buffer, &mut engine, "d");
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["lo world"]);
+        assert_eq2!(engine.vim_state.mode, VimMode::Normal);
+        assert!(!buffer.has_selection());
+    }
+
+    #[test]
+    fn test_read_only() {
+        let mut buffer = make_editor_buffer(&["a", "b"]);
+        let mut engine = make_vim_engine();
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+
+        type_keys(&mut buffer, &mut engine, "d");
+        assert!(matches!(
+            press(&mut buffer, &mut engine, Key::Character('d')),
+            EditorEngineApplyEventResult::NotApplied
+        ));
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a", "b"]);
+    }
+}
+
+#[cfg(test)]
+mod test_search_highlight {
+    use r3bl_core::{assert_eq2, ch, position, size, SelectionRange, UnicodeString};
+
+    use crate::{find_search_matches,
+                get_search_match_style,
+                system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              make_editor_buffer},
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEngineConfig,
+                EditorEvent,
+                FlexBox,
+                HasFocus,
+                LineNumberGutterMode,
+                RenderOp,
+                SearchMatch,
+                ZOrder};
+
+    fn render(engine: &mut EditorEngine, buffer: &mut EditorBuffer) -> Vec<RenderOp> {
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 5),
+            ..Default::default()
+        };
+        EditorEngineApi::render_engine(
+            engine,
+            buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 40, row_count: 10),
+        )
+        .unwrap()
+        .get_all_render_op_in(ZOrder::Normal)
+        .unwrap()
+    }
+
+    fn get_painted_search_texts(render_ops: &[RenderOp]) -> Vec<String> {
+        render_ops
+            .iter()
+            .filter_map(|it| match it {
+                RenderOp::PaintTextWithAttributes(text, Some(style))
+                    if *style == get_search_match_style() =>
+                {
+                    Some(text.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_search_matches() {
+        let lines: Vec<UnicodeString> = ["aaaa", "xa😀a😀a", "none"]
+            .iter()
+            .map(|it| UnicodeString::from(*it))
+            .collect();
+
+        // Matches don't overlap.
+        assert_eq2!(
+            find_search_matches(&lines, "aa"),
+            vec![
+                SearchMatch {
+                    row_index: ch!(0),
+                    range: SelectionRange::new(ch!(0), ch!(2)),
+                },
+                SearchMatch {
+                    row_index: ch!(0),
+                    range: SelectionRange::new(ch!(2), ch!(4)),
+                },
+            ]
+        );
+
+        // The range is in display cols, and the emoji is 2 cols wide.
+        assert_eq2!(
+            find_search_matches(&lines, "😀a"),
+            vec![
+                SearchMatch {
+                    row_index: ch!(1),
+                    range: SelectionRange::new(ch!(2), ch!(5)),
+                },
+                SearchMatch {
+                    row_index: ch!(1),
+                    range: SelectionRange::new(ch!(5), ch!(8)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matches_are_synced_after_edit() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = make_editor_buffer(&["foo bar", "bar"]);
+
+        buffer.set_search_query(Some("foo".into()));
+        buffer.sync_search_matches();
+        assert_eq2!(buffer.get_search().get_query(), Some("foo"));
+        assert_eq2!(buffer.get_search().get_matches().len(), 1);
+
+        // Typing another match makes it show up the next time the buffer is synced.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::InsertString("foo".into())],
+            &mut TestClipboard::default(),
+        );
+        buffer.sync_search_matches();
+        assert_eq2!(buffer.get_search().get_matches().len(), 2);
+
+        // An empty query stops the search.
+        buffer.set_search_query(Some("".into()));
+        buffer.sync_search_matches();
+        assert_eq2!(buffer.get_search().get_query(), None);
+        assert!(buffer.get_search().get_matches().is_empty());
+    }
+
+    #[test]
+    fn test_render_matches_and_gutter_markers() {
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                line_number_gutter: LineNumberGutterMode::Absolute,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut lines = vec!["line"; 12];
+        lines[0] = "a foo";
+        lines[11] = "foo";
+        let mut buffer = make_editor_buffer(&lines);
+        buffer.set_search_query(Some("foo".into()));
+
+        // The match on the 1st line is in the viewport, and the one on the last line is
+        // marked in the gutter.
+        let render_ops = render(&mut engine, &mut buffer);
+        assert_eq2!(
+            get_painted_search_texts(&render_ops),
+            vec!["foo".to_string(), "▼".to_string()]
+        );
+
+        // Without a search, nothing is painted w/ the search style.
+        buffer.set_search_query(None);
+        let render_ops = render(&mut engine, &mut buffer);
+        assert!(get_painted_search_texts(&render_ops).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_completion_popup {
+    use std::sync::Arc;
+
+    use r3bl_core::{assert_eq2, ch, position, size, Position, SelectionRange};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                CaretKind,
+                CompletionItem,
+                CompletionProvider,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                FlexBox,
+                HasFocus,
+                InputEvent,
+                Key,
+                KeyPress,
+                SpecialKey,
+                ZOrder,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    /// Completes the word to the left of the caret w/ the words that start w/ it.
+    #[derive(Debug)]
+    struct WordCompletionProvider(Vec<&'static str>);
+
+    impl CompletionProvider for WordCompletionProvider {
+        fn completions(
+            &self,
+            editor_buffer: &EditorBuffer,
+            caret: Position,
+        ) -> Vec<CompletionItem> {
+            let line = &editor_buffer.get_lines()[ch!(@to_usize caret.row_index)];
+            let text_before_caret = line.clip_to_width(ch!(0), caret.col_index);
+            let word = text_before_caret
+                .rsplit(|it: char| !it.is_alphanumeric())
+                .next()
+                .unwrap_or_default();
+            if word.is_empty() {
+                return vec![];
+            }
+            let start_col_index = caret.col_index - ch!(word.len());
+            self.0
+                .iter()
+                .filter(|it| it.starts_with(word))
+                .map(|it| CompletionItem {
+                    label: it.to_string(),
+                    insert_text: it.to_string(),
+                    replace_range: SelectionRange::new(start_col_index, caret.col_index),
+                })
+                .collect()
+        }
+    }
+
+    fn apply_keys(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        key_presses: Vec<KeyPress>,
+    ) {
+        for key_press in key_presses {
+            EditorEngineApi::apply_event(
+                buffer,
+                engine,
+                InputEvent::Keyboard(key_press),
+                &mut TestClipboard::default(),
+            )
+            .unwrap();
+        }
+    }
+
+    fn character_key(character: char) -> KeyPress {
+        KeyPress::Plain {
+            key: Key::Character(character),
+        }
+    }
+
+    fn special(special_key: SpecialKey) -> KeyPress {
+        KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        }
+    }
+
+    #[test]
+    fn test_typing_opens_and_updates_popup() {
+        let mut engine = EditorEngine {
+            maybe_completion_provider: Some(Arc::new(WordCompletionProvider(vec![
+                "foo", "foobar", "baz",
+            ]))),
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        apply_keys(&mut buffer, &mut engine, vec![character_key('f')]);
+        assert_eq2!(engine.completion_popup.items.len(), 2);
+
+        apply_keys(
+            &mut buffer,
+            &mut engine,
+            vec![character_key('o'), character_key('o'), character_key('b')],
+        );
+        assert_eq2!(engine.completion_popup.items.len(), 1);
+
+        // Deleting a char updates the items.
+        apply_keys(
+            &mut buffer,
+            &mut engine,
+            vec![special(SpecialKey::Backspace)],
+        );
+        assert_eq2!(engine.completion_popup.items.len(), 2);
+
+        // Esc closes the popup.
+        apply_keys(&mut buffer, &mut engine, vec![special(SpecialKey::Esc)]);
+        assert!(!engine.completion_popup.is_open());
+
+        // Nothing matches.
+        apply_keys(&mut buffer, &mut engine, vec![character_key('x')]);
+        assert!(!engine.completion_popup.is_open());
+    }
+
+    #[test]
+    fn test_accept_selected_item() {
+        let mut engine = EditorEngine {
+            maybe_completion_provider: Some(Arc::new(WordCompletionProvider(vec![
+                "foo", "foobar", "baz",
+            ]))),
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        // Down selects the 2nd item, and Tab accepts it.
+        apply_keys(
+            &mut buffer,
+            &mut engine,
+            vec![
+                character_key('a'),
+                character_key(' '),
+                character_key('f'),
+                special(SpecialKey::Down),
+                special(SpecialKey::Tab),
+            ],
+        );
+        assert!(!engine.completion_popup.is_open());
+        assert_eq2!(buffer.get_lines()[0].string, "a foobar");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 8, row_index: 0)
+        );
+
+        // Up wraps around to the last item, and Enter accepts it.
+        apply_keys(
+            &mut buffer,
+            &mut engine,
+            vec![
+                character_key(' '),
+                character_key('f'),
+                special(SpecialKey::Up),
+                special(SpecialKey::Enter),
+            ],
+        );
+        assert_eq2!(buffer.get_lines().len(), 1);
+        assert_eq2!(buffer.get_lines()[0].string, "a foobar foobar");
+    }
+
+    #[test]
+    fn test_popup_is_rendered_on_glass_layer() {
+        let mut engine = EditorEngine {
+            maybe_completion_provider: Some(Arc::new(WordCompletionProvider(vec![
+                "foo", "foobar", "baz",
+            ]))),
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        apply_keys(&mut buffer, &mut engine, vec![character_key('f')]);
+
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 5),
+            ..Default::default()
+        };
+        let pipeline = EditorEngineApi::render_engine(
+            &mut engine,
+            &mut buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 40, row_count: 10),
+        )
+        .unwrap();
+
+        let render_ops = pipeline.get_all_render_op_in(ZOrder::Glass).unwrap();
+        let painted_texts = render_ops
+            .iter()
+            .filter_map(|it| match it {
+                crate::RenderOp::PaintTextWithAttributes(text, _) => Some(text.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq2!(painted_texts, vec![" foo    ", " foobar "]);
+    }
+}
+
+#[cfg(test)]
+mod test_snippets {
+    use r3bl_core::{assert_eq2, ch, position, SelectionRange};
+
+    use crate::{test_fixtures::mock_real_objects_for_editor::{self,
+                                                              apply_editor_events},
+                CaretKind,
+                EditorBuffer,
+                EditorEvent,
+                Snippet,
+                SnippetTabStop,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn tab_stop(
+        number: usize,
+        row_index: usize,
+        start: usize,
+        end: usize,
+    ) -> SnippetTabStop {
+        SnippetTabStop {
+            number,
+            row_index: ch!(row_index),
+            range: SelectionRange::new(ch!(start), ch!(end)),
+        }
+    }
+
+    #[test]
+    fn test_parse_snippet() {
+        let snippet = Snippet::parse("for ${1:item} in ${2:items} {\n    $0\n}");
+        assert_eq2!(snippet.lines, vec!["for item in items {", "    ", "}"]);
+        assert_eq2!(
+            snippet.tab_stops,
+            vec![
+                tab_stop(1, 0, 4, 8),
+                tab_stop(2, 0, 12, 17),
+                tab_stop(0, 1, 4, 4)
+            ]
+        );
+
+        // A tab stop w/out a placeholder mirrors the one w/ the same number.
+        let snippet = Snippet::parse("${1:a} + $1 = ${2}");
+        assert_eq2!(snippet.lines, vec!["a + a = "]);
+        assert_eq2!(
+            snippet.tab_stops,
+            vec![
+                tab_stop(1, 0, 0, 1),
+                tab_stop(1, 0, 4, 5),
+                tab_stop(2, 0, 8, 8)
+            ]
+        );
+
+        // Escapes, and `$` that isn't a tab stop.
+        let snippet = Snippet::parse(r"\${1} costs $ 5 {\}}");
+        assert_eq2!(snippet.lines, vec!["${1} costs $ 5 {}}"]);
+        assert!(snippet.tab_stops.is_empty());
+    }
+
+    #[test]
+    fn test_tab_moves_between_tab_stops() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        apply_editor_events(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::InsertSnippet(
+                "fn ${1:name}(${2:args}) {\n    $0\n}".into(),
+            )],
+        );
+        assert!(buffer.is_snippet_active());
+        assert_eq2!(buffer.get_lines().len(), 3);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(3), ch!(7)))
+        );
+
+        // Typing replaces the placeholder, and the next tab stop moves w/ it.
+        apply_editor_events(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::InsertChar('g'),
+                EditorEvent::InsertChar('o'),
+                EditorEvent::InsertTab,
+            ],
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "fn go(args) {");
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(6), ch!(10)))
+        );
+
+        // Shift+Tab goes back to the previous tab stop.
+        apply_editor_events(&mut buffer, &mut engine, vec![EditorEvent::Outdent]);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(3), ch!(5)))
+        );
+
+        // The final tab stop ends the snippet.
+        apply_editor_events(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::InsertTab, EditorEvent::InsertTab],
+        );
+        assert!(!buffer.is_snippet_active());
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_mirrored_tab_stops_are_edited_together() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        apply_editor_events(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::InsertSnippet("${1:a} + $1 = $2".into()),
+                EditorEvent::InsertChar('b'),
+                EditorEvent::InsertChar('c'),
+                EditorEvent::InsertTab,
+                EditorEvent::InsertChar('x'),
+                EditorEvent::InsertTab,
+            ],
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "bc + bc = x");
+        assert!(!buffer.is_snippet_active());
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 11, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_moving_the_caret_ends_the_snippet() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        apply_editor_events(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::InsertSnippet("${1:a}, ${2:b}".into()),
+                EditorEvent::Home,
+            ],
+        );
+        assert!(!buffer.is_snippet_active());
+
+        // Tab inserts spaces again.
+        apply_editor_events(&mut buffer, &mut engine, vec![EditorEvent::InsertTab]);
+        assert!(buffer.get_lines()[0].string.ends_with("a, b"));
+        assert!(buffer.get_lines()[0].string.starts_with(' '));
+    }
+}
+
+#[cfg(test)]
+mod test_change_markers {
+    use r3bl_core::{assert_eq2, ch, position, size};
+
+    use crate::{diff_lines,
+                system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                CaretKind,
+                ChangeMarkersMode,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEngineConfig,
+                EditorEvent,
+                FlexBox,
+                HasFocus,
+                LineChange,
+                LineChangeKind,
+                LineNumberGutterMode,
+                RenderOp,
+                ZOrder,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn change(row_index: usize, kind: LineChangeKind) -> LineChange {
+        LineChange {
+            row_index: ch!(row_index),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let baseline = ["a", "b", "c", "d", "e"];
+
+        assert!(diff_lines(&baseline, &baseline).is_empty());
+
+        // Added & modified.
+        assert_eq2!(
+            diff_lines(&baseline, &["a", "B", "new", "c", "d", "e"]),
+            vec![
+                change(1, LineChangeKind::Modified),
+                change(2, LineChangeKind::Added),
+            ]
+        );
+
+        // Removed in the middle & at the end.
+        assert_eq2!(
+            diff_lines(&baseline, &["a", "d", "e"]),
+            vec![change(1, LineChangeKind::Removed)]
+        );
+        assert_eq2!(
+            diff_lines(&baseline, &["a", "b", "c"]),
+            vec![change(2, LineChangeKind::Removed)]
+        );
+
+        // Moving a line is a removal and an addition.
+        assert_eq2!(
+            diff_lines(&baseline, &["b", "c", "a", "d", "e"]),
+            vec![
+                change(0, LineChangeKind::Removed),
+                change(2, LineChangeKind::Added),
+            ]
+        );
+    }
+
+    fn render(engine: &mut EditorEngine, buffer: &mut EditorBuffer) -> Vec<RenderOp> {
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 10),
+            ..Default::default()
+        };
+        EditorEngineApi::render_engine(
+            engine,
+            buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 40, row_count: 10),
+        )
+        .unwrap()
+        .get_all_render_op_in(ZOrder::Normal)
+        .unwrap()
+    }
+
+    #[test]
+    fn test_changes_are_tracked_against_baseline() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_config(
+            EditorEngineConfig {
+                line_number_gutter: LineNumberGutterMode::Absolute,
+                change_markers: ChangeMarkersMode::Enable,
+                ..Default::default()
+            },
+        );
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((0..8).map(|it| it.to_string()).collect());
+
+        // There's no baseline, so nothing is marked.
+        render(&mut engine, &mut buffer);
+        assert!(!buffer.get_diff().has_baseline());
+
+        buffer.reset_change_baseline();
+        render(&mut engine, &mut buffer);
+        assert!(buffer.get_diff().has_baseline());
+        assert!(buffer.get_diff().get_changes().is_empty());
+
+        // Change the 1st line, and add a line after the 6th.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::InsertChar('x'),
+                EditorEvent::GoToLine(6),
+                EditorEvent::End,
+                EditorEvent::InsertNewLine,
+                EditorEvent::InsertChar('y'),
+                EditorEvent::GoToTop,
+            ],
+            &mut TestClipboard::default(),
+        );
+        let render_ops = render(&mut engine, &mut buffer);
+        assert_eq2!(
+            buffer.get_diff().get_changes(),
+            &[
+                change(0, LineChangeKind::Modified),
+                change(6, LineChangeKind::Added),
+            ]
+        );
+        let marker_count = render_ops
+            .iter()
+            .filter(|it| matches!(it, RenderOp::PaintTextWithAttributes(text, _) if text == "▎"))
+            .count();
+        assert_eq2!(marker_count, 2);
+
+        // Go to the next & previous change.
+        let mut go_to = |editor_event: EditorEvent| {
+            EditorEvent::apply_editor_events::<(), ()>(
+                &mut engine,
+                &mut buffer,
+                vec![editor_event],
+                &mut TestClipboard::default(),
+            );
+            buffer.get_caret(CaretKind::ScrollAdjusted).row_index
+        };
+        assert_eq2!(go_to(EditorEvent::GoToNextChange), ch!(6));
+        assert_eq2!(go_to(EditorEvent::GoToNextChange), ch!(6));
+        assert_eq2!(go_to(EditorEvent::GoToPreviousChange), ch!(0));
+    }
+}
+
+#[cfg(test)]
+mod test_whitespace {
+    use r3bl_core::{assert_eq2, position, size, Position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                CaretKind,
+                EditorBuffer,
+                EditorEngineApi,
+                EditorEngineConfig,
+                EditorEvent,
+                FlexBox,
+                HasFocus,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                RenderOp,
+                TrimTrailingWhitespaceMode,
+                WhitespaceRenderMode,
+                ZOrder,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    #[test]
+    fn test_trim_trailing_whitespace() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["abc  ".into(), "  de".into(), "f \u{a0} ".into()]);
+
+        // Move the caret into the trailing whitespace of the 1st line.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::TrimTrailingWhitespace],
+            &mut TestClipboard::default(),
+        );
+
+        let lines: Vec<&str> = buffer
+            .get_lines()
+            .iter()
+            .map(|it| it.string.as_str())
+            .collect();
+        assert_eq2!(lines, vec!["abc", "  de", "f"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 3, row_index: 0)
+        );
+        assert!(buffer.is_dirty());
+
+        // Nothing to trim.
+        let content_version = buffer.get_content_version();
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::TrimTrailingWhitespace],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_content_version(), content_version);
+    }
+
+    #[test]
+    fn test_save_trims_trailing_whitespace() {
+        let path = std::env::temp_dir().join(format!(
+            "r3bl_tui_test_editor_trim_on_save_{}.md",
+            std::process::id()
+        ));
+        let mut buffer = EditorBuffer::new_empty(
+            &Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()),
+            &Some(path.to_string_lossy().to_string()),
+        );
+        buffer.set_lines(vec!["abc ".into(), "de\t".into()]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_config(
+            EditorEngineConfig {
+                trim_trailing_whitespace: TrimTrailingWhitespaceMode::OnSave,
+                ..Default::default()
+            },
+        );
+
+        let ctrl_s = InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character('s'),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        });
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            ctrl_s,
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+
+        assert!(!buffer.is_dirty());
+        assert_eq2!(std::fs::read_to_string(&path).unwrap(), "abc\nde");
+
+        // The trim can be undone.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Undo],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(buffer.get_lines()[0].string, "abc ");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_render_whitespace() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine_with_config(
+            EditorEngineConfig {
+                whitespace_render: WhitespaceRenderMode::Enable,
+                ..Default::default()
+            },
+        );
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["a b  ".into(), "c\u{a0}d".into()]);
+
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 10),
+            ..Default::default()
+        };
+        let render_ops = EditorEngineApi::render_engine(
+            &mut engine,
+            &mut buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 20, row_count: 10),
+        )
+        .unwrap()
+        .get_all_render_op_in(ZOrder::Normal)
+        .unwrap();
+
+        // The glyphs are painted after moving to their position.
+        let glyph_positions: Vec<(Position, &str)> = render_ops
+            .windows(2)
+            .filter_map(|it| match it {
+                [RenderOp::MoveCursorPositionRelTo(_, pos), RenderOp::PaintTextWithAttributes(text, _)]
+                    if text == "·" || text == "␣" =>
+                {
+                    Some((*pos, text.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq2!(
+            glyph_positions,
+            vec![
+                (position!(col_index: 3, row_index: 0), "·"),
+                (position!(col_index: 4, row_index: 0), "·"),
+                (position!(col_index: 1, row_index: 1), "␣"),
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_caret_style {
+    use std::time::Instant;
+
+    use r3bl_core::{assert_eq2, position, size};
+    use r3bl_macro::tui_style;
+
+    use crate::{test_fixtures::mock_real_objects_for_editor,
+                CaretBlinkMode,
+                CaretStyle,
+                CursorShape,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEngineConfig,
+                FlexBox,
+                FlexBoxId,
+                HasFocus,
+                KeymapMode,
+                RenderOp,
+                VimMode,
+                ZOrder,
+                CARET_BAR_CHAR,
+                DEFAULT_CARET_BLINK_INTERVAL,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn make_engine(caret_style: CaretStyle, caret_blink: CaretBlinkMode) -> EditorEngine {
+        mock_real_objects_for_editor::make_editor_engine_with_config(EditorEngineConfig {
+            caret_style,
+            caret_blink,
+            ..Default::default()
+        })
+    }
+
+    fn render(engine: &mut EditorEngine, line: &str) -> Vec<RenderOp> {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec![line.into()]);
+
+        let mut has_focus = HasFocus::default();
+        has_focus.set_id(FlexBoxId::default());
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 5),
+            ..Default::default()
+        };
+        EditorEngineApi::render_engine(
+            engine,
+            &mut buffer,
+            current_box,
+            &mut has_focus,
+            size!(col_count: 20, row_count: 5),
+        )
+        .unwrap()
+        .get_all_render_op_in(ZOrder::Normal)
+        .unwrap()
+    }
+
+    /// Returns the last op that paints text, which paints the caret (if it is visible).
+    fn render_caret(engine: &mut EditorEngine, line: &str) -> Option<RenderOp> {
+        render(engine, line)
+            .into_iter()
+            .rev()
+            .find(|it| matches!(it, RenderOp::PaintTextWithAttributes(..)))
+    }
+
+    #[test]
+    fn test_caret_styles() {
+        let mut engine = make_engine(CaretStyle::Block, CaretBlinkMode::Disable);
+        assert_eq2!(
+            render_caret(&mut engine, "abc"),
+            Some(RenderOp::PaintTextWithAttributes(
+                "a".into(),
+                tui_style! { attrib: [reverse] }.into()
+            ))
+        );
+
+        let mut engine = make_engine(CaretStyle::Underline, CaretBlinkMode::Disable);
+        assert_eq2!(
+            render_caret(&mut engine, "abc"),
+            Some(RenderOp::PaintTextWithAttributes(
+                "a".into(),
+                tui_style! { attrib: [underline] }.into()
+            ))
+        );
+
+        // The bar is painted on whitespace, and the character is underlined otherwise.
+        let mut engine = make_engine(CaretStyle::Bar, CaretBlinkMode::Disable);
+        assert_eq2!(
+            render_caret(&mut engine, " bc"),
+            Some(RenderOp::PaintTextWithAttributes(
+                CARET_BAR_CHAR.into(),
+                Some(Default::default())
+            ))
+        );
+        assert_eq2!(
+            render_caret(&mut engine, "abc"),
+            Some(RenderOp::PaintTextWithAttributes(
+                "a".into(),
+                tui_style! { attrib: [underline] }.into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_terminal_cursor() {
+        let mut engine = make_engine(CaretStyle::TerminalCursor, CaretBlinkMode::Disable);
+        let show_cursor_ops = |engine: &mut EditorEngine| -> Vec<RenderOp> {
+            render(engine, "abc")
+                .into_iter()
+                .filter(|it| matches!(it, RenderOp::ShowCursor(_)))
+                .collect()
+        };
+        assert_eq2!(
+            show_cursor_ops(&mut engine),
+            vec![RenderOp::ShowCursor(CursorShape::SteadyBar)]
+        );
+
+        engine.is_overwrite_mode = true;
+        assert_eq2!(engine.get_cursor_shape(), CursorShape::SteadyUnderline);
+
+        engine.config_options.keymap_mode = KeymapMode::Vim;
+        engine.vim_state.mode = VimMode::Normal;
+        engine.config_options.caret_blink = CaretBlinkMode::Enable;
+        assert_eq2!(
+            show_cursor_ops(&mut engine),
+            vec![RenderOp::ShowCursor(CursorShape::BlinkingBlock)]
+        );
+    }
+
+    #[test]
+    fn test_caret_blink() {
+        let mut engine = make_engine(CaretStyle::Block, CaretBlinkMode::Enable);
+        let now = Instant::now();
+
+        // The caret is visible until the 1st event resets the blink.
+        assert!(engine.is_caret_visible(now));
+
+        engine.maybe_caret_blink_start = Some(now);
+        assert!(engine.is_caret_visible(now));
+        assert!(!engine.is_caret_visible(now + DEFAULT_CARET_BLINK_INTERVAL));
+        assert!(engine.is_caret_visible(now + DEFAULT_CARET_BLINK_INTERVAL * 2));
+
+        // Hidden carets aren't painted.
+        engine.maybe_caret_blink_start = Some(now - DEFAULT_CARET_BLINK_INTERVAL);
+        let caret_style = tui_style! { attrib: [reverse] };
+        assert!(!matches!(
+            render_caret(&mut engine, "abc"),
+            Some(RenderOp::PaintTextWithAttributes(_, Some(style))) if style == caret_style
+        ));
+
+        // Disabling the blink keeps the caret visible.
+        engine.config_options.caret_blink = CaretBlinkMode::Disable;
+        assert!(engine.is_caret_visible(now + DEFAULT_CARET_BLINK_INTERVAL));
+    }
+}
+
+#[cfg(test)]
+mod test_editor_tabs {
+    use std::collections::HashMap;
+
+    use r3bl_core::{assert_eq2, position, size, OutputDevice};
+    use r3bl_test_fixtures::output_device_ext::OutputDeviceExt as _;
+    use tokio::sync::mpsc;
+
+    use crate::{get_editor_buffer_title,
+                Component,
+                EditorBuffer,
+                EditorBufferTabs,
+                EditorTabBarComponent,
+                EditorTabEvent,
+                EventPropagation,
+                FlexBox,
+                FlexBoxId,
+                GlobalData,
+                HasEditorBufferTabs,
+                HasFocus,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                RenderOp,
+                SpecialKey,
+                SurfaceBounds,
+                ZOrder,
+                CHANNEL_WIDTH,
+                DEFAULT_SYN_HI_FILE_EXT,
+                EDITOR_TAB_DIRTY_INDICATOR};
+
+    fn make_buffer(maybe_file_path: Option<&str>) -> EditorBuffer {
+        EditorBuffer::new_empty(
+            &Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()),
+            &maybe_file_path.map(|it| it.to_owned()),
+        )
+    }
+
+    fn get_titles(tabs: &EditorBufferTabs) -> Vec<String> {
+        tabs.get_buffers()
+            .iter()
+            .map(get_editor_buffer_title)
+            .collect()
+    }
+
+    #[test]
+    fn test_open_close_reopen() {
+        let mut tabs = EditorBufferTabs::new(make_buffer(Some("/tmp/a.md")));
+        tabs.open(make_buffer(Some("/tmp/b.md")));
+        tabs.open(make_buffer(None));
+        assert_eq2!(get_titles(&tabs), vec!["a.md", "b.md", "untitled"]);
+        assert_eq2!(tabs.get_active_index(), 2);
+
+        // Opening a file that is already open activates it.
+        tabs.open(make_buffer(Some("/tmp/a.md")));
+        assert_eq2!(tabs.len(), 3);
+        assert_eq2!(tabs.get_active_index(), 0);
+
+        tabs.activate_previous();
+        assert_eq2!(tabs.get_active_index(), 2);
+        tabs.activate_next();
+        assert_eq2!(tabs.get_active_index(), 0);
+
+        tabs.close_active();
+        assert_eq2!(get_titles(&tabs), vec!["b.md", "untitled"]);
+        assert_eq2!(tabs.get_active_index(), 0);
+
+        // Reopening puts the buffer after the active one.
+        assert!(tabs.reopen_closed());
+        assert_eq2!(get_titles(&tabs), vec!["b.md", "a.md", "untitled"]);
+        assert_eq2!(tabs.get_active_index(), 1);
+        assert!(!tabs.reopen_closed());
+
+        // Closing the last buffer leaves an empty one.
+        let mut tabs = EditorBufferTabs::new(make_buffer(Some("/tmp/a.md")));
+        tabs.close_active();
+        assert_eq2!(get_titles(&tabs), vec!["untitled"]);
+        assert_eq2!(tabs.get_closed_count(), 1);
+    }
+
+    #[test]
+    fn test_input_event_conversion() {
+        let key = |key: Key, mask: ModifierKeysMask| {
+            EditorTabEvent::try_from(InputEvent::Keyboard(KeyPress::WithModifiers {
+                key,
+                mask,
+            }))
+        };
+        let ctrl = ModifierKeysMask::new().with_ctrl();
+        let ctrl_shift = ModifierKeysMask::new().with_ctrl().with_shift();
+
+        assert_eq2!(
+            key(Key::SpecialKey(SpecialKey::Tab), ctrl),
+            Ok(EditorTabEvent::NextTab)
+        );
+        assert_eq2!(
+            key(Key::SpecialKey(SpecialKey::BackTab), ctrl_shift),
+            Ok(EditorTabEvent::PreviousTab)
+        );
+        assert_eq2!(key(Key::Character('w'), ctrl), Ok(EditorTabEvent::CloseTab));
+        assert_eq2!(
+            key(Key::Character('T'), ctrl_shift),
+            Ok(EditorTabEvent::ReopenClosedTab)
+        );
+        assert!(key(Key::Character('w'), ctrl_shift).is_err());
+    }
+
+    const EDITOR_ID: FlexBoxId = FlexBoxId(1);
+    const TAB_BAR_ID: FlexBoxId = FlexBoxId(2);
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct State {
+        editor_buffer_tabs: HashMap<FlexBoxId, EditorBufferTabs>,
+    }
+
+    impl HasEditorBufferTabs for State {
+        fn get_editor_buffer_tabs(
+            &self,
+            editor_id: FlexBoxId,
+        ) -> Option<&EditorBufferTabs> {
+            self.editor_buffer_tabs.get(&editor_id)
+        }
+
+        fn get_mut_editor_buffer_tabs(
+            &mut self,
+            editor_id: FlexBoxId,
+        ) -> Option<&mut EditorBufferTabs> {
+            self.editor_buffer_tabs.get_mut(&editor_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tab_bar_component() {
+        let mut tabs = EditorBufferTabs::new(make_buffer(Some("/tmp/a.md")));
+        tabs.open(make_buffer(Some("/tmp/b.md")));
+        tabs.get_active_mut().set_dirty(true);
+        let state = State {
+            editor_buffer_tabs: HashMap::from([(EDITOR_ID, tabs)]),
+        };
+
+        let (main_thread_channel_sender, _receiver) = mpsc::channel::<_>(CHANNEL_WIDTH);
+        let (output_device, _stdout_mock) = OutputDevice::new_mock();
+        let mut global_data = GlobalData::<State, ()> {
+            state,
+            window_size: size!(col_count: 40, row_count: 10),
+            maybe_saved_offscreen_buffer: Default::default(),
+            main_thread_channel_sender,
+            output_device,
+            layout_cache: Default::default(),
+            box_registry: Default::default(),
+            last_paint_stats: Default::default(),
+            render_scheduler: Default::default(),
+            resize_debouncer: Default::default(),
+            maybe_title: None,
+            maybe_paint_task: None,
+            toasts: Default::default(),
+            shortcuts: Default::default(),
+            timers: Default::default(),
+            context_menu: Default::default(),
+            maybe_splash_screen: None,
+            pending_clipboard_contents: Default::default(),
+        };
+
+        let mut component =
+            EditorTabBarComponent::<State, ()>::new(TAB_BAR_ID, EDITOR_ID, |_, _| {});
+        let mut has_focus = HasFocus::default();
+
+        // Both tabs are painted, the dirty one w/ the indicator.
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 40, row_count: 1),
+            ..Default::default()
+        };
+        let render_ops = component
+            .render(
+                &mut global_data,
+                current_box,
+                SurfaceBounds::default(),
+                &mut has_focus,
+            )
+            .unwrap()
+            .get_all_render_op_in(ZOrder::Normal)
+            .unwrap();
+        let texts: Vec<String> = render_ops
+            .into_iter()
+            .filter_map(|it| match it {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text),
+                _ => None,
+            })
+            .collect();
+        assert_eq2!(
+            texts,
+            vec![
+                " a.md ".to_string(),
+                format!(" b.md {EDITOR_TAB_DIRTY_INDICATOR} ")
+            ]
+        );
+
+        // Ctrl+Tab switches to the next buffer, other keys are propagated.
+        let ctrl_tab = InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::SpecialKey(SpecialKey::Tab),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        });
+        let result = component
+            .handle_event(&mut global_data, ctrl_tab, &mut has_focus)
+            .unwrap();
+        assert_eq2!(result, EventPropagation::ConsumedRender);
+        assert_eq2!(
+            global_data.state.editor_buffer_tabs[&EDITOR_ID].get_active_index(),
+            0
+        );
+
+        let result = component
+            .handle_event(
+                &mut global_data,
+                InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::Character('a'),
+                }),
+                &mut has_focus,
+            )
+            .unwrap();
+        assert_eq2!(result, EventPropagation::Propagate);
+    }
+}
+
+#[cfg(test)]
+mod test_spell_check {
+    use std::sync::Arc;
+
+    use r3bl_core::{assert_eq2, ch, position, size, Position, SelectionRange};
+
+    use crate::{find_misspellings,
+                get_misspelled_word_style,
+                system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEvent,
+                FlexBox,
+                HasFocus,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                RenderOp,
+                SpecialKey,
+                SpellChecker,
+                ZOrder,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    /// Knows a handful of words, and suggests the same ones for every misspelling.
+    #[derive(Debug)]
+    struct TestSpellChecker;
+
+    impl SpellChecker for TestSpellChecker {
+        fn is_correct(&self, word: &str) -> bool {
+            ["the", "cat", "and", "don't"].contains(&word.to_lowercase().as_str())
+        }
+
+        fn suggestions(&self, _word: &str) -> Vec<String> {
+            vec!["The".to_string(), "Ten".to_string()]
+        }
+    }
+
+    fn alt_s() -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character('s'),
+            mask: ModifierKeysMask::new().with_alt(),
+        })
+    }
+
+    #[test]
+    fn test_find_misspellings() {
+        let lines = vec![
+            "Teh cat `fn foo` and https://exmple.com x".into(),
+            "```".into(),
+            "zzz".into(),
+            "```".into(),
+            "HTTP snake_case camelCase don't wrod".into(),
+        ];
+        let misspellings = find_misspellings(&lines, &TestSpellChecker);
+
+        let found: Vec<(usize, SelectionRange, &str)> = misspellings
+            .iter()
+            .map(|it| (ch!(@to_usize it.row_index), it.range, it.word.as_str()))
+            .collect();
+        assert_eq2!(
+            found,
+            vec![
+                (0, SelectionRange::new(ch!(0), ch!(3)), "Teh"),
+                (4, SelectionRange::new(ch!(32), ch!(36)), "wrod"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_only_markdown_is_checked() {
+        let mut engine = EditorEngine {
+            maybe_spell_checker: Some(Arc::new(TestSpellChecker)),
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut buffer = EditorBuffer::new_empty(&Some("rs".to_owned()), &None);
+        buffer.set_lines(vec!["Teh cat".into()]);
+
+        buffer.sync_spell_check(engine.maybe_spell_checker.as_ref());
+        assert!(buffer.get_spell_check().get_misspellings().is_empty());
+
+        // Without a spell checker nothing is flagged.
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["Teh cat".into()]);
+        engine.maybe_spell_checker = None;
+        buffer.sync_spell_check(engine.maybe_spell_checker.as_ref());
+        assert!(buffer.get_spell_check().get_misspellings().is_empty());
+    }
+
+    #[test]
+    fn test_accept_spelling_suggestion() {
+        let mut engine = EditorEngine {
+            maybe_spell_checker: Some(Arc::new(TestSpellChecker)),
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["cat Teh".into()]);
+
+        // The caret is right after the misspelled word.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End],
+            &mut TestClipboard::default(),
+        );
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            alt_s(),
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        assert_eq2!(engine.completion_popup.items.len(), 2);
+
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Enter),
+            }),
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        assert!(!engine.completion_popup.is_open());
+        assert_eq2!(buffer.get_lines()[0].string, "cat The");
+
+        // The word at the caret is spelled correctly now.
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            alt_s(),
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        assert!(!engine.completion_popup.is_open());
+    }
+
+    #[test]
+    fn test_render_misspellings() {
+        let mut engine = EditorEngine {
+            maybe_spell_checker: Some(Arc::new(TestSpellChecker)),
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["cat Teh".into()]);
+
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 10),
+            ..Default::default()
+        };
+        let render_ops = EditorEngineApi::render_engine(
+            &mut engine,
+            &mut buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 20, row_count: 10),
+        )
+        .unwrap()
+        .get_all_render_op_in(ZOrder::Normal)
+        .unwrap();
+
+        let misspelled_positions: Vec<(Position, &str)> = render_ops
+            .windows(2)
+            .filter_map(|it| match it {
+                [RenderOp::MoveCursorPositionRelTo(_, pos), RenderOp::PaintTextWithAttributes(text, style)]
+                    if *style == Some(get_misspelled_word_style()) =>
+                {
+                    Some((*pos, text.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq2!(
+            misspelled_positions,
+            vec![(position!(col_index: 4, row_index: 0), "Teh")]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_language_metadata {
+    use r3bl_core::{assert_eq2, position};
+
+    use crate::{get_language_metadata,
+                system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              get_lines_as_strings},
+                CaretKind,
+                EditorBuffer,
+                EditorEvent,
+                IndentByLanguageMode,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                SelectionAction,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    #[test]
+    fn test_get_language_metadata() {
+        assert_eq2!(get_language_metadata("rs").unwrap().name, "rust");
+        assert_eq2!(get_language_metadata("YML").unwrap().name, "yaml");
+        assert!(get_language_metadata("xyz").is_none());
+    }
+
+    #[test]
+    fn test_tab_width_by_language() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.config_options.tab_width = 8;
+
+        let yaml_buffer = EditorBuffer::new_empty(&Some("yaml".to_owned()), &None);
+        let md_buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        assert_eq2!(engine.get_tab_width(&yaml_buffer), 2);
+        assert_eq2!(engine.get_tab_width(&md_buffer), 8);
+
+        engine.config_options.indent_by_language = IndentByLanguageMode::Disable;
+        assert_eq2!(engine.get_tab_width(&yaml_buffer), 8);
+    }
+
+    #[test]
+    fn test_toggle_line_comment_at_caret() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = EditorBuffer::new_empty(&Some("rs".to_owned()), &None);
+        buffer.set_lines(vec!["    let x = 1;".into()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::End, EditorEvent::ToggleLineComment],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["    // let x = 1;"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 17, row_index: 0)
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ToggleLineComment],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["    let x = 1;"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 14, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_toggle_line_comment_in_selection() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer = EditorBuffer::new_empty(&Some("sh".to_owned()), &None);
+        buffer.set_lines(vec!["a".into(), "".into(), "  b".into()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Select(SelectionAction::All),
+                EditorEvent::ToggleLineComment,
+            ],
+            &mut TestClipboard::default(),
+        );
+        // The blank line is left alone, and the comments are aligned.
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["# a", "", "#   b"]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ToggleLineComment],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a", "", "  b"]);
+    }
+
+    #[test]
+    fn test_toggle_line_comment_w_block_comment_syntax() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["hello".into()]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ToggleLineComment],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["<!-- hello -->"]);
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ToggleLineComment],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["hello"]);
+
+        // Languages that aren't in the table are left alone.
+        let mut buffer = EditorBuffer::new_empty(&Some("xyz".to_owned()), &None);
+        buffer.set_lines(vec!["hello".into()]);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::ToggleLineComment],
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["hello"]);
+    }
+
+    #[test]
+    fn test_toggle_line_comment_key_binding() {
+        for character in ['/', '7'] {
+            let input_event = InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character(character),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            });
+            assert_eq2!(
+                EditorEvent::try_from(input_event),
+                Ok(EditorEvent::ToggleLineComment)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_editor_macro {
+    use r3bl_core::assert_eq2;
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                CaretDirection,
+                EditorBuffer,
+                EditorEngineApi,
+                EditorEvent,
+                InputEvent,
+                Key,
+                KeyPress,
+                ModifierKeysMask,
+                SpecialKey,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn alt(character: char) -> KeyPress {
+        KeyPress::WithModifiers {
+            key: Key::Character(character),
+            mask: ModifierKeysMask::new().with_alt(),
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay_macro() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["a".into(), "b".into(), "c".into()]);
+
+        let key_presses = vec![
+            alt('r'),
+            KeyPress::Plain {
+                key: Key::Character('-'),
+            },
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Down),
+            },
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Home),
+            },
+            alt('q'),
+        ];
+        for key_press in key_presses {
+            EditorEngineApi::apply_event(
+                &mut buffer,
+                &mut engine,
+                InputEvent::Keyboard(key_press),
+                &mut TestClipboard::default(),
+            )
+            .unwrap();
+        }
+
+        // The macro events themselves aren't recorded.
+        assert!(!engine.editor_macro.is_recording());
+        assert_eq2!(
+            engine.editor_macro.get_recorded_events(),
+            &[
+                EditorEvent::InsertChar('-'),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::Home,
+            ]
+        );
+
+        EditorEngineApi::apply_editor_event(
+            &mut buffer,
+            &mut engine,
+            EditorEvent::ReplayMacro(2),
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        let lines: Vec<&str> = buffer
+            .get_lines()
+            .iter()
+            .map(|it| it.string.as_str())
+            .collect();
+        assert_eq2!(lines, vec!["-a", "-b", "-c"]);
+
+        // Each replayed edit is saved in history.
+        EditorEngineApi::apply_editor_event(
+            &mut buffer,
+            &mut engine,
+            EditorEvent::Undo,
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        assert_eq2!(buffer.get_lines()[2].string, "c");
+        assert_eq2!(buffer.get_lines()[1].string, "-b");
+    }
+
+    #[test]
+    fn test_start_recording_discards_recording_in_progress() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        for editor_event in [
+            EditorEvent::StartMacroRecording,
+            EditorEvent::InsertChar('a'),
+            EditorEvent::StartMacroRecording,
+            EditorEvent::InsertChar('b'),
+            EditorEvent::StopMacroRecording,
+        ] {
+            EditorEngineApi::apply_editor_event(
+                &mut buffer,
+                &mut engine,
+                editor_event,
+                &mut TestClipboard::default(),
+            )
+            .unwrap();
+        }
+        assert_eq2!(
+            engine.editor_macro.get_recorded_events(),
+            &[EditorEvent::InsertChar('b')]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_syntax_theme {
+    use r3bl_core::{assert_eq2, position, size};
+
+    use crate::{test_fixtures::mock_real_objects_for_editor,
+                try_load_r3bl_theme,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                FlexBox,
+                HasFocus,
+                SyntaxThemeSource};
+
+    #[test]
+    fn test_set_syntax_theme() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        assert_eq2!(engine.theme, try_load_r3bl_theme().unwrap());
+
+        // A theme that can't be loaded leaves the current one in place.
+        let result = engine.set_syntax_theme(SyntaxThemeSource::File(
+            "no/such/theme.tmTheme".to_string(),
+        ));
+        assert!(result.is_err());
+        assert_eq2!(engine.theme_version, 0);
+        assert_eq2!(engine.config_options.syntax_theme, SyntaxThemeSource::R3bl);
+
+        let mut buffer = EditorBuffer::new_empty(&Some("rs".to_owned()), &None);
+        buffer.set_lines(vec!["fn main() {}".into()]);
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 10),
+            ..Default::default()
+        };
+        let render = |engine: &mut EditorEngine, buffer: &mut EditorBuffer| {
+            EditorEngineApi::render_engine(
+                engine,
+                buffer,
+                current_box,
+                &mut HasFocus::default(),
+                size!(col_count: 20, row_count: 10),
+            )
+            .unwrap()
+        };
+        let old_render_pipeline = render(&mut engine, &mut buffer);
+
+        let source = SyntaxThemeSource::Syntect("InspiredGitHub".to_string());
+        engine.set_syntax_theme(source.clone()).unwrap();
+        assert_eq2!(engine.theme_version, 1);
+        assert_eq2!(engine.config_options.syntax_theme, source);
+
+        // The buffer is re-highlighted w/ the new theme.
+        let new_render_pipeline = render(&mut engine, &mut buffer);
+        assert!(old_render_pipeline != new_render_pipeline);
+    }
+}
+
+#[cfg(test)]
+mod test_highlight_provider {
+    use std::sync::{atomic::{AtomicUsize, Ordering},
+                    Arc};
+
+    use r3bl_core::{assert_eq2, position, size, TuiStyle, UnicodeString};
+
+    use crate::{test_fixtures::mock_real_objects_for_editor,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                FlexBox,
+                HasFocus,
+                HighlightProvider,
+                RenderOp,
+                StyleUSSpan,
+                StyleUSSpanLine,
+                ZOrder};
+
+    /// Highlights each line as a single bold span, and counts how many times it is
+    /// synced.
+    #[derive(Debug, Default)]
+    struct BoldHighlightProvider {
+        sync_count: AtomicUsize,
+    }
+
+    impl HighlightProvider for BoldHighlightProvider {
+        fn sync(&self, _: &mut EditorBuffer, _: &EditorEngine, _: usize) {
+            self.sync_count.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn highlight_line(
+            &self,
+            editor_buffer: &EditorBuffer,
+            _: &EditorEngine,
+            row_index: usize,
+        ) -> Option<StyleUSSpanLine> {
+            let line = editor_buffer.get_lines().get(row_index)?;
+            Some(StyleUSSpanLine::from(vec![StyleUSSpan::new(
+                get_bold_style(),
+                UnicodeString::from(line.string.as_str()),
+            )]))
+        }
+    }
+
+    fn get_bold_style() -> TuiStyle {
+        TuiStyle {
+            bold: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_custom_highlight_provider() {
+        let provider = Arc::new(BoldHighlightProvider::default());
+        let mut engine = EditorEngine {
+            maybe_highlight_provider: Some(provider.clone()),
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut buffer = EditorBuffer::new_empty(&Some("rs".to_owned()), &None);
+        buffer.set_lines(vec!["fn main() {}".into(), "let x = 1;".into()]);
+
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 10),
+            ..Default::default()
+        };
+        let render_ops = EditorEngineApi::render_engine(
+            &mut engine,
+            &mut buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 20, row_count: 10),
+        )
+        .unwrap()
+        .get_all_render_op_in(ZOrder::Normal)
+        .unwrap();
+
+        assert_eq2!(provider.sync_count.load(Ordering::SeqCst), 1);
+        let bold_texts: Vec<String> = render_ops
+            .iter()
+            .filter_map(|it| match it {
+                RenderOp::PaintTextWithAttributes(text, Some(style))
+                    if *style == get_bold_style() =>
+                {
+                    Some(text.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq2!(bold_texts, vec!["fn main() {}", "let x = 1;"]);
+    }
+}
+
+#[cfg(test)]
+mod test_bookmarks {
+    use r3bl_core::{assert_eq2, ch, position, size};
+
+    use crate::{get_bookmark_style,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              apply_editor_events},
+                Bookmark,
+                CaretKind,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEngineConfig,
+                EditorEvent,
+                FlexBox,
+                HasFocus,
+                LineNumberGutterMode,
+                RenderOp,
+                ZOrder,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn bookmark(row_index: usize, maybe_name: Option<char>) -> Bookmark {
+        Bookmark {
+            row_index: ch!(row_index),
+            maybe_name,
+        }
+    }
+
+    fn render(engine: &mut EditorEngine, buffer: &mut EditorBuffer) -> Vec<RenderOp> {
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 10),
+            ..Default::default()
+        };
+        EditorEngineApi::render_engine(
+            engine,
+            buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 40, row_count: 10),
+        )
+        .unwrap()
+        .get_all_render_op_in(ZOrder::Normal)
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bookmarks_follow_their_lines() {
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                line_number_gutter: LineNumberGutterMode::Absolute,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((0..8).map(|it| it.to_string()).collect());
+
+        // Bookmark the 3rd & 6th lines, and then add a line above them.
+        apply_editor_events(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::GoToLine(3),
+                EditorEvent::ToggleBookmark(None),
+                EditorEvent::GoToLine(6),
+                EditorEvent::ToggleBookmark(Some('a')),
+                EditorEvent::GoToTop,
+                EditorEvent::InsertNewLine,
+            ],
+        );
+        let render_ops = render(&mut engine, &mut buffer);
+        assert_eq2!(
+            buffer.get_bookmarks().get_bookmarks(),
+            &[bookmark(3, None), bookmark(6, Some('a'))]
+        );
+
+        // The bookmarks are painted in place of the line numbers.
+        let painted_bookmarks: Vec<&str> = render_ops
+            .iter()
+            .filter_map(|it| match it {
+                RenderOp::PaintTextWithAttributes(text, Some(style))
+                    if *style == get_bookmark_style() =>
+                {
+                    Some(text.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq2!(painted_bookmarks, vec!["● ", "a "]);
+
+        // Go to the next & previous bookmark, which wraps around.
+        let mut go_to = |editor_event: EditorEvent| {
+            apply_editor_events(&mut buffer, &mut engine, vec![editor_event]);
+            buffer.get_caret(CaretKind::ScrollAdjusted).row_index
+        };
+        assert_eq2!(go_to(EditorEvent::GoToNextBookmark), ch!(3));
+        assert_eq2!(go_to(EditorEvent::GoToNextBookmark), ch!(6));
+        assert_eq2!(go_to(EditorEvent::GoToNextBookmark), ch!(3));
+        assert_eq2!(go_to(EditorEvent::GoToPreviousBookmark), ch!(6));
+        assert_eq2!(go_to(EditorEvent::GoToTop), ch!(0));
+        assert_eq2!(go_to(EditorEvent::GoToBookmark('a')), ch!(6));
+
+        // Editing a bookmarked line keeps its bookmark, and removing it removes the
+        // bookmark.
+        apply_editor_events(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::DeleteLine,
+                EditorEvent::GoToLine(4),
+                EditorEvent::InsertChar('x'),
+            ],
+        );
+        assert_eq2!(buffer.find_named_bookmark('a'), None);
+        assert_eq2!(buffer.get_bookmarks().get_bookmarks(), &[bookmark(3, None)]);
+    }
+
+    #[test]
+    fn test_toggle_named_bookmark() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines((0..4).map(|it| it.to_string()).collect());
+
+        buffer.toggle_bookmark(ch!(0), None);
+        buffer.toggle_bookmark(ch!(2), Some('a'));
+        assert_eq2!(
+            buffer.get_bookmarks().get_bookmarks(),
+            &[bookmark(0, None), bookmark(2, Some('a'))]
+        );
+
+        // A named bookmark replaces the bookmark on its line, and is moved from the line
+        // that it was on.
+        buffer.toggle_bookmark(ch!(0), Some('a'));
+        assert_eq2!(
+            buffer.get_bookmarks().get_bookmarks(),
+            &[bookmark(0, Some('a'))]
+        );
+
+        // Toggling it again removes it, and so does toggling w/out a name.
+        buffer.toggle_bookmark(ch!(0), Some('a'));
+        assert!(buffer.get_bookmarks().get_bookmarks().is_empty());
+        buffer.toggle_bookmark(ch!(1), Some('b'));
+        buffer.toggle_bookmark(ch!(1), None);
+        assert!(buffer.get_bookmarks().get_bookmarks().is_empty());
+
+        // Rows past the end of the buffer can't be bookmarked.
+        buffer.toggle_bookmark(ch!(4), None);
+        assert!(buffer.get_bookmarks().get_bookmarks().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_telemetry {
+    use std::time::Duration;
+
+    use r3bl_core::{assert_eq2, position, size};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorTelemetry,
+                FlexBox,
+                HasFocus,
+                InputEvent,
+                Key,
+                KeyPress,
+                TelemetryKind,
+                TelemetryMode,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    fn render(engine: &mut EditorEngine, buffer: &mut EditorBuffer) {
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 20, row_count: 10),
+            ..Default::default()
+        };
+        EditorEngineApi::render_engine(
+            engine,
+            buffer,
+            current_box,
+            &mut HasFocus::default(),
+            size!(col_count: 20, row_count: 10),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_frames_are_recorded_when_enabled() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(vec!["# Title".into(), "text".into()]);
+
+        // Nothing is recorded by default.
+        render(&mut engine, &mut buffer);
+        assert!(engine.telemetry.get_frames().is_empty());
+
+        engine.config_options.telemetry = TelemetryMode::Enable;
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character('a'),
+            }),
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        render(&mut engine, &mut buffer);
+
+        assert_eq2!(engine.telemetry.get_frames().len(), 1);
+        let frame = *engine.telemetry.get_last_frame().unwrap();
+        assert!(frame.apply_event > Duration::ZERO);
+        assert!(frame.md_parse > Duration::ZERO);
+        assert!(frame.render_engine >= frame.md_parse);
+        // Markdown buffers aren't highlighted by the highlight provider.
+        assert_eq2!(frame.highlight, Duration::ZERO);
+
+        // The content is painted from the render cache, so it isn't parsed again.
+        render(&mut engine, &mut buffer);
+        assert_eq2!(engine.telemetry.get_frames().len(), 2);
+        let frame = *engine.telemetry.get_last_frame().unwrap();
+        assert_eq2!(frame.apply_event, Duration::ZERO);
+        assert_eq2!(frame.md_parse, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_oldest_frames_are_dropped() {
+        let mut telemetry = EditorTelemetry::new(2);
+        for millis in 1..=3 {
+            telemetry.record(TelemetryKind::ApplyEvent, Duration::from_millis(millis));
+            telemetry.record(TelemetryKind::ApplyEvent, Duration::from_millis(millis));
+            telemetry.end_frame(Duration::from_millis(10));
+        }
+
+        let apply_event_times: Vec<Duration> = telemetry
+            .get_frames()
+            .iter()
+            .map(|it| it.apply_event)
+            .collect();
+        assert_eq2!(
+            apply_event_times,
+            vec![Duration::from_millis(4), Duration::from_millis(6)]
+        );
+        assert_eq2!(
+            telemetry.get_slowest_frame().unwrap().apply_event,
+            Duration::from_millis(6)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_insert_text {
+    use r3bl_core::{assert_eq2, ch, position};
+
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                test_fixtures::mock_real_objects_for_editor::{self,
+                                                              apply_editor_events_w_history,
+                                                              get_lines_as_strings},
+                CaretDirection,
+                CaretKind,
+                EditorBuffer,
+                EditorEngineApi,
+                EditorEngineApplyEventResult,
+                EditorEvent,
+                InputEvent,
+                DEFAULT_SYN_HI_FILE_EXT};
+
+    #[test]
+    fn test_insert_text_in_the_middle_of_a_line() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        apply_editor_events_w_history(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::InsertString("abc xyz".into()),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::InsertText("1\n  2\n3 ".into()),
+            ],
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["abc 1", "  2", "3 xyz"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 2, row_index: 2)
+        );
+
+        // The whole text is removed in one undo step.
+        apply_editor_events_w_history(&mut buffer, &mut engine, vec![EditorEvent::Undo]);
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["abc xyz"]);
+    }
+
+    #[test]
+    fn test_paste_input_event_is_inserted_as_text() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        // The new lines aren't applied as Enter key presses (which are auto indented).
+        let result = EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Paste("fn main() {\n    foo();\n}".into()),
+            &mut TestClipboard::default(),
+        );
+        assert!(matches!(result, Ok(EditorEngineApplyEventResult::Applied)));
+        assert_eq2!(
+            get_lines_as_strings(&buffer),
+            vec!["fn main() {", "    foo();", "}"]
+        );
+    }
+
+    #[test]
+    fn test_insert_text_w_crlf_line_endings() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        apply_editor_events_w_history(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::InsertText("a\r\nb\r\n".into())],
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a", "b", ""]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn test_insert_text_w_cr_line_endings() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        // A lone `\r` is a line ending too, even when it is mixed w/ the others.
+        apply_editor_events_w_history(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::InsertText("a\rb\r\nc\nd".into())],
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a", "b", "c", "d"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 3)
+        );
+
+        // A single line w/ a `\r` at the end is split too.
+        apply_editor_events_w_history(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::InsertText("e\r".into())],
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["a", "b", "c", "de", ""]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 4)
+        );
+    }
+
+    #[test]
+    fn test_insert_text_w_tabs() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let tab_width = engine.get_tab_width(&buffer);
+        assert_eq2!(tab_width, 4);
+
+        apply_editor_events_w_history(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::InsertString("\t12".into()),
+                EditorEvent::MoveCaret(CaretDirection::Left),
+                EditorEvent::InsertText("x\n\tab\t".into()),
+            ],
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["\t1x", "\tab\t2"]);
+
+        // The tabs in the line before the caret (in the 1st line, that includes the
+        // part of the line that was before the caret) are as wide as it takes to get to
+        // the next tab stop.
+        assert_eq2!(buffer.get_lines()[0].display_width, ch!(6));
+        assert_eq2!(buffer.get_lines()[1].display_width, ch!(9));
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 8, row_index: 1)
+        );
+
+        // So the next character is inserted right after the text.
+        apply_editor_events_w_history(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::InsertString("!".into())],
+        );
+        assert_eq2!(get_lines_as_strings(&buffer), vec!["\t1x", "\tab\t!2"]);
+    }
+
+    #[test]
+    fn test_insert_text_w_many_lines() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+
+        let text = (0..10_000)
+            .map(|it| format!("line {it}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        apply_editor_events_w_history(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::InsertText(text)],
+        );
+
+        assert_eq2!(buffer.len(), ch!(10_000));
+        assert_eq2!(buffer.get_lines()[9_999].string, "line 9999");
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 9, row_index: 9_999)
+        );
+    }
+}
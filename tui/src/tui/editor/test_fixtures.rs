@@ -23,7 +23,20 @@ pub mod mock_real_objects_for_editor {
     use r3bl_test_fixtures::{output_device_ext::OutputDeviceExt as _, StdoutMock};
     use tokio::sync::mpsc;
 
-    use crate::{EditorEngine, FlexBox, GlobalData, PartialFlexBox, CHANNEL_WIDTH};
+    use crate::{system_clipboard_service_provider::test_fixtures::TestClipboard,
+                EditorBuffer,
+                EditorEngine,
+                EditorEngineApi,
+                EditorEngineApplyEventResult,
+                EditorEngineConfig,
+                EditorEvent,
+                FlexBox,
+                GlobalData,
+                InputEvent,
+                KeyPress,
+                PartialFlexBox,
+                CHANNEL_WIDTH,
+                DEFAULT_SYN_HI_FILE_EXT};
 
     pub fn make_global_data<S, AS>(
         window_size: Option<Size>,
@@ -41,6 +54,19 @@ pub mod mock_real_objects_for_editor {
             main_thread_channel_sender: sender,
             state: Default::default(),
             output_device,
+            layout_cache: Default::default(),
+            box_registry: Default::default(),
+            last_paint_stats: Default::default(),
+            render_scheduler: Default::default(),
+            resize_debouncer: Default::default(),
+            maybe_title: None,
+            maybe_paint_task: None,
+            toasts: Default::default(),
+            shortcuts: Default::default(),
+            timers: Default::default(),
+            context_menu: Default::default(),
+            maybe_splash_screen: None,
+            pending_clipboard_contents: Default::default(),
         };
 
         (global_data, stdout_mock)
@@ -71,6 +97,96 @@ pub mod mock_real_objects_for_editor {
             ..Default::default()
         }
     }
+
+    pub fn make_editor_engine_with_config(
+        config_options: EditorEngineConfig,
+    ) -> EditorEngine {
+        EditorEngine {
+            config_options,
+            ..make_editor_engine()
+        }
+    }
+
+    pub fn make_editor_buffer(lines: &[impl AsRef<str>]) -> EditorBuffer {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines(lines.iter().map(|it| it.as_ref().to_string()).collect());
+        buffer
+    }
+
+    pub fn apply_editor_event(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        event: EditorEvent,
+    ) {
+        apply_editor_events(buffer, engine, vec![event]);
+    }
+
+    pub fn apply_editor_events(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        events: Vec<EditorEvent>,
+    ) {
+        apply_editor_events_w_clipboard(
+            buffer,
+            engine,
+            events,
+            &mut TestClipboard::default(),
+        );
+    }
+
+    pub fn apply_editor_events_w_clipboard(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        events: Vec<EditorEvent>,
+        clipboard: &mut TestClipboard,
+    ) {
+        EditorEvent::apply_editor_events::<(), ()>(engine, buffer, events, clipboard);
+    }
+
+    /// Just like [apply_editor_events], except that the events are applied w/
+    /// [EditorEngineApi::apply_editor_event] (like the editor component does), so that
+    /// each one is saved in history.
+    pub fn apply_editor_events_w_history(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        events: Vec<EditorEvent>,
+    ) {
+        for event in events {
+            EditorEngineApi::apply_editor_event(
+                buffer,
+                engine,
+                event,
+                &mut TestClipboard::default(),
+            )
+            .unwrap();
+        }
+    }
+
+    /// Apply the `key_press` w/ [EditorEngineApi::apply_event] (like the editor
+    /// component does), and return whether it was applied.
+    pub fn apply_key_press(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        key_press: KeyPress,
+        clipboard: &mut TestClipboard,
+    ) -> EditorEngineApplyEventResult {
+        EditorEngineApi::apply_event(
+            buffer,
+            engine,
+            InputEvent::Keyboard(key_press),
+            clipboard,
+        )
+        .unwrap()
+    }
+
+    pub fn get_lines_as_strings(buffer: &EditorBuffer) -> Vec<String> {
+        buffer
+            .get_lines()
+            .iter()
+            .map(|it| it.string.clone())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -58,4 +58,6 @@ pub enum BorderGlyphCharacter {
 }
 
 pub const DEFAULT_CURSOR_CHAR: char = '▒';
+pub const CARET_BAR_CHAR: char = '▏';
 pub const DEFAULT_SYN_HI_FILE_EXT: &str = "md";
+pub const DEFAULT_TAB_WIDTH: usize = 4;
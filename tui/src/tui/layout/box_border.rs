@@ -0,0 +1,197 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, position, Position, Size, TuiStyle, UnicodeString};
+
+use crate::{render_ops, BorderGlyphCharacter, RenderOp, RenderOps};
+
+/// A border (w/ an optional title) that is painted around a [crate::FlexBox], inside of
+/// its [crate::FlexBox::margin]. It is painted when the box is added to the
+/// [crate::Surface], and the content of the box is inset by 1 col / row on each side, so
+/// that components don't paint over it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BoxBorder {
+    /// This is painted in the top line of the border, and it is truncated if the box
+    /// isn't wide enough for it.
+    pub maybe_title: Option<String>,
+    pub maybe_style: Option<TuiStyle>,
+}
+
+impl BoxBorder {
+    /// Returns the [RenderOps] to paint this border around the box at `origin_pos` w/ the
+    /// given `bounds_size`. Nothing is painted if the box is too small to have a border.
+    pub fn render(&self, origin_pos: Position, bounds_size: Size) -> RenderOps {
+        let mut ops = render_ops!();
+        if bounds_size.col_count < ch!(2) || bounds_size.row_count < ch!(2) {
+            return ops;
+        }
+
+        let inner_width = ch!(@to_usize bounds_size.col_count - 2);
+        let last_row_index = bounds_size.row_count - 1;
+
+        for row_index in 0..*bounds_size.row_count {
+            let row_index = ch!(row_index);
+            let text_content = if row_index == ch!(0) {
+                format!(
+                    "{}{}{}",
+                    BorderGlyphCharacter::TopLeft.as_ref(),
+                    self.render_top_line(inner_width),
+                    BorderGlyphCharacter::TopRight.as_ref()
+                )
+            } else if row_index == last_row_index {
+                format!(
+                    "{}{}{}",
+                    BorderGlyphCharacter::BottomLeft.as_ref(),
+                    BorderGlyphCharacter::Horizontal
+                        .as_ref()
+                        .repeat(inner_width),
+                    BorderGlyphCharacter::BottomRight.as_ref()
+                )
+            } else {
+                // Only the sides are painted, so that the content isn't cleared.
+                let right_col_index = bounds_size.col_count - 1;
+                for col_index in [ch!(0), right_col_index] {
+                    paint(
+                        &mut ops,
+                        origin_pos
+                            + position!(col_index: col_index, row_index: row_index),
+                        BorderGlyphCharacter::Vertical.as_ref(),
+                        self.maybe_style,
+                    );
+                }
+                continue;
+            };
+
+            paint(
+                &mut ops,
+                origin_pos + position!(col_index: 0, row_index: row_index),
+                &text_content,
+                self.maybe_style,
+            );
+        }
+
+        ops
+    }
+
+    /// The top line (w/out the corners), w/ the title in it (if there is one, and there
+    /// is enough room for it), eg: `─ Title ─────`.
+    fn render_top_line(&self, inner_width: usize) -> String {
+        let horizontal = BorderGlyphCharacter::Horizontal.as_ref();
+
+        // The title needs 1 col for the line before it, and 2 for the spaces around it.
+        let max_title_width = inner_width.saturating_sub(3);
+        let maybe_title = self
+            .maybe_title
+            .as_deref()
+            .map(|it| {
+                UnicodeString::from(it)
+                    .clip_to_width(ch!(0), ch!(max_title_width))
+                    .to_string()
+            })
+            .filter(|it| !it.is_empty());
+
+        match maybe_title {
+            Some(title) => {
+                let title_width = UnicodeString::str_display_width(&title);
+                format!(
+                    "{horizontal} {title} {}",
+                    horizontal.repeat(inner_width - 3 - title_width)
+                )
+            }
+            None => horizontal.repeat(inner_width),
+        }
+    }
+}
+
+fn paint(
+    ops: &mut RenderOps,
+    position: Position,
+    text: &str,
+    maybe_style: Option<TuiStyle>,
+) {
+    ops.push(RenderOp::ResetColor);
+    ops.push(RenderOp::MoveCursorPositionAbs(position));
+    ops.push(RenderOp::ApplyColors(maybe_style));
+    ops.push(RenderOp::PaintTextWithAttributes(text.into(), maybe_style));
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+
+    fn get_painted_texts(ops: &RenderOps) -> Vec<(Position, String)> {
+        let mut it = vec![];
+        let mut maybe_position = None;
+        for render_op in ops.iter() {
+            match render_op {
+                RenderOp::MoveCursorPositionAbs(position) => {
+                    maybe_position = Some(*position)
+                }
+                RenderOp::PaintTextWithAttributes(text, _) => {
+                    it.push((maybe_position.unwrap(), text.clone()))
+                }
+                _ => {}
+            }
+        }
+        it
+    }
+
+    #[test]
+    fn test_render_box_border_w_title() {
+        let box_border = BoxBorder {
+            maybe_title: Some("Logs".into()),
+            ..Default::default()
+        };
+        let ops = box_border.render(
+            position!(col_index: 2, row_index: 1),
+            size!(col_count: 12, row_count: 3),
+        );
+        assert_eq2!(
+            get_painted_texts(&ops),
+            vec![
+                (position!(col_index: 2, row_index: 1), "╭─ Logs ───╮".into()),
+                (position!(col_index: 2, row_index: 2), "│".into()),
+                (position!(col_index: 13, row_index: 2), "│".into()),
+                (position!(col_index: 2, row_index: 3), "╰──────────╯".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_box_border_truncates_title() {
+        let box_border = BoxBorder {
+            maybe_title: Some("Long title".into()),
+            ..Default::default()
+        };
+        let ops =
+            box_border.render(Position::default(), size!(col_count: 8, row_count: 2));
+        assert_eq2!(
+            get_painted_texts(&ops),
+            vec![
+                (Position::default(), "╭─ Lon ╮".into()),
+                (position!(col_index: 0, row_index: 1), "╰──────╯".into()),
+            ]
+        );
+
+        // Too small for a border.
+        let ops =
+            box_border.render(Position::default(), size!(col_count: 8, row_count: 1));
+        assert!(get_painted_texts(&ops).is_empty());
+    }
+}
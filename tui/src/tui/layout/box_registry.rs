@@ -0,0 +1,141 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::Position;
+
+use super::{FlexBox, FlexBoxId, Surface};
+
+/// The boxes that were laid out by a [Surface] in the last render, so that they can be
+/// looked up (by id, or by a position in the window) after the surface is gone, eg: to
+/// route mouse events to the component under the pointer, or to anchor a popup to a box.
+///
+/// The surface is usually created from scratch in each [crate::App::app_render], so save
+/// this in [crate::GlobalData::box_registry] at the end of it (w/
+/// [Surface::get_box_registry]).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FlexBoxRegistry {
+    /// In the order that they were added, so a box comes after its container (and
+    /// floating boxes come after all the others).
+    boxes: Vec<FlexBox>,
+}
+
+impl FlexBoxRegistry {
+    pub fn new(boxes: Vec<FlexBox>) -> Self { Self { boxes } }
+
+    /// Returns the bounds of the box w/ the given `id` (the last one, if there is more
+    /// than one).
+    pub fn get_box(&self, id: FlexBoxId) -> Option<&FlexBox> {
+        self.boxes.iter().rev().find(|it| it.id == id)
+    }
+
+    /// Returns the visible boxes that contain the `position`, from the top most one
+    /// (eg: a child, or a floating box) to the bottom most one (eg: the root box).
+    pub fn get_boxes_at(&self, position: Position) -> impl Iterator<Item = &FlexBox> {
+        self.boxes
+            .iter()
+            .rev()
+            .filter(move |it| it.visible && it.contains(position))
+    }
+
+    /// Returns the id of the top most box that contains the `position`.
+    pub fn get_id_at(&self, position: Position) -> Option<FlexBoxId> {
+        self.get_boxes_at(position).next().map(|it| it.id)
+    }
+
+    /// Returns all the boxes, in the order that they were laid out.
+    pub fn get_boxes(&self) -> &[FlexBox] { &self.boxes }
+
+    pub fn len(&self) -> usize { self.boxes.len() }
+
+    pub fn is_empty(&self) -> bool { self.boxes.is_empty() }
+}
+
+impl Surface {
+    /// Returns the boxes that were laid out since [crate::LayoutManagement::surface_start]
+    /// (including the floating ones).
+    pub fn get_box_registry(&self) -> FlexBoxRegistry {
+        FlexBoxRegistry::new(self.laid_out_boxes.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, position, size};
+
+    use super::*;
+
+    fn make_box(id: u8, col_index: u16, col_count: u16) -> FlexBox {
+        FlexBox {
+            id: FlexBoxId::from(id),
+            origin_pos: position!(col_index: col_index, row_index: 0),
+            bounds_size: size!(col_count: col_count, row_count: 10),
+            ..Default::default()
+        }
+    }
+
+    fn box_registry() -> FlexBoxRegistry {
+        FlexBoxRegistry::new(vec![
+            make_box(0, 0, 40),
+            make_box(1, 0, 20),
+            make_box(2, 20, 20),
+            FlexBox {
+                visible: false,
+                ..make_box(3, 20, 10)
+            },
+        ])
+    }
+
+    #[test]
+    fn test_get_box() {
+        let box_registry = box_registry();
+        assert_eq2!(
+            box_registry.get_box(FlexBoxId::from(2)),
+            Some(&make_box(2, 20, 20))
+        );
+        assert_eq2!(box_registry.get_box(FlexBoxId::from(9)), None);
+    }
+
+    #[test]
+    fn test_get_id_at() {
+        let box_registry = box_registry();
+
+        // The child is on top of its container.
+        assert_eq2!(
+            box_registry.get_id_at(position!(col_index: 5, row_index: 5)),
+            Some(FlexBoxId::from(1))
+        );
+
+        // The hidden box is skipped.
+        assert_eq2!(
+            box_registry.get_id_at(position!(col_index: 25, row_index: 5)),
+            Some(FlexBoxId::from(2))
+        );
+        assert_eq2!(
+            box_registry
+                .get_boxes_at(position!(col_index: 25, row_index: 5))
+                .map(|it| it.id)
+                .collect::<Vec<_>>(),
+            vec![FlexBoxId::from(2), FlexBoxId::from(0)]
+        );
+
+        // Outside of all the boxes.
+        assert_eq2!(
+            box_registry.get_id_at(position!(col_index: 40, row_index: 5)),
+            None
+        );
+    }
+}
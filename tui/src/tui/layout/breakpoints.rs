@@ -0,0 +1,142 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::Size;
+
+use super::Surface;
+
+/// A layout that is used when the [Surface] is at least `min_size` large (in both
+/// directions).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Breakpoint<L> {
+    pub min_size: Size,
+    pub layout: L,
+}
+
+/// Alternate layouts for a [Surface], which are picked based on its size, eg: to collapse
+/// a 2 col layout into stacked rows when the terminal is less than 80 cols wide.
+///
+/// The layout (`L`) is usually an enum that is defined by the app, and that is matched
+/// on in its [crate::SurfaceRender] to decide how to lay out its boxes. Since the surface
+/// is laid out again every time that it is rendered (which happens when the terminal is
+/// resized), the active layout is picked w/ [Surface::get_active_layout] on each render.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Breakpoints<L> {
+    default_layout: L,
+    breakpoints: Vec<Breakpoint<L>>,
+}
+
+impl<L> Breakpoints<L> {
+    /// The `default_layout` is used when the surface is smaller than all the breakpoints.
+    pub fn new(default_layout: L) -> Self {
+        Self {
+            default_layout,
+            breakpoints: vec![],
+        }
+    }
+
+    /// Use the `layout` when the surface is at least `min_size` large. When more than one
+    /// breakpoint fits, the one that was added last is used, so add them from the
+    /// smallest to the largest.
+    pub fn add(mut self, min_size: Size, layout: L) -> Self {
+        self.breakpoints.push(Breakpoint { min_size, layout });
+        self
+    }
+
+    /// Returns the layout for a surface w/ the given `size`.
+    pub fn get_active_layout(&self, size: Size) -> &L {
+        self.breakpoints
+            .iter()
+            .rev()
+            .find(|it| {
+                size.col_count >= it.min_size.col_count
+                    && size.row_count >= it.min_size.row_count
+            })
+            .map_or(&self.default_layout, |it| &it.layout)
+    }
+}
+
+impl Surface {
+    /// Returns the layout in the `breakpoints` for the size of this surface, which is set
+    /// in [crate::LayoutManagement::surface_start].
+    pub fn get_active_layout<'a, L>(&self, breakpoints: &'a Breakpoints<L>) -> &'a L {
+        breakpoints.get_active_layout(self.box_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, position, size, CommonResult};
+
+    use super::*;
+    use crate::{LayoutManagement, SurfaceProps};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Columns {
+        Stacked,
+        TwoCols,
+        ThreeCols,
+    }
+
+    fn breakpoints() -> Breakpoints<Columns> {
+        Breakpoints::new(Columns::Stacked)
+            .add(size!(col_count: 80, row_count: 0), Columns::TwoCols)
+            .add(size!(col_count: 120, row_count: 20), Columns::ThreeCols)
+    }
+
+    #[test]
+    fn test_get_active_layout() {
+        let breakpoints = breakpoints();
+        assert_eq2!(
+            breakpoints.get_active_layout(size!(col_count: 79, row_count: 40)),
+            &Columns::Stacked
+        );
+        assert_eq2!(
+            breakpoints.get_active_layout(size!(col_count: 80, row_count: 10)),
+            &Columns::TwoCols
+        );
+        // Wide enough, but not high enough for 3 cols.
+        assert_eq2!(
+            breakpoints.get_active_layout(size!(col_count: 150, row_count: 10)),
+            &Columns::TwoCols
+        );
+        assert_eq2!(
+            breakpoints.get_active_layout(size!(col_count: 150, row_count: 30)),
+            &Columns::ThreeCols
+        );
+    }
+
+    #[test]
+    fn test_surface_picks_layout_on_resize() -> CommonResult<()> {
+        let breakpoints = breakpoints();
+        let mut surface = Surface::default();
+
+        for (window_size, expected) in [
+            (size!(col_count: 100, row_count: 30), Columns::TwoCols),
+            (size!(col_count: 60, row_count: 30), Columns::Stacked),
+        ] {
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: window_size,
+            })?;
+            assert_eq2!(surface.get_active_layout(&breakpoints), &expected);
+            surface.surface_end()?;
+        }
+
+        Ok(())
+    }
+}
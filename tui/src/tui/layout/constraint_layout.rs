@@ -0,0 +1,267 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use cassowary::{strength::{MEDIUM, REQUIRED, STRONG, WEAK},
+                Expression,
+                Solver,
+                Variable,
+                WeightedRelation::{EQ, GE, LE}};
+use r3bl_core::{ch, position, size, ChUnit, CommonResult, Percent, Position, Size};
+use serde::{Deserialize, Serialize};
+
+use super::LayoutDirection;
+use crate::{LayoutError, LayoutErrorType};
+
+/// A constraint on the size of a segment of a [ConstraintLayout]. The constraints are
+/// relaxed in this order when they can't all be met: [LayoutConstraint::Fill] first, then
+/// [LayoutConstraint::Length] & [LayoutConstraint::Percent], and then
+/// [LayoutConstraint::Min] & [LayoutConstraint::Max].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum LayoutConstraint {
+    /// Exactly this many cols (or rows).
+    Length(ChUnit),
+    /// A percentage of the size of the container.
+    Percent(Percent),
+    /// At least this many cols (or rows).
+    Min(ChUnit),
+    /// At most this many cols (or rows).
+    Max(ChUnit),
+    /// A share (w/ this weight) of the space that the other segments don't use.
+    Fill(u16),
+}
+
+/// Splits a [crate::FlexBox] into segments (in its [LayoutDirection]) whose sizes are
+/// found by a linear constraint solver ([cassowary]). This can express relationships that
+/// [r3bl_core::RequestedSizePercent] can't, eg: "the sidebar is 25% wide, but at least 20
+/// cols, and the editor gets the rest":
+///
+/// ```ignore
+/// ConstraintLayout::new(vec![
+///     vec![LayoutConstraint::Percent(percent!(25)?), LayoutConstraint::Min(ch!(20))],
+///     vec![LayoutConstraint::Fill(1)],
+/// ])
+/// ```
+///
+/// Set it in the [crate::FlexBoxProps::maybe_constraint_layout] of the container. Its
+/// children are placed in the segments in the order that they are added, and their
+/// [r3bl_core::RequestedSizePercent] is ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct ConstraintLayout {
+    /// The constraints for each segment.
+    pub segments: Vec<Vec<LayoutConstraint>>,
+}
+
+impl ConstraintLayout {
+    pub fn new(segments: Vec<Vec<LayoutConstraint>>) -> Self { Self { segments } }
+
+    /// Returns the origin & size of each segment, in a box at `origin_pos` w/ the given
+    /// `bounds_size`, that is split in the given `dir`.
+    pub fn calc_segment_bounds(
+        &self,
+        dir: LayoutDirection,
+        origin_pos: Position,
+        bounds_size: Size,
+    ) -> CommonResult<Vec<(Position, Size)>> {
+        let available = match dir {
+            LayoutDirection::Horizontal => bounds_size.col_count,
+            LayoutDirection::Vertical => bounds_size.row_count,
+        };
+
+        let mut offset = ch!(0);
+        let segment_bounds = self
+            .calc_segment_sizes(available)?
+            .into_iter()
+            .map(|segment_size| {
+                let it = match dir {
+                    LayoutDirection::Horizontal => (
+                        origin_pos + position!(col_index: offset, row_index: 0),
+                        size!(col_count: segment_size, row_count: bounds_size.row_count),
+                    ),
+                    LayoutDirection::Vertical => (
+                        origin_pos + position!(col_index: 0, row_index: offset),
+                        size!(col_count: bounds_size.col_count, row_count: segment_size),
+                    ),
+                };
+                offset += segment_size;
+                it
+            })
+            .collect();
+
+        Ok(segment_bounds)
+    }
+
+    /// Returns the size of each segment, which add up to `available`.
+    pub fn calc_segment_sizes(&self, available: ChUnit) -> CommonResult<Vec<ChUnit>> {
+        if self.segments.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // The segments are next to each other, so segment `n` is between the edges `n`
+        // and `n + 1`.
+        let total = f64::from(*available);
+        let edges: Vec<Variable> =
+            (0..=self.segments.len()).map(|_| Variable::new()).collect();
+        let mut constraints = vec![
+            edges[0] | EQ(REQUIRED) | 0.0,
+            edges[self.segments.len()] | EQ(REQUIRED) | total,
+        ];
+        let mut fills: Vec<(Expression, f64)> = vec![];
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            let segment_size = edges[index + 1] - edges[index];
+            constraints.push(segment_size.clone() | GE(REQUIRED) | 0.0);
+            for constraint in segment {
+                constraints.push(match constraint {
+                    LayoutConstraint::Min(it) => {
+                        segment_size.clone() | GE(STRONG) | f64::from(**it)
+                    }
+                    LayoutConstraint::Max(it) => {
+                        segment_size.clone() | LE(STRONG) | f64::from(**it)
+                    }
+                    LayoutConstraint::Length(it) => {
+                        segment_size.clone() | EQ(MEDIUM) | f64::from(**it)
+                    }
+                    LayoutConstraint::Percent(it) => {
+                        segment_size.clone()
+                            | EQ(MEDIUM)
+                            | f64::from(*it.calc_percentage(available))
+                    }
+                    LayoutConstraint::Fill(weight) => {
+                        fills.push((segment_size.clone(), f64::from(*weight)));
+                        continue;
+                    }
+                });
+            }
+        }
+
+        // The fill segments share the space in proportion to their weights.
+        for pair in fills.windows(2) {
+            let [(lhs_size, lhs_weight), (rhs_size, rhs_weight)] = pair else {
+                continue;
+            };
+            constraints.push(
+                lhs_size.clone() * *rhs_weight
+                    | EQ(WEAK)
+                    | rhs_size.clone() * *lhs_weight,
+            );
+        }
+
+        let mut solver = Solver::new();
+        if let Err(err) = solver.add_constraints(&constraints) {
+            LayoutError::new_error_result(
+                LayoutErrorType::InvalidConstraintLayout,
+                format!("{err:?}"),
+            )?
+        }
+
+        // The edges are rounded (and not the sizes), so that the sizes add up.
+        let mut previous_edge = ch!(0);
+        let segment_sizes = edges[1..]
+            .iter()
+            .map(|edge| {
+                let edge_value = solver.get_value(*edge).round().clamp(0.0, total);
+                let edge = ch!(edge_value as u16).max(previous_edge);
+                let segment_size = edge - previous_edge;
+                previous_edge = edge;
+                segment_size
+            })
+            .collect();
+
+        Ok(segment_sizes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, percent};
+
+    use super::*;
+
+    fn sidebar_and_editor() -> CommonResult<ConstraintLayout> {
+        Ok(ConstraintLayout::new(vec![
+            vec![
+                LayoutConstraint::Percent(percent!(25)?),
+                LayoutConstraint::Min(ch!(20)),
+            ],
+            vec![LayoutConstraint::Fill(1)],
+        ]))
+    }
+
+    #[test]
+    fn test_calc_segment_sizes() -> CommonResult<()> {
+        let layout = sidebar_and_editor()?;
+        assert_eq2!(layout.calc_segment_sizes(ch!(120))?, vec![ch!(30), ch!(90)]);
+
+        // 25% is less than the min size.
+        assert_eq2!(layout.calc_segment_sizes(ch!(60))?, vec![ch!(20), ch!(40)]);
+
+        // There isn't enough space for the min size.
+        assert_eq2!(layout.calc_segment_sizes(ch!(10))?, vec![ch!(10), ch!(0)]);
+
+        assert_eq2!(
+            ConstraintLayout::default().calc_segment_sizes(ch!(10))?,
+            vec![]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calc_segment_sizes_w_fill_weights_and_max() -> CommonResult<()> {
+        let layout = ConstraintLayout::new(vec![
+            vec![LayoutConstraint::Length(ch!(10))],
+            vec![LayoutConstraint::Fill(1)],
+            vec![LayoutConstraint::Fill(2)],
+        ]);
+        assert_eq2!(
+            layout.calc_segment_sizes(ch!(70))?,
+            vec![ch!(10), ch!(20), ch!(40)]
+        );
+
+        let layout = ConstraintLayout::new(vec![
+            vec![LayoutConstraint::Fill(1), LayoutConstraint::Max(ch!(15))],
+            vec![LayoutConstraint::Fill(1)],
+        ]);
+        assert_eq2!(layout.calc_segment_sizes(ch!(50))?, vec![ch!(15), ch!(35)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calc_segment_bounds() -> CommonResult<()> {
+        let layout = sidebar_and_editor()?;
+        assert_eq2!(
+            layout.calc_segment_bounds(
+                LayoutDirection::Vertical,
+                position!(col_index: 5, row_index: 1),
+                size!(col_count: 30, row_count: 80),
+            )?,
+            vec![
+                (
+                    position!(col_index: 5, row_index: 1),
+                    size!(col_count: 30, row_count: 20)
+                ),
+                (
+                    position!(col_index: 5, row_index: 21),
+                    size!(col_count: 30, row_count: 60)
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+}
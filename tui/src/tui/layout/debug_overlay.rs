@@ -0,0 +1,157 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ANSIBasicColor, TuiColor, TuiStyle};
+
+use super::{BoxBorder, FlexBox};
+use crate::{render_ops, RenderOps};
+
+/// The colors of the outlines, which are cycled through so that boxes that are next to
+/// (or inside of) each other can be told apart.
+const DEBUG_OVERLAY_COLORS: [ANSIBasicColor; 5] = [
+    ANSIBasicColor::Magenta,
+    ANSIBasicColor::Cyan,
+    ANSIBasicColor::Yellow,
+    ANSIBasicColor::Green,
+    ANSIBasicColor::Red,
+];
+
+/// Returns the [RenderOps] to paint an outline around each of the `laid_out_boxes`, w/
+/// its id and its size in the top line, eg: `╭─ #2 40x20 ───╮`. This is painted by
+/// [crate::LayoutManagement::surface_end] (at [crate::ZOrder::Glass]) when
+/// [crate::Surface::is_debug_overlay_enabled] is set, to see where the boxes are w/out
+/// having to log their sizes.
+///
+/// The outline is painted over the edges of the box (and the content that is there), so
+/// it is only meant to be turned on while debugging a layout.
+pub fn render_debug_overlay(laid_out_boxes: &[FlexBox]) -> RenderOps {
+    let mut ops = render_ops!();
+    for (index, flex_box) in laid_out_boxes.iter().enumerate() {
+        let color = DEBUG_OVERLAY_COLORS[index % DEBUG_OVERLAY_COLORS.len()];
+        let outline = BoxBorder {
+            maybe_title: Some(format!(
+                "#{} {}x{}",
+                flex_box.id.0,
+                *flex_box.bounds_size.col_count,
+                *flex_box.bounds_size.row_count
+            )),
+            maybe_style: Some(TuiStyle {
+                color_fg: Some(TuiColor::Basic(color)),
+                ..Default::default()
+            }),
+        };
+        ops.extend(
+            outline
+                .render(flex_box.origin_pos, flex_box.bounds_size)
+                .list,
+        );
+    }
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2,
+                    get_tui_styles,
+                    position,
+                    requested_size_percent,
+                    size,
+                    CommonResult};
+
+    use super::*;
+    use crate::{box_end,
+                box_props,
+                box_start,
+                FlexBoxId,
+                LayoutDirection,
+                LayoutManagement,
+                RenderOp,
+                Surface,
+                SurfaceProps,
+                ZOrder};
+
+    #[test]
+    fn test_render_debug_overlay() {
+        let laid_out_boxes = [
+            FlexBox {
+                id: FlexBoxId::from(1),
+                origin_pos: position!(col_index: 0, row_index: 0),
+                bounds_size: size!(col_count: 12, row_count: 3),
+                ..Default::default()
+            },
+            FlexBox {
+                id: FlexBoxId::from(2),
+                origin_pos: position!(col_index: 12, row_index: 0),
+                bounds_size: size!(col_count: 12, row_count: 3),
+                ..Default::default()
+            },
+        ];
+        let ops = render_debug_overlay(&laid_out_boxes);
+
+        let texts: Vec<String> = ops
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text.to_string()),
+                _ => None,
+            })
+            .filter(|it| it.starts_with('╭'))
+            .collect();
+        assert_eq2!(
+            texts,
+            vec!["╭─ #1 12x3 ╮".to_string(), "╭─ #2 12x3 ╮".to_string()]
+        );
+
+        // Each box has its own color.
+        let styles: Vec<Option<TuiStyle>> = ops
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(_, maybe_style) => Some(*maybe_style),
+                _ => None,
+            })
+            .collect();
+        assert_ne!(styles.first(), styles.last());
+    }
+
+    #[test]
+    fn test_surface_paints_debug_overlay_when_enabled() -> CommonResult<()> {
+        for is_debug_overlay_enabled in [false, true] {
+            let mut surface = Surface {
+                is_debug_overlay_enabled,
+                ..Default::default()
+            };
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count: 20, row_count: 5),
+            })?;
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 []
+            }
+            box_end!(in: surface);
+            surface.surface_end()?;
+
+            assert_eq2!(
+                surface.render_pipeline.get(&ZOrder::Glass).is_some(),
+                is_debug_overlay_enabled
+            );
+        }
+        Ok(())
+    }
+}
@@ -17,7 +17,14 @@
 
 use std::fmt::Debug;
 
-use r3bl_core::{Position, RequestedSizePercent, Size, TuiStyle};
+use r3bl_core::{ch,
+                position,
+                size,
+                ChUnit,
+                Position,
+                RequestedSizePercent,
+                Size,
+                TuiStyle};
 use serde::{Deserialize, Serialize};
 
 use super::FlexBoxId;
@@ -32,9 +39,235 @@ pub enum LayoutDirection {
     Vertical,
 }
 
+/// Whether the children of a [FlexBox] that don't fit in what is left of the current
+/// line (a row for [LayoutDirection::Horizontal], or a col for
+/// [LayoutDirection::Vertical]) are moved to the next one, like CSS `flex-wrap`. This is
+/// useful for toolbars and lists of tags, whose children have a [FixedSize].
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum FlexWrap {
+    /// The children overflow the line (and are clipped).
+    #[default]
+    NoWrap,
+    /// Each line is as high (or wide) as its largest child.
+    Wrap,
+}
+
+/// Limits on the size of a [FlexBox], in addition to its [RequestedSizePercent]. The
+/// percentages are converted into a size first, which is then clamped to these limits, so
+/// that a box doesn't collapse below a usable size on small terminals (or grow too large
+/// on big ones).
+///
+/// A box is never larger than its container though, so on a terminal that is too small
+/// for the minimum size, the box takes up all the space that is available.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct SizeConstraints {
+    pub min_width: Option<ChUnit>,
+    pub max_width: Option<ChUnit>,
+    pub min_height: Option<ChUnit>,
+    pub max_height: Option<ChUnit>,
+    /// The box is shrunk (after it is clamped to the other limits) so that it has this
+    /// [AspectRatio], eg: for an image or a graph that has to stay square-ish. This can
+    /// make it smaller than its min size.
+    pub maybe_aspect_ratio: Option<AspectRatio>,
+}
+
+impl SizeConstraints {
+    /// Clamp the `requested_size` to these limits, and to the `container_bounds`.
+    pub fn apply(&self, requested_size: Size, container_bounds: Size) -> Size {
+        let clamped_size = size!(
+            col_count: clamp(
+                requested_size.col_count,
+                self.min_width,
+                self.max_width,
+                container_bounds.col_count
+            ),
+            row_count: clamp(
+                requested_size.row_count,
+                self.min_height,
+                self.max_height,
+                container_bounds.row_count
+            )
+        );
+        match self.maybe_aspect_ratio {
+            Some(aspect_ratio) => aspect_ratio.fit(clamped_size),
+            None => clamped_size,
+        }
+    }
+}
+
+/// Terminal cells are about twice as high as they are wide, so a box that looks square
+/// is twice as many cols wide as it is rows high.
+pub const CELL_ASPECT_RATIO: u16 = 2;
+
+/// The ratio of the width to the height of a box as it looks on the screen (and not of
+/// its cols to its rows), see [CELL_ASPECT_RATIO]. The box is only approximately this
+/// shape, since its size is rounded down to whole cols & rows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct AspectRatio {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl AspectRatio {
+    pub fn new(width: u16, height: u16) -> Self { Self { width, height } }
+
+    pub fn square() -> Self { Self::new(1, 1) }
+
+    /// Returns the largest size w/ this aspect ratio that fits in `size`. The `size` is
+    /// returned as is, if either side of the ratio is 0.
+    pub fn fit(&self, size: Size) -> Size {
+        if self.width == 0 || self.height == 0 {
+            return size;
+        }
+
+        // A box that looks `width` wide & `height` high is `width * 2` cols by `height`
+        // rows.
+        let width = u32::from(self.width) * u32::from(CELL_ASPECT_RATIO);
+        let height = u32::from(self.height);
+        let col_count = u32::from(*size.col_count);
+        let row_count = u32::from(*size.row_count);
+
+        // Use all the rows if there are enough cols for them, otherwise use all the cols.
+        let col_count_for_rows = row_count * width / height;
+        match col_count_for_rows <= col_count {
+            true => {
+                size!(col_count: col_count_for_rows as u16, row_count: size.row_count)
+            }
+            false => {
+                let row_count_for_cols = col_count * height / width;
+                size!(col_count: size.col_count, row_count: row_count_for_cols as u16)
+            }
+        }
+    }
+}
+
+fn clamp(
+    value: ChUnit,
+    maybe_min: Option<ChUnit>,
+    maybe_max: Option<ChUnit>,
+    available: ChUnit,
+) -> ChUnit {
+    let value = maybe_max.map_or(value, |max| value.min(max));
+    let value = maybe_min.map_or(value, |min| value.max(min));
+    value.min(available)
+}
+
+/// An absolute size for a [FlexBox], eg: for a status bar that is 1 row high, or a
+/// sidebar that is 30 cols wide. The dimensions that are set are used instead of the ones
+/// from its [RequestedSizePercent] (they are still clamped to its [SizeConstraints]).
+///
+/// The container of a box w/ a fixed size has to know how much space its fixed size
+/// children take up before they are added (see [FlexBox::fixed_children_size]), so that
+/// its other children can share the space that is left over.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct FixedSize {
+    pub col_count: Option<ChUnit>,
+    pub row_count: Option<ChUnit>,
+}
+
+/// Where a [FlexBox] is placed in the space that it is given by its container (along one
+/// axis), when it doesn't fill it, eg: because it has a [FixedSize].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum Alignment {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// The box is resized to fill the space.
+    Stretch,
+}
+
+impl Alignment {
+    /// Returns the offset & size of a box w/ the given `size` in the `available` space.
+    pub fn apply(&self, size: ChUnit, available: ChUnit) -> (ChUnit, ChUnit) {
+        let size = size.min(available);
+        match self {
+            Alignment::Start => (ch!(0), size),
+            Alignment::Center => ((available - size) / 2, size),
+            Alignment::End => (available - size, size),
+            Alignment::Stretch => (ch!(0), available),
+        }
+    }
+}
+
+/// The horizontal & vertical [Alignment] of a [FlexBox]. A box that is laid out in its
+/// container's [LayoutDirection] gets all the space that it asks for in that direction,
+/// so it is only aligned in the other one. A root box is aligned in the
+/// [crate::Surface], in both directions.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct BoxAlignment {
+    pub horizontal: Alignment,
+    pub vertical: Alignment,
+}
+
+impl BoxAlignment {
+    /// Centered in both directions.
+    pub fn center() -> Self {
+        Self {
+            horizontal: Alignment::Center,
+            vertical: Alignment::Center,
+        }
+    }
+
+    /// Returns the origin & size of a box w/ the given `bounds_size`, when it is aligned
+    /// in the space at `origin_pos` w/ the given `available_size`.
+    pub fn apply(
+        &self,
+        origin_pos: Position,
+        bounds_size: Size,
+        available_size: Size,
+    ) -> (Position, Size) {
+        let (col_offset, col_count) = self
+            .horizontal
+            .apply(bounds_size.col_count, available_size.col_count);
+        let (row_offset, row_count) = self
+            .vertical
+            .apply(bounds_size.row_count, available_size.row_count);
+        (
+            origin_pos + position!(col_index: col_offset, row_index: row_offset),
+            size!(col_count: col_count, row_count: row_count),
+        )
+    }
+}
+
+/// The space on each side of a [FlexBox], for its [FlexBox::margin] and
+/// [FlexBox::padding].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct BoxSides {
+    pub top: ChUnit,
+    pub right: ChUnit,
+    pub bottom: ChUnit,
+    pub left: ChUnit,
+}
+
+impl BoxSides {
+    /// The same space on all the sides.
+    pub fn all(it: impl Into<ChUnit>) -> Self {
+        let it = it.into();
+        Self {
+            top: it,
+            right: it,
+            bottom: it,
+            left: it,
+        }
+    }
+
+    /// Returns the origin & size of the area inside these sides, for a box at
+    /// `origin_pos` w/ the given `bounds_size`.
+    pub fn inset(&self, origin_pos: Position, bounds_size: Size) -> (Position, Size) {
+        (
+            origin_pos + position!(col_index: self.left, row_index: self.top),
+            size!(
+                col_count: bounds_size.col_count - self.left - self.right,
+                row_count: bounds_size.row_count - self.top - self.bottom
+            ),
+        )
+    }
+}
+
 /// A box is a rectangle with a position and size. The direction of the box determines how
 /// it's contained elements are positioned.
-#[derive(Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub struct FlexBox {
     pub id: FlexBoxId,
     pub dir: LayoutDirection,
@@ -43,12 +276,75 @@ pub struct FlexBox {
     pub style_adjusted_origin_pos: Position,
     pub style_adjusted_bounds_size: Size,
     pub requested_size_percent: RequestedSizePercent,
+    pub size_constraints: SizeConstraints,
+    pub fixed_size: FixedSize,
+    /// The cols (for [LayoutDirection::Horizontal]) or rows (for
+    /// [LayoutDirection::Vertical]) that the children of this box that have a
+    /// [FixedSize] take up. The [RequestedSizePercent] of the other children is a
+    /// percentage of the space that is left over.
+    pub fixed_children_size: ChUnit,
+    /// The cols (or rows) that the hidden children of this box would have taken up, and
+    /// that haven't been given to a visible child yet. This is added to the size of the
+    /// next visible child (that isn't in a grid cell or a constraint layout segment).
+    pub hidden_children_size: ChUnit,
+    /// The space around the box (outside of its border). The `style_adjusted_*` origin
+    /// and size are inside of it.
+    pub margin: BoxSides,
+    /// The space between the border of the box (inside of its margin) and its content.
+    /// This is in addition to the padding from the style.
+    pub padding: BoxSides,
+    /// The content is inset by 1 on each side (between the margin and the padding) when
+    /// a [crate::BoxBorder] is painted around the box.
+    pub has_border: bool,
+    /// A hidden box (or a box in a hidden box) has no size, and the component in it
+    /// isn't rendered w/ [crate::render_component_in_current_box!].
+    pub visible: bool,
+    pub wrap: FlexWrap,
+    /// The rows (for [LayoutDirection::Horizontal]) or cols (for
+    /// [LayoutDirection::Vertical]) of the current line of children, when this box has
+    /// [FlexWrap::Wrap]. This is the size of its largest child.
+    pub wrap_line_size: ChUnit,
     pub insertion_pos_for_next_box: Option<Position>,
     pub maybe_computed_style: Option<TuiStyle>,
 }
 
+impl Default for FlexBox {
+    fn default() -> Self {
+        Self {
+            id: FlexBoxId::default(),
+            dir: LayoutDirection::default(),
+            origin_pos: Position::default(),
+            bounds_size: Size::default(),
+            style_adjusted_origin_pos: Position::default(),
+            style_adjusted_bounds_size: Size::default(),
+            requested_size_percent: RequestedSizePercent::default(),
+            size_constraints: SizeConstraints::default(),
+            fixed_size: FixedSize::default(),
+            fixed_children_size: ChUnit::default(),
+            hidden_children_size: ChUnit::default(),
+            margin: BoxSides::default(),
+            padding: BoxSides::default(),
+            has_border: false,
+            visible: true,
+            wrap: FlexWrap::default(),
+            wrap_line_size: ChUnit::default(),
+            insertion_pos_for_next_box: None,
+            maybe_computed_style: None,
+        }
+    }
+}
+
 impl FlexBox {
     pub fn get_computed_style(&self) -> Option<TuiStyle> { self.maybe_computed_style }
+
+    /// Whether the `position` is inside of the bounds of this box (including its margin).
+    pub fn contains(&self, position: Position) -> bool {
+        let end_pos = self.origin_pos + self.bounds_size;
+        position.col_index >= self.origin_pos.col_index
+            && position.row_index >= self.origin_pos.row_index
+            && position.col_index < end_pos.col_index
+            && position.row_index < end_pos.row_index
+    }
 }
 
 impl Debug for FlexBox {
@@ -64,6 +360,16 @@ impl Debug for FlexBox {
                 &self.style_adjusted_bounds_size,
             )
             .field("requested_size_percent", &self.requested_size_percent)
+            .field("size_constraints", &self.size_constraints)
+            .field("fixed_size", &self.fixed_size)
+            .field("fixed_children_size", &self.fixed_children_size)
+            .field("hidden_children_size", &self.hidden_children_size)
+            .field("margin", &self.margin)
+            .field("padding", &self.padding)
+            .field("has_border", &self.has_border)
+            .field("visible", &self.visible)
+            .field("wrap", &self.wrap)
+            .field("wrap_line_size", &self.wrap_line_size)
             .field(
                 "insertion_pos_for_next_box",
                 format_option!(&self.insertion_pos_for_next_box),
@@ -78,7 +384,7 @@ impl Debug for FlexBox {
 
 #[cfg(test)]
 mod tests {
-    use r3bl_core::{ok, position, requested_size_percent, size, CommonResult};
+    use r3bl_core::{ch, ok, position, requested_size_percent, size, CommonResult};
 
     use super::*;
 
@@ -95,6 +401,16 @@ mod tests {
             flex_box.requested_size_percent,
             RequestedSizePercent::default()
         );
+        assert_eq!(flex_box.size_constraints, SizeConstraints::default());
+        assert_eq!(flex_box.fixed_size, FixedSize::default());
+        assert_eq!(flex_box.fixed_children_size, ch!(0));
+        assert_eq!(flex_box.margin, BoxSides::default());
+        assert_eq!(flex_box.padding, BoxSides::default());
+        assert_eq!(flex_box.hidden_children_size, ch!(0));
+        assert!(!flex_box.has_border);
+        assert!(flex_box.visible);
+        assert_eq!(flex_box.wrap, FlexWrap::NoWrap);
+        assert_eq!(flex_box.wrap_line_size, ch!(0));
         assert!(flex_box.insertion_pos_for_next_box.is_none());
         assert!(flex_box.maybe_computed_style.is_none());
     }
@@ -128,6 +444,18 @@ mod tests {
                 width: 50,
                 height: 50
             ),
+            size_constraints: SizeConstraints {
+                min_width: Some(ch!(10)),
+                ..Default::default()
+            },
+            fixed_size: FixedSize {
+                col_count: None,
+                row_count: Some(ch!(1)),
+            },
+            fixed_children_size: ch!(0),
+            margin: BoxSides::all(1),
+            padding: BoxSides::default(),
+            has_border: true,
             insertion_pos_for_next_box: position! { col_index: 9, row_index: 10 }.into(),
             maybe_computed_style: TuiStyle::default().into(),
         };
@@ -141,9 +469,118 @@ mod tests {
         assert!(debug_str.contains("style_adjusted_origin_pos"));
         assert!(debug_str.contains("style_adjusted_bounds_size"));
         assert!(debug_str.contains("requested_size_percent"));
+        assert!(debug_str.contains("size_constraints"));
+        assert!(debug_str.contains("fixed_size"));
+        assert!(debug_str.contains("fixed_children_size"));
+        assert!(debug_str.contains("margin"));
+        assert!(debug_str.contains("padding"));
+        assert!(debug_str.contains("has_border"));
         assert!(debug_str.contains("insertion_pos_for_next_box"));
         assert!(debug_str.contains("maybe_computed_style"));
 
         ok!()
     }
+
+    #[test]
+    fn test_size_constraints_apply() {
+        let container_bounds = size! { col_count: 100, row_count: 20 };
+        let size_constraints = SizeConstraints {
+            min_width: Some(ch!(30)),
+            max_width: Some(ch!(60)),
+            min_height: Some(ch!(5)),
+            max_height: None,
+            maybe_aspect_ratio: None,
+        };
+
+        // Within the limits.
+        assert_eq!(
+            size_constraints
+                .apply(size! { col_count: 40, row_count: 10 }, container_bounds),
+            size! { col_count: 40, row_count: 10 }
+        );
+
+        // Below the min & above the max.
+        assert_eq!(
+            size_constraints
+                .apply(size! { col_count: 10, row_count: 2 }, container_bounds),
+            size! { col_count: 30, row_count: 5 }
+        );
+        assert_eq!(
+            size_constraints
+                .apply(size! { col_count: 80, row_count: 20 }, container_bounds),
+            size! { col_count: 60, row_count: 20 }
+        );
+
+        // The min doesn't make the box larger than its container.
+        assert_eq!(
+            size_constraints.apply(
+                size! { col_count: 10, row_count: 2 },
+                size! { col_count: 20, row_count: 3 }
+            ),
+            size! { col_count: 20, row_count: 3 }
+        );
+    }
+
+    #[test]
+    fn test_aspect_ratio_fit() {
+        // A square box is twice as wide (in cols) as it is high (in rows).
+        assert_eq!(
+            AspectRatio::square().fit(size! { col_count: 100, row_count: 20 }),
+            size! { col_count: 40, row_count: 20 }
+        );
+        assert_eq!(
+            AspectRatio::square().fit(size! { col_count: 30, row_count: 20 }),
+            size! { col_count: 30, row_count: 15 }
+        );
+        assert_eq!(
+            AspectRatio::new(16, 9).fit(size! { col_count: 200, row_count: 27 }),
+            size! { col_count: 96, row_count: 27 }
+        );
+        assert_eq!(
+            AspectRatio::new(0, 1).fit(size! { col_count: 30, row_count: 20 }),
+            size! { col_count: 30, row_count: 20 }
+        );
+
+        // The aspect ratio is applied after the other limits.
+        let size_constraints = SizeConstraints {
+            max_height: Some(ch!(10)),
+            maybe_aspect_ratio: Some(AspectRatio::square()),
+            ..Default::default()
+        };
+        assert_eq!(
+            size_constraints.apply(
+                size! { col_count: 100, row_count: 20 },
+                size! { col_count: 100, row_count: 20 }
+            ),
+            size! { col_count: 20, row_count: 10 }
+        );
+    }
+
+    #[test]
+    fn test_box_sides_inset() {
+        let box_sides = BoxSides {
+            top: ch!(1),
+            right: ch!(2),
+            bottom: ch!(3),
+            left: ch!(4),
+        };
+        assert_eq!(
+            box_sides.inset(
+                position! { col_index: 10, row_index: 10 },
+                size! { col_count: 20, row_count: 10 }
+            ),
+            (
+                position! { col_index: 14, row_index: 11 },
+                size! { col_count: 14, row_count: 6 }
+            )
+        );
+
+        // The size doesn't go below 0.
+        assert_eq!(
+            BoxSides::all(5)
+                .inset(Position::default(), size! { col_count: 4, row_count: 4 })
+                .1,
+            size! { col_count: 0, row_count: 0 }
+        );
+    }
 }
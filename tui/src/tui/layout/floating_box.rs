@@ -0,0 +1,238 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{position, Position, Size, TuiStyle};
+
+use super::{BoxBorder, FlexBox, FlexBoxId, SurfaceBounds};
+use crate::ZOrder;
+
+/// Where a floating box is placed, see [FloatingBoxProps].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FloatingBoxAnchor {
+    /// At the given position in the window.
+    Absolute(Position),
+    /// Right above the box w/ the given id (that was already added to the
+    /// [crate::Surface]), aligned w/ its left edge. It is placed below it instead, if
+    /// there isn't enough room above it.
+    Above(FlexBoxId),
+    /// Right below the box w/ the given id, aligned w/ its left edge. It is placed above
+    /// it instead, if there isn't enough room below it.
+    Below(FlexBoxId),
+    /// Centered over the box w/ the given id.
+    CenteredIn(FlexBoxId),
+    /// Centered in the [crate::Surface].
+    CenteredInSurface,
+}
+
+impl FloatingBoxAnchor {
+    /// The id of the box that this is anchored to, if any.
+    pub fn get_anchor_box_id(&self) -> Option<FlexBoxId> {
+        match self {
+            FloatingBoxAnchor::Above(id)
+            | FloatingBoxAnchor::Below(id)
+            | FloatingBoxAnchor::CenteredIn(id) => Some(*id),
+            FloatingBoxAnchor::Absolute(_) | FloatingBoxAnchor::CenteredInSurface => None,
+        }
+    }
+
+    /// Returns the origin of a floating box w/ the given `size`. The `maybe_anchor_box` is
+    /// the box w/ the [Self::get_anchor_box_id] (the surface is used if it is [None]).
+    /// The floating box is moved so that it stays inside of the `surface_bounds` (as much
+    /// as it can).
+    pub fn calc_origin_pos(
+        &self,
+        size: Size,
+        surface_bounds: SurfaceBounds,
+        maybe_anchor_box: Option<&FlexBox>,
+    ) -> Position {
+        let SurfaceBounds {
+            origin_pos: surface_origin_pos,
+            box_size: surface_size,
+        } = surface_bounds;
+        let (anchor_origin_pos, anchor_size) = match maybe_anchor_box {
+            Some(anchor_box) => (anchor_box.origin_pos, anchor_box.bounds_size),
+            None => (surface_origin_pos, surface_size),
+        };
+
+        let room_above = anchor_origin_pos.row_index - surface_origin_pos.row_index;
+        let anchor_bottom_row_index = anchor_origin_pos.row_index + anchor_size.row_count;
+        let room_below = (surface_origin_pos.row_index + surface_size.row_count)
+            - anchor_bottom_row_index;
+        let above = position!(
+            col_index: anchor_origin_pos.col_index,
+            row_index: anchor_origin_pos.row_index - size.row_count
+        );
+        let below = position!(
+            col_index: anchor_origin_pos.col_index,
+            row_index: anchor_bottom_row_index
+        );
+
+        let origin_pos = match self {
+            FloatingBoxAnchor::Absolute(position) => *position,
+            FloatingBoxAnchor::Above(_) => {
+                match size.row_count > room_above && size.row_count <= room_below {
+                    true => below,
+                    false => above,
+                }
+            }
+            FloatingBoxAnchor::Below(_) => {
+                match size.row_count > room_below && size.row_count <= room_above {
+                    true => above,
+                    false => below,
+                }
+            }
+            FloatingBoxAnchor::CenteredIn(_) | FloatingBoxAnchor::CenteredInSurface => {
+                anchor_origin_pos
+                    + position!(
+                        col_index: anchor_size.col_count / 2 - size.col_count / 2,
+                        row_index: anchor_size.row_count / 2 - size.row_count / 2
+                    )
+            }
+        };
+
+        // Keep it inside of the surface.
+        let max_origin_pos = surface_origin_pos
+            + position!(
+                col_index: surface_size.col_count - size.col_count,
+                row_index: surface_size.row_count - size.row_count
+            );
+        position!(
+            col_index: origin_pos
+                .col_index
+                .min(max_origin_pos.col_index)
+                .max(surface_origin_pos.col_index),
+            row_index: origin_pos
+                .row_index
+                .min(max_origin_pos.row_index)
+                .max(surface_origin_pos.row_index)
+        )
+    }
+}
+
+/// Properties that are needed to add a floating box to a [crate::Surface] w/
+/// [crate::Surface::add_floating_box]. Floating boxes aren't part of the layout (of the
+/// stack of boxes), they are painted on top of it, eg: for tooltips, popups, and context
+/// menus. Use [crate::render_component_in_floating_box!] to render a component in one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FloatingBoxProps {
+    pub id: FlexBoxId,
+    pub anchor: FloatingBoxAnchor,
+    /// This is clipped to the size of the surface.
+    pub size: Size,
+    /// The box (its background, border, and the component in it) is painted at this
+    /// [ZOrder].
+    pub z_order: ZOrder,
+    pub maybe_border: Option<BoxBorder>,
+    pub maybe_styles: Option<Vec<TuiStyle>>,
+}
+
+impl Default for FloatingBoxProps {
+    fn default() -> Self {
+        Self {
+            id: FlexBoxId::default(),
+            anchor: FloatingBoxAnchor::CenteredInSurface,
+            size: Size::default(),
+            z_order: ZOrder::Glass,
+            maybe_border: None,
+            maybe_styles: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+
+    fn surface_bounds() -> SurfaceBounds {
+        SurfaceBounds {
+            origin_pos: position!(col_index: 0, row_index: 1),
+            box_size: size!(col_count: 80, row_count: 20),
+        }
+    }
+
+    fn anchor_box(row_index: u16) -> FlexBox {
+        FlexBox {
+            id: FlexBoxId::from(1),
+            origin_pos: position!(col_index: 10, row_index: row_index),
+            bounds_size: size!(col_count: 20, row_count: 2),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_calc_origin_pos_centered() {
+        let size = size!(col_count: 10, row_count: 4);
+        assert_eq2!(
+            FloatingBoxAnchor::CenteredInSurface.calc_origin_pos(
+                size,
+                surface_bounds(),
+                None
+            ),
+            position!(col_index: 35, row_index: 9)
+        );
+        assert_eq2!(
+            FloatingBoxAnchor::CenteredIn(FlexBoxId::from(1)).calc_origin_pos(
+                size!(col_count: 10, row_count: 2),
+                surface_bounds(),
+                Some(&anchor_box(5))
+            ),
+            position!(col_index: 15, row_index: 5)
+        );
+    }
+
+    #[test]
+    fn test_calc_origin_pos_above_and_below() {
+        let size = size!(col_count: 30, row_count: 5);
+        let anchor = FloatingBoxAnchor::Below(FlexBoxId::from(1));
+
+        assert_eq2!(
+            anchor.calc_origin_pos(size, surface_bounds(), Some(&anchor_box(5))),
+            position!(col_index: 10, row_index: 7)
+        );
+
+        // There isn't enough room below, so it is placed above.
+        assert_eq2!(
+            anchor.calc_origin_pos(size, surface_bounds(), Some(&anchor_box(17))),
+            position!(col_index: 10, row_index: 12)
+        );
+
+        // There isn't enough room above, so it is placed below.
+        assert_eq2!(
+            FloatingBoxAnchor::Above(FlexBoxId::from(1)).calc_origin_pos(
+                size,
+                surface_bounds(),
+                Some(&anchor_box(3))
+            ),
+            position!(col_index: 10, row_index: 5)
+        );
+    }
+
+    #[test]
+    fn test_calc_origin_pos_stays_in_surface() {
+        assert_eq2!(
+            FloatingBoxAnchor::Absolute(position!(col_index: 75, row_index: 0))
+                .calc_origin_pos(
+                    size!(col_count: 10, row_count: 3),
+                    surface_bounds(),
+                    None
+                ),
+            position!(col_index: 70, row_index: 1)
+        );
+    }
+}
@@ -0,0 +1,239 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, position, size, ChUnit, CommonResult, Percent, Position, Size};
+use serde::{Deserialize, Serialize};
+
+use crate::{unwrap_or_err, LayoutErrorType};
+
+/// How the size of a row or a col of a [GridLayout] is calculated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum GridTrack {
+    /// A fixed number of rows (or cols).
+    Fixed(ChUnit),
+    /// A percentage of the height (or width) of the grid.
+    Percent(Percent),
+    /// An equal share of the space that is left over after the fixed and percentage
+    /// tracks got theirs.
+    Auto,
+}
+
+/// The cell of a [GridLayout] that a [crate::FlexBox] is placed in (see
+/// [crate::FlexBoxProps::maybe_grid_cell]). It can span more than one row and col.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct GridCell {
+    pub row_index: usize,
+    pub col_index: usize,
+    pub row_span: usize,
+    pub col_span: usize,
+}
+
+impl GridCell {
+    /// A cell that spans one row and one col.
+    pub fn new(row_index: usize, col_index: usize) -> Self {
+        Self {
+            row_index,
+            col_index,
+            row_span: 1,
+            col_span: 1,
+        }
+    }
+}
+
+/// Arranges the children of a [crate::FlexBox] into rows and cols, which is easier than
+/// nesting boxes w/ different [crate::LayoutDirection]s for dashboard like layouts. Set
+/// it in the [crate::FlexBoxProps::maybe_grid_layout] of the container, and the
+/// [crate::FlexBoxProps::maybe_grid_cell] of each of its children.
+///
+/// The children are placed in their cells, and not one after the other, so they can be
+/// added in any order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct GridLayout {
+    pub row_tracks: Vec<GridTrack>,
+    pub col_tracks: Vec<GridTrack>,
+}
+
+impl GridLayout {
+    pub fn new(row_tracks: Vec<GridTrack>, col_tracks: Vec<GridTrack>) -> Self {
+        Self {
+            row_tracks,
+            col_tracks,
+        }
+    }
+
+    /// Returns the origin & size of the `grid_cell`, in a grid that is at `origin_pos` w/
+    /// the given `bounds_size`.
+    pub fn calc_cell_bounds(
+        &self,
+        grid_cell: GridCell,
+        origin_pos: Position,
+        bounds_size: Size,
+    ) -> CommonResult<(Position, Size)> {
+        let row_sizes = calc_track_sizes(&self.row_tracks, bounds_size.row_count);
+        let col_sizes = calc_track_sizes(&self.col_tracks, bounds_size.col_count);
+
+        let (row_offset, row_count) = unwrap_or_err!(
+            calc_span(&row_sizes, grid_cell.row_index, grid_cell.row_span),
+            LayoutErrorType::InvalidGridCell,
+            "{:?} is outside of the {} rows of the grid",
+            grid_cell,
+            row_sizes.len()
+        );
+        let (col_offset, col_count) = unwrap_or_err!(
+            calc_span(&col_sizes, grid_cell.col_index, grid_cell.col_span),
+            LayoutErrorType::InvalidGridCell,
+            "{:?} is outside of the {} cols of the grid",
+            grid_cell,
+            col_sizes.len()
+        );
+
+        Ok((
+            origin_pos + position!(col_index: col_offset, row_index: row_offset),
+            size!(col_count: col_count, row_count: row_count),
+        ))
+    }
+}
+
+/// The fixed and percentage tracks get their sizes first (as much of them as fits in the
+/// `available` space), and the auto tracks share what is left over. The first auto
+/// tracks get the extra rows (or cols) when it can't be shared evenly.
+pub fn calc_track_sizes(tracks: &[GridTrack], available: ChUnit) -> Vec<ChUnit> {
+    let mut remaining = available;
+    let mut track_sizes: Vec<ChUnit> = tracks
+        .iter()
+        .map(|track| {
+            let track_size = match track {
+                GridTrack::Fixed(it) => *it,
+                GridTrack::Percent(it) => it.calc_percentage(available),
+                GridTrack::Auto => ch!(0),
+            }
+            .min(remaining);
+            remaining = remaining - track_size;
+            track_size
+        })
+        .collect();
+
+    let auto_track_count = tracks
+        .iter()
+        .filter(|it| matches!(it, GridTrack::Auto))
+        .count();
+    if auto_track_count > 0 {
+        let remaining = ch!(@to_usize remaining);
+        let share = remaining / auto_track_count;
+        let extra = remaining % auto_track_count;
+        tracks
+            .iter()
+            .zip(track_sizes.iter_mut())
+            .filter(|(track, _)| matches!(track, GridTrack::Auto))
+            .enumerate()
+            .for_each(|(auto_track_index, (_, track_size))| {
+                *track_size = ch!(share + usize::from(auto_track_index < extra));
+            });
+    }
+
+    track_sizes
+}
+
+/// Returns the offset & size of the tracks from `start_index` that are spanned, or [None]
+/// if they aren't in the grid.
+fn calc_span(
+    track_sizes: &[ChUnit],
+    start_index: usize,
+    span: usize,
+) -> Option<(ChUnit, ChUnit)> {
+    if span == 0 {
+        return None;
+    }
+    let end_index = start_index.checked_add(span)?;
+    let spanned_track_sizes = track_sizes.get(start_index..end_index)?;
+    let sum = |it: &[ChUnit]| it.iter().fold(ch!(0), |acc, it| acc + *it);
+    Some((sum(&track_sizes[..start_index]), sum(spanned_track_sizes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::percent;
+
+    use super::*;
+
+    #[test]
+    fn test_calc_track_sizes() -> CommonResult<()> {
+        let tracks = [
+            GridTrack::Fixed(ch!(10)),
+            GridTrack::Auto,
+            GridTrack::Percent(percent!(25)?),
+            GridTrack::Auto,
+        ];
+        assert_eq!(
+            calc_track_sizes(&tracks, ch!(41)),
+            vec![ch!(10), ch!(11), ch!(10), ch!(10)]
+        );
+
+        // The fixed & percentage tracks don't get more than the available space.
+        assert_eq!(
+            calc_track_sizes(&tracks, ch!(12)),
+            vec![ch!(10), ch!(0), ch!(2), ch!(0)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calc_cell_bounds() -> CommonResult<()> {
+        let grid_layout = GridLayout::new(
+            vec![GridTrack::Fixed(ch!(1)), GridTrack::Auto],
+            vec![GridTrack::Auto, GridTrack::Auto, GridTrack::Auto],
+        );
+        let origin_pos = position!(col_index: 5, row_index: 5);
+        let bounds_size = size!(col_count: 30, row_count: 10);
+
+        assert_eq!(
+            grid_layout.calc_cell_bounds(GridCell::new(1, 1), origin_pos, bounds_size)?,
+            (
+                position!(col_index: 15, row_index: 6),
+                size!(col_count: 10, row_count: 9)
+            )
+        );
+
+        // Spans all the cols of the 1st row.
+        let grid_cell = GridCell {
+            col_span: 3,
+            ..GridCell::new(0, 0)
+        };
+        assert_eq!(
+            grid_layout.calc_cell_bounds(grid_cell, origin_pos, bounds_size)?,
+            (
+                position!(col_index: 5, row_index: 5),
+                size!(col_count: 30, row_count: 1)
+            )
+        );
+
+        // Outside of the grid.
+        let grid_cell = GridCell {
+            row_span: 2,
+            ..GridCell::new(1, 0)
+        };
+        assert!(grid_layout
+            .calc_cell_bounds(grid_cell, origin_pos, bounds_size)
+            .is_err());
+        assert!(grid_layout
+            .calc_cell_bounds(GridCell::new(0, 3), origin_pos, bounds_size)
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use r3bl_core::{Position, Size};
+
+use super::FlexBox;
+
+/// A box that was laid out in a previous render, see [LayoutCache].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedBox {
+    /// The box that was added to the stack of boxes.
+    pub flex_box: FlexBox,
+    /// The container of the box, after the box was added to it (its insertion position
+    /// moves past the box). This is [None] for a root box.
+    pub maybe_updated_container: Option<FlexBox>,
+    /// The segments of the [crate::FlexBoxProps::maybe_constraint_layout] of the box, so
+    /// that the constraints don't have to be solved again.
+    pub maybe_constraint_segments: Option<Vec<(Position, Size)>>,
+}
+
+/// Saves the boxes that are laid out by a [crate::Surface], so that they don't have to be
+/// laid out again in the next render, when nothing about them has changed. This reduces
+/// the CPU used by each render for layouts that don't change (which is most of them).
+///
+/// Each box is saved w/ a key that is a hash of everything that its layout depends on:
+/// - its [crate::FlexBoxProps],
+/// - the bounds of the surface,
+/// - its container (and the grid or constraint layout of its container).
+///
+/// So a box is laid out again when any of these change. All the boxes are laid out again
+/// when the surface is resized, and the ones that weren't used in a render are removed
+/// at the end of it.
+///
+/// The surface is usually created from scratch in each [crate::App::app_render], so the
+/// cache has to be kept somewhere else between renders, eg: in
+/// [crate::GlobalData::layout_cache]. Move it into
+/// [crate::Surface::maybe_layout_cache] before the surface is laid out, and take it back
+/// out after.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LayoutCache {
+    maybe_surface_bounds: Option<(Position, Size)>,
+    cached_boxes: HashMap<u64, CachedBox>,
+    used_keys: HashSet<u64>,
+    hit_count: usize,
+}
+
+impl LayoutCache {
+    /// Called by [crate::LayoutManagement::surface_start]. Everything is cleared if the
+    /// surface was moved or resized since the last render.
+    pub fn start_frame(&mut self, origin_pos: Position, box_size: Size) {
+        if self.maybe_surface_bounds != Some((origin_pos, box_size)) {
+            self.cached_boxes.clear();
+            self.maybe_surface_bounds = Some((origin_pos, box_size));
+        }
+        self.used_keys.clear();
+        self.hit_count = 0;
+    }
+
+    /// Called by [crate::LayoutManagement::surface_end]. The boxes that weren't laid out
+    /// in this render are removed.
+    pub fn end_frame(&mut self) {
+        let used_keys = &self.used_keys;
+        self.cached_boxes.retain(|key, _| used_keys.contains(key));
+    }
+
+    pub fn get(&mut self, key: u64) -> Option<CachedBox> {
+        let it = self.cached_boxes.get(&key).cloned()?;
+        self.used_keys.insert(key);
+        self.hit_count += 1;
+        Some(it)
+    }
+
+    pub fn insert(&mut self, key: u64, cached_box: CachedBox) {
+        self.used_keys.insert(key);
+        self.cached_boxes.insert(key, cached_box);
+    }
+
+    /// Clear everything, eg: when the app changes its layout in a way that isn't in the
+    /// [crate::FlexBoxProps] of the boxes.
+    pub fn invalidate(&mut self) {
+        self.cached_boxes.clear();
+        self.maybe_surface_bounds = None;
+    }
+
+    /// The number of boxes that were taken from the cache (instead of being laid out)
+    /// since the last [Self::start_frame].
+    pub fn get_hit_count(&self) -> usize { self.hit_count }
+
+    pub fn len(&self) -> usize { self.cached_boxes.len() }
+
+    pub fn is_empty(&self) -> bool { self.cached_boxes.is_empty() }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, position, size};
+
+    use super::*;
+
+    fn cached_box() -> CachedBox {
+        CachedBox {
+            flex_box: FlexBox::default(),
+            maybe_updated_container: None,
+            maybe_constraint_segments: None,
+        }
+    }
+
+    #[test]
+    fn test_unused_boxes_are_removed_at_end_of_frame() {
+        let origin_pos = position!(col_index: 0, row_index: 0);
+        let box_size = size!(col_count: 80, row_count: 20);
+        let mut cache = LayoutCache::default();
+
+        cache.start_frame(origin_pos, box_size);
+        cache.insert(1, cached_box());
+        cache.insert(2, cached_box());
+        cache.end_frame();
+        assert_eq2!(cache.len(), 2);
+
+        cache.start_frame(origin_pos, box_size);
+        assert_eq2!(cache.get(1), Some(cached_box()));
+        assert_eq2!(cache.get(3), None);
+        assert_eq2!(cache.get_hit_count(), 1);
+        cache.end_frame();
+        assert_eq2!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_is_cleared_on_resize() {
+        let origin_pos = position!(col_index: 0, row_index: 0);
+        let mut cache = LayoutCache::default();
+
+        cache.start_frame(origin_pos, size!(col_count: 80, row_count: 20));
+        cache.insert(1, cached_box());
+        cache.end_frame();
+
+        cache.start_frame(origin_pos, size!(col_count: 100, row_count: 20));
+        assert_eq2!(cache.get(1), None);
+        assert!(cache.is_empty());
+    }
+}
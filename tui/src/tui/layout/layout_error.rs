@@ -44,6 +44,10 @@ pub enum LayoutErrorType {
     ContainerBoxBoundsUndefined,
     BoxCursorPositionUndefined,
     ContentCursorPositionUndefined,
+    GridLayoutUndefined,
+    InvalidGridCell,
+    AnchorBoxNotFound,
+    InvalidConstraintLayout,
 }
 
 /// Implement [`Error`] trait.
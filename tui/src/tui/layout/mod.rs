@@ -16,23 +16,46 @@
  */
 
 // Attach source files.
+pub mod box_border;
+pub mod box_registry;
+pub mod breakpoints;
+pub mod constraint_layout;
+pub mod debug_overlay;
 pub mod flex_box;
 pub mod flex_box_id;
+pub mod floating_box;
+pub mod grid_layout;
 pub mod layout_and_positioning_traits;
+pub mod layout_cache;
 pub mod layout_error;
 pub mod partial_flex_box;
 pub mod props;
+pub mod split_pane;
 pub mod surface;
 
 // Re-export the public items.
+pub use box_border::*;
+pub use box_registry::*;
+pub use breakpoints::*;
+pub use constraint_layout::*;
+pub use debug_overlay::*;
 pub use flex_box::*;
 pub use flex_box_id::*;
+pub use floating_box::*;
+pub use grid_layout::*;
 pub use layout_and_positioning_traits::*;
+pub use layout_cache::*;
 pub use layout_error::*;
 pub use partial_flex_box::*;
 pub use props::*;
+pub use split_pane::*;
 pub use surface::*;
 
 // Tests.
 mod test_surface_2_col_complex;
 mod test_surface_2_col_simple;
+mod test_surface_constraint_layout;
+mod test_surface_floating_box;
+mod test_surface_grid;
+mod test_surface_layout_cache;
+mod test_surface_wrap;
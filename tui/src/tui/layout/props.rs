@@ -15,19 +15,80 @@
  *   limitations under the License.
  */
 
-use r3bl_core::{Position, RequestedSizePercent, Size, TuiStyle};
+use r3bl_core::{ChUnit, Position, RequestedSizePercent, Size, TuiStyle};
 
-use super::{FlexBoxId, LayoutDirection};
+use super::{BoxAlignment,
+            BoxBorder,
+            BoxSides,
+            ConstraintLayout,
+            FixedSize,
+            FlexBoxId,
+            FlexWrap,
+            GridCell,
+            GridLayout,
+            LayoutDirection,
+            SizeConstraints};
 
 /// Properties that are needed to create a [crate::FlexBox].
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Hash)]
 pub struct FlexBoxProps {
     pub id: FlexBoxId,
     pub dir: LayoutDirection,
     pub requested_size_percent: RequestedSizePercent,
+    pub size_constraints: SizeConstraints,
+    pub fixed_size: FixedSize,
+    /// See [crate::FlexBox::fixed_children_size].
+    pub fixed_children_size: ChUnit,
+    /// Where the box is placed in the space that its container gives it, when it doesn't
+    /// fill it.
+    pub alignment: BoxAlignment,
+    /// When this is false, the box doesn't take up any space (the space that it would
+    /// have taken up is given to the next visible box in its container), and the
+    /// component in it isn't rendered. See [crate::FlexBox::visible].
+    pub visible: bool,
+    /// Move the children of this box to the next line (row or col) when they don't fit in
+    /// what is left of the current one, instead of letting them overflow it.
+    pub wrap: FlexWrap,
+    /// Arrange the children of this box in a grid, instead of one after the other.
+    pub maybe_grid_layout: Option<GridLayout>,
+    /// The cell that this box is placed in, when its container has a
+    /// [FlexBoxProps::maybe_grid_layout]. The size of the box is the size of the cell.
+    pub maybe_grid_cell: Option<GridCell>,
+    /// Place the children of this box in the segments of a [ConstraintLayout], instead
+    /// of sizing them w/ their [FlexBoxProps::requested_size_percent].
+    pub maybe_constraint_layout: Option<ConstraintLayout>,
+    /// See [crate::FlexBox::margin].
+    pub margin: BoxSides,
+    /// See [crate::FlexBox::padding].
+    pub padding: BoxSides,
+    /// Paint a border around the box (between its margin and its padding).
+    pub maybe_border: Option<BoxBorder>,
     pub maybe_styles: Option<Vec<TuiStyle>>,
 }
 
+impl Default for FlexBoxProps {
+    fn default() -> Self {
+        Self {
+            id: FlexBoxId::default(),
+            dir: LayoutDirection::default(),
+            requested_size_percent: RequestedSizePercent::default(),
+            size_constraints: SizeConstraints::default(),
+            fixed_size: FixedSize::default(),
+            fixed_children_size: ChUnit::default(),
+            alignment: BoxAlignment::default(),
+            visible: true,
+            wrap: FlexWrap::default(),
+            maybe_grid_layout: None,
+            maybe_grid_cell: None,
+            maybe_constraint_layout: None,
+            margin: BoxSides::default(),
+            padding: BoxSides::default(),
+            maybe_border: None,
+            maybe_styles: None,
+        }
+    }
+}
+
 /// Properties that are needed to create a [crate::Surface].
 #[derive(Clone, Debug, Default)]
 pub struct SurfaceProps {
@@ -37,7 +98,7 @@ pub struct SurfaceProps {
 
 #[cfg(test)]
 mod tests {
-    use r3bl_core::{ok, position, requested_size_percent, size, CommonResult};
+    use r3bl_core::{ch, ok, position, requested_size_percent, size, CommonResult};
 
     use super::*;
     use crate::tui::layout::{FlexBoxId, LayoutDirection};
@@ -51,6 +112,18 @@ mod tests {
             props.requested_size_percent,
             RequestedSizePercent::default()
         );
+        assert_eq!(props.size_constraints, SizeConstraints::default());
+        assert_eq!(props.fixed_size, FixedSize::default());
+        assert_eq!(props.fixed_children_size, ch!(0));
+        assert_eq!(props.alignment, BoxAlignment::default());
+        assert!(props.visible);
+        assert_eq!(props.wrap, FlexWrap::NoWrap);
+        assert_eq!(props.maybe_grid_layout, None);
+        assert_eq!(props.maybe_grid_cell, None);
+        assert_eq!(props.maybe_constraint_layout, None);
+        assert_eq!(props.margin, BoxSides::default());
+        assert_eq!(props.padding, BoxSides::default());
+        assert_eq!(props.maybe_border, None);
         assert_eq!(props.maybe_styles, None);
     }
 
@@ -60,6 +133,24 @@ mod tests {
             id: FlexBoxId::from(10),
             dir: LayoutDirection::Horizontal,
             requested_size_percent: requested_size_percent!(width: 50, height: 50),
+            size_constraints: SizeConstraints {
+                min_height: Some(ch!(5)),
+                ..Default::default()
+            },
+            fixed_size: FixedSize {
+                col_count: Some(ch!(30)),
+                row_count: None,
+            },
+            fixed_children_size: ch!(0),
+            alignment: BoxAlignment::center(),
+            visible: false,
+            wrap: FlexWrap::Wrap,
+            maybe_grid_layout: None,
+            maybe_grid_cell: Some(GridCell::new(1, 2)),
+            maybe_constraint_layout: None,
+            margin: BoxSides::default(),
+            padding: BoxSides::all(1),
+            maybe_border: Some(BoxBorder::default()),
             maybe_styles: Some(vec![TuiStyle::default()]),
         };
         assert_eq!(props.id.0, 10);
@@ -68,6 +159,13 @@ mod tests {
             props.requested_size_percent,
             requested_size_percent!(width: 50, height: 50)
         );
+        assert_eq!(props.size_constraints.min_height, Some(ch!(5)));
+        assert_eq!(props.fixed_size.col_count, Some(ch!(30)));
+        assert_eq!(props.alignment, BoxAlignment::center());
+        assert!(!props.visible);
+        assert_eq!(props.wrap, FlexWrap::Wrap);
+        assert_eq!(props.maybe_grid_cell, Some(GridCell::new(1, 2)));
+        assert_eq!(props.padding, BoxSides::all(1));
         assert_eq!(props.maybe_styles.unwrap().len(), 1);
 
         ok!()
@@ -0,0 +1,506 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, position, size, ChUnit, Position, Size, TuiStyle};
+use serde::{Deserialize, Serialize};
+
+use super::{FlexBox, FlexBoxId};
+use crate::{render_ops,
+            BorderGlyphCharacter,
+            Button,
+            InputEvent,
+            Key,
+            KeyPress,
+            ModifierKeysMask,
+            MouseInput,
+            MouseInputKind,
+            RenderOp,
+            RenderOps,
+            SpecialKey};
+
+/// Neither pane of a [SplitPane] is made smaller than this by default.
+pub const DEFAULT_MIN_PANE_SIZE: u16 = 5;
+
+/// How the space of a [SplitPane] is split between its 2 panes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum SplitKind {
+    /// The panes are side by side, w/ a vertical divider between them, which is moved w/
+    /// <kbd>Ctrl+Left</kbd> & <kbd>Ctrl+Right</kbd>.
+    HSplit,
+    /// The panes are stacked, w/ a horizontal divider between them, which is moved w/
+    /// <kbd>Ctrl+Up</kbd> & <kbd>Ctrl+Down</kbd>.
+    VSplit,
+}
+
+/// The bounds of the panes of a [SplitPane] & the divider between them, which are
+/// returned by [SplitPane::calc_layout].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SplitPaneLayout {
+    pub first_pane: (Position, Size),
+    pub divider: (Position, Size),
+    pub second_pane: (Position, Size),
+}
+
+impl SplitPaneLayout {
+    /// The box to render the component of the first pane in, eg: w/
+    /// [crate::render_component_in_given_box!].
+    pub fn get_first_pane_box(&self, id: FlexBoxId) -> FlexBox {
+        make_pane_box(id, self.first_pane)
+    }
+
+    pub fn get_second_pane_box(&self, id: FlexBoxId) -> FlexBox {
+        make_pane_box(id, self.second_pane)
+    }
+}
+
+fn make_pane_box(id: FlexBoxId, (origin_pos, bounds_size): (Position, Size)) -> FlexBox {
+    FlexBox {
+        id,
+        origin_pos,
+        bounds_size,
+        style_adjusted_origin_pos: origin_pos,
+        style_adjusted_bounds_size: bounds_size,
+        ..Default::default()
+    }
+}
+
+/// What happened when an [InputEvent] was applied to a [SplitPane] w/
+/// [SplitPane::apply_event].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SplitPaneApplyResponse {
+    /// The divider was moved, so the components in the panes have to be laid out (and
+    /// rendered) again w/ the new bounds.
+    Resized(SplitPaneLayout),
+    /// The divider was grabbed (or dragged, or let go of) w/ the mouse, but it didn't
+    /// move.
+    Consumed,
+    /// The event wasn't for the split pane, so it should be passed on.
+    Noop,
+}
+
+/// Splits a box into 2 panes w/ a divider between them, which can be moved at runtime w/
+/// the keyboard or by dragging it w/ the mouse (see [SplitKind]).
+///
+/// This holds the position of the divider, so it has to be kept in the app's state (or
+/// in a component) between renders. Call [Self::calc_layout] when rendering to get the
+/// bounds of the panes, and [Self::apply_event] for input events. When it returns
+/// [SplitPaneApplyResponse::Resized], the app should re-render, so that the components in
+/// the panes are laid out w/ their new bounds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitPane {
+    pub kind: SplitKind,
+    /// The size (cols for [SplitKind::HSplit], and rows for [SplitKind::VSplit]) of the
+    /// first pane. [None] splits the space evenly.
+    pub maybe_first_pane_size: Option<ChUnit>,
+    /// Neither pane is made smaller than this, unless there isn't enough space for it.
+    pub min_pane_size: ChUnit,
+    pub maybe_divider_style: Option<TuiStyle>,
+    /// The bounds from the last call to [Self::calc_layout], which are needed to move
+    /// the divider.
+    maybe_last_bounds: Option<(Position, Size)>,
+    is_dragging: bool,
+}
+
+impl SplitPane {
+    pub fn new(kind: SplitKind) -> Self {
+        Self {
+            kind,
+            maybe_first_pane_size: None,
+            min_pane_size: ch!(DEFAULT_MIN_PANE_SIZE),
+            maybe_divider_style: None,
+            maybe_last_bounds: None,
+            is_dragging: false,
+        }
+    }
+
+    pub fn is_dragging(&self) -> bool { self.is_dragging }
+
+    /// Returns the bounds of the panes & the divider in the box at `origin_pos` w/ the
+    /// given `bounds_size`, and remembers them for [Self::apply_event].
+    pub fn calc_layout(
+        &mut self,
+        origin_pos: Position,
+        bounds_size: Size,
+    ) -> SplitPaneLayout {
+        self.maybe_last_bounds = Some((origin_pos, bounds_size));
+        self.calc_layout_in(origin_pos, bounds_size)
+    }
+
+    fn calc_layout_in(&self, origin_pos: Position, bounds_size: Size) -> SplitPaneLayout {
+        let first_pane_size = self.clamp_first_pane_size(bounds_size);
+        let second_pane_size = self.get_main_axis_size(bounds_size) - 1 - first_pane_size;
+
+        match self.kind {
+            SplitKind::HSplit => {
+                let row_count = bounds_size.row_count;
+                let divider_pos =
+                    origin_pos + position!(col_index: first_pane_size, row_index: 0);
+                SplitPaneLayout {
+                    first_pane: (
+                        origin_pos,
+                        size!(col_count: first_pane_size, row_count: row_count),
+                    ),
+                    divider: (divider_pos, size!(col_count: 1, row_count: row_count)),
+                    second_pane: (
+                        divider_pos + position!(col_index: 1, row_index: 0),
+                        size!(col_count: second_pane_size, row_count: row_count),
+                    ),
+                }
+            }
+            SplitKind::VSplit => {
+                let col_count = bounds_size.col_count;
+                let divider_pos =
+                    origin_pos + position!(col_index: 0, row_index: first_pane_size);
+                SplitPaneLayout {
+                    first_pane: (
+                        origin_pos,
+                        size!(col_count: col_count, row_count: first_pane_size),
+                    ),
+                    divider: (divider_pos, size!(col_count: col_count, row_count: 1)),
+                    second_pane: (
+                        divider_pos + position!(col_index: 0, row_index: 1),
+                        size!(col_count: col_count, row_count: second_pane_size),
+                    ),
+                }
+            }
+        }
+    }
+
+    /// The cols (or rows) that the panes & the divider are split along.
+    fn get_main_axis_size(&self, bounds_size: Size) -> ChUnit {
+        match self.kind {
+            SplitKind::HSplit => bounds_size.col_count,
+            SplitKind::VSplit => bounds_size.row_count,
+        }
+    }
+
+    /// The size of the first pane, so that both panes are at least
+    /// [Self::min_pane_size] (if there's enough space for that).
+    fn clamp_first_pane_size(&self, bounds_size: Size) -> ChUnit {
+        let available = self.get_main_axis_size(bounds_size) - 1;
+        let min_pane_size = self.min_pane_size.min(available / 2);
+        self.maybe_first_pane_size
+            .unwrap_or(available / 2)
+            .max(min_pane_size)
+            .min(available - min_pane_size)
+    }
+
+    /// Move the divider so that the first pane has the given size (which is clamped).
+    /// Returns [None] if it didn't move, or if [Self::calc_layout] wasn't called yet.
+    fn resize_first_pane(&mut self, first_pane_size: ChUnit) -> Option<SplitPaneLayout> {
+        let (origin_pos, bounds_size) = self.maybe_last_bounds?;
+        let old_first_pane_size = self.clamp_first_pane_size(bounds_size);
+        self.maybe_first_pane_size = Some(first_pane_size);
+        let new_first_pane_size = self.clamp_first_pane_size(bounds_size);
+        self.maybe_first_pane_size = Some(new_first_pane_size);
+        (old_first_pane_size != new_first_pane_size)
+            .then(|| self.calc_layout_in(origin_pos, bounds_size))
+    }
+
+    pub fn apply_event(&mut self, input_event: InputEvent) -> SplitPaneApplyResponse {
+        let Some((origin_pos, bounds_size)) = self.maybe_last_bounds else {
+            return SplitPaneApplyResponse::Noop;
+        };
+        let first_pane_size = self.clamp_first_pane_size(bounds_size);
+        let ctrl = ModifierKeysMask::new().with_ctrl();
+
+        let maybe_layout = match (self.kind, input_event) {
+            (
+                SplitKind::HSplit,
+                InputEvent::Keyboard(KeyPress::WithModifiers {
+                    key: Key::SpecialKey(SpecialKey::Left),
+                    mask,
+                }),
+            )
+            | (
+                SplitKind::VSplit,
+                InputEvent::Keyboard(KeyPress::WithModifiers {
+                    key: Key::SpecialKey(SpecialKey::Up),
+                    mask,
+                }),
+            ) if mask == ctrl => self.resize_first_pane(first_pane_size - 1),
+
+            (
+                SplitKind::HSplit,
+                InputEvent::Keyboard(KeyPress::WithModifiers {
+                    key: Key::SpecialKey(SpecialKey::Right),
+                    mask,
+                }),
+            )
+            | (
+                SplitKind::VSplit,
+                InputEvent::Keyboard(KeyPress::WithModifiers {
+                    key: Key::SpecialKey(SpecialKey::Down),
+                    mask,
+                }),
+            ) if mask == ctrl => self.resize_first_pane(first_pane_size + 1),
+
+            (
+                _,
+                InputEvent::Mouse(MouseInput {
+                    pos,
+                    kind: MouseInputKind::MouseDown(Button::Left),
+                    ..
+                }),
+            ) => {
+                let (divider_pos, divider_size) =
+                    self.calc_layout_in(origin_pos, bounds_size).divider;
+                if !is_inside(pos, divider_pos, divider_size) {
+                    return SplitPaneApplyResponse::Noop;
+                }
+                self.is_dragging = true;
+                return SplitPaneApplyResponse::Consumed;
+            }
+
+            (
+                split_kind,
+                InputEvent::Mouse(MouseInput {
+                    pos,
+                    kind: MouseInputKind::MouseDrag(Button::Left),
+                    ..
+                }),
+            ) if self.is_dragging => {
+                let first_pane_size = match split_kind {
+                    SplitKind::HSplit => pos.col_index - origin_pos.col_index,
+                    SplitKind::VSplit => pos.row_index - origin_pos.row_index,
+                };
+                // The drag is consumed, even if the divider didn't move.
+                return match self.resize_first_pane(first_pane_size) {
+                    Some(layout) => SplitPaneApplyResponse::Resized(layout),
+                    None => SplitPaneApplyResponse::Consumed,
+                };
+            }
+
+            (
+                _,
+                InputEvent::Mouse(MouseInput {
+                    kind: MouseInputKind::MouseUp(Button::Left),
+                    ..
+                }),
+            ) if self.is_dragging => {
+                self.is_dragging = false;
+                return SplitPaneApplyResponse::Consumed;
+            }
+
+            _ => None,
+        };
+
+        match maybe_layout {
+            Some(layout) => SplitPaneApplyResponse::Resized(layout),
+            None => SplitPaneApplyResponse::Noop,
+        }
+    }
+
+    /// Returns the [RenderOps] to paint the divider in the `layout`.
+    pub fn render_divider(&self, layout: &SplitPaneLayout) -> RenderOps {
+        let mut ops = render_ops!();
+        let (divider_pos, divider_size) = layout.divider;
+        let (glyph, count, row_count) = match self.kind {
+            SplitKind::HSplit => {
+                (BorderGlyphCharacter::Vertical, 1, divider_size.row_count)
+            }
+            SplitKind::VSplit => (
+                BorderGlyphCharacter::Horizontal,
+                ch!(@to_usize divider_size.col_count),
+                ch!(1),
+            ),
+        };
+        let text = glyph.as_ref().repeat(count);
+
+        for row_index in 0..*row_count {
+            ops.push(RenderOp::ResetColor);
+            ops.push(RenderOp::MoveCursorPositionAbs(
+                divider_pos + position!(col_index: 0, row_index: row_index),
+            ));
+            ops.push(RenderOp::ApplyColors(self.maybe_divider_style));
+            ops.push(RenderOp::PaintTextWithAttributes(
+                text.clone().into(),
+                self.maybe_divider_style,
+            ));
+        }
+
+        ops
+    }
+}
+
+fn is_inside(pos: Position, origin_pos: Position, bounds_size: Size) -> bool {
+    pos.col_index >= origin_pos.col_index
+        && pos.col_index < origin_pos.col_index + bounds_size.col_count
+        && pos.row_index >= origin_pos.row_index
+        && pos.row_index < origin_pos.row_index + bounds_size.row_count
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn ctrl(special_key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::SpecialKey(special_key),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        })
+    }
+
+    fn mouse(kind: MouseInputKind, col_index: u16, row_index: u16) -> InputEvent {
+        InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind,
+            maybe_modifier_keys: None,
+        })
+    }
+
+    #[test]
+    fn test_calc_layout() {
+        let mut split_pane = SplitPane::new(SplitKind::HSplit);
+        let layout = split_pane.calc_layout(
+            position!(col_index: 0, row_index: 1),
+            size!(col_count: 41, row_count: 10),
+        );
+        assert_eq2!(
+            layout,
+            SplitPaneLayout {
+                first_pane: (
+                    position!(col_index: 0, row_index: 1),
+                    size!(col_count: 20, row_count: 10)
+                ),
+                divider: (
+                    position!(col_index: 20, row_index: 1),
+                    size!(col_count: 1, row_count: 10)
+                ),
+                second_pane: (
+                    position!(col_index: 21, row_index: 1),
+                    size!(col_count: 20, row_count: 10)
+                ),
+            }
+        );
+
+        let mut split_pane = SplitPane {
+            maybe_first_pane_size: Some(ch!(1)),
+            ..SplitPane::new(SplitKind::VSplit)
+        };
+        let layout = split_pane
+            .calc_layout(Position::default(), size!(col_count: 40, row_count: 20));
+        // The first pane isn't made smaller than the min pane size.
+        assert_eq2!(
+            layout.first_pane,
+            (Position::default(), size!(col_count: 40, row_count: 5))
+        );
+        assert_eq2!(
+            layout.second_pane,
+            (
+                position!(col_index: 0, row_index: 6),
+                size!(col_count: 40, row_count: 14)
+            )
+        );
+    }
+
+    #[test]
+    fn test_resize_w_keyboard() {
+        let mut split_pane = SplitPane::new(SplitKind::HSplit);
+
+        // It can't be resized before it is laid out.
+        assert_eq2!(
+            split_pane.apply_event(ctrl(SpecialKey::Left)),
+            SplitPaneApplyResponse::Noop
+        );
+
+        split_pane.calc_layout(Position::default(), size!(col_count: 21, row_count: 5));
+        let SplitPaneApplyResponse::Resized(layout) =
+            split_pane.apply_event(ctrl(SpecialKey::Left))
+        else {
+            panic!("Expected the split pane to be resized");
+        };
+        assert_eq2!(layout.first_pane.1, size!(col_count: 9, row_count: 5));
+        assert_eq2!(layout.second_pane.1, size!(col_count: 11, row_count: 5));
+
+        // The keys for the other kind of split are ignored.
+        assert_eq2!(
+            split_pane.apply_event(ctrl(SpecialKey::Down)),
+            SplitPaneApplyResponse::Noop
+        );
+
+        // The divider doesn't move past the min pane size.
+        for _ in 0..10 {
+            split_pane.apply_event(ctrl(SpecialKey::Right));
+        }
+        assert_eq2!(split_pane.maybe_first_pane_size, Some(ch!(15)));
+        assert_eq2!(
+            split_pane.apply_event(ctrl(SpecialKey::Right)),
+            SplitPaneApplyResponse::Noop
+        );
+    }
+
+    #[test]
+    fn test_resize_w_mouse_drag() {
+        let mut split_pane = SplitPane::new(SplitKind::HSplit);
+        split_pane.calc_layout(
+            position!(col_index: 2, row_index: 0),
+            size!(col_count: 21, row_count: 5),
+        );
+
+        // Dragging outside of the divider doesn't do anything.
+        assert_eq2!(
+            split_pane.apply_event(mouse(MouseInputKind::MouseDown(Button::Left), 5, 2)),
+            SplitPaneApplyResponse::Noop
+        );
+        assert_eq2!(
+            split_pane.apply_event(mouse(MouseInputKind::MouseDrag(Button::Left), 8, 2)),
+            SplitPaneApplyResponse::Noop
+        );
+
+        assert_eq2!(
+            split_pane.apply_event(mouse(MouseInputKind::MouseDown(Button::Left), 12, 2)),
+            SplitPaneApplyResponse::Consumed
+        );
+        assert!(split_pane.is_dragging());
+
+        let SplitPaneApplyResponse::Resized(layout) =
+            split_pane.apply_event(mouse(MouseInputKind::MouseDrag(Button::Left), 16, 3))
+        else {
+            panic!("Expected the split pane to be resized");
+        };
+        assert_eq2!(
+            layout.divider,
+            (
+                position!(col_index: 16, row_index: 0),
+                size!(col_count: 1, row_count: 5)
+            )
+        );
+
+        assert_eq2!(
+            split_pane.apply_event(mouse(MouseInputKind::MouseUp(Button::Left), 16, 3)),
+            SplitPaneApplyResponse::Consumed
+        );
+        assert!(!split_pane.is_dragging());
+    }
+
+    #[test]
+    fn test_render_divider() {
+        let mut split_pane = SplitPane::new(SplitKind::VSplit);
+        let layout = split_pane
+            .calc_layout(Position::default(), size!(col_count: 4, row_count: 11));
+        let ops = split_pane.render_divider(&layout);
+        assert_eq2!(
+            ops.iter()
+                .filter(|it| matches!(it, RenderOp::PaintTextWithAttributes(..)))
+                .collect::<Vec<_>>(),
+            vec![&RenderOp::PaintTextWithAttributes("────".into(), None)]
+        );
+    }
+}
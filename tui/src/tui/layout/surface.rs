@@ -15,8 +15,14 @@
  *   limitations under the License.
  */
 
-use r3bl_core::{size,
+use std::{collections::{hash_map::DefaultHasher, VecDeque},
+          hash::{Hash, Hasher}};
+
+use r3bl_core::{ch,
+                position,
+                size,
                 throws,
+                throws_with_return,
                 CommonResult,
                 Position,
                 RequestedSizePercent,
@@ -25,13 +31,28 @@ use r3bl_core::{size,
                 TuiStylesheet};
 use serde::{Deserialize, Serialize};
 
-use super::{FlexBox,
+use super::{render_debug_overlay,
+            BoxSides,
+            CachedBox,
+            FixedSize,
+            FlexBox,
+            FlexBoxId,
             FlexBoxProps,
+            FlexWrap,
+            FloatingBoxProps,
+            GridLayout,
+            LayoutCache,
             LayoutDirection,
             LayoutManagement,
             PerformPositioningAndSizing,
             SurfaceProps};
-use crate::{unwrap_or_err, LayoutError, LayoutErrorType, RenderPipeline};
+use crate::{render_ops,
+            unwrap_or_err,
+            LayoutError,
+            LayoutErrorType,
+            RenderOp,
+            RenderPipeline,
+            ZOrder};
 
 /// Represents a rectangular area of the terminal screen, and not necessarily the full terminal
 /// screen.
@@ -40,6 +61,22 @@ pub struct Surface {
     pub origin_pos: Position,
     pub box_size: Size,
     pub stack_of_boxes: Vec<FlexBox>,
+    /// The [FlexBoxProps::maybe_grid_layout] of each box in `stack_of_boxes`.
+    pub stack_of_grid_layouts: Vec<Option<GridLayout>>,
+    /// The segments of the [FlexBoxProps::maybe_constraint_layout] of each box in
+    /// `stack_of_boxes` that haven't been used by its children yet.
+    pub stack_of_constraint_segments: Vec<Option<VecDeque<(Position, Size)>>>,
+    /// All the boxes that were added since [LayoutManagement::surface_start] (including
+    /// the ones that were already removed from `stack_of_boxes`, and the floating ones),
+    /// so that floating boxes can be anchored to them, see [Surface::get_box_registry].
+    pub laid_out_boxes: Vec<FlexBox>,
+    /// When this is set, the boxes that were laid out in a previous render are reused
+    /// (if nothing about them has changed), see [LayoutCache].
+    #[serde(skip)]
+    pub maybe_layout_cache: Option<LayoutCache>,
+    /// Paint an outline (w/ the id & size) around each box when the surface ends, see
+    /// [render_debug_overlay]. This can be toggled while the app is running.
+    pub is_debug_overlay_enabled: bool,
     pub stylesheet: TuiStylesheet,
     pub render_pipeline: RenderPipeline,
 }
@@ -106,6 +143,10 @@ impl LayoutManagement for Surface {
             }
             self.origin_pos = pos;
             self.box_size = size;
+            self.laid_out_boxes.clear();
+            if let Some(layout_cache) = self.maybe_layout_cache.as_mut() {
+                layout_cache.start_frame(pos, size);
+            }
         });
     }
 
@@ -121,15 +162,92 @@ impl LayoutManagement for Surface {
                     ),
                 )?
             }
+            if let Some(layout_cache) = self.maybe_layout_cache.as_mut() {
+                layout_cache.end_frame();
+            }
+            if self.is_debug_overlay_enabled {
+                self.render_pipeline
+                    .push(ZOrder::Glass, render_debug_overlay(&self.laid_out_boxes));
+            }
         });
     }
 
-    fn box_start(&mut self, flex_box_props: FlexBoxProps) -> CommonResult<()> {
+    fn box_start(&mut self, mut flex_box_props: FlexBoxProps) -> CommonResult<()> {
         throws!({
-            match self.no_boxes_added() {
-                true => self.add_root_box(flex_box_props),
-                false => self.add_non_root_box(flex_box_props),
-            }?
+            let maybe_cache_key = self
+                .maybe_layout_cache
+                .is_some()
+                .then(|| self.calc_layout_cache_key(&flex_box_props));
+            let maybe_grid_layout = flex_box_props.maybe_grid_layout.take();
+            let maybe_constraint_layout = flex_box_props.maybe_constraint_layout.take();
+            let maybe_border = flex_box_props.maybe_border.clone();
+
+            let maybe_cached_box =
+                match (self.maybe_layout_cache.as_mut(), maybe_cache_key) {
+                    (Some(layout_cache), Some(cache_key)) => layout_cache.get(cache_key),
+                    _ => None,
+                };
+            let maybe_constraint_segments = match maybe_cached_box {
+                Some(cached_box) => self.add_cached_box(cached_box, &flex_box_props)?,
+                None => {
+                    let is_root = self.no_boxes_added();
+                    match is_root {
+                        true => self.add_root_box(flex_box_props),
+                        false => self.add_non_root_box(flex_box_props),
+                    }?;
+                    let current_box = *self.current_box()?;
+                    let maybe_constraint_segments = match maybe_constraint_layout {
+                        Some(constraint_layout) => {
+                            Some(constraint_layout.calc_segment_bounds(
+                                current_box.dir,
+                                current_box.origin_pos,
+                                current_box.bounds_size,
+                            )?)
+                        }
+                        None => None,
+                    };
+                    if let (Some(layout_cache), Some(cache_key)) =
+                        (self.maybe_layout_cache.as_mut(), maybe_cache_key)
+                    {
+                        let maybe_updated_container = match is_root {
+                            true => None,
+                            false => self
+                                .stack_of_boxes
+                                .len()
+                                .checked_sub(2)
+                                .and_then(|index| self.stack_of_boxes.get(index))
+                                .copied(),
+                        };
+                        layout_cache.insert(
+                            cache_key,
+                            CachedBox {
+                                flex_box: current_box,
+                                maybe_updated_container,
+                                maybe_constraint_segments: maybe_constraint_segments
+                                    .clone(),
+                            },
+                        );
+                    }
+                    maybe_constraint_segments
+                }
+            };
+
+            self.stack_of_grid_layouts.push(maybe_grid_layout);
+            let current_box = *self.current_box()?;
+            self.laid_out_boxes.push(current_box);
+            self.stack_of_constraint_segments
+                .push(maybe_constraint_segments.map(VecDeque::from));
+
+            // The border is painted before the content of the box, which is inset so
+            // that it doesn't paint over it.
+            if let Some(border) = maybe_border.filter(|_| current_box.visible) {
+                let current_box = self.current_box()?;
+                let (origin_pos, bounds_size) = current_box
+                    .margin
+                    .inset(current_box.origin_pos, current_box.bounds_size);
+                self.render_pipeline
+                    .push(ZOrder::Normal, border.render(origin_pos, bounds_size));
+            }
         });
     }
 
@@ -146,6 +264,150 @@ impl LayoutManagement for Surface {
                 )?
             }
             self.stack_of_boxes.pop();
+            self.stack_of_grid_layouts.pop();
+            self.stack_of_constraint_segments.pop();
+        });
+    }
+}
+
+impl Surface {
+    /// Returns the last box w/ the given `id` that was added to this surface.
+    pub fn get_laid_out_box(&self, id: FlexBoxId) -> Option<&FlexBox> {
+        self.laid_out_boxes.iter().rev().find(|it| it.id == id)
+    }
+
+    /// Add a floating box, which isn't part of the stack of boxes, so it can be added at
+    /// any time (after the box that it is anchored to). Its background (and its border,
+    /// if it has one) is painted at [FloatingBoxProps::z_order], so that it covers the
+    /// boxes below it.
+    ///
+    /// Returns the [FlexBox] that a component can be rendered in, eg: w/
+    /// [crate::render_component_in_floating_box!].
+    pub fn add_floating_box(
+        &mut self,
+        FloatingBoxProps {
+            id,
+            anchor,
+            size,
+            z_order,
+            maybe_border,
+            maybe_styles,
+        }: FloatingBoxProps,
+    ) -> CommonResult<FlexBox> {
+        throws_with_return!({
+            let maybe_anchor_box = match anchor.get_anchor_box_id() {
+                Some(anchor_box_id) => Some(*unwrap_or_err!(
+                    self.get_laid_out_box(anchor_box_id),
+                    LayoutErrorType::AnchorBoxNotFound,
+                    "{:?} must be added to the surface before the floating box {:?}",
+                    anchor_box_id,
+                    id
+                )),
+                None => None,
+            };
+
+            let bounds_size = size!(
+              col_count: size.col_count.min(self.box_size.col_count),
+              row_count: size.row_count.min(self.box_size.row_count)
+            );
+            let origin_pos = anchor.calc_origin_pos(
+                bounds_size,
+                SurfaceBounds::from(&*self),
+                maybe_anchor_box.as_ref(),
+            );
+
+            let computed_style = TuiStylesheet::compute(&maybe_styles);
+            let has_border = maybe_border.is_some();
+            let (style_adjusted_origin_pos, style_adjusted_bounds_size) =
+                adjust_with_style(
+                    &computed_style,
+                    BoxSides::default(),
+                    has_border,
+                    BoxSides::default(),
+                    origin_pos,
+                    bounds_size,
+                );
+
+            // Clear what is painted below the box, then paint the border over it.
+            let mut render_ops = render_ops!();
+            let blank_line = " ".repeat(ch!(@to_usize bounds_size.col_count));
+            for row_index in 0..*bounds_size.row_count {
+                render_ops.push(RenderOp::ResetColor);
+                render_ops.push(RenderOp::MoveCursorPositionAbs(
+                    origin_pos + position!(col_index: 0, row_index: row_index),
+                ));
+                render_ops.push(RenderOp::ApplyColors(computed_style));
+                render_ops.push(RenderOp::PaintTextWithAttributes(
+                    blank_line.clone().into(),
+                    computed_style,
+                ));
+            }
+            self.render_pipeline.push(z_order, render_ops);
+            if let Some(border) = maybe_border {
+                self.render_pipeline
+                    .push(z_order, border.render(origin_pos, bounds_size));
+            }
+
+            let floating_box = FlexBox {
+                id,
+                origin_pos,
+                bounds_size,
+                style_adjusted_origin_pos,
+                style_adjusted_bounds_size,
+                has_border,
+                maybe_computed_style: computed_style,
+                ..Default::default()
+            };
+            self.laid_out_boxes.push(floating_box);
+            floating_box
+        });
+    }
+}
+
+impl Surface {
+    /// Returns the key of a box in the [LayoutCache], which is a hash of everything that
+    /// its layout depends on. This must be called before the box is added.
+    fn calc_layout_cache_key(&self, flex_box_props: &FlexBoxProps) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.origin_pos.hash(&mut hasher);
+        self.box_size.hash(&mut hasher);
+        self.stack_of_boxes.last().hash(&mut hasher);
+        self.stack_of_grid_layouts.last().hash(&mut hasher);
+        self.stack_of_constraint_segments
+            .last()
+            .and_then(Option::as_ref)
+            .and_then(VecDeque::front)
+            .hash(&mut hasher);
+        flex_box_props.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Add a box that was laid out in a previous render, and update its container (and
+    /// the segments of its container's constraint layout) just like
+    /// [Self::add_non_root_box] does. Returns the segments of the box's own constraint
+    /// layout.
+    fn add_cached_box(
+        &mut self,
+        CachedBox {
+            flex_box,
+            maybe_updated_container,
+            maybe_constraint_segments,
+        }: CachedBox,
+        flex_box_props: &FlexBoxProps,
+    ) -> CommonResult<Option<Vec<(Position, Size)>>> {
+        throws_with_return!({
+            if let Some(updated_container) = maybe_updated_container {
+                *self.current_box()? = updated_container;
+                if flex_box_props.maybe_grid_cell.is_none() {
+                    if let Some(Some(segments)) =
+                        self.stack_of_constraint_segments.last_mut()
+                    {
+                        segments.pop_front();
+                    }
+                }
+            }
+            self.stack_of_boxes.push(flex_box);
+            maybe_constraint_segments
         });
     }
 }
@@ -190,12 +452,17 @@ impl PerformPositioningAndSizing for Surface {
           LayoutErrorType::ErrorCalculatingNextBoxPos
         };
 
-        let new_pos: Position = current_insertion_pos + allocated_size;
-
-        // Adjust `new_pos` using Direction.
+        // Only move in the direction of the box, so that the next box lines up w/ this
+        // one (even when the box isn't at the top left corner of the surface).
         let new_pos: Position = match current_box.dir {
-            LayoutDirection::Vertical => new_pos * (0, 1),
-            LayoutDirection::Horizontal => new_pos * (1, 0),
+            LayoutDirection::Vertical => {
+                current_insertion_pos
+                    + size!(col_count: 0, row_count: allocated_size.row_count)
+            }
+            LayoutDirection::Horizontal => {
+                current_insertion_pos
+                    + size!(col_count: allocated_size.col_count, row_count: 0)
+            }
         };
 
         // Update the box_cursor_pos of the current layout.
@@ -206,35 +473,148 @@ impl PerformPositioningAndSizing for Surface {
 
     /// 🍀 Handle non-root box to add to stack of boxes. [Position] and [Size] will be calculated.
     /// `insertion_pos_for_next_box` will also be updated.
-    fn add_non_root_box(&mut self, flex_box_props: FlexBoxProps) -> CommonResult<()> {
+    fn add_non_root_box(&mut self, mut flex_box_props: FlexBoxProps) -> CommonResult<()> {
         throws!({
+            let has_constraint_segments =
+                matches!(self.stack_of_constraint_segments.last(), Some(Some(_)));
             let container_box = self.current_box()?;
             let container_bounds = container_box.bounds_size;
 
+            // A box in a hidden box is hidden too.
+            flex_box_props.visible &= container_box.visible;
+
             let maybe_cascaded_style: Option<TuiStyle> =
                 cascade_styles(container_box, &flex_box_props);
 
-            let RequestedSizePercent {
-                width_pc,
-                height_pc,
-            } = flex_box_props.requested_size_percent;
-
-            let requested_size_allocation = size!(
-              col_count: width_pc.calc_percentage(container_bounds.col_count),
-              row_count: height_pc.calc_percentage(container_bounds.row_count)
-            );
-
-            let origin_pos = unwrap_or_err! {
-              container_box.insertion_pos_for_next_box,
-              LayoutErrorType::BoxCursorPositionUndefined
+            let (origin_pos, bounds_size) = match flex_box_props.maybe_grid_cell {
+                // Grid cells are positioned & sized by the grid of the container, so the
+                // insertion position isn't used (or updated).
+                Some(grid_cell) => {
+                    let container_origin_pos = container_box.origin_pos;
+                    let grid_layout = unwrap_or_err! {
+                      self.stack_of_grid_layouts.last().and_then(Option::as_ref),
+                      LayoutErrorType::GridLayoutUndefined
+                    };
+                    grid_layout.calc_cell_bounds(
+                        grid_cell,
+                        container_origin_pos,
+                        container_bounds,
+                    )?
+                }
+                // The segments of a constraint layout are used by the children in the
+                // order that they are added.
+                None if has_constraint_segments => {
+                    unwrap_or_err!(
+                        self.stack_of_constraint_segments
+                            .last_mut()
+                            .and_then(Option::as_mut)
+                            .and_then(VecDeque::pop_front),
+                        LayoutErrorType::InvalidConstraintLayout,
+                        "{:?} doesn't fit in the segments of its container",
+                        flex_box_props.id
+                    )
+                }
+                // A hidden box doesn't take up any space (so the insertion position
+                // isn't updated), the space that it would have taken up is given to the
+                // next visible box.
+                None if !flex_box_props.visible => {
+                    let bounds_size = calc_bounds_size(
+                        &flex_box_props,
+                        container_bounds,
+                        calc_flexible_bounds(container_box),
+                    );
+                    container_box.hidden_children_size += match container_box.dir {
+                        LayoutDirection::Horizontal => bounds_size.col_count,
+                        LayoutDirection::Vertical => bounds_size.row_count,
+                    };
+                    // It has no size, so it is placed at the insertion position of its
+                    // container (if it has one, which containers w/ a grid or constraint
+                    // layout don't).
+                    let origin_pos = container_box
+                        .insertion_pos_for_next_box
+                        .unwrap_or(container_box.origin_pos);
+                    (origin_pos, Size::default())
+                }
+                None => {
+                    let bounds_size = calc_bounds_size(
+                        &flex_box_props,
+                        container_bounds,
+                        calc_flexible_bounds(container_box),
+                    );
+
+                    let origin_pos = unwrap_or_err! {
+                      container_box.insertion_pos_for_next_box,
+                      LayoutErrorType::BoxCursorPositionUndefined
+                    };
+
+                    // The space that the hidden boxes before this one would have taken
+                    // up is added to its size.
+                    let hidden_children_size = container_box.hidden_children_size;
+                    container_box.hidden_children_size = ch!(0);
+                    let bounds_size = match container_box.dir {
+                        LayoutDirection::Horizontal => size!(
+                          col_count: (bounds_size.col_count + hidden_children_size)
+                            .min(container_bounds.col_count),
+                          row_count: bounds_size.row_count
+                        ),
+                        LayoutDirection::Vertical => size!(
+                          col_count: bounds_size.col_count,
+                          row_count: (bounds_size.row_count + hidden_children_size)
+                            .min(container_bounds.row_count)
+                        ),
+                    };
+
+                    // Move to the next line if the box doesn't fit in what is left of
+                    // this one.
+                    let origin_pos = match container_box.wrap {
+                        FlexWrap::NoWrap => origin_pos,
+                        FlexWrap::Wrap => {
+                            let it = calc_wrapped_insertion_pos(
+                                container_box,
+                                origin_pos,
+                                bounds_size,
+                            );
+                            container_box.insertion_pos_for_next_box = Some(it);
+                            it
+                        }
+                    };
+
+                    // The box gets all the space that it asks for in the direction of
+                    // its container, so it is only aligned in the other direction. Boxes
+                    // that are wrapped aren't aligned, since they don't fill their line.
+                    let available_size = match container_box.dir {
+                        _ if container_box.wrap == FlexWrap::Wrap => bounds_size,
+                        LayoutDirection::Horizontal => size!(
+                          col_count: bounds_size.col_count,
+                          row_count: container_bounds.row_count
+                        ),
+                        LayoutDirection::Vertical => size!(
+                          col_count: container_bounds.col_count,
+                          row_count: bounds_size.row_count
+                        ),
+                    };
+
+                    self.update_insertion_pos_for_next_box(bounds_size)?;
+
+                    flex_box_props.alignment.apply(
+                        origin_pos,
+                        bounds_size,
+                        available_size,
+                    )
+                }
             };
 
-            self.update_insertion_pos_for_next_box(requested_size_allocation)?;
+            // A hidden box in a grid cell (or a constraint layout segment) still uses it
+            // up, so that the boxes after it stay in their own cells.
+            let bounds_size = match flex_box_props.visible {
+                true => bounds_size,
+                false => Size::default(),
+            };
 
             self.stack_of_boxes.push(make_non_root_box_with_style(
                 flex_box_props,
                 origin_pos,
-                container_bounds,
+                bounds_size,
                 maybe_cascaded_style,
             ));
         });
@@ -243,19 +623,19 @@ impl PerformPositioningAndSizing for Surface {
     /// 🌳 Handle root (first) box to add to stack of boxes, explicitly sized & positioned.
     fn add_root_box(&mut self, flex_box_props: FlexBoxProps) -> CommonResult<()> {
         throws!({
-            let RequestedSizePercent {
-                width_pc,
-                height_pc,
-            } = flex_box_props.requested_size_percent;
-
-            let bounds_size = size!(
-              col_count: width_pc.calc_percentage(self.box_size.col_count),
-              row_count: height_pc.calc_percentage(self.box_size.row_count)
+            let bounds_size = match flex_box_props.visible {
+                true => calc_bounds_size(&flex_box_props, self.box_size, self.box_size),
+                false => Size::default(),
+            };
+            let (origin_pos, bounds_size) = flex_box_props.alignment.apply(
+                self.origin_pos,
+                bounds_size,
+                self.box_size,
             );
 
             self.stack_of_boxes.push(make_root_box_with_style(
                 flex_box_props,
-                self.origin_pos,
+                origin_pos,
                 bounds_size,
             ));
         });
@@ -265,32 +645,44 @@ impl PerformPositioningAndSizing for Surface {
 /// - If `is_root` is true:
 ///   - The `insertion_pos_for_next_box` is origin_pos + padding adjustment (from style)
 /// - If `is_root` is false:
-///   - The `insertion_pos_for_next_box` is `None` non-root box; it needs to be calculated by
-///     `update_box_cursor_pos_for_next_box_insertion()`
+///   - The `insertion_pos_for_next_box` is origin_pos too, so that the box can have children
+///     that are laid out one after the other (it is moved by
+///     `update_insertion_pos_for_next_box()` as they are added)
 fn make_non_root_box_with_style(
     FlexBoxProps {
         id,
         dir,
-        requested_size_percent:
-            RequestedSizePercent {
-                width_pc,
-                height_pc,
-            },
+        requested_size_percent,
+        size_constraints,
+        fixed_size,
+        fixed_children_size,
+        alignment: _,
+        visible,
+        wrap,
+        maybe_grid_layout: _,
+        maybe_grid_cell: _,
+        maybe_constraint_layout: _,
+        margin,
+        padding,
+        maybe_border,
         maybe_styles: _,
     }: FlexBoxProps,
     origin_pos: Position,
-    container_bounds: Size,
+    bounds_size: Size,
     maybe_cascaded_style: Option<TuiStyle>,
 ) -> FlexBox {
-    let bounds_size = size!(
-      col_count: width_pc.calc_percentage(container_bounds.col_count),
-      row_count: height_pc.calc_percentage(container_bounds.row_count)
+    // Adjust `bounds_size` & `origin` based on the margin, the border, and the padding
+    // (from the props and the style).
+    let has_border = maybe_border.is_some();
+    let (style_adjusted_origin_pos, style_adjusted_bounds_size) = adjust_with_style(
+        &maybe_cascaded_style,
+        margin,
+        has_border,
+        padding,
+        origin_pos,
+        bounds_size,
     );
 
-    // Adjust `bounds_size` & `origin` based on the style's padding.
-    let (style_adjusted_origin_pos, style_adjusted_bounds_size) =
-        adjust_with_style(&maybe_cascaded_style, origin_pos, bounds_size);
-
     FlexBox {
         id,
         dir,
@@ -298,12 +690,19 @@ fn make_non_root_box_with_style(
         bounds_size,
         style_adjusted_origin_pos,
         style_adjusted_bounds_size,
-        requested_size_percent: RequestedSizePercent {
-            width_pc,
-            height_pc,
-        },
+        requested_size_percent,
+        size_constraints,
+        fixed_size,
+        fixed_children_size,
+        hidden_children_size: ch!(0),
+        margin,
+        padding,
+        has_border,
+        visible,
+        wrap,
+        wrap_line_size: ch!(0),
         maybe_computed_style: maybe_cascaded_style,
-        insertion_pos_for_next_box: None,
+        insertion_pos_for_next_box: Some(origin_pos),
     }
 }
 
@@ -312,6 +711,18 @@ fn make_root_box_with_style(
         id,
         dir,
         requested_size_percent,
+        size_constraints,
+        fixed_size,
+        fixed_children_size,
+        alignment: _,
+        visible,
+        wrap,
+        maybe_grid_layout: _,
+        maybe_grid_cell: _,
+        maybe_constraint_layout: _,
+        margin,
+        padding,
+        maybe_border,
         maybe_styles,
     }: FlexBoxProps,
     origin_pos: Position,
@@ -319,9 +730,17 @@ fn make_root_box_with_style(
 ) -> FlexBox {
     let computed_style = TuiStylesheet::compute(&maybe_styles);
 
-    // Adjust `bounds_size` & `origin` based on the style's padding.
-    let (style_adjusted_origin_pos, style_adjusted_bounds_size) =
-        adjust_with_style(&computed_style, origin_pos, bounds_size);
+    // Adjust `bounds_size` & `origin` based on the margin, the border, and the padding
+    // (from the props and the style).
+    let has_border = maybe_border.is_some();
+    let (style_adjusted_origin_pos, style_adjusted_bounds_size) = adjust_with_style(
+        &computed_style,
+        margin,
+        has_border,
+        padding,
+        origin_pos,
+        bounds_size,
+    );
 
     FlexBox {
         id,
@@ -331,19 +750,142 @@ fn make_root_box_with_style(
         style_adjusted_origin_pos,
         style_adjusted_bounds_size,
         requested_size_percent,
+        size_constraints,
+        fixed_size,
+        fixed_children_size,
+        hidden_children_size: ch!(0),
+        margin,
+        padding,
+        has_border,
+        visible,
+        wrap,
+        wrap_line_size: ch!(0),
         maybe_computed_style: computed_style,
         insertion_pos_for_next_box: Some(origin_pos),
     }
 }
 
-/// Adjust `origin` & `bounds_size` based on the `maybe_style`'s padding.
+/// Returns where the next child of a [FlexWrap::Wrap] `container_box` goes, which is at
+/// the start of the next line if it doesn't fit in what is left of the current one (and
+/// it isn't the first child on it). This also updates the [FlexBox::wrap_line_size] of
+/// the container.
+fn calc_wrapped_insertion_pos(
+    container_box: &mut FlexBox,
+    insertion_pos: Position,
+    child_size: Size,
+) -> Position {
+    let FlexBox {
+        origin_pos,
+        bounds_size,
+        wrap_line_size,
+        ..
+    } = *container_box;
+
+    let (next_line_pos, child_line_size) = match container_box.dir {
+        LayoutDirection::Horizontal => {
+            let is_line_start = insertion_pos.col_index == origin_pos.col_index;
+            let does_fit = insertion_pos.col_index + child_size.col_count
+                <= origin_pos.col_index + bounds_size.col_count;
+            let next_line_pos = (!is_line_start && !does_fit).then(|| {
+                position!(
+                  col_index: origin_pos.col_index,
+                  row_index: insertion_pos.row_index + wrap_line_size
+                )
+            });
+            (next_line_pos, child_size.row_count)
+        }
+        LayoutDirection::Vertical => {
+            let is_line_start = insertion_pos.row_index == origin_pos.row_index;
+            let does_fit = insertion_pos.row_index + child_size.row_count
+                <= origin_pos.row_index + bounds_size.row_count;
+            let next_line_pos = (!is_line_start && !does_fit).then(|| {
+                position!(
+                  col_index: insertion_pos.col_index + wrap_line_size,
+                  row_index: origin_pos.row_index
+                )
+            });
+            (next_line_pos, child_size.col_count)
+        }
+    };
+
+    match next_line_pos {
+        Some(next_line_pos) => {
+            container_box.wrap_line_size = child_line_size;
+            next_line_pos
+        }
+        None => {
+            container_box.wrap_line_size = wrap_line_size.max(child_line_size);
+            insertion_pos
+        }
+    }
+}
+
+/// The size of the box is its [FlexBoxProps::fixed_size], or the
+/// [FlexBoxProps::requested_size_percent] of the `flexible_bounds` (for the dimensions
+/// that aren't fixed). This is clamped to its [FlexBoxProps::size_constraints] and the
+/// `container_bounds`.
+fn calc_bounds_size(
+    flex_box_props: &FlexBoxProps,
+    container_bounds: Size,
+    flexible_bounds: Size,
+) -> Size {
+    let RequestedSizePercent {
+        width_pc,
+        height_pc,
+    } = flex_box_props.requested_size_percent;
+    let FixedSize {
+        col_count,
+        row_count,
+    } = flex_box_props.fixed_size;
+
+    let requested_size = size!(
+      col_count: col_count.unwrap_or_else(|| width_pc.calc_percentage(flexible_bounds.col_count)),
+      row_count: row_count.unwrap_or_else(|| height_pc.calc_percentage(flexible_bounds.row_count))
+    );
+
+    flex_box_props
+        .size_constraints
+        .apply(requested_size, container_bounds)
+}
+
+/// The space in the `container_box` that is left over for the children that don't have a
+/// [FixedSize], ie: its bounds minus its [FlexBox::fixed_children_size] (in its
+/// [LayoutDirection]).
+fn calc_flexible_bounds(container_box: &FlexBox) -> Size {
+    let Size {
+        col_count,
+        row_count,
+    } = container_box.bounds_size;
+    let fixed_children_size = container_box.fixed_children_size;
+    match container_box.dir {
+        LayoutDirection::Horizontal => size!(
+          col_count: col_count - fixed_children_size,
+          row_count: row_count
+        ),
+        LayoutDirection::Vertical => size!(
+          col_count: col_count,
+          row_count: row_count - fixed_children_size
+        ),
+    }
+}
+
+/// Adjust `origin` & `bounds_size` based on the `margin`, the border (if the box
+/// `has_border`), the `padding`, and the `maybe_style`'s padding.
 fn adjust_with_style(
     maybe_computed_style: &Option<TuiStyle>,
+    margin: BoxSides,
+    has_border: bool,
+    padding: BoxSides,
     origin_pos: Position,
     bounds_size: Size,
 ) -> (Position, Size) {
-    let mut style_adjusted_origin_pos = origin_pos;
-    let mut style_adjusted_bounds_size = bounds_size;
+    let (origin_pos, bounds_size) = margin.inset(origin_pos, bounds_size);
+    let (origin_pos, bounds_size) = match has_border {
+        true => BoxSides::all(1).inset(origin_pos, bounds_size),
+        false => (origin_pos, bounds_size),
+    };
+    let (mut style_adjusted_origin_pos, mut style_adjusted_bounds_size) =
+        padding.inset(origin_pos, bounds_size);
 
     if let Some(ref style) = maybe_computed_style {
         if let Some(padding) = style.padding {
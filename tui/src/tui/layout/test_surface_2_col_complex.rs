@@ -74,6 +74,18 @@ mod tests {
                 id: FlexBoxId::from(0),
                 dir: LayoutDirection::Horizontal,
                 requested_size_percent: requested_size_percent!(width:100, height:100),
+                size_constraints: Default::default(),
+                fixed_size: Default::default(),
+                fixed_children_size: Default::default(),
+                alignment: Default::default(),
+                visible: true,
+                wrap: Default::default(),
+                maybe_grid_layout: None,
+                maybe_grid_cell: None,
+                maybe_constraint_layout: None,
+                margin: Default::default(),
+                padding: Default::default(),
+                maybe_border: None,
                 maybe_styles: get_tui_styles! { @from: surface.stylesheet, [0] },
             })?;
 
@@ -182,6 +194,18 @@ mod tests {
                 id: FlexBoxId::from(2),
                 dir: LayoutDirection::Vertical,
                 requested_size_percent: requested_size_percent!(width:50, height:100),
+                size_constraints: Default::default(),
+                fixed_size: Default::default(),
+                fixed_children_size: Default::default(),
+                alignment: Default::default(),
+                visible: true,
+                wrap: Default::default(),
+                maybe_grid_layout: None,
+                maybe_grid_cell: None,
+                maybe_constraint_layout: None,
+                margin: Default::default(),
+                padding: Default::default(),
+                maybe_border: None,
             })?;
             make_right_col_assertions(surface)?;
             surface.box_end()?;
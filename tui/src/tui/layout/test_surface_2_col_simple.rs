@@ -35,12 +35,462 @@ mod tests {
     use crate::{box_end,
                 box_props,
                 box_start,
+                Alignment,
+                BoxAlignment,
+                BoxBorder,
+                BoxSides,
+                FixedSize,
                 FlexBoxId,
                 FlexBoxProps,
                 LayoutDirection,
                 LayoutManagement,
+                RenderOp,
+                SizeConstraints,
                 Surface,
-                SurfaceProps};
+                SurfaceProps,
+                ZOrder};
+
+    #[test]
+    fn test_surface_2_col_w_size_constraints() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface {
+                stylesheet: dsl_stylesheet()?,
+                ..Default::default()
+            };
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:40, row_count:10),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 []
+            }
+
+            // 20% of 40 cols is 8, which is below the min width.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:20, height:100),
+              styles:                 [1],
+              size_constraints:       SizeConstraints {
+                                        min_width: Some(ch!(15)),
+                                        ..Default::default()
+                                      },
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.bounds_size, size!(col_count:15, row_count:10));
+            box_end!(in: surface);
+
+            // 80% of 40 cols is 32, which is above the max width.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(2),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:80, height:100),
+              styles:                 [2],
+              size_constraints:       SizeConstraints {
+                                        max_width: Some(ch!(20)),
+                                        min_height: Some(ch!(50)),
+                                        ..Default::default()
+                                      },
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:15, row_index:0));
+            // The min height is larger than the container, so it is capped.
+            assert_eq2!(layout_item.bounds_size, size!(col_count:20, row_count:10));
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_2_col_w_fixed_size_children() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface {
+                stylesheet: dsl_stylesheet()?,
+                ..Default::default()
+            };
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:100, row_count:20),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+              fixed_children_size:    ch!(30),
+            }
+
+            // The sidebar is fixed.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:0, height:100),
+              styles:                 [1],
+              fixed_size:             FixedSize {
+                                        col_count: Some(ch!(30)),
+                                        row_count: None,
+                                      },
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.bounds_size, size!(col_count:30, row_count:20));
+            box_end!(in: surface);
+
+            // The other cols share the rest of the width.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(2),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:50, height:100),
+              styles:                 [2]
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:30, row_index:0));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:35, row_count:20));
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_2_col_w_hidden_sidebar() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:100, row_count:20),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 []
+            }
+
+            // The sidebar is hidden, so it doesn't take up any space, and its border
+            // isn't painted.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:30, height:100),
+              styles:                 [],
+              visible:                false,
+              maybe_border:           Some(BoxBorder::default()),
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert!(!layout_item.visible);
+            assert_eq2!(layout_item.bounds_size, size!(col_count:0, row_count:0));
+
+            // So are the boxes in it.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(2),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 []
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert!(!layout_item.visible);
+            assert_eq2!(layout_item.bounds_size, size!(col_count:0, row_count:0));
+            box_end!(in: surface);
+            box_end!(in: surface);
+
+            // The editor gets the space of the sidebar.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(3),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:70, height:100),
+              styles:                 []
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert!(layout_item.visible);
+            assert_eq2!(layout_item.origin_pos, position!(col_index:0, row_index:0));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:100, row_count:20));
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+
+            assert!(surface.render_pipeline.get(&ZOrder::Normal).is_none());
+        });
+    }
+
+    #[test]
+    fn test_surface_2_row_w_fixed_size_status_bar() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface {
+                stylesheet: dsl_stylesheet()?,
+                ..Default::default()
+            };
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:100, row_count:20),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+              fixed_children_size:    ch!(1),
+            }
+
+            // The content is added before the status bar, and it gets the rest of the
+            // height.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 []
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.bounds_size, size!(col_count:100, row_count:19));
+            box_end!(in: surface);
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(2),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:0),
+              styles:                 [],
+              fixed_size:             FixedSize {
+                                        col_count: None,
+                                        row_count: Some(ch!(1)),
+                                      },
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:0, row_index:19));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:100, row_count:1));
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_w_aligned_children() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:40, row_count:10),
+            })?;
+
+            // The root box is centered vertically in the surface.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:50),
+              styles:                 [],
+              alignment:              BoxAlignment {
+                                        vertical: Alignment::Center,
+                                        ..Default::default()
+                                      },
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:0, row_index:2));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:40, row_count:5));
+
+            let fixed_size = FixedSize {
+                col_count: Some(ch!(10)),
+                row_count: None,
+            };
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:40),
+              styles:                 [],
+              fixed_size:             fixed_size,
+              alignment:              BoxAlignment {
+                                        horizontal: Alignment::Center,
+                                        ..Default::default()
+                                      },
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:15, row_index:2));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:10, row_count:2));
+            box_end!(in: surface);
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(2),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:40),
+              styles:                 [],
+              fixed_size:             fixed_size,
+              alignment:              BoxAlignment {
+                                        horizontal: Alignment::Stretch,
+                                        ..Default::default()
+                                      },
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:0, row_index:4));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:40, row_count:2));
+            box_end!(in: surface);
+
+            // It is only aligned across the direction of its container.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(3),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:20),
+              styles:                 [],
+              fixed_size:             fixed_size,
+              alignment:              BoxAlignment {
+                                        horizontal: Alignment::End,
+                                        vertical: Alignment::End,
+                                      },
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:30, row_index:6));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:10, row_count:1));
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_2_col_w_margin_and_padding() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface {
+                stylesheet: dsl_stylesheet()?,
+                ..Default::default()
+            };
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:100, row_count:20),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+              margin:                 BoxSides::all(1),
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.bounds_size, size!(col_count:100, row_count:20));
+            assert_eq2!(
+                layout_item.style_adjusted_origin_pos,
+                position!(col_index:1, row_index:1)
+            );
+            assert_eq2!(
+                layout_item.style_adjusted_bounds_size,
+                size!(col_count:98, row_count:18)
+            );
+
+            // The margin, the padding, and the padding from the style (2) add up.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:50, height:100),
+              styles:                 [1],
+              margin:                 BoxSides::all(1),
+              padding:                BoxSides { left: ch!(3), ..Default::default() },
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:0, row_index:0));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:50, row_count:20));
+            assert_eq2!(
+                layout_item.style_adjusted_origin_pos,
+                position!(col_index:6, row_index:3)
+            );
+            assert_eq2!(
+                layout_item.style_adjusted_bounds_size,
+                size!(col_count:41, row_count:14)
+            );
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_2_col_w_border() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:20, row_count:10),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+              margin:                 BoxSides { top: ch!(1), ..Default::default() },
+              maybe_border:           Some(BoxBorder {
+                                        maybe_title: Some("Title".into()),
+                                        ..Default::default()
+                                      }),
+            }
+
+            // The content is inside the margin & the border.
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert!(layout_item.has_border);
+            assert_eq2!(
+                layout_item.style_adjusted_origin_pos,
+                position!(col_index:1, row_index:2)
+            );
+            assert_eq2!(
+                layout_item.style_adjusted_bounds_size,
+                size!(col_count:18, row_count:7)
+            );
+
+            // The border is painted inside the margin.
+            let render_ops = surface
+                .render_pipeline
+                .get_all_render_op_in(ZOrder::Normal)
+                .unwrap();
+            assert!(render_ops.contains(&RenderOp::MoveCursorPositionAbs(
+                position!(col_index:0, row_index:1)
+            )));
+            assert!(render_ops.contains(&RenderOp::PaintTextWithAttributes(
+                "╭─ Title ──────────╮".into(),
+                None
+            )));
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
 
     #[test]
     fn test_surface_2_col_simple() -> CommonResult<()> {
@@ -74,6 +524,18 @@ mod tests {
                 id: FlexBoxId::from(0),
                 dir: LayoutDirection::Horizontal,
                 requested_size_percent: requested_size_percent!(width:100, height:100),
+                size_constraints: Default::default(),
+                fixed_size: Default::default(),
+                fixed_children_size: Default::default(),
+                alignment: Default::default(),
+                visible: true,
+                wrap: Default::default(),
+                maybe_grid_layout: None,
+                maybe_grid_cell: None,
+                maybe_constraint_layout: None,
+                margin: Default::default(),
+                padding: Default::default(),
+                maybe_border: None,
                 maybe_styles: None,
             })?;
 
@@ -165,6 +627,18 @@ mod tests {
                 id: FlexBoxId::from(2),
                 dir: LayoutDirection::Vertical,
                 requested_size_percent: requested_size_percent!(width:50, height:100),
+                size_constraints: Default::default(),
+                fixed_size: Default::default(),
+                fixed_children_size: Default::default(),
+                alignment: Default::default(),
+                visible: true,
+                wrap: Default::default(),
+                maybe_grid_layout: None,
+                maybe_grid_cell: None,
+                maybe_constraint_layout: None,
+                margin: Default::default(),
+                padding: Default::default(),
+                maybe_border: None,
             })?;
             make_right_col_assertions(surface)?;
             surface.box_end()?;
@@ -0,0 +1,104 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2,
+                    ch,
+                    get_tui_styles,
+                    percent,
+                    position,
+                    requested_size_percent,
+                    size,
+                    throws,
+                    CommonResult};
+
+    use crate::{box_end,
+                box_props,
+                box_start,
+                ConstraintLayout,
+                FlexBoxId,
+                LayoutConstraint,
+                LayoutDirection,
+                LayoutManagement,
+                Surface,
+                SurfaceProps};
+
+    #[test]
+    fn test_surface_constraint_layout() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:60, row_count:20),
+            })?;
+
+            // The sidebar is 25% wide, but at least 20 cols, and the editor gets the
+            // rest.
+            box_start! {
+              in:                      surface,
+              id:                      FlexBoxId::from(0),
+              dir:                     LayoutDirection::Horizontal,
+              requested_size_percent:  requested_size_percent!(width:100, height:100),
+              styles:                  [],
+              maybe_constraint_layout: Some(ConstraintLayout::new(vec![
+                                         vec![
+                                           LayoutConstraint::Percent(percent!(25)?),
+                                           LayoutConstraint::Min(ch!(20)),
+                                         ],
+                                         vec![LayoutConstraint::Fill(1)],
+                                       ])),
+            }
+
+            // The requested size percent of the children is ignored.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:50, height:50),
+              styles:                 []
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:0, row_index:0));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:20, row_count:20));
+            box_end!(in: surface);
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(2),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:50, height:50),
+              styles:                 []
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:20, row_index:0));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:40, row_count:20));
+            box_end!(in: surface);
+
+            // There are only 2 segments.
+            let result = surface.box_start(box_props! {
+              id:                     FlexBoxId::from(3),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:50, height:50),
+            });
+            assert!(result.is_err());
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
+}
@@ -0,0 +1,117 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2,
+                    get_tui_styles,
+                    position,
+                    requested_size_percent,
+                    size,
+                    throws,
+                    CommonResult};
+
+    use crate::{box_end,
+                box_props,
+                box_start,
+                BoxBorder,
+                FlexBoxId,
+                FloatingBoxAnchor,
+                FloatingBoxProps,
+                LayoutDirection,
+                LayoutManagement,
+                Surface,
+                SurfaceProps,
+                ZOrder};
+
+    #[test]
+    fn test_surface_floating_box() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:40, row_count:10),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 []
+            }
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:30),
+              styles:                 []
+            }
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+
+            // The anchor box can be used after it is removed from the stack of boxes.
+            let floating_box = surface.add_floating_box(FloatingBoxProps {
+                id: FlexBoxId::from(2),
+                anchor: FloatingBoxAnchor::Below(FlexBoxId::from(1)),
+                size: size!(col_count:20, row_count:4),
+                maybe_border: Some(BoxBorder::default()),
+                ..Default::default()
+            })?;
+            assert_eq2!(floating_box.id, FlexBoxId::from(2));
+            assert_eq2!(floating_box.origin_pos, position!(col_index:0, row_index:3));
+            assert_eq2!(floating_box.bounds_size, size!(col_count:20, row_count:4));
+            assert_eq2!(
+                floating_box.style_adjusted_origin_pos,
+                position!(col_index:1, row_index:4)
+            );
+            assert_eq2!(
+                floating_box.style_adjusted_bounds_size,
+                size!(col_count:18, row_count:2)
+            );
+
+            // The background & the border are painted on top of everything else.
+            assert_eq2!(
+                surface.render_pipeline.get(&ZOrder::Glass).unwrap().len(),
+                2
+            );
+            assert!(surface.render_pipeline.get(&ZOrder::Normal).is_none());
+
+            // It is clipped to the size of the surface.
+            let floating_box = surface.add_floating_box(FloatingBoxProps {
+                id: FlexBoxId::from(3),
+                size: size!(col_count:50, row_count:4),
+                ..Default::default()
+            })?;
+            assert_eq2!(floating_box.origin_pos, position!(col_index:0, row_index:3));
+            assert_eq2!(floating_box.bounds_size, size!(col_count:40, row_count:4));
+
+            // The anchor box must be added to the surface first.
+            assert!(surface
+                .add_floating_box(FloatingBoxProps {
+                    id: FlexBoxId::from(4),
+                    anchor: FloatingBoxAnchor::Above(FlexBoxId::from(5)),
+                    ..Default::default()
+                })
+                .is_err());
+
+            surface.surface_end()?;
+        });
+    }
+}
@@ -0,0 +1,162 @@
+/*
+ *   Copyright (c) 2022 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2,
+                    ch,
+                    get_tui_styles,
+                    percent,
+                    position,
+                    requested_size_percent,
+                    size,
+                    throws,
+                    CommonResult};
+
+    use crate::{box_end,
+                box_props,
+                box_start,
+                FlexBoxId,
+                GridCell,
+                GridLayout,
+                GridTrack,
+                LayoutDirection,
+                LayoutManagement,
+                Surface,
+                SurfaceProps};
+
+    #[test]
+    fn test_surface_grid() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:60, row_count:21),
+            })?;
+
+            // A header row, and 2 rows of 2 cols below it.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+              maybe_grid_layout:      Some(GridLayout::new(
+                                        vec![GridTrack::Fixed(ch!(1)), GridTrack::Auto, GridTrack::Auto],
+                                        vec![GridTrack::Percent(percent!(25)?), GridTrack::Auto],
+                                      )),
+            }
+
+            // The cells can be added in any order.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+              maybe_grid_cell:        Some(GridCell::new(2, 1)),
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(
+                layout_item.origin_pos,
+                position!(col_index:15, row_index:11)
+            );
+            assert_eq2!(layout_item.bounds_size, size!(col_count:45, row_count:10));
+            box_end!(in: surface);
+
+            // The header spans both cols.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(2),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+              maybe_grid_cell:        Some(GridCell { col_span: 2, ..GridCell::new(0, 0) }),
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:0, row_index:0));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:60, row_count:1));
+            box_end!(in: surface);
+
+            // The sidebar spans both rows below the header.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(3),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+              maybe_grid_cell:        Some(GridCell { row_span: 2, ..GridCell::new(1, 0) }),
+            }
+            let layout_item = surface.stack_of_boxes.last().unwrap();
+            assert_eq2!(layout_item.origin_pos, position!(col_index:0, row_index:1));
+            assert_eq2!(layout_item.bounds_size, size!(col_count:15, row_count:20));
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
+
+    #[test]
+    fn test_surface_grid_cell_errors() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:60, row_count:20),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+            }
+
+            // The container doesn't have a grid.
+            let mut cell_props = box_props! {
+                id:                     FlexBoxId::from(1),
+                dir:                    LayoutDirection::Vertical,
+                requested_size_percent: requested_size_percent!(width:100, height:100),
+            };
+            cell_props.maybe_grid_cell = Some(GridCell::new(0, 0));
+            assert!(surface.box_start(cell_props.clone()).is_err());
+
+            box_end!(in: surface);
+
+            // The cell isn't in the grid.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 [],
+              maybe_grid_layout:      Some(GridLayout::new(
+                                        vec![GridTrack::Auto],
+                                        vec![GridTrack::Auto],
+                                      )),
+            }
+            cell_props.maybe_grid_cell = Some(GridCell::new(0, 1));
+            assert!(surface.box_start(cell_props).is_err());
+
+            box_end!(in: surface);
+            surface.surface_end()?;
+        });
+    }
+}
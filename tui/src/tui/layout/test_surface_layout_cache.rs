@@ -0,0 +1,161 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2,
+                    ch,
+                    get_tui_styles,
+                    position,
+                    requested_size_percent,
+                    size,
+                    throws,
+                    throws_with_return,
+                    CommonResult,
+                    Size};
+
+    use crate::{box_end,
+                box_props,
+                box_start,
+                ConstraintLayout,
+                FlexBox,
+                FlexBoxId,
+                LayoutCache,
+                LayoutConstraint,
+                LayoutDirection,
+                LayoutManagement,
+                Surface,
+                SurfaceProps};
+
+    /// Lay out a top row w/ a sidebar & an editor (in a constraint layout), and a bottom
+    /// row below it, and return the boxes that were laid out.
+    fn render(
+        layout_cache: &mut LayoutCache,
+        window_size: Size,
+        bottom_row_height: u16,
+    ) -> CommonResult<Vec<FlexBox>> {
+        throws_with_return!({
+            let mut surface = Surface {
+                maybe_layout_cache: Some(std::mem::take(layout_cache)),
+                ..Default::default()
+            };
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: window_size,
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 []
+            }
+
+            box_start! {
+              in:                      surface,
+              id:                      FlexBoxId::from(1),
+              dir:                     LayoutDirection::Horizontal,
+              requested_size_percent:  requested_size_percent!(width:100, height:50),
+              styles:                  [],
+              maybe_constraint_layout: Some(ConstraintLayout::new(vec![
+                                         vec![LayoutConstraint::Length(ch!(20))],
+                                         vec![LayoutConstraint::Fill(1)],
+                                       ])),
+            }
+            for id in [2, 3] {
+                box_start! {
+                  in:                     surface,
+                  id:                     FlexBoxId::from(id),
+                  dir:                    LayoutDirection::Vertical,
+                  requested_size_percent: requested_size_percent!(width:100, height:100),
+                  styles:                 []
+                }
+                box_end!(in: surface);
+            }
+            box_end!(in: surface);
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(4),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:bottom_row_height),
+              styles:                 []
+            }
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+
+            surface.surface_end()?;
+
+            if let Some(it) = surface.maybe_layout_cache.take() {
+                *layout_cache = it;
+            }
+            surface.laid_out_boxes
+        });
+    }
+
+    #[test]
+    fn test_surface_reuses_cached_boxes() -> CommonResult<()> {
+        throws!({
+            let window_size = size!(col_count:60, row_count:20);
+            let mut layout_cache = LayoutCache::default();
+
+            let first_render = render(&mut layout_cache, window_size, 50)?;
+            assert_eq2!(layout_cache.get_hit_count(), 0);
+            assert_eq2!(layout_cache.len(), 5);
+
+            // Nothing changed, so none of the boxes are laid out again.
+            let second_render = render(&mut layout_cache, window_size, 50)?;
+            assert_eq2!(layout_cache.get_hit_count(), 5);
+            assert_eq2!(second_render, first_render);
+
+            // The boxes are the same as the ones that are laid out from scratch.
+            let mut empty_cache = LayoutCache::default();
+            assert_eq2!(render(&mut empty_cache, window_size, 50)?, second_render);
+
+            let sidebar = second_render[2];
+            assert_eq2!(sidebar.bounds_size, size!(col_count:20, row_count:10));
+            let editor = second_render[3];
+            assert_eq2!(editor.origin_pos, position!(col_index:20, row_index:0));
+            assert_eq2!(editor.bounds_size, size!(col_count:40, row_count:10));
+            let bottom_row = second_render[4];
+            assert_eq2!(bottom_row.origin_pos, position!(col_index:0, row_index:10));
+            assert_eq2!(bottom_row.bounds_size, size!(col_count:60, row_count:10));
+        });
+    }
+
+    #[test]
+    fn test_surface_relayouts_changed_boxes() -> CommonResult<()> {
+        throws!({
+            let window_size = size!(col_count:60, row_count:20);
+            let mut layout_cache = LayoutCache::default();
+            render(&mut layout_cache, window_size, 50)?;
+
+            // Only the bottom row is laid out again.
+            let boxes = render(&mut layout_cache, window_size, 30)?;
+            assert_eq2!(layout_cache.get_hit_count(), 4);
+            assert_eq2!(layout_cache.len(), 5);
+            assert_eq2!(boxes[4].bounds_size, size!(col_count:60, row_count:6));
+
+            // Everything is laid out again when the window is resized.
+            let boxes = render(&mut layout_cache, size!(col_count:80, row_count:20), 30)?;
+            assert_eq2!(layout_cache.get_hit_count(), 0);
+            assert_eq2!(boxes[3].bounds_size, size!(col_count:60, row_count:10));
+        });
+    }
+}
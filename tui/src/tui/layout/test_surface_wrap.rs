@@ -0,0 +1,112 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2,
+                    ch,
+                    get_tui_styles,
+                    position,
+                    requested_size_percent,
+                    size,
+                    throws,
+                    CommonResult};
+
+    use crate::{box_end,
+                box_props,
+                box_start,
+                FixedSize,
+                FlexBoxId,
+                FlexWrap,
+                LayoutDirection,
+                LayoutManagement,
+                Surface,
+                SurfaceProps};
+
+    /// A toolbar (that is 30 cols wide) w/ buttons that are 12 cols wide, so only 2 of
+    /// them fit on each row. The 2nd button is 2 rows high, so the 2nd row of buttons
+    /// starts below it.
+    #[test]
+    fn test_surface_wrap_toolbar() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 0),
+                size: size!(col_count:30, row_count:10),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 []
+            }
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(1),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:50),
+              styles:                 [],
+              wrap:                   FlexWrap::Wrap,
+            }
+            for (id, row_count) in [(2, 1), (3, 2), (4, 1), (5, 1)] {
+                box_start! {
+                  in:                     surface,
+                  id:                     FlexBoxId::from(id),
+                  dir:                    LayoutDirection::Vertical,
+                  requested_size_percent: requested_size_percent!(width:100, height:100),
+                  styles:                 [],
+                  fixed_size:             FixedSize {
+                                            col_count: Some(ch!(12)),
+                                            row_count: Some(ch!(row_count)),
+                                          },
+                }
+                box_end!(in: surface);
+            }
+            box_end!(in: surface);
+
+            // The box after the toolbar is placed below all of it.
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(6),
+              dir:                    LayoutDirection::Vertical,
+              requested_size_percent: requested_size_percent!(width:100, height:50),
+              styles:                 []
+            }
+            box_end!(in: surface);
+
+            box_end!(in: surface);
+
+            surface.surface_end()?;
+
+            let origins: Vec<_> = surface
+                .laid_out_boxes
+                .iter()
+                .map(|it| (*it.origin_pos.col_index, *it.origin_pos.row_index))
+                .collect();
+            assert_eq2!(
+                origins,
+                vec![(0, 0), (0, 0), (0, 0), (12, 0), (0, 2), (12, 2), (0, 5)]
+            );
+
+            let button = surface.laid_out_boxes[3];
+            assert_eq2!(button.bounds_size, size!(col_count:12, row_count:2));
+        });
+    }
+}
@@ -0,0 +1,785 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, Position, PrettyPrintDebug, Size, TuiStyle, UnicodeString};
+
+use crate::{constants::{CHECKED,
+                        CHECKED_OUTPUT,
+                        CODE_BLOCK_START_PARTIAL,
+                        ORDERED_LIST_PARTIAL_PREFIX,
+                        UNCHECKED,
+                        UNCHECKED_OUTPUT,
+                        UNORDERED_LIST_PREFIX},
+            get_bold_style,
+            get_checkbox_checked_style,
+            get_checkbox_unchecked_style,
+            get_code_block_content_style,
+            get_code_block_lang_style,
+            get_foreground_dim_style,
+            get_foreground_style,
+            get_inline_code_style,
+            get_italic_style,
+            get_link_text_style,
+            get_list_bullet_style,
+            get_metadata_tags_values_style,
+            get_metadata_title_value_style,
+            parse_markdown,
+            Button,
+            CodeBlockLineContent,
+            HyperlinkData,
+            InputEvent,
+            Key,
+            KeyPress,
+            MdBlock,
+            MdDocument,
+            MdLineFragment,
+            MouseInput,
+            MouseInputKind,
+            SpecialKey};
+
+/// This is painted between the cells of a table.
+pub const MARKDOWN_VIEWER_TABLE_COLUMN_SEPARATOR: &str = " │ ";
+/// This is painted between the cells of the line below the header of a table.
+pub const MARKDOWN_VIEWER_TABLE_HEADER_SEPARATOR: &str = "─┼─";
+/// The lines of a code block are indented by this many cols.
+pub const MARKDOWN_VIEWER_CODE_BLOCK_INDENT_COL_COUNT: usize = 2;
+
+/// A line of the rendered markdown, made up of spans of text w/ their styles.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkdownViewerLine {
+    pub spans: Vec<(String, TuiStyle)>,
+}
+
+impl MarkdownViewerLine {
+    /// Returns the text of the line (w/out its styles).
+    pub fn get_text(&self) -> String {
+        self.spans.iter().map(|(text, _)| text.as_str()).collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkdownViewerItemKind {
+    Link {
+        url: String,
+    },
+    /// The `index` is the position of the checkbox in the document, starting at 0.
+    Checkbox {
+        index: usize,
+        is_checked: bool,
+    },
+}
+
+/// A link or a checkbox in the rendered markdown, which can be selected (w/
+/// <kbd>Tab</kbd>) and activated (w/ <kbd>Enter</kbd>), or clicked on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkdownViewerItem {
+    /// The position of the item in [MarkdownViewerBuffer::get_lines].
+    pub row_index: usize,
+    pub col_index: usize,
+    pub text: String,
+    pub style: TuiStyle,
+    pub kind: MarkdownViewerItemKind,
+}
+
+impl MarkdownViewerItem {
+    fn contains(&self, row_index: usize, col_index: usize) -> bool {
+        self.row_index == row_index
+            && col_index >= self.col_index
+            && col_index < self.col_index + UnicodeString::str_display_width(&self.text)
+    }
+}
+
+/// What happened when an [InputEvent] was applied to a [MarkdownViewerBuffer] w/
+/// [MarkdownViewerBuffer::apply_event].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkdownViewerApplyResponse {
+    /// A different item was selected (see [MarkdownViewerBuffer::get_selected_item]).
+    SelectionChanged,
+    /// A link was activated. It is up to the app to open the `url`.
+    LinkActivated { url: String },
+    /// A checkbox was toggled, and the source was changed to match (see
+    /// [MarkdownViewerBuffer::get_source]), eg: so that the app can save it.
+    CheckboxToggled { index: usize, is_checked: bool },
+    /// The lines were scrolled.
+    Scrolled,
+    /// The key was handled, but nothing changed, eg: <kbd>Down</kbd> at the end.
+    Consumed,
+    /// The event wasn't for the markdown viewer, so it should be passed on.
+    Noop,
+}
+
+/// The markdown that is displayed (read only) by a [crate::MarkdownViewerComponent]. It
+/// is parsed w/ [parse_markdown], and rendered into styled lines, eg: to show a help
+/// screen, or to preview a file. This is kept in the app's state (see
+/// [crate::HasMarkdownViewerBuffers]), so that the app can change the markdown.
+///
+/// Headings, lists, code blocks & metadata are rendered w/out their markdown syntax, and
+/// lines that start w/ `|` are rendered as a table (w/ aligned columns). The lines are
+/// clipped to the width of the viewer (they aren't wrapped).
+///
+/// Key bindings:
+/// - <kbd>Up</kbd>, <kbd>Down</kbd>, <kbd>PageUp</kbd>, <kbd>PageDown</kbd>,
+///   <kbd>Home</kbd> & <kbd>End</kbd> scroll the lines.
+/// - <kbd>Tab</kbd> & <kbd>Shift+Tab</kbd> select the next (or previous) link or
+///   checkbox.
+/// - <kbd>Enter</kbd> or <kbd>Space</kbd> activates the selected link, or toggles the
+///   selected checkbox (which changes the source).
+///
+/// The mouse wheel scrolls the lines, and clicking on a link or a checkbox selects it &
+/// activates it.
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownViewerBuffer {
+    source: String,
+    lines: Vec<MarkdownViewerLine>,
+    items: Vec<MarkdownViewerItem>,
+    scroll_offset: usize,
+    maybe_selected_item_index: Option<usize>,
+    maybe_last_bounds: Option<(Position, Size)>,
+}
+
+impl MarkdownViewerBuffer {
+    pub fn new(source: impl Into<String>) -> Self {
+        let mut it = Self::default();
+        it.set_source(source);
+        it
+    }
+
+    /// Replace the markdown, and render it again. The scroll offset is kept (if it still
+    /// fits), but the selection is cleared.
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = source.into();
+        self.maybe_selected_item_index = None;
+        self.render_source();
+    }
+
+    pub fn get_source(&self) -> &str { &self.source }
+
+    pub fn get_lines(&self) -> &[MarkdownViewerLine] { &self.lines }
+
+    pub fn get_items(&self) -> &[MarkdownViewerItem] { &self.items }
+
+    pub fn get_selected_item(&self) -> Option<&MarkdownViewerItem> {
+        self.items.get(self.maybe_selected_item_index?)
+    }
+
+    pub fn get_scroll_offset(&self) -> usize { self.scroll_offset }
+
+    /// Remember the bounds that the markdown viewer is painted in, so that it can be
+    /// scrolled a page at a time, and mouse clicks can be mapped to the items.
+    pub fn set_viewport(&mut self, origin_pos: Position, bounds_size: Size) {
+        self.maybe_last_bounds = Some((origin_pos, bounds_size));
+        self.scroll_offset = self.scroll_offset.min(self.get_max_scroll_offset());
+    }
+
+    /// The number of rows that fit in the box from the last call to
+    /// [Self::set_viewport], or [None] if it wasn't called yet.
+    pub fn get_viewport_row_count(&self) -> Option<usize> {
+        let (_, bounds_size) = self.maybe_last_bounds?;
+        Some(ch!(@to_usize bounds_size.row_count))
+    }
+
+    /// Toggle the checkbox at `index` (in the order that they appear in the document) in
+    /// the source, and render it again. Returns whether the checkbox is checked now, or
+    /// [None] if there is no such checkbox.
+    pub fn toggle_checkbox(&mut self, index: usize) -> Option<bool> {
+        let byte_index = *find_checkbox_byte_indices(&self.source).get(index)?;
+        let is_checked = self.source[byte_index..].starts_with(UNCHECKED);
+        let replacement = match is_checked {
+            true => CHECKED,
+            false => UNCHECKED,
+        };
+        self.source
+            .replace_range(byte_index..byte_index + replacement.len(), replacement);
+        self.render_source();
+        Some(is_checked)
+    }
+
+    fn render_source(&mut self) {
+        let mut builder = MarkdownViewerLinesBuilder::default();
+        match parse_markdown(&self.source) {
+            Ok((remainder, document)) => {
+                builder.add_document(&document);
+                // Anything that couldn't be parsed is displayed as is.
+                for line in remainder.lines() {
+                    builder.push_line(vec![(line.to_string(), get_foreground_style())]);
+                }
+            }
+            Err(_) => {
+                for line in self.source.lines() {
+                    builder.push_line(vec![(line.to_string(), get_foreground_style())]);
+                }
+            }
+        }
+        builder.flush_table();
+
+        self.lines = builder.lines;
+        self.items = builder.items;
+        if self
+            .maybe_selected_item_index
+            .is_some_and(|it| it >= self.items.len())
+        {
+            self.maybe_selected_item_index = None;
+        }
+        self.scroll_offset = self.scroll_offset.min(self.get_max_scroll_offset());
+    }
+
+    fn get_max_scroll_offset(&self) -> usize {
+        let viewport_row_count = self.get_viewport_row_count().unwrap_or_default();
+        self.lines.len().saturating_sub(viewport_row_count)
+    }
+
+    fn scroll_by(&mut self, delta: isize) -> MarkdownViewerApplyResponse {
+        let scroll_offset = self
+            .scroll_offset
+            .saturating_add_signed(delta)
+            .min(self.get_max_scroll_offset());
+        if scroll_offset != self.scroll_offset {
+            self.scroll_offset = scroll_offset;
+            MarkdownViewerApplyResponse::Scrolled
+        } else if self.lines.is_empty() {
+            MarkdownViewerApplyResponse::Noop
+        } else {
+            MarkdownViewerApplyResponse::Consumed
+        }
+    }
+
+    /// Select the next item (or the previous one, if `delta` is negative), wrapping
+    /// around at either end, and scroll it into view.
+    fn select_next_item(&mut self, delta: isize) -> MarkdownViewerApplyResponse {
+        if self.items.is_empty() {
+            return MarkdownViewerApplyResponse::Noop;
+        }
+        let item_count = self.items.len() as isize;
+        let item_index = match self.maybe_selected_item_index {
+            Some(it) => (it as isize + delta).rem_euclid(item_count) as usize,
+            None if delta < 0 => self.items.len() - 1,
+            None => 0,
+        };
+        if self.maybe_selected_item_index == Some(item_index) {
+            return MarkdownViewerApplyResponse::Consumed;
+        }
+        self.maybe_selected_item_index = Some(item_index);
+
+        // Scroll the selected item into view.
+        let row_index = self.items[item_index].row_index;
+        if let Some(viewport_row_count) = self.get_viewport_row_count() {
+            if row_index < self.scroll_offset {
+                self.scroll_offset = row_index;
+            } else if viewport_row_count > 0
+                && row_index >= self.scroll_offset + viewport_row_count
+            {
+                self.scroll_offset = row_index + 1 - viewport_row_count;
+            }
+        }
+        MarkdownViewerApplyResponse::SelectionChanged
+    }
+
+    fn activate_selected_item(&mut self) -> MarkdownViewerApplyResponse {
+        let Some(item) = self.get_selected_item() else {
+            return MarkdownViewerApplyResponse::Noop;
+        };
+        match item.kind.clone() {
+            MarkdownViewerItemKind::Link { url } => {
+                MarkdownViewerApplyResponse::LinkActivated { url }
+            }
+            MarkdownViewerItemKind::Checkbox { index, .. } => {
+                match self.toggle_checkbox(index) {
+                    Some(is_checked) => {
+                        MarkdownViewerApplyResponse::CheckboxToggled { index, is_checked }
+                    }
+                    None => MarkdownViewerApplyResponse::Consumed,
+                }
+            }
+        }
+    }
+
+    pub fn apply_event(
+        &mut self,
+        input_event: InputEvent,
+    ) -> MarkdownViewerApplyResponse {
+        let page_size = self.get_viewport_row_count().unwrap_or(1).max(1) as isize;
+
+        match input_event {
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(special_key),
+            }) => match special_key {
+                SpecialKey::Up => self.scroll_by(-1),
+                SpecialKey::Down => self.scroll_by(1),
+                SpecialKey::PageUp => self.scroll_by(-page_size),
+                SpecialKey::PageDown => self.scroll_by(page_size),
+                SpecialKey::Home => self.scroll_by(isize::MIN),
+                SpecialKey::End => self.scroll_by(isize::MAX),
+                SpecialKey::Tab => self.select_next_item(1),
+                SpecialKey::BackTab => self.select_next_item(-1),
+                SpecialKey::Enter => self.activate_selected_item(),
+                _ => MarkdownViewerApplyResponse::Noop,
+            },
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character(' '),
+            }) => self.activate_selected_item(),
+
+            InputEvent::Mouse(MouseInput { kind, pos, .. }) => {
+                self.apply_mouse_event(kind, pos)
+            }
+
+            _ => MarkdownViewerApplyResponse::Noop,
+        }
+    }
+
+    fn apply_mouse_event(
+        &mut self,
+        kind: MouseInputKind,
+        pos: Position,
+    ) -> MarkdownViewerApplyResponse {
+        let Some((origin_pos, bounds_size)) = self.maybe_last_bounds else {
+            return MarkdownViewerApplyResponse::Noop;
+        };
+
+        match kind {
+            MouseInputKind::ScrollUp | MouseInputKind::ScrollDown => {
+                let delta = match kind {
+                    MouseInputKind::ScrollUp => -1,
+                    _ => 1,
+                };
+                match self.scroll_by(delta) {
+                    MarkdownViewerApplyResponse::Scrolled => {
+                        MarkdownViewerApplyResponse::Scrolled
+                    }
+                    _ => MarkdownViewerApplyResponse::Noop,
+                }
+            }
+
+            MouseInputKind::MouseDown(Button::Left) => {
+                if pos.col_index < origin_pos.col_index
+                    || pos.row_index < origin_pos.row_index
+                    || pos.col_index >= origin_pos.col_index + bounds_size.col_count
+                    || pos.row_index >= origin_pos.row_index + bounds_size.row_count
+                {
+                    return MarkdownViewerApplyResponse::Noop;
+                }
+                let col_index = ch!(@to_usize pos.col_index - origin_pos.col_index);
+                let row_index = self.scroll_offset
+                    + ch!(@to_usize pos.row_index - origin_pos.row_index);
+                let Some(item_index) = self
+                    .items
+                    .iter()
+                    .position(|it| it.contains(row_index, col_index))
+                else {
+                    return MarkdownViewerApplyResponse::Noop;
+                };
+                self.maybe_selected_item_index = Some(item_index);
+                self.activate_selected_item()
+            }
+
+            _ => MarkdownViewerApplyResponse::Noop,
+        }
+    }
+}
+
+/// Returns the byte index of each checkbox (eg: `[ ]`) at the start of a list item in
+/// the `source`, in the order that they appear (skipping the ones in code blocks).
+fn find_checkbox_byte_indices(source: &str) -> Vec<usize> {
+    let mut it = vec![];
+    let mut line_byte_index = 0;
+    let mut is_in_code_block = false;
+    for line in source.split_inclusive('\n') {
+        let trimmed_line = line.trim_start();
+        if trimmed_line.starts_with(CODE_BLOCK_START_PARTIAL) {
+            is_in_code_block = !is_in_code_block;
+        } else if !is_in_code_block {
+            let maybe_rest =
+                trimmed_line
+                    .strip_prefix(UNORDERED_LIST_PREFIX)
+                    .or_else(|| {
+                        let number_len =
+                            trimmed_line.find(|it: char| !it.is_ascii_digit())?;
+                        match number_len {
+                            0 => None,
+                            _ => trimmed_line[number_len..]
+                                .strip_prefix(ORDERED_LIST_PARTIAL_PREFIX),
+                        }
+                    });
+            if let Some(rest) = maybe_rest {
+                if rest.starts_with(CHECKED) || rest.starts_with(UNCHECKED) {
+                    it.push(line_byte_index + line.len() - rest.len());
+                }
+            }
+        }
+        line_byte_index += line.len();
+    }
+    it
+}
+
+/// Renders the blocks of a [MdDocument] into [MarkdownViewerLine]s, and keeps track of
+/// the [MarkdownViewerItem]s in them.
+#[derive(Debug, Default)]
+struct MarkdownViewerLinesBuilder {
+    lines: Vec<MarkdownViewerLine>,
+    items: Vec<MarkdownViewerItem>,
+    checkbox_count: usize,
+    /// The lines of the table that is being rendered, which are rendered once all of
+    /// them are known (to align the columns).
+    pending_table_lines: Vec<String>,
+}
+
+impl MarkdownViewerLinesBuilder {
+    fn push_line(&mut self, spans: Vec<(String, TuiStyle)>) {
+        self.lines.push(MarkdownViewerLine { spans });
+    }
+
+    fn add_document(&mut self, document: &MdDocument<'_>) {
+        for block in document.iter() {
+            if let MdBlock::Text(fragments) = block {
+                let text = fragments.pretty_print_debug();
+                if text.trim_start().starts_with('|') {
+                    self.pending_table_lines.push(text);
+                    continue;
+                }
+            }
+            self.flush_table();
+            self.add_block(block);
+        }
+    }
+
+    fn add_block(&mut self, block: &MdBlock<'_>) {
+        match block {
+            MdBlock::Title(title) => self
+                .push_line(vec![(title.to_string(), get_metadata_title_value_style())]),
+            MdBlock::Date(date) => {
+                self.push_line(vec![(date.to_string(), get_foreground_dim_style())])
+            }
+            MdBlock::Tags(tags) => {
+                self.push_line(vec![(tags.join(", "), get_metadata_tags_values_style())])
+            }
+            MdBlock::Authors(authors) => self
+                .push_line(vec![(authors.join(", "), get_metadata_tags_values_style())]),
+            MdBlock::Heading(heading_data) => {
+                let style = TuiStyle {
+                    bold: true,
+                    underline: heading_data.heading_level.level == 1,
+                    ..get_foreground_style()
+                };
+                self.push_line(vec![(heading_data.text.to_string(), style)]);
+            }
+            MdBlock::Text(fragments) => self.add_fragments(fragments),
+            MdBlock::SmartList((lines, _bullet_kind, _indent)) => {
+                for fragments in lines.iter() {
+                    self.add_fragments(fragments);
+                }
+            }
+            MdBlock::CodeBlock(code_block_lines) => {
+                for code_block_line in code_block_lines.iter() {
+                    match code_block_line.content {
+                        CodeBlockLineContent::StartTag => {
+                            if let Some(language) = code_block_line.language {
+                                self.push_line(vec![(
+                                    language.to_string(),
+                                    get_code_block_lang_style(),
+                                )]);
+                            }
+                        }
+                        CodeBlockLineContent::Text(text) => self.push_line(vec![(
+                            format!(
+                                "{}{text}",
+                                " ".repeat(MARKDOWN_VIEWER_CODE_BLOCK_INDENT_COL_COUNT)
+                            ),
+                            get_code_block_content_style(),
+                        )]),
+                        CodeBlockLineContent::EndTag => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// The markdown syntax (eg: `*` around bold text) is dropped, and the links &
+    /// checkboxes are added as items.
+    fn add_fragments(&mut self, fragments: &[MdLineFragment<'_>]) {
+        let row_index = self.lines.len();
+        let mut col_index = 0;
+        let mut spans = vec![];
+        for fragment in fragments {
+            let (text, style) = match fragment {
+                MdLineFragment::UnorderedListBullet { .. }
+                | MdLineFragment::OrderedListBullet { .. } => {
+                    (fragment.pretty_print_debug(), get_list_bullet_style())
+                }
+                MdLineFragment::Plain(text) => (text.to_string(), get_foreground_style()),
+                MdLineFragment::Bold(text) => (text.to_string(), get_bold_style()),
+                MdLineFragment::Italic(text) => (text.to_string(), get_italic_style()),
+                MdLineFragment::InlineCode(text) => {
+                    (text.to_string(), get_inline_code_style())
+                }
+                MdLineFragment::Image(HyperlinkData { text, .. }) => {
+                    (format!("[{text}]"), get_foreground_dim_style())
+                }
+                MdLineFragment::Link(HyperlinkData { text, url }) => {
+                    let style = TuiStyle {
+                        underline: true,
+                        ..get_link_text_style()
+                    };
+                    self.items.push(MarkdownViewerItem {
+                        row_index,
+                        col_index,
+                        text: text.to_string(),
+                        style,
+                        kind: MarkdownViewerItemKind::Link {
+                            url: url.to_string(),
+                        },
+                    });
+                    (text.to_string(), style)
+                }
+                MdLineFragment::Checkbox(is_checked) => {
+                    let (text, style) = match *is_checked {
+                        true => (CHECKED_OUTPUT, get_checkbox_checked_style()),
+                        false => (UNCHECKED_OUTPUT, get_checkbox_unchecked_style()),
+                    };
+                    self.items.push(MarkdownViewerItem {
+                        row_index,
+                        col_index,
+                        text: text.to_string(),
+                        style,
+                        kind: MarkdownViewerItemKind::Checkbox {
+                            index: self.checkbox_count,
+                            is_checked: *is_checked,
+                        },
+                    });
+                    self.checkbox_count += 1;
+                    (text.to_string(), style)
+                }
+            };
+            col_index += UnicodeString::str_display_width(&text);
+            spans.push((text, style));
+        }
+        self.push_line(spans);
+    }
+
+    /// Render the pending lines of a table, w/ the cells of each column padded to the
+    /// width of the widest one. The rows above the `|---|` line are the header.
+    fn flush_table(&mut self) {
+        if self.pending_table_lines.is_empty() {
+            return;
+        }
+        let rows: Vec<Vec<String>> = std::mem::take(&mut self.pending_table_lines)
+            .iter()
+            .map(|it| split_table_row(it))
+            .collect();
+        let header_row_count = rows
+            .iter()
+            .position(|it| is_table_separator_row(it))
+            .unwrap_or(0);
+
+        let column_count = rows.iter().map(Vec::len).max().unwrap_or_default();
+        let column_widths: Vec<usize> = (0..column_count)
+            .map(|column_index| {
+                rows.iter()
+                    .filter(|it| !is_table_separator_row(it))
+                    .filter_map(|it| it.get(column_index))
+                    .map(|it| UnicodeString::str_display_width(it))
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        let border_style = get_foreground_dim_style();
+
+        for (row_index, row) in rows.iter().enumerate() {
+            if is_table_separator_row(row) {
+                let line = column_widths
+                    .iter()
+                    .map(|it| "─".repeat(*it))
+                    .collect::<Vec<_>>()
+                    .join(MARKDOWN_VIEWER_TABLE_HEADER_SEPARATOR);
+                self.push_line(vec![(line, border_style)]);
+                continue;
+            }
+
+            let cell_style = match row_index < header_row_count {
+                true => get_bold_style(),
+                false => get_foreground_style(),
+            };
+            let mut spans = vec![];
+            for (column_index, width) in column_widths.iter().enumerate() {
+                let cell = row
+                    .get(column_index)
+                    .map(String::as_str)
+                    .unwrap_or_default();
+                let is_last_column = column_index == column_count - 1;
+                if column_index > 0 {
+                    spans.push((
+                        MARKDOWN_VIEWER_TABLE_COLUMN_SEPARATOR.to_string(),
+                        border_style,
+                    ));
+                }
+                let cell = match is_last_column {
+                    true => cell.to_string(),
+                    false => UnicodeString::from(cell)
+                        .pad_end_with_spaces_to_fit_width(" ", ch!(*width)),
+                };
+                spans.push((cell, cell_style));
+            }
+            self.push_line(spans);
+        }
+    }
+}
+
+/// Returns the cells of a table row, eg: `| a | b |` has `a` & `b`.
+fn split_table_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(|it| it.trim().to_string()).collect()
+}
+
+/// The line below the header of a table, eg: `|---|:--:|`.
+fn is_table_separator_row(cells: &[String]) -> bool {
+    cells
+        .iter()
+        .all(|it| !it.is_empty() && it.chars().all(|it| it == '-' || it == ':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, position, size};
+
+    use super::*;
+
+    const SOURCE: &str = "# Help
+Press *q* to [quit](https://r3bl.com).
+- [ ] todo
+| Key | Action |
+|-----|--------|
+| q   | Quit   |";
+
+    fn key(special_key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        })
+    }
+
+    fn get_line_texts(buffer: &MarkdownViewerBuffer) -> Vec<String> {
+        buffer.get_lines().iter().map(|it| it.get_text()).collect()
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let buffer = MarkdownViewerBuffer::new(SOURCE);
+        assert_eq2!(
+            get_line_texts(&buffer),
+            vec![
+                "Help",
+                "Press q to quit.",
+                "─┤┊┈┊ todo",
+                "Key │ Action",
+                "────┼───────",
+                "q   │ Quit",
+            ]
+        );
+
+        let items = buffer.get_items();
+        assert_eq2!(items.len(), 2);
+        assert_eq2!((items[0].row_index, items[0].col_index), (1, 11));
+        assert_eq2!(
+            items[0].kind,
+            MarkdownViewerItemKind::Link {
+                url: "https://r3bl.com".to_string()
+            }
+        );
+        assert_eq2!((items[1].row_index, items[1].col_index), (2, 2));
+    }
+
+    #[test]
+    fn test_activate_items_w_keyboard() {
+        let mut buffer = MarkdownViewerBuffer::new(SOURCE);
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Enter)),
+            MarkdownViewerApplyResponse::Noop
+        );
+
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Tab)),
+            MarkdownViewerApplyResponse::SelectionChanged
+        );
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Enter)),
+            MarkdownViewerApplyResponse::LinkActivated {
+                url: "https://r3bl.com".to_string()
+            }
+        );
+
+        // Toggling the checkbox changes the source.
+        buffer.apply_event(key(SpecialKey::Tab));
+        assert_eq2!(
+            buffer.apply_event(InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character(' ')
+            })),
+            MarkdownViewerApplyResponse::CheckboxToggled {
+                index: 0,
+                is_checked: true
+            }
+        );
+        assert!(buffer.get_source().contains("- [x] todo"));
+        assert_eq2!(get_line_texts(&buffer)[2], "─┤┊✔┊ todo".to_string());
+
+        // The selection wraps around.
+        buffer.apply_event(key(SpecialKey::Tab));
+        assert_eq2!(buffer.get_selected_item().map(|it| it.row_index), Some(1));
+        buffer.apply_event(key(SpecialKey::BackTab));
+        assert_eq2!(buffer.get_selected_item().map(|it| it.row_index), Some(2));
+    }
+
+    #[test]
+    fn test_scroll_and_click() {
+        let mut buffer = MarkdownViewerBuffer::new(SOURCE);
+        buffer.set_viewport(
+            position!(col_index: 10, row_index: 5),
+            size!(col_count: 20, row_count: 2),
+        );
+
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Down)),
+            MarkdownViewerApplyResponse::Scrolled
+        );
+        assert_eq2!(buffer.get_scroll_offset(), 1);
+
+        // Click on the link, which is in the first row of the viewport now.
+        assert_eq2!(
+            buffer.apply_event(InputEvent::Mouse(MouseInput {
+                pos: position!(col_index: 22, row_index: 5),
+                kind: MouseInputKind::MouseDown(Button::Left),
+                maybe_modifier_keys: None,
+            })),
+            MarkdownViewerApplyResponse::LinkActivated {
+                url: "https://r3bl.com".to_string()
+            }
+        );
+
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::End)),
+            MarkdownViewerApplyResponse::Scrolled
+        );
+        assert_eq2!(buffer.get_scroll_offset(), 4);
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Down)),
+            MarkdownViewerApplyResponse::Consumed
+        );
+
+        // The selected checkbox is scrolled into view.
+        buffer.apply_event(key(SpecialKey::BackTab));
+        assert_eq2!(buffer.get_scroll_offset(), 2);
+    }
+}
@@ -0,0 +1,305 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_core::{ch,
+                position,
+                throws_with_return,
+                CommonResult,
+                TuiStyle,
+                UnicodeString};
+
+use crate::{paint_spans,
+            render_ops,
+            render_pipeline,
+            BoxedSafeComponent,
+            Component,
+            EventPropagation,
+            FlexBox,
+            FlexBoxId,
+            GlobalData,
+            HasFocus,
+            HasMarkdownViewerBuffers,
+            InputEvent,
+            MarkdownViewerApplyResponse,
+            MarkdownViewerBuffer,
+            MarkdownViewerLine,
+            OnMarkdownViewerEventFn,
+            RenderOps,
+            RenderPipeline,
+            SurfaceBounds,
+            ZOrder};
+
+/// This is a re-usable component that displays the markdown in a [MarkdownViewerBuffer]
+/// (which is kept in the app's state, see [HasMarkdownViewerBuffers]), read only, eg: as
+/// a help screen, or as a preview pane next to an editor.
+///
+/// Register it in the [crate::ComponentRegistryMap] like the editor component, and route
+/// the input events (including mouse events) to it. See [MarkdownViewerBuffer] for the
+/// key bindings. The `on_markdown_viewer_event_handler` is called when a link is
+/// activated, or a checkbox is toggled.
+#[derive(Debug)]
+pub struct MarkdownViewerComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub data: MarkdownViewerComponentData<S, AS>,
+}
+
+#[derive(Debug)]
+pub struct MarkdownViewerComponentData<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub on_markdown_viewer_event_handler: Option<OnMarkdownViewerEventFn<S, AS>>,
+}
+
+impl<S, AS> Component<S, AS> for MarkdownViewerComponent<S, AS>
+where
+    S: HasMarkdownViewerBuffers + Default + Clone + Debug + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    fn reset(&mut self) {}
+
+    fn get_id(&self) -> FlexBoxId { self.data.id }
+
+    fn is_focusable(&self) -> bool { true }
+
+    fn render(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        current_box: FlexBox,
+        _surface_bounds: SurfaceBounds, /* Ignore this. */
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<RenderPipeline> {
+        throws_with_return!({
+            let mut render_pipeline = render_pipeline!();
+            let has_focus = has_focus.does_current_box_have_focus(current_box);
+            if let Some(buffer) = global_data
+                .state
+                .get_mut_markdown_viewer_buffer(self.data.id)
+            {
+                render_pipeline.push(
+                    ZOrder::Normal,
+                    render_markdown_viewer(buffer, &current_box, has_focus),
+                );
+            }
+            render_pipeline
+        });
+    }
+
+    fn handle_event(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        input_event: InputEvent,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        throws_with_return!({
+            let MarkdownViewerComponentData {
+                id,
+                on_markdown_viewer_event_handler,
+                ..
+            } = &self.data;
+
+            let Some(buffer) = global_data.state.get_mut_markdown_viewer_buffer(*id)
+            else {
+                return Ok(EventPropagation::Propagate);
+            };
+
+            let response = buffer.apply_event(input_event);
+            match response {
+                MarkdownViewerApplyResponse::Noop => EventPropagation::Propagate,
+                MarkdownViewerApplyResponse::Consumed => EventPropagation::Consumed,
+                MarkdownViewerApplyResponse::Scrolled
+                | MarkdownViewerApplyResponse::SelectionChanged => {
+                    EventPropagation::ConsumedRender
+                }
+                MarkdownViewerApplyResponse::LinkActivated { .. }
+                | MarkdownViewerApplyResponse::CheckboxToggled { .. } => {
+                    if let Some(on_markdown_viewer_event_handler) =
+                        on_markdown_viewer_event_handler
+                    {
+                        on_markdown_viewer_event_handler(
+                            *id,
+                            response,
+                            &mut global_data.state,
+                            &mut global_data.main_thread_channel_sender,
+                        );
+                    }
+                    EventPropagation::ConsumedRender
+                }
+            }
+        });
+    }
+}
+
+/// The selected link or checkbox is dimmed when the markdown viewer doesn't have focus.
+fn get_markdown_viewer_selected_item_style(style: TuiStyle, has_focus: bool) -> TuiStyle {
+    TuiStyle {
+        reverse: true,
+        dim: !has_focus,
+        ..style
+    }
+}
+
+/// Paint the lines in the viewport of the `buffer` (clipped to the width of the box), and
+/// then the selected item on top of them.
+fn render_markdown_viewer(
+    buffer: &mut MarkdownViewerBuffer,
+    current_box: &FlexBox,
+    has_focus: bool,
+) -> RenderOps {
+    let origin_pos = current_box.style_adjusted_origin_pos;
+    let bounds_size = current_box.style_adjusted_bounds_size;
+    buffer.set_viewport(origin_pos, bounds_size);
+
+    let col_count = ch!(@to_usize bounds_size.col_count);
+    let scroll_offset = buffer.get_scroll_offset();
+    let viewport_row_count = buffer.get_viewport_row_count().unwrap_or_default();
+
+    let mut render_ops = render_ops!();
+    for (row_index, line) in buffer
+        .get_lines()
+        .iter()
+        .skip(scroll_offset)
+        .take(viewport_row_count)
+        .enumerate()
+    {
+        paint_spans(
+            &mut render_ops,
+            origin_pos + position!(col_index: 0, row_index: row_index),
+            clip_line(line, col_count),
+        );
+    }
+
+    if let Some(item) = buffer.get_selected_item() {
+        let is_visible = item.row_index >= scroll_offset
+            && item.row_index < scroll_offset + viewport_row_count
+            && item.col_index < col_count;
+        if is_visible {
+            let text = UnicodeString::from(item.text.as_str())
+                .clip_to_width(ch!(0), ch!(col_count - item.col_index))
+                .to_string();
+            paint_spans(
+                &mut render_ops,
+                origin_pos
+                    + position!(
+                        col_index: item.col_index,
+                        row_index: item.row_index - scroll_offset
+                    ),
+                vec![(
+                    text,
+                    Some(get_markdown_viewer_selected_item_style(
+                        item.style, has_focus,
+                    )),
+                )],
+            );
+        }
+    }
+
+    render_ops
+}
+
+/// Returns the spans of the `line` that fit in `col_count` cols (the last one is clipped).
+fn clip_line(
+    line: &MarkdownViewerLine,
+    col_count: usize,
+) -> Vec<(String, Option<TuiStyle>)> {
+    let mut it = vec![];
+    let mut avail_col_count = col_count;
+    for (text, style) in &line.spans {
+        if avail_col_count == 0 {
+            break;
+        }
+        let text = UnicodeString::from(text.as_str())
+            .clip_to_width(ch!(0), ch!(avail_col_count))
+            .to_string();
+        avail_col_count -= UnicodeString::str_display_width(&text);
+        it.push((text, Some(*style)));
+    }
+    it
+}
+
+pub mod constructor {
+    use super::*;
+
+    impl<S, AS> MarkdownViewerComponent<S, AS>
+    where
+        S: Debug + Default + Clone + Sync + Send + HasMarkdownViewerBuffers + 'static,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        /// The `on_markdown_viewer_event` handler is called when a link is activated, or
+        /// a checkbox is toggled.
+        pub fn new(
+            id: FlexBoxId,
+            on_markdown_viewer_event: OnMarkdownViewerEventFn<S, AS>,
+        ) -> Self {
+            Self {
+                data: MarkdownViewerComponentData {
+                    id,
+                    on_markdown_viewer_event_handler: Some(on_markdown_viewer_event),
+                },
+            }
+        }
+
+        pub fn new_boxed(
+            id: FlexBoxId,
+            on_markdown_viewer_event: OnMarkdownViewerEventFn<S, AS>,
+        ) -> BoxedSafeComponent<S, AS> {
+            Box::new(MarkdownViewerComponent::new(id, on_markdown_viewer_event))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+    use crate::{Key, KeyPress, RenderOp, SpecialKey};
+
+    #[test]
+    fn test_render_markdown_viewer() {
+        let mut buffer =
+            MarkdownViewerBuffer::new("# Help\n- see [docs](https://r3bl.com) now");
+        buffer.apply_event(InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(SpecialKey::Tab),
+        }));
+
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 8, row_count: 3),
+            ..Default::default()
+        };
+        let render_ops = render_markdown_viewer(&mut buffer, &current_box, false);
+
+        let texts: Vec<String> = render_ops
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text.clone()),
+                _ => None,
+            })
+            .collect();
+
+        // The 2nd line is clipped, and the selected link is painted on top of it.
+        assert_eq2!(texts, vec!["Help", "─┤", "see ", "do", "do"]);
+    }
+}
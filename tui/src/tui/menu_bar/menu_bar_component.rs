@@ -0,0 +1,142 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_core::{send_signal, throws_with_return, CommonResult};
+
+use crate::{BoxedSafeComponent,
+            Component,
+            EventPropagation,
+            FlexBox,
+            FlexBoxId,
+            GlobalData,
+            HasFocus,
+            InputEvent,
+            MenuBar,
+            MenuBarApplyResponse,
+            RenderPipeline,
+            SurfaceBounds,
+            TerminalWindowMainThreadSignal};
+
+/// This is a re-usable component that paints a [MenuBar] in the first row of its box, and
+/// its open dropdowns on top of the other components. When an item is chosen, its action
+/// is dispatched w/ [TerminalWindowMainThreadSignal::ApplyAction], so the app handles it
+/// like any other action.
+///
+/// The menu bar handles its accelerators (and the clicks on its dropdowns, which are
+/// outside of its box) even when another component has focus, so the app has to route
+/// input events to this component before the focused one, using
+/// [crate::ComponentRegistry::route_event_to_component]. All events are consumed while
+/// the menus are open.
+#[derive(Debug)]
+pub struct MenuBarComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub data: MenuBarComponentData<S, AS>,
+}
+
+#[derive(Debug)]
+pub struct MenuBarComponentData<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub menu_bar: MenuBar<AS>,
+    _phantom: std::marker::PhantomData<S>,
+}
+
+impl<S, AS> Component<S, AS> for MenuBarComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    /// Close the menus.
+    fn reset(&mut self) { self.data.menu_bar.close(); }
+
+    fn get_id(&self) -> FlexBoxId { self.data.id }
+
+    fn render(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        current_box: FlexBox,
+        _surface_bounds: SurfaceBounds, /* Ignore this. */
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<RenderPipeline> {
+        throws_with_return!({
+            self.data.menu_bar.render(
+                current_box.style_adjusted_origin_pos,
+                current_box.style_adjusted_bounds_size.col_count,
+                global_data.window_size,
+            )
+        });
+    }
+
+    fn handle_event(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        input_event: InputEvent,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        throws_with_return!({
+            match self.data.menu_bar.apply_event(input_event) {
+                MenuBarApplyResponse::Chosen(action) => {
+                    send_signal!(
+                        global_data.main_thread_channel_sender,
+                        TerminalWindowMainThreadSignal::ApplyAction(action)
+                    );
+                    EventPropagation::ConsumedRender
+                }
+                MenuBarApplyResponse::Updated | MenuBarApplyResponse::Closed => {
+                    EventPropagation::ConsumedRender
+                }
+                MenuBarApplyResponse::Consumed => EventPropagation::Consumed,
+                MenuBarApplyResponse::Noop => EventPropagation::Propagate,
+            }
+        });
+    }
+}
+
+pub mod constructor {
+    use super::*;
+
+    impl<S, AS> MenuBarComponent<S, AS>
+    where
+        S: Debug + Default + Clone + Sync + Send + 'static,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        pub fn new(id: FlexBoxId, menu_bar: MenuBar<AS>) -> Self {
+            Self {
+                data: MenuBarComponentData {
+                    id,
+                    menu_bar,
+                    _phantom: std::marker::PhantomData,
+                },
+            }
+        }
+
+        pub fn new_boxed(
+            id: FlexBoxId,
+            menu_bar: MenuBar<AS>,
+        ) -> BoxedSafeComponent<S, AS> {
+            Box::new(MenuBarComponent::new(id, menu_bar))
+        }
+    }
+}
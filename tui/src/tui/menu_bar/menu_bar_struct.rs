@@ -0,0 +1,895 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, position, size, ChUnit, Position, Size, TuiStyle, UnicodeString};
+
+use crate::{render_ops,
+            render_pipeline,
+            BorderGlyphCharacter,
+            BoxBorder,
+            Button,
+            FunctionKey,
+            InputEvent,
+            Key,
+            KeyPress,
+            ModifierKeysMask,
+            MouseInput,
+            MouseInputKind,
+            RenderOp,
+            RenderOps,
+            RenderPipeline,
+            SpecialKey,
+            ZOrder};
+
+/// This is painted at the end of an item that opens a submenu.
+pub const MENU_BAR_SUBMENU_INDICATOR: &str = "▸";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MenuItemKind<AS> {
+    /// The action is dispatched when the item is chosen.
+    Action(AS),
+    Submenu(Vec<MenuItem<AS>>),
+    Separator,
+}
+
+/// An item in a dropdown [Menu]. Its accelerator is painted after its label, and it
+/// chooses the item when the menus are closed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuItem<AS> {
+    pub label: String,
+    pub maybe_accelerator: Option<KeyPress>,
+    pub kind: MenuItemKind<AS>,
+    pub is_enabled: bool,
+}
+
+impl<AS> MenuItem<AS> {
+    fn new(label: impl Into<String>, kind: MenuItemKind<AS>) -> Self {
+        Self {
+            label: label.into(),
+            maybe_accelerator: None,
+            kind,
+            is_enabled: true,
+        }
+    }
+
+    pub fn action(label: impl Into<String>, action: AS) -> Self {
+        Self::new(label, MenuItemKind::Action(action))
+    }
+
+    pub fn submenu(label: impl Into<String>, items: Vec<MenuItem<AS>>) -> Self {
+        Self::new(label, MenuItemKind::Submenu(items))
+    }
+
+    pub fn separator() -> Self { Self::new("", MenuItemKind::Separator) }
+
+    pub fn with_accelerator(mut self, key_press: KeyPress) -> Self {
+        self.maybe_accelerator = Some(key_press);
+        self
+    }
+
+    /// A disabled item is dimmed, and it can't be highlighted or chosen.
+    pub fn disabled(mut self) -> Self {
+        self.is_enabled = false;
+        self
+    }
+
+    pub(crate) fn is_selectable(&self) -> bool {
+        self.is_enabled && !matches!(self.kind, MenuItemKind::Separator)
+    }
+
+    /// Returns the accelerator, eg: `Ctrl+S`, or [None] if there isn't one.
+    pub fn get_accelerator_text(&self) -> Option<String> {
+        self.maybe_accelerator
+            .map(|key_press| key_press.get_display_text())
+    }
+}
+
+/// A menu in a [MenuBar], eg: `File`, w/ the items of its dropdown.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Menu<AS> {
+    pub title: String,
+    pub items: Vec<MenuItem<AS>>,
+}
+
+impl<AS> Menu<AS> {
+    pub fn new(title: impl Into<String>, items: Vec<MenuItem<AS>>) -> Self {
+        Self {
+            title: title.into(),
+            items,
+        }
+    }
+}
+
+/// What happened when an [InputEvent] was applied to a [MenuBar] w/
+/// [MenuBar::apply_event].
+#[derive(Clone, Debug, PartialEq)]
+pub enum MenuBarApplyResponse<AS> {
+    /// An item was chosen (from a dropdown, or w/ its accelerator), and the menus were
+    /// closed.
+    Chosen(AS),
+    /// A menu was opened, or the highlighted item changed.
+    Updated,
+    Closed,
+    /// The event was handled, but nothing changed. All events are consumed while the
+    /// menus are open.
+    Consumed,
+    /// The event wasn't for the menu bar, so it should be passed on.
+    Noop,
+}
+
+/// The bounds of the titles & the open dropdowns from the last call to
+/// [MenuBar::render], which are needed to handle mouse clicks.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct MenuBarLayout {
+    bar_origin_pos: Position,
+    /// The start col & width of each title (relative to the bar).
+    title_col_ranges: Vec<(ChUnit, ChUnit)>,
+    /// The bounds of each open dropdown (w/ its border), from the dropdown of the menu
+    /// to the one of the innermost submenu.
+    dropdowns: Vec<(Position, Size)>,
+}
+
+/// A row of menus (eg: `File`, `Edit`, …), each w/ a dropdown of items, which can have
+/// nested submenus (see [Menu] & [MenuItem]).
+///
+/// Key bindings:
+/// - <kbd>F10</kbd> opens the first menu, and <kbd>Alt</kbd> w/ the first letter of the
+///   title of a menu opens it.
+/// - The accelerators of the items choose them, while the menus are closed.
+/// - <kbd>Up</kbd> & <kbd>Down</kbd> move the highlight, skipping separators & disabled
+///   items.
+/// - <kbd>Right</kbd> opens the highlighted submenu, or the next menu, and <kbd>Left</kbd>
+///   closes the innermost submenu, or opens the previous menu.
+/// - <kbd>Enter</kbd> chooses the highlighted item.
+/// - <kbd>Esc</kbd> closes the innermost submenu, or all the menus.
+///
+/// Clicking on a title opens (or closes) its menu, clicking on an item chooses it, and
+/// clicking anywhere else closes the menus.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuBar<AS> {
+    pub menus: Vec<Menu<AS>>,
+    pub maybe_style: Option<TuiStyle>,
+    /// Empty when the menus are closed. Otherwise, the index of the open menu, followed
+    /// by the index of the highlighted item in each of the open dropdowns.
+    open_path: Vec<usize>,
+    maybe_last_layout: Option<MenuBarLayout>,
+}
+
+/// Returns the index of the first item (starting at `start_index`, in the direction of
+/// `step`, and wrapping around) that can be highlighted.
+pub(crate) fn find_selectable_item<AS>(
+    items: &[MenuItem<AS>],
+    start_index: isize,
+    step: isize,
+) -> Option<usize> {
+    let item_count = items.len() as isize;
+    (0..item_count)
+        .map(|offset| (start_index + offset * step).rem_euclid(item_count) as usize)
+        .find(|index| items[*index].is_selectable())
+}
+
+fn find_accelerator<AS: Clone>(
+    items: &[MenuItem<AS>],
+    key_press: KeyPress,
+) -> Option<AS> {
+    items
+        .iter()
+        .filter(|it| it.is_enabled)
+        .find_map(|item| match &item.kind {
+            MenuItemKind::Action(action) if item.maybe_accelerator == Some(key_press) => {
+                Some(action.clone())
+            }
+            MenuItemKind::Submenu(children) => find_accelerator(children, key_press),
+            _ => None,
+        })
+}
+
+impl<AS: Clone> MenuBar<AS> {
+    pub fn new(menus: Vec<Menu<AS>>) -> Self {
+        Self {
+            menus,
+            maybe_style: None,
+            open_path: vec![],
+            maybe_last_layout: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool { !self.open_path.is_empty() }
+
+    /// See [Self::open_path].
+    pub fn get_open_path(&self) -> &[usize] { &self.open_path }
+
+    pub fn close(&mut self) { self.open_path.clear(); }
+
+    /// Open the menu at `menu_index` (closing the one that is open), and highlight its
+    /// first item.
+    pub fn open_menu(&mut self, menu_index: usize) -> bool {
+        let Some(menu) = self.menus.get(menu_index) else {
+            return false;
+        };
+        let maybe_item_index = find_selectable_item(&menu.items, 0, 1);
+        self.open_path = vec![menu_index];
+        self.open_path.extend(maybe_item_index);
+        true
+    }
+
+    fn open_adjacent_menu(&mut self, step: isize) {
+        let Some(menu_index) = self.open_path.first() else {
+            return;
+        };
+        let menu_count = self.menus.len() as isize;
+        let menu_index = (*menu_index as isize + step).rem_euclid(menu_count) as usize;
+        self.open_menu(menu_index);
+    }
+
+    /// Returns the items of the open dropdown at `depth` (0 is the dropdown of the open
+    /// menu, 1 is its open submenu, and so on).
+    fn get_items_at(&self, depth: usize) -> Option<&[MenuItem<AS>]> {
+        let menu = self.menus.get(*self.open_path.first()?)?;
+        let mut items = menu.items.as_slice();
+        for index in self.open_path.iter().skip(1).take(depth) {
+            match &items.get(*index)?.kind {
+                MenuItemKind::Submenu(children) => items = children,
+                _ => return None,
+            }
+        }
+        Some(items)
+    }
+
+    /// Returns the highlighted item in the innermost open dropdown.
+    pub fn get_highlighted_item(&self) -> Option<&MenuItem<AS>> {
+        let depth = self.open_path.len().checked_sub(2)?;
+        self.get_items_at(depth)?.get(*self.open_path.last()?)
+    }
+
+    fn move_highlight(&mut self, step: isize) {
+        let Some(depth) = self.open_path.len().checked_sub(2) else {
+            return;
+        };
+        let Some(&item_index) = self.open_path.last() else {
+            return;
+        };
+        let maybe_item_index = self.get_items_at(depth).and_then(|items| {
+            find_selectable_item(items, item_index as isize + step, step)
+        });
+        if let (Some(new_item_index), Some(last)) =
+            (maybe_item_index, self.open_path.last_mut())
+        {
+            *last = new_item_index;
+        }
+    }
+
+    fn open_submenu(&mut self) -> bool {
+        let maybe_item_index = match self.get_highlighted_item() {
+            Some(MenuItem {
+                kind: MenuItemKind::Submenu(children),
+                is_enabled: true,
+                ..
+            }) => find_selectable_item(children, 0, 1),
+            _ => None,
+        };
+        match maybe_item_index {
+            Some(item_index) => {
+                self.open_path.push(item_index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn close_submenu(&mut self) -> bool {
+        if self.open_path.len() <= 2 {
+            return false;
+        }
+        self.open_path.pop();
+        true
+    }
+
+    /// Choose the highlighted item, which dispatches its action, or opens its submenu.
+    fn choose_highlighted_item(&mut self) -> MenuBarApplyResponse<AS> {
+        let maybe_action = match self.get_highlighted_item() {
+            Some(MenuItem {
+                kind: MenuItemKind::Action(action),
+                is_enabled: true,
+                ..
+            }) => Some(action.clone()),
+            _ => None,
+        };
+        if let Some(action) = maybe_action {
+            self.close();
+            return MenuBarApplyResponse::Chosen(action);
+        }
+        match self.open_submenu() {
+            true => MenuBarApplyResponse::Updated,
+            false => MenuBarApplyResponse::Consumed,
+        }
+    }
+
+    pub fn apply_event(&mut self, input_event: InputEvent) -> MenuBarApplyResponse<AS> {
+        let key_press = match input_event {
+            InputEvent::Keyboard(key_press) => key_press,
+            InputEvent::Mouse(MouseInput {
+                pos,
+                kind: MouseInputKind::MouseDown(Button::Left),
+                ..
+            }) => return self.apply_click(pos),
+            // Other events are consumed while the menus are open, so that they don't
+            // reach the components under the dropdowns.
+            _ => {
+                return match self.is_open() {
+                    true => MenuBarApplyResponse::Consumed,
+                    false => MenuBarApplyResponse::Noop,
+                };
+            }
+        };
+
+        if !self.is_open() {
+            return self.apply_key_press_when_closed(key_press);
+        }
+
+        match key_press {
+            KeyPress::Plain {
+                key: Key::SpecialKey(special_key),
+            } => match special_key {
+                SpecialKey::Esc => {
+                    if self.close_submenu() {
+                        return MenuBarApplyResponse::Updated;
+                    }
+                    self.close();
+                    MenuBarApplyResponse::Closed
+                }
+                SpecialKey::Left => {
+                    if !self.close_submenu() {
+                        self.open_adjacent_menu(-1);
+                    }
+                    MenuBarApplyResponse::Updated
+                }
+                SpecialKey::Right => {
+                    if !self.open_submenu() {
+                        self.open_adjacent_menu(1);
+                    }
+                    MenuBarApplyResponse::Updated
+                }
+                SpecialKey::Up => {
+                    self.move_highlight(-1);
+                    MenuBarApplyResponse::Updated
+                }
+                SpecialKey::Down => {
+                    self.move_highlight(1);
+                    MenuBarApplyResponse::Updated
+                }
+                SpecialKey::Enter => self.choose_highlighted_item(),
+                _ => MenuBarApplyResponse::Consumed,
+            },
+            KeyPress::Plain {
+                key: Key::FunctionKey(FunctionKey::F10),
+            } => {
+                self.close();
+                MenuBarApplyResponse::Closed
+            }
+            _ => MenuBarApplyResponse::Consumed,
+        }
+    }
+
+    fn apply_key_press_when_closed(
+        &mut self,
+        key_press: KeyPress,
+    ) -> MenuBarApplyResponse<AS> {
+        let maybe_menu_index = match key_press {
+            KeyPress::Plain {
+                key: Key::FunctionKey(FunctionKey::F10),
+            } => Some(0),
+            KeyPress::WithModifiers {
+                key: Key::Character(character),
+                mask,
+            } if mask == ModifierKeysMask::new().with_alt() => {
+                self.menus.iter().position(|menu| {
+                    menu.title.chars().next().map(|it| it.to_ascii_lowercase())
+                        == Some(character.to_ascii_lowercase())
+                })
+            }
+            _ => None,
+        };
+        if let Some(menu_index) = maybe_menu_index {
+            if self.open_menu(menu_index) {
+                return MenuBarApplyResponse::Updated;
+            }
+        }
+
+        match self
+            .menus
+            .iter()
+            .find_map(|menu| find_accelerator(&menu.items, key_press))
+        {
+            Some(action) => MenuBarApplyResponse::Chosen(action),
+            None => MenuBarApplyResponse::Noop,
+        }
+    }
+
+    fn apply_click(&mut self, pos: Position) -> MenuBarApplyResponse<AS> {
+        let Some(layout) = self.maybe_last_layout.clone() else {
+            return MenuBarApplyResponse::Noop;
+        };
+
+        // The submenus are on top of their parents, so they are checked first.
+        if self.is_open() {
+            for (depth, (origin_pos, bounds_size)) in
+                layout.dropdowns.iter().enumerate().rev()
+            {
+                if !is_inside(pos, *origin_pos, *bounds_size) {
+                    continue;
+                }
+                // Skip the top border.
+                let Some(item_index) =
+                    ch!(@to_usize pos.row_index - origin_pos.row_index).checked_sub(1)
+                else {
+                    return MenuBarApplyResponse::Consumed;
+                };
+                let is_selectable = self
+                    .get_items_at(depth)
+                    .and_then(|items| items.get(item_index))
+                    .is_some_and(|it| it.is_selectable());
+                if !is_selectable {
+                    return MenuBarApplyResponse::Consumed;
+                }
+                self.open_path.truncate(depth + 1);
+                self.open_path.push(item_index);
+                return self.choose_highlighted_item();
+            }
+        }
+
+        let bar_origin_pos = layout.bar_origin_pos;
+        if pos.row_index == bar_origin_pos.row_index {
+            let maybe_menu_index =
+                layout.title_col_ranges.iter().position(|(start, width)| {
+                    pos.col_index >= bar_origin_pos.col_index + *start
+                        && pos.col_index < bar_origin_pos.col_index + *start + *width
+                });
+            if let Some(menu_index) = maybe_menu_index {
+                if self.open_path.first() == Some(&menu_index) {
+                    self.close();
+                    return MenuBarApplyResponse::Closed;
+                }
+                self.open_menu(menu_index);
+                return MenuBarApplyResponse::Updated;
+            }
+        }
+
+        match self.is_open() {
+            true => {
+                self.close();
+                MenuBarApplyResponse::Closed
+            }
+            false => MenuBarApplyResponse::Noop,
+        }
+    }
+
+    fn calc_layout(&self, bar_origin_pos: Position, window_size: Size) -> MenuBarLayout {
+        let mut title_col_ranges = vec![];
+        let mut col_index = ch!(0);
+        for menu in &self.menus {
+            let width = ch!(UnicodeString::str_display_width(&get_title_text(menu)));
+            title_col_ranges.push((col_index, width));
+            col_index += width;
+        }
+
+        let mut dropdowns: Vec<(Position, Size)> = vec![];
+        for depth in 0..self.open_path.len().saturating_sub(1) {
+            let Some(items) = self.get_items_at(depth) else {
+                break;
+            };
+            let bounds_size = size!(
+                col_count: calc_dropdown_inner_width(items) + 2,
+                row_count: items.len() + 2
+            );
+            // A dropdown is below its title, and a submenu is next to the item that
+            // opened it.
+            let origin_pos = match dropdowns.last() {
+                None => {
+                    let (start, _) = title_col_ranges[self.open_path[0]];
+                    bar_origin_pos + position!(col_index: start, row_index: 1)
+                }
+                Some((parent_origin_pos, parent_bounds_size)) => {
+                    *parent_origin_pos
+                        + position!(
+                            col_index: parent_bounds_size.col_count,
+                            row_index: self.open_path[depth] + 1
+                        )
+                }
+            };
+            // Keep the dropdown inside the window.
+            let origin_pos = position!(
+                col_index: origin_pos
+                    .col_index
+                    .min(window_size.col_count - bounds_size.col_count),
+                row_index: origin_pos
+                    .row_index
+                    .min(window_size.row_count - bounds_size.row_count)
+            );
+            dropdowns.push((origin_pos, bounds_size));
+        }
+
+        MenuBarLayout {
+            bar_origin_pos,
+            title_col_ranges,
+            dropdowns,
+        }
+    }
+
+    /// Returns the [RenderPipeline] to paint the titles in the row at `bar_origin_pos`
+    /// (w/ the given `col_count`), and the open dropdowns on top of everything else (in
+    /// [ZOrder::Glass]), so that they aren't clipped to the box of the menu bar.
+    pub fn render(
+        &mut self,
+        bar_origin_pos: Position,
+        col_count: ChUnit,
+        window_size: Size,
+    ) -> RenderPipeline {
+        let layout = self.calc_layout(bar_origin_pos, window_size);
+        let mut render_pipeline = render_pipeline!();
+
+        // Paint the titles, w/ the one of the open menu highlighted.
+        let mut render_ops = render_ops!();
+        paint(
+            &mut render_ops,
+            bar_origin_pos,
+            " ".repeat(ch!(@to_usize col_count)),
+            self.maybe_style,
+        );
+        for (menu_index, (menu, (start, _))) in
+            self.menus.iter().zip(&layout.title_col_ranges).enumerate()
+        {
+            let maybe_style = match self.open_path.first() == Some(&menu_index) {
+                true => Some(get_menu_highlight_style()),
+                false => self.maybe_style,
+            };
+            paint(
+                &mut render_ops,
+                bar_origin_pos + position!(col_index: *start, row_index: 0),
+                get_title_text(menu),
+                maybe_style,
+            );
+        }
+        render_pipeline.push(ZOrder::Normal, render_ops);
+
+        let mut render_ops = render_ops!();
+        for (depth, (origin_pos, bounds_size)) in layout.dropdowns.iter().enumerate() {
+            let Some(items) = self.get_items_at(depth) else {
+                break;
+            };
+            render_dropdown(
+                &mut render_ops,
+                (*origin_pos, *bounds_size),
+                items,
+                self.open_path[depth + 1],
+                self.maybe_style,
+            );
+        }
+        render_pipeline.push(ZOrder::Glass, render_ops);
+
+        self.maybe_last_layout = Some(layout);
+        render_pipeline
+    }
+}
+
+/// Paint the `items` of a dropdown (w/ its border) in the given bounds, w/ the item at
+/// `highlighted_index` highlighted. This is also used by [crate::ContextMenu].
+pub(crate) fn render_dropdown<AS>(
+    render_ops: &mut RenderOps,
+    (origin_pos, bounds_size): (Position, Size),
+    items: &[MenuItem<AS>],
+    highlighted_index: usize,
+    maybe_style: Option<TuiStyle>,
+) {
+    render_ops.extend(
+        BoxBorder {
+            maybe_title: None,
+            maybe_style,
+        }
+        .render(origin_pos, bounds_size)
+        .list,
+    );
+
+    let inner_width = ch!(@to_usize bounds_size.col_count - 2);
+    for (item_index, item) in items.iter().enumerate() {
+        let row_pos = origin_pos + position!(col_index: 0, row_index: item_index + 1);
+        if matches!(item.kind, MenuItemKind::Separator) {
+            let line = format!(
+                "{}{}{}",
+                BorderGlyphCharacter::LineUpDownRight.as_ref(),
+                BorderGlyphCharacter::Horizontal
+                    .as_ref()
+                    .repeat(inner_width),
+                BorderGlyphCharacter::LineUpDownLeft.as_ref(),
+            );
+            paint(render_ops, row_pos, line, maybe_style);
+            continue;
+        }
+        let item_style = match (item_index == highlighted_index, item.is_enabled) {
+            (true, _) => Some(get_menu_highlight_style()),
+            (false, false) => Some(TuiStyle {
+                dim: true,
+                ..maybe_style.unwrap_or_default()
+            }),
+            (false, true) => maybe_style,
+        };
+        paint(
+            render_ops,
+            row_pos + position!(col_index: 1, row_index: 0),
+            get_item_text(item, inner_width),
+            item_style,
+        );
+    }
+}
+
+fn get_menu_highlight_style() -> TuiStyle {
+    TuiStyle {
+        reverse: true,
+        ..Default::default()
+    }
+}
+
+fn get_title_text<AS>(menu: &Menu<AS>) -> String { format!(" {} ", menu.title) }
+
+/// The text of an item is its label, followed by its accelerator (or the submenu
+/// indicator) at the end, eg: ` Save      Ctrl+S `.
+fn get_item_text<AS>(item: &MenuItem<AS>, inner_width: usize) -> String {
+    let left_text = format!(" {}", item.label);
+    let right_text = match item.kind {
+        MenuItemKind::Submenu(_) => MENU_BAR_SUBMENU_INDICATOR.to_string(),
+        _ => item.get_accelerator_text().unwrap_or_default(),
+    };
+    let gap_width = inner_width.saturating_sub(
+        UnicodeString::str_display_width(&left_text)
+            + UnicodeString::str_display_width(&right_text)
+            + 1,
+    );
+    format!("{left_text}{}{right_text} ", " ".repeat(gap_width))
+}
+
+/// The widest item has 1 col of padding on each side, and 2 cols between its label &
+/// its accelerator.
+pub(crate) fn calc_dropdown_inner_width<AS>(items: &[MenuItem<AS>]) -> usize {
+    items
+        .iter()
+        .map(|item| {
+            let right_text = match item.kind {
+                MenuItemKind::Submenu(_) => MENU_BAR_SUBMENU_INDICATOR.to_string(),
+                _ => item.get_accelerator_text().unwrap_or_default(),
+            };
+            let right_width = match UnicodeString::str_display_width(&right_text) {
+                0 => 0,
+                it => it + 2,
+            };
+            UnicodeString::str_display_width(&item.label) + right_width + 2
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+fn paint(
+    render_ops: &mut RenderOps,
+    pos: Position,
+    text: String,
+    maybe_style: Option<TuiStyle>,
+) {
+    render_ops.push(RenderOp::MoveCursorPositionAbs(pos));
+    render_ops.push(RenderOp::ApplyColors(maybe_style));
+    render_ops.push(RenderOp::PaintTextWithAttributes(text, maybe_style));
+    render_ops.push(RenderOp::ResetColor);
+}
+
+pub(crate) fn is_inside(pos: Position, origin_pos: Position, bounds_size: Size) -> bool {
+    pos.col_index >= origin_pos.col_index
+        && pos.col_index < origin_pos.col_index + bounds_size.col_count
+        && pos.row_index >= origin_pos.row_index
+        && pos.row_index < origin_pos.row_index + bounds_size.row_count
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum TestAction {
+        Open,
+        Save,
+        Quit,
+        Copy,
+        Markdown,
+        PlainText,
+    }
+
+    fn ctrl(character: char) -> KeyPress {
+        KeyPress::WithModifiers {
+            key: Key::Character(character),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        }
+    }
+
+    fn key(special_key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        })
+    }
+
+    fn click(col_index: u16, row_index: u16) -> InputEvent {
+        InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind: MouseInputKind::MouseDown(Button::Left),
+            maybe_modifier_keys: None,
+        })
+    }
+
+    fn make_menu_bar() -> MenuBar<TestAction> {
+        MenuBar::new(vec![
+            Menu::new(
+                "File",
+                vec![
+                    MenuItem::action("Open", TestAction::Open)
+                        .with_accelerator(ctrl('o')),
+                    MenuItem::action("Save", TestAction::Save)
+                        .with_accelerator(ctrl('s'))
+                        .disabled(),
+                    MenuItem::separator(),
+                    MenuItem::submenu(
+                        "Language",
+                        vec![
+                            MenuItem::action("Markdown", TestAction::Markdown),
+                            MenuItem::action("Plain text", TestAction::PlainText),
+                        ],
+                    ),
+                    MenuItem::action("Quit", TestAction::Quit)
+                        .with_accelerator(ctrl('q')),
+                ],
+            ),
+            Menu::new("Edit", vec![MenuItem::action("Copy", TestAction::Copy)]),
+        ])
+    }
+
+    #[test]
+    fn test_keyboard_navigation() {
+        let mut menu_bar = make_menu_bar();
+
+        // The accelerators work while the menus are closed.
+        assert_eq2!(
+            menu_bar.apply_event(InputEvent::Keyboard(ctrl('q'))),
+            MenuBarApplyResponse::Chosen(TestAction::Quit)
+        );
+        // Disabled items can't be chosen.
+        assert_eq2!(
+            menu_bar.apply_event(InputEvent::Keyboard(ctrl('s'))),
+            MenuBarApplyResponse::Noop
+        );
+
+        // Open the "File" menu w/ its mnemonic.
+        assert_eq2!(
+            menu_bar.apply_event(InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('f'),
+                mask: ModifierKeysMask::new().with_alt(),
+            })),
+            MenuBarApplyResponse::Updated
+        );
+        assert_eq2!(menu_bar.get_open_path(), &[0, 0]);
+
+        // The disabled item & the separator are skipped.
+        menu_bar.apply_event(key(SpecialKey::Down));
+        assert_eq2!(menu_bar.get_open_path(), &[0, 3]);
+
+        // Open the submenu, and choose its 2nd item.
+        menu_bar.apply_event(key(SpecialKey::Right));
+        assert_eq2!(menu_bar.get_open_path(), &[0, 3, 0]);
+        menu_bar.apply_event(key(SpecialKey::Down));
+        assert_eq2!(
+            menu_bar.apply_event(key(SpecialKey::Enter)),
+            MenuBarApplyResponse::Chosen(TestAction::PlainText)
+        );
+        assert!(!menu_bar.is_open());
+
+        // Esc closes the submenu, and then the menu.
+        menu_bar.apply_event(InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::FunctionKey(FunctionKey::F10),
+        }));
+        menu_bar.apply_event(key(SpecialKey::Up));
+        menu_bar.apply_event(key(SpecialKey::Up));
+        menu_bar.apply_event(key(SpecialKey::Right));
+        assert_eq2!(menu_bar.get_open_path(), &[0, 3, 0]);
+        menu_bar.apply_event(key(SpecialKey::Esc));
+        assert_eq2!(menu_bar.get_open_path(), &[0, 3]);
+
+        // Left & Right move between the menus (and wrap around).
+        menu_bar.apply_event(key(SpecialKey::Left));
+        assert_eq2!(menu_bar.get_open_path(), &[1, 0]);
+        assert_eq2!(
+            menu_bar.apply_event(key(SpecialKey::Esc)),
+            MenuBarApplyResponse::Closed
+        );
+    }
+
+    #[test]
+    fn test_render_and_mouse() {
+        let mut menu_bar = make_menu_bar();
+        let window_size = size!(col_count: 80, row_count: 24);
+        menu_bar.render(position!(col_index: 0, row_index: 0), ch!(80), window_size);
+
+        // Click on the "Edit" title (` File ` takes up the first 6 cols).
+        assert_eq2!(
+            menu_bar.apply_event(click(7, 0)),
+            MenuBarApplyResponse::Updated
+        );
+        assert_eq2!(menu_bar.get_open_path(), &[1, 0]);
+        assert_eq2!(
+            menu_bar.apply_event(click(7, 0)),
+            MenuBarApplyResponse::Closed
+        );
+
+        menu_bar.open_menu(0);
+        menu_bar.apply_event(key(SpecialKey::Down));
+        menu_bar.apply_event(key(SpecialKey::Right));
+        let render_pipeline =
+            menu_bar.render(position!(col_index: 0, row_index: 0), ch!(80), window_size);
+        assert!(render_pipeline.get(&ZOrder::Glass).is_some());
+
+        // The dropdown of "File" is below its title, and the submenu is next to the
+        // "Language" item.
+        let layout = menu_bar.maybe_last_layout.clone().unwrap_or_default();
+        assert_eq2!(
+            layout.dropdowns,
+            vec![
+                (
+                    position!(col_index: 0, row_index: 1),
+                    size!(col_count: 16, row_count: 7)
+                ),
+                (
+                    position!(col_index: 16, row_index: 5),
+                    size!(col_count: 14, row_count: 4)
+                ),
+            ]
+        );
+
+        // Clicking on the separator does nothing, and clicking on an item chooses it.
+        assert_eq2!(
+            menu_bar.apply_event(click(3, 4)),
+            MenuBarApplyResponse::Consumed
+        );
+        assert_eq2!(
+            menu_bar.apply_event(click(18, 6)),
+            MenuBarApplyResponse::Chosen(TestAction::Markdown)
+        );
+
+        // Clicking outside of the menus closes them.
+        menu_bar.open_menu(0);
+        assert_eq2!(
+            menu_bar.apply_event(click(40, 10)),
+            MenuBarApplyResponse::Closed
+        );
+        assert_eq2!(
+            menu_bar.apply_event(click(40, 10)),
+            MenuBarApplyResponse::Noop
+        );
+    }
+
+    #[test]
+    fn test_get_item_text() {
+        let item = MenuItem::action("Open", ()).with_accelerator(ctrl('o'));
+        assert_eq2!(item.get_accelerator_text(), Some("Ctrl+O".to_string()));
+        assert_eq2!(calc_dropdown_inner_width(&[item.clone()]), 14);
+        assert_eq2!(get_item_text(&item, 16), " Open    Ctrl+O ");
+    }
+}
@@ -15,9 +15,15 @@
  *   limitations under the License.
  */
 
+use chrono::NaiveDate;
+use r3bl_core::TuiColor;
 use tokio::sync::mpsc::Sender;
 
-use crate::{DialogBuffer, FlexBoxId, TerminalWindowMainThreadSignal};
+use crate::{DialogBuffer,
+            DialogConfirmChoice,
+            DialogFormValues,
+            FlexBoxId,
+            TerminalWindowMainThreadSignal};
 
 /// This marker trait is meant to be implemented by whatever state struct is being used to
 /// store the dialog buffer for this re-usable editor component.
@@ -33,6 +39,14 @@ pub trait HasDialogBuffers {
 pub enum DialogChoice {
     Yes(String),
     No,
+    /// The form was submitted, in [crate::DialogEngineMode::ModalForm].
+    Form(DialogFormValues),
+    /// A button was pressed, in [crate::DialogEngineMode::ModalConfirm].
+    Confirm(DialogConfirmChoice),
+    /// A date was picked, in [crate::DialogEngineMode::ModalDatePicker].
+    Date(NaiveDate),
+    /// A color was picked, in [crate::DialogEngineMode::ModalColorPicker].
+    Color(TuiColor),
 }
 
 pub type OnDialogPressFn<S, AS> = fn(
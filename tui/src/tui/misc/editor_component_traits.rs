@@ -15,7 +15,7 @@
  *   limitations under the License.
  */
 
-use crate::{EditorBuffer, FlexBoxId};
+use crate::{EditorBuffer, EditorBufferTabs, FlexBoxId};
 
 /// This marker trait is meant to be implemented by whatever state struct is being used to
 /// store the editor buffer for this re-usable editor component.
@@ -28,3 +28,14 @@ pub trait HasEditorBuffers {
     fn insert_editor_buffer(&mut self, id: FlexBoxId, buffer: EditorBuffer);
     fn contains_editor_buffer(&self, id: FlexBoxId) -> bool;
 }
+
+/// This trait is meant to be implemented by the state struct of apps that use an
+/// [crate::EditorTabBarComponent], to give it access to the [EditorBufferTabs] that are
+/// open in the editor component w/ the given [FlexBoxId].
+pub trait HasEditorBufferTabs {
+    fn get_editor_buffer_tabs(&self, editor_id: FlexBoxId) -> Option<&EditorBufferTabs>;
+    fn get_mut_editor_buffer_tabs(
+        &mut self,
+        editor_id: FlexBoxId,
+    ) -> Option<&mut EditorBufferTabs>;
+}
@@ -0,0 +1,43 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use tokio::sync::mpsc::Sender;
+
+use crate::{FlexBoxId,
+            MarkdownViewerApplyResponse,
+            MarkdownViewerBuffer,
+            TerminalWindowMainThreadSignal};
+
+/// This trait is meant to be implemented by the state struct of apps that use a
+/// [crate::MarkdownViewerComponent], to give it access to the [MarkdownViewerBuffer] w/
+/// the markdown that it displays.
+pub trait HasMarkdownViewerBuffers {
+    fn get_mut_markdown_viewer_buffer(
+        &mut self,
+        id: FlexBoxId,
+    ) -> Option<&mut MarkdownViewerBuffer>;
+}
+
+/// This is called when a link is activated, or a checkbox is toggled, in a markdown
+/// viewer. When a checkbox is toggled, the source in the [MarkdownViewerBuffer] (which is
+/// in the state) has already been changed, so the handler can save it.
+pub type OnMarkdownViewerEventFn<S, AS> = fn(
+    FlexBoxId,
+    MarkdownViewerApplyResponse,
+    &mut S,
+    main_thread_channel_sender: &mut Sender<TerminalWindowMainThreadSignal<AS>>,
+);
@@ -23,6 +23,9 @@ pub mod dialog_component_traits;
 pub mod editor_component_traits;
 pub mod format_option;
 pub mod list_of;
+pub mod markdown_viewer_component_traits;
+pub mod table_view_component_traits;
+pub mod tree_view_component_traits;
 
 // Re-export.
 pub use aliases::*;
@@ -32,3 +35,6 @@ pub use dialog_component_traits::*;
 pub use editor_component_traits::*;
 pub use format_option::*;
 pub use list_of::*;
+pub use markdown_viewer_component_traits::*;
+pub use table_view_component_traits::*;
+pub use tree_view_component_traits::*;
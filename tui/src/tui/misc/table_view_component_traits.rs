@@ -0,0 +1,44 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use tokio::sync::mpsc::Sender;
+
+use crate::{FlexBoxId,
+            TableViewApplyResponse,
+            TableViewBuffer,
+            TerminalWindowMainThreadSignal};
+
+/// This trait is meant to be implemented by the state struct of apps that use a
+/// [crate::TableViewComponent], to give it access to the [TableViewBuffer] w/ the rows of
+/// type `R` that it displays. An app that has tables w/ different types of rows can
+/// implement it once for each type.
+pub trait HasTableViewBuffers<R> {
+    fn get_mut_table_view_buffer(
+        &mut self,
+        id: FlexBoxId,
+    ) -> Option<&mut TableViewBuffer<R>>;
+}
+
+/// This is called when the selection of a table view changes, or a row is activated, or
+/// the rows are sorted. The [TableViewBuffer] is in the state, so the handler can get the
+/// selected row from it.
+pub type OnTableViewEventFn<S, AS> = fn(
+    FlexBoxId,
+    TableViewApplyResponse,
+    &mut S,
+    main_thread_channel_sender: &mut Sender<TerminalWindowMainThreadSignal<AS>>,
+);
@@ -0,0 +1,43 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use tokio::sync::mpsc::Sender;
+
+use crate::{FlexBoxId,
+            TerminalWindowMainThreadSignal,
+            TreeViewApplyResponse,
+            TreeViewBuffer};
+
+/// This trait is meant to be implemented by the state struct of apps that use a
+/// [crate::TreeViewComponent], to give it access to the [TreeViewBuffer] w/ the nodes of
+/// type `T` that it displays.
+pub trait HasTreeViewBuffers<T> {
+    fn get_mut_tree_view_buffer(
+        &mut self,
+        id: FlexBoxId,
+    ) -> Option<&mut TreeViewBuffer<T>>;
+}
+
+/// This is called when the selection of a tree view changes, or a node is activated,
+/// expanded, or collapsed. The [TreeViewBuffer] is in the state, so the handler can get
+/// the selected node from it.
+pub type OnTreeViewEventFn<S, AS> = fn(
+    FlexBoxId,
+    TreeViewApplyResponse,
+    &mut S,
+    main_thread_channel_sender: &mut Sender<TerminalWindowMainThreadSignal<AS>>,
+);
@@ -50,29 +50,51 @@ pub const DEBUG_TUI_SHOW_TERMINAL_BACKEND: bool = false;
 
 // Attach sources.
 pub mod animator;
+pub mod chart;
+pub mod context_menu;
 pub mod dialog;
 pub mod editor;
 pub mod global_constants;
 pub mod layout;
+pub mod markdown_viewer;
 pub mod md_parser;
+pub mod menu_bar;
 pub mod misc;
+pub mod progress;
 pub mod rsx;
+pub mod shortcuts;
+pub mod splash;
+pub mod status_bar;
 pub mod syntax_highlighting;
+pub mod table_view;
 pub mod terminal_lib_backends;
 pub mod terminal_window;
+pub mod toast;
+pub mod tree_view;
 
 // Re-export.
 pub use animator::*;
+pub use chart::*;
+pub use context_menu::*;
 pub use dialog::*;
 pub use editor::*;
 pub use global_constants::*;
 pub use layout::*;
+pub use markdown_viewer::*;
 pub use md_parser::*;
+pub use menu_bar::*;
 pub use misc::*;
+pub use progress::*;
 pub use rsx::*;
+pub use shortcuts::*;
+pub use splash::*;
+pub use status_bar::*;
 pub use syntax_highlighting::*;
+pub use table_view::*;
 pub use terminal_lib_backends::*;
 pub use terminal_window::*;
+pub use toast::*;
+pub use tree_view::*;
 
 // Tests.
 mod test_make_style_macro;
@@ -0,0 +1,239 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::Duration;
+
+use r3bl_core::{position, Position, TuiStyle, UnicodeString};
+
+use crate::{render_ops, RenderOp, RenderOps};
+
+pub const PROGRESS_BAR_FILLED_CHAR: &str = "█";
+pub const PROGRESS_BAR_EMPTY_CHAR: &str = "░";
+
+/// The cell at the end of the filled part of a determinate bar is partially filled (in
+/// eighths of a cell), so that the bar moves smoothly, even when it is narrow.
+const PROGRESS_BAR_PARTIAL_CHARS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// The width of the block that bounces back & forth in an indeterminate bar.
+pub const PROGRESS_BAR_INDETERMINATE_COL_COUNT: usize = 4;
+
+/// The block of an indeterminate bar moves by 1 col each time that this elapses, see
+/// [ProgressBar::get_spans].
+pub const PROGRESS_BAR_TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProgressValue {
+    /// `done` out of `total` units of work (eg: bytes, or files) are done.
+    Determinate { done: u64, total: u64 },
+    /// It isn't known how much work is left.
+    Indeterminate,
+}
+
+impl ProgressValue {
+    /// Returns a value between 0 & 1, or [None] if this is indeterminate. A `total` of 0
+    /// is complete.
+    pub fn get_fraction(&self) -> Option<f64> {
+        match *self {
+            ProgressValue::Determinate { total: 0, .. } => Some(1.0),
+            ProgressValue::Determinate { done, total } => {
+                Some(done.min(total) as f64 / total as f64)
+            }
+            ProgressValue::Indeterminate => None,
+        }
+    }
+
+    /// Returns a value between 0 & 100 (rounded down), or [None] if this is
+    /// indeterminate.
+    pub fn get_percent(&self) -> Option<u8> {
+        self.get_fraction().map(|it| (it * 100.0) as u8)
+    }
+}
+
+/// A widget that paints a [ProgressValue] in a single row, eg: `██████▌░░░░░` for a
+/// determinate one, or a block that bounces back & forth for an indeterminate one. It
+/// is used by [crate::ProgressPanel], and it can be painted by any component.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgressBar {
+    pub value: ProgressValue,
+    pub maybe_filled_style: Option<TuiStyle>,
+    pub maybe_empty_style: Option<TuiStyle>,
+}
+
+impl ProgressBar {
+    pub fn new(value: ProgressValue) -> Self {
+        Self {
+            value,
+            maybe_filled_style: None,
+            maybe_empty_style: Some(TuiStyle {
+                dim: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn determinate(done: u64, total: u64) -> Self {
+        Self::new(ProgressValue::Determinate { done, total })
+    }
+
+    pub fn indeterminate() -> Self { Self::new(ProgressValue::Indeterminate) }
+
+    pub fn with_styles(
+        mut self,
+        maybe_filled_style: Option<TuiStyle>,
+        maybe_empty_style: Option<TuiStyle>,
+    ) -> Self {
+        self.maybe_filled_style = maybe_filled_style;
+        self.maybe_empty_style = maybe_empty_style;
+        self
+    }
+
+    /// Returns the spans (w/ their styles) that make up the bar, which is `col_count`
+    /// wide. The `tick` is the position of the block of an indeterminate bar, which
+    /// bounces back when it reaches either end (it is ignored by a determinate bar).
+    pub fn get_spans(
+        &self,
+        col_count: usize,
+        tick: usize,
+    ) -> Vec<(String, Option<TuiStyle>)> {
+        let (empty_before, filled, empty_after) = match self.value.get_fraction() {
+            Some(fraction) => {
+                let filled_eighths = (fraction * (col_count * 8) as f64) as usize;
+                let (full_cols, partial_eighths) =
+                    (filled_eighths / 8, filled_eighths % 8);
+                let partial_cols = usize::from(partial_eighths > 0);
+                (
+                    String::new(),
+                    format!(
+                        "{}{}",
+                        PROGRESS_BAR_FILLED_CHAR.repeat(full_cols),
+                        PROGRESS_BAR_PARTIAL_CHARS[partial_eighths]
+                    ),
+                    PROGRESS_BAR_EMPTY_CHAR
+                        .repeat(col_count.saturating_sub(full_cols + partial_cols)),
+                )
+            }
+            None => {
+                let block_cols = PROGRESS_BAR_INDETERMINATE_COL_COUNT.min(col_count);
+                let range = col_count - block_cols;
+                let block_col_index = match range {
+                    0 => 0,
+                    _ => match tick % (range * 2) {
+                        it if it <= range => it,
+                        it => range * 2 - it,
+                    },
+                };
+                (
+                    PROGRESS_BAR_EMPTY_CHAR.repeat(block_col_index),
+                    PROGRESS_BAR_FILLED_CHAR.repeat(block_cols),
+                    PROGRESS_BAR_EMPTY_CHAR.repeat(range - block_col_index),
+                )
+            }
+        };
+
+        [
+            (empty_before, self.maybe_empty_style),
+            (filled, self.maybe_filled_style),
+            (empty_after, self.maybe_empty_style),
+        ]
+        .into_iter()
+        .filter(|(text, _)| !text.is_empty())
+        .collect()
+    }
+
+    /// Returns the text of the bar (w/out its styles), see [ProgressBar::get_spans].
+    pub fn get_text(&self, col_count: usize, tick: usize) -> String {
+        self.get_spans(col_count, tick)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect()
+    }
+
+    /// Returns the [RenderOps] to paint the bar at `origin_pos`, see
+    /// [ProgressBar::get_spans].
+    pub fn render(
+        &self,
+        origin_pos: Position,
+        col_count: usize,
+        tick: usize,
+    ) -> RenderOps {
+        let mut render_ops = render_ops!();
+        paint_spans(&mut render_ops, origin_pos, self.get_spans(col_count, tick));
+        render_ops
+    }
+}
+
+/// Paint the spans one after the other, starting at `origin_pos`.
+pub(crate) fn paint_spans(
+    render_ops: &mut RenderOps,
+    origin_pos: Position,
+    spans: Vec<(String, Option<TuiStyle>)>,
+) {
+    let mut col_index = 0;
+    for (text, maybe_style) in spans {
+        let text_width = UnicodeString::str_display_width(&text);
+        render_ops.push(RenderOp::MoveCursorPositionAbs(
+            origin_pos + position!(col_index: col_index, row_index: 0),
+        ));
+        render_ops.push(RenderOp::ApplyColors(maybe_style));
+        render_ops.push(RenderOp::PaintTextWithAttributes(text, maybe_style));
+        render_ops.push(RenderOp::ResetColor);
+        col_index += text_width;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_determinate_progress_bar() {
+        assert_eq2!(
+            ProgressBar::determinate(5, 10).get_text(10, 0),
+            "█████░░░░░"
+        );
+
+        // 55% of 10 cols is 5 full cols, and half of the next one.
+        assert_eq2!(
+            ProgressBar::determinate(55, 100).get_text(10, 0),
+            "█████▌░░░░"
+        );
+        assert_eq2!(ProgressBar::determinate(0, 10).get_text(4, 0), "░░░░");
+        assert_eq2!(ProgressBar::determinate(20, 10).get_text(4, 0), "████");
+        assert_eq2!(ProgressBar::determinate(0, 0).get_text(4, 0), "████");
+        assert_eq2!(
+            ProgressValue::Determinate { done: 1, total: 3 }.get_percent(),
+            Some(33)
+        );
+    }
+
+    #[test]
+    fn test_indeterminate_progress_bar() {
+        let progress_bar = ProgressBar::indeterminate();
+        assert_eq2!(progress_bar.value.get_percent(), None);
+        assert_eq2!(progress_bar.get_text(10, 0), "████░░░░░░");
+        assert_eq2!(progress_bar.get_text(10, 6), "░░░░░░████");
+
+        // The block bounces back once it reaches the end.
+        assert_eq2!(progress_bar.get_text(10, 7), "░░░░░████░");
+        assert_eq2!(progress_bar.get_text(10, 12), "████░░░░░░");
+
+        // The block is never wider than the bar.
+        assert_eq2!(progress_bar.get_text(3, 5), "███");
+    }
+}
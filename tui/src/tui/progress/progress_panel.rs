@@ -0,0 +1,536 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug,
+          sync::{Arc, Mutex},
+          time::Instant};
+
+use r3bl_core::{ch,
+                position,
+                send_signal,
+                ANSIBasicColor,
+                Position,
+                Size,
+                TuiColor,
+                TuiStyle,
+                UnicodeString};
+use tokio::sync::mpsc::Sender;
+
+use super::paint_spans;
+use crate::{render_ops,
+            ProgressBar,
+            ProgressValue,
+            RenderOps,
+            TerminalWindowMainThreadSignal,
+            PROGRESS_BAR_TICK_INTERVAL};
+
+/// The names of the tasks are right aligned in a column that is as wide as the longest
+/// one, up to this width.
+pub const PROGRESS_PANEL_MAX_NAME_COL_COUNT: usize = 20;
+
+/// The width of the bars (it is narrower if the panel doesn't have room for it).
+pub const PROGRESS_PANEL_BAR_COL_COUNT: usize = 20;
+
+/// The width of the status of a task, eg: ` 42%`, or `done`.
+const PROGRESS_PANEL_STATUS_COL_COUNT: usize = 4;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProgressTaskStatus {
+    Running,
+    Finished,
+    /// The error is painted instead of the message of the task.
+    Failed(String),
+}
+
+/// A task that is tracked by a [ProgressPanel], eg: `Compiling` w/ the message
+/// `r3bl_tui`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgressTask {
+    pub name: String,
+    pub message: String,
+    pub value: ProgressValue,
+    pub status: ProgressTaskStatus,
+}
+
+/// Identifies a task in a [ProgressPanel].
+pub type ProgressTaskId = usize;
+
+#[derive(Debug)]
+struct TrackedProgressTask {
+    id: ProgressTaskId,
+    task: ProgressTask,
+    /// There is a task that asks for a render on each tick, while this task is running
+    /// & indeterminate (so that its bar moves).
+    is_ticking: bool,
+}
+
+#[derive(Debug, Default)]
+struct ProgressPanelState {
+    /// In the order that they were added.
+    tasks: Vec<TrackedProgressTask>,
+    next_id: ProgressTaskId,
+}
+
+/// The tasks whose progress is painted by a [crate::ProgressPanelComponent], one per row,
+/// like the output of `cargo build`, eg:
+///
+/// ```text
+/// Compiling ███████▌░░░░░░░░░░░░  37% r3bl_tui
+///  Fetching ░░░████░░░░░░░░░░░░░      crates.io index
+/// ```
+///
+/// This is cheap to clone (the clones share the same tasks). Keep a clone of it, eg: in
+/// the app, to add the tasks w/ [ProgressPanel::add_task]. The [ProgressHandle] that it
+/// returns can be moved into a background (tokio) task to update the progress, and the
+/// main event loop is asked to render again on each update.
+#[derive(Clone, Debug)]
+pub struct ProgressPanel {
+    inner: Arc<Mutex<ProgressPanelState>>,
+    created_at: Instant,
+}
+
+impl Default for ProgressPanel {
+    fn default() -> Self {
+        Self {
+            inner: Default::default(),
+            created_at: Instant::now(),
+        }
+    }
+}
+
+impl ProgressPanel {
+    /// Add a task (which is running) to the bottom of the panel. This must be called from
+    /// a tokio runtime.
+    pub fn add_task<AS>(
+        &self,
+        name: impl Into<String>,
+        value: ProgressValue,
+        main_thread_channel_sender: &Sender<TerminalWindowMainThreadSignal<AS>>,
+    ) -> ProgressHandle<AS>
+    where
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        let id = match self.inner.lock() {
+            Ok(mut state) => {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.tasks.push(TrackedProgressTask {
+                    id,
+                    task: ProgressTask {
+                        name: name.into(),
+                        message: String::new(),
+                        value,
+                        status: ProgressTaskStatus::Running,
+                    },
+                    is_ticking: false,
+                });
+                id
+            }
+            Err(_) => ProgressTaskId::default(),
+        };
+
+        let progress_handle = ProgressHandle {
+            progress_panel: self.clone(),
+            id,
+            main_thread_channel_sender: main_thread_channel_sender.clone(),
+        };
+        progress_handle.request_render();
+        progress_handle
+    }
+
+    /// Returns the task w/ the `id`, if it hasn't been removed.
+    pub fn get_task(&self, id: ProgressTaskId) -> Option<ProgressTask> {
+        let state = self.inner.lock().ok()?;
+        state
+            .tasks
+            .iter()
+            .find(|it| it.id == id)
+            .map(|it| it.task.clone())
+    }
+
+    /// Returns the tasks, in the order that they were added.
+    pub fn get_tasks(&self) -> Vec<ProgressTask> {
+        let Ok(state) = self.inner.lock() else {
+            return vec![];
+        };
+        state.tasks.iter().map(|it| it.task.clone()).collect()
+    }
+
+    /// Remove the tasks that have finished (or failed).
+    pub fn remove_done_tasks(&self) {
+        if let Ok(mut state) = self.inner.lock() {
+            state
+                .tasks
+                .retain(|it| it.task.status == ProgressTaskStatus::Running);
+        }
+    }
+
+    pub fn len(&self) -> usize { self.inner.lock().map(|it| it.tasks.len()).unwrap_or(0) }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Returns the position of the blocks of the indeterminate bars at `now`, see
+    /// [ProgressBar::get_spans].
+    pub fn get_tick(&self, now: Instant) -> usize {
+        (now.saturating_duration_since(self.created_at).as_millis()
+            / PROGRESS_BAR_TICK_INTERVAL.as_millis()) as usize
+    }
+
+    /// Returns the [RenderOps] to paint the tasks in the box at `origin_pos`, one per
+    /// row. If there are more tasks than rows, the last ones that were added are
+    /// painted.
+    pub fn render(
+        &self,
+        origin_pos: Position,
+        bounds_size: Size,
+        tick: usize,
+    ) -> RenderOps {
+        let mut render_ops = render_ops!();
+        let tasks = self.get_tasks();
+        let row_count = ch!(@to_usize bounds_size.row_count);
+        let col_count = ch!(@to_usize bounds_size.col_count);
+        let visible_tasks = &tasks[tasks.len().saturating_sub(row_count)..];
+
+        let name_width = visible_tasks
+            .iter()
+            .map(|it| UnicodeString::str_display_width(&it.name))
+            .max()
+            .unwrap_or_default()
+            .min(PROGRESS_PANEL_MAX_NAME_COL_COUNT);
+
+        for (row_index, task) in visible_tasks.iter().enumerate() {
+            paint_spans(
+                &mut render_ops,
+                origin_pos + position!(col_index: 0, row_index: row_index),
+                get_row_spans(task, name_width, col_count, tick),
+            );
+        }
+        render_ops
+    }
+}
+
+/// A row has the name of the task (right aligned), its bar, its status, and its message
+/// (or error), which is clipped to the `col_count`. The row is padded to the
+/// `col_count`, so that the previous content of the row is cleared.
+fn get_row_spans(
+    task: &ProgressTask,
+    name_width: usize,
+    col_count: usize,
+    tick: usize,
+) -> Vec<(String, Option<TuiStyle>)> {
+    let name_style = TuiStyle {
+        bold: true,
+        color_fg: match task.status {
+            ProgressTaskStatus::Running => None,
+            ProgressTaskStatus::Finished => Some(TuiColor::Basic(ANSIBasicColor::Green)),
+            ProgressTaskStatus::Failed(_) => Some(TuiColor::Basic(ANSIBasicColor::Red)),
+        },
+        ..Default::default()
+    };
+    let name = UnicodeString::from(task.name.as_str());
+    let name = UnicodeString::from(name.truncate_end_to_fit_width(ch!(name_width)));
+    let mut spans = vec![(
+        format!(
+            "{}{} ",
+            " ".repeat(name_width - ch!(@to_usize name.display_width)),
+            name.string
+        ),
+        Some(name_style),
+    )];
+
+    // The bar of a finished task is full.
+    let bar_width = PROGRESS_PANEL_BAR_COL_COUNT
+        .min(col_count.saturating_sub(name_width + PROGRESS_PANEL_STATUS_COL_COUNT + 3));
+    if bar_width > 0 {
+        let progress_bar = match task.status {
+            ProgressTaskStatus::Finished => ProgressBar::determinate(1, 1),
+            _ => ProgressBar::new(task.value),
+        };
+        spans.extend(progress_bar.get_spans(bar_width, tick));
+        spans.push((" ".to_string(), None));
+    }
+
+    let (status, message, maybe_message_style) = match task.status {
+        ProgressTaskStatus::Running => (
+            match task.value.get_percent() {
+                Some(percent) => format!("{percent:>3}%"),
+                None => " ".repeat(PROGRESS_PANEL_STATUS_COL_COUNT),
+            },
+            task.message.as_str(),
+            None,
+        ),
+        ProgressTaskStatus::Finished => ("done".to_string(), task.message.as_str(), None),
+        ProgressTaskStatus::Failed(ref error) => (
+            "fail".to_string(),
+            error.as_str(),
+            Some(TuiStyle {
+                color_fg: Some(TuiColor::Basic(ANSIBasicColor::Red)),
+                ..Default::default()
+            }),
+        ),
+    };
+    spans.push((format!("{status} "), None));
+    spans.push((message.to_string(), maybe_message_style));
+
+    // Clip (or pad) the spans to the width of the panel.
+    let mut available_width = col_count;
+    let mut it = vec![];
+    for (text, maybe_style) in spans {
+        let text = UnicodeString::from(text.as_str());
+        let text = text
+            .truncate_end_to_fit_width(ch!(available_width))
+            .to_string();
+        available_width -= UnicodeString::str_display_width(&text);
+        it.push((text, maybe_style));
+    }
+    it.push((" ".repeat(available_width), None));
+    it.retain(|(text, _)| !text.is_empty());
+    it
+}
+
+/// Updates a task in a [ProgressPanel], eg: from a background task. It is cheap to clone.
+/// Each update asks the main event loop to render again (the requests are coalesced,
+/// see [crate::RenderScheduler]). All of these must be called from a tokio runtime.
+#[derive(Clone, Debug)]
+pub struct ProgressHandle<AS>
+where
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    progress_panel: ProgressPanel,
+    id: ProgressTaskId,
+    main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
+}
+
+impl<AS> ProgressHandle<AS>
+where
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    pub fn get_id(&self) -> ProgressTaskId { self.id }
+
+    pub fn set_progress(&self, done: u64, total: u64) {
+        self.update(|task| task.value = ProgressValue::Determinate { done, total });
+    }
+
+    /// Add `delta` to the units of work that are done (if the task is determinate).
+    pub fn inc(&self, delta: u64) {
+        self.update(|task| {
+            if let ProgressValue::Determinate { ref mut done, .. } = task.value {
+                *done = done.saturating_add(delta);
+            }
+        });
+    }
+
+    pub fn set_indeterminate(&self) {
+        self.update(|task| task.value = ProgressValue::Indeterminate);
+    }
+
+    pub fn set_message(&self, message: impl Into<String>) {
+        let message = message.into();
+        self.update(|task| task.message = message);
+    }
+
+    pub fn finish(&self) {
+        self.update(|task| task.status = ProgressTaskStatus::Finished);
+    }
+
+    pub fn fail(&self, error: impl Into<String>) {
+        let error = error.into();
+        self.update(|task| task.status = ProgressTaskStatus::Failed(error));
+    }
+
+    /// Nothing happens if the task was removed from the panel.
+    fn update(&self, update_task: impl FnOnce(&mut ProgressTask)) {
+        let Ok(mut state) = self.progress_panel.inner.lock() else {
+            return;
+        };
+        let Some(tracked_task) = state.tasks.iter_mut().find(|it| it.id == self.id)
+        else {
+            return;
+        };
+        update_task(&mut tracked_task.task);
+        drop(state);
+        self.request_render();
+    }
+
+    /// Ask for a render, and for one on each tick while the task is running &
+    /// indeterminate.
+    fn request_render(&self) {
+        send_signal!(
+            self.main_thread_channel_sender,
+            TerminalWindowMainThreadSignal::RequestRender(None)
+        );
+
+        if !self.start_ticking() {
+            return;
+        }
+        let progress_handle = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(PROGRESS_BAR_TICK_INTERVAL).await;
+                if !progress_handle.keep_ticking() {
+                    break;
+                }
+                let _ = progress_handle
+                    .main_thread_channel_sender
+                    .send(TerminalWindowMainThreadSignal::RequestRender(None))
+                    .await;
+            }
+        });
+    }
+
+    /// Returns true if the task should tick, and it wasn't ticking already.
+    fn start_ticking(&self) -> bool {
+        let Ok(mut state) = self.progress_panel.inner.lock() else {
+            return false;
+        };
+        let Some(tracked_task) = state.tasks.iter_mut().find(|it| it.id == self.id)
+        else {
+            return false;
+        };
+        if tracked_task.is_ticking || !is_ticking_task(&tracked_task.task) {
+            return false;
+        }
+        tracked_task.is_ticking = true;
+        true
+    }
+
+    /// Returns false (and stops ticking) once the task is done, or determinate, or it
+    /// was removed from the panel.
+    fn keep_ticking(&self) -> bool {
+        let Ok(mut state) = self.progress_panel.inner.lock() else {
+            return false;
+        };
+        let Some(tracked_task) = state.tasks.iter_mut().find(|it| it.id == self.id)
+        else {
+            return false;
+        };
+        tracked_task.is_ticking = is_ticking_task(&tracked_task.task);
+        tracked_task.is_ticking
+    }
+}
+
+fn is_ticking_task(task: &ProgressTask) -> bool {
+    task.status == ProgressTaskStatus::Running
+        && task.value == ProgressValue::Indeterminate
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::{RenderOp, CHANNEL_WIDTH};
+
+    #[tokio::test]
+    async fn test_progress_handle_updates_task() {
+        let (sender, mut receiver) =
+            mpsc::channel::<TerminalWindowMainThreadSignal<()>>(CHANNEL_WIDTH);
+        let progress_panel = ProgressPanel::default();
+
+        let build = progress_panel.add_task(
+            "Compiling",
+            ProgressValue::Determinate { done: 0, total: 10 },
+            &sender,
+        );
+        let fetch =
+            progress_panel.add_task("Fetching", ProgressValue::Indeterminate, &sender);
+
+        // The handle can be moved into a background task.
+        tokio::spawn(async move {
+            build.set_message("r3bl_tui");
+            build.inc(3);
+            build.inc(2);
+        })
+        .await
+        .unwrap();
+        fetch.fail("offline");
+
+        let tasks = progress_panel.get_tasks();
+        assert_eq2!(
+            tasks[0].value,
+            ProgressValue::Determinate { done: 5, total: 10 }
+        );
+        assert_eq2!(tasks[0].message, "r3bl_tui".to_string());
+        assert_eq2!(
+            tasks[1].status,
+            ProgressTaskStatus::Failed("offline".to_string())
+        );
+
+        // The main event loop is asked to render again.
+        assert!(matches!(
+            receiver.recv().await,
+            Some(TerminalWindowMainThreadSignal::RequestRender(None))
+        ));
+
+        progress_panel.remove_done_tasks();
+        assert_eq2!(progress_panel.len(), 1);
+    }
+
+    #[test]
+    fn test_render_rows() {
+        let progress_panel = ProgressPanel::default();
+        if let Ok(mut state) = progress_panel.inner.lock() {
+            for (id, name, value, status) in [
+                (
+                    0,
+                    "Compiling",
+                    ProgressValue::Determinate { done: 3, total: 10 },
+                    ProgressTaskStatus::Running,
+                ),
+                (
+                    1,
+                    "Fetching",
+                    ProgressValue::Indeterminate,
+                    ProgressTaskStatus::Failed("offline".to_string()),
+                ),
+            ] {
+                state.tasks.push(TrackedProgressTask {
+                    id,
+                    task: ProgressTask {
+                        name: name.to_string(),
+                        message: "r3bl_tui".to_string(),
+                        value,
+                        status,
+                    },
+                    is_ticking: false,
+                });
+            }
+        }
+
+        let render_ops = progress_panel.render(
+            position!(col_index: 0, row_index: 0),
+            size!(col_count: 45, row_count: 5),
+            0,
+        );
+        let text: String = render_ops
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq2!(
+            text,
+            format!(
+                "{}{}",
+                "Compiling ██████░░░░░░░░░░░░░░  30% r3bl_tui ",
+                " Fetching ████░░░░░░░░░░░░░░░░ fail offline  ",
+            )
+        );
+    }
+}
@@ -0,0 +1,128 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, marker::PhantomData, time::Instant};
+
+use r3bl_core::{throws_with_return, CommonResult};
+
+use crate::{render_pipeline,
+            BoxedSafeComponent,
+            Component,
+            EventPropagation,
+            FlexBox,
+            FlexBoxId,
+            GlobalData,
+            HasFocus,
+            InputEvent,
+            ProgressPanel,
+            RenderPipeline,
+            SurfaceBounds,
+            ZOrder};
+
+/// This is a re-usable component that paints the tasks of a [ProgressPanel] in its box,
+/// one per row. It doesn't handle any input events.
+///
+/// The panel is shared w/ the app (it is cheap to clone), which adds the tasks to it,
+/// and updates them (eg: from background tasks) w/ their [crate::ProgressHandle]s.
+#[derive(Debug, Default)]
+pub struct ProgressPanelComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub data: ProgressPanelComponentData<S, AS>,
+}
+
+#[derive(Debug, Default)]
+pub struct ProgressPanelComponentData<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub progress_panel: ProgressPanel,
+    _phantom: PhantomData<(S, AS)>,
+}
+
+impl<S, AS> Component<S, AS> for ProgressPanelComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    fn reset(&mut self) {}
+
+    fn get_id(&self) -> FlexBoxId { self.data.id }
+
+    fn render(
+        &mut self,
+        _global_data: &mut GlobalData<S, AS>,
+        current_box: FlexBox,
+        _surface_bounds: SurfaceBounds, /* Ignore this. */
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<RenderPipeline> {
+        throws_with_return!({
+            let progress_panel = &self.data.progress_panel;
+            let mut render_pipeline = render_pipeline!();
+            render_pipeline.push(
+                ZOrder::Normal,
+                progress_panel.render(
+                    current_box.style_adjusted_origin_pos,
+                    current_box.style_adjusted_bounds_size,
+                    progress_panel.get_tick(Instant::now()),
+                ),
+            );
+            render_pipeline
+        });
+    }
+
+    fn handle_event(
+        &mut self,
+        _global_data: &mut GlobalData<S, AS>,
+        _input_event: InputEvent,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        Ok(EventPropagation::Propagate)
+    }
+}
+
+pub mod constructor {
+    use super::*;
+
+    impl<S, AS> ProgressPanelComponent<S, AS>
+    where
+        S: Debug + Default + Clone + Sync + Send + 'static,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        /// The `progress_panel` is a clone of the one that the app adds the tasks to.
+        pub fn new(id: FlexBoxId, progress_panel: ProgressPanel) -> Self {
+            Self {
+                data: ProgressPanelComponentData {
+                    id,
+                    progress_panel,
+                    _phantom: PhantomData,
+                },
+            }
+        }
+
+        pub fn new_boxed(
+            id: FlexBoxId,
+            progress_panel: ProgressPanel,
+        ) -> BoxedSafeComponent<S, AS> {
+            Box::new(ProgressPanelComponent::new(id, progress_panel))
+        }
+    }
+}
@@ -43,6 +43,28 @@ macro_rules! box_start {
             maybe_styles:           get_tui_styles! { @from: $arg_surface.stylesheet, [$($args)*] }
         })?
     };
+
+    // Any of the other fields of [crate::FlexBoxProps] can be set after the styles, eg:
+    // `fixed_size: FixedSize { row_count: Some(ch!(1)), ..Default::default() }`.
+    (
+        in:                     $arg_surface : expr,                // Eg: in: surface,
+        id:                     $arg_id : expr,                     // Eg: 0,
+        dir:                    $arg_dir : expr,                    // Eg: Direction::Horizontal,
+        requested_size_percent: $arg_requested_size_percent : expr, // Eg: (50, 100).try_into()?,
+        styles:                 [$($args:tt)*],                     // Eg: [ "style1" , "style2" ]
+        $($arg_field : ident : $arg_value : expr),+                 // Eg: size_constraints: SizeConstraints { .. }
+        $(,)*                   /* Optional trailing comma https://stackoverflow.com/a/43143459/2085356. */
+    ) => {
+      $arg_surface.box_start($crate::FlexBoxProps {
+            $($arg_field: $arg_value,)+
+            ..box_props! {
+                id:                     $arg_id,
+                dir:                    $arg_dir,
+                requested_size_percent: $arg_requested_size_percent,
+                maybe_styles:           get_tui_styles! { @from: $arg_surface.stylesheet, [$($args)*] }
+            }
+        })?
+    };
 }
 
 #[macro_export]
@@ -60,6 +82,18 @@ macro_rules! box_props {
       id: $arg_id,
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
+      size_constraints: Default::default(),
+      fixed_size: Default::default(),
+      fixed_children_size: Default::default(),
+      alignment: Default::default(),
+      visible: true,
+      wrap: Default::default(),
+      maybe_grid_layout: None,
+      maybe_grid_cell: None,
+      maybe_constraint_layout: None,
+      margin: Default::default(),
+      padding: Default::default(),
+      maybe_border: None,
       maybe_styles: $arg_styles,
     }
   };
@@ -75,6 +109,18 @@ macro_rules! box_props {
       id: $arg_id,
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
+      size_constraints: Default::default(),
+      fixed_size: Default::default(),
+      fixed_children_size: Default::default(),
+      alignment: Default::default(),
+      visible: true,
+      wrap: Default::default(),
+      maybe_grid_layout: None,
+      maybe_grid_cell: None,
+      maybe_constraint_layout: None,
+      margin: Default::default(),
+      padding: Default::default(),
+      maybe_border: None,
       maybe_styles: Some(vec![$($args)*]),
     }
   };
@@ -89,6 +135,18 @@ macro_rules! box_props {
       id: $arg_id,
       dir: $arg_dir,
       requested_size_percent: $arg_requested_size_percent,
+      size_constraints: Default::default(),
+      fixed_size: Default::default(),
+      fixed_children_size: Default::default(),
+      alignment: Default::default(),
+      visible: true,
+      wrap: Default::default(),
+      maybe_grid_layout: None,
+      maybe_grid_cell: None,
+      maybe_constraint_layout: None,
+      margin: Default::default(),
+      padding: Default::default(),
+      maybe_border: None,
       maybe_styles: None,
     }
   };
@@ -16,7 +16,8 @@
  */
 
 /// Render the component in the current box (which is retrieved from the surface). This is the
-/// "normal" way to render a component, in the FlexBox that is currently being laid out.
+/// "normal" way to render a component, in the FlexBox that is currently being laid out. Nothing
+/// is rendered if the box is hidden (see [crate::FlexBoxProps::visible]).
 #[macro_export]
 macro_rules! render_component_in_current_box {
     (
@@ -33,14 +34,16 @@ macro_rules! render_component_in_current_box {
 
         if let Some(component_ref) = maybe_component_ref {
             let surface_bounds = $crate::SurfaceBounds::from(&*($arg_surface));
-            let current_box = $arg_surface.current_box()?;
-            let queue = component_ref.render(
-                $arg_global_data,
-                *current_box,
-                surface_bounds,
-                $arg_has_focus,
-            )?;
-            $arg_surface.render_pipeline += queue;
+            let current_box = *$arg_surface.current_box()?;
+            if current_box.visible {
+                let queue = component_ref.render(
+                    $arg_global_data,
+                    current_box,
+                    surface_bounds,
+                    $arg_has_focus,
+                )?;
+                $arg_surface.render_pipeline += queue;
+            }
         }
     };
 }
@@ -76,3 +79,70 @@ macro_rules! render_component_in_given_box {
         }
     }};
 }
+
+/// Render all the modal components (eg: dialogs) in the modal stack of [crate::HasFocus]
+/// (see [crate::HasFocus::try_push_modal_id]), from the bottom most one to the top most
+/// one, so that each one paints on top of the ones below it (eg: a confirm dialog on top
+/// of a form dialog). Just like [render_component_in_given_box!], the modals break out
+/// of their box.
+#[macro_export]
+macro_rules! render_modal_stack {
+    (
+        in:           $arg_surface                  : expr, // Eg: in: surface
+        from:         $arg_component_registry_map   : expr, // Eg: from: component_registry_map
+        global_data:  $arg_global_data              : expr, // Eg: global_data
+        has_focus:    $arg_has_focus                : expr  // Eg: has_focus
+     ) => {{
+        let modal_ids: Vec<$crate::FlexBoxId> = $arg_has_focus.get_modal_ids().to_vec();
+        for modal_id in modal_ids {
+            $crate::render_component_in_given_box! {
+              in:           $arg_surface,
+              box:          $crate::FlexBox::default(), /* This is not used as the modal breaks out of its box. */
+              component_id: modal_id,
+              from:         $arg_component_registry_map,
+              global_data:  $arg_global_data,
+              has_focus:    $arg_has_focus
+            };
+        }
+    }};
+}
+
+/// Add a floating box (see [crate::FloatingBoxProps]) to the surface, and render the
+/// component in it. The [crate::ZOrder::Normal] [crate::RenderOps] of the component are
+/// moved to the [crate::FloatingBoxProps::z_order] of the box, so that they are painted on
+/// top of the boxes below it.
+///
+/// This is for tooltips, popups, and context menus, which are anchored to a box (or a
+/// position in the window) and paint on top of everything else in the window.
+#[macro_export]
+macro_rules! render_component_in_floating_box {
+    (
+        in:           $arg_surface                  : expr, // Eg: in: surface
+        props:        $arg_props                    : expr, // Eg: props: FloatingBoxProps { .. }
+        component_id: $arg_component_id             : expr, // Eg: "0"
+        from:         $arg_component_registry_map   : expr, // Eg: from: component_registry_map
+        global_data:  $arg_global_data              : expr, // Eg: global_data
+        has_focus:    $arg_has_focus                : expr  // Eg: has_focus
+     ) => {{
+        let props: $crate::FloatingBoxProps = $arg_props;
+        let z_order = props.z_order;
+        let floating_box = $arg_surface.add_floating_box(props)?;
+
+        let maybe_component_ref = $crate::ComponentRegistry::try_to_get_component_by_id(
+            $arg_component_registry_map,
+            $arg_component_id,
+        );
+
+        if let Some(component_ref) = maybe_component_ref {
+            let surface_bounds = $crate::SurfaceBounds::from(&*($arg_surface));
+            let mut queue: $crate::RenderPipeline = component_ref.render(
+                $arg_global_data,
+                floating_box,
+                surface_bounds,
+                $arg_has_focus,
+            )?;
+            queue.hoist($crate::ZOrder::Normal, z_order);
+            $arg_surface.render_pipeline += queue;
+        }
+    }};
+}
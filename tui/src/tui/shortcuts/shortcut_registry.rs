@@ -0,0 +1,663 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_core::{ch,
+                position,
+                size,
+                CommonError,
+                CommonErrorType,
+                CommonResult,
+                Position,
+                Size,
+                TuiStyle,
+                UnicodeString};
+
+use crate::{render_ops,
+            render_pipeline,
+            BoxBorder,
+            ComponentRegistryMap,
+            EventPropagation,
+            FlexBoxId,
+            HasFocus,
+            InputEvent,
+            Key,
+            KeyPress,
+            RenderOp,
+            RenderOps,
+            RenderPipeline,
+            SpecialKey,
+            ZOrder};
+
+/// Pressing this (when the app doesn't consume it) shows the help overlay, see
+/// [ShortcutRegistry].
+pub const HELP_OVERLAY_KEY_PRESS: KeyPress = KeyPress::Plain {
+    key: Key::Character('?'),
+};
+
+pub const HELP_OVERLAY_TITLE: &str = "Keyboard shortcuts (Esc to close)";
+
+/// The width of the help overlay (including its border) is clamped to this.
+pub const HELP_OVERLAY_MAX_COL_COUNT: usize = 72;
+
+/// The title of the group that has the built-in shortcuts (eg:
+/// [HELP_OVERLAY_KEY_PRESS]).
+pub const BUILT_IN_SHORTCUT_GROUP_TITLE: &str = "General";
+
+/// A key binding, w/ a description of what it does, that is listed in the help overlay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shortcut {
+    pub key_press: KeyPress,
+    pub description: String,
+}
+
+impl Shortcut {
+    pub fn new(key_press: KeyPress, description: impl Into<String>) -> Self {
+        Self {
+            key_press,
+            description: description.into(),
+        }
+    }
+}
+
+/// The shortcuts that are declared by the app (see [crate::App::app_get_shortcuts]) or
+/// by a component (see [crate::Component::get_shortcuts]), which are listed under the
+/// `title` in the help overlay.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShortcutGroup {
+    pub title: String,
+    pub shortcuts: Vec<Shortcut>,
+}
+
+impl ShortcutGroup {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            shortcuts: vec![],
+        }
+    }
+
+    pub fn add(mut self, key_press: KeyPress, description: impl Into<String>) -> Self {
+        self.shortcuts.push(Shortcut::new(key_press, description));
+        self
+    }
+}
+
+/// Where a [ShortcutGroup] applies.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShortcutScope {
+    /// Everywhere (the app handles it before, or regardless of, the focused component).
+    App,
+    /// Only while the component w/ this id has focus.
+    Component(FlexBoxId),
+}
+
+impl ShortcutScope {
+    /// The shortcuts of the app apply everywhere, and the ones of a component only apply
+    /// when it has focus, so 2 components can use the same key press, but a component
+    /// can't use one of the app's.
+    pub fn overlaps(&self, other: &ShortcutScope) -> bool {
+        match (self, other) {
+            (ShortcutScope::App, _) | (_, ShortcutScope::App) => true,
+            (ShortcutScope::Component(lhs), ShortcutScope::Component(rhs)) => lhs == rhs,
+        }
+    }
+}
+
+/// The keyboard shortcuts of the app & its components, which are listed (grouped by
+/// component) in a help overlay that is shown w/ [HELP_OVERLAY_KEY_PRESS].
+///
+/// - The main event loop [reloads](ShortcutRegistry::try_reload) this from
+///   [crate::App::app_get_shortcuts] & [crate::Component::get_shortcuts] after
+///   [crate::App::app_init], and each time the help overlay is shown (since components
+///   can be added later on). Conflicts are detected when a shortcut is registered, see
+///   [ShortcutScope::overlaps], and are logged.
+/// - The help overlay is painted by the main event loop, after the app is rendered, at
+///   [ZOrder::Glass]. While it is shown, it gets all the input events (before the app
+///   does), so it can be scrolled & closed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShortcutRegistry {
+    /// In the order that they were registered.
+    groups: Vec<(ShortcutScope, ShortcutGroup)>,
+    is_help_overlay_shown: bool,
+    help_overlay_scroll_offset: usize,
+}
+
+impl ShortcutRegistry {
+    /// Register the `shortcut` under the group w/ the given `scope` & `title` (which is
+    /// added if there isn't one). An error is returned (and nothing is registered) if
+    /// its key press is already registered in an overlapping scope.
+    pub fn try_register(
+        &mut self,
+        scope: ShortcutScope,
+        title: &str,
+        shortcut: Shortcut,
+    ) -> CommonResult<()> {
+        self.register(scope, title, shortcut)
+            .or_else(|msg| new_conflict_error_result(&[msg]))
+    }
+
+    /// Returns the conflict (as a message) instead of registering the `shortcut`, if
+    /// there is one.
+    fn register(
+        &mut self,
+        scope: ShortcutScope,
+        title: &str,
+        shortcut: Shortcut,
+    ) -> Result<(), String> {
+        let maybe_conflict = self
+            .groups
+            .iter()
+            .filter(|(other_scope, _)| scope.overlaps(other_scope))
+            .find_map(|(_, other_group)| {
+                other_group
+                    .shortcuts
+                    .iter()
+                    .find(|it| it.key_press == shortcut.key_press)
+                    .map(|other_shortcut| (other_group, other_shortcut))
+            });
+
+        if let Some((other_group, other_shortcut)) = maybe_conflict {
+            return Err(format!(
+                "Shortcut {} ({}) in {} conflicts w/ {} in {}.",
+                shortcut.key_press.get_display_text(),
+                shortcut.description,
+                title,
+                other_shortcut.description,
+                other_group.title
+            ));
+        }
+
+        match self
+            .groups
+            .iter_mut()
+            .find(|(it_scope, it_group)| *it_scope == scope && it_group.title == title)
+        {
+            Some((_, group)) => group.shortcuts.push(shortcut),
+            None => self.groups.push((
+                scope,
+                ShortcutGroup {
+                    title: title.to_string(),
+                    shortcuts: vec![shortcut],
+                },
+            )),
+        }
+
+        Ok(())
+    }
+
+    /// Register all the shortcuts in the `group`. The ones that conflict are skipped, and
+    /// an error (that lists all of them) is returned.
+    pub fn try_register_group(
+        &mut self,
+        scope: ShortcutScope,
+        group: ShortcutGroup,
+    ) -> CommonResult<()> {
+        let mut conflicts = vec![];
+        self.register_group(scope, group, &mut conflicts);
+        match conflicts.is_empty() {
+            true => Ok(()),
+            false => new_conflict_error_result(&conflicts),
+        }
+    }
+
+    fn register_group(
+        &mut self,
+        scope: ShortcutScope,
+        group: ShortcutGroup,
+        conflicts: &mut Vec<String>,
+    ) {
+        for shortcut in group.shortcuts {
+            if let Err(msg) = self.register(scope, &group.title, shortcut) {
+                conflicts.push(msg);
+            }
+        }
+    }
+
+    /// Replace all the shortcuts w/ the built-in ones, the app's `maybe_app_group`, and
+    /// the ones of the components in the `component_registry_map` (in the order of their
+    /// ids). All of them are registered even if there are conflicts, which are returned
+    /// as a single error.
+    pub fn try_reload<S, AS>(
+        &mut self,
+        maybe_app_group: Option<ShortcutGroup>,
+        component_registry_map: &ComponentRegistryMap<S, AS>,
+    ) -> CommonResult<()>
+    where
+        S: Debug + Default + Clone + Sync + Send,
+        AS: Debug + Default + Clone + Sync + Send,
+    {
+        self.groups.clear();
+
+        let mut groups = vec![(
+            ShortcutScope::App,
+            ShortcutGroup::new(BUILT_IN_SHORTCUT_GROUP_TITLE)
+                .add(HELP_OVERLAY_KEY_PRESS, "Show keyboard shortcuts"),
+        )];
+        if let Some(app_group) = maybe_app_group {
+            groups.push((ShortcutScope::App, app_group));
+        }
+        let mut ids: Vec<FlexBoxId> = component_registry_map.keys().copied().collect();
+        ids.sort_by_key(|it| it.0);
+        for id in ids {
+            if let Some(group) = component_registry_map
+                .get(&id)
+                .and_then(|component| component.get_shortcuts())
+            {
+                groups.push((ShortcutScope::Component(id), group));
+            }
+        }
+
+        let mut conflicts = vec![];
+        for (scope, group) in groups {
+            self.register_group(scope, group, &mut conflicts);
+        }
+        match conflicts.is_empty() {
+            true => Ok(()),
+            false => new_conflict_error_result(&conflicts),
+        }
+    }
+
+    /// Returns the groups that are listed in the help overlay: the app's groups first,
+    /// then the focused component's, and then the other components'.
+    pub fn get_groups(&self, has_focus: &HasFocus) -> Vec<&ShortcutGroup> {
+        let rank = |scope: &ShortcutScope| match scope {
+            ShortcutScope::App => 0,
+            ShortcutScope::Component(id) if has_focus.does_id_have_focus(*id) => 1,
+            ShortcutScope::Component(_) => 2,
+        };
+        let mut it: Vec<&(ShortcutScope, ShortcutGroup)> = self.groups.iter().collect();
+        it.sort_by_key(|(scope, _)| rank(scope));
+        it.into_iter().map(|(_, group)| group).collect()
+    }
+
+    pub fn is_help_overlay_shown(&self) -> bool { self.is_help_overlay_shown }
+
+    pub fn show_help_overlay(&mut self) {
+        self.is_help_overlay_shown = true;
+        self.help_overlay_scroll_offset = 0;
+    }
+
+    pub fn hide_help_overlay(&mut self) { self.is_help_overlay_shown = false; }
+
+    pub fn is_help_overlay_key(input_event: &InputEvent) -> bool {
+        matches!(input_event, InputEvent::Keyboard(it) if *it == HELP_OVERLAY_KEY_PRESS)
+    }
+
+    /// While the help overlay is shown, it handles all the input events: <kbd>Esc</kbd>
+    /// (or [HELP_OVERLAY_KEY_PRESS]) closes it, the arrow & page keys scroll it, and all
+    /// the other events are consumed. [None] is returned if it isn't shown.
+    pub fn handle_input_event(
+        &mut self,
+        input_event: &InputEvent,
+        window_size: Size,
+    ) -> Option<EventPropagation> {
+        if !self.is_help_overlay_shown {
+            return None;
+        }
+
+        let InputEvent::Keyboard(key_press) = input_event else {
+            return Some(EventPropagation::Consumed);
+        };
+
+        let page_row_count = calc_help_overlay_visible_row_count(window_size).max(1);
+        let max_scroll_offset = self
+            .get_help_overlay_line_count()
+            .saturating_sub(calc_help_overlay_visible_row_count(window_size));
+        let scroll_offset = self.help_overlay_scroll_offset;
+
+        let new_scroll_offset = match key_press {
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Esc),
+            } => {
+                self.hide_help_overlay();
+                return Some(EventPropagation::ConsumedRender);
+            }
+            _ if *key_press == HELP_OVERLAY_KEY_PRESS => {
+                self.hide_help_overlay();
+                return Some(EventPropagation::ConsumedRender);
+            }
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Up),
+            } => scroll_offset.saturating_sub(1),
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Down),
+            } => scroll_offset + 1,
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::PageUp),
+            } => scroll_offset.saturating_sub(page_row_count),
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::PageDown),
+            } => scroll_offset + page_row_count,
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Home),
+            } => 0,
+            KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::End),
+            } => max_scroll_offset,
+            _ => return Some(EventPropagation::Consumed),
+        };
+
+        let new_scroll_offset = new_scroll_offset.min(max_scroll_offset);
+        if new_scroll_offset == scroll_offset {
+            return Some(EventPropagation::Consumed);
+        }
+        self.help_overlay_scroll_offset = new_scroll_offset;
+        Some(EventPropagation::ConsumedRender)
+    }
+
+    /// Each group has a line for its title, a line for each shortcut, and a blank line
+    /// after it (except for the last group).
+    fn get_help_overlay_line_count(&self) -> usize {
+        let line_count: usize = self
+            .groups
+            .iter()
+            .map(|(_, group)| group.shortcuts.len() + 2)
+            .sum();
+        line_count.saturating_sub(1)
+    }
+
+    /// Returns the lines of the help overlay (w/out the border), w/ the key presses
+    /// aligned in a column.
+    pub fn get_help_overlay_lines(
+        &self,
+        has_focus: &HasFocus,
+    ) -> Vec<(String, Option<TuiStyle>)> {
+        let groups = self.get_groups(has_focus);
+        let key_col_count = groups
+            .iter()
+            .flat_map(|group| group.shortcuts.iter())
+            .map(|it| UnicodeString::str_display_width(&it.key_press.get_display_text()))
+            .max()
+            .unwrap_or_default();
+
+        let mut it = vec![];
+        for (group_index, group) in groups.iter().enumerate() {
+            if group_index > 0 {
+                it.push((String::new(), None));
+            }
+            it.push((
+                group.title.clone(),
+                Some(TuiStyle {
+                    bold: true,
+                    ..Default::default()
+                }),
+            ));
+            for shortcut in group.shortcuts.iter() {
+                let key_text = UnicodeString::from(shortcut.key_press.get_display_text())
+                    .pad_end_with_spaces_to_fit_width(" ", ch!(key_col_count));
+                it.push((format!("  {key_text}  {}", shortcut.description), None));
+            }
+        }
+        it
+    }
+
+    /// Returns the [RenderPipeline] to paint the help overlay (at [ZOrder::Glass]) in the
+    /// middle of the window, if it is shown. This is called by the main event loop, after
+    /// the app is rendered.
+    pub fn render(&self, window_size: Size, has_focus: &HasFocus) -> RenderPipeline {
+        let mut render_pipeline = render_pipeline!();
+        if !self.is_help_overlay_shown {
+            return render_pipeline;
+        }
+
+        let lines = self.get_help_overlay_lines(has_focus);
+        let window_col_count = ch!(@to_usize window_size.col_count);
+        let window_row_count = ch!(@to_usize window_size.row_count);
+        let col_count = lines
+            .iter()
+            .map(|(line, _)| UnicodeString::str_display_width(line) + 4)
+            .chain([UnicodeString::str_display_width(HELP_OVERLAY_TITLE) + 6])
+            .max()
+            .unwrap_or_default()
+            .min(HELP_OVERLAY_MAX_COL_COUNT)
+            .min(window_col_count.saturating_sub(2));
+        let visible_row_count = calc_help_overlay_visible_row_count(window_size);
+        let row_count = lines.len().min(visible_row_count) + 2;
+        if col_count < 5 || visible_row_count == 0 {
+            return render_pipeline;
+        }
+
+        let origin_pos = position!(
+            col_index: (window_col_count - col_count) / 2,
+            row_index: (window_row_count - row_count) / 2
+        );
+        let mut render_ops = render_ops!();
+        render_ops.extend(
+            BoxBorder {
+                maybe_title: Some(HELP_OVERLAY_TITLE.to_string()),
+                maybe_style: None,
+            }
+            .render(
+                origin_pos,
+                size!(col_count: col_count, row_count: row_count),
+            )
+            .list,
+        );
+
+        let scroll_offset = self
+            .help_overlay_scroll_offset
+            .min(lines.len().saturating_sub(visible_row_count));
+        let text_width = col_count - 4;
+        for (row_index, (line, maybe_style)) in lines
+            .iter()
+            .skip(scroll_offset)
+            .take(visible_row_count)
+            .enumerate()
+        {
+            render_help_overlay_line(
+                &mut render_ops,
+                origin_pos + position!(col_index: 1, row_index: row_index + 1),
+                line,
+                *maybe_style,
+                text_width,
+            );
+        }
+
+        render_pipeline.push(ZOrder::Glass, render_ops);
+        render_pipeline
+    }
+}
+
+/// All the `conflicts` are in the message of the error, one per line.
+fn new_conflict_error_result(conflicts: &[String]) -> CommonResult<()> {
+    CommonError::new_error_result(
+        CommonErrorType::InvalidArguments,
+        &conflicts.join("\n"),
+    )
+}
+
+/// The number of lines of the help overlay that fit in the window (w/ its border & a
+/// margin of 1 row above & below it).
+fn calc_help_overlay_visible_row_count(window_size: Size) -> usize {
+    ch!(@to_usize window_size.row_count).saturating_sub(4)
+}
+
+/// The line is padded to the width of the help overlay, so that the content of the app
+/// below it is cleared.
+fn render_help_overlay_line(
+    render_ops: &mut RenderOps,
+    pos: Position,
+    line: &str,
+    maybe_style: Option<TuiStyle>,
+    text_width: usize,
+) {
+    let line = UnicodeString::from(line);
+    let text = UnicodeString::from(line.truncate_end_to_fit_width(ch!(text_width)))
+        .pad_end_with_spaces_to_fit_width(" ", ch!(text_width));
+    render_ops.push(RenderOp::MoveCursorPositionAbs(pos));
+    render_ops.push(RenderOp::ApplyColors(maybe_style));
+    render_ops.push(RenderOp::PaintTextWithAttributes(
+        format!(" {text} "),
+        maybe_style,
+    ));
+    render_ops.push(RenderOp::ResetColor);
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{keypress, ModifierKeysMask};
+
+    fn ctrl(character: char) -> KeyPress {
+        keypress!(@char ModifierKeysMask::new().with_ctrl(), character)
+    }
+
+    /// The app has a save shortcut, and 2 editors have the same find shortcut.
+    fn make_shortcut_registry() -> ShortcutRegistry {
+        let mut shortcut_registry = ShortcutRegistry::default();
+        for (scope, group) in [
+            (
+                ShortcutScope::App,
+                ShortcutGroup::new("App").add(ctrl('s'), "Save"),
+            ),
+            (
+                ShortcutScope::Component(FlexBoxId::from(1)),
+                ShortcutGroup::new("Left editor").add(ctrl('f'), "Find"),
+            ),
+            (
+                ShortcutScope::Component(FlexBoxId::from(2)),
+                ShortcutGroup::new("Right editor").add(ctrl('f'), "Find"),
+            ),
+        ] {
+            assert!(shortcut_registry.try_register_group(scope, group).is_ok());
+        }
+        shortcut_registry
+    }
+
+    #[test]
+    fn test_conflicts_are_detected() {
+        let mut shortcut_registry = make_shortcut_registry();
+
+        // A component can't use one of the app's shortcuts.
+        let result = shortcut_registry.try_register(
+            ShortcutScope::Component(FlexBoxId::from(2)),
+            "Right editor",
+            Shortcut::new(ctrl('s'), "Save as"),
+        );
+        let error = result.err().unwrap();
+        let CommonError {
+            error_message: msg, ..
+        } = error.downcast_ref::<CommonError>().unwrap();
+        assert_eq2!(
+            msg.as_ref().unwrap(),
+            "Shortcut Ctrl+S (Save as) in Right editor conflicts w/ Save in App."
+        );
+
+        // The app can't use one of a component's shortcuts.
+        assert!(shortcut_registry
+            .try_register(
+                ShortcutScope::App,
+                "App",
+                Shortcut::new(ctrl('f'), "Search")
+            )
+            .is_err());
+
+        // The ones that don't conflict are still registered.
+        let group = ShortcutGroup::new("Left editor")
+            .add(ctrl('f'), "Find again")
+            .add(ctrl('g'), "Go to line");
+        assert!(shortcut_registry
+            .try_register_group(ShortcutScope::Component(FlexBoxId::from(1)), group)
+            .is_err());
+        let groups = shortcut_registry.get_groups(&HasFocus::default());
+        assert_eq2!(groups.len(), 3);
+        assert_eq2!(groups[1].shortcuts.len(), 2);
+        assert_eq2!(groups[1].shortcuts[1].description, "Go to line".to_string());
+    }
+
+    #[test]
+    fn test_help_overlay_lines_are_grouped() {
+        let shortcut_registry = make_shortcut_registry();
+        let mut has_focus = HasFocus::default();
+        has_focus.set_id(FlexBoxId::from(2));
+
+        // The focused component's group comes right after the app's.
+        let lines: Vec<String> = shortcut_registry
+            .get_help_overlay_lines(&has_focus)
+            .into_iter()
+            .map(|(line, _)| line)
+            .collect();
+        assert_eq2!(
+            lines,
+            vec![
+                "App".to_string(),
+                "  Ctrl+S  Save".to_string(),
+                "".to_string(),
+                "Right editor".to_string(),
+                "  Ctrl+F  Find".to_string(),
+                "".to_string(),
+                "Left editor".to_string(),
+                "  Ctrl+F  Find".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_help_overlay_handles_input_events() {
+        let mut shortcut_registry = make_shortcut_registry();
+        let window_size = size!(col_count: 40, row_count: 7);
+        let esc = InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
+        let down = InputEvent::Keyboard(keypress!(@special SpecialKey::Down));
+        let help = InputEvent::Keyboard(HELP_OVERLAY_KEY_PRESS);
+
+        // Nothing is handled while it is hidden.
+        assert_eq2!(
+            shortcut_registry.handle_input_event(&down, window_size),
+            None
+        );
+        assert!(ShortcutRegistry::is_help_overlay_key(&help));
+        assert!(shortcut_registry
+            .render(window_size, &HasFocus::default())
+            .get(&ZOrder::Glass)
+            .is_none());
+
+        shortcut_registry.show_help_overlay();
+        assert!(shortcut_registry
+            .render(window_size, &HasFocus::default())
+            .get(&ZOrder::Glass)
+            .is_some());
+
+        // 3 of the 8 lines fit, so it scrolls down until the last line is shown.
+        for _ in 0..5 {
+            assert_eq2!(
+                shortcut_registry.handle_input_event(&down, window_size),
+                Some(EventPropagation::ConsumedRender)
+            );
+        }
+        assert_eq2!(
+            shortcut_registry.handle_input_event(&down, window_size),
+            Some(EventPropagation::Consumed)
+        );
+
+        // Other events don't get to the app.
+        assert_eq2!(
+            shortcut_registry
+                .handle_input_event(&InputEvent::Keyboard(ctrl('s')), window_size),
+            Some(EventPropagation::Consumed)
+        );
+
+        assert_eq2!(
+            shortcut_registry.handle_input_event(&esc, window_size),
+            Some(EventPropagation::ConsumedRender)
+        );
+        assert!(!shortcut_registry.is_help_overlay_shown());
+    }
+}
@@ -0,0 +1,324 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug,
+          sync::{Arc, Mutex},
+          time::{Duration, Instant}};
+
+use r3bl_core::{ch, position, send_signal, Size, TuiStyle, UnicodeString};
+use tokio::sync::mpsc::Sender;
+
+use crate::{paint_spans,
+            render_ops,
+            render_pipeline,
+            RenderPipeline,
+            TerminalWindowMainThreadSignal,
+            ZOrder};
+
+/// How often the spinner moves (while the splash screen is shown).
+pub const SPLASH_SCREEN_TICK_INTERVAL: Duration = Duration::from_millis(80);
+
+pub const SPLASH_SCREEN_SPINNER_FRAMES: [&str; 10] =
+    ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// The number of progress messages that are painted below the logo. The older ones are
+/// dropped.
+pub const SPLASH_SCREEN_MAX_MESSAGE_COUNT: usize = 3;
+
+#[derive(Debug, Default)]
+struct SplashScreenState {
+    /// In the order that they were added.
+    messages: Vec<String>,
+    is_ready: bool,
+    /// There is a task that asks for a render on each tick, until the splash screen is
+    /// ready (so that the spinner moves).
+    is_ticking: bool,
+}
+
+/// The first frame of an app, which is shown while the app performs its (async) startup
+/// work, eg: loading a config, or connecting to a server. It is painted in the middle of
+/// the window, w/ a logo, a spinner, and the last few progress messages, eg:
+///
+/// ```text
+///       R3BL CMDR
+///
+///  ✔ Loaded config
+///  ⠹ Connecting…
+/// ```
+///
+/// Return one of these from [crate::App::app_start_splash_screen] (which is called before
+/// [crate::App::app_init]), and move a [SplashHandle] (from
+/// [SplashScreen::get_handle]) into the background (tokio) task that does the startup
+/// work. The app doesn't get any input events (other than the exit keys) and isn't
+/// rendered until [SplashHandle::finish] is called, and then it replaces the splash
+/// screen.
+///
+/// This is cheap to clone (the clones share the same messages).
+#[derive(Clone, Debug)]
+pub struct SplashScreen {
+    /// Each line is centered (so the lines of ASCII art should have the same width).
+    pub logo: String,
+    pub maybe_logo_style: Option<TuiStyle>,
+    inner: Arc<Mutex<SplashScreenState>>,
+    created_at: Instant,
+}
+
+impl SplashScreen {
+    pub fn new(logo: impl Into<String>) -> Self {
+        Self {
+            logo: logo.into(),
+            maybe_logo_style: Some(TuiStyle {
+                bold: true,
+                ..Default::default()
+            }),
+            inner: Default::default(),
+            created_at: Instant::now(),
+        }
+    }
+
+    /// Returns a handle to add progress messages, and to replace the splash screen w/
+    /// the app once the startup work is done. This must be called from a tokio runtime.
+    pub fn get_handle<AS>(
+        &self,
+        main_thread_channel_sender: &Sender<TerminalWindowMainThreadSignal<AS>>,
+    ) -> SplashHandle<AS>
+    where
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        let splash_handle = SplashHandle {
+            splash_screen: self.clone(),
+            main_thread_channel_sender: main_thread_channel_sender.clone(),
+        };
+        splash_handle.start_ticking();
+        splash_handle
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.inner.lock().map(|it| it.is_ready).unwrap_or(true)
+    }
+
+    /// Returns the last [SPLASH_SCREEN_MAX_MESSAGE_COUNT] progress messages, in the order
+    /// that they were added.
+    pub fn get_messages(&self) -> Vec<String> {
+        let Ok(state) = self.inner.lock() else {
+            return vec![];
+        };
+        state.messages.clone()
+    }
+
+    /// Returns the frame of the spinner at `now`, see [SPLASH_SCREEN_SPINNER_FRAMES].
+    pub fn get_tick(&self, now: Instant) -> usize {
+        (now.saturating_duration_since(self.created_at).as_millis()
+            / SPLASH_SCREEN_TICK_INTERVAL.as_millis()) as usize
+    }
+
+    /// Returns the lines to paint (the logo, an empty line, and the progress messages),
+    /// where the last message has the spinner in front of it.
+    fn get_lines(&self, tick: usize) -> Vec<(String, Option<TuiStyle>)> {
+        let mut lines: Vec<(String, Option<TuiStyle>)> = self
+            .logo
+            .lines()
+            .map(|it| (it.to_string(), self.maybe_logo_style))
+            .collect();
+        lines.push((String::new(), None));
+
+        let spinner =
+            SPLASH_SCREEN_SPINNER_FRAMES[tick % SPLASH_SCREEN_SPINNER_FRAMES.len()];
+        let messages = self.get_messages();
+        let Some((last_message, done_messages)) = messages.split_last() else {
+            lines.push((spinner.to_string(), None));
+            return lines;
+        };
+        let done_style = TuiStyle {
+            dim: true,
+            ..Default::default()
+        };
+        for message in done_messages {
+            lines.push((format!("✔ {message}"), Some(done_style)));
+        }
+        lines.push((format!("{spinner} {last_message}"), None));
+        lines
+    }
+
+    /// Returns the [RenderPipeline] to paint the splash screen in the middle of the
+    /// window. The lines that don't fit are clipped.
+    pub fn render(&self, window_size: Size, tick: usize) -> RenderPipeline {
+        let mut render_ops = render_ops!();
+        let col_count = ch!(@to_usize window_size.col_count);
+        let row_count = ch!(@to_usize window_size.row_count);
+
+        let lines = self.get_lines(tick);
+        let first_row_index = row_count.saturating_sub(lines.len()) / 2;
+        for (row_index, (text, maybe_style)) in lines.into_iter().enumerate() {
+            let row_index = first_row_index + row_index;
+            if row_index >= row_count {
+                break;
+            }
+            let text = UnicodeString::from(text.as_str());
+            let text = text.truncate_end_to_fit_width(ch!(col_count)).to_string();
+            let col_index =
+                col_count.saturating_sub(UnicodeString::str_display_width(&text)) / 2;
+            paint_spans(
+                &mut render_ops,
+                position!(col_index: col_index, row_index: row_index),
+                vec![(text, maybe_style)],
+            );
+        }
+
+        let mut render_pipeline = render_pipeline!();
+        render_pipeline.push(ZOrder::Normal, render_ops);
+        render_pipeline
+    }
+}
+
+/// Adds progress messages to a [SplashScreen], and replaces it w/ the app, eg: from the
+/// background task that does the startup work. It is cheap to clone. Each update asks
+/// the main event loop to render again. All of these must be called from a tokio
+/// runtime.
+#[derive(Clone, Debug)]
+pub struct SplashHandle<AS>
+where
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    splash_screen: SplashScreen,
+    main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
+}
+
+impl<AS> SplashHandle<AS>
+where
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    /// Add a progress message, eg: `Loading config…`.
+    pub fn set_message(&self, message: impl Into<String>) {
+        let message = message.into();
+        self.update(|state| {
+            state.messages.push(message);
+            let excess_count = state
+                .messages
+                .len()
+                .saturating_sub(SPLASH_SCREEN_MAX_MESSAGE_COUNT);
+            state.messages.drain(..excess_count);
+        });
+    }
+
+    /// The startup work is done, so the app is rendered (instead of the splash screen)
+    /// from now on.
+    pub fn finish(&self) { self.update(|state| state.is_ready = true); }
+
+    fn update(&self, update_state: impl FnOnce(&mut SplashScreenState)) {
+        if let Ok(mut state) = self.splash_screen.inner.lock() {
+            update_state(&mut state);
+        }
+        send_signal!(
+            self.main_thread_channel_sender,
+            TerminalWindowMainThreadSignal::RequestRender(None)
+        );
+    }
+
+    /// Ask for a render on each tick, until the splash screen is ready.
+    fn start_ticking(&self) {
+        match self.splash_screen.inner.lock() {
+            Ok(mut state) if !state.is_ticking && !state.is_ready => {
+                state.is_ticking = true
+            }
+            _ => return,
+        }
+        let splash_handle = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(SPLASH_SCREEN_TICK_INTERVAL).await;
+                if splash_handle.splash_screen.is_ready() {
+                    break;
+                }
+                let _ = splash_handle
+                    .main_thread_channel_sender
+                    .send(TerminalWindowMainThreadSignal::RequestRender(None))
+                    .await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::{RenderOp, CHANNEL_WIDTH};
+
+    #[tokio::test]
+    async fn test_splash_handle_updates_splash_screen() {
+        let (sender, mut receiver) =
+            mpsc::channel::<TerminalWindowMainThreadSignal<()>>(CHANNEL_WIDTH);
+        let splash_screen = SplashScreen::new("r3bl");
+        let splash_handle = splash_screen.get_handle(&sender);
+
+        // The handle can be moved into a background task.
+        tokio::spawn(async move {
+            for message in ["one", "two", "three", "four"] {
+                splash_handle.set_message(message);
+            }
+            splash_handle.finish();
+        })
+        .await
+        .unwrap();
+
+        assert!(splash_screen.is_ready());
+        assert_eq2!(
+            splash_screen.get_messages(),
+            vec!["two".to_string(), "three".to_string(), "four".to_string()]
+        );
+
+        // The main event loop is asked to render again.
+        assert!(matches!(
+            receiver.recv().await,
+            Some(TerminalWindowMainThreadSignal::RequestRender(None))
+        ));
+    }
+
+    #[test]
+    fn test_render_lines() {
+        let splash_screen = SplashScreen::new("LOGO\nlogo");
+        if let Ok(mut state) = splash_screen.inner.lock() {
+            state.messages = vec!["Loaded".to_string(), "Loading".to_string()];
+        }
+
+        let texts = |render_pipeline: RenderPipeline| -> Vec<String> {
+            render_pipeline
+                .get_all_render_op_in(ZOrder::Normal)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|render_op| match render_op {
+                    RenderOp::PaintTextWithAttributes(text, _) => Some(text),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        assert_eq2!(
+            texts(splash_screen.render(size!(col_count: 20, row_count: 10), 1)),
+            vec!["LOGO", "logo", "", "✔ Loaded", "⠙ Loading"]
+        );
+
+        // The lines that don't fit are clipped.
+        assert_eq2!(
+            texts(splash_screen.render(size!(col_count: 3, row_count: 2), 0)),
+            vec!["LOG", "log"]
+        );
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_core::{throws_with_return, CommonResult};
+
+use crate::{render_pipeline,
+            BoxedSafeComponent,
+            Component,
+            EventPropagation,
+            FlexBox,
+            FlexBoxId,
+            GlobalData,
+            HasFocus,
+            InputEvent,
+            RenderPipeline,
+            StatusBar,
+            SurfaceBounds,
+            ZOrder};
+
+/// This is called before the status bar is rendered, to update its segments from the
+/// app's state, eg: w/ [StatusBar::set_mode] or [StatusBar::set_caret_position].
+pub type OnStatusBarUpdateFn<S> = fn(&S, &mut StatusBar);
+
+/// This is a re-usable component that paints a [StatusBar] in the first row of its box.
+/// It doesn't handle any input events.
+///
+/// The status bar is owned by the component (and not the app's state), since the app's
+/// state is where its segments are derived from. The `on_status_bar_update_handler` is
+/// called each time that the component is rendered, to update them.
+#[derive(Debug, Default)]
+pub struct StatusBarComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub data: StatusBarComponentData<S, AS>,
+}
+
+#[derive(Debug, Default)]
+pub struct StatusBarComponentData<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub status_bar: StatusBar,
+    pub on_status_bar_update_handler: Option<OnStatusBarUpdateFn<S>>,
+    _phantom: std::marker::PhantomData<AS>,
+}
+
+impl<S, AS> Component<S, AS> for StatusBarComponent<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    fn reset(&mut self) {}
+
+    fn get_id(&self) -> FlexBoxId { self.data.id }
+
+    fn render(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        current_box: FlexBox,
+        _surface_bounds: SurfaceBounds, /* Ignore this. */
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<RenderPipeline> {
+        throws_with_return!({
+            let StatusBarComponentData {
+                status_bar,
+                on_status_bar_update_handler,
+                ..
+            } = &mut self.data;
+
+            if let Some(on_status_bar_update_handler) = on_status_bar_update_handler {
+                on_status_bar_update_handler(&global_data.state, status_bar);
+            }
+
+            let mut render_pipeline = render_pipeline!();
+            render_pipeline.push(
+                ZOrder::Normal,
+                status_bar.render(
+                    current_box.style_adjusted_origin_pos,
+                    current_box.style_adjusted_bounds_size.col_count,
+                ),
+            );
+            render_pipeline
+        });
+    }
+
+    fn handle_event(
+        &mut self,
+        _global_data: &mut GlobalData<S, AS>,
+        _input_event: InputEvent,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        Ok(EventPropagation::Propagate)
+    }
+}
+
+pub mod constructor {
+    use super::*;
+
+    impl<S, AS> StatusBarComponent<S, AS>
+    where
+        S: Debug + Default + Clone + Sync + Send + 'static,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        /// The `status_bar` has the segments (and their alignments & priorities) that
+        /// the `on_status_bar_update` handler updates.
+        pub fn new(
+            id: FlexBoxId,
+            status_bar: StatusBar,
+            on_status_bar_update: OnStatusBarUpdateFn<S>,
+        ) -> Self {
+            Self {
+                data: StatusBarComponentData {
+                    id,
+                    status_bar,
+                    on_status_bar_update_handler: Some(on_status_bar_update),
+                    ..Default::default()
+                },
+            }
+        }
+
+        pub fn new_boxed(
+            id: FlexBoxId,
+            status_bar: StatusBar,
+            on_status_bar_update: OnStatusBarUpdateFn<S>,
+        ) -> BoxedSafeComponent<S, AS> {
+            Box::new(StatusBarComponent::new(
+                id,
+                status_bar,
+                on_status_bar_update,
+            ))
+        }
+    }
+}
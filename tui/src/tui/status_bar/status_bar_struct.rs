@@ -0,0 +1,476 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::cmp::Reverse;
+
+use chrono::Timelike;
+use r3bl_core::{ch, position, ChUnit, Position, TuiStyle, UnicodeString};
+use serde::{Deserialize, Serialize};
+
+use crate::{render_ops, RenderOp, RenderOps, EDITOR_TAB_DIRTY_INDICATOR};
+
+/// The id of the segment that is updated by [StatusBar::set_mode].
+pub const STATUS_BAR_MODE_SEGMENT_ID: &str = "mode";
+/// The id of the segment that is updated by [StatusBar::set_file_name].
+pub const STATUS_BAR_FILE_NAME_SEGMENT_ID: &str = "file_name";
+/// The id of the segment that is updated by [StatusBar::set_caret_position].
+pub const STATUS_BAR_CARET_SEGMENT_ID: &str = "caret";
+/// The id of the segment that is updated by [StatusBar::set_time].
+pub const STATUS_BAR_TIME_SEGMENT_ID: &str = "time";
+
+/// This is painted between the segments that have the same alignment.
+pub const DEFAULT_STATUS_BAR_SEPARATOR: &str = " │ ";
+
+/// This is painted at the end of a segment that is clipped, because it doesn't fit by
+/// itself.
+pub const STATUS_BAR_ELLIPSIS: &str = "…";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum StatusBarAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+/// A piece of text in a [StatusBar], which is looked up by its `id` to update it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusBarSegment {
+    pub id: String,
+    pub text: String,
+    pub maybe_style: Option<TuiStyle>,
+    pub alignment: StatusBarAlignment,
+    /// When the segments don't all fit, the ones w/ the lowest priority are dropped
+    /// first.
+    pub priority: u8,
+}
+
+impl StatusBarSegment {
+    pub fn new(
+        id: impl Into<String>,
+        text: impl Into<String>,
+        alignment: StatusBarAlignment,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+            maybe_style: None,
+            alignment,
+            priority: 0,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_style(mut self, style: TuiStyle) -> Self {
+        self.maybe_style = Some(style);
+        self
+    }
+}
+
+/// A piece of text that is painted at `col_index` (relative to the start of the status
+/// bar), which is returned by [StatusBar::calc_layout].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusBarSpan {
+    pub col_index: ChUnit,
+    pub text: String,
+    pub maybe_style: Option<TuiStyle>,
+}
+
+/// A single row that has segments on the left, in the center, and on the right. Segments
+/// w/ the same alignment are separated by the `separator`, and they are painted in the
+/// order that they were added. Segments w/ empty text are hidden.
+///
+/// When the segments don't all fit, the ones w/ the lowest [StatusBarSegment::priority]
+/// are dropped (the last one, if there is a tie) until the rest do. If a single segment
+/// is left that still doesn't fit, it is clipped.
+///
+/// There are methods to update the segments that most apps have (mode, file name, caret
+/// position & time), eg: [Self::set_mode], which add them the first time that they are
+/// called. Other segments are added w/ [Self::set_segment] and updated w/
+/// [Self::set_text].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusBar {
+    pub segments: Vec<StatusBarSegment>,
+    pub separator: String,
+    pub maybe_separator_style: Option<TuiStyle>,
+    /// This is applied to the whole row, under the styles of the segments.
+    pub maybe_style: Option<TuiStyle>,
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self {
+            segments: vec![],
+            separator: DEFAULT_STATUS_BAR_SEPARATOR.to_string(),
+            maybe_separator_style: Some(TuiStyle {
+                dim: true,
+                ..Default::default()
+            }),
+            maybe_style: None,
+        }
+    }
+}
+
+impl StatusBar {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn get_segment(&self, id: &str) -> Option<&StatusBarSegment> {
+        self.segments.iter().find(|it| it.id == id)
+    }
+
+    /// Replace the segment w/ the same id, or add it after the others.
+    pub fn set_segment(&mut self, segment: StatusBarSegment) {
+        match self.segments.iter_mut().find(|it| it.id == segment.id) {
+            Some(it) => *it = segment,
+            None => self.segments.push(segment),
+        }
+    }
+
+    /// Update the text of the segment w/ the given id. Returns `false` if there isn't
+    /// one.
+    pub fn set_text(&mut self, id: &str, text: impl Into<String>) -> bool {
+        match self.segments.iter_mut().find(|it| it.id == id) {
+            Some(segment) => {
+                segment.text = text.into();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove_segment(&mut self, id: &str) -> Option<StatusBarSegment> {
+        let index = self.segments.iter().position(|it| it.id == id)?;
+        Some(self.segments.remove(index))
+    }
+
+    /// Update the text of the segment w/ the given id, or add it (w/ the given alignment
+    /// & priority) if there isn't one. The alignment & priority of an existing segment
+    /// are kept, so that the app can change them.
+    fn set_text_or_add(
+        &mut self,
+        id: &str,
+        text: String,
+        alignment: StatusBarAlignment,
+        priority: u8,
+    ) {
+        if !self.set_text(id, text.clone()) {
+            self.segments
+                .push(StatusBarSegment::new(id, text, alignment).with_priority(priority));
+        }
+    }
+
+    /// The mode of the app (eg: `NORMAL` or `INSERT`) is on the left, and it is the last
+    /// segment to be dropped.
+    pub fn set_mode(&mut self, mode: impl Into<String>) {
+        self.set_text_or_add(
+            STATUS_BAR_MODE_SEGMENT_ID,
+            mode.into(),
+            StatusBarAlignment::Left,
+            u8::MAX,
+        );
+    }
+
+    /// The file name is in the center, followed by a dot if it has unsaved changes.
+    pub fn set_file_name(&mut self, file_name: impl Into<String>, is_dirty: bool) {
+        let file_name = file_name.into();
+        let text = match is_dirty {
+            true => format!("{file_name} {EDITOR_TAB_DIRTY_INDICATOR}"),
+            false => file_name,
+        };
+        self.set_text_or_add(
+            STATUS_BAR_FILE_NAME_SEGMENT_ID,
+            text,
+            StatusBarAlignment::Center,
+            2,
+        );
+    }
+
+    /// The caret position is on the right, eg: `Ln 3, Col 7` (starting at 1, like the
+    /// line number gutter of the editor).
+    pub fn set_caret_position(&mut self, caret: Position) {
+        self.set_text_or_add(
+            STATUS_BAR_CARET_SEGMENT_ID,
+            format!("Ln {}, Col {}", *caret.row_index + 1, *caret.col_index + 1),
+            StatusBarAlignment::Right,
+            1,
+        );
+    }
+
+    /// The time is on the right (after the caret position), eg: `09:05`, and it is the
+    /// first segment to be dropped. Pass in `chrono::Local::now()` to show the current
+    /// time.
+    pub fn set_time(&mut self, time: impl Timelike) {
+        self.set_text_or_add(
+            STATUS_BAR_TIME_SEGMENT_ID,
+            format!("{:02}:{:02}", time.hour(), time.minute()),
+            StatusBarAlignment::Right,
+            0,
+        );
+    }
+
+    /// Returns the width of the segments (w/ the separators between the ones that have
+    /// the same alignment, and a 1 col gap between the alignments).
+    fn calc_width(&self, segments: &[&StatusBarSegment]) -> usize {
+        let group_widths = [
+            StatusBarAlignment::Left,
+            StatusBarAlignment::Center,
+            StatusBarAlignment::Right,
+        ]
+        .map(|alignment| self.calc_group_width(segments, alignment));
+        let non_empty_group_count = group_widths.iter().filter(|it| **it > 0).count();
+        group_widths.iter().sum::<usize>() + non_empty_group_count.saturating_sub(1)
+    }
+
+    fn calc_group_width(
+        &self,
+        segments: &[&StatusBarSegment],
+        alignment: StatusBarAlignment,
+    ) -> usize {
+        let widths: Vec<usize> = segments
+            .iter()
+            .filter(|it| it.alignment == alignment)
+            .map(|it| UnicodeString::str_display_width(&it.text))
+            .collect();
+        let separator_width = UnicodeString::str_display_width(&self.separator);
+        widths.iter().sum::<usize>() + widths.len().saturating_sub(1) * separator_width
+    }
+
+    /// Returns the texts to paint in a row w/ the given `col_count`, after dropping (or
+    /// clipping) the segments that don't fit.
+    pub fn calc_layout(&self, col_count: ChUnit) -> Vec<StatusBarSpan> {
+        let available = ch!(@to_usize col_count);
+        let mut visible_segments: Vec<StatusBarSegment> = self
+            .segments
+            .iter()
+            .filter(|it| !it.text.is_empty())
+            .cloned()
+            .collect();
+
+        // Drop the segments w/ the lowest priority until the rest fit.
+        while visible_segments.len() > 1
+            && self.calc_width(&visible_segments.iter().collect::<Vec<_>>()) > available
+        {
+            let Some((index, _)) = visible_segments
+                .iter()
+                .enumerate()
+                .min_by_key(|(index, it)| (it.priority, Reverse(*index)))
+            else {
+                break;
+            };
+            visible_segments.remove(index);
+        }
+
+        // Clip the last segment if it still doesn't fit.
+        if let [segment] = visible_segments.as_mut_slice() {
+            let text = UnicodeString::from(segment.text.as_str());
+            if ch!(@to_usize text.display_width) > available {
+                segment.text = match available {
+                    0 => String::new(),
+                    _ => format!(
+                        "{}{STATUS_BAR_ELLIPSIS}",
+                        text.truncate_end_to_fit_width(ch!(available - 1))
+                    ),
+                };
+            }
+        }
+
+        let segments: Vec<&StatusBarSegment> = visible_segments.iter().collect();
+        let left_width = self.calc_group_width(&segments, StatusBarAlignment::Left);
+        let center_width = self.calc_group_width(&segments, StatusBarAlignment::Center);
+        let right_width = self.calc_group_width(&segments, StatusBarAlignment::Right);
+
+        // The center segments are moved (away from the middle) so that they don't overlap
+        // the others.
+        let right_col_index = available.saturating_sub(right_width);
+        let min_center_col_index = match left_width {
+            0 => 0,
+            _ => left_width + 1,
+        };
+        let max_center_col_index = match right_width {
+            0 => right_col_index,
+            _ => right_col_index.saturating_sub(1),
+        }
+        .saturating_sub(center_width);
+        let center_col_index = (available.saturating_sub(center_width) / 2)
+            .min(max_center_col_index)
+            .max(min_center_col_index);
+
+        let mut spans = vec![];
+        for (alignment, start_col_index) in [
+            (StatusBarAlignment::Left, 0),
+            (StatusBarAlignment::Center, center_col_index),
+            (StatusBarAlignment::Right, right_col_index),
+        ] {
+            let mut col_index = start_col_index;
+            let group = segments.iter().filter(|it| it.alignment == alignment);
+            for (index, segment) in group.enumerate() {
+                if index > 0 {
+                    spans.push(StatusBarSpan {
+                        col_index: ch!(col_index),
+                        text: self.separator.clone(),
+                        maybe_style: merge_styles(
+                            self.maybe_style,
+                            self.maybe_separator_style,
+                        ),
+                    });
+                    col_index += UnicodeString::str_display_width(&self.separator);
+                }
+                spans.push(StatusBarSpan {
+                    col_index: ch!(col_index),
+                    text: segment.text.clone(),
+                    maybe_style: merge_styles(self.maybe_style, segment.maybe_style),
+                });
+                col_index += UnicodeString::str_display_width(&segment.text);
+            }
+        }
+        spans
+    }
+
+    /// Returns the [RenderOps] to paint the status bar in the row at `origin_pos`, w/
+    /// the given `col_count`.
+    pub fn render(&self, origin_pos: Position, col_count: ChUnit) -> RenderOps {
+        let mut render_ops = render_ops!();
+
+        // Fill the row, so that the background of the status bar is painted.
+        if self.maybe_style.is_some() {
+            render_ops.push(RenderOp::MoveCursorPositionAbs(origin_pos));
+            render_ops.push(RenderOp::ApplyColors(self.maybe_style));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                " ".repeat(ch!(@to_usize col_count)),
+                self.maybe_style,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+
+        for span in self.calc_layout(col_count) {
+            render_ops.push(RenderOp::MoveCursorPositionAbs(
+                origin_pos + position!(col_index: span.col_index, row_index: 0),
+            ));
+            render_ops.push(RenderOp::ApplyColors(span.maybe_style));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                span.text,
+                span.maybe_style,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+
+        render_ops
+    }
+}
+
+fn merge_styles(
+    maybe_lhs: Option<TuiStyle>,
+    maybe_rhs: Option<TuiStyle>,
+) -> Option<TuiStyle> {
+    match (maybe_lhs, maybe_rhs) {
+        (Some(lhs), Some(rhs)) => Some(lhs + rhs),
+        (maybe_lhs, None) => maybe_lhs,
+        (None, maybe_rhs) => maybe_rhs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    /// Paint the spans into a line, to see where they are.
+    fn to_line(status_bar: &StatusBar, col_count: u16) -> String {
+        let mut line = " ".repeat(usize::from(col_count));
+        for span in status_bar.calc_layout(ch!(col_count)) {
+            let start_index = ch!(@to_usize span.col_index);
+            // The texts in the tests are ASCII, except for the separator.
+            let text = span.text.replace('│', "|");
+            line.replace_range(start_index..start_index + text.len(), &text);
+        }
+        line
+    }
+
+    fn make_status_bar() -> StatusBar {
+        let mut status_bar = StatusBar::new();
+        status_bar.set_mode("INSERT");
+        status_bar.set_file_name("main.rs", true);
+        status_bar.set_caret_position(position!(col_index: 6, row_index: 2));
+        status_bar.set_time(NaiveTime::from_hms_opt(9, 5, 0).unwrap_or_default());
+        status_bar
+    }
+
+    #[test]
+    fn test_set_segments() {
+        let mut status_bar = make_status_bar();
+        assert_eq2!(
+            status_bar
+                .get_segment(STATUS_BAR_FILE_NAME_SEGMENT_ID)
+                .map(|it| it.text.as_str()),
+            Some("main.rs ●")
+        );
+
+        // Updating a segment keeps its position.
+        status_bar.set_mode("NORMAL");
+        assert_eq2!(status_bar.segments[0].text, "NORMAL");
+        assert_eq2!(status_bar.segments.len(), 4);
+
+        assert!(!status_bar.set_text("missing", "text"));
+        assert!(status_bar
+            .remove_segment(STATUS_BAR_TIME_SEGMENT_ID)
+            .is_some());
+        assert_eq2!(status_bar.segments.len(), 3);
+    }
+
+    #[test]
+    fn test_calc_layout() {
+        let mut status_bar = make_status_bar();
+        status_bar.set_file_name("main.rs", false);
+
+        // The center segment is in the middle.
+        assert_eq2!(
+            to_line(&status_bar, 50),
+            format!(
+                "INSERT{}main.rs{}Ln 3, Col 7 | 09:05",
+                " ".repeat(15),
+                " ".repeat(3)
+            )
+        );
+
+        // The time is dropped first, then the caret position, and then the file name,
+        // which is moved so that it doesn't overlap the mode.
+        assert_eq2!(
+            to_line(&status_bar, 30),
+            format!("INSERT{}main.rs Ln 3, Col 7", " ".repeat(5))
+        );
+        assert_eq2!(
+            to_line(&status_bar, 20),
+            format!("INSERT main.rs{}", " ".repeat(6))
+        );
+
+        // The mode is clipped, since it doesn't fit by itself.
+        let spans = status_bar.calc_layout(ch!(4));
+        assert_eq2!(
+            spans,
+            vec![StatusBarSpan {
+                col_index: ch!(0),
+                text: "INS…".to_string(),
+                maybe_style: None,
+            }]
+        );
+    }
+}
@@ -78,7 +78,7 @@ pub fn convert_highlighted_line_from_syntect_to_tui(
 
     // Remove the background color from each style in the theme.
     it.iter_mut()
-        .for_each(|StyleUSSpan { style, text: _ }| style.remove_bg_color());
+        .for_each(|StyleUSSpan { style, .. }| style.remove_bg_color());
 
     return it;
 
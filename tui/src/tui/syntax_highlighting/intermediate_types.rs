@@ -49,10 +49,27 @@ use crate::{constants::{COLON, COMMA, SPACE},
 pub struct StyleUSSpan {
     pub style: TuiStyle,
     pub text: US,
+    /// The URL that the text links to (eg: the text of a markdown link), which is
+    /// painted w/ [crate::RenderOp::SetHyperlink].
+    pub maybe_hyperlink: Option<String>,
 }
 
 impl StyleUSSpan {
-    pub fn new(style: TuiStyle, text: US) -> Self { Self { style, text } }
+    pub fn new(style: TuiStyle, text: US) -> Self {
+        Self {
+            style,
+            text,
+            maybe_hyperlink: None,
+        }
+    }
+
+    pub fn new_hyperlink(style: TuiStyle, text: US, url: String) -> Self {
+        Self {
+            style,
+            text,
+            maybe_hyperlink: Some(url),
+        }
+    }
 }
 
 /// A line of text is made up of multiple [StyleUSSpan]s.
@@ -127,7 +144,7 @@ impl StyleUSSpanLine {
     /// This applies the given style to every single item in the list. It has the highest
     /// specificity.
     pub fn add_style(&mut self, style: TuiStyle) {
-        for StyleUSSpan { style: s, text: _ } in self.iter_mut() {
+        for StyleUSSpan { style: s, .. } in self.iter_mut() {
             *s += style;
         }
     }
@@ -139,6 +156,19 @@ impl StyleUSSpanLine {
         scroll_offset_col_index: ChUnit,
         max_display_col_count: ChUnit,
     ) -> TuiStyledTexts {
+        TuiStyledTexts::from(
+            self.clip_spans(scroll_offset_col_index, max_display_col_count),
+        )
+    }
+
+    /// Same as [Self::clip], but the clipped spans keep their
+    /// [StyleUSSpan::maybe_hyperlink], so that they can be painted w/
+    /// [crate::render_style_us_span_line_into].
+    pub fn clip_spans(
+        &self,
+        scroll_offset_col_index: ChUnit,
+        max_display_col_count: ChUnit,
+    ) -> StyleUSSpanLine {
         // Populated and returned at the end.
         let mut list: List<StyleUSSpan> = List::default();
 
@@ -156,6 +186,7 @@ impl StyleUSSpanLine {
             let StyleUSSpan {
                 style,
                 text: formatted_text_unicode_string,
+                maybe_hyperlink,
             } = span;
 
             let mut clipped_text_fragment = String::new();
@@ -187,20 +218,20 @@ impl StyleUSSpanLine {
             }
 
             if !clipped_text_fragment.is_empty() {
-                list.push(StyleUSSpan::new(*style, US::from(clipped_text_fragment)));
+                list.push(StyleUSSpan {
+                    style: *style,
+                    text: US::from(clipped_text_fragment),
+                    maybe_hyperlink: maybe_hyperlink.clone(),
+                });
             }
         }
 
-        TuiStyledTexts::from(list)
+        list
     }
 
     pub fn display_width(&self) -> ChUnit {
         let mut size = ch!(0);
-        for StyleUSSpan {
-            style: _,
-            text: item,
-        } in self.iter()
-        {
+        for StyleUSSpan { text: item, .. } in self.iter() {
             size += item.display_width;
         }
         size
@@ -208,11 +239,7 @@ impl StyleUSSpanLine {
 
     pub fn get_plain_text(&self) -> String {
         let mut plain_text = String::new();
-        for StyleUSSpan {
-            style: _,
-            text: item,
-        } in self.iter()
-        {
+        for StyleUSSpan { text: item, .. } in self.iter() {
             plain_text.push_str(&item.string);
         }
         plain_text
@@ -241,7 +268,7 @@ mod convert {
     impl From<StyleUSSpanLine> for TuiStyledTexts {
         fn from(styles: StyleUSSpanLine) -> Self {
             let mut acc = TuiStyledTexts::default();
-            for StyleUSSpan { style, text } in styles.iter() {
+            for StyleUSSpan { style, text, .. } in styles.iter() {
                 acc += tui_styled_text!(@style: *style, @text: text.string.clone());
             }
             acc
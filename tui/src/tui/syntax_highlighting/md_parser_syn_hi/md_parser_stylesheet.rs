@@ -43,6 +43,194 @@ pub fn get_selection_style() -> TuiStyle {
     }
 }
 
+/// This style is for the line numbers that are painted in the editor's gutter.
+pub fn get_line_number_gutter_style() -> TuiStyle {
+    tui_style! {
+        attrib: [dim]
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5f5f5f")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(59)), // Grey37.
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+            _ => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+        }
+    }
+}
+
+/// This style is for the bracket that matches the one at the caret.
+pub fn get_matching_bracket_style() -> TuiStyle {
+    tui_style! {
+        attrib: [bold, underline]
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#ffaf00")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(214)), // Orange1.
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            _ => TuiColor::Basic(ANSIBasicColor::Yellow),
+        }
+    }
+}
+
+/// This style is for the secondary carets in the editor (when there is more than one
+/// caret). The primary caret is painted in reverse video, so these are painted w/ a
+/// different background color to tell them apart.
+pub fn get_secondary_caret_style() -> TuiStyle {
+    tui_style! {
+        color_fg: TuiColor::Basic(ANSIBasicColor::Black)
+        color_bg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5fafd7")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(74)), // SkyBlue3.
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::Grey),
+            _ => TuiColor::Basic(ANSIBasicColor::Cyan),
+        }
+    }
+}
+
+/// This style is for the matches of the search that is active in the editor (see
+/// [crate::EditorBuffer::set_search_query]), and for the markers in the line number
+/// gutter that show where the matches that are out of view are.
+pub fn get_search_match_style() -> TuiStyle {
+    tui_style! {
+        color_fg: TuiColor::Basic(ANSIBasicColor::Black)
+        color_bg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#d7d75f")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(185)), // Khaki3.
+            ColorSupport::Grayscale => TuiColor::Basic(ANSIBasicColor::White),
+            _ => TuiColor::Basic(ANSIBasicColor::Yellow),
+        }
+    }
+}
+
+/// This style is for the markers in the editor's line number gutter for lines that were
+/// added since the buffer was last loaded or saved (see [crate::ChangeMarkersMode]).
+pub fn get_added_line_marker_style() -> TuiStyle {
+    tui_style! {
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5fd75f")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(77)), // PaleGreen3.
+            _ => TuiColor::Basic(ANSIBasicColor::Green),
+        }
+    }
+}
+
+/// This style is for the gutter markers of lines that were modified.
+pub fn get_modified_line_marker_style() -> TuiStyle {
+    tui_style! {
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5fafff")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(75)), // SteelBlue1.
+            _ => TuiColor::Basic(ANSIBasicColor::Blue),
+        }
+    }
+}
+
+/// This style is for the gutter markers of lines that were removed.
+pub fn get_removed_line_marker_style() -> TuiStyle {
+    tui_style! {
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#ff5f5f")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(203)), // IndianRed1.
+            _ => TuiColor::Basic(ANSIBasicColor::Red),
+        }
+    }
+}
+
+/// This style is for the bookmark indicators that are painted in the line number gutter
+/// in place of the line numbers (see [crate::EditorBookmarks]).
+pub fn get_bookmark_style() -> TuiStyle {
+    tui_style! {
+        attrib: [bold]
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#d787ff")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(177)), // Violet.
+            _ => TuiColor::Basic(ANSIBasicColor::Magenta),
+        }
+    }
+}
+
+/// This style is for the glyphs that are painted in place of invisible characters (eg:
+/// trailing spaces), see [crate::WhitespaceRenderMode].
+pub fn get_whitespace_marker_style() -> TuiStyle {
+    tui_style! {
+        attrib: [dim]
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5f5f5f")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(59)), // Grey37.
+            _ => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+        }
+    }
+}
+
+/// This style is for the words that the editor's [crate::SpellChecker] flags as
+/// misspelled. It is a curly underline (a squiggle) in terminals that support it, and a
+/// plain underline in the others.
+pub fn get_misspelled_word_style() -> TuiStyle {
+    let color = match global_color_support::detect() {
+        ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#ff5f5f")),
+        ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(203)), // IndianRed1.
+        _ => TuiColor::Basic(ANSIBasicColor::Red),
+    };
+    tui_style! {
+        attrib: [curly_underline]
+        color_fg: color
+        color_underline: color
+    }
+}
+
+/// This style is for the tabs in the [crate::EditorTabBarComponent], except the active
+/// one.
+pub fn get_editor_tab_style() -> TuiStyle {
+    tui_style! {
+        color_fg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#8a8a8a")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(245)), // Grey54.
+            _ => TuiColor::Basic(ANSIBasicColor::Grey),
+        }
+        color_bg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#262626")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(235)), // Grey15.
+            _ => TuiColor::Basic(ANSIBasicColor::Black),
+        }
+    }
+}
+
+/// This style is for the active tab in the [crate::EditorTabBarComponent].
+pub fn get_editor_tab_active_style() -> TuiStyle {
+    tui_style! {
+        attrib: [bold]
+        color_fg: TuiColor::Basic(ANSIBasicColor::White)
+        color_bg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5f5fd7")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(62)), // SlateBlue3.
+            _ => TuiColor::Basic(ANSIBasicColor::Blue),
+        }
+    }
+}
+
+/// This style is for the items in the editor's completion popup (see
+/// [crate::CompletionProvider]).
+pub fn get_completion_popup_style() -> TuiStyle {
+    tui_style! {
+        color_fg: TuiColor::Basic(ANSIBasicColor::White)
+        color_bg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#303030")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(236)), // Grey19.
+            _ => TuiColor::Basic(ANSIBasicColor::DarkGrey),
+        }
+    }
+}
+
+/// This style is for the selected item in the editor's completion popup.
+pub fn get_completion_popup_selected_style() -> TuiStyle {
+    tui_style! {
+        attrib: [bold]
+        color_fg: TuiColor::Basic(ANSIBasicColor::White)
+        color_bg: match global_color_support::detect() {
+            ColorSupport::Truecolor => TuiColor::Rgb(RgbValue::from_hex("#5f5fd7")),
+            ColorSupport::Ansi256 => TuiColor::Ansi(AnsiValue::new(62)), // SlateBlue3.
+            _ => TuiColor::Basic(ANSIBasicColor::Blue),
+        }
+    }
+}
+
 /// This style is for the foreground text of the entire document. This is the default
 /// style. It is overridden by other styles like bold, italic, etc. below.
 pub fn get_foreground_style() -> TuiStyle {
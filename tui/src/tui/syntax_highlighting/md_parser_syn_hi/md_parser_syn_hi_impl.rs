@@ -447,6 +447,8 @@ enum HyperlinkType {
 }
 
 impl StyleUSSpan {
+    /// The link text & the URL are both painted as a link to the URL (so they can be
+    /// clicked in terminals that support hyperlinks).
     fn format_hyperlink_data(
         link_data: &HyperlinkData<'_>,
         maybe_current_box_computed_style: &Option<TuiStyle>,
@@ -473,7 +475,11 @@ impl StyleUSSpan {
                     HyperlinkType::Image => LEFT_IMAGE,
                 }),
             ),
-            StyleUSSpan::new(link_text_style, US::from(link_text)),
+            StyleUSSpan::new_hyperlink(
+                link_text_style,
+                US::from(link_text),
+                link_url.clone(),
+            ),
             StyleUSSpan::new(
                 base_style,
                 US::from(match hyperlink_type {
@@ -483,7 +489,11 @@ impl StyleUSSpan {
             ),
             // (link_url)
             StyleUSSpan::new(base_style, US::from(LEFT_PARENTHESIS)),
-            StyleUSSpan::new(link_url_style, US::from(link_url)),
+            StyleUSSpan::new_hyperlink(
+                link_url_style,
+                US::from(link_url.clone()),
+                link_url,
+            ),
             StyleUSSpan::new(base_style, US::from(RIGHT_PARENTHESIS)),
         ]
     }
@@ -623,7 +633,7 @@ impl PrettyPrintDebug for StyleUSSpanLine {
     fn pretty_print_debug(&self) -> String {
         let mut it = vec![];
         for span in &self.inner {
-            let StyleUSSpan { style, text } = span;
+            let StyleUSSpan { style, text, .. } = span;
             let line_text = format!("fragment[ {:?} , {:?} ]", text.string, style);
             it.push(line_text);
         }
@@ -850,13 +860,14 @@ mod tests_style_us_span_lines_from {
                     .unwrap_or(TuiColor::Basic(ANSIBasicColor::White));
                 assert_eq2!(
                     actual,
-                    &StyleUSSpan::new(
+                    &StyleUSSpan::new_hyperlink(
                         style
                             + tui_style! {
                                 color_fg: actual_style_color_fg
                                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
                             },
-                        US::from("R3BL")
+                        US::from("R3BL"),
+                        "https://r3bl.com".to_string(),
                     )
                 )
             };
@@ -912,14 +923,15 @@ mod tests_style_us_span_lines_from {
                     .unwrap_or(TuiColor::Basic(ANSIBasicColor::White));
                 assert_eq2!(
                     actual,
-                    &StyleUSSpan::new(
+                    &StyleUSSpan::new_hyperlink(
                         style
                             + tui_style! {
                                 attrib: [underline]
                                 color_fg: actual_style_color_fg
                                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
                             },
-                        US::from("https://r3bl.com")
+                        US::from("https://r3bl.com"),
+                        "https://r3bl.com".to_string(),
                     )
                 );
             }
@@ -1296,7 +1308,7 @@ mod tests_style_us_span_lines_from {
 
             let line_0 = &lines.inner[0];
             let span_0_in_line_0 = &line_0.inner[0];
-            let StyleUSSpan { style, text } = span_0_in_line_0;
+            let StyleUSSpan { style, text, .. } = span_0_in_line_0;
             assert_eq2!(text.string, "Foobar");
             assert_eq2!(style, &(*style + get_foreground_style()));
         }
@@ -15,10 +15,30 @@
  *   limitations under the License.
  */
 
-use std::io::{BufReader, Cursor};
+use std::{io::{BufReader, Cursor},
+          path::Path};
 
+use serde::{Deserialize, Serialize};
 use syntect::highlighting::{Theme, ThemeSet};
 
+/// The name of the syntect theme that is used when no other theme can be loaded.
+pub const DEFAULT_SYNTECT_THEME_NAME: &str = "base16-ocean.dark";
+
+/// Where the [Theme] that is used for syntax highlighting in the editor comes from, see
+/// [crate::EditorEngineConfig::syntax_theme].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyntaxThemeSource {
+    /// The r3bl theme that is bundled w/ this crate.
+    R3bl,
+    /// One of the themes that are bundled w/ syntect, eg: `InspiredGitHub` or
+    /// [DEFAULT_SYNTECT_THEME_NAME].
+    Syntect(String),
+    /// The path to a `.tmTheme` file.
+    File(String),
+    /// The `<name>.tmTheme` file in the `themes_dir`.
+    Named { themes_dir: String, name: String },
+}
+
 pub fn try_load_r3bl_theme() -> std::io::Result<Theme> {
     // Load bytes from file asset.
     let theme_bytes = include_bytes!("assets/r3bl.tmTheme");
@@ -42,14 +62,59 @@ pub fn try_load_r3bl_theme() -> std::io::Result<Theme> {
 
 pub fn load_default_theme() -> Theme {
     let theme_set = ThemeSet::load_defaults();
-    theme_set.themes["base16-ocean.dark"].clone()
+    theme_set.themes[DEFAULT_SYNTECT_THEME_NAME].clone()
+}
+
+pub fn try_load_theme_from_file(path: impl AsRef<Path>) -> std::io::Result<Theme> {
+    ThemeSet::get_theme(path.as_ref()).map_err(|error| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Failed to load theme from {}: {error}",
+                path.as_ref().display()
+            ),
+        )
+    })
+}
+
+pub fn try_load_theme(source: &SyntaxThemeSource) -> std::io::Result<Theme> {
+    match source {
+        SyntaxThemeSource::R3bl => try_load_r3bl_theme(),
+        SyntaxThemeSource::Syntect(name) => {
+            let mut theme_set = ThemeSet::load_defaults();
+            theme_set.themes.remove(name).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("No syntect theme named {name}"),
+                )
+            })
+        }
+        SyntaxThemeSource::File(path) => try_load_theme_from_file(path),
+        SyntaxThemeSource::Named { themes_dir, name } => try_load_theme_from_file(
+            Path::new(themes_dir).join(format!("{name}.tmTheme")),
+        ),
+    }
+}
+
+/// Load the theme from the `source`, and if that fails, fall back to the r3bl theme, and
+/// then to the [DEFAULT_SYNTECT_THEME_NAME] theme.
+pub fn load_theme_or_default(source: &SyntaxThemeSource) -> Theme {
+    try_load_theme(source).unwrap_or_else(|error| {
+        tracing::error!("Failed to load the syntax theme {source:?}: {error}");
+        try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme())
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use r3bl_core::throws;
 
-    use crate::try_load_r3bl_theme;
+    use crate::{load_default_theme,
+                load_theme_or_default,
+                try_load_r3bl_theme,
+                try_load_theme,
+                SyntaxThemeSource,
+                DEFAULT_SYNTECT_THEME_NAME};
 
     /// Use a [std::io::Cursor] as a fake [std::fs::File]:
     /// <https://stackoverflow.com/a/41069910/2085356>
@@ -60,4 +125,40 @@ mod tests {
             dbg!(&theme);
         });
     }
+
+    #[test]
+    fn load_theme_from_sources() -> std::io::Result<()> {
+        throws!({
+            let theme = try_load_theme(&SyntaxThemeSource::Syntect(
+                DEFAULT_SYNTECT_THEME_NAME.to_string(),
+            ))?;
+            assert_eq!(theme, load_default_theme());
+
+            // Write the bundled theme to a themes dir, and load it by name.
+            let themes_dir = std::env::temp_dir()
+                .join(format!("r3bl_tui_test_themes_{}", std::process::id()));
+            std::fs::create_dir_all(&themes_dir)?;
+            std::fs::write(
+                themes_dir.join("r3bl.tmTheme"),
+                include_bytes!("assets/r3bl.tmTheme"),
+            )?;
+            let theme = try_load_theme(&SyntaxThemeSource::Named {
+                themes_dir: themes_dir.to_string_lossy().to_string(),
+                name: "r3bl".to_string(),
+            })?;
+            assert_eq!(theme, try_load_r3bl_theme()?);
+            std::fs::remove_dir_all(&themes_dir)?;
+        });
+    }
+
+    #[test]
+    fn load_missing_theme_falls_back() {
+        let source = SyntaxThemeSource::File("no/such/theme.tmTheme".to_string());
+        assert!(try_load_theme(&source).is_err());
+        assert_eq!(
+            load_theme_or_default(&source),
+            try_load_r3bl_theme().unwrap()
+        );
+        assert!(try_load_theme(&SyntaxThemeSource::Syntect("nope".to_string())).is_err());
+    }
 }
@@ -0,0 +1,715 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::cmp::Ordering;
+
+use r3bl_core::{ch, ChUnit, CommonResult, Position, Size};
+use serde::{Deserialize, Serialize};
+
+use crate::{Button,
+            ConstraintLayout,
+            InputEvent,
+            Key,
+            KeyPress,
+            LayoutConstraint,
+            MouseInput,
+            MouseInputKind,
+            SpecialKey};
+
+/// The direction that the rows of a [TableViewBuffer] are sorted in. The indicator is
+/// painted after the title of the column that the rows are sorted by.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    pub fn toggle(self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+
+    pub fn get_indicator(self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "▲",
+            SortDirection::Descending => "▼",
+        }
+    }
+}
+
+/// Returns the text that is painted in a cell for the given row.
+pub type TableCellTextFn<R> = fn(&R) -> String;
+
+/// Compares 2 rows, to sort them by a column.
+pub type TableRowCompareFn<R> = fn(&R, &R) -> Ordering;
+
+/// A column of a [TableViewBuffer], which gets the text of its cells from the rows w/
+/// `get_cell_text`.
+#[derive(Clone, Debug)]
+pub struct TableColumn<R> {
+    pub title: String,
+    /// The width of the column, which is shared w/ the other columns like the segments
+    /// of a [ConstraintLayout].
+    pub constraints: Vec<LayoutConstraint>,
+    pub get_cell_text: TableCellTextFn<R>,
+    /// The rows are sorted by the text of their cells when this is [None], which isn't
+    /// what you want for numbers or dates.
+    pub maybe_compare: Option<TableRowCompareFn<R>>,
+    pub is_sortable: bool,
+}
+
+impl<R> TableColumn<R> {
+    pub fn new(
+        title: impl Into<String>,
+        constraints: Vec<LayoutConstraint>,
+        get_cell_text: TableCellTextFn<R>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            constraints,
+            get_cell_text,
+            maybe_compare: None,
+            is_sortable: true,
+        }
+    }
+
+    pub fn with_compare(mut self, compare: TableRowCompareFn<R>) -> Self {
+        self.maybe_compare = Some(compare);
+        self
+    }
+
+    pub fn not_sortable(mut self) -> Self {
+        self.is_sortable = false;
+        self
+    }
+
+    fn compare(&self, lhs: &R, rhs: &R) -> Ordering {
+        match self.maybe_compare {
+            Some(compare) => compare(lhs, rhs),
+            None => (self.get_cell_text)(lhs).cmp(&(self.get_cell_text)(rhs)),
+        }
+    }
+}
+
+/// What happened when an [InputEvent] was applied to a [TableViewBuffer] w/
+/// [TableViewBuffer::apply_event].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableViewApplyResponse {
+    /// A different row was selected (see [TableViewBuffer::get_selected_row]).
+    SelectionChanged,
+    /// The selected row was activated, w/ <kbd>Enter</kbd> or by clicking on it again.
+    Activated,
+    /// The rows were sorted by a different column, or in the other direction.
+    Sorted,
+    /// The rows were scrolled w/ the mouse wheel, but the selection didn't change.
+    Scrolled,
+    /// The key was handled, but nothing changed, eg: <kbd>Down</kbd> at the last row.
+    Consumed,
+    /// The event wasn't for the table view, so it should be passed on.
+    Noop,
+}
+
+/// The rows & columns of a table (or a list, see [Self::new_list]) that are displayed
+/// by a [crate::TableViewComponent]. This is kept in the app's state (see
+/// [crate::HasTableViewBuffers]), so that the app can change the rows, and read the
+/// selection in its handlers.
+///
+/// The rows are never reordered. Sorting changes the order that they are displayed in, so
+/// the indices that are passed to [Self::select] (and returned by
+/// [Self::get_selected_index]) are display indices, while [Self::get_selected_row]
+/// returns the row itself.
+///
+/// Key bindings:
+/// - <kbd>Up</kbd>, <kbd>Down</kbd>, <kbd>PageUp</kbd>, <kbd>PageDown</kbd>,
+///   <kbd>Home</kbd> & <kbd>End</kbd> move the selection.
+/// - <kbd>Enter</kbd> activates the selected row.
+/// - <kbd>s</kbd> sorts by the next sortable column, and <kbd>r</kbd> reverses the sort
+///   direction.
+///
+/// The mouse wheel scrolls the rows, clicking on a row selects it (and clicking on it
+/// again activates it), and clicking on the title of a column sorts by it (or reverses
+/// the direction, if the rows are already sorted by it).
+#[derive(Clone, Debug)]
+pub struct TableViewBuffer<R> {
+    pub columns: Vec<TableColumn<R>>,
+    pub is_header_visible: bool,
+    rows: Vec<R>,
+    /// The indices of the rows, in the order that they are displayed in.
+    display_order: Vec<usize>,
+    maybe_sort: Option<(usize, SortDirection)>,
+    /// A display index.
+    maybe_selected_index: Option<usize>,
+    /// The display index of the first row in the viewport.
+    scroll_offset: usize,
+    /// The bounds & column widths from the last call to [Self::calc_layout], which are
+    /// needed to scroll & to handle mouse clicks.
+    maybe_last_layout: Option<TableViewLayout>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TableViewLayout {
+    origin_pos: Position,
+    bounds_size: Size,
+    column_widths: Vec<ChUnit>,
+}
+
+impl<R> Default for TableViewBuffer<R> {
+    fn default() -> Self {
+        Self {
+            columns: vec![],
+            is_header_visible: true,
+            rows: vec![],
+            display_order: vec![],
+            maybe_sort: None,
+            maybe_selected_index: None,
+            scroll_offset: 0,
+            maybe_last_layout: None,
+        }
+    }
+}
+
+impl<R> TableViewBuffer<R> {
+    pub fn new(columns: Vec<TableColumn<R>>) -> Self {
+        Self {
+            columns,
+            ..Default::default()
+        }
+    }
+
+    /// A list is a table w/ a single column that takes up all the width, and no header.
+    pub fn new_list(get_item_text: TableCellTextFn<R>) -> Self {
+        Self {
+            columns: vec![TableColumn::new(
+                "",
+                vec![LayoutConstraint::Fill(1)],
+                get_item_text,
+            )],
+            is_header_visible: false,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_rows(mut self, rows: Vec<R>) -> Self {
+        self.set_rows(rows);
+        self
+    }
+
+    /// Replace the rows, keeping the sort order. The first row is selected (if there
+    /// are any).
+    pub fn set_rows(&mut self, rows: Vec<R>) {
+        self.rows = rows;
+        self.display_order = (0..self.rows.len()).collect();
+        self.apply_sort();
+        self.scroll_offset = 0;
+        self.maybe_selected_index = (!self.rows.is_empty()).then_some(0);
+    }
+
+    pub fn get_rows(&self) -> &[R] { &self.rows }
+
+    pub fn len(&self) -> usize { self.rows.len() }
+
+    pub fn is_empty(&self) -> bool { self.rows.is_empty() }
+
+    /// Returns the row at the given display index.
+    pub fn get_row_at(&self, display_index: usize) -> Option<&R> {
+        let row_index = *self.display_order.get(display_index)?;
+        self.rows.get(row_index)
+    }
+
+    pub fn get_selected_index(&self) -> Option<usize> { self.maybe_selected_index }
+
+    pub fn get_selected_row(&self) -> Option<&R> {
+        self.get_row_at(self.maybe_selected_index?)
+    }
+
+    pub fn get_scroll_offset(&self) -> usize { self.scroll_offset }
+
+    pub fn get_sort(&self) -> Option<(usize, SortDirection)> { self.maybe_sort }
+
+    /// Select the row at the given display index (which is clamped), and scroll it into
+    /// view. Returns `true` if the selection changed.
+    pub fn select(&mut self, display_index: usize) -> bool {
+        if self.rows.is_empty() {
+            return false;
+        }
+        let display_index = display_index.min(self.rows.len() - 1);
+        let has_changed = self.maybe_selected_index != Some(display_index);
+        self.maybe_selected_index = Some(display_index);
+        self.scroll_selection_into_view();
+        has_changed
+    }
+
+    /// Sort the rows by the column at `column_index`, or reverse the direction if they
+    /// are already sorted by it. The selected row stays selected. Returns `false` if the
+    /// column isn't sortable.
+    pub fn sort_by_column(&mut self, column_index: usize) -> bool {
+        match self.columns.get(column_index) {
+            Some(column) if column.is_sortable => {}
+            _ => return false,
+        }
+        let direction = match self.maybe_sort {
+            Some((index, direction)) if index == column_index => direction.toggle(),
+            _ => SortDirection::Ascending,
+        };
+        self.maybe_sort = Some((column_index, direction));
+
+        let maybe_selected_row_index = self
+            .maybe_selected_index
+            .and_then(|it| self.display_order.get(it).copied());
+        self.apply_sort();
+        if let Some(row_index) = maybe_selected_row_index {
+            self.maybe_selected_index =
+                self.display_order.iter().position(|it| *it == row_index);
+            self.scroll_selection_into_view();
+        }
+        true
+    }
+
+    fn apply_sort(&mut self) {
+        let Some((column_index, direction)) = self.maybe_sort else {
+            return;
+        };
+        let Some(column) = self.columns.get(column_index) else {
+            return;
+        };
+        let rows = &self.rows;
+        // The sort is stable, so rows that are equal stay in their original order.
+        self.display_order.sort_by(|lhs, rhs| {
+            let ordering = column.compare(&rows[*lhs], &rows[*rhs]);
+            match direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+    }
+
+    /// Returns the width of each column, in a box w/ the given `bounds_size`, and
+    /// remembers them (and the bounds) for [Self::apply_event].
+    pub fn calc_layout(
+        &mut self,
+        origin_pos: Position,
+        bounds_size: Size,
+    ) -> CommonResult<Vec<ChUnit>> {
+        let column_widths = ConstraintLayout::new(
+            self.columns
+                .iter()
+                .map(|it| it.constraints.clone())
+                .collect(),
+        )
+        .calc_segment_sizes(bounds_size.col_count)?;
+        self.maybe_last_layout = Some(TableViewLayout {
+            origin_pos,
+            bounds_size,
+            column_widths: column_widths.clone(),
+        });
+        self.scroll_selection_into_view();
+        Ok(column_widths)
+    }
+
+    /// The number of rows that fit in the box from the last call to
+    /// [Self::calc_layout] (below the header), or [None] if it wasn't called yet.
+    pub fn get_viewport_row_count(&self) -> Option<usize> {
+        let layout = self.maybe_last_layout.as_ref()?;
+        let row_count = ch!(@to_usize layout.bounds_size.row_count);
+        Some(match self.is_header_visible {
+            true => row_count.saturating_sub(1),
+            false => row_count,
+        })
+    }
+
+    fn get_max_scroll_offset(&self) -> usize {
+        let viewport_row_count = self.get_viewport_row_count().unwrap_or_default();
+        self.rows.len().saturating_sub(viewport_row_count)
+    }
+
+    fn scroll_selection_into_view(&mut self) {
+        let Some(viewport_row_count) = self.get_viewport_row_count() else {
+            return;
+        };
+        if let Some(selected_index) = self.maybe_selected_index {
+            if selected_index < self.scroll_offset {
+                self.scroll_offset = selected_index;
+            } else if viewport_row_count > 0
+                && selected_index >= self.scroll_offset + viewport_row_count
+            {
+                self.scroll_offset = selected_index + 1 - viewport_row_count;
+            }
+        }
+        self.scroll_offset = self.scroll_offset.min(self.get_max_scroll_offset());
+    }
+
+    /// Scroll the rows w/out changing the selection. Returns `true` if they scrolled.
+    fn scroll_by(&mut self, delta: isize) -> bool {
+        let scroll_offset = self
+            .scroll_offset
+            .saturating_add_signed(delta)
+            .min(self.get_max_scroll_offset());
+        let has_changed = scroll_offset != self.scroll_offset;
+        self.scroll_offset = scroll_offset;
+        has_changed
+    }
+
+    fn move_selection_by(&mut self, delta: isize) -> bool {
+        let display_index = match self.maybe_selected_index {
+            Some(selected_index) => selected_index.saturating_add_signed(delta),
+            None => 0,
+        };
+        self.select(display_index)
+    }
+
+    fn get_next_sortable_column_index(&self) -> Option<usize> {
+        let start_index = match self.maybe_sort {
+            Some((column_index, _)) => column_index + 1,
+            None => 0,
+        };
+        (0..self.columns.len())
+            .map(|offset| (start_index + offset) % self.columns.len())
+            .find(|index| self.columns[*index].is_sortable)
+    }
+
+    /// Returns the index of the column at the given col (relative to the box).
+    fn get_column_index_at(&self, col_index: ChUnit) -> Option<usize> {
+        let layout = self.maybe_last_layout.as_ref()?;
+        let mut end_col_index = ch!(0);
+        layout.column_widths.iter().position(|width| {
+            end_col_index += *width;
+            col_index < end_col_index
+        })
+    }
+
+    pub fn apply_event(&mut self, input_event: InputEvent) -> TableViewApplyResponse {
+        let page_size = self.get_viewport_row_count().unwrap_or(1).max(1) as isize;
+
+        let has_selection_changed = match input_event {
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(special_key),
+            }) => match special_key {
+                SpecialKey::Up => self.move_selection_by(-1),
+                SpecialKey::Down => self.move_selection_by(1),
+                SpecialKey::PageUp => self.move_selection_by(-page_size),
+                SpecialKey::PageDown => self.move_selection_by(page_size),
+                SpecialKey::Home => self.select(0),
+                SpecialKey::End => self.select(self.rows.len().saturating_sub(1)),
+                SpecialKey::Enter if self.maybe_selected_index.is_some() => {
+                    return TableViewApplyResponse::Activated;
+                }
+                _ => return TableViewApplyResponse::Noop,
+            },
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character(character),
+            }) => {
+                let has_sorted = match character {
+                    's' => match self.get_next_sortable_column_index() {
+                        Some(column_index) => {
+                            // Start w/ the ascending direction, even if the rows are
+                            // already sorted by the only sortable column.
+                            self.maybe_sort = None;
+                            self.sort_by_column(column_index)
+                        }
+                        None => false,
+                    },
+                    'r' => match self.maybe_sort {
+                        Some((column_index, _)) => self.sort_by_column(column_index),
+                        None => false,
+                    },
+                    _ => return TableViewApplyResponse::Noop,
+                };
+                return match has_sorted {
+                    true => TableViewApplyResponse::Sorted,
+                    false => TableViewApplyResponse::Noop,
+                };
+            }
+
+            InputEvent::Mouse(MouseInput { kind, pos, .. }) => {
+                return self.apply_mouse_event(kind, pos);
+            }
+
+            _ => return TableViewApplyResponse::Noop,
+        };
+
+        match has_selection_changed {
+            true => TableViewApplyResponse::SelectionChanged,
+            // The key is consumed even if the selection is already at the first (or
+            // last) row, so that it isn't handled by another component.
+            false if self.rows.is_empty() => TableViewApplyResponse::Noop,
+            false => TableViewApplyResponse::Consumed,
+        }
+    }
+
+    fn apply_mouse_event(
+        &mut self,
+        kind: MouseInputKind,
+        pos: Position,
+    ) -> TableViewApplyResponse {
+        let Some(layout) = self.maybe_last_layout.as_ref() else {
+            return TableViewApplyResponse::Noop;
+        };
+        let origin_pos = layout.origin_pos;
+        let bounds_size = layout.bounds_size;
+
+        match kind {
+            MouseInputKind::ScrollUp | MouseInputKind::ScrollDown => {
+                let delta = match kind {
+                    MouseInputKind::ScrollUp => -1,
+                    _ => 1,
+                };
+                match self.scroll_by(delta) {
+                    true => TableViewApplyResponse::Scrolled,
+                    false => TableViewApplyResponse::Noop,
+                }
+            }
+
+            MouseInputKind::MouseDown(Button::Left) => {
+                if pos.col_index < origin_pos.col_index
+                    || pos.row_index < origin_pos.row_index
+                    || pos.col_index >= origin_pos.col_index + bounds_size.col_count
+                    || pos.row_index >= origin_pos.row_index + bounds_size.row_count
+                {
+                    return TableViewApplyResponse::Noop;
+                }
+                let col_index = pos.col_index - origin_pos.col_index;
+                let mut row_index = ch!(@to_usize pos.row_index - origin_pos.row_index);
+
+                if self.is_header_visible {
+                    if row_index == 0 {
+                        return match self
+                            .get_column_index_at(col_index)
+                            .map(|it| self.sort_by_column(it))
+                        {
+                            Some(true) => TableViewApplyResponse::Sorted,
+                            _ => TableViewApplyResponse::Noop,
+                        };
+                    }
+                    row_index -= 1;
+                }
+
+                let display_index = self.scroll_offset + row_index;
+                if display_index >= self.rows.len() {
+                    return TableViewApplyResponse::Noop;
+                }
+                match self.select(display_index) {
+                    true => TableViewApplyResponse::SelectionChanged,
+                    false => TableViewApplyResponse::Activated,
+                }
+            }
+
+            _ => TableViewApplyResponse::Noop,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, position, size};
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Fruit {
+        name: &'static str,
+        count: u32,
+    }
+
+    fn make_table_view_buffer() -> TableViewBuffer<Fruit> {
+        TableViewBuffer::new(vec![
+            TableColumn::new("Name", vec![LayoutConstraint::Fill(1)], |it: &Fruit| {
+                it.name.to_string()
+            }),
+            TableColumn::new(
+                "Count",
+                vec![LayoutConstraint::Length(ch!(6))],
+                |it: &Fruit| it.count.to_string(),
+            )
+            .with_compare(|lhs, rhs| lhs.count.cmp(&rhs.count)),
+        ])
+        .with_rows(vec![
+            Fruit {
+                name: "cherry",
+                count: 12,
+            },
+            Fruit {
+                name: "apple",
+                count: 3,
+            },
+            Fruit {
+                name: "banana",
+                count: 100,
+            },
+            Fruit {
+                name: "date",
+                count: 7,
+            },
+            Fruit {
+                name: "elderberry",
+                count: 1,
+            },
+        ])
+    }
+
+    fn key(special_key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        })
+    }
+
+    fn mouse(kind: MouseInputKind, col_index: u16, row_index: u16) -> InputEvent {
+        InputEvent::Mouse(MouseInput {
+            pos: position!(col_index: col_index, row_index: row_index),
+            kind,
+            maybe_modifier_keys: None,
+        })
+    }
+
+    fn get_names(buffer: &TableViewBuffer<Fruit>) -> Vec<&'static str> {
+        (0..buffer.len())
+            .filter_map(|it| buffer.get_row_at(it))
+            .map(|it| it.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_keeps_selected_row() {
+        let mut buffer = make_table_view_buffer();
+        buffer.select(1);
+        assert_eq2!(buffer.get_selected_row().map(|it| it.name), Some("apple"));
+
+        // Sort by the text of the cells.
+        assert!(buffer.sort_by_column(0));
+        assert_eq2!(
+            get_names(&buffer),
+            vec!["apple", "banana", "cherry", "date", "elderberry"]
+        );
+        assert_eq2!(buffer.get_selected_index(), Some(0));
+        assert_eq2!(buffer.get_selected_row().map(|it| it.name), Some("apple"));
+
+        // Sort by the column again to reverse the direction.
+        assert!(buffer.sort_by_column(0));
+        assert_eq2!(buffer.get_sort(), Some((0, SortDirection::Descending)));
+        assert_eq2!(buffer.get_selected_index(), Some(4));
+
+        // Sort w/ the compare fn of the column (not the text, where "100" < "12").
+        assert!(buffer.sort_by_column(1));
+        assert_eq2!(
+            get_names(&buffer),
+            vec!["elderberry", "apple", "date", "cherry", "banana"]
+        );
+
+        // The rows themselves aren't reordered.
+        assert_eq2!(buffer.get_rows()[0].name, "cherry");
+    }
+
+    #[test]
+    fn test_selection_scrolls_into_view() -> CommonResult<()> {
+        let mut buffer = make_table_view_buffer();
+        // The header takes up 1 row, which leaves 2 rows in the viewport.
+        let column_widths = buffer.calc_layout(
+            position!(col_index: 0, row_index: 0),
+            size!(col_count: 20, row_count: 3),
+        )?;
+        assert_eq2!(column_widths, vec![ch!(14), ch!(6)]);
+        assert_eq2!(buffer.get_viewport_row_count(), Some(2));
+
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Down)),
+            TableViewApplyResponse::SelectionChanged
+        );
+        assert_eq2!(buffer.get_scroll_offset(), 0);
+        buffer.apply_event(key(SpecialKey::Down));
+        assert_eq2!(buffer.get_selected_index(), Some(2));
+        assert_eq2!(buffer.get_scroll_offset(), 1);
+
+        buffer.apply_event(key(SpecialKey::End));
+        assert_eq2!(buffer.get_selected_index(), Some(4));
+        assert_eq2!(buffer.get_scroll_offset(), 3);
+
+        // The selection stays at the last row.
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::PageDown)),
+            TableViewApplyResponse::Consumed
+        );
+        assert_eq2!(buffer.get_selected_index(), Some(4));
+
+        buffer.apply_event(key(SpecialKey::PageUp));
+        assert_eq2!(buffer.get_selected_index(), Some(2));
+        buffer.apply_event(key(SpecialKey::Home));
+        assert_eq2!(buffer.get_scroll_offset(), 0);
+
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Enter)),
+            TableViewApplyResponse::Activated
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_mouse() -> CommonResult<()> {
+        let mut buffer = make_table_view_buffer();
+        buffer.calc_layout(
+            position!(col_index: 10, row_index: 5),
+            size!(col_count: 20, row_count: 3),
+        )?;
+
+        // Scroll w/out changing the selection.
+        assert_eq2!(
+            buffer.apply_event(mouse(MouseInputKind::ScrollDown, 12, 6)),
+            TableViewApplyResponse::Scrolled
+        );
+        assert_eq2!(buffer.get_scroll_offset(), 1);
+        assert_eq2!(buffer.get_selected_index(), Some(0));
+
+        // Click on the 2nd row in the viewport to select it, and again to activate it.
+        let click = mouse(MouseInputKind::MouseDown(Button::Left), 12, 7);
+        assert_eq2!(
+            buffer.apply_event(click),
+            TableViewApplyResponse::SelectionChanged
+        );
+        assert_eq2!(buffer.get_selected_row().map(|it| it.name), Some("banana"));
+        assert_eq2!(buffer.apply_event(click), TableViewApplyResponse::Activated);
+
+        // Click on the title of the 2nd column to sort by it.
+        assert_eq2!(
+            buffer.apply_event(mouse(MouseInputKind::MouseDown(Button::Left), 26, 5)),
+            TableViewApplyResponse::Sorted
+        );
+        assert_eq2!(buffer.get_sort(), Some((1, SortDirection::Ascending)));
+
+        // Outside of the box.
+        assert_eq2!(
+            buffer.apply_event(mouse(MouseInputKind::MouseDown(Button::Left), 5, 6)),
+            TableViewApplyResponse::Noop
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_list() {
+        let mut buffer = TableViewBuffer::new_list(|it: &String| it.clone())
+            .with_rows(vec!["b".to_string(), "a".to_string()]);
+        assert!(!buffer.is_header_visible);
+        assert_eq2!(
+            buffer.apply_event(InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character('s'),
+            })),
+            TableViewApplyResponse::Sorted
+        );
+        assert_eq2!(buffer.get_row_at(0).map(String::as_str), Some("a"));
+    }
+}
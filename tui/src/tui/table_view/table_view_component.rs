@@ -0,0 +1,360 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use r3bl_core::{ch,
+                position,
+                throws_with_return,
+                ChUnit,
+                CommonResult,
+                Position,
+                TuiStyle,
+                UnicodeString};
+
+use crate::{render_ops,
+            render_pipeline,
+            BoxedSafeComponent,
+            Component,
+            EventPropagation,
+            FlexBox,
+            FlexBoxId,
+            GlobalData,
+            HasFocus,
+            HasTableViewBuffers,
+            InputEvent,
+            OnTableViewEventFn,
+            RenderOp,
+            RenderOps,
+            RenderPipeline,
+            SurfaceBounds,
+            TableViewApplyResponse,
+            TableViewBuffer,
+            ZOrder};
+
+/// This is a re-usable component that displays the rows of a [TableViewBuffer] (which is
+/// kept in the app's state, see [HasTableViewBuffers]) in a table, w/ a header that has
+/// the titles of the columns. Use [TableViewBuffer::new_list] to display a list instead.
+///
+/// Register it in the [crate::ComponentRegistryMap] like the editor component, and route
+/// the input events (including mouse events, w/
+/// [crate::ComponentRegistry::route_mouse_event_to_component_at_pos]) to it. See
+/// [TableViewBuffer] for the key bindings. The `on_table_view_event_handler` is called
+/// when the selection changes, a row is activated, or the rows are sorted.
+#[derive(Debug)]
+pub struct TableViewComponent<S, AS, R>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+    R: Debug + Clone + Sync + Send,
+{
+    pub data: TableViewComponentData<S, AS, R>,
+}
+
+#[derive(Debug)]
+pub struct TableViewComponentData<S, AS, R>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+    R: Debug + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub on_table_view_event_handler: Option<OnTableViewEventFn<S, AS>>,
+    _phantom: PhantomData<R>,
+}
+
+impl<S, AS, R> Component<S, AS> for TableViewComponent<S, AS, R>
+where
+    S: HasTableViewBuffers<R> + Default + Clone + Debug + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+    R: Debug + Clone + Sync + Send,
+{
+    fn reset(&mut self) {}
+
+    fn get_id(&self) -> FlexBoxId { self.data.id }
+
+    fn is_focusable(&self) -> bool { true }
+
+    fn render(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        current_box: FlexBox,
+        _surface_bounds: SurfaceBounds, /* Ignore this. */
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<RenderPipeline> {
+        throws_with_return!({
+            let mut render_pipeline = render_pipeline!();
+            let has_focus = has_focus.does_current_box_have_focus(current_box);
+            if let Some(buffer) =
+                global_data.state.get_mut_table_view_buffer(self.data.id)
+            {
+                render_pipeline.push(
+                    ZOrder::Normal,
+                    render_table_view(buffer, &current_box, has_focus)?,
+                );
+            }
+            render_pipeline
+        });
+    }
+
+    fn handle_event(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        input_event: InputEvent,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        throws_with_return!({
+            let TableViewComponentData {
+                id,
+                on_table_view_event_handler,
+                ..
+            } = &self.data;
+
+            let Some(buffer) = global_data.state.get_mut_table_view_buffer(*id) else {
+                return Ok(EventPropagation::Propagate);
+            };
+
+            let response = buffer.apply_event(input_event);
+            match response {
+                TableViewApplyResponse::Noop => EventPropagation::Propagate,
+                TableViewApplyResponse::Consumed => EventPropagation::Consumed,
+                TableViewApplyResponse::Scrolled => EventPropagation::ConsumedRender,
+                TableViewApplyResponse::SelectionChanged
+                | TableViewApplyResponse::Activated
+                | TableViewApplyResponse::Sorted => {
+                    if let Some(on_table_view_event_handler) = on_table_view_event_handler
+                    {
+                        on_table_view_event_handler(
+                            *id,
+                            response,
+                            &mut global_data.state,
+                            &mut global_data.main_thread_channel_sender,
+                        );
+                    }
+                    EventPropagation::ConsumedRender
+                }
+            }
+        });
+    }
+}
+
+fn get_table_view_header_style() -> TuiStyle {
+    TuiStyle {
+        bold: true,
+        underline: true,
+        ..Default::default()
+    }
+}
+
+/// The selected row is dimmed when the table view doesn't have focus.
+fn get_table_view_selected_row_style(has_focus: bool) -> TuiStyle {
+    TuiStyle {
+        reverse: true,
+        dim: !has_focus,
+        ..Default::default()
+    }
+}
+
+/// Paint the header (if it is visible) and the rows in the viewport of the `buffer`,
+/// clipping each cell to the width of its column.
+fn render_table_view<R>(
+    buffer: &mut TableViewBuffer<R>,
+    current_box: &FlexBox,
+    has_focus: bool,
+) -> CommonResult<RenderOps> {
+    let origin_pos = current_box.style_adjusted_origin_pos;
+    let column_widths =
+        buffer.calc_layout(origin_pos, current_box.style_adjusted_bounds_size)?;
+
+    let mut render_ops = render_ops!();
+    let mut row_index = 0;
+
+    if buffer.is_header_visible {
+        let titles: Vec<String> = buffer
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(column_index, column)| match buffer.get_sort() {
+                Some((sort_column_index, direction))
+                    if sort_column_index == column_index =>
+                {
+                    format!("{} {}", column.title, direction.get_indicator())
+                }
+                _ => column.title.clone(),
+            })
+            .collect();
+        render_row(
+            &mut render_ops,
+            origin_pos,
+            row_index,
+            &titles,
+            &column_widths,
+            Some(get_table_view_header_style()),
+        );
+        row_index += 1;
+    }
+
+    let scroll_offset = buffer.get_scroll_offset();
+    let viewport_row_count = buffer.get_viewport_row_count().unwrap_or_default();
+    for display_index in scroll_offset..scroll_offset + viewport_row_count {
+        let Some(row) = buffer.get_row_at(display_index) else {
+            break;
+        };
+        let cells: Vec<String> = buffer
+            .columns
+            .iter()
+            .map(|column| (column.get_cell_text)(row))
+            .collect();
+        let maybe_style = (buffer.get_selected_index() == Some(display_index))
+            .then(|| get_table_view_selected_row_style(has_focus));
+        render_row(
+            &mut render_ops,
+            origin_pos,
+            row_index,
+            &cells,
+            &column_widths,
+            maybe_style,
+        );
+        row_index += 1;
+    }
+
+    Ok(render_ops)
+}
+
+/// Each cell is padded to the width of its column (so that the style of a selected row
+/// fills it), and all but the last one leave a 1 col gap before the next column.
+fn render_row(
+    render_ops: &mut RenderOps,
+    origin_pos: Position,
+    row_index: usize,
+    cells: &[String],
+    column_widths: &[ChUnit],
+    maybe_style: Option<TuiStyle>,
+) {
+    let mut line = String::new();
+    for (column_index, (cell, width)) in cells.iter().zip(column_widths).enumerate() {
+        let is_last_column = column_index == column_widths.len() - 1;
+        let text_width = match is_last_column {
+            true => *width,
+            false => *width - 1,
+        };
+        let cell = UnicodeString::from(cell.as_str());
+        let clipped_cell = UnicodeString::from(cell.clip_to_width(ch!(0), text_width));
+        line.push_str(&clipped_cell.pad_end_with_spaces_to_fit_width(" ", *width));
+    }
+
+    render_ops.push(RenderOp::MoveCursorPositionRelTo(
+        origin_pos,
+        position!(col_index: 0, row_index: row_index),
+    ));
+    render_ops.push(RenderOp::ApplyColors(maybe_style));
+    render_ops.push(RenderOp::PaintTextWithAttributes(line, maybe_style));
+    render_ops.push(RenderOp::ResetColor);
+}
+
+pub mod constructor {
+    use super::*;
+
+    impl<S, AS, R> TableViewComponent<S, AS, R>
+    where
+        S: Debug + Default + Clone + Sync + Send + HasTableViewBuffers<R> + 'static,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+        R: Debug + Clone + Sync + Send + 'static,
+    {
+        /// The `on_table_view_event` handler is called when the selection changes, a row
+        /// is activated, or the rows are sorted.
+        pub fn new(
+            id: FlexBoxId,
+            on_table_view_event: OnTableViewEventFn<S, AS>,
+        ) -> Self {
+            Self {
+                data: TableViewComponentData {
+                    id,
+                    on_table_view_event_handler: Some(on_table_view_event),
+                    _phantom: PhantomData,
+                },
+            }
+        }
+
+        pub fn new_boxed(
+            id: FlexBoxId,
+            on_table_view_event: OnTableViewEventFn<S, AS>,
+        ) -> BoxedSafeComponent<S, AS> {
+            Box::new(TableViewComponent::new(id, on_table_view_event))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+    use crate::{LayoutConstraint, TableColumn};
+
+    type Row = (&'static str, u32);
+
+    #[test]
+    fn test_render_table_view() -> CommonResult<()> {
+        let mut buffer = TableViewBuffer::new(vec![
+            TableColumn::new("Name", vec![LayoutConstraint::Fill(1)], |it: &Row| {
+                it.0.to_string()
+            }),
+            TableColumn::new(
+                "Qty",
+                vec![LayoutConstraint::Length(ch!(5))],
+                |it: &Row| it.1.to_string(),
+            ),
+        ])
+        .with_rows(vec![("apple", 3), ("blueberry", 250), ("cherry", 12)]);
+        buffer.sort_by_column(0);
+
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 12, row_count: 3),
+            ..Default::default()
+        };
+        let render_ops = render_table_view(&mut buffer, &current_box, true)?;
+
+        let lines: Vec<(String, Option<TuiStyle>)> = render_ops
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, maybe_style) => {
+                    Some((text.clone(), *maybe_style))
+                }
+                _ => None,
+            })
+            .collect();
+
+        // Only 2 of the 3 rows fit below the header, and the cells are clipped.
+        assert_eq2!(
+            lines,
+            vec![
+                (
+                    "Name ▲ Qty  ".to_string(),
+                    Some(get_table_view_header_style())
+                ),
+                (
+                    "apple  3    ".to_string(),
+                    Some(get_table_view_selected_row_style(true))
+                ),
+                ("bluebe 250  ".to_string(), None),
+            ]
+        );
+        Ok(())
+    }
+}
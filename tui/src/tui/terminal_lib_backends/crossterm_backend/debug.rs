@@ -68,6 +68,17 @@ impl DebugFormatRenderOp for CrosstermDebugFormatRenderOp {
                 RenderOp::PaintTextWithAttributes(text, maybe_style) => {
                     format_print_text("PrintTextWithAttributes", text, maybe_style)
                 }
+                RenderOp::SetHyperlink(maybe_url) =>
+                    format!("SetHyperlink({maybe_url:?})"),
+                RenderOp::BeginSynchronizedUpdate => "BeginSynchronizedUpdate".into(),
+                RenderOp::EndSynchronizedUpdate => "EndSynchronizedUpdate".into(),
+                RenderOp::PaintImage { data, bounds } =>
+                    format!("PaintImage({} bytes, {bounds:?})", data.len()),
+                RenderOp::SetTitle(title) => format!("SetTitle({title:?})"),
+                RenderOp::SetClipboard(content) =>
+                    format!("SetClipboard({} chars)", content.len()),
+                RenderOp::ShowCursor(shape) => format!("ShowCursor({shape:?})"),
+                RenderOp::HideCursor => "HideCursor".into(),
             }
         )
     }
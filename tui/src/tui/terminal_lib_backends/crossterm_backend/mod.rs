@@ -19,8 +19,10 @@
 pub mod debug;
 pub mod offscreen_buffer_paint_impl;
 pub mod render_op_impl;
+pub mod terminal_backend_impl;
 
 // Re-export.
 pub use debug::*;
 pub use offscreen_buffer_paint_impl::*;
 pub use render_op_impl::*;
+pub use terminal_backend_impl::*;
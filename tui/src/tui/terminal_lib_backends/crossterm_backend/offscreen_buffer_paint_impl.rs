@@ -20,6 +20,7 @@ use r3bl_core::{call_if_true,
                 position,
                 ChUnit,
                 LockedOutputDevice,
+                Position,
                 Size,
                 TuiStyle,
                 UnicodeString,
@@ -39,6 +40,24 @@ use crate::{render_ops,
 
 pub struct OffscreenBufferPaintImplCrossterm;
 
+/// Each frame is wrapped in [RenderOp::BeginSynchronizedUpdate] &
+/// [RenderOp::EndSynchronizedUpdate], which are ignored by the backends if the terminal
+/// doesn't support them (see [crate::is_synchronized_update_supported]).
+fn execute_render_op(
+    render_op: RenderOp,
+    skip_flush: &mut bool,
+    window_size: Size,
+    locked_output_device: LockedOutputDevice<'_>,
+    is_mock: bool,
+) {
+    render_ops!(@new render_op).execute_all(
+        skip_flush,
+        window_size,
+        locked_output_device,
+        is_mock,
+    );
+}
+
 impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
     fn paint(
         &mut self,
@@ -50,6 +69,15 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
     ) {
         let mut skip_flush = false;
 
+        // The clear is part of the frame, so that it isn't shown by itself.
+        execute_render_op(
+            RenderOp::BeginSynchronizedUpdate,
+            &mut skip_flush,
+            window_size,
+            locked_output_device,
+            is_mock,
+        );
+
         if let FlushKind::ClearBeforeFlush = flush_kind {
             RenderOp::default().clear_before_flush(locked_output_device);
         }
@@ -62,6 +90,14 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
             is_mock,
         );
 
+        execute_render_op(
+            RenderOp::EndSynchronizedUpdate,
+            &mut skip_flush,
+            window_size,
+            locked_output_device,
+            is_mock,
+        );
+
         // Flush everything to the terminal.
         if !skip_flush {
             RenderOp::default().flush(locked_output_device)
@@ -85,12 +121,26 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
         let mut skip_flush = false;
 
         // Execute each RenderOp.
+        execute_render_op(
+            RenderOp::BeginSynchronizedUpdate,
+            &mut skip_flush,
+            window_size,
+            locked_output_device,
+            is_mock,
+        );
         render_ops.execute_all(
             &mut skip_flush,
             window_size,
             locked_output_device,
             is_mock,
         );
+        execute_render_op(
+            RenderOp::EndSynchronizedUpdate,
+            &mut skip_flush,
+            window_size,
+            locked_output_device,
+            is_mock,
+        );
 
         // Flush everything to the terminal.
         if !skip_flush {
@@ -136,28 +186,39 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
 
             // For each pixel char in the line.
             for (pixel_char_index, pixel_char) in line.iter().enumerate() {
-                let (pixel_char_str, pixel_char_style): (&str, Option<TuiStyle>) =
-                    match pixel_char {
-                        PixelChar::Void => continue,
-                        PixelChar::Spacer => (SPACER, None),
-                        PixelChar::PlainText {
-                            content,
-                            maybe_style,
-                        } => (&content.string, *maybe_style),
-                    };
+                let (pixel_char_str, pixel_char_style, pixel_char_hyperlink): (
+                    &str,
+                    Option<TuiStyle>,
+                    &Option<String>,
+                ) = match pixel_char {
+                    PixelChar::Void => continue,
+                    PixelChar::Spacer => (SPACER, None, &None),
+                    PixelChar::PlainText {
+                        content,
+                        maybe_style,
+                        maybe_hyperlink,
+                    } => (&content.string, *maybe_style, maybe_hyperlink),
+                };
 
                 let is_style_same_as_prev =
                     render_helpers::style_eq(&pixel_char_style, &context.prev_style);
+                let is_hyperlink_same_as_prev =
+                    pixel_char_hyperlink == &context.prev_hyperlink;
                 let is_at_end_of_line = ch!(pixel_char_index) == (ch!(line.len() - 1));
                 let is_first_loop_iteration = row_index == 0 && pixel_char_index == 0;
 
                 // Deal w/: fg and bg colors | text attrib style | ANSI <-> PLAIN switchover.
-                if !is_style_same_as_prev {
+                if !is_style_same_as_prev || !is_hyperlink_same_as_prev {
                     // The style changed / render path has changed and something is already in the
                     // buffer, so flush it!
                     render_helpers::flush_all_buffers(&mut context);
                 }
 
+                // Deal w/: hyperlink (which is painted around the text, when it is flushed).
+                if !is_hyperlink_same_as_prev {
+                    context.prev_hyperlink.clone_from(pixel_char_hyperlink);
+                }
+
                 // Deal w/: fg and bg colors | text attrib style
                 if is_first_loop_iteration || !is_style_same_as_prev {
                     context.render_ops.push(RenderOp::ResetColor);
@@ -191,10 +252,26 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
             render_helpers::flush_all_buffers(&mut context);
         }
 
+        // The images are painted on top of the (cleared) cells. They don't have to be
+        // painted by [Self::render_diff], since the buffers can't be diff'd when the
+        // images change.
+        for image in &offscreen_buffer.images {
+            context.render_ops.push(RenderOp::PaintImage {
+                data: image.data.clone(),
+                bounds: image.bounds,
+            });
+        }
+
         context.render_ops
     }
 
+    /// Generate the [RenderOp]s to paint only the [PixelChar]s that changed. The changed
+    /// [PixelChar]s that are next to each other (in the same line) and have the same
+    /// [TuiStyle] are painted together, so that the cursor only has to be moved (and the
+    /// colors set) once for each run of them, and not for each [PixelChar].
     fn render_diff(&mut self, diff_chunks: &PixelCharDiffChunks) -> RenderOps {
+        use diff_helpers::*;
+
         call_if_true!(DEBUG_TUI_COMPOSITOR, {
             tracing::info!(
                 "🎨 offscreen_buffer_paint_impl_crossterm::render_diff() ok ✅: \ndiff_chunks: \n{}",
@@ -203,35 +280,98 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
         });
 
         let mut it = render_ops!();
+        let mut maybe_run: Option<Run> = None;
 
         for (position, pixel_char) in diff_chunks.iter() {
-            it.push(RenderOp::MoveCursorPositionAbs(*position));
-            it.push(RenderOp::ResetColor);
-            match pixel_char {
+            let (
+                pixel_char_str,
+                pixel_char_style,
+                pixel_char_hyperlink,
+                pixel_char_width,
+            ): (&str, Option<TuiStyle>, &Option<String>, ChUnit) = match pixel_char {
+                // This is painted w/ the wide pixel char before it.
                 PixelChar::Void => continue,
-                PixelChar::Spacer => {
-                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
-                        SPACER.into(),
-                        None,
-                    ))
-                }
+                PixelChar::Spacer => (SPACER, None, &None, ch!(1)),
                 PixelChar::PlainText {
                     content,
                     maybe_style,
-                } => {
-                    it.push(RenderOp::ApplyColors(*maybe_style));
-                    it.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
-                        content.string.clone(),
-                        *maybe_style,
-                    ))
+                    maybe_hyperlink,
+                } => (
+                    &content.string,
+                    *maybe_style,
+                    maybe_hyperlink,
+                    content.unicode_width,
+                ),
+            };
+
+            // Add it to the current run, if it continues it.
+            if let Some(run) = maybe_run.as_mut() {
+                let is_next_to_run = run.pos.row_index == position.row_index
+                    && run.next_col_index == position.col_index;
+                if is_next_to_run
+                    && render_helpers::style_eq(&run.maybe_style, &pixel_char_style)
+                    && &run.maybe_hyperlink == pixel_char_hyperlink
+                {
+                    run.text.push_str(pixel_char_str);
+                    run.next_col_index += pixel_char_width;
+                    continue;
                 }
             }
+
+            if let Some(run) = maybe_run.take() {
+                flush_run(run, &mut it);
+            }
+            maybe_run = Some(Run {
+                pos: *position,
+                next_col_index: position.col_index + pixel_char_width,
+                text: pixel_char_str.to_string(),
+                maybe_style: pixel_char_style,
+                maybe_hyperlink: pixel_char_hyperlink.clone(),
+            });
+        }
+
+        if let Some(run) = maybe_run.take() {
+            flush_run(run, &mut it);
         }
 
         it
     }
 }
 
+mod diff_helpers {
+    use super::*;
+
+    /// Changed [PixelChar]s that are next to each other in a line, w/ the same style (and
+    /// hyperlink).
+    #[derive(Debug, Clone)]
+    pub struct Run {
+        pub pos: Position,
+        pub next_col_index: ChUnit,
+        pub text: String,
+        pub maybe_style: Option<TuiStyle>,
+        pub maybe_hyperlink: Option<String>,
+    }
+
+    pub fn flush_run(run: Run, render_ops: &mut RenderOps) {
+        render_ops.push(RenderOp::MoveCursorPositionAbs(run.pos));
+        render_ops.push(RenderOp::ResetColor);
+        if run.maybe_style.is_some() {
+            render_ops.push(RenderOp::ApplyColors(run.maybe_style));
+        }
+        let is_hyperlink = run.maybe_hyperlink.is_some();
+        if is_hyperlink {
+            render_ops.push(RenderOp::SetHyperlink(run.maybe_hyperlink));
+        }
+        render_ops.push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+            run.text,
+            run.maybe_style,
+        ));
+        if is_hyperlink {
+            render_ops.push(RenderOp::SetHyperlink(None));
+        }
+    }
+}
+
 mod render_helpers {
     use super::*;
 
@@ -241,6 +381,7 @@ mod render_helpers {
         pub display_row_index: ChUnit,
         pub buffer_plain_text: String,
         pub prev_style: Option<TuiStyle>,
+        pub prev_hyperlink: Option<String>,
         pub render_ops: RenderOps,
     }
 
@@ -252,6 +393,7 @@ mod render_helpers {
                 render_ops: render_ops!(),
                 display_row_index: ch!(0),
                 prev_style: None,
+                prev_hyperlink: None,
             }
         }
 
@@ -268,6 +410,8 @@ mod render_helpers {
     /// - `bold`
     /// - `dim`
     /// - `underline`
+    /// - `underline_style`
+    /// - `color_underline`
     /// - `reverse`
     /// - `hidden`
     /// - `strikethrough`
@@ -282,6 +426,8 @@ mod render_helpers {
                     && this.bold == other.bold
                     && this.dim == other.dim
                     && this.underline == other.underline
+                    && this.underline_style == other.underline_style
+                    && this.color_underline == other.color_underline
                     && this.reverse == other.reverse
                     && this.hidden == other.hidden
                     && this.strikethrough == other.strikethrough
@@ -305,13 +451,22 @@ mod render_helpers {
             .render_ops
             .push(RenderOp::MoveCursorPositionAbs(pos));
 
-        // Deal w/ style attribs & actually paint the `temp_line_buffer`.
+        // Deal w/ style attribs & actually paint the `temp_line_buffer` (inside of the
+        // hyperlink, if there is one).
+        if context.prev_hyperlink.is_some() {
+            context
+                .render_ops
+                .push(RenderOp::SetHyperlink(context.prev_hyperlink.clone()));
+        }
         context
             .render_ops
             .push(RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
                 context.buffer_plain_text.to_string(),
                 context.prev_style,
             ));
+        if context.prev_hyperlink.is_some() {
+            context.render_ops.push(RenderOp::SetHyperlink(None));
+        }
 
         // Update `display_col_index_for_line`.
         let plain_text_display_width =
@@ -329,7 +484,10 @@ mod tests {
     use r3bl_macro::tui_style;
 
     use super::*;
-    use crate::render_pipeline_to_offscreen_buffer::print_text_with_attributes;
+    use crate::{render_pipeline_to_offscreen_buffer::print_text_with_attributes,
+                ImageBounds,
+                OffscreenBufferDiffResult,
+                OffscreenImage};
 
     /// Helper function to make an `OffscreenBuffer`.
     fn make_offscreen_buffer_plain_text() -> OffscreenBuffer {
@@ -441,4 +599,136 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_render_diff_joins_adjacent_pixel_chars() {
+        let old_offscreen_buffer = make_offscreen_buffer_plain_text();
+        let mut new_offscreen_buffer = old_offscreen_buffer.clone();
+
+        // Change "hello" to "jelly", and add "ab" to the next line.
+        let style = Some(tui_style! { color_fg: color!(@red) });
+        for (col_index, row_index, text) in
+            [(0, 0, "j"), (4, 0, "y"), (3, 1, "a"), (4, 1, "b")]
+        {
+            new_offscreen_buffer.buffer[row_index][col_index] = PixelChar::PlainText {
+                content: text.into(),
+                maybe_style: style,
+                maybe_hyperlink: None,
+            };
+        }
+
+        let OffscreenBufferDiffResult::Comparable(diff_chunks) =
+            old_offscreen_buffer.diff(&new_offscreen_buffer)
+        else {
+            panic!("The buffers are the same size, so they should be comparable");
+        };
+        assert_eq2!(diff_chunks.len(), 4);
+
+        let mut paint = OffscreenBufferPaintImplCrossterm {};
+        let render_ops = paint.render_diff(&diff_chunks);
+
+        // Each of the 2 pixel chars in the 1st line is painted on its own, and the ones in
+        // the 2nd line are painted together.
+        let paint_ops: Vec<_> = render_ops
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::MoveCursorPositionAbs(pos) => Some(format!("{pos:?}")),
+                RenderOp::CompositorNoClipTruncPaintTextWithAttributes(text, _) => {
+                    Some(text.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq2!(
+            paint_ops,
+            vec![
+                format!("{:?}", position! { col_index: 0, row_index: 0 }),
+                "j".to_string(),
+                format!("{:?}", position! { col_index: 4, row_index: 0 }),
+                "y".to_string(),
+                format!("{:?}", position! { col_index: 3, row_index: 1 }),
+                "ab".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_wraps_hyperlinks() {
+        let window_size = size! { col_count: 6, row_count: 1};
+        let url = "https://r3bl.com".to_string();
+        let old_offscreen_buffer =
+            OffscreenBuffer::new_with_capacity_initialized(window_size);
+        let mut new_offscreen_buffer = old_offscreen_buffer.clone();
+        for (col_index, text) in [(1, "a"), (2, "b")] {
+            new_offscreen_buffer.buffer[0][col_index] = PixelChar::PlainText {
+                content: text.into(),
+                maybe_style: None,
+                maybe_hyperlink: Some(url.clone()),
+            };
+        }
+        let expected_ops = vec![
+            RenderOp::SetHyperlink(Some(url.clone())),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                "ab".to_string(),
+                None,
+            ),
+            RenderOp::SetHyperlink(None),
+        ];
+        let mut paint = OffscreenBufferPaintImplCrossterm {};
+
+        // The linked text is painted on its own, between the spacers before & after it.
+        let render_ops = paint.render(&new_offscreen_buffer);
+        let index = render_ops
+            .iter()
+            .position(|it| matches!(it, RenderOp::SetHyperlink(Some(_))))
+            .unwrap();
+        assert_eq2!(render_ops[index..index + 3].to_vec(), expected_ops);
+        assert_eq2!(
+            render_ops[index + 4],
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                "   ".to_string(),
+                None
+            )
+        );
+
+        let OffscreenBufferDiffResult::Comparable(diff_chunks) =
+            old_offscreen_buffer.diff(&new_offscreen_buffer)
+        else {
+            panic!("The buffers are the same size, so they should be comparable");
+        };
+        let render_ops = paint.render_diff(&diff_chunks);
+        assert_eq2!(render_ops[2..].to_vec(), expected_ops);
+    }
+
+    #[test]
+    fn test_render_paints_images_last() {
+        let window_size = size! { col_count: 6, row_count: 2};
+        let old_offscreen_buffer =
+            OffscreenBuffer::new_with_capacity_initialized(window_size);
+        let mut new_offscreen_buffer = old_offscreen_buffer.clone();
+        let image = OffscreenImage {
+            data: vec![1, 2, 3],
+            bounds: ImageBounds {
+                origin_pos: position! { col_index: 0, row_index: 1 },
+                bounds_size: size! { col_count: 6, row_count: 1 },
+            },
+        };
+        new_offscreen_buffer.images.push(image.clone());
+
+        let render_ops =
+            OffscreenBufferPaintImplCrossterm {}.render(&new_offscreen_buffer);
+        assert_eq2!(
+            render_ops.last(),
+            Some(&RenderOp::PaintImage {
+                data: image.data,
+                bounds: image.bounds,
+            })
+        );
+
+        // Everything is painted again when the images change.
+        assert!(matches!(
+            old_offscreen_buffer.diff(&new_offscreen_buffer),
+            OffscreenBufferDiffResult::NotComparable
+        ));
+    }
 }
@@ -19,15 +19,21 @@ use std::borrow::Cow;
 
 use crossterm::{self,
                 cursor::{Hide, MoveTo, Show},
-                event::{DisableMouseCapture, EnableMouseCapture},
+                event::{DisableBracketedPaste,
+                        DisableMouseCapture,
+                        EnableBracketedPaste,
+                        EnableMouseCapture},
                 style::{Attribute,
                         Print,
                         ResetColor,
                         SetAttribute,
                         SetBackgroundColor,
-                        SetForegroundColor},
-                terminal::{Clear,
+                        SetForegroundColor,
+                        SetUnderlineColor},
+                terminal::{BeginSynchronizedUpdate,
+                           Clear,
                            ClearType,
+                           EndSynchronizedUpdate,
                            EnterAlternateScreen,
                            LeaveAlternateScreen}};
 use r3bl_core::{call_if_true,
@@ -36,17 +42,34 @@ use r3bl_core::{call_if_true,
                 Size,
                 TuiColor,
                 TuiStyle,
+                TuiUnderlineStyle,
                 UnicodeString};
 
 use crate::{crossterm_color_converter::convert_from_tui_color_to_crossterm_color,
             disable_raw_mode_now,
             flush_now,
+            get_clear_images_escape_sequence,
+            get_cursor_shape_escape_sequence,
+            get_hyperlink_escape_sequence,
+            get_image_escape_sequence,
+            get_image_protocol,
+            get_mouse_capture_mode,
+            get_osc52_escape_sequence,
+            get_title_escape_sequence,
+            is_hyperlink_supported,
+            is_inside_tmux,
+            is_synchronized_update_supported,
             queue_render_op,
             sanitize_and_save_abs_position,
             Flush,
+            ImageBounds,
+            MouseCaptureMode,
             PaintRenderOp,
             RenderOp,
-            RenderOpsLocalData};
+            RenderOpsLocalData,
+            POP_TITLE_SEQUENCE,
+            PUSH_TITLE_SEQUENCE,
+            RESET_CURSOR_SHAPE_SEQUENCE};
 
 /// Struct representing the implementation of [RenderOp] for crossterm terminal backend.
 /// This empty struct is needed since the [Flush] trait needs to be implemented.
@@ -106,16 +129,28 @@ mod impl_trait_paint_render_op {
                     )
                 }
                 RenderOp::SetFgColor(color) => {
-                    RenderOpImplCrossterm::set_fg_color(*color, locked_output_device);
+                    RenderOpImplCrossterm::set_fg_color(
+                        *color,
+                        local_data,
+                        locked_output_device,
+                    );
                 }
                 RenderOp::SetBgColor(color) => {
-                    RenderOpImplCrossterm::set_bg_color(*color, locked_output_device);
+                    RenderOpImplCrossterm::set_bg_color(
+                        *color,
+                        local_data,
+                        locked_output_device,
+                    );
                 }
                 RenderOp::ResetColor => {
-                    queue_render_op!(locked_output_device, "ResetColor", ResetColor)
+                    RenderOpImplCrossterm::reset_color(local_data, locked_output_device);
                 }
                 RenderOp::ApplyColors(style) => {
-                    RenderOpImplCrossterm::apply_colors(style, locked_output_device);
+                    RenderOpImplCrossterm::apply_colors(
+                        style,
+                        local_data,
+                        locked_output_device,
+                    );
                 }
                 RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
                     text,
@@ -129,6 +164,58 @@ mod impl_trait_paint_render_op {
                         locked_output_device,
                     );
                 }
+                RenderOp::SetHyperlink(maybe_url) => {
+                    RenderOpImplCrossterm::set_hyperlink(
+                        maybe_url.as_deref(),
+                        locked_output_device,
+                    );
+                }
+                RenderOp::BeginSynchronizedUpdate => {
+                    RenderOpImplCrossterm::set_synchronized_update(
+                        true,
+                        locked_output_device,
+                    );
+                }
+                RenderOp::EndSynchronizedUpdate => {
+                    RenderOpImplCrossterm::set_synchronized_update(
+                        false,
+                        locked_output_device,
+                    );
+                }
+                RenderOp::PaintImage { data, bounds } => {
+                    RenderOpImplCrossterm::paint_image(
+                        data,
+                        *bounds,
+                        window_size,
+                        local_data,
+                        locked_output_device,
+                    );
+                }
+                RenderOp::SetTitle(title) => {
+                    queue_render_op!(
+                        locked_output_device,
+                        format!("SetTitle({title:?})"),
+                        Print(get_title_escape_sequence(title)),
+                    );
+                }
+                RenderOp::SetClipboard(content) => {
+                    queue_render_op!(
+                        locked_output_device,
+                        format!("SetClipboard({} chars)", content.len()),
+                        Print(get_osc52_escape_sequence(content, is_inside_tmux())),
+                    );
+                }
+                RenderOp::ShowCursor(shape) => {
+                    queue_render_op!(
+                        locked_output_device,
+                        format!("ShowCursor({shape:?})"),
+                        Print(get_cursor_shape_escape_sequence(*shape)),
+                        Show,
+                    );
+                }
+                RenderOp::HideCursor => {
+                    queue_render_op!(locked_output_device, "HideCursor", Hide);
+                }
                 RenderOp::PaintTextWithAttributes(_text, _maybe_style) => {
                     // This should never be executed! The compositor always renders to an offscreen
                     // buffer first, then that is diff'd and then painted via calls to
@@ -154,6 +241,9 @@ pub mod impl_trait_flush {
                 ResetColor,
                 Clear(ClearType::All),
             );
+            if let Some(it) = get_clear_images_escape_sequence(get_image_protocol()) {
+                crate::queue_render_op!(locked_output_device, "ClearImages", Print(it));
+            }
         }
     }
 }
@@ -179,16 +269,30 @@ mod impl_self {
             );
         }
 
+        /// This is skipped if the cursor is already at `abs_pos`, eg: when painting text
+        /// that starts right where the previous text ended.
         pub fn move_cursor_position_abs(
             abs_pos: Position,
             window_size: Size,
             local_data: &mut RenderOpsLocalData,
             locked_output_device: LockedOutputDevice<'_>,
         ) {
+            let maybe_prev_cursor_position = local_data
+                .is_cursor_position_known
+                .then_some(local_data.cursor_position);
+
+            let new_abs_pos =
+                sanitize_and_save_abs_position(abs_pos, window_size, local_data);
+            local_data.is_cursor_position_known = true;
+
+            if maybe_prev_cursor_position == Some(new_abs_pos) {
+                return;
+            }
+
             let Position {
                 col_index: col,
                 row_index: row,
-            } = sanitize_and_save_abs_position(abs_pos, window_size, local_data);
+            } = new_abs_pos;
 
             queue_render_op!(
                 locked_output_device,
@@ -204,10 +308,16 @@ mod impl_self {
         ) {
             queue_render_op!(
                 locked_output_device,
-                "ExitRawMode -> Show, LeaveAlternateScreen, DisableMouseCapture",
+                "ExitRawMode -> ResetCursorShape, Show, LeaveAlternateScreen, DisableBracketedPaste, PopTitle",
+                Print(RESET_CURSOR_SHAPE_SEQUENCE),
                 Show,
                 LeaveAlternateScreen,
-                DisableMouseCapture
+                DisableBracketedPaste,
+                Print(POP_TITLE_SEQUENCE),
+            );
+            RenderOpImplCrossterm::disable_mouse_capture(
+                get_mouse_capture_mode(),
+                locked_output_device,
             );
 
             flush_now!(locked_output_device, "ExitRawMode -> flush()");
@@ -224,10 +334,15 @@ mod impl_self {
         ) {
             enable_raw_mode_now!(is_mock, "EnterRawMode -> enable_raw_mode()");
 
+            RenderOpImplCrossterm::enable_mouse_capture(
+                get_mouse_capture_mode(),
+                locked_output_device,
+            );
             queue_render_op!(
                 locked_output_device,
-                "EnterRawMode -> EnableMouseCapture, EnterAlternateScreen, MoveTo(0,0), Clear(ClearType::All), Hide",
-                EnableMouseCapture,
+                "EnterRawMode -> PushTitle, EnableBracketedPaste, EnterAlternateScreen, MoveTo(0,0), Clear(ClearType::All), Hide",
+                Print(PUSH_TITLE_SEQUENCE),
+                EnableBracketedPaste,
                 EnterAlternateScreen,
                 MoveTo(0,0),
                 Clear(ClearType::All),
@@ -241,11 +356,134 @@ mod impl_self {
             *skip_flush = true;
         }
 
+        /// Mouse capture is only turned on (when entering raw mode) if the `mode` is
+        /// [MouseCaptureMode::Enable].
+        pub fn enable_mouse_capture(
+            mode: MouseCaptureMode,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            if mode == MouseCaptureMode::Enable {
+                queue_render_op!(
+                    locked_output_device,
+                    "EnterRawMode -> EnableMouseCapture",
+                    EnableMouseCapture
+                );
+            }
+        }
+
+        /// Mouse capture is only turned off (when exiting raw mode) if the `mode` is
+        /// [MouseCaptureMode::Enable], since it wasn't turned on otherwise.
+        pub fn disable_mouse_capture(
+            mode: MouseCaptureMode,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            if mode == MouseCaptureMode::Enable {
+                queue_render_op!(
+                    locked_output_device,
+                    "ExitRawMode -> DisableMouseCapture",
+                    DisableMouseCapture
+                );
+            }
+        }
+
+        /// This is a no-op if the terminal doesn't support hyperlinks.
+        pub fn set_hyperlink(
+            maybe_url: Option<&str>,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            if !is_hyperlink_supported() {
+                return;
+            }
+            queue_render_op!(
+                locked_output_device,
+                format!("SetHyperlink({maybe_url:?})"),
+                Print(get_hyperlink_escape_sequence(maybe_url)),
+            );
+        }
+
+        pub fn paint_image(
+            data: &[u8],
+            bounds: ImageBounds,
+            window_size: Size,
+            local_data: &mut RenderOpsLocalData,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            let Some(escape_sequence) =
+                get_image_escape_sequence(get_image_protocol(), data, bounds.bounds_size)
+            else {
+                return;
+            };
+            RenderOpImplCrossterm::move_cursor_position_abs(
+                bounds.origin_pos,
+                window_size,
+                local_data,
+                locked_output_device,
+            );
+            queue_render_op!(
+                locked_output_device,
+                format!("PaintImage({} bytes, {bounds:?})", data.len()),
+                Print(escape_sequence),
+            );
+
+            // Where the cursor ends up depends on the terminal & the image protocol.
+            local_data.is_cursor_position_known = false;
+        }
+
+        pub fn set_synchronized_update(
+            is_begin: bool,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            if !is_synchronized_update_supported() {
+                return;
+            }
+            if is_begin {
+                queue_render_op!(
+                    locked_output_device,
+                    "BeginSynchronizedUpdate",
+                    BeginSynchronizedUpdate,
+                );
+            } else {
+                queue_render_op!(
+                    locked_output_device,
+                    "EndSynchronizedUpdate",
+                    EndSynchronizedUpdate,
+                );
+            }
+        }
+
+        /// This is skipped if the colors & attributes are already reset.
+        pub fn reset_color(
+            local_data: &mut RenderOpsLocalData,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            if !local_data.is_color_reset {
+                queue_render_op!(locked_output_device, "ResetColor", ResetColor);
+            }
+            Self::save_color_reset(local_data);
+        }
+
+        /// [ResetColor] & `SetAttribute(Attribute::Reset)` both reset the colors & the
+        /// attributes.
+        pub fn save_color_reset(local_data: &mut RenderOpsLocalData) {
+            local_data.is_color_reset = true;
+            local_data.maybe_fg_color = None;
+            local_data.maybe_bg_color = None;
+            local_data.maybe_attributes_style = None;
+        }
+
+        /// This is skipped if the `color` is already set.
         pub fn set_fg_color(
-            color: TuiColor,
+            tui_color: TuiColor,
+            local_data: &mut RenderOpsLocalData,
             locked_output_device: LockedOutputDevice<'_>,
         ) {
-            let color = convert_from_tui_color_to_crossterm_color(color);
+            if local_data.maybe_fg_color == Some(tui_color) {
+                return;
+            }
+            local_data.maybe_fg_color = Some(tui_color);
+            local_data.is_color_reset = false;
+
+            let color = convert_from_tui_color_to_crossterm_color(tui_color);
 
             queue_render_op!(
                 locked_output_device,
@@ -254,12 +492,20 @@ mod impl_self {
             );
         }
 
+        /// This is skipped if the `color` is already set.
         pub fn set_bg_color(
-            color: TuiColor,
+            tui_color: TuiColor,
+            local_data: &mut RenderOpsLocalData,
             locked_output_device: LockedOutputDevice<'_>,
         ) {
+            if local_data.maybe_bg_color == Some(tui_color) {
+                return;
+            }
+            local_data.maybe_bg_color = Some(tui_color);
+            local_data.is_color_reset = false;
+
             let color: crossterm::style::Color =
-                convert_from_tui_color_to_crossterm_color(color);
+                convert_from_tui_color_to_crossterm_color(tui_color);
 
             queue_render_op!(
                 locked_output_device,
@@ -289,46 +535,26 @@ mod impl_self {
                 window_size,
             };
 
-            let needs_reset = Cow::Owned(false);
-
             // Paint plain_text.
-            paint_style_and_text(
-                &mut paint_args,
-                needs_reset,
-                local_data,
-                locked_output_device,
-            );
+            paint_style_and_text(&mut paint_args, local_data, locked_output_device);
         }
 
         /// Use [crossterm::style::Color] to set crossterm Colors.
         /// Docs: <https://docs.rs/crossterm/latest/crossterm/style/index.html#colors>
         pub fn apply_colors(
             maybe_style: &Option<TuiStyle>,
+            local_data: &mut RenderOpsLocalData,
             locked_output_device: LockedOutputDevice<'_>,
         ) {
             if let Some(style) = maybe_style {
                 // Handle background color.
                 if let Some(tui_color_bg) = style.color_bg {
-                    let color_bg: crossterm::style::Color =
-                        crate::convert_from_tui_color_to_crossterm_color(tui_color_bg);
-
-                    queue_render_op!(
-                        locked_output_device,
-                        format!("ApplyColors -> SetBgColor({color_bg:?})"),
-                        SetBackgroundColor(color_bg),
-                    );
+                    Self::set_bg_color(tui_color_bg, local_data, locked_output_device);
                 }
 
                 // Handle foreground color.
                 if let Some(tui_color_fg) = style.color_fg {
-                    let color_fg: crossterm::style::Color =
-                        crate::convert_from_tui_color_to_crossterm_color(tui_color_fg);
-
-                    queue_render_op!(
-                        locked_output_device,
-                        format!("ApplyColors -> SetFgColor({color_fg:?})"),
-                        SetForegroundColor(color_fg),
-                    );
+                    Self::set_fg_color(tui_color_fg, local_data, locked_output_device);
                 }
             }
         }
@@ -358,7 +584,13 @@ mod perform_paint {
             it.push(Attribute::Dim);
         }
         if style.underline {
-            it.push(Attribute::Underlined);
+            it.push(match style.underline_style {
+                TuiUnderlineStyle::Single => Attribute::Underlined,
+                TuiUnderlineStyle::Double => Attribute::DoubleUnderlined,
+                TuiUnderlineStyle::Curly => Attribute::Undercurled,
+                TuiUnderlineStyle::Dotted => Attribute::Underdotted,
+                TuiUnderlineStyle::Dashed => Attribute::Underdashed,
+            });
         }
         if style.reverse {
             it.push(Attribute::Reverse);
@@ -372,37 +604,96 @@ mod perform_paint {
         it
     }
 
+    /// The attributes & the underline color of the `maybe_style`, which are set (and
+    /// reset) together.
+    fn get_attributes(
+        maybe_style: &Option<TuiStyle>,
+    ) -> (Vec<Attribute>, Option<crossterm::style::Color>) {
+        let Some(style) = maybe_style else {
+            return (vec![], None);
+        };
+        let maybe_color_underline = match (style.underline, style.color_underline) {
+            (true, Some(it)) => Some(convert_from_tui_color_to_crossterm_color(it)),
+            _ => None,
+        };
+        (style_to_attribute(style), maybe_color_underline)
+    }
+
     /// Use [Style] to set crossterm [Attributes] ([docs](
     /// https://docs.rs/crossterm/latest/crossterm/style/index.html#attributes)).
+    ///
+    /// The attributes aren't reset after the text is painted, they are left set for the
+    /// next text, which doesn't have to set them again if it has the same ones. They are
+    /// reset when the next text has different ones, or by [RenderOp::ResetColor], or at
+    /// the end of [crate::RenderOps::execute_all].
     pub fn paint_style_and_text(
         paint_args: &mut PaintArgs<'_>,
-        mut needs_reset: Cow<'_, bool>,
         local_data: &mut RenderOpsLocalData,
         locked_output_device: LockedOutputDevice<'_>,
     ) {
         let PaintArgs { maybe_style, .. } = paint_args;
 
-        if let Some(style) = maybe_style {
-            let attrib_vec = style_to_attribute(style);
+        let (attrib_vec, maybe_color_underline) = get_attributes(maybe_style);
+        let (set_attrib_vec, set_maybe_color_underline) =
+            get_attributes(&local_data.maybe_attributes_style);
+
+        if (&attrib_vec, maybe_color_underline)
+            != (&set_attrib_vec, set_maybe_color_underline)
+        {
+            if !set_attrib_vec.is_empty() || set_maybe_color_underline.is_some() {
+                // This (SGR 0) resets the colors too, so set them again.
+                let (maybe_fg_color, maybe_bg_color) =
+                    (local_data.maybe_fg_color, local_data.maybe_bg_color);
+                queue_render_op!(
+                    locked_output_device,
+                    format!("PaintWithAttributes -> SetAttribute(Reset))"),
+                    SetAttribute(Attribute::Reset),
+                );
+                RenderOpImplCrossterm::save_color_reset(local_data);
+                if let Some(color) = maybe_fg_color {
+                    RenderOpImplCrossterm::set_fg_color(
+                        color,
+                        local_data,
+                        locked_output_device,
+                    );
+                }
+                if let Some(color) = maybe_bg_color {
+                    RenderOpImplCrossterm::set_bg_color(
+                        color,
+                        local_data,
+                        locked_output_device,
+                    );
+                }
+            }
+
             attrib_vec.iter().for_each(|attr| {
                 queue_render_op!(
                     locked_output_device,
                     format!("PaintWithAttributes -> SetAttribute({attr:?})"),
                     SetAttribute(*attr),
                 );
-                needs_reset = Cow::Owned(true);
             });
-        }
 
-        paint_text(paint_args, local_data, locked_output_device);
+            // The underline color is reset w/ the attributes (by SGR 0).
+            if let Some(color_underline) = maybe_color_underline {
+                queue_render_op!(
+                    locked_output_device,
+                    format!(
+                        "PaintWithAttributes -> SetUnderlineColor({color_underline:?})"
+                    ),
+                    SetUnderlineColor(color_underline),
+                );
+            }
 
-        if *needs_reset {
-            queue_render_op!(
-                locked_output_device,
-                format!("PaintWithAttributes -> SetAttribute(Reset))"),
-                SetAttribute(Attribute::Reset),
-            );
+            if attrib_vec.is_empty() && maybe_color_underline.is_none() {
+                local_data.maybe_attributes_style = None;
+            } else {
+                local_data.maybe_attributes_style = **maybe_style;
+                local_data.is_color_reset = false;
+            }
         }
+
+        paint_text(paint_args, local_data, locked_output_device);
     }
 
     pub fn paint_text(
@@ -436,6 +727,139 @@ mod perform_paint {
 
         cursor_position_copy.col_index += display_width;
         sanitize_and_save_abs_position(cursor_position_copy, *window_size, local_data);
+
+        // The cursor stays at the last column (instead of moving past it) when text is
+        // painted up to the end of the line, so it isn't where it was saved.
+        if cursor_position_copy.col_index >= window_size.col_count {
+            local_data.is_cursor_position_known = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::Command;
+    use r3bl_core::{color, output_device_as_mut, position, size, OutputDevice};
+    use r3bl_macro::tui_style;
+    use r3bl_test_fixtures::OutputDeviceExt as _;
+
+    use super::*;
+
+    /// Paint the `render_ops` (w/ the same [RenderOpsLocalData], like
+    /// [crate::RenderOps::execute_all] does) and return what was written.
+    fn paint_all(render_ops: &[RenderOp]) -> String {
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+        let mut local_data = RenderOpsLocalData::default();
+        let mut skip_flush = false;
+        for render_op in render_ops {
+            RenderOpImplCrossterm.paint(
+                &mut skip_flush,
+                render_op,
+                size!(col_count: 10, row_count: 2),
+                &mut local_data,
+                output_device_as_mut!(output_device),
+                output_device.is_mock,
+            );
+        }
+        stdout_mock.get_copy_of_buffer_as_string()
+    }
+
+    fn ansi(command: impl Command) -> String {
+        let mut it = String::new();
+        command.write_ansi(&mut it).unwrap();
+        it
+    }
+
+    #[test]
+    fn test_mouse_capture_mode_toggles_mouse_capture() {
+        for (mode, expected_enable, expected_disable) in [
+            (
+                MouseCaptureMode::Enable,
+                ansi(EnableMouseCapture),
+                ansi(DisableMouseCapture),
+            ),
+            (MouseCaptureMode::Disable, String::new(), String::new()),
+        ] {
+            let (output_device, stdout_mock) = OutputDevice::new_mock();
+            RenderOpImplCrossterm::enable_mouse_capture(
+                mode,
+                output_device_as_mut!(output_device),
+            );
+            assert_eq!(stdout_mock.get_copy_of_buffer_as_string(), expected_enable);
+
+            let (output_device, stdout_mock) = OutputDevice::new_mock();
+            RenderOpImplCrossterm::disable_mouse_capture(
+                mode,
+                output_device_as_mut!(output_device),
+            );
+            assert_eq!(stdout_mock.get_copy_of_buffer_as_string(), expected_disable);
+        }
+    }
+
+    #[test]
+    fn test_redundant_moves_and_colors_are_skipped() {
+        let red = color!(@red);
+        let output = paint_all(&[
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+            RenderOp::ResetColor,
+            RenderOp::SetFgColor(red),
+            RenderOp::SetFgColor(red),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("ab".into(), None),
+            // The cursor is already here.
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 2, row_index: 0)),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("cd".into(), None),
+            RenderOp::ResetColor,
+            RenderOp::ResetColor,
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 1)),
+        ]);
+
+        let expected = [
+            ansi(MoveTo(0, 0)),
+            ansi(ResetColor),
+            ansi(SetForegroundColor(
+                convert_from_tui_color_to_crossterm_color(red),
+            )),
+            "abcd".to_string(),
+            ansi(ResetColor),
+            ansi(MoveTo(0, 1)),
+        ]
+        .concat();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_adjacent_texts_with_same_attributes_are_batched() {
+        let bold = Some(tui_style! { attrib: [bold] });
+        let output = paint_all(&[
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("ab".into(), bold),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("cd".into(), bold),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("ef".into(), None),
+        ]);
+
+        let expected = [
+            ansi(MoveTo(0, 0)),
+            ansi(SetAttribute(Attribute::Bold)),
+            "abcd".to_string(),
+            ansi(SetAttribute(Attribute::Reset)),
+            "ef".to_string(),
+        ]
+        .concat();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_cursor_position_is_unknown_after_end_of_line() {
+        let output = paint_all(&[
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                "0123456789".into(),
+                None,
+            ),
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 10, row_index: 0)),
+        ]);
+
+        assert_eq!(output.matches(&ansi(MoveTo(10, 0))).count(), 1);
     }
 }
 
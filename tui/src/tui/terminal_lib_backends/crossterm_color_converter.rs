@@ -54,8 +54,10 @@ pub fn convert_from_crossterm_color_to_tui_color(value: crossterm::style::Color)
     }
 }
 
-/// Respect the color support of the terminal and downgrade the color if needed. This really only
-/// applies to the [TuiColor::Rgb] variant.
+/// Respect the color support of the terminal (see [global_color_support::detect]) and
+/// downgrade the color if needed. [TuiColor::Rgb] is quantized to the ANSI 256 palette, or
+/// to the 16 system colors (if that is all the terminal supports), and [TuiColor::Ansi] is
+/// quantized to the 16 system colors.
 pub fn convert_from_tui_color_to_crossterm_color(
     from_tui_color: TuiColor,
 ) -> crossterm::style::Color {
@@ -86,7 +88,7 @@ pub fn convert_from_tui_color_to_crossterm_color(
 
             // Keep it as is.
             #[rustfmt::skip]
-            ColorSupport::Ansi256 | ColorSupport::Truecolor => match from_basic_color {
+            ColorSupport::Ansi16 | ColorSupport::Ansi256 | ColorSupport::Truecolor => match from_basic_color {
                 ANSIBasicColor::Black =>        crossterm::style::Color::Black,
                 ANSIBasicColor::White =>        crossterm::style::Color::White,
                 ANSIBasicColor::Grey =>         crossterm::style::Color::Grey,
@@ -113,6 +115,14 @@ pub fn convert_from_tui_color_to_crossterm_color(
                     crossterm::style::Color::AnsiValue(from_ansi_value.color)
                 }
 
+                // Convert to the closest system color.
+                ColorSupport::Ansi16 => {
+                    let ansi16_color =
+                        r3bl_ansi_color::Color::Ansi256(from_ansi_value.color)
+                            .as_ansi16();
+                    convert_ansi16_index_to_crossterm_color(ansi16_color.index)
+                }
+
                 // Convert to grayscale.
                 ColorSupport::Grayscale | ColorSupport::NoColor => {
                     let ansi_grayscale_color =
@@ -141,6 +151,12 @@ pub fn convert_from_tui_color_to_crossterm_color(
                     crossterm::style::Color::AnsiValue(ansi_value)
                 }
 
+                // Convert to the closest system color.
+                ColorSupport::Ansi16 => {
+                    let ansi16_color = r3bl_ansi_color::Color::Rgb(r, g, b).as_ansi16();
+                    convert_ansi16_index_to_crossterm_color(ansi16_color.index)
+                }
+
                 // Convert to grayscale.
                 ColorSupport::NoColor | ColorSupport::Grayscale => {
                     convert_rgb_to_ansi_grayscale(r, g, b)
@@ -154,3 +170,62 @@ fn convert_rgb_to_ansi_grayscale(r: u8, g: u8, b: u8) -> crossterm::style::Color
     let ansi_grayscale_color = r3bl_ansi_color::Color::Rgb(r, g, b).as_grayscale();
     crossterm::style::Color::AnsiValue(ansi_grayscale_color.index)
 }
+
+/// The 16 system colors are in the same order as the ones in the ANSI 256 palette.
+#[rustfmt::skip]
+fn convert_ansi16_index_to_crossterm_color(index: u8) -> crossterm::style::Color {
+    match index {
+        0  => crossterm::style::Color::Black,
+        1  => crossterm::style::Color::DarkRed,
+        2  => crossterm::style::Color::DarkGreen,
+        3  => crossterm::style::Color::DarkYellow,
+        4  => crossterm::style::Color::DarkBlue,
+        5  => crossterm::style::Color::DarkMagenta,
+        6  => crossterm::style::Color::DarkCyan,
+        7  => crossterm::style::Color::Grey,
+        8  => crossterm::style::Color::DarkGrey,
+        9  => crossterm::style::Color::Red,
+        10 => crossterm::style::Color::Green,
+        11 => crossterm::style::Color::Yellow,
+        12 => crossterm::style::Color::Blue,
+        13 => crossterm::style::Color::Magenta,
+        14 => crossterm::style::Color::Cyan,
+        _  => crossterm::style::Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serial_test::serial;
+
+    use super::*;
+
+    #[serial]
+    #[test]
+    fn test_convert_rgb_w_ansi16_color_support() {
+        global_color_support::set_override(ColorSupport::Ansi16);
+
+        assert_eq!(
+            convert_from_tui_color_to_crossterm_color(TuiColor::Rgb(RgbValue {
+                red: 250,
+                green: 10,
+                blue: 10
+            })),
+            crossterm::style::Color::Red
+        );
+        assert_eq!(
+            convert_from_tui_color_to_crossterm_color(TuiColor::Ansi(AnsiValue::new(
+                231
+            ))),
+            crossterm::style::Color::White
+        );
+        assert_eq!(
+            convert_from_tui_color_to_crossterm_color(TuiColor::Basic(
+                ANSIBasicColor::DarkCyan
+            )),
+            crossterm::style::Color::DarkCyan
+        );
+
+        global_color_support::clear_override();
+    }
+}
@@ -0,0 +1,119 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+use serde::{Deserialize, Serialize};
+
+/// The DECSCUSR escape sequence that resets the shape of the terminal's cursor to the
+/// one that the user configured. This is painted when raw mode is exited, so that the
+/// shape that the app used doesn't leak into the shell.
+pub const RESET_CURSOR_SHAPE_SEQUENCE: &str = "\x1b[0 q";
+
+/// The shape of the terminal's (hardware) cursor, see [super::RenderOp::ShowCursor].
+/// Each one maps to a parameter of the DECSCUSR escape sequence, see
+/// <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h4-Functions-using-CSI-_-ordered-by-the-final-character-lparen-s-rparen:CSI-Ps-SP-q.1D81>.
+/// The terminals that don't support it ignore it (and keep their own shape).
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    size_of::SizeOf,
+)]
+pub enum CursorShape {
+    /// The shape that the user configured in the terminal.
+    #[default]
+    Default,
+    BlinkingBlock,
+    SteadyBlock,
+    BlinkingUnderline,
+    SteadyUnderline,
+    BlinkingBar,
+    SteadyBar,
+}
+
+impl CursorShape {
+    /// The blinking or steady variant of this shape.
+    pub fn with_blink(self, is_blinking: bool) -> Self {
+        match (self, is_blinking) {
+            (Self::BlinkingBlock | Self::SteadyBlock, true) => Self::BlinkingBlock,
+            (Self::BlinkingBlock | Self::SteadyBlock, false) => Self::SteadyBlock,
+            (Self::BlinkingUnderline | Self::SteadyUnderline, true) => {
+                Self::BlinkingUnderline
+            }
+            (Self::BlinkingUnderline | Self::SteadyUnderline, false) => {
+                Self::SteadyUnderline
+            }
+            (Self::BlinkingBar | Self::SteadyBar, true) => Self::BlinkingBar,
+            (Self::BlinkingBar | Self::SteadyBar, false) => Self::SteadyBar,
+            (Self::Default, _) => Self::Default,
+        }
+    }
+}
+
+/// Returns the escape sequence that sets the shape of the terminal's cursor.
+pub fn get_cursor_shape_escape_sequence(shape: CursorShape) -> String {
+    let param = match shape {
+        CursorShape::Default => 0,
+        CursorShape::BlinkingBlock => 1,
+        CursorShape::SteadyBlock => 2,
+        CursorShape::BlinkingUnderline => 3,
+        CursorShape::SteadyUnderline => 4,
+        CursorShape::BlinkingBar => 5,
+        CursorShape::SteadyBar => 6,
+    };
+    format!("\x1b[{param} q")
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_get_cursor_shape_escape_sequence() {
+        assert_eq2!(
+            get_cursor_shape_escape_sequence(CursorShape::Default),
+            RESET_CURSOR_SHAPE_SEQUENCE.to_string()
+        );
+        assert_eq2!(
+            get_cursor_shape_escape_sequence(CursorShape::SteadyBlock),
+            "\x1b[2 q".to_string()
+        );
+        assert_eq2!(
+            get_cursor_shape_escape_sequence(CursorShape::BlinkingBar),
+            "\x1b[5 q".to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_blink() {
+        assert_eq2!(
+            CursorShape::SteadyBar.with_blink(true),
+            CursorShape::BlinkingBar
+        );
+        assert_eq2!(
+            CursorShape::BlinkingUnderline.with_blink(false),
+            CursorShape::SteadyUnderline
+        );
+        assert_eq2!(CursorShape::Default.with_blink(true), CursorShape::Default);
+    }
+}
@@ -0,0 +1,383 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::{Formatter, Result};
+
+use r3bl_core::{ch,
+                CommonResult,
+                InputDevice,
+                LockedOutputDevice,
+                Position,
+                Size,
+                TuiColor,
+                TuiStyle,
+                UnicodeString};
+
+use super::{sanitize_and_save_abs_position,
+            DebugFormatRenderOp,
+            Flush,
+            OffscreenBuffer,
+            OffscreenBufferPaint,
+            OffscreenBufferPaintImplCrossterm,
+            OffscreenImage,
+            PaintRenderOp,
+            PixelChar,
+            RenderOp,
+            RenderOps,
+            RenderOpsLocalData,
+            RenderPipeline,
+            TerminalBackend};
+use crate::CrosstermDebugFormatRenderOp;
+
+/// A [TerminalBackend] that doesn't need a terminal. It paints the [RenderOp]s that it
+/// executes into a grid of cells (w/ the text & the style of each one), so that what a
+/// component renders can be checked in tests w/out a TTY (or having to parse the ANSI
+/// escape sequences that would be written to it).
+///
+/// ```ignore
+/// let mut backend = HeadlessBackend::new(size!(col_count: 20, row_count: 2));
+/// backend.paint_pipeline(&pipeline);
+/// assert_eq!(backend.get_line_text(0), "Hello               ");
+/// ```
+///
+/// A copy of the grid is saved each time that it is flushed, see [Self::get_frames].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessBackend {
+    pub window_size: Size,
+    pub is_raw_mode: bool,
+    frame: OffscreenBuffer,
+    frames: Vec<OffscreenBuffer>,
+    cursor_position: Position,
+    maybe_fg_color: Option<TuiColor>,
+    maybe_bg_color: Option<TuiColor>,
+    maybe_hyperlink: Option<String>,
+}
+
+impl HeadlessBackend {
+    pub fn new(window_size: Size) -> Self {
+        Self {
+            window_size,
+            is_raw_mode: false,
+            frame: OffscreenBuffer::new_with_capacity_initialized(window_size),
+            frames: vec![],
+            cursor_position: Position::default(),
+            maybe_fg_color: None,
+            maybe_bg_color: None,
+            maybe_hyperlink: None,
+        }
+    }
+
+    /// Paint the `pipeline` in the same way that [crate::paint] does: it is composited
+    /// into an [OffscreenBuffer] first, which is then painted w/ [RenderOp]s (and
+    /// flushed).
+    pub fn paint_pipeline(&mut self, pipeline: &RenderPipeline) {
+        let offscreen_buffer = pipeline.convert(self.window_size);
+        let mut paint_impl = OffscreenBufferPaintImplCrossterm {};
+        let render_ops = paint_impl.render(&offscreen_buffer);
+        self.execute_render_ops(&render_ops);
+        self.flush(&mut std::io::sink());
+    }
+
+    pub fn execute_render_ops(&mut self, render_ops: &RenderOps) {
+        let mut skip_flush = false;
+        let mut local_data = RenderOpsLocalData {
+            cursor_position: self.cursor_position,
+            ..Default::default()
+        };
+        for render_op in render_ops.iter() {
+            self.paint(
+                &mut skip_flush,
+                render_op,
+                self.window_size,
+                &mut local_data,
+                &mut std::io::sink(),
+                true,
+            );
+        }
+        self.cursor_position = local_data.cursor_position;
+    }
+
+    /// The cells that were painted so far.
+    pub fn get_frame(&self) -> &OffscreenBuffer { &self.frame }
+
+    /// The cells at each [Flush::flush], oldest first.
+    pub fn get_frames(&self) -> &[OffscreenBuffer] { &self.frames }
+
+    /// The text in the line at `row_index` (w/ a space for each empty cell).
+    pub fn get_line_text(&self, row_index: usize) -> String {
+        let Some(line) = self.frame.buffer.get(row_index) else {
+            return String::new();
+        };
+        line.iter()
+            .filter_map(|pixel_char| match pixel_char {
+                PixelChar::Void => None,
+                PixelChar::Spacer => Some(" "),
+                PixelChar::PlainText { content, .. } => Some(content.string.as_str()),
+            })
+            .collect()
+    }
+
+    pub fn get_pixel_char_at(&self, pos: Position) -> Option<&PixelChar> {
+        self.frame
+            .buffer
+            .get(ch!(@to_usize pos.row_index))?
+            .get(ch!(@to_usize pos.col_index))
+    }
+
+    pub fn get_style_at(&self, pos: Position) -> Option<TuiStyle> {
+        match self.get_pixel_char_at(pos)? {
+            PixelChar::PlainText { maybe_style, .. } => *maybe_style,
+            _ => None,
+        }
+    }
+
+    /// The URL that the text at `pos` links to, see [RenderOp::SetHyperlink].
+    pub fn get_hyperlink_at(&self, pos: Position) -> Option<&str> {
+        match self.get_pixel_char_at(pos)? {
+            PixelChar::PlainText {
+                maybe_hyperlink, ..
+            } => maybe_hyperlink.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The current colors (set by [RenderOp::SetFgColor], [RenderOp::SetBgColor] &
+    /// [RenderOp::ApplyColors]) are applied to the `maybe_style` of the text.
+    fn paint_text(
+        &mut self,
+        text: &str,
+        maybe_style: &Option<TuiStyle>,
+        local_data: &mut RenderOpsLocalData,
+    ) {
+        let maybe_style = match (maybe_style, self.maybe_fg_color, self.maybe_bg_color) {
+            (None, None, None) => None,
+            (maybe_style, maybe_fg_color, maybe_bg_color) => {
+                let style = maybe_style.unwrap_or_default();
+                Some(TuiStyle {
+                    color_fg: maybe_fg_color.or(style.color_fg),
+                    color_bg: maybe_bg_color.or(style.color_bg),
+                    ..style
+                })
+            }
+        };
+
+        let mut cursor_position = local_data.cursor_position;
+        let unicode_string = UnicodeString::from(text);
+        for segment in unicode_string.vec_segment.iter() {
+            let row_index = ch!(@to_usize cursor_position.row_index);
+            let col_index = ch!(@to_usize cursor_position.col_index);
+            let Some(line) = self.frame.buffer.get_mut(row_index) else {
+                break;
+            };
+            if col_index >= line.len() {
+                break;
+            }
+            line[col_index] = PixelChar::PlainText {
+                content: segment.clone(),
+                maybe_style,
+                maybe_hyperlink: self.maybe_hyperlink.clone(),
+            };
+            // The cells that are covered by a wide char are skipped when painting.
+            for void_col_index in
+                col_index + 1..col_index + ch!(@to_usize segment.unicode_width)
+            {
+                if let Some(pixel_char) = line.get_mut(void_col_index) {
+                    *pixel_char = PixelChar::Void;
+                }
+            }
+            cursor_position.col_index += segment.unicode_width;
+        }
+
+        sanitize_and_save_abs_position(cursor_position, self.window_size, local_data);
+    }
+}
+
+impl PaintRenderOp for HeadlessBackend {
+    fn paint(
+        &mut self,
+        skip_flush: &mut bool,
+        render_op: &RenderOp,
+        window_size: Size,
+        local_data: &mut RenderOpsLocalData,
+        _locked_output_device: LockedOutputDevice<'_>,
+        _is_mock: bool,
+    ) {
+        match render_op {
+            RenderOp::Noop
+            | RenderOp::PaintTextWithAttributes(..)
+            | RenderOp::BeginSynchronizedUpdate
+            | RenderOp::EndSynchronizedUpdate
+            | RenderOp::SetClipboard(..) => {}
+            RenderOp::EnterRawMode => {
+                self.is_raw_mode = true;
+                *skip_flush = true;
+            }
+            RenderOp::ExitRawMode => {
+                self.is_raw_mode = false;
+                *skip_flush = true;
+            }
+            RenderOp::MoveCursorPositionAbs(abs_pos) => {
+                sanitize_and_save_abs_position(*abs_pos, window_size, local_data);
+            }
+            RenderOp::MoveCursorPositionRelTo(box_origin_pos, content_rel_pos) => {
+                sanitize_and_save_abs_position(
+                    *box_origin_pos + *content_rel_pos,
+                    window_size,
+                    local_data,
+                );
+            }
+            RenderOp::ClearScreen => self.frame.clear(),
+            RenderOp::SetFgColor(color) => self.maybe_fg_color = Some(*color),
+            RenderOp::SetBgColor(color) => self.maybe_bg_color = Some(*color),
+            RenderOp::ResetColor => {
+                self.maybe_fg_color = None;
+                self.maybe_bg_color = None;
+            }
+            RenderOp::ApplyColors(maybe_style) => {
+                if let Some(style) = maybe_style {
+                    self.maybe_fg_color = style.color_fg.or(self.maybe_fg_color);
+                    self.maybe_bg_color = style.color_bg.or(self.maybe_bg_color);
+                }
+            }
+            RenderOp::SetHyperlink(maybe_url) => {
+                self.maybe_hyperlink.clone_from(maybe_url)
+            }
+            RenderOp::PaintImage { data, bounds } => {
+                self.frame.images.push(OffscreenImage {
+                    data: data.clone(),
+                    bounds: *bounds,
+                });
+            }
+            RenderOp::SetTitle(title) => self.frame.maybe_title = Some(title.clone()),
+            RenderOp::ShowCursor(shape) => {
+                self.frame.maybe_cursor = Some((local_data.cursor_position, *shape))
+            }
+            RenderOp::HideCursor => self.frame.maybe_cursor = None,
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes(text, maybe_style) => {
+                self.paint_text(text, maybe_style, local_data);
+            }
+        }
+    }
+}
+
+impl Flush for HeadlessBackend {
+    fn flush(&mut self, _locked_output_device: LockedOutputDevice<'_>) {
+        self.frames.push(self.frame.clone());
+    }
+
+    fn clear_before_flush(&mut self, _locked_output_device: LockedOutputDevice<'_>) {
+        self.maybe_fg_color = None;
+        self.maybe_bg_color = None;
+        self.frame.clear();
+    }
+}
+
+impl DebugFormatRenderOp for HeadlessBackend {
+    fn debug_format(&self, this: &RenderOp, f: &mut Formatter<'_>) -> Result {
+        CrosstermDebugFormatRenderOp {}.debug_format(this, f)
+    }
+}
+
+impl TerminalBackend for HeadlessBackend {
+    fn lookup_size(&self) -> CommonResult<Size> { Ok(self.window_size) }
+
+    /// There is no input, so the stream ends right away.
+    fn new_input_device(&self) -> InputDevice {
+        InputDevice {
+            resource: Box::pin(futures_util::stream::empty()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, color, position, size, ANSIBasicColor};
+    use r3bl_macro::tui_style;
+
+    use super::*;
+    use crate::{render_ops, render_pipeline, ZOrder};
+
+    #[test]
+    fn test_headless_backend_paints_pipeline() {
+        let style = tui_style! { attrib: [bold] color_fg: color!(@red) };
+        let pipeline = render_pipeline!(@new ZOrder::Normal
+          =>
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 2, row_index: 1)),
+            RenderOp::PaintTextWithAttributes("hi😃!".into(), Some(style))
+        );
+
+        let mut backend = HeadlessBackend::new(size!(col_count: 8, row_count: 2));
+        backend.paint_pipeline(&pipeline);
+
+        assert_eq2!(backend.get_line_text(0), "        ");
+        assert_eq2!(backend.get_line_text(1), "  hi😃! ");
+        assert_eq2!(
+            backend.get_pixel_char_at(position!(col_index: 5, row_index: 1)),
+            Some(&PixelChar::Void)
+        );
+        let painted_style = backend
+            .get_style_at(position!(col_index: 2, row_index: 1))
+            .unwrap();
+        assert!(painted_style.bold);
+        assert_eq2!(painted_style.color_fg, Some(color!(@red)));
+        assert_eq2!(
+            backend.get_style_at(position!(col_index: 0, row_index: 1)),
+            None
+        );
+        assert_eq2!(backend.get_frames().len(), 1);
+    }
+
+    #[test]
+    fn test_headless_backend_executes_render_ops() {
+        let mut backend = HeadlessBackend::new(size!(col_count: 6, row_count: 1));
+        backend.execute_render_ops(&render_ops!(
+          @new
+          RenderOp::EnterRawMode,
+          RenderOp::MoveCursorPositionAbs(position!(col_index: 1, row_index: 0)),
+          RenderOp::SetBgColor(color!(@blue)),
+          RenderOp::CompositorNoClipTruncPaintTextWithAttributes("ab".into(), None),
+          RenderOp::ResetColor,
+          RenderOp::SetHyperlink(Some("https://r3bl.com".into())),
+          RenderOp::CompositorNoClipTruncPaintTextWithAttributes("c".into(), None),
+          RenderOp::SetHyperlink(None),
+        ));
+
+        assert!(backend.is_raw_mode);
+        assert_eq2!(backend.get_line_text(0), " abc  ");
+        assert_eq2!(
+            backend
+                .get_style_at(position!(col_index: 2, row_index: 0))
+                .and_then(|it| it.color_bg),
+            Some(color!(@blue))
+        );
+        assert_eq2!(
+            backend.get_style_at(position!(col_index: 3, row_index: 0)),
+            None
+        );
+        assert_eq2!(
+            backend.get_hyperlink_at(position!(col_index: 3, row_index: 0)),
+            Some("https://r3bl.com")
+        );
+        assert_eq2!(
+            backend.get_hyperlink_at(position!(col_index: 2, row_index: 0)),
+            None
+        );
+
+        // Nothing was flushed.
+        assert!(backend.get_frames().is_empty());
+    }
+}
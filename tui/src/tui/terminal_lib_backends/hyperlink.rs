@@ -0,0 +1,126 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::sync::OnceLock;
+
+/// The OSC 8 escape sequences that start & end a hyperlink, see
+/// <https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda>.
+const OSC8_START: &str = "\x1b]8;;";
+const OSC8_END: &str = "\x1b\\";
+
+static HYPERLINK_SUPPORT: OnceLock<bool> = OnceLock::new();
+
+/// Override the detection of hyperlink support (eg: w/ a command line flag), since it
+/// isn't always possible to detect it. This has to be called before anything is painted,
+/// and it can only be called once. If it was already set (or detected), the `value` is
+/// returned in the [Err].
+pub fn set_hyperlink_support(value: bool) -> Result<(), bool> {
+    HYPERLINK_SUPPORT.set(value)
+}
+
+/// Whether the terminal supports OSC 8 hyperlinks. This is detected (once) from the
+/// environment variables that the terminals that support them are known to set.
+pub fn is_hyperlink_supported() -> bool {
+    *HYPERLINK_SUPPORT
+        .get_or_init(|| detect_hyperlink_support(|key| std::env::var(key).ok()))
+}
+
+/// Terminals that don't support OSC 8 are supposed to ignore it, but some of them print
+/// it, so this only returns true for the ones that are known to support it.
+pub fn detect_hyperlink_support(get_env: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(term_program) = get_env("TERM_PROGRAM") {
+        if matches!(term_program.as_str(), "iTerm.app" | "WezTerm" | "vscode") {
+            return true;
+        }
+    }
+
+    // Windows Terminal.
+    if get_env("WT_SESSION").is_some() {
+        return true;
+    }
+
+    // GNOME Terminal, Tilix, etc (VTE 0.50 & later).
+    if let Some(vte_version) = get_env("VTE_VERSION") {
+        if vte_version.parse::<u32>().is_ok_and(|it| it >= 5000) {
+            return true;
+        }
+    }
+
+    if let Some(term) = get_env("TERM") {
+        if ["kitty", "alacritty", "foot"]
+            .iter()
+            .any(|it| term.contains(it))
+        {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Returns the escape sequence that starts a link to `url` (when it is [Some]), or ends
+/// the current link (when it is [None]). The control chars are removed from the `url`,
+/// so that it can't end the escape sequence early.
+pub fn get_hyperlink_escape_sequence(maybe_url: Option<&str>) -> String {
+    let url: String = maybe_url
+        .unwrap_or_default()
+        .chars()
+        .filter(|it| !it.is_control())
+        .collect();
+    format!("{OSC8_START}{url}{OSC8_END}")
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn detect(vars: &[(&str, &str)]) -> bool {
+        detect_hyperlink_support(|key| {
+            vars.iter()
+                .find(|(it, _)| *it == key)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_detect_hyperlink_support() {
+        assert!(detect(&[("TERM_PROGRAM", "WezTerm")]));
+        assert!(detect(&[("TERM", "xterm-kitty")]));
+        assert!(detect(&[("VTE_VERSION", "6800")]));
+        assert!(!detect(&[("VTE_VERSION", "4200")]));
+        assert!(!detect(&[("TERM_PROGRAM", "Apple_Terminal")]));
+        assert!(!detect(&[]));
+    }
+
+    #[test]
+    fn test_get_hyperlink_escape_sequence() {
+        assert_eq2!(
+            get_hyperlink_escape_sequence(Some("https://r3bl.com")),
+            "\x1b]8;;https://r3bl.com\x1b\\".to_string()
+        );
+        assert_eq2!(
+            get_hyperlink_escape_sequence(Some("https://r3bl.com\x1b\\oops")),
+            "\x1b]8;;https://r3bl.com\\oops\x1b\\".to_string()
+        );
+        assert_eq2!(
+            get_hyperlink_escape_sequence(None),
+            "\x1b]8;;\x1b\\".to_string()
+        );
+    }
+}
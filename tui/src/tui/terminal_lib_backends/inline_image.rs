@@ -0,0 +1,413 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+use std::sync::OnceLock;
+#[cfg(feature = "images")]
+use std::{collections::BTreeMap, io::Cursor};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+#[cfg(feature = "images")]
+use image::{imageops::FilterType, ImageFormat, ImageReader, RgbaImage};
+use r3bl_core::{size, Position, Size};
+use serde::{Deserialize, Serialize};
+
+/// The size of a cell in pixels, which is used to preserve the aspect ratio of an image.
+/// The real size isn't known (it depends on the font), but this is close enough for most
+/// terminals.
+const CELL_WIDTH_PX: u32 = 10;
+const CELL_HEIGHT_PX: u32 = 20;
+
+/// The kitty graphics protocol requires the image data to be sent in chunks of (at most)
+/// this many bytes.
+#[cfg(feature = "images")]
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// Where an image is painted by [super::RenderOp::PaintImage]. The `origin_pos` is the
+/// absolute column and row on the terminal screen.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    size_of::SizeOf,
+)]
+pub struct ImageBounds {
+    pub origin_pos: Position,
+    pub bounds_size: Size,
+}
+
+/// The escape sequences that are used to paint images, see [get_image_protocol].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// <https://sw.kovidgoyal.net/kitty/graphics-protocol/>
+    Kitty,
+    /// <https://iterm2.com/documentation-images.html>
+    ITerm2,
+    /// <https://vt100.net/docs/vt3xx-gp/chapter14.html>
+    Sixel,
+    /// Images are not painted, so the alt text is shown instead.
+    NotSupported,
+}
+
+static IMAGE_PROTOCOL: OnceLock<ImageProtocol> = OnceLock::new();
+
+/// Override the detection of the image protocol (eg: w/ a command line flag), since it
+/// isn't always possible to detect it. This has to be called before anything is painted,
+/// and it can only be called once. If it was already set (or detected), the `value` is
+/// returned in the [Err].
+pub fn set_image_protocol(value: ImageProtocol) -> Result<(), ImageProtocol> {
+    IMAGE_PROTOCOL.set(value)
+}
+
+/// The protocol that the terminal supports to paint images. This is detected (once) from
+/// the environment variables that the terminals that support them are known to set.
+pub fn get_image_protocol() -> ImageProtocol {
+    *IMAGE_PROTOCOL.get_or_init(|| detect_image_protocol(|key| std::env::var(key).ok()))
+}
+
+/// Some terminals support more than one protocol, in which case the one that works best
+/// in them is used.
+pub fn detect_image_protocol(get_env: impl Fn(&str) -> Option<String>) -> ImageProtocol {
+    let term = get_env("TERM").unwrap_or_default();
+    let term_program = get_env("TERM_PROGRAM").unwrap_or_default();
+
+    if get_env("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || term.contains("ghostty")
+    {
+        return ImageProtocol::Kitty;
+    }
+
+    if matches!(term_program.as_str(), "iTerm.app" | "WezTerm") {
+        return ImageProtocol::ITerm2;
+    }
+
+    if ["foot", "mlterm", "contour", "sixel"]
+        .iter()
+        .any(|it| term.contains(it))
+    {
+        return ImageProtocol::Sixel;
+    }
+
+    ImageProtocol::NotSupported
+}
+
+/// Returns the escape sequence that paints the image in `data` (the bytes of a PNG, JPEG
+/// or GIF file) at the cursor, so that it fits in `bounds_size` (w/out changing its
+/// aspect ratio). [None] is returned if the `protocol` is
+/// [ImageProtocol::NotSupported], or the image can't be decoded.
+///
+/// The images are only decoded w/ the `images` feature, which [ImageProtocol::Kitty] and
+/// [ImageProtocol::Sixel] need. W/out it, only [ImageProtocol::ITerm2] (which is sent the
+/// file as is) paints images.
+pub fn get_image_escape_sequence(
+    protocol: ImageProtocol,
+    data: &[u8],
+    bounds_size: Size,
+) -> Option<String> {
+    if *bounds_size.col_count == 0 || *bounds_size.row_count == 0 {
+        return None;
+    }
+
+    match protocol {
+        ImageProtocol::NotSupported => None,
+        ImageProtocol::ITerm2 => Some(format!(
+            "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+            data.len(),
+            *bounds_size.col_count,
+            *bounds_size.row_count,
+            STANDARD.encode(data)
+        )),
+        #[cfg(not(feature = "images"))]
+        ImageProtocol::Kitty | ImageProtocol::Sixel => None,
+        #[cfg(feature = "images")]
+        ImageProtocol::Kitty => {
+            let image_size_px = ImageReader::new(Cursor::new(data))
+                .with_guessed_format()
+                .ok()?
+                .into_dimensions()
+                .ok()?;
+            let cells = fit_image_in_cells(image_size_px, bounds_size);
+
+            // PNG is sent as is, anything else has to be decoded into RGBA pixels.
+            let (format_keys, payload) = match image::guess_format(data) {
+                Ok(ImageFormat::Png) => ("f=100".to_string(), STANDARD.encode(data)),
+                _ => {
+                    let rgba_image = image::load_from_memory(data).ok()?.to_rgba8();
+                    let (width, height) = rgba_image.dimensions();
+                    (
+                        format!("f=32,s={width},v={height}"),
+                        STANDARD.encode(rgba_image.into_raw()),
+                    )
+                }
+            };
+
+            Some(encode_kitty(
+                &format!(
+                    "a=T,q=2,C=1,{format_keys},c={},r={}",
+                    *cells.col_count, *cells.row_count
+                ),
+                &payload,
+            ))
+        }
+        #[cfg(feature = "images")]
+        ImageProtocol::Sixel => {
+            let image = image::load_from_memory(data).ok()?;
+            let cells = fit_image_in_cells((image.width(), image.height()), bounds_size);
+            let image = image.resize(
+                *cells.col_count as u32 * CELL_WIDTH_PX,
+                *cells.row_count as u32 * CELL_HEIGHT_PX,
+                FilterType::Triangle,
+            );
+            Some(encode_sixel(&image.to_rgba8()))
+        }
+    }
+}
+
+/// Returns the escape sequence that removes all the images from the screen, for the
+/// protocols in which clearing the screen doesn't remove them (kitty). The images that
+/// are painted w/ the other protocols are just cells, which are cleared w/ the screen.
+pub fn get_clear_images_escape_sequence(protocol: ImageProtocol) -> Option<&'static str> {
+    match protocol {
+        ImageProtocol::Kitty => Some("\x1b_Ga=d,d=A,q=2\x1b\\"),
+        _ => None,
+    }
+}
+
+/// Returns the number of cells that the image takes up, when it is scaled (up or down) to
+/// fit in `bounds_size`.
+pub fn fit_image_in_cells(image_size_px: (u32, u32), bounds_size: Size) -> Size {
+    let (width_px, height_px) = image_size_px;
+    let max_cols = *bounds_size.col_count as f64;
+    let max_rows = *bounds_size.row_count as f64;
+
+    let scale = f64::min(
+        max_cols * CELL_WIDTH_PX as f64 / width_px.max(1) as f64,
+        max_rows * CELL_HEIGHT_PX as f64 / height_px.max(1) as f64,
+    );
+    let cols = (width_px as f64 * scale / CELL_WIDTH_PX as f64).round();
+    let rows = (height_px as f64 * scale / CELL_HEIGHT_PX as f64).round();
+
+    size!(
+        col_count: cols.clamp(1.0, max_cols) as u16,
+        row_count: rows.clamp(1.0, max_rows) as u16
+    )
+}
+
+/// The `keys` are only sent w/ the first chunk, and all the chunks but the last one have
+/// `m=1`.
+#[cfg(feature = "images")]
+fn encode_kitty(keys: &str, payload: &str) -> String {
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+    let mut acc = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let is_first = index == 0;
+        let is_last = index == chunks.len() - 1;
+        acc.push_str("\x1b_G");
+        if is_first {
+            acc.push_str(keys);
+            acc.push(',');
+        }
+        acc.push_str(if is_last { "m=0;" } else { "m=1;" });
+        acc.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        acc.push_str("\x1b\\");
+    }
+    acc
+}
+
+/// Each pixel is mapped to the closest color in a 6x6x6 color cube (the same one that is
+/// used by the ANSI 256 color palette), and the (mostly) transparent pixels are skipped.
+/// The pixels are painted in bands of 6 rows, one color at a time.
+#[cfg(feature = "images")]
+fn encode_sixel(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let to_cube_index = |it: u8| (it as u16 * 5 + 127) / 255;
+
+    let mut acc = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    for color_index in 0..216_u16 {
+        let [red, green, blue] =
+            [color_index / 36, (color_index / 6) % 6, color_index % 6].map(|it| it * 20);
+        acc.push_str(&format!("#{color_index};2;{red};{green};{blue}"));
+    }
+
+    for band_top in (0..height).step_by(6) {
+        // The sixels of each color in this band.
+        let mut sixels_by_color: BTreeMap<u16, Vec<u8>> = BTreeMap::new();
+        for row_offset in 0..6.min(height - band_top) {
+            for col in 0..width {
+                let [red, green, blue, alpha] =
+                    image.get_pixel(col, band_top + row_offset).0;
+                if alpha < 128 {
+                    continue;
+                }
+                let color_index = to_cube_index(red) * 36
+                    + to_cube_index(green) * 6
+                    + to_cube_index(blue);
+                sixels_by_color
+                    .entry(color_index)
+                    .or_insert_with(|| vec![0; width as usize])[col as usize] |=
+                    1 << row_offset;
+            }
+        }
+
+        for (color_index, sixels) in sixels_by_color {
+            acc.push_str(&format!("#{color_index}"));
+            // Run length encode the sixels.
+            let mut iter = sixels.iter().peekable();
+            while let Some(&sixel) = iter.next() {
+                let mut run_length = 1;
+                while iter.next_if_eq(&&sixel).is_some() {
+                    run_length += 1;
+                }
+                let sixel_char = (63 + sixel) as char;
+                if run_length > 3 {
+                    acc.push_str(&format!("!{run_length}{sixel_char}"));
+                } else {
+                    acc.push_str(&sixel_char.to_string().repeat(run_length));
+                }
+            }
+            // Go back to the start of the band for the next color.
+            acc.push('$');
+        }
+
+        // Go to the next band.
+        acc.push('-');
+    }
+
+    acc.push_str("\x1b\\");
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "images")]
+    use image::{DynamicImage, Rgba};
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn detect(vars: &[(&str, &str)]) -> ImageProtocol {
+        detect_image_protocol(|key| {
+            vars.iter()
+                .find(|(it, _)| *it == key)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[cfg(feature = "images")]
+    fn make_png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_pixel(width, height, Rgba([255, 0, 0, 255]));
+        let mut acc = vec![];
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut acc), ImageFormat::Png)
+            .unwrap();
+        acc
+    }
+
+    #[test]
+    fn test_detect_image_protocol() {
+        assert_eq2!(detect(&[("TERM", "xterm-kitty")]), ImageProtocol::Kitty);
+        assert_eq2!(
+            detect(&[("TERM_PROGRAM", "iTerm.app")]),
+            ImageProtocol::ITerm2
+        );
+        assert_eq2!(detect(&[("TERM", "foot")]), ImageProtocol::Sixel);
+        assert_eq2!(
+            detect(&[("TERM", "xterm-256color")]),
+            ImageProtocol::NotSupported
+        );
+    }
+
+    #[test]
+    fn test_fit_image_in_cells() {
+        let bounds_size = size!(col_count: 40, row_count: 10);
+        // Wide images are limited by the width, and tall ones by the height.
+        assert_eq2!(
+            fit_image_in_cells((800, 100), bounds_size),
+            size!(col_count: 40, row_count: 3)
+        );
+        assert_eq2!(
+            fit_image_in_cells((100, 400), bounds_size),
+            size!(col_count: 5, row_count: 10)
+        );
+    }
+
+    #[cfg(feature = "images")]
+    #[test]
+    fn test_get_image_escape_sequence() {
+        let data = make_png(20, 20);
+        let bounds_size = size!(col_count: 10, row_count: 5);
+
+        let kitty =
+            get_image_escape_sequence(ImageProtocol::Kitty, &data, bounds_size).unwrap();
+        assert!(kitty.starts_with("\x1b_Ga=T,q=2,C=1,f=100,c=10,r=5,m=0;"));
+        assert!(kitty.ends_with("\x1b\\"));
+
+        let iterm2 =
+            get_image_escape_sequence(ImageProtocol::ITerm2, &data, bounds_size).unwrap();
+        assert!(iterm2.starts_with(&format!(
+            "\x1b]1337;File=inline=1;size={};width=10;height=5;",
+            data.len()
+        )));
+
+        let sixel =
+            get_image_escape_sequence(ImageProtocol::Sixel, &data, bounds_size).unwrap();
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;100;100"));
+        assert!(sixel.ends_with("-\x1b\\"));
+
+        assert_eq2!(
+            get_image_escape_sequence(ImageProtocol::NotSupported, &data, bounds_size),
+            None
+        );
+        assert_eq2!(
+            get_image_escape_sequence(ImageProtocol::Sixel, b"not an image", bounds_size),
+            None
+        );
+    }
+
+    #[cfg(not(feature = "images"))]
+    #[test]
+    fn test_get_image_escape_sequence_wout_images_feature() {
+        let data = b"not decoded";
+        let bounds_size = size!(col_count: 10, row_count: 5);
+
+        assert!(
+            get_image_escape_sequence(ImageProtocol::ITerm2, data, bounds_size).is_some()
+        );
+        assert_eq2!(
+            get_image_escape_sequence(ImageProtocol::Kitty, data, bounds_size),
+            None
+        );
+        assert_eq2!(
+            get_image_escape_sequence(ImageProtocol::Sixel, data, bounds_size),
+            None
+        );
+    }
+
+    #[cfg(feature = "images")]
+    #[test]
+    fn test_encode_kitty_in_chunks() {
+        let payload = "a".repeat(KITTY_CHUNK_SIZE + 1);
+        let it = encode_kitty("a=T", &payload);
+        assert!(it.starts_with("\x1b_Ga=T,m=1;aaa"));
+        assert!(it.ends_with("\x1b_Gm=0;a\x1b\\"));
+    }
+}
@@ -17,7 +17,13 @@
 
 use std::fmt::{Display, Formatter};
 
-use crossterm::event::{Event::{self, FocusGained, FocusLost, Key, Mouse, Resize},
+use crossterm::event::{Event::{self,
+                               FocusGained,
+                               FocusLost,
+                               Key,
+                               Mouse,
+                               Paste,
+                               Resize},
                        KeyEvent,
                        MouseEvent};
 use r3bl_core::{size, Size};
@@ -27,12 +33,18 @@ use super::{KeyPress, MouseInput};
 
 /// Please see [KeyPress] for more information about handling keyboard input.
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InputEvent {
     Keyboard(KeyPress),
     Resize(Size),
     Mouse(MouseInput),
     Focus(FocusEvent),
+    /// Text that was pasted into the terminal, which is turned on by
+    /// [crate::RenderOp::EnterRawMode] (bracketed paste). It arrives as a single event,
+    /// instead of one [InputEvent::Keyboard] for each char, so that it can be inserted
+    /// in one go (eg: w/ [crate::EditorEvent::InsertText]) & its new lines aren't
+    /// mistaken for the Enter key.
+    Paste(String),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -96,7 +108,7 @@ pub(crate) mod converters {
                 Resize(cols, rows) => Ok((rows, cols).into()),
                 FocusGained => Ok(InputEvent::Focus(FocusEvent::Gained)),
                 FocusLost => Ok(InputEvent::Focus(FocusEvent::Lost)),
-                _ => Err(()),
+                Paste(text) => Ok(InputEvent::Paste(text)),
             }
         }
     }
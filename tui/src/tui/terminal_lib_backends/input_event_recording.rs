@@ -0,0 +1,262 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::VecDeque,
+          fs::File,
+          io::{BufRead, BufReader, BufWriter, Write},
+          path::{Path, PathBuf},
+          sync::OnceLock,
+          time::Duration};
+
+use r3bl_core::{CommonError, CommonErrorType, CommonResult};
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use super::InputEvent;
+
+/// Whether the [InputEvent]s of the app are recorded to a file, or replayed from one, see
+/// [set_input_event_recording_mode].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputEventRecordingMode {
+    /// Save all the input events that the main event loop gets from the terminal (w/
+    /// the time that they arrived) to the file, see [InputEventRecorder].
+    Record(PathBuf),
+    /// Feed the input events in the file into the main event loop (at the same times
+    /// that they were recorded), see [InputEventPlayer]. The terminal's input events
+    /// are still handled, so the user can take over once the playback is done.
+    Playback(PathBuf),
+}
+
+static SELECTED_INPUT_EVENT_RECORDING_MODE: OnceLock<InputEventRecordingMode> =
+    OnceLock::new();
+
+/// Record or replay the input events of the app, eg: to attach a recording that
+/// reproduces a bug to a bug report, or to run a scripted demo. This has to be called at
+/// startup, before [crate::TerminalWindow::main_event_loop], and it can only be called
+/// once. If a mode was already selected, the `mode` is returned in the [Err].
+pub fn set_input_event_recording_mode(
+    mode: InputEventRecordingMode,
+) -> Result<(), InputEventRecordingMode> {
+    SELECTED_INPUT_EVENT_RECORDING_MODE.set(mode)
+}
+
+pub fn get_input_event_recording_mode() -> Option<&'static InputEventRecordingMode> {
+    SELECTED_INPUT_EVENT_RECORDING_MODE.get()
+}
+
+/// An [InputEvent] w/ the time that it arrived, since the start of the recording. A
+/// recording is a file w/ one of these (in JSON) per line, so it can be edited by hand,
+/// eg: to write a scripted demo.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedInputEvent {
+    pub elapsed_ms: u64,
+    pub input_event: InputEvent,
+}
+
+/// Saves [InputEvent]s to a file, see [InputEventRecordingMode::Record].
+///
+/// [InputEvent::Resize] isn't recorded, since a recording is replayed at the size of the
+/// terminal that it is replayed in.
+#[derive(Debug)]
+pub struct InputEventRecorder {
+    writer: BufWriter<File>,
+    start: Instant,
+}
+
+impl InputEventRecorder {
+    /// Create (or truncate) the file at `path`.
+    pub fn try_new(path: impl AsRef<Path>) -> CommonResult<Self> {
+        let path = path.as_ref();
+        match File::create(path) {
+            Ok(file) => Ok(Self {
+                writer: BufWriter::new(file),
+                start: Instant::now(),
+            }),
+            Err(error) => CommonError::new_error_result(
+                CommonErrorType::IOError,
+                &format!("Failed to create recording: {path:?}, error: {error}"),
+            ),
+        }
+    }
+
+    pub fn record(&mut self, input_event: &InputEvent) -> CommonResult<()> {
+        if let InputEvent::Resize(_) = input_event {
+            return Ok(());
+        }
+
+        let recorded_input_event = RecordedInputEvent {
+            elapsed_ms: self.start.elapsed().as_millis() as u64,
+            input_event: input_event.clone(),
+        };
+        let result = serde_json::to_string(&recorded_input_event)
+            .map_err(std::io::Error::from)
+            .and_then(|line| writeln!(self.writer, "{line}"))
+            // Flushed right away, so that the recording isn't lost if the app crashes.
+            .and_then(|_| self.writer.flush());
+        if let Err(error) = result {
+            return CommonError::new_error_result(
+                CommonErrorType::IOError,
+                &format!("Failed to record input event, error: {error}"),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Replays [RecordedInputEvent]s, see [InputEventRecordingMode::Playback]. The times of
+/// the events are relative to when the player was created.
+#[derive(Debug, Default)]
+pub struct InputEventPlayer {
+    recorded_input_events: VecDeque<RecordedInputEvent>,
+    start: Option<Instant>,
+}
+
+impl InputEventPlayer {
+    pub fn new(recorded_input_events: Vec<RecordedInputEvent>) -> Self {
+        Self {
+            recorded_input_events: recorded_input_events.into(),
+            start: Some(Instant::now()),
+        }
+    }
+
+    /// Load a recording that was saved by [InputEventRecorder]. The blank lines are
+    /// skipped.
+    pub fn try_load(path: impl AsRef<Path>) -> CommonResult<Self> {
+        let path = path.as_ref();
+        let file = match File::open(path) {
+            Ok(it) => it,
+            Err(error) => {
+                return CommonError::new_error_result(
+                    CommonErrorType::IOError,
+                    &format!("Failed to open recording: {path:?}, error: {error}"),
+                );
+            }
+        };
+
+        let mut acc = vec![];
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line_number = index + 1;
+            let parsed = line.map_err(|error| error.to_string()).and_then(|line| {
+                match line.trim() {
+                    "" => Ok(None),
+                    it => serde_json::from_str(it)
+                        .map(Some)
+                        .map_err(|error| error.to_string()),
+                }
+            });
+            match parsed {
+                Ok(Some(it)) => acc.push(it),
+                Ok(None) => {}
+                Err(error) => {
+                    return CommonError::new_error_result(
+                        CommonErrorType::ParsingError,
+                        &format!(
+                            "Failed to parse recording: {path:?}, line: {line_number}, \
+                             error: {error}"
+                        ),
+                    );
+                }
+            }
+        }
+
+        Ok(Self::new(acc))
+    }
+
+    /// The number of events that haven't been replayed yet.
+    pub fn len(&self) -> usize { self.recorded_input_events.len() }
+
+    pub fn is_empty(&self) -> bool { self.recorded_input_events.is_empty() }
+
+    /// Wait until the next event is due, and return it. This never returns once all the
+    /// events have been replayed. It is cancel safe, so it can be used in
+    /// [tokio::select!].
+    pub async fn next_input_event(&mut self) -> InputEvent {
+        let (Some(start), Some(next)) = (self.start, self.recorded_input_events.front())
+        else {
+            return std::future::pending().await;
+        };
+
+        tokio::time::sleep_until(start + Duration::from_millis(next.elapsed_ms)).await;
+
+        match self.recorded_input_events.pop_front() {
+            Some(it) => it.input_event,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{Key, KeyPress, SpecialKey};
+
+    fn recorded_input_events() -> Vec<RecordedInputEvent> {
+        vec![
+            RecordedInputEvent {
+                elapsed_ms: 0,
+                input_event: InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::Character('a'),
+                }),
+            },
+            RecordedInputEvent {
+                elapsed_ms: 10,
+                input_event: InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::SpecialKey(SpecialKey::Enter),
+                }),
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_playback_in_order() {
+        let mut player = InputEventPlayer::new(recorded_input_events());
+        assert_eq2!(player.len(), 2);
+        for it in recorded_input_events() {
+            assert_eq2!(player.next_input_event().await, it.input_event);
+        }
+        assert!(player.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_record_and_load() -> CommonResult<()> {
+        let path = std::env::temp_dir().join(format!(
+            "r3bl_tui_input_event_recording_{}.jsonl",
+            std::process::id()
+        ));
+
+        let mut recorder = InputEventRecorder::try_new(&path)?;
+        for it in recorded_input_events() {
+            recorder.record(&it.input_event)?;
+        }
+        // Resize events aren't recorded.
+        recorder.record(&InputEvent::Resize(r3bl_core::size!(
+            col_count: 10, row_count: 10
+        )))?;
+        drop(recorder);
+
+        let mut player = InputEventPlayer::try_load(&path)?;
+        let _ = std::fs::remove_file(&path);
+        assert_eq2!(player.len(), 2);
+        for it in recorded_input_events() {
+            assert_eq2!(player.next_input_event().await, it.input_event);
+        }
+
+        Ok(())
+    }
+}
@@ -23,7 +23,7 @@ use crossterm::event::{KeyCode,
                        ModifierKeyCode};
 use serde::{Deserialize, Serialize};
 
-use super::{Enhanced, ModifierKeysMask};
+use super::{Enhanced, KeyState, ModifierKeysMask};
 use crate::{convert_key_modifiers, MediaKey, ModifierKeyEnum, SpecialKeyExt};
 
 /// Examples.
@@ -133,6 +133,37 @@ pub enum KeyPress {
     WithModifiers { key: Key, mask: ModifierKeysMask },
 }
 
+impl KeyPress {
+    /// Returns the text to show this key press to the user, eg: `Ctrl+S`, or `Enter`.
+    pub fn get_display_text(&self) -> String {
+        let (key, maybe_mask) = match *self {
+            KeyPress::Plain { key } => (key, None),
+            KeyPress::WithModifiers { key, mask } => (key, Some(mask)),
+        };
+
+        let mut it = String::new();
+        if let Some(mask) = maybe_mask {
+            for (key_state, name) in [
+                (mask.ctrl_key_state, "Ctrl"),
+                (mask.alt_key_state, "Alt"),
+                (mask.shift_key_state, "Shift"),
+            ] {
+                if key_state == KeyState::Pressed {
+                    it.push_str(name);
+                    it.push('+');
+                }
+            }
+        }
+        match key {
+            Key::Character(character) => it.push(character.to_ascii_uppercase()),
+            Key::SpecialKey(special_key) => it.push_str(&format!("{special_key:?}")),
+            Key::FunctionKey(function_key) => it.push_str(&format!("{function_key:?}")),
+            Key::KittyKeyboardProtocol(enhanced) => it.push_str(&format!("{enhanced:?}")),
+        }
+        it
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Copy)]
 pub enum Key {
     /// [char] that can be printed to the console. Displayable characters are:
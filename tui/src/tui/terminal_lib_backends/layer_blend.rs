@@ -0,0 +1,247 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+use r3bl_core::{RgbValue, TuiColor, SPACER};
+use serde::{Deserialize, Serialize};
+
+use crate::PixelChar;
+
+/// How the [PixelChar]s that are painted in a [crate::ZOrder] are combined w/ the ones
+/// that were already painted beneath them, when a [crate::RenderPipeline] is converted
+/// into an [crate::OffscreenBuffer]. Set it for a [crate::ZOrder] w/
+/// [crate::RenderPipeline::set_layer_blend].
+#[derive(
+    Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, size_of::SizeOf,
+)]
+pub enum LayerBlend {
+    /// The [PixelChar]s beneath are overwritten. This is the default.
+    Overwrite,
+    /// The bg colors of the [PixelChar]s are mixed w/ the colors beneath them, w/ this
+    /// alpha (`0` is transparent, `255` is opaque).
+    /// - Text replaces the text beneath it, and its bg color is mixed w/ the bg color
+    ///   beneath it.
+    /// - Spaces (that have a bg color) keep the text beneath them, and tint its colors.
+    ///   So painting spaces over the whole window in [crate::ZOrder::High] (w/ this set
+    ///   for it) dims everything beneath a modal dialog that is painted in
+    ///   [crate::ZOrder::Glass].
+    ///
+    /// The [PixelChar]s w/out a bg color overwrite the ones beneath them. A color that
+    /// can't be converted to RGB (ie: [TuiColor::Reset], or the terminal's default
+    /// color) isn't mixed.
+    Alpha(u8),
+}
+
+impl Default for LayerBlend {
+    fn default() -> Self { Self::Overwrite }
+}
+
+/// Returns the [PixelChar] that replaces `pixel_char_beneath` when `new_pixel_char` is
+/// painted over it w/ the given `layer_blend`.
+pub fn blend_pixel_char(
+    new_pixel_char: PixelChar,
+    pixel_char_beneath: &PixelChar,
+    layer_blend: LayerBlend,
+) -> PixelChar {
+    let LayerBlend::Alpha(alpha) = layer_blend else {
+        return new_pixel_char;
+    };
+
+    let PixelChar::PlainText {
+        content,
+        maybe_style: Some(mut style),
+        maybe_hyperlink,
+    } = new_pixel_char
+    else {
+        return new_pixel_char;
+    };
+    let Some(color_bg) = style.color_bg else {
+        return PixelChar::PlainText {
+            content,
+            maybe_style: Some(style),
+            maybe_hyperlink,
+        };
+    };
+
+    match pixel_char_beneath {
+        // Tint the text beneath.
+        PixelChar::PlainText {
+            content: content_beneath,
+            maybe_style: maybe_style_beneath,
+            maybe_hyperlink: maybe_hyperlink_beneath,
+        } if content.string == SPACER => {
+            let mut style_beneath = maybe_style_beneath.unwrap_or_default();
+            style_beneath.color_bg =
+                Some(mix_colors(color_bg, style_beneath.color_bg, alpha));
+            style_beneath.color_fg = style_beneath
+                .color_fg
+                .map(|color_fg| mix_colors(color_bg, Some(color_fg), alpha));
+            PixelChar::PlainText {
+                content: content_beneath.clone(),
+                maybe_style: Some(style_beneath),
+                maybe_hyperlink: maybe_hyperlink_beneath.clone(),
+            }
+        }
+        // This is part of the wide char before it (which is tinted on its own).
+        PixelChar::Void if content.string == SPACER => PixelChar::Void,
+        _ => {
+            let maybe_color_bg_beneath = match pixel_char_beneath {
+                PixelChar::PlainText {
+                    maybe_style: Some(style_beneath),
+                    ..
+                } => style_beneath.color_bg,
+                _ => None,
+            };
+            style.color_bg = Some(mix_colors(color_bg, maybe_color_bg_beneath, alpha));
+            PixelChar::PlainText {
+                content,
+                maybe_style: Some(style),
+                maybe_hyperlink,
+            }
+        }
+    }
+}
+
+/// Mix the `top` color over the `maybe_bottom` color w/ the given `alpha` (`0` is
+/// transparent, `255` is opaque). Returns `top` if either color can't be converted to
+/// RGB.
+pub fn mix_colors(top: TuiColor, maybe_bottom: Option<TuiColor>, alpha: u8) -> TuiColor {
+    let (Some(top_rgb), Some(bottom_rgb)) =
+        (try_get_rgb(top), maybe_bottom.and_then(try_get_rgb))
+    else {
+        return top;
+    };
+
+    let mix = |top: u8, bottom: u8| -> u8 {
+        let (top, bottom, alpha) = (top as u16, bottom as u16, alpha as u16);
+        ((top * alpha + bottom * (255 - alpha) + 127) / 255) as u8
+    };
+
+    TuiColor::Rgb(RgbValue::from_u8(
+        mix(top_rgb.red, bottom_rgb.red),
+        mix(top_rgb.green, bottom_rgb.green),
+        mix(top_rgb.blue, bottom_rgb.blue),
+    ))
+}
+
+/// Returns the RGB value of the `color`, or [None] for [TuiColor::Reset] (the terminal's
+/// default color, which isn't known).
+pub fn try_get_rgb(color: TuiColor) -> Option<RgbValue> {
+    match color {
+        TuiColor::Reset => None,
+        TuiColor::Ansi(ansi_value) => Some(RgbValue::from(ansi_value)),
+        _ => RgbValue::try_from_tui_color(color).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, color, GraphemeClusterSegment, TuiStyle};
+
+    use super::*;
+
+    fn make_pixel_char(text: &str, maybe_style: Option<TuiStyle>) -> PixelChar {
+        PixelChar::PlainText {
+            content: GraphemeClusterSegment::from(text),
+            maybe_style,
+            maybe_hyperlink: None,
+        }
+    }
+
+    #[test]
+    fn test_mix_colors() {
+        let black = color!(0, 0, 0);
+        let white = color!(255, 255, 255);
+        assert_eq2!(mix_colors(black, Some(white), 255), black);
+        assert_eq2!(mix_colors(black, Some(white), 0), white);
+        assert_eq2!(mix_colors(black, Some(white), 128), color!(127, 127, 127));
+        // The default color can't be mixed.
+        assert_eq2!(mix_colors(black, None, 128), black);
+        assert_eq2!(mix_colors(black, Some(TuiColor::Reset), 128), black);
+    }
+
+    #[test]
+    fn test_spaces_tint_the_text_beneath() {
+        let backdrop = make_pixel_char(
+            SPACER,
+            Some(TuiStyle {
+                color_bg: Some(color!(0, 0, 0)),
+                ..Default::default()
+            }),
+        );
+        let beneath = make_pixel_char(
+            "a",
+            Some(TuiStyle {
+                color_fg: Some(color!(255, 255, 255)),
+                color_bg: Some(color!(0, 0, 255)),
+                ..Default::default()
+            }),
+        );
+
+        // Overwritten.
+        assert_eq2!(
+            blend_pixel_char(backdrop.clone(), &beneath, LayerBlend::Overwrite),
+            backdrop
+        );
+
+        // Tinted.
+        assert_eq2!(
+            blend_pixel_char(backdrop.clone(), &beneath, LayerBlend::Alpha(128)),
+            make_pixel_char(
+                "a",
+                Some(TuiStyle {
+                    color_fg: Some(color!(127, 127, 127)),
+                    color_bg: Some(color!(0, 0, 127)),
+                    ..Default::default()
+                }),
+            )
+        );
+    }
+
+    #[test]
+    fn test_text_replaces_the_text_beneath() {
+        let beneath = make_pixel_char(
+            "a",
+            Some(TuiStyle {
+                color_bg: Some(color!(0, 0, 255)),
+                ..Default::default()
+            }),
+        );
+        let new_pixel_char = make_pixel_char(
+            "b",
+            Some(TuiStyle {
+                color_bg: Some(color!(255, 0, 0)),
+                ..Default::default()
+            }),
+        );
+        assert_eq2!(
+            blend_pixel_char(new_pixel_char, &beneath, LayerBlend::Alpha(128)),
+            make_pixel_char(
+                "b",
+                Some(TuiStyle {
+                    color_bg: Some(color!(128, 0, 127)),
+                    ..Default::default()
+                }),
+            )
+        );
+
+        // W/out a bg color, nothing is mixed.
+        let new_pixel_char = make_pixel_char("b", None);
+        assert_eq2!(
+            blend_pixel_char(new_pixel_char.clone(), &beneath, LayerBlend::Alpha(128)),
+            new_pixel_char
+        );
+    }
+}
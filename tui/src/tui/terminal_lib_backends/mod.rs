@@ -47,50 +47,79 @@
 //! - Sodium:
 //!   - repo: <https://github.com/redox-os/sodium>
 
+/// The terminal libraries that can be used to paint & read input events, see
+/// [TerminalBackend]. Termion is only available w/ the `termion` feature (and only on
+/// unix).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TerminalLibBackend {
     Crossterm,
+    #[cfg(feature = "termion")]
     Termion,
 }
 
+/// The backend that is used unless another one is selected w/ [set_terminal_lib_backend].
 pub const TERMINAL_LIB_BACKEND: TerminalLibBackend = TerminalLibBackend::Crossterm;
 
 // Attach source files.
 pub mod crossterm_backend;
 pub mod crossterm_color_converter;
+pub mod cursor_shape;
 pub mod enhanced_keys;
+pub mod headless_backend;
+pub mod hyperlink;
+pub mod inline_image;
 pub mod input_device_ext;
 pub mod input_event;
+pub mod input_event_recording;
 pub mod keypress;
+pub mod layer_blend;
 pub mod modifier_keys_mask;
 pub mod mouse_input;
 pub mod offscreen_buffer;
+pub mod offscreen_buffer_snapshot;
 pub mod paint;
+pub mod paint_task;
 pub mod raw_mode;
 pub mod render_op;
 pub mod render_pipeline;
 pub mod render_pipeline_to_offscreen_buffer;
 pub mod render_tui_styled_texts;
+pub mod synchronized_update;
+pub mod terminal_backend;
 pub mod terminal_lib_operations;
+pub mod terminal_title;
 pub mod termion_backend;
 pub mod z_order;
 
 // Re-export.
 pub use crossterm_backend::*;
 pub use crossterm_color_converter::*;
+pub use cursor_shape::*;
 pub use enhanced_keys::*;
+pub use headless_backend::*;
+pub use hyperlink::*;
+pub use inline_image::*;
 pub use input_device_ext::*;
 pub use input_event::*;
+pub use input_event_recording::*;
 pub use keypress::*;
+pub use layer_blend::*;
 pub use modifier_keys_mask::*;
 pub use mouse_input::*;
 pub use offscreen_buffer::*;
+pub use offscreen_buffer_snapshot::*;
 pub use paint::*;
+pub use paint_task::*;
 pub use raw_mode::*;
 pub use render_op::*;
 pub use render_pipeline::*;
 pub use render_pipeline_to_offscreen_buffer::*;
 pub use render_tui_styled_texts::*;
+pub use synchronized_update::*;
+pub use terminal_backend::*;
 pub use terminal_lib_operations::*;
+pub use terminal_title::*;
+pub use termion_backend::*;
 pub use z_order::*;
 
 // Tests.
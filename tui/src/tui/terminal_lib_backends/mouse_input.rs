@@ -15,12 +15,41 @@
  *   limitations under the License.
  */
 
+use std::sync::OnceLock;
+
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use r3bl_core::{position, Position};
 use serde::{Deserialize, Serialize};
 
 use super::{convert_key_modifiers, ModifierKeysMask};
 
+static MOUSE_CAPTURE_MODE: OnceLock<MouseCaptureMode> = OnceLock::new();
+
+/// Controls whether the terminal reports mouse events (as [crate::InputEvent::Mouse])
+/// while the app is running, which is turned on by [crate::RenderOp::EnterRawMode]. When
+/// it is on, the terminal's own text selection (by dragging) only works w/ a modifier
+/// key held down (eg: `Shift`, or `Option` in iTerm2), so some apps might not want it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MouseCaptureMode {
+    Enable,
+    Disable,
+}
+
+/// Set the [MouseCaptureMode] (eg: from a command line flag). This has to be called
+/// before [crate::TerminalWindow::main_event_loop], and it can only be called once. If
+/// it was already set (or used), the `mode` is returned in the [Err].
+pub fn set_mouse_capture_mode(mode: MouseCaptureMode) -> Result<(), MouseCaptureMode> {
+    MOUSE_CAPTURE_MODE.set(mode)
+}
+
+/// Mouse capture is enabled, unless [set_mouse_capture_mode] was used to disable it.
+pub fn get_mouse_capture_mode() -> MouseCaptureMode {
+    *MOUSE_CAPTURE_MODE.get_or_init(|| MouseCaptureMode::Enable)
+}
+
+/// The pointer's position is relative to the top left corner of the terminal window
+/// (not a box). To find the component that is under it, use
+/// [crate::ComponentRegistry::route_mouse_event_to_component_at_pos].
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Copy)]
 pub struct MouseInput {
     pub pos: Position,
@@ -71,7 +100,7 @@ impl From<MouseEventKind> for MouseInputKind {
             MouseEventKind::Drag(button) => MouseInputKind::MouseDrag(button.into()),
             MouseEventKind::ScrollUp => MouseInputKind::ScrollUp,
             MouseEventKind::ScrollDown => MouseInputKind::ScrollDown,
-            MouseEventKind::ScrollLeft => MouseInputKind::ScrollDown,
+            MouseEventKind::ScrollLeft => MouseInputKind::ScrollLeft,
             MouseEventKind::ScrollRight => MouseInputKind::ScrollRight,
         }
     }
@@ -31,7 +31,7 @@ use r3bl_core::{ch,
                 TuiStyle};
 use serde::{Deserialize, Serialize};
 
-use super::{FlushKind, RenderOps};
+use super::{CursorShape, FlushKind, ImageBounds, LayerBlend, RenderOps};
 use crate::List;
 
 /// Represents a grid of cells where the row/column index maps to the terminal screen.
@@ -54,6 +54,41 @@ pub struct OffscreenBuffer {
     pub my_pos: Position,
     pub my_fg_color: Option<TuiColor>,
     pub my_bg_color: Option<TuiColor>,
+    /// Set by [super::RenderOp::SetHyperlink], and saved in the [PixelChar]s that are painted
+    /// while it is set.
+    pub my_hyperlink: Option<String>,
+    /// How the [PixelChar]s that are painted are combined w/ the ones beneath them. This
+    /// is set for each [super::ZOrder] by [crate::RenderPipeline::convert].
+    pub my_layer_blend: LayerBlend,
+    /// The images that are painted on top of the [PixelChar]s, in the order that they were
+    /// added by [super::RenderOp::PaintImage].
+    pub images: Vec<OffscreenImage>,
+    /// Set by [super::RenderOp::SetTitle]. It is painted by [super::paint] when it isn't
+    /// the same as the one in the last frame.
+    pub maybe_title: Option<String>,
+    /// Set by [super::RenderOp::ShowCursor] (w/ the position that the cursor was at),
+    /// and cleared by [super::RenderOp::HideCursor]. It is painted by [super::paint]
+    /// after the rest of the frame.
+    pub maybe_cursor: Option<(Position, CursorShape)>,
+}
+
+/// An image that is painted on top of the cells of an [OffscreenBuffer] (which are
+/// cleared), see [super::RenderOp::PaintImage].
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, size_of::SizeOf)]
+pub struct OffscreenImage {
+    pub data: Vec<u8>,
+    pub bounds: ImageBounds,
+}
+
+impl Debug for OffscreenImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "OffscreenImage({} bytes, {:?})",
+            self.data.len(),
+            self.bounds
+        )
+    }
 }
 
 pub enum OffscreenBufferDiffResult {
@@ -64,6 +99,17 @@ pub enum OffscreenBufferDiffResult {
 pub type PixelCharDiffChunks = List<DiffChunk>;
 pub type DiffChunk = (Position, PixelChar);
 
+/// How much of the [OffscreenBuffer] was painted to the terminal in the last render, see
+/// [crate::paint]. Only the cells that changed since the previous render are painted,
+/// unless the window was resized (or this is the first render), in which case all of them
+/// are. This is saved in [crate::GlobalData::last_paint_stats].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PaintStats {
+    pub cells_painted: usize,
+    pub cells_total: usize,
+    pub is_full_paint: bool,
+}
+
 mod offscreen_buffer_impl {
     use super::*;
 
@@ -77,6 +123,52 @@ mod offscreen_buffer_impl {
         }
     }
 
+    impl PaintStats {
+        pub fn new_full_paint(window_size: Size) -> Self {
+            let cells_total = get_cell_count(window_size);
+            Self {
+                cells_painted: cells_total,
+                cells_total,
+                is_full_paint: true,
+            }
+        }
+
+        /// The [PixelChar::Void] chunks aren't counted, since they are painted w/ the
+        /// wide [PixelChar] before them.
+        pub fn new_diff_paint(
+            window_size: Size,
+            diff_chunks: &PixelCharDiffChunks,
+        ) -> Self {
+            Self {
+                cells_painted: diff_chunks
+                    .iter()
+                    .filter(|(_, pixel_char)| *pixel_char != PixelChar::Void)
+                    .count(),
+                cells_total: get_cell_count(window_size),
+                is_full_paint: false,
+            }
+        }
+    }
+
+    fn get_cell_count(window_size: Size) -> usize {
+        ch!(@to_usize window_size.col_count) * ch!(@to_usize window_size.row_count)
+    }
+
+    impl fmt::Display for PaintStats {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "painted {} of {} cells ({})",
+                self.cells_painted,
+                self.cells_total,
+                match self.is_full_paint {
+                    true => "full",
+                    false => "diff",
+                }
+            )
+        }
+    }
+
     impl Deref for OffscreenBuffer {
         type Target = PixelCharLines;
 
@@ -102,7 +194,9 @@ mod offscreen_buffer_impl {
         /// Checks for differences between self and other. Returns a list of positions and pixel
         /// chars if there are differences (from other).
         pub fn diff(&self, other: &Self) -> OffscreenBufferDiffResult {
-            if self.window_size != other.window_size {
+            // The images can't be painted partially, and the old ones have to be removed
+            // (by clearing the screen), so everything is painted again when they change.
+            if self.window_size != other.window_size || self.images != other.images {
                 return OffscreenBufferDiffResult::NotComparable;
             }
 
@@ -132,12 +226,18 @@ mod offscreen_buffer_impl {
                 my_pos: Default::default(),
                 my_fg_color: None,
                 my_bg_color: None,
+                my_hyperlink: None,
+                my_layer_blend: LayerBlend::Overwrite,
+                images: vec![],
+                maybe_title: None,
+                maybe_cursor: None,
             }
         }
 
         // Make sure each line is full of empty chars.
         pub fn clear(&mut self) {
             self.buffer = PixelCharLines::new_with_capacity_initialized(self.window_size);
+            self.images.clear();
         }
 
         pub fn pretty_print(&self) -> String {
@@ -381,6 +481,8 @@ pub enum PixelChar {
     PlainText {
         content: GraphemeClusterSegment,
         maybe_style: Option<TuiStyle>,
+        /// The URL that this char links to, see [super::RenderOp::SetHyperlink].
+        maybe_hyperlink: Option<String>,
     },
 }
 
@@ -415,6 +517,7 @@ mod pixel_char_impl {
                 PixelChar::PlainText {
                     content: character,
                     maybe_style,
+                    ..
                 } => {
                     let output = match maybe_style {
                         // Content + style.
@@ -488,10 +591,12 @@ mod tests {
         my_offscreen_buffer.buffer[0][0] = PixelChar::PlainText {
             content: GraphemeClusterSegment::from("a"),
             maybe_style: Some(tui_style! {color_bg: color!(@green) }),
+            maybe_hyperlink: None,
         };
         my_offscreen_buffer.buffer[1][9] = PixelChar::PlainText {
             content: GraphemeClusterSegment::from("z"),
             maybe_style: Some(tui_style! {color_bg: color!(@red) }),
+            maybe_hyperlink: None,
         };
         // println!("my_offscreen_buffer: \n{:#?}", my_offscreen_buffer);
         my_offscreen_buffer.clear();
@@ -502,4 +607,46 @@ mod tests {
         }
         // println!("my_offscreen_buffer: \n{:#?}", my_offscreen_buffer);
     }
+
+    #[test]
+    fn test_offscreen_buffer_diff_paint_stats() {
+        let window_size = size! { col_count: 10, row_count: 2};
+        let old_offscreen_buffer =
+            OffscreenBuffer::new_with_capacity_initialized(window_size);
+        let mut new_offscreen_buffer = old_offscreen_buffer.clone();
+        new_offscreen_buffer.buffer[1][2] = PixelChar::PlainText {
+            content: GraphemeClusterSegment::from("😃"),
+            maybe_style: None,
+            maybe_hyperlink: None,
+        };
+        new_offscreen_buffer.buffer[1][3] = PixelChar::Void;
+
+        let OffscreenBufferDiffResult::Comparable(diff_chunks) =
+            old_offscreen_buffer.diff(&new_offscreen_buffer)
+        else {
+            panic!("The buffers are the same size, so they should be comparable");
+        };
+        assert_eq2!(diff_chunks.len(), 2);
+        assert_eq2!(
+            PaintStats::new_diff_paint(window_size, &diff_chunks),
+            PaintStats {
+                cells_painted: 1,
+                cells_total: 20,
+                is_full_paint: false,
+            }
+        );
+
+        // The window was resized.
+        let resized_offscreen_buffer = OffscreenBuffer::new_with_capacity_initialized(
+            size! { col_count: 12, row_count: 2},
+        );
+        assert!(matches!(
+            old_offscreen_buffer.diff(&resized_offscreen_buffer),
+            OffscreenBufferDiffResult::NotComparable
+        ));
+        assert_eq2!(
+            PaintStats::new_full_paint(window_size).to_string(),
+            "painted 20 of 20 cells (full)"
+        );
+    }
 }
@@ -0,0 +1,366 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+use std::{fmt::Write as _, fs, path::Path};
+
+use crossterm::style::Colored;
+use r3bl_core::{CommonError,
+                CommonErrorType,
+                CommonResult,
+                RgbValue,
+                TuiStyle,
+                TuiUnderlineStyle};
+
+use super::{convert_from_tui_color_to_crossterm_color,
+            get_hyperlink_escape_sequence,
+            try_get_rgb,
+            OffscreenBuffer,
+            PixelChar};
+
+/// The colors that are used in [SnapshotFormat::Html] for the terminal's default fg & bg
+/// colors (which aren't known), eg: for [r3bl_core::TuiColor::Reset], or for the text
+/// that doesn't have a color.
+const HTML_DEFAULT_FG_COLOR: &str = "#d0d0d0";
+const HTML_DEFAULT_BG_COLOR: &str = "#1c1c1c";
+
+/// The formats that an [OffscreenBuffer] can be saved in w/
+/// [OffscreenBuffer::render_snapshot] ("screenshots" of the app).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// Plain text w/ ANSI escape sequences for the styles (& OSC 8 for the hyperlinks),
+    /// which can be printed in a terminal (eg: w/ `cat`), or compared in golden-file
+    /// tests.
+    Ansi,
+    /// A standalone HTML file, w/ the styles in inline CSS, that can be attached to bug
+    /// reports or used in docs.
+    Html,
+}
+
+impl OffscreenBuffer {
+    /// Renders the cells of the buffer in the `format`, one line per row. The images
+    /// (see [super::RenderOp::PaintImage]) aren't included, only their (empty) cells.
+    pub fn render_snapshot(&self, format: SnapshotFormat) -> String {
+        match format {
+            SnapshotFormat::Ansi => render_ansi_snapshot(self),
+            SnapshotFormat::Html => render_html_snapshot(self),
+        }
+    }
+
+    /// Renders the buffer w/ [Self::render_snapshot], and saves it to `path`.
+    pub fn save_snapshot(
+        &self,
+        path: impl AsRef<Path>,
+        format: SnapshotFormat,
+    ) -> CommonResult<()> {
+        let path = path.as_ref();
+        if let Err(error) = fs::write(path, self.render_snapshot(format)) {
+            return CommonError::new_error_result(
+                CommonErrorType::IOError,
+                &format!("Failed to save snapshot: {path:?}, error: {error}"),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The text of each cell, w/ the style & the hyperlink that it is painted with. The
+/// [PixelChar::Void]s (that follow wide chars) are skipped.
+fn get_cells(
+    pixel_chars: &[PixelChar],
+) -> impl Iterator<Item = (&str, Option<&TuiStyle>, Option<&str>)> {
+    pixel_chars
+        .iter()
+        .filter_map(|pixel_char| match pixel_char {
+            PixelChar::Void => None,
+            PixelChar::Spacer => Some((" ", None, None)),
+            PixelChar::PlainText {
+                content,
+                maybe_style,
+                maybe_hyperlink,
+            } => Some((
+                content.string.as_str(),
+                maybe_style.as_ref(),
+                maybe_hyperlink.as_deref(),
+            )),
+        })
+}
+
+fn render_ansi_snapshot(offscreen_buffer: &OffscreenBuffer) -> String {
+    let mut acc = String::new();
+
+    for line in offscreen_buffer.buffer.iter() {
+        let mut current_sgr_params = String::new();
+        let mut maybe_current_hyperlink: Option<&str> = None;
+
+        for (text, maybe_style, maybe_hyperlink) in get_cells(line) {
+            if maybe_hyperlink != maybe_current_hyperlink {
+                acc.push_str(&get_hyperlink_escape_sequence(maybe_hyperlink));
+                maybe_current_hyperlink = maybe_hyperlink;
+            }
+
+            let sgr_params = maybe_style.map(get_sgr_params).unwrap_or_default();
+            if sgr_params != current_sgr_params {
+                if sgr_params.is_empty() {
+                    acc.push_str("\x1b[0m");
+                } else {
+                    let _ = write!(acc, "\x1b[0;{sgr_params}m");
+                }
+                current_sgr_params = sgr_params;
+            }
+
+            acc.push_str(text);
+        }
+
+        // Each line starts w/out any style, so it can be printed by itself.
+        if !current_sgr_params.is_empty() {
+            acc.push_str("\x1b[0m");
+        }
+        if maybe_current_hyperlink.is_some() {
+            acc.push_str(&get_hyperlink_escape_sequence(None));
+        }
+        acc.push('\n');
+    }
+
+    acc
+}
+
+/// The parameters of the SGR escape sequence that applies the `style`, eg: `1;38;5;9`.
+fn get_sgr_params(style: &TuiStyle) -> String {
+    let mut it: Vec<String> = vec![];
+    if style.bold {
+        it.push("1".into());
+    }
+    if style.dim {
+        it.push("2".into());
+    }
+    if style.italic {
+        it.push("3".into());
+    }
+    if style.underline {
+        it.push(style.underline_style.get_sgr_params().into());
+        if let Some(color_underline) = style.color_underline {
+            it.push(
+                Colored::UnderlineColor(convert_from_tui_color_to_crossterm_color(
+                    color_underline,
+                ))
+                .to_string(),
+            );
+        }
+    }
+    if style.reverse {
+        it.push("7".into());
+    }
+    if style.hidden {
+        it.push("8".into());
+    }
+    if style.strikethrough {
+        it.push("9".into());
+    }
+    if let Some(color_fg) = style.color_fg {
+        it.push(
+            Colored::ForegroundColor(convert_from_tui_color_to_crossterm_color(color_fg))
+                .to_string(),
+        );
+    }
+    if let Some(color_bg) = style.color_bg {
+        it.push(
+            Colored::BackgroundColor(convert_from_tui_color_to_crossterm_color(color_bg))
+                .to_string(),
+        );
+    }
+    it.join(";")
+}
+
+fn render_html_snapshot(offscreen_buffer: &OffscreenBuffer) -> String {
+    let title = offscreen_buffer
+        .maybe_title
+        .as_deref()
+        .unwrap_or("Snapshot");
+
+    let mut acc = String::new();
+    acc.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(acc, "<title>{}</title>", escape_html(title));
+    acc.push_str("</head>\n<body style=\"margin: 0\">\n");
+    let _ = write!(
+        acc,
+        "<pre style=\"margin: 0; padding: 1em; font-family: monospace; \
+         color: {HTML_DEFAULT_FG_COLOR}; background-color: {HTML_DEFAULT_BG_COLOR}\">"
+    );
+
+    for line in offscreen_buffer.buffer.iter() {
+        // The cells w/ the same style & hyperlink are painted in a single span.
+        let mut runs: Vec<(String, Option<&str>, String)> = vec![];
+        for (text, maybe_style, maybe_hyperlink) in get_cells(line) {
+            let css = maybe_style.map(get_css).unwrap_or_default();
+            match runs.last_mut() {
+                Some((last_css, last_hyperlink, last_text))
+                    if *last_css == css && *last_hyperlink == maybe_hyperlink =>
+                {
+                    last_text.push_str(text);
+                }
+                _ => runs.push((css, maybe_hyperlink, text.to_string())),
+            }
+        }
+
+        for (css, maybe_hyperlink, text) in runs {
+            if let Some(url) = maybe_hyperlink {
+                let _ = write!(acc, "<a href=\"{}\">", escape_html(url));
+            }
+            if css.is_empty() {
+                acc.push_str(&escape_html(&text));
+            } else {
+                let _ =
+                    write!(acc, "<span style=\"{css}\">{}</span>", escape_html(&text));
+            }
+            if maybe_hyperlink.is_some() {
+                acc.push_str("</a>");
+            }
+        }
+        acc.push('\n');
+    }
+
+    acc.push_str("</pre>\n</body>\n</html>\n");
+    acc
+}
+
+/// The inline CSS that applies the `style`.
+fn get_css(style: &TuiStyle) -> String {
+    let to_css_color = |rgb: RgbValue| -> String {
+        format!("#{:02x}{:02x}{:02x}", rgb.red, rgb.green, rgb.blue)
+    };
+    let maybe_fg = style.color_fg.and_then(try_get_rgb).map(to_css_color);
+    let maybe_bg = style.color_bg.and_then(try_get_rgb).map(to_css_color);
+    let (maybe_fg, maybe_bg) = if style.reverse {
+        (
+            Some(maybe_bg.unwrap_or_else(|| HTML_DEFAULT_BG_COLOR.into())),
+            Some(maybe_fg.unwrap_or_else(|| HTML_DEFAULT_FG_COLOR.into())),
+        )
+    } else {
+        (maybe_fg, maybe_bg)
+    };
+
+    let mut it: Vec<String> = vec![];
+    if let Some(fg) = maybe_fg {
+        it.push(format!("color: {fg}"));
+    }
+    if let Some(bg) = maybe_bg {
+        it.push(format!("background-color: {bg}"));
+    }
+    if style.bold {
+        it.push("font-weight: bold".into());
+    }
+    if style.dim {
+        it.push("opacity: 0.6".into());
+    }
+    if style.italic {
+        it.push("font-style: italic".into());
+    }
+
+    let mut text_decorations = vec![];
+    if style.underline {
+        text_decorations.push("underline");
+    }
+    if style.strikethrough {
+        text_decorations.push("line-through");
+    }
+    if !text_decorations.is_empty() {
+        it.push(format!("text-decoration: {}", text_decorations.join(" ")));
+    }
+    if style.underline {
+        let text_decoration_style = match style.underline_style {
+            TuiUnderlineStyle::Single => "solid",
+            TuiUnderlineStyle::Double => "double",
+            TuiUnderlineStyle::Curly => "wavy",
+            TuiUnderlineStyle::Dotted => "dotted",
+            TuiUnderlineStyle::Dashed => "dashed",
+        };
+        it.push(format!("text-decoration-style: {text_decoration_style}"));
+        if let Some(rgb) = style.color_underline.and_then(try_get_rgb) {
+            it.push(format!("text-decoration-color: {}", to_css_color(rgb)));
+        }
+    }
+    if style.hidden {
+        it.push("visibility: hidden".into());
+    }
+
+    it.join("; ")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut acc = String::with_capacity(text.len());
+    for it in text.chars() {
+        match it {
+            '&' => acc.push_str("&amp;"),
+            '<' => acc.push_str("&lt;"),
+            '>' => acc.push_str("&gt;"),
+            '"' => acc.push_str("&quot;"),
+            _ => acc.push(it),
+        }
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, color, size, GraphemeClusterSegment};
+    use r3bl_macro::tui_style;
+
+    use super::*;
+
+    fn make_offscreen_buffer() -> OffscreenBuffer {
+        let mut offscreen_buffer = OffscreenBuffer::new_with_capacity_initialized(
+            size! { col_count: 4, row_count: 2 },
+        );
+        offscreen_buffer.buffer[0][0] = PixelChar::PlainText {
+            content: GraphemeClusterSegment::from("a"),
+            maybe_style: Some(tui_style! { attrib: [bold] color_fg: color!(255, 0, 0) }),
+            maybe_hyperlink: None,
+        };
+        offscreen_buffer.buffer[0][1] = PixelChar::PlainText {
+            content: GraphemeClusterSegment::from("<"),
+            maybe_style: Some(tui_style! { attrib: [bold] color_fg: color!(255, 0, 0) }),
+            maybe_hyperlink: None,
+        };
+        offscreen_buffer.buffer[1][0] = PixelChar::PlainText {
+            content: GraphemeClusterSegment::from("😃"),
+            maybe_style: None,
+            maybe_hyperlink: Some("https://r3bl.com".into()),
+        };
+        offscreen_buffer.buffer[1][1] = PixelChar::Void;
+        offscreen_buffer
+    }
+
+    #[test]
+    fn test_render_ansi_snapshot() {
+        let snapshot = make_offscreen_buffer().render_snapshot(SnapshotFormat::Ansi);
+        assert_eq2!(
+            snapshot,
+            "\x1b[0;1;38;2;255;0;0ma<\x1b[0m  \n\
+             \x1b]8;;https://r3bl.com\x1b\\😃\x1b]8;;\x1b\\  \n"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_render_html_snapshot() {
+        let snapshot = make_offscreen_buffer().render_snapshot(SnapshotFormat::Html);
+        assert!(snapshot.starts_with("<!DOCTYPE html>"));
+        assert!(snapshot.contains(
+            "<span style=\"color: #ff0000; font-weight: bold\">a&lt;</span>  \n"
+        ));
+        assert!(snapshot.contains("<a href=\"https://r3bl.com\">😃</a>  \n"));
+    }
+}
@@ -19,17 +19,21 @@ use std::fmt::Debug;
 
 use r3bl_core::{call_if_true, LockedOutputDevice, Position, Size};
 
-use super::{FlushKind, RenderOp, RenderOpsLocalData, RenderPipeline};
-use crate::{GlobalData,
-            OffscreenBuffer,
+use super::{CursorShape,
+            FlushKind,
+            RenderOp,
+            RenderOps,
+            RenderOpsLocalData,
+            RenderPipeline};
+use crate::{render_ops,
+            GlobalData,
             OffscreenBufferDiffResult,
             OffscreenBufferPaint,
             OffscreenBufferPaintImplCrossterm,
-            PixelCharDiffChunks,
-            TerminalLibBackend,
+            PaintStats,
+            DEBUG_TUI_COMPOSITOR,
             DEBUG_TUI_MOD,
-            DEBUG_TUI_SHOW_PIPELINE_EXPANDED,
-            TERMINAL_LIB_BACKEND};
+            DEBUG_TUI_SHOW_PIPELINE_EXPANDED};
 
 pub trait PaintRenderOp {
     fn paint(
@@ -46,8 +50,20 @@ pub trait PaintRenderOp {
 /// Paint the render pipeline. The render pipeline contains a list of [crate::RenderOps]
 /// for each [crate::ZOrder]. This function is responsible for:
 /// 1. Actually executing those [crate::RenderOps] in the correct order.
-/// 2. And routing the execution to the correct backend specified in
-///    [TERMINAL_LIB_BACKEND].
+/// 2. And routing the execution to the [crate::TerminalBackend] that is selected w/
+///    [crate::set_terminal_lib_backend].
+///
+/// Only the cells that changed since the last render (which is saved in
+/// [GlobalData::maybe_saved_offscreen_buffer]) are painted, unless the window was resized.
+/// How many of them were painted is saved in [GlobalData::last_paint_stats].
+///
+/// In terminals that support it, each frame is painted in a synchronized update, so that
+/// it is shown all at once, see [crate::is_synchronized_update_supported].
+///
+/// The frame is prepared here (see [prepare_paint_frame]), but if there is a
+/// [GlobalData::maybe_paint_task], it is painted by that (in the background), so that a
+/// slow terminal doesn't hold up the main event loop. Otherwise it is painted right away
+/// to the `locked_output_device` (see [paint_frame]).
 ///
 /// See [crate::RenderOps] for more details of "atomic paint operations".
 pub fn paint<S, AS>(
@@ -60,93 +76,164 @@ pub fn paint<S, AS>(
     S: Debug + Default + Clone + Sync + Send,
     AS: Debug + Default + Clone + Sync + Send,
 {
-    let maybe_saved_offscreen_buffer = global_data.maybe_saved_offscreen_buffer.clone();
+    let frame = prepare_paint_frame(pipeline, flush_kind, global_data);
+    match global_data.maybe_paint_task {
+        Some(ref paint_task) if paint_task.is_running() => {
+            paint_task.paint(frame);
+        }
+        _ => paint_frame(frame, locked_output_device, is_mock),
+    }
+}
+
+/// The [RenderOps] of a frame that is ready to be painted, see [prepare_paint_frame].
+#[derive(Clone, Debug)]
+pub struct PaintFrame {
+    pub render_ops: RenderOps,
+    /// All the cells are painted (after the screen is cleared, depending on the
+    /// [FlushKind]), or [None] if only the ones that changed are painted.
+    pub maybe_full_paint: Option<FlushKind>,
+    pub window_size: Size,
+}
+
+/// Lay out the `pipeline` in an [crate::OffscreenBuffer] & compare it w/ the one that was
+/// painted last, to get the [RenderOps] that paint the difference. The new buffer is
+/// saved in [GlobalData::maybe_saved_offscreen_buffer], and the stats of the frame are
+/// saved in [GlobalData::last_paint_stats]. Nothing is written to the terminal, that is
+/// done by [paint_frame].
+pub fn prepare_paint_frame<S, AS>(
+    pipeline: &RenderPipeline,
+    flush_kind: FlushKind,
+    global_data: &mut GlobalData<S, AS>,
+) -> PaintFrame
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    let maybe_saved_offscreen_buffer = global_data.maybe_saved_offscreen_buffer.take();
 
     let window_size = global_data.window_size;
 
-    let offscreen_buffer = pipeline.convert(window_size);
+    let mut offscreen_buffer = pipeline.convert(window_size);
 
-    match maybe_saved_offscreen_buffer {
-        None => {
-            perform_full_paint(
-                &offscreen_buffer,
-                flush_kind,
-                window_size,
-                locked_output_device,
-                is_mock,
-            );
+    // The title that is set in the pipeline replaces the one in global data, and it is
+    // only painted when it changes. It is painted in the frame, which flushes it.
+    match offscreen_buffer.maybe_title {
+        Some(ref title) => global_data.maybe_title = Some(title.clone()),
+        None => offscreen_buffer
+            .maybe_title
+            .clone_from(&global_data.maybe_title),
+    }
+    let maybe_painted_title = maybe_saved_offscreen_buffer
+        .as_ref()
+        .and_then(|it| it.maybe_title.as_ref());
+    let mut title_render_ops = render_ops!();
+    if let Some(ref title) = offscreen_buffer.maybe_title {
+        if maybe_painted_title != Some(title) {
+            title_render_ops.push(RenderOp::SetTitle(title.clone()));
         }
+    }
+
+    let (before_frame_ops, after_frame_ops) = get_cursor_render_ops(
+        maybe_saved_offscreen_buffer
+            .as_ref()
+            .and_then(|it| it.maybe_cursor),
+        offscreen_buffer.maybe_cursor,
+    );
+    let mut render_ops = title_render_ops;
+    // The text that was copied w/ OSC 52 (while handling an input event) is painted w/
+    // this frame, so that it isn't written to the terminal while a frame is painted.
+    for content in global_data.take_pending_clipboard_contents() {
+        render_ops.push(RenderOp::SetClipboard(content));
+    }
+    render_ops.extend(before_frame_ops.list);
+
+    // The offscreen buffer is painted w/ RenderOps, which are routed to the selected
+    // terminal backend, so this works for all of them.
+    let mut paint_impl = OffscreenBufferPaintImplCrossterm {};
+    let maybe_diff_chunks = match maybe_saved_offscreen_buffer {
+        None => None,
+        // Compare offscreen buffers & paint only the diff.
         Some(saved_offscreen_buffer) => {
-            // Compare offscreen buffers & paint only the diff.
             match saved_offscreen_buffer.diff(&offscreen_buffer) {
-                OffscreenBufferDiffResult::NotComparable => {
-                    perform_full_paint(
-                        &offscreen_buffer,
-                        flush_kind,
-                        window_size,
-                        locked_output_device,
-                        is_mock,
-                    );
-                }
-                OffscreenBufferDiffResult::Comparable(ref diff_chunks) => {
-                    perform_diff_paint(
-                        diff_chunks,
-                        window_size,
-                        locked_output_device,
-                        is_mock,
-                    );
-                }
+                OffscreenBufferDiffResult::NotComparable => None,
+                OffscreenBufferDiffResult::Comparable(diff_chunks) => Some(diff_chunks),
             }
         }
-    }
+    };
+    let (maybe_full_paint, paint_stats) = match maybe_diff_chunks {
+        None => {
+            render_ops.extend(paint_impl.render(&offscreen_buffer).list);
+            (Some(flush_kind), PaintStats::new_full_paint(window_size))
+        }
+        Some(ref diff_chunks) => {
+            render_ops.extend(paint_impl.render_diff(diff_chunks).list);
+            (None, PaintStats::new_diff_paint(window_size, diff_chunks))
+        }
+    };
+    render_ops.extend(after_frame_ops.list);
+
+    call_if_true!(DEBUG_TUI_COMPOSITOR, {
+        tracing::info!("🎨 paint() ok ✅: {paint_stats}");
+    });
 
     global_data.maybe_saved_offscreen_buffer = Some(offscreen_buffer);
+    global_data.last_paint_stats = paint_stats;
 
-    fn perform_diff_paint(
-        diff_chunks: &PixelCharDiffChunks,
-        window_size: Size,
-        locked_output_device: LockedOutputDevice<'_>,
-        is_mock: bool,
-    ) {
-        match TERMINAL_LIB_BACKEND {
-            TerminalLibBackend::Crossterm => {
-                let mut crossterm_impl = OffscreenBufferPaintImplCrossterm {};
-                let render_ops = crossterm_impl.render_diff(diff_chunks);
-                crossterm_impl.paint_diff(
-                    render_ops,
-                    window_size,
-                    locked_output_device,
-                    is_mock,
-                );
-            }
-            TerminalLibBackend::Termion => todo!(), // FUTURE: implement OffscreenBufferPaint trait for termion
-        }
+    PaintFrame {
+        render_ops,
+        maybe_full_paint,
+        window_size,
     }
+}
 
-    fn perform_full_paint(
-        offscreen_buffer: &OffscreenBuffer,
-        flush_kind: FlushKind,
-        window_size: Size,
-        locked_output_device: LockedOutputDevice<'_>,
-        is_mock: bool,
-    ) {
-        match TERMINAL_LIB_BACKEND {
-            TerminalLibBackend::Crossterm => {
-                let mut crossterm_impl = OffscreenBufferPaintImplCrossterm {};
-                let render_ops = crossterm_impl.render(offscreen_buffer);
-                crossterm_impl.paint(
-                    render_ops,
-                    flush_kind,
-                    window_size,
-                    locked_output_device,
-                    is_mock,
-                );
-            }
-            TerminalLibBackend::Termion => todo!(), // FUTURE: implement OffscreenBufferPaint trait for termion
+/// Write the frame that was prepared by [prepare_paint_frame] to the terminal.
+pub fn paint_frame(
+    frame: PaintFrame,
+    locked_output_device: LockedOutputDevice<'_>,
+    is_mock: bool,
+) {
+    let PaintFrame {
+        render_ops,
+        maybe_full_paint,
+        window_size,
+    } = frame;
+    let mut paint_impl = OffscreenBufferPaintImplCrossterm {};
+    match maybe_full_paint {
+        Some(flush_kind) => paint_impl.paint(
+            render_ops,
+            flush_kind,
+            window_size,
+            locked_output_device,
+            is_mock,
+        ),
+        None => {
+            paint_impl.paint_diff(render_ops, window_size, locked_output_device, is_mock)
         }
     }
 }
 
+/// Returns the [RenderOps] that are painted before & after a frame, to show the terminal's
+/// cursor (see [RenderOp::ShowCursor]) at the position that it was at in the pipeline.
+/// Painting the frame moves the cursor, so it is hidden while the frame is painted, and
+/// then it is moved back & shown again.
+pub fn get_cursor_render_ops(
+    maybe_painted_cursor: Option<(Position, CursorShape)>,
+    maybe_cursor: Option<(Position, CursorShape)>,
+) -> (RenderOps, RenderOps) {
+    let mut before_frame_ops = render_ops!();
+    if maybe_painted_cursor.is_some() {
+        before_frame_ops.push(RenderOp::HideCursor);
+    }
+
+    let mut after_frame_ops = render_ops!();
+    if let Some((position, shape)) = maybe_cursor {
+        after_frame_ops.push(RenderOp::MoveCursorPositionAbs(position));
+        after_frame_ops.push(RenderOp::ShowCursor(shape));
+    }
+
+    (before_frame_ops, after_frame_ops)
+}
+
 /// 1. Ensure that the [Position] is within the bounds of the terminal window using
 ///    [RenderOpsLocalData].
 /// 2. If the [Position] is outside of the bounds of the window then it is clamped to the nearest
@@ -197,3 +284,48 @@ pub fn sanitize_and_save_abs_position(
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+    use crate::test_fixtures::mock_real_objects_for_editor;
+
+    #[test]
+    fn test_copied_text_is_painted_w_the_next_frame() {
+        let (mut global_data, _stdout_mock) =
+            mock_real_objects_for_editor::make_global_data::<(), ()>(Some(
+                size!(col_count: 10, row_count: 2),
+            ));
+        global_data
+            .pending_clipboard_contents
+            .lock()
+            .unwrap()
+            .push("hello".to_string());
+        assert!(global_data.has_pending_clipboard_contents());
+
+        let get_set_clipboard_ops = |frame: &PaintFrame| -> Vec<RenderOp> {
+            frame
+                .render_ops
+                .iter()
+                .filter(|it| matches!(it, RenderOp::SetClipboard(..)))
+                .cloned()
+                .collect()
+        };
+
+        let pipeline = RenderPipeline::default();
+        let frame =
+            prepare_paint_frame(&pipeline, FlushKind::ClearBeforeFlush, &mut global_data);
+        assert_eq2!(
+            get_set_clipboard_ops(&frame),
+            vec![RenderOp::SetClipboard("hello".to_string())]
+        );
+        assert!(!global_data.has_pending_clipboard_contents());
+
+        // It is only painted once.
+        let frame =
+            prepare_paint_frame(&pipeline, FlushKind::ClearBeforeFlush, &mut global_data);
+        assert_eq2!(get_set_clipboard_ops(&frame), vec![]);
+    }
+}
@@ -0,0 +1,218 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{sync::{mpsc, Arc, Condvar, Mutex as StdMutex},
+          thread::{self, JoinHandle}};
+
+use r3bl_core::{output_device_as_mut, OutputDevice};
+use tokio::sync::Notify;
+
+use super::{paint_frame, PaintFrame};
+
+/// Paints the frames that are prepared by the main event loop (see
+/// [super::prepare_paint_frame]) on a dedicated thread, so that writing them to a slow
+/// terminal (eg: over a high latency SSH connection) doesn't hold up the processing of
+/// input events & state updates.
+///
+/// It is double buffered: the main event loop can prepare the next frame while the
+/// last one is being painted. If that isn't done yet when the next render is due, the
+/// render is deferred (see [Self::is_busy]), so the frames don't pile up, and the one
+/// that is painted next has the latest state. The main event loop is woken up once the
+/// frame has been painted (see [Self::wait_until_idle_async]), to do the deferred render.
+///
+/// This is saved in [crate::GlobalData::maybe_paint_task], which is set up by the main
+/// event loop.
+#[derive(Debug)]
+pub struct PaintTask {
+    maybe_sender: Option<mpsc::Sender<PaintFrame>>,
+    maybe_join_handle: Option<JoinHandle<()>>,
+    /// How many frames were sent that haven't been painted yet, and how many were.
+    frame_counts: Arc<(StdMutex<FrameCounts>, Condvar)>,
+    /// Notified each time the thread is done painting a frame.
+    idle_notify: Arc<Notify>,
+}
+
+impl PaintTask {
+    /// Start the thread that paints the frames to the `output_device`. If it can't be
+    /// started, [Self::is_running] is false, and the frames should be painted right away
+    /// instead.
+    pub fn start(output_device: OutputDevice) -> Self {
+        let (sender, receiver) = mpsc::channel::<PaintFrame>();
+        let frame_counts =
+            Arc::new((StdMutex::new(FrameCounts::default()), Condvar::new()));
+        let idle_notify = Arc::new(Notify::new());
+
+        let frame_counts_clone = frame_counts.clone();
+        let idle_notify_clone = idle_notify.clone();
+        let result =
+            thread::Builder::new()
+                .name("r3bl_tui_paint".into())
+                .spawn(move || {
+                    for it in receiver {
+                        paint_frame(
+                            it,
+                            output_device_as_mut!(output_device),
+                            output_device.is_mock,
+                        );
+                        update_frame_counts(&frame_counts_clone, |it| {
+                            it.pending = it.pending.saturating_sub(1);
+                            it.painted += 1;
+                        });
+                        idle_notify_clone.notify_one();
+                    }
+                });
+
+        match result {
+            Ok(join_handle) => Self {
+                maybe_sender: Some(sender),
+                maybe_join_handle: Some(join_handle),
+                frame_counts,
+                idle_notify,
+            },
+            Err(error) => {
+                tracing::error!("PaintTask -> Failed to start paint thread: {error}");
+                Self {
+                    maybe_sender: None,
+                    maybe_join_handle: None,
+                    frame_counts,
+                    idle_notify,
+                }
+            }
+        }
+    }
+
+    pub fn is_running(&self) -> bool { self.maybe_sender.is_some() }
+
+    /// Whether any of the frames that were sent haven't been painted yet.
+    pub fn is_busy(&self) -> bool { self.get_frame_counts().pending > 0 }
+
+    /// How many frames have been painted so far.
+    pub fn get_painted_frame_count(&self) -> usize { self.get_frame_counts().painted }
+
+    fn get_frame_counts(&self) -> FrameCounts {
+        let (mutex, _) = &*self.frame_counts;
+        mutex.lock().map(|it| *it).unwrap_or_default()
+    }
+
+    /// Send the frame to be painted (in order w/ the frames that were sent before it).
+    pub fn paint(&self, frame: PaintFrame) {
+        let Some(ref sender) = self.maybe_sender else {
+            return;
+        };
+        update_frame_counts(&self.frame_counts, |it| it.pending += 1);
+        if sender.send(frame).is_err() {
+            // The thread is gone (eg: it panicked), so the frame won't be painted.
+            update_frame_counts(&self.frame_counts, |it| {
+                it.pending = it.pending.saturating_sub(1)
+            });
+        }
+    }
+
+    /// Wait until the thread is done painting a frame, w/out blocking the main event
+    /// loop. The last frame that was sent might still be painting when this returns, so
+    /// check [Self::is_busy] again. It is cancel safe, so it can be used in
+    /// [tokio::select!].
+    pub async fn wait_until_idle_async(&self) { self.idle_notify.notified().await; }
+
+    /// Block until the frames that were sent have been painted, eg: before raw mode is
+    /// exited, so that they aren't painted after it. Don't call this while the output
+    /// device is locked, since the frames can't be painted until it is unlocked.
+    pub fn wait_until_idle(&self) {
+        let (mutex, condvar) = &*self.frame_counts;
+        let Ok(mut frame_counts) = mutex.lock() else {
+            return;
+        };
+        while frame_counts.pending > 0 {
+            match condvar.wait(frame_counts) {
+                Ok(it) => frame_counts = it,
+                Err(_) => return,
+            }
+        }
+    }
+
+    /// Paint the frames that were sent, and stop the thread.
+    pub fn stop(&mut self) {
+        // The thread exits once the channel is closed & empty.
+        self.maybe_sender.take();
+        if let Some(join_handle) = self.maybe_join_handle.take() {
+            let _ = join_handle.join();
+        }
+        update_frame_counts(&self.frame_counts, |it| it.pending = 0);
+    }
+}
+
+impl Drop for PaintTask {
+    fn drop(&mut self) { self.stop(); }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct FrameCounts {
+    pending: usize,
+    painted: usize,
+}
+
+fn update_frame_counts(
+    frame_counts: &(StdMutex<FrameCounts>, Condvar),
+    update: impl FnOnce(&mut FrameCounts),
+) {
+    let (mutex, condvar) = frame_counts;
+    if let Ok(mut it) = mutex.lock() {
+        update(&mut it);
+    }
+    condvar.notify_all();
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::size;
+    use r3bl_test_fixtures::OutputDeviceExt as _;
+
+    use super::*;
+    use crate::{render_ops, RenderOp};
+
+    #[test]
+    fn test_frames_are_painted_in_background() {
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+        let mut paint_task = PaintTask::start(output_device);
+        assert!(paint_task.is_running());
+
+        for text in ["hello", "world"] {
+            paint_task.paint(PaintFrame {
+                render_ops: render_ops!(
+                    @new RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                        text.into(),
+                        None
+                    )
+                ),
+                maybe_full_paint: None,
+                window_size: size!(col_count: 10, row_count: 2),
+            });
+        }
+        // Both frames are painted, not just the first one.
+        paint_task.wait_until_idle();
+        assert!(!paint_task.is_busy());
+        assert_eq!(paint_task.get_painted_frame_count(), 2);
+
+        let output = stdout_mock.get_copy_of_buffer_as_string();
+        let hello_index = output.find("hello").unwrap();
+        let world_index = output.find("world").unwrap();
+        assert!(hello_index < world_index);
+
+        paint_task.stop();
+        assert!(!paint_task.is_running());
+    }
+}
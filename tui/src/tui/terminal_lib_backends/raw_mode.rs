@@ -15,6 +15,8 @@
  *   limitations under the License.
  */
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use r3bl_core::{LockedOutputDevice, Size};
 
 use super::{RenderOp, RenderOps, RenderOpsLocalData};
@@ -24,12 +26,20 @@ use super::{RenderOp, RenderOps, RenderOpsLocalData};
 #[derive(Debug, Clone)]
 pub struct RawMode;
 
+/// Whether the (real) terminal is in raw mode, ie: [RawMode::start] was called w/out a
+/// matching [RawMode::end]. This is used to restore the terminal when the app panics,
+/// see [crate::install_panic_hook].
+static IS_RAW_MODE_ACTIVE: AtomicBool = AtomicBool::new(false);
+
 impl RawMode {
     pub fn start(
         window_size: Size,
         locked_output_device: LockedOutputDevice<'_>,
         is_mock: bool,
     ) {
+        if !is_mock {
+            IS_RAW_MODE_ACTIVE.store(true, Ordering::SeqCst);
+        }
         let mut skip_flush = false;
         RenderOps::route_paint_render_op_to_backend(
             &mut RenderOpsLocalData::default(),
@@ -46,6 +56,9 @@ impl RawMode {
         locked_output_device: LockedOutputDevice<'_>,
         is_mock: bool,
     ) {
+        if !is_mock {
+            IS_RAW_MODE_ACTIVE.store(false, Ordering::SeqCst);
+        }
         let mut skip_flush = false;
         RenderOps::route_paint_render_op_to_backend(
             &mut RenderOpsLocalData::default(),
@@ -56,4 +69,14 @@ impl RawMode {
             is_mock,
         );
     }
+
+    /// Whether the terminal is in raw mode, ie: [Self::start] was called (w/ a real
+    /// terminal) w/out a matching [Self::end].
+    pub fn is_active() -> bool { IS_RAW_MODE_ACTIVE.load(Ordering::SeqCst) }
+
+    /// Pretend that the terminal is (or isn't) in raw mode, w/out changing it.
+    #[cfg(test)]
+    pub(crate) fn set_is_active(is_active: bool) {
+        IS_RAW_MODE_ACTIVE.store(is_active, Ordering::SeqCst);
+    }
 }
@@ -21,11 +21,8 @@ use std::{fmt::{Debug, Formatter, Result},
 use r3bl_core::{LockedOutputDevice, Position, Size, TuiColor, TuiStyle};
 use serde::{Deserialize, Serialize};
 
-use super::TERMINAL_LIB_BACKEND;
-use crate::{CrosstermDebugFormatRenderOp,
-            PaintRenderOp,
-            RenderOpImplCrossterm,
-            TerminalLibBackend};
+use super::route_to_terminal_backend;
+use crate::PaintRenderOp;
 
 /// Here's an example. Refer to [RenderOps] for more details.
 ///
@@ -157,9 +154,29 @@ pub struct RenderOps {
     pub list: Vec<RenderOp>,
 }
 
+/// The state that is carried from one [RenderOp] to the next in
+/// [RenderOps::execute_all].
+///
+/// The crossterm backend uses the fields after `cursor_position` to keep track of what
+/// is known to be set in the terminal, so that it can skip the [RenderOp]s that wouldn't
+/// change anything (eg: moving the cursor to where it already is, or setting a color
+/// that is already set). This cuts the bytes written per frame, which matters most over
+/// SSH. Nothing is known about the terminal when [RenderOps::execute_all] starts.
 #[derive(Default, Debug)]
 pub struct RenderOpsLocalData {
     pub cursor_position: Position,
+    /// Whether the terminal's cursor is known to be at `cursor_position`.
+    pub is_cursor_position_known: bool,
+    /// Whether the colors & attributes are known to be reset (to the defaults).
+    pub is_color_reset: bool,
+    /// The fg color that is known to be set.
+    pub maybe_fg_color: Option<TuiColor>,
+    /// The bg color that is known to be set.
+    pub maybe_bg_color: Option<TuiColor>,
+    /// The style whose attributes (bold, italic, etc) are set. They aren't reset after
+    /// each text is painted, so that adjacent texts w/ the same attributes are painted
+    /// as if they were one.
+    pub maybe_attributes_style: Option<TuiStyle>,
 }
 
 pub mod render_ops_impl {
@@ -184,6 +201,18 @@ pub mod render_ops_impl {
                     is_mock,
                 );
             }
+
+            // Don't leave the attributes of the last text set in the terminal.
+            if local_data.maybe_attributes_style.is_some() {
+                RenderOps::route_paint_render_op_to_backend(
+                    &mut local_data,
+                    skip_flush,
+                    &RenderOp::ResetColor,
+                    window_size,
+                    locked_output_device,
+                    is_mock,
+                );
+            }
         }
 
         pub fn route_paint_render_op_to_backend(
@@ -194,19 +223,16 @@ pub mod render_ops_impl {
             locked_output_device: LockedOutputDevice<'_>,
             is_mock: bool,
         ) {
-            match TERMINAL_LIB_BACKEND {
-                TerminalLibBackend::Crossterm => {
-                    RenderOpImplCrossterm {}.paint(
-                        skip_flush,
-                        render_op,
-                        window_size,
-                        local_data,
-                        locked_output_device,
-                        is_mock,
-                    );
-                }
-                TerminalLibBackend::Termion => todo!(), // FUTURE: implement PaintRenderOp trait for termion
-            }
+            route_to_terminal_backend(|backend| {
+                backend.paint(
+                    skip_flush,
+                    render_op,
+                    window_size,
+                    local_data,
+                    locked_output_device,
+                    is_mock,
+                );
+            });
         }
     }
 
@@ -298,6 +324,51 @@ pub enum RenderOp {
     /// padding.
     CompositorNoClipTruncPaintTextWithAttributes(String, Option<TuiStyle>),
 
+    /// The text that is painted after this is a link to the given URL (until this is
+    /// used again w/ [None]). This is painted using OSC 8 escape sequences, which make
+    /// the text clickable in terminals that support them (see
+    /// [super::is_hyperlink_supported]), and is ignored in the ones that don't.
+    SetHyperlink(/* url */ Option<String>),
+
+    /// The terminal doesn't show anything that is painted after this, until
+    /// [RenderOp::EndSynchronizedUpdate] is used, so that a frame is shown all at once.
+    /// This is only painted in terminals that support it (see
+    /// [super::is_synchronized_update_supported]).
+    BeginSynchronizedUpdate,
+
+    EndSynchronizedUpdate,
+
+    /// Paint the image in `data` (the bytes of a PNG, JPEG or GIF file) so that it fits
+    /// in the `bounds` (w/out changing its aspect ratio). This is painted using the
+    /// protocol that the terminal supports (see [super::get_image_protocol]), and is
+    /// ignored in terminals that don't support any of them. The cells in the `bounds` are
+    /// cleared, so the image should be painted after any text that it covers.
+    PaintImage {
+        data: Vec<u8>,
+        bounds: super::ImageBounds,
+    },
+
+    /// Set the title of the terminal window (or tab), using the OSC 0 escape sequence.
+    /// The title that the terminal had before raw mode was entered is restored when it is
+    /// exited (in terminals that support it). This is only painted when the title
+    /// changes, see [crate::GlobalData::set_title].
+    SetTitle(String),
+
+    /// Put the text into the clipboard of the terminal, using the OSC 52 escape sequence
+    /// (which is wrapped in a passthrough when the app is running in tmux). This is
+    /// painted w/ the frame after the text is copied w/ [crate::Osc52Clipboard].
+    SetClipboard(String),
+
+    /// Show the terminal's own cursor at the current position (eg: after
+    /// [RenderOp::MoveCursorPositionRelTo]), w/ the given [super::CursorShape]. The
+    /// cursor is hidden while the app is running, unless this is used in the frame that
+    /// is painted. The shape that the terminal had is restored when raw mode is exited.
+    ShowCursor(super::CursorShape),
+
+    /// Hide the terminal's own cursor. This doesn't have to be used by apps, since the
+    /// cursor is hidden in each frame that doesn't use [RenderOp::ShowCursor].
+    HideCursor,
+
     /// For [Default] impl.
     Noop,
 }
@@ -314,12 +385,7 @@ mod render_op_impl {
         /// using this method. Also [crate::queue_render_op!] does not use this; it has its
         /// own way of logging output.
         fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-            match TERMINAL_LIB_BACKEND {
-                TerminalLibBackend::Crossterm => {
-                    CrosstermDebugFormatRenderOp {}.debug_format(self, f)
-                }
-                TerminalLibBackend::Termion => todo!(), // FUTURE: implement debug formatter for termion
-            }
+            route_to_terminal_backend(|backend| backend.debug_format(self, f))
         }
     }
 }
@@ -329,21 +395,13 @@ mod render_op_impl_trait_flush {
 
     impl Flush for RenderOp {
         fn flush(&mut self, locked_output_device: LockedOutputDevice<'_>) {
-            match TERMINAL_LIB_BACKEND {
-                TerminalLibBackend::Crossterm => {
-                    RenderOpImplCrossterm {}.flush(locked_output_device);
-                }
-                TerminalLibBackend::Termion => todo!(), // FUTURE: implement flush for termion
-            }
+            route_to_terminal_backend(|backend| backend.flush(locked_output_device));
         }
 
         fn clear_before_flush(&mut self, locked_output_device: LockedOutputDevice<'_>) {
-            match TERMINAL_LIB_BACKEND {
-                TerminalLibBackend::Crossterm => {
-                    RenderOpImplCrossterm {}.clear_before_flush(locked_output_device);
-                }
-                TerminalLibBackend::Termion => todo!(), // FUTURE: implement clear_before_flush for termion
-            }
+            route_to_terminal_backend(|backend| {
+                backend.clear_before_flush(locked_output_device)
+            });
         }
     }
 }
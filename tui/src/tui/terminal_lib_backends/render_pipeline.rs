@@ -22,7 +22,7 @@ use std::{collections::{hash_map::Entry, HashMap},
 use r3bl_core::LockedOutputDevice;
 use serde::{Deserialize, Serialize};
 
-use super::{paint::paint, render_op::RenderOp, ZOrder};
+use super::{paint::paint, render_op::RenderOp, LayerBlend, ZOrder};
 use crate::{tui::DEBUG_TUI_SHOW_PIPELINE_EXPANDED, FlushKind, GlobalData, RenderOps};
 
 /// This macro is a convenience macro for creating a [RenderPipeline]. It works w/ [RenderOp] items.
@@ -163,11 +163,29 @@ type PipelineMap = HashMap<ZOrder, Vec<RenderOps>>;
 pub struct RenderPipeline {
     /// [RenderOps] to paint for each [ZOrder].
     pub pipeline_map: PipelineMap,
+    /// How each [ZOrder] is blended w/ the ones beneath it, see [LayerBlend]. The ones
+    /// that aren't in here overwrite what is beneath them.
+    pub layer_blend_map: HashMap<ZOrder, LayerBlend>,
 }
 
 impl RenderPipeline {
-    /// This will add `rhs` to `self`.
+    /// Set how the [RenderOps] in the `z_order` are blended w/ the ones in the [ZOrder]s
+    /// beneath it, eg: to dim what is beneath a modal dialog.
+    pub fn set_layer_blend(&mut self, z_order: ZOrder, layer_blend: LayerBlend) {
+        self.layer_blend_map.insert(z_order, layer_blend);
+    }
+
+    pub fn get_layer_blend(&self, z_order: ZOrder) -> LayerBlend {
+        self.layer_blend_map
+            .get(&z_order)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// This will add `rhs` to `self`. The [LayerBlend]s that are set in `rhs` replace the
+    /// ones in `self`.
     pub fn join_into(&mut self, mut rhs: RenderPipeline) {
+        self.layer_blend_map.extend(rhs.layer_blend_map.drain());
         for (z_order, mut rhs_render_ops_vec) in rhs.drain() {
             // Insert rhs_render_ops_vec into self_render_ops_vec.
             match self.entry(z_order) {
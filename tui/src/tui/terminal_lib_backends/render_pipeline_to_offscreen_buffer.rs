@@ -29,7 +29,13 @@ use r3bl_core::{call_if_true,
                 UnicodeStringExt,
                 SPACER};
 
-use super::{sanitize_and_save_abs_position, OffscreenBuffer, RenderOp, RenderPipeline};
+use super::{blend_pixel_char,
+            sanitize_and_save_abs_position,
+            ImageBounds,
+            OffscreenBuffer,
+            OffscreenImage,
+            RenderOp,
+            RenderPipeline};
 use crate::{PixelChar, RenderOpsLocalData, ZOrder, DEBUG_TUI_COMPOSITOR};
 
 impl RenderPipeline {
@@ -45,6 +51,7 @@ impl RenderPipeline {
         let mut local_data = RenderOpsLocalData::default();
 
         for z_order in ZOrder::get_render_order().iter() {
+            my_offscreen_buffer.my_layer_blend = self.get_layer_blend(*z_order);
             if let Some(render_ops_vec) = self.get(z_order) {
                 for render_ops in render_ops_vec.iter() {
                     for render_op in render_ops.iter() {
@@ -58,6 +65,7 @@ impl RenderPipeline {
                 }
             }
         }
+        my_offscreen_buffer.my_layer_blend = Default::default();
 
         call_if_true!(DEBUG_TUI_COMPOSITOR, {
             tracing::debug!("offscreen_buffer: \n🌟🌟🌟\n{my_offscreen_buffer:#?}");
@@ -67,6 +75,45 @@ impl RenderPipeline {
     }
 }
 
+/// The `bounds` are clipped to the window, and the cells in them are cleared (so that
+/// the cells that were painted before are not painted on top of the image).
+fn print_image(
+    data: &[u8],
+    bounds: ImageBounds,
+    window_size: Size,
+    my_offscreen_buffer: &mut OffscreenBuffer,
+) {
+    let ImageBounds {
+        origin_pos,
+        mut bounds_size,
+    } = bounds;
+    bounds_size.col_count = bounds_size
+        .col_count
+        .min(window_size.col_count - origin_pos.col_index);
+    bounds_size.row_count = bounds_size
+        .row_count
+        .min(window_size.row_count - origin_pos.row_index);
+    if bounds_size.col_count == ch!(0) || bounds_size.row_count == ch!(0) {
+        return;
+    }
+
+    let start_row = ch!(@to_usize origin_pos.row_index);
+    let start_col = ch!(@to_usize origin_pos.col_index);
+    for row_index in start_row..start_row + ch!(@to_usize bounds_size.row_count) {
+        for col_index in start_col..start_col + ch!(@to_usize bounds_size.col_count) {
+            my_offscreen_buffer.buffer[row_index][col_index] = PixelChar::Spacer;
+        }
+    }
+
+    my_offscreen_buffer.images.push(OffscreenImage {
+        data: data.to_vec(),
+        bounds: ImageBounds {
+            origin_pos,
+            bounds_size,
+        },
+    });
+}
+
 fn process_render_op(
     render_op: &RenderOp,
     window_size: Size,
@@ -75,7 +122,12 @@ fn process_render_op(
 ) {
     match render_op {
         // Don't process these.
-        RenderOp::Noop | RenderOp::EnterRawMode | RenderOp::ExitRawMode => {}
+        RenderOp::Noop
+        | RenderOp::EnterRawMode
+        | RenderOp::ExitRawMode
+        | RenderOp::BeginSynchronizedUpdate
+        | RenderOp::EndSynchronizedUpdate
+        | RenderOp::SetClipboard(..) => {}
         // Do process these.
         RenderOp::ClearScreen => {
             my_offscreen_buffer.clear();
@@ -105,6 +157,21 @@ fn process_render_op(
                 my_offscreen_buffer.my_bg_color = style_ref.color_bg;
             }
         }
+        RenderOp::SetHyperlink(maybe_url_ref) => {
+            my_offscreen_buffer.my_hyperlink.clone_from(maybe_url_ref);
+        }
+        RenderOp::PaintImage { data, bounds } => {
+            print_image(data, *bounds, window_size, my_offscreen_buffer);
+        }
+        RenderOp::SetTitle(title) => {
+            my_offscreen_buffer.maybe_title = Some(title.clone());
+        }
+        RenderOp::ShowCursor(shape) => {
+            my_offscreen_buffer.maybe_cursor = Some((my_offscreen_buffer.my_pos, *shape));
+        }
+        RenderOp::HideCursor => {
+            my_offscreen_buffer.maybe_cursor = None;
+        }
         RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
             _arg_text_ref,
             _maybe_style_ref,
@@ -255,17 +322,26 @@ pub fn print_plain_text(
             let pixel_char = {
                 let new_gc_segment =
                     GraphemeClusterSegment::from(gc_segment.string.as_ref());
-                match (&maybe_style, new_gc_segment.string.as_str()) {
-                    (None, SPACER) => PixelChar::Spacer,
+                match (
+                    &maybe_style,
+                    &my_offscreen_buffer.my_hyperlink,
+                    new_gc_segment.string.as_str(),
+                ) {
+                    (None, None, SPACER) => PixelChar::Spacer,
                     _ => PixelChar::PlainText {
                         content: new_gc_segment,
                         maybe_style,
+                        maybe_hyperlink: my_offscreen_buffer.my_hyperlink.clone(),
                     },
                 }
             };
 
-            if line_copy.get(insertion_col_index).is_some() {
-                line_copy[insertion_col_index] = pixel_char;
+            if let Some(pixel_char_beneath) = line_copy.get(insertion_col_index) {
+                line_copy[insertion_col_index] = blend_pixel_char(
+                    pixel_char,
+                    pixel_char_beneath,
+                    my_offscreen_buffer.my_layer_blend,
+                );
             }
 
             // Deal w/ the display width of the `PixelChar` > 1. This is the equivalent of
@@ -362,7 +438,7 @@ mod tests {
     use r3bl_macro::tui_style;
 
     use super::*;
-    use crate::render_pipeline;
+    use crate::{render_pipeline, CursorShape, LayerBlend};
 
     #[test]
     fn test_print_plain_text_render_path_reuse_buffer() {
@@ -402,6 +478,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold, italic] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -411,6 +488,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold, italic] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -420,6 +498,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold, italic] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -429,6 +508,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold, italic] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
         }
@@ -465,6 +545,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -474,6 +555,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -483,6 +565,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][9], PixelChar::Spacer);
@@ -527,6 +610,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -536,6 +620,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -545,6 +630,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -554,6 +640,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
         }
@@ -592,6 +679,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -601,6 +689,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -610,6 +699,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][9], PixelChar::Spacer);
@@ -647,6 +737,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -656,6 +747,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -665,6 +757,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -674,6 +767,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][9], PixelChar::Void);
@@ -728,6 +822,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -737,6 +832,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -746,6 +842,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -755,6 +852,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][8], PixelChar::Void);
@@ -821,6 +919,7 @@ mod tests {
                 maybe_style: Some(
                     tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                 ),
+                maybe_hyperlink: None,
             }
         );
         assert_eq2!(
@@ -830,6 +929,7 @@ mod tests {
                 maybe_style: Some(
                     tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                 ),
+                maybe_hyperlink: None,
             }
         );
         assert_eq2!(my_offscreen_buffer.buffer[0][8], PixelChar::Void);
@@ -917,6 +1017,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -926,6 +1027,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[0][8], PixelChar::Void);
@@ -945,6 +1047,7 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(
@@ -954,9 +1057,191 @@ mod tests {
                     maybe_style: Some(
                         tui_style! { attrib: [dim, bold] color_fg: color!(@green) color_bg: color!(@blue) }
                     ),
+                    maybe_hyperlink: None,
                 }
             );
             assert_eq2!(my_offscreen_buffer.buffer[1][9], PixelChar::Spacer);
         }
     }
+
+    #[test]
+    fn test_convert_hyperlink() {
+        let window_size = size! { col_count: 10, row_count: 1 };
+        let url = "https://r3bl.com".to_string();
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+            RenderOp::PaintTextWithAttributes("a ".to_string(), None),
+            RenderOp::SetHyperlink(Some(url.clone())),
+            RenderOp::PaintTextWithAttributes("b c".to_string(), None),
+            RenderOp::SetHyperlink(None),
+            RenderOp::PaintTextWithAttributes("d".to_string(), None)
+        );
+
+        let my_offscreen_buffer = pipeline.convert(window_size);
+        assert_eq2!(my_offscreen_buffer.my_hyperlink, None);
+        assert_eq2!(my_offscreen_buffer.buffer[0][1], PixelChar::Spacer);
+        // The space in the link is part of it (so it isn't a spacer).
+        for (col_index, text) in [(2, "b"), (3, " "), (4, "c")] {
+            assert_eq2!(
+                my_offscreen_buffer.buffer[0][col_index],
+                PixelChar::PlainText {
+                    content: GraphemeClusterSegment::from(text),
+                    maybe_style: None,
+                    maybe_hyperlink: Some(url.clone()),
+                }
+            );
+        }
+        assert_eq2!(
+            my_offscreen_buffer.buffer[0][5],
+            PixelChar::PlainText {
+                content: GraphemeClusterSegment::from("d"),
+                maybe_style: None,
+                maybe_hyperlink: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_blended_layer() {
+        let window_size = size! { col_count: 4, row_count: 1 };
+        let mut pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+            RenderOp::SetFgColor(color!(255, 255, 255)),
+            RenderOp::SetBgColor(color!(0, 0, 255)),
+            RenderOp::PaintTextWithAttributes("abcd".to_string(), None)
+        );
+        // Dim the text beneath, & paint "x" on top of it.
+        render_pipeline!(@push_into pipeline at ZOrder::Glass =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+            RenderOp::SetBgColor(color!(0, 0, 0)),
+            RenderOp::PaintTextWithAttributes("  x".to_string(), None)
+        );
+        pipeline.set_layer_blend(ZOrder::Glass, LayerBlend::Alpha(128));
+
+        let my_offscreen_buffer = pipeline.convert(window_size);
+        let dimmed_style = Some(TuiStyle {
+            color_fg: Some(color!(127, 127, 127)),
+            color_bg: Some(color!(0, 0, 127)),
+            ..Default::default()
+        });
+        assert_eq2!(
+            my_offscreen_buffer.buffer[0][1],
+            PixelChar::PlainText {
+                content: GraphemeClusterSegment::from("b"),
+                maybe_style: dimmed_style,
+                maybe_hyperlink: None,
+            }
+        );
+        assert_eq2!(
+            my_offscreen_buffer.buffer[0][2],
+            PixelChar::PlainText {
+                content: GraphemeClusterSegment::from("x"),
+                maybe_style: Some(TuiStyle {
+                    color_fg: Some(color!(255, 255, 255)),
+                    color_bg: Some(color!(0, 0, 127)),
+                    ..Default::default()
+                }),
+                maybe_hyperlink: None,
+            }
+        );
+        // The text that isn't under the glass layer isn't dimmed.
+        assert_eq2!(
+            my_offscreen_buffer.buffer[0][3],
+            PixelChar::PlainText {
+                content: GraphemeClusterSegment::from("d"),
+                maybe_style: Some(TuiStyle {
+                    color_fg: Some(color!(255, 255, 255)),
+                    color_bg: Some(color!(0, 0, 255)),
+                    ..Default::default()
+                }),
+                maybe_hyperlink: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_title() {
+        let window_size = size! { col_count: 10, row_count: 1 };
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::SetTitle("edi - a.md".to_string()),
+            RenderOp::SetTitle("edi - b.md".to_string())
+        );
+
+        let my_offscreen_buffer = pipeline.convert(window_size);
+        assert_eq2!(
+            my_offscreen_buffer.maybe_title,
+            Some("edi - b.md".to_string())
+        );
+        // The title isn't painted in the cells.
+        assert_eq2!(my_offscreen_buffer.buffer[0][0], PixelChar::Spacer);
+    }
+
+    #[test]
+    fn test_convert_cursor() {
+        let window_size = size! { col_count: 10, row_count: 2 };
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionRelTo(
+                position! { col_index: 2, row_index: 1 },
+                position! { col_index: 3, row_index: 0 }
+            ),
+            RenderOp::ShowCursor(CursorShape::SteadyBar),
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 0 }),
+            RenderOp::PaintTextWithAttributes("abc".to_string(), None)
+        );
+
+        // The cursor stays where it was shown.
+        let my_offscreen_buffer = pipeline.convert(window_size);
+        assert_eq2!(
+            my_offscreen_buffer.maybe_cursor,
+            Some((
+                position! { col_index: 5, row_index: 1 },
+                CursorShape::SteadyBar
+            ))
+        );
+
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::ShowCursor(CursorShape::SteadyBar),
+            RenderOp::HideCursor
+        );
+        assert_eq2!(pipeline.convert(window_size).maybe_cursor, None);
+    }
+
+    #[test]
+    fn test_convert_image() {
+        let window_size = size! { col_count: 10, row_count: 3 };
+        let pipeline = render_pipeline!(@new ZOrder::Normal =>
+            RenderOp::MoveCursorPositionAbs(position! { col_index: 0, row_index: 1 }),
+            RenderOp::PaintTextWithAttributes("abcdef".to_string(), None),
+            RenderOp::PaintImage {
+                data: vec![1, 2, 3],
+                bounds: ImageBounds {
+                    origin_pos: position! { col_index: 4, row_index: 1 },
+                    bounds_size: size! { col_count: 20, row_count: 1 },
+                },
+            }
+        );
+
+        let my_offscreen_buffer = pipeline.convert(window_size);
+        // The image is clipped to the window.
+        assert_eq2!(
+            my_offscreen_buffer.images,
+            vec![OffscreenImage {
+                data: vec![1, 2, 3],
+                bounds: ImageBounds {
+                    origin_pos: position! { col_index: 4, row_index: 1 },
+                    bounds_size: size! { col_count: 6, row_count: 1 },
+                },
+            }]
+        );
+        // The text under the image is cleared.
+        assert_eq2!(
+            my_offscreen_buffer.buffer[1][3],
+            PixelChar::PlainText {
+                content: GraphemeClusterSegment::from("d"),
+                maybe_style: None,
+                maybe_hyperlink: None,
+            }
+        );
+        assert_eq2!(my_offscreen_buffer.buffer[1][4], PixelChar::Spacer);
+    }
 }
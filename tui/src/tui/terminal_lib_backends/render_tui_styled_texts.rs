@@ -17,7 +17,7 @@
 
 use r3bl_core::TuiStyledTexts;
 
-use crate::{RenderOp, RenderOps};
+use crate::{RenderOp, RenderOps, StyleUSSpan, StyleUSSpanLine};
 
 pub fn render_tui_styled_texts_into(texts: &TuiStyledTexts, render_ops: &mut RenderOps) {
     for styled_text in texts.inner.iter() {
@@ -32,6 +32,33 @@ pub fn render_tui_styled_texts_into(texts: &TuiStyledTexts, render_ops: &mut Ren
     }
 }
 
+/// Same as [render_tui_styled_texts_into], but the spans that have a
+/// [StyleUSSpan::maybe_hyperlink] are painted inside of a [RenderOp::SetHyperlink].
+pub fn render_style_us_span_line_into(
+    line: &StyleUSSpanLine,
+    render_ops: &mut RenderOps,
+) {
+    for StyleUSSpan {
+        style,
+        text,
+        maybe_hyperlink,
+    } in line.iter()
+    {
+        if maybe_hyperlink.is_some() {
+            render_ops.push(RenderOp::SetHyperlink(maybe_hyperlink.clone()));
+        }
+        render_ops.push(RenderOp::ApplyColors(Some(*style)));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            text.string.clone(),
+            Some(*style),
+        ));
+        render_ops.push(RenderOp::ResetColor);
+        if maybe_hyperlink.is_some() {
+            render_ops.push(RenderOp::SetHyperlink(None));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use r3bl_core::{assert_eq2,
@@ -45,11 +72,12 @@ mod tests {
                     CommonResult,
                     RgbValue,
                     TuiColor,
+                    TuiStyle,
                     TuiStylesheet};
     use r3bl_macro::tui_style;
 
     use super::*;
-    use crate::{render_ops, render_pipeline, ZOrder};
+    use crate::{list, render_ops, render_pipeline, ZOrder};
 
     #[test]
     fn test_styled_text_renders_correctly() -> CommonResult<()> {
@@ -77,6 +105,28 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_style_us_span_line_renders_hyperlinks() {
+        let url = "https://r3bl.com".to_string();
+        let line: StyleUSSpanLine = list! {
+            StyleUSSpan::new(TuiStyle::default(), "see ".into()),
+            StyleUSSpan::new_hyperlink(TuiStyle::default(), "R3BL".into(), url.clone())
+        };
+        let mut render_ops = render_ops!();
+        render_style_us_span_line_into(&line, &mut render_ops);
+
+        assert_eq2!(render_ops.len(), 3 + 5);
+        assert_eq2!(render_ops[3], RenderOp::SetHyperlink(Some(url)));
+        assert_eq2!(
+            render_ops[5],
+            RenderOp::PaintTextWithAttributes(
+                "R3BL".to_string(),
+                Some(TuiStyle::default())
+            )
+        );
+        assert_eq2!(render_ops[7], RenderOp::SetHyperlink(None));
+    }
+
     mod helpers {
         use super::*;
 
@@ -0,0 +1,119 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::sync::OnceLock;
+
+/// The escape sequences that begin & end a synchronized update (DEC private mode 2026),
+/// see <https://gist.github.com/christianparpart/d8a62cc1ab659194337d73e399004036>.
+const BSU: &str = "\x1b[?2026h";
+const ESU: &str = "\x1b[?2026l";
+
+static SYNCHRONIZED_UPDATE_SUPPORT: OnceLock<bool> = OnceLock::new();
+
+/// Override the detection of synchronized update support (eg: w/ a command line flag).
+/// This has to be called before anything is painted, and it can only be called once. If
+/// it was already set (or detected), the `value` is returned in the [Err].
+pub fn set_synchronized_update_support(value: bool) -> Result<(), bool> {
+    SYNCHRONIZED_UPDATE_SUPPORT.set(value)
+}
+
+/// Whether the terminal supports synchronized updates. When it does, each paint is
+/// wrapped in [RenderOp::BeginSynchronizedUpdate] & [RenderOp::EndSynchronizedUpdate],
+/// so that the terminal shows the whole frame at once (instead of showing it while it is
+/// being painted, which causes tearing & flicker in full screen repaints). This is
+/// detected (once) from the environment variables that the terminals that support them
+/// are known to set.
+///
+/// [RenderOp::BeginSynchronizedUpdate]: crate::RenderOp::BeginSynchronizedUpdate
+/// [RenderOp::EndSynchronizedUpdate]: crate::RenderOp::EndSynchronizedUpdate
+pub fn is_synchronized_update_supported() -> bool {
+    *SYNCHRONIZED_UPDATE_SUPPORT
+        .get_or_init(|| detect_synchronized_update_support(|key| std::env::var(key).ok()))
+}
+
+/// Terminals are supposed to ignore private modes that they don't know about, but there is
+/// no point in sending them, so this only returns true for the ones that are known to
+/// support mode 2026.
+pub fn detect_synchronized_update_support(
+    get_env: impl Fn(&str) -> Option<String>,
+) -> bool {
+    if let Some(term_program) = get_env("TERM_PROGRAM") {
+        if matches!(term_program.as_str(), "iTerm.app" | "WezTerm" | "contour") {
+            return true;
+        }
+    }
+
+    // Windows Terminal.
+    if get_env("WT_SESSION").is_some() {
+        return true;
+    }
+
+    if let Some(term) = get_env("TERM") {
+        if ["kitty", "alacritty", "foot", "contour"]
+            .iter()
+            .any(|it| term.contains(it))
+        {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Returns the escape sequence that begins (when `is_begin` is true) or ends a
+/// synchronized update.
+pub fn get_synchronized_update_escape_sequence(is_begin: bool) -> &'static str {
+    if is_begin {
+        BSU
+    } else {
+        ESU
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    fn detect(vars: &[(&str, &str)]) -> bool {
+        detect_synchronized_update_support(|key| {
+            vars.iter()
+                .find(|(it, _)| *it == key)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_detect_synchronized_update_support() {
+        assert!(detect(&[("TERM_PROGRAM", "WezTerm")]));
+        assert!(detect(&[("TERM", "xterm-kitty")]));
+        assert!(detect(&[("WT_SESSION", "1")]));
+        assert!(!detect(&[("TERM_PROGRAM", "Apple_Terminal")]));
+        assert!(!detect(&[("TERM", "xterm-256color")]));
+        assert!(!detect(&[]));
+    }
+
+    #[test]
+    fn test_get_synchronized_update_escape_sequence() {
+        assert_eq2!(get_synchronized_update_escape_sequence(true), "\x1b[?2026h");
+        assert_eq2!(
+            get_synchronized_update_escape_sequence(false),
+            "\x1b[?2026l"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::sync::OnceLock;
+
+use r3bl_core::{CommonResult, InputDevice, Size};
+
+use super::{DebugFormatRenderOp, Flush, PaintRenderOp, TerminalLibBackend};
+use crate::RenderOpImplCrossterm;
+
+/// Everything that the TUI needs from a terminal library:
+/// - Raw mode, which is entered & exited by painting [crate::RenderOp::EnterRawMode] &
+///   [crate::RenderOp::ExitRawMode] (see [crate::RawMode]).
+/// - Executing [crate::RenderOp]s ([PaintRenderOp] & [Flush]).
+/// - Reading input events ([TerminalBackend::new_input_device]).
+/// - Looking up the size of the terminal window ([TerminalBackend::lookup_size]).
+///
+/// The backend is picked w/ [set_terminal_lib_backend], and everything is routed to it
+/// w/ [route_to_terminal_backend].
+pub trait TerminalBackend: PaintRenderOp + Flush + DebugFormatRenderOp {
+    fn lookup_size(&self) -> CommonResult<Size>;
+
+    /// The input events are converted into [crossterm::event::Event]s, so that they can be
+    /// turned into [crate::InputEvent]s in the same way for all the backends.
+    fn new_input_device(&self) -> InputDevice;
+}
+
+static SELECTED_TERMINAL_LIB_BACKEND: OnceLock<TerminalLibBackend> = OnceLock::new();
+
+/// Select the terminal library to use, instead of [super::TERMINAL_LIB_BACKEND]. This
+/// has to be called at startup, before [crate::TerminalWindow::main_event_loop], and it
+/// can only be called once. If a backend was already selected, the `backend` is returned
+/// in the [Err].
+pub fn set_terminal_lib_backend(
+    backend: TerminalLibBackend,
+) -> Result<(), TerminalLibBackend> {
+    SELECTED_TERMINAL_LIB_BACKEND.set(backend)
+}
+
+pub fn get_terminal_lib_backend() -> TerminalLibBackend {
+    SELECTED_TERMINAL_LIB_BACKEND
+        .get()
+        .copied()
+        .unwrap_or(super::TERMINAL_LIB_BACKEND)
+}
+
+/// Run `f` w/ the [TerminalBackend] that was selected w/ [set_terminal_lib_backend].
+pub fn route_to_terminal_backend<R>(f: impl FnOnce(&mut dyn TerminalBackend) -> R) -> R {
+    match get_terminal_lib_backend() {
+        TerminalLibBackend::Crossterm => f(&mut RenderOpImplCrossterm {}),
+        #[cfg(feature = "termion")]
+        TerminalLibBackend::Termion => f(&mut crate::RenderOpImplTermion {}),
+    }
+}
@@ -15,12 +15,12 @@
  *   limitations under the License.
  */
 
-use miette::IntoDiagnostic as _;
-use r3bl_core::{size, CommonResult, Size};
+use r3bl_core::{CommonResult, Size};
 
-/// Interrogate crossterm [crossterm::terminal::size()] to get the size of the terminal window.
+use super::route_to_terminal_backend;
+
+/// Interrogate the selected [crate::TerminalBackend] to get the size of the terminal
+/// window.
 pub fn lookup_size() -> CommonResult<Size> {
-    let (col, row) = crossterm::terminal::size().into_diagnostic()?;
-    let size: Size = size!(col_count: col, row_count: row);
-    Ok(size)
+    route_to_terminal_backend(|backend| backend.lookup_size())
 }
@@ -0,0 +1,55 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+/// The OSC 0 escape sequence that sets the title of the terminal window (or tab), see
+/// <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h3-Operating-System-Commands>.
+const OSC0_START: &str = "\x1b]0;";
+const OSC0_END: &str = "\x07";
+
+/// Save the title on the terminal's title stack (XTWINOPS 22), when raw mode is entered,
+/// so that it can be restored w/ [POP_TITLE_SEQUENCE] when raw mode is exited. The
+/// terminals that don't support this ignore it.
+pub const PUSH_TITLE_SEQUENCE: &str = "\x1b[22;0t";
+
+/// Restore the title that was saved w/ [PUSH_TITLE_SEQUENCE] (XTWINOPS 23).
+pub const POP_TITLE_SEQUENCE: &str = "\x1b[23;0t";
+
+/// Returns the escape sequence that sets the title of the terminal to `title`. The
+/// control chars are removed from the `title`, so that it can't end the escape sequence
+/// early.
+pub fn get_title_escape_sequence(title: &str) -> String {
+    let title: String = title.chars().filter(|it| !it.is_control()).collect();
+    format!("{OSC0_START}{title}{OSC0_END}")
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_get_title_escape_sequence() {
+        assert_eq2!(
+            get_title_escape_sequence("edi - README.md"),
+            "\x1b]0;edi - README.md\x07".to_string()
+        );
+        assert_eq2!(
+            get_title_escape_sequence("edi\x07\x1b]0;oops"),
+            "\x1b]0;edi]0;oops\x07".to_string()
+        );
+    }
+}
@@ -0,0 +1,459 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{io,
+          os::fd::{AsRawFd as _, FromRawFd as _, OwnedFd, RawFd},
+          sync::mpsc as std_mpsc,
+          thread::{self, JoinHandle},
+          time::Duration};
+
+use crossterm::event::{Event,
+                       KeyCode,
+                       KeyEvent,
+                       KeyModifiers,
+                       MouseButton,
+                       MouseEvent,
+                       MouseEventKind};
+use r3bl_core::{CrosstermEventResult, InputDevice};
+use termion::{event as termion_event, input::TermRead as _};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+/// How often the size of the terminal is checked, since termion doesn't report resize
+/// events.
+const RESIZE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The markers that the terminal wraps pasted text in, when bracketed paste is on.
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Returns an [InputDevice] that reads the input events w/ termion. They are converted
+/// into [crossterm::event::Event]s, so that the rest of the TUI doesn't have to know
+/// which backend is used.
+///
+/// Termion only has a blocking API to read from stdin, so it is read in its own thread.
+/// The size of the terminal is polled in another thread, so that a resize event is sent
+/// when it changes. Both threads are stopped when the [InputDevice] is dropped, see
+/// [InputReaderThreads].
+pub fn new_termion_input_device() -> InputDevice {
+    new_termion_input_device_from_fd(libc::STDIN_FILENO)
+}
+
+/// Same as [new_termion_input_device], but the input is read from `fd` (instead of
+/// stdin), eg: in tests.
+pub fn new_termion_input_device_from_fd(fd: RawFd) -> InputDevice {
+    let (sender, receiver) = unbounded_channel::<CrosstermEventResult>();
+    let threads = InputReaderThreads::start(fd, sender);
+
+    InputDevice {
+        resource: Box::pin(futures_util::stream::unfold(
+            (receiver, threads),
+            |(mut receiver, threads)| async move {
+                receiver.recv().await.map(|it| (it, (receiver, threads)))
+            },
+        )),
+    }
+}
+
+/// The threads that read the input events & poll the size of the terminal. This is
+/// owned by the stream of the [InputDevice], so when that is dropped (eg: once the main
+/// event loop exits), the threads are stopped & joined. Otherwise the stdin reader
+/// would swallow the first key press that is meant for whatever reads stdin next (eg:
+/// readline), and the resize poller would keep running for the rest of the process.
+#[derive(Debug)]
+pub struct InputReaderThreads {
+    /// Closing this wakes up the stdin reader, which polls the other end of the pipe.
+    maybe_shutdown_fd: Option<OwnedFd>,
+    /// Dropping this wakes up the resize poller.
+    maybe_shutdown_sender: Option<std_mpsc::Sender<()>>,
+    join_handles: Vec<JoinHandle<()>>,
+}
+
+impl InputReaderThreads {
+    pub fn start(fd: RawFd, sender: UnboundedSender<CrosstermEventResult>) -> Self {
+        let mut join_handles = vec![];
+
+        let maybe_shutdown_fd = match make_pipe() {
+            Ok((shutdown_read_fd, shutdown_write_fd)) => {
+                let sender = sender.clone();
+                join_handles.push(thread::spawn(move || {
+                    read_input_events(fd, shutdown_read_fd, sender)
+                }));
+                Some(shutdown_write_fd)
+            }
+            Err(error) => {
+                tracing::error!("termion -> Failed to create shutdown pipe: {error}");
+                None
+            }
+        };
+
+        let (shutdown_sender, shutdown_receiver) = std_mpsc::channel::<()>();
+        join_handles.push(thread::spawn(move || {
+            poll_terminal_size(shutdown_receiver, sender)
+        }));
+
+        Self {
+            maybe_shutdown_fd,
+            maybe_shutdown_sender: Some(shutdown_sender),
+            join_handles,
+        }
+    }
+}
+
+impl Drop for InputReaderThreads {
+    fn drop(&mut self) {
+        self.maybe_shutdown_fd.take();
+        self.maybe_shutdown_sender.take();
+        for join_handle in self.join_handles.drain(..) {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+/// Returns the (read, write) ends of a new pipe.
+fn make_pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds: [RawFd; 2] = [0; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+/// Only read from `fd` when there is input, so that nothing is read once the
+/// `shutdown_fd` is closed (by [InputReaderThreads::drop]).
+fn read_input_events(
+    fd: RawFd,
+    shutdown_fd: OwnedFd,
+    sender: UnboundedSender<CrosstermEventResult>,
+) {
+    let mut paste_collector = BracketedPasteCollector::default();
+    let mut buffer = [0_u8; 1024];
+    loop {
+        let mut poll_fds = [
+            libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: shutdown_fd.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+        if unsafe { libc::poll(poll_fds.as_mut_ptr(), 2, -1) } < 0 {
+            match io::Error::last_os_error().kind() {
+                io::ErrorKind::Interrupted => continue,
+                _ => break,
+            }
+        }
+
+        // The input device was dropped.
+        if poll_fds[1].revents != 0 {
+            break;
+        }
+        if poll_fds[0].revents == 0 {
+            continue;
+        }
+
+        let count = unsafe { libc::read(fd, buffer.as_mut_ptr().cast(), buffer.len()) };
+        if count < 0 && io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+            continue;
+        }
+        // EOF or error.
+        if count <= 0 {
+            break;
+        }
+
+        for result in (&buffer[..count as usize]).events() {
+            let maybe_event = match result {
+                Ok(event) => paste_collector.collect(event).map(Ok),
+                Err(err) => Some(Err(err)),
+            };
+            if let Some(event) = maybe_event {
+                // The input device was dropped.
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+fn poll_terminal_size(
+    shutdown_receiver: std_mpsc::Receiver<()>,
+    sender: UnboundedSender<CrosstermEventResult>,
+) {
+    let mut maybe_last_size = termion::terminal_size().ok();
+    // This returns an error once the shutdown sender is dropped.
+    while let Err(std_mpsc::RecvTimeoutError::Timeout) =
+        shutdown_receiver.recv_timeout(RESIZE_POLL_INTERVAL)
+    {
+        let maybe_size = termion::terminal_size().ok();
+        if maybe_size == maybe_last_size {
+            continue;
+        }
+        maybe_last_size = maybe_size;
+        if let Some((cols, rows)) = maybe_size {
+            if sender.send(Ok(Event::Resize(cols, rows))).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+/// Termion doesn't know about bracketed paste, so the pasted text arrives as key events
+/// between 2 unsupported events (the start & end markers). They are collected into a
+/// single [Event::Paste] here, so that the rest of the TUI gets the same event as it
+/// does from crossterm.
+#[derive(Debug, Default)]
+pub struct BracketedPasteCollector {
+    maybe_text: Option<String>,
+}
+
+impl BracketedPasteCollector {
+    /// Returns the event that should be sent for the termion `event`, which is [None]
+    /// while the pasted text is being collected.
+    pub fn collect(&mut self, event: termion_event::Event) -> Option<Event> {
+        match event {
+            termion_event::Event::Unsupported(bytes) if bytes == PASTE_START => {
+                self.maybe_text = Some(String::new());
+                None
+            }
+            termion_event::Event::Unsupported(bytes) if bytes == PASTE_END => {
+                self.maybe_text.take().map(Event::Paste)
+            }
+            event => {
+                let Some(text) = &mut self.maybe_text else {
+                    return convert_from_termion_event(event);
+                };
+                match event {
+                    termion_event::Event::Key(termion_event::Key::Char(character)) => {
+                        text.push(character)
+                    }
+                    termion_event::Event::Key(termion_event::Key::Ctrl('i')) => {
+                        text.push('\t')
+                    }
+                    termion_event::Event::Key(
+                        termion_event::Key::Ctrl('m') | termion_event::Key::Ctrl('j'),
+                    ) => text.push('\n'),
+                    _ => {}
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Returns [None] for the events that can't be represented as a crossterm event.
+pub fn convert_from_termion_event(event: termion_event::Event) -> Option<Event> {
+    match event {
+        termion_event::Event::Key(key) => convert_from_termion_key(key)
+            .map(|(code, modifiers)| Event::Key(KeyEvent::new(code, modifiers))),
+        termion_event::Event::Mouse(mouse_event) => {
+            Some(Event::Mouse(convert_from_termion_mouse_event(mouse_event)))
+        }
+        termion_event::Event::Unsupported(_) => None,
+    }
+}
+
+fn convert_from_termion_key(key: termion_event::Key) -> Option<(KeyCode, KeyModifiers)> {
+    use termion_event::Key;
+    let it = match key {
+        Key::Backspace => (KeyCode::Backspace, KeyModifiers::NONE),
+        Key::Left => (KeyCode::Left, KeyModifiers::NONE),
+        Key::Right => (KeyCode::Right, KeyModifiers::NONE),
+        Key::Up => (KeyCode::Up, KeyModifiers::NONE),
+        Key::Down => (KeyCode::Down, KeyModifiers::NONE),
+        Key::ShiftLeft => (KeyCode::Left, KeyModifiers::SHIFT),
+        Key::ShiftRight => (KeyCode::Right, KeyModifiers::SHIFT),
+        Key::ShiftUp => (KeyCode::Up, KeyModifiers::SHIFT),
+        Key::ShiftDown => (KeyCode::Down, KeyModifiers::SHIFT),
+        Key::AltLeft => (KeyCode::Left, KeyModifiers::ALT),
+        Key::AltRight => (KeyCode::Right, KeyModifiers::ALT),
+        Key::AltUp => (KeyCode::Up, KeyModifiers::ALT),
+        Key::AltDown => (KeyCode::Down, KeyModifiers::ALT),
+        Key::CtrlLeft => (KeyCode::Left, KeyModifiers::CONTROL),
+        Key::CtrlRight => (KeyCode::Right, KeyModifiers::CONTROL),
+        Key::CtrlUp => (KeyCode::Up, KeyModifiers::CONTROL),
+        Key::CtrlDown => (KeyCode::Down, KeyModifiers::CONTROL),
+        Key::Home => (KeyCode::Home, KeyModifiers::NONE),
+        Key::End => (KeyCode::End, KeyModifiers::NONE),
+        Key::CtrlHome => (KeyCode::Home, KeyModifiers::CONTROL),
+        Key::CtrlEnd => (KeyCode::End, KeyModifiers::CONTROL),
+        Key::PageUp => (KeyCode::PageUp, KeyModifiers::NONE),
+        Key::PageDown => (KeyCode::PageDown, KeyModifiers::NONE),
+        Key::BackTab => (KeyCode::BackTab, KeyModifiers::SHIFT),
+        Key::Delete => (KeyCode::Delete, KeyModifiers::NONE),
+        Key::Insert => (KeyCode::Insert, KeyModifiers::NONE),
+        Key::F(number) => (KeyCode::F(number), KeyModifiers::NONE),
+        Key::Char('\n') => (KeyCode::Enter, KeyModifiers::NONE),
+        Key::Char('\t') => (KeyCode::Tab, KeyModifiers::NONE),
+        Key::Char(character) => (KeyCode::Char(character), KeyModifiers::NONE),
+        Key::Alt(character) => (KeyCode::Char(character), KeyModifiers::ALT),
+        Key::Ctrl(character) => (KeyCode::Char(character), KeyModifiers::CONTROL),
+        Key::Esc => (KeyCode::Esc, KeyModifiers::NONE),
+        _ => return None,
+    };
+    Some(it)
+}
+
+/// Termion positions start at 1 (and not 0). It doesn't report which button is released
+/// or dragged, so that is assumed to be the left button.
+fn convert_from_termion_mouse_event(
+    mouse_event: termion_event::MouseEvent,
+) -> MouseEvent {
+    let (kind, col, row) = match mouse_event {
+        termion_event::MouseEvent::Press(button, col, row) => {
+            let kind = match button {
+                termion_event::MouseButton::Left => {
+                    MouseEventKind::Down(MouseButton::Left)
+                }
+                termion_event::MouseButton::Right => {
+                    MouseEventKind::Down(MouseButton::Right)
+                }
+                termion_event::MouseButton::Middle => {
+                    MouseEventKind::Down(MouseButton::Middle)
+                }
+                termion_event::MouseButton::WheelUp => MouseEventKind::ScrollUp,
+                termion_event::MouseButton::WheelDown => MouseEventKind::ScrollDown,
+                termion_event::MouseButton::WheelLeft => MouseEventKind::ScrollLeft,
+                termion_event::MouseButton::WheelRight => MouseEventKind::ScrollRight,
+            };
+            (kind, col, row)
+        }
+        termion_event::MouseEvent::Release(col, row) => {
+            (MouseEventKind::Up(MouseButton::Left), col, row)
+        }
+        termion_event::MouseEvent::Hold(col, row) => {
+            (MouseEventKind::Drag(MouseButton::Left), col, row)
+        }
+    };
+
+    MouseEvent {
+        kind,
+        column: col.saturating_sub(1),
+        row: row.saturating_sub(1),
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt as _;
+
+    use super::*;
+
+    fn write_to_fd(fd: &OwnedFd, bytes: &[u8]) {
+        let count =
+            unsafe { libc::write(fd.as_raw_fd(), bytes.as_ptr().cast(), bytes.len()) };
+        assert_eq!(count, bytes.len() as isize);
+    }
+
+    #[tokio::test]
+    async fn test_input_events_are_read_from_fd() {
+        let (read_fd, write_fd) = make_pipe().unwrap();
+        let mut input_device = new_termion_input_device_from_fd(read_fd.as_raw_fd());
+
+        write_to_fd(&write_fd, b"a");
+        let event = input_device.resource.next().await.unwrap().unwrap();
+        assert_eq!(
+            event,
+            Event::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE))
+        );
+    }
+
+    #[test]
+    fn test_nothing_is_read_after_drop() {
+        let (read_fd, write_fd) = make_pipe().unwrap();
+        let input_device = new_termion_input_device_from_fd(read_fd.as_raw_fd());
+        // This joins the threads.
+        drop(input_device);
+
+        // The input is left for whatever reads it next.
+        write_to_fd(&write_fd, b"a");
+        let mut buffer = [0_u8; 1];
+        let count =
+            unsafe { libc::read(read_fd.as_raw_fd(), buffer.as_mut_ptr().cast(), 1) };
+        assert_eq!(count, 1);
+        assert_eq!(buffer[0], b'a');
+    }
+
+    #[test]
+    fn test_convert_from_termion_key() {
+        assert_eq!(
+            convert_from_termion_event(termion_event::Event::Key(
+                termion_event::Key::Ctrl('q')
+            )),
+            Some(Event::Key(KeyEvent::new(
+                KeyCode::Char('q'),
+                KeyModifiers::CONTROL
+            )))
+        );
+        assert_eq!(
+            convert_from_termion_event(termion_event::Event::Key(
+                termion_event::Key::Char('\n')
+            )),
+            Some(Event::Key(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::NONE
+            )))
+        );
+        assert_eq!(
+            convert_from_termion_event(termion_event::Event::Unsupported(vec![0])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bracketed_paste_collector() {
+        let mut paste_collector = BracketedPasteCollector::default();
+        let events = [
+            termion_event::Event::Unsupported(PASTE_START.to_vec()),
+            termion_event::Event::Key(termion_event::Key::Char('a')),
+            termion_event::Event::Key(termion_event::Key::Char('\n')),
+            termion_event::Event::Key(termion_event::Key::Char('b')),
+            termion_event::Event::Unsupported(PASTE_END.to_vec()),
+            termion_event::Event::Key(termion_event::Key::Char('c')),
+        ];
+        let converted_events: Vec<Event> = events
+            .into_iter()
+            .filter_map(|it| paste_collector.collect(it))
+            .collect();
+        assert_eq!(
+            converted_events,
+            vec![
+                Event::Paste("a\nb".to_string()),
+                Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_convert_from_termion_mouse_event() {
+        assert_eq!(
+            convert_from_termion_event(termion_event::Event::Mouse(
+                termion_event::MouseEvent::Press(termion_event::MouseButton::Left, 1, 5)
+            )),
+            Some(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: 0,
+                row: 4,
+                modifiers: KeyModifiers::NONE,
+            }))
+        );
+    }
+}
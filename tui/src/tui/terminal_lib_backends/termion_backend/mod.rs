@@ -14,3 +14,26 @@
  *   See the License for the specific language governing permissions and
  *   limitations under the License.
  */
+
+//! A [crate::TerminalBackend] that uses [termion] instead of crossterm. This is only
+//! available w/ the `termion` feature (and only on unix). Select it at startup w/:
+//!
+//! ```ignore
+//! set_terminal_lib_backend(TerminalLibBackend::Termion).ok();
+//! ```
+
+// Attach.
+#[cfg(feature = "termion")]
+pub mod input_device_impl;
+#[cfg(feature = "termion")]
+pub mod render_op_impl;
+#[cfg(feature = "termion")]
+pub mod termion_color_converter;
+
+// Re-export.
+#[cfg(feature = "termion")]
+pub use input_device_impl::*;
+#[cfg(feature = "termion")]
+pub use render_op_impl::*;
+#[cfg(feature = "termion")]
+pub use termion_color_converter::*;
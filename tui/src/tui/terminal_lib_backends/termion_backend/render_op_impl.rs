@@ -0,0 +1,534 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::{Formatter, Result},
+          io::{stdout, Stdout}};
+
+use miette::IntoDiagnostic as _;
+use r3bl_core::{size,
+                CommonResult,
+                InputDevice,
+                LockedOutputDevice,
+                Position,
+                Size,
+                StdMutex,
+                TuiStyle,
+                UnicodeString};
+use termion::{clear,
+              cursor,
+              raw::{IntoRawMode as _, RawTerminal},
+              screen,
+              style};
+
+use super::{convert_from_tui_color_to_termion_bg,
+            convert_from_tui_color_to_termion_fg,
+            new_termion_input_device};
+use crate::{get_clear_images_escape_sequence,
+            get_cursor_shape_escape_sequence,
+            get_hyperlink_escape_sequence,
+            get_image_escape_sequence,
+            get_image_protocol,
+            get_mouse_capture_mode,
+            get_osc52_escape_sequence,
+            get_synchronized_update_escape_sequence,
+            get_title_escape_sequence,
+            is_hyperlink_supported,
+            is_inside_tmux,
+            is_synchronized_update_supported,
+            sanitize_and_save_abs_position,
+            CrosstermDebugFormatRenderOp,
+            DebugFormatRenderOp,
+            Flush,
+            MouseCaptureMode,
+            PaintRenderOp,
+            RenderOp,
+            RenderOpsLocalData,
+            TerminalBackend,
+            POP_TITLE_SEQUENCE,
+            PUSH_TITLE_SEQUENCE,
+            RESET_CURSOR_SHAPE_SEQUENCE};
+
+/// Struct representing the implementation of [RenderOp] for termion terminal backend.
+/// This empty struct is needed since the [Flush] trait needs to be implemented.
+pub struct RenderOpImplTermion;
+
+/// Termion restores the terminal (exits raw mode) when the [RawTerminal] is dropped, so
+/// it is kept here between [RenderOp::EnterRawMode] and [RenderOp::ExitRawMode].
+static RAW_TERMINAL: StdMutex<Option<RawTerminal<Stdout>>> = StdMutex::new(None);
+
+/// These aren't exposed by termion (it writes them when a
+/// [termion::input::MouseTerminal] is created & dropped).
+const ENTER_MOUSE_SEQUENCE: &str = "\x1b[?1000h\x1b[?1002h\x1b[?1015h\x1b[?1006h";
+const EXIT_MOUSE_SEQUENCE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
+/// Turn bracketed paste on & off, see [super::BracketedPasteCollector].
+const ENTER_BRACKETED_PASTE_SEQUENCE: &str = "\x1b[?2004h";
+const EXIT_BRACKETED_PASTE_SEQUENCE: &str = "\x1b[?2004l";
+
+/// Write each of the escape sequences (anything that implements [std::fmt::Display]),
+/// and depending on what the [Result] is, run [tracing::error!] or [tracing::info!]. This
+/// is the termion version of [crate::queue_render_op!].
+macro_rules! write_render_op {
+    ($writer: expr, $arg_log_msg: expr $(, $escape_sequence: expr)* $(,)?) => {{
+        use r3bl_core::call_if_true;
+        use $crate::DEBUG_TUI_SHOW_TERMINAL_BACKEND;
+        $(
+            match write!($writer, "{}", $escape_sequence) {
+                Ok(_) => {
+                    let msg = format!("termion: ✅ {} successfully", $arg_log_msg);
+                    call_if_true!(DEBUG_TUI_SHOW_TERMINAL_BACKEND, tracing::info!(msg));
+                }
+                Err(err) => {
+                    let msg =
+                        format!("termion: ❌ Failed to {} due to {}", $arg_log_msg, err);
+                    call_if_true!(DEBUG_TUI_SHOW_TERMINAL_BACKEND, tracing::error!(msg));
+                }
+            };
+        )*
+    }};
+}
+
+mod impl_trait_terminal_backend {
+    use super::*;
+
+    impl TerminalBackend for RenderOpImplTermion {
+        fn lookup_size(&self) -> CommonResult<Size> {
+            let (col, row) = termion::terminal_size().into_diagnostic()?;
+            Ok(size!(col_count: col, row_count: row))
+        }
+
+        fn new_input_device(&self) -> InputDevice { new_termion_input_device() }
+    }
+
+    impl DebugFormatRenderOp for RenderOpImplTermion {
+        /// The [RenderOp]s are formatted in the same way for all the backends.
+        fn debug_format(&self, this: &RenderOp, f: &mut Formatter<'_>) -> Result {
+            CrosstermDebugFormatRenderOp {}.debug_format(this, f)
+        }
+    }
+}
+
+mod impl_trait_paint_render_op {
+    use super::*;
+
+    impl PaintRenderOp for RenderOpImplTermion {
+        fn paint(
+            &mut self,
+            skip_flush: &mut bool,
+            command_ref: &RenderOp,
+            window_size: Size,
+            local_data: &mut RenderOpsLocalData,
+            locked_output_device: LockedOutputDevice<'_>,
+            is_mock: bool,
+        ) {
+            match command_ref {
+                RenderOp::Noop => {}
+                RenderOp::EnterRawMode => {
+                    RenderOpImplTermion::raw_mode_enter(
+                        skip_flush,
+                        locked_output_device,
+                        is_mock,
+                    );
+                }
+                RenderOp::ExitRawMode => {
+                    RenderOpImplTermion::raw_mode_exit(
+                        skip_flush,
+                        locked_output_device,
+                        is_mock,
+                    );
+                }
+                RenderOp::MoveCursorPositionAbs(abs_pos) => {
+                    RenderOpImplTermion::move_cursor_position_abs(
+                        *abs_pos,
+                        window_size,
+                        local_data,
+                        locked_output_device,
+                    );
+                }
+                RenderOp::MoveCursorPositionRelTo(box_origin_pos, content_rel_pos) => {
+                    RenderOpImplTermion::move_cursor_position_abs(
+                        *box_origin_pos + *content_rel_pos,
+                        window_size,
+                        local_data,
+                        locked_output_device,
+                    );
+                }
+                RenderOp::ClearScreen => {
+                    write_render_op!(locked_output_device, "ClearScreen", clear::All)
+                }
+                RenderOp::SetFgColor(color) => {
+                    write_render_op!(
+                        locked_output_device,
+                        format!("SetFgColor({color:?})"),
+                        convert_from_tui_color_to_termion_fg(*color),
+                    )
+                }
+                RenderOp::SetBgColor(color) => {
+                    write_render_op!(
+                        locked_output_device,
+                        format!("SetBgColor({color:?})"),
+                        convert_from_tui_color_to_termion_bg(*color),
+                    )
+                }
+                RenderOp::ResetColor => {
+                    write_render_op!(
+                        locked_output_device,
+                        "ResetColor",
+                        termion::color::Fg(termion::color::Reset),
+                        termion::color::Bg(termion::color::Reset),
+                    )
+                }
+                RenderOp::ApplyColors(maybe_style) => {
+                    RenderOpImplTermion::apply_colors(maybe_style, locked_output_device);
+                }
+                RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                    text,
+                    maybe_style,
+                ) => {
+                    RenderOpImplTermion::paint_text_with_attributes(
+                        text,
+                        maybe_style,
+                        window_size,
+                        local_data,
+                        locked_output_device,
+                    );
+                }
+                RenderOp::SetHyperlink(maybe_url) => {
+                    if is_hyperlink_supported() {
+                        write_render_op!(
+                            locked_output_device,
+                            format!("SetHyperlink({maybe_url:?})"),
+                            get_hyperlink_escape_sequence(maybe_url.as_deref()),
+                        )
+                    }
+                }
+                RenderOp::BeginSynchronizedUpdate => {
+                    if is_synchronized_update_supported() {
+                        write_render_op!(
+                            locked_output_device,
+                            "BeginSynchronizedUpdate",
+                            get_synchronized_update_escape_sequence(true),
+                        )
+                    }
+                }
+                RenderOp::EndSynchronizedUpdate => {
+                    if is_synchronized_update_supported() {
+                        write_render_op!(
+                            locked_output_device,
+                            "EndSynchronizedUpdate",
+                            get_synchronized_update_escape_sequence(false),
+                        )
+                    }
+                }
+                RenderOp::PaintImage { data, bounds } => {
+                    if let Some(escape_sequence) = get_image_escape_sequence(
+                        get_image_protocol(),
+                        data,
+                        bounds.bounds_size,
+                    ) {
+                        RenderOpImplTermion::move_cursor_position_abs(
+                            bounds.origin_pos,
+                            window_size,
+                            local_data,
+                            locked_output_device,
+                        );
+                        write_render_op!(
+                            locked_output_device,
+                            format!("PaintImage({} bytes, {bounds:?})", data.len()),
+                            escape_sequence,
+                        )
+                    }
+                }
+                RenderOp::SetTitle(title) => {
+                    write_render_op!(
+                        locked_output_device,
+                        format!("SetTitle({title:?})"),
+                        get_title_escape_sequence(title),
+                    )
+                }
+                RenderOp::SetClipboard(content) => {
+                    write_render_op!(
+                        locked_output_device,
+                        format!("SetClipboard({} chars)", content.len()),
+                        get_osc52_escape_sequence(content, is_inside_tmux()),
+                    )
+                }
+                RenderOp::ShowCursor(shape) => {
+                    write_render_op!(
+                        locked_output_device,
+                        format!("ShowCursor({shape:?})"),
+                        get_cursor_shape_escape_sequence(*shape),
+                        cursor::Show,
+                    )
+                }
+                RenderOp::HideCursor => {
+                    write_render_op!(locked_output_device, "HideCursor", cursor::Hide)
+                }
+                RenderOp::PaintTextWithAttributes(_text, _maybe_style) => {
+                    // This should never be executed! The compositor always renders to an offscreen
+                    // buffer first, then that is diff'd and then painted via calls to
+                    // CompositorNoClipTruncPaintTextWithAttributes.
+                }
+            }
+        }
+    }
+}
+
+mod impl_trait_flush {
+    use super::*;
+
+    impl Flush for RenderOpImplTermion {
+        fn flush(&mut self, locked_output_device: LockedOutputDevice<'_>) {
+            crate::flush_now!(locked_output_device, "flush() -> output_device");
+        }
+
+        fn clear_before_flush(&mut self, locked_output_device: LockedOutputDevice<'_>) {
+            write_render_op!(
+                locked_output_device,
+                "flush() -> after ResetColor, Clear",
+                termion::color::Fg(termion::color::Reset),
+                termion::color::Bg(termion::color::Reset),
+                clear::All,
+            );
+            if let Some(it) = get_clear_images_escape_sequence(get_image_protocol()) {
+                write_render_op!(locked_output_device, "ClearImages", it);
+            }
+        }
+    }
+}
+
+mod impl_self {
+    use super::*;
+
+    impl RenderOpImplTermion {
+        pub fn move_cursor_position_abs(
+            abs_pos: Position,
+            window_size: Size,
+            local_data: &mut RenderOpsLocalData,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            let Position {
+                col_index: col,
+                row_index: row,
+            } = sanitize_and_save_abs_position(abs_pos, window_size, local_data);
+
+            // Termion positions start at 1 (and not 0).
+            write_render_op!(
+                locked_output_device,
+                format!("MoveCursorPosition(col: {}, row: {})", *col, *row),
+                cursor::Goto(*col + 1, *row + 1)
+            )
+        }
+
+        pub fn raw_mode_enter(
+            skip_flush: &mut bool,
+            locked_output_device: LockedOutputDevice<'_>,
+            is_mock: bool,
+        ) {
+            if is_mock {
+                return;
+            }
+
+            match stdout().into_raw_mode() {
+                Ok(raw_terminal) => {
+                    if let Ok(mut it) = RAW_TERMINAL.lock() {
+                        *it = Some(raw_terminal);
+                    }
+                }
+                Err(err) => {
+                    tracing::error!("termion: ❌ Failed to enable raw mode due to {err}");
+                }
+            }
+
+            RenderOpImplTermion::enable_mouse_capture(
+                get_mouse_capture_mode(),
+                locked_output_device,
+            );
+            write_render_op!(
+                locked_output_device,
+                "EnterRawMode -> PushTitle, EnableBracketedPaste, EnterAlternateScreen, Goto(1,1), Clear(All), Hide",
+                PUSH_TITLE_SEQUENCE,
+                ENTER_BRACKETED_PASTE_SEQUENCE,
+                screen::ToAlternateScreen,
+                cursor::Goto(1, 1),
+                clear::All,
+                cursor::Hide,
+            );
+
+            crate::flush_now!(locked_output_device, "EnterRawMode -> flush()");
+
+            *skip_flush = true;
+        }
+
+        pub fn raw_mode_exit(
+            skip_flush: &mut bool,
+            locked_output_device: LockedOutputDevice<'_>,
+            is_mock: bool,
+        ) {
+            write_render_op!(
+                locked_output_device,
+                "ExitRawMode -> ResetCursorShape, Show, ToMainScreen, DisableBracketedPaste, PopTitle",
+                RESET_CURSOR_SHAPE_SEQUENCE,
+                cursor::Show,
+                screen::ToMainScreen,
+                EXIT_BRACKETED_PASTE_SEQUENCE,
+                POP_TITLE_SEQUENCE,
+            );
+            RenderOpImplTermion::disable_mouse_capture(
+                get_mouse_capture_mode(),
+                locked_output_device,
+            );
+
+            crate::flush_now!(locked_output_device, "ExitRawMode -> flush()");
+
+            if !is_mock {
+                // Dropping the raw terminal restores the terminal.
+                if let Ok(mut it) = RAW_TERMINAL.lock() {
+                    it.take();
+                }
+            }
+
+            *skip_flush = true;
+        }
+
+        /// Mouse capture is only turned on (when entering raw mode) if the `mode` is
+        /// [MouseCaptureMode::Enable].
+        pub fn enable_mouse_capture(
+            mode: MouseCaptureMode,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            if mode == MouseCaptureMode::Enable {
+                write_render_op!(
+                    locked_output_device,
+                    "EnterRawMode -> EnableMouseCapture",
+                    ENTER_MOUSE_SEQUENCE,
+                );
+            }
+        }
+
+        /// Mouse capture is only turned off (when exiting raw mode) if the `mode` is
+        /// [MouseCaptureMode::Enable], since it wasn't turned on otherwise.
+        pub fn disable_mouse_capture(
+            mode: MouseCaptureMode,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            if mode == MouseCaptureMode::Enable {
+                write_render_op!(
+                    locked_output_device,
+                    "ExitRawMode -> DisableMouseCapture",
+                    EXIT_MOUSE_SEQUENCE,
+                );
+            }
+        }
+
+        pub fn apply_colors(
+            maybe_style: &Option<TuiStyle>,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            let Some(style) = maybe_style else {
+                return;
+            };
+
+            if let Some(color_bg) = style.color_bg {
+                write_render_op!(
+                    locked_output_device,
+                    format!("ApplyColors -> SetBgColor({color_bg:?})"),
+                    convert_from_tui_color_to_termion_bg(color_bg),
+                );
+            }
+
+            if let Some(color_fg) = style.color_fg {
+                write_render_op!(
+                    locked_output_device,
+                    format!("ApplyColors -> SetFgColor({color_fg:?})"),
+                    convert_from_tui_color_to_termion_fg(color_fg),
+                );
+            }
+        }
+
+        pub fn paint_text_with_attributes(
+            text: &str,
+            maybe_style: &Option<TuiStyle>,
+            window_size: Size,
+            local_data: &mut RenderOpsLocalData,
+            locked_output_device: LockedOutputDevice<'_>,
+        ) {
+            let attributes = match maybe_style {
+                Some(style) => style_to_attributes(style),
+                None => vec![],
+            };
+
+            for attribute in &attributes {
+                write_render_op!(
+                    locked_output_device,
+                    "PaintWithAttributes -> SetAttribute",
+                    attribute,
+                );
+            }
+
+            write_render_op!(locked_output_device, format!("Print(\"{text}\")"), text);
+
+            if !attributes.is_empty() {
+                write_render_op!(
+                    locked_output_device,
+                    "PaintWithAttributes -> SetAttribute(Reset)",
+                    style::Reset,
+                );
+            }
+
+            // Update cursor position after paint.
+            let mut cursor_position_copy = local_data.cursor_position;
+            cursor_position_copy.col_index += UnicodeString::from(text).display_width;
+            sanitize_and_save_abs_position(cursor_position_copy, window_size, local_data);
+        }
+    }
+
+    /// Termion doesn't have attributes for [TuiStyle::hidden], the extended kinds of
+    /// [TuiStyle::underline_style], or [TuiStyle::color_underline], so their escape
+    /// sequences are used directly.
+    fn style_to_attributes(style: &TuiStyle) -> Vec<String> {
+        let mut it = vec![];
+        if style.bold {
+            it.push(style::Bold.to_string());
+        }
+        if style.italic {
+            it.push(style::Italic.to_string());
+        }
+        if style.dim {
+            it.push(style::Faint.to_string());
+        }
+        if style.underline {
+            it.push(format!("\x1b[{}m", style.underline_style.get_sgr_params()));
+            if let Some(color_underline) = style.color_underline {
+                let color_underline = crossterm::style::Colored::UnderlineColor(
+                    crate::convert_from_tui_color_to_crossterm_color(color_underline),
+                );
+                it.push(format!("\x1b[{color_underline}m"));
+            }
+        }
+        if style.reverse {
+            it.push(style::Invert.to_string());
+        }
+        if style.hidden {
+            it.push("\x1b[8m".to_string());
+        }
+        if style.strikethrough {
+            it.push(style::CrossedOut.to_string());
+        }
+        it
+    }
+}
@@ -0,0 +1,91 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use termion::color;
+
+use crate::convert_from_tui_color_to_crossterm_color;
+
+/// Returns the escape sequence to set the fg (or bg) to the `tui_color`. The color is
+/// downgraded (if the terminal doesn't support it) in the same way as it is for
+/// crossterm, see [convert_from_tui_color_to_crossterm_color].
+macro_rules! termion_color_escape {
+    ($wrapper: path, $tui_color: expr) => {{
+        use crossterm::style::Color;
+        match convert_from_tui_color_to_crossterm_color($tui_color) {
+            Color::Reset => $wrapper(color::Reset).to_string(),
+            Color::Black => $wrapper(color::Black).to_string(),
+            Color::DarkGrey => $wrapper(color::LightBlack).to_string(),
+            Color::Red => $wrapper(color::LightRed).to_string(),
+            Color::DarkRed => $wrapper(color::Red).to_string(),
+            Color::Green => $wrapper(color::LightGreen).to_string(),
+            Color::DarkGreen => $wrapper(color::Green).to_string(),
+            Color::Yellow => $wrapper(color::LightYellow).to_string(),
+            Color::DarkYellow => $wrapper(color::Yellow).to_string(),
+            Color::Blue => $wrapper(color::LightBlue).to_string(),
+            Color::DarkBlue => $wrapper(color::Blue).to_string(),
+            Color::Magenta => $wrapper(color::LightMagenta).to_string(),
+            Color::DarkMagenta => $wrapper(color::Magenta).to_string(),
+            Color::Cyan => $wrapper(color::LightCyan).to_string(),
+            Color::DarkCyan => $wrapper(color::Cyan).to_string(),
+            Color::White => $wrapper(color::LightWhite).to_string(),
+            Color::Grey => $wrapper(color::White).to_string(),
+            Color::Rgb { r, g, b } => $wrapper(color::Rgb(r, g, b)).to_string(),
+            Color::AnsiValue(number) => $wrapper(color::AnsiValue(number)).to_string(),
+        }
+    }};
+}
+
+pub fn convert_from_tui_color_to_termion_fg(tui_color: r3bl_core::TuiColor) -> String {
+    termion_color_escape!(color::Fg, tui_color)
+}
+
+pub fn convert_from_tui_color_to_termion_bg(tui_color: r3bl_core::TuiColor) -> String {
+    termion_color_escape!(color::Bg, tui_color)
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{ANSIBasicColor, RgbValue, TuiColor};
+
+    use super::*;
+
+    #[test]
+    fn test_convert_from_tui_color_to_termion() {
+        assert_eq!(
+            convert_from_tui_color_to_termion_fg(TuiColor::Reset),
+            "\x1b[39m"
+        );
+        assert_eq!(
+            convert_from_tui_color_to_termion_bg(TuiColor::Reset),
+            "\x1b[49m"
+        );
+        assert_eq!(
+            convert_from_tui_color_to_termion_fg(TuiColor::Basic(ANSIBasicColor::Black)),
+            "\x1b[38;5;0m"
+        );
+        // Only check the prefix, since the color may be downgraded if the terminal that
+        // runs the test doesn't support truecolor.
+        assert!(
+            convert_from_tui_color_to_termion_bg(TuiColor::Rgb(RgbValue {
+                red: 1,
+                green: 2,
+                blue: 3
+            }))
+            .starts_with("\x1b[48;")
+        );
+    }
+}
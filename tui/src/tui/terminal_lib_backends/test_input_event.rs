@@ -57,6 +57,15 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_convert_paste_event_into_input_event() {
+        let event = crossterm::event::Event::Paste("a\nb".to_string());
+        assert_eq2!(
+            InputEvent::try_from(event),
+            Ok(InputEvent::Paste("a\nb".to_string()))
+        );
+    }
+
     #[test]
     fn test_input_event_matches_correctly() -> Result<(), ()> {
         throws!({
@@ -92,6 +92,24 @@ mod tests {
                 position!(col_index: 0, row_index: 0)
             );
         }
+        // Mouse scroll left & right.
+        for (kind, expected_kind) in [
+            (MouseEventKind::ScrollLeft, MouseInputKind::ScrollLeft),
+            (MouseEventKind::ScrollRight, MouseInputKind::ScrollRight),
+        ] {
+            let mouse_event: MouseEvent = MouseEvent {
+                kind,
+                column: 4,
+                row: 2,
+                modifiers: KeyModifiers::NONE,
+            };
+            let converted_mouse_input: MouseInput = mouse_event.into();
+            assert_eq2!(converted_mouse_input.kind, expected_kind);
+            assert_eq2!(
+                converted_mouse_input.pos,
+                position!(col_index: 4, row_index: 2)
+            );
+        }
     }
 
     #[test]
@@ -17,10 +17,10 @@
 
 use std::fmt::Debug;
 
-use r3bl_core::CommonResult;
+use r3bl_core::{CommonResult, Size};
 
 use super::{ComponentRegistryMap, EventPropagation, GlobalData, HasFocus};
-use crate::{InputEvent, RenderPipeline};
+use crate::{InputEvent, RenderPipeline, ShortcutGroup, SplashScreen};
 
 /// An app is typically a holder for [crate::ComponentRegistry].
 ///
@@ -77,6 +77,30 @@ pub trait App {
         has_focus: &mut HasFocus,
     ) -> CommonResult<EventPropagation>;
 
+    /// This is called once after the terminal is resized (and a burst of resize events
+    /// has settled, see [crate::ResizeDebouncer]), before the app is rendered at the
+    /// `new_size`. Override this to invalidate only what depends on the size (eg: by
+    /// comparing the `old_size` w/ the `new_size`).
+    ///
+    /// By default, this passes an [InputEvent::Resize] w/ the `new_size` to
+    /// [App::app_handle_input_event].
+    fn app_handle_resize(
+        &mut self,
+        old_size: Size,
+        new_size: Size,
+        global_data: &mut GlobalData<Self::S, Self::AS>,
+        component_registry_map: &mut ComponentRegistryMap<Self::S, Self::AS>,
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        let _ = old_size;
+        self.app_handle_input_event(
+            InputEvent::Resize(new_size),
+            global_data,
+            component_registry_map,
+            has_focus,
+        )
+    }
+
     /// Use the state to render the output (via crossterm). The state is immutable. If you
     /// want to change it then it should be done in the [App::app_handle_input_event]
     /// method.
@@ -89,4 +113,20 @@ pub trait App {
         component_registry_map: &mut ComponentRegistryMap<Self::S, Self::AS>,
         has_focus: &mut HasFocus,
     ) -> CommonResult<RenderPipeline>;
+
+    /// The keyboard shortcuts that the app handles (regardless of which component has
+    /// focus), which are listed in the help overlay, see [crate::ShortcutRegistry]. The
+    /// components declare their own w/ [crate::Component::get_shortcuts].
+    fn app_get_shortcuts(&self) -> Option<ShortcutGroup> { None }
+
+    /// This is called once, before [App::app_init]. Return a [SplashScreen] to have it
+    /// painted (instead of the app) while the app performs its async startup work, and
+    /// spawn that work here w/ a [crate::SplashHandle] (see [SplashScreen::get_handle]),
+    /// which replaces the splash screen w/ the app when it is done.
+    fn app_start_splash_screen(
+        &mut self,
+        _global_data: &mut GlobalData<Self::S, Self::AS>,
+    ) -> Option<SplashScreen> {
+        None
+    }
 }
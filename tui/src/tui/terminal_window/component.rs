@@ -17,10 +17,17 @@
 
 use std::fmt::Debug;
 
-use r3bl_core::CommonResult;
+use r3bl_core::{CommonResult, Position};
 
-use super::{ComponentRegistryMap, EventPropagation, GlobalData, HasFocus};
-use crate::{FlexBox, FlexBoxId, InputEvent, RenderPipeline, Surface, SurfaceBounds};
+use super::{ComponentRegistryMap, EventPropagation, FocusChange, GlobalData, HasFocus};
+use crate::{FlexBox,
+            FlexBoxId,
+            InputEvent,
+            MenuItem,
+            RenderPipeline,
+            ShortcutGroup,
+            Surface,
+            SurfaceBounds};
 
 /// See [crate::App].
 pub trait Component<S, AS>
@@ -89,6 +96,60 @@ where
         input_event: InputEvent,
         has_focus: &mut HasFocus,
     ) -> CommonResult<EventPropagation>;
+
+    /// Whether this component takes part in <kbd>Tab</kbd> & <kbd>Shift+Tab</kbd> focus
+    /// traversal, see [crate::ComponentRegistry::try_move_focus]. Components that only
+    /// display something (eg: a status bar) or that are modal (eg: a dialog) shouldn't
+    /// get focus this way, so this is opt-in.
+    fn is_focusable(&self) -> bool { false }
+
+    /// This is called when this component gains or loses keyboard focus by way of
+    /// [crate::ComponentRegistry::change_focus]. The app is rendered after the focus
+    /// moves, and [Component::render] can check `has_focus` to restyle the component, so
+    /// this only needs to be implemented to update some state (eg: to hide a popup when
+    /// focus is lost).
+    fn handle_focus_change(
+        &mut self,
+        _global_data: &mut GlobalData<S, AS>,
+        _focus_change: FocusChange,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<()> {
+        Ok(())
+    }
+
+    /// The keyboard shortcuts that this component handles (while it has focus), which
+    /// are listed in the help overlay, see [crate::ShortcutRegistry].
+    fn get_shortcuts(&self) -> Option<ShortcutGroup> { None }
+
+    /// This is called by the main event loop on each tick of this component's
+    /// subscription to [GlobalData::timers] (see [crate::TimerService::subscribe]), eg:
+    /// to advance a spinner. Return [EventPropagation::ConsumedRender] to have the app
+    /// rendered again, and unsubscribe once there's nothing left to animate.
+    fn handle_tick(
+        &mut self,
+        _global_data: &mut GlobalData<S, AS>,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        Ok(EventPropagation::Propagate)
+    }
+
+    /// The items of this component's context menu, which is opened when it is right
+    /// clicked, or w/ <kbd>Shift+F10</kbd> while it has focus (see
+    /// [crate::ComponentRegistry::try_open_context_menu]). The action of the item that is
+    /// chosen is dispatched to the app. Return no items to not have a context menu.
+    fn context_menu_items(&self, _global_data: &GlobalData<S, AS>) -> Vec<MenuItem<AS>> {
+        vec![]
+    }
+
+    /// Where the context menu is opened w/ the keyboard, relative to the top left corner
+    /// of this component's (style adjusted) box, eg: below the caret. The menu is opened
+    /// at the top left corner if this returns [None].
+    fn get_context_menu_anchor(
+        &self,
+        _global_data: &GlobalData<S, AS>,
+    ) -> Option<Position> {
+        None
+    }
 }
 
 pub trait SurfaceRender<S, AS>
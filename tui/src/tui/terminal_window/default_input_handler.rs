@@ -21,10 +21,9 @@ use crate::InputEvent;
 pub struct DefaultInputEventHandler;
 
 impl DefaultInputEventHandler {
-    /// This function does **not** consume the `input_event` argument. [InputEvent] implements [Copy]
-    /// (no need to pass references into this function).
+    /// This function does **not** consume the `input_event` argument.
     pub fn no_consume(
-        input_event: InputEvent,
+        input_event: &InputEvent,
         exit_keys: &[InputEvent],
     ) -> Continuation<String> {
         // Early return if any exit key sequence is pressed.
@@ -0,0 +1,308 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(unix)]
+use std::sync::Mutex as StdMutex;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+#[cfg(unix)]
+use tokio::signal::unix::{signal, Signal, SignalKind};
+
+use crate::{keypress, InputEvent, ModifierKeysMask};
+
+static SUSPEND_KEY_MODE: OnceLock<SuspendKeyMode> = OnceLock::new();
+
+/// The `SIGTSTP` handler that tokio installed, which is replaced w/ the default one when
+/// [JobControlSignals] is dropped, see [restore_default_sigtstp_handler]. Tokio only
+/// installs its handler once per process, so this is used to install it again for the
+/// next [JobControlSignals].
+#[cfg(unix)]
+static MAYBE_TOKIO_SIGTSTP_HANDLER: StdMutex<Option<libc::sigaction>> =
+    StdMutex::new(None);
+
+/// Controls whether pressing Ctrl+Z (when the app doesn't consume it) suspends the app,
+/// like it does in a shell, see [is_suspend_key]. It is off by default, since the apps
+/// (or their components) might use Ctrl+Z for something else, eg: the editor uses it to
+/// undo, so it would suspend the app whenever the focus is on another component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuspendKeyMode {
+    Enable,
+    Disable,
+}
+
+/// Set the [SuspendKeyMode]. This has to be called before
+/// [crate::TerminalWindow::main_event_loop], and it can only be called once. If it was
+/// already set (or used), the `mode` is returned in the [Err].
+pub fn set_suspend_key_mode(mode: SuspendKeyMode) -> Result<(), SuspendKeyMode> {
+    SUSPEND_KEY_MODE.set(mode)
+}
+
+/// The suspend key is disabled, unless [set_suspend_key_mode] was used to enable it.
+pub fn is_suspend_key_enabled() -> bool {
+    *SUSPEND_KEY_MODE.get_or_init(|| SuspendKeyMode::Disable) == SuspendKeyMode::Enable
+}
+
+/// The job control signals that are handled by the main event loop, see
+/// [JobControlSignals].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JobControlSignal {
+    /// `SIGTSTP` was received, eg: from `kill -TSTP <pid>`. The terminal is restored
+    /// and the process is suspended, see [crate::handle_suspend].
+    Suspend,
+    /// `SIGCONT` was received, ie: the process was resumed (eg: w/ `fg` in the shell),
+    /// so the app is painted again from scratch.
+    Resume,
+}
+
+/// Listens for `SIGTSTP` & `SIGCONT` (on unix), so that apps behave like vim under job
+/// control.
+///
+/// Note that pressing Ctrl+Z doesn't send `SIGTSTP` while the terminal is in raw mode,
+/// it is delivered to the app as an [InputEvent] instead. If the app doesn't consume
+/// it, the main event loop suspends the app when the [SuspendKeyMode] is enabled, see
+/// [is_suspend_key].
+///
+/// The default `SIGTSTP` handler is restored when this is dropped, so that the process
+/// can be stopped again once the main event loop exits.
+#[derive(Debug, Default)]
+pub struct JobControlSignals {
+    #[cfg(unix)]
+    maybe_sigtstp: Option<Signal>,
+    #[cfg(unix)]
+    maybe_sigcont: Option<Signal>,
+    is_next_resume_skipped: bool,
+}
+
+impl JobControlSignals {
+    /// Start listening for the signals. Nothing is listened for w/ a mock terminal (eg:
+    /// in tests), or if the signal handlers can't be installed.
+    pub fn new(is_mock: bool) -> Self {
+        if is_mock {
+            return Self::default();
+        }
+
+        #[cfg(unix)]
+        let maybe_sigtstp = signal(SignalKind::from_raw(libc::SIGTSTP)).ok();
+        #[cfg(unix)]
+        if maybe_sigtstp.is_some() {
+            reinstall_tokio_sigtstp_handler();
+        }
+
+        Self {
+            #[cfg(unix)]
+            maybe_sigtstp,
+            #[cfg(unix)]
+            maybe_sigcont: signal(SignalKind::from_raw(libc::SIGCONT)).ok(),
+            is_next_resume_skipped: false,
+        }
+    }
+
+    /// Call this after the process suspended itself (see [suspend_process]) and has
+    /// already been painted from scratch, since the `SIGCONT` that resumed it is still
+    /// going to be received. Otherwise it would be painted a second time.
+    pub fn skip_next_resume(&mut self) {
+        #[cfg(unix)]
+        {
+            self.is_next_resume_skipped = self.maybe_sigcont.is_some();
+        }
+    }
+
+    /// Returns `true` (and clears the flag) if the [JobControlSignal::Resume] that was
+    /// just received should be ignored, see [JobControlSignals::skip_next_resume].
+    pub fn take_is_next_resume_skipped(&mut self) -> bool {
+        std::mem::take(&mut self.is_next_resume_skipped)
+    }
+
+    /// Wait for the next signal. This never returns if nothing is listened for. It is
+    /// cancel safe, so it can be used in [tokio::select!].
+    pub async fn recv(&mut self) -> JobControlSignal {
+        #[cfg(unix)]
+        {
+            tokio::select! {
+                _ = recv_signal(&mut self.maybe_sigtstp) => JobControlSignal::Suspend,
+                _ = recv_signal(&mut self.maybe_sigcont) => JobControlSignal::Resume,
+            }
+        }
+
+        #[cfg(not(unix))]
+        {
+            std::future::pending().await
+        }
+    }
+}
+
+impl Drop for JobControlSignals {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if self.maybe_sigtstp.is_some() {
+            restore_default_sigtstp_handler();
+        }
+    }
+}
+
+/// Tokio never removes the handlers it installs, so once the main event loop exits, the
+/// `SIGTSTP` (eg: from Ctrl+Z in the shell) would still be caught, and the process would
+/// not be stopped. The handler is saved, so that it can be installed again, see
+/// [reinstall_tokio_sigtstp_handler]. The `SIGCONT` handler is left alone, since the
+/// process is resumed whether or not that signal is caught.
+#[cfg(unix)]
+fn restore_default_sigtstp_handler() {
+    let Ok(mut maybe_tokio_handler) = MAYBE_TOKIO_SIGTSTP_HANDLER.lock() else {
+        return;
+    };
+    unsafe {
+        let mut default_handler: libc::sigaction = std::mem::zeroed();
+        default_handler.sa_sigaction = libc::SIG_DFL;
+        let mut tokio_handler: libc::sigaction = std::mem::zeroed();
+        if libc::sigaction(libc::SIGTSTP, &default_handler, &mut tokio_handler) == 0 {
+            *maybe_tokio_handler = Some(tokio_handler);
+        }
+    }
+}
+
+/// Install the `SIGTSTP` handler that was replaced by [restore_default_sigtstp_handler]
+/// (if any) again.
+#[cfg(unix)]
+fn reinstall_tokio_sigtstp_handler() {
+    let Ok(maybe_tokio_handler) = MAYBE_TOKIO_SIGTSTP_HANDLER.lock() else {
+        return;
+    };
+    if let Some(ref tokio_handler) = *maybe_tokio_handler {
+        unsafe {
+            libc::sigaction(libc::SIGTSTP, tokio_handler, std::ptr::null_mut());
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn recv_signal(maybe_signal: &mut Option<Signal>) {
+    match maybe_signal {
+        Some(signal) if signal.recv().await.is_some() => {}
+        _ => std::future::pending().await,
+    }
+}
+
+/// Pressing Ctrl+Z (when the app doesn't consume it, and the [SuspendKeyMode] is
+/// enabled) sends a [crate::TerminalWindowMainThreadSignal::Suspend], like it does in a
+/// shell.
+pub fn is_suspend_key(input_event: &InputEvent) -> bool {
+    let ctrl_z = keypress!(@char ModifierKeysMask::new().with_ctrl(), 'z');
+    matches!(input_event, InputEvent::Keyboard(it) if *it == ctrl_z)
+}
+
+/// Stop the process (like Ctrl+Z does in a shell that isn't in raw mode), and return
+/// once it is resumed, eg: w/ `fg`. The terminal has to be restored before this is
+/// called, see [crate::handle_suspend]. This does nothing on platforms that don't have
+/// job control.
+pub fn suspend_process() {
+    // SIGSTOP is used (instead of SIGTSTP) since it can't be caught, so it isn't
+    // delivered to the SIGTSTP handler of [JobControlSignals].
+    #[cfg(unix)]
+    unsafe {
+        libc::raise(libc::SIGSTOP);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{Key, KeyPress};
+
+    #[test]
+    fn test_is_suspend_key() {
+        let ctrl_z = keypress!(@char ModifierKeysMask::new().with_ctrl(), 'z');
+        assert!(is_suspend_key(&InputEvent::Keyboard(ctrl_z)));
+
+        let z = KeyPress::Plain {
+            key: Key::Character('z'),
+        };
+        assert!(!is_suspend_key(&InputEvent::Keyboard(z)));
+    }
+
+    #[test]
+    fn test_resume_is_not_skipped_w_mock_terminal() {
+        let mut job_control_signals = JobControlSignals::new(true);
+        job_control_signals.skip_next_resume();
+        assert!(!job_control_signals.take_is_next_resume_skipped());
+    }
+
+    #[cfg(unix)]
+    fn get_sigtstp_handler() -> libc::sighandler_t {
+        let mut handler: libc::sigaction = unsafe { std::mem::zeroed() };
+        unsafe { libc::sigaction(libc::SIGTSTP, std::ptr::null(), &mut handler) };
+        handler.sa_sigaction
+    }
+
+    #[cfg(unix)]
+    async fn recv_w_timeout(
+        job_control_signals: &mut JobControlSignals,
+    ) -> JobControlSignal {
+        tokio::time::timeout(Duration::from_secs(5), job_control_signals.recv())
+            .await
+            .unwrap()
+    }
+
+    /// The signals are raised in this process, so this is a single test, so that they
+    /// don't interfere w/ each other.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_job_control_signals() {
+        let mut job_control_signals = JobControlSignals::new(false);
+
+        // The SIGCONT that resumes the process after it suspended itself is skipped,
+        // like the main event loop does after handle_suspend.
+        job_control_signals.skip_next_resume();
+        unsafe { libc::raise(libc::SIGCONT) };
+        assert_eq!(
+            recv_w_timeout(&mut job_control_signals).await,
+            JobControlSignal::Resume
+        );
+        assert!(job_control_signals.take_is_next_resume_skipped());
+
+        // The next one (eg: when it is resumed after `kill -STOP`) isn't skipped.
+        unsafe { libc::raise(libc::SIGCONT) };
+        assert_eq!(
+            recv_w_timeout(&mut job_control_signals).await,
+            JobControlSignal::Resume
+        );
+        assert!(!job_control_signals.take_is_next_resume_skipped());
+
+        // SIGTSTP is caught (and doesn't stop the process) while it is listened for.
+        assert_ne!(get_sigtstp_handler(), libc::SIG_DFL);
+        unsafe { libc::raise(libc::SIGTSTP) };
+        assert_eq!(
+            recv_w_timeout(&mut job_control_signals).await,
+            JobControlSignal::Suspend
+        );
+
+        // It stops the process again once the main event loop exits.
+        drop(job_control_signals);
+        assert_eq!(get_sigtstp_handler(), libc::SIG_DFL);
+
+        // It is caught again by the next main event loop.
+        let mut job_control_signals = JobControlSignals::new(false);
+        assert_ne!(get_sigtstp_handler(), libc::SIG_DFL);
+        unsafe { libc::raise(libc::SIGTSTP) };
+        assert_eq!(
+            recv_w_timeout(&mut job_control_signals).await,
+            JobControlSignal::Suspend
+        );
+    }
+}
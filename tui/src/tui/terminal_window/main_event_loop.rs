@@ -22,6 +22,7 @@ use r3bl_core::{call_if_true,
                 ok,
                 output_device_as_mut,
                 position,
+                send_signal,
                 throws,
                 Ansi256GradientIndex,
                 ColorWheel,
@@ -30,7 +31,6 @@ use r3bl_core::{call_if_true,
                 CommonResult,
                 GradientGenerationPolicy,
                 InputDevice,
-                LockedOutputDevice,
                 OutputDevice,
                 Size,
                 TextColorizationPolicy,
@@ -38,22 +38,41 @@ use r3bl_core::{call_if_true,
                 UnicodeString};
 use r3bl_macro::tui_style;
 use size_of::SizeOf as _;
-use tokio::sync::mpsc;
-
-use super::{BoxedSafeApp, Continuation, DefaultInputEventHandler, EventPropagation};
-use crate::{render_pipeline,
+use tokio::{sync::mpsc, time::Instant};
+
+use super::{is_suspend_key,
+            is_suspend_key_enabled,
+            suspend_process,
+            BoxedSafeApp,
+            Continuation,
+            DefaultInputEventHandler,
+            EventPropagation,
+            JobControlSignal,
+            JobControlSignals};
+use crate::{get_input_event_recording_mode,
+            lookup_size,
+            render_pipeline,
             telemetry_global_static,
+            ComponentRegistry,
             ComponentRegistryMap,
+            ContextMenuApplyResponse,
             Flush as _,
             FlushKind,
             GlobalData,
             HasFocus,
             InputDeviceExt,
             InputEvent,
+            InputEventPlayer,
+            InputEventRecorder,
+            InputEventRecordingMode,
             MinSize,
+            PaintTask,
             RawMode,
             RenderOp,
             RenderPipeline,
+            SettledResize,
+            ShortcutRegistry,
+            TerminalRestoreGuard,
             TerminalWindowMainThreadSignal,
             ZOrder,
             DEBUG_TUI_MOD};
@@ -96,6 +115,11 @@ where
         output_device_as_mut!(output_device),
         output_device.is_mock,
     );
+    // Restore the terminal if this returns early w/ an error.
+    let _terminal_restore_guard = TerminalRestoreGuard::new(output_device.clone());
+
+    // Paint the frames in the background, see [PaintTask].
+    global_data_ref.maybe_paint_task = Some(PaintTask::start(output_device.clone()));
 
     let app = &mut app;
 
@@ -105,22 +129,54 @@ where
     // 2. The methods provided allow components to be added to the map.
     let component_registry_map = &mut ComponentRegistryMap::default();
     let has_focus = &mut HasFocus::default();
+    let mut job_control_signals = JobControlSignals::new(output_device.is_mock);
+
+    // Record or replay the input events, see [set_input_event_recording_mode].
+    let mut maybe_input_event_recorder = None;
+    let mut input_event_player = InputEventPlayer::default();
+    match get_input_event_recording_mode() {
+        Some(InputEventRecordingMode::Record(path)) => {
+            maybe_input_event_recorder = Some(InputEventRecorder::try_new(path)?);
+        }
+        Some(InputEventRecordingMode::Playback(path)) => {
+            input_event_player = InputEventPlayer::try_load(path)?;
+        }
+        None => {}
+    }
 
-    // Init the app, and perform first render.
+    // Init the app, and perform first render (which might be its splash screen).
+    global_data_ref.maybe_splash_screen = app.app_start_splash_screen(global_data_ref);
     app.app_init(component_registry_map, has_focus);
+    reload_shortcuts(app, global_data_ref, component_registry_map);
     AppManager::render_app(
         app,
         global_data_ref,
         component_registry_map,
         has_focus,
-        output_device_as_mut!(output_device),
-        output_device.is_mock,
+        &output_device,
     )?;
 
     global_data_ref.dump_to_log("main_event_loop -> Startup 🚀");
 
     // Main event loop.
     loop {
+        // When the pending render (if any) is due, see [crate::RenderScheduler]. It isn't
+        // armed while the last frame is still being painted, since the render would just
+        // be deferred (w/out waiting), which spins the loop. It happens once the frame
+        // has been painted instead, see [PaintTask].
+        let is_painting = global_data_ref.is_painting();
+        let maybe_render_deadline = match is_painting {
+            true => None,
+            false => global_data_ref
+                .render_scheduler
+                .get_render_deadline(Instant::now()),
+        };
+        // When the pending resize (if any) has settled, see [crate::ResizeDebouncer].
+        let maybe_resize_deadline =
+            global_data_ref.resize_debouncer.get_resize_deadline();
+        // When the next tick (if any) is due, see [crate::TimerService].
+        let maybe_tick_deadline = global_data_ref.timers.get_tick_deadline();
+
         tokio::select! {
             // Handle signals on the channel.
             // This branch is cancel safe since recv is cancel safe.
@@ -128,7 +184,19 @@ where
                 if let Some(ref signal) = maybe_signal {
                     match signal {
                         TerminalWindowMainThreadSignal::Exit => {
+                            // Don't drop the last requested render.
+                            global_data_ref.wait_until_painted();
+                            if global_data_ref.render_scheduler.is_render_pending() {
+                                AppManager::render_app(
+                                    app,
+                                    global_data_ref,
+                                    component_registry_map,
+                                    has_focus,
+                                    &output_device,
+                                )?;
+                            }
                             // 🐒 Actually exit the main loop!
+                            global_data_ref.wait_until_painted();
                             RawMode::end(
                                 global_data_ref.window_size,
                                 output_device_as_mut!(output_device),
@@ -142,9 +210,35 @@ where
                                 global_data_ref,
                                 component_registry_map,
                                 has_focus,
-                                output_device_as_mut!(output_device),
-                                output_device.is_mock,
+                                &output_device,
+                            )?;
+                        },
+                        TerminalWindowMainThreadSignal::RequestRender(_) => {
+                            global_data_ref.render_scheduler.request_render();
+                        },
+                        TerminalWindowMainThreadSignal::Resized { old, new } => {
+                            handle_resize(
+                                *old,
+                                *new,
+                                global_data_ref,
+                                app,
+                                &exit_keys,
+                                component_registry_map,
+                                has_focus,
+                                &output_device,
+                            )?;
+                        },
+                        TerminalWindowMainThreadSignal::Suspend => {
+                            global_data_ref.wait_until_painted();
+                            handle_suspend(
+                                global_data_ref,
+                                app,
+                                &exit_keys,
+                                component_registry_map,
+                                has_focus,
+                                &output_device,
                             )?;
+                            job_control_signals.skip_next_resume();
                         },
                         TerminalWindowMainThreadSignal::ApplyAction(action) => {
                             let result = app.app_handle_signal(action, global_data_ref, component_registry_map, has_focus);
@@ -152,18 +246,109 @@ where
                                 result,
                                 None,
                                 &exit_keys,
-                                app,
                                 global_data_ref,
-                                component_registry_map,
-                                has_focus,
-                                output_device_as_mut!(output_device),
-                                output_device.is_mock,
                             );
                         },
                     }
                 }
             }
 
+            // Render the app once the pending render is due. This coalesces all the
+            // render requests that came in since the last render.
+            // This branch is cancel safe since sleep_until is cancel safe.
+            _ = tokio::time::sleep_until(
+                maybe_render_deadline.unwrap_or_else(Instant::now)
+            ), if maybe_render_deadline.is_some() => {
+                AppManager::render_app(
+                    app,
+                    global_data_ref,
+                    component_registry_map,
+                    has_focus,
+                    &output_device,
+                )?;
+            }
+
+            // Wake up once the last frame has been painted, so that the render deadline is
+            // armed again (if a render is pending).
+            // This branch is cancel safe since wait_until_painted_async is cancel safe.
+            _ = global_data_ref.wait_until_painted_async(), if is_painting => {}
+
+            // Deliver the pending resize once the terminal has stopped changing size.
+            // This branch is cancel safe since sleep_until is cancel safe.
+            _ = tokio::time::sleep_until(
+                maybe_resize_deadline.unwrap_or_else(Instant::now)
+            ), if maybe_resize_deadline.is_some() => {
+                match global_data_ref.resize_debouncer.take_pending_resize() {
+                    Some(SettledResize::Resized { old_size, new_size }) => {
+                        send_resized_signal(
+                            global_data_ref.main_thread_channel_sender.clone(),
+                            old_size,
+                            new_size,
+                        );
+                    }
+                    Some(SettledResize::Repaint) => request_full_repaint(global_data_ref),
+                    None => {}
+                }
+            }
+
+            // Tick the components whose tick is due, see [crate::TimerService].
+            // This branch is cancel safe since sleep_until is cancel safe.
+            _ = tokio::time::sleep_until(
+                maybe_tick_deadline.unwrap_or_else(Instant::now)
+            ), if maybe_tick_deadline.is_some() => {
+                handle_ticks(
+                    global_data_ref,
+                    &exit_keys,
+                    component_registry_map,
+                    has_focus,
+                );
+            }
+
+            // Handle SIGTSTP & SIGCONT, see [JobControlSignals].
+            // This branch is cancel safe since recv is cancel safe.
+            job_control_signal = job_control_signals.recv() => {
+                match job_control_signal {
+                    JobControlSignal::Suspend => {
+                        global_data_ref.wait_until_painted();
+                        handle_suspend(
+                            global_data_ref,
+                            app,
+                            &exit_keys,
+                            component_registry_map,
+                            has_focus,
+                            &output_device,
+                        )?;
+                        job_control_signals.skip_next_resume();
+                    }
+                    JobControlSignal::Resume => {
+                        // Already painted by handle_suspend, if it suspended the process.
+                        if !job_control_signals.take_is_next_resume_skipped() {
+                            handle_resume(
+                                global_data_ref,
+                                app,
+                                &exit_keys,
+                                component_registry_map,
+                                has_focus,
+                                &output_device,
+                            )?;
+                        }
+                    }
+                }
+            }
+
+            // Replay the recorded input events, see [InputEventRecordingMode::Playback].
+            // This branch is cancel safe since next_input_event is cancel safe.
+            input_event = input_event_player.next_input_event() => {
+                handle_input_event(
+                    global_data_ref,
+                    app,
+                    input_event,
+                    &exit_keys,
+                    component_registry_map,
+                    has_focus,
+                );
+            }
+
             // Handle input event.
             // This branch is cancel safe because no state is declared inside the
             // future in the following block.
@@ -172,36 +357,53 @@ where
             //   pinned_input_stream isn't used and the state isn't modified.
             maybe_input_event = input_device.next_input_event() => {
                 if let Some(input_event) = maybe_input_event {
-                    telemetry_global_static::set_start_ts();
-
-                    call_if_true!(DEBUG_TUI_MOD, {
-                        if let InputEvent::Keyboard(_)= input_event {
-                            tracing::info!("main_event_loop -> Tick: ⏰ {input_event}");
+                    if let Some(ref mut recorder) = maybe_input_event_recorder {
+                        if let Err(error) = recorder.record(&input_event) {
+                            tracing::error!("main_event_loop -> record input event. Error: {error}");
                         }
-                    });
-
-                    handle_resize_if_applicable(input_event,
-                        global_data_ref, app,
-                        component_registry_map,
-                        has_focus,
-                        output_device_as_mut!(output_device),
-                        output_device.is_mock,
-                    );
-
-                    actually_process_input_event(
+                    }
+                    handle_input_event(
                         global_data_ref,
                         app,
                         input_event,
                         &exit_keys,
                         component_registry_map,
                         has_focus,
-                        output_device_as_mut!(output_device),
-                        output_device.is_mock,
                     );
                 } else {
                     // There are no events in the stream, so exit. This happens in test
                     // environments with InputDevice::new_mock_with_delay() or
                     // InputDevice::new_mock().
+                    global_data_ref.wait_until_painted();
+                    match global_data_ref.resize_debouncer.take_pending_resize() {
+                        Some(SettledResize::Resized { old_size, new_size }) => {
+                            handle_resize(
+                                old_size,
+                                new_size,
+                                global_data_ref,
+                                app,
+                                &exit_keys,
+                                component_registry_map,
+                                has_focus,
+                                &output_device,
+                            )?;
+                            global_data_ref.wait_until_painted();
+                        }
+                        Some(SettledResize::Repaint) => {
+                            request_full_repaint(global_data_ref)
+                        }
+                        None => {}
+                    }
+                    if global_data_ref.render_scheduler.is_render_pending() {
+                        AppManager::render_app(
+                            app,
+                            global_data_ref,
+                            component_registry_map,
+                            has_focus,
+                            &output_device,
+                        )?;
+                    }
+                    global_data_ref.wait_until_painted();
                     break;
                 }
             }
@@ -215,7 +417,51 @@ where
     ok!((global_data, input_device, output_device))
 }
 
-#[allow(clippy::too_many_arguments)]
+fn handle_input_event<S, AS>(
+    global_data: &mut GlobalData<S, AS>,
+    app: &mut BoxedSafeApp<S, AS>,
+    input_event: InputEvent,
+    exit_keys: &[InputEvent],
+    component_registry_map: &mut ComponentRegistryMap<S, AS>,
+    has_focus: &mut HasFocus,
+) where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    telemetry_global_static::set_start_ts();
+
+    call_if_true!(DEBUG_TUI_MOD, {
+        if let InputEvent::Keyboard(_) = input_event {
+            tracing::info!("main_event_loop -> Tick: ⏰ {input_event}");
+        }
+    });
+
+    if let InputEvent::Resize(new_size) = input_event {
+        // The app is told about it once the resize events have settled, see
+        // [crate::ResizeDebouncer].
+        global_data.resize_debouncer.on_resize(
+            global_data.window_size,
+            new_size,
+            Instant::now(),
+        );
+    } else {
+        actually_process_input_event(
+            global_data,
+            app,
+            input_event,
+            exit_keys,
+            component_registry_map,
+            has_focus,
+        );
+    }
+
+    // The text that was copied w/ OSC 52 is painted w/ the next frame, see
+    // [crate::Osc52Clipboard].
+    if global_data.has_pending_clipboard_contents() {
+        global_data.render_scheduler.request_render();
+    }
+}
+
 fn actually_process_input_event<S, AS>(
     global_data: &mut GlobalData<S, AS>,
     app: &mut BoxedSafeApp<S, AS>,
@@ -223,71 +469,318 @@ fn actually_process_input_event<S, AS>(
     exit_keys: &[InputEvent],
     component_registry_map: &mut ComponentRegistryMap<S, AS>,
     has_focus: &mut HasFocus,
-    locked_output_device: LockedOutputDevice<'_>,
-    is_mock: bool,
 ) where
     S: Debug + Default + Clone + Sync + Send,
     AS: Debug + Default + Clone + Sync + Send + 'static,
 {
-    let result = app.app_handle_input_event(
-        input_event,
+    // The app doesn't get any input events (other than the exit keys) until it replaces
+    // the splash screen, see [crate::SplashScreen].
+    if global_data.is_splash_screen_shown() {
+        handle_result_generated_by_app_after_handling_action_or_input_event(
+            Ok(EventPropagation::Propagate),
+            Some(input_event),
+            exit_keys,
+            global_data,
+        );
+        return;
+    }
+
+    // The help overlay gets all the input events while it is shown, see
+    // [crate::ShortcutRegistry::handle_input_event].
+    if let Some(event_propagation) = global_data
+        .shortcuts
+        .handle_input_event(&input_event, global_data.window_size)
+    {
+        handle_result_generated_by_app_after_handling_action_or_input_event(
+            Ok(event_propagation),
+            None,
+            exit_keys,
+            global_data,
+        );
+        return;
+    }
+
+    // The context menu gets all the input events while it is open, and the action of the
+    // item that is chosen is dispatched, see [crate::ContextMenu::apply_event].
+    let maybe_event_propagation =
+        match global_data.context_menu.apply_event(input_event.clone()) {
+            ContextMenuApplyResponse::Chosen(action) => {
+                send_signal!(
+                    global_data.main_thread_channel_sender,
+                    TerminalWindowMainThreadSignal::ApplyAction(action)
+                );
+                Some(EventPropagation::ConsumedRender)
+            }
+            ContextMenuApplyResponse::Updated | ContextMenuApplyResponse::Closed => {
+                Some(EventPropagation::ConsumedRender)
+            }
+            ContextMenuApplyResponse::Consumed => Some(EventPropagation::Consumed),
+            ContextMenuApplyResponse::Noop => None,
+        };
+    if let Some(event_propagation) = maybe_event_propagation {
+        handle_result_generated_by_app_after_handling_action_or_input_event(
+            Ok(event_propagation),
+            None,
+            exit_keys,
+            global_data,
+        );
+        return;
+    }
+
+    // The key press of a toast's action is handled before the app gets it, w/out
+    // moving the focus, see [crate::ToastManager::handle_input_event].
+    if let Some(action) = global_data.toasts.handle_input_event(&input_event) {
+        send_signal!(
+            global_data.main_thread_channel_sender,
+            TerminalWindowMainThreadSignal::ApplyAction(action)
+        );
+        global_data.render_scheduler.request_render();
+        return;
+    }
+
+    let mut result = app.app_handle_input_event(
+        input_event.clone(),
         global_data,
         component_registry_map,
         has_focus,
     );
 
+    // Tab & Shift+Tab move the focus between the focusable components, unless the app
+    // consumed them, see [ComponentRegistry::try_move_focus].
+    if let Ok(EventPropagation::Propagate) = result {
+        result = ComponentRegistry::try_move_focus(
+            global_data,
+            &input_event,
+            component_registry_map,
+            has_focus,
+        );
+    }
+
+    // A right click (or Shift+F10) opens the context menu of a component, unless the app
+    // consumed it, see [ComponentRegistry::try_open_context_menu].
+    if let Ok(EventPropagation::Propagate) = result {
+        result = ComponentRegistry::try_open_context_menu(
+            global_data,
+            &input_event,
+            component_registry_map,
+            has_focus,
+        );
+    }
+
+    // "?" shows the help overlay, unless the app consumed it, see
+    // [crate::ShortcutRegistry].
+    if let Ok(EventPropagation::Propagate) = result {
+        if ShortcutRegistry::is_help_overlay_key(&input_event) {
+            reload_shortcuts(app, global_data, component_registry_map);
+            global_data.shortcuts.show_help_overlay();
+            result = Ok(EventPropagation::ConsumedRender);
+        }
+    }
+
+    // Ctrl+Z suspends the app (if enabled), unless the app consumed it, see
+    // [is_suspend_key].
+    if let Ok(EventPropagation::Propagate) = result {
+        if is_suspend_key_enabled() && is_suspend_key(&input_event) {
+            send_signal!(
+                global_data.main_thread_channel_sender,
+                TerminalWindowMainThreadSignal::Suspend
+            );
+            result = Ok(EventPropagation::Consumed);
+        }
+    }
+
     handle_result_generated_by_app_after_handling_action_or_input_event(
         result,
         Some(input_event),
         exit_keys,
-        app,
         global_data,
+    );
+}
+
+/// Call [crate::Component::handle_tick] on each of the components whose tick is due. The
+/// subscriptions of the components that aren't in the `component_registry_map` anymore
+/// are dropped.
+fn handle_ticks<S, AS>(
+    global_data: &mut GlobalData<S, AS>,
+    exit_keys: &[InputEvent],
+    component_registry_map: &mut ComponentRegistryMap<S, AS>,
+    has_focus: &mut HasFocus,
+) where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    for id in global_data.timers.take_due_ticks(Instant::now()) {
+        let Some(component) =
+            ComponentRegistry::try_to_get_component_by_id(component_registry_map, id)
+        else {
+            global_data.timers.unsubscribe(id);
+            continue;
+        };
+        let result = component.handle_tick(global_data, has_focus);
+        handle_result_generated_by_app_after_handling_action_or_input_event(
+            result,
+            None,
+            exit_keys,
+            global_data,
+        );
+    }
+}
+
+/// Load the shortcuts that the app & its components declare (the components might have
+/// changed since they were loaded), and log the ones that conflict.
+fn reload_shortcuts<S, AS>(
+    app: &mut BoxedSafeApp<S, AS>,
+    global_data: &mut GlobalData<S, AS>,
+    component_registry_map: &mut ComponentRegistryMap<S, AS>,
+) where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    if let Err(error) = global_data
+        .shortcuts
+        .try_reload(app.app_get_shortcuts(), component_registry_map)
+    {
+        tracing::error!("main_event_loop -> reload shortcuts. Error: {error}");
+    }
+}
+
+/// Handle a [TerminalWindowMainThreadSignal::Suspend] (or `SIGTSTP`). The terminal is
+/// restored (raw mode is exited) before the process is suspended, and once it is
+/// resumed, raw mode is entered again and the app is painted from scratch (see
+/// [handle_resume]). Call [GlobalData::wait_until_painted] before this, so that the last
+/// frame isn't painted after raw mode is exited.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_suspend<S, AS>(
+    global_data: &mut GlobalData<S, AS>,
+    app: &mut BoxedSafeApp<S, AS>,
+    exit_keys: &[InputEvent],
+    component_registry_map: &mut ComponentRegistryMap<S, AS>,
+    has_focus: &mut HasFocus,
+    output_device: &OutputDevice,
+) -> CommonResult<()>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    RawMode::end(
+        global_data.window_size,
+        output_device_as_mut!(output_device),
+        output_device.is_mock,
+    );
+    if !output_device.is_mock {
+        // This returns once the process is resumed.
+        suspend_process();
+    }
+    RawMode::start(
+        global_data.window_size,
+        output_device_as_mut!(output_device),
+        output_device.is_mock,
+    );
+
+    handle_resume(
+        global_data,
+        app,
+        exit_keys,
         component_registry_map,
         has_focus,
-        locked_output_device,
-        is_mock,
-    );
+        output_device,
+    )
 }
 
-/// Before any app gets to process the `input_event`, perform special handling in case
-/// it is a resize event.
-pub fn handle_resize_if_applicable<S, AS>(
-    input_event: InputEvent,
+/// Paint the app from scratch after the process is resumed (eg: w/ `fg`), since the
+/// screen was used by something else while it was suspended. The terminal might have
+/// been resized in the meantime, in which case the app is told about it (see
+/// [handle_resize]).
+#[allow(clippy::too_many_arguments)]
+pub fn handle_resume<S, AS>(
     global_data: &mut GlobalData<S, AS>,
     app: &mut BoxedSafeApp<S, AS>,
+    exit_keys: &[InputEvent],
     component_registry_map: &mut ComponentRegistryMap<S, AS>,
     has_focus: &mut HasFocus,
-    locked_output_device: LockedOutputDevice<'_>,
-    is_mock: bool,
-) where
+    output_device: &OutputDevice,
+) -> CommonResult<()>
+where
     S: Debug + Default + Clone + Sync + Send,
-    AS: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send + 'static,
 {
-    if let InputEvent::Resize(new_size) = input_event {
-        global_data.set_size(new_size);
-        global_data.maybe_saved_offscreen_buffer = None;
-        let _ = AppManager::render_app(
-            app,
+    let old_size = global_data.window_size;
+    let new_size = match output_device.is_mock {
+        true => old_size,
+        false => lookup_size().unwrap_or(old_size),
+    };
+    if new_size != old_size {
+        return handle_resize(
+            old_size,
+            new_size,
             global_data,
+            app,
+            exit_keys,
             component_registry_map,
             has_focus,
-            locked_output_device,
-            is_mock,
+            output_device,
         );
     }
+
+    global_data.maybe_saved_offscreen_buffer = None;
+    AppManager::render_app(
+        app,
+        global_data,
+        component_registry_map,
+        has_focus,
+        output_device,
+    )
 }
 
+/// Handle a [TerminalWindowMainThreadSignal::Resized]. The saved offscreen buffer is
+/// thrown away (since it is the wrong size), the app is told about the resize (in
+/// [crate::App::app_handle_resize]), and then it is rendered at the `new_size`.
 #[allow(clippy::too_many_arguments)]
+pub fn handle_resize<S, AS>(
+    old_size: Size,
+    new_size: Size,
+    global_data: &mut GlobalData<S, AS>,
+    app: &mut BoxedSafeApp<S, AS>,
+    exit_keys: &[InputEvent],
+    component_registry_map: &mut ComponentRegistryMap<S, AS>,
+    has_focus: &mut HasFocus,
+    output_device: &OutputDevice,
+) -> CommonResult<()>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    global_data.set_size(new_size);
+    global_data.maybe_saved_offscreen_buffer = None;
+
+    let result = app.app_handle_resize(
+        old_size,
+        new_size,
+        global_data,
+        component_registry_map,
+        has_focus,
+    );
+    handle_result_generated_by_app_after_handling_action_or_input_event(
+        result,
+        None,
+        exit_keys,
+        global_data,
+    );
+
+    AppManager::render_app(
+        app,
+        global_data,
+        component_registry_map,
+        has_focus,
+        output_device,
+    )
+}
+
 fn handle_result_generated_by_app_after_handling_action_or_input_event<S, AS>(
     result: CommonResult<EventPropagation>,
     maybe_input_event: Option<InputEvent>,
     exit_keys: &[InputEvent],
-    app: &mut BoxedSafeApp<S, AS>,
     global_data: &mut GlobalData<S, AS>,
-    component_registry_map: &mut ComponentRegistryMap<S, AS>,
-    has_focus: &mut HasFocus,
-    locked_output_device: LockedOutputDevice<'_>,
-    is_mock: bool,
 ) where
     S: Debug + Default + Clone + Sync + Send,
     AS: Debug + Default + Clone + Sync + Send + 'static,
@@ -299,7 +792,7 @@ fn handle_result_generated_by_app_after_handling_action_or_input_event<S, AS>(
             EventPropagation::Propagate => {
                 if let Some(input_event) = maybe_input_event {
                     let check_if_exit_keys_pressed =
-                        DefaultInputEventHandler::no_consume(input_event, exit_keys);
+                        DefaultInputEventHandler::no_consume(&input_event, exit_keys);
                     if let Continuation::Exit = check_if_exit_keys_pressed {
                         request_exit_by_sending_signal(main_thread_channel_sender);
                     };
@@ -307,14 +800,8 @@ fn handle_result_generated_by_app_after_handling_action_or_input_event<S, AS>(
             }
 
             EventPropagation::ConsumedRender => {
-                let _ = AppManager::render_app(
-                    app,
-                    global_data,
-                    component_registry_map,
-                    has_focus,
-                    locked_output_device,
-                    is_mock,
-                );
+                // Coalesced w/ any other render requests in this frame.
+                global_data.render_scheduler.request_render();
             }
 
             EventPropagation::Consumed => {}
@@ -344,6 +831,37 @@ fn request_exit_by_sending_signal<AS>(
     });
 }
 
+/// Paint the whole app in the next render, eg: after the terminal was resized & then
+/// went back to the size it was before, see [SettledResize::Repaint]. The app isn't laid
+/// out again, since the size didn't change.
+fn request_full_repaint<S, AS>(global_data: &mut GlobalData<S, AS>)
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    global_data.maybe_saved_offscreen_buffer = None;
+    global_data.render_scheduler.request_render();
+}
+
+fn send_resized_signal<AS>(
+    channel_sender: mpsc::Sender<TerminalWindowMainThreadSignal<AS>>,
+    old_size: Size,
+    new_size: Size,
+) where
+    AS: Debug + Default + Clone + Sync + Send + 'static,
+{
+    // Sent on the channel (instead of being handled right away), so that it is handled
+    // in order w/ the signals that are already in it.
+    tokio::spawn(async move {
+        let _ = channel_sender
+            .send(TerminalWindowMainThreadSignal::Resized {
+                old: old_size,
+                new: new_size,
+            })
+            .await;
+    });
+}
+
 struct AppManager<S, AS>
 where
     S: Debug + Default + Clone + Sync + Send,
@@ -362,27 +880,49 @@ where
         global_data: &mut GlobalData<S, AS>,
         component_registry_map: &mut ComponentRegistryMap<S, AS>,
         has_focus: &mut HasFocus,
-        locked_output_device: LockedOutputDevice<'_>,
-        is_mock: bool,
+        output_device: &OutputDevice,
     ) -> CommonResult<()> {
         throws!({
+            // The last frame is still being painted (eg: the terminal is slow), so this
+            // render happens once it is done, w/ the latest state, see [PaintTask]. This
+            // is checked before the output device is locked, since the paint thread holds
+            // the lock while it paints.
+            if global_data.is_painting() {
+                global_data.render_scheduler.defer_render();
+                return Ok(());
+            }
+
             let window_size = global_data.window_size;
 
+            // The splash screen is replaced by the app once its startup work is done.
+            if !global_data.is_splash_screen_shown() {
+                global_data.maybe_splash_screen = None;
+            }
+
             // Check to see if the window_size is large enough to render.
-            let render_result =
-                match window_size.fits_min_size(MinSize::Col as u8, MinSize::Row as u8) {
-                    TooSmallToDisplayResult::IsLargeEnough => {
-                        app.app_render(global_data, component_registry_map, has_focus)
-                    }
-                    TooSmallToDisplayResult::IsTooSmall => {
-                        global_data.maybe_saved_offscreen_buffer = None;
-                        Ok(render_window_too_small_error(window_size))
+            let render_result = match window_size
+                .fits_min_size(MinSize::Col as u8, MinSize::Row as u8)
+            {
+                TooSmallToDisplayResult::IsLargeEnough => {
+                    match global_data.maybe_splash_screen {
+                        Some(ref splash_screen) => Ok(splash_screen.render(
+                            window_size,
+                            splash_screen.get_tick(std::time::Instant::now()),
+                        )),
+                        None => {
+                            app.app_render(global_data, component_registry_map, has_focus)
+                        }
                     }
-                };
+                }
+                TooSmallToDisplayResult::IsTooSmall => {
+                    global_data.maybe_saved_offscreen_buffer = None;
+                    Ok(render_window_too_small_error(window_size))
+                }
+            };
 
             match render_result {
                 Err(error) => {
-                    RenderOp::default().flush(locked_output_device);
+                    RenderOp::default().flush(output_device_as_mut!(output_device));
 
                     telemetry_global_static::set_end_ts();
 
@@ -390,12 +930,19 @@ where
                         tracing::error!("MySubscriber::render() error ❌: {error}");
                     });
                 }
-                Ok(render_pipeline) => {
+                Ok(mut render_pipeline) => {
+                    // The toasts (and the context menu & the help overlay) are painted
+                    // above the app.
+                    render_pipeline += global_data.toasts.render(window_size);
+                    render_pipeline += global_data.context_menu.render(window_size);
+                    render_pipeline +=
+                        global_data.shortcuts.render(window_size, has_focus);
+
                     render_pipeline.paint(
                         FlushKind::ClearBeforeFlush,
                         global_data,
-                        locked_output_device,
-                        is_mock,
+                        output_device_as_mut!(output_device),
+                        output_device.is_mock,
                     );
 
                     telemetry_global_static::set_end_ts();
@@ -423,6 +970,8 @@ where
                     });
                 }
             }
+
+            global_data.render_scheduler.on_render(Instant::now());
         });
     }
 }
@@ -474,6 +1023,8 @@ fn render_window_too_small_error(window_size: Size) -> RenderPipeline {
 #[cfg(test)]
 mod tests {
     use std::{fmt::{Display, Formatter},
+              io::Write,
+              sync::{Arc, Mutex as StdMutex},
               time::Duration};
 
     use position::Position;
@@ -504,7 +1055,9 @@ mod tests {
                     UnicodeString,
                     DEFAULT_GRADIENT_STOPS};
     use r3bl_macro::tui_style;
-    use r3bl_test_fixtures::{output_device_ext::OutputDeviceExt as _, InputDeviceExt};
+    use r3bl_test_fixtures::{output_device_ext::OutputDeviceExt as _,
+                             InputDeviceExt,
+                             StdoutMock};
     use size::Size;
     use state::{AppSignal, State};
 
@@ -604,10 +1157,8 @@ mod tests {
         if let TTYResult::IsNotInteractive = is_fully_uninteractive_terminal() {
             // Check pixel char at 4 x 7.
             {
-                let PixelChar::PlainText {
-                    content,
-                    maybe_style: _,
-                } = my_offscreen_buffer.buffer[4][7].clone()
+                let PixelChar::PlainText { content, .. } =
+                    my_offscreen_buffer.buffer[4][7].clone()
                 else {
                     panic!(
                         "Expected PixelChar::PlainText, got: {:?}",
@@ -619,10 +1170,8 @@ mod tests {
 
             // Check pixel char at 10 x 7.
             {
-                let PixelChar::PlainText {
-                    content,
-                    maybe_style: _,
-                } = my_offscreen_buffer.buffer[10][7].clone()
+                let PixelChar::PlainText { content, .. } =
+                    my_offscreen_buffer.buffer[10][7].clone()
                 else {
                     panic!(
                         "Expected PixelChar::PlainText, got: {:?}",
@@ -643,6 +1192,7 @@ mod tests {
                             color_fg: Some(color!(102, 0, 255)),
                             ..Default::default()
                         }),
+                        maybe_hyperlink: None,
                     },
                     my_offscreen_buffer.buffer[4][7].clone()
                 );
@@ -658,6 +1208,7 @@ mod tests {
                             dim: true,
                             ..Default::default()
                         }),
+                        maybe_hyperlink: None,
                     },
                     my_offscreen_buffer.buffer[10][7].clone()
                 );
@@ -667,6 +1218,89 @@ mod tests {
         ok!()
     }
 
+    /// Writes to a [StdoutMock], but each flush takes `delay`, like a terminal over a
+    /// high latency SSH connection.
+    struct SlowStdoutMock {
+        stdout_mock: StdoutMock,
+        delay: Duration,
+    }
+
+    impl Write for SlowStdoutMock {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.stdout_mock.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            std::thread::sleep(self.delay);
+            self.stdout_mock.flush()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_main_event_loop_impl_w_slow_output_device() -> CommonResult<()> {
+        let flush_delay = Duration::from_millis(50);
+        let input_event_count = 20;
+
+        // Simulated key inputs, which come in a lot faster than the frames are painted.
+        let generator_vec: Vec<CrosstermEventResult> = (0..input_event_count)
+            .map(|_| {
+                Ok(crossterm::event::Event::Key(
+                    crossterm::event::KeyEvent::new(
+                        crossterm::event::KeyCode::Up,
+                        crossterm::event::KeyModifiers::empty(),
+                    ),
+                ))
+            })
+            .collect();
+        let input_device =
+            InputDevice::new_mock_with_delay(generator_vec, Duration::from_millis(5));
+
+        let stdout_mock = StdoutMock::default();
+        let output_device = OutputDevice {
+            resource: Arc::new(StdMutex::new(SlowStdoutMock {
+                stdout_mock: stdout_mock.clone(),
+                delay: flush_delay,
+            })),
+            is_mock: true,
+        };
+
+        let (global_data, _, _) = main_event_loop_impl(
+            Box::<AppMain>::default(),
+            vec![],
+            State::default(),
+            size!(col_count: 65, row_count: 11),
+            input_device,
+            output_device,
+        )
+        .await?;
+
+        // The input events are handled while the frames are painted, instead of waiting
+        // for each one to be painted, so they are painted in fewer frames.
+        assert_eq!(global_data.state.counter, input_event_count);
+        let painted_frame_count = global_data
+            .maybe_paint_task
+            .as_ref()
+            .unwrap()
+            .get_painted_frame_count();
+        assert!(painted_frame_count > 0);
+        assert!(
+            painted_frame_count < input_event_count as usize,
+            "painted_frame_count: {painted_frame_count}"
+        );
+        assert!(!stdout_mock.get_copy_of_buffer().is_empty());
+
+        // The renders aren't attempted (& deferred) over & over while the frames are
+        // painted, which would spin the main event loop.
+        let deferred_render_count =
+            global_data.render_scheduler.get_deferred_render_count();
+        assert!(
+            deferred_render_count <= painted_frame_count,
+            "deferred_render_count: {deferred_render_count}"
+        );
+
+        ok!()
+    }
+
     mod state {
         use super::*;
 
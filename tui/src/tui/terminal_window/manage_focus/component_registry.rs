@@ -20,7 +20,12 @@ use std::{collections::HashMap, fmt::Debug, marker::PhantomData};
 use r3bl_core::{CommonResult, ContainsResult};
 
 use super::HasFocus;
-use crate::{BoxedSafeComponent, EventPropagation, FlexBoxId, GlobalData, InputEvent};
+use crate::{BoxedSafeComponent,
+            EventPropagation,
+            FlexBoxId,
+            GlobalData,
+            InputEvent,
+            MouseInput};
 
 #[derive(Debug)]
 pub struct ComponentRegistry<S, AS>
@@ -127,4 +132,67 @@ where
             Ok(EventPropagation::Propagate)
         }
     }
+
+    /// Route the `input_event` to the component w/ the given `id`, whether it has focus
+    /// or not. This is useful for components that handle keys on behalf of the focused
+    /// one (eg: [crate::EditorTabBarComponent] handles <kbd>Ctrl+Tab</kbd> for the
+    /// editor).
+    pub fn route_event_to_component(
+        id: FlexBoxId,
+        global_data: &mut GlobalData<S, AS>,
+        input_event: InputEvent,
+        component_registry_map: &mut ComponentRegistryMap<S, AS>,
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        if let Some(component) =
+            ComponentRegistry::try_to_get_component_by_id(component_registry_map, id)
+        {
+            component.handle_event(global_data, input_event, has_focus)
+        } else {
+            // input_event not handled, propagate it.
+            Ok(EventPropagation::Propagate)
+        }
+    }
+
+    /// Route a mouse `input_event` to the component in the top most box (w/ a component)
+    /// under the pointer, using the boxes in [GlobalData::box_registry]. Other events
+    /// (and mouse events outside of all the components) are propagated. While a modal
+    /// is active, mouse events only go to the top most modal (see
+    /// [HasFocus::get_modal_ids]), wherever the pointer is.
+    pub fn route_mouse_event_to_component_at_pos(
+        global_data: &mut GlobalData<S, AS>,
+        input_event: InputEvent,
+        component_registry_map: &mut ComponentRegistryMap<S, AS>,
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        let InputEvent::Mouse(MouseInput { pos, .. }) = input_event else {
+            return Ok(EventPropagation::Propagate);
+        };
+
+        if has_focus.is_modal_set() {
+            return ComponentRegistry::route_event_to_focused_component(
+                global_data,
+                input_event,
+                component_registry_map,
+                has_focus,
+            );
+        }
+
+        let maybe_id = global_data
+            .box_registry
+            .get_boxes_at(pos)
+            .map(|it| it.id)
+            .find(|id| component_registry_map.contains_key(id));
+
+        match maybe_id {
+            Some(id) => ComponentRegistry::route_event_to_component(
+                id,
+                global_data,
+                input_event,
+                component_registry_map,
+                has_focus,
+            ),
+            None => Ok(EventPropagation::Propagate),
+        }
+    }
 }
@@ -0,0 +1,435 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::Debug;
+
+use r3bl_core::{throws, throws_with_return, CommonResult};
+
+use super::{ComponentRegistry, ComponentRegistryMap, HasFocus};
+use crate::{EventPropagation,
+            FlexBoxId,
+            FlexBoxRegistry,
+            GlobalData,
+            InputEvent,
+            Key,
+            KeyPress,
+            KeyState,
+            ModifierKeysMask,
+            SpecialKey};
+
+/// This is delivered to a component (in [crate::Component::handle_focus_change]) when it
+/// gains or loses keyboard focus by way of [ComponentRegistry::change_focus], so that it
+/// can restyle itself (eg: dim its cursor, or reset a transient selection).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FocusChange {
+    Gained,
+    Lost,
+}
+
+/// The direction in which [ComponentRegistry::try_move_focus] moves the keyboard focus
+/// through the focus order.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FocusDirection {
+    /// <kbd>Tab</kbd>.
+    Next,
+    /// <kbd>Shift+Tab</kbd>.
+    Previous,
+}
+
+impl FocusDirection {
+    /// Returns the direction for <kbd>Tab</kbd> & <kbd>Shift+Tab</kbd> (which some
+    /// terminals report as `BackTab` w/ the shift key pressed, and some w/out it).
+    pub fn from_input_event(input_event: &InputEvent) -> Option<Self> {
+        match input_event {
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(SpecialKey::Tab),
+            }) => Some(Self::Next),
+            InputEvent::Keyboard(
+                KeyPress::Plain {
+                    key: Key::SpecialKey(SpecialKey::BackTab),
+                }
+                | KeyPress::WithModifiers {
+                    key: Key::SpecialKey(SpecialKey::BackTab),
+                    mask:
+                        ModifierKeysMask {
+                            shift_key_state: KeyState::Pressed,
+                            ctrl_key_state: KeyState::NotPressed,
+                            alt_key_state: KeyState::NotPressed,
+                        },
+                },
+            ) => Some(Self::Previous),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the id that comes after (or before) `maybe_current_id` in the `focus_order`,
+/// wrapping around at either end. If there's no current id (or it isn't in the focus
+/// order) then the first (or last) id is returned.
+pub fn get_next_focus_id(
+    focus_order: &[FlexBoxId],
+    maybe_current_id: Option<FlexBoxId>,
+    direction: FocusDirection,
+) -> Option<FlexBoxId> {
+    let len = focus_order.len();
+    if len == 0 {
+        return None;
+    }
+
+    let maybe_current_index = maybe_current_id
+        .and_then(|current_id| focus_order.iter().position(|it| *it == current_id));
+
+    let next_index = match (maybe_current_index, direction) {
+        (None, FocusDirection::Next) => 0,
+        (None, FocusDirection::Previous) => len - 1,
+        (Some(index), FocusDirection::Next) => (index + 1) % len,
+        (Some(index), FocusDirection::Previous) => (index + len - 1) % len,
+    };
+
+    focus_order.get(next_index).copied()
+}
+
+impl<S, AS> ComponentRegistry<S, AS>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    /// Returns the ids of the focusable components (see
+    /// [crate::Component::is_focusable]) in layout order, ie: in the order that their
+    /// (visible) boxes were laid out in the last render (see [GlobalData::box_registry]).
+    /// If the app doesn't save the boxes, then the ids are sorted instead.
+    pub fn get_focus_order(
+        map: &ComponentRegistryMap<S, AS>,
+        box_registry: &FlexBoxRegistry,
+    ) -> Vec<FlexBoxId> {
+        let is_focusable =
+            |id: &FlexBoxId| map.get(id).is_some_and(|it| it.is_focusable());
+
+        if box_registry.is_empty() {
+            let mut focus_order: Vec<FlexBoxId> =
+                map.keys().copied().filter(is_focusable).collect();
+            focus_order.sort_by_key(|it| it.0);
+            return focus_order;
+        }
+
+        let mut focus_order: Vec<FlexBoxId> = vec![];
+        for flex_box in box_registry.get_boxes() {
+            if flex_box.visible
+                && is_focusable(&flex_box.id)
+                && !focus_order.contains(&flex_box.id)
+            {
+                focus_order.push(flex_box.id);
+            }
+        }
+        focus_order
+    }
+
+    /// Give keyboard focus to the component w/ the given `id`, and let the component
+    /// that had it (if any) & the one that gets it know about it, see
+    /// [crate::Component::handle_focus_change]. This does nothing if it already has
+    /// focus.
+    pub fn change_focus(
+        global_data: &mut GlobalData<S, AS>,
+        component_registry_map: &mut ComponentRegistryMap<S, AS>,
+        has_focus: &mut HasFocus,
+        id: FlexBoxId,
+    ) -> CommonResult<()> {
+        throws!({
+            let maybe_old_id = has_focus.get_id();
+            if maybe_old_id == Some(id) {
+                return Ok(());
+            }
+
+            has_focus.set_id(id);
+
+            if let Some(old_id) = maybe_old_id {
+                if let Some(component) = ComponentRegistry::try_to_get_component_by_id(
+                    component_registry_map,
+                    old_id,
+                ) {
+                    component.handle_focus_change(
+                        global_data,
+                        FocusChange::Lost,
+                        has_focus,
+                    )?;
+                }
+            }
+
+            if let Some(component) =
+                ComponentRegistry::try_to_get_component_by_id(component_registry_map, id)
+            {
+                component.handle_focus_change(
+                    global_data,
+                    FocusChange::Gained,
+                    has_focus,
+                )?;
+            }
+        });
+    }
+
+    /// Move the keyboard focus to the next (<kbd>Tab</kbd>) or previous
+    /// (<kbd>Shift+Tab</kbd>) focusable component, see
+    /// [ComponentRegistry::get_focus_order]. The main event loop calls this w/ the input
+    /// events that the app didn't consume, so components (eg: an editor) that handle
+    /// these keys themselves keep them.
+    ///
+    /// The `input_event` is propagated if it isn't one of these keys, if a modal has focus
+    /// (see [HasFocus::is_modal_set]), or if the focus doesn't move.
+    pub fn try_move_focus(
+        global_data: &mut GlobalData<S, AS>,
+        input_event: &InputEvent,
+        component_registry_map: &mut ComponentRegistryMap<S, AS>,
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        throws_with_return!({
+            let Some(direction) = FocusDirection::from_input_event(input_event) else {
+                return Ok(EventPropagation::Propagate);
+            };
+            if has_focus.is_modal_set() {
+                return Ok(EventPropagation::Propagate);
+            }
+
+            let focus_order = ComponentRegistry::get_focus_order(
+                component_registry_map,
+                &global_data.box_registry,
+            );
+            let maybe_current_id = has_focus.get_id();
+            match get_next_focus_id(&focus_order, maybe_current_id, direction) {
+                Some(next_id) if Some(next_id) != maybe_current_id => {
+                    ComponentRegistry::change_focus(
+                        global_data,
+                        component_registry_map,
+                        has_focus,
+                        next_id,
+                    )?;
+                    EventPropagation::ConsumedRender
+                }
+                _ => EventPropagation::Propagate,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use r3bl_core::{assert_eq2, position, size};
+
+    use super::*;
+    use crate::{keypress,
+                test_fixtures::mock_real_objects_for_editor,
+                Component,
+                FlexBox,
+                RenderPipeline,
+                SurfaceBounds};
+
+    type FocusChangeLog = Arc<Mutex<Vec<(FlexBoxId, FocusChange)>>>;
+
+    #[derive(Debug)]
+    struct TestComponent {
+        id: FlexBoxId,
+        is_focusable: bool,
+        focus_change_log: FocusChangeLog,
+    }
+
+    impl Component<(), ()> for TestComponent {
+        fn reset(&mut self) {}
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        fn render(
+            &mut self,
+            _global_data: &mut GlobalData<(), ()>,
+            _current_box: FlexBox,
+            _surface_bounds: SurfaceBounds,
+            _has_focus: &mut HasFocus,
+        ) -> CommonResult<RenderPipeline> {
+            Ok(RenderPipeline::default())
+        }
+
+        fn handle_event(
+            &mut self,
+            _global_data: &mut GlobalData<(), ()>,
+            _input_event: InputEvent,
+            _has_focus: &mut HasFocus,
+        ) -> CommonResult<EventPropagation> {
+            Ok(EventPropagation::Propagate)
+        }
+
+        fn is_focusable(&self) -> bool { self.is_focusable }
+
+        fn handle_focus_change(
+            &mut self,
+            _global_data: &mut GlobalData<(), ()>,
+            focus_change: FocusChange,
+            _has_focus: &mut HasFocus,
+        ) -> CommonResult<()> {
+            self.focus_change_log
+                .lock()
+                .unwrap()
+                .push((self.id, focus_change));
+            Ok(())
+        }
+    }
+
+    fn make_component_registry_map(
+        focus_change_log: &FocusChangeLog,
+    ) -> ComponentRegistryMap<(), ()> {
+        let mut map = ComponentRegistryMap::default();
+        for (id, is_focusable) in [(1, true), (2, false), (3, true), (4, true)] {
+            let component = TestComponent {
+                id: FlexBoxId::from(id),
+                is_focusable,
+                focus_change_log: focus_change_log.clone(),
+            };
+            ComponentRegistry::put(&mut map, FlexBoxId::from(id), Box::new(component));
+        }
+        map
+    }
+
+    /// The boxes are laid out right to left, so the layout order isn't the id order.
+    fn make_box_registry() -> FlexBoxRegistry {
+        let make_box = |id: u8, col_index: u16| FlexBox {
+            id: FlexBoxId::from(id),
+            origin_pos: position!(col_index: col_index, row_index: 0),
+            bounds_size: size!(col_count: 10, row_count: 10),
+            ..Default::default()
+        };
+        FlexBoxRegistry::new(vec![
+            make_box(0, 0),
+            make_box(4, 30),
+            make_box(3, 20),
+            make_box(2, 10),
+            make_box(1, 0),
+        ])
+    }
+
+    #[test]
+    fn test_get_next_focus_id() {
+        let order = [1, 3, 4].map(FlexBoxId::from);
+        let id = |it: u8| Some(FlexBoxId::from(it));
+
+        assert_eq2!(
+            get_next_focus_id(&order, id(1), FocusDirection::Next),
+            id(3)
+        );
+        assert_eq2!(
+            get_next_focus_id(&order, id(4), FocusDirection::Next),
+            id(1)
+        );
+        assert_eq2!(
+            get_next_focus_id(&order, id(1), FocusDirection::Previous),
+            id(4)
+        );
+        assert_eq2!(get_next_focus_id(&order, None, FocusDirection::Next), id(1));
+        assert_eq2!(
+            get_next_focus_id(&order, id(9), FocusDirection::Previous),
+            id(4)
+        );
+        assert_eq2!(get_next_focus_id(&[], id(1), FocusDirection::Next), None);
+    }
+
+    #[test]
+    fn test_get_focus_order() {
+        let focus_change_log = FocusChangeLog::default();
+        let map = make_component_registry_map(&focus_change_log);
+
+        // Layout order, w/out the components that aren't focusable.
+        assert_eq2!(
+            ComponentRegistry::get_focus_order(&map, &make_box_registry()),
+            [4, 3, 1].map(FlexBoxId::from).to_vec()
+        );
+
+        // Id order, when there are no boxes.
+        assert_eq2!(
+            ComponentRegistry::get_focus_order(&map, &FlexBoxRegistry::default()),
+            [1, 3, 4].map(FlexBoxId::from).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_try_move_focus() -> CommonResult<()> {
+        throws!({
+            let focus_change_log = FocusChangeLog::default();
+            let map = &mut make_component_registry_map(&focus_change_log);
+            let (mut global_data, _) =
+                mock_real_objects_for_editor::make_global_data::<(), ()>(None);
+            global_data.box_registry = make_box_registry();
+            let has_focus = &mut HasFocus::default();
+            has_focus.set_id(FlexBoxId::from(4));
+
+            let tab = InputEvent::Keyboard(keypress!(@special SpecialKey::Tab));
+            let shift_tab = InputEvent::Keyboard(keypress!(@special SpecialKey::BackTab));
+
+            let event_propagation = ComponentRegistry::try_move_focus(
+                &mut global_data,
+                &tab,
+                map,
+                has_focus,
+            )?;
+            assert_eq2!(event_propagation, EventPropagation::ConsumedRender);
+            assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(3)));
+            assert_eq2!(
+                *focus_change_log.lock().unwrap(),
+                vec![
+                    (FlexBoxId::from(4), FocusChange::Lost),
+                    (FlexBoxId::from(3), FocusChange::Gained),
+                ]
+            );
+
+            // Wrap around from the first to the last one.
+            ComponentRegistry::try_move_focus(&mut global_data, &tab, map, has_focus)?;
+            ComponentRegistry::try_move_focus(&mut global_data, &tab, map, has_focus)?;
+            assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(4)));
+            ComponentRegistry::try_move_focus(
+                &mut global_data,
+                &shift_tab,
+                map,
+                has_focus,
+            )?;
+            assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+
+            // Other keys are propagated.
+            let enter = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
+            assert_eq2!(
+                ComponentRegistry::try_move_focus(
+                    &mut global_data,
+                    &enter,
+                    map,
+                    has_focus
+                )?,
+                EventPropagation::Propagate
+            );
+
+            // The focus doesn't move while a modal has it.
+            has_focus.try_set_modal_id(FlexBoxId::from(9))?;
+            focus_change_log.lock().unwrap().clear();
+            assert_eq2!(
+                ComponentRegistry::try_move_focus(
+                    &mut global_data,
+                    &tab,
+                    map,
+                    has_focus
+                )?,
+                EventPropagation::Propagate
+            );
+            assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(9)));
+            assert!(focus_change_log.lock().unwrap().is_empty());
+        });
+    }
+}
@@ -28,21 +28,26 @@ use crate::{FlexBox, FlexBoxId};
 /// 1. Non modal focus - This is just a single `id` that is stored. To change focus a new
 ///    `id` is set in its place. Internally a `Vec` of capacity 2 is used to store this
 ///    and the modal `id`.
-/// 2. Modal focus - There can only be one modal at a time. When a modal is active, the
-///    `id` of the [FlexBox] that had focus before the modal was activated is saved. When
-///    the modal is closed, the `id` of the [FlexBox] that had focus before the modal was
-///    activated is restored.
+/// 2. Modal focus - When a modal is active, the `id` of the [FlexBox] that had focus
+///    before the modal was activated is saved. When the modal is closed, the `id` of the
+///    [FlexBox] that had focus before the modal was activated is restored.
 ///
 /// # Modal `id`, which is used by modal dialog box
 ///
-/// 1. Only one modal can be active at any time.
-/// 2. When a modal is active, the `id` of the [FlexBox] that had focus before the modal
+/// 1. When a modal is active, the `id` of the [FlexBox] that had focus before the modal
 ///    was activated is saved.
-/// 3. When the modal is closed, the `id` of the [FlexBox] that had focus before the modal
+/// 2. When the modal is closed, the `id` of the [FlexBox] that had focus before the modal
 ///    was activated is restored.
+/// 3. Modals can be nested (eg: a confirm dialog on top of a form dialog) w/
+///    [HasFocus::try_push_modal_id], in which case they make up a stack. Only the top
+///    most one has focus (so it is the only one that gets input events), and the ones
+///    below it are still painted (bottom first, see [crate::render_modal_stack!]). When
+///    it is closed, the one below it gets focus again.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct HasFocus {
-    /// This `id` has keyboard focus. This is global.
+    /// The last `id` has keyboard focus. This is global. The first one is the non modal
+    /// `id`, and the rest (if any) are the modal `id`s, from the bottom most one to the
+    /// top most one.
     id_vec: Vec<FlexBoxId>,
 }
 
@@ -92,7 +97,8 @@ impl HasFocus {
 
 impl HasFocus {
     /// Pushes the `id` to the `id_vec`. The previous `id` is saved and can be restored with
-    /// [reset_modal_id](HasFocus::reset_modal_id).
+    /// [reset_modal_id](HasFocus::reset_modal_id). This fails if a modal is already
+    /// active, use [try_push_modal_id](HasFocus::try_push_modal_id) to nest modals.
     pub fn try_set_modal_id(&mut self, id: FlexBoxId) -> CommonResult<()> {
         throws!({
             // Must have a non modal id already set.
@@ -119,19 +125,66 @@ impl HasFocus {
         });
     }
 
+    /// Pushes the modal `id` on top of the modal stack (if there is one), so that it has
+    /// focus. The previous `id` (modal or not) is restored by
+    /// [reset_modal_id](HasFocus::reset_modal_id) (or
+    /// [pop_modal_id](HasFocus::pop_modal_id)).
+    pub fn try_push_modal_id(&mut self, id: FlexBoxId) -> CommonResult<()> {
+        throws!({
+            // Must have a non modal id already set.
+            if !self.is_set() {
+                let msg =
+                    "Modal id can only be pushed if id is already set. id is not set.";
+                return CommonError::new_error_result_with_only_msg(msg);
+            }
+
+            // Must not be in the modal stack already.
+            if self.is_in_modal_stack(id) {
+                let msg = format!("Modal id {} is already in the modal stack.", id);
+                return CommonError::new_error_result_with_only_msg(&msg);
+            }
+
+            // Ok to push modal id.
+            self.id_vec.push(id);
+        });
+    }
+
     /// Checks whether any modal `id` is set.
-    pub fn is_modal_set(&self) -> bool { self.id_vec.len() == 2 }
+    pub fn is_modal_set(&self) -> bool { self.id_vec.len() >= 2 }
 
-    /// Checks whether the given `id` is the modal `id`.
+    /// Checks whether the given `id` is the top most modal `id` (the one w/ focus).
     pub fn is_modal_id(&self, id: FlexBoxId) -> bool {
         self.is_modal_set() && self.does_id_have_focus(id)
     }
 
-    /// Restores the modal `id` to the previous non-modal `id`. It does nothing if there's no modal
-    /// `id` set.
-    pub fn reset_modal_id(&mut self) {
-        if self.is_modal_set() {
-            self.id_vec.pop();
+    /// Checks whether the given `id` is any of the modal `id`s (whether it has focus or
+    /// not).
+    pub fn is_in_modal_stack(&self, id: FlexBoxId) -> bool {
+        self.get_modal_ids().contains(&id)
+    }
+
+    /// Returns the modal `id`s, from the bottom most one to the top most one (the one w/
+    /// focus). This is empty if no modal is set.
+    pub fn get_modal_ids(&self) -> &[FlexBoxId] {
+        match self.is_modal_set() {
+            true => &self.id_vec[1..],
+            false => &[],
+        }
+    }
+
+    /// Get the non modal `id`, which gets focus once all the modals are closed.
+    pub fn get_non_modal_id(&self) -> Option<FlexBoxId> { self.id_vec.first().copied() }
+
+    /// Restores the top most modal `id` to the previous `id` (the modal below it, or the
+    /// non-modal `id`). It does nothing if there's no modal `id` set.
+    pub fn reset_modal_id(&mut self) { self.pop_modal_id(); }
+
+    /// Same as [reset_modal_id](HasFocus::reset_modal_id), and returns the modal `id`
+    /// that was closed.
+    pub fn pop_modal_id(&mut self) -> Option<FlexBoxId> {
+        match self.is_modal_set() {
+            true => self.id_vec.pop(),
+            false => None,
         }
     }
 }
@@ -230,4 +283,42 @@ mod has_focus_tests {
         assert!(has_focus.is_set());
         assert!(!has_focus.is_empty());
     }
+
+    #[test]
+    fn works_with_nested_modal_ids() {
+        let mut has_focus = HasFocus::default();
+        assert!(has_focus.try_push_modal_id(FlexBoxId::from(2)).is_err());
+
+        has_focus.set_id(FlexBoxId::from(1));
+        assert!(has_focus.try_push_modal_id(FlexBoxId::from(2)).is_ok());
+        assert!(has_focus.try_push_modal_id(FlexBoxId::from(3)).is_ok());
+
+        // The same modal can't be pushed twice.
+        assert!(has_focus.try_push_modal_id(FlexBoxId::from(2)).is_err());
+
+        // Only the top most modal has focus.
+        assert_eq2!(
+            has_focus.get_modal_ids(),
+            &[FlexBoxId::from(2), FlexBoxId::from(3)]
+        );
+        assert!(has_focus.is_modal_id(FlexBoxId::from(3)));
+        assert!(!has_focus.is_modal_id(FlexBoxId::from(2)));
+        assert!(has_focus.is_in_modal_stack(FlexBoxId::from(2)));
+        assert_eq2!(has_focus.get_non_modal_id(), Some(FlexBoxId::from(1)));
+
+        // A single modal can't be set on top of them.
+        assert!(has_focus.try_set_modal_id(FlexBoxId::from(4)).is_err());
+
+        // Each one restores the focus to the one below it.
+        assert_eq2!(has_focus.pop_modal_id(), Some(FlexBoxId::from(3)));
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(2)));
+        assert!(has_focus.is_modal_id(FlexBoxId::from(2)));
+
+        has_focus.reset_modal_id();
+        assert!(!has_focus.is_modal_set());
+        assert!(has_focus.get_modal_ids().is_empty());
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+        assert_eq2!(has_focus.pop_modal_id(), None);
+        assert_eq2!(has_focus.get_id(), Some(FlexBoxId::from(1)));
+    }
 }
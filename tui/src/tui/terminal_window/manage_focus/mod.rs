@@ -17,8 +17,10 @@
 
 // Attach files.
 pub mod component_registry;
+pub mod focus_traversal;
 pub mod has_focus;
 
 // Re-export.
 pub use component_registry::*;
+pub use focus_traversal::*;
 pub use has_focus::*;
@@ -20,11 +20,16 @@ pub mod app;
 pub mod component;
 pub mod default_input_handler;
 pub mod event_routing_support;
+pub mod job_control;
 pub mod main_event_loop;
 pub mod manage_focus;
 pub mod public_api;
+pub mod render_scheduler;
+pub mod resize_debouncer;
 pub mod shared_global_data;
 pub mod static_global_data;
+pub mod terminal_restore;
+pub mod timer_service;
 pub mod type_aliases;
 
 // Re-export.
@@ -32,9 +37,14 @@ pub use app::*;
 pub use component::*;
 pub use default_input_handler::*;
 pub use event_routing_support::*;
+pub use job_control::*;
 pub use main_event_loop::*;
 pub use manage_focus::*;
 pub use public_api::*;
+pub use render_scheduler::*;
+pub use resize_debouncer::*;
 pub use shared_global_data::*;
 pub use static_global_data::*;
+pub use terminal_restore::*;
+pub use timer_service::*;
 pub use type_aliases::*;
@@ -17,10 +17,10 @@
 
 use std::fmt::Debug;
 
-use r3bl_core::{CommonResult, InputDevice, OutputDevice};
+use r3bl_core::{CommonResult, InputDevice, OutputDevice, Size};
 
-use super::{main_event_loop_impl, BoxedSafeApp, GlobalData};
-use crate::{terminal_lib_operations, FlexBoxId, InputEvent};
+use super::{install_panic_hook, main_event_loop_impl, BoxedSafeApp, GlobalData};
+use crate::{route_to_terminal_backend, terminal_lib_operations, FlexBoxId, InputEvent};
 
 pub struct TerminalWindow;
 
@@ -31,10 +31,28 @@ where
 {
     /// Exit the main event loop.
     Exit,
-    /// Render the app.
+    /// Render the app right away.
     Render(Option<FlexBoxId>),
+    /// Ask for the app to be rendered. Unlike [TerminalWindowMainThreadSignal::Render],
+    /// the requests that are sent within a frame are coalesced into a single render, and
+    /// the frame rate is capped (to [crate::DEFAULT_MAX_FPS] by default). Use this when
+    /// the state changes often, eg: on each keystroke, or from a background task. More
+    /// info in [crate::RenderScheduler].
+    RequestRender(Option<FlexBoxId>),
     /// Apply an action to the app.
     ApplyAction(AS),
+    /// The terminal was resized from `old` to `new`. This is sent (once) by the main
+    /// event loop after a burst of resize events has settled, see
+    /// [crate::ResizeDebouncer]. The app is laid out & painted again at the `new` size,
+    /// and it is told about the resize in [crate::App::app_handle_resize].
+    Resized { old: Size, new: Size },
+    /// Restore the terminal & suspend the process (like Ctrl+Z does in a shell). If the
+    /// [crate::SuspendKeyMode] is enabled, this is sent when the user presses Ctrl+Z &
+    /// the app doesn't consume it (see [crate::is_suspend_key]), since that doesn't send
+    /// `SIGTSTP` while the terminal is in raw mode. Once the process is resumed (eg: w/
+    /// `fg`), raw mode is entered again and the app is painted from scratch. More info in
+    /// [crate::JobControlSignals].
+    Suspend,
 }
 
 impl TerminalWindow {
@@ -43,6 +61,9 @@ impl TerminalWindow {
     /// processing. It is also responsible for rendering the [crate::App] after each input
     /// event. It is also responsible for handling all signals sent from the [crate::App]
     /// to the main event loop (eg: exit, re-render, apply action, etc).
+    ///
+    /// The terminal is restored (see [crate::restore_terminal]) when this returns, even
+    /// if it returns w/ an error, or if the app panics.
     pub async fn main_event_loop<S, AS>(
         app: BoxedSafeApp<S, AS>,
         exit_keys: Vec<InputEvent>,
@@ -56,9 +77,12 @@ impl TerminalWindow {
         S: Debug + Default + Clone + Sync + Send,
         AS: Debug + Default + Clone + Sync + Send + 'static,
     {
-        let initial_size = terminal_lib_operations::lookup_size()?;
-        let input_device = InputDevice::new_event_stream();
         let output_device = OutputDevice::new_stdout();
+        install_panic_hook(&output_device);
+
+        let initial_size = terminal_lib_operations::lookup_size()?;
+        let input_device =
+            route_to_terminal_backend(|backend| backend.new_input_device());
 
         main_event_loop_impl(
             app,
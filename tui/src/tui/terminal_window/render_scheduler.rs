@@ -0,0 +1,155 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+use std::{cmp, time::Duration};
+
+use tokio::time::Instant;
+
+/// The default cap on how many frames are painted per second, see [RenderScheduler].
+pub const DEFAULT_MAX_FPS: u32 = 60;
+
+/// Coalesces the requests to render the app (from
+/// [crate::TerminalWindowMainThreadSignal::RequestRender] &
+/// [crate::EventPropagation::ConsumedRender]), so that a burst of them (eg: from typing
+/// fast, or from a paste, or from a background task that updates the state often) results
+/// in a single render at the end of the frame, instead of one full repaint for each.
+///
+/// - Nothing is rendered unless a render was requested.
+/// - A requested render happens right away if the last one was more than a frame budget
+///   ago (`1s / max_fps`), otherwise it is deferred until the frame budget is up. All the
+///   requests that come in before then are merged into it.
+///
+/// This is saved in [crate::GlobalData::render_scheduler], so that the app can change
+/// its frame rate w/ [RenderScheduler::set_max_fps]. Use
+/// [crate::TerminalWindowMainThreadSignal::Render] to render right away (eg: when
+/// something has to be painted w/out any delay).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderScheduler {
+    frame_budget: Duration,
+    maybe_last_render_at: Option<Instant>,
+    is_render_pending: bool,
+    deferred_render_count: usize,
+}
+
+impl Default for RenderScheduler {
+    fn default() -> Self { Self::new(DEFAULT_MAX_FPS) }
+}
+
+impl RenderScheduler {
+    pub fn new(max_fps: u32) -> Self {
+        Self {
+            frame_budget: get_frame_budget(max_fps),
+            maybe_last_render_at: None,
+            is_render_pending: false,
+            deferred_render_count: 0,
+        }
+    }
+
+    pub fn set_max_fps(&mut self, max_fps: u32) {
+        self.frame_budget = get_frame_budget(max_fps);
+    }
+
+    pub fn get_frame_budget(&self) -> Duration { self.frame_budget }
+
+    /// Ask for a render, which happens at [Self::get_render_deadline].
+    pub fn request_render(&mut self) { self.is_render_pending = true; }
+
+    pub fn is_render_pending(&self) -> bool { self.is_render_pending }
+
+    /// Ask for a render again, since the one that was due couldn't happen yet (eg: the
+    /// last frame is still being painted, see [crate::PaintTask]).
+    pub fn defer_render(&mut self) {
+        self.request_render();
+        self.deferred_render_count += 1;
+    }
+
+    /// How many renders have been deferred so far, see [Self::defer_render].
+    pub fn get_deferred_render_count(&self) -> usize { self.deferred_render_count }
+
+    /// When the pending render should happen, which is never sooner than a frame budget
+    /// after the last render. Returns [None] if no render was requested.
+    pub fn get_render_deadline(&self, now: Instant) -> Option<Instant> {
+        if !self.is_render_pending {
+            return None;
+        }
+        let deadline = match self.maybe_last_render_at {
+            Some(last_render_at) => cmp::max(now, last_render_at + self.frame_budget),
+            None => now,
+        };
+        Some(deadline)
+    }
+
+    /// This has to be called after every render (whether it was requested or not), which
+    /// clears the pending request, since the app was just painted w/ the latest state.
+    pub fn on_render(&mut self, now: Instant) {
+        self.is_render_pending = false;
+        self.maybe_last_render_at = Some(now);
+    }
+}
+
+fn get_frame_budget(max_fps: u32) -> Duration { Duration::from_secs(1) / max_fps.max(1) }
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_nothing_is_rendered_unless_requested() {
+        let scheduler = RenderScheduler::default();
+        assert!(!scheduler.is_render_pending());
+        assert_eq2!(scheduler.get_render_deadline(Instant::now()), None);
+    }
+
+    #[test]
+    fn test_requests_are_coalesced_within_frame_budget() {
+        let mut scheduler = RenderScheduler::new(50);
+        assert_eq2!(scheduler.get_frame_budget(), Duration::from_millis(20));
+
+        // The first request is rendered right away.
+        let start = Instant::now();
+        scheduler.request_render();
+        assert_eq2!(scheduler.get_render_deadline(start), Some(start));
+        scheduler.on_render(start);
+        assert!(!scheduler.is_render_pending());
+
+        // The ones that come in before the frame budget is up are deferred until then.
+        let now = start + Duration::from_millis(5);
+        scheduler.request_render();
+        scheduler.request_render();
+        assert_eq2!(
+            scheduler.get_render_deadline(now),
+            Some(start + Duration::from_millis(20))
+        );
+
+        // After the frame budget is up, it is rendered right away.
+        let now = start + Duration::from_millis(30);
+        assert_eq2!(scheduler.get_render_deadline(now), Some(now));
+        scheduler.on_render(now);
+        assert_eq2!(scheduler.get_render_deadline(now), None);
+    }
+
+    #[test]
+    fn test_set_max_fps() {
+        let mut scheduler = RenderScheduler::default();
+        scheduler.set_max_fps(10);
+        assert_eq2!(scheduler.get_frame_budget(), Duration::from_millis(100));
+        // 0 isn't a valid frame rate.
+        scheduler.set_max_fps(0);
+        assert_eq2!(scheduler.get_frame_budget(), Duration::from_secs(1));
+    }
+}
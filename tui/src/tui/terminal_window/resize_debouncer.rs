@@ -0,0 +1,179 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+use std::time::Duration;
+
+use r3bl_core::Size;
+use tokio::time::Instant;
+
+/// How long the terminal has to stay the same size before the app is laid out again, see
+/// [ResizeDebouncer].
+pub const DEFAULT_RESIZE_DEBOUNCE_DELAY: Duration = Duration::from_millis(50);
+
+/// Merges a burst of [crate::InputEvent::Resize] events (eg: from dragging the edge of
+/// the terminal window) into a single
+/// [crate::TerminalWindowMainThreadSignal::Resized], which is sent once the size has
+/// stopped changing for the debounce delay. This way the app is laid out & painted once,
+/// at the final size, instead of once for each of the sizes in between.
+///
+/// The `old` size is the one before the burst started, and the `new` size is the last one
+/// in it. If the terminal ends up the same size that it started at, the app isn't laid
+/// out again, but it is painted from scratch, see [SettledResize::Repaint].
+///
+/// This is saved in [crate::GlobalData::resize_debouncer], so that the app can change the
+/// delay w/ [ResizeDebouncer::set_delay].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResizeDebouncer {
+    delay: Duration,
+    maybe_pending_resize: Option<PendingResize>,
+}
+
+/// What to do once a burst of resize events has settled, see
+/// [ResizeDebouncer::take_pending_resize].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SettledResize {
+    /// The terminal is a different size than it was before the burst, so the app is
+    /// laid out & painted again, see [crate::TerminalWindowMainThreadSignal::Resized].
+    Resized { old_size: Size, new_size: Size },
+    /// The terminal ended up the same size that it was before the burst. The layout is
+    /// still valid, but the terminal might have reflowed (or cleared) what was painted
+    /// while it was a different size, so all of it is painted again.
+    Repaint,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct PendingResize {
+    old_size: Size,
+    new_size: Size,
+    deadline: Instant,
+}
+
+impl Default for ResizeDebouncer {
+    fn default() -> Self { Self::new(DEFAULT_RESIZE_DEBOUNCE_DELAY) }
+}
+
+impl ResizeDebouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            maybe_pending_resize: None,
+        }
+    }
+
+    pub fn set_delay(&mut self, delay: Duration) { self.delay = delay; }
+
+    pub fn get_delay(&self) -> Duration { self.delay }
+
+    /// Call this for each resize event. The `old_size` is only used by the first one in
+    /// a burst, and each one pushes the deadline back by the debounce delay.
+    pub fn on_resize(&mut self, old_size: Size, new_size: Size, now: Instant) {
+        let old_size = match self.maybe_pending_resize {
+            Some(pending_resize) => pending_resize.old_size,
+            None => old_size,
+        };
+        self.maybe_pending_resize = Some(PendingResize {
+            old_size,
+            new_size,
+            deadline: now + self.delay,
+        });
+    }
+
+    pub fn is_resize_pending(&self) -> bool { self.maybe_pending_resize.is_some() }
+
+    /// When the pending resize should be delivered. Returns [None] if there isn't one.
+    pub fn get_resize_deadline(&self) -> Option<Instant> {
+        self.maybe_pending_resize.map(|it| it.deadline)
+    }
+
+    /// Clears the pending resize, and returns what to do w/ it. Returns [None] if there
+    /// isn't one.
+    pub fn take_pending_resize(&mut self) -> Option<SettledResize> {
+        let PendingResize {
+            old_size, new_size, ..
+        } = self.maybe_pending_resize.take()?;
+        Some(match old_size == new_size {
+            true => SettledResize::Repaint,
+            false => SettledResize::Resized { old_size, new_size },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+
+    #[test]
+    fn test_burst_of_resizes_is_merged() {
+        let mut debouncer = ResizeDebouncer::new(Duration::from_millis(50));
+        assert_eq2!(debouncer.get_resize_deadline(), None);
+
+        let start = Instant::now();
+        debouncer.on_resize(
+            size!(col_count: 80, row_count: 24),
+            size!(col_count: 90, row_count: 24),
+            start,
+        );
+        let now = start + Duration::from_millis(20);
+        debouncer.on_resize(
+            size!(col_count: 90, row_count: 24),
+            size!(col_count: 100, row_count: 30),
+            now,
+        );
+
+        // Each resize pushes the deadline back.
+        assert!(debouncer.is_resize_pending());
+        assert_eq2!(
+            debouncer.get_resize_deadline(),
+            Some(now + Duration::from_millis(50))
+        );
+
+        // The old size is the one from before the burst.
+        assert_eq2!(
+            debouncer.take_pending_resize(),
+            Some(SettledResize::Resized {
+                old_size: size!(col_count: 80, row_count: 24),
+                new_size: size!(col_count: 100, row_count: 30)
+            })
+        );
+        assert!(!debouncer.is_resize_pending());
+        assert_eq2!(debouncer.take_pending_resize(), None);
+    }
+
+    #[test]
+    fn test_resize_back_to_same_size_is_repainted() {
+        let mut debouncer = ResizeDebouncer::default();
+        let now = Instant::now();
+        debouncer.on_resize(
+            size!(col_count: 80, row_count: 24),
+            size!(col_count: 90, row_count: 24),
+            now,
+        );
+        debouncer.on_resize(
+            size!(col_count: 90, row_count: 24),
+            size!(col_count: 80, row_count: 24),
+            now,
+        );
+
+        // The app isn't laid out again, but it is painted from scratch.
+        assert_eq2!(
+            debouncer.take_pending_resize(),
+            Some(SettledResize::Repaint)
+        );
+        assert_eq2!(debouncer.take_pending_resize(), None);
+    }
+}
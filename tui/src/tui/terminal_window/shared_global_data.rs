@@ -15,13 +15,36 @@
  *   limitations under the License.
  */
 
-use std::fmt::{Debug, Formatter};
+use std::{fmt::{Debug, Formatter},
+          path::Path};
 
-use r3bl_core::{call_if_true, CommonResult, OutputDevice, Size};
+use r3bl_core::{call_if_true,
+                CommonError,
+                CommonErrorType,
+                CommonResult,
+                OutputDevice,
+                Size};
 use tokio::sync::mpsc::Sender;
 
 use super::TerminalWindowMainThreadSignal;
-use crate::{OffscreenBuffer, DEBUG_TUI_COMPOSITOR, DEBUG_TUI_MOD};
+use crate::{ContextMenu,
+            FlexBoxRegistry,
+            LayoutCache,
+            OffscreenBuffer,
+            PaintStats,
+            PaintTask,
+            RenderScheduler,
+            ResizeDebouncer,
+            SharedPendingClipboardContents,
+            ShortcutRegistry,
+            SnapshotFormat,
+            SplashScreen,
+            TimerService,
+            Toast,
+            ToastId,
+            ToastManager,
+            DEBUG_TUI_COMPOSITOR,
+            DEBUG_TUI_MOD};
 
 /// This is a global data structure that holds state for the entire application
 /// [crate::App] and the terminal window [crate::TerminalWindow] itself.
@@ -34,6 +57,31 @@ use crate::{OffscreenBuffer, DEBUG_TUI_COMPOSITOR, DEBUG_TUI_MOD};
 /// - The `output_device` is the terminal's output device (anything that implements
 ///   [r3bl_core::SafeRawTerminal] which can be [std::io::stdout] or
 ///   [r3bl_core::SharedWriter], etc.`).
+/// - The `layout_cache` holds the boxes that were laid out in the last render, see
+///   [LayoutCache].
+/// - The `box_registry` holds the bounds of the boxes from the last render, so that they
+///   can be looked up when handling input events, see [FlexBoxRegistry].
+/// - The `last_paint_stats` holds how many cells were painted in the last render, see
+///   [PaintStats].
+/// - The `render_scheduler` coalesces the requests to render the app & caps the frame
+///   rate, see [RenderScheduler].
+/// - The `resize_debouncer` merges a burst of resize events into a single
+///   [TerminalWindowMainThreadSignal::Resized], see [ResizeDebouncer].
+/// - The `maybe_title` is the title of the terminal window (or tab), see
+///   [GlobalData::set_title].
+/// - The `maybe_paint_task` paints the frames in the background, see [PaintTask].
+/// - The `toasts` are the notifications that are painted above the app, see
+///   [ToastManager] & [GlobalData::post_toast].
+/// - The `shortcuts` are the keyboard shortcuts of the app & its components, which are
+///   listed in a help overlay, see [ShortcutRegistry].
+/// - The `timers` tick the components that animate something, see [TimerService].
+/// - The `context_menu` is the popup menu that is opened by right clicking a component,
+///   see [ContextMenu].
+/// - The `maybe_splash_screen` is painted instead of the app until its startup work is
+///   done, see [SplashScreen].
+/// - The `pending_clipboard_contents` is the text that was copied w/
+///   [crate::Osc52Clipboard], which is painted w/ the next frame, see
+///   [GlobalData::take_pending_clipboard_contents].
 pub struct GlobalData<S, AS>
 where
     S: Debug + Default + Clone + Sync + Send,
@@ -44,6 +92,19 @@ where
     pub main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
     pub state: S,
     pub output_device: OutputDevice,
+    pub layout_cache: LayoutCache,
+    pub box_registry: FlexBoxRegistry,
+    pub last_paint_stats: PaintStats,
+    pub render_scheduler: RenderScheduler,
+    pub resize_debouncer: ResizeDebouncer,
+    pub maybe_title: Option<String>,
+    pub maybe_paint_task: Option<PaintTask>,
+    pub toasts: ToastManager<AS>,
+    pub shortcuts: ShortcutRegistry,
+    pub timers: TimerService,
+    pub context_menu: ContextMenu<AS>,
+    pub maybe_splash_screen: Option<SplashScreen>,
+    pub pending_clipboard_contents: SharedPendingClipboardContents,
 }
 
 impl<S, AS> Debug for GlobalData<S, AS>
@@ -62,6 +123,7 @@ where
                     true => offscreen_buffer.pretty_print(),
                 },
             });
+            it.push(format!("last_paint_stats: {}", self.last_paint_stats));
             it
         };
         write!(f, "\nGlobalData\n  - {}", vec_lines.join("\n  - "))
@@ -88,6 +150,19 @@ where
             state,
             main_thread_channel_sender,
             output_device,
+            layout_cache: Default::default(),
+            box_registry: Default::default(),
+            last_paint_stats: Default::default(),
+            render_scheduler: Default::default(),
+            resize_debouncer: Default::default(),
+            maybe_title: None,
+            maybe_paint_task: None,
+            toasts: Default::default(),
+            shortcuts: Default::default(),
+            timers: Default::default(),
+            context_menu: Default::default(),
+            maybe_splash_screen: None,
+            pending_clipboard_contents: Default::default(),
         };
 
         it.set_size(initial_size);
@@ -102,6 +177,94 @@ where
 
     pub fn get_size(&self) -> Size { self.window_size }
 
+    /// Set the title of the terminal window (or tab), eg: to the name of the file that
+    /// is open. It is painted in the next render (only if it changed), and the original
+    /// title is restored when the app exits. A [crate::RenderOp::SetTitle] in the
+    /// [crate::RenderPipeline] replaces it.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.maybe_title = Some(title.into());
+    }
+
+    /// The title of the terminal window (or tab), if it was set by the app.
+    pub fn get_title(&self) -> Option<&str> { self.maybe_title.as_deref() }
+
+    /// Save a "screenshot" of what was painted in the last render to `path`, see
+    /// [OffscreenBuffer::save_snapshot]. This fails if nothing has been painted yet.
+    pub fn save_snapshot(
+        &self,
+        path: impl AsRef<Path>,
+        format: SnapshotFormat,
+    ) -> CommonResult<()> {
+        match &self.maybe_saved_offscreen_buffer {
+            Some(offscreen_buffer) => offscreen_buffer.save_snapshot(path, format),
+            None => CommonError::new_error_result(
+                CommonErrorType::InvalidState,
+                "There is no offscreen buffer to save, nothing has been painted yet",
+            ),
+        }
+    }
+
+    /// Post a toast, which is painted above the app (in a corner of the window) until
+    /// its timeout elapses. To post a toast from another task, use a clone of
+    /// [GlobalData::toasts] w/ [ToastManager::post].
+    pub fn post_toast(&self, toast: Toast<AS>) -> ToastId
+    where
+        AS: 'static,
+    {
+        self.toasts.post(toast, &self.main_thread_channel_sender)
+    }
+
+    /// Whether the [SplashScreen] is painted instead of the app, ie: until its startup
+    /// work is done.
+    pub fn is_splash_screen_shown(&self) -> bool {
+        self.maybe_splash_screen
+            .as_ref()
+            .is_some_and(|it| !it.is_ready())
+    }
+
+    /// Whether any text was copied w/ [crate::Osc52Clipboard] since the last render. A
+    /// render is requested when there is, so that it is painted right away.
+    pub fn has_pending_clipboard_contents(&self) -> bool {
+        self.pending_clipboard_contents
+            .lock()
+            .is_ok_and(|it| !it.is_empty())
+    }
+
+    /// Take the text that was copied w/ [crate::Osc52Clipboard] since the last render,
+    /// to paint it w/ [crate::RenderOp::SetClipboard].
+    pub fn take_pending_clipboard_contents(&self) -> Vec<String> {
+        self.pending_clipboard_contents
+            .lock()
+            .map(|mut it| std::mem::take(&mut *it))
+            .unwrap_or_default()
+    }
+
+    /// Block until the frames that were sent to the [PaintTask] (if any) have been
+    /// painted. Don't call this while the output device is locked.
+    pub fn wait_until_painted(&self) {
+        if let Some(ref paint_task) = self.maybe_paint_task {
+            paint_task.wait_until_idle();
+        }
+    }
+
+    /// Whether the last frame that was sent to the [PaintTask] (if any) is still being
+    /// painted. The app isn't rendered until it is done, see [PaintTask::is_busy].
+    pub fn is_painting(&self) -> bool {
+        self.maybe_paint_task
+            .as_ref()
+            .is_some_and(PaintTask::is_busy)
+    }
+
+    /// Wait until the [PaintTask] is done painting a frame, w/out blocking the main event
+    /// loop. This never returns if there is no [PaintTask]. It is cancel safe, so it can
+    /// be used in [tokio::select!].
+    pub async fn wait_until_painted_async(&self) {
+        match self.maybe_paint_task {
+            Some(ref paint_task) => paint_task.wait_until_idle_async().await,
+            None => std::future::pending().await,
+        }
+    }
+
     pub fn dump_to_log(&self, msg: &str) {
         call_if_true!(DEBUG_TUI_MOD, tracing::info!("{msg} -> {self:?}"));
     }
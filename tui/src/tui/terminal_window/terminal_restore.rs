@@ -0,0 +1,160 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{panic,
+          sync::{Mutex as StdMutex, Once, PoisonError, TryLockError}};
+
+use r3bl_core::{OutputDevice, Size};
+
+use crate::RawMode;
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+/// The [OutputDevice] of the app that is running, which the terminal is restored w/ when
+/// the app panics, see [install_panic_hook].
+static MAYBE_PANIC_OUTPUT_DEVICE: StdMutex<Option<OutputDevice>> = StdMutex::new(None);
+
+/// Install a panic hook that restores the terminal (see [restore_terminal]) before the
+/// panic message is printed, and then runs the hook that was installed before it (which
+/// prints the message). W/out this, a panic in the app leaves the terminal in raw mode &
+/// in the alternate screen, w/ the cursor hidden & the mouse captured, so the panic
+/// message is lost and the user's shell is unusable until they run `reset`.
+///
+/// This is called by [crate::TerminalWindow::main_event_loop] w/ the app's
+/// `output_device`, and it is safe to call it more than once (the hook is only installed
+/// the first time, and the terminal is restored w/ the last `output_device`).
+pub fn install_panic_hook(output_device: &OutputDevice) {
+    if let Ok(mut it) = MAYBE_PANIC_OUTPUT_DEVICE.lock() {
+        *it = Some(output_device.clone());
+    }
+    INSTALL_PANIC_HOOK.call_once(chain_panic_hook);
+}
+
+/// Replace the panic hook w/ one that restores the terminal, and then runs the hook that
+/// was installed before it.
+fn chain_panic_hook() {
+    let prev_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        let maybe_output_device = match MAYBE_PANIC_OUTPUT_DEVICE.try_lock() {
+            Ok(it) => it.clone(),
+            Err(_) => None,
+        };
+        if let Some(ref output_device) = maybe_output_device {
+            restore_terminal_after_panic(output_device);
+        }
+        prev_hook(panic_info);
+    }));
+}
+
+/// Exit raw mode, leave the alternate screen, release the mouse capture, and show the
+/// cursor, if the terminal is still in raw mode (see [RawMode::is_active]), otherwise
+/// this does nothing.
+///
+/// This waits for the `output_device` to be unlocked, eg: until the frame that is being
+/// painted by the [crate::PaintTask] is done.
+pub fn restore_terminal(output_device: &OutputDevice) {
+    if !RawMode::is_active() {
+        return;
+    }
+    let mut locked_output_device = output_device
+        .resource
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    RawMode::end(
+        Size::default(),
+        &mut *locked_output_device,
+        output_device.is_mock,
+    );
+}
+
+/// Just like [restore_terminal], except that it doesn't wait for the `output_device` to
+/// be unlocked, since it might be locked by the code that panicked. In that case, a new
+/// handle to [std::io::stdout] is written to instead.
+fn restore_terminal_after_panic(output_device: &OutputDevice) {
+    if !RawMode::is_active() {
+        return;
+    }
+    let is_mock = output_device.is_mock;
+    match output_device.resource.try_lock() {
+        Ok(mut it) => RawMode::end(Size::default(), &mut *it, is_mock),
+        Err(TryLockError::Poisoned(it)) => {
+            RawMode::end(Size::default(), &mut *it.into_inner(), is_mock)
+        }
+        Err(TryLockError::WouldBlock) => {
+            RawMode::end(Size::default(), &mut std::io::stdout(), is_mock)
+        }
+    }
+}
+
+/// Restores the terminal (see [restore_terminal]) w/ the app's `output_device` when it
+/// is dropped, so that the terminal isn't left in raw mode when the main event loop
+/// returns early, eg: w/ an error. It does nothing if raw mode was already ended.
+pub struct TerminalRestoreGuard {
+    pub output_device: OutputDevice,
+}
+
+impl TerminalRestoreGuard {
+    pub fn new(output_device: OutputDevice) -> Self { Self { output_device } }
+}
+
+impl Drop for TerminalRestoreGuard {
+    fn drop(&mut self) { restore_terminal(&self.output_device); }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use r3bl_test_fixtures::output_device_ext::OutputDeviceExt as _;
+
+    use super::*;
+
+    #[test]
+    fn test_terminal_restore_guard_restores_only_when_raw_mode_is_active() {
+        let (output_device, stdout_mock) = OutputDevice::new_mock();
+
+        // Raw mode was never started (or it was already ended).
+        assert!(!RawMode::is_active());
+        drop(TerminalRestoreGuard::new(output_device.clone()));
+        assert!(stdout_mock.get_copy_of_buffer().is_empty());
+
+        // Raw mode is still active, eg: the main event loop returned early w/ an error.
+        RawMode::set_is_active(true);
+        drop(TerminalRestoreGuard::new(output_device.clone()));
+        RawMode::set_is_active(false);
+        assert!(!stdout_mock.get_copy_of_buffer().is_empty());
+    }
+
+    #[test]
+    fn test_panic_hook_runs_the_previous_hook() {
+        static IS_PREV_HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+
+        let original_hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {
+            IS_PREV_HOOK_CALLED.store(true, Ordering::SeqCst)
+        }));
+        chain_panic_hook();
+
+        let result = panic::catch_unwind(|| panic!("💥"));
+
+        let _ = panic::take_hook();
+        panic::set_hook(original_hook);
+
+        assert!(result.is_err());
+        assert!(IS_PREV_HOOK_CALLED.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,187 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{cmp, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::FlexBoxId;
+
+/// The shortest interval that a component can subscribe to, see
+/// [TimerService::subscribe].
+pub const MIN_TIMER_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Ticks the components that subscribed to it, so that they can animate something (eg:
+/// a spinner, a blinking caret, a timeout, or smooth scrolling) w/out each of them
+/// spawning its own tokio task. The main event loop waits for the next tick (along w/
+/// the input events & signals), and calls [crate::Component::handle_tick] on each of
+/// the components whose tick is due.
+///
+/// - A component subscribes w/ [TimerService::subscribe] (eg: when it starts
+///   animating), and unsubscribes w/ [TimerService::unsubscribe] once it is done, so
+///   that the main event loop doesn't wake up for nothing.
+/// - If a tick is late (eg: the app was busy), the missed ticks are skipped instead of
+///   being delivered in a burst.
+/// - The subscription of a component that isn't in the
+///   [crate::ComponentRegistryMap] anymore is dropped on its next tick.
+///
+/// This is saved in [crate::GlobalData::timers].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimerService {
+    subscriptions: Vec<TimerSubscription>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct TimerSubscription {
+    id: FlexBoxId,
+    interval: Duration,
+    next_tick_at: Instant,
+}
+
+impl TimerService {
+    /// Tick the component w/ the given `id` every `interval` (which is at least
+    /// [MIN_TIMER_INTERVAL]), starting an `interval` from `now`. If it is already
+    /// subscribed, its interval is changed.
+    pub fn subscribe(&mut self, id: FlexBoxId, interval: Duration, now: Instant) {
+        let interval = cmp::max(interval, MIN_TIMER_INTERVAL);
+        let subscription = TimerSubscription {
+            id,
+            interval,
+            next_tick_at: now + interval,
+        };
+        match self.subscriptions.iter_mut().find(|it| it.id == id) {
+            Some(it) if it.interval == interval => {}
+            Some(it) => *it = subscription,
+            None => self.subscriptions.push(subscription),
+        }
+    }
+
+    /// Returns true if the component w/ the given `id` was subscribed.
+    pub fn unsubscribe(&mut self, id: FlexBoxId) -> bool {
+        let old_len = self.subscriptions.len();
+        self.subscriptions.retain(|it| it.id != id);
+        self.subscriptions.len() != old_len
+    }
+
+    pub fn is_subscribed(&self, id: FlexBoxId) -> bool {
+        self.subscriptions.iter().any(|it| it.id == id)
+    }
+
+    pub fn get_interval(&self, id: FlexBoxId) -> Option<Duration> {
+        self.subscriptions
+            .iter()
+            .find(|it| it.id == id)
+            .map(|it| it.interval)
+    }
+
+    pub fn is_empty(&self) -> bool { self.subscriptions.is_empty() }
+
+    /// When the next tick is due. Returns [None] if no component is subscribed.
+    pub fn get_tick_deadline(&self) -> Option<Instant> {
+        self.subscriptions.iter().map(|it| it.next_tick_at).min()
+    }
+
+    /// Returns the ids of the components whose tick is due at `now` (in the order that
+    /// they subscribed), and schedules their next tick.
+    pub fn take_due_ticks(&mut self, now: Instant) -> Vec<FlexBoxId> {
+        let mut it = vec![];
+        for subscription in self.subscriptions.iter_mut() {
+            if subscription.next_tick_at > now {
+                continue;
+            }
+            it.push(subscription.id);
+
+            // Skip the ticks that were missed.
+            let missed_tick_count = (now - subscription.next_tick_at).as_nanos()
+                / subscription.interval.as_nanos();
+            subscription.next_tick_at +=
+                subscription.interval * (missed_tick_count as u32 + 1);
+        }
+        it
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_ticks_are_due_at_their_interval() {
+        let start = Instant::now();
+        let mut timers = TimerService::default();
+        assert_eq2!(timers.get_tick_deadline(), None);
+
+        let spinner = FlexBoxId::from(1);
+        let caret = FlexBoxId::from(2);
+        timers.subscribe(spinner, Duration::from_millis(100), start);
+        timers.subscribe(caret, Duration::from_millis(500), start);
+        assert_eq2!(
+            timers.get_tick_deadline(),
+            Some(start + Duration::from_millis(100))
+        );
+
+        // Nothing is due yet.
+        assert!(timers
+            .take_due_ticks(start + Duration::from_millis(50))
+            .is_empty());
+
+        assert_eq2!(
+            timers.take_due_ticks(start + Duration::from_millis(100)),
+            vec![spinner]
+        );
+        assert_eq2!(
+            timers.get_tick_deadline(),
+            Some(start + Duration::from_millis(200))
+        );
+
+        // Both are due, and the 3 ticks of the spinner that were missed are skipped.
+        assert_eq2!(
+            timers.take_due_ticks(start + Duration::from_millis(550)),
+            vec![spinner, caret]
+        );
+        assert_eq2!(
+            timers.get_tick_deadline(),
+            Some(start + Duration::from_millis(600))
+        );
+    }
+
+    #[test]
+    fn test_subscribe_and_unsubscribe() {
+        let start = Instant::now();
+        let mut timers = TimerService::default();
+        let id = FlexBoxId::from(1);
+
+        // The interval is clamped.
+        timers.subscribe(id, Duration::from_millis(1), start);
+        assert_eq2!(timers.get_interval(id), Some(MIN_TIMER_INTERVAL));
+
+        // Subscribing again w/ the same interval doesn't push the next tick back.
+        timers.subscribe(id, MIN_TIMER_INTERVAL, start + Duration::from_millis(5));
+        assert_eq2!(timers.get_tick_deadline(), Some(start + MIN_TIMER_INTERVAL));
+
+        timers.subscribe(id, Duration::from_millis(250), start);
+        assert_eq2!(timers.get_interval(id), Some(Duration::from_millis(250)));
+        assert!(timers.is_subscribed(id));
+
+        assert!(timers.unsubscribe(id));
+        assert!(!timers.unsubscribe(id));
+        assert!(timers.is_empty());
+        assert_eq2!(timers.get_tick_deadline(), None);
+    }
+}
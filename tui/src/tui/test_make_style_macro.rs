@@ -27,7 +27,13 @@
 
 #[cfg(test)]
 mod tests {
-    use r3bl_core::{assert_eq2, ch, color, with, ANSIBasicColor, TuiStyle};
+    use r3bl_core::{assert_eq2,
+                    ch,
+                    color,
+                    with,
+                    ANSIBasicColor,
+                    TuiStyle,
+                    TuiUnderlineStyle};
     use r3bl_macro::tui_style;
 
     #[test]
@@ -135,4 +141,25 @@ mod tests {
           }
         }
     }
+
+    #[test]
+    fn test_with_underline_style_and_color() {
+        with! {
+          tui_style! {
+            id: 1
+            attrib: [curly_underline]
+            color_underline: color!(@red)
+          },
+          as it,
+          run {
+            assert!(it.underline);
+            assert_eq2!(it.underline_style, TuiUnderlineStyle::Curly);
+            assert_eq!(it.color_underline, color!(@red).into());
+          }
+        }
+
+        let style = tui_style! { attrib: [underline] };
+        assert_eq2!(style.underline_style, TuiUnderlineStyle::Single);
+        assert_eq!(style.color_underline, None);
+    }
 }
@@ -0,0 +1,634 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug,
+          sync::{Arc, Mutex},
+          time::{Duration, Instant}};
+
+use r3bl_core::{ch,
+                position,
+                size,
+                ANSIBasicColor,
+                Position,
+                Size,
+                TuiColor,
+                TuiStyle,
+                UnicodeString};
+use tokio::sync::mpsc::Sender;
+
+use crate::{render_ops,
+            render_pipeline,
+            BoxBorder,
+            InputEvent,
+            KeyPress,
+            RenderOp,
+            RenderOps,
+            RenderPipeline,
+            TerminalWindowMainThreadSignal,
+            ZOrder};
+
+/// A toast is dismissed after this, unless it has its own timeout, see
+/// [Toast::with_timeout].
+pub const DEFAULT_TOAST_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// The width of a toast (including its border) is clamped to this range.
+pub const TOAST_MIN_COL_COUNT: usize = 16;
+pub const TOAST_MAX_COL_COUNT: usize = 48;
+
+/// The number of cols between a toast & the left or right edge of the window.
+pub const TOAST_MARGIN_COL_COUNT: usize = 1;
+
+/// This is painted at the end of a line that is too long to fit in a toast.
+pub const TOAST_ELLIPSIS: &str = "…";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl ToastLevel {
+    /// This is painted in the top line of the border of the toast.
+    pub fn get_title(&self) -> &'static str {
+        match self {
+            ToastLevel::Info => "Info",
+            ToastLevel::Warn => "Warning",
+            ToastLevel::Error => "Error",
+        }
+    }
+
+    fn get_border_style(&self) -> TuiStyle {
+        let color = match self {
+            ToastLevel::Info => ANSIBasicColor::Cyan,
+            ToastLevel::Warn => ANSIBasicColor::Yellow,
+            ToastLevel::Error => ANSIBasicColor::Red,
+        };
+        TuiStyle {
+            color_fg: Some(TuiColor::Basic(color)),
+            bold: *self == ToastLevel::Error,
+            ..Default::default()
+        }
+    }
+}
+
+/// The action of a toast is dispatched (w/
+/// [TerminalWindowMainThreadSignal::ApplyAction]) when the `key_press` is pressed while
+/// the toast is shown, and the toast is dismissed. A hint, eg: `Ctrl+R: Retry`, is
+/// painted below the message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToastAction<AS> {
+    pub key_press: KeyPress,
+    pub label: String,
+    pub action: AS,
+}
+
+/// A notification that is painted in a corner of the window, above the app, see
+/// [ToastManager].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast<AS> {
+    pub message: String,
+    pub level: ToastLevel,
+    /// The toast stays until it is dismissed (or its action is taken) if this is [None].
+    pub maybe_timeout: Option<Duration>,
+    pub maybe_action: Option<ToastAction<AS>>,
+}
+
+impl<AS> Toast<AS> {
+    pub fn new(level: ToastLevel, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level,
+            maybe_timeout: Some(DEFAULT_TOAST_TIMEOUT),
+            maybe_action: None,
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Info, message)
+    }
+
+    pub fn warn(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Warn, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(ToastLevel::Error, message)
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.maybe_timeout = Some(timeout);
+        self
+    }
+
+    /// The toast isn't dismissed automatically.
+    pub fn sticky(mut self) -> Self {
+        self.maybe_timeout = None;
+        self
+    }
+
+    pub fn with_action(
+        mut self,
+        key_press: KeyPress,
+        label: impl Into<String>,
+        action: AS,
+    ) -> Self {
+        self.maybe_action = Some(ToastAction {
+            key_press,
+            label: label.into(),
+            action,
+        });
+        self
+    }
+
+    /// The message, followed by the hint for the action (if there is one).
+    fn get_lines(&self) -> Vec<String> {
+        let mut it = vec![self.message.clone()];
+        if let Some(ref toast_action) = self.maybe_action {
+            it.push(format!(
+                "{}: {}",
+                toast_action.key_press.get_display_text(),
+                toast_action.label
+            ));
+        }
+        it
+    }
+}
+
+/// Identifies a toast that was posted, so that it can be dismissed, see
+/// [ToastManager::dismiss].
+pub type ToastId = usize;
+
+/// The corner of the window where the toasts are stacked. The newest toast is the one
+/// that is closest to the corner.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ToastCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct PostedToast<AS> {
+    id: ToastId,
+    toast: Toast<AS>,
+    maybe_expires_at: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct ToastQueue<AS> {
+    /// From the oldest to the newest.
+    toasts: Vec<PostedToast<AS>>,
+    next_id: ToastId,
+    corner: ToastCorner,
+}
+
+impl<AS> Default for ToastQueue<AS> {
+    fn default() -> Self {
+        Self {
+            toasts: vec![],
+            next_id: 0,
+            corner: ToastCorner::default(),
+        }
+    }
+}
+
+/// Where a toast is painted, see [ToastManager::calc_layout].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ToastLayout {
+    pub id: ToastId,
+    pub origin_pos: Position,
+    pub bounds_size: Size,
+}
+
+/// The toasts (notifications) that are shown above the app. This is cheap to clone (the
+/// clones share the same toasts), so any task can post a toast, eg: a background task
+/// that saves a file, w/ a clone of [crate::GlobalData::toasts] and of
+/// [crate::GlobalData::main_thread_channel_sender].
+///
+/// - The toasts are painted by the main event loop, after the app is rendered, at
+///   [ZOrder::Glass], stacked in a [ToastCorner] of the window. As many of the newest
+///   toasts as fit in the window are painted.
+/// - They don't take the focus away from the component that has it. The only input
+///   events that they handle are the key presses of their actions (see [ToastAction]),
+///   which are handled before the app gets them.
+/// - They are dismissed after their timeout (the main event loop is asked to render
+///   again when that happens), or w/ [ToastManager::dismiss].
+#[derive(Debug)]
+pub struct ToastManager<AS> {
+    inner: Arc<Mutex<ToastQueue<AS>>>,
+}
+
+impl<AS> Clone for ToastManager<AS> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<AS> Default for ToastManager<AS> {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ToastQueue::default())),
+        }
+    }
+}
+
+impl<AS> ToastManager<AS>
+where
+    AS: Debug + Default + Clone + Sync + Send,
+{
+    /// Post the `toast`, and ask for it to be painted. Once its timeout elapses, it is
+    /// dismissed, and the main event loop is asked to render again. This must be called
+    /// from a tokio runtime.
+    pub fn post(
+        &self,
+        toast: Toast<AS>,
+        main_thread_channel_sender: &Sender<TerminalWindowMainThreadSignal<AS>>,
+    ) -> ToastId
+    where
+        AS: 'static,
+    {
+        let maybe_timeout = toast.maybe_timeout;
+        let id = self.push(toast, Instant::now());
+
+        let toast_manager = self.clone();
+        let main_thread_channel_sender = main_thread_channel_sender.clone();
+        tokio::spawn(async move {
+            let _ = main_thread_channel_sender
+                .send(TerminalWindowMainThreadSignal::RequestRender(None))
+                .await;
+
+            let Some(timeout) = maybe_timeout else {
+                return;
+            };
+            tokio::time::sleep(timeout).await;
+            if toast_manager.remove_expired(Instant::now()) {
+                let _ = main_thread_channel_sender
+                    .send(TerminalWindowMainThreadSignal::RequestRender(None))
+                    .await;
+            }
+        });
+
+        id
+    }
+
+    /// Add the `toast` w/out asking for it to be painted, or for it to be dismissed once
+    /// its timeout (from `now`) elapses. Use [ToastManager::post] instead, unless the
+    /// app takes care of both.
+    pub fn push(&self, toast: Toast<AS>, now: Instant) -> ToastId {
+        let Ok(mut queue) = self.inner.lock() else {
+            return ToastId::default();
+        };
+        let id = queue.next_id;
+        queue.next_id += 1;
+        let maybe_expires_at = toast.maybe_timeout.map(|timeout| now + timeout);
+        queue.toasts.push(PostedToast {
+            id,
+            toast,
+            maybe_expires_at,
+        });
+        id
+    }
+
+    /// Returns true if the toast w/ the `id` was shown (and it is dismissed now).
+    pub fn dismiss(&self, id: ToastId) -> bool {
+        let Ok(mut queue) = self.inner.lock() else {
+            return false;
+        };
+        let old_len = queue.toasts.len();
+        queue.toasts.retain(|it| it.id != id);
+        queue.toasts.len() != old_len
+    }
+
+    pub fn dismiss_all(&self) {
+        if let Ok(mut queue) = self.inner.lock() {
+            queue.toasts.clear();
+        }
+    }
+
+    /// Dismiss the toasts whose timeout has elapsed by `now`. Returns true if any were.
+    pub fn remove_expired(&self, now: Instant) -> bool {
+        let Ok(mut queue) = self.inner.lock() else {
+            return false;
+        };
+        let old_len = queue.toasts.len();
+        queue.toasts.retain(|it| {
+            !it.maybe_expires_at
+                .is_some_and(|expires_at| expires_at <= now)
+        });
+        queue.toasts.len() != old_len
+    }
+
+    /// Returns the toasts that are shown, from the oldest to the newest.
+    pub fn get_toasts(&self) -> Vec<(ToastId, Toast<AS>)> {
+        let Ok(queue) = self.inner.lock() else {
+            return vec![];
+        };
+        queue
+            .toasts
+            .iter()
+            .map(|it| (it.id, it.toast.clone()))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().map(|it| it.toasts.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    pub fn set_corner(&self, corner: ToastCorner) {
+        if let Ok(mut queue) = self.inner.lock() {
+            queue.corner = corner;
+        }
+    }
+
+    /// If the `input_event` is the key press of the action of a toast (the newest one, if
+    /// more than one has it), that toast is dismissed, and its action is returned (to be
+    /// dispatched). Nothing happens to the toasts for all the other input events.
+    pub fn handle_input_event(&self, input_event: &InputEvent) -> Option<AS> {
+        let InputEvent::Keyboard(key_press) = input_event else {
+            return None;
+        };
+        let mut queue = self.inner.lock().ok()?;
+        let index = queue.toasts.iter().rposition(|it| {
+            it.toast
+                .maybe_action
+                .as_ref()
+                .is_some_and(|toast_action| toast_action.key_press == *key_press)
+        })?;
+        let posted_toast = queue.toasts.remove(index);
+        posted_toast.toast.maybe_action.map(|it| it.action)
+    }
+
+    /// Returns where each of the toasts that fit in the window is painted, from the
+    /// newest (closest to the corner) to the oldest.
+    pub fn calc_layout(&self, window_size: Size) -> Vec<ToastLayout> {
+        let Ok(queue) = self.inner.lock() else {
+            return vec![];
+        };
+        calc_layout(&queue, window_size)
+    }
+
+    /// Returns the [RenderPipeline] to paint the toasts (at [ZOrder::Glass]), after the
+    /// ones whose timeout has elapsed are dismissed. This is called by the main event
+    /// loop, after the app is rendered.
+    pub fn render(&self, window_size: Size) -> RenderPipeline {
+        self.remove_expired(Instant::now());
+
+        let mut render_pipeline = render_pipeline!();
+        let Ok(queue) = self.inner.lock() else {
+            return render_pipeline;
+        };
+        if queue.toasts.is_empty() {
+            return render_pipeline;
+        }
+
+        let mut render_ops = render_ops!();
+        for layout in calc_layout(&queue, window_size) {
+            let Some(posted_toast) = queue.toasts.iter().find(|it| it.id == layout.id)
+            else {
+                continue;
+            };
+            render_toast(&mut render_ops, &posted_toast.toast, &layout);
+        }
+        render_pipeline.push(ZOrder::Glass, render_ops);
+        render_pipeline
+    }
+}
+
+/// Each toast is as wide as its longest line (w/ 1 col of padding on each side), and it
+/// has a line for its message, and one for the hint of its action.
+fn calc_layout<AS>(queue: &ToastQueue<AS>, window_size: Size) -> Vec<ToastLayout> {
+    let window_col_count = ch!(@to_usize window_size.col_count);
+    let window_row_count = ch!(@to_usize window_size.row_count);
+    let max_col_count = TOAST_MAX_COL_COUNT
+        .min(window_col_count.saturating_sub(TOAST_MARGIN_COL_COUNT * 2));
+
+    let mut it = vec![];
+    let mut stacked_row_count = 0;
+    for posted_toast in queue.toasts.iter().rev() {
+        let lines = posted_toast.toast.get_lines();
+        let col_count = lines
+            .iter()
+            .map(|line| UnicodeString::str_display_width(line) + 4)
+            .max()
+            .unwrap_or_default()
+            .max(TOAST_MIN_COL_COUNT)
+            .min(max_col_count);
+        let row_count = lines.len() + 2;
+        if col_count < 5 || stacked_row_count + row_count > window_row_count {
+            break;
+        }
+
+        let col_index = match queue.corner {
+            ToastCorner::TopLeft | ToastCorner::BottomLeft => TOAST_MARGIN_COL_COUNT,
+            ToastCorner::TopRight | ToastCorner::BottomRight => {
+                window_col_count - TOAST_MARGIN_COL_COUNT - col_count
+            }
+        };
+        let row_index = match queue.corner {
+            ToastCorner::TopLeft | ToastCorner::TopRight => stacked_row_count,
+            ToastCorner::BottomLeft | ToastCorner::BottomRight => {
+                window_row_count - stacked_row_count - row_count
+            }
+        };
+        stacked_row_count += row_count;
+
+        it.push(ToastLayout {
+            id: posted_toast.id,
+            origin_pos: position!(col_index: col_index, row_index: row_index),
+            bounds_size: size!(col_count: col_count, row_count: row_count),
+        });
+    }
+    it
+}
+
+/// The lines are padded to the width of the toast, so that the content of the app below
+/// it is cleared.
+fn render_toast<AS>(render_ops: &mut RenderOps, toast: &Toast<AS>, layout: &ToastLayout) {
+    let border_style = toast.level.get_border_style();
+    render_ops.extend(
+        BoxBorder {
+            maybe_title: Some(toast.level.get_title().to_string()),
+            maybe_style: Some(border_style),
+        }
+        .render(layout.origin_pos, layout.bounds_size)
+        .list,
+    );
+
+    let text_width = ch!(@to_usize layout.bounds_size.col_count - 4);
+    for (line_index, line) in toast.get_lines().iter().enumerate() {
+        let line = UnicodeString::from(line.as_str());
+        let text = match line.display_width > ch!(text_width) {
+            true => format!(
+                "{}{TOAST_ELLIPSIS}",
+                line.truncate_end_to_fit_width(ch!(text_width - 1))
+            ),
+            false => line.string.clone(),
+        };
+        let text = UnicodeString::from(text.as_str())
+            .pad_end_with_spaces_to_fit_width(" ", ch!(text_width));
+
+        // The hint for the action is dimmed.
+        let maybe_style = match line_index {
+            0 => None,
+            _ => Some(TuiStyle {
+                dim: true,
+                ..Default::default()
+            }),
+        };
+        let pos = layout.origin_pos + position!(col_index: 1, row_index: line_index + 1);
+        render_ops.push(RenderOp::MoveCursorPositionAbs(pos));
+        render_ops.push(RenderOp::ApplyColors(maybe_style));
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            format!(" {text} "),
+            maybe_style,
+        ));
+        render_ops.push(RenderOp::ResetColor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::assert_eq2;
+
+    use super::*;
+    use crate::{Key, ModifierKeysMask};
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    enum TestAction {
+        #[default]
+        Noop,
+        Retry,
+    }
+
+    fn ctrl(character: char) -> KeyPress {
+        KeyPress::WithModifiers {
+            key: Key::Character(character),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        }
+    }
+
+    /// An info toast, followed by an error toast w/ an action.
+    fn make_toast_manager(now: Instant) -> ToastManager<TestAction> {
+        let toast_manager = ToastManager::default();
+        toast_manager.push(Toast::info("Saved"), now);
+        toast_manager.push(
+            Toast::error("Build failed")
+                .with_timeout(Duration::from_secs(10))
+                .with_action(ctrl('r'), "Retry", TestAction::Retry),
+            now,
+        );
+        toast_manager
+    }
+
+    #[test]
+    fn test_action_key_dismisses_toast() {
+        let toast_manager = make_toast_manager(Instant::now());
+
+        // Other key presses are left for the app.
+        assert_eq2!(
+            toast_manager.handle_input_event(&InputEvent::Keyboard(ctrl('s'))),
+            None
+        );
+        assert_eq2!(toast_manager.len(), 2);
+
+        assert_eq2!(
+            toast_manager.handle_input_event(&InputEvent::Keyboard(ctrl('r'))),
+            Some(TestAction::Retry)
+        );
+        let toasts = toast_manager.get_toasts();
+        assert_eq2!(toasts.len(), 1);
+        assert_eq2!(toasts[0].1.message, "Saved".to_string());
+    }
+
+    #[test]
+    fn test_toasts_expire() {
+        let now = Instant::now();
+        let toast_manager = make_toast_manager(now);
+        let sticky_id = toast_manager.push(Toast::warn("Offline").sticky(), now);
+
+        assert!(!toast_manager.remove_expired(now + Duration::from_secs(1)));
+        assert!(toast_manager.remove_expired(now + DEFAULT_TOAST_TIMEOUT));
+        assert_eq2!(toast_manager.len(), 2);
+        assert!(toast_manager.remove_expired(now + Duration::from_secs(10)));
+
+        // The sticky toast stays until it is dismissed.
+        assert!(!toast_manager.remove_expired(now + Duration::from_secs(3600)));
+        assert!(toast_manager.dismiss(sticky_id));
+        assert!(toast_manager.is_empty());
+    }
+
+    #[test]
+    fn test_calc_layout() {
+        let toast_manager = make_toast_manager(Instant::now());
+        let window_size = size!(col_count: 60, row_count: 20);
+
+        // The newest toast is in the corner, w/ the older one stacked above it. The
+        // error toast is as wide as its hint `Ctrl+R: Retry` (w/ its padding & border),
+        // and the info toast is as wide as the min width.
+        assert_eq2!(
+            toast_manager.calc_layout(window_size),
+            vec![
+                ToastLayout {
+                    id: 1,
+                    origin_pos: position!(col_index: 42, row_index: 16),
+                    bounds_size: size!(col_count: 17, row_count: 4),
+                },
+                ToastLayout {
+                    id: 0,
+                    origin_pos: position!(col_index: 43, row_index: 13),
+                    bounds_size: size!(col_count: 16, row_count: 3),
+                },
+            ]
+        );
+
+        toast_manager.set_corner(ToastCorner::TopLeft);
+        let layouts = toast_manager.calc_layout(window_size);
+        assert_eq2!(layouts[0].origin_pos, position!(col_index: 1, row_index: 0));
+        assert_eq2!(layouts[1].origin_pos, position!(col_index: 1, row_index: 4));
+
+        // Only the newest toast fits.
+        let layouts = toast_manager.calc_layout(size!(col_count: 60, row_count: 5));
+        assert_eq2!(layouts.len(), 1);
+    }
+
+    #[test]
+    fn test_render_truncates_long_message() {
+        let toast_manager = ToastManager::<TestAction>::default();
+        toast_manager.push(Toast::info("x".repeat(100)), Instant::now());
+
+        let render_pipeline = toast_manager.render(size!(col_count: 80, row_count: 20));
+        let texts: Vec<String> = render_pipeline
+            .get(&ZOrder::Glass)
+            .unwrap()
+            .iter()
+            .flat_map(|render_ops| render_ops.iter())
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, _) => Some(text.to_string()),
+                _ => None,
+            })
+            .filter(|it| it.starts_with(' '))
+            .collect();
+        assert_eq2!(texts, vec![format!(" {}{TOAST_ELLIPSIS} ", "x".repeat(43))]);
+    }
+}
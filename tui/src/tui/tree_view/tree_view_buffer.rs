@@ -0,0 +1,639 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_core::{ch, Position, Size};
+use serde::{Deserialize, Serialize};
+
+use crate::{Button, InputEvent, Key, KeyPress, MouseInput, MouseInputKind, SpecialKey};
+
+/// This is painted before the label of an expanded node.
+pub const TREE_VIEW_EXPANDED_INDICATOR: &str = "▾ ";
+/// This is painted before the label of a collapsed node (that might have children).
+pub const TREE_VIEW_COLLAPSED_INDICATOR: &str = "▸ ";
+/// Each level of the tree is indented by this many cols.
+pub const TREE_VIEW_INDENT_COL_COUNT: usize = 2;
+
+/// The indices of a node & its ancestors, starting w/ the index of its root in
+/// [TreeViewBuffer::get_roots].
+pub type TreeNodePath = Vec<usize>;
+
+/// Returns the text that is painted for a node.
+pub type TreeNodeLabelFn<T> = fn(&T) -> String;
+
+/// Returns the children of a node, when it is expanded for the first time.
+pub type TreeLoadChildrenFn<T> = fn(&T) -> Vec<TreeNode<T>>;
+
+/// A node of a [TreeViewBuffer]. Its children are loaded lazily (w/ the
+/// [TreeLoadChildrenFn] of the tree view) when it is expanded for the first time, unless
+/// they are passed in w/ [Self::with_children].
+#[derive(Clone, Debug)]
+pub struct TreeNode<T> {
+    pub data: T,
+    pub is_expanded: bool,
+    is_leaf: bool,
+    /// [None] until the children are loaded.
+    maybe_children: Option<Vec<TreeNode<T>>>,
+}
+
+impl<T> TreeNode<T> {
+    /// A node whose children are loaded when it is expanded.
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            is_expanded: false,
+            is_leaf: false,
+            maybe_children: None,
+        }
+    }
+
+    /// A node that can't be expanded, eg: a file in a file explorer.
+    pub fn new_leaf(data: T) -> Self {
+        Self {
+            is_leaf: true,
+            ..Self::new(data)
+        }
+    }
+
+    pub fn with_children(data: T, children: Vec<TreeNode<T>>) -> Self {
+        Self {
+            maybe_children: Some(children),
+            ..Self::new(data)
+        }
+    }
+
+    pub fn is_leaf(&self) -> bool { self.is_leaf }
+
+    /// Returns [None] if the children weren't loaded yet.
+    pub fn get_children(&self) -> Option<&[TreeNode<T>]> {
+        self.maybe_children.as_deref()
+    }
+
+    /// Replace the children, eg: to reload them after they changed. They are loaded
+    /// again the next time the node is expanded, if this is [None].
+    pub fn set_children(&mut self, maybe_children: Option<Vec<TreeNode<T>>>) {
+        self.maybe_children = maybe_children;
+    }
+
+    /// Whether the node might have children, which is the case until they are loaded.
+    pub fn can_expand(&self) -> bool {
+        !self.is_leaf
+            && match &self.maybe_children {
+                Some(children) => !children.is_empty(),
+                None => true,
+            }
+    }
+}
+
+/// A node that is visible in a [TreeViewBuffer] (ie, all its ancestors are expanded),
+/// which is returned by [TreeViewBuffer::get_visible_rows].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeViewRow {
+    pub path: TreeNodePath,
+    pub depth: usize,
+}
+
+/// What happened when an [InputEvent] was applied to a [TreeViewBuffer] w/
+/// [TreeViewBuffer::apply_event].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TreeViewApplyResponse {
+    /// A different node was selected (see [TreeViewBuffer::get_selected_node]).
+    SelectionChanged,
+    /// The selected node was activated, w/ <kbd>Enter</kbd> or by clicking on it again.
+    Activated,
+    /// The selected node was expanded (and its children were loaded, if this is the
+    /// first time).
+    Expanded,
+    Collapsed,
+    /// The nodes were scrolled w/ the mouse wheel, but the selection didn't change.
+    Scrolled,
+    /// The key was handled, but nothing changed, eg: <kbd>Down</kbd> at the last node.
+    Consumed,
+    /// The event wasn't for the tree view, so it should be passed on.
+    Noop,
+}
+
+/// The nodes of a tree that are displayed by a [crate::TreeViewComponent]. This is kept
+/// in the app's state (see [crate::HasTreeViewBuffers]), so that the app can change the
+/// nodes, and read the selection in its handlers.
+///
+/// Key bindings:
+/// - <kbd>Up</kbd>, <kbd>Down</kbd>, <kbd>PageUp</kbd>, <kbd>PageDown</kbd>,
+///   <kbd>Home</kbd> & <kbd>End</kbd> move the selection.
+/// - <kbd>Right</kbd> expands the selected node, or selects its first child if it is
+///   already expanded.
+/// - <kbd>Left</kbd> collapses the selected node, or selects its parent if it is already
+///   collapsed.
+/// - <kbd>Space</kbd> expands or collapses the selected node.
+/// - <kbd>Enter</kbd> activates the selected node.
+///
+/// The mouse wheel scrolls the nodes, clicking on a node selects it (and clicking on it
+/// again activates it), and clicking on the indicator before its label expands or
+/// collapses it.
+#[derive(Clone, Debug)]
+pub struct TreeViewBuffer<T> {
+    pub get_label: TreeNodeLabelFn<T>,
+    pub maybe_load_children: Option<TreeLoadChildrenFn<T>>,
+    roots: Vec<TreeNode<T>>,
+    maybe_selected_path: Option<TreeNodePath>,
+    /// The index (in [Self::get_visible_rows]) of the first row in the viewport.
+    scroll_offset: usize,
+    /// The bounds from the last call to [Self::set_viewport], which are needed to scroll
+    /// & to handle mouse clicks.
+    maybe_last_bounds: Option<(Position, Size)>,
+}
+
+impl<T> TreeViewBuffer<T> {
+    pub fn new(get_label: TreeNodeLabelFn<T>) -> Self {
+        Self {
+            get_label,
+            maybe_load_children: None,
+            roots: vec![],
+            maybe_selected_path: None,
+            scroll_offset: 0,
+            maybe_last_bounds: None,
+        }
+    }
+
+    pub fn with_load_children(mut self, load_children: TreeLoadChildrenFn<T>) -> Self {
+        self.maybe_load_children = Some(load_children);
+        self
+    }
+
+    pub fn with_roots(mut self, roots: Vec<TreeNode<T>>) -> Self {
+        self.set_roots(roots);
+        self
+    }
+
+    /// Replace all the nodes. The first root is selected (if there are any).
+    pub fn set_roots(&mut self, roots: Vec<TreeNode<T>>) {
+        self.roots = roots;
+        self.scroll_offset = 0;
+        self.maybe_selected_path = (!self.roots.is_empty()).then(|| vec![0]);
+    }
+
+    pub fn get_roots(&self) -> &[TreeNode<T>] { &self.roots }
+
+    pub fn get_node(&self, path: &[usize]) -> Option<&TreeNode<T>> {
+        let (root_index, child_indices) = path.split_first()?;
+        let mut node = self.roots.get(*root_index)?;
+        for index in child_indices {
+            node = node.maybe_children.as_ref()?.get(*index)?;
+        }
+        Some(node)
+    }
+
+    pub fn get_mut_node(&mut self, path: &[usize]) -> Option<&mut TreeNode<T>> {
+        let (root_index, child_indices) = path.split_first()?;
+        let mut node = self.roots.get_mut(*root_index)?;
+        for index in child_indices {
+            node = node.maybe_children.as_mut()?.get_mut(*index)?;
+        }
+        Some(node)
+    }
+
+    pub fn get_selected_path(&self) -> Option<&TreeNodePath> {
+        self.maybe_selected_path.as_ref()
+    }
+
+    pub fn get_selected_node(&self) -> Option<&TreeNode<T>> {
+        self.get_node(self.maybe_selected_path.as_ref()?)
+    }
+
+    pub fn get_scroll_offset(&self) -> usize { self.scroll_offset }
+
+    /// Returns the nodes whose ancestors are all expanded, in the order that they are
+    /// displayed in.
+    pub fn get_visible_rows(&self) -> Vec<TreeViewRow> {
+        fn add_rows<T>(
+            nodes: &[TreeNode<T>],
+            parent_path: &[usize],
+            rows: &mut Vec<TreeViewRow>,
+        ) {
+            for (index, node) in nodes.iter().enumerate() {
+                let mut path = parent_path.to_vec();
+                path.push(index);
+                rows.push(TreeViewRow {
+                    path: path.clone(),
+                    depth: parent_path.len(),
+                });
+                if let (true, Some(children)) = (node.is_expanded, &node.maybe_children) {
+                    add_rows(children, &path, rows);
+                }
+            }
+        }
+
+        let mut rows = vec![];
+        add_rows(&self.roots, &[], &mut rows);
+        rows
+    }
+
+    /// Select the node at the given path (if it is visible), and scroll it into view.
+    /// Returns `true` if the selection changed.
+    pub fn select(&mut self, path: &[usize]) -> bool {
+        if !self.get_visible_rows().iter().any(|it| it.path == path) {
+            return false;
+        }
+        let has_changed = self.maybe_selected_path.as_deref() != Some(path);
+        self.maybe_selected_path = Some(path.to_vec());
+        self.scroll_selection_into_view();
+        has_changed
+    }
+
+    /// Expand the node at the given path, and load its children if this is the first
+    /// time. Returns `false` if it is a leaf, or it is already expanded.
+    pub fn expand(&mut self, path: &[usize]) -> bool {
+        let maybe_load_children = self.maybe_load_children;
+        let Some(node) = self.get_mut_node(path) else {
+            return false;
+        };
+        if node.is_leaf || node.is_expanded {
+            return false;
+        }
+        if node.maybe_children.is_none() {
+            node.maybe_children = Some(match maybe_load_children {
+                Some(load_children) => load_children(&node.data),
+                None => vec![],
+            });
+        }
+        node.is_expanded = true;
+        true
+    }
+
+    /// Collapse the node at the given path. If the selected node is one of its
+    /// descendants, then the node is selected instead. Returns `false` if it is already
+    /// collapsed.
+    pub fn collapse(&mut self, path: &[usize]) -> bool {
+        match self.get_mut_node(path) {
+            Some(node) if node.is_expanded => node.is_expanded = false,
+            _ => return false,
+        }
+        if let Some(selected_path) = &self.maybe_selected_path {
+            if selected_path.len() > path.len() && selected_path.starts_with(path) {
+                self.maybe_selected_path = Some(path.to_vec());
+            }
+        }
+        self.scroll_selection_into_view();
+        true
+    }
+
+    /// Remember the bounds that the tree view is painted in, so that the selection can
+    /// be scrolled into view, and mouse clicks can be mapped to the nodes.
+    pub fn set_viewport(&mut self, origin_pos: Position, bounds_size: Size) {
+        self.maybe_last_bounds = Some((origin_pos, bounds_size));
+        self.scroll_selection_into_view();
+    }
+
+    /// The number of rows that fit in the box from the last call to
+    /// [Self::set_viewport], or [None] if it wasn't called yet.
+    pub fn get_viewport_row_count(&self) -> Option<usize> {
+        let (_, bounds_size) = self.maybe_last_bounds?;
+        Some(ch!(@to_usize bounds_size.row_count))
+    }
+
+    fn get_selected_row_index(&self, rows: &[TreeViewRow]) -> Option<usize> {
+        let selected_path = self.maybe_selected_path.as_ref()?;
+        rows.iter().position(|it| it.path == *selected_path)
+    }
+
+    fn get_max_scroll_offset(&self, row_count: usize) -> usize {
+        let viewport_row_count = self.get_viewport_row_count().unwrap_or_default();
+        row_count.saturating_sub(viewport_row_count)
+    }
+
+    fn scroll_selection_into_view(&mut self) {
+        let Some(viewport_row_count) = self.get_viewport_row_count() else {
+            return;
+        };
+        let rows = self.get_visible_rows();
+        if let Some(selected_index) = self.get_selected_row_index(&rows) {
+            if selected_index < self.scroll_offset {
+                self.scroll_offset = selected_index;
+            } else if viewport_row_count > 0
+                && selected_index >= self.scroll_offset + viewport_row_count
+            {
+                self.scroll_offset = selected_index + 1 - viewport_row_count;
+            }
+        }
+        self.scroll_offset = self
+            .scroll_offset
+            .min(self.get_max_scroll_offset(rows.len()));
+    }
+
+    fn move_selection_by(&mut self, delta: isize) -> bool {
+        let rows = self.get_visible_rows();
+        if rows.is_empty() {
+            return false;
+        }
+        let row_index = match self.get_selected_row_index(&rows) {
+            Some(selected_index) => selected_index.saturating_add_signed(delta),
+            None => 0,
+        };
+        self.select(&rows[row_index.min(rows.len() - 1)].path)
+    }
+
+    pub fn apply_event(&mut self, input_event: InputEvent) -> TreeViewApplyResponse {
+        let page_size = self.get_viewport_row_count().unwrap_or(1).max(1) as isize;
+        let maybe_selected_path = self.maybe_selected_path.clone();
+
+        let has_selection_changed = match input_event {
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(special_key),
+            }) => match (special_key, maybe_selected_path) {
+                (SpecialKey::Up, _) => self.move_selection_by(-1),
+                (SpecialKey::Down, _) => self.move_selection_by(1),
+                (SpecialKey::PageUp, _) => self.move_selection_by(-page_size),
+                (SpecialKey::PageDown, _) => self.move_selection_by(page_size),
+                (SpecialKey::Home, _) => self.move_selection_by(isize::MIN),
+                (SpecialKey::End, _) => self.move_selection_by(isize::MAX),
+                (SpecialKey::Right, Some(path)) => {
+                    if self.expand(&path) {
+                        return TreeViewApplyResponse::Expanded;
+                    }
+                    let mut child_path = path;
+                    child_path.push(0);
+                    self.select(&child_path)
+                }
+                (SpecialKey::Left, Some(path)) => {
+                    if self.collapse(&path) {
+                        return TreeViewApplyResponse::Collapsed;
+                    }
+                    match path.split_last() {
+                        Some((_, parent_path)) if !parent_path.is_empty() => {
+                            self.select(parent_path)
+                        }
+                        _ => false,
+                    }
+                }
+                (SpecialKey::Enter, Some(_)) => return TreeViewApplyResponse::Activated,
+                _ => return TreeViewApplyResponse::Noop,
+            },
+
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character(' '),
+            }) => {
+                return match maybe_selected_path {
+                    Some(path) => self.toggle(&path),
+                    None => TreeViewApplyResponse::Noop,
+                };
+            }
+
+            InputEvent::Mouse(MouseInput { kind, pos, .. }) => {
+                return self.apply_mouse_event(kind, pos);
+            }
+
+            _ => return TreeViewApplyResponse::Noop,
+        };
+
+        match has_selection_changed {
+            true => TreeViewApplyResponse::SelectionChanged,
+            // The key is consumed even if the selection is already at the first (or
+            // last) node, so that it isn't handled by another component.
+            false if self.roots.is_empty() => TreeViewApplyResponse::Noop,
+            false => TreeViewApplyResponse::Consumed,
+        }
+    }
+
+    fn toggle(&mut self, path: &[usize]) -> TreeViewApplyResponse {
+        if self.collapse(path) {
+            TreeViewApplyResponse::Collapsed
+        } else if self.expand(path) {
+            TreeViewApplyResponse::Expanded
+        } else {
+            TreeViewApplyResponse::Consumed
+        }
+    }
+
+    fn apply_mouse_event(
+        &mut self,
+        kind: MouseInputKind,
+        pos: Position,
+    ) -> TreeViewApplyResponse {
+        let Some((origin_pos, bounds_size)) = self.maybe_last_bounds else {
+            return TreeViewApplyResponse::Noop;
+        };
+        let rows = self.get_visible_rows();
+
+        match kind {
+            MouseInputKind::ScrollUp | MouseInputKind::ScrollDown => {
+                let scroll_offset = match kind {
+                    MouseInputKind::ScrollUp => self.scroll_offset.saturating_sub(1),
+                    _ => (self.scroll_offset + 1)
+                        .min(self.get_max_scroll_offset(rows.len())),
+                };
+                if scroll_offset == self.scroll_offset {
+                    return TreeViewApplyResponse::Noop;
+                }
+                self.scroll_offset = scroll_offset;
+                TreeViewApplyResponse::Scrolled
+            }
+
+            MouseInputKind::MouseDown(Button::Left) => {
+                if pos.col_index < origin_pos.col_index
+                    || pos.row_index < origin_pos.row_index
+                    || pos.col_index >= origin_pos.col_index + bounds_size.col_count
+                    || pos.row_index >= origin_pos.row_index + bounds_size.row_count
+                {
+                    return TreeViewApplyResponse::Noop;
+                }
+                let col_index = ch!(@to_usize pos.col_index - origin_pos.col_index);
+                let row_index = ch!(@to_usize pos.row_index - origin_pos.row_index);
+                let Some(row) = rows.get(self.scroll_offset + row_index) else {
+                    return TreeViewApplyResponse::Noop;
+                };
+
+                // The indicator is after the indent.
+                let indicator_col_index = row.depth * TREE_VIEW_INDENT_COL_COUNT;
+                let is_on_indicator = col_index >= indicator_col_index
+                    && col_index < indicator_col_index + TREE_VIEW_INDENT_COL_COUNT;
+                if is_on_indicator {
+                    let response = self.toggle(&row.path);
+                    if response != TreeViewApplyResponse::Consumed {
+                        return response;
+                    }
+                }
+
+                match self.select(&row.path) {
+                    true => TreeViewApplyResponse::SelectionChanged,
+                    false => TreeViewApplyResponse::Activated,
+                }
+            }
+
+            _ => TreeViewApplyResponse::Noop,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, position, size};
+
+    use super::*;
+
+    /// Each node (up to 2 levels deep) has 2 children, which are named after their
+    /// parent, eg: `a` has `a.0` & `a.1`.
+    fn make_tree_view_buffer() -> TreeViewBuffer<String> {
+        TreeViewBuffer::new(|it: &String| it.clone())
+            .with_load_children(|name: &String| {
+                (0..2)
+                    .map(|index| format!("{name}.{index}"))
+                    .map(|it| match it.matches('.').count() {
+                        2 => TreeNode::new_leaf(it),
+                        _ => TreeNode::new(it),
+                    })
+                    .collect()
+            })
+            .with_roots(vec![TreeNode::new("a".into()), TreeNode::new("b".into())])
+    }
+
+    fn key(special_key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        })
+    }
+
+    fn get_visible_labels(buffer: &TreeViewBuffer<String>) -> Vec<String> {
+        buffer
+            .get_visible_rows()
+            .iter()
+            .filter_map(|it| buffer.get_node(&it.path))
+            .map(|it| it.data.clone())
+            .collect()
+    }
+
+    fn get_selected_label(buffer: &TreeViewBuffer<String>) -> Option<String> {
+        buffer.get_selected_node().map(|it| it.data.clone())
+    }
+
+    #[test]
+    fn test_expand_loads_children_lazily() {
+        let mut buffer = make_tree_view_buffer();
+        assert!(buffer.get_roots()[0].get_children().is_none());
+        assert_eq2!(get_visible_labels(&buffer), vec!["a", "b"]);
+
+        assert!(buffer.expand(&[0]));
+        assert!(buffer.expand(&[0, 1]));
+        assert_eq2!(
+            get_visible_labels(&buffer),
+            vec!["a", "a.0", "a.1", "a.1.0", "a.1.1", "b"]
+        );
+
+        // Leaves can't be expanded.
+        assert!(!buffer.expand(&[0, 1, 0]));
+
+        // The children are kept when the node is collapsed.
+        assert!(buffer.collapse(&[0]));
+        assert_eq2!(get_visible_labels(&buffer), vec!["a", "b"]);
+        assert!(buffer.expand(&[0]));
+        assert_eq2!(get_visible_labels(&buffer).len(), 6);
+    }
+
+    #[test]
+    fn test_keyboard_navigation() {
+        let mut buffer = make_tree_view_buffer();
+        assert_eq2!(get_selected_label(&buffer), Some("a".to_string()));
+
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Right)),
+            TreeViewApplyResponse::Expanded
+        );
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Right)),
+            TreeViewApplyResponse::SelectionChanged
+        );
+        assert_eq2!(get_selected_label(&buffer), Some("a.0".to_string()));
+
+        buffer.apply_event(key(SpecialKey::Down));
+        buffer.apply_event(key(SpecialKey::Right));
+        buffer.apply_event(key(SpecialKey::Right));
+        assert_eq2!(get_selected_label(&buffer), Some("a.1.0".to_string()));
+
+        // A leaf can't be expanded.
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Right)),
+            TreeViewApplyResponse::Consumed
+        );
+
+        // Select the parent, and then collapse it.
+        buffer.apply_event(key(SpecialKey::Left));
+        assert_eq2!(get_selected_label(&buffer), Some("a.1".to_string()));
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Left)),
+            TreeViewApplyResponse::Collapsed
+        );
+
+        // Collapsing an ancestor selects it.
+        buffer.apply_event(key(SpecialKey::Right));
+        buffer.apply_event(key(SpecialKey::Right));
+        assert!(buffer.collapse(&[0]));
+        assert_eq2!(get_selected_label(&buffer), Some("a".to_string()));
+
+        buffer.apply_event(key(SpecialKey::End));
+        assert_eq2!(get_selected_label(&buffer), Some("b".to_string()));
+        assert_eq2!(
+            buffer.apply_event(key(SpecialKey::Enter)),
+            TreeViewApplyResponse::Activated
+        );
+    }
+
+    #[test]
+    fn test_mouse_and_scrolling() {
+        let mut buffer = make_tree_view_buffer();
+        buffer.expand(&[0]);
+        buffer.set_viewport(
+            position!(col_index: 5, row_index: 5),
+            size!(col_count: 20, row_count: 2),
+        );
+
+        // The selection is scrolled into view.
+        buffer.apply_event(key(SpecialKey::End));
+        assert_eq2!(get_selected_label(&buffer), Some("b".to_string()));
+        assert_eq2!(buffer.get_scroll_offset(), 2);
+
+        let click = |col_index: u16, row_index: u16| {
+            InputEvent::Mouse(MouseInput {
+                pos: position!(col_index: col_index, row_index: row_index),
+                kind: MouseInputKind::MouseDown(Button::Left),
+                maybe_modifier_keys: None,
+            })
+        };
+
+        // Click on the label of `a.1`, and then on its indicator.
+        assert_eq2!(
+            buffer.apply_event(click(12, 5)),
+            TreeViewApplyResponse::SelectionChanged
+        );
+        assert_eq2!(get_selected_label(&buffer), Some("a.1".to_string()));
+        assert_eq2!(
+            buffer.apply_event(click(7, 5)),
+            TreeViewApplyResponse::Expanded
+        );
+        assert_eq2!(
+            buffer.apply_event(click(12, 5)),
+            TreeViewApplyResponse::Activated
+        );
+
+        assert_eq2!(
+            buffer.apply_event(InputEvent::Mouse(MouseInput {
+                pos: position!(col_index: 5, row_index: 5),
+                kind: MouseInputKind::ScrollUp,
+                maybe_modifier_keys: None,
+            })),
+            TreeViewApplyResponse::Scrolled
+        );
+        assert_eq2!(buffer.get_scroll_offset(), 1);
+    }
+}
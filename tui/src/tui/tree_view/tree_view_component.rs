@@ -0,0 +1,300 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use r3bl_core::{ch,
+                position,
+                throws_with_return,
+                CommonResult,
+                TuiStyle,
+                UnicodeString};
+
+use crate::{render_ops,
+            render_pipeline,
+            BoxedSafeComponent,
+            Component,
+            EventPropagation,
+            FlexBox,
+            FlexBoxId,
+            GlobalData,
+            HasFocus,
+            HasTreeViewBuffers,
+            InputEvent,
+            OnTreeViewEventFn,
+            RenderOp,
+            RenderOps,
+            RenderPipeline,
+            SurfaceBounds,
+            TreeViewApplyResponse,
+            TreeViewBuffer,
+            ZOrder,
+            TREE_VIEW_COLLAPSED_INDICATOR,
+            TREE_VIEW_EXPANDED_INDICATOR,
+            TREE_VIEW_INDENT_COL_COUNT};
+
+/// This is a re-usable component that displays the nodes of a [TreeViewBuffer] (which is
+/// kept in the app's state, see [HasTreeViewBuffers]), eg: the files in a file explorer,
+/// or the headings in an outline panel.
+///
+/// Register it in the [crate::ComponentRegistryMap] like the editor component, and route
+/// the input events (including mouse events, w/
+/// [crate::ComponentRegistry::route_mouse_event_to_component_at_pos]) to it. See
+/// [TreeViewBuffer] for the key bindings. The `on_tree_view_event_handler` is called
+/// when the selection changes, or a node is activated, expanded, or collapsed.
+#[derive(Debug)]
+pub struct TreeViewComponent<S, AS, T>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+    T: Debug + Clone + Sync + Send,
+{
+    pub data: TreeViewComponentData<S, AS, T>,
+}
+
+#[derive(Debug)]
+pub struct TreeViewComponentData<S, AS, T>
+where
+    S: Debug + Default + Clone + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+    T: Debug + Clone + Sync + Send,
+{
+    pub id: FlexBoxId,
+    pub on_tree_view_event_handler: Option<OnTreeViewEventFn<S, AS>>,
+    _phantom: PhantomData<T>,
+}
+
+impl<S, AS, T> Component<S, AS> for TreeViewComponent<S, AS, T>
+where
+    S: HasTreeViewBuffers<T> + Default + Clone + Debug + Sync + Send,
+    AS: Debug + Default + Clone + Sync + Send,
+    T: Debug + Clone + Sync + Send,
+{
+    fn reset(&mut self) {}
+
+    fn get_id(&self) -> FlexBoxId { self.data.id }
+
+    fn is_focusable(&self) -> bool { true }
+
+    fn render(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        current_box: FlexBox,
+        _surface_bounds: SurfaceBounds, /* Ignore this. */
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<RenderPipeline> {
+        throws_with_return!({
+            let mut render_pipeline = render_pipeline!();
+            let has_focus = has_focus.does_current_box_have_focus(current_box);
+            if let Some(buffer) = global_data.state.get_mut_tree_view_buffer(self.data.id)
+            {
+                render_pipeline.push(
+                    ZOrder::Normal,
+                    render_tree_view(buffer, &current_box, has_focus),
+                );
+            }
+            render_pipeline
+        });
+    }
+
+    fn handle_event(
+        &mut self,
+        global_data: &mut GlobalData<S, AS>,
+        input_event: InputEvent,
+        _has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        throws_with_return!({
+            let TreeViewComponentData {
+                id,
+                on_tree_view_event_handler,
+                ..
+            } = &self.data;
+
+            let Some(buffer) = global_data.state.get_mut_tree_view_buffer(*id) else {
+                return Ok(EventPropagation::Propagate);
+            };
+
+            let response = buffer.apply_event(input_event);
+            match response {
+                TreeViewApplyResponse::Noop => EventPropagation::Propagate,
+                TreeViewApplyResponse::Consumed => EventPropagation::Consumed,
+                TreeViewApplyResponse::Scrolled => EventPropagation::ConsumedRender,
+                TreeViewApplyResponse::SelectionChanged
+                | TreeViewApplyResponse::Activated
+                | TreeViewApplyResponse::Expanded
+                | TreeViewApplyResponse::Collapsed => {
+                    if let Some(on_tree_view_event_handler) = on_tree_view_event_handler {
+                        on_tree_view_event_handler(
+                            *id,
+                            response,
+                            &mut global_data.state,
+                            &mut global_data.main_thread_channel_sender,
+                        );
+                    }
+                    EventPropagation::ConsumedRender
+                }
+            }
+        });
+    }
+}
+
+/// The selected node is dimmed when the tree view doesn't have focus.
+fn get_tree_view_selected_node_style(has_focus: bool) -> TuiStyle {
+    TuiStyle {
+        reverse: true,
+        dim: !has_focus,
+        ..Default::default()
+    }
+}
+
+/// Paint the visible nodes in the viewport of the `buffer`, each one indented by its
+/// depth, and w/ an indicator before its label if it can be expanded (or collapsed).
+fn render_tree_view<T>(
+    buffer: &mut TreeViewBuffer<T>,
+    current_box: &FlexBox,
+    has_focus: bool,
+) -> RenderOps {
+    let origin_pos = current_box.style_adjusted_origin_pos;
+    let bounds_size = current_box.style_adjusted_bounds_size;
+    buffer.set_viewport(origin_pos, bounds_size);
+
+    let mut render_ops = render_ops!();
+    let rows = buffer.get_visible_rows();
+    let visible_rows = rows
+        .iter()
+        .skip(buffer.get_scroll_offset())
+        .take(ch!(@to_usize bounds_size.row_count));
+
+    for (row_index, row) in visible_rows.enumerate() {
+        let Some(node) = buffer.get_node(&row.path) else {
+            continue;
+        };
+        let indicator = match (node.can_expand(), node.is_expanded) {
+            (false, _) => " ".repeat(TREE_VIEW_INDENT_COL_COUNT),
+            (true, true) => TREE_VIEW_EXPANDED_INDICATOR.to_string(),
+            (true, false) => TREE_VIEW_COLLAPSED_INDICATOR.to_string(),
+        };
+        let line = format!(
+            "{}{indicator}{}",
+            " ".repeat(row.depth * TREE_VIEW_INDENT_COL_COUNT),
+            (buffer.get_label)(&node.data)
+        );
+        let line = UnicodeString::from(line.as_str());
+        let clipped_line =
+            UnicodeString::from(line.clip_to_width(ch!(0), bounds_size.col_count));
+        let maybe_style = (buffer.get_selected_path() == Some(&row.path))
+            .then(|| get_tree_view_selected_node_style(has_focus));
+
+        render_ops.push(RenderOp::MoveCursorPositionRelTo(
+            origin_pos,
+            position!(col_index: 0, row_index: row_index),
+        ));
+        render_ops.push(RenderOp::ApplyColors(maybe_style));
+        // Pad the line, so that the style of the selected node fills the row.
+        render_ops.push(RenderOp::PaintTextWithAttributes(
+            clipped_line.pad_end_with_spaces_to_fit_width(" ", bounds_size.col_count),
+            maybe_style,
+        ));
+        render_ops.push(RenderOp::ResetColor);
+    }
+
+    render_ops
+}
+
+pub mod constructor {
+    use super::*;
+
+    impl<S, AS, T> TreeViewComponent<S, AS, T>
+    where
+        S: Debug + Default + Clone + Sync + Send + HasTreeViewBuffers<T> + 'static,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+        T: Debug + Clone + Sync + Send + 'static,
+    {
+        /// The `on_tree_view_event` handler is called when the selection changes, or a
+        /// node is activated, expanded, or collapsed.
+        pub fn new(id: FlexBoxId, on_tree_view_event: OnTreeViewEventFn<S, AS>) -> Self {
+            Self {
+                data: TreeViewComponentData {
+                    id,
+                    on_tree_view_event_handler: Some(on_tree_view_event),
+                    _phantom: PhantomData,
+                },
+            }
+        }
+
+        pub fn new_boxed(
+            id: FlexBoxId,
+            on_tree_view_event: OnTreeViewEventFn<S, AS>,
+        ) -> BoxedSafeComponent<S, AS> {
+            Box::new(TreeViewComponent::new(id, on_tree_view_event))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_core::{assert_eq2, size};
+
+    use super::*;
+    use crate::TreeNode;
+
+    #[test]
+    fn test_render_tree_view() {
+        let mut buffer =
+            TreeViewBuffer::new(|it: &&str| it.to_string()).with_roots(vec![
+                TreeNode::with_children(
+                    "src",
+                    vec![TreeNode::new_leaf("lib.rs"), TreeNode::new_leaf("main.rs")],
+                ),
+                TreeNode::with_children("empty", vec![]),
+                TreeNode::new("target"),
+            ]);
+        buffer.expand(&[0]);
+
+        let current_box = FlexBox {
+            style_adjusted_origin_pos: position!(col_index: 0, row_index: 0),
+            style_adjusted_bounds_size: size!(col_count: 9, row_count: 4),
+            ..Default::default()
+        };
+        let render_ops = render_tree_view(&mut buffer, &current_box, true);
+
+        let lines: Vec<(String, Option<TuiStyle>)> = render_ops
+            .iter()
+            .filter_map(|render_op| match render_op {
+                RenderOp::PaintTextWithAttributes(text, maybe_style) => {
+                    Some((text.clone(), *maybe_style))
+                }
+                _ => None,
+            })
+            .collect();
+
+        // The children of `src` are indented, the node w/out children doesn't have an
+        // indicator, and the last node doesn't fit.
+        assert_eq2!(
+            lines,
+            vec![
+                (
+                    "▾ src    ".to_string(),
+                    Some(get_tree_view_selected_node_style(true))
+                ),
+                ("    lib.r".to_string(), None),
+                ("    main.".to_string(), None),
+                ("  empty  ".to_string(), None),
+            ]
+        );
+    }
+}
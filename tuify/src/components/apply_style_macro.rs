@@ -31,6 +31,7 @@ pub fn get_crossterm_color_based_on_terminal_capabilities(
                 b: rgb_color.blue,
             }
         }
+        ColorSupport::Ansi16 => Color::AnsiValue(color.as_ansi16().index),
         _ => Color::AnsiValue(color.as_ansi256().index),
     }
 }